@@ -19,6 +19,14 @@ impl CallData {
             decoder: Decoder::new(),
         }
     }
+
+    /// New instance, rejecting decoded `String`s longer than `max_str_len` bytes.
+    pub fn with_max_str_len(bytes: &[u8], max_str_len: usize) -> Self {
+        Self {
+            cursor: Cursor::new(bytes),
+            decoder: Decoder::with_max_str_len(max_str_len),
+        }
+    }
 }
 
 /// `CallData` implements the `Iterator` trait.
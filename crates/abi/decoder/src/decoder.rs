@@ -1,5 +1,5 @@
 use svm_abi_layout::layout;
-use svm_sdk_std::{safe_try, Result, Vec};
+use svm_sdk_std::{safe_try, Result, String, Vec};
 use svm_sdk_types::value::{Primitive, Value};
 use svm_sdk_types::{Address, Amount};
 
@@ -25,11 +25,18 @@ enum TypeKind {
     U32,
     I64,
     U64,
+    Str,
     Array,
 }
 
 pub enum ValueError {
     NotEnoughBytes,
+
+    /// A `String` is longer than the maximum allowed length
+    StrTooLong,
+
+    /// A `String` isn't valid UTF-8
+    InvalidUTF8String,
 }
 
 /// Denotes a decode error
@@ -62,12 +69,25 @@ macro_rules! decode_fixed_primitive {
 }
 
 /// Decodes an encoded function buffer back into a `svm_sdk_types::value::Value`
-pub struct Decoder;
+pub struct Decoder {
+    max_str_len: usize,
+}
 
 impl Decoder {
     /// New instance
     pub fn new() -> Self {
-        Self {}
+        Self::with_max_str_len(layout::STR_MAX_LEN)
+    }
+
+    /// New instance rejecting `String`s longer than `max_str_len` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_str_len` exceeds `layout::STR_MAX_LEN`.
+    pub fn with_max_str_len(max_str_len: usize) -> Self {
+        svm_sdk_std::ensure!(max_str_len <= layout::STR_MAX_LEN);
+
+        Self { max_str_len }
     }
 
     /// Decodes the next `sdk_types::Value` (primitive or composite) and returns it.
@@ -101,6 +121,7 @@ impl Decoder {
             TypeKind::U32 => safe_try!(self.decode_u32(cursor)).into(),
             TypeKind::I64 => safe_try!(self.decode_i64(cursor)).into(),
             TypeKind::U64 => safe_try!(self.decode_u64(cursor)).into(),
+            TypeKind::Str => safe_try!(self.decode_str(cursor)).into(),
             _ => svm_sdk_std::panic(),
         };
 
@@ -260,6 +281,31 @@ impl Decoder {
         Result::Ok(num)
     }
 
+    fn decode_str(&self, cursor: &mut Cursor) -> Result<String, DecodeError> {
+        let byte = safe_try!(self.read_byte(cursor));
+
+        debug_assert_eq!(byte, layout::STR);
+
+        let len = safe_try!(self.read_byte(cursor)) as usize;
+
+        if len > self.max_str_len {
+            return Result::Err(DecodeError::Value(ValueError::StrTooLong));
+        }
+
+        let mut bytes = Vec::with_capacity(len);
+
+        if len > 0 {
+            let ptr = safe_try!(self.read_bytes(cursor, len));
+            let slice = unsafe { core::slice::from_raw_parts(ptr, len) };
+
+            for &byte in slice {
+                bytes.push(byte);
+            }
+        }
+
+        String::from_utf8(bytes).ok_or(DecodeError::Value(ValueError::InvalidUTF8String))
+    }
+
     fn decode_array(&self, cursor: &mut Cursor) -> Result<Value, DecodeError> {
         assert_no_eof!(cursor);
 
@@ -281,9 +327,10 @@ impl Decoder {
             layout::ARR_10 => 10,
             _ => svm_sdk_std::panic(),
         };
+        // The items may be composites themselves (i.e arrays of structs).
         seq_macro::seq!(n in 0..11 {
             if len > n {
-                let value = safe_try!(self.decode_primitive(cursor));
+                let value = safe_try!(self.decode_value(cursor));
                 values.push(value);
             }
         });
@@ -345,6 +392,7 @@ impl Decoder {
             layout::UNIT => TypeKind::Unit,
             layout::BOOL_FALSE | layout::BOOL_TRUE => TypeKind::Bool,
             layout::ADDRESS => TypeKind::Address,
+            layout::STR => TypeKind::Str,
 
             layout::AMOUNT_1B
             | layout::AMOUNT_2B
@@ -394,7 +442,7 @@ impl Decoder {
             | layout::ARR_9
             | layout::ARR_10 => TypeKind::Array,
 
-            _ => svm_sdk_std::panic(),
+            _ => return Result::Err(DecodeError::Type(TypeError::InvalidTypeKind(byte))),
         };
 
         Result::Ok(kind)
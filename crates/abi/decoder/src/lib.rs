@@ -22,7 +22,12 @@ mod decoder;
 
 pub use calldata::CallData;
 pub use cursor::Cursor;
-pub use decoder::{DecodeError, Decoder};
+pub use decoder::{DecodeError, Decoder, ValueError};
 
 /// `ReturnData` is a type alias to `CallData` for now.
 pub type ReturnData = CallData;
+
+/// `VerifyData` decodes the binary `verifydata` of a transaction (the input of `svm_verify`).
+///
+/// It's a type alias to `CallData` for now, since both share the same encoding.
+pub type VerifyData = CallData;
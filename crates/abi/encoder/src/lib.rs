@@ -10,5 +10,5 @@
 mod traits;
 mod types;
 
-pub use traits::{ByteSize, Encoder};
+pub use traits::{ByteSize, Encoder, Push};
 pub use types::*;
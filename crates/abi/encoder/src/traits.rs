@@ -6,9 +6,12 @@ pub trait Encoder<W> {
     fn encode(&self, w: &mut W);
 }
 
+/// A trait for sinks that encoded data can be pushed into (one item at a time).
 pub trait Push {
+    /// The type of the pushed items (`u8` for encoders' output).
     type Item;
 
+    /// Appends `item` to the sink.
     fn push(&mut self, item: Self::Item);
 }
 
@@ -2,7 +2,10 @@
 //!
 //! * Primitive - Currently only `Address` (20 bytes) and `PublicKey256` (256-bit <=> 32 bytes) are supported.
 //!
-//! * Composite - Currently only an `Array` of the `Primitive`(s) above is supported.
+//! * String - A UTF-8 string of at most 255 bytes.
+//!
+//! * Composite - An `Array` of values (which may be composites themselves).
+//!   A user-defined struct (see `#[derive(AbiEncode)]`) is encoded as an `Array` of its fields.
 //!
 //! ## Primitive Encoding:
 //!
@@ -13,19 +16,23 @@
 //! +------------------------------------+
 //!
 //!
-//! ## Composite Encoding:
+//! #### String
 //!
-//! ### Array of primitives
+//! +---------------------------------------------------+
+//! | String Marker | length (1 byte) | UTF-8 bytes     |
+//! +---------------------------------------------------+
 //!
-//! +-----------------------------------------------------+-----------------------------------------------------------+
-//! | Array Start Marker (1 byte) | Primitive #1 Encoding | . . . | Primitive #N Encoding | Array End Marker (1 byte) |
-//! +-----------------------------------------------------------------------------------------------------------------+
 //!
+//! ## Composite Encoding:
+//!
+//! ### Array
 //!
-//! ### Note:
+//! +-------------------------------------------------------------------------------+
+//! | Array Marker (holds the length) | Item #1 Encoding | . . . | Item #N Encoding |
+//! +-------------------------------------------------------------------------------+
 //!
-//! Actually the current `Encoder` code supports encoding also `Array` of `Array`'s but it'll error when decoded
-//! (see the `svm-abi-decoder` crate).
+//! An item can be either a `Primitive` or a `Composite`, so `Array`s may be nested
+//! (for example: an `Array` of structs, each holding an `Array` field).
 //!
 
 mod address;
@@ -34,6 +41,7 @@ mod boolean;
 mod numeric;
 mod option;
 mod small_array;
+mod string;
 mod tuples;
 mod unit;
 use svm_sdk_types::value::{Composite, Primitive, Value};
@@ -75,6 +83,7 @@ fn encode_primitive(p: &Primitive, w: &mut impl Push<Item = u8>) {
         Primitive::U32(p) => p.encode(w),
         Primitive::I64(p) => p.encode(w),
         Primitive::U64(p) => p.encode(w),
+        Primitive::Str(p) => p.encode(w),
     }
 }
 
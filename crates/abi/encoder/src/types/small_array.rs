@@ -42,7 +42,7 @@ where
             // The compiler complains, but it's wrong! The following comparison
             // might be useless or not, depending on the array const generic.
             #[allow(unused_comparisons)]
-            if N >= i {
+            if N > i {
                 payload_size += self[i].byte_size();
             }
         });
@@ -54,6 +54,19 @@ where
     }
 }
 
+/// Encodes the layout marker byte of an array of size `len`.
+///
+/// The items themselves are expected to be encoded right afterwards.
+/// This is used by the code generated by `#[derive(AbiEncode)]` since a struct is encoded as an array of its fields.
+pub fn encode_array_header<W>(len: usize, w: &mut W)
+where
+    W: Push<Item = u8>,
+{
+    assert!(len < 11);
+
+    w.push(layout_array(len));
+}
+
 /// Calculates the layout marker byte of an array of size `len`.
 const fn layout_array(len: usize) -> u8 {
     if len < 8 {
@@ -0,0 +1,46 @@
+use svm_abi_layout::layout;
+use svm_sdk_std::String;
+
+use crate::traits::Push;
+use crate::{ByteSize, Encoder};
+
+impl<W> Encoder<W> for String
+where
+    W: Push<Item = u8>,
+{
+    fn encode(&self, w: &mut W) {
+        let bytes = self.as_bytes();
+
+        assert!(bytes.len() <= layout::STR_MAX_LEN);
+
+        w.push(layout::STR);
+        w.push(bytes.len() as u8);
+
+        for &byte in bytes {
+            w.push(byte);
+        }
+    }
+}
+
+impl ByteSize for String {
+    fn byte_size(&self) -> usize {
+        2 + self.len()
+    }
+
+    fn max_byte_size() -> usize {
+        2 + layout::STR_MAX_LEN
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_byte_size() {
+        let s = String::new_short("Hello".as_bytes());
+
+        assert_eq!(s.byte_size(), 7);
+        assert_eq!(String::max_byte_size(), 257);
+    }
+}
@@ -7,7 +7,7 @@
 //! | 0 | 0 1 0 | 0 0 0 0 |  None                     |
 //! | 0 | 0 1 1 | 0 0 0 0 |  Unit                     |
 //! | 0 | 1 0 0 | 0 0 0 0 |  Address                  |
-//! | 0 | 1 0 1 | 0 0 0 0 |  Reserved                 |
+//! | 0 | 1 0 1 | 0 0 0 0 |  String                   |
 //! | 0 | 1 1 0 | 0 0 0 0 |  Reserved                 |
 //! | 0 | 1 1 1 | 0 0 0 0 |  Reserved                 |
 //! +---+-------+---------+---------------------------+
@@ -69,6 +69,10 @@
 //! | 0 | 0 1 0 | 0 1 1 1 |  Array - 10 items         |
 //! +---+-----------------+---------------------------+
 //!
+//! A `String` marker is followed by a single byte holding the length of the
+//! string (in bytes), and then by the UTF-8 encoded string itself.
+//! Hence, an encoded string is at most `STR_MAX_LEN` bytes long.
+//!
 
 #![no_std]
 #![deny(missing_docs)]
@@ -92,6 +96,10 @@ pub mod layout {
     // Address
     pub const ADDRESS: u8 = 0b_0_100_0000;
 
+    // String
+    pub const STR: u8 = 0b_0_101_0000;
+    pub const STR_MAX_LEN: usize = 255;
+
     /// Amount
     pub const AMOUNT_1B: u8 = 0b_0_000_0001;
     pub const AMOUNT_2B: u8 = 0b_0_001_0001;
@@ -8,10 +8,10 @@
 
 #[cfg(test)]
 mod tests {
-    use svm_abi_decoder::CallData;
-    use svm_abi_encoder::Encoder;
+    use svm_abi_decoder::{CallData, Cursor, DecodeError, Decoder, ValueError};
+    use svm_abi_encoder::{ByteSize, Encoder};
 
-    use svm_sdk_std::{Option, Vec};
+    use svm_sdk_std::{Option, String, Vec};
 
     use svm_sdk_types::value::Value;
     use svm_sdk_types::{Address, Amount};
@@ -287,6 +287,88 @@ mod tests {
         test_primitive!(Address, addr);
     }
 
+    #[test]
+    fn encode_decode_nested_array() {
+        let inner = |a: u8, b: Option<u32>| {
+            let mut values = Vec::with_capacity(2);
+            values.push(a.into());
+            values.push(b.into());
+
+            Value::from(values)
+        };
+
+        let mut values = Vec::with_capacity(3);
+        values.push(inner(1, Option::Some(10)));
+        values.push(inner(2, Option::None));
+        values.push(inner(3, Option::Some(30)));
+
+        let mut buf = Vec::with_capacity(1000);
+        let value = Value::from(values);
+        value.encode(&mut buf);
+
+        let mut calldata = CallData::new(as_static!(buf));
+        assert_eq!(calldata.next().unwrap(), value);
+        assert_eq!(calldata.next(), Option::None);
+    }
+
+    fn string(s: &str) -> String {
+        let mut bytes = Vec::with_capacity(s.len());
+        for &byte in s.as_bytes() {
+            bytes.push(byte);
+        }
+
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn encode_decode_str() {
+        for s in ["", "Hello", "Hello World", "שלום", &"a".repeat(255)].iter() {
+            let value: Value = string(s).into();
+
+            let mut buf = Vec::with_capacity(String::max_byte_size());
+            value.encode(&mut buf);
+
+            assert_eq!(buf.len(), string(s).byte_size());
+
+            let mut calldata = CallData::new(as_static!(buf));
+            let decoded: String = calldata.next_1();
+
+            assert_eq!(decoded.as_bytes(), s.as_bytes());
+        }
+    }
+
+    #[test]
+    fn decode_str_invalid_utf8() {
+        let bytes = [svm_abi_layout::layout::STR, 2, 0xC3, 0x28];
+
+        let mut cursor = Cursor::new(&bytes);
+        let res = Decoder::new().decode_value(&mut cursor);
+
+        assert!(matches!(
+            res,
+            svm_sdk_std::Result::Err(DecodeError::Value(ValueError::InvalidUTF8String))
+        ));
+    }
+
+    #[test]
+    fn decode_str_too_long() {
+        let mut buf = Vec::with_capacity(String::max_byte_size());
+        string("Hello").encode(&mut buf);
+
+        let mut cursor = Cursor::new(as_static!(buf));
+        let res = Decoder::with_max_str_len(4).decode_value(&mut cursor);
+
+        assert!(matches!(
+            res,
+            svm_sdk_std::Result::Err(DecodeError::Value(ValueError::StrTooLong))
+        ));
+
+        let mut cursor = Cursor::new(as_static!(buf));
+        let res = Decoder::with_max_str_len(5).decode_value(&mut cursor);
+
+        assert!(res.is_ok());
+    }
+
     #[test]
     fn calldata_next() {
         let a: u32 = 10;
@@ -4,9 +4,16 @@
 
 #![allow(unused)]
 
+mod subcmd_abi_vectors;
+mod subcmd_armor;
+mod subcmd_craft_call;
 mod subcmd_craft_deploy;
+mod subcmd_inspect;
+mod subcmd_receipts;
+mod subcmd_replay;
 mod subcmd_tx;
 mod subcmd_validate;
+mod subcmd_verify_artifact;
 
 use clap::ArgMatches;
 use thiserror::Error;
@@ -24,9 +31,16 @@ use svm_gas::validate_wasm;
 use svm_gas::ProgramPricing;
 use svm_program::{Program, ProgramVisitor};
 
+use subcmd_abi_vectors::{clap_app_abi_vectors, subcmd_abi_vectors};
+use subcmd_armor::{clap_app_armor, clap_app_unarmor, subcmd_armor, subcmd_unarmor};
+use subcmd_craft_call::{clap_app_craft_call, subcmd_craft_call};
 use subcmd_craft_deploy::{clap_app_craft_deploy, subcmd_craft_deploy};
+use subcmd_inspect::{clap_app_inspect, subcmd_inspect};
+use subcmd_receipts::{clap_app_receipts, subcmd_receipts};
+use subcmd_replay::{clap_app_replay, subcmd_replay};
 use subcmd_tx::{clap_app_tx, subcmd_tx};
 use subcmd_validate::{clap_app_validate, subcmd_validate};
+use subcmd_verify_artifact::{clap_app_verify_artifact, subcmd_verify_artifact};
 
 fn main() -> anyhow::Result<()> {
     let clap_matches = clap_app().get_matches();
@@ -34,6 +48,14 @@ fn main() -> anyhow::Result<()> {
         ("validate", Some(args)) => subcmd_validate(args)?,
         ("tx", Some(args)) => subcmd_tx(args)?,
         ("craft-deploy", Some(args)) => subcmd_craft_deploy(args)?,
+        ("craft-call", Some(args)) => subcmd_craft_call(args)?,
+        ("inspect", Some(args)) => subcmd_inspect(args)?,
+        ("replay", Some(args)) => subcmd_replay(args)?,
+        ("receipts", Some(args)) => subcmd_receipts(args)?,
+        ("verify-artifact", Some(args)) => subcmd_verify_artifact(args)?,
+        ("armor", Some(args)) => subcmd_armor(args)?,
+        ("unarmor", Some(args)) => subcmd_unarmor(args)?,
+        ("abi-vectors", Some(args)) => subcmd_abi_vectors(args)?,
         (_, _) => unreachable!(),
     }
     Ok(())
@@ -63,4 +85,12 @@ fn clap_app() -> clap::App<'static, 'static> {
         .subcommand(clap_app_validate())
         .subcommand(clap_app_tx())
         .subcommand(clap_app_craft_deploy())
+        .subcommand(clap_app_craft_call())
+        .subcommand(clap_app_inspect())
+        .subcommand(clap_app_replay())
+        .subcommand(clap_app_receipts())
+        .subcommand(clap_app_verify_artifact())
+        .subcommand(clap_app_armor())
+        .subcommand(clap_app_unarmor())
+        .subcommand(clap_app_abi_vectors())
 }
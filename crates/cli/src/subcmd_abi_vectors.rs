@@ -0,0 +1,46 @@
+use clap::ArgMatches;
+
+use std::fs;
+use std::path::Path;
+
+use svm_codec::api::json;
+
+pub fn clap_app_abi_vectors() -> clap::App<'static, 'static> {
+    use clap::*;
+
+    SubCommand::with_name("abi-vectors")
+        .about("Generates the ABI conformance vectors (a JSON file along with a binary file per vector)")
+        .arg(
+            Arg::with_name("output")
+                .help(
+                    "Writes the vectors into this directory: `vectors.json`, \
+                     and `<name>.bin` per vector (where each `/` of the name is replaced by a `.`)",
+                )
+                .short("o")
+                .long("output")
+                .required(true)
+                .takes_value(true),
+        )
+}
+
+pub fn subcmd_abi_vectors(args: &ArgMatches) -> anyhow::Result<()> {
+    let dir = Path::new(args.value_of("output").unwrap());
+    fs::create_dir_all(dir)?;
+
+    let corpus = json::abi_vectors_json();
+    fs::write(
+        dir.join("vectors.json"),
+        serde_json::to_string_pretty(&corpus)?,
+    )?;
+
+    let vectors = json::abi_vectors();
+
+    for vector in vectors.iter() {
+        let path = dir.join(format!("{}.bin", vector.name.replace('/', ".")));
+        fs::write(path, &vector.encoded)?;
+    }
+
+    println!("Wrote {} vectors into {}", vectors.len(), dir.display());
+
+    Ok(())
+}
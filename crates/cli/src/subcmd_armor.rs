@@ -0,0 +1,90 @@
+use clap::ArgMatches;
+
+use std::fs::File;
+use std::io::Write;
+
+use svm_codec::armor::{self, ArmorEncoding};
+
+pub fn clap_app_armor() -> clap::App<'static, 'static> {
+    use clap::*;
+
+    SubCommand::with_name("armor")
+        .about("Armors a binary transaction as a QR code friendly text")
+        .arg(
+            Arg::with_name("input")
+                .help("Reads the binary transaction from this file")
+                .short("i")
+                .long("input")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("output")
+                .help("Writes the armored text to this file (prints it when missing)")
+                .short("o")
+                .long("output")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("encoding")
+                .help("The text encoding of the armored transaction")
+                .long("encoding")
+                .takes_value(true)
+                .default_value("base45")
+                .possible_values(&["base45", "base64url"]),
+        )
+}
+
+pub fn clap_app_unarmor() -> clap::App<'static, 'static> {
+    use clap::*;
+
+    SubCommand::with_name("unarmor")
+        .about("Recovers a binary transaction from its armored text")
+        .arg(
+            Arg::with_name("input")
+                .help("Reads the armored text from this file")
+                .short("i")
+                .long("input")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("output")
+                .help("Writes the binary transaction to this file")
+                .short("o")
+                .long("output")
+                .required(true)
+                .takes_value(true),
+        )
+}
+
+pub fn subcmd_armor(args: &ArgMatches) -> anyhow::Result<()> {
+    let encoding = match args.value_of("encoding").unwrap() {
+        "base45" => ArmorEncoding::Base45,
+        "base64url" => ArmorEncoding::Base64Url,
+        _ => unreachable!(),
+    };
+
+    let bytes = std::fs::read(args.value_of("input").unwrap())?;
+    let armored = armor::armor(&bytes, encoding);
+
+    match args.value_of("output") {
+        Some(path) => {
+            let mut file = File::create(path)?;
+            writeln!(file, "{}", armored)?;
+        }
+        None => println!("{}", armored),
+    }
+
+    Ok(())
+}
+
+pub fn subcmd_unarmor(args: &ArgMatches) -> anyhow::Result<()> {
+    let armored = std::fs::read_to_string(args.value_of("input").unwrap())?;
+    let (bytes, _encoding) = armor::unarmor(&armored)?;
+
+    let mut file = File::create(args.value_of("output").unwrap())?;
+    file.write_all(&bytes)?;
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use clap::ArgMatches;
+use serde_json::{json, Value as Json};
+
+use std::fs::File;
+use std::io::Write;
+
+use svm_codec::api::json;
+
+pub fn clap_app_craft_call() -> clap::App<'static, 'static> {
+    use clap::*;
+
+    SubCommand::with_name("craft-call")
+        .about("Crafts a binary `Call` transaction (or decodes an existing one)")
+        .arg(
+            Arg::with_name("target")
+                .help("The address of the called `Account` (20 bytes, hex-encoded)")
+                .long("target")
+                .required_unless("decode")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("func")
+                .help("The name of the called function")
+                .long("func")
+                .required_unless("decode")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("calldata-hex")
+                .help("The binary `Calldata` of the call (hex-encoded)")
+                .long("calldata-hex")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("abi")
+                .help(
+                    "Reads the `Calldata` of the call from this JSON file \
+                     (either `{\"abi\": [...], \"data\": [...]}` or `{\"params\": [...], \"args\": {...}}`)",
+                )
+                .long("abi")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("version")
+                .help("The transaction format version")
+                .long("version")
+                .takes_value(true)
+                .default_value("0"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .help("Writes the binary transaction to this file")
+                .short("o")
+                .long("output")
+                .required_unless("decode")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("decode")
+                .help("Prints the binary transaction of this file as JSON (instead of crafting one)")
+                .long("decode")
+                .takes_value(true)
+                .conflicts_with_all(&["target", "func", "calldata-hex", "abi", "output"]),
+        )
+        .group(ArgGroup::with_name("calldata").args(&["calldata-hex", "abi"]))
+}
+
+pub fn subcmd_craft_call(args: &ArgMatches) -> anyhow::Result<()> {
+    if let Some(path) = args.value_of("decode") {
+        return decode(path);
+    }
+
+    let version: u16 = args.value_of("version").unwrap().parse()?;
+    let calldata = match (args.value_of("calldata-hex"), args.value_of("abi")) {
+        (Some(calldata), _) => calldata.to_string(),
+        (None, Some(path)) => {
+            let input = std::fs::read_to_string(path)?;
+            let encoded = json::encode_inputdata(&input)?;
+
+            encoded["data"].as_str().unwrap().to_string()
+        }
+        (None, None) => String::new(),
+    };
+
+    let call = json!({
+        "version": version,
+        "target": args.value_of("target").unwrap(),
+        "func_name": args.value_of("func").unwrap(),
+        "verifydata": "",
+        "calldata": calldata,
+    });
+    let bytes = json::encode_call_raw(&call.to_string())?;
+
+    let mut file = File::create(args.value_of("output").unwrap())?;
+    file.write_all(&bytes)?;
+
+    Ok(())
+}
+
+fn decode(path: &str) -> anyhow::Result<()> {
+    let bytes = std::fs::read(path)?;
+    let encoded = json!({ "data": hex::encode_upper(&bytes) });
+    let decoded: Json = json::decode_call(&encoded.to_string())?;
+
+    println!("{}", serde_json::to_string_pretty(&decoded)?);
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+
+use std::path::Path;
+
+use svm_program::Linker;
+
+/// A link manifest, describing how to assemble a `#[template]` split into several Wasm modules.
+///
+/// ```json
+/// {
+///   "modules": [
+///     { "name": "main", "path": "main.wasm" },
+///     { "name": "math", "path": "math.wat" }
+///   ],
+///   "aliases": [
+///     { "import": "env.add", "target": "math.add" }
+///   ],
+///   "exports": [
+///     { "module": "main", "name": "initialize" },
+///     { "module": "math", "name": "add", "as": "math_add" }
+///   ]
+/// }
+/// ```
+///
+/// The modules' paths are relative to the manifest. The `aliases` and `exports` are optional
+/// (by default the exports of the first module are exported).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LinkManifest {
+    modules: Vec<LinkModule>,
+    #[serde(default)]
+    aliases: Vec<LinkAlias>,
+    #[serde(default)]
+    exports: Vec<LinkExport>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LinkModule {
+    name: String,
+    path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LinkAlias {
+    import: String,
+    target: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LinkExport {
+    module: String,
+    name: String,
+    #[serde(rename = "as")]
+    export_as: Option<String>,
+}
+
+impl LinkManifest {
+    /// Links the modules listed by the manifest at `path` into a single Wasm module.
+    pub fn link(path: &Path) -> anyhow::Result<Vec<u8>> {
+        let manifest: LinkManifest = {
+            let string = std::fs::read_to_string(path)?;
+            serde_json::from_str(string.as_str())?
+        };
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+        let mut linker = Linker::new();
+
+        for module in manifest.modules.iter() {
+            let module_path = dir.join(&module.path);
+            let bytes = std::fs::read(&module_path)?;
+
+            if module.path.ends_with(".wat") || module.path.ends_with(".wast") {
+                linker.add_wat(&module.name, std::str::from_utf8(&bytes)?)?;
+            } else {
+                linker.add_module(&module.name, &bytes)?;
+            }
+        }
+
+        for alias in manifest.aliases.iter() {
+            let (import_module, import_name) = split_qualified(&alias.import)?;
+            let (target_module, target_name) = split_qualified(&alias.target)?;
+
+            linker.alias(import_module, import_name, target_module, target_name);
+        }
+
+        for export in manifest.exports.iter() {
+            let export_as = export.export_as.as_ref().unwrap_or(&export.name);
+
+            linker.export(&export.module, &export.name, export_as);
+        }
+
+        Ok(linker.link()?)
+    }
+}
+
+/// Splits `module.name` into its parts.
+fn split_qualified(qualified: &str) -> anyhow::Result<(&str, &str)> {
+    let mut parts = qualified.splitn(2, '.');
+
+    match (parts.next(), parts.next()) {
+        (Some(module), Some(name)) if !module.is_empty() && !name.is_empty() => Ok((module, name)),
+        _ => Err(anyhow::anyhow!(
+            "Expected `module.name` but got `{}`",
+            qualified
+        )),
+    }
+}
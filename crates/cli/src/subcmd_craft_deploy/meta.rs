@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value as Json;
 
-use svm_layout::{FixedLayoutBuilder, Id};
-use svm_types::{CtorsSection, DataSection};
+use svm_layout::{DynamicLayout, FixedLayoutBuilder, Id, Layout, Primitive};
+use svm_types::{
+    ApiFunc, ApiParam, ApiSection, ApiType, ApiVarDoc, CtorsSection, DataSection, DefaultsSection,
+};
 
 // Note: at the time of writing (2021-07-26), we don't care about most fields
 // within the "meta" JSON. As such, the [`TemplateMeta`] sub-entities can be
@@ -14,6 +16,12 @@ use svm_types::{CtorsSection, DataSection};
 pub struct TemplateMeta {
     schema: Vec<TemplateMetaVar>,
     api: Vec<TemplateMetaApi>,
+    /// The storage budget given by `#[template(max_storage_bytes = ...)]`.
+    #[serde(default)]
+    max_storage_bytes: Option<u32>,
+    /// The `///` docs of the template.
+    #[serde(default)]
+    doc: String,
 }
 
 impl TemplateMeta {
@@ -32,12 +40,77 @@ impl TemplateMeta {
         builder.set_first(Id(0));
 
         for schema_var in self.schema.iter() {
+            if schema_var.bit.is_some() {
+                builder.push_bit();
+                continue;
+            }
+
             for _ in 0..schema_var.length.unwrap_or(1) {
                 builder.push(schema_var.byte_count as u32);
             }
         }
 
-        DataSection::with_layout(svm_layout::Layout::Fixed(builder.build()))
+        let mut section = DataSection::with_layout(Layout::Fixed(builder.build()));
+
+        let dynamic_ids: Vec<Id> = self
+            .schema
+            .iter()
+            .filter(|var| var.dynamic)
+            .map(|var| Id(var.id as u32))
+            .collect();
+
+        if !dynamic_ids.is_empty() {
+            section.add_layout(Layout::Dynamic(DynamicLayout::new(dynamic_ids)));
+        }
+
+        section.with_max_storage_bytes(self.max_storage_bytes)
+    }
+
+    /// The `API Section`, holding the signatures of the exported functions (ordered by name)
+    /// and the `#[fallback]` function (if any), along with the docs of the template.
+    pub fn api_section(&self) -> anyhow::Result<ApiSection> {
+        let mut api = ApiSection::new().with_doc(self.doc.clone());
+
+        for schema_var in self.schema.iter().filter(|var| !var.doc.is_empty()) {
+            api = api.with_var_doc(ApiVarDoc {
+                name: schema_var.name.clone(),
+                doc: schema_var.doc.clone(),
+            });
+        }
+
+        if let Some(export) = self.api.iter().find(|export| export.is_fallback) {
+            api = api.with_fallback(export.wasm_name.clone());
+        }
+
+        let mut exports: Vec<&TemplateMetaApi> = self
+            .api
+            .iter()
+            .filter(|export| !export.is_fallback)
+            .collect();
+        exports.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for export in exports {
+            api = api.with_func(export.api_func()?);
+        }
+
+        Ok(api)
+    }
+
+    /// The default values given by `#[storage(default = ...)]` (an empty section when there are none).
+    pub fn defaults_section(&self) -> anyhow::Result<DefaultsSection> {
+        let mut section = DefaultsSection::default();
+
+        for schema_var in self.schema.iter() {
+            if let Some(default) = &schema_var.default {
+                let value = hex::decode(default).map_err(|e| {
+                    anyhow::anyhow!("Invalid default of variable `{}`: {}", schema_var.name, e)
+                })?;
+
+                section.push(Id(schema_var.id as u32), value);
+            }
+        }
+
+        Ok(section)
     }
 }
 
@@ -50,6 +123,15 @@ struct TemplateMetaVar {
     offset: usize,
     byte_count: usize,
     length: Option<usize>,
+    default: Option<String>,
+    /// Set for a packed `bool` (its bit within the byte at `offset`).
+    #[serde(default)]
+    bit: Option<u8>,
+    /// Set for a `Mapping<K, V>` or a `DynVec<T>` (its entries are stored under keys of their own).
+    #[serde(default)]
+    dynamic: bool,
+    #[serde(default)]
+    doc: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -58,10 +140,100 @@ struct TemplateMetaApi {
     wasm_name: String,
     is_ctor: bool,
     is_fundable: bool,
+    #[serde(default)]
+    is_fallback: bool,
     doc: String,
     signature: TemplateMetaSig,
 }
 
+impl TemplateMetaApi {
+    fn api_func(&self) -> anyhow::Result<ApiFunc> {
+        let invalid = || anyhow::anyhow!("Invalid signature of function `{}`", self.name);
+
+        let params = self
+            .signature
+            .params
+            .iter()
+            .map(|param| {
+                let ty = api_type(&param.ty, param.length).ok_or_else(invalid)?;
+
+                Ok(ApiParam {
+                    name: param.name.clone(),
+                    ty,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        // The `returns` is either `{}` (nothing is returned), a single type or an array of types (a tuple)
+        let returns = match &self.signature.returns {
+            Json::Object(obj) if obj.is_empty() => Vec::new(),
+            Json::Array(types) => types
+                .iter()
+                .map(|ty| return_type(ty).ok_or_else(invalid))
+                .collect::<anyhow::Result<Vec<_>>>()?,
+            ty => vec![return_type(ty).ok_or_else(invalid)?],
+        };
+
+        Ok(ApiFunc {
+            name: self.name.clone(),
+            wasm_name: self.wasm_name.clone(),
+            is_ctor: self.is_ctor,
+            is_fundable: self.is_fundable,
+            params,
+            returns,
+            doc: self.doc.clone(),
+        })
+    }
+}
+
+fn return_type(json: &Json) -> Option<ApiType> {
+    let ty = json["type"].as_str()?;
+    let length = match &json["length"] {
+        Json::Null => None,
+        length => Some(length.as_u64()? as usize),
+    };
+
+    api_type(ty, length)
+}
+
+/// Translates a type name of the SVM SDK (along with its `length` for an array).
+/// Any name which isn't of a primitive stands for a struct.
+fn api_type(ty: &str, length: Option<usize>) -> Option<ApiType> {
+    match (
+        ty.strip_prefix('[').and_then(|ty| ty.strip_suffix(']')),
+        length,
+    ) {
+        (Some(elem), Some(length)) => Some(ApiType::Array {
+            primitive: primitive(elem)?,
+            length,
+        }),
+        (None, None) => match primitive(ty) {
+            Some(primitive) => Some(ApiType::Primitive(primitive)),
+            None => Some(ApiType::Struct(ty.to_string())),
+        },
+        _ => None,
+    }
+}
+
+fn primitive(ty: &str) -> Option<Primitive> {
+    let primitive = match ty.trim_start_matches("svm_sdk :: ") {
+        "bool" => Primitive::Bool,
+        "i8" => Primitive::I8,
+        "u8" => Primitive::U8,
+        "i16" => Primitive::I16,
+        "u16" => Primitive::U16,
+        "i32" => Primitive::I32,
+        "u32" => Primitive::U32,
+        "i64" => Primitive::I64,
+        "u64" => Primitive::U64,
+        "Amount" => Primitive::Amount,
+        "Address" => Primitive::Address,
+        _ => return None,
+    };
+
+    Some(primitive)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct TemplateMetaSig {
     params: Vec<TemplateMetaSigParam>,
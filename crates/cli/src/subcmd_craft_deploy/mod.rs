@@ -1,3 +1,4 @@
+mod link;
 mod meta;
 
 use clap::ArgMatches;
@@ -6,11 +7,13 @@ use serde_json::Value;
 
 use std::fs::File;
 use std::io::Write;
+use std::path::Path;
 
 use svm_codec::{api::builder::TemplateBuilder, SectionsEncoder};
 use svm_layout::{FixedLayout, FixedLayoutBuilder, Id, Layout};
 use svm_types::{CodeSection, CtorsSection, DataSection, Section, Sections};
 
+use link::LinkManifest;
 use meta::TemplateMeta;
 
 pub fn clap_app_craft_deploy() -> clap::App<'static, 'static> {
@@ -22,7 +25,14 @@ pub fn clap_app_craft_deploy() -> clap::App<'static, 'static> {
             Arg::with_name("smwasm")
                 .help("Path to the smWasm `#[template]` code")
                 .long("smwasm")
-                .required(true)
+                .required_unless("link")
+                .conflicts_with("link")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("link")
+                .help("Path to a JSON link manifest, listing the Wasm modules to be linked into the `#[template]` code")
+                .long("link")
                 .takes_value(true),
         )
         .arg(
@@ -43,9 +53,12 @@ pub fn clap_app_craft_deploy() -> clap::App<'static, 'static> {
 }
 
 pub fn subcmd_craft_deploy(args: &ArgMatches) -> anyhow::Result<()> {
-    let smwasm = {
-        let path = args.value_of("smwasm").unwrap();
-        std::fs::read(path)?
+    let smwasm = match args.value_of("link") {
+        Some(path) => LinkManifest::link(Path::new(path))?,
+        None => {
+            let path = args.value_of("smwasm").unwrap();
+            std::fs::read(path)?
+        }
     };
     let meta: TemplateMeta = {
         let path = args.value_of("meta").unwrap();
@@ -67,6 +80,13 @@ pub fn subcmd_craft_deploy(args: &ArgMatches) -> anyhow::Result<()> {
     sections.insert(Section::Ctors(meta.ctors_section()));
     sections.insert(Section::Data(meta.data_section()));
 
+    let defaults = meta.defaults_section()?;
+    if !defaults.is_empty() {
+        sections.insert(Section::Defaults(defaults));
+    }
+
+    sections.insert(Section::Api(meta.api_section()?));
+
     let mut encoder = SectionsEncoder::with_capacity(3);
     encoder.encode(&sections);
     let bytes = encoder.finish();
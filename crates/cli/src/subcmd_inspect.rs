@@ -0,0 +1,78 @@
+use clap::ArgMatches;
+
+use svm_codec::inspect::{self, MessageKind};
+
+/// The number of bytes of each field printed (longer fields are cut short).
+const PREVIEW_LEN: usize = 16;
+
+pub fn clap_app_inspect() -> clap::App<'static, 'static> {
+    use clap::*;
+
+    SubCommand::with_name("inspect")
+        .about("Identifies a binary message (deploy/spawn/call/receipt) and prints its fields")
+        .arg(
+            Arg::with_name("input")
+                .help("Reads the binary message from this file")
+                .short("i")
+                .long("input")
+                .required(true)
+                .takes_value(true),
+        )
+}
+
+pub fn subcmd_inspect(args: &ArgMatches) -> anyhow::Result<()> {
+    let bytes = std::fs::read(args.value_of("input").unwrap())?;
+
+    let inspection = match inspect::inspect(&bytes) {
+        Some(inspection) => inspection,
+        None => {
+            let reasons: Vec<String> = MessageKind::ALL
+                .iter()
+                .map(|&kind| {
+                    let err = inspect::inspect_as(kind, &bytes).unwrap_err();
+                    format!("  {}: {}", kind.name(), err)
+                })
+                .collect();
+
+            anyhow::bail!("Unrecognized binary message:\n{}", reasons.join("\n"));
+        }
+    };
+
+    println!("{} ({} bytes)", inspection.kind.name(), bytes.len());
+    println!();
+
+    let path_width = inspection
+        .fields
+        .iter()
+        .map(|field| field.path.len())
+        .max()
+        .unwrap_or(0);
+
+    println!(
+        "{:>8}  {:>8}  {:<width$}  bytes",
+        "offset",
+        "length",
+        "field",
+        width = path_width
+    );
+
+    for field in inspection.fields.iter() {
+        let data = &bytes[field.range.clone()];
+        let mut preview = hex::encode_upper(&data[..data.len().min(PREVIEW_LEN)]);
+
+        if data.len() > PREVIEW_LEN {
+            preview.push_str("...");
+        }
+
+        println!(
+            "{:>8}  {:>8}  {:<width$}  {}",
+            field.range.start,
+            field.range.len(),
+            field.path,
+            preview,
+            width = path_width
+        );
+    }
+
+    Ok(())
+}
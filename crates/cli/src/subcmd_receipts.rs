@@ -0,0 +1,92 @@
+use clap::ArgMatches;
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use svm_runtime::export::ExportFormat;
+use svm_runtime::replay::ReceiptStore;
+use svm_types::Layer;
+
+use crate::subcmd_replay::read_store;
+
+pub fn clap_app_receipts() -> clap::App<'static, 'static> {
+    use clap::*;
+
+    SubCommand::with_name("receipts")
+        .about("Tools for the stored receipts")
+        .setting(AppSettings::SubcommandRequired)
+        .subcommand(
+            SubCommand::with_name("export")
+                .about("Exports the stored receipts as flattened rows, for analytics")
+                .arg(
+                    Arg::with_name("store")
+                        .help("Reads the executed transactions (in execution order) from this JSON file")
+                        .long("store")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("format")
+                        .help("The format of the exported rows")
+                        .long("format")
+                        .possible_values(&["csv", "parquet"])
+                        .default_value("csv")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("from-layer")
+                        .help("Exports the transactions starting at this layer (inclusive)")
+                        .long("from-layer")
+                        .default_value("0")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("to-layer")
+                        .help("Exports the transactions up to this layer (exclusive)")
+                        .long("to-layer")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .help("Writes the rows to this file (instead of the standard output)")
+                        .short("o")
+                        .long("output")
+                        .takes_value(true),
+                ),
+        )
+}
+
+pub fn subcmd_receipts(args: &ArgMatches) -> anyhow::Result<()> {
+    match args.subcommand() {
+        ("export", Some(args)) => subcmd_receipts_export(args),
+        (_, _) => unreachable!(),
+    }
+}
+
+fn subcmd_receipts_export(args: &ArgMatches) -> anyhow::Result<()> {
+    let store = read_store(args.value_of("store").unwrap())?;
+
+    let format: ExportFormat = args
+        .value_of("format")
+        .unwrap()
+        .parse()
+        .map_err(anyhow::Error::msg)?;
+
+    let from: u64 = args.value_of("from-layer").unwrap().parse()?;
+    let to: u64 = match args.value_of("to-layer") {
+        Some(to) => to.parse()?,
+        None => u64::MAX,
+    };
+
+    let mut writer: Box<dyn Write> = match args.value_of("output") {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(BufWriter::new(io::stdout())),
+    };
+
+    let count = store.export(Layer(from)..Layer(to), format, &mut writer)?;
+    writer.flush()?;
+
+    eprintln!("Exported {} transactions ({}).", count, format);
+
+    Ok(())
+}
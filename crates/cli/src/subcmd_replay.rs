@@ -0,0 +1,147 @@
+use clap::ArgMatches;
+use serde::Deserialize;
+
+use std::io::Cursor;
+
+use svm_codec::{context, envelope, receipt};
+use svm_runtime::replay::{self, MemReceiptStore, ReceiptStore, TxRecord};
+use svm_runtime::testing;
+use svm_types::{Receipt, TransactionId};
+
+/// The size limit (in bytes) of each recorded execution trace.
+const TRACE_LIMIT: usize = 1024 * 1024;
+
+pub fn clap_app_replay() -> clap::App<'static, 'static> {
+    use clap::*;
+
+    SubCommand::with_name("replay")
+        .about("Re-executes a stored transaction and diffs its receipt against the stored one")
+        .arg(
+            Arg::with_name("store")
+                .help("Reads the executed transactions (in execution order) from this JSON file")
+                .long("store")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("tx-id")
+                .help("The id of the replayed transaction (32 bytes, hex-encoded)")
+                .long("tx-id")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("verbose")
+                .help("Prints the storage accesses and host calls made during execution")
+                .short("v")
+                .long("verbose"),
+        )
+}
+
+/// A single executed transaction, as written in the store JSON file.
+///
+/// All fields are hex-encoded SVM binary encodings.
+#[derive(Debug, Deserialize)]
+struct StoredTx {
+    envelope: String,
+    context: String,
+    message: String,
+    receipt: String,
+}
+
+pub fn subcmd_replay(args: &ArgMatches) -> anyhow::Result<()> {
+    let tx_id: TransactionId = args.value_of("tx-id").unwrap().parse()?;
+    let store = read_store(args.value_of("store").unwrap())?;
+
+    let record = store
+        .load(&tx_id)
+        .ok_or_else(|| anyhow::anyhow!("Transaction {} not found", tx_id))?;
+
+    // The pre-state of the replayed transaction is rebuilt by re-executing
+    // all the transactions preceding it.
+    let mut runtime = testing::create_memory_runtime();
+    for prev in store.iter().take_while(|r| r.tx_id() != &tx_id) {
+        let replay = replay::replay(&mut runtime, prev)?;
+
+        if !replay.is_match() {
+            println!(
+                "[WARNING] Preceding transaction {} diverges from its stored receipt.",
+                prev.tx_id()
+            );
+        }
+    }
+
+    if args.is_present("verbose") {
+        init_logger();
+        runtime.set_trace_limit(Some(TRACE_LIMIT));
+    }
+
+    let replay = replay::replay(&mut runtime, record)?;
+
+    for phase in replay.phases.iter() {
+        println!(
+            "Phase `{}`: success = {}, gas_used = {:?}",
+            phase.name, phase.success, phase.gas_used
+        );
+
+        if let Some(trace) = phase.trace.as_ref() {
+            for entry in trace.entries() {
+                println!("    {}", entry);
+            }
+            if trace.is_truncated() {
+                println!("    ... (trace truncated)");
+            }
+        }
+    }
+
+    if replay.is_match() {
+        println!("The replayed receipt matches the stored one.");
+        Ok(())
+    } else {
+        for diff in replay.diffs.iter() {
+            println!("[DIFF] {}", diff);
+        }
+        anyhow::bail!("The replayed receipt differs from the stored one.")
+    }
+}
+
+/// Reads the store JSON file at `path` (see [`StoredTx`]).
+pub fn read_store(path: &str) -> anyhow::Result<MemReceiptStore> {
+    let string = std::fs::read_to_string(path)?;
+    let txs: Vec<StoredTx> = serde_json::from_str(string.as_str())?;
+
+    load_store(txs)
+}
+
+fn load_store(txs: Vec<StoredTx>) -> anyhow::Result<MemReceiptStore> {
+    let mut store = MemReceiptStore::new();
+
+    for tx in txs {
+        let envelope = hex::decode(tx.envelope)?;
+        let envelope = envelope::decode(&mut Cursor::new(&envelope[..]))?;
+
+        let context = hex::decode(tx.context)?;
+        let context = context::decode(&mut Cursor::new(&context[..]))?;
+
+        let message = hex::decode(tx.message)?;
+
+        let receipt = hex::decode(tx.receipt)?;
+        anyhow::ensure!(!receipt.is_empty(), "Empty receipt");
+        let receipt: Receipt = receipt::decode_receipt(&receipt);
+
+        store.store(TxRecord {
+            envelope,
+            context,
+            message,
+            receipt,
+        });
+    }
+
+    Ok(store)
+}
+
+fn init_logger() {
+    env_logger::Builder::new()
+        .filter_module("svm_runtime", log::LevelFilter::Trace)
+        .init();
+}
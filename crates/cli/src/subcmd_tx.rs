@@ -49,8 +49,8 @@ pub fn subcmd_tx(args: &ArgMatches) -> anyhow::Result<()> {
     let input_s = std::fs::read_to_string(input_path)?;
     let bytes = match action {
         Action::Call => json::encode_call_raw(&input_s).expect("Invalid JSON"),
-        Action::Deploy => json::deploy_template(&input_s).expect("Invalid JSON"),
-        Action::Spawn => json::encode_spawn(&input_s).expect("Invalid JSON"),
+        Action::Deploy => json::deploy_template_raw(&input_s).expect("Invalid JSON"),
+        Action::Spawn => json::encode_spawn_raw(&input_s).expect("Invalid JSON"),
     };
 
     let mut file = File::create(args.value_of("output").unwrap())?;
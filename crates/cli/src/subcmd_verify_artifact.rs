@@ -0,0 +1,298 @@
+use clap::ArgMatches;
+use maplit::hashset;
+
+use std::path::Path;
+
+use svm_hash::{Blake3Hasher, Hasher};
+use svm_types::{SectionKind, TemplateAddr};
+
+pub fn clap_app_verify_artifact() -> clap::App<'static, 'static> {
+    use clap::*;
+
+    SubCommand::with_name("verify-artifact")
+        .about("Verifies that a deployed `Template` code corresponds to a local smWasm artifact")
+        .arg(
+            Arg::with_name("smwasm")
+                .help("Path to the local smWasm artifact")
+                .long("smwasm")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("template-addr")
+                .help("The address of the deployed `Template` (20 bytes, hex-encoded)")
+                .long("template-addr")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("state-dir")
+                .help(
+                    "Reads the deployed `Template`s from the `rocksdb` store of the `Runtime` \
+                     persisting under this directory (i.e its `kv_path`)",
+                )
+                .long("state-dir")
+                .required(true)
+                .takes_value(true),
+        )
+}
+
+pub fn subcmd_verify_artifact(args: &ArgMatches) -> anyhow::Result<()> {
+    let template_addr = parse_template_addr(args.value_of("template-addr").unwrap())?;
+
+    let local = {
+        let path = args.value_of("smwasm").unwrap();
+        std::fs::read(path)?
+    };
+    let state_dir = Path::new(args.value_of("state-dir").unwrap());
+
+    let verification = verify_artifact(&local, state_dir, &template_addr)?;
+
+    let Verification { local, deployed } = &verification;
+
+    local.print("Local");
+    deployed.print("Deployed");
+
+    if verification.is_match() {
+        println!("The deployed code matches the local artifact.");
+        Ok(())
+    } else {
+        if local.size != deployed.size {
+            println!(
+                "[DIFF] normalized size: expected {}, got {}",
+                local.size, deployed.size
+            );
+        }
+        anyhow::bail!("The deployed code differs from the local artifact.")
+    }
+}
+
+/// Compares the `local` smWasm artifact against the code of the `Template` deployed at `template_addr`
+/// (as read from the store of the `Runtime` persisting under `state_dir`).
+fn verify_artifact(
+    local: &[u8],
+    state_dir: &Path,
+    template_addr: &TemplateAddr,
+) -> anyhow::Result<Verification> {
+    let deployed = load_code(state_dir, template_addr)?;
+
+    Ok(Verification {
+        local: Artifact::new(local)?,
+        deployed: Artifact::new(&deployed)?,
+    })
+}
+
+/// The outcome of [`verify_artifact`].
+struct Verification {
+    local: Artifact,
+    deployed: Artifact,
+}
+
+impl Verification {
+    /// Whether the local artifact matches the deployed code (once both are normalized).
+    fn is_match(&self) -> bool {
+        self.local.hash == self.deployed.hash
+    }
+}
+
+/// A Wasm binary after stripping its custom sections.
+struct Artifact {
+    hash: [u8; 32],
+    size: usize,
+    raw_size: usize,
+    custom_sections: Vec<String>,
+}
+
+impl Artifact {
+    fn new(wasm: &[u8]) -> anyhow::Result<Self> {
+        let (normalized, custom_sections) = strip_custom_sections(wasm)?;
+
+        Ok(Self {
+            hash: Blake3Hasher::hash(&normalized),
+            size: normalized.len(),
+            raw_size: wasm.len(),
+            custom_sections,
+        })
+    }
+
+    fn print(&self, name: &str) {
+        println!("{} code:", name);
+        println!("    hash (normalized): {}", hex::encode(self.hash));
+        println!("    size (normalized): {} bytes", self.size);
+        println!("    size (raw): {} bytes", self.raw_size);
+        println!("    custom sections: {:?}", self.custom_sections);
+    }
+}
+
+fn parse_template_addr(s: &str) -> anyhow::Result<TemplateAddr> {
+    let bytes = hex::decode(s.trim_start_matches("0x"))?;
+
+    anyhow::ensure!(
+        bytes.len() == TemplateAddr::len(),
+        "A `Template` address must be {} bytes long",
+        TemplateAddr::len()
+    );
+
+    Ok(TemplateAddr::from(&bytes[..]))
+}
+
+#[cfg(feature = "default-rocksdb")]
+fn load_code(state_dir: &Path, addr: &TemplateAddr) -> anyhow::Result<Vec<u8>> {
+    use svm_runtime::TemplateStore;
+    use svm_storage::kv::WriteMode;
+
+    anyhow::ensure!(
+        state_dir.is_dir(),
+        "No such directory `{}`",
+        state_dir.display()
+    );
+
+    let store = svm_runtime::open_rocksdb_template_store(&state_dir, WriteMode::Synchronous);
+
+    let interests = Some(hashset! { SectionKind::Code });
+    let template = store.load(addr, interests).ok_or_else(|| {
+        anyhow::anyhow!(
+            "There is no deployed `Template` at {}",
+            hex::encode(addr.as_slice())
+        )
+    })?;
+
+    Ok(template.code().to_vec())
+}
+
+#[cfg(not(feature = "default-rocksdb"))]
+fn load_code(_state_dir: &Path, _addr: &TemplateAddr) -> anyhow::Result<Vec<u8>> {
+    anyhow::bail!("Reading the deployed `Template`s requires building `svm-cli` with the `default-rocksdb` feature")
+}
+
+/// Drops the custom sections (section id `0`) of a Wasm binary, leaving the rest of it untouched.
+///
+/// Returns the normalized binary along with the names of the dropped sections.
+fn strip_custom_sections(wasm: &[u8]) -> anyhow::Result<(Vec<u8>, Vec<String>)> {
+    const HEADER_LEN: usize = 8;
+
+    anyhow::ensure!(
+        wasm.len() >= HEADER_LEN && &wasm[..4] == b"\0asm",
+        "Not a Wasm binary"
+    );
+
+    let mut normalized = wasm[..HEADER_LEN].to_vec();
+    let mut custom_sections = Vec::new();
+    let mut rest = &wasm[HEADER_LEN..];
+
+    while !rest.is_empty() {
+        let id = rest[0];
+        let (size, size_len) = read_leb128_u32(&rest[1..])?;
+        let start = 1 + size_len;
+        let end = start + size as usize;
+
+        anyhow::ensure!(end <= rest.len(), "Truncated Wasm section");
+
+        if id == 0 {
+            let payload = &rest[start..end];
+            let (name_len, name_len_len) = read_leb128_u32(payload)?;
+            let name = payload
+                .get(name_len_len..name_len_len + name_len as usize)
+                .ok_or_else(|| anyhow::anyhow!("Invalid Wasm custom section name"))?;
+
+            custom_sections.push(String::from_utf8_lossy(name).into_owned());
+        } else {
+            normalized.extend_from_slice(&rest[..end]);
+        }
+
+        rest = &rest[end..];
+    }
+
+    Ok((normalized, custom_sections))
+}
+
+fn read_leb128_u32(bytes: &[u8]) -> anyhow::Result<(u32, usize)> {
+    let mut value: u32 = 0;
+
+    for (i, &byte) in bytes.iter().take(5).enumerate() {
+        value |= ((byte & 0x7F) as u32) << (7 * i);
+
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+
+    anyhow::bail!("Invalid LEB128 encoding")
+}
+
+#[cfg(all(test, feature = "default-rocksdb"))]
+mod tests {
+    use super::*;
+
+    use std::path::PathBuf;
+
+    use svm_codec::api::builder::TemplateBuilder;
+    use svm_runtime::TemplateStore;
+    use svm_storage::kv::WriteMode;
+    use svm_types::{CodeSection, CtorsSection, DataSection, HeaderSection};
+
+    /// A Wasm binary holding a single (empty) type section.
+    const WASM: &[u8] = b"\0asm\x01\0\0\0\x01\x01\x00";
+
+    /// A custom section named `name`.
+    const CUSTOM_SECTION: &[u8] = b"\x00\x05\x04name";
+
+    fn deploy(name: &str, addr: &TemplateAddr, code: Vec<u8>) -> PathBuf {
+        let state_dir = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&state_dir);
+        std::fs::create_dir_all(&state_dir).unwrap();
+
+        let template = TemplateBuilder::default()
+            .with_code(CodeSection::new_fixed(code, 0))
+            .with_data(DataSection::default())
+            .with_ctors(CtorsSection::new(vec!["init".to_string()]))
+            .with_header(HeaderSection::new(
+                0,
+                "My Template".to_string(),
+                "".to_string(),
+            ))
+            .build();
+
+        let mut store =
+            svm_runtime::open_rocksdb_template_store(&state_dir, WriteMode::Synchronous);
+        store.store(&template, addr, &[0x01; 32]);
+
+        state_dir
+    }
+
+    #[test]
+    fn verify_artifact_match() {
+        let addr = TemplateAddr::repeat(0x10);
+
+        // The deployed code differs from the local artifact by its custom sections only
+        let mut code = WASM.to_vec();
+        code.extend_from_slice(CUSTOM_SECTION);
+
+        let state_dir = deploy("svm-cli-verify-artifact-match", &addr, code);
+        let verification = verify_artifact(WASM, &state_dir, &addr).unwrap();
+
+        assert!(verification.is_match());
+        assert_eq!(
+            verification.deployed.custom_sections,
+            vec!["name".to_string()]
+        );
+        assert!(verification.local.custom_sections.is_empty());
+    }
+
+    #[test]
+    fn verify_artifact_mismatch() {
+        let addr = TemplateAddr::repeat(0x20);
+
+        let state_dir = deploy("svm-cli-verify-artifact-mismatch", &addr, WASM.to_vec());
+
+        let mut local = WASM.to_vec();
+        local.extend_from_slice(b"\x01\x01\x00");
+
+        let verification = verify_artifact(&local, &state_dir, &addr).unwrap();
+        assert!(!verification.is_match());
+        assert_ne!(verification.local.size, verification.deployed.size);
+
+        let unknown = TemplateAddr::repeat(0x30);
+        assert!(verify_artifact(WASM, &state_dir, &unknown).is_err());
+    }
+}
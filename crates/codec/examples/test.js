@@ -274,6 +274,19 @@ describe("WASM Buffer", function () {
   });
 });
 
+describe("Self Test", function () {
+  it("Passes the embedded golden vectors", function () {
+    return compileWasmCodec().then((instance) => {
+      const result = instance.exports.wasm_self_test();
+      const report = loadWasmBufferDataAsJson(instance, result);
+
+      assert.strictEqual(report.passed, true, JSON.stringify(report));
+
+      wasmBufferFree(instance, result);
+    });
+  });
+});
+
 describe("Deploy Template", function () {
   it("Encodes & Decodes valid transactions", function () {
     return compileWasmCodec().then((instance) => {
@@ -294,11 +307,23 @@ describe("Deploy Template", function () {
       const slice = wasmBufferDataSlice(instance, result, 0, len);
       assert.strictEqual(slice[0], OK_MARKER);
 
-      // `bytes` is a `Uint8Array` holding the encoded `SVM spawn-account` transaction
+      // `bytes` is a `Uint8Array` holding the encoded `SVM deploy-template` transaction
       const bytes = slice.slice(1);
 
+      const decodeBuf = wasmNewBuffer(instance, { data: binToString(bytes) });
+      const decodeResult = instanceCall(instance, "wasm_decode_deploy", decodeBuf);
+      const json = loadWasmBufferDataAsJson(instance, decodeResult);
+
+      assert.strictEqual(json.name, "My Template");
+      assert.strictEqual(json.desc, "A few words");
+      assert.strictEqual(json.code_len, 2);
+      assert.strictEqual(json.data, "0000000100000003");
+      assert.deepStrictEqual(json.ctors, ["init", "start"]);
+
       wasmBufferFree(instance, buf);
       wasmBufferFree(instance, result);
+      wasmBufferFree(instance, decodeBuf);
+      wasmBufferFree(instance, decodeResult);
     });
   });
   it("Handles errors for invalid transactions", function () {
@@ -490,6 +515,26 @@ describe("Call Account", function () {
         "The value of a specific field is invalid (`target`)."
       );
 
+      wasmBufferFree(instance, buf);
+      wasmBufferFree(instance, result);
+    });
+  });
+  it("Peeks a single field", function () {
+    return compileWasmCodec().then((instance) => {
+      const target = generateAddress("1020304050");
+      const bytes = encodeCall(instance, target, "", "");
+
+      const buf = wasmBufferAlloc(instance, bytes.length);
+      copyToWasmBufferData(instance, buf, bytes);
+
+      const TARGET_FIELD = 1;
+      const result = instance.exports.wasm_tx_peek(buf, TARGET_FIELD);
+
+      let len = wasmBufferLength(instance, result);
+      const slice = wasmBufferDataSlice(instance, result, 0, len);
+      assert.strictEqual(slice[0], OK_MARKER);
+      assert.strictEqual(binToString(slice.slice(1)), target);
+
       wasmBufferFree(instance, buf);
       wasmBufferFree(instance, result);
     });
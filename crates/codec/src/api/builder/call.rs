@@ -1,10 +1,12 @@
-use svm_types::{Address, Transaction};
+use svm_types::{Address, Transaction, TransactionId};
 
-use crate::call;
+use super::error::{required, validate_inputdata, validate_name, validate_size, BuildError};
+use crate::{call, limits, Field, HashingWriter};
 
 /// Builds a binary representation for [`Transaction`].
 ///
-/// Should be used mainly for testing only.
+/// Host-side tooling should use [`CallBuilder::try_build`], which validates the [`Transaction`]
+/// (while [`CallBuilder::build`] panics on invalid input, so it's meant mainly for testing).
 pub struct CallBuilder {
     version: Option<u16>,
     target: Option<Address>,
@@ -89,6 +91,61 @@ impl CallBuilder {
     }
 
     pub fn build(self) -> Vec<u8> {
+        let tx = self.into_tx();
+        let mut w = Vec::new();
+
+        call::encode_call(&tx, &mut w).unwrap();
+
+        w
+    }
+
+    /// Same as [`Self::build`], also returning the [`TransactionId`] of the built bytes
+    /// (computed while encoding, see [`HashingWriter`]).
+    pub fn build_bytes_and_id(self) -> (Vec<u8>, TransactionId) {
+        let tx = self.into_tx();
+        let mut w = HashingWriter::new();
+
+        call::encode_call(&tx, &mut w).unwrap();
+
+        w.finish()
+    }
+
+    /// Validates the [`Transaction`] and builds it, returning it along with its binary encoding.
+    ///
+    /// Unlike [`Self::build`], missing or invalid fields are reported (instead of panicking).
+    pub fn try_build(self) -> Result<(Transaction, Vec<u8>), BuildError> {
+        let version = required(self.version, Field::Version)?;
+        let target = required(self.target, Field::TargetAddr)?;
+        let func_name = required(self.func_name, Field::Function)?;
+        let verifydata = self.verifydata.unwrap_or_default();
+        let calldata = self.calldata.unwrap_or_default();
+
+        validate_name(&func_name, Field::Function)?;
+        validate_size(
+            verifydata.len(),
+            call::MAX_VERIFYDATA_SIZE,
+            Field::VerifyData,
+        )?;
+        validate_inputdata(version, &verifydata, Field::VerifyData)?;
+        validate_inputdata(version, &calldata, Field::InputData)?;
+
+        let tx = Transaction {
+            version,
+            target,
+            func_name,
+            verifydata,
+            calldata,
+        };
+
+        let mut w = Vec::new();
+        call::encode_call(&tx, &mut w)?;
+
+        validate_size(w.len(), limits::MAX_CALL_SIZE, Field::Message)?;
+
+        Ok((tx, w))
+    }
+
+    fn into_tx(self) -> Transaction {
         let version = self.version.unwrap();
         let target = self.target.unwrap();
         let func_name = self.func_name.unwrap();
@@ -103,18 +160,81 @@ impl CallBuilder {
             Some(calldata) => calldata.to_vec(),
         };
 
-        let tx = Transaction {
+        Transaction {
             version,
             target,
             func_name,
             verifydata,
             calldata,
-        };
+        }
+    }
+}
 
-        let mut w = Vec::new();
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
 
-        call::encode_call(&tx, &mut w);
+    use super::*;
 
-        w
+    fn builder() -> CallBuilder {
+        CallBuilder::new()
+            .with_version(0)
+            .with_target(&Address::of("@target"))
+            .with_func("do_work")
+            .with_calldata(&[0x10, 0x20])
+    }
+
+    #[test]
+    fn call_builder_try_build() {
+        let (tx, bytes) = builder().try_build().unwrap();
+
+        assert_eq!(tx.func_name(), "do_work");
+        assert_eq!(bytes, builder().build());
+
+        let mut cursor = Cursor::new(&bytes[..]);
+        assert_eq!(call::decode_call(&mut cursor).unwrap(), tx);
+    }
+
+    #[test]
+    fn call_builder_try_build_invalid() {
+        let err = CallBuilder::new().with_version(0).try_build().unwrap_err();
+        assert_eq!(err, BuildError::MissingField(Field::TargetAddr));
+
+        let err = builder().with_func("").try_build().unwrap_err();
+        assert_eq!(err, BuildError::EmptyField(Field::Function));
+
+        let err = builder()
+            .with_verifydata(&[0; call::MAX_VERIFYDATA_SIZE + 1])
+            .try_build()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            BuildError::TooManyBytes {
+                field: Field::VerifyData,
+                max: call::MAX_VERIFYDATA_SIZE
+            }
+        );
+
+        let err = builder().with_calldata(&[0; 256]).try_build().unwrap_err();
+        assert_eq!(
+            err,
+            BuildError::TooManyBytes {
+                field: Field::InputData,
+                max: 255
+            }
+        );
+
+        let err = builder()
+            .with_version(2)
+            .with_calldata(&[0; limits::MAX_CALL_SIZE])
+            .try_build()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            BuildError::TooManyBytes {
+                field: Field::Message,
+                max: limits::MAX_CALL_SIZE
+            }
+        );
     }
 }
@@ -1,8 +1,11 @@
 use svm_types::{
-    ApiSection, CodeSection, CtorsSection, DataSection, DeploySection, HeaderSection,
-    SchemaSection, Section, SectionKind, Sections, Template,
+    ApiSection, CodeSection, CtorsSection, CustomSection, DataSection, DefaultsSection,
+    DeploySection, HeaderSection, SchemaSection, Section, SectionKind, Sections, Template,
+    TransactionId,
 };
 
+use crate::{template, HashingWriter};
+
 /// Builds a `Template`
 ///
 /// # Example
@@ -84,6 +87,18 @@ impl TemplateBuilder {
         self
     }
 
+    /// Appends `DefaultsSection`
+    pub fn with_defaults(mut self, section: DefaultsSection) -> Self {
+        self.add(section.into());
+        self
+    }
+
+    /// Appends a `CustomSection` (replacing a previous one of the same kind)
+    pub fn with_custom(mut self, section: CustomSection) -> Self {
+        self.add(section.into());
+        self
+    }
+
     /// Appends `DeploySection`
     pub fn with_deploy(mut self, section: DeploySection) -> Self {
         self.add(section.into());
@@ -138,6 +153,17 @@ impl TemplateBuilder {
         Template::new(self.sections)
     }
 
+    /// Builds a `Template` (see [`Self::build`]) and returns its binary `Deploy Template`
+    /// along with its [`TransactionId`] (computed while encoding, see [`HashingWriter`]).
+    pub fn build_bytes_and_id(self) -> (Vec<u8>, TransactionId) {
+        let template = self.build();
+        let mut w = HashingWriter::new();
+
+        template::encode_into(&template, &mut w);
+
+        w.finish()
+    }
+
     fn add(&mut self, section: Section) {
         self.sections.insert(section);
     }
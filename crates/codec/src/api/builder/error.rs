@@ -0,0 +1,74 @@
+use thiserror::Error;
+
+use crate::{error, inputdata, EncodeError, Field};
+
+/// The reason a transaction can't be built (see [`CallBuilder::try_build`](super::CallBuilder::try_build)
+/// and [`SpawnBuilder::try_build`](super::SpawnBuilder::try_build)).
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum BuildError {
+    /// A mandatory field hasn't been set.
+    #[error("Field `{0}` is missing")]
+    MissingField(Field),
+
+    /// A field has been set to an empty value.
+    #[error("Field `{0}` must not be empty")]
+    EmptyField(Field),
+
+    /// A field exceeds its maximum size.
+    #[error("Field `{field}` exceeds {max} bytes")]
+    TooManyBytes {
+        /// The field.
+        field: Field,
+        /// Its maximum size (in bytes).
+        max: usize,
+    },
+
+    /// A field holds more items than its encoding can hold.
+    #[error("Field `{field}` exceeds {max} items")]
+    TooManyItems {
+        /// The field.
+        field: Field,
+        /// Its maximum number of items.
+        max: usize,
+    },
+
+    /// A field isn't supported by the format version of the message.
+    #[error("Field `{0}` isn't supported by the message version")]
+    NotSupported(Field),
+}
+
+impl From<EncodeError> for BuildError {
+    fn from(err: EncodeError) -> Self {
+        match err {
+            EncodeError::TooManyBytes { field, max } => BuildError::TooManyBytes { field, max },
+            EncodeError::TooManyItems { field, max } => BuildError::TooManyItems { field, max },
+            EncodeError::NotSupported(field) => BuildError::NotSupported(field),
+        }
+    }
+}
+
+pub(super) fn required<T>(value: Option<T>, field: Field) -> Result<T, BuildError> {
+    value.ok_or(BuildError::MissingField(field))
+}
+
+/// Validates a non-empty string field (strings are prefixed by their length as a single byte).
+pub(super) fn validate_name(name: &str, field: Field) -> Result<(), BuildError> {
+    if name.is_empty() {
+        return Err(BuildError::EmptyField(field));
+    }
+
+    error::validate_string(name, field).map_err(BuildError::from)
+}
+
+/// Validates an `InputData` field of a message of format version `version`.
+pub(super) fn validate_inputdata(
+    version: u16,
+    data: &[u8],
+    field: Field,
+) -> Result<(), BuildError> {
+    inputdata::validate_inputdata(version, data, field).map_err(BuildError::from)
+}
+
+pub(super) fn validate_size(size: usize, max: usize, field: Field) -> Result<(), BuildError> {
+    error::validate_size(size, max, field).map_err(BuildError::from)
+}
@@ -2,8 +2,10 @@
 
 mod call;
 mod deploy;
+mod error;
 mod spawn;
 
 pub use call::CallBuilder;
 pub use deploy::TemplateBuilder;
+pub use error::BuildError;
 pub use spawn::SpawnBuilder;
@@ -1,16 +1,19 @@
-use svm_types::{Account, SpawnAccount, TemplateAddr};
+use svm_types::{Account, SpawnAccount, SpawnCall, TemplateAddr, TransactionId};
 
-use crate::spawn;
+use super::error::{required, validate_inputdata, validate_name, validate_size, BuildError};
+use crate::{limits, spawn, Field, HashingWriter};
 
 /// Builds a binary representation for [`SpawnAccount`]
 ///
-/// Should be used mainly for testing only.
+/// Host-side tooling should use [`SpawnBuilder::try_build`], which validates the [`SpawnAccount`]
+/// (while [`SpawnBuilder::build`] panics on invalid input, so it's meant mainly for testing).
 pub struct SpawnBuilder {
     version: Option<u16>,
     template: Option<TemplateAddr>,
     name: Option<String>,
     ctor_name: Option<String>,
     calldata: Option<Vec<u8>>,
+    call: Option<SpawnCall>,
 }
 
 ///
@@ -43,6 +46,7 @@ pub struct SpawnBuilder {
 ///                  account: Account { name, template_addr },
 ///                  ctor_name: ctor_name.to_string(),
 ///                  calldata,
+///                  call: None,
 ///                };
 ///
 //// assert_eq!(expected, actual);
@@ -58,6 +62,7 @@ impl SpawnBuilder {
             name: None,
             ctor_name: None,
             calldata: None,
+            call: None,
         }
     }
 
@@ -86,7 +91,74 @@ impl SpawnBuilder {
         self
     }
 
+    pub fn with_call(mut self, func_name: &str, calldata: &[u8]) -> Self {
+        self.call = Some(SpawnCall {
+            func_name: func_name.to_string(),
+            calldata: calldata.to_vec(),
+        });
+        self
+    }
+
     pub fn build(self) -> Vec<u8> {
+        let spawn = self.into_spawn();
+        let mut w = Vec::new();
+
+        spawn::encode(&spawn, &mut w).unwrap();
+
+        w
+    }
+
+    /// Same as [`Self::build`], also returning the [`TransactionId`] of the built bytes
+    /// (computed while encoding, see [`HashingWriter`]).
+    pub fn build_bytes_and_id(self) -> (Vec<u8>, TransactionId) {
+        let spawn = self.into_spawn();
+        let mut w = HashingWriter::new();
+
+        spawn::encode(&spawn, &mut w).unwrap();
+
+        w.finish()
+    }
+
+    /// Validates the [`SpawnAccount`] and builds it, returning it along with its binary encoding.
+    ///
+    /// Unlike [`Self::build`], missing or invalid fields are reported (instead of panicking).
+    pub fn try_build(self) -> Result<(SpawnAccount, Vec<u8>), BuildError> {
+        let version = required(self.version, Field::Version)?;
+        let template_addr = required(self.template, Field::TemplateAddr)?;
+        let name = required(self.name, Field::Name)?;
+        let ctor_name = required(self.ctor_name, Field::Ctor)?;
+        let calldata = self.calldata.unwrap_or_default();
+
+        validate_size(name.len(), std::u8::MAX as usize, Field::Name)?;
+        validate_name(&ctor_name, Field::Ctor)?;
+        validate_inputdata(version, &calldata, Field::InputData)?;
+
+        if let Some(call) = self.call.as_ref() {
+            if version < spawn::SPAWN_CALL_VERSION {
+                return Err(BuildError::NotSupported(Field::Function));
+            }
+
+            validate_name(&call.func_name, Field::Function)?;
+            validate_inputdata(version, &call.calldata, Field::InputData)?;
+        }
+
+        let spawn = SpawnAccount {
+            version,
+            account: Account::new(template_addr, name),
+            ctor_name,
+            calldata,
+            call: self.call,
+        };
+
+        let mut w = Vec::new();
+        spawn::encode(&spawn, &mut w)?;
+
+        validate_size(w.len(), limits::MAX_SPAWN_SIZE, Field::Message)?;
+
+        Ok((spawn, w))
+    }
+
+    fn into_spawn(self) -> SpawnAccount {
         let version = self.version.unwrap();
         let template_addr = self.template.unwrap();
         let name = self.name.unwrap();
@@ -97,17 +169,64 @@ impl SpawnBuilder {
             Some(calldata) => calldata.to_vec(),
         };
 
-        let spawn = SpawnAccount {
+        SpawnAccount {
             version,
             account: Account::new(template_addr, name),
             ctor_name,
             calldata,
-        };
+            call: self.call,
+        }
+    }
+}
 
-        let mut w = Vec::new();
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
 
-        spawn::encode(&spawn, &mut w);
+    use super::*;
 
-        w
+    fn builder() -> SpawnBuilder {
+        SpawnBuilder::new()
+            .with_version(0)
+            .with_template(&TemplateAddr::of("@template"))
+            .with_name("My Account")
+            .with_ctor("initialize")
+            .with_calldata(&[0x10, 0x20])
+    }
+
+    #[test]
+    fn spawn_builder_try_build() {
+        let (spawn, bytes) = builder().try_build().unwrap();
+
+        assert_eq!(spawn.ctor_name(), "initialize");
+        assert_eq!(bytes, builder().build());
+
+        let mut cursor = Cursor::new(&bytes[..]);
+        assert_eq!(spawn::decode(&mut cursor).unwrap(), spawn);
+    }
+
+    #[test]
+    fn spawn_builder_try_build_invalid() {
+        let err = SpawnBuilder::new().with_version(0).try_build().unwrap_err();
+        assert_eq!(err, BuildError::MissingField(Field::TemplateAddr));
+
+        let err = builder().with_ctor("").try_build().unwrap_err();
+        assert_eq!(err, BuildError::EmptyField(Field::Ctor));
+
+        let err = builder().with_calldata(&[0; 256]).try_build().unwrap_err();
+        assert_eq!(
+            err,
+            BuildError::TooManyBytes {
+                field: Field::InputData,
+                max: 255
+            }
+        );
+
+        // Messages of version `2` aren't limited by their length prefixes
+        let result = builder()
+            .with_version(2)
+            .with_calldata(&[0; 256])
+            .try_build();
+        assert!(result.is_ok());
     }
 }
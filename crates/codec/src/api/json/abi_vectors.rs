@@ -0,0 +1,348 @@
+use serde_json::{json, Value as Json};
+
+use super::inputdata::DecodedInputData;
+use super::serde_types::HexBlob;
+use crate::api::json;
+
+/// An ABI conformance vector: values (the `data`) of the given types (the `abi`),
+/// along with their binary encoding by this crate.
+///
+/// The `abi` and the `data` are given just like to [`encode_inputdata`](json::encode_inputdata).
+#[derive(Debug, Clone, PartialEq)]
+pub struct AbiVector {
+    /// A unique name of the form `<group>/<case>` (e.g `u32/max`).
+    pub name: String,
+
+    /// The types of the encoded values.
+    pub abi: Json,
+
+    /// The encoded values.
+    pub data: Json,
+
+    /// The binary encoding of `data`.
+    pub encoded: Vec<u8>,
+}
+
+/// Generates the ABI conformance vectors. They cover:
+///
+/// * Every primitive, at its boundary values (the minimum, the maximum and
+///   the values around each change of the encoded byte size).
+/// * Arrays of each primitive (empty, of a single item and of the maximum number of items).
+/// * Nested arrays, tuples, options and structs (and nestings of those).
+/// * Multiple values of different types.
+///
+/// Meant for proving that other implementations of the ABI (e.g the TS and Go clients) are byte-exact
+/// with the Rust encoder (see also [`abi_vectors_json`]).
+pub fn abi_vectors() -> Vec<AbiVector> {
+    let mut vectors = Vectors::default();
+
+    primitives(&mut vectors);
+    arrays(&mut vectors);
+    composites(&mut vectors);
+
+    vectors.0
+}
+
+/// Same as [`abi_vectors`], but returns the vectors as a JSON of the form:
+///
+/// ```json
+/// {
+///   "schema_version": 0,
+///   "vectors": [
+///     { "name": "u8/max", "abi": ["u8"], "data": [255], "encoded": "12FF" },
+///     ...
+///   ]
+/// }
+/// ```
+pub fn abi_vectors_json() -> Json {
+    let vectors: Vec<Json> = abi_vectors()
+        .into_iter()
+        .map(|vector| {
+            json!({
+                "name": vector.name,
+                "abi": vector.abi,
+                "data": vector.data,
+                "encoded": HexBlob(&vector.encoded),
+            })
+        })
+        .collect();
+
+    json!({
+        "schema_version": json::SCHEMA_VERSION,
+        "vectors": vectors,
+    })
+}
+
+#[derive(Default)]
+struct Vectors(Vec<AbiVector>);
+
+impl Vectors {
+    /// Adds a vector of the values `data` of the types `abi`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `data` can't be encoded (i.e the vector itself is invalid).
+    fn add(&mut self, name: String, abi: Json, data: Json) {
+        let input = json!({ "abi": abi, "data": data }).to_string();
+        let encoded = DecodedInputData::new(&input)
+            .and_then(|decoded| decoded.encode())
+            .unwrap_or_else(|err| panic!("Invalid ABI vector `{}`: {}", name, err));
+
+        self.0.push(AbiVector {
+            name,
+            abi,
+            data,
+            encoded,
+        });
+    }
+}
+
+/// The primitives, along with their boundary values.
+fn primitive_values() -> Vec<(&'static str, Vec<(String, Json)>)> {
+    let addr = |byte: u8| json!(hex::encode_upper([byte; 20]));
+    let seq_addr: Vec<u8> = (1..=20).collect();
+
+    vec![
+        (
+            "bool",
+            vec![("false".into(), json!(false)), ("true".into(), json!(true))],
+        ),
+        ("i8", signed_values(1)),
+        ("u8", unsigned_values(1)),
+        ("i16", signed_values(2)),
+        ("u16", unsigned_values(2)),
+        ("i32", signed_values(4)),
+        ("u32", unsigned_values(4)),
+        ("i64", signed_values(8)),
+        ("u64", unsigned_values(8)),
+        ("amount", unsigned_values(8)),
+        (
+            "address",
+            vec![
+                ("zeros".into(), addr(0x00)),
+                ("ones".into(), addr(0xFF)),
+                ("seq".into(), json!(hex::encode_upper(seq_addr))),
+            ],
+        ),
+        (
+            "string",
+            vec![
+                ("empty".into(), json!("")),
+                ("ascii".into(), json!("Hello World")),
+                ("utf8".into(), json!("שלום")),
+                ("max-len".into(), json!("a".repeat(255))),
+            ],
+        ),
+    ]
+}
+
+/// The boundary values of an unsigned integer of `byte_size` bytes:
+/// zero, the values around each change of the encoded byte size, and the maximum.
+fn unsigned_values(byte_size: u32) -> Vec<(String, Json)> {
+    let max = u64::MAX >> (64 - 8 * byte_size);
+
+    let mut values = vec![
+        ("zero".to_string(), json!(0u64)),
+        ("one".into(), json!(1u64)),
+    ];
+
+    for bytes in 1..byte_size {
+        let bound = 1u64 << (8 * bytes);
+
+        values.push((format!("{}-bytes-max", bytes), json!(bound - 1)));
+        values.push((format!("{}-bytes-min", bytes + 1), json!(bound)));
+    }
+
+    values.push(("max".into(), json!(max)));
+    values
+}
+
+/// Same as [`unsigned_values`], but for a signed integer (including the negative values).
+fn signed_values(byte_size: u32) -> Vec<(String, Json)> {
+    let min = i64::MIN >> (64 - 8 * byte_size);
+    let max = i64::MAX >> (64 - 8 * byte_size);
+
+    let mut values = vec![
+        ("min".to_string(), json!(min)),
+        ("minus-one".into(), json!(-1i64)),
+        ("zero".into(), json!(0i64)),
+        ("one".into(), json!(1i64)),
+    ];
+
+    for bytes in 1..byte_size {
+        let bound = 1i64 << (8 * bytes - 1);
+
+        values.push((format!("{}-bytes-max", bytes), json!(bound - 1)));
+        values.push((format!("{}-bytes-min", bytes), json!(-bound)));
+        values.push((format!("{}-bytes-positive", bytes + 1), json!(bound)));
+        values.push((format!("{}-bytes-negative", bytes + 1), json!(-bound - 1)));
+    }
+
+    values.push(("max".into(), json!(max)));
+    values
+}
+
+fn primitives(vectors: &mut Vectors) {
+    for (ty, values) in primitive_values() {
+        for (case, value) in values {
+            vectors.add(format!("{}/{}", ty, case), json!([ty]), json!([value]));
+        }
+    }
+}
+
+fn arrays(vectors: &mut Vectors) {
+    const MAX_ITEMS: usize = 10;
+
+    for (ty, values) in primitive_values() {
+        let items = |len: usize| -> Vec<Json> {
+            values
+                .iter()
+                .cycle()
+                .take(len)
+                .map(|(_, value)| value.clone())
+                .collect()
+        };
+
+        for &len in [0, 1, MAX_ITEMS].iter() {
+            vectors.add(
+                format!("array/{}/len-{}", ty, len),
+                json!([[ty]]),
+                json!([items(len)]),
+            );
+        }
+    }
+
+    vectors.add(
+        "array/nested".into(),
+        json!([[["u8"]]]),
+        json!([[[1, 2], [], [3]]]),
+    );
+    vectors.add(
+        "array/nested-max".into(),
+        json!([[["bool"]]]),
+        json!([vec![vec![true; MAX_ITEMS]; MAX_ITEMS]]),
+    );
+}
+
+fn composites(vectors: &mut Vectors) {
+    let addr = hex::encode_upper([0x10; 20]);
+    let transfer = json!({"struct": [
+        {"name": "to", "type": "address"},
+        {"name": "amount", "type": "amount"},
+        {"name": "memo", "type": {"option": "string"}},
+    ]});
+
+    // Tuples
+    vectors.add(
+        "tuple/pair".into(),
+        json!([{"tuple": ["u8", "bool"]}]),
+        json!([[7, true]]),
+    );
+    vectors.add(
+        "tuple/max-items".into(),
+        json!([{"tuple": ["bool", "i8", "u8", "i16", "u16", "i32", "u32", "i64", "u64", "amount"]}]),
+        json!([[true, -1, 2, -3, 4, -5, 6, -7, 8, 9]]),
+    );
+    vectors.add(
+        "tuple/nested".into(),
+        json!([{"tuple": ["string", {"tuple": ["u32", ["i8"]]}]}]),
+        json!([["svm", [10, [-1, 0, 1]]]]),
+    );
+
+    // Options
+    vectors.add(
+        "option/none".into(),
+        json!([{"option": "u32"}]),
+        json!([null]),
+    );
+    vectors.add(
+        "option/some".into(),
+        json!([{"option": "u32"}]),
+        json!([10]),
+    );
+    vectors.add(
+        "option/array".into(),
+        json!([[{"option": "string"}]]),
+        json!([["a", null, "b"]]),
+    );
+    vectors.add(
+        "option/tuple".into(),
+        json!([{"option": {"tuple": ["u8", "address"]}}, {"option": {"tuple": ["u8", "address"]}}]),
+        json!([[1, addr], null]),
+    );
+
+    // Structs
+    vectors.add(
+        "struct/flat".into(),
+        json!([transfer]),
+        json!([{"to": addr, "amount": 10, "memo": "rent"}]),
+    );
+    vectors.add(
+        "struct/array".into(),
+        json!([[transfer]]),
+        json!([[
+            {"to": addr, "amount": 10, "memo": "rent"},
+            {"to": addr, "amount": 20, "memo": null},
+        ]]),
+    );
+    vectors.add(
+        "struct/nested".into(),
+        json!([{"struct": [
+            {"name": "id", "type": "u64"},
+            {"name": "transfer", "type": transfer},
+            {"name": "tags", "type": ["u16"]},
+        ]}]),
+        json!([{"id": 1, "transfer": {"to": addr, "amount": 0, "memo": null}, "tags": [1, 2]}]),
+    );
+
+    // Multiple values
+    vectors.add(
+        "multi/primitives".into(),
+        json!(["u8", "bool", "amount", "string", "address"]),
+        json!([7, false, 100, "svm", addr]),
+    );
+    vectors.add("multi/empty".into(), json!([]), json!([]));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    #[test]
+    fn json_abi_vectors_round_trip() {
+        let vectors = abi_vectors();
+
+        let names: HashSet<&str> = vectors.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names.len(), vectors.len());
+
+        for vector in vectors.iter() {
+            let json = json!({ "data": HexBlob(&vector.encoded), "abi": vector.abi }).to_string();
+            let decoded = json::decode_inputdata(&json).unwrap();
+
+            assert_eq!(decoded["data"], vector.data, "{}", vector.name);
+        }
+    }
+
+    #[test]
+    fn json_abi_vectors_boundaries() {
+        let vectors = abi_vectors();
+        let encoded = |name: &str| {
+            let vector = vectors.iter().find(|v| v.name == name).unwrap();
+            hex::encode_upper(&vector.encoded)
+        };
+
+        // The byte size changes past each boundary
+        assert_eq!(
+            encoded("u32/1-bytes-max").len() + 2,
+            encoded("u32/2-bytes-min").len()
+        );
+        assert_eq!(encoded("i64/min").len(), encoded("i64/max").len());
+        assert_eq!(encoded("multi/empty"), "");
+
+        let json = abi_vectors_json();
+        assert_eq!(json["vectors"].as_array().unwrap().len(), vectors.len());
+        assert_eq!(json["vectors"][0]["name"], "bool/false");
+    }
+}
@@ -0,0 +1,154 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as Json};
+
+use super::serde_types::HexBlob;
+use super::JsonSerdeUtils;
+use crate::api::json::JsonError;
+use crate::armor::{self, ArmorEncoding};
+
+/// Given a binary transaction wrapped inside a JSON, armors it as a text (see [`crate::armor`]).
+///
+/// ```json
+/// {
+///   "data": "FFC103...",  // the binary transaction
+///   "encoding": "base45"  // (optional) `base45` (the default) or `base64url`
+/// }
+/// ```
+///
+/// The response is of the form:
+///
+/// ```json
+/// {
+///   "armored": "SVM0:B45:..."
+/// }
+/// ```
+pub fn armor(json: &str) -> Result<Json, JsonError> {
+    let tx = ArmorTx::from_json_str(json)?;
+    let armored = armor::armor(&tx.data.0, tx.encoding.into());
+
+    Ok(json!({ "armored": armored }))
+}
+
+/// Given an armored text wrapped inside a JSON, returns the binary transaction it armors.
+///
+/// ```json
+/// {
+///   "armored": "SVM0:B45:..."
+/// }
+/// ```
+///
+/// The response is of the form:
+///
+/// ```json
+/// {
+///   "data": "FFC103...",
+///   "encoding": "base45"
+/// }
+/// ```
+pub fn unarmor(json: &str) -> Result<Json, JsonError> {
+    let armored = ArmoredTx::from_json_str(json)?;
+
+    let (bytes, encoding) =
+        armor::unarmor(&armored.armored).map_err(|_| JsonError::InvalidField {
+            path: "armored".to_string(),
+        })?;
+
+    Ok(json!({
+        "data": HexBlob(&bytes),
+        "encoding": Encoding::from(encoding),
+    }))
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Encoding {
+    Base45,
+    Base64url,
+}
+
+impl Default for Encoding {
+    fn default() -> Self {
+        Encoding::Base45
+    }
+}
+
+impl From<Encoding> for ArmorEncoding {
+    fn from(encoding: Encoding) -> Self {
+        match encoding {
+            Encoding::Base45 => ArmorEncoding::Base45,
+            Encoding::Base64url => ArmorEncoding::Base64Url,
+        }
+    }
+}
+
+impl From<ArmorEncoding> for Encoding {
+    fn from(encoding: ArmorEncoding) -> Self {
+        match encoding {
+            ArmorEncoding::Base45 => Encoding::Base45,
+            ArmorEncoding::Base64Url => Encoding::Base64url,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ArmorTx {
+    data: HexBlob<Vec<u8>>,
+
+    #[serde(default)]
+    encoding: Encoding,
+}
+
+impl JsonSerdeUtils for ArmorTx {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ArmoredTx {
+    armored: String,
+}
+
+impl JsonSerdeUtils for ArmoredTx {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_armor_unarmor() {
+        let data = vec![0x10, 0x20, 0x30];
+
+        for encoding in &["base45", "base64url"] {
+            let json = json!({ "data": HexBlob(&data), "encoding": encoding });
+            let armored = armor(&json.to_string()).unwrap();
+
+            let json = unarmor(&armored.to_string()).unwrap();
+            assert_eq!(
+                json,
+                json!({
+                    "data": "102030",
+                    "encoding": encoding
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn json_armor_default_encoding() {
+        let json = json!({ "data": "102030" });
+        let armored = armor(&json.to_string()).unwrap();
+
+        let expected = armor::armor(&[0x10, 0x20, 0x30], ArmorEncoding::Base45);
+        assert_eq!(armored, json!({ "armored": expected }));
+    }
+
+    #[test]
+    fn json_unarmor_invalid() {
+        let json = json!({ "armored": "SVM0:B45:%%%" });
+        let err = unarmor(&json.to_string()).unwrap_err();
+
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "armored".to_string()
+            }
+        );
+    }
+}
@@ -8,6 +8,7 @@ use svm_types::Transaction;
 use super::inputdata::{decode_raw_input, DecodedInputData};
 use super::serde_types::*;
 use crate::api::json::{JsonError, JsonSerdeUtils};
+use crate::limits::{self, MessageFamily};
 
 /// Transforms a user-friendly `call` into an encoded form:
 ///
@@ -20,21 +21,23 @@ use crate::api::json::{JsonError, JsonSerdeUtils};
 ///   "calldata": {"abi": [], "data": []},
 /// }
 ///
-/// The `calldata` field can be both encoded and user-friendly form.
+/// The `verifydata` and `calldata` fields can be both encoded and user-friendly form.
+/// The encoded `verifydata` can't exceed [`MAX_VERIFYDATA_SIZE`](crate::call::MAX_VERIFYDATA_SIZE) bytes.
+/// An optional `echo` field is returned verbatim within the result.
 ///
 /// Result:
 ///
 /// ```json
 /// {
-///   "data": "AABBCCFF81..."
+///   "data": "AABBCCFF81...",
+///   "schema_version": 0,
+///   "echo": ...
 /// }
 /// ```
 pub fn encode_call(json: &str) -> Result<Json, JsonError> {
     let encoded_bytes = encode_call_raw(json)?;
-    Ok(EncodedData {
-        data: HexBlob(encoded_bytes),
-    }
-    .to_json())
+
+    Ok(super::encode_response(encoded_bytes, super::echo_of(json)))
 }
 
 /// Much like [`encode_call`], but instead of returning a JSON wrapper it
@@ -43,8 +46,16 @@ pub fn encode_call_raw(json: &str) -> Result<Vec<u8>, JsonError> {
     let decoded_call = DecodedCall::from_json_str(json)?;
     let tx = Transaction::from(decoded_call);
 
+    if tx.verifydata().len() > crate::call::MAX_VERIFYDATA_SIZE {
+        return Err(JsonError::InvalidField {
+            path: "verifydata".to_string(),
+        });
+    }
+
     let mut buf = Vec::new();
-    crate::call::encode_call(&tx, &mut buf);
+    crate::call::encode_call(&tx, &mut buf)?;
+
+    limits::check_size(MessageFamily::Call, buf.len())?;
 
     Ok(buf)
 }
@@ -57,10 +68,25 @@ pub fn encode_call_raw(json: &str) -> Result<Vec<u8>, JsonError> {
 ///   "data": "E9E50C787F2076BD5E44"
 /// }
 /// ```
+///
+/// The result is of the form:
+///
+/// ```json
+/// {
+///   "version": 0,
+///   "target": "A2FB...",
+///   "func_name": "do_work",
+///   "verifydata": {"abi": [], "data": []},
+///   "calldata": {"abi": [], "data": []}
+/// }
+/// ```
 pub fn decode_call(json: &str) -> Result<Json, JsonError> {
     let encoded_call = EncodedData::from_json_str(json)?;
     let mut cursor = Cursor::new(&encoded_call.data.0[..]);
-    let tx = crate::call::decode_call(&mut cursor).unwrap();
+    let tx = crate::call::decode_call(&mut cursor).map_err(|err| JsonError::InvalidBinary {
+        path: "data".to_string(),
+        error: err.at("CallAccount", &cursor),
+    })?;
 
     Ok(DecodedCall::from(tx).to_json())
 }
@@ -134,6 +160,20 @@ mod tests {
     use super::*;
     use crate::api::json;
 
+    #[test]
+    fn json_decode_call_truncated() {
+        // version `0` followed by 3 bytes of the `target`
+        let json = json!({ "data": "0000A2FB01" }).to_string();
+
+        let err = decode_call(&json).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The binary value of a specific field is invalid (`data`): \
+             Not enough bytes for field `TargetAddr` \
+             (at `CallAccount.target_addr`, offset 2: expected 20 bytes, 3 available)."
+        );
+    }
+
     #[test]
     fn json_call_missing_version() {
         let json = json!({}).to_string();
@@ -276,4 +316,108 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn json_call_verifydata_too_large() {
+        let verifydata = "FF".repeat(crate::call::MAX_VERIFYDATA_SIZE + 1);
+
+        let json = json!({
+            "version": 0,
+            "target": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "func_name": "do_something",
+            "verifydata": verifydata,
+            "calldata": "",
+        })
+        .to_string();
+
+        let err = encode_call(&json).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "verifydata".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn json_call_too_large() {
+        let calldata = "00".repeat(crate::limits::MAX_CALL_SIZE);
+
+        let json = json!({
+            "version": 2,
+            "target": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "func_name": "do_something",
+            "verifydata": "",
+            "calldata": calldata,
+        })
+        .to_string();
+
+        let err = encode_call(&json).unwrap_err();
+        assert!(matches!(
+            err,
+            JsonError::MessageTooLarge(crate::limits::MessageTooLarge {
+                family: MessageFamily::Call,
+                max: crate::limits::MAX_CALL_SIZE,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn json_call_func_name_too_long() {
+        let json = json!({
+            "version": 0,
+            "target": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "func_name": "f".repeat(256),
+            "verifydata": "",
+            "calldata": "",
+        })
+        .to_string();
+
+        let err = encode_call(&json).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::Encode(crate::EncodeError::TooManyBytes {
+                field: crate::Field::Function,
+                max: 255
+            })
+        );
+    }
+
+    #[test]
+    fn json_call_echo() {
+        let json = json!({
+            "version": 0,
+            "target": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "func_name": "do_something",
+            "verifydata": "",
+            "calldata": "",
+            "echo": "request #1",
+        })
+        .to_string();
+
+        let encoded_json = encode_call(&json).unwrap();
+
+        assert_eq!(encoded_json["echo"], json!("request #1"));
+        assert_eq!(encoded_json["schema_version"], json!(json::SCHEMA_VERSION));
+        assert!(decode_call(&encoded_json.to_string()).is_ok());
+    }
+
+    #[test]
+    fn json_decode_call_golden() {
+        let json = json!({
+            "version": 0,
+            "target": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "func_name": "do_something",
+            "verifydata": {"abi": ["bool"], "data": [true]},
+            "calldata": {"abi": ["i32", "address"], "data": [10, "10203040506070809000A0B0C0D0E0F0ABCDEFFF"]},
+        })
+        .to_string();
+
+        let encoded_json = encode_call(&json).unwrap();
+        let json = decode_call(&encoded_json.to_string()).unwrap();
+
+        let golden = r#"{"version":0,"target":"10203040506070809000A0B0C0D0E0F0ABCDEFFF","func_name":"do_something","verifydata":{"abi":["bool"],"data":[true]},"calldata":{"abi":["i32","address"],"data":[10,"10203040506070809000A0B0C0D0E0F0ABCDEFFF"]}}"#;
+        assert_eq!(json.to_string(), golden);
+    }
 }
@@ -1,11 +1,21 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as Json};
 
-use svm_layout::{FixedLayoutBuilder, Id, Layout};
-use svm_types::{CodeSection, CtorsSection, DataSection, HeaderSection};
+use svm_hash::{Blake3Hasher, Hasher};
+use svm_layout::{
+    DynamicLayout, FixedLayoutBuilder, Id, Layout, LayoutKind, Primitive, SymbolicVar, Type,
+};
+use svm_types::{
+    ApiFunc, ApiParam, ApiSection, ApiType, ApiVarDoc, CodeSection, CtorsSection, DataSection,
+    HeaderSection, SchemaSection, Section, SectionKind, Sections,
+};
 
-use super::{serde_types::HexBlob, JsonError, JsonSerdeUtils};
+use super::serde_types::{AddressWrapper, EncodedData, HexBlob, TemplateAddrWrapper};
+use super::{JsonError, JsonSerdeUtils};
 use crate::api::builder::TemplateBuilder;
-use crate::template;
+use crate::limits::{self, MessageFamily};
+use crate::section::decode_raw_section;
+use crate::{template, SectionsDecoder};
 
 ///
 /// ```json
@@ -15,26 +25,486 @@ use crate::template;
 ///   "code_version": "...",  // number (`u32`)
 ///   "desc": "...",          // string
 ///   "code": "...",          // string (represents a `blob`)
+///   "precompile": 0,        // (optional) number (`u32`)
 ///   "data": "",             // string (represents a `blob`)
 ///   "ctors": ["", ""],      // string[]
+///   "max_storage_bytes": 0, // (optional) number (`u32`)
+///   "dynamic": [],          // (optional) number[] (`u32`)
+///   "schema": [],           // (optional) the storage variables (see below)
+///   "fallback": "...",      // (optional) string
+///   "api": [],              // (optional) the signatures of the exported functions (see below)
+///   "doc": "...",           // (optional) the documentation of the `Template`
+///   "var_docs": [],         // (optional) the documentation of the storage variables (see below)
 /// }
 /// ```
-pub fn deploy_template(json: &str) -> Result<Vec<u8>, JsonError> {
+///
+/// The `data` is the byte size of each variable (as Big-Endian `u32`s),
+/// where a zero byte size stands for a packed boolean variable.
+///
+/// The `dynamic` are the ids of the dynamic variables (e.g a `Mapping` or a `DynVec`),
+/// each allocated a variable of the `data` as well.
+///
+/// The `schema` names and types the variables of the `data` (by their ids), resulting in a `Schema Section`:
+///
+/// ```json
+/// [
+///   {"id": 0, "name": "owner", "type": "address"},
+///   {"id": 1, "name": "prices", "type": "[u64]", "length": 4}
+/// ]
+/// ```
+///
+/// The types are the primitives of the `Input Data` ABI (e.g `"u32"` or `"amount"`),
+/// and arrays of primitives (e.g `"[u32]"`, along with their `length`).
+///
+/// The `api` (along with the `fallback`) results in an `Api Section`:
+///
+/// ```json
+/// [
+///   {
+///     "name": "transfer",
+///     "wasm_name": "transfer",  // (optional) defaults to the `name`
+///     "is_ctor": false,         // (optional) defaults to `false`
+///     "is_fundable": true,      // (optional) defaults to `false`
+///     "params": [{"name": "to", "type": "address"}, {"name": "t", "type": "Transfer"}],
+///     "returns": [{"type": "[u64]", "length": 2}],
+///     "doc": "Transfers coins"  // (optional) the function's documentation
+///   }
+/// ]
+/// ```
+///
+/// Besides the types of the `schema`, a param (or a returned value) may be of a user-defined struct,
+/// given by its (capitalized) name.
+///
+/// The `doc` and the `var_docs` (e.g `[{"name": "owner", "doc": "The owner"}]`) are held by the `Api Section`
+/// (see [`decode_docs`]).
+///
+/// A precompile `Template` (see [`CodeKind::Precompile`](svm_types::CodeKind::Precompile))
+/// is given the id of its `precompile` (along with an empty `code`).
+///
+/// An optional `echo` field is returned verbatim within the result.
+///
+/// Result:
+///
+/// ```json
+/// {
+///   "data": "AABBCCFF81...",
+///   "schema_version": 0,
+///   "echo": ...
+/// }
+/// ```
+pub fn deploy_template(json: &str) -> Result<Json, JsonError> {
+    let encoded_bytes = deploy_template_raw(json)?;
+
+    Ok(super::encode_response(encoded_bytes, super::echo_of(json)))
+}
+
+/// Much like [`deploy_template`], but instead of returning a JSON wrapper it
+/// returns the raw bytes.
+pub fn deploy_template_raw(json: &str) -> Result<Vec<u8>, JsonError> {
     let deploy = DecodedDeploy::from_json_str(json)?;
     let layout = to_data_layout(deploy.data.0)?;
-    let code = CodeSection::new_fixed(deploy.code.0, deploy.svm_version);
-    let data = DataSection::with_layout(layout);
+    let code = match deploy.precompile {
+        None => CodeSection::new_fixed(deploy.code.0, deploy.svm_version),
+        Some(id) if deploy.code.0.is_empty() => CodeSection::new_precompile(id, deploy.svm_version),
+        Some(..) => {
+            return Err(JsonError::InvalidField {
+                path: "code".to_string(),
+            })
+        }
+    };
+    let mut data =
+        DataSection::with_layout(layout).with_max_storage_bytes(deploy.max_storage_bytes);
+
+    if let Some(dynamic) = deploy.dynamic.filter(|ids| !ids.is_empty()) {
+        data.add_layout(Layout::Dynamic(DynamicLayout::from(dynamic.as_slice())));
+    }
     let ctors = CtorsSection::new(deploy.ctors);
     let header = HeaderSection::new(deploy.code_version, deploy.name, deploy.desc);
 
-    let template = TemplateBuilder::default()
+    let mut builder = TemplateBuilder::default()
         .with_code(code)
         .with_data(data)
         .with_ctors(ctors)
-        .with_header(header)
-        .build();
+        .with_header(header);
+
+    if let Some(schema) = deploy.schema {
+        builder = builder.with_schema(to_schema_section(schema)?);
+    }
+
+    let has_docs = deploy.doc.is_some() || deploy.var_docs.is_some();
+
+    if deploy.fallback.is_some() || deploy.api.is_some() || has_docs {
+        let funcs = deploy.api.unwrap_or_default();
+        let mut api = to_api_section("api", deploy.fallback, funcs)?;
+
+        api = api.with_doc(deploy.doc.unwrap_or_default());
+
+        for var_doc in deploy.var_docs.unwrap_or_default() {
+            api = api.with_var_doc(ApiVarDoc {
+                name: var_doc.name,
+                doc: var_doc.doc,
+            });
+        }
+
+        builder = builder.with_api(api);
+    }
+
+    let template = builder.build();
+    let bytes = template::encode(&template);
+
+    limits::check_size(MessageFamily::Deploy, bytes.len())?;
+
+    Ok(bytes)
+}
+
+/// Given a binary `Deploy Template` wrapped inside JSON,
+/// Decodes its `Section`s and returns a user-friendly JSON.
+///
+/// ```json
+/// {
+///   "data": "0004000100000016..."
+/// }
+/// ```
+///
+/// Only the `Section`s present are returned. The `Code` itself is summarized by its
+/// length and hash, and the payload of each `Custom Section` is returned as is:
+///
+/// ```json
+/// {
+///   "svm_version": 1,
+///   "code_len": 2,
+///   "code_hash": "...",                         // string (Blake3 hash)
+///   "precompile": 7,                            // only for a precompile `Template`
+///   "data": "0000000100000003",
+///   "max_storage_bytes": 4096,                  // only when the storage has a budget
+///   "dynamic": [2, 5],                          // only when there are dynamic variables
+///   "ctors": ["init", "start"],
+///   "schema": [{"id": 0, "name": "owner", "type": "address"}],
+///   "code_version": 2,                          // `Header Section`
+///   "name": "My Template",                      // `Header Section`
+///   "desc": "A few words",                      // `Header Section`
+///   "fallback": "fallback",                     // `Api Section`
+///   "api": [{"name": "init", ...}],             // `Api Section` (only when it has functions)
+///   "doc": "A multi-sig wallet",                // `Api Section` (only when documented)
+///   "var_docs": [{"name": "owner", ...}],       // `Api Section` (only when documented)
+///   "custom": [{ "kind": 32769, "payload": "C0FFEE" }],
+///   "deploy": {                                 // `Deploy Section`
+///     "tx_id": "...",                           // string (hex)
+///     "layer": 10,
+///     "deployer": "...",                        // string (`Address`)
+///     "template": "...",                        // string (`Template Address`)
+///     "addr_scheme": 0                          // the address derivation scheme
+///   }
+/// }
+/// ```
+pub fn decode_deploy(json: &str) -> Result<Json, JsonError> {
+    let encoded = EncodedData::from_json_str(json)?;
+    let template =
+        template::decode_at(&encoded.data.0[..]).map_err(|error| JsonError::InvalidBinary {
+            path: "data".to_string(),
+            error,
+        })?;
+
+    Ok(sections_to_json(template.sections()))
+}
+
+/// The canonical key order of a decoded `Deploy Template` (see [`decode_deploy`]).
+const DECODED_DEPLOY_KEYS: &[&str] = &[
+    "svm_version",
+    "code_len",
+    "code_hash",
+    "precompile",
+    "data",
+    "max_storage_bytes",
+    "dynamic",
+    "ctors",
+    "schema",
+    "code_version",
+    "name",
+    "desc",
+    "fallback",
+    "api",
+    "doc",
+    "var_docs",
+    "custom",
+    "deploy",
+];
+
+/// Given a binary `Deploy Template` wrapped inside JSON,
+/// returns the documentation of the `Template` held by its `Api Section`.
+///
+/// ```json
+/// {
+///   "data": "0004000100000016..."
+/// }
+/// ```
+///
+/// The documentation pages of a `Template` can be rendered out of the returned JSON
+/// (being of the same format as the docs JSON emitted by the SVM SDK):
+///
+/// ```json
+/// {
+///   "name": "My Template",                                  // only when there is a `Header Section`
+///   "doc": "A multi-sig wallet",
+///   "storage": [{"name": "owner", "doc": "The owner"}],
+///   "api": [{"name": "init", "doc": "Initializes the wallet"}]
+/// }
+/// ```
+///
+/// A `Template` without an `Api Section` has no documentation (i.e empty `doc`, `storage` and `api`).
+pub fn decode_docs(json: &str) -> Result<Json, JsonError> {
+    let encoded = EncodedData::from_json_str(json)?;
+    let template =
+        template::decode_at(&encoded.data.0[..]).map_err(|error| JsonError::InvalidBinary {
+            path: "data".to_string(),
+            error,
+        })?;
+
+    let mut json = json!({});
+
+    if let Some(section) = template.sections().try_get(SectionKind::Header) {
+        json["name"] = json!(section.as_header().name());
+    }
+
+    let (doc, storage, api) = match template.api_section() {
+        Some(section) => {
+            let storage: Vec<Json> = section
+                .var_docs()
+                .iter()
+                .map(|var_doc| json!({"name": var_doc.name, "doc": var_doc.doc}))
+                .collect();
+            let api: Vec<Json> = section
+                .funcs()
+                .iter()
+                .map(|func| json!({"name": func.name, "doc": func.doc}))
+                .collect();
+
+            (section.doc(), storage, api)
+        }
+        None => ("", Vec::new(), Vec::new()),
+    };
+
+    json["doc"] = json!(doc);
+    json["storage"] = Json::Array(storage);
+    json["api"] = Json::Array(api);
+
+    Ok(json)
+}
+
+/// Given a binary `Deploy Template` (i.e its raw `Section`s), returns a JSON array of its `Section`s
+/// (in their encoded order), without requiring the `Section`s to form a valid `Template`.
+///
+/// ```json
+/// {
+///   "data": "..." // string (the raw `Section`s, hex)
+/// }
+/// ```
+///
+/// Each `Section` is given as:
+///
+/// ```json
+/// {
+///   "kind": "code",     // "header" | "code" | "data" | "ctors" | "schema" | "api" | "deploy" | "defaults" | "custom"
+///   "custom_kind": 0,   // (only for "custom") number (`u16`)
+///   "length": 100,      // number (the `Section` byte size)
+///   "contents": { ... } // the `Section` keys as in `decode_deploy` (hex of the raw bytes when they can't be decoded)
+/// }
+/// ```
+///
+/// The `contents` of a `Defaults Section` are an array of `{"id": 0, "value": "..."}`,
+/// and the `contents` of a `Custom Section` are its payload (hex).
+pub fn decode_sections(json: &str) -> Result<Json, JsonError> {
+    const STRUCTURE: &str = "DeployTemplate";
+
+    let encoded = EncodedData::from_json_str(json)?;
+    let bytes = &encoded.data.0[..];
+    let invalid = |error| JsonError::InvalidBinary {
+        path: "data".to_string(),
+        error,
+    };
+
+    let cursor = std::io::Cursor::new(bytes);
+    let mut decoder =
+        SectionsDecoder::new(cursor.clone()).map_err(|err| invalid(err.at(STRUCTURE, &cursor)))?;
+
+    let mut sections = Vec::with_capacity(decoder.section_count());
+
+    while !decoder.is_eof() {
+        let preview = decoder
+            .next_preview()
+            .map_err(|err| invalid(err.at(STRUCTURE, decoder.cursor())))?;
+        let raw = decoder.raw_section().map_err(|err| {
+            let structure = format!("{}.{:?}", STRUCTURE, preview.kind());
+            invalid(err.at(&structure, decoder.cursor()))
+        })?;
+
+        let mut json = json!({
+            "kind": section_kind_name(preview.kind()),
+            "length": preview.byte_size(),
+        });
+
+        if let SectionKind::Custom(kind) = preview.kind() {
+            json["custom_kind"] = json!(kind);
+        }
+
+        json["contents"] = match decode_raw_section(preview.kind(), raw) {
+            Ok(section) => section_contents(&section),
+            Err(..) => json!(hex::encode_upper(raw)),
+        };
+
+        sections.push(json);
+    }
+
+    Ok(Json::Array(sections))
+}
+
+fn section_kind_name(kind: SectionKind) -> &'static str {
+    match kind {
+        SectionKind::Header => "header",
+        SectionKind::Code => "code",
+        SectionKind::Data => "data",
+        SectionKind::Ctors => "ctors",
+        SectionKind::Schema => "schema",
+        SectionKind::Api => "api",
+        SectionKind::Deploy => "deploy",
+        SectionKind::Defaults => "defaults",
+        SectionKind::Custom(..) => "custom",
+    }
+}
+
+fn section_contents(section: &Section) -> Json {
+    match section {
+        Section::Custom(section) => json!(hex::encode_upper(section.payload())),
+        Section::Defaults(defaults) => {
+            let defaults: Vec<Json> = defaults
+                .defaults()
+                .iter()
+                .map(|(id, value)| json!({ "id": id.0, "value": hex::encode_upper(value) }))
+                .collect();
+
+            Json::Array(defaults)
+        }
+        _ => {
+            let mut json = json!({});
+            section_to_json(section, &mut json);
 
-    Ok(template::encode(&template))
+            super::canonical_order(json, DECODED_DEPLOY_KEYS)
+        }
+    }
+}
+
+fn sections_to_json(sections: &Sections) -> Json {
+    let mut json = json!({});
+    let mut custom = Vec::new();
+
+    for section in sections.iter() {
+        match section {
+            Section::Custom(section) => custom.push(json!({
+                "kind": section.kind(),
+                "payload": hex::encode_upper(section.payload()),
+            })),
+            _ => section_to_json(section, &mut json),
+        }
+    }
+
+    if !custom.is_empty() {
+        json["custom"] = Json::Array(custom);
+    }
+
+    super::canonical_order(json, DECODED_DEPLOY_KEYS)
+}
+
+/// Adds the keys of a (non-custom) `Section` to the `json` of a decoded `Deploy Template`.
+fn section_to_json(section: &Section, json: &mut Json) {
+    match section {
+        Section::Code(code) => {
+            json["svm_version"] = json!(code.svm_version());
+            json["code_len"] = json!(code.code().len());
+            json["code_hash"] = json!(hex::encode_upper(Blake3Hasher::hash(code.code())));
+
+            if let Some(id) = code.precompile_id() {
+                json["precompile"] = json!(id);
+            }
+        }
+        Section::Data(data) => {
+            json["data"] = json!(hex::encode_upper(from_data_layout(data)));
+
+            if let Some(max_storage_bytes) = data.max_storage_bytes() {
+                json["max_storage_bytes"] = json!(max_storage_bytes);
+            }
+
+            if let Some(dynamic) = data.dynamic_layout() {
+                let ids: Vec<u32> = dynamic.iter().map(|id| id.0).collect();
+
+                json["dynamic"] = json!(ids);
+            }
+        }
+        Section::Ctors(ctors) => {
+            json["ctors"] = json!(ctors.ctors());
+        }
+        Section::Schema(schema) => {
+            json["schema"] = from_schema_section(schema);
+        }
+        Section::Header(header) => {
+            json["code_version"] = json!(header.code_version());
+            json["name"] = json!(header.name());
+            json["desc"] = json!(header.desc());
+        }
+        Section::Api(api) => {
+            json["fallback"] = json!(api.fallback());
+
+            if !api.funcs().is_empty() {
+                json["api"] = from_api_section(api);
+            }
+
+            if !api.doc().is_empty() {
+                json["doc"] = json!(api.doc());
+            }
+
+            if !api.var_docs().is_empty() {
+                let var_docs: Vec<Json> = api
+                    .var_docs()
+                    .iter()
+                    .map(|var_doc| {
+                        let var_doc = VarDocJson {
+                            name: var_doc.name.clone(),
+                            doc: var_doc.doc.clone(),
+                        };
+
+                        var_doc.to_json()
+                    })
+                    .collect();
+
+                json["var_docs"] = Json::Array(var_docs);
+            }
+        }
+        Section::Deploy(deploy) => {
+            json["deploy"] = json!({
+                "tx_id": hex::encode_upper(deploy.tx_id().as_slice()),
+                "layer": deploy.layer().0,
+                "deployer": AddressWrapper::from(deploy.deployer()),
+                "template": TemplateAddrWrapper::from(deploy.template()),
+                "addr_scheme": deploy.addr_scheme(),
+            });
+        }
+        _ => {}
+    }
+}
+
+fn from_data_layout(data: &DataSection) -> Vec<u8> {
+    data.layouts()
+        .iter()
+        .filter(|layout| layout.kind() == LayoutKind::Fixed)
+        .flat_map(|layout| layout.as_fixed().iter())
+        .map(|var| {
+            if var.bit().is_some() {
+                0
+            } else {
+                var.byte_size()
+            }
+        })
+        .flat_map(|byte_size| byte_size.to_be_bytes().to_vec())
+        .collect()
 }
 
 fn to_data_layout(blob: Vec<u8>) -> Result<Layout, JsonError> {
@@ -65,6 +535,273 @@ fn to_data_layout(blob: Vec<u8>) -> Result<Layout, JsonError> {
     Ok(layout)
 }
 
+fn from_schema_section(schema: &SchemaSection) -> Json {
+    let vars = schema
+        .vars()
+        .iter()
+        .map(|var| {
+            let (ty, length) = type_name(var.ty());
+            let var_json = SchemaVar {
+                id: var.id().0,
+                name: var.name().to_string(),
+                ty,
+                length,
+            };
+
+            var_json.to_json()
+        })
+        .collect();
+
+    Json::Array(vars)
+}
+
+fn to_schema_section(vars: Vec<SchemaVar>) -> Result<SchemaSection, JsonError> {
+    let mut schema = SchemaSection::with_capacity(vars.len());
+
+    for (i, var) in vars.into_iter().enumerate() {
+        let invalid = |field: &str| JsonError::InvalidField {
+            path: format!("schema[{}].{}", i, field),
+        };
+
+        let ty = to_type(&var.ty, var.length).map_err(invalid)?;
+
+        schema.push_var(SymbolicVar::new(Id(var.id), var.name, ty));
+    }
+
+    Ok(schema)
+}
+
+fn from_api_section(api: &ApiSection) -> Json {
+    let funcs = api
+        .funcs()
+        .iter()
+        .map(|func| {
+            let func_json = ApiFuncJson {
+                name: func.name.clone(),
+                wasm_name: Some(func.wasm_name.clone()),
+                is_ctor: func.is_ctor,
+                is_fundable: func.is_fundable,
+                params: func
+                    .params
+                    .iter()
+                    .map(|param| {
+                        let (ty, length) = api_type_name(&param.ty);
+
+                        ApiParamJson {
+                            name: param.name.clone(),
+                            ty,
+                            length,
+                        }
+                    })
+                    .collect(),
+                returns: func
+                    .returns
+                    .iter()
+                    .map(|ty| {
+                        let (ty, length) = api_type_name(ty);
+
+                        ApiTypeJson { ty, length }
+                    })
+                    .collect(),
+                doc: func.doc.clone(),
+            };
+
+            func_json.to_json()
+        })
+        .collect();
+
+    Json::Array(funcs)
+}
+
+/// Builds an [`ApiSection`] out of the signatures `funcs` given under the `field` of the JSON.
+pub(super) fn to_api_section(
+    field: &str,
+    fallback: Option<String>,
+    funcs: Vec<ApiFuncJson>,
+) -> Result<ApiSection, JsonError> {
+    let mut api = ApiSection::new();
+
+    if let Some(fallback) = fallback {
+        api = api.with_fallback(fallback);
+    }
+
+    for (i, func) in funcs.into_iter().enumerate() {
+        let mut params = Vec::with_capacity(func.params.len());
+        let mut returns = Vec::with_capacity(func.returns.len());
+
+        for (j, param) in func.params.into_iter().enumerate() {
+            let ty = to_api_type(&param.ty, param.length).map_err(|invalid| {
+                JsonError::InvalidField {
+                    path: format!("{}[{}].params[{}].{}", field, i, j, invalid),
+                }
+            })?;
+
+            params.push(ApiParam {
+                name: param.name,
+                ty,
+            });
+        }
+
+        for (j, ty) in func.returns.into_iter().enumerate() {
+            let ty = to_api_type(&ty.ty, ty.length).map_err(|invalid| JsonError::InvalidField {
+                path: format!("{}[{}].returns[{}].{}", field, i, j, invalid),
+            })?;
+
+            returns.push(ty);
+        }
+
+        let name = func.name;
+        let wasm_name = func.wasm_name.unwrap_or_else(|| name.clone());
+
+        api = api.with_func(ApiFunc {
+            name,
+            wasm_name,
+            is_ctor: func.is_ctor,
+            is_fundable: func.is_fundable,
+            params,
+            returns,
+            doc: func.doc,
+        });
+    }
+
+    Ok(api)
+}
+
+/// Parses a type (along with its `length` for an array).
+///
+/// On failure returns the name of the invalid field (`"type"` or `"length"`).
+fn to_type(ty: &str, length: Option<usize>) -> Result<Type, &'static str> {
+    match (
+        ty.strip_prefix('[').and_then(|ty| ty.strip_suffix(']')),
+        length,
+    ) {
+        (None, None) => Ok(Type::Primitive(to_primitive(ty).ok_or("type")?)),
+        (Some(ty), Some(length)) => Ok(Type::Array {
+            primitive: to_primitive(ty).ok_or("type")?,
+            length,
+        }),
+        (None, Some(..)) | (Some(..), None) => Err("length"),
+    }
+}
+
+/// Same as [`to_type`], but a capitalized name stands for a struct.
+fn to_api_type(ty: &str, length: Option<usize>) -> Result<ApiType, &'static str> {
+    if length.is_none() && ty.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return Ok(ApiType::Struct(ty.to_string()));
+    }
+
+    match to_type(ty, length)? {
+        Type::Primitive(primitive) => Ok(ApiType::Primitive(primitive)),
+        Type::Array { primitive, length } => Ok(ApiType::Array { primitive, length }),
+    }
+}
+
+fn type_name(ty: &Type) -> (String, Option<usize>) {
+    match ty {
+        Type::Primitive(primitive) => (primitive_name(*primitive).to_string(), None),
+        Type::Array { primitive, length } => {
+            (format!("[{}]", primitive_name(*primitive)), Some(*length))
+        }
+    }
+}
+
+fn api_type_name(ty: &ApiType) -> (String, Option<usize>) {
+    match ty {
+        ApiType::Primitive(primitive) => type_name(&Type::Primitive(*primitive)),
+        ApiType::Array { primitive, length } => type_name(&Type::Array {
+            primitive: *primitive,
+            length: *length,
+        }),
+        ApiType::Struct(name) => (name.clone(), None),
+    }
+}
+
+/// The primitives, along with their names within the `schema`.
+const PRIMITIVES: &[(Primitive, &str)] = &[
+    (Primitive::Bool, "bool"),
+    (Primitive::I8, "i8"),
+    (Primitive::U8, "u8"),
+    (Primitive::I16, "i16"),
+    (Primitive::U16, "u16"),
+    (Primitive::I32, "i32"),
+    (Primitive::U32, "u32"),
+    (Primitive::I64, "i64"),
+    (Primitive::U64, "u64"),
+    (Primitive::Amount, "amount"),
+    (Primitive::Address, "address"),
+];
+
+fn primitive_name(primitive: Primitive) -> &'static str {
+    PRIMITIVES
+        .iter()
+        .find(|(p, _)| *p == primitive)
+        .map(|(_, name)| *name)
+        .unwrap()
+}
+
+fn to_primitive(name: &str) -> Option<Primitive> {
+    PRIMITIVES
+        .iter()
+        .find(|(_, n)| *n == name)
+        .map(|(primitive, _)| *primitive)
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct SchemaVar {
+    id: u32,
+    name: String,
+    #[serde(rename = "type")]
+    ty: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    length: Option<usize>,
+}
+
+impl JsonSerdeUtils for SchemaVar {}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(super) struct ApiFuncJson {
+    name: String,
+    #[serde(default)]
+    wasm_name: Option<String>,
+    #[serde(default)]
+    is_ctor: bool,
+    #[serde(default)]
+    is_fundable: bool,
+    #[serde(default)]
+    params: Vec<ApiParamJson>,
+    #[serde(default)]
+    returns: Vec<ApiTypeJson>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    doc: String,
+}
+
+impl JsonSerdeUtils for ApiFuncJson {}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct VarDocJson {
+    name: String,
+    doc: String,
+}
+
+impl JsonSerdeUtils for VarDocJson {}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct ApiParamJson {
+    name: String,
+    #[serde(rename = "type")]
+    ty: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    length: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct ApiTypeJson {
+    #[serde(rename = "type")]
+    ty: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    length: Option<usize>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 struct DecodedDeploy {
     svm_version: u32,
@@ -72,8 +809,24 @@ struct DecodedDeploy {
     name: String,
     desc: String,
     code: HexBlob<Vec<u8>>,
+    #[serde(default)]
+    precompile: Option<u32>,
     data: HexBlob<Vec<u8>>,
     ctors: Vec<String>,
+    #[serde(default)]
+    max_storage_bytes: Option<u32>,
+    #[serde(default)]
+    dynamic: Option<Vec<u32>>,
+    #[serde(default)]
+    schema: Option<Vec<SchemaVar>>,
+    #[serde(default)]
+    fallback: Option<String>,
+    #[serde(default)]
+    api: Option<Vec<ApiFuncJson>>,
+    #[serde(default)]
+    doc: Option<String>,
+    #[serde(default)]
+    var_docs: Option<Vec<VarDocJson>>,
 }
 
 impl JsonSerdeUtils for DecodedDeploy {}
@@ -84,8 +837,8 @@ mod tests {
 
     use std::io::Cursor;
 
-    use serde_json::json;
-    use svm_layout::FixedLayout;
+    use svm_layout::{FixedLayout, RawVar};
+    use svm_types::{Address, CustomSection, DeploySection, Layer, TemplateAddr, TransactionId};
 
     #[test]
     fn json_deploy_template_missing_svm_version() {
@@ -224,7 +977,7 @@ mod tests {
         })
         .to_string();
 
-        let bytes = deploy_template(&json).unwrap();
+        let bytes = deploy_template_raw(&json).unwrap();
         let cursor = Cursor::new(&bytes[..]);
         let actual = template::decode(cursor, None).unwrap();
 
@@ -243,4 +996,480 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn json_deploy_template_echo() {
+        let json = json!({
+            "svm_version": 1,
+            "code_version": 2,
+            "name": "My Template",
+            "desc": "A few words",
+            "code": "C0DE",
+            "data": "0000000100000003",
+            "ctors": ["init", "start"],
+            "echo": {"request": 7}
+        })
+        .to_string();
+
+        let encoded_json = deploy_template(&json).unwrap();
+
+        assert_eq!(encoded_json["echo"], json!({"request": 7}));
+        assert_eq!(
+            encoded_json["schema_version"],
+            json!(super::super::SCHEMA_VERSION)
+        );
+        assert!(decode_deploy(&encoded_json.to_string()).is_ok());
+    }
+
+    #[test]
+    fn json_deploy_template_packed_bools() {
+        let json = json!({
+            "svm_version": 1,
+            "code_version": 2,
+            "name": "My Template",
+            "desc": "A few words",
+            "code": "C0DE",
+            "data": "00000004000000000000000000000002",
+            "ctors": ["init"]
+        })
+        .to_string();
+
+        let bytes = deploy_template_raw(&json).unwrap();
+        let cursor = Cursor::new(&bytes[..]);
+        let template = template::decode(cursor, None).unwrap();
+
+        let data = template.data_section();
+        let fixed = data.layouts()[0].as_fixed();
+
+        assert_eq!(fixed.get(Id(1)), &RawVar::new_bit(Id(1), 4, 0));
+        assert_eq!(fixed.get(Id(2)), &RawVar::new_bit(Id(2), 4, 1));
+        assert_eq!(fixed.get(Id(3)), &RawVar::new(Id(3), 5, 2));
+        assert_eq!(data.storage_bytes(), 7);
+
+        let json = json!({ "data": hex::encode_upper(&bytes) }).to_string();
+        let decoded = decode_deploy(&json).unwrap();
+
+        assert_eq!(decoded["data"], json!("00000004000000000000000000000002"));
+    }
+
+    #[test]
+    fn json_deploy_template_precompile() {
+        let json = json!({
+            "svm_version": 1,
+            "code_version": 2,
+            "name": "Transfer",
+            "desc": "",
+            "code": "",
+            "precompile": 7,
+            "data": "00000008",
+            "ctors": ["init"]
+        });
+
+        let bytes = deploy_template_raw(&json.to_string()).unwrap();
+        let cursor = Cursor::new(&bytes[..]);
+        let template = template::decode(cursor, None).unwrap();
+
+        assert_eq!(template.code_section(), &CodeSection::new_precompile(7, 1));
+
+        let decoded = decode_deploy(&json!({ "data": HexBlob(&bytes) }).to_string()).unwrap();
+        assert_eq!(decoded["precompile"], 7);
+
+        let mut json = json;
+        json["code"] = json!("C0DE");
+
+        let err = deploy_template(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "code".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn json_deploy_template_with_storage_budget() {
+        let json = json!({
+            "svm_version": 1,
+            "code_version": 2,
+            "name": "My Template",
+            "desc": "A few words",
+            "code": "C0DE",
+            "data": "0000000100000003",
+            "ctors": ["init"],
+            "max_storage_bytes": 4096
+        })
+        .to_string();
+
+        let bytes = deploy_template_raw(&json).unwrap();
+        let json = json!({ "data": hex::encode_upper(&bytes) }).to_string();
+
+        let decoded = decode_deploy(&json).unwrap();
+        assert_eq!(decoded["data"], "0000000100000003");
+        assert_eq!(decoded["max_storage_bytes"], 4096);
+    }
+
+    #[test]
+    fn json_deploy_template_with_dynamic_vars() {
+        let json = json!({
+            "svm_version": 1,
+            "code_version": 2,
+            "name": "My Template",
+            "desc": "A few words",
+            "code": "C0DE",
+            "data": "000000010000000400000004",
+            "ctors": ["init"],
+            "dynamic": [2, 1]
+        })
+        .to_string();
+
+        let bytes = deploy_template_raw(&json).unwrap();
+        let template = template::decode_at(&bytes).unwrap();
+
+        let dynamic = template.dynamic_layout().unwrap();
+        assert_eq!(dynamic.iter().collect::<Vec<_>>(), vec![Id(1), Id(2)]);
+        assert_eq!(template.fixed_layout().len(), 3);
+
+        let json = json!({ "data": hex::encode_upper(&bytes) }).to_string();
+
+        let decoded = decode_deploy(&json).unwrap();
+        assert_eq!(decoded["data"], "000000010000000400000004");
+        assert_eq!(decoded["dynamic"], json!([1, 2]));
+    }
+
+    #[test]
+    fn json_deploy_template_with_schema() {
+        let schema = json!([
+            {"id": 0, "name": "owner", "type": "address"},
+            {"id": 1, "name": "prices", "type": "[u64]", "length": 4}
+        ]);
+        let mut json = json!({
+            "svm_version": 1,
+            "code_version": 2,
+            "name": "My Template",
+            "desc": "A few words",
+            "code": "C0DE",
+            "data": "0000001400000020",
+            "ctors": ["init"],
+            "schema": schema
+        });
+
+        let bytes = deploy_template_raw(&json.to_string()).unwrap();
+        let template = template::decode(Cursor::new(&bytes[..]), None).unwrap();
+
+        let vars = template.schema_section().vars();
+        assert_eq!(vars.len(), 2);
+        assert_eq!(vars[0].name(), "owner");
+        assert_eq!(
+            vars[1].ty(),
+            &Type::Array {
+                primitive: Primitive::U64,
+                length: 4
+            }
+        );
+
+        let decoded = decode_deploy(&json!({ "data": HexBlob(&bytes) }).to_string()).unwrap();
+        assert_eq!(decoded["schema"], schema);
+
+        json["schema"] = json!([{"id": 0, "name": "owner", "type": "u128"}]);
+        let err = deploy_template(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "schema[0].type".to_string()
+            }
+        );
+
+        json["schema"] = json!([{"id": 0, "name": "prices", "type": "[u64]"}]);
+        let err = deploy_template(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "schema[0].length".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn json_deploy_template_with_api() {
+        let api = json!([
+            {
+                "name": "init",
+                "wasm_name": "init",
+                "is_ctor": true,
+                "is_fundable": false,
+                "params": [],
+                "returns": []
+            },
+            {
+                "name": "transfer",
+                "wasm_name": "transfer",
+                "is_ctor": false,
+                "is_fundable": true,
+                "params": [
+                    {"name": "to", "type": "address"},
+                    {"name": "t", "type": "Transfer"}
+                ],
+                "returns": [{"type": "[u64]", "length": 2}]
+            }
+        ]);
+        let mut json = json!({
+            "svm_version": 1,
+            "code_version": 2,
+            "name": "My Template",
+            "desc": "A few words",
+            "code": "C0DE",
+            "data": "0000001400000020",
+            "ctors": ["init"],
+            "fallback": "forward",
+            "api": api
+        });
+
+        let bytes = deploy_template_raw(&json.to_string()).unwrap();
+        let template = template::decode(Cursor::new(&bytes[..]), None).unwrap();
+
+        let section = template.api_section().unwrap();
+        assert_eq!(section.fallback(), Some("forward"));
+        assert_eq!(
+            section.func("transfer").unwrap().params[1].ty,
+            ApiType::Struct("Transfer".to_string())
+        );
+
+        let decoded = decode_deploy(&json!({ "data": HexBlob(&bytes) }).to_string()).unwrap();
+        assert_eq!(decoded["fallback"], json!("forward"));
+        assert_eq!(decoded["api"], api);
+
+        json["api"] = json!([{"name": "transfer", "params": [{"name": "to", "type": "u128"}]}]);
+        let err = deploy_template(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "api[0].params[0].type".to_string()
+            }
+        );
+
+        json["api"] = json!([{"name": "transfer", "returns": [{"type": "u64", "length": 2}]}]);
+        let err = deploy_template(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "api[0].returns[0].length".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn json_deploy_template_with_docs() {
+        let json = json!({
+            "svm_version": 1,
+            "code_version": 2,
+            "name": "My Template",
+            "desc": "A few words",
+            "code": "C0DE",
+            "data": "00000014",
+            "ctors": ["init"],
+            "api": [{"name": "init", "is_ctor": true, "doc": "Initializes the wallet"}],
+            "doc": "A multi-sig wallet",
+            "var_docs": [{"name": "owner", "doc": "The owner"}]
+        });
+
+        let bytes = deploy_template_raw(&json.to_string()).unwrap();
+        let template = template::decode(Cursor::new(&bytes[..]), None).unwrap();
+
+        let section = template.api_section().unwrap();
+        assert_eq!(section.doc(), "A multi-sig wallet");
+        assert_eq!(section.func("init").unwrap().doc, "Initializes the wallet");
+
+        let data = json!({ "data": HexBlob(&bytes) }).to_string();
+
+        let decoded = decode_deploy(&data).unwrap();
+        assert_eq!(decoded["doc"], json!("A multi-sig wallet"));
+        assert_eq!(
+            decoded["var_docs"],
+            json!([{"name": "owner", "doc": "The owner"}])
+        );
+        assert_eq!(decoded["api"][0]["doc"], json!("Initializes the wallet"));
+
+        let docs = decode_docs(&data).unwrap();
+        assert_eq!(
+            docs,
+            json!({
+                "name": "My Template",
+                "doc": "A multi-sig wallet",
+                "storage": [{"name": "owner", "doc": "The owner"}],
+                "api": [{"name": "init", "doc": "Initializes the wallet"}]
+            })
+        );
+    }
+
+    #[test]
+    fn json_decode_docs_without_api_section() {
+        let template = TemplateBuilder::default()
+            .with_code(CodeSection::new_fixed(vec![0xC0, 0xDE], 1))
+            .with_data(DataSection::with_layout(Layout::Fixed(FixedLayout::from(
+                vec![1],
+            ))))
+            .with_ctors(CtorsSection::new(vec!["init".into()]))
+            .build();
+        let bytes = template::encode(&template);
+
+        let docs = decode_docs(&json!({ "data": HexBlob(&bytes) }).to_string()).unwrap();
+        assert_eq!(docs, json!({"doc": "", "storage": [], "api": []}));
+    }
+
+    #[test]
+    fn json_decode_deploy_with_custom_section() {
+        let code = CodeSection::new_fixed(vec![0xC0, 0xDE], 1);
+        let data = DataSection::with_layout(Layout::Fixed(FixedLayout::from(vec![1, 3])));
+        let ctors = CtorsSection::new(vec!["init".into()]);
+        let custom = CustomSection::new(0x80_01, vec![0xC0, 0xFF, 0xEE]);
+
+        let template = TemplateBuilder::default()
+            .with_code(code)
+            .with_data(data)
+            .with_ctors(ctors)
+            .with_custom(custom)
+            .build();
+
+        let bytes = template::encode(&template);
+        let json = json!({ "data": hex::encode_upper(&bytes) }).to_string();
+
+        let decoded = decode_deploy(&json).unwrap();
+        assert_eq!(
+            decoded,
+            json!({
+                "svm_version": 1,
+                "code_len": 2,
+                "code_hash": hex::encode_upper(Blake3Hasher::hash(&[0xC0, 0xDE])),
+                "data": "0000000100000003",
+                "ctors": ["init"],
+                "custom": [{ "kind": 0x80_01, "payload": "C0FFEE" }]
+            })
+        );
+    }
+
+    #[test]
+    fn json_decode_deploy_with_deploy_section() {
+        let code = CodeSection::new_fixed(vec![0xC0, 0xDE], 1);
+        let data = DataSection::with_layout(Layout::Fixed(FixedLayout::from(vec![1, 3])));
+        let ctors = CtorsSection::new(vec!["init".into()]);
+        let deploy = DeploySection::new(
+            TransactionId::repeat(0x10),
+            Layer(10),
+            Address::repeat(0x20),
+            TemplateAddr::repeat(0x30),
+        )
+        .with_addr_scheme(1);
+
+        let mut template = TemplateBuilder::default()
+            .with_code(code)
+            .with_data(data)
+            .with_ctors(ctors)
+            .build();
+        template.set_deploy_section(deploy);
+
+        let bytes = template::encode(&template);
+        let json = json!({ "data": hex::encode_upper(&bytes) }).to_string();
+
+        let decoded = decode_deploy(&json).unwrap();
+        assert_eq!(
+            decoded["deploy"],
+            json!({
+                "tx_id": "10".repeat(TransactionId::len()),
+                "layer": 10,
+                "deployer": "20".repeat(Address::len()),
+                "template": "30".repeat(TemplateAddr::len()),
+                "addr_scheme": 1
+            })
+        );
+    }
+
+    #[test]
+    fn json_decode_sections() {
+        use crate::section::{preview, SectionPreview};
+
+        let code = CodeSection::new_fixed(vec![0xC0, 0xDE], 1);
+        let data = DataSection::with_layout(Layout::Fixed(FixedLayout::from(vec![1, 3])));
+        let ctors = CtorsSection::new(vec!["init".into()]);
+        let custom = CustomSection::new(0x80_01, vec![0xC0, 0xFF, 0xEE]);
+
+        let template = TemplateBuilder::default()
+            .with_code(code)
+            .with_data(data)
+            .with_ctors(ctors)
+            .with_custom(custom)
+            .build();
+
+        let mut bytes = template::encode(&template);
+
+        // Appending a `Schema Section` which can't be decoded
+        let count = u16::from_be_bytes([bytes[0], bytes[1]]) + 1;
+        bytes[..2].copy_from_slice(&count.to_be_bytes());
+        preview::encode(&SectionPreview::new(SectionKind::Schema, 3), &mut bytes);
+        bytes.extend_from_slice(&[0xFF, 0xFF, 0xFF]);
+
+        let json = json!({ "data": hex::encode_upper(&bytes) }).to_string();
+        let decoded = decode_sections(&json).unwrap();
+        let sections = decoded.as_array().unwrap();
+
+        let section = |kind: &str| sections.iter().find(|s| s["kind"] == kind).unwrap();
+
+        assert_eq!(sections.len(), count as usize);
+        assert_eq!(section("ctors")["contents"], json!({ "ctors": ["init"] }));
+        assert_eq!(section("code")["contents"]["code_len"], 2);
+        assert_eq!(section("data")["contents"]["data"], "0000000100000003");
+        assert_eq!(
+            section("custom"),
+            &json!({ "kind": "custom", "custom_kind": 0x80_01, "length": 3, "contents": "C0FFEE" })
+        );
+        assert_eq!(
+            section("schema"),
+            &json!({ "kind": "schema", "length": 3, "contents": "FFFFFF" })
+        );
+
+        // A truncated `Section` can't be skipped
+        let json = json!({ "data": hex::encode_upper(&bytes[..bytes.len() - 1]) }).to_string();
+        let err = decode_sections(&json).unwrap_err();
+        assert!(matches!(
+            err,
+            JsonError::InvalidBinary { path, error } if path == "data" && error.path == "DeployTemplate.Schema.section"
+        ));
+    }
+
+    #[test]
+    fn json_decode_deploy_invalid_data() {
+        let json = json!({ "data": "0001FFFF" }).to_string();
+
+        let err = decode_deploy(&json).unwrap_err();
+        assert!(matches!(
+            err,
+            JsonError::InvalidBinary { path, error }
+                if path == "data" && error.path == "DeployTemplate.section_byte_size" && error.offset == 4
+        ));
+    }
+
+    #[test]
+    fn json_decode_deploy_golden() {
+        // The `Section`s are encoded out of their canonical order
+        let mut sections = Sections::default();
+        sections.insert(Section::Header(HeaderSection::new(
+            2,
+            "My Template".into(),
+            "A few words".into(),
+        )));
+        sections.insert(Section::Ctors(CtorsSection::new(vec!["init".into()])));
+        sections.insert(Section::Data(DataSection::with_layout(Layout::Fixed(
+            FixedLayout::from(vec![1, 3]),
+        ))));
+        sections.insert(Section::Code(CodeSection::new_fixed(vec![0xC0, 0xDE], 1)));
+
+        let mut encoder = crate::SectionsEncoder::with_capacity(sections.len());
+        encoder.encode(&sections);
+        let bytes = encoder.finish();
+
+        let json = json!({ "data": hex::encode_upper(&bytes) }).to_string();
+        let decoded = decode_deploy(&json).unwrap();
+
+        let golden = r#"{"svm_version":1,"code_len":2,"code_hash":"D685A259447BF416466216779D0A842D41E17710EAEF5BBB115CE48DF59F425B","data":"0000000100000003","ctors":["init"],"code_version":2,"name":"My Template","desc":"A few words"}"#;
+        assert_eq!(decoded.to_string(), golden);
+    }
 }
@@ -0,0 +1,223 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value as Json;
+
+use std::io::Cursor;
+
+use svm_types::{Envelope, Gas};
+
+use super::serde_types::*;
+use crate::api::json::{JsonError, JsonSerdeUtils};
+
+/// Transforms a user-friendly `Envelope` into its binary form:
+///
+/// ```json
+/// {
+///   "principal": "A2FB...", // string
+///   "amount": 10,           // number
+///   "gas_limit": 1000,      // number (`0` stands for no limit)
+///   "gas_fee": 1            // number
+/// }
+/// ```
+///
+/// An optional `echo` field is returned verbatim within the result.
+///
+/// Result:
+///
+/// ```json
+/// {
+///   "data": "A2FB...",
+///   "schema_version": 0,
+///   "echo": ...
+/// }
+/// ```
+pub fn encode_envelope(json: &str) -> Result<Json, JsonError> {
+    let encoded_bytes = encode_envelope_raw(json)?;
+
+    Ok(super::encode_response(encoded_bytes, super::echo_of(json)))
+}
+
+/// Much like [`encode_envelope`], but instead of returning a JSON wrapper it
+/// returns the raw bytes.
+pub fn encode_envelope_raw(json: &str) -> Result<Vec<u8>, JsonError> {
+    let decoded = DecodedEnvelope::from_json_str(json)?;
+    let envelope = Envelope::from(decoded);
+
+    let mut buf = Vec::with_capacity(crate::envelope::byte_size());
+    crate::envelope::encode(&envelope, &mut buf);
+
+    Ok(buf)
+}
+
+/// Given a binary [`Envelope`] wrapped inside JSON,
+/// Decodes it and returns a user-friendly JSON.
+///
+/// ```json
+/// {
+///   "data": "A2FB..."
+/// }
+/// ```
+///
+/// The result is of the form:
+///
+/// ```json
+/// {
+///   "principal": "A2FB...",
+///   "amount": 10,
+///   "gas_limit": 1000,
+///   "gas_fee": 1
+/// }
+/// ```
+pub fn decode_envelope(json: &str) -> Result<Json, JsonError> {
+    let encoded = EncodedData::from_json_str(json)?;
+    let bytes = &encoded.data.0[..];
+
+    let invalid_data = || JsonError::InvalidField {
+        path: "data".to_string(),
+    };
+
+    if bytes.len() != crate::envelope::byte_size() {
+        return Err(invalid_data());
+    }
+
+    let mut cursor = Cursor::new(bytes);
+    let envelope = crate::envelope::decode(&mut cursor).map_err(|_| invalid_data())?;
+
+    Ok(DecodedEnvelope::from(envelope).to_json())
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub(super) struct DecodedEnvelope {
+    principal: AddressWrapper,
+    amount: u64,
+    gas_limit: u64,
+    gas_fee: u64,
+}
+
+impl JsonSerdeUtils for DecodedEnvelope {}
+
+impl From<DecodedEnvelope> for Envelope {
+    fn from(decoded: DecodedEnvelope) -> Self {
+        let gas_limit = if decoded.gas_limit > 0 {
+            Gas::with(decoded.gas_limit)
+        } else {
+            Gas::new()
+        };
+
+        Envelope::new(
+            decoded.principal.into(),
+            decoded.amount,
+            gas_limit,
+            decoded.gas_fee,
+        )
+    }
+}
+
+impl From<Envelope> for DecodedEnvelope {
+    fn from(envelope: Envelope) -> Self {
+        DecodedEnvelope {
+            principal: AddressWrapper::from(envelope.principal()),
+            amount: envelope.amount(),
+            gas_limit: envelope.gas_limit().unwrap_or(0),
+            gas_fee: envelope.gas_fee(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn json_envelope_missing_principal() {
+        let json = json!({ "amount": 10, "gas_limit": 1000, "gas_fee": 1 }).to_string();
+
+        let err = encode_envelope(&json).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::MissingField {
+                field_name: "principal".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn json_envelope_invalid_principal() {
+        let json = json!({
+            "principal": "1020",
+            "amount": 10,
+            "gas_limit": 1000,
+            "gas_fee": 1
+        })
+        .to_string();
+
+        let err = encode_envelope(&json).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "principal".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn json_envelope_valid() {
+        let principal = "10203040506070809000A0B0C0D0E0F0ABCDEFFF";
+
+        let json = json!({
+            "principal": principal,
+            "amount": 10,
+            "gas_limit": 1000,
+            "gas_fee": 1
+        })
+        .to_string();
+
+        let res = encode_envelope(&json).unwrap();
+        assert_eq!(
+            res["data"].as_str().unwrap().len(),
+            crate::envelope::byte_size() * 2
+        );
+
+        let json = decode_envelope(&res.to_string()).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "principal": principal,
+                "amount": 10,
+                "gas_limit": 1000,
+                "gas_fee": 1
+            })
+        );
+    }
+
+    #[test]
+    fn json_envelope_no_gas_limit() {
+        let json = json!({
+            "principal": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "amount": 0,
+            "gas_limit": 0,
+            "gas_fee": 0
+        })
+        .to_string();
+
+        let bytes = encode_envelope_raw(&json).unwrap();
+        let mut cursor = Cursor::new(&bytes[..]);
+        let envelope = crate::envelope::decode(&mut cursor).unwrap();
+
+        assert!(envelope.gas_limit().is_none());
+    }
+
+    #[test]
+    fn json_decode_envelope_invalid_length() {
+        let json = json!({ "data": "102030" }).to_string();
+
+        let err = decode_envelope(&json).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "data".to_string(),
+            }
+        );
+    }
+}
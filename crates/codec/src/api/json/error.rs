@@ -1,5 +1,8 @@
 use thiserror::Error;
 
+use crate::limits::MessageTooLarge;
+use crate::{EncodeError, ParseErrorAt};
+
 #[doc(hidden)]
 #[derive(Debug, PartialEq, Eq, Error)]
 pub enum JsonError {
@@ -20,6 +23,33 @@ pub enum JsonError {
     /// The value of a specific field is invalid.
     #[error("The value of a specific field is invalid (`{path}`).")]
     InvalidField { path: String },
+    /// The binary value of a specific field can't be decoded.
+    #[error("The binary value of a specific field is invalid (`{path}`): {error}.")]
+    InvalidBinary { path: String, error: ParseErrorAt },
+    /// A named argument of a function parameter is missing.
+    #[error("The argument of function parameter `{name}` is missing.")]
+    MissingArg { name: String },
+    /// A named argument doesn't match any function parameter.
+    #[error("The argument `{name}` doesn't match any function parameter.")]
+    UnknownArg { name: String },
+    /// The encoded message exceeds the maximum byte size of its family (see [`crate::limits`]).
+    #[error("{0}.")]
+    MessageTooLarge(MessageTooLarge),
+    /// The message can't be encoded (e.g a name exceeding 255 bytes).
+    #[error("{0}.")]
+    Encode(EncodeError),
+}
+
+impl From<EncodeError> for JsonError {
+    fn from(err: EncodeError) -> Self {
+        Self::Encode(err)
+    }
+}
+
+impl From<MessageTooLarge> for JsonError {
+    fn from(err: MessageTooLarge) -> Self {
+        Self::MessageTooLarge(err)
+    }
 }
 
 impl From<std::str::Utf8Error> for JsonError {
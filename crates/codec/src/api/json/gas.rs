@@ -0,0 +1,417 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as Json};
+
+use super::serde_types::HexBlob;
+use super::JsonSerdeUtils;
+use crate::api::json::JsonError;
+use crate::{gas_estimate, intrinsic_gas};
+
+/// Given a binary transaction wrapped inside a JSON, returns its intrinsic gas.
+///
+/// ```json
+/// {
+///   "tx_type": "call",    // one of `deploy`, `spawn` or `call`
+///   "data": "FFC103..."   // the binary transaction
+/// }
+/// ```
+///
+/// The response is of the form:
+///
+/// ```json
+/// {
+///   "gas": 1000
+/// }
+/// ```
+pub fn intrinsic_gas(json: &str) -> Result<Json, JsonError> {
+    let tx = EncodedTx::from_json_str(json)?;
+    let bytes = &tx.data.0;
+
+    let gas = match tx.tx_type {
+        TxType::Deploy => intrinsic_gas::deploy(bytes),
+        TxType::Spawn => intrinsic_gas::spawn(bytes),
+        TxType::Call => intrinsic_gas::call(bytes),
+    };
+
+    Ok(json!({ "gas": gas }))
+}
+
+/// Given a draft transaction wrapped inside a JSON, estimates its fee.
+///
+/// ```json
+/// {
+///   "tx_type": "call",    // one of `deploy`, `spawn` or `call`
+///   "data": "FFC103...",  // the binary transaction
+///   "tx": { ... },        // or its JSON (as given to the encode endpoint of `tx_type`)
+///   "gas_price": 2,       // the price of a single unit of gas
+///   "exec_gas": 5000,     // (optional) the execution gas the transaction is expected to use
+///   "decimals": 0         // (optional) the number of decimals of the `fee_formatted`
+/// }
+/// ```
+///
+/// The suggested `gas_limit` is the intrinsic gas plus the execution gas. When `exec_gas` is missing,
+/// the execution gas defaults to the allowance of `tx_type` (see [`intrinsic_gas`](crate::intrinsic_gas)).
+///
+/// The response is of the form:
+///
+/// ```json
+/// {
+///   "intrinsic_gas": 1000,
+///   "gas_limit": 1001000,
+///   "gas_price": 2,
+///   "fee": 2002000,
+///   "fee_formatted": "2,002,000"
+/// }
+/// ```
+pub fn estimate_fee(json: &str) -> Result<Json, JsonError> {
+    let draft = DraftTx::from_json_str(json)?;
+    let bytes = draft.encode()?;
+
+    let (intrinsic_gas, exec_gas) = match draft.tx_type {
+        TxType::Deploy => (
+            intrinsic_gas::deploy(&bytes),
+            intrinsic_gas::DEPLOY_EXEC_GAS,
+        ),
+        TxType::Spawn => (intrinsic_gas::spawn(&bytes), intrinsic_gas::SPAWN_EXEC_GAS),
+        TxType::Call => (intrinsic_gas::call(&bytes), intrinsic_gas::CALL_EXEC_GAS),
+    };
+    let exec_gas = draft.exec_gas.unwrap_or(exec_gas);
+
+    let gas_limit = intrinsic_gas
+        .checked_add(exec_gas)
+        .ok_or_else(|| JsonError::InvalidField {
+            path: "exec_gas".to_string(),
+        })?;
+    let fee = gas_limit
+        .checked_mul(draft.gas_price)
+        .ok_or_else(|| JsonError::InvalidField {
+            path: "gas_price".to_string(),
+        })?;
+
+    Ok(json!({
+        "intrinsic_gas": intrinsic_gas,
+        "gas_limit": gas_limit,
+        "gas_price": draft.gas_price,
+        "fee": fee,
+        "fee_formatted": format_units(fee, draft.decimals),
+    }))
+}
+
+/// Given a binary gas estimate (as returned by the `Runtime`) wrapped inside a JSON,
+/// decodes it into a user-friendly JSON.
+///
+/// ```json
+/// {
+///   "data": "00000000000003E8..."  // the binary gas estimate
+/// }
+/// ```
+///
+/// The response is of the form (the `func_price` is `null` when the code can't be priced upfront):
+///
+/// ```json
+/// {
+///   "payload_price": 1000,
+///   "template_load_price": 200,
+///   "func_price": 5000,
+///   "gas_limit": 6200
+/// }
+/// ```
+pub fn decode_gas_estimate(json: &str) -> Result<Json, JsonError> {
+    let encoded = EncodedGasEstimate::from_json_str(json)?;
+    let bytes = encoded.data.0.as_slice();
+
+    let mut cursor = std::io::Cursor::new(bytes);
+    let estimate = match gas_estimate::decode(&mut cursor) {
+        Ok(estimate) if cursor.position() as usize == bytes.len() => estimate,
+        _ => {
+            return Err(JsonError::InvalidField {
+                path: "data".to_string(),
+            })
+        }
+    };
+
+    Ok(json!({
+        "payload_price": estimate.payload_price,
+        "template_load_price": estimate.template_load_price,
+        "func_price": estimate.func_price,
+        "gas_limit": estimate.gas_limit,
+    }))
+}
+
+/// Formats `amount` as a decimal number having `decimals` fractional digits
+/// (omitting the trailing zeros), grouping the integral digits by thousands.
+fn format_units(amount: u64, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    let digits = format!("{:0>width$}", amount, width = decimals + 1);
+    let (int, frac) = digits.split_at(digits.len() - decimals);
+
+    let mut formatted = String::with_capacity(digits.len() + int.len() / 3 + 1);
+    for (i, c) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            formatted.push(',');
+        }
+        formatted.push(c);
+    }
+
+    let frac = frac.trim_end_matches('0');
+    if !frac.is_empty() {
+        formatted.push('.');
+        formatted.push_str(frac);
+    }
+
+    formatted
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum TxType {
+    Deploy,
+    Spawn,
+    Call,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EncodedTx {
+    tx_type: TxType,
+    data: HexBlob<Vec<u8>>,
+}
+
+impl JsonSerdeUtils for EncodedTx {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EncodedGasEstimate {
+    data: HexBlob<Vec<u8>>,
+}
+
+impl JsonSerdeUtils for EncodedGasEstimate {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DraftTx {
+    tx_type: TxType,
+    #[serde(default)]
+    data: Option<HexBlob<Vec<u8>>>,
+    #[serde(default)]
+    tx: Option<Json>,
+    gas_price: u64,
+    #[serde(default)]
+    exec_gas: Option<u64>,
+    #[serde(default)]
+    decimals: u8,
+}
+
+impl DraftTx {
+    /// Returns the binary transaction (encoding its JSON when given one).
+    fn encode(&self) -> Result<Vec<u8>, JsonError> {
+        encode_draft(self.tx_type, self.data.as_ref(), self.tx.as_ref())
+    }
+}
+
+/// Returns the binary transaction of type `tx_type` given either as binary `data`
+/// or as its JSON `tx` (as given to the encode endpoint of `tx_type`).
+pub(super) fn encode_draft(
+    tx_type: TxType,
+    data: Option<&HexBlob<Vec<u8>>>,
+    tx: Option<&Json>,
+) -> Result<Vec<u8>, JsonError> {
+    if let Some(data) = data {
+        return Ok(data.0.clone());
+    }
+
+    let tx = tx.ok_or_else(|| JsonError::MissingField {
+        field_name: "data".to_string(),
+    })?;
+    let tx = tx.to_string();
+
+    let bytes = match tx_type {
+        TxType::Deploy => super::deploy_template_raw(&tx),
+        TxType::Spawn => super::encode_spawn_raw(&tx),
+        TxType::Call => super::encode_call_raw(&tx),
+    };
+
+    bytes.map_err(|err| match err {
+        JsonError::InvalidField { path } => JsonError::InvalidField {
+            path: format!("tx.{}", path),
+        },
+        err => err,
+    })
+}
+
+impl JsonSerdeUtils for DraftTx {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_intrinsic_gas_valid() {
+        let data = vec![0xAB; 10];
+
+        for (tx_type, expected) in [
+            ("deploy", intrinsic_gas::deploy(&data)),
+            ("spawn", intrinsic_gas::spawn(&data)),
+            ("call", intrinsic_gas::call(&data)),
+        ] {
+            let json = json!({
+                "tx_type": tx_type,
+                "data": HexBlob(&data),
+            });
+
+            let json = intrinsic_gas(&json.to_string()).unwrap();
+            assert_eq!(json, json!({ "gas": expected }));
+        }
+    }
+
+    #[test]
+    fn json_intrinsic_gas_invalid_tx_type() {
+        let json = json!({
+            "tx_type": "transfer",
+            "data": "00",
+        });
+
+        let err = intrinsic_gas(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "tx_type".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn json_estimate_fee_valid() {
+        let data = vec![0xAB; 10];
+        let json = json!({
+            "tx_type": "call",
+            "data": HexBlob(&data),
+            "gas_price": 3,
+            "exec_gas": 5000,
+        });
+
+        let json = estimate_fee(&json.to_string()).unwrap();
+        let intrinsic = intrinsic_gas::call(&data);
+        let gas_limit = intrinsic + 5000;
+
+        assert_eq!(
+            json,
+            json!({
+                "intrinsic_gas": intrinsic,
+                "gas_limit": gas_limit,
+                "gas_price": 3,
+                "fee": gas_limit * 3,
+                "fee_formatted": "45,000",
+            })
+        );
+    }
+
+    #[test]
+    fn json_estimate_fee_draft_tx() {
+        let tx = json!({
+            "version": 0,
+            "target": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "func_name": "do_something",
+            "verifydata": {"abi": [], "data": []},
+            "calldata": {"abi": ["i8"], "data": [3]},
+        });
+        let bytes = crate::api::json::encode_call_raw(&tx.to_string()).unwrap();
+
+        let json = json!({
+            "tx_type": "call",
+            "tx": tx,
+            "gas_price": 1,
+            "decimals": 3,
+        });
+
+        let json = estimate_fee(&json.to_string()).unwrap();
+        let gas_limit = intrinsic_gas::call(&bytes) + intrinsic_gas::CALL_EXEC_GAS;
+
+        assert_eq!(json["gas_limit"], gas_limit);
+        assert_eq!(json["fee"], gas_limit);
+        assert_eq!(json["fee_formatted"], format_units(gas_limit, 3));
+    }
+
+    #[test]
+    fn json_estimate_fee_overflow() {
+        let json = json!({
+            "tx_type": "deploy",
+            "data": "00",
+            "gas_price": u64::MAX,
+        });
+
+        let err = estimate_fee(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "gas_price".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn json_estimate_fee_missing_tx() {
+        let json = json!({
+            "tx_type": "spawn",
+            "gas_price": 1,
+        });
+
+        let err = estimate_fee(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::MissingField {
+                field_name: "data".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn json_decode_gas_estimate() {
+        use svm_types::GasEstimate;
+
+        let mut bytes = Vec::new();
+        gas_estimate::encode(&GasEstimate::new(1000, 200, None, 5000), &mut bytes);
+
+        let json = json!({ "data": HexBlob(&bytes) });
+        let json = decode_gas_estimate(&json.to_string()).unwrap();
+
+        assert_eq!(
+            json,
+            json!({
+                "payload_price": 1000,
+                "template_load_price": 200,
+                "func_price": null,
+                "gas_limit": 6200,
+            })
+        );
+
+        let json = json!({ "data": HexBlob(&bytes[1..]) });
+        let err = decode_gas_estimate(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "data".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn format_units_decimals() {
+        assert_eq!(format_units(0, 0), "0");
+        assert_eq!(format_units(999, 0), "999");
+        assert_eq!(format_units(1_234_567, 0), "1,234,567");
+        assert_eq!(format_units(1_234_567, 3), "1,234.567");
+        assert_eq!(format_units(1_200, 3), "1.2");
+        assert_eq!(format_units(5, 3), "0.005");
+        assert_eq!(format_units(2_000, 3), "2");
+    }
+
+    #[test]
+    fn json_intrinsic_gas_missing_data() {
+        let json = json!({ "tx_type": "call" });
+
+        let err = intrinsic_gas(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::MissingField {
+                field_name: "data".to_string()
+            }
+        );
+    }
+}
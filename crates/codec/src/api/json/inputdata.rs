@@ -6,25 +6,101 @@ use std::convert::TryFrom;
 
 use svm_abi_decoder::CallData;
 use svm_abi_encoder::{ByteSize, Encoder};
+use svm_abi_layout::layout;
 use svm_sdk_types::value::{Composite, Primitive, Value as SdkValue};
 use svm_sdk_types::{Address, Amount};
 
-use super::serde_types::{AddressWrapper, EncodedData, HexBlob};
+use super::serde_types::{AddressWrapper, HexBlob};
 use super::JsonSerdeUtils;
 use crate::api::json::JsonError;
 
 /// Given an `Input Data` JSON, encodes it into a binary `Input Data`
 /// and returns the result wrapped with a JSON.
+/// An optional `echo` field is returned verbatim within the result.
+///
+/// The arguments are given either positionally:
+///
+/// ```json
+/// {
+///   "abi": ["bool", "u64"],
+///   "data": [true, 10]
+/// }
+/// ```
+///
+/// or by name, along with the function parameters (as listed under the function `signature` in the `Template` meta):
+///
+/// ```json
+/// {
+///   "params": [{"name": "a", "type": "bool"}, {"name": "b", "type": "u64"}],
+///   "args": {"b": 10, "a": true}
+/// }
+/// ```
+///
+/// Besides the primitives and the arrays (i.e `["u32"]`), the types may be nested composites:
+///
+/// ```json
+/// {
+///   "abi": [
+///     {"tuple": ["u8", "bool"]},                  // given as `[7, true]`
+///     {"option": "u32"},                          // given as `10` (or `null` when missing)
+///     [{"struct": [{"name": "to", "type": "address"}, {"name": "amount", "type": "amount"}]}]
+///   ],
+///   "data": [[7, true], null, [{"to": "1020...", "amount": 10}]]
+/// }
+/// ```
+///
+/// A tuple and a struct are encoded as an ABI `Array` of their items (just like the `svm-sdk`
+/// encodes the structs deriving `AbiEncode`), so each may hold at most 10 items.
+///
+/// The result is of the form:
 ///
 /// ```json
 /// {
-///   "data": "FFC103..."
+///   "data": "FFC103...",
+///   "schema_version": 0,
+///   "echo": ...
 /// }
 /// ```
 pub fn encode_inputdata(json: &str) -> Result<Json, JsonError> {
     let decoded = DecodedInputData::new(json)?;
-    let calldata = HexBlob(decoded.encode().unwrap());
-    Ok(EncodedData { data: calldata }.to_json())
+    let calldata = decoded.encode()?;
+
+    Ok(super::encode_response(calldata, super::echo_of(json)))
+}
+
+/// Given the meta of a `Template` (as emitted by the `svm-sdk`), the name of one of its functions
+/// and the function's named arguments, encodes the function's binary `Calldata`
+/// and returns the result wrapped with a JSON (see [`encode_inputdata`]).
+///
+/// ```json
+/// {
+///   "meta": { "api": [{ "name": "transfer", "signature": { "params": [...] }, ... }], ... },
+///   "func_name": "transfer",
+///   "args": { "to": "1020...", "amount": 10 }
+/// }
+/// ```
+pub fn encode_typed_calldata(json: &str) -> Result<Json, JsonError> {
+    let TypedCalldata {
+        meta,
+        func_name,
+        args,
+    } = TypedCalldata::from_json_str(json)?;
+
+    let export = meta
+        .api
+        .into_iter()
+        .find(|export| export.name == func_name)
+        .ok_or(JsonError::InvalidField {
+            path: "func_name".to_string(),
+        })?;
+
+    let named = NamedInputData {
+        params: export.signature.params,
+        args,
+    };
+    let calldata = named.into_positional()?.encode()?;
+
+    Ok(super::encode_response(calldata, super::echo_of(json)))
 }
 
 pub fn decode_raw_input(data: &[u8]) -> Result<Json, JsonError> {
@@ -33,12 +109,40 @@ pub fn decode_raw_input(data: &[u8]) -> Result<Json, JsonError> {
 }
 
 /// Given a binary `Calldata` (wrapped within a JSON), decodes it into a JSON
+/// of the form `{"abi": [...], "data": [...]}`.
+///
+/// The binary `Calldata` doesn't tell apart a struct from a tuple (or an `Option` from its value).
+/// Hence, the `abi` may be given along with the `data` for decoding the values by their types
+/// (for example, structs are decoded into JSON objects):
+///
+/// ```json
+/// {
+///   "data": "FFC103...",
+///   "abi": [{"struct": [{"name": "a", "type": "u8"}]}]
+/// }
+/// ```
+///
+/// Otherwise, the `abi` is inferred from the decoded values (where `null` stands for an unknown type).
 pub fn decode_inputdata(json: &str) -> Result<Json, JsonError> {
-    let encoded = EncodedData::from_json_str(json)?;
+    let encoded = EncodedTypedData::from_json_str(json)?;
     let calldata = CallData::new(&encoded.data.0);
-    Ok(calldata_to_json(calldata))
+
+    match encoded.abi {
+        Some(abi) => typed_calldata_to_json(calldata, abi),
+        None => Ok(calldata_to_json(calldata)),
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct EncodedTypedData {
+    data: HexBlob<Vec<u8>>,
+
+    #[serde(default)]
+    abi: Option<Vec<TySig>>,
 }
 
+impl JsonSerdeUtils for EncodedTypedData {}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub(crate) struct DecodedInputData {
@@ -48,6 +152,10 @@ pub(crate) struct DecodedInputData {
 
 impl DecodedInputData {
     pub fn new(json: &str) -> Result<Self, JsonError> {
+        if has_named_args(json) {
+            return NamedInputData::from_json_str(json)?.into_positional();
+        }
+
         let decoded = Self::from_json_str(json)?;
 
         if decoded.abi.len() != decoded.data.len() {
@@ -87,6 +195,123 @@ impl DecodedInputData {
 
 impl JsonSerdeUtils for DecodedInputData {}
 
+fn has_named_args(json: &str) -> bool {
+    serde_json::from_str::<Json>(json)
+        .ok()
+        .map_or(false, |json| json.get("params").is_some())
+}
+
+/// A function parameter, as listed under a function `signature` in the `Template` meta.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Param {
+    name: String,
+
+    /// Either a `Template` meta type (i.e `"svm_sdk :: Amount"` or `"[u32]"`), or a [`TySig`] JSON
+    /// (for nested composites).
+    #[serde(rename = "type")]
+    ty: Json,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    length: Option<usize>,
+}
+
+impl Param {
+    /// Translates the type of the parameter into its [`TySig`].
+    fn ty_sig(&self) -> Option<TySig> {
+        fn prim(ty: &str) -> Option<TySigPrim> {
+            // Types may be fully-qualified (i.e `svm_sdk :: Amount`).
+            let ty = ty.rsplit("::").next()?.trim().to_lowercase();
+
+            serde_json::from_value(Json::String(ty)).ok()
+        }
+
+        let ty = match &self.ty {
+            Json::String(ty) => ty.trim(),
+            ty => return serde_json::from_value(ty.clone()).ok(),
+        };
+
+        if ty.starts_with('[') && ty.ends_with(']') {
+            let elem = prim(&ty[1..ty.len() - 1])?;
+
+            Some(TySig::Array(vec![TySig::Prim(elem)]))
+        } else {
+            prim(ty).map(TySig::Prim)
+        }
+    }
+}
+
+/// `Input Data` given as named arguments along with the function parameters.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct NamedInputData {
+    params: Vec<Param>,
+    args: serde_json::Map<String, Json>,
+}
+
+impl NamedInputData {
+    /// Orders the named arguments by the function parameters.
+    fn into_positional(mut self) -> Result<DecodedInputData, JsonError> {
+        let mut abi = Vec::with_capacity(self.params.len());
+        let mut data = Vec::with_capacity(self.params.len());
+
+        for (i, param) in self.params.iter().enumerate() {
+            let ty = param.ty_sig().ok_or_else(|| JsonError::InvalidField {
+                path: format!("params[{}].type", i),
+            })?;
+
+            let arg = self
+                .args
+                .remove(&param.name)
+                .ok_or_else(|| JsonError::MissingArg {
+                    name: param.name.clone(),
+                })?;
+
+            if let Some(length) = param.length {
+                if arg.as_array().map(|elems| elems.len()) != Some(length) {
+                    return Err(JsonError::InvalidField {
+                        path: format!("args.{}", param.name),
+                    });
+                }
+            }
+
+            abi.push(ty);
+            data.push(arg);
+        }
+
+        if let Some(name) = self.args.keys().next() {
+            return Err(JsonError::UnknownArg { name: name.clone() });
+        }
+
+        Ok(DecodedInputData { abi, data })
+    }
+}
+
+impl JsonSerdeUtils for NamedInputData {}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ExportSignature {
+    params: Vec<Param>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Export {
+    name: String,
+    signature: ExportSignature,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct TemplateMeta {
+    api: Vec<Export>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct TypedCalldata {
+    meta: TemplateMeta,
+    func_name: String,
+    args: serde_json::Map<String, Json>,
+}
+
+impl JsonSerdeUtils for TypedCalldata {}
+
 fn calldata_to_json(mut calldata: CallData) -> Json {
     let mut abi = vec![];
     let mut data = vec![];
@@ -99,6 +324,28 @@ fn calldata_to_json(mut calldata: CallData) -> Json {
     json!({ "abi": abi, "data": data })
 }
 
+fn typed_calldata_to_json(mut calldata: CallData, abi: Vec<TySig>) -> Result<Json, JsonError> {
+    let invalid_data = || JsonError::InvalidField {
+        path: "data".to_string(),
+    };
+
+    let mut data = Vec::with_capacity(abi.len());
+
+    for ty in abi.iter() {
+        let value: Option<SdkValue> = calldata.next().into();
+        let value = value.ok_or_else(invalid_data)?;
+
+        data.push(sdk_value_utils::typed_sdk_value_to_json(ty, value).ok_or_else(invalid_data)?);
+    }
+
+    let rest: Option<SdkValue> = calldata.next().into();
+    if rest.is_some() {
+        return Err(invalid_data());
+    }
+
+    Ok(json!({ "abi": abi, "data": data }))
+}
+
 mod sdk_value_utils {
     use svm_types::Address;
 
@@ -122,6 +369,8 @@ mod sdk_value_utils {
                 Primitive::U64(x) => json!(x),
                 Primitive::Amount(x) => json!(x.0),
                 Primitive::Address(x) => AddressWrapper(Address::from(x.as_slice())).to_json(),
+                Primitive::Str(x) => json!(String::from_utf8_lossy(x.as_bytes())),
+                Primitive::None => Json::Null,
                 _ => unreachable!(),
             },
             SdkValue::Composite(Composite::Vec(values)) => Json::Array(
@@ -135,8 +384,12 @@ mod sdk_value_utils {
 
     /// Given a [`svm_sdk_types::value::Value`], encodes its type signature as a
     /// JSON value.
+    ///
+    /// An `Array` holding items of different types is taken as a tuple.
+    /// The type of an `Option` which is missing (or of an empty `Array`) is unknown (`null`).
     pub fn ty_sig_of_sdk_value(value: &SdkValue) -> Json {
         match value {
+            SdkValue::Primitive(Primitive::None) => Json::Null,
             SdkValue::Primitive(prim) => match prim {
                 Primitive::Bool(_) => "bool",
                 Primitive::I8(_) => "i8",
@@ -149,17 +402,74 @@ mod sdk_value_utils {
                 Primitive::U64(_) => "u64",
                 Primitive::Amount(_) => "amount",
                 Primitive::Address(_) => "address",
+                Primitive::Str(_) => "string",
                 _ => unreachable!(),
             }
             .into(),
             SdkValue::Composite(Composite::Vec(values)) => {
-                if values.is_empty() {
-                    Json::Null
+                let types: Vec<Json> = values.iter().map(ty_sig_of_sdk_value).collect();
+
+                match types.first() {
+                    None => Json::Null,
+                    Some(ty) if types.iter().all(|other| other == ty) => json!([ty]),
+                    Some(..) => json!({ "tuple": types }),
+                }
+            }
+        }
+    }
+
+    /// Given a [`svm_sdk_types::value::Value`] along with its expected [`TySig`],
+    /// encodes its value as a JSON value (structs are encoded as JSON objects).
+    ///
+    /// Returns `None` when `value` isn't of type `ty`.
+    pub(super) fn typed_sdk_value_to_json(ty: &TySig, value: SdkValue) -> Option<Json> {
+        match (ty, value) {
+            (TySig::Option { .. }, SdkValue::Primitive(Primitive::None)) => Some(Json::Null),
+            (TySig::Option { option }, value) => typed_sdk_value_to_json(option, value),
+            (TySig::Prim(prim), value @ SdkValue::Primitive(..)) => {
+                if ty_sig_of_sdk_value(&value) == json!(prim) {
+                    Some(sdk_value_to_json(value))
                 } else {
-                    let ty = &values.last().unwrap();
-                    Json::Array(vec![ty_sig_of_sdk_value(ty)])
+                    None
+                }
+            }
+            (TySig::Array(types), SdkValue::Composite(Composite::Vec(values))) => {
+                let ty = types.first()?;
+
+                values
+                    .into_iter()
+                    .map(|value| typed_sdk_value_to_json(ty, value))
+                    .collect::<Option<Vec<_>>>()
+                    .map(Json::Array)
+            }
+            (TySig::Tuple { tuple }, SdkValue::Composite(Composite::Vec(values))) => {
+                if values.len() != tuple.len() {
+                    return None;
                 }
+
+                tuple
+                    .iter()
+                    .zip(values.into_iter())
+                    .map(|(ty, value)| typed_sdk_value_to_json(ty, value))
+                    .collect::<Option<Vec<_>>>()
+                    .map(Json::Array)
             }
+            (TySig::Struct { fields }, SdkValue::Composite(Composite::Vec(values))) => {
+                if values.len() != fields.len() {
+                    return None;
+                }
+
+                fields
+                    .iter()
+                    .zip(values.into_iter())
+                    .map(|(field, value)| {
+                        typed_sdk_value_to_json(&field.ty, value)
+                            .map(|value| (field.name.clone(), value))
+                    })
+                    .collect::<Option<serde_json::Map<_, _>>>()
+                    .map(Json::Object)
+            }
+            _ => None,
         }
     }
 
@@ -193,6 +503,15 @@ mod sdk_value_utils {
             TySigPrim::I64 => json_as_numeric::<i64>(json),
             // [`u64`] is the only JSON integer type which doesn't fit into `i64`.
             TySigPrim::U64 => json.as_u64().map(Into::into),
+            TySigPrim::Str => json
+                .as_str()
+                .filter(|s| s.len() <= layout::STR_MAX_LEN)
+                .map(|s| {
+                    let mut bytes = svm_sdk_std::Vec::with_capacity(s.len());
+                    s.bytes().for_each(|b| bytes.push(b));
+
+                    svm_sdk_std::String::from_utf8(bytes).unwrap().into()
+                }),
         }
     }
 }
@@ -203,11 +522,73 @@ mod sdk_value_utils {
 enum TySig {
     Prim(TySigPrim),
     Array(Vec<TySig>),
+    /// Items of (possibly) different types, given as a JSON array.
+    Tuple {
+        tuple: Vec<TySig>,
+    },
+    /// A value which may be missing (given as `null`).
+    Option {
+        option: Box<TySig>,
+    },
+    /// Named fields, given as a JSON object (and encoded by the order of `fields`).
+    Struct {
+        #[serde(rename = "struct")]
+        fields: Vec<FieldSig>,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct FieldSig {
+    name: String,
+
+    #[serde(rename = "type")]
+    ty: TySig,
+}
+
+/// The maximum number of items of an ABI `Array` (see `layout::ARR_10`).
+const MAX_ARRAY_ITEMS: usize = 10;
+
+fn invalid_calldata() -> JsonError {
+    JsonError::InvalidField {
+        path: "calldata".to_string(),
+    }
 }
 
 impl TySig {
     fn value_byte_size(&self, value: &Json) -> Result<usize, JsonError> {
         let byte_size = match self {
+            TySig::Option { option } => {
+                if value.is_null() {
+                    // The `None` marker.
+                    1
+                } else {
+                    option.value_byte_size(value)?
+                }
+            }
+            TySig::Tuple { tuple } => {
+                let elems = value.as_array().ok_or_else(invalid_calldata)?;
+
+                if elems.len() != tuple.len() {
+                    return Err(invalid_calldata());
+                }
+
+                let mut byte_size = 1;
+                for (ty, elem) in tuple.iter().zip(elems) {
+                    byte_size += ty.value_byte_size(elem)?;
+                }
+
+                byte_size
+            }
+            TySig::Struct { fields } => {
+                let mut byte_size = 1;
+                for field in fields {
+                    let elem = value.get(&field.name).ok_or_else(invalid_calldata)?;
+
+                    byte_size += field.ty.value_byte_size(elem)?;
+                }
+
+                byte_size
+            }
             TySig::Array(types) => {
                 assert_eq!(types.len(), 1);
 
@@ -238,6 +619,13 @@ impl TySig {
                 TySigPrim::U64 => u64::max_byte_size(),
                 TySigPrim::Amount => Amount::max_byte_size(),
                 TySigPrim::Address => Address::max_byte_size(),
+                TySigPrim::Str => {
+                    let s = value.as_str().ok_or(JsonError::InvalidField {
+                        path: "calldata".to_string(),
+                    })?;
+
+                    2 + s.len()
+                }
             },
         };
         Ok(byte_size)
@@ -258,6 +646,9 @@ pub enum TySigPrim {
     U64,
     Amount,
     Address,
+    /// A UTF-8 string of at most `layout::STR_MAX_LEN` bytes.
+    #[serde(rename = "string", alias = "str")]
+    Str,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -274,33 +665,89 @@ enum TyPrimSdkValue {
     U64(u64),
     Amount(u64),
     Address(AddressWrapper),
+    Str(String),
 }
 
 fn encode_value(ty: TySig, value: Json) -> Result<SdkValue, JsonError> {
     match ty {
         TySig::Array(types) => encode_array(&types, value),
+        TySig::Tuple { tuple } => encode_tuple(tuple, value),
+        TySig::Struct { fields } => encode_struct(fields, value),
+        TySig::Option { option } => {
+            if value.is_null() {
+                Ok(SdkValue::none())
+            } else {
+                encode_value(*option, value)
+            }
+        }
         TySig::Prim(prim) => {
-            sdk_value_utils::sdk_value_from_json(value, prim).ok_or(JsonError::InvalidField {
-                path: "calldata".to_string(),
-            })
+            sdk_value_utils::sdk_value_from_json(value, prim).ok_or_else(invalid_calldata)
         }
     }
 }
 
-fn encode_array(types: &[TySig], mut value: Json) -> Result<SdkValue, JsonError> {
+fn encode_array(types: &[TySig], value: Json) -> Result<SdkValue, JsonError> {
     assert_eq!(types.len(), 1);
 
     let ty = &types[0];
 
-    let mut value = value.take();
-    let elems = value.as_array_mut().ok_or(JsonError::InvalidField {
-        path: "calldata".to_string(),
-    })?;
+    let elems = match value {
+        Json::Array(elems) => elems,
+        _ => return Err(invalid_calldata()),
+    };
+
+    let len = elems.len();
+
+    encode_items(elems.into_iter().map(|elem| (ty.clone(), elem)), len)
+}
+
+fn encode_tuple(tuple: Vec<TySig>, value: Json) -> Result<SdkValue, JsonError> {
+    let elems = match value {
+        Json::Array(elems) if elems.len() == tuple.len() => elems,
+        _ => return Err(invalid_calldata()),
+    };
+
+    let len = elems.len();
+
+    encode_items(tuple.into_iter().zip(elems), len)
+}
+
+fn encode_struct(fields: Vec<FieldSig>, value: Json) -> Result<SdkValue, JsonError> {
+    let mut object = match value {
+        Json::Object(object) => object,
+        _ => return Err(invalid_calldata()),
+    };
+
+    let len = fields.len();
+    let mut items = Vec::with_capacity(len);
+
+    for field in fields {
+        let elem = object.remove(&field.name).ok_or_else(invalid_calldata)?;
+
+        items.push((field.ty, elem));
+    }
 
-    let mut vec = svm_sdk_std::Vec::with_capacity(10);
+    // Unknown fields aren't allowed.
+    if !object.is_empty() {
+        return Err(invalid_calldata());
+    }
 
-    for elem in elems.iter_mut() {
-        let elem = encode_value(ty.clone(), elem.take())?;
+    encode_items(items.into_iter(), len)
+}
+
+/// Encodes the items of an ABI `Array` (of at most [`MAX_ARRAY_ITEMS`] items).
+fn encode_items(
+    items: impl Iterator<Item = (TySig, Json)>,
+    len: usize,
+) -> Result<SdkValue, JsonError> {
+    if len > MAX_ARRAY_ITEMS {
+        return Err(invalid_calldata());
+    }
+
+    let mut vec = svm_sdk_std::Vec::with_capacity(len);
+
+    for (ty, elem) in items {
+        let elem = encode_value(ty, elem)?;
 
         vec.push(elem);
     }
@@ -377,10 +824,338 @@ mod tests {
         test!(["address"], [addr]);
     }
 
+    #[test]
+    fn encode_calldata_string() {
+        test!(["string", "string"], ["", "Hello World"]);
+        test!(["string"], ["שלום"]);
+        test!([["string"]], [["a", "b"]]);
+    }
+
+    #[test]
+    fn encode_calldata_string_too_long() {
+        let json = json!({"abi": ["string"], "data": ["a".repeat(256)]});
+
+        let err = encode_inputdata(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "calldata".to_string()
+            }
+        );
+
+        let json = json!({"abi": ["string"], "data": ["a".repeat(255)]});
+        assert!(encode_inputdata(&json.to_string()).is_ok());
+    }
+
     #[test]
     fn encode_calldata_array() {
         test!([["u32"]], [[10, 20, 30]]);
         test!([["i8"]], [[-10, 0, 30]]);
         test!([["u32"], ["i8"]], [[10, 20, 30], [-10, 0, 20]]);
     }
+
+    #[test]
+    fn encode_calldata_tuple() {
+        test!(json!([{"tuple": ["u8", "bool"]}]), json!([[7, true]]));
+        test!(
+            json!([[{"tuple": ["u8", "string"]}]]),
+            json!([[[1, "a"], [2, "b"]]])
+        );
+        test!(json!([[["u8"]]]), json!([[[1, 2], [3]]]));
+    }
+
+    #[test]
+    fn encode_calldata_option() {
+        let json = json!({"abi": [{"option": "u32"}, {"option": "u32"}], "data": [null, 10]});
+
+        let encoded = encode_inputdata(&json.to_string()).unwrap();
+        let decoded = decode_inputdata(&encoded.to_string()).unwrap();
+
+        assert_eq!(decoded, json!({"abi": [null, "u32"], "data": [null, 10]}));
+    }
+
+    #[test]
+    fn encode_calldata_nested_struct() {
+        let transfer = json!({"struct": [
+            {"name": "to", "type": "address"},
+            {"name": "amount", "type": "amount"},
+            {"name": "memo", "type": {"option": "string"}},
+            {"name": "tags", "type": ["u8"]},
+        ]});
+        let addr = "1020304050607080900010203040506070809000";
+
+        let json = json!({
+            "abi": [[transfer], "bool"],
+            "data": [
+                [
+                    {"to": addr, "amount": 10, "memo": "rent", "tags": [1, 2]},
+                    {"to": addr, "amount": 20, "memo": null, "tags": []},
+                ],
+                true
+            ]
+        });
+
+        let encoded = encode_inputdata(&json.to_string()).unwrap();
+
+        let typed = json!({"data": encoded["data"], "abi": json["abi"]});
+        let decoded = decode_inputdata(&typed.to_string()).unwrap();
+        assert_eq!(decoded, json);
+
+        // Without the `abi`, structs are decoded as tuples.
+        let decoded = decode_inputdata(&encoded.to_string()).unwrap();
+        assert_eq!(
+            decoded["data"],
+            json!([[[addr, 10, "rent", [1, 2]], [addr, 20, null, []]], true])
+        );
+    }
+
+    #[test]
+    fn encode_calldata_struct_agrees_with_sdk() {
+        use svm_abi_encoder::encode_array_header;
+
+        // Mimics the code generated by `#[derive(AbiEncode)]` for `struct { a: u8, b: Option<u32> }`.
+        let mut expected = svm_sdk_std::Vec::with_capacity(100);
+        encode_array_header(2, &mut expected);
+        7u8.encode(&mut expected);
+        svm_sdk_std::Option::<u32>::None.encode(&mut expected);
+
+        let json = json!({
+            "abi": [{"struct": [{"name": "a", "type": "u8"}, {"name": "b", "type": {"option": "u32"}}]}],
+            "data": [{"b": null, "a": 7}]
+        });
+        let calldata = DecodedInputData::new(&json.to_string())
+            .unwrap()
+            .encode()
+            .unwrap();
+
+        assert_eq!(calldata, expected.as_slice());
+    }
+
+    #[test]
+    fn encode_calldata_struct_invalid_fields() {
+        let abi = json!([{"struct": [{"name": "a", "type": "u8"}, {"name": "b", "type": "bool"}]}]);
+
+        for data in [
+            json!([{"a": 1}]),
+            json!([{"a": 1, "b": true, "c": 2}]),
+            json!([[1, true]]),
+        ] {
+            let json = json!({"abi": abi, "data": data});
+
+            let err = encode_inputdata(&json.to_string()).unwrap_err();
+            assert_eq!(
+                err,
+                JsonError::InvalidField {
+                    path: "calldata".to_string()
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn encode_calldata_array_too_long() {
+        let json = json!({"abi": [["u8"]], "data": [vec![0; 11]]});
+
+        let err = encode_inputdata(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "calldata".to_string()
+            }
+        );
+
+        let json = json!({"abi": [["u8"]], "data": [vec![0; 10]]});
+        assert!(encode_inputdata(&json.to_string()).is_ok());
+    }
+
+    #[test]
+    fn decode_calldata_typed_mismatch() {
+        let json = json!({"abi": [{"tuple": ["u8", "bool"]}], "data": [[7, true]]});
+        let encoded = encode_inputdata(&json.to_string()).unwrap();
+
+        for abi in [
+            json!([{"tuple": ["u8", "u8"]}]),
+            json!([{"tuple": ["u8"]}]),
+            json!([{"tuple": ["u8", "bool"]}, "u8"]),
+            json!([]),
+        ] {
+            let typed = json!({"data": encoded["data"], "abi": abi});
+
+            let err = decode_inputdata(&typed.to_string()).unwrap_err();
+            assert_eq!(
+                err,
+                JsonError::InvalidField {
+                    path: "data".to_string()
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn encode_calldata_named_args_nested() {
+        let named = json!({
+            "params": [
+                {"name": "a", "type": {"tuple": ["u8", "bool"]}},
+                {"name": "b", "type": {"option": "amount"}},
+            ],
+            "args": {"b": null, "a": [1, false]}
+        });
+        let positional = json!({
+            "abi": [{"tuple": ["u8", "bool"]}, {"option": "amount"}],
+            "data": [[1, false], null]
+        });
+
+        let named = encode_inputdata(&named.to_string()).unwrap();
+        let positional = encode_inputdata(&positional.to_string()).unwrap();
+
+        assert_eq!(named, positional);
+    }
+
+    #[test]
+    fn encode_calldata_named_args() {
+        let named = json!({
+            "params": [
+                {"name": "a", "type": "bool"},
+                {"name": "b", "type": "u64"},
+                {"name": "c", "type": "Amount"},
+                {"name": "d", "type": "[u32]", "length": 2},
+            ],
+            "args": {"d": [1, 2], "c": 10, "a": true, "b": 20}
+        });
+        let positional = json!({
+            "abi": ["bool", "u64", "amount", ["u32"]],
+            "data": [true, 20, 10, [1, 2]]
+        });
+
+        let named = encode_inputdata(&named.to_string()).unwrap();
+        let positional = encode_inputdata(&positional.to_string()).unwrap();
+
+        assert_eq!(named, positional);
+    }
+
+    #[test]
+    fn encode_calldata_named_args_missing() {
+        let json = json!({
+            "params": [{"name": "a", "type": "bool"}, {"name": "b", "type": "u64"}],
+            "args": {"a": true}
+        });
+
+        let err = encode_inputdata(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::MissingArg {
+                name: "b".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn encode_calldata_named_args_unknown() {
+        let json = json!({
+            "params": [{"name": "a", "type": "bool"}],
+            "args": {"a": true, "z": 1}
+        });
+
+        let err = encode_inputdata(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::UnknownArg {
+                name: "z".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn encode_calldata_named_args_array_length_mismatch() {
+        let json = json!({
+            "params": [{"name": "a", "type": "[u8]", "length": 3}],
+            "args": {"a": [1, 2]}
+        });
+
+        let err = encode_inputdata(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "args.a".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn encode_typed_calldata_valid() {
+        let meta = json!({
+            "schema": [],
+            "api": [{
+                "name": "transfer",
+                "wasm_name": "transfer",
+                "is_ctor": false,
+                "is_fundable": false,
+                "doc": "",
+                "signature": {
+                    "params": [
+                        {"name": "to", "type": "svm_sdk :: Address"},
+                        {"name": "amount", "type": "Amount"},
+                    ],
+                    "returns": {}
+                }
+            }]
+        });
+        let addr = "1020304050607080900010203040506070809000";
+
+        let typed = json!({
+            "meta": meta,
+            "func_name": "transfer",
+            "args": {"amount": 10, "to": addr}
+        });
+        let positional = json!({
+            "abi": ["address", "amount"],
+            "data": [addr, 10]
+        });
+
+        let typed = encode_typed_calldata(&typed.to_string()).unwrap();
+        let positional = encode_inputdata(&positional.to_string()).unwrap();
+
+        assert_eq!(typed, positional);
+
+        let unknown_func = json!({
+            "meta": meta,
+            "func_name": "withdraw",
+            "args": {}
+        });
+
+        let err = encode_typed_calldata(&unknown_func.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "func_name".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn encode_calldata_echo() {
+        let json = json!({"abi": ["bool"], "data": [true], "echo": {"request": 7}});
+        let encoded = encode_inputdata(&json.to_string()).unwrap();
+
+        assert_eq!(encoded["echo"], json!({"request": 7}));
+        assert_eq!(
+            encoded["schema_version"],
+            json!(crate::api::json::SCHEMA_VERSION)
+        );
+
+        let json = json!({"abi": ["bool"], "data": [true]});
+        let encoded = encode_inputdata(&json.to_string()).unwrap();
+
+        assert!(encoded.get("echo").is_none());
+    }
+
+    #[test]
+    fn json_decode_inputdata_golden() {
+        let json = json!({"abi": ["u8", ["bool"], "amount"], "data": [7, [true, false], 100]});
+        let encoded = encode_inputdata(&json.to_string()).unwrap();
+        let decoded = decode_inputdata(&encoded.to_string()).unwrap();
+
+        let golden = r#"{"abi":["u8",["bool"],"amount"],"data":[7,[true,false],100]}"#;
+        assert_eq!(decoded.to_string(), golden);
+    }
 }
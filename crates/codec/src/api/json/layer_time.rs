@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use svm_types::Layer;
+
+/// Maps `Layer`s to their (Unix) start time, so that decoded payloads can be enriched
+/// with human-readable timestamps alongside their layer numbers.
+///
+/// It's given either as the network's genesis time and layer duration (both in seconds):
+///
+/// ```json
+/// { "genesis_time": 1622505600, "layer_duration": 30 }
+/// ```
+///
+/// or as an explicit mapping from layers (given as JSON object keys) to their start time (in seconds):
+///
+/// ```json
+/// { "layers": { "10": 1622505900, "11": 1622505930 } }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum LayerTime {
+    Genesis {
+        genesis_time: u64,
+        layer_duration: u64,
+    },
+    Mapping {
+        layers: BTreeMap<String, u64>,
+    },
+}
+
+impl LayerTime {
+    /// Returns the start time of `layer` (in seconds since the Unix epoch),
+    /// or `None` when it's unknown (not mapped, or out of range).
+    pub fn unix_time(&self, layer: Layer) -> Option<u64> {
+        match self {
+            LayerTime::Genesis {
+                genesis_time,
+                layer_duration,
+            } => layer
+                .as_u64()
+                .checked_mul(*layer_duration)
+                .and_then(|offset| genesis_time.checked_add(offset)),
+            LayerTime::Mapping { layers } => layers.get(&layer.to_string()).copied(),
+        }
+    }
+
+    /// Returns the start time of `layer` as an ISO-8601 UTC timestamp (see [`iso8601`]).
+    pub fn timestamp(&self, layer: Layer) -> Option<String> {
+        self.unix_time(layer).and_then(iso8601)
+    }
+}
+
+/// Formats `secs` (since the Unix epoch) as an ISO-8601 UTC timestamp (e.g `2021-06-01T00:00:00Z`).
+///
+/// Returns `None` for times beyond the year `9999` (which can't be given by 4 digits).
+pub(crate) fn iso8601(secs: u64) -> Option<String> {
+    let days = secs / 86400;
+    let secs_of_day = secs % 86400;
+
+    let (year, month, day) = civil_from_days(days);
+
+    if year > 9999 {
+        return None;
+    }
+
+    Some(format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        (secs_of_day % 3600) / 60,
+        secs_of_day % 60
+    ))
+}
+
+/// Converts a number of days since the Unix epoch into a (proleptic Gregorian) `(year, month, day)`.
+///
+/// The computation works over 400-years eras (each having exactly 146097 days),
+/// with years starting at March (so that the leap day is the last day of a year).
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // shifting the epoch from 1970-01-01 to 0000-03-01
+    let days = days + 719468;
+
+    let era = days / 146097;
+    let day_of_era = days % 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iso8601_formatting() {
+        assert_eq!(iso8601(0).unwrap(), "1970-01-01T00:00:00Z");
+        assert_eq!(iso8601(951782400).unwrap(), "2000-02-29T00:00:00Z");
+        assert_eq!(iso8601(1622505600 + 3661).unwrap(), "2021-06-01T01:01:01Z");
+        assert_eq!(iso8601(4102444799).unwrap(), "2099-12-31T23:59:59Z");
+        assert_eq!(iso8601(253402300799).unwrap(), "9999-12-31T23:59:59Z");
+        assert_eq!(iso8601(253402300800), None);
+    }
+
+    #[test]
+    fn layer_time_genesis_and_mapping() {
+        let genesis = LayerTime::Genesis {
+            genesis_time: 1622505600,
+            layer_duration: 30,
+        };
+
+        assert_eq!(genesis.unix_time(Layer(10)), Some(1622505900));
+        assert_eq!(
+            genesis.timestamp(Layer(10)).unwrap(),
+            "2021-06-01T00:05:00Z"
+        );
+        assert_eq!(genesis.unix_time(Layer(u64::MAX)), None);
+
+        let mapping = LayerTime::Mapping {
+            layers: vec![("10".to_string(), 1622505900)].into_iter().collect(),
+        };
+
+        assert_eq!(
+            mapping.timestamp(Layer(10)).unwrap(),
+            "2021-06-01T00:05:00Z"
+        );
+        assert_eq!(mapping.timestamp(Layer(11)), None);
+    }
+}
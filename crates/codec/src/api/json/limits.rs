@@ -0,0 +1,49 @@
+use serde_json::{json, Value as Json};
+
+use crate::limits::MessageFamily;
+
+/// Returns the maximum byte sizes of the encoded messages, per message family (see [`crate::limits`]).
+///
+/// Clients can use them for validating the size of a payload prior to calling into the codec.
+///
+/// ```json
+/// {
+///   "deploy": 2097152,
+///   "upgrade": 2097152,
+///   "spawn": 65536,
+///   "call": 65536,
+///   "batch": 262144
+/// }
+/// ```
+pub fn limits() -> Json {
+    let mut json = json!({});
+
+    for family in MessageFamily::ALL.iter().copied() {
+        json[family.name()] = json!(family.max_size());
+    }
+
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::limits::{MAX_BATCH_SIZE, MAX_CALL_SIZE, MAX_DEPLOY_SIZE, MAX_SPAWN_SIZE};
+
+    #[test]
+    fn json_limits() {
+        let json = limits();
+
+        assert_eq!(
+            json,
+            json!({
+                "deploy": MAX_DEPLOY_SIZE,
+                "upgrade": MAX_DEPLOY_SIZE,
+                "spawn": MAX_SPAWN_SIZE,
+                "call": MAX_CALL_SIZE,
+                "batch": MAX_BATCH_SIZE
+            })
+        );
+    }
+}
@@ -1,26 +1,60 @@
 //! JSON API
+//!
+//! ## Key order
+//!
+//! The keys of the JSON objects returned by the decode endpoints are emitted in a canonical order,
+//! so that clients can hash or snapshot the output. The canonical order of each payload is the one
+//! of its documented example, and it depends neither on the `serde_json` version nor on the order
+//! of the decoded input (e.g the order of a `Deploy Template`'s `Section`s).
 
+mod abi_vectors;
+mod armor;
 mod call;
 mod deploy;
+mod envelope;
 mod error;
+mod gas;
 mod inputdata;
+mod layer_time;
+mod limits;
 mod receipt;
+mod self_test;
+mod signing;
 mod spawn;
+mod transaction;
 
 pub(crate) mod serde_types;
 
+use serde_types::HexBlob;
+
+pub use abi_vectors::{abi_vectors, abi_vectors_json, AbiVector};
+pub use armor::{armor, unarmor};
 pub use call::{decode_call, encode_call, encode_call_raw};
-pub use deploy::deploy_template;
+pub use deploy::{
+    decode_deploy, decode_docs, decode_sections, deploy_template, deploy_template_raw,
+};
+pub use envelope::{decode_envelope, encode_envelope, encode_envelope_raw};
 pub use error::JsonError;
-pub use inputdata::{decode_inputdata, encode_inputdata};
-pub use receipt::decode_receipt;
-pub use spawn::{decode_spawn, encode_spawn};
+pub use gas::{decode_gas_estimate, estimate_fee, intrinsic_gas};
+pub use inputdata::{decode_inputdata, encode_inputdata, encode_typed_calldata};
+pub use limits::limits;
+pub use receipt::{decode_receipt, encode_receipt, encode_receipt_raw};
+pub use self_test::self_test;
+pub use signing::signing_payload;
+pub use spawn::{decode_spawn, encode_spawn, encode_spawn_raw};
+pub use transaction::build_transaction;
 
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value as Json};
 
 use svm_types::{Gas, ReceiptLog};
 
+/// The version of the JSON schema used by the `api::json` endpoints.
+///
+/// Every encode endpoint returns it within its response, so that clients can detect
+/// a version skew between them and the codec build.
+pub const SCHEMA_VERSION: u16 = 0;
+
 /// Provides very simple utility functions to working with [`serde_json::Value`]
 /// in an easy way.
 pub(crate) trait JsonSerdeUtils: Serialize + for<'a> Deserialize<'a> {
@@ -47,6 +81,38 @@ pub(crate) fn to_bytes(json: &Json) -> Vec<u8> {
         .into_bytes()
 }
 
+/// Returns the optional `echo` field of a request JSON (verbatim).
+///
+/// Each encode endpoint accepts an `echo` field and returns it within its response,
+/// so that clients can correlate async requests and responses.
+pub(crate) fn echo_of(json: &str) -> Option<Json> {
+    serde_json::from_str::<Json>(json)
+        .ok()
+        .and_then(|json| json.get("echo").cloned())
+}
+
+/// Wraps the binary output of an encode endpoint within its response JSON.
+///
+/// ```json
+/// {
+///   "data": "FFC103...",
+///   "schema_version": 0,
+///   "echo": ...           // only when the request has an `echo` field
+/// }
+/// ```
+pub(crate) fn encode_response(data: Vec<u8>, echo: Option<Json>) -> Json {
+    let mut json = json!({
+        "data": HexBlob(data),
+        "schema_version": SCHEMA_VERSION,
+    });
+
+    if let Some(echo) = echo {
+        json["echo"] = echo;
+    }
+
+    json
+}
+
 pub(crate) fn gas_to_json(gas: &Gas) -> i64 {
     if gas.is_some() {
         gas.unwrap() as _
@@ -60,9 +126,43 @@ pub(crate) fn logs_to_json(logs: &[ReceiptLog]) -> Vec<Json> {
         .map(|log| {
             let data = unsafe { String::from_utf8_unchecked(log.as_bytes().to_vec()) };
 
-            json!({
-                "data": data,
-            })
+            match log.code() {
+                Some(code) => {
+                    let topics: Vec<Json> = log
+                        .topics()
+                        .iter()
+                        .map(|topic| json!(HexBlob(&topic[..])))
+                        .collect();
+
+                    json!({
+                        "code": code,
+                        "topics": topics,
+                        "data": data,
+                    })
+                }
+                None => json!({
+                    "data": data,
+                }),
+            }
         })
         .collect()
 }
+
+/// Reorders the keys of the `json` object according to `order`.
+///
+/// The keys missing from `order` are placed last (keeping their relative order).
+pub(crate) fn canonical_order(json: Json, order: &[&str]) -> Json {
+    match json {
+        Json::Object(map) => {
+            let position = |key: &str| order.iter().position(|k| *k == key);
+
+            let (mut known, rest): (Vec<_>, Vec<_>) = map
+                .into_iter()
+                .partition(|(key, _)| position(key).is_some());
+            known.sort_by_key(|(key, _)| position(key));
+
+            Json::Object(known.into_iter().chain(rest).collect())
+        }
+        json => json,
+    }
+}
@@ -1,39 +1,244 @@
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
-use svm_types::RuntimeError;
-use svm_types::{CallReceipt, DeployReceipt, Receipt, ReceiptLog, SpawnReceipt};
+use svm_types::{BatchReceipt, UpgradeReceipt};
+use svm_types::{CallReceipt, DeployReceipt, GasBreakdown, Receipt, ReceiptLog, SpawnReceipt};
+use svm_types::{Gas, Layer, ReceiptMetric, RuntimeError};
 
+use super::layer_time::LayerTime;
 use super::JsonSerdeUtils;
-use crate::api::json::serde_types::{AddressWrapper, EncodedData, HexBlob, TemplateAddrWrapper};
+use crate::api::json::serde_types::{AddressWrapper, HexBlob, TemplateAddrWrapper};
 use crate::api::json::{self, JsonError};
-use crate::receipt;
+use crate::client::{self, CallAccountReceipt, DeployTemplateReceipt, SpawnAccountReceipt};
+use crate::client::{CallBatchReceipt, UpgradeTemplateReceipt};
+use crate::receipt::{self, PartialReceipt};
 
 /// Given a binary Receipt wrapped inside a JSON,
 /// decodes it into a user-friendly JSON.
+///
+/// ```json
+/// {
+///   "data": "A2FB...",  // string
+///   "tolerant": false,  // bool (optional, defaults to `false`)
+///   "layer": 10,        // number (optional)
+///   "layer_time": {     // object (optional)
+///     "genesis_time": 1622505600,
+///     "layer_duration": 30
+///   }
+/// }
+/// ```
+///
+/// The returned keys are emitted in the following order:
+///
+/// * `deploy-template`: `type`, `success`, `gas_schedule_version`, `addr`, `gas_used`, `logs`,
+///   `gas_breakdown`
+/// * `spawn-account`: `type`, `success`, `gas_schedule_version`, `account`, `state`, `returndata`,
+///   `gas_used`, `logs`, `gas_breakdown`, `metrics`, `call`
+/// * `call-account`: `type`, `success`, `gas_schedule_version`, `new_state`, `returndata`, `gas_used`,
+///   `logs`, `gas_breakdown`, `metrics`
+/// * `upgrade-template`: `type`, `success`, `gas_schedule_version`, `addr`, `template_version`,
+///   `gas_used`, `logs`, `gas_breakdown`
+/// * `call-batch`: `type`, `success`, `gas_schedule_version`, `new_state`, `gas_used`, `logs`,
+///   `gas_breakdown`, `receipts` (the `call-account` receipts of the batched calls)
+/// * a failure: `type`, `success`, `gas_schedule_version`, `err_type`, the fields of the error
+///   (`template_addr`, `account_addr`, `func`, `message`), `logs`
+///
+/// The `gas_breakdown`, `metrics` and `call` keys are present only when the receipt has these.
+/// The `gas_breakdown` keys are `intrinsic`, `execution`, `storage`, `logs` and `refund`,
+/// and the `metrics` are given as `[{"id": 1, "value": 80}, ...]`.
+/// An event log is given as `{"code": 1, "topics": ["AAAA...", ...], "data": "..."}`
+/// (its topics are hex-encoded), and a raw log only has its `data`.
+///
+/// Since the binary receipt doesn't hold the `Layer` its transaction has been included at,
+/// it can be given as `layer`. Then a `layer` key is appended to the result, followed by
+/// a `timestamp` key holding the layer's start time as an ISO-8601 UTC timestamp
+/// (e.g `"2021-06-01T00:05:00Z"`) when `layer_time` is given too. The `layer_time` is either
+/// the network's `genesis_time` and `layer_duration` (in seconds), or an explicit mapping
+/// from layers to their (Unix) start time, e.g `{"layers": {"10": 1622505900}}`.
+/// The `timestamp` is omitted when the layer isn't mapped.
+///
+/// A malformed `data` is rejected, unless `tolerant` is set. Then the keys recovered
+/// by [`receipt::decode_receipt_partial`] are returned (the keys of the fields following
+/// the corruption are omitted), followed by a `partial` key telling where and why
+/// decoding has stopped:
+///
+/// ```json
+/// {
+///   "type": "call-account",
+///   "success": true,
+///   "gas_schedule_version": 0,
+///   "new_state": "A0A0...",
+///   "logs": [],
+///   "partial": {
+///     "offset": 36,
+///     "message": "Not enough bytes for field `ReturnData`"
+///   }
+/// }
+/// ```
 pub fn decode_receipt(json: &str) -> Result<Value, JsonError> {
-    let encoded_receipt = EncodedData::from_json_str(json)?;
+    let encoded_receipt = EncodedReceipt::from_json_str(json)?;
     let bytes = encoded_receipt.data.0.as_slice();
 
-    assert!(bytes.len() > 0);
+    let partial = receipt::decode_receipt_partial(bytes);
 
-    let receipt = receipt::decode_receipt(&bytes);
-    let ty = receipt_type(&receipt);
+    if encoded_receipt.tolerant {
+        let mut json = partial_to_json(partial);
+        encoded_receipt.enrich(&mut json);
 
-    let json = if receipt.success() {
-        match receipt {
-            Receipt::Deploy(receipt) => decode_deploy(&receipt, ty),
-            Receipt::Spawn(receipt) => decode_spawn(&receipt, ty),
-            Receipt::Call(receipt) => decode_call(&receipt, ty),
+        return Ok(json);
+    }
+
+    let structure = match partial.receipt.as_ref() {
+        Some(receipt) => receipt::structure(receipt),
+        None => "Receipt",
+    };
+    let mut cursor = std::io::Cursor::new(bytes);
+    cursor.set_position(partial.offset as u64);
+
+    match partial.into_result() {
+        Ok(receipt) => {
+            let mut json = receipt_to_json(&receipt);
+            encoded_receipt.enrich(&mut json);
+
+            Ok(json)
         }
-    } else {
-        let ty = receipt_type(&receipt);
-        let logs = receipt.logs();
-        let err = receipt.error();
+        Err(err) => Err(JsonError::InvalidBinary {
+            path: "data".to_string(),
+            error: err.at(structure, &cursor),
+        }),
+    }
+}
+
+/// Transforms a user-friendly Receipt JSON (of the form returned by [`decode_receipt`])
+/// into its binary form, so that test harnesses and mock nodes can produce receipts out of fixtures.
+///
+/// ```json
+/// {
+///   "type": "call-account",
+///   "success": true,
+///   "gas_schedule_version": 0,
+///   "new_state": "A0A0...",
+///   "returndata": "1020",
+///   "gas_used": 10,
+///   "logs": [{ "data": "Log entry #1" }]
+/// }
+/// ```
+///
+/// A successful receipt requires the keys identifying its outcome (`addr`, `account` and `state`,
+/// `new_state` (of a `call-account` or a `call-batch`), or `addr` and `template_version`), and a failed one requires an `err_type` (along with the fields of the error).
+/// The receipts format version is the lowest one able to hold the receipt (i.e `6` when any of the
+/// `logs` is an event, `5` when there are `metrics`, `2` when `gas_schedule_version` isn't `0`,
+/// and `1` when there is a `gas_breakdown`).
+///
+/// An optional `echo` field is returned verbatim within the result.
+///
+/// Result:
+///
+/// ```json
+/// {
+///   "data": "02000001...",
+///   "schema_version": 0,
+///   "echo": ...
+/// }
+/// ```
+pub fn encode_receipt(json: &str) -> Result<Value, JsonError> {
+    let encoded_bytes = encode_receipt_raw(json)?;
+
+    Ok(json::encode_response(encoded_bytes, json::echo_of(json)))
+}
+
+/// Much like [`encode_receipt`], but instead of returning a JSON wrapper it
+/// returns the raw bytes.
+pub fn encode_receipt_raw(json: &str) -> Result<Vec<u8>, JsonError> {
+    let bytes = match client::Receipt::from_json(json)? {
+        client::Receipt::DeployTemplate(receipt) => {
+            receipt::encode_deploy(&deploy_from_client(receipt)?)
+        }
+        client::Receipt::SpawnAccount(receipt) => {
+            receipt::encode_spawn(&spawn_from_client(receipt)?)
+        }
+        client::Receipt::CallAccount(receipt) => receipt::encode_call(&call_from_client(receipt)?),
+        client::Receipt::UpgradeTemplate(receipt) => {
+            receipt::encode_upgrade(&upgrade_from_client(receipt)?)
+        }
+        client::Receipt::CallBatch(receipt) => receipt::encode_batch(&batch_from_client(receipt)?),
+    };
+
+    Ok(bytes)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct EncodedReceipt {
+    data: HexBlob<Vec<u8>>,
+
+    #[serde(default)]
+    tolerant: bool,
+
+    #[serde(default)]
+    layer: Option<Layer>,
+
+    #[serde(default)]
+    layer_time: Option<LayerTime>,
+}
+
+impl JsonSerdeUtils for EncodedReceipt {}
+
+impl EncodedReceipt {
+    /// Appends the `layer` and `timestamp` keys (see [`decode_receipt`]).
+    fn enrich(&self, json: &mut Value) {
+        if let Some(layer) = self.layer {
+            json["layer"] = json!(layer.as_u64());
 
-        decode_error(ty, err, logs)
+            let timestamp = self
+                .layer_time
+                .as_ref()
+                .and_then(|layer_time| layer_time.timestamp(layer));
+
+            if let Some(timestamp) = timestamp {
+                json["timestamp"] = json!(timestamp);
+            }
+        }
+    }
+}
+
+fn partial_to_json(partial: PartialReceipt) -> Value {
+    let mut json = match &partial.receipt {
+        Some(receipt) => receipt_to_json(receipt),
+        None => json!({}),
     };
 
-    Ok(json)
+    if let Some(err) = partial.error {
+        json["partial"] = json!({
+            "offset": partial.offset,
+            "message": err.to_string(),
+        });
+    }
+
+    json
+}
+
+fn receipt_to_json(receipt: &Receipt) -> Value {
+    let ty = receipt_type(receipt);
+
+    if receipt.success() {
+        match receipt {
+            Receipt::Deploy(receipt) => decode_deploy(receipt, ty),
+            Receipt::Spawn(receipt) => decode_spawn(receipt, ty),
+            Receipt::Call(receipt) => decode_call(receipt, ty),
+            Receipt::Upgrade(receipt) => decode_upgrade(receipt, ty),
+            Receipt::Batch(receipt) => decode_batch(receipt, ty),
+        }
+    } else {
+        let err = match receipt {
+            Receipt::Deploy(receipt) => receipt.error.as_ref(),
+            Receipt::Spawn(receipt) => receipt.error.as_ref(),
+            Receipt::Call(receipt) => receipt.error.as_ref(),
+            Receipt::Upgrade(receipt) => receipt.error.as_ref(),
+            Receipt::Batch(receipt) => receipt.error.as_ref(),
+        };
+
+        decode_error(ty, err, receipt.gas_schedule_version(), receipt.logs())
+    }
 }
 
 fn receipt_type(receipt: &Receipt) -> &'static str {
@@ -41,102 +246,145 @@ fn receipt_type(receipt: &Receipt) -> &'static str {
         Receipt::Deploy(..) => "deploy-template",
         Receipt::Spawn(..) => "spawn-account",
         Receipt::Call(..) => "call-account",
+        Receipt::Upgrade(..) => "upgrade-template",
+        Receipt::Batch(..) => "call-batch",
     }
 }
 
-fn decode_error(ty: &'static str, err: &RuntimeError, logs: &[ReceiptLog]) -> Value {
-    let mut json = {
-        match err {
-            RuntimeError::OOG => json!({
-                "err_type": "oog",
-            }),
-            RuntimeError::TemplateNotFound(template_addr) => json!({
-                "err_type": "template-not-found",
-                "template_addr": TemplateAddrWrapper::from(template_addr),
-            }),
-            RuntimeError::AccountNotFound(account_addr) => json!({
-                "err_type": "account-not-found",
-                "account_addr": AddressWrapper::from(account_addr),
-            }),
-            RuntimeError::CompilationFailed {
-                target: account_addr,
-                template: template_addr,
-                msg,
-            } => json!({
-                "err_type": "compilation-failed",
-                "template_addr": TemplateAddrWrapper::from(template_addr),
-                "account_addr": AddressWrapper::from(account_addr),
-                "message": msg,
-            }),
-            RuntimeError::InstantiationFailed {
-                target: account_addr,
-                template: template_addr,
-                msg,
-            } => json!({
-                "err_type": "instantiation-failed",
-                "template_addr": TemplateAddrWrapper::from(template_addr),
-                "account_addr": AddressWrapper::from(account_addr),
-                "message": msg,
-            }),
-            RuntimeError::FuncNotFound {
-                target: account_addr,
-                template: template_addr,
-                func,
-            } => json!({
-                "err_type": "function-not-found",
-                "template_addr": TemplateAddrWrapper::from(template_addr),
-                "account_addr": AddressWrapper::from(account_addr),
-                "func": func,
-            }),
-            RuntimeError::FuncFailed {
-                target: account_addr,
-                template: template_addr,
-                func,
-                msg,
-            } => json!({
-                "err_type": "function-failed",
-                "template_addr": TemplateAddrWrapper::from(template_addr),
-                "account_addr": AddressWrapper::from(account_addr),
-                "func": func,
-                "message": msg,
-            }),
-            RuntimeError::FuncNotAllowed {
-                target: account_addr,
-                template: template_addr,
-                func,
-                msg,
-            } => json!({
-                "err_type": "function-not-allowed",
-                "template_addr": TemplateAddrWrapper::from(template_addr),
-                "account_addr": AddressWrapper::from(account_addr),
-                "func": func,
-                "message": msg,
-            }),
-            RuntimeError::FuncInvalidSignature {
-                target: account_addr,
-                template: template_addr,
-                func,
-            } => json!({
-                "err_type": "function-invalid-signature",
-                "template_addr": TemplateAddrWrapper::from(template_addr),
-                "account_addr": AddressWrapper::from(account_addr),
-                "func": func,
-            }),
-        }
-    };
-
+/// The name of the binary structure of `receipt` (as used within [`ParseErrorAt`](crate::ParseErrorAt) paths).
+fn decode_error(
+    ty: &'static str,
+    err: Option<&RuntimeError>,
+    gas_schedule_version: u16,
+    logs: &[ReceiptLog],
+) -> Value {
     let logs = json::logs_to_json(logs);
 
-    let map: &mut serde_json::Map<String, Value> = json.as_object_mut().unwrap();
-    let mut map: serde_json::Map<String, Value> = std::mem::take(map);
-
+    let mut map = serde_json::Map::new();
     map.insert("type".into(), Value::String(ty.into()));
     map.insert("success".into(), Value::Bool(false));
+    map.insert("gas_schedule_version".into(), gas_schedule_version.into());
+
+    if let Some(err) = err {
+        let mut json = error_to_json(err);
+        map.extend(std::mem::take(json.as_object_mut().unwrap()));
+    }
+
     map.insert("logs".into(), Value::Array(logs));
 
     map.into()
 }
 
+fn error_to_json(err: &RuntimeError) -> Value {
+    match err {
+        RuntimeError::OOG => json!({
+            "err_type": "oog",
+        }),
+        RuntimeError::TemplateNotFound(template_addr) => json!({
+            "err_type": "template-not-found",
+            "template_addr": TemplateAddrWrapper::from(template_addr),
+        }),
+        RuntimeError::AccountNotFound(account_addr) => json!({
+            "err_type": "account-not-found",
+            "account_addr": AddressWrapper::from(account_addr),
+        }),
+        RuntimeError::CompilationFailed {
+            target: account_addr,
+            template: template_addr,
+            msg,
+        } => json!({
+            "err_type": "compilation-failed",
+            "template_addr": TemplateAddrWrapper::from(template_addr),
+            "account_addr": AddressWrapper::from(account_addr),
+            "message": msg,
+        }),
+        RuntimeError::InstantiationFailed {
+            target: account_addr,
+            template: template_addr,
+            msg,
+        } => json!({
+            "err_type": "instantiation-failed",
+            "template_addr": TemplateAddrWrapper::from(template_addr),
+            "account_addr": AddressWrapper::from(account_addr),
+            "message": msg,
+        }),
+        RuntimeError::FuncNotFound {
+            target: account_addr,
+            template: template_addr,
+            func,
+        } => json!({
+            "err_type": "function-not-found",
+            "template_addr": TemplateAddrWrapper::from(template_addr),
+            "account_addr": AddressWrapper::from(account_addr),
+            "func": func,
+        }),
+        RuntimeError::FuncFailed {
+            target: account_addr,
+            template: template_addr,
+            func,
+            msg,
+        } => json!({
+            "err_type": "function-failed",
+            "template_addr": TemplateAddrWrapper::from(template_addr),
+            "account_addr": AddressWrapper::from(account_addr),
+            "func": func,
+            "message": msg,
+        }),
+        RuntimeError::FuncNotAllowed {
+            target: account_addr,
+            template: template_addr,
+            func,
+            msg,
+        } => json!({
+            "err_type": "function-not-allowed",
+            "template_addr": TemplateAddrWrapper::from(template_addr),
+            "account_addr": AddressWrapper::from(account_addr),
+            "func": func,
+            "message": msg,
+        }),
+        RuntimeError::FuncInvalidSignature {
+            target: account_addr,
+            template: template_addr,
+            func,
+        } => json!({
+            "err_type": "function-invalid-signature",
+            "template_addr": TemplateAddrWrapper::from(template_addr),
+            "account_addr": AddressWrapper::from(account_addr),
+            "func": func,
+        }),
+        RuntimeError::UpgradeNotAllowed {
+            template: template_addr,
+            msg,
+        } => json!({
+            "err_type": "upgrade-not-allowed",
+            "template_addr": TemplateAddrWrapper::from(template_addr),
+            "message": msg,
+        }),
+        RuntimeError::InternalError { msg } => json!({
+            "err_type": "internal-error",
+            "message": msg,
+        }),
+        RuntimeError::InsufficientBalance {
+            target: account_addr,
+            balance,
+            amount,
+        } => json!({
+            "err_type": "insufficient-balance",
+            "account_addr": AddressWrapper::from(account_addr),
+            "balance": balance,
+            "amount": amount,
+        }),
+        RuntimeError::StorageCorrupted {
+            target: account_addr,
+            msg,
+        } => json!({
+            "err_type": "storage-corrupted",
+            "account_addr": AddressWrapper::from(account_addr),
+            "message": msg,
+        }),
+    }
+}
+
 fn decode_deploy(receipt: &DeployReceipt, ty: &'static str) -> Value {
     debug_assert!(receipt.success);
     debug_assert!(receipt.error.is_none());
@@ -144,17 +392,32 @@ fn decode_deploy(receipt: &DeployReceipt, ty: &'static str) -> Value {
     let DeployReceipt {
         addr,
         gas_used,
+        gas_breakdown,
         logs,
         ..
     } = receipt;
 
-    json!({
+    let mut json = json!({
         "type": ty,
         "success": true,
-        "addr": TemplateAddrWrapper::from(addr.as_ref().unwrap()),
-        "gas_used": json::gas_to_json(&gas_used),
-        "logs": json::logs_to_json(&logs),
-    })
+        "gas_schedule_version": receipt.gas_schedule_version,
+    });
+
+    if let Some(addr) = addr {
+        json["addr"] = json!(TemplateAddrWrapper::from(addr));
+    }
+
+    if gas_used.is_some() {
+        json["gas_used"] = json!(json::gas_to_json(&gas_used));
+    }
+
+    json["logs"] = json!(json::logs_to_json(&logs));
+
+    if let Some(breakdown) = gas_breakdown {
+        json["gas_breakdown"] = gas_breakdown_to_json(breakdown);
+    }
+
+    json
 }
 
 fn decode_spawn(receipt: &SpawnReceipt, ty: &'static str) -> Value {
@@ -166,19 +429,50 @@ fn decode_spawn(receipt: &SpawnReceipt, ty: &'static str) -> Value {
         init_state,
         returndata,
         gas_used,
+        gas_breakdown,
         logs,
+        metrics,
+        call,
         ..
     } = receipt;
 
-    json!({
+    let mut json = json!({
         "type": ty,
         "success": true,
-        "account": AddressWrapper::from(account_addr.as_ref().unwrap()),
-        "state": HexBlob(init_state.as_ref().unwrap().as_slice()),
-        "returndata": HexBlob(returndata.as_ref().unwrap()),
-        "gas_used": json::gas_to_json(&gas_used),
-        "logs": json::logs_to_json(&logs),
-    })
+        "gas_schedule_version": receipt.gas_schedule_version,
+    });
+
+    if let Some(account_addr) = account_addr {
+        json["account"] = json!(AddressWrapper::from(account_addr));
+    }
+
+    if let Some(init_state) = init_state {
+        json["state"] = json!(HexBlob(init_state.as_slice()));
+    }
+
+    if let Some(returndata) = returndata {
+        json["returndata"] = json!(HexBlob(returndata));
+    }
+
+    if gas_used.is_some() {
+        json["gas_used"] = json!(json::gas_to_json(&gas_used));
+    }
+
+    json["logs"] = json!(json::logs_to_json(&logs));
+
+    if let Some(breakdown) = gas_breakdown {
+        json["gas_breakdown"] = gas_breakdown_to_json(breakdown);
+    }
+
+    if !metrics.is_empty() {
+        json["metrics"] = metrics_to_json(metrics);
+    }
+
+    if let Some(call) = call {
+        json["call"] = decode_call(call, "call-account");
+    }
+
+    json
 }
 
 fn decode_call(receipt: &CallReceipt, ty: &'static str) -> Value {
@@ -189,26 +483,330 @@ fn decode_call(receipt: &CallReceipt, ty: &'static str) -> Value {
         new_state,
         returndata,
         gas_used,
+        gas_breakdown,
         logs,
+        metrics,
         ..
     } = receipt;
 
-    json!({
+    let mut json = json!({
         "type": ty,
         "success": true,
-        "new_state": HexBlob(new_state.as_ref().unwrap().as_slice()),
-        "returndata": HexBlob(returndata.as_ref().unwrap()),
-        "gas_used": json::gas_to_json(&gas_used),
-        "logs": json::logs_to_json(&logs),
+        "gas_schedule_version": receipt.gas_schedule_version,
+    });
+
+    if let Some(new_state) = new_state {
+        json["new_state"] = json!(HexBlob(new_state.as_slice()));
+    }
+
+    if let Some(returndata) = returndata {
+        json["returndata"] = json!(HexBlob(returndata));
+    }
+
+    if gas_used.is_some() {
+        json["gas_used"] = json!(json::gas_to_json(&gas_used));
+    }
+
+    json["logs"] = json!(json::logs_to_json(&logs));
+
+    if let Some(breakdown) = gas_breakdown {
+        json["gas_breakdown"] = gas_breakdown_to_json(breakdown);
+    }
+
+    if !metrics.is_empty() {
+        json["metrics"] = metrics_to_json(metrics);
+    }
+
+    json
+}
+
+fn decode_upgrade(receipt: &UpgradeReceipt, ty: &'static str) -> Value {
+    debug_assert!(receipt.success);
+    debug_assert!(receipt.error.is_none());
+
+    let UpgradeReceipt {
+        addr,
+        template_version,
+        gas_used,
+        gas_breakdown,
+        logs,
+        ..
+    } = receipt;
+
+    let mut json = json!({
+        "type": ty,
+        "success": true,
+        "gas_schedule_version": receipt.gas_schedule_version,
+    });
+
+    if let Some(addr) = addr {
+        json["addr"] = json!(TemplateAddrWrapper::from(addr));
+    }
+
+    json["template_version"] = json!(template_version);
+
+    if gas_used.is_some() {
+        json["gas_used"] = json!(json::gas_to_json(&gas_used));
+    }
+
+    json["logs"] = json!(json::logs_to_json(&logs));
+
+    if let Some(breakdown) = gas_breakdown {
+        json["gas_breakdown"] = gas_breakdown_to_json(breakdown);
+    }
+
+    json
+}
+
+fn decode_batch(receipt: &BatchReceipt, ty: &'static str) -> Value {
+    debug_assert!(receipt.success);
+    debug_assert!(receipt.error.is_none());
+
+    let BatchReceipt {
+        new_state,
+        receipts,
+        gas_used,
+        gas_breakdown,
+        logs,
+        ..
+    } = receipt;
+
+    let mut json = json!({
+        "type": ty,
+        "success": true,
+        "gas_schedule_version": receipt.gas_schedule_version,
+    });
+
+    if let Some(new_state) = new_state {
+        json["new_state"] = json!(HexBlob(new_state.as_slice()));
+    }
+
+    if gas_used.is_some() {
+        json["gas_used"] = json!(json::gas_to_json(&gas_used));
+    }
+
+    json["logs"] = json!(json::logs_to_json(&logs));
+
+    if let Some(breakdown) = gas_breakdown {
+        json["gas_breakdown"] = gas_breakdown_to_json(breakdown);
+    }
+
+    let receipts = receipts
+        .iter()
+        .map(|call| decode_call(call, "call-account"))
+        .collect::<Vec<_>>();
+    json["receipts"] = Value::Array(receipts);
+
+    json
+}
+
+fn gas_breakdown_to_json(breakdown: &GasBreakdown) -> Value {
+    json!({
+        "intrinsic": breakdown.intrinsic,
+        "execution": breakdown.execution,
+        "storage": breakdown.storage,
+        "logs": breakdown.logs,
+        "refund": breakdown.refund,
     })
 }
 
+fn metrics_to_json(metrics: &[ReceiptMetric]) -> Value {
+    let metrics: Vec<Value> = metrics
+        .iter()
+        .map(|metric| json!({ "id": metric.id, "value": metric.value }))
+        .collect();
+
+    Value::Array(metrics)
+}
+
+fn deploy_from_client(receipt: DeployTemplateReceipt) -> Result<DeployReceipt, JsonError> {
+    let error = error_from_client(receipt.success, receipt.error)?;
+    let addr = success_field(receipt.success, receipt.addr, "addr")?;
+
+    let mut deploy = DeployReceipt {
+        version: 0,
+        success: receipt.success,
+        error,
+        addr,
+        gas_used: gas_from_client(receipt.gas_used),
+        gas_breakdown: None,
+        gas_schedule_version: 0,
+        logs: receipt.logs,
+    };
+
+    if let Some(breakdown) = receipt.gas_breakdown {
+        deploy.set_gas_breakdown(breakdown);
+    }
+    deploy.set_gas_schedule_version(receipt.gas_schedule_version);
+    deploy.set_logs_version();
+
+    Ok(deploy)
+}
+
+fn spawn_from_client(receipt: SpawnAccountReceipt) -> Result<SpawnReceipt, JsonError> {
+    let error = error_from_client(receipt.success, receipt.error)?;
+    let account_addr = success_field(receipt.success, receipt.account, "account")?;
+    let init_state = success_field(receipt.success, receipt.state, "state")?;
+    let call = match receipt.call {
+        Some(call) => Some(call_from_client(call)?),
+        None => None,
+    };
+
+    let mut spawn = SpawnReceipt {
+        version: 7,
+        success: receipt.success,
+        error,
+        account_addr,
+        init_state,
+        returndata: receipt.returndata,
+        gas_used: gas_from_client(receipt.gas_used),
+        gas_breakdown: None,
+        gas_schedule_version: 0,
+        logs_bloom: None,
+        logs: receipt.logs,
+        metrics: Vec::new(),
+        call,
+    };
+
+    if let Some(breakdown) = receipt.gas_breakdown {
+        spawn.set_gas_breakdown(breakdown);
+    }
+    spawn.set_metrics(receipt.metrics);
+    spawn.set_gas_schedule_version(receipt.gas_schedule_version);
+    spawn.set_logs_version();
+
+    Ok(spawn)
+}
+
+fn call_from_client(receipt: CallAccountReceipt) -> Result<CallReceipt, JsonError> {
+    let error = error_from_client(receipt.success, receipt.error)?;
+    let new_state = success_field(receipt.success, receipt.new_state, "new_state")?;
+
+    let mut call = CallReceipt {
+        version: 0,
+        success: receipt.success,
+        error,
+        new_state,
+        returndata: receipt.returndata,
+        gas_used: gas_from_client(receipt.gas_used),
+        gas_breakdown: None,
+        gas_schedule_version: 0,
+        logs_bloom: None,
+        logs: receipt.logs,
+        metrics: Vec::new(),
+    };
+
+    if let Some(breakdown) = receipt.gas_breakdown {
+        call.set_gas_breakdown(breakdown);
+    }
+    call.set_metrics(receipt.metrics);
+    call.set_gas_schedule_version(receipt.gas_schedule_version);
+    call.set_logs_version();
+
+    Ok(call)
+}
+
+fn upgrade_from_client(receipt: UpgradeTemplateReceipt) -> Result<UpgradeReceipt, JsonError> {
+    let error = error_from_client(receipt.success, receipt.error)?;
+    let addr = success_field(receipt.success, receipt.addr, "addr")?;
+    let template_version = success_field(
+        receipt.success,
+        receipt.template_version,
+        "template_version",
+    )?;
+
+    let mut upgrade = UpgradeReceipt {
+        version: 0,
+        success: receipt.success,
+        error,
+        addr,
+        template_version: template_version.unwrap_or(0),
+        gas_used: gas_from_client(receipt.gas_used),
+        gas_breakdown: None,
+        gas_schedule_version: 0,
+        logs: receipt.logs,
+    };
+
+    if let Some(breakdown) = receipt.gas_breakdown {
+        upgrade.set_gas_breakdown(breakdown);
+    }
+    upgrade.set_gas_schedule_version(receipt.gas_schedule_version);
+    upgrade.set_logs_version();
+
+    Ok(upgrade)
+}
+
+fn batch_from_client(receipt: CallBatchReceipt) -> Result<BatchReceipt, JsonError> {
+    let error = error_from_client(receipt.success, receipt.error)?;
+    let new_state = success_field(receipt.success, receipt.new_state, "new_state")?;
+    let receipts = receipt
+        .receipts
+        .into_iter()
+        .map(call_from_client)
+        .collect::<Result<_, _>>()?;
+
+    let mut batch = BatchReceipt {
+        version: 0,
+        success: receipt.success,
+        error,
+        new_state,
+        receipts,
+        gas_used: gas_from_client(receipt.gas_used),
+        gas_breakdown: None,
+        gas_schedule_version: 0,
+        logs: receipt.logs,
+    };
+
+    if let Some(breakdown) = receipt.gas_breakdown {
+        batch.set_gas_breakdown(breakdown);
+    }
+    batch.set_gas_schedule_version(receipt.gas_schedule_version);
+    batch.set_logs_version();
+
+    Ok(batch)
+}
+
+/// A failed receipt can't be encoded without its error.
+fn error_from_client(
+    success: bool,
+    error: Option<RuntimeError>,
+) -> Result<Option<RuntimeError>, JsonError> {
+    match (success, error) {
+        (true, _) => Ok(None),
+        (false, Some(err)) => Ok(Some(err)),
+        (false, None) => Err(JsonError::MissingField {
+            field_name: "err_type".to_string(),
+        }),
+    }
+}
+
+/// A successful receipt can't be encoded without the fields identifying its outcome.
+fn success_field<T>(
+    success: bool,
+    value: Option<T>,
+    field_name: &str,
+) -> Result<Option<T>, JsonError> {
+    match (success, value) {
+        (false, _) => Ok(None),
+        (true, Some(value)) => Ok(Some(value)),
+        (true, None) => Err(JsonError::MissingField {
+            field_name: field_name.to_string(),
+        }),
+    }
+}
+
+fn gas_from_client(gas_used: Option<u64>) -> Gas {
+    gas_used.map_or_else(Gas::new, Gas::with)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use svm_types::{Address, Gas, ReceiptLog, State, TemplateAddr};
 
+    use crate::{Field, ParseError, ParseErrorAt};
+
     #[test]
     fn decode_receipt_deploy_success() {
         let template = TemplateAddr::repeat(0x10);
@@ -224,6 +822,8 @@ mod tests {
             error: None,
             addr: Some(template),
             gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
             logs,
         };
 
@@ -235,6 +835,7 @@ mod tests {
             json,
             json!({
                 "success": true,
+                "gas_schedule_version": 0,
                 "type": "deploy-template",
                 "addr": "1010101010101010101010101010101010101010",
                 "gas_used": 10,
@@ -246,6 +847,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decode_receipt_upgrade_success() {
+        let template = TemplateAddr::repeat(0x10);
+
+        let mut receipt = UpgradeReceipt::new(template, 1, Gas::with(10));
+        receipt.logs = vec![ReceiptLog::new(b"Log entry #1".to_vec())];
+
+        let bytes = crate::receipt::encode_upgrade(&receipt);
+        let data = HexBlob(&bytes);
+        let json = decode_receipt(&json!({ "data": data }).to_string()).unwrap();
+
+        assert_eq!(
+            json,
+            json!({
+                "success": true,
+                "gas_schedule_version": 0,
+                "type": "upgrade-template",
+                "addr": "1010101010101010101010101010101010101010",
+                "template_version": 1,
+                "gas_used": 10,
+                "logs": [
+                    {"data": "Log entry #1"}
+                ]
+            })
+        );
+
+        let encoded = encode_receipt_raw(&json.to_string()).unwrap();
+        assert_eq!(encoded, bytes);
+    }
+
+    #[test]
+    fn decode_receipt_batch_success() {
+        let call = CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            new_state: Some(State::repeat(0xA0)),
+            returndata: Some(vec![0x10]),
+            gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: vec![ReceiptLog::new(b"Log entry #1".to_vec())],
+            metrics: Vec::new(),
+        };
+        let receipt = BatchReceipt::new(vec![call], Gas::with(20));
+
+        let bytes = crate::receipt::encode_batch(&receipt);
+        let data = HexBlob(&bytes);
+        let json = decode_receipt(&json!({ "data": data }).to_string()).unwrap();
+
+        assert_eq!(
+            json,
+            json!({
+                "type": "call-batch",
+                "success": true,
+                "gas_schedule_version": 0,
+                "new_state": "A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0",
+                "gas_used": 20,
+                "logs": [{"data": "Log entry #1"}],
+                "receipts": [{
+                    "type": "call-account",
+                    "success": true,
+                    "gas_schedule_version": 0,
+                    "new_state": "A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0",
+                    "returndata": "10",
+                    "gas_used": 10,
+                    "logs": [{"data": "Log entry #1"}]
+                }]
+            })
+        );
+
+        let encoded = encode_receipt_raw(&json.to_string()).unwrap();
+        assert_eq!(encoded, bytes);
+    }
+
     #[test]
     fn decode_receipt_spawn_success() {
         let account = Address::repeat(0x10);
@@ -264,7 +941,12 @@ mod tests {
             init_state: Some(state),
             returndata: Some(vec![0x10, 0x20, 0x30]),
             gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
             logs,
+            metrics: Vec::new(),
+            call: None,
         };
 
         let bytes = crate::receipt::encode_spawn(&receipt);
@@ -275,6 +957,7 @@ mod tests {
             json,
             json!({
                 "success": true,
+                "gas_schedule_version": 0,
                 "type": "spawn-account",
                 "account": "1010101010101010101010101010101010101010",
                 "gas_used": 10,
@@ -300,7 +983,12 @@ mod tests {
             init_state: None,
             returndata: None,
             gas_used: Gas::with(1000),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
             logs,
+            metrics: Vec::new(),
+            call: None,
         };
 
         let bytes = crate::receipt::encode_spawn(&receipt);
@@ -312,6 +1000,7 @@ mod tests {
             json!({
                "type": "spawn-account",
                "success": false,
+               "gas_schedule_version": 0,
                "err_type": "oog",
                "logs": [{"data": "Reached OOG"}],
             })
@@ -334,7 +1023,11 @@ mod tests {
             new_state: Some(state),
             returndata: Some(vec![0x10, 0x20]),
             gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
             logs,
+            metrics: Vec::new(),
         };
 
         let bytes = crate::receipt::encode_call(&receipt);
@@ -345,6 +1038,7 @@ mod tests {
             json,
             json!({
                 "success": true,
+                "gas_schedule_version": 0,
                 "type": "call-account",
                 "gas_used": 10,
                 "returndata": "1020",
@@ -356,4 +1050,402 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn decode_receipt_with_layer_timestamp() {
+        let receipt = CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            new_state: Some(State::repeat(0xA0)),
+            returndata: Some(vec![0x10, 0x20]),
+            gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: Vec::new(),
+            metrics: Vec::new(),
+        };
+
+        let bytes = crate::receipt::encode_call(&receipt);
+        let data = HexBlob(&bytes);
+
+        let json = decode_receipt(
+            &json!({
+                "data": data,
+                "layer": 10,
+                "layer_time": { "genesis_time": 1622505600, "layer_duration": 30 }
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let keys: Vec<&str> = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|k| k.as_str())
+            .collect();
+        assert_eq!(&keys[keys.len() - 2..], ["layer", "timestamp"]);
+        assert_eq!(json["layer"], json!(10));
+        assert_eq!(json["timestamp"], json!("2021-06-01T00:05:00Z"));
+
+        let json = decode_receipt(
+            &json!({
+                "data": data,
+                "layer": 11,
+                "layer_time": { "layers": { "10": 1622505900 } }
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(json["layer"], json!(11));
+        assert!(json.get("timestamp").is_none());
+
+        let json = decode_receipt(&json!({ "data": data }).to_string()).unwrap();
+
+        assert!(json.get("layer").is_none());
+        assert!(json.get("timestamp").is_none());
+    }
+
+    #[test]
+    fn decode_receipt_call_success_with_gas_breakdown() {
+        let mut receipt = CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            new_state: Some(State::repeat(0xA0)),
+            returndata: Some(Vec::new()),
+            gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: Vec::new(),
+            metrics: Vec::new(),
+        };
+        receipt.set_gas_breakdown(GasBreakdown {
+            intrinsic: 4,
+            execution: 3,
+            storage: 2,
+            logs: 1,
+            refund: 0,
+        });
+
+        let bytes = crate::receipt::encode_call(&receipt);
+        let data = HexBlob(&bytes);
+        let json = decode_receipt(&json!({ "data": data }).to_string()).unwrap();
+
+        assert_eq!(
+            json["gas_breakdown"],
+            json!({
+                "intrinsic": 4,
+                "execution": 3,
+                "storage": 2,
+                "logs": 1,
+                "refund": 0
+            })
+        );
+    }
+
+    #[test]
+    fn decode_receipt_call_with_event_logs() {
+        let mut receipt = CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            new_state: Some(State::repeat(0xA0)),
+            returndata: Some(Vec::new()),
+            gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: vec![
+                ReceiptLog::new(b"Log entry #1".to_vec()),
+                ReceiptLog::event(7, vec![[0xAB; 32]], b"Transfer".to_vec()),
+            ],
+            metrics: Vec::new(),
+        };
+        receipt.set_logs_version();
+        assert_eq!(receipt.version, svm_types::EVENT_LOGS_VERSION);
+
+        let bytes = crate::receipt::encode_call(&receipt);
+        let data = HexBlob(&bytes);
+        let json = decode_receipt(&json!({ "data": data }).to_string()).unwrap();
+
+        assert_eq!(
+            json["logs"],
+            json!([
+                {"data": "Log entry #1"},
+                {
+                    "code": 7,
+                    "topics": ["ABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABAB"],
+                    "data": "Transfer"
+                }
+            ])
+        );
+
+        let encoded = encode_receipt_raw(&json.to_string()).unwrap();
+        assert_eq!(encoded, bytes);
+    }
+
+    #[test]
+    fn decode_receipt_call_golden() {
+        let mut receipt = CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            new_state: Some(State::repeat(0xA0)),
+            returndata: Some(vec![0x10, 0x20]),
+            gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: vec![ReceiptLog::new(b"Log entry #1".to_vec())],
+            metrics: Vec::new(),
+        };
+        receipt.set_gas_breakdown(GasBreakdown {
+            intrinsic: 4,
+            execution: 3,
+            storage: 2,
+            logs: 1,
+            refund: 0,
+        });
+
+        let bytes = crate::receipt::encode_call(&receipt);
+        let data = HexBlob(&bytes);
+        let json = decode_receipt(&json!({ "data": data }).to_string()).unwrap();
+
+        let golden = r#"{"type":"call-account","success":true,"gas_schedule_version":0,"new_state":"A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0","returndata":"1020","gas_used":10,"logs":[{"data":"Log entry #1"}],"gas_breakdown":{"intrinsic":4,"execution":3,"storage":2,"logs":1,"refund":0}}"#;
+        assert_eq!(json.to_string(), golden);
+    }
+
+    #[test]
+    fn decode_receipt_error_golden() {
+        let receipt = CallReceipt {
+            version: 0,
+            success: false,
+            error: Some(RuntimeError::FuncFailed {
+                target: Address::repeat(0x10),
+                template: TemplateAddr::repeat(0x20),
+                func: "run".to_string(),
+                msg: "Reached the end".to_string(),
+            }),
+            new_state: None,
+            returndata: None,
+            gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: vec![ReceiptLog::new(b"Log entry #1".to_vec())],
+            metrics: Vec::new(),
+        };
+
+        let bytes = crate::receipt::encode_call(&receipt);
+        let data = HexBlob(&bytes);
+        let json = decode_receipt(&json!({ "data": data }).to_string()).unwrap();
+
+        let golden = r#"{"type":"call-account","success":false,"gas_schedule_version":0,"err_type":"function-failed","template_addr":"2020202020202020202020202020202020202020","account_addr":"1010101010101010101010101010101010101010","func":"run","message":"Reached the end","logs":[{"data":"Log entry #1"}]}"#;
+        assert_eq!(json.to_string(), golden);
+    }
+
+    #[test]
+    fn decode_receipt_truncated() {
+        let receipt = CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            new_state: Some(State::repeat(0xA0)),
+            returndata: Some(vec![0x10, 0x20]),
+            gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: Vec::new(),
+            metrics: Vec::new(),
+        };
+
+        let bytes = crate::receipt::encode_call(&receipt);
+        let data = HexBlob(&bytes[..bytes.len() - 4]);
+
+        let err = decode_receipt(&json!({ "data": data }).to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidBinary {
+                path: "data".to_string(),
+                error: ParseErrorAt {
+                    error: ParseError::NotEnoughBytes(Field::GasUsed),
+                    offset: 40,
+                    path: "CallReceipt.gas_used".to_string(),
+                    expected: Some(8),
+                    available: 5,
+                }
+            }
+        );
+
+        let json = decode_receipt(&json!({ "data": data, "tolerant": true }).to_string()).unwrap();
+
+        let golden = r#"{"type":"call-account","success":true,"gas_schedule_version":0,"new_state":"A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0","returndata":"1020","logs":[],"partial":{"offset":40,"message":"Not enough bytes for field `GasUsed`"}}"#;
+        assert_eq!(json.to_string(), golden);
+    }
+
+    #[test]
+    fn decode_receipt_tolerant_invalid_header() {
+        let data = HexBlob(vec![0xFF, 0x00]);
+
+        let json = decode_receipt(&json!({ "data": data, "tolerant": true }).to_string()).unwrap();
+
+        assert_eq!(
+            json,
+            json!({
+                "partial": {
+                    "offset": 0,
+                    "message": "Feature `ReceiptType` is not supported yet"
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn decode_receipt_tolerant_complete() {
+        let receipt = DeployReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            addr: Some(TemplateAddr::repeat(0x10)),
+            gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs: Vec::new(),
+        };
+
+        let bytes = crate::receipt::encode_deploy(&receipt);
+        let data = HexBlob(&bytes);
+
+        let strict = decode_receipt(&json!({ "data": data }).to_string()).unwrap();
+        let tolerant =
+            decode_receipt(&json!({ "data": data, "tolerant": true }).to_string()).unwrap();
+
+        assert_eq!(strict, tolerant);
+    }
+
+    fn encode_decode(json: &str) -> String {
+        let encoded = encode_receipt(json).unwrap();
+        let data = &encoded["data"];
+
+        decode_receipt(&json!({ "data": data }).to_string())
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn encode_receipt_golden_round_trip() {
+        let call = r#"{"type":"call-account","success":true,"gas_schedule_version":0,"new_state":"A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0","returndata":"1020","gas_used":10,"logs":[{"data":"Log entry #1"}],"gas_breakdown":{"intrinsic":4,"execution":3,"storage":2,"logs":1,"refund":0}}"#;
+        let error = r#"{"type":"call-account","success":false,"gas_schedule_version":0,"err_type":"function-failed","template_addr":"2020202020202020202020202020202020202020","account_addr":"1010101010101010101010101010101010101010","func":"run","message":"Reached the end","logs":[{"data":"Log entry #1"}]}"#;
+        let deploy = r#"{"type":"deploy-template","success":true,"gas_schedule_version":3,"addr":"1010101010101010101010101010101010101010","gas_used":10,"logs":[]}"#;
+
+        assert_eq!(encode_decode(call), call);
+        assert_eq!(encode_decode(error), error);
+        assert_eq!(encode_decode(deploy), deploy);
+    }
+
+    #[test]
+    fn encode_receipt_spawn_with_call() {
+        let json = r#"{"type":"spawn-account","success":true,"gas_schedule_version":1,"account":"1010101010101010101010101010101010101010","state":"A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0","returndata":"","gas_used":100,"logs":[],"call":{"type":"call-account","success":true,"gas_schedule_version":1,"new_state":"B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0","returndata":"10","gas_used":20,"logs":[{"data":"called"}]}}"#;
+
+        assert_eq!(encode_decode(json), json);
+    }
+
+    #[test]
+    fn encode_receipt_with_metrics() {
+        let json = r#"{"type":"spawn-account","success":true,"gas_schedule_version":0,"account":"1010101010101010101010101010101010101010","state":"A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0","returndata":"","gas_used":100,"logs":[],"metrics":[{"id":1,"value":80}],"call":{"type":"call-account","success":true,"gas_schedule_version":0,"new_state":"B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0B0","returndata":"10","gas_used":20,"logs":[],"metrics":[{"id":2,"value":18446744073709551615}]}}"#;
+
+        assert_eq!(encode_decode(json), json);
+
+        let bytes = encode_receipt_raw(json).unwrap();
+        let receipt = crate::receipt::decode_spawn(&bytes);
+
+        assert_eq!(receipt.version, crate::receipt::CALL_RECEIPT_VERSION);
+        assert_eq!(receipt.metrics(), &[ReceiptMetric::new(1, 80)]);
+    }
+
+    #[test]
+    fn encode_receipt_matches_binary_encoding() {
+        let receipt = CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            new_state: Some(State::repeat(0xA0)),
+            returndata: Some(vec![0x10, 0x20]),
+            gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: Vec::new(),
+            metrics: Vec::new(),
+        };
+        let bytes = crate::receipt::encode_call(&receipt);
+
+        let json = json!({
+            "type": "call-account",
+            "success": true,
+            "new_state": "A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0",
+            "returndata": "1020",
+            "gas_used": 10,
+            "echo": 7
+        });
+        let encoded = encode_receipt(&json.to_string()).unwrap();
+
+        assert_eq!(
+            encoded,
+            json!({
+                "data": HexBlob(&bytes),
+                "schema_version": json::SCHEMA_VERSION,
+                "echo": 7
+            })
+        );
+    }
+
+    #[test]
+    fn encode_receipt_missing_fields() {
+        let json = json!({
+            "type": "call-account",
+            "success": true,
+            "gas_used": 10
+        });
+        let err = encode_receipt(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::MissingField {
+                field_name: "new_state".to_string()
+            }
+        );
+
+        let json = json!({
+            "type": "deploy-template",
+            "success": false
+        });
+        let err = encode_receipt(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::MissingField {
+                field_name: "err_type".to_string()
+            }
+        );
+
+        let json = json!({
+            "type": "call-account",
+            "success": false,
+            "err_type": "function-failed",
+            "func": "run",
+            "message": "Reached the end"
+        });
+        let err = encode_receipt(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::MissingField {
+                field_name: "account_addr".to_string()
+            }
+        );
+    }
 }
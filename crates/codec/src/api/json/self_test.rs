@@ -0,0 +1,136 @@
+use serde_json::{json, Value as Json};
+
+use super::inputdata::DecodedInputData;
+use super::serde_types::HexBlob;
+use crate::api::json::{self, JsonError};
+
+/// A golden vector embedded into the codec.
+///
+/// When `encode` is given, `json` is expected to be encoded into `data` (hex-encoded).
+/// In any case, `data` is expected to be decoded back into `json`.
+struct GoldenVector {
+    name: &'static str,
+    encode: Option<fn(&str) -> Result<Vec<u8>, JsonError>>,
+    decode: fn(&str) -> Result<Json, JsonError>,
+    json: &'static str,
+    data: &'static str,
+}
+
+const GOLDEN_VECTORS: &[GoldenVector] = &[
+    GoldenVector {
+        name: "inputdata",
+        encode: Some(encode_inputdata_raw),
+        decode: json::decode_inputdata,
+        json: r#"{"abi":["u8",["bool"],"amount",{"tuple":["i32","string"]}],"data":[7,[true,false],100,[-1,"svm"]]}"#,
+        data: "120726100001642633FFFFFFFF500373766D",
+    },
+    GoldenVector {
+        name: "envelope",
+        encode: Some(json::encode_envelope_raw),
+        decode: json::decode_envelope,
+        json: r#"{"principal":"10203040506070809000A0B0C0D0E0F0ABCDEFFF","amount":10,"gas_limit":1000,"gas_fee":1}"#,
+        data: "10203040506070809000A0B0C0D0E0F0ABCDEFFF000000000000000A00000000000003E80000000000000001",
+    },
+    GoldenVector {
+        name: "spawn",
+        encode: Some(json::encode_spawn_raw),
+        decode: json::decode_spawn,
+        json: r#"{"version":1,"template":"10203040506070809000A0B0C0D0E0F0ABCDEFFF","name":"My Account","ctor_name":"initialize","calldata":{"abi":["i32","i64"],"data":[10,20]}}"#,
+        data: "000110203040506070809000A0B0C0D0E0F0ABCDEFFF0A4D79204163636F756E740A696E697469616C697A6504030A0414",
+    },
+    GoldenVector {
+        name: "call",
+        encode: Some(json::encode_call_raw),
+        decode: json::decode_call,
+        json: r#"{"version":0,"target":"10203040506070809000A0B0C0D0E0F0ABCDEFFF","func_name":"do_something","verifydata":{"abi":["bool"],"data":[true]},"calldata":{"abi":["i32","address"],"data":[10,"10203040506070809000A0B0C0D0E0F0ABCDEFFF"]}}"#,
+        data: "000010203040506070809000A0B0C0D0E0F0ABCDEFFF0C646F5F736F6D657468696E67011017030A4010203040506070809000A0B0C0D0E0F0ABCDEFFF",
+    },
+    GoldenVector {
+        name: "receipt",
+        encode: Some(json::encode_receipt_raw),
+        decode: json::decode_receipt,
+        json: r#"{"type":"call-account","success":false,"gas_schedule_version":0,"err_type":"function-failed","template_addr":"2020202020202020202020202020202020202020","account_addr":"1010101010101010101010101010101010101010","func":"run","message":"Reached the end","logs":[{"data":"Log entry #1"}]}"#,
+        data: "020000000601000C4C6F6720656E747279202331202020202020202020202020202020202020202010101010101010101010101010101010101010100372756E0F526561636865642074686520656E64",
+    },
+];
+
+/// Runs the golden vectors embedded into the codec (encode and decode round trips),
+/// and returns a report of the form:
+///
+/// ```json
+/// {
+///   "codec_version": "0.0.0",
+///   "schema_version": 0,
+///   "passed": false,
+///   "vectors": [
+///     { "name": "envelope", "passed": true },
+///     { "name": "call", "passed": false, "error": "..." },
+///     ...
+///   ]
+/// }
+/// ```
+///
+/// It's meant for verifying that a deployed `svm_codec.wasm` behaves just like
+/// the version of this crate it has been built from.
+pub fn self_test() -> Json {
+    let vectors: Vec<Json> = GOLDEN_VECTORS
+        .iter()
+        .map(|vector| match run_vector(vector) {
+            Ok(()) => json!({ "name": vector.name, "passed": true }),
+            Err(error) => json!({ "name": vector.name, "passed": false, "error": error }),
+        })
+        .collect();
+
+    let passed = vectors.iter().all(|vector| vector["passed"] == true);
+
+    json!({
+        "codec_version": env!("CARGO_PKG_VERSION"),
+        "schema_version": json::SCHEMA_VERSION,
+        "passed": passed,
+        "vectors": vectors,
+    })
+}
+
+fn run_vector(vector: &GoldenVector) -> Result<(), String> {
+    if let Some(encode) = vector.encode {
+        let bytes = encode(vector.json).map_err(|err| format!("encode failed: {}", err))?;
+        let data = hex::encode_upper(&bytes);
+
+        if data != vector.data {
+            return Err(format!("encoded into {} (expected {})", data, vector.data));
+        }
+    }
+
+    let data = json!({ "data": HexBlob(hex::decode(vector.data).unwrap()) }).to_string();
+    let decoded = (vector.decode)(&data).map_err(|err| format!("decode failed: {}", err))?;
+    let decoded = decoded.to_string();
+
+    if decoded != vector.json {
+        return Err(format!(
+            "decoded into {} (expected {})",
+            decoded, vector.json
+        ));
+    }
+
+    Ok(())
+}
+
+fn encode_inputdata_raw(json: &str) -> Result<Vec<u8>, JsonError> {
+    DecodedInputData::new(json)?.encode()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_self_test_passes() {
+        let report = self_test();
+
+        assert_eq!(report["passed"], true, "{}", report);
+        assert_eq!(
+            report["vectors"].as_array().unwrap().len(),
+            GOLDEN_VECTORS.len()
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as Json};
+
+use std::io::Cursor;
+
+use svm_types::Envelope;
+
+use super::envelope::DecodedEnvelope;
+use super::gas::{encode_draft, TxType};
+use super::serde_types::HexBlob;
+use super::JsonSerdeUtils;
+use crate::api::json::JsonError;
+use crate::{call, signing, spawn, template};
+
+/// Given a transaction's `Envelope` and message, returns the signing payload
+/// (i.e the exact bytes the `principal` should sign) and its hash.
+///
+/// ```json
+/// {
+///   "tx_type": "call",    // one of `deploy`, `spawn` or `call`
+///   "envelope": {         // as given to `encode_envelope`
+///     "principal": "A2FB...",
+///     "amount": 10,
+///     "gas_limit": 1000,
+///     "gas_fee": 1
+///   },
+///   "data": "FFC103...",  // the binary message
+///   "tx": { ... }         // or its JSON (as given to the encode endpoint of `tx_type`)
+/// }
+/// ```
+///
+/// The payload is derived by [`crate::signing`], just like it's derived by the signature verifiers.
+/// An optional `echo` field is returned verbatim within the result.
+///
+/// Result:
+///
+/// ```json
+/// {
+///   "data": "0002A2FB...",  // the signing payload
+///   "hash": "4D1E...",      // its hash (the signed bytes)
+///   "schema_version": 0,
+///   "echo": ...
+/// }
+/// ```
+pub fn signing_payload(json: &str) -> Result<Json, JsonError> {
+    let request = SigningRequest::from_json_str(json)?;
+    let message = encode_draft(request.tx_type, request.data.as_ref(), request.tx.as_ref())?;
+
+    let tx_type = validate_message(request.tx_type, &message)?;
+
+    let envelope = Envelope::from(request.envelope);
+    let payload = signing::payload(tx_type, &envelope, &message);
+    let hash = signing::hash(&payload);
+
+    let mut json = super::encode_response(payload, super::echo_of(json));
+    json["hash"] = json!(HexBlob(&hash[..]));
+
+    Ok(super::canonical_order(
+        json,
+        &["data", "hash", "schema_version", "echo"],
+    ))
+}
+
+/// Returns the [`signing::TxType`] of `message` (or `None` when it isn't a valid message of `tx_type`).
+pub(super) fn validate_message(
+    tx_type: TxType,
+    message: &[u8],
+) -> Result<signing::TxType, JsonError> {
+    let mut cursor = Cursor::new(message);
+
+    let result = match tx_type {
+        TxType::Deploy => template::decode_at(message).map(|_| ()),
+        TxType::Spawn => spawn::decode(&mut cursor)
+            .map(|_| ())
+            .map_err(|err| err.at("SpawnAccount", &cursor)),
+        TxType::Call => call::decode_call(&mut cursor)
+            .map(|_| ())
+            .map_err(|err| err.at("CallAccount", &cursor)),
+    };
+
+    result.map_err(|error| JsonError::InvalidBinary {
+        path: "data".to_string(),
+        error,
+    })?;
+
+    let tx_type = match tx_type {
+        TxType::Deploy => signing::TxType::Deploy,
+        TxType::Spawn => signing::TxType::Spawn,
+        TxType::Call => signing::TxType::Call,
+    };
+
+    Ok(tx_type)
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct SigningRequest {
+    tx_type: TxType,
+    envelope: DecodedEnvelope,
+    #[serde(default)]
+    data: Option<HexBlob<Vec<u8>>>,
+    #[serde(default)]
+    tx: Option<Json>,
+}
+
+impl JsonSerdeUtils for SigningRequest {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call_tx() -> Json {
+        json!({
+            "version": 0,
+            "target": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "func_name": "do_something",
+            "verifydata": {"abi": [], "data": []},
+            "calldata": {"abi": ["i8"], "data": [3]},
+        })
+    }
+
+    fn envelope() -> Json {
+        json!({
+            "principal": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "amount": 10,
+            "gas_limit": 1000,
+            "gas_fee": 1
+        })
+    }
+
+    #[test]
+    fn json_signing_payload_valid() {
+        let message = super::super::encode_call_raw(&call_tx().to_string()).unwrap();
+        let envelope_bytes = super::super::encode_envelope_raw(&envelope().to_string()).unwrap();
+
+        let json = json!({
+            "tx_type": "call",
+            "envelope": envelope(),
+            "data": HexBlob(&message),
+            "echo": 7,
+        });
+        let json = signing_payload(&json.to_string()).unwrap();
+
+        let mut expected = vec![signing::VERSION, 2];
+        expected.extend_from_slice(&envelope_bytes);
+        expected.extend_from_slice(&message);
+
+        assert_eq!(
+            json,
+            json!({
+                "data": HexBlob(&expected),
+                "hash": HexBlob(&signing::hash(&expected)[..]),
+                "schema_version": 0,
+                "echo": 7,
+            })
+        );
+    }
+
+    #[test]
+    fn json_signing_payload_draft_tx() {
+        let message = super::super::encode_call_raw(&call_tx().to_string()).unwrap();
+
+        let from_data = json!({
+            "tx_type": "call",
+            "envelope": envelope(),
+            "data": HexBlob(&message),
+        });
+        let from_tx = json!({
+            "tx_type": "call",
+            "envelope": envelope(),
+            "tx": call_tx(),
+        });
+
+        assert_eq!(
+            signing_payload(&from_data.to_string()).unwrap(),
+            signing_payload(&from_tx.to_string()).unwrap()
+        );
+    }
+
+    #[test]
+    fn json_signing_payload_invalid_message() {
+        let spawn_tx = json!({
+            "version": crate::spawn::SPAWN_CALL_VERSION,
+            "template": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "name": "My Account",
+            "ctor_name": "initialize",
+            "calldata": "",
+        });
+        let mut message = super::super::encode_spawn_raw(&spawn_tx.to_string()).unwrap();
+
+        // `Has Call` must be either `0` or `1`
+        *message.last_mut().unwrap() = 2;
+
+        let json = json!({
+            "tx_type": "spawn",
+            "envelope": envelope(),
+            "data": HexBlob(&message),
+        });
+
+        let err = signing_payload(&json.to_string()).unwrap_err();
+        assert!(matches!(
+            err,
+            JsonError::InvalidBinary { path, error }
+                if path == "data" && error.path == "SpawnAccount.function"
+        ));
+    }
+
+    #[test]
+    fn json_signing_payload_invalid_envelope() {
+        let mut envelope = envelope();
+        envelope["principal"] = json!("1020");
+
+        let json = json!({
+            "tx_type": "call",
+            "envelope": envelope,
+            "tx": call_tx(),
+        });
+
+        let err = signing_payload(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "envelope.principal".to_string()
+            }
+        );
+    }
+}
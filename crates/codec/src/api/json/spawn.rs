@@ -3,13 +3,15 @@ use serde_json::Value;
 
 use std::io::Cursor;
 
-use svm_types::{Account, SpawnAccount};
+use svm_types::{Account, ApiFunc, ApiSection, SpawnAccount, SpawnCall};
 
 use super::call::EncodedOrDecodedCalldata;
+use super::deploy::{to_api_section, ApiFuncJson};
 use super::inputdata::DecodedInputData;
 use super::serde_types::{EncodedData, TemplateAddrWrapper};
 use super::{JsonError, JsonSerdeUtils};
-use crate::spawn;
+use crate::limits::{self, MessageFamily};
+use crate::{signature, spawn};
 
 ///
 /// ```json
@@ -19,24 +21,95 @@ use crate::spawn;
 ///   "name": "My Account",      // string
 ///   "ctor_name": "initialize", // number
 ///   "calldata": "",            // string
+///   "call": {                  // optional
+///     "func_name": "setup",    // string
+///     "calldata": "",          // string
+///   },
+///   "ctors": []                // optional
 /// }
 /// ```
-pub fn encode_spawn(json: &str) -> Result<Vec<u8>, JsonError> {
-    let decoded = DecodedSpawn::from_json_str(json)?;
+///
+/// The optional `ctors` are the signatures of the `Template`'s `ctor`s (as listed by its `API Section`,
+/// see [`deploy_template`](super::deploy_template)). When given, the `ctor_name` may be the name shared by
+/// the overloads of a `ctor`: the overload is resolved by the types of the `calldata`,
+/// and its Wasm name is encoded instead (see [`signature::resolve_ctor`]).
+///
+/// ```json
+/// {
+///   "ctor_name": "initialize",
+///   "calldata": {"abi": ["bool"], "data": [true]},
+///   "ctors": [
+///     {"name": "initialize", "wasm_name": "init_u32", "params": [{"name": "count", "type": "u32"}]},
+///     {"name": "initialize", "wasm_name": "init_bool", "params": [{"name": "flag", "type": "bool"}]}
+///   ]
+/// }
+/// ```
+///
+/// An optional `echo` field is returned verbatim within the result.
+///
+/// Result:
+///
+/// ```json
+/// {
+///   "data": "AABBCCFF81...",
+///   "schema_version": 0,
+///   "echo": ...
+/// }
+/// ```
+pub fn encode_spawn(json: &str) -> Result<Value, JsonError> {
+    let encoded_bytes = encode_spawn_raw(json)?;
+
+    Ok(super::encode_response(encoded_bytes, super::echo_of(json)))
+}
+
+/// Much like [`encode_spawn`], but instead of returning a JSON wrapper it
+/// returns the raw bytes.
+pub fn encode_spawn_raw(json: &str) -> Result<Vec<u8>, JsonError> {
+    let mut decoded = DecodedSpawn::from_json_str(json)?;
+    decoded.resolve_ctor()?;
+
+    if decoded.call.is_some() && decoded.version < spawn::SPAWN_CALL_VERSION {
+        return Err(JsonError::InvalidField {
+            path: "call".to_string(),
+        });
+    }
+
     let spawn = decoded.into();
 
     let mut buf = Vec::new();
-    spawn::encode(&spawn, &mut buf);
+    spawn::encode(&spawn, &mut buf)?;
+
+    limits::check_size(MessageFamily::Spawn, buf.len())?;
+
     Ok(buf)
 }
 
 /// Given a binary [`SpawnAccount`] transaction wrapped inside a JSON,
 /// decodes it into a user-friendly JSON.
+///
+/// The result is of the form (the `call` is present only when the transaction has one):
+///
+/// ```json
+/// {
+///   "version": 0,
+///   "template": "A2FB...",
+///   "name": "My Account",
+///   "ctor_name": "initialize",
+///   "calldata": {"abi": [], "data": []},
+///   "call": {
+///     "func_name": "setup",
+///     "calldata": {"abi": [], "data": []}
+///   }
+/// }
+/// ```
 pub fn decode_spawn(json: &str) -> Result<Value, JsonError> {
     let encoded_spawn = EncodedData::from_json_str(json)?;
 
     let mut cursor = Cursor::new(&encoded_spawn.data.0[..]);
-    let spawn = spawn::decode(&mut cursor).unwrap();
+    let spawn = spawn::decode(&mut cursor).map_err(|err| JsonError::InvalidBinary {
+        path: "data".to_string(),
+        error: err.at("SpawnAccount", &cursor),
+    })?;
 
     Ok(DecodedSpawn::from(spawn).to_json())
 }
@@ -49,24 +122,88 @@ struct DecodedSpawn {
     name: String,
     ctor_name: String,
     calldata: EncodedOrDecodedCalldata,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    call: Option<DecodedSpawnCall>,
+    #[serde(default, skip_serializing)]
+    ctors: Option<Vec<ApiFuncJson>>,
 }
 
 impl JsonSerdeUtils for DecodedSpawn {}
 
+impl DecodedSpawn {
+    /// Replaces the `ctor_name` by the Wasm name of the `ctor` (out of the given `ctors`) matching the `calldata`.
+    fn resolve_ctor(&mut self) -> Result<(), JsonError> {
+        let ctors = match self.ctors.take() {
+            Some(ctors) => to_api_section("ctors", None, ctors)?,
+            None => return Ok(()),
+        };
+
+        let api = ctors
+            .funcs()
+            .iter()
+            .map(|func| ApiFunc {
+                is_ctor: true,
+                ..func.clone()
+            })
+            .fold(ApiSection::new(), ApiSection::with_func);
+
+        let calldata = self.calldata.clone().encode();
+        let ctor = signature::resolve_ctor(&api, &self.ctor_name, &calldata).map_err(|_| {
+            JsonError::InvalidField {
+                path: "ctor_name".to_string(),
+            }
+        })?;
+
+        self.ctor_name = ctor.wasm_name.clone();
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+struct DecodedSpawnCall {
+    func_name: String,
+    calldata: EncodedOrDecodedCalldata,
+}
+
+impl From<SpawnCall> for DecodedSpawnCall {
+    fn from(call: SpawnCall) -> Self {
+        Self {
+            func_name: call.func_name,
+            calldata: decoded_calldata(&call.calldata),
+        }
+    }
+}
+
+impl From<DecodedSpawnCall> for SpawnCall {
+    fn from(wrapper: DecodedSpawnCall) -> Self {
+        SpawnCall {
+            func_name: wrapper.func_name,
+            calldata: wrapper.calldata.encode(),
+        }
+    }
+}
+
+fn decoded_calldata(calldata: &[u8]) -> EncodedOrDecodedCalldata {
+    let decoded = super::inputdata::decode_raw_input(calldata).unwrap();
+
+    EncodedOrDecodedCalldata::Decoded(
+        DecodedInputData::new(&decoded.to_string())
+            .expect("Invalid JSON immediately after serialization"),
+    )
+}
+
 impl From<SpawnAccount> for DecodedSpawn {
     fn from(spawn: SpawnAccount) -> Self {
         let template_addr = TemplateAddrWrapper(spawn.template_addr().clone());
-        let decoded_calldata = super::inputdata::decode_raw_input(&spawn.calldata).unwrap();
 
         Self {
             version: spawn.version,
             name: spawn.account.name,
             template_addr,
             ctor_name: spawn.ctor_name,
-            calldata: EncodedOrDecodedCalldata::Decoded(
-                DecodedInputData::new(&decoded_calldata.to_string())
-                    .expect("Invalid JSON immediately after serialization"),
-            ),
+            calldata: decoded_calldata(&spawn.calldata),
+            call: spawn.call.map(Into::into),
+            ctors: None,
         }
     }
 }
@@ -80,6 +217,7 @@ impl From<DecodedSpawn> for SpawnAccount {
             account: Account::new(template_addr, wrapper.name),
             ctor_name: wrapper.ctor_name,
             calldata: wrapper.calldata.encode(),
+            call: wrapper.call.map(Into::into),
         }
     }
 }
@@ -196,7 +334,7 @@ mod tests {
         .to_string();
         println!("SPAWNING {}", json);
 
-        let bytes = encode_spawn(&json).unwrap();
+        let bytes = encode_spawn_raw(&json).unwrap();
         let data = HexBlob(&bytes);
         let json = decode_spawn(&json!({ "data": data }).to_string()).unwrap();
 
@@ -214,4 +352,162 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn json_spawn_echo() {
+        let json = json!({
+            "version": 1,
+            "template": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "name": "My Account",
+            "ctor_name": "initialize",
+            "calldata": "",
+            "echo": "request #1",
+        })
+        .to_string();
+
+        let encoded_json = encode_spawn(&json).unwrap();
+
+        assert_eq!(encoded_json["echo"], json!("request #1"));
+        assert_eq!(encoded_json["schema_version"], json!(json::SCHEMA_VERSION));
+        assert!(decode_spawn(&encoded_json.to_string()).is_ok());
+    }
+
+    #[test]
+    fn json_spawn_with_call_valid() {
+        let calldata = json::encode_inputdata(
+            &json!({
+                "abi": ["i32"],
+                "data": [10]
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let json = json!({
+            "version": 3,
+            "template": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "name": "My Account",
+            "ctor_name": "initialize",
+            "calldata": calldata["data"],
+            "call": {
+                "func_name": "setup",
+                "calldata": {
+                    "abi": ["bool"],
+                    "data": [true]
+                }
+            }
+        })
+        .to_string();
+
+        let bytes = encode_spawn_raw(&json).unwrap();
+        let data = HexBlob(&bytes);
+        let json = decode_spawn(&json!({ "data": data }).to_string()).unwrap();
+
+        assert_eq!(
+            json,
+            json!({
+                "version": 3,
+                "template": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+                "name": "My Account",
+                "ctor_name": "initialize",
+                "calldata": {
+                    "abi": ["i32"],
+                    "data": [10]
+                },
+                "call": {
+                    "func_name": "setup",
+                    "calldata": {
+                        "abi": ["bool"],
+                        "data": [true]
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn json_spawn_with_call_requires_version() {
+        let json = json!({
+            "version": 2,
+            "template": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "name": "My Account",
+            "ctor_name": "initialize",
+            "calldata": {"abi": [], "data": []},
+            "call": {
+                "func_name": "setup",
+                "calldata": {"abi": [], "data": []},
+            },
+        })
+        .to_string();
+
+        let err = encode_spawn(&json).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "call".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn json_decode_spawn_golden() {
+        let json = json!({
+            "version": 3,
+            "template": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "name": "My Account",
+            "ctor_name": "initialize",
+            "calldata": {"abi": ["i32"], "data": [10]},
+            "call": {
+                "func_name": "setup",
+                "calldata": {"abi": ["bool"], "data": [true]},
+            },
+        })
+        .to_string();
+
+        let bytes = encode_spawn_raw(&json).unwrap();
+        let data = HexBlob(&bytes);
+        let json = decode_spawn(&json!({ "data": data }).to_string()).unwrap();
+
+        let golden = r#"{"version":3,"template":"10203040506070809000A0B0C0D0E0F0ABCDEFFF","name":"My Account","ctor_name":"initialize","calldata":{"abi":["i32"],"data":[10]},"call":{"func_name":"setup","calldata":{"abi":["bool"],"data":[true]}}}"#;
+        assert_eq!(json.to_string(), golden);
+    }
+
+    #[test]
+    fn json_spawn_resolves_ctor_overload() {
+        let spawn = |calldata: serde_json::Value| {
+            json!({
+                "version": 0,
+                "template": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+                "name": "My Account",
+                "ctor_name": "initialize",
+                "calldata": calldata,
+                "ctors": [
+                    {"name": "initialize", "wasm_name": "init_u32", "params": [{"name": "count", "type": "u32"}]},
+                    {"name": "initialize", "wasm_name": "init_bool", "params": [{"name": "flag", "type": "bool"}]},
+                ],
+            })
+            .to_string()
+        };
+        let ctor_name = |json: &str| {
+            let bytes = encode_spawn_raw(json).unwrap();
+            let data = HexBlob(&bytes);
+            let json = decode_spawn(&json!({ "data": data }).to_string()).unwrap();
+
+            json["ctor_name"].as_str().unwrap().to_string()
+        };
+
+        let json = spawn(json!({"abi": ["bool"], "data": [true]}));
+        assert_eq!(ctor_name(&json), "init_bool");
+
+        let json = spawn(json!({"abi": ["u32"], "data": [10]}));
+        assert_eq!(ctor_name(&json), "init_u32");
+
+        let json = spawn(json!({"abi": ["u64"], "data": [10]}));
+        assert_eq!(
+            encode_spawn(&json).unwrap_err(),
+            JsonError::InvalidField {
+                path: "ctor_name".to_string(),
+            }
+        );
+    }
 }
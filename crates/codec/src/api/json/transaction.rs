@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value as Json;
+
+use svm_types::{Context, Envelope, Layer, State, TransactionId};
+
+use super::envelope::DecodedEnvelope;
+use super::gas::{encode_draft, TxType};
+use super::serde_types::HexBlob;
+use super::signing::validate_message;
+use super::JsonSerdeUtils;
+use crate::api::json::JsonError;
+use crate::wire;
+
+/// Given a transaction's `Envelope`, `Context` and message, returns the node-submittable
+/// binary transaction (see [`crate::wire`]).
+///
+/// ```json
+/// {
+///   "tx_type": "call",    // one of `deploy`, `spawn` or `call`
+///   "envelope": {         // as given to `encode_envelope`
+///     "principal": "A2FB...",
+///     "amount": 10,
+///     "gas_limit": 1000,
+///     "gas_fee": 1
+///   },
+///   "context": {
+///     "tx_id": "1E0F...", // 32 bytes
+///     "layer": 10,
+///     "state": "5C3A..."  // 32 bytes
+///   },
+///   "data": "FFC103...",  // the binary message
+///   "tx": { ... }         // or its JSON (as given to the encode endpoint of `tx_type`)
+/// }
+/// ```
+///
+/// An optional `echo` field is returned verbatim within the result.
+///
+/// Result:
+///
+/// ```json
+/// {
+///   "data": "0002A2FB...",
+///   "schema_version": 0,
+///   "echo": ...
+/// }
+/// ```
+pub fn build_transaction(json: &str) -> Result<Json, JsonError> {
+    let request = BuildRequest::from_json_str(json)?;
+    let message = encode_draft(request.tx_type, request.data.as_ref(), request.tx.as_ref())?;
+
+    let tx_type = validate_message(request.tx_type, &message)?;
+
+    let envelope = Envelope::from(request.envelope);
+    let context = request.context.into_context()?;
+    let bytes = wire::encode(tx_type, &envelope, &context, &message);
+
+    Ok(super::encode_response(bytes, super::echo_of(json)))
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct BuildRequest {
+    tx_type: TxType,
+    envelope: DecodedEnvelope,
+    context: DecodedContext,
+    #[serde(default)]
+    data: Option<HexBlob<Vec<u8>>>,
+    #[serde(default)]
+    tx: Option<Json>,
+}
+
+impl JsonSerdeUtils for BuildRequest {}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct DecodedContext {
+    tx_id: HexBlob<Vec<u8>>,
+    layer: u64,
+    state: HexBlob<Vec<u8>>,
+}
+
+impl DecodedContext {
+    fn into_context(self) -> Result<Context, JsonError> {
+        let invalid_field = |field: &str| JsonError::InvalidField {
+            path: format!("context.{}", field),
+        };
+
+        if self.tx_id.0.len() != TransactionId::len() {
+            return Err(invalid_field("tx_id"));
+        }
+
+        if self.state.0.len() != State::len() {
+            return Err(invalid_field("state"));
+        }
+
+        let tx_id = TransactionId::from(&self.tx_id.0[..]);
+        let state = State::from(&self.state.0[..]);
+
+        Ok(Context::new(tx_id, Layer(self.layer), state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::signing;
+
+    fn call_tx() -> Json {
+        json!({
+            "version": 0,
+            "target": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "func_name": "do_something",
+            "verifydata": {"abi": [], "data": []},
+            "calldata": {"abi": ["i8"], "data": [3]},
+        })
+    }
+
+    fn envelope() -> Json {
+        json!({
+            "principal": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+            "amount": 10,
+            "gas_limit": 1000,
+            "gas_fee": 1
+        })
+    }
+
+    fn context() -> Json {
+        json!({
+            "tx_id": HexBlob(&[0x20; 32][..]),
+            "layer": 5,
+            "state": HexBlob(&[0x30; 32][..]),
+        })
+    }
+
+    #[test]
+    fn json_build_transaction_valid() {
+        let message = super::super::encode_call_raw(&call_tx().to_string()).unwrap();
+        let envelope_bytes = super::super::encode_envelope_raw(&envelope().to_string()).unwrap();
+
+        let json = json!({
+            "tx_type": "call",
+            "envelope": envelope(),
+            "context": context(),
+            "data": HexBlob(&message),
+            "echo": 7,
+        });
+        let json = build_transaction(&json.to_string()).unwrap();
+
+        let context = Context::new(TransactionId::repeat(0x20), Layer(5), State::repeat(0x30));
+        let mut expected = vec![wire::VERSION, signing::TxType::Call.as_u8()];
+        expected.extend_from_slice(&envelope_bytes);
+        crate::context::encode(&context, &mut expected);
+        expected.extend_from_slice(&message);
+
+        assert_eq!(
+            json,
+            json!({
+                "data": HexBlob(&expected),
+                "schema_version": 0,
+                "echo": 7,
+            })
+        );
+    }
+
+    #[test]
+    fn json_build_transaction_draft_tx() {
+        let message = super::super::encode_call_raw(&call_tx().to_string()).unwrap();
+
+        let from_data = json!({
+            "tx_type": "call",
+            "envelope": envelope(),
+            "context": context(),
+            "data": HexBlob(&message),
+        });
+        let from_tx = json!({
+            "tx_type": "call",
+            "envelope": envelope(),
+            "context": context(),
+            "tx": call_tx(),
+        });
+
+        assert_eq!(
+            build_transaction(&from_data.to_string()).unwrap(),
+            build_transaction(&from_tx.to_string()).unwrap()
+        );
+    }
+
+    #[test]
+    fn json_build_transaction_invalid_context() {
+        let mut context = context();
+        context["state"] = json!("3030");
+
+        let json = json!({
+            "tx_type": "call",
+            "envelope": envelope(),
+            "context": context,
+            "tx": call_tx(),
+        });
+
+        let err = build_transaction(&json.to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "context.state".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use super::wasm_buf_apply;
+use crate::api::{self, json::JsonError};
+
+/// Armors the binary transaction wrapped inside a JSON (given as an offset to a Wasm buffer).
+///
+/// Returns an offset to a new Wasm buffer holding the result JSON.
+pub fn armor(offset: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(offset, |json: &str| {
+        let json = api::json::armor(json)?;
+
+        Ok(api::json::to_bytes(&json))
+    })
+}
+
+/// Unarmors the text wrapped inside a JSON (given as an offset to a Wasm buffer).
+///
+/// Returns an offset to a new Wasm buffer holding the result JSON.
+pub fn unarmor(offset: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(offset, |json: &str| {
+        let json = api::json::unarmor(json)?;
+
+        Ok(api::json::to_bytes(&json))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::api::wasm::{free, to_wasm_buffer, wasm_buffer_data, BUF_OK_MARKER};
+
+    #[test]
+    fn wasm_armor_unarmor() {
+        let json = json!({ "data": "102030", "encoding": "base64url" }).to_string();
+
+        let json_buf = to_wasm_buffer(json.as_bytes());
+        let armored_buf = armor(json_buf).unwrap();
+
+        let data = wasm_buffer_data(armored_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let armored_json_buf = to_wasm_buffer(&data[1..]);
+        let unarmored_buf = unarmor(armored_json_buf).unwrap();
+
+        let data = wasm_buffer_data(unarmored_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let json: Value = serde_json::from_slice(&data[1..]).unwrap();
+        assert_eq!(json, json!({ "data": "102030", "encoding": "base64url" }));
+
+        free(json_buf);
+        free(armored_buf);
+        free(armored_json_buf);
+        free(unarmored_buf);
+    }
+}
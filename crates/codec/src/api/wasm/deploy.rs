@@ -8,7 +8,40 @@ use crate::api::{self, json::JsonError};
 ///
 /// See also: `alloc` and `free`
 pub fn encode_deploy(ptr: usize) -> Result<usize, JsonError> {
-    wasm_buf_apply(ptr, api::json::deploy_template)
+    wasm_buf_apply(ptr, api::json::deploy_template_raw)
+}
+
+/// Decodes a `Deploy Template` transaction into a JSON of its `Section`s,
+/// stores that JSON content into a new Wasm Buffer,
+/// and finally returns that Wasm buffer offset
+pub fn decode_deploy(offset: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(offset, |json: &str| {
+        let json = api::json::decode_deploy(json)?;
+
+        Ok(api::json::to_bytes(&json))
+    })
+}
+
+/// Decodes the raw `Section`s of a `Deploy Template` transaction into a JSON array,
+/// stores that JSON content into a new Wasm Buffer,
+/// and finally returns that Wasm buffer offset
+pub fn decode_sections(offset: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(offset, |json: &str| {
+        let json = api::json::decode_sections(json)?;
+
+        Ok(api::json::to_bytes(&json))
+    })
+}
+
+/// Decodes the documentation held by a `Deploy Template` transaction into a JSON,
+/// stores that JSON content into a new Wasm Buffer,
+/// and finally returns that Wasm buffer offset
+pub fn decode_docs(offset: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(offset, |json: &str| {
+        let json = api::json::decode_docs(json)?;
+
+        Ok(api::json::to_bytes(&json))
+    })
 }
 
 #[cfg(test)]
@@ -18,10 +51,13 @@ mod test {
     use std::io::Cursor;
     use std::vec;
 
+    use serde_json::{json, Value};
+
     use svm_layout::Layout;
     use svm_types::{CodeKind, CodeSection, CtorsSection, DataSection, GasMode, HeaderSection};
 
     use crate::api::builder::TemplateBuilder;
+    use crate::api::json::serde_types::HexBlob;
     use crate::api::wasm::{
         error_as_string, free, to_wasm_buffer, wasm_buffer_data, BUF_OK_MARKER,
     };
@@ -73,6 +109,112 @@ mod test {
         free(tx_buf);
     }
 
+    #[test]
+    fn wasm_decode_deploy_valid() {
+        let json = r#"{
+          "name": "My Template",
+          "desc": "A few words",
+          "code": "C0DE",
+          "svm_version": 1,
+          "code_version": 2,
+          "data": "0000000100000003",
+          "ctors": ["init", "start"]
+        }"#;
+
+        let json_buf = to_wasm_buffer(json.as_bytes());
+        let tx_buf = encode_deploy(json_buf).unwrap();
+
+        let data = wasm_buffer_data(tx_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let data = json!({ "data": HexBlob(&data[1..]) }).to_string();
+        let data_buf = to_wasm_buffer(data.as_bytes());
+        let res_buf = decode_deploy(data_buf).unwrap();
+
+        let data = wasm_buffer_data(res_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let json: Value = serde_json::from_slice(&data[1..]).unwrap();
+        assert_eq!(json["name"], "My Template");
+        assert_eq!(json["desc"], "A few words");
+        assert_eq!(json["code_len"], 2);
+        assert_eq!(json["code_version"], 2);
+        assert_eq!(json["data"], "0000000100000003");
+        assert_eq!(json["ctors"], json!(["init", "start"]));
+
+        free(json_buf);
+        free(tx_buf);
+        free(data_buf);
+        free(res_buf);
+    }
+
+    #[test]
+    fn wasm_decode_deploy_invalid() {
+        let json = json!({ "data": "FFFF" }).to_string();
+
+        let json_buf = to_wasm_buffer(json.as_bytes());
+        let error = decode_deploy(json_buf).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "The binary value of a specific field is invalid (`data`): \
+             Not enough bytes for field `SectionKind` \
+             (at `DeployTemplate.section_kind`, offset 2: expected 2 bytes, 0 available)."
+        );
+
+        free(json_buf);
+    }
+
+    #[test]
+    fn wasm_decode_sections_valid() {
+        let json = r#"{
+          "name": "My Template",
+          "desc": "A few words",
+          "code": "C0DE",
+          "svm_version": 1,
+          "code_version": 2,
+          "data": "0000000100000003",
+          "ctors": ["init", "start"]
+        }"#;
+
+        let json_buf = to_wasm_buffer(json.as_bytes());
+        let tx_buf = encode_deploy(json_buf).unwrap();
+
+        let data = wasm_buffer_data(tx_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let data = json!({ "data": HexBlob(&data[1..]) }).to_string();
+        let data_buf = to_wasm_buffer(data.as_bytes());
+        let res_buf = decode_sections(data_buf).unwrap();
+
+        let data = wasm_buffer_data(res_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let json: Value = serde_json::from_slice(&data[1..]).unwrap();
+        let kinds: Vec<&str> = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|section| section["kind"].as_str().unwrap())
+            .collect();
+
+        assert!(kinds.contains(&"code"));
+        assert!(kinds.contains(&"header"));
+
+        let ctors = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|section| section["kind"] == "ctors")
+            .unwrap();
+        assert_eq!(ctors["contents"], json!({ "ctors": ["init", "start"] }));
+
+        free(json_buf);
+        free(tx_buf);
+        free(data_buf);
+        free(res_buf);
+    }
+
     #[test]
     fn wasm_deploy_invalid() {
         let json = "{";
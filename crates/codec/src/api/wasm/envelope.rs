@@ -0,0 +1,96 @@
+use super::wasm_buf_apply;
+use crate::api::{self, json::JsonError};
+
+/// Encodes an `Envelope` JSON into SVM binary format.
+/// The JSON input is passed by giving WASM memory start address (`ptr` parameter).
+///
+/// Returns a pointer to an `envelope buffer`.
+///
+/// See also: `alloc` and `free`
+///
+pub fn encode_envelope(offset: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(offset, |json| {
+        api::json::encode_envelope_raw(&json.to_string())
+    })
+}
+
+/// Decodes a binary `Envelope` into a JSON,
+/// stores that JSON content into a new Wasm Buffer,
+/// and finally returns that Wasm buffer offset
+pub fn decode_envelope(offset: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(offset, |json: &str| {
+        let json = api::json::decode_envelope(json)?;
+
+        Ok(api::json::to_bytes(&json))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::api::json::serde_types::HexBlob;
+    use crate::api::wasm::{
+        error_as_string, free, to_wasm_buffer, wasm_buffer_data, BUF_OK_MARKER,
+    };
+
+    use serde_json::{json, Value};
+
+    #[test]
+    fn wasm_envelope_valid() {
+        let principal = "1122334455667788990011223344556677889900";
+
+        let json = json!({
+            "principal": principal,
+            "amount": 10,
+            "gas_limit": 1000,
+            "gas_fee": 1
+        })
+        .to_string();
+
+        let json_buf = to_wasm_buffer(json.as_bytes());
+        let envelope_buf = encode_envelope(json_buf).unwrap();
+
+        let data = wasm_buffer_data(envelope_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let data = HexBlob(&data[1..]);
+        let data = json!({ "data": data }).to_string();
+        let data_buf = to_wasm_buffer(data.as_bytes());
+        let res_buf = decode_envelope(data_buf).unwrap();
+
+        let data = wasm_buffer_data(res_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let json: Value = serde_json::from_slice(&data[1..]).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "principal": principal,
+                "amount": 10,
+                "gas_limit": 1000,
+                "gas_fee": 1
+            })
+        );
+
+        free(json_buf);
+        free(envelope_buf);
+        free(data_buf);
+        free(res_buf);
+    }
+
+    #[test]
+    fn wasm_envelope_invalid() {
+        let json = "{";
+
+        let json_buf = to_wasm_buffer(json.as_bytes());
+        let error_buf = encode_envelope(json_buf).unwrap();
+
+        let error = unsafe { error_as_string(error_buf) };
+
+        assert_eq!(error, "The given JSON is syntactically invalid due to EOF.");
+
+        free(json_buf);
+        free(error_buf);
+    }
+}
@@ -0,0 +1,97 @@
+use super::wasm_buf_apply;
+use crate::api::{self, json::JsonError};
+
+/// Computes the intrinsic gas of a binary transaction wrapped inside a JSON
+/// (given as an offset to a Wasm buffer).
+///
+/// Returns an offset to a new Wasm buffer holding the result JSON.
+pub fn intrinsic_gas(offset: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(offset, |json: &str| {
+        let json = api::json::intrinsic_gas(json)?;
+
+        Ok(api::json::to_bytes(&json))
+    })
+}
+
+/// Estimates the fee of a draft transaction wrapped inside a JSON
+/// (given as an offset to a Wasm buffer).
+///
+/// Returns an offset to a new Wasm buffer holding the result JSON.
+pub fn estimate_fee(offset: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(offset, |json: &str| {
+        let json = api::json::estimate_fee(json)?;
+
+        Ok(api::json::to_bytes(&json))
+    })
+}
+
+/// Decodes a binary gas estimate wrapped inside a JSON
+/// (given as an offset to a Wasm buffer).
+///
+/// Returns an offset to a new Wasm buffer holding the result JSON.
+pub fn decode_gas_estimate(offset: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(offset, |json: &str| {
+        let json = api::json::decode_gas_estimate(json)?;
+
+        Ok(api::json::to_bytes(&json))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::api::json::serde_types::HexBlob;
+    use crate::api::wasm::{free, to_wasm_buffer, wasm_buffer_data, BUF_OK_MARKER};
+
+    #[test]
+    fn wasm_intrinsic_gas_valid() {
+        let data = vec![0x10, 0x20, 0x30];
+        let json = json!({
+            "tx_type": "call",
+            "data": HexBlob(&data),
+        });
+        let json = serde_json::to_string(&json).unwrap();
+
+        let json_buf = to_wasm_buffer(json.as_bytes());
+        let gas_buf = intrinsic_gas(json_buf).unwrap();
+
+        let data = wasm_buffer_data(gas_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let actual: Value = serde_json::from_slice(&data[1..]).unwrap();
+        let expected = json!({ "gas": svm_gas::transaction::call(&[0x10, 0x20, 0x30]) });
+
+        assert_eq!(actual, expected);
+
+        free(json_buf);
+        free(gas_buf);
+    }
+
+    #[test]
+    fn wasm_estimate_fee_valid() {
+        let data = vec![0x10, 0x20, 0x30];
+        let json = json!({
+            "tx_type": "deploy",
+            "data": HexBlob(&data),
+            "gas_price": 2,
+        });
+        let json = serde_json::to_string(&json).unwrap();
+
+        let json_buf = to_wasm_buffer(json.as_bytes());
+        let fee_buf = estimate_fee(json_buf).unwrap();
+
+        let data = wasm_buffer_data(fee_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let actual: Value = serde_json::from_slice(&data[1..]).unwrap();
+        let gas = svm_gas::transaction::deploy(&[0x10, 0x20, 0x30]);
+
+        assert_eq!(actual["gas_limit"], gas);
+        assert_eq!(actual["fee"], gas * 2);
+
+        free(json_buf);
+        free(fee_buf);
+    }
+}
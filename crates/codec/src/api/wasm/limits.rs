@@ -0,0 +1,38 @@
+use super::{to_wasm_buffer, BUF_OK_MARKER};
+use crate::api;
+
+/// Returns the maximum byte sizes of the encoded messages (see [`api::json::limits`]).
+///
+/// Returns a pointer to a new WASM buffer holding the limits JSON.
+pub fn limits() -> usize {
+    let json = api::json::to_bytes(&api::json::limits());
+
+    let mut buf = Vec::with_capacity(1 + json.len());
+    buf.push(BUF_OK_MARKER);
+    buf.extend_from_slice(&json);
+
+    to_wasm_buffer(&buf)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::api::wasm::{free, wasm_buffer_data};
+    use crate::limits::MAX_CALL_SIZE;
+
+    use serde_json::Value;
+
+    #[test]
+    fn wasm_limits() {
+        let limits_buf = limits();
+
+        let data = wasm_buffer_data(limits_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let json: Value = serde_json::from_slice(&data[1..]).unwrap();
+        assert_eq!(json["call"], MAX_CALL_SIZE);
+
+        free(limits_buf);
+    }
+}
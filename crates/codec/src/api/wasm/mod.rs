@@ -1,18 +1,34 @@
 //! WASM API
 
+mod armor;
 mod call;
 mod deploy;
+mod envelope;
 mod error;
+mod gas;
 mod inputdata;
+mod limits;
+mod peek;
 mod receipt;
+mod self_test;
+mod signing;
 mod spawn;
+mod transaction;
 
+pub use armor::{armor, unarmor};
 pub use call::{decode_call, encode_call};
-pub use deploy::encode_deploy;
+pub use deploy::{decode_deploy, decode_docs, decode_sections, encode_deploy};
+pub use envelope::{decode_envelope, encode_envelope};
 pub use error::{error_as_string, into_error_buffer};
+pub use gas::{decode_gas_estimate, estimate_fee, intrinsic_gas};
 pub use inputdata::{decode_inputdata, encode_inputdata};
-pub use receipt::decode_receipt;
+pub use limits::limits;
+pub use peek::tx_peek;
+pub use receipt::{decode_receipt, encode_receipt};
+pub use self_test::self_test;
+pub use signing::signing_payload;
 pub use spawn::{decode_spawn, encode_spawn};
+pub use transaction::build_transaction;
 
 use crate::api::json::JsonError;
 
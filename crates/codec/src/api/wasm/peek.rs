@@ -0,0 +1,89 @@
+use std::io::Cursor;
+
+use super::{to_wasm_buffer, wasm_buffer_data, BUF_OK_MARKER};
+use crate::api::json::JsonError;
+use crate::call::{self, CallField};
+
+/// Reads field `field_id` (see [`CallField::from_id`]) out of the binary `Call Account` transaction
+/// held by the Wasm buffer at `offset`, without decoding the transaction into a JSON.
+///
+/// Returns an offset to a new Wasm buffer holding the raw bytes of the field
+/// (e.g the 20 bytes of the `target` `Address`).
+pub fn tx_peek(offset: usize, field_id: u32) -> Result<usize, JsonError> {
+    let field = CallField::from_id(field_id).ok_or_else(|| JsonError::InvalidField {
+        path: "field_id".to_string(),
+    })?;
+
+    let tx = wasm_buffer_data(offset);
+    let mut cursor = Cursor::new(tx);
+
+    let bytes = call::peek_call(&mut cursor, field).map_err(|err| JsonError::InvalidBinary {
+        path: "data".to_string(),
+        error: err.at("CallAccount", &cursor),
+    })?;
+
+    let mut buf = Vec::with_capacity(1 + bytes.len());
+    buf.push(BUF_OK_MARKER);
+    buf.extend_from_slice(bytes);
+
+    Ok(to_wasm_buffer(&buf))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use svm_types::{Address, Transaction};
+
+    use crate::api::wasm::{free, BUF_ERROR_MARKER};
+
+    #[test]
+    fn wasm_tx_peek_target() {
+        let tx = Transaction {
+            version: 0,
+            target: Address::of("@target"),
+            func_name: "do_work".to_string(),
+            verifydata: vec![],
+            calldata: vec![0x10, 0x20],
+        };
+
+        let mut bytes = Vec::new();
+        call::encode_call(&tx, &mut bytes).unwrap();
+
+        let tx_buf = to_wasm_buffer(&bytes);
+
+        let target_buf = tx_peek(tx_buf, 1).unwrap();
+        let data = wasm_buffer_data(target_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+        assert_eq!(&data[1..], tx.target().as_slice());
+
+        let calldata_buf = tx_peek(tx_buf, 4).unwrap();
+        let data = wasm_buffer_data(calldata_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+        assert_eq!(&data[1..], &[0x10, 0x20]);
+
+        free(tx_buf);
+        free(target_buf);
+        free(calldata_buf);
+    }
+
+    #[test]
+    fn wasm_tx_peek_invalid() {
+        let tx_buf = to_wasm_buffer(&[0x00, 0x00, 0x10]);
+
+        let err = tx_peek(tx_buf, 5).unwrap_err();
+        assert_eq!(
+            err,
+            JsonError::InvalidField {
+                path: "field_id".to_string()
+            }
+        );
+
+        let err = tx_peek(tx_buf, 1).unwrap_err();
+        let error_buf = crate::api::wasm::into_error_buffer(err);
+        assert_eq!(wasm_buffer_data(error_buf)[0], BUF_ERROR_MARKER);
+
+        free(tx_buf);
+        free(error_buf);
+    }
+}
@@ -1,6 +1,17 @@
 use super::wasm_buf_apply;
 use crate::api::{self, json::JsonError};
 
+/// Given an offset to a Wasm buffer holding a Receipt JSON (as returned by [`decode_receipt`]),
+/// encodes it and returns an offset to a new Wasm buffer holding the binary Receipt
+/// wrapped inside a JSON.
+pub fn encode_receipt(offset: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(offset, |json: &str| {
+        let json = api::json::encode_receipt(json)?;
+
+        Ok(api::json::to_bytes(&json))
+    })
+}
+
 /// Decodes a binary Receipt given as an offset to a Wasm buffer,
 /// and then returns an offset to a new Wasm buffer holding the decoded Receipt
 /// in a JSON format.
@@ -36,7 +47,12 @@ mod test {
             init_state: Some(state),
             returndata: Some(vec![0x10, 0x20]),
             gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
             logs,
+            metrics: Vec::new(),
+            call: None,
         };
 
         let bytes = crate::receipt::encode_spawn(&receipt);
@@ -56,6 +72,7 @@ mod test {
             json,
             json!({
                 "success": true,
+                "gas_schedule_version": 0,
                 "type": "spawn-account",
                 "account": "1010101010101010101010101010101010101010",
                 "gas_used": 10,
@@ -68,4 +85,36 @@ mod test {
         free(json_buf);
         free(receipt_buf);
     }
+
+    #[test]
+    fn wasm_encode_receipt_valid() {
+        let json = json!({
+            "type": "call-account",
+            "success": false,
+            "gas_schedule_version": 0,
+            "err_type": "oog",
+            "logs": [{"data": "Reached OOG"}]
+        });
+        let json = serde_json::to_string(&json).unwrap();
+
+        let json_buf = to_wasm_buffer(json.as_bytes());
+        let encoded_buf = encode_receipt(json_buf).unwrap();
+
+        let data = wasm_buffer_data(encoded_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let encoded: Value = serde_json::from_slice(&data[1..]).unwrap();
+        let encoded = serde_json::to_string(&json!({ "data": encoded["data"] })).unwrap();
+
+        let receipt_buf = to_wasm_buffer(encoded.as_bytes());
+        let decoded_buf = decode_receipt(receipt_buf).unwrap();
+        let data = wasm_buffer_data(decoded_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+        assert_eq!(std::str::from_utf8(&data[1..]).unwrap(), json);
+
+        free(json_buf);
+        free(encoded_buf);
+        free(receipt_buf);
+        free(decoded_buf);
+    }
 }
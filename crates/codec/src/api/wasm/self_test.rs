@@ -0,0 +1,37 @@
+use super::{to_wasm_buffer, BUF_OK_MARKER};
+use crate::api;
+
+/// Runs the golden vectors embedded into the codec (see [`api::json::self_test`]).
+///
+/// Returns a pointer to a new WASM buffer holding the report JSON.
+pub fn self_test() -> usize {
+    let report = api::json::to_bytes(&api::json::self_test());
+
+    let mut buf = Vec::with_capacity(1 + report.len());
+    buf.push(BUF_OK_MARKER);
+    buf.extend_from_slice(&report);
+
+    to_wasm_buffer(&buf)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::api::wasm::{free, wasm_buffer_data};
+
+    use serde_json::Value;
+
+    #[test]
+    fn wasm_self_test_report() {
+        let report_buf = self_test();
+
+        let data = wasm_buffer_data(report_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let report: Value = serde_json::from_slice(&data[1..]).unwrap();
+        assert_eq!(report["passed"], true);
+
+        free(report_buf);
+    }
+}
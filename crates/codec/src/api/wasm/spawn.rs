@@ -6,7 +6,7 @@ use crate::api::{self, json::JsonError};
 ///
 /// Returns an offset to a Wasm buffer holding the encoded transaction (wrapped within a JSON)
 pub fn encode_spawn(offset: usize) -> Result<usize, JsonError> {
-    wasm_buf_apply(offset, api::json::encode_spawn)
+    wasm_buf_apply(offset, api::json::encode_spawn_raw)
 }
 
 /// Decodes a binary `Spawn Account` transaction given as a Wasm buffer (the `offset` parameter),
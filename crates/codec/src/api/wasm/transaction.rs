@@ -0,0 +1,62 @@
+use super::wasm_buf_apply;
+use crate::api::{self, json::JsonError};
+
+/// Builds the node-submittable binary transaction out of a transaction's `Envelope`, `Context`
+/// and message wrapped inside a JSON (given as an offset to a Wasm buffer).
+///
+/// Returns an offset to a new Wasm buffer holding the result JSON.
+pub fn build_transaction(offset: usize) -> Result<usize, JsonError> {
+    wasm_buf_apply(offset, |json: &str| {
+        let json = api::json::build_transaction(json)?;
+
+        Ok(api::json::to_bytes(&json))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::api::wasm::{free, to_wasm_buffer, wasm_buffer_data, BUF_OK_MARKER};
+
+    #[test]
+    fn wasm_build_transaction_valid() {
+        let json = json!({
+            "tx_type": "call",
+            "envelope": {
+                "principal": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+                "amount": 10,
+                "gas_limit": 1000,
+                "gas_fee": 1
+            },
+            "context": {
+                "tx_id": "2020202020202020202020202020202020202020202020202020202020202020",
+                "layer": 5,
+                "state": "3030303030303030303030303030303030303030303030303030303030303030"
+            },
+            "tx": {
+                "version": 0,
+                "target": "10203040506070809000A0B0C0D0E0F0ABCDEFFF",
+                "func_name": "do_something",
+                "verifydata": {"abi": [], "data": []},
+                "calldata": {"abi": [], "data": []},
+            },
+        });
+        let json = serde_json::to_string(&json).unwrap();
+
+        let json_buf = to_wasm_buffer(json.as_bytes());
+        let tx_buf = build_transaction(json_buf).unwrap();
+
+        let data = wasm_buffer_data(tx_buf);
+        assert_eq!(data[0], BUF_OK_MARKER);
+
+        let actual: Value = serde_json::from_slice(&data[1..]).unwrap();
+        let expected = api::json::build_transaction(&json).unwrap();
+
+        assert_eq!(actual, expected);
+
+        free(json_buf);
+        free(tx_buf);
+    }
+}
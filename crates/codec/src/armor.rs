@@ -0,0 +1,361 @@
+//! A compact text armoring of binary transactions, meant to be exchanged via QR codes
+//! (e.g with air-gapped signers).
+//!
+//! ```text
+//!
+//!  +-----------+-----------+----------------------------------------------+
+//!  |           |           |                                              |
+//!  |  Header   | Encoding  |            Payload (encoded)                 |
+//!  |           |           |                                              |
+//!  | `SVM<v>:` | `B45:` or | +------------------------+-----------------+ |
+//!  |           |  `B64:`   | |   binary transaction   |    checksum     | |
+//!  |           |           | |        (Blob)          |    (4 bytes)    | |
+//!  |           |           | +------------------------+-----------------+ |
+//!  +-----------+-----------+----------------------------------------------+
+//!
+//! ```
+//!
+//! * `<v>` is the armoring format version (see [`ARMOR_VERSION`]).
+//! * `B45` stands for Base45 (RFC 9285). Its alphabet is the QR codes alphanumeric mode
+//!   one, so the whole armored text fits the QR alphanumeric mode.
+//! * `B64` stands for the unpadded Base64url (RFC 4648). It's more compact, but requires
+//!   the QR byte mode.
+//! * The `checksum` is the first 4 bytes of the `Blake3` hash of the binary transaction.
+
+use thiserror::Error;
+
+use std::fmt;
+
+use svm_hash::{Blake3Hasher, Hasher};
+
+/// The current armoring format version.
+pub const ARMOR_VERSION: u8 = 0;
+
+const HEADER_PREFIX: &str = "SVM";
+
+const CHECKSUM_LEN: usize = 4;
+
+const BASE45_ALPHABET: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+const BASE64URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// The text encoding of an armored payload.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArmorEncoding {
+    /// Base45 (fits the QR codes alphanumeric mode).
+    Base45,
+
+    /// Unpadded Base64url.
+    Base64Url,
+}
+
+impl ArmorEncoding {
+    /// The tag of the encoding within the armored text header.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            ArmorEncoding::Base45 => "B45",
+            ArmorEncoding::Base64Url => "B64",
+        }
+    }
+
+    /// Returns the [`ArmorEncoding`] tagged `tag`.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "B45" => Some(ArmorEncoding::Base45),
+            "B64" => Some(ArmorEncoding::Base64Url),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ArmorEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArmorEncoding::Base45 => write!(f, "base45"),
+            ArmorEncoding::Base64Url => write!(f, "base64url"),
+        }
+    }
+}
+
+/// The error type that can arise when unarmoring a text.
+#[derive(Debug, PartialEq, Clone, Error)]
+pub enum ArmorError {
+    /// The text doesn't start with an `SVM<v>:<encoding>:` header.
+    #[error("Missing the `SVM<version>:<encoding>:` header")]
+    InvalidHeader,
+
+    /// The armoring format version isn't supported.
+    #[error("Armoring format version `{0}` is not supported")]
+    UnsupportedVersion(String),
+
+    /// The encoding tag isn't supported.
+    #[error("Armoring encoding `{0}` is not supported")]
+    UnsupportedEncoding(String),
+
+    /// The payload has a character outside the encoding alphabet.
+    #[error("Invalid character `{0}` in the armored payload")]
+    InvalidCharacter(char),
+
+    /// The payload isn't a valid sequence of the encoding.
+    #[error("The armored payload is malformed")]
+    MalformedPayload,
+
+    /// The checksum doesn't match the binary transaction.
+    #[error("The armored payload checksum doesn't match")]
+    ChecksumMismatch,
+}
+
+/// Armors the binary transaction `bytes` as text, using `encoding`.
+pub fn armor(bytes: &[u8], encoding: ArmorEncoding) -> String {
+    let mut payload = bytes.to_vec();
+    payload.extend_from_slice(&checksum(bytes));
+
+    let encoded = match encoding {
+        ArmorEncoding::Base45 => encode_base45(&payload),
+        ArmorEncoding::Base64Url => encode_base64url(&payload),
+    };
+
+    format!(
+        "{}{}:{}:{}",
+        HEADER_PREFIX,
+        ARMOR_VERSION,
+        encoding.tag(),
+        encoded
+    )
+}
+
+/// Unarmors a text created by [`armor`], returning the binary transaction along with the
+/// [`ArmorEncoding`] used.
+///
+/// Leading and trailing whitespaces are ignored.
+pub fn unarmor(text: &str) -> Result<(Vec<u8>, ArmorEncoding), ArmorError> {
+    let mut parts = text.trim().splitn(3, ':');
+
+    let (version, tag, encoded) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(version), Some(tag), Some(encoded)) => (version, tag, encoded),
+        _ => return Err(ArmorError::InvalidHeader),
+    };
+
+    let version = version
+        .strip_prefix(HEADER_PREFIX)
+        .ok_or(ArmorError::InvalidHeader)?;
+
+    if version != ARMOR_VERSION.to_string() {
+        return Err(ArmorError::UnsupportedVersion(version.to_string()));
+    }
+
+    let encoding =
+        ArmorEncoding::from_tag(tag).ok_or_else(|| ArmorError::UnsupportedEncoding(tag.into()))?;
+
+    let mut payload = match encoding {
+        ArmorEncoding::Base45 => decode_base45(encoded)?,
+        ArmorEncoding::Base64Url => decode_base64url(encoded)?,
+    };
+
+    if payload.len() < CHECKSUM_LEN {
+        return Err(ArmorError::MalformedPayload);
+    }
+
+    let expected = payload.split_off(payload.len() - CHECKSUM_LEN);
+
+    if expected != checksum(&payload) {
+        return Err(ArmorError::ChecksumMismatch);
+    }
+
+    Ok((payload, encoding))
+}
+
+fn checksum(bytes: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let hash = Blake3Hasher::hash(bytes);
+
+    let mut checksum = [0; CHECKSUM_LEN];
+    checksum.copy_from_slice(&hash[..CHECKSUM_LEN]);
+    checksum
+}
+
+/// Each 2 bytes are encoded as 3 characters (the last odd byte as 2 characters),
+/// least significant digit first.
+fn encode_base45(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len() / 2 * 3 + 2);
+
+    for chunk in bytes.chunks(2) {
+        let (mut n, digits) = match chunk {
+            [a, b] => ((*a as usize) << 8 | *b as usize, 3),
+            [a] => (*a as usize, 2),
+            _ => unreachable!(),
+        };
+
+        for _ in 0..digits {
+            encoded.push(BASE45_ALPHABET[n % 45] as char);
+            n /= 45;
+        }
+    }
+
+    encoded
+}
+
+fn decode_base45(encoded: &str) -> Result<Vec<u8>, ArmorError> {
+    let digits = encoded
+        .chars()
+        .map(|c| {
+            BASE45_ALPHABET
+                .iter()
+                .position(|&d| d as char == c)
+                .ok_or(ArmorError::InvalidCharacter(c))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut bytes = Vec::with_capacity(digits.len() / 3 * 2 + 1);
+
+    for chunk in digits.chunks(3) {
+        let n = chunk.iter().rev().fold(0, |n, d| n * 45 + d);
+
+        match chunk.len() {
+            3 if n <= 0xFFFF => bytes.extend_from_slice(&(n as u16).to_be_bytes()),
+            2 if n <= 0xFF => bytes.push(n as u8),
+            _ => return Err(ArmorError::MalformedPayload),
+        }
+    }
+
+    Ok(bytes)
+}
+
+fn encode_base64url(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity((bytes.len() * 4 + 2) / 3);
+
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+
+        // `N` bytes are encoded as `N + 1` characters
+        for i in 0..=chunk.len() {
+            let index = (n >> (18 - 6 * i)) & 0x3F;
+
+            encoded.push(BASE64URL_ALPHABET[index as usize] as char);
+        }
+    }
+
+    encoded
+}
+
+fn decode_base64url(encoded: &str) -> Result<Vec<u8>, ArmorError> {
+    let digits = encoded
+        .chars()
+        .map(|c| {
+            BASE64URL_ALPHABET
+                .iter()
+                .position(|&d| d as char == c)
+                .ok_or(ArmorError::InvalidCharacter(c))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut bytes = Vec::with_capacity(digits.len() * 3 / 4);
+
+    for chunk in digits.chunks(4) {
+        if chunk.len() == 1 {
+            return Err(ArmorError::MalformedPayload);
+        }
+
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0, |n, (i, d)| n | (*d as u32) << (18 - 6 * i));
+
+        let nbytes = chunk.len() - 1;
+        let chunk_bytes = &n.to_be_bytes()[1..=nbytes];
+
+        // the unused trailing bits must be zeros (so that each payload has a single encoding)
+        if n & (0xFF_FF_FF >> (8 * nbytes)) != 0 {
+            return Err(ArmorError::MalformedPayload);
+        }
+
+        bytes.extend_from_slice(chunk_bytes);
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base45_rfc_vectors() {
+        assert_eq!(encode_base45(b"AB"), "BB8");
+        assert_eq!(encode_base45(b"Hello!!"), "%69 VD92EX0");
+        assert_eq!(encode_base45(b"base-45"), "UJCLQE7W581");
+
+        assert_eq!(decode_base45("QED8WEX0").unwrap(), b"ietf!");
+        assert_eq!(decode_base45("GGW"), Err(ArmorError::MalformedPayload));
+    }
+
+    #[test]
+    fn base64url_vectors() {
+        assert_eq!(encode_base64url(b""), "");
+        assert_eq!(encode_base64url(b"f"), "Zg");
+        assert_eq!(encode_base64url(b"fo"), "Zm8");
+        assert_eq!(encode_base64url(b"foo"), "Zm9v");
+        assert_eq!(encode_base64url(&[0xFB, 0xFF]), "-_8");
+
+        assert_eq!(decode_base64url("Zm9vYg").unwrap(), b"foob");
+        assert_eq!(decode_base64url("Zh"), Err(ArmorError::MalformedPayload));
+        assert_eq!(decode_base64url("Zm9vY"), Err(ArmorError::MalformedPayload));
+    }
+
+    #[test]
+    fn armor_unarmor() {
+        let bytes = (0..=255).collect::<Vec<u8>>();
+
+        for &encoding in &[ArmorEncoding::Base45, ArmorEncoding::Base64Url] {
+            for len in 0..8 {
+                let armored = armor(&bytes[..len], encoding);
+
+                assert_eq!(unarmor(&armored), Ok((bytes[..len].to_vec(), encoding)));
+            }
+
+            let armored = armor(&bytes, encoding);
+            assert_eq!(unarmor(&armored), Ok((bytes.clone(), encoding)));
+        }
+    }
+
+    #[test]
+    fn armor_base45_is_qr_alphanumeric() {
+        let armored = armor(&[0x10, 0x20, 0x30], ArmorEncoding::Base45);
+
+        assert!(armored.starts_with("SVM0:B45:"));
+        assert!(armored.bytes().all(|c| BASE45_ALPHABET.contains(&c)));
+    }
+
+    #[test]
+    fn unarmor_errors() {
+        let armored = armor(&[0x10, 0x20, 0x30], ArmorEncoding::Base64Url);
+        let payload = armored.strip_prefix("SVM0:B64:").unwrap();
+
+        assert_eq!(unarmor(payload), Err(ArmorError::InvalidHeader));
+        assert_eq!(
+            unarmor(&format!("SVM9:B64:{}", payload)),
+            Err(ArmorError::UnsupportedVersion("9".to_string()))
+        );
+        assert_eq!(
+            unarmor(&format!("SVM0:B32:{}", payload)),
+            Err(ArmorError::UnsupportedEncoding("B32".to_string()))
+        );
+        assert_eq!(
+            unarmor(&format!("SVM0:B64:{}=", payload)),
+            Err(ArmorError::InvalidCharacter('='))
+        );
+
+        let tampered = armor(&[0x10, 0x20, 0x31], ArmorEncoding::Base64Url);
+        let tampered_payload = tampered.strip_prefix("SVM0:B64:").unwrap();
+        let mixed = format!("SVM0:B64:{}{}", &tampered_payload[..4], &payload[4..]);
+
+        assert_eq!(unarmor(&mixed), Err(ArmorError::ChecksumMismatch));
+        assert_eq!(
+            unarmor(&format!("  {}\n", armored)),
+            Ok((vec![0x10, 0x20, 0x30], ArmorEncoding::Base64Url))
+        );
+    }
+}
@@ -0,0 +1,132 @@
+//! Encoding of binary [`BatchTransaction`].
+//!
+//! ```text
+//!
+//!  +-----------+-------------+--------------+---------+--------------+
+//!  |           |             |              |         |              |
+//!  |  Version  |   #Calls    |   Call #1    |  . . .  |   Call #N    |
+//!  |   (u16)   |    (u16)    |  (binary     |         |  (binary     |
+//!  |           |             | Transaction) |         | Transaction) |
+//!  |           |             |              |         |              |
+//!  +-----------+-------------+--------------+---------+--------------+
+//!
+//! ```
+//!
+//! Each batched call is encoded just like a `Call Account` transaction (see [`crate::call`]).
+//! A batch must hold at least one call, and the whole message can't exceed
+//! [`MAX_BATCH_SIZE`](crate::limits::MAX_BATCH_SIZE) bytes.
+
+use std::io::Cursor;
+
+use svm_types::BatchTransaction;
+
+use crate::limits::{self, MessageFamily};
+use crate::{call, version};
+use crate::{EncodeError, Field, ParseError, ReadExt, WriteExt};
+
+/// Encodes a binary [`BatchTransaction`]
+///
+/// Fails (writing nothing) when the batch holds more than `65535` calls,
+/// or when any of them can't be encoded (see [`call::encode_call`]).
+pub fn encode_batch(batch: &BatchTransaction, w: &mut Vec<u8>) -> Result<(), EncodeError> {
+    let calls = batch.calls();
+    let max = std::u16::MAX as usize;
+
+    if calls.len() > max {
+        return Err(EncodeError::TooManyItems {
+            field: Field::BatchCallsCount,
+            max,
+        });
+    }
+
+    let mut bytes = Vec::new();
+    version::encode_version(batch.version, &mut bytes);
+    bytes.write_u16_be(calls.len() as u16);
+
+    for tx in calls {
+        call::encode_call(tx, &mut bytes)?;
+    }
+
+    w.extend_from_slice(&bytes);
+
+    Ok(())
+}
+
+/// Parsing a binary [`BatchTransaction`].
+///
+/// Returns the parsed transaction as [`BatchTransaction`] struct.
+/// On failure, returns `ParseError`
+pub fn decode_batch(cursor: &mut Cursor<&[u8]>) -> Result<BatchTransaction, ParseError> {
+    limits::check_buffer(MessageFamily::Batch, cursor)?;
+
+    let version = version::decode_version(cursor)?;
+
+    let count = cursor
+        .read_u16_be()
+        .map_err(|_| ParseError::NotEnoughBytes(Field::BatchCallsCount))?;
+
+    if count == 0 {
+        return Err(ParseError::EmptyField(Field::BatchCallsCount));
+    }
+
+    let mut calls = Vec::with_capacity(count as usize);
+
+    for _ in 0..count {
+        let tx = call::decode_call_fields(cursor)?;
+        calls.push(tx);
+    }
+
+    Ok(BatchTransaction { version, calls })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_types::{Address, Transaction};
+
+    fn tx(func_name: &str, calldata: Vec<u8>) -> Transaction {
+        Transaction {
+            version: 0,
+            target: Address::of("@Account"),
+            func_name: func_name.to_string(),
+            verifydata: Vec::new(),
+            calldata,
+        }
+    }
+
+    #[test]
+    fn encode_decode_batch() {
+        let batch = BatchTransaction {
+            version: 0,
+            calls: vec![tx("deposit", vec![0x10, 0x20]), tx("withdraw", vec![0x30])],
+        };
+
+        let mut bytes = Vec::new();
+        encode_batch(&batch, &mut bytes).unwrap();
+
+        let mut cursor = Cursor::new(&bytes[..]);
+        let decoded = decode_batch(&mut cursor).unwrap();
+
+        assert_eq!(decoded, batch);
+        assert_eq!(cursor.position() as usize, bytes.len());
+    }
+
+    #[test]
+    fn decode_batch_empty() {
+        let batch = BatchTransaction {
+            version: 0,
+            calls: Vec::new(),
+        };
+
+        let mut bytes = Vec::new();
+        encode_batch(&batch, &mut bytes).unwrap();
+
+        let mut cursor = Cursor::new(&bytes[..]);
+
+        assert_eq!(
+            decode_batch(&mut cursor),
+            Err(ParseError::EmptyField(Field::BatchCallsCount))
+        );
+    }
+}
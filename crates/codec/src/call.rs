@@ -15,21 +15,54 @@
 //!  +-----------+-------------+----------------+
 //!
 //! ```
+//!
+//! The `VerifyData` is the input of the `svm_verify` stage (i.e signatures or any other
+//! witness a custom verification scheme relies on). It's encoded like the `CallData`,
+//! but can't exceed [`MAX_VERIFYDATA_SIZE`] bytes.
+//!
+//! From version `2` onwards, the length prefixes of the `VerifyData` and `CallData` are
+//! variable-length integers (a single byte length for earlier versions).
+//!
+//! A single field can be read without decoding the whole [`Transaction`] (see [`peek_call`]).
+//!
+//! The whole message can't exceed [`MAX_CALL_SIZE`](crate::limits::MAX_CALL_SIZE) bytes.
 
 use svm_types::{Address, Transaction};
 
 use std::io::Cursor;
 
+use crate::error::{validate_size, validate_string};
+use crate::limits::{self, MessageFamily};
 use crate::{inputdata, version};
-use crate::{Field, ParseError, ReadExt, WriteExt};
+use crate::{EncodeError, Field, ParseError, ReadExt, WriteExt};
+
+/// The maximum size (in bytes) of a [`Transaction`]'s `VerifyData`.
+pub const MAX_VERIFYDATA_SIZE: usize = 192;
 
 /// Encodes a binary [`Transaction`]
-pub fn encode_call(tx: &Transaction, w: &mut Vec<u8>) {
+///
+/// Fails (writing nothing) when a field of `tx` can't be encoded, i.e a `Function` name exceeding `255` bytes,
+/// a `VerifyData` exceeding [`MAX_VERIFYDATA_SIZE`] bytes or an `InputData` exceeding the size its version can hold.
+pub fn encode_call(tx: &Transaction, w: &mut impl WriteExt) -> Result<(), EncodeError> {
+    validate_call(tx)?;
+
     encode_version(tx, w);
     encode_target(tx, w);
     encode_func(tx, w);
     encode_verifydata(tx, w);
     encode_calldata(tx, w);
+
+    Ok(())
+}
+
+/// Validates that all the fields of `tx` can be encoded (see [`encode_call`]).
+fn validate_call(tx: &Transaction) -> Result<(), EncodeError> {
+    let verifydata = tx.verifydata();
+
+    validate_string(tx.func_name(), Field::Function)?;
+    validate_size(verifydata.len(), MAX_VERIFYDATA_SIZE, Field::VerifyData)?;
+    inputdata::validate_inputdata(tx.version, verifydata, Field::VerifyData)?;
+    inputdata::validate_inputdata(tx.version, tx.calldata(), Field::InputData)
 }
 
 /// Parsing a binary [`Transaction`].
@@ -37,11 +70,18 @@ pub fn encode_call(tx: &Transaction, w: &mut Vec<u8>) {
 /// Returns the parsed transaction as [`Transaction`] struct.
 /// On failure, returns `ParseError`
 pub fn decode_call(cursor: &mut Cursor<&[u8]>) -> Result<Transaction, ParseError> {
+    limits::check_buffer(MessageFamily::Call, cursor)?;
+
+    decode_call_fields(cursor)
+}
+
+/// Decodes the fields of a binary [`Transaction`] (laid out within a larger message, such as a `Batch`).
+pub(crate) fn decode_call_fields(cursor: &mut Cursor<&[u8]>) -> Result<Transaction, ParseError> {
     let version = decode_version(cursor)?;
     let target = decode_target(cursor)?;
     let func_name = decode_func(cursor)?;
-    let verifydata = inputdata::decode_inputdata(cursor)?;
-    let calldata = inputdata::decode_inputdata(cursor)?;
+    let verifydata = decode_verifydata(version, cursor)?;
+    let calldata = inputdata::decode_inputdata(version, cursor)?;
 
     let tx = Transaction {
         version,
@@ -54,47 +94,161 @@ pub fn decode_call(cursor: &mut Cursor<&[u8]>) -> Result<Transaction, ParseError
     Ok(tx)
 }
 
+/// The fields of a binary [`Transaction`] (see [`peek_call`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallField {
+    /// The format version (2 bytes, Big-Endian).
+    Version,
+
+    /// The `Address` of the called `Account` (20 bytes).
+    Target,
+
+    /// The name of the called function (UTF-8).
+    Function,
+
+    /// The `VerifyData`.
+    VerifyData,
+
+    /// The `CallData`.
+    CallData,
+}
+
+impl CallField {
+    /// Returns the field identified by `id` (`0` for [`CallField::Version`], in the order of the layout).
+    pub fn from_id(id: u32) -> Option<Self> {
+        let field = match id {
+            0 => CallField::Version,
+            1 => CallField::Target,
+            2 => CallField::Function,
+            3 => CallField::VerifyData,
+            4 => CallField::CallData,
+            _ => return None,
+        };
+
+        Some(field)
+    }
+}
+
+/// Returns the bytes of `field` out of a binary [`Transaction`], without decoding any other field.
+///
+/// The fields laid out before `field` are skipped by their fixed sizes or length prefixes
+/// (e.g the function name isn't checked to be a valid UTF-8 string when peeking the `CallData`),
+/// and the ones laid out after `field` aren't read at all.
+///
+/// The returned bytes don't include the length prefix of `field`.
+pub fn peek_call<'a>(
+    cursor: &mut Cursor<&'a [u8]>,
+    field: CallField,
+) -> Result<&'a [u8], ParseError> {
+    let start = cursor.position() as usize;
+    let version = decode_version(cursor)?;
+
+    if field == CallField::Version {
+        return Ok(&cursor.get_ref()[start..start + 2]);
+    }
+
+    let target = skip(cursor, Address::len(), Field::TargetAddr)?;
+    if field == CallField::Target {
+        return Ok(target);
+    }
+
+    let func_len = cursor
+        .read_byte()
+        .map_err(|_| ParseError::NotEnoughBytes(Field::Function))?;
+    let func = skip(cursor, func_len as usize, Field::Function)?;
+
+    if field == CallField::Function {
+        return match std::str::from_utf8(func) {
+            Ok(..) => Ok(func),
+            Err(..) => Err(ParseError::InvalidUTF8String(Field::Function)),
+        };
+    }
+
+    let verifydata = skip_inputdata(version, cursor)?;
+    if field == CallField::VerifyData {
+        if verifydata.len() > MAX_VERIFYDATA_SIZE {
+            return Err(ParseError::TooManyBytes(Field::VerifyData));
+        }
+
+        return Ok(verifydata);
+    }
+
+    skip_inputdata(version, cursor)
+}
+
+/// Advances `cursor` by `length` bytes, and returns them.
+fn skip<'a>(
+    cursor: &mut Cursor<&'a [u8]>,
+    length: usize,
+    field: Field,
+) -> Result<&'a [u8], ParseError> {
+    let bytes: &'a [u8] = cursor.get_ref();
+    let start = cursor.position() as usize;
+
+    if length > bytes.len().saturating_sub(start) {
+        return Err(ParseError::NotEnoughBytes(field));
+    }
+
+    cursor.set_position((start + length) as u64);
+
+    Ok(&bytes[start..start + length])
+}
+
+/// Advances `cursor` over an `InputData` (see [`inputdata::decode_inputdata`]), and returns its data.
+fn skip_inputdata<'a>(version: u16, cursor: &mut Cursor<&'a [u8]>) -> Result<&'a [u8], ParseError> {
+    let length = if version >= inputdata::UVARINT_LENGTH_VERSION {
+        cursor.read_uvarint()
+    } else {
+        cursor.read_byte().map(u64::from)
+    };
+
+    match length {
+        Err(..) => Err(ParseError::NotEnoughBytes(Field::InputDataLength)),
+        Ok(length) => skip(cursor, length as usize, Field::InputData),
+    }
+}
+
 /// Encoders
 
-fn encode_version(tx: &Transaction, w: &mut Vec<u8>) {
+fn encode_version(tx: &Transaction, w: &mut impl WriteExt) {
     let v = &tx.version;
 
     version::encode_version(*v, w);
 }
 
-fn encode_target(tx: &Transaction, w: &mut Vec<u8>) {
+fn encode_target(tx: &Transaction, w: &mut impl WriteExt) {
     w.write_address(tx.target());
 }
 
-fn encode_func(tx: &Transaction, w: &mut Vec<u8>) {
+fn encode_func(tx: &Transaction, w: &mut impl WriteExt) {
     let func = tx.func_name();
     w.write_string(func);
 }
 
-fn encode_verifydata(tx: &Transaction, w: &mut Vec<u8>) {
+fn encode_verifydata(tx: &Transaction, w: &mut impl WriteExt) {
     let verifydata = tx.verifydata();
-    inputdata::encode_inputdata(verifydata, w)
+    inputdata::encode_inputdata(tx.version, verifydata, w)
 }
 
-fn encode_calldata(tx: &Transaction, w: &mut Vec<u8>) {
+fn encode_calldata(tx: &Transaction, w: &mut impl WriteExt) {
     let calldata = tx.calldata();
-    inputdata::encode_inputdata(calldata, w)
+    inputdata::encode_inputdata(tx.version, calldata, w)
 }
 
 /// Decoders
 
 #[inline]
-fn decode_version(cursor: &mut Cursor<&[u8]>) -> Result<u16, ParseError> {
+pub(crate) fn decode_version(cursor: &mut Cursor<&[u8]>) -> Result<u16, ParseError> {
     version::decode_version(cursor)
 }
 
-fn decode_target(cursor: &mut Cursor<&[u8]>) -> Result<Address, ParseError> {
+pub(crate) fn decode_target(cursor: &mut Cursor<&[u8]>) -> Result<Address, ParseError> {
     cursor
         .read_address()
         .map_err(|_| ParseError::NotEnoughBytes(Field::TargetAddr))
 }
 
-fn decode_func(cursor: &mut Cursor<&[u8]>) -> Result<String, ParseError> {
+pub(crate) fn decode_func(cursor: &mut Cursor<&[u8]>) -> Result<String, ParseError> {
     match cursor.read_string() {
         Ok(Ok(func)) => Ok(func),
         Ok(Err(..)) => Err(ParseError::InvalidUTF8String(Field::Function)),
@@ -102,6 +256,19 @@ fn decode_func(cursor: &mut Cursor<&[u8]>) -> Result<String, ParseError> {
     }
 }
 
+pub(crate) fn decode_verifydata(
+    version: u16,
+    cursor: &mut Cursor<&[u8]>,
+) -> Result<Vec<u8>, ParseError> {
+    let verifydata = inputdata::decode_inputdata(version, cursor)?;
+
+    if verifydata.len() > MAX_VERIFYDATA_SIZE {
+        return Err(ParseError::TooManyBytes(Field::VerifyData));
+    }
+
+    Ok(verifydata)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,11 +286,176 @@ mod tests {
         };
 
         let mut bytes = Vec::new();
-        encode_call(&tx, &mut bytes);
+        encode_call(&tx, &mut bytes).unwrap();
 
         let mut cursor = Cursor::new(&bytes[..]);
         let decoded = decode_call(&mut cursor).unwrap();
 
         assert_eq!(tx, decoded);
     }
+
+    #[test]
+    fn encode_call_invalid_fields() {
+        let tx = Transaction {
+            version: 0,
+            target: Address::of("@target"),
+            func_name: "do_work".to_string(),
+            verifydata: vec![],
+            calldata: vec![],
+        };
+
+        let encode = |tx: &Transaction| {
+            let mut bytes = Vec::new();
+            let res = encode_call(tx, &mut bytes);
+
+            // Nothing is written on failure
+            if res.is_err() {
+                assert!(bytes.is_empty());
+            }
+
+            res
+        };
+
+        let mut invalid = tx.clone();
+        invalid.func_name = "f".repeat(256);
+        assert_eq!(
+            encode(&invalid),
+            Err(EncodeError::TooManyBytes {
+                field: Field::Function,
+                max: 255
+            })
+        );
+
+        let mut invalid = tx.clone();
+        invalid.verifydata = vec![0; MAX_VERIFYDATA_SIZE + 1];
+        assert_eq!(
+            encode(&invalid),
+            Err(EncodeError::TooManyBytes {
+                field: Field::VerifyData,
+                max: MAX_VERIFYDATA_SIZE
+            })
+        );
+
+        let mut invalid = tx;
+        invalid.calldata = vec![0; 256];
+        assert_eq!(
+            encode(&invalid),
+            Err(EncodeError::TooManyBytes {
+                field: Field::InputData,
+                max: 255
+            })
+        );
+
+        // From version `2` onwards the `InputData` isn't bounded by its length prefix
+        invalid.version = 2;
+        assert!(encode(&invalid).is_ok());
+    }
+
+    #[test]
+    fn decode_call_verifydata_too_large() {
+        let tx = Transaction {
+            version: 0,
+            target: Address::of("@target").into(),
+            func_name: "do_work".to_string(),
+            verifydata: vec![],
+            calldata: vec![0x10, 0x0, 0x30],
+        };
+
+        let mut bytes = Vec::new();
+        encode_version(&tx, &mut bytes);
+        encode_target(&tx, &mut bytes);
+        encode_func(&tx, &mut bytes);
+        inputdata::encode_inputdata(0, &[0xFF; MAX_VERIFYDATA_SIZE + 1], &mut bytes);
+        encode_calldata(&tx, &mut bytes);
+
+        let mut cursor = Cursor::new(&bytes[..]);
+        let err = decode_call(&mut cursor).unwrap_err();
+
+        assert_eq!(err, ParseError::TooManyBytes(Field::VerifyData));
+    }
+
+    #[test]
+    fn decode_call_too_large() {
+        let tx = Transaction {
+            version: 2,
+            target: Address::of("@target").into(),
+            func_name: "do_work".to_string(),
+            verifydata: vec![],
+            calldata: vec![0; crate::limits::MAX_CALL_SIZE],
+        };
+
+        let mut bytes = Vec::new();
+        encode_call(&tx, &mut bytes).unwrap();
+
+        let mut cursor = Cursor::new(&bytes[..]);
+        let err = decode_call(&mut cursor).unwrap_err();
+
+        assert_eq!(err, ParseError::TooManyBytes(Field::Message));
+    }
+
+    #[test]
+    fn peek_call_fields() {
+        let tx = Transaction {
+            version: 2,
+            target: Address::of("@target"),
+            func_name: "do_work".to_string(),
+            verifydata: vec![0xAA, 0xBB],
+            calldata: vec![0x10; 300],
+        };
+
+        let mut bytes = Vec::new();
+        encode_call(&tx, &mut bytes).unwrap();
+
+        let peek = |field| peek_call(&mut Cursor::new(&bytes[..]), field);
+
+        assert_eq!(peek(CallField::Version).unwrap(), &2u16.to_be_bytes());
+        assert_eq!(peek(CallField::Target).unwrap(), tx.target().as_slice());
+        assert_eq!(peek(CallField::Function).unwrap(), b"do_work");
+        assert_eq!(peek(CallField::VerifyData).unwrap(), &[0xAA, 0xBB]);
+        assert_eq!(peek(CallField::CallData).unwrap(), &tx.calldata[..]);
+    }
+
+    #[test]
+    fn peek_call_skips_other_fields() {
+        let tx = Transaction {
+            version: 0,
+            target: Address::of("@target"),
+            func_name: "do_work".to_string(),
+            verifydata: vec![],
+            calldata: vec![0x10, 0x20],
+        };
+
+        let mut bytes = Vec::new();
+        encode_call(&tx, &mut bytes).unwrap();
+
+        // An invalid UTF-8 function name fails only its own peeking
+        let func_offset = 2 + Address::len() + 1;
+        bytes[func_offset] = 0xFF;
+
+        let mut cursor = Cursor::new(&bytes[..]);
+        assert_eq!(
+            peek_call(&mut cursor, CallField::Function),
+            Err(ParseError::InvalidUTF8String(Field::Function))
+        );
+
+        let mut cursor = Cursor::new(&bytes[..]);
+        assert_eq!(
+            peek_call(&mut cursor, CallField::CallData).unwrap(),
+            &[0x10, 0x20]
+        );
+
+        // The fields following the peeked one aren't read
+        let truncated = &bytes[..func_offset];
+        let mut cursor = Cursor::new(truncated);
+        assert_eq!(
+            peek_call(&mut cursor, CallField::Target).unwrap(),
+            tx.target().as_slice()
+        );
+
+        let mut cursor = Cursor::new(truncated);
+        assert_eq!(
+            peek_call(&mut cursor, CallField::Function),
+            Err(ParseError::NotEnoughBytes(Field::Function))
+        );
+    }
 }
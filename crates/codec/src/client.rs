@@ -0,0 +1,1074 @@
+//! Strongly-typed receipts for Rust clients (e.g indexers).
+//!
+//! The types mirror the JSON returned by [`decode_receipt`](crate::api::json::decode_receipt),
+//! and can be built from that JSON, from a binary receipt or from a [`svm_types::Receipt`].
+//! A failed receipt holds only its `error` and `logs` (just like its JSON).
+
+use std::convert::TryFrom;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use svm_types::{
+    Address, Gas, GasBreakdown, LogTopic, ReceiptLog, ReceiptMetric, RuntimeError, State,
+    TemplateAddr, MAX_LOG_TOPICS,
+};
+use svm_types::{BatchReceipt, CallReceipt, DeployReceipt, SpawnReceipt, UpgradeReceipt};
+
+use crate::api::json::serde_types::{AddressWrapper, HexBlob, TemplateAddrWrapper};
+use crate::api::json::{JsonError, JsonSerdeUtils};
+use crate::ParseError;
+
+/// A receipt of kind `Deploy/Spawn/Call/Upgrade/Batch`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Receipt {
+    /// `Deploy Template` (JSON `type` of `deploy-template`)
+    DeployTemplate(DeployTemplateReceipt),
+
+    /// `Spawn Account` (JSON `type` of `spawn-account`)
+    SpawnAccount(SpawnAccountReceipt),
+
+    /// `Call Account` (JSON `type` of `call-account`)
+    CallAccount(CallAccountReceipt),
+
+    /// `Upgrade Template` (JSON `type` of `upgrade-template`)
+    UpgradeTemplate(UpgradeTemplateReceipt),
+
+    /// `Call Batch` (JSON `type` of `call-batch`)
+    CallBatch(CallBatchReceipt),
+}
+
+/// A `Deploy Template` receipt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeployTemplateReceipt {
+    /// Whether the transaction succeeded.
+    pub success: bool,
+
+    /// The error of a failed transaction.
+    pub error: Option<RuntimeError>,
+
+    /// The `Address` of the deployed `Template`.
+    pub addr: Option<TemplateAddr>,
+
+    /// The gas used by the transaction.
+    pub gas_used: Option<u64>,
+
+    /// The logs emitted by the transaction.
+    pub logs: Vec<ReceiptLog>,
+
+    /// The gas used broken down by phase (when detailed gas accounting is enabled).
+    pub gas_breakdown: Option<GasBreakdown>,
+
+    /// The version of the gas schedule the transaction has been priced by.
+    pub gas_schedule_version: u16,
+}
+
+/// An `Upgrade Template` receipt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpgradeTemplateReceipt {
+    /// Whether the transaction succeeded.
+    pub success: bool,
+
+    /// The error of a failed transaction.
+    pub error: Option<RuntimeError>,
+
+    /// The `Address` of the upgraded `Template`.
+    pub addr: Option<TemplateAddr>,
+
+    /// The version of the upgraded `Template`.
+    pub template_version: Option<u16>,
+
+    /// The gas used by the transaction.
+    pub gas_used: Option<u64>,
+
+    /// The logs emitted by the transaction.
+    pub logs: Vec<ReceiptLog>,
+
+    /// The gas used broken down by phase (when detailed gas accounting is enabled).
+    pub gas_breakdown: Option<GasBreakdown>,
+
+    /// The version of the gas schedule the transaction has been priced by.
+    pub gas_schedule_version: u16,
+}
+
+/// A `Spawn Account` receipt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnAccountReceipt {
+    /// Whether the transaction succeeded.
+    pub success: bool,
+
+    /// The error of a failed transaction.
+    pub error: Option<RuntimeError>,
+
+    /// The `Address` of the spawned `Account`.
+    pub account: Option<Address>,
+
+    /// The `State` of the spawned `Account` (following its initialization).
+    pub state: Option<State>,
+
+    /// The data returned by the `ctor`.
+    pub returndata: Option<Vec<u8>>,
+
+    /// The gas used by the transaction.
+    pub gas_used: Option<u64>,
+
+    /// The logs emitted by the transaction.
+    pub logs: Vec<ReceiptLog>,
+
+    /// The gas used broken down by phase (when detailed gas accounting is enabled).
+    pub gas_breakdown: Option<GasBreakdown>,
+
+    /// The metrics reported by the `ctor`.
+    pub metrics: Vec<ReceiptMetric>,
+
+    /// The version of the gas schedule the transaction has been priced by.
+    pub gas_schedule_version: u16,
+
+    /// The receipt of the call executed right after the `ctor` (if any).
+    pub call: Option<CallAccountReceipt>,
+}
+
+/// A `Call Account` receipt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallAccountReceipt {
+    /// Whether the transaction succeeded.
+    pub success: bool,
+
+    /// The error of a failed transaction.
+    pub error: Option<RuntimeError>,
+
+    /// The global `State` following the transaction.
+    pub new_state: Option<State>,
+
+    /// The data returned by the called function.
+    pub returndata: Option<Vec<u8>>,
+
+    /// The gas used by the transaction.
+    pub gas_used: Option<u64>,
+
+    /// The logs emitted by the transaction.
+    pub logs: Vec<ReceiptLog>,
+
+    /// The gas used broken down by phase (when detailed gas accounting is enabled).
+    pub gas_breakdown: Option<GasBreakdown>,
+
+    /// The metrics reported by the called function.
+    pub metrics: Vec<ReceiptMetric>,
+
+    /// The version of the gas schedule the transaction has been priced by.
+    pub gas_schedule_version: u16,
+}
+
+/// A `Call Batch` receipt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallBatchReceipt {
+    /// Whether all the batched calls succeeded.
+    pub success: bool,
+
+    /// The error of a failed transaction.
+    pub error: Option<RuntimeError>,
+
+    /// The global `State` following the last batched call.
+    pub new_state: Option<State>,
+
+    /// The receipts of the batched calls.
+    pub receipts: Vec<CallAccountReceipt>,
+
+    /// The gas used by the transaction.
+    pub gas_used: Option<u64>,
+
+    /// The logs emitted by the transaction.
+    pub logs: Vec<ReceiptLog>,
+
+    /// The gas used broken down by phase (when detailed gas accounting is enabled).
+    pub gas_breakdown: Option<GasBreakdown>,
+
+    /// The version of the gas schedule the transaction has been priced by.
+    pub gas_schedule_version: u16,
+}
+
+/// The kind of a [`RuntimeError`] (serialized as the JSON `err_type`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorCode {
+    /// Out of gas (`oog`)
+    Oog,
+
+    /// `template-not-found`
+    TemplateNotFound,
+
+    /// `account-not-found`
+    AccountNotFound,
+
+    /// `compilation-failed`
+    CompilationFailed,
+
+    /// `instantiation-failed`
+    InstantiationFailed,
+
+    /// `function-not-found`
+    FunctionNotFound,
+
+    /// `function-failed`
+    FunctionFailed,
+
+    /// `function-not-allowed`
+    FunctionNotAllowed,
+
+    /// `function-invalid-signature`
+    FunctionInvalidSignature,
+
+    /// `upgrade-not-allowed`
+    UpgradeNotAllowed,
+
+    /// `internal-error`
+    InternalError,
+
+    /// `insufficient-balance`
+    InsufficientBalance,
+
+    /// `storage-corrupted`
+    StorageCorrupted,
+}
+
+impl ErrorCode {
+    /// Returns the [`ErrorCode`] of `err`.
+    pub fn of(err: &RuntimeError) -> Self {
+        match err {
+            RuntimeError::OOG => Self::Oog,
+            RuntimeError::TemplateNotFound(..) => Self::TemplateNotFound,
+            RuntimeError::AccountNotFound(..) => Self::AccountNotFound,
+            RuntimeError::CompilationFailed { .. } => Self::CompilationFailed,
+            RuntimeError::InstantiationFailed { .. } => Self::InstantiationFailed,
+            RuntimeError::FuncNotFound { .. } => Self::FunctionNotFound,
+            RuntimeError::FuncFailed { .. } => Self::FunctionFailed,
+            RuntimeError::FuncNotAllowed { .. } => Self::FunctionNotAllowed,
+            RuntimeError::FuncInvalidSignature { .. } => Self::FunctionInvalidSignature,
+            RuntimeError::UpgradeNotAllowed { .. } => Self::UpgradeNotAllowed,
+            RuntimeError::InternalError { .. } => Self::InternalError,
+            RuntimeError::InsufficientBalance { .. } => Self::InsufficientBalance,
+            RuntimeError::StorageCorrupted { .. } => Self::StorageCorrupted,
+        }
+    }
+
+    /// Returns the JSON `err_type` of [`Self`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Oog => "oog",
+            Self::TemplateNotFound => "template-not-found",
+            Self::AccountNotFound => "account-not-found",
+            Self::CompilationFailed => "compilation-failed",
+            Self::InstantiationFailed => "instantiation-failed",
+            Self::FunctionNotFound => "function-not-found",
+            Self::FunctionFailed => "function-failed",
+            Self::FunctionNotAllowed => "function-not-allowed",
+            Self::FunctionInvalidSignature => "function-invalid-signature",
+            Self::UpgradeNotAllowed => "upgrade-not-allowed",
+            Self::InternalError => "internal-error",
+            Self::InsufficientBalance => "insufficient-balance",
+            Self::StorageCorrupted => "storage-corrupted",
+        }
+    }
+}
+
+impl Receipt {
+    /// Decodes a binary receipt.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        crate::receipt::try_decode_receipt(bytes).map(Self::from)
+    }
+
+    /// Parses the JSON of a decoded receipt (see [`decode_receipt`](crate::api::json::decode_receipt)).
+    ///
+    /// A partially decoded receipt (i.e one having a `partial` key) is rejected.
+    pub fn from_json(json: &str) -> Result<Self, JsonError> {
+        RawReceipt::from_json_str(json)?.into_receipt()
+    }
+
+    /// Returns whether the transaction succeeded.
+    pub fn success(&self) -> bool {
+        match self {
+            Self::DeployTemplate(receipt) => receipt.success,
+            Self::SpawnAccount(receipt) => receipt.success,
+            Self::CallAccount(receipt) => receipt.success,
+            Self::UpgradeTemplate(receipt) => receipt.success,
+            Self::CallBatch(receipt) => receipt.success,
+        }
+    }
+
+    /// Returns the error of a failed transaction.
+    pub fn error(&self) -> Option<&RuntimeError> {
+        match self {
+            Self::DeployTemplate(receipt) => receipt.error.as_ref(),
+            Self::SpawnAccount(receipt) => receipt.error.as_ref(),
+            Self::CallAccount(receipt) => receipt.error.as_ref(),
+            Self::UpgradeTemplate(receipt) => receipt.error.as_ref(),
+            Self::CallBatch(receipt) => receipt.error.as_ref(),
+        }
+    }
+
+    /// Returns the [`ErrorCode`] of a failed transaction.
+    pub fn error_code(&self) -> Option<ErrorCode> {
+        self.error().map(ErrorCode::of)
+    }
+
+    /// Returns whether the transaction has run out of gas.
+    pub fn is_oog(&self) -> bool {
+        self.reverted_with(ErrorCode::Oog)
+    }
+
+    /// Returns whether the transaction has failed with an error of kind `code`.
+    pub fn reverted_with(&self, code: ErrorCode) -> bool {
+        self.error_code() == Some(code)
+    }
+
+    /// Returns the gas used by the transaction (not available for failed transactions).
+    pub fn gas_used(&self) -> Option<u64> {
+        match self {
+            Self::DeployTemplate(receipt) => receipt.gas_used,
+            Self::SpawnAccount(receipt) => receipt.gas_used,
+            Self::CallAccount(receipt) => receipt.gas_used,
+            Self::UpgradeTemplate(receipt) => receipt.gas_used,
+            Self::CallBatch(receipt) => receipt.gas_used,
+        }
+    }
+
+    /// Returns the version of the gas schedule the transaction has been priced by.
+    pub fn gas_schedule_version(&self) -> u16 {
+        match self {
+            Self::DeployTemplate(receipt) => receipt.gas_schedule_version,
+            Self::SpawnAccount(receipt) => receipt.gas_schedule_version,
+            Self::CallAccount(receipt) => receipt.gas_schedule_version,
+            Self::UpgradeTemplate(receipt) => receipt.gas_schedule_version,
+            Self::CallBatch(receipt) => receipt.gas_schedule_version,
+        }
+    }
+
+    /// Returns the logs emitted by the transaction.
+    pub fn logs(&self) -> &[ReceiptLog] {
+        match self {
+            Self::DeployTemplate(receipt) => &receipt.logs,
+            Self::SpawnAccount(receipt) => &receipt.logs,
+            Self::CallAccount(receipt) => &receipt.logs,
+            Self::UpgradeTemplate(receipt) => &receipt.logs,
+            Self::CallBatch(receipt) => &receipt.logs,
+        }
+    }
+}
+
+impl From<svm_types::Receipt> for Receipt {
+    fn from(receipt: svm_types::Receipt) -> Self {
+        match receipt {
+            svm_types::Receipt::Deploy(receipt) => Self::DeployTemplate(receipt.into()),
+            svm_types::Receipt::Spawn(receipt) => Self::SpawnAccount(receipt.into()),
+            svm_types::Receipt::Call(receipt) => Self::CallAccount(receipt.into()),
+            svm_types::Receipt::Upgrade(receipt) => Self::UpgradeTemplate(receipt.into()),
+            svm_types::Receipt::Batch(receipt) => Self::CallBatch(receipt.into()),
+        }
+    }
+}
+
+impl From<DeployReceipt> for DeployTemplateReceipt {
+    fn from(receipt: DeployReceipt) -> Self {
+        if !receipt.success {
+            return Self {
+                success: false,
+                error: receipt.error,
+                addr: None,
+                gas_used: None,
+                logs: receipt.logs,
+                gas_breakdown: None,
+                gas_schedule_version: receipt.gas_schedule_version,
+            };
+        }
+
+        Self {
+            success: true,
+            error: None,
+            addr: receipt.addr,
+            gas_used: gas_to_option(&receipt.gas_used),
+            logs: receipt.logs,
+            gas_breakdown: receipt.gas_breakdown,
+            gas_schedule_version: receipt.gas_schedule_version,
+        }
+    }
+}
+
+impl From<SpawnReceipt> for SpawnAccountReceipt {
+    fn from(receipt: SpawnReceipt) -> Self {
+        if !receipt.success {
+            return Self {
+                success: false,
+                error: receipt.error,
+                account: None,
+                state: None,
+                returndata: None,
+                gas_used: None,
+                logs: receipt.logs,
+                gas_breakdown: None,
+                metrics: Vec::new(),
+                gas_schedule_version: receipt.gas_schedule_version,
+                call: None,
+            };
+        }
+
+        Self {
+            success: true,
+            error: None,
+            account: receipt.account_addr,
+            state: receipt.init_state,
+            returndata: receipt.returndata,
+            gas_used: gas_to_option(&receipt.gas_used),
+            logs: receipt.logs,
+            gas_breakdown: receipt.gas_breakdown,
+            metrics: receipt.metrics,
+            gas_schedule_version: receipt.gas_schedule_version,
+            call: receipt.call.map(CallAccountReceipt::from),
+        }
+    }
+}
+
+impl From<CallReceipt> for CallAccountReceipt {
+    fn from(receipt: CallReceipt) -> Self {
+        if !receipt.success {
+            return Self {
+                success: false,
+                error: receipt.error,
+                new_state: None,
+                returndata: None,
+                gas_used: None,
+                logs: receipt.logs,
+                gas_breakdown: None,
+                metrics: Vec::new(),
+                gas_schedule_version: receipt.gas_schedule_version,
+            };
+        }
+
+        Self {
+            success: true,
+            error: None,
+            new_state: receipt.new_state,
+            returndata: receipt.returndata,
+            gas_used: gas_to_option(&receipt.gas_used),
+            logs: receipt.logs,
+            gas_breakdown: receipt.gas_breakdown,
+            metrics: receipt.metrics,
+            gas_schedule_version: receipt.gas_schedule_version,
+        }
+    }
+}
+
+impl From<UpgradeReceipt> for UpgradeTemplateReceipt {
+    fn from(receipt: UpgradeReceipt) -> Self {
+        if !receipt.success {
+            return Self {
+                success: false,
+                error: receipt.error,
+                addr: None,
+                template_version: None,
+                gas_used: None,
+                logs: receipt.logs,
+                gas_breakdown: None,
+                gas_schedule_version: receipt.gas_schedule_version,
+            };
+        }
+
+        Self {
+            success: true,
+            error: None,
+            addr: receipt.addr,
+            template_version: Some(receipt.template_version),
+            gas_used: gas_to_option(&receipt.gas_used),
+            logs: receipt.logs,
+            gas_breakdown: receipt.gas_breakdown,
+            gas_schedule_version: receipt.gas_schedule_version,
+        }
+    }
+}
+
+impl From<BatchReceipt> for CallBatchReceipt {
+    fn from(receipt: BatchReceipt) -> Self {
+        if !receipt.success {
+            return Self {
+                success: false,
+                error: receipt.error,
+                new_state: None,
+                receipts: Vec::new(),
+                gas_used: None,
+                logs: receipt.logs,
+                gas_breakdown: None,
+                gas_schedule_version: receipt.gas_schedule_version,
+            };
+        }
+
+        Self {
+            success: true,
+            error: None,
+            new_state: receipt.new_state,
+            receipts: receipt
+                .receipts
+                .into_iter()
+                .map(CallAccountReceipt::from)
+                .collect(),
+            gas_used: gas_to_option(&receipt.gas_used),
+            logs: receipt.logs,
+            gas_breakdown: receipt.gas_breakdown,
+            gas_schedule_version: receipt.gas_schedule_version,
+        }
+    }
+}
+
+fn gas_to_option(gas: &Gas) -> Option<u64> {
+    if gas.is_some() {
+        Some(gas.unwrap())
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum ReceiptType {
+    DeployTemplate,
+    SpawnAccount,
+    CallAccount,
+    UpgradeTemplate,
+    CallBatch,
+}
+
+/// The JSON of a decoded receipt (holding the keys of all the receipt types).
+#[derive(Debug, Serialize, Deserialize)]
+struct RawReceipt {
+    #[serde(rename = "type")]
+    ty: ReceiptType,
+    success: bool,
+    err_type: Option<ErrorCode>,
+    template_addr: Option<TemplateAddrWrapper>,
+    account_addr: Option<AddressWrapper>,
+    func: Option<String>,
+    message: Option<String>,
+    balance: Option<u64>,
+    amount: Option<u64>,
+    addr: Option<TemplateAddrWrapper>,
+    template_version: Option<u16>,
+    account: Option<AddressWrapper>,
+    state: Option<HexBlob<Vec<u8>>>,
+    new_state: Option<HexBlob<Vec<u8>>>,
+    returndata: Option<HexBlob<Vec<u8>>>,
+    gas_used: Option<u64>,
+    #[serde(default)]
+    logs: Vec<RawLog>,
+    gas_breakdown: Option<RawGasBreakdown>,
+    #[serde(default)]
+    metrics: Vec<RawMetric>,
+    #[serde(default)]
+    gas_schedule_version: u16,
+    call: Option<Box<RawReceipt>>,
+    receipts: Option<Vec<RawReceipt>>,
+    partial: Option<Value>,
+}
+
+impl JsonSerdeUtils for RawReceipt {}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RawLog {
+    code: Option<u32>,
+    #[serde(default)]
+    topics: Vec<HexBlob<Vec<u8>>>,
+    data: String,
+}
+
+impl RawLog {
+    /// A log having a `code` is an event (having up to [`MAX_LOG_TOPICS`] 32 bytes long `topics`).
+    fn into_log(self) -> Result<ReceiptLog, JsonError> {
+        let data = self.data.into_bytes();
+
+        let code = match self.code {
+            Some(code) => code,
+            None if self.topics.is_empty() => return Ok(ReceiptLog::new(data)),
+            None => {
+                return Err(JsonError::MissingField {
+                    field_name: "code".to_string(),
+                })
+            }
+        };
+
+        if self.topics.len() > MAX_LOG_TOPICS {
+            return Err(JsonError::InvalidField {
+                path: "topics".to_string(),
+            });
+        }
+
+        let topics = self
+            .topics
+            .into_iter()
+            .map(|topic| {
+                LogTopic::try_from(&topic.0[..]).map_err(|_| JsonError::InvalidField {
+                    path: "topics".to_string(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(ReceiptLog::event(code, topics, data))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RawMetric {
+    id: u32,
+    value: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RawGasBreakdown {
+    intrinsic: u64,
+    execution: u64,
+    storage: u64,
+    logs: u64,
+    refund: u64,
+}
+
+impl From<RawGasBreakdown> for GasBreakdown {
+    fn from(raw: RawGasBreakdown) -> Self {
+        GasBreakdown {
+            intrinsic: raw.intrinsic,
+            execution: raw.execution,
+            storage: raw.storage,
+            logs: raw.logs,
+            refund: raw.refund,
+        }
+    }
+}
+
+impl RawReceipt {
+    fn into_receipt(self) -> Result<Receipt, JsonError> {
+        let receipt = match self.ty {
+            ReceiptType::DeployTemplate => Receipt::DeployTemplate(self.into_deploy()?),
+            ReceiptType::SpawnAccount => Receipt::SpawnAccount(self.into_spawn()?),
+            ReceiptType::CallAccount => Receipt::CallAccount(self.into_call()?),
+            ReceiptType::UpgradeTemplate => Receipt::UpgradeTemplate(self.into_upgrade()?),
+            ReceiptType::CallBatch => Receipt::CallBatch(self.into_batch()?),
+        };
+
+        Ok(receipt)
+    }
+
+    fn into_deploy(mut self) -> Result<DeployTemplateReceipt, JsonError> {
+        let error = self.take_error()?;
+        let logs = self.take_logs()?;
+
+        Ok(DeployTemplateReceipt {
+            success: self.success,
+            error,
+            addr: self.addr.map(Into::into),
+            gas_used: self.gas_used,
+            logs,
+            gas_breakdown: self.gas_breakdown.map(Into::into),
+            gas_schedule_version: self.gas_schedule_version,
+        })
+    }
+
+    fn into_upgrade(mut self) -> Result<UpgradeTemplateReceipt, JsonError> {
+        let error = self.take_error()?;
+        let logs = self.take_logs()?;
+
+        Ok(UpgradeTemplateReceipt {
+            success: self.success,
+            error,
+            addr: self.addr.map(Into::into),
+            template_version: self.template_version,
+            gas_used: self.gas_used,
+            logs,
+            gas_breakdown: self.gas_breakdown.map(Into::into),
+            gas_schedule_version: self.gas_schedule_version,
+        })
+    }
+
+    fn into_batch(mut self) -> Result<CallBatchReceipt, JsonError> {
+        let error = self.take_error()?;
+        let logs = self.take_logs()?;
+        let receipts = self
+            .receipts
+            .take()
+            .unwrap_or_default()
+            .into_iter()
+            .map(RawReceipt::into_call)
+            .collect::<Result<_, _>>()?;
+
+        Ok(CallBatchReceipt {
+            success: self.success,
+            error,
+            new_state: to_state(self.new_state, "new_state")?,
+            receipts,
+            gas_used: self.gas_used,
+            logs,
+            gas_breakdown: self.gas_breakdown.map(Into::into),
+            gas_schedule_version: self.gas_schedule_version,
+        })
+    }
+
+    fn into_spawn(mut self) -> Result<SpawnAccountReceipt, JsonError> {
+        let error = self.take_error()?;
+        let logs = self.take_logs()?;
+        let metrics = self.take_metrics();
+        let call = match self.call.take() {
+            Some(call) => Some(call.into_call()?),
+            None => None,
+        };
+
+        Ok(SpawnAccountReceipt {
+            success: self.success,
+            error,
+            account: self.account.map(Into::into),
+            state: to_state(self.state, "state")?,
+            returndata: self.returndata.map(|blob| blob.0),
+            gas_used: self.gas_used,
+            logs,
+            gas_breakdown: self.gas_breakdown.map(Into::into),
+            metrics,
+            gas_schedule_version: self.gas_schedule_version,
+            call,
+        })
+    }
+
+    fn into_call(mut self) -> Result<CallAccountReceipt, JsonError> {
+        let error = self.take_error()?;
+        let logs = self.take_logs()?;
+        let metrics = self.take_metrics();
+
+        Ok(CallAccountReceipt {
+            success: self.success,
+            error,
+            new_state: to_state(self.new_state, "new_state")?,
+            returndata: self.returndata.map(|blob| blob.0),
+            gas_used: self.gas_used,
+            logs,
+            gas_breakdown: self.gas_breakdown.map(Into::into),
+            metrics,
+            gas_schedule_version: self.gas_schedule_version,
+        })
+    }
+
+    fn take_logs(&mut self) -> Result<Vec<ReceiptLog>, JsonError> {
+        self.logs.drain(..).map(RawLog::into_log).collect()
+    }
+
+    fn take_metrics(&mut self) -> Vec<ReceiptMetric> {
+        self.metrics
+            .drain(..)
+            .map(|metric| ReceiptMetric::new(metric.id, metric.value))
+            .collect()
+    }
+
+    fn take_error(&mut self) -> Result<Option<RuntimeError>, JsonError> {
+        if self.partial.is_some() {
+            return Err(JsonError::InvalidField {
+                path: "partial".to_string(),
+            });
+        }
+
+        if self.success {
+            return Ok(None);
+        }
+
+        let code = match self.err_type {
+            Some(code) => code,
+            None => return Ok(None),
+        };
+
+        let err = match code {
+            ErrorCode::Oog => RuntimeError::OOG,
+            ErrorCode::TemplateNotFound => RuntimeError::TemplateNotFound(self.template()?),
+            ErrorCode::AccountNotFound => RuntimeError::AccountNotFound(self.target()?),
+            ErrorCode::CompilationFailed => RuntimeError::CompilationFailed {
+                target: self.target()?,
+                template: self.template()?,
+                msg: self.message()?,
+            },
+            ErrorCode::InstantiationFailed => RuntimeError::InstantiationFailed {
+                target: self.target()?,
+                template: self.template()?,
+                msg: self.message()?,
+            },
+            ErrorCode::FunctionNotFound => RuntimeError::FuncNotFound {
+                target: self.target()?,
+                template: self.template()?,
+                func: self.func()?,
+            },
+            ErrorCode::FunctionFailed => RuntimeError::FuncFailed {
+                target: self.target()?,
+                template: self.template()?,
+                func: self.func()?,
+                msg: self.message()?,
+            },
+            ErrorCode::FunctionNotAllowed => RuntimeError::FuncNotAllowed {
+                target: self.target()?,
+                template: self.template()?,
+                func: self.func()?,
+                msg: self.message()?,
+            },
+            ErrorCode::FunctionInvalidSignature => RuntimeError::FuncInvalidSignature {
+                target: self.target()?,
+                template: self.template()?,
+                func: self.func()?,
+            },
+            ErrorCode::UpgradeNotAllowed => RuntimeError::UpgradeNotAllowed {
+                template: self.template()?,
+                msg: self.message()?,
+            },
+            ErrorCode::InternalError => RuntimeError::InternalError {
+                msg: self.message()?,
+            },
+            ErrorCode::InsufficientBalance => RuntimeError::InsufficientBalance {
+                target: self.target()?,
+                balance: required(self.balance.take(), "balance")?,
+                amount: required(self.amount.take(), "amount")?,
+            },
+            ErrorCode::StorageCorrupted => RuntimeError::StorageCorrupted {
+                target: self.target()?,
+                msg: self.message()?,
+            },
+        };
+
+        Ok(Some(err))
+    }
+
+    fn target(&mut self) -> Result<Address, JsonError> {
+        required(self.account_addr.take(), "account_addr").map(Into::into)
+    }
+
+    fn template(&mut self) -> Result<TemplateAddr, JsonError> {
+        required(self.template_addr.take(), "template_addr").map(Into::into)
+    }
+
+    fn func(&mut self) -> Result<String, JsonError> {
+        required(self.func.take(), "func")
+    }
+
+    fn message(&mut self) -> Result<String, JsonError> {
+        required(self.message.take(), "message")
+    }
+}
+
+fn required<T>(value: Option<T>, field_name: &str) -> Result<T, JsonError> {
+    value.ok_or_else(|| JsonError::MissingField {
+        field_name: field_name.to_string(),
+    })
+}
+
+fn to_state(blob: Option<HexBlob<Vec<u8>>>, path: &str) -> Result<Option<State>, JsonError> {
+    match blob {
+        Some(blob) if blob.0.len() != State::len() => Err(JsonError::InvalidField {
+            path: path.to_string(),
+        }),
+        Some(blob) => Ok(Some(State::from(&blob.0[..]))),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn decode_json(bytes: &[u8]) -> String {
+        let json = json!({ "data": HexBlob(bytes) }).to_string();
+
+        crate::api::json::decode_receipt(&json).unwrap().to_string()
+    }
+
+    fn logs() -> Vec<ReceiptLog> {
+        vec![
+            ReceiptLog::new(b"Log entry #1".to_vec()),
+            ReceiptLog::new(b"Log entry #2".to_vec()),
+        ]
+    }
+
+    #[test]
+    fn client_receipt_deploy() {
+        let receipt = DeployReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            addr: Some(TemplateAddr::repeat(0x10)),
+            gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs: logs(),
+        };
+
+        let bytes = crate::receipt::encode_deploy(&receipt);
+        let from_bytes = Receipt::from_bytes(&bytes).unwrap();
+        let from_json = Receipt::from_json(&decode_json(&bytes)).unwrap();
+
+        assert_eq!(from_bytes, from_json);
+        assert_eq!(from_json, Receipt::DeployTemplate(receipt.into()));
+        assert!(from_json.success());
+        assert_eq!(from_json.gas_used(), Some(10));
+        assert_eq!(from_json.logs(), &logs()[..]);
+    }
+
+    #[test]
+    fn client_receipt_spawn_with_call() {
+        let call = CallReceipt {
+            version: 2,
+            success: true,
+            error: None,
+            new_state: Some(State::repeat(0xB0)),
+            returndata: Some(vec![0x40]),
+            gas_used: Gas::with(5),
+            gas_breakdown: Some(GasBreakdown {
+                intrinsic: 1,
+                execution: 2,
+                storage: 1,
+                logs: 1,
+                refund: 0,
+            }),
+            gas_schedule_version: 3,
+            logs_bloom: None,
+            logs: Vec::new(),
+            metrics: Vec::new(),
+        };
+
+        let receipt = SpawnReceipt {
+            version: 7,
+            success: true,
+            error: None,
+            account_addr: Some(Address::repeat(0x10)),
+            init_state: Some(State::repeat(0xA0)),
+            returndata: Some(vec![0x10, 0x20, 0x30]),
+            gas_used: Gas::with(15),
+            gas_breakdown: Some(GasBreakdown {
+                intrinsic: 5,
+                execution: 10,
+                storage: 0,
+                logs: 0,
+                refund: 0,
+            }),
+            gas_schedule_version: 3,
+            logs_bloom: None,
+            logs: logs(),
+            metrics: Vec::new(),
+            call: Some(call),
+        };
+
+        let bytes = crate::receipt::encode_spawn(&receipt);
+        let from_bytes = Receipt::from_bytes(&bytes).unwrap();
+        let from_json = Receipt::from_json(&decode_json(&bytes)).unwrap();
+
+        assert_eq!(from_bytes, from_json);
+        assert_eq!(from_json.gas_schedule_version(), 3);
+
+        match from_json {
+            Receipt::SpawnAccount(spawn) => {
+                let call = spawn.call.unwrap();
+
+                assert_eq!(spawn.account, Some(Address::repeat(0x10)));
+                assert_eq!(call.new_state, Some(State::repeat(0xB0)));
+                assert_eq!(call.gas_schedule_version, 3);
+                assert_eq!(call.gas_breakdown.unwrap().execution, 2);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn client_receipt_errors() {
+        let target = Address::repeat(0x10);
+        let template = TemplateAddr::repeat(0x20);
+
+        let errors = vec![
+            RuntimeError::OOG,
+            RuntimeError::TemplateNotFound(template.clone()),
+            RuntimeError::AccountNotFound(target.clone()),
+            RuntimeError::CompilationFailed {
+                target: target.clone(),
+                template: template.clone(),
+                msg: "Invalid code".to_string(),
+            },
+            RuntimeError::InstantiationFailed {
+                target: target.clone(),
+                template: template.clone(),
+                msg: "Invalid input".to_string(),
+            },
+            RuntimeError::FuncNotFound {
+                target: target.clone(),
+                template: template.clone(),
+                func: "do_something".to_string(),
+            },
+            RuntimeError::FuncFailed {
+                target: target.clone(),
+                template: template.clone(),
+                func: "do_something".to_string(),
+                msg: "Invalid input".to_string(),
+            },
+            RuntimeError::FuncNotAllowed {
+                target: target.clone(),
+                template: template.clone(),
+                func: "init".to_string(),
+                msg: "expected function to be a non-ctor".to_string(),
+            },
+            RuntimeError::FuncInvalidSignature {
+                target: target.clone(),
+                template: template.clone(),
+                func: "do_something".to_string(),
+            },
+            RuntimeError::StorageCorrupted {
+                target: target.clone(),
+                msg: "checksum mismatch".to_string(),
+            },
+        ];
+
+        for err in errors {
+            let code = ErrorCode::of(&err);
+            let receipt = CallReceipt::from_err(err.clone(), logs());
+
+            let bytes = crate::receipt::encode_call(&receipt);
+            let json = decode_json(&bytes);
+
+            let err_type: Value = serde_json::from_str(&json).unwrap();
+            assert_eq!(err_type["err_type"], json!(code.as_str()));
+
+            let from_bytes = Receipt::from_bytes(&bytes).unwrap();
+            let from_json = Receipt::from_json(&json).unwrap();
+
+            assert_eq!(from_bytes, from_json);
+            assert!(!from_json.success());
+            assert_eq!(from_json.error(), Some(&err));
+            assert!(from_json.reverted_with(code));
+            assert_eq!(from_json.is_oog(), code == ErrorCode::Oog);
+        }
+    }
+
+    #[test]
+    fn client_receipt_partial_rejected() {
+        let receipt = CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            new_state: Some(State::repeat(0xA0)),
+            returndata: Some(vec![0x10, 0x20]),
+            gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: Vec::new(),
+            metrics: Vec::new(),
+        };
+
+        let bytes = crate::receipt::encode_call(&receipt);
+        let bytes = &bytes[..bytes.len() - 3];
+
+        assert!(Receipt::from_bytes(bytes).is_err());
+
+        let json = json!({ "data": HexBlob(bytes), "tolerant": true }).to_string();
+        let json = crate::api::json::decode_receipt(&json).unwrap().to_string();
+
+        assert_eq!(
+            Receipt::from_json(&json),
+            Err(JsonError::InvalidField {
+                path: "partial".to_string()
+            })
+        );
+    }
+}
@@ -28,7 +28,7 @@ pub const fn byte_size() -> usize {
 /// Encodes a binary [`Context`] of a transaction.
 pub fn encode(context: &Context, w: &mut Vec<u8>) {
     w.write_tx_id(context.tx_id());
-    w.write_u64_be(context.layer().0);
+    w.write_u64_be(context.layer().as_u64());
     w.write_state(context.state());
 }
 
@@ -41,6 +41,6 @@ pub fn decode(cursor: &mut Cursor<&[u8]>) -> std::io::Result<Context> {
     let layer = cursor.read_u64_be()?;
     let state = cursor.read_state()?;
 
-    let context = Context::new(tx_id, Layer(layer), state);
+    let context = Context::new(tx_id, Layer::new(layer), state);
     Ok(context)
 }
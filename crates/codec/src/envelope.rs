@@ -13,12 +13,25 @@
 //!  +-------------+--------------+----------------+----------------+
 //!
 //! ```
+//!
+//! Where an [`Envelope`] is to be extended, it's followed by a `Tagged Fields` block
+//! (see [`encode_with_fields`] and the [`tagged`](crate::tagged) module).
 
 use std::io::Cursor;
 
 use svm_types::{Envelope, Gas};
 
-use crate::{ReadExt, WriteExt};
+use crate::tagged::{self, TaggedFields};
+use crate::{Field, ParseError, ReadExt, WriteExt};
+
+/// The tags of the fields following an [`Envelope`] (see [`encode_with_fields`]).
+///
+/// Right now, none is defined. Any optional field is kept as is,
+/// while any required field isn't supported.
+pub mod tags {
+    /// The tags of the required fields understood by this version of the codec.
+    pub const KNOWN: &[u64] = &[];
+}
 
 /// Returns the number of bytes required to hold a binary [`Envelope`].
 pub const fn byte_size() -> usize {
@@ -52,3 +65,92 @@ pub fn decode(cursor: &mut Cursor<&[u8]>) -> std::io::Result<Envelope> {
     let envelope = Envelope::new(principal, amount, gas_limit, gas_fee);
     Ok(envelope)
 }
+
+/// Encodes a binary [`Envelope`] followed by the Tagged `fields` extending it.
+pub fn encode_with_fields(envelope: &Envelope, fields: &TaggedFields, w: &mut Vec<u8>) {
+    encode(envelope, w);
+
+    tagged::encode_tagged(fields, w);
+}
+
+/// Decodes a binary [`Envelope`] followed by the Tagged fields extending it.
+///
+/// The optional fields unknown to this version of the codec are returned as is
+/// (see [`tagged::decode_tagged`]).
+pub fn decode_with_fields(
+    cursor: &mut Cursor<&[u8]>,
+) -> Result<(Envelope, TaggedFields), ParseError> {
+    let envelope = decode(cursor).map_err(|_| ParseError::NotEnoughBytes(Field::Envelope))?;
+    let fields = tagged::decode_tagged(cursor, tags::KNOWN)?;
+
+    Ok((envelope, fields))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_types::Address;
+
+    fn envelope() -> Envelope {
+        Envelope::new(Address::repeat(0x10), 10, Gas::with(100), 20)
+    }
+
+    #[test]
+    fn envelope_with_fields_encode_decode() {
+        let mut fields = TaggedFields::new();
+        fields.insert(1, vec![0xAB, 0xCD]);
+
+        let mut bytes = Vec::new();
+        encode_with_fields(&envelope(), &fields, &mut bytes);
+
+        let mut cursor = Cursor::new(&bytes[..]);
+        let decoded = decode_with_fields(&mut cursor).unwrap();
+
+        assert_eq!(decoded, (envelope(), fields));
+        assert_eq!(cursor.position() as usize, bytes.len());
+    }
+
+    #[test]
+    fn envelope_with_fields_decoded_as_plain_envelope() {
+        let mut fields = TaggedFields::new();
+        fields.insert(3, vec![0xAB]);
+
+        let mut bytes = Vec::new();
+        encode_with_fields(&envelope(), &fields, &mut bytes);
+
+        // A decoder unaware of the fields reads the `Envelope` only
+        let mut cursor = Cursor::new(&bytes[..]);
+        assert_eq!(decode(&mut cursor).unwrap(), envelope());
+        assert_eq!(cursor.position() as usize, byte_size());
+    }
+
+    #[test]
+    fn envelope_with_unknown_required_field() {
+        let mut fields = TaggedFields::new();
+        fields.insert(2, vec![0xAB]);
+
+        let mut bytes = Vec::new();
+        encode_with_fields(&envelope(), &fields, &mut bytes);
+
+        let mut cursor = Cursor::new(&bytes[..]);
+
+        assert_eq!(
+            decode_with_fields(&mut cursor),
+            Err(ParseError::NotSupported(Field::TaggedFieldTag))
+        );
+    }
+
+    #[test]
+    fn envelope_with_fields_truncated_envelope() {
+        let mut bytes = Vec::new();
+        encode(&envelope(), &mut bytes);
+
+        let mut cursor = Cursor::new(&bytes[..byte_size() - 1]);
+
+        assert_eq!(
+            decode_with_fields(&mut cursor),
+            Err(ParseError::NotEnoughBytes(Field::Envelope))
+        );
+    }
+}
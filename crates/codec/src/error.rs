@@ -1,11 +1,12 @@
 use thiserror::Error;
 
 use std::fmt;
+use std::io::Cursor;
 
 use crate::Field;
 
 #[allow(missing_docs)]
-#[derive(PartialEq, Clone, Error)]
+#[derive(PartialEq, Eq, Clone, Error)]
 pub enum ParseError {
     ReachedEOF,
     ExpectedEOF,
@@ -15,6 +16,7 @@ pub enum ParseError {
     NotSupported(Field),
     InvalidUTF8String(Field),
     UnexpectedLayout(Field),
+    NonCanonical(Field),
     InvalidSection,
 }
 
@@ -37,6 +39,9 @@ impl fmt::Display for ParseError {
             ParseError::UnexpectedLayout(field) => {
                 write!(f, "Unexpected Wasm value layout for field `{}`", field)
             }
+            ParseError::NonCanonical(field) => {
+                write!(f, "Field `{}` isn't canonically encoded", field)
+            }
             ParseError::InvalidSection => write!(f, "Invalid section kind"),
         }
     }
@@ -47,3 +52,158 @@ impl fmt::Debug for ParseError {
         <Self as fmt::Display>::fmt(self, f)
     }
 }
+
+impl ParseError {
+    /// The [`Field`] the error refers to (if any).
+    pub fn field(&self) -> Option<Field> {
+        match self {
+            ParseError::EmptyField(field)
+            | ParseError::NotEnoughBytes(field)
+            | ParseError::TooManyBytes(field)
+            | ParseError::NotSupported(field)
+            | ParseError::InvalidUTF8String(field)
+            | ParseError::UnexpectedLayout(field)
+            | ParseError::NonCanonical(field) => Some(*field),
+            ParseError::ReachedEOF | ParseError::ExpectedEOF | ParseError::InvalidSection => None,
+        }
+    }
+
+    /// Locates the error, given the `cursor` decoding has stopped at
+    /// and the path of the structure being decoded (e.g `SpawnAccount`).
+    pub fn at(self, structure: &str, cursor: &Cursor<&[u8]>) -> ParseErrorAt {
+        let offset = cursor.position();
+        let available = (cursor.get_ref().len() as u64).saturating_sub(offset);
+
+        let (path, expected) = match (&self, self.field()) {
+            (ParseError::NotEnoughBytes(..), Some(field)) => (
+                format!("{}.{}", structure, field.path_name()),
+                field.byte_size().map(|size| size as u64),
+            ),
+            (_, Some(field)) => (format!("{}.{}", structure, field.path_name()), None),
+            (_, None) => (structure.to_string(), None),
+        };
+
+        ParseErrorAt {
+            error: self,
+            offset,
+            path,
+            expected,
+            available,
+        }
+    }
+}
+
+/// A [`ParseError`] along with where it has occurred (see [`ParseError::at`]).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseErrorAt {
+    /// The error.
+    pub error: ParseError,
+
+    /// The offset (within the decoded bytes) decoding has stopped at.
+    pub offset: u64,
+
+    /// The path of the field that couldn't be decoded (e.g `SpawnAccount.ctor`).
+    pub path: String,
+
+    /// The byte size of that field (when it has a fixed width, and there weren't enough bytes).
+    pub expected: Option<u64>,
+
+    /// The number of bytes left from `offset` on.
+    pub available: u64,
+}
+
+impl fmt::Display for ParseErrorAt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} (at `{}`, offset {}: ",
+            self.error, self.path, self.offset
+        )?;
+
+        match self.expected {
+            Some(expected) => write!(
+                f,
+                "expected {} bytes, {} available)",
+                expected, self.available
+            ),
+            None => write!(f, "{} bytes available)", self.available),
+        }
+    }
+}
+
+impl std::error::Error for ParseErrorAt {}
+
+/// The reason a message can't be encoded (see [`call::encode_call`](crate::call::encode_call),
+/// [`spawn::encode`](crate::spawn::encode) and [`batch::encode_batch`](crate::batch::encode_batch)).
+///
+/// The encoders validate the whole message before writing any of it.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum EncodeError {
+    /// A field exceeds the maximum size its encoding can hold.
+    #[error("Field `{field}` exceeds {max} bytes")]
+    TooManyBytes {
+        /// The field.
+        field: Field,
+        /// Its maximum size (in bytes).
+        max: usize,
+    },
+
+    /// A field holds more items than its encoding can hold.
+    #[error("Field `{field}` exceeds {max} items")]
+    TooManyItems {
+        /// The field.
+        field: Field,
+        /// Its maximum number of items.
+        max: usize,
+    },
+
+    /// A field isn't supported by the format version of the message.
+    #[error("Field `{0}` isn't supported by the message version")]
+    NotSupported(Field),
+}
+
+/// Validates that a field of `size` bytes doesn't exceed `max` bytes.
+pub(crate) fn validate_size(size: usize, max: usize, field: Field) -> Result<(), EncodeError> {
+    if size > max {
+        Err(EncodeError::TooManyBytes { field, max })
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates a string field (strings are prefixed by their length as a single byte, see [`WriteExt::write_string`](crate::WriteExt::write_string)).
+pub(crate) fn validate_string(s: &str, field: Field) -> Result<(), EncodeError> {
+    validate_size(s.len(), std::u8::MAX as usize, field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_error_at() {
+        let bytes = [0u8; 30];
+        let mut cursor = Cursor::new(&bytes[..]);
+        cursor.set_position(12);
+
+        let err = ParseError::NotEnoughBytes(Field::TargetAddr).at("CallAccount", &cursor);
+
+        assert_eq!(err.offset, 12);
+        assert_eq!(err.path, "CallAccount.target_addr");
+        assert_eq!(err.expected, Some(20));
+        assert_eq!(err.available, 18);
+        assert_eq!(
+            err.to_string(),
+            "Not enough bytes for field `TargetAddr` (at `CallAccount.target_addr`, offset 12: expected 20 bytes, 18 available)"
+        );
+
+        let err = ParseError::ExpectedEOF.at("CallAccount", &cursor);
+
+        assert_eq!(err.path, "CallAccount");
+        assert_eq!(err.expected, None);
+        assert_eq!(
+            err.to_string(),
+            "Expected EOF but there are more left bytes (at `CallAccount`, offset 12: 18 bytes available)"
+        );
+    }
+}
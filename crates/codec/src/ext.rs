@@ -1,8 +1,11 @@
-use std::io::{Cursor, Read, Result};
+use std::io::{Cursor, Error, ErrorKind, Read, Result};
 use std::string::FromUtf8Error;
 
 use svm_types::{Address, State, TemplateAddr, TransactionId};
 
+/// The maximum byte size of an encoded `u64` variable-length integer.
+const UVARINT_MAX_BYTES: usize = 10;
+
 /// A trait to be implemented by Decoders
 pub trait ReadExt {
     /// Reads a single byte
@@ -23,6 +26,12 @@ pub trait ReadExt {
     /// Reads an unsigned 64-bit integer (Big-Endian)
     fn read_u64_be(&mut self) -> Result<u64>;
 
+    /// Reads an unsigned variable-length integer (LEB128, see [`WriteExt::write_uvarint`]).
+    ///
+    /// Fails on non-canonical encodings (i.e having redundant trailing zero groups)
+    /// and on encodings exceeding 64 bits.
+    fn read_uvarint(&mut self) -> Result<u64>;
+
     /// Reads a UTF-8 String
     fn read_string(&mut self) -> Result<std::result::Result<String, FromUtf8Error>>;
 
@@ -59,7 +68,19 @@ pub trait WriteExt {
     /// Writes an unsigned 64-bit integer (Big-Endian)
     fn write_u64_be(&mut self, n: u64);
 
-    /// Writes a UTF-8 String
+    /// Writes an unsigned variable-length integer (LEB128).
+    ///
+    /// The integer is split into groups of 7 bits, least significant first.
+    /// Each group takes a byte, whose most significant bit is set when more groups follow
+    /// (so numbers below `128` take a single byte, and a `u64` takes at most 10 bytes).
+    fn write_uvarint(&mut self, n: u64);
+
+    /// Writes a UTF-8 String (prefixed by its length as a single byte)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` exceeds `255` bytes. The encoders of transactions validate their strings
+    /// beforehand (failing with an [`EncodeError`](crate::EncodeError) instead).
     fn write_string(&mut self, s: &str);
 
     /// Writes an `Account Address`
@@ -85,6 +106,16 @@ impl ReadExt for Cursor<&[u8]> {
     }
 
     fn read_bytes(&mut self, length: usize) -> Result<Vec<u8>> {
+        let remaining = self
+            .get_ref()
+            .len()
+            .saturating_sub(self.position() as usize);
+
+        // Guards against allocating for lengths exceeding the input (e.g a corrupted length prefix)
+        if length > remaining {
+            return Err(ErrorKind::UnexpectedEof.into());
+        }
+
         let mut buf = vec![0; length];
 
         let _ = self.read_exact(&mut buf)?;
@@ -127,6 +158,30 @@ impl ReadExt for Cursor<&[u8]> {
         Ok(num)
     }
 
+    fn read_uvarint(&mut self) -> Result<u64> {
+        let mut num = 0;
+
+        for i in 0..UVARINT_MAX_BYTES {
+            let byte = self.read_byte()?;
+            let group = (byte & 0x7F) as u64;
+
+            let overflows = i == UVARINT_MAX_BYTES - 1 && byte > 1;
+            let redundant = i > 0 && byte == 0;
+
+            if overflows || redundant {
+                return Err(Error::new(ErrorKind::InvalidData, "invalid uvarint"));
+            }
+
+            num |= group << (7 * i);
+
+            if byte & 0x80 == 0 {
+                return Ok(num);
+            }
+        }
+
+        unreachable!()
+    }
+
     fn read_string(&mut self) -> Result<std::result::Result<String, FromUtf8Error>> {
         let length = self.read_byte()?;
         let bytes = self.read_bytes(length as usize)?;
@@ -198,6 +253,15 @@ impl WriteExt for Vec<u8> {
         self.write_bytes(&bytes[..]);
     }
 
+    fn write_uvarint(&mut self, mut n: u64) {
+        while n >= 0x80 {
+            self.write_byte((n as u8 & 0x7F) | 0x80);
+            n >>= 7;
+        }
+
+        self.write_byte(n as u8);
+    }
+
     fn write_string(&mut self, s: &str) {
         let length = s.len();
         assert!(length <= std::u8::MAX as usize);
@@ -232,3 +296,109 @@ impl WriteExt for Vec<u8> {
         self.write_bytes(bytes);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_uvarint(n: u64) -> Vec<u8> {
+        let mut w = Vec::new();
+        w.write_uvarint(n);
+
+        w
+    }
+
+    fn decode_uvarint(bytes: &[u8]) -> Result<u64> {
+        let mut cursor = Cursor::new(bytes);
+        let n = cursor.read_uvarint()?;
+
+        assert_eq!(cursor.position() as usize, bytes.len());
+
+        Ok(n)
+    }
+
+    #[test]
+    fn uvarint_known_encodings() {
+        assert_eq!(encode_uvarint(0), vec![0x00]);
+        assert_eq!(encode_uvarint(1), vec![0x01]);
+        assert_eq!(encode_uvarint(127), vec![0x7F]);
+        assert_eq!(encode_uvarint(128), vec![0x80, 0x01]);
+        assert_eq!(encode_uvarint(300), vec![0xAC, 0x02]);
+        assert_eq!(encode_uvarint(16_383), vec![0xFF, 0x7F]);
+        assert_eq!(encode_uvarint(16_384), vec![0x80, 0x80, 0x01]);
+        assert_eq!(
+            encode_uvarint(u64::MAX),
+            vec![0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]
+        );
+    }
+
+    #[test]
+    fn uvarint_round_trip_group_boundaries() {
+        for bits in 0..64 {
+            let power = 1u64 << bits;
+
+            for n in [power - 1, power, power + 1, power.wrapping_mul(3)] {
+                let bytes = encode_uvarint(n);
+                let bits = 64 - n.leading_zeros() as usize;
+
+                assert_eq!(bytes.len(), std::cmp::max(1, (bits + 6) / 7));
+                assert_eq!(decode_uvarint(&bytes).unwrap(), n);
+            }
+        }
+
+        assert_eq!(decode_uvarint(&encode_uvarint(u64::MAX)).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn uvarint_round_trip_small_numbers() {
+        for n in 0..=u16::MAX as u64 {
+            assert_eq!(decode_uvarint(&encode_uvarint(n)).unwrap(), n);
+        }
+    }
+
+    #[test]
+    fn uvarint_truncated() {
+        for n in [128, 300, u32::MAX as u64, u64::MAX] {
+            let bytes = encode_uvarint(n);
+
+            for len in 0..bytes.len() {
+                let err = decode_uvarint(&bytes[..len]).unwrap_err();
+                assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+            }
+        }
+    }
+
+    #[test]
+    fn uvarint_non_canonical() {
+        for bytes in [
+            &[0x80, 0x00][..],
+            &[0xFF, 0x80, 0x00],
+            &[0x81, 0x80, 0x80, 0x00],
+        ] {
+            let err = decode_uvarint(bytes).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData);
+        }
+    }
+
+    #[test]
+    fn uvarint_overflow() {
+        let too_big = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02];
+        let too_long = [
+            0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x81, 0x01,
+        ];
+
+        for bytes in [&too_big[..], &too_long[..]] {
+            let err = decode_uvarint(bytes).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData);
+        }
+    }
+
+    #[test]
+    fn read_bytes_beyond_input() {
+        let mut cursor = Cursor::new(&[0x10, 0x20][..]);
+
+        let err = cursor.read_bytes(usize::MAX).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(cursor.read_bytes(2).unwrap(), vec![0x10, 0x20]);
+    }
+}
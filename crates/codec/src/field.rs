@@ -1,19 +1,24 @@
 use std::fmt;
 
 #[allow(missing_docs)]
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Field {
     Section,
     SectionKind,
     SectionByteSize,
     SectionCount,
+    SectionRefCount,
+    SectionHash,
     String,
     GasUsed,
+    GasBreakdown,
+    GasScheduleVersion,
     GasMode,
     DeployerAddr,
     PrincipalAddr,
     Creator,
     Version,
+    AddrScheme,
     Description,
     Name,
     State,
@@ -27,26 +32,144 @@ pub enum Field {
     Layer,
     Address,
     TemplateAddr,
+    TemplateVersion,
     TargetAddr,
     InputDataLength,
     InputData,
+    VerifyData,
     LayoutKind,
     LayoutCount,
     LayoutFirstVarId,
     RawVarCount,
     RawVarSize,
+    DynamicVarCount,
+    DynamicVarId,
+    MaxStorageBytes,
     ErrorLength,
     Function,
     Ctor,
     CtorsCount,
+    Fallback,
     ReceiptType,
     ReceiptStatus,
+    ReturnData,
+    CallReceipt,
+    BatchCallsCount,
+    BatchReceiptsCount,
+    ErrorCode,
+    ErrorMessage,
     LogsCount,
     LogData,
     LogDataLength,
+    LogCode,
+    LogTopicsCount,
+    LogTopic,
+    LogsBloom,
     SymbolicVarCount,
+    SymbolicVarId,
     SymbolicVarType,
     SymbolicVarName,
+    DefaultsCount,
+    DefaultVarId,
+    DefaultValue,
+    Envelope,
+    TaggedFieldsCount,
+    TaggedFieldTag,
+    TaggedFieldLength,
+    TaggedFieldValue,
+    MetricsCount,
+    MetricId,
+    MetricValue,
+    ApiFuncCount,
+    ApiFuncName,
+    ApiFuncWasmName,
+    ApiFuncFlags,
+    ApiParamCount,
+    ApiParamName,
+    ApiReturnCount,
+    ApiType,
+    ApiFlags,
+    ApiDoc,
+    ApiVarDocCount,
+    ApiVarDocName,
+    Balance,
+    Amount,
+    Message,
+}
+
+impl Field {
+    /// The byte size of fixed-width fields (`None` for variable-length ones).
+    pub fn byte_size(&self) -> Option<usize> {
+        let size = match self {
+            Field::ReceiptType
+            | Field::ReceiptStatus
+            | Field::ErrorCode
+            | Field::LogTopicsCount
+            | Field::CtorsCount
+            | Field::ApiFlags
+            | Field::ApiFuncFlags
+            | Field::ApiParamCount
+            | Field::ApiReturnCount => 1,
+            Field::Version
+            | Field::GasScheduleVersion
+            | Field::AddrScheme
+            | Field::CodeKind
+            | Field::SectionKind
+            | Field::SectionRefCount
+            | Field::LayoutKind
+            | Field::LayoutCount
+            | Field::RawVarCount
+            | Field::RawVarSize
+            | Field::DynamicVarCount
+            | Field::SymbolicVarCount
+            | Field::ApiFuncCount
+            | Field::ApiVarDocCount
+            | Field::DefaultsCount
+            | Field::BatchCallsCount
+            | Field::BatchReceiptsCount => 2,
+            Field::CodeVersion
+            | Field::SvmVersion
+            | Field::LayoutFirstVarId
+            | Field::DynamicVarId
+            | Field::DefaultVarId
+            | Field::SymbolicVarId => 4,
+            Field::GasUsed
+            | Field::GasMode
+            | Field::CodeFlags
+            | Field::Layer
+            | Field::Balance
+            | Field::Amount => 8,
+            Field::Envelope => crate::envelope::byte_size(),
+            Field::Address
+            | Field::DeployerAddr
+            | Field::PrincipalAddr
+            | Field::TargetAddr
+            | Field::TemplateAddr => 20,
+            Field::State | Field::TransactionId | Field::SectionHash | Field::LogTopic => 32,
+            _ => return None,
+        };
+
+        Some(size)
+    }
+
+    /// The name of the field within a path (e.g `target_addr`).
+    pub fn path_name(&self) -> String {
+        let name = format!("{:?}", self);
+        let mut path_name = String::with_capacity(name.len() + 4);
+
+        for (i, c) in name.chars().enumerate() {
+            if c.is_ascii_uppercase() {
+                if i > 0 {
+                    path_name.push('_');
+                }
+                path_name.push(c.to_ascii_lowercase());
+            } else {
+                path_name.push(c);
+            }
+        }
+
+        path_name
+    }
 }
 
 impl fmt::Display for Field {
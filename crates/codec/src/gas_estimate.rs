@@ -0,0 +1,82 @@
+//! Encoding of a binary [`GasEstimate`].
+//!
+//! ```text
+//!
+//!  +---------------+-----------------+-----------------+----------------+----------------+
+//!  |               |                 |                 |                |                |
+//!  | Payload Price |  Template Load  | Has Func Price  |   Func Price   |   Gas Limit    |
+//!  |    (u64)      |   Price (u64)   |     (bool)      |     (u64)      |     (u64)      |
+//!  |               |                 |                 |                |                |
+//!  |   8 bytes     |    8 bytes      |     1 byte      |    8 bytes     |    8 bytes     |
+//!  | (Big-Endian)  |  (Big-Endian)   |                 |  (Big-Endian)  |  (Big-Endian)  |
+//!  |               |                 |                 |                |                |
+//!  +---------------+-----------------+-----------------+----------------+----------------+
+//!
+//! ```
+//!
+//! A missing `Func Price` is encoded as zero (following a `false` flag).
+
+use std::io::Cursor;
+
+use svm_types::GasEstimate;
+
+use crate::{ReadExt, WriteExt};
+
+/// Returns the number of bytes required to hold a binary [`GasEstimate`].
+pub const fn byte_size() -> usize {
+    8 + 8 + 1 + 8 + 8
+}
+
+/// Encodes a binary [`GasEstimate`].
+pub fn encode(estimate: &GasEstimate, w: &mut Vec<u8>) {
+    w.write_u64_be(estimate.payload_price);
+    w.write_u64_be(estimate.template_load_price);
+    w.write_bool(estimate.func_price.is_some());
+    w.write_u64_be(estimate.func_price.unwrap_or(0));
+    w.write_u64_be(estimate.gas_limit);
+}
+
+/// Decodes a binary [`GasEstimate`].
+///
+/// Returns the decoded [`GasEstimate`],
+/// On failure, returns [`std::io::Result`].
+pub fn decode(cursor: &mut Cursor<&[u8]>) -> std::io::Result<GasEstimate> {
+    let payload_price = cursor.read_u64_be()?;
+    let template_load_price = cursor.read_u64_be()?;
+    let has_func_price = cursor.read_bool()?;
+    let func_price = cursor.read_u64_be()?;
+    let gas_limit = cursor.read_u64_be()?;
+
+    Ok(GasEstimate {
+        payload_price,
+        template_load_price,
+        func_price: if has_func_price {
+            Some(func_price)
+        } else {
+            None
+        },
+        gas_limit,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gas_estimate_encode_decode() {
+        for func_price in [Some(300), None] {
+            let estimate = GasEstimate::new(100, 20, func_price, 1000);
+
+            let mut bytes = Vec::new();
+            encode(&estimate, &mut bytes);
+            assert_eq!(bytes.len(), byte_size());
+
+            let mut cursor = Cursor::new(&bytes[..]);
+            assert_eq!(decode(&mut cursor).unwrap(), estimate);
+        }
+
+        let mut cursor = Cursor::new(&[0u8; 10][..]);
+        assert!(decode(&mut cursor).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use svm_hash::{Blake3Hasher, Hasher};
+use svm_types::{Address, State, TemplateAddr, TransactionId};
+
+use crate::WriteExt;
+
+/// A [`WriteExt`] encoding into a buffer, while feeding the written bytes to a streaming hash.
+///
+/// Encoding a transaction through a [`HashingWriter`] yields both its bytes and its [`TransactionId`]
+/// (the `Blake3` hash of the bytes) in a single pass, without re-reading the whole encoding.
+#[derive(Debug, Default)]
+pub struct HashingWriter {
+    bytes: Vec<u8>,
+    hasher: Blake3Hasher,
+}
+
+impl HashingWriter {
+    /// Creates a new empty writer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new empty writer, allocating room for `capacity` bytes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            bytes: Vec::with_capacity(capacity),
+            hasher: Blake3Hasher::default(),
+        }
+    }
+
+    /// Borrows the bytes written so far.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the written bytes along with their [`TransactionId`].
+    pub fn finish(self) -> (Vec<u8>, TransactionId) {
+        let hash = self.hasher.finalize();
+
+        (self.bytes, TransactionId::from(&hash[..]))
+    }
+}
+
+impl WriteExt for HashingWriter {
+    fn write_byte(&mut self, byte: u8) {
+        self.write_bytes(&[byte]);
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.write_bytes(bytes);
+        self.hasher.update(bytes);
+    }
+
+    fn write_bool(&mut self, b: bool) {
+        self.write_byte(b as u8);
+    }
+
+    fn write_u16_be(&mut self, n: u16) {
+        self.write_bytes(&n.to_be_bytes());
+    }
+
+    fn write_u32_be(&mut self, n: u32) {
+        self.write_bytes(&n.to_be_bytes());
+    }
+
+    fn write_u64_be(&mut self, n: u64) {
+        self.write_bytes(&n.to_be_bytes());
+    }
+
+    fn write_uvarint(&mut self, n: u64) {
+        let mut bytes = Vec::with_capacity(10);
+        bytes.write_uvarint(n);
+
+        self.write_bytes(&bytes);
+    }
+
+    fn write_string(&mut self, s: &str) {
+        let length = s.len();
+        assert!(length <= std::u8::MAX as usize);
+
+        self.write_byte(length as u8);
+        self.write_bytes(s.as_bytes());
+    }
+
+    fn write_address(&mut self, addr: &Address) {
+        self.write_bytes(addr.as_slice());
+    }
+
+    fn write_template_addr(&mut self, addr: &TemplateAddr) {
+        self.write_bytes(addr.as_slice());
+    }
+
+    fn write_state(&mut self, state: &State) {
+        self.write_bytes(state.as_slice());
+    }
+
+    fn write_tx_id(&mut self, tx_id: &TransactionId) {
+        self.write_bytes(tx_id.as_slice());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashing_writer_tees_written_bytes() {
+        let mut w = HashingWriter::new();
+        let mut expected = Vec::new();
+
+        w.write_u16_be(7);
+        expected.write_u16_be(7);
+        w.write_string("My Account");
+        expected.write_string("My Account");
+        w.write_address(&Address::of("@Account"));
+        expected.write_address(&Address::of("@Account"));
+
+        let (bytes, tx_id) = w.finish();
+        assert_eq!(bytes, expected);
+        assert_eq!(
+            tx_id,
+            TransactionId::from(&Blake3Hasher::hash(&expected)[..])
+        );
+    }
+
+    #[test]
+    fn hashing_writer_builds_bytes_and_id() {
+        use svm_layout::Layout;
+        use svm_types::{CodeKind, CodeSection, CtorsSection, DataSection, GasMode};
+
+        use crate::api::builder::{CallBuilder, TemplateBuilder};
+
+        let id_of = |bytes: &[u8]| TransactionId::from(&Blake3Hasher::hash(bytes)[..]);
+
+        let code = CodeSection::new(
+            CodeKind::Wasm,
+            vec![0xC0; 1024],
+            CodeSection::exec_flags(),
+            GasMode::Fixed,
+            1,
+        );
+        let template = || {
+            TemplateBuilder::default()
+                .with_code(code.clone())
+                .with_data(DataSection::with_layout(Layout::Fixed(vec![4].into())))
+                .with_ctors(CtorsSection::new(vec!["init".into()]))
+        };
+
+        let (bytes, tx_id) = template().build_bytes_and_id();
+        assert_eq!(bytes, crate::template::encode(&template().build()));
+        assert_eq!(tx_id, id_of(&bytes));
+
+        let call = || {
+            CallBuilder::new()
+                .with_version(0)
+                .with_target(&Address::of("@Target"))
+                .with_func("do_work")
+                .with_calldata(&[0x10, 0x20])
+        };
+
+        let (bytes, tx_id) = call().build_bytes_and_id();
+        assert_eq!(bytes, call().build());
+        assert_eq!(tx_id, id_of(&bytes));
+    }
+}
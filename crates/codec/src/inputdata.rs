@@ -1,25 +1,99 @@
 use std::io::Cursor;
 
+use crate::error::{validate_size, EncodeError};
 use crate::{Field, ParseError, ReadExt, WriteExt};
 
-pub fn encode_inputdata(data: &[u8], w: &mut Vec<u8>) {
+/// The messages format version from which the length prefix of an `InputData` is encoded
+/// as a variable-length integer (see [`WriteExt::write_uvarint`]).
+///
+/// Messages of earlier versions prefix an `InputData` by its length as a single byte
+/// (so it can't exceed `255` bytes).
+pub const UVARINT_LENGTH_VERSION: u16 = 2;
+
+/// The maximum size (in bytes) of an `InputData` of a message of format version `version`
+/// (`None` if its size isn't bounded by the encoding).
+pub fn max_inputdata_size(version: u16) -> Option<usize> {
+    if version >= UVARINT_LENGTH_VERSION {
+        None
+    } else {
+        Some(std::u8::MAX as usize)
+    }
+}
+
+/// Validates that `data` fits an `InputData` of a message of format version `version` (see [`max_inputdata_size`]).
+pub fn validate_inputdata(version: u16, data: &[u8], field: Field) -> Result<(), EncodeError> {
+    match max_inputdata_size(version) {
+        Some(max) => validate_size(data.len(), max, field),
+        None => Ok(()),
+    }
+}
+
+/// Encodes `data` as an `InputData` of a message of format version `version`.
+///
+/// # Panics
+///
+/// Panics if `data` exceeds [`max_inputdata_size`] (see [`validate_inputdata`]).
+pub fn encode_inputdata(version: u16, data: &[u8], w: &mut impl WriteExt) {
     let length = data.len();
 
-    assert!(length <= std::u8::MAX as usize);
+    if version >= UVARINT_LENGTH_VERSION {
+        w.write_uvarint(length as u64);
+    } else {
+        assert!(length <= std::u8::MAX as usize);
+
+        w.write_byte(length as u8);
+    }
 
-    w.write_byte(length as u8);
     w.write_bytes(data);
 }
 
-pub fn decode_inputdata<'a>(cursor: &mut Cursor<&[u8]>) -> Result<Vec<u8>, ParseError> {
-    match cursor.read_byte() {
+pub fn decode_inputdata(version: u16, cursor: &mut Cursor<&[u8]>) -> Result<Vec<u8>, ParseError> {
+    let length = if version >= UVARINT_LENGTH_VERSION {
+        cursor.read_uvarint()
+    } else {
+        cursor.read_byte().map(u64::from)
+    };
+
+    match length {
         Err(..) => Err(ParseError::NotEnoughBytes(Field::InputDataLength)),
-        Ok(byte) => {
-            let length = byte as usize;
+        Ok(length) => cursor
+            .read_bytes(length as usize)
+            .map_err(|_| ParseError::NotEnoughBytes(Field::InputData)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inputdata_length_prefix_by_version() {
+        let data = vec![0xAB; 300];
+
+        let mut bytes = Vec::new();
+        encode_inputdata(UVARINT_LENGTH_VERSION, &data, &mut bytes);
+        assert_eq!(&bytes[..2], &[0xAC, 0x02]);
+
+        let mut cursor = Cursor::new(&bytes[..]);
+        let decoded = decode_inputdata(UVARINT_LENGTH_VERSION, &mut cursor).unwrap();
+        assert_eq!(decoded, data);
+
+        let mut bytes = Vec::new();
+        encode_inputdata(0, &data[..10], &mut bytes);
+        assert_eq!(bytes[0], 10);
+
+        let mut cursor = Cursor::new(&bytes[..]);
+        let decoded = decode_inputdata(0, &mut cursor).unwrap();
+        assert_eq!(decoded, &data[..10]);
+    }
+
+    #[test]
+    fn inputdata_length_exceeding_input() {
+        let mut bytes = Vec::new();
+        bytes.write_uvarint(u64::MAX);
 
-            cursor
-                .read_bytes(length)
-                .map_err(|_| ParseError::NotEnoughBytes(Field::InputData))
-        }
+        let mut cursor = Cursor::new(&bytes[..]);
+        let err = decode_inputdata(UVARINT_LENGTH_VERSION, &mut cursor).unwrap_err();
+        assert_eq!(err, ParseError::NotEnoughBytes(Field::InputData));
     }
 }
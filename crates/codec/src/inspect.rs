@@ -0,0 +1,448 @@
+//! Identifying binary messages of an unknown kind, and breaking them down into their fields.
+//!
+//! A binary message doesn't carry its own kind (that's the role of the `Tx Type` of the [`wire`](crate::wire) layout),
+//! so [`inspect`] tries out the layout of each [`MessageKind`] in turn (see [`MessageKind::ALL`]),
+//! picking the first one decoding the whole message.
+//!
+//! The layouts don't rule out each other entirely (e.g. a `Call Account` may happen to decode as a `Spawn Account`),
+//! so a message crafted to be ambiguous is attributed to the kind tried first.
+//! Receipts are tried first, since they open with a `tx type` byte followed by a bounded `version`.
+
+use std::io::Cursor;
+use std::ops::Range;
+
+use crate::receipt::{self, decode_receipt_partial};
+use crate::{call, inputdata, spawn, template};
+use crate::{ParseError, SectionsDecoder};
+
+/// The kinds of binary messages [`inspect`] is able to identify.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageKind {
+    /// A binary Receipt (of any transaction type).
+    Receipt,
+
+    /// A `Deploy Template` transaction.
+    Deploy,
+
+    /// A `Spawn Account` transaction.
+    Spawn,
+
+    /// A `Call Account` transaction.
+    Call,
+}
+
+impl MessageKind {
+    /// All the kinds, in the order [`inspect`] tries them out.
+    pub const ALL: [MessageKind; 4] = [
+        MessageKind::Receipt,
+        MessageKind::Deploy,
+        MessageKind::Spawn,
+        MessageKind::Call,
+    ];
+
+    /// A human-readable name of the kind.
+    pub fn name(&self) -> &'static str {
+        match self {
+            MessageKind::Receipt => "Receipt",
+            MessageKind::Deploy => "Deploy Template",
+            MessageKind::Spawn => "Spawn Account",
+            MessageKind::Call => "Call Account",
+        }
+    }
+}
+
+/// The bytes of a single field within a binary message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSpan {
+    /// The path of the field (e.g `SpawnAccount.ctor`).
+    pub path: String,
+
+    /// The range of the field's bytes within the message.
+    pub range: Range<usize>,
+}
+
+impl FieldSpan {
+    fn new(path: impl Into<String>, range: Range<usize>) -> Self {
+        Self {
+            path: path.into(),
+            range,
+        }
+    }
+}
+
+/// A binary message broken down into its fields (see [`inspect`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inspection {
+    /// The identified kind of the message.
+    pub kind: MessageKind,
+
+    /// The fields of the message, in the order they're laid out (together, they cover the whole message).
+    pub fields: Vec<FieldSpan>,
+}
+
+/// Identifies the kind of the binary message `bytes` and breaks it down into its fields.
+///
+/// Returns `None` if `bytes` doesn't decode as a whole as any of the [`MessageKind`]s
+/// (see [`inspect_as`] for the reason each kind was ruled out).
+pub fn inspect(bytes: &[u8]) -> Option<Inspection> {
+    MessageKind::ALL.iter().find_map(|&kind| {
+        inspect_as(kind, bytes)
+            .ok()
+            .map(|fields| Inspection { kind, fields })
+    })
+}
+
+/// Breaks down the binary message `bytes` of kind `kind` into its fields.
+///
+/// On failure, returns the [`ParseError`] of the first field that couldn't be decoded
+/// ([`ParseError::ExpectedEOF`] if there are bytes left past the message).
+pub fn inspect_as(kind: MessageKind, bytes: &[u8]) -> Result<Vec<FieldSpan>, ParseError> {
+    let fields = match kind {
+        MessageKind::Receipt => receipt_fields(bytes),
+        MessageKind::Deploy => deploy_fields(bytes),
+        MessageKind::Spawn => spawn_fields(bytes),
+        MessageKind::Call => call_fields(bytes),
+    }?;
+
+    let end = fields.last().map_or(0, |field| field.range.end);
+
+    if end != bytes.len() {
+        return Err(ParseError::ExpectedEOF);
+    }
+
+    Ok(fields)
+}
+
+/// The Receipts are laid out differently for each transaction type, `version` and outcome,
+/// so instead of walking these layouts their fields are located using [`decode_receipt_partial`].
+///
+/// When a truncated Receipt is decoded, decoding stops at the start of the field the truncation falls into.
+/// So a field ends at the shortest truncation that makes decoding stop past its start.
+fn receipt_fields(bytes: &[u8]) -> Result<Vec<FieldSpan>, ParseError> {
+    let partial = decode_receipt_partial(bytes);
+    let end = partial.offset;
+    let receipt = partial.into_result()?;
+
+    let structure = receipt::structure(&receipt);
+    let bytes = &bytes[..end];
+
+    let stopped_at = |len: usize| {
+        let partial = decode_receipt_partial(&bytes[..len]);
+
+        (partial.offset, partial.error.and_then(|err| err.field()))
+    };
+
+    let mut fields = Vec::new();
+    let mut start = 0;
+
+    while start < end {
+        let (mut lo, mut hi) = (start + 1, end);
+
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+
+            if stopped_at(mid).0 > start {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+
+        let path = match stopped_at(lo - 1).1 {
+            Some(field) => format!("{}.{}", structure, field.path_name()),
+            None => structure.to_string(),
+        };
+        fields.push(FieldSpan::new(path, start..lo));
+
+        start = lo;
+    }
+
+    Ok(fields)
+}
+
+fn deploy_fields(bytes: &[u8]) -> Result<Vec<FieldSpan>, ParseError> {
+    template::decode_at(bytes).map_err(|err| err.error)?;
+
+    let mut fields = Vec::new();
+    let mut decoder = SectionsDecoder::new(Cursor::new(bytes))?;
+    let position = |decoder: &SectionsDecoder| decoder.cursor().position() as usize;
+
+    fields.push(FieldSpan::new(
+        "DeployTemplate.section_count",
+        0..position(&decoder),
+    ));
+
+    while !decoder.is_eof() {
+        let start = position(&decoder);
+        let preview = decoder.next_preview()?;
+        let structure = format!("DeployTemplate.{:?}", preview.kind());
+
+        fields.push(FieldSpan::new(
+            format!("{}.preview", structure),
+            start..position(&decoder),
+        ));
+        fields.push(FieldSpan::new(structure, decoder.section_range()?));
+    }
+
+    Ok(fields)
+}
+
+fn spawn_fields(bytes: &[u8]) -> Result<Vec<FieldSpan>, ParseError> {
+    let mut walker = Walker::new(bytes);
+
+    let version = walker.field("SpawnAccount.version", spawn::decode_version)?;
+    walker.field("SpawnAccount.template_addr", spawn::decode_template)?;
+    walker.field("SpawnAccount.name", spawn::decode_name)?;
+    walker.field("SpawnAccount.ctor", spawn::decode_ctor)?;
+    walker.field("SpawnAccount.calldata", |cursor| {
+        spawn::decode_ctor_calldata(version, cursor)
+    })?;
+
+    if version >= spawn::SPAWN_CALL_VERSION {
+        walker.field("SpawnAccount.call", |cursor| {
+            spawn::decode_call(version, cursor)
+        })?;
+    }
+
+    Ok(walker.fields)
+}
+
+fn call_fields(bytes: &[u8]) -> Result<Vec<FieldSpan>, ParseError> {
+    let mut walker = Walker::new(bytes);
+
+    let version = walker.field("CallAccount.version", call::decode_version)?;
+    walker.field("CallAccount.target_addr", call::decode_target)?;
+    walker.field("CallAccount.function", call::decode_func)?;
+    walker.field("CallAccount.verifydata", |cursor| {
+        call::decode_verifydata(version, cursor)
+    })?;
+    walker.field("CallAccount.calldata", |cursor| {
+        inputdata::decode_inputdata(version, cursor)
+    })?;
+
+    Ok(walker.fields)
+}
+
+/// Decodes a binary message field by field, recording where each one lays.
+struct Walker<'a> {
+    cursor: Cursor<&'a [u8]>,
+    fields: Vec<FieldSpan>,
+}
+
+impl<'a> Walker<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            cursor: Cursor::new(bytes),
+            fields: Vec::new(),
+        }
+    }
+
+    fn field<T, F>(&mut self, path: &str, decode: F) -> Result<T, ParseError>
+    where
+        F: FnOnce(&mut Cursor<&[u8]>) -> Result<T, ParseError>,
+    {
+        let start = self.cursor.position() as usize;
+        let value = decode(&mut self.cursor)?;
+        let end = self.cursor.position() as usize;
+
+        self.fields.push(FieldSpan::new(path, start..end));
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_layout::Layout;
+    use svm_types::{
+        Account, Address, CallReceipt, CodeKind, CodeSection, CtorsSection, DataSection, Gas,
+        GasMode, ReceiptLog, SpawnAccount, State, TemplateAddr, Transaction,
+    };
+
+    use crate::api::builder::TemplateBuilder;
+    use crate::Field;
+
+    fn paths(fields: &[FieldSpan]) -> Vec<&str> {
+        fields.iter().map(|field| field.path.as_str()).collect()
+    }
+
+    fn call_bytes() -> Vec<u8> {
+        let tx = Transaction {
+            version: 0,
+            target: Address::of("@target"),
+            func_name: "do_work".to_string(),
+            verifydata: vec![0xAA],
+            calldata: vec![0x10, 0x20, 0x30],
+        };
+
+        let mut bytes = Vec::new();
+        call::encode_call(&tx, &mut bytes).unwrap();
+
+        bytes
+    }
+
+    #[test]
+    fn inspect_call() {
+        let bytes = call_bytes();
+        let inspection = inspect(&bytes).unwrap();
+
+        assert_eq!(inspection.kind, MessageKind::Call);
+        assert_eq!(
+            inspection.fields,
+            vec![
+                FieldSpan::new("CallAccount.version", 0..2),
+                FieldSpan::new("CallAccount.target_addr", 2..22),
+                FieldSpan::new("CallAccount.function", 22..30),
+                FieldSpan::new("CallAccount.verifydata", 30..32),
+                FieldSpan::new("CallAccount.calldata", 32..36),
+            ]
+        );
+    }
+
+    #[test]
+    fn inspect_spawn() {
+        let spawn = SpawnAccount {
+            version: 0,
+            account: Account {
+                name: "@account".to_string(),
+                template_addr: TemplateAddr::of("@template"),
+            },
+            ctor_name: "initialize".to_string(),
+            calldata: vec![0x10, 0x20],
+            call: None,
+        };
+
+        let mut bytes = Vec::new();
+        spawn::encode(&spawn, &mut bytes).unwrap();
+
+        let inspection = inspect(&bytes).unwrap();
+
+        assert_eq!(inspection.kind, MessageKind::Spawn);
+        assert_eq!(
+            paths(&inspection.fields),
+            vec![
+                "SpawnAccount.version",
+                "SpawnAccount.template_addr",
+                "SpawnAccount.name",
+                "SpawnAccount.ctor",
+                "SpawnAccount.calldata",
+            ]
+        );
+        assert_eq!(inspection.fields[4].range.end, bytes.len());
+
+        // From `SPAWN_CALL_VERSION` onwards, the message ends with `Has Call`
+        let spawn = SpawnAccount {
+            version: spawn::SPAWN_CALL_VERSION,
+            ..spawn
+        };
+
+        let mut bytes = Vec::new();
+        spawn::encode(&spawn, &mut bytes).unwrap();
+
+        let inspection = inspect(&bytes).unwrap();
+
+        assert_eq!(inspection.fields[5].path, "SpawnAccount.call");
+        assert_eq!(inspection.fields[5].range, bytes.len() - 1..bytes.len());
+    }
+
+    #[test]
+    fn inspect_deploy() {
+        let code = CodeSection::new(
+            CodeKind::Wasm,
+            vec![0xC0; 64],
+            CodeSection::exec_flags(),
+            GasMode::Fixed,
+            1,
+        );
+        let template = TemplateBuilder::default()
+            .with_code(code)
+            .with_data(DataSection::with_layout(Layout::Fixed(vec![4].into())))
+            .with_ctors(CtorsSection::new(vec!["init".into()]))
+            .build();
+        let bytes = template::encode(&template);
+
+        let inspection = inspect(&bytes).unwrap();
+
+        assert_eq!(inspection.kind, MessageKind::Deploy);
+        assert_eq!(inspection.fields.len(), 1 + 3 * 2);
+        assert_eq!(
+            inspection.fields[0],
+            FieldSpan::new("DeployTemplate.section_count", 0..2)
+        );
+
+        let mut kinds = paths(&inspection.fields[1..]);
+        kinds.sort_unstable();
+        assert_eq!(
+            kinds,
+            vec![
+                "DeployTemplate.Code",
+                "DeployTemplate.Code.preview",
+                "DeployTemplate.Ctors",
+                "DeployTemplate.Ctors.preview",
+                "DeployTemplate.Data",
+                "DeployTemplate.Data.preview",
+            ]
+        );
+    }
+
+    #[test]
+    fn inspect_receipt() {
+        let receipt = CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            new_state: Some(State::of("some-state")),
+            returndata: Some(vec![0x10, 0x20]),
+            gas_used: Gas::with(100),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: vec![
+                ReceiptLog::new(b"Log entry #1".to_vec()),
+                ReceiptLog::new(b"Log entry #2".to_vec()),
+            ],
+            metrics: Vec::new(),
+        };
+        let bytes = receipt::encode_call(&receipt);
+
+        let inspection = inspect(&bytes).unwrap();
+
+        assert_eq!(inspection.kind, MessageKind::Receipt);
+        assert_eq!(
+            inspection.fields,
+            vec![
+                FieldSpan::new("CallReceipt.receipt_type", 0..1),
+                FieldSpan::new("CallReceipt.version", 1..3),
+                FieldSpan::new("CallReceipt.receipt_status", 3..4),
+                FieldSpan::new("CallReceipt.state", 4..36),
+                FieldSpan::new("CallReceipt.return_data", 36..40),
+                FieldSpan::new("CallReceipt.gas_used", 40..48),
+                FieldSpan::new("CallReceipt.logs_count", 48..49),
+                FieldSpan::new("CallReceipt.log_data", 49..63),
+                FieldSpan::new("CallReceipt.log_data", 63..77),
+            ]
+        );
+    }
+
+    #[test]
+    fn inspect_trailing_bytes() {
+        let mut bytes = call_bytes();
+        bytes.push(0xFF);
+
+        assert_eq!(
+            inspect_as(MessageKind::Call, &bytes),
+            Err(ParseError::ExpectedEOF)
+        );
+    }
+
+    #[test]
+    fn inspect_unknown() {
+        assert_eq!(inspect(&[0xFF; 3]), None);
+        assert_eq!(
+            inspect_as(MessageKind::Receipt, &[0xFF; 3]),
+            Err(ParseError::NotSupported(Field::ReceiptType))
+        );
+    }
+}
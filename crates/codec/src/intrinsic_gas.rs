@@ -0,0 +1,20 @@
+//! Intrinsic gas of binary transactions.
+//!
+//! The intrinsic gas of a transaction is charged up-front, before any code gets executed.
+//! A transaction whose `gas_limit` is lower than its intrinsic gas always runs out of gas,
+//! so clients can use these functions to display the minimum viable `gas_limit`.
+
+pub use svm_gas::transaction::{call, deploy, spawn};
+
+/// The execution gas a `deploy` transaction is expected to use (on top of its intrinsic gas).
+///
+/// Deploying a `Template` doesn't run any code.
+pub const DEPLOY_EXEC_GAS: u64 = 0;
+
+/// The execution gas a `spawn` transaction is expected to use (on top of its intrinsic gas),
+/// unless told otherwise. It covers the running of the `ctor`.
+pub const SPAWN_EXEC_GAS: u64 = 1_000_000;
+
+/// The execution gas a `call` transaction is expected to use (on top of its intrinsic gas),
+/// unless told otherwise.
+pub const CALL_EXEC_GAS: u64 = 1_000_000;
@@ -14,18 +14,33 @@
 
 mod ext;
 mod field;
+mod hashing;
 mod inputdata;
 mod section;
 mod version;
 
+pub mod batch;
 pub mod call;
+pub mod client;
 pub mod spawn;
 pub mod template;
+pub mod upgrade;
 pub use ext::{ReadExt, WriteExt};
 pub use field::Field;
+pub use hashing::HashingWriter;
 pub mod api;
+pub mod armor;
 pub mod context;
 pub mod envelope;
+pub mod gas_estimate;
+pub mod inspect;
+pub mod intrinsic_gas;
+pub mod limits;
+pub mod malleability;
+pub mod signature;
+pub mod signing;
+pub mod tagged;
+pub mod wire;
 
 pub use section::{SectionPreview, SectionsDecoder, SectionsEncoder};
 
@@ -33,7 +48,7 @@ pub use section::{SectionPreview, SectionsDecoder, SectionsEncoder};
 pub mod receipt;
 
 mod error;
-pub use error::ParseError;
+pub use error::{EncodeError, ParseError, ParseErrorAt};
 
 /// # WASM API
 ///
@@ -79,8 +94,8 @@ pub use error::ParseError;
 
 #[cfg(target_arch = "wasm32")]
 macro_rules! wasm_func_call {
-    ($func:ident, $buf_offset:expr) => {{
-        match api::wasm::$func($buf_offset as usize) {
+    ($func:ident, $buf_offset:expr $(, $arg:expr)*) => {{
+        match api::wasm::$func($buf_offset as usize $(, $arg)*) {
             Ok(tx_offset) => tx_offset as _,
             Err(err) => {
                 let err_offset = api::wasm::into_error_buffer(err);
@@ -104,6 +119,39 @@ pub extern "C" fn wasm_encode_deploy(offset: i32) -> i32 {
     wasm_func_call!(encode_deploy, offset)
 }
 
+/// Decodes the binary `Deploy Template` given as a WASM buffer (parameter `offset`).
+///
+/// Returns a pointer to a new WASM buffer holding a JSON of the decoded `Template` `Section`s
+/// (see [`api::json::decode_deploy`]).
+/// If the decoding fails, the returned WASM buffer will contain a String containing the error message.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_decode_deploy(offset: i32) -> i32 {
+    wasm_func_call!(decode_deploy, offset)
+}
+
+/// Decodes the raw `Section`s of a binary `Deploy Template` given as a WASM buffer (parameter `offset`).
+///
+/// Returns a pointer to a new WASM buffer holding a JSON array of the `Section`s
+/// (see [`api::json::decode_sections`]).
+/// If the decoding fails, the returned WASM buffer will contain a String containing the error message.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_decode_sections(offset: i32) -> i32 {
+    wasm_func_call!(decode_sections, offset)
+}
+
+/// Decodes the documentation of a binary `Deploy Template` given as a WASM buffer (parameter `offset`).
+///
+/// Returns a pointer to a new WASM buffer holding a JSON of the `Template` documentation
+/// (see [`api::json::decode_docs`]).
+/// If the decoding fails, the returned WASM buffer will contain a String containing the error message.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_decode_docs(offset: i32) -> i32 {
+    wasm_func_call!(decode_docs, offset)
+}
+
 /// ## WASM `Spawn Account`
 ///
 /// Reads the WASM buffer given at parameter `offset` containing a JSON value.
@@ -150,6 +198,29 @@ pub extern "C" fn wasm_decode_call(offset: i32) -> i32 {
     wasm_func_call!(decode_call, offset)
 }
 
+/// ## WASM `Envelope`
+///
+/// Reads the WASM buffer given at parameter `offset` containing a JSON value.
+/// Encodes a binary `Envelope` using that JSON value.
+///
+/// Returns a pointer to a new WASM buffer holding the encoded `Envelope`.
+/// If the encoding failed, the returned WASM buffer will contain a String containing the error message.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_encode_envelope(offset: i32) -> i32 {
+    wasm_func_call!(encode_envelope, offset)
+}
+
+/// Decodes the binary `Envelope` given as a WASM buffer (parameter `offset`).
+///
+/// Returns a pointer to a new WASM buffer holding the decoded `Envelope`.
+/// If the decoding fails, the returned WASM buffer will contain a String containing the error message.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_decode_envelope(offset: i32) -> i32 {
+    wasm_func_call!(decode_envelope, offset)
+}
+
 /// ## WASM Buffer Allocation
 ///
 /// Allocates a new WASM Buffer holding data of `length` bytes.
@@ -226,3 +297,130 @@ pub extern "C" fn wasm_decode_inputdata(offset: i32) -> i32 {
 pub extern "C" fn wasm_decode_receipt(offset: i32) -> i32 {
     wasm_func_call!(decode_receipt, offset)
 }
+
+/// Encodes the `Receipt` JSON given as a WASM buffer (parameter `offset`).
+///
+/// Returns a pointer to a new WASM buffer holding the encoded `Receipt` (wrapped inside a JSON).
+/// If the encoding fails, the returned WASM buffer will contain a String containing the error message.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_encode_receipt(offset: i32) -> i32 {
+    wasm_func_call!(encode_receipt, offset)
+}
+
+/// Computes the intrinsic gas of the binary transaction wrapped by the JSON given as a WASM buffer (parameter `offset`).
+///
+/// Returns a pointer to a new WASM buffer holding the result JSON.
+/// If the computation fails, the returned WASM buffer will contain a String containing the error message.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_intrinsic_gas(offset: i32) -> i32 {
+    wasm_func_call!(intrinsic_gas, offset)
+}
+
+/// Estimates the fee of the draft transaction wrapped by the JSON given as a WASM buffer (parameter `offset`).
+///
+/// Returns a pointer to a new WASM buffer holding the result JSON.
+/// If the estimation fails, the returned WASM buffer will contain a String containing the error message.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_estimate_fee(offset: i32) -> i32 {
+    wasm_func_call!(estimate_fee, offset)
+}
+
+/// Decodes the binary gas estimate wrapped by the JSON given as a WASM buffer (parameter `offset`).
+///
+/// Returns a pointer to a new WASM buffer holding the decoded gas estimate.
+/// If the decoding fails, the returned WASM buffer will contain a String containing the error message.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_decode_gas_estimate(offset: i32) -> i32 {
+    wasm_func_call!(decode_gas_estimate, offset)
+}
+
+/// Computes the signing payload (and its hash) of the transaction wrapped by the JSON
+/// given as a WASM buffer (parameter `offset`).
+///
+/// Returns a pointer to a new WASM buffer holding the result JSON.
+/// If the computation fails, the returned WASM buffer will contain a String containing the error message.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_signing_payload(offset: i32) -> i32 {
+    wasm_func_call!(signing_payload, offset)
+}
+
+/// Builds the node-submittable binary transaction (see [`wire`]) out of the `Envelope`, `Context`
+/// and message wrapped by the JSON given as a WASM buffer (parameter `offset`).
+///
+/// Returns a pointer to a new WASM buffer holding the result JSON.
+/// If the building fails, the returned WASM buffer will contain a String containing the error message.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_build_transaction(offset: i32) -> i32 {
+    wasm_func_call!(build_transaction, offset)
+}
+
+/// Armors the binary transaction wrapped by the JSON given as a WASM buffer (parameter `offset`)
+/// as a QR code friendly text (see [`armor`]).
+///
+/// Returns a pointer to a new WASM buffer holding the result JSON.
+/// If the armoring fails, the returned WASM buffer will contain a String containing the error message.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_armor(offset: i32) -> i32 {
+    wasm_func_call!(armor, offset)
+}
+
+/// Unarmors the text wrapped by the JSON given as a WASM buffer (parameter `offset`)
+/// back into a binary transaction (see [`armor`]).
+///
+/// Returns a pointer to a new WASM buffer holding the result JSON.
+/// If the unarmoring fails, the returned WASM buffer will contain a String containing the error message.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_unarmor(offset: i32) -> i32 {
+    wasm_func_call!(unarmor, offset)
+}
+
+/// ## WASM Peek Transaction Field
+///
+/// Reads the WASM buffer given at parameter `offset` containing a binary `Call Account` transaction,
+/// and decodes only its field `field_id` (skipping the other fields by their lengths):
+///
+/// * `0` - the version
+/// * `1` - the target `Address`
+/// * `2` - the function name
+/// * `3` - the `VerifyData`
+/// * `4` - the `CallData`
+///
+/// Returns a pointer to a new WASM buffer holding the raw bytes of the field.
+/// If the decoding failed, the returned WASM buffer will contain a String containing the error message.
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_tx_peek(offset: i32, field_id: i32) -> i32 {
+    wasm_func_call!(tx_peek, offset, field_id as u32)
+}
+
+/// ## WASM Self-Test
+///
+/// Runs the golden vectors embedded into the codec (encode and decode round trips),
+/// letting clients verify that the `svm_codec.wasm` they ship behaves like the `svm-codec` version it's built from.
+///
+/// Returns a pointer to a new WASM buffer holding the report JSON (see [`api::json::self_test`]).
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_self_test() -> i32 {
+    api::wasm::self_test() as _
+}
+
+/// ## WASM Limits
+///
+/// Returns the maximum byte sizes of the encoded messages, per message family (see [`limits`]),
+/// letting clients validate their payloads prior to encoding them.
+///
+/// Returns a pointer to a new WASM buffer holding the limits JSON (see [`api::json::limits`]).
+#[no_mangle]
+#[cfg(target_arch = "wasm32")]
+pub extern "C" fn wasm_limits() -> i32 {
+    api::wasm::limits() as _
+}
@@ -0,0 +1,161 @@
+//! The maximum byte sizes of the encoded messages, per message family.
+//!
+//! These are the single source of truth of the codec (and of its clients):
+//!
+//! * The binary decoders reject an oversized message up-front, by the length of its buffer
+//!   (with [`ParseError::TooManyBytes`] of [`Field::Message`]), before decoding any of its fields.
+//! * The encoders of the [`api`](crate::api) fail with an error instead of producing an oversized message.
+//! * Clients can fetch the limits (see [`api::json::limits`](crate::api::json::limits)) and pre-validate
+//!   their payloads before calling into the codec.
+
+use thiserror::Error;
+
+use std::fmt;
+use std::io::Cursor;
+
+use crate::{Field, ParseError};
+
+/// The maximum byte size of a `Deploy Template` message (mostly made of its Wasm code).
+pub const MAX_DEPLOY_SIZE: usize = 2 * 1024 * 1024;
+
+/// The maximum byte size of an `Upgrade Template` message (its `Template` is laid out as of a `Deploy Template`).
+pub const MAX_UPGRADE_SIZE: usize = MAX_DEPLOY_SIZE;
+
+/// The maximum byte size of a `Spawn Account` message.
+pub const MAX_SPAWN_SIZE: usize = 64 * 1024;
+
+/// The maximum byte size of a `Call Account` message.
+pub const MAX_CALL_SIZE: usize = 64 * 1024;
+
+/// The maximum byte size of a `Batch` message (of all its calls, each limited as a `Call Account` message).
+pub const MAX_BATCH_SIZE: usize = 256 * 1024;
+
+/// A family of messages sharing the same layout (and the same maximum byte size).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageFamily {
+    /// `Deploy Template` (see [`crate::template`]).
+    Deploy,
+
+    /// `Upgrade Template` (see [`crate::upgrade`]).
+    Upgrade,
+
+    /// `Spawn Account` (see [`crate::spawn`]).
+    Spawn,
+
+    /// `Call Account` (see [`crate::call`]).
+    Call,
+
+    /// `Batch` (see [`crate::batch`]).
+    Batch,
+}
+
+impl MessageFamily {
+    /// All the message families.
+    pub const ALL: [MessageFamily; 5] = [
+        MessageFamily::Deploy,
+        MessageFamily::Upgrade,
+        MessageFamily::Spawn,
+        MessageFamily::Call,
+        MessageFamily::Batch,
+    ];
+
+    /// The maximum byte size of the family's messages.
+    pub const fn max_size(self) -> usize {
+        match self {
+            MessageFamily::Deploy => MAX_DEPLOY_SIZE,
+            MessageFamily::Upgrade => MAX_UPGRADE_SIZE,
+            MessageFamily::Spawn => MAX_SPAWN_SIZE,
+            MessageFamily::Call => MAX_CALL_SIZE,
+            MessageFamily::Batch => MAX_BATCH_SIZE,
+        }
+    }
+
+    /// The name of the family (e.g `deploy`).
+    pub fn name(self) -> &'static str {
+        match self {
+            MessageFamily::Deploy => "deploy",
+            MessageFamily::Upgrade => "upgrade",
+            MessageFamily::Spawn => "spawn",
+            MessageFamily::Call => "call",
+            MessageFamily::Batch => "batch",
+        }
+    }
+}
+
+impl fmt::Display for MessageFamily {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// An encoded message exceeds the maximum byte size of its family.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+#[error("A `{family}` message can't exceed {max} bytes (got {size} bytes)")]
+pub struct MessageTooLarge {
+    /// The family of the message.
+    pub family: MessageFamily,
+
+    /// The byte size of the message.
+    pub size: usize,
+
+    /// The maximum byte size of the family's messages.
+    pub max: usize,
+}
+
+/// Checks that a message of `family` taking `size` bytes doesn't exceed the family's maximum byte size.
+pub fn check_size(family: MessageFamily, size: usize) -> Result<(), MessageTooLarge> {
+    let max = family.max_size();
+
+    if size > max {
+        Err(MessageTooLarge { family, size, max })
+    } else {
+        Ok(())
+    }
+}
+
+/// Rejects a message of `family` whose (remaining) buffer exceeds the family's maximum byte size,
+/// prior to decoding it.
+pub(crate) fn check_buffer(
+    family: MessageFamily,
+    cursor: &Cursor<&[u8]>,
+) -> Result<(), ParseError> {
+    let remaining = (cursor.get_ref().len() as u64).saturating_sub(cursor.position());
+
+    check_size(family, remaining as usize).map_err(|_| ParseError::TooManyBytes(Field::Message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_size_by_family() {
+        for family in MessageFamily::ALL.iter().copied() {
+            let max = family.max_size();
+
+            assert_eq!(check_size(family, max), Ok(()));
+            assert_eq!(
+                check_size(family, max + 1),
+                Err(MessageTooLarge {
+                    family,
+                    size: max + 1,
+                    max
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn check_buffer_counts_remaining_bytes() {
+        let bytes = vec![0; MAX_CALL_SIZE + 2];
+        let mut cursor = Cursor::new(&bytes[..]);
+
+        assert_eq!(
+            check_buffer(MessageFamily::Call, &cursor),
+            Err(ParseError::TooManyBytes(Field::Message))
+        );
+
+        cursor.set_position(2);
+        assert_eq!(check_buffer(MessageFamily::Call, &cursor), Ok(()));
+    }
+}
@@ -0,0 +1,181 @@
+//! Malleability analysis of encoded messages.
+//!
+//! A binary message is malleable when some of its bytes can be altered without changing
+//! its decoded value (i.e padding, non-canonical encodings or ignored fields).
+//! Since the `Transaction Id` is derived from the message bytes, a third-party could
+//! relay a malleable message under a different `Transaction Id`.
+//!
+//! The analysis is empirical: each byte is replaced (in turn) by every other possible value,
+//! and the outcome is decoded and compared against the decoded original message.
+//! It's thorough, but expensive (`256` decodings per byte) - so it's meant for tests and audits only.
+
+use std::io::Cursor;
+
+use svm_types::{SpawnAccount, Template, Transaction};
+
+use crate::{call, spawn, template, ParseError};
+
+/// A byte of an encoded message that can be altered without changing its decoded value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MalleableByte {
+    /// The position of the byte within the message.
+    pub offset: usize,
+
+    /// The values the byte can be replaced with (the original value excluded).
+    pub alternatives: Vec<u8>,
+}
+
+/// The outcome of a malleability analysis (see [`analyze`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MalleabilityReport {
+    /// The malleable bytes (ordered by their position).
+    pub bytes: Vec<MalleableByte>,
+
+    /// Whether bytes appended to the message are ignored by the decoding.
+    pub accepts_trailing_bytes: bool,
+}
+
+impl MalleabilityReport {
+    /// Returns whether the analyzed message has a single valid encoding.
+    pub fn is_canonical(&self) -> bool {
+        self.bytes.is_empty() && !self.accepts_trailing_bytes
+    }
+
+    /// The positions of the malleable bytes.
+    pub fn offsets(&self) -> Vec<usize> {
+        self.bytes.iter().map(|b| b.offset).collect()
+    }
+}
+
+/// Reports the bytes of `bytes` that can be altered without changing the value decoded by `decode`.
+///
+/// Returns an error when `bytes` itself can't be decoded.
+pub fn analyze<T, F>(bytes: &[u8], decode: F) -> Result<MalleabilityReport, ParseError>
+where
+    T: PartialEq,
+    F: Fn(&[u8]) -> Result<T, ParseError>,
+{
+    let original = decode(bytes)?;
+    let same = |mutated: &[u8]| matches!(decode(mutated), Ok(value) if value == original);
+
+    let mut mutated = bytes.to_vec();
+    let mut malleable = Vec::new();
+
+    for offset in 0..bytes.len() {
+        let byte = bytes[offset];
+
+        let alternatives: Vec<u8> = (0..=u8::MAX)
+            .filter(|&value| value != byte)
+            .filter(|&value| {
+                mutated[offset] = value;
+                same(&mutated)
+            })
+            .collect();
+
+        mutated[offset] = byte;
+
+        if !alternatives.is_empty() {
+            malleable.push(MalleableByte {
+                offset,
+                alternatives,
+            });
+        }
+    }
+
+    mutated.push(0);
+    let accepts_trailing_bytes = same(&mutated);
+
+    let report = MalleabilityReport {
+        bytes: malleable,
+        accepts_trailing_bytes,
+    };
+
+    Ok(report)
+}
+
+/// Analyzes an encoded `Call Account` transaction (see [`analyze`]).
+pub fn analyze_call(bytes: &[u8]) -> Result<MalleabilityReport, ParseError> {
+    analyze::<Transaction, _>(bytes, |bytes| {
+        let mut cursor = Cursor::new(bytes);
+
+        call::decode_call(&mut cursor)
+    })
+}
+
+/// Analyzes an encoded `Spawn Account` transaction (see [`analyze`]).
+pub fn analyze_spawn(bytes: &[u8]) -> Result<MalleabilityReport, ParseError> {
+    analyze::<SpawnAccount, _>(bytes, |bytes| {
+        let mut cursor = Cursor::new(bytes);
+
+        spawn::decode(&mut cursor)
+    })
+}
+
+/// Analyzes an encoded `Deploy Template` transaction (see [`analyze`]).
+pub fn analyze_deploy(bytes: &[u8]) -> Result<MalleabilityReport, ParseError> {
+    analyze::<Template, _>(bytes, |bytes| {
+        let cursor = Cursor::new(bytes);
+
+        template::decode(cursor, None)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_types::{Account, Address, SpawnCall, TemplateAddr};
+
+    #[test]
+    fn analyze_undecodable_message() {
+        let err = analyze_call(&[0x00]).unwrap_err();
+
+        assert!(matches!(err, ParseError::NotEnoughBytes(..)));
+    }
+
+    #[test]
+    fn analyze_call_report() {
+        let tx = Transaction {
+            version: 0,
+            target: Address::repeat(0x10),
+            func_name: "do_work".to_string(),
+            verifydata: vec![0xAA],
+            calldata: vec![0x10, 0x20],
+        };
+
+        let mut bytes = Vec::new();
+        call::encode_call(&tx, &mut bytes).unwrap();
+
+        let report = analyze_call(&bytes).unwrap();
+
+        assert!(report.bytes.is_empty());
+        assert!(report.accepts_trailing_bytes);
+        assert!(!report.is_canonical());
+    }
+
+    #[test]
+    fn analyze_spawn_report() {
+        let spawn = SpawnAccount {
+            version: spawn::SPAWN_CALL_VERSION,
+            account: Account {
+                name: "@account".to_string(),
+                template_addr: TemplateAddr::repeat(0x10),
+            },
+            ctor_name: "initialize".to_string(),
+            calldata: vec![0x10, 0x20, 0x30],
+            call: Some(SpawnCall {
+                func_name: "setup".to_string(),
+                calldata: vec![0x40, 0x50],
+            }),
+        };
+
+        let mut bytes = Vec::new();
+        spawn::encode(&spawn, &mut bytes).unwrap();
+
+        let report = analyze_spawn(&bytes).unwrap();
+
+        // `Has Call` accepts only `0` and `1` (which decode differently), so no byte is malleable
+        assert!(report.bytes.is_empty());
+        assert!(report.accepts_trailing_bytes);
+    }
+}
@@ -0,0 +1,211 @@
+//!  ## `Call Batch` Receipt Binary Format Versions 0 to 6
+//!
+//!  On success (`is_success = 1`)
+//!
+//!  ```text
+//!  +---------------------------------------------------------+
+//!  |           |            |             |                  |
+//!  |  tx type  |  version   | is_success  |    new State     |
+//!  | (1 byte)  | (2 bytes)  |  (1 byte)   |   (32 bytes)     |
+//!  |           |            |             |                  |
+//!  +---------------------------------------------------------+
+//!  |            |             |                |             |
+//!  |  gas_used  |  #receipts  |  call receipt  |  receipt #1 |
+//!  | (8 bytes)  |  (2 bytes)  |   byte-size    |   (Blob)    |
+//!  |            |             |   (4 bytes)    |             |
+//!  |            |             |                |             |
+//!  +---------------------------------------------------------+
+//!  |                      |                                  |
+//!  |        . . .         |            receipt #N            |
+//!  |                      |                                  |
+//!  +---------------------------------------------------------+
+//!  ```
+//!
+//!  Each receipt is the binary `Call Receipt` of a batched call (in execution order).
+//!  The logs of a successful batch are held by these receipts only (and aren't encoded twice).
+//!
+//!  Version `1` appends the `gas_used` breakdown by phase
+//!  (following the last receipt, see [gas.rs](./gas.rs)).
+//!
+//!  Version `2` records the gas schedule version (2 bytes) right after `is_success`
+//!  (for failed receipts too), and makes the `gas_used` breakdown optional.
+//!
+//!  Version `3` is laid out as version `2`.
+//!
+//!  Version `4` encodes `#logs` and the logs `data length` as variable-length integers
+//!  (see [logs.rs](./logs.rs)).
+//!
+//!  Version `5` ends the receipt (on success and on error alike) with a `Tagged Fields` block
+//!  (see [fields.rs](./fields.rs)).
+//!
+//!  Version `6` prefixes each log with its event code, followed by the indexed topics
+//!  of event logs (see [logs.rs](./logs.rs)).
+//!
+//!  On Error (`is_success = 0`)
+//!  See [error.rs][./error.rs]
+
+use std::io::Cursor;
+
+use svm_types::{BatchReceipt, Receipt};
+
+use super::spawn::{decode_sized_call_receipt, encode_sized_call_receipt};
+use super::{decode_error, decode_field, encode_error, fields, gas, read_field, types};
+use crate::version;
+use crate::{Field, ParseError, ReadExt, WriteExt};
+
+/// Encodes a [`BatchReceipt`] into its binary format.
+pub fn encode_batch(receipt: &BatchReceipt) -> Vec<u8> {
+    let mut w = Vec::new();
+
+    w.write_byte(types::BATCH);
+    version::encode_version(receipt.version, &mut w);
+    w.write_bool(receipt.success);
+    gas::encode_gas_schedule_version(receipt.version, receipt.gas_schedule_version, &mut w);
+
+    if receipt.success {
+        w.write_state(receipt.new_state());
+        gas::encode_gas_used(&receipt.gas_used, &mut w);
+        encode_receipts(receipt, &mut w);
+        gas::encode_gas_breakdown(receipt.version, receipt.gas_breakdown.as_ref(), &mut w);
+    } else {
+        encode_error(receipt.version, receipt.error(), receipt.logs(), &mut w);
+    };
+
+    fields::encode_tagged_fields(receipt.version, &[], &mut w);
+
+    w
+}
+
+/// Decodes a binary [`BatchReceipt`].
+///
+/// # Panics
+///
+/// Panics if `bytes` isn't a valid binary [`BatchReceipt`] (see [`super::try_decode_receipt`]).
+pub fn decode_batch(bytes: &[u8]) -> BatchReceipt {
+    let receipt = super::decode_receipt(bytes);
+    debug_assert!(matches!(receipt, Receipt::Batch(..)));
+
+    receipt.into_batch()
+}
+
+/// Decodes into `receipt` the fields following the header of a binary [`BatchReceipt`].
+///
+/// On failure, `receipt` holds the fields decoded so far.
+pub(crate) fn decode_batch_body(
+    cursor: &mut Cursor<&[u8]>,
+    receipt: &mut BatchReceipt,
+) -> Result<(), ParseError> {
+    if !receipt.success {
+        return decode_error(
+            receipt.version,
+            cursor,
+            &mut receipt.error,
+            &mut receipt.logs,
+        );
+    }
+
+    let new_state = read_field(cursor, Field::State, |cursor| cursor.read_state())?;
+    receipt.new_state = Some(new_state);
+
+    receipt.gas_used = decode_field(cursor, gas::decode_gas_used)?;
+
+    decode_receipts(cursor, receipt)?;
+
+    let version = receipt.version;
+    receipt.gas_breakdown =
+        decode_field(cursor, |cursor| gas::decode_gas_breakdown(version, cursor))?;
+
+    Ok(())
+}
+
+fn encode_receipts(receipt: &BatchReceipt, w: &mut Vec<u8>) {
+    debug_assert!(receipt.success);
+
+    let receipts = &receipt.receipts;
+    assert!(receipts.len() <= std::u16::MAX as usize);
+
+    w.write_u16_be(receipts.len() as u16);
+
+    for call in receipts {
+        encode_sized_call_receipt(call, w);
+    }
+}
+
+/// Decodes the batched calls receipts, collecting their logs into the `receipt` logs.
+fn decode_receipts(
+    cursor: &mut Cursor<&[u8]>,
+    receipt: &mut BatchReceipt,
+) -> Result<(), ParseError> {
+    let count = read_field(cursor, Field::BatchReceiptsCount, |cursor| {
+        cursor.read_u16_be()
+    })?;
+
+    for _ in 0..count {
+        let mut call = None;
+        let result = decode_sized_call_receipt(cursor, &mut call);
+
+        if let Some(call) = call {
+            receipt.logs.extend_from_slice(call.logs());
+            receipt.receipts.push(call);
+        }
+
+        result?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_types::{Address, CallReceipt, Gas, ReceiptLog, RuntimeError, State, TemplateAddr};
+
+    use crate::receipt::decode_receipt;
+
+    fn call_receipt(state: u8, log: &[u8]) -> CallReceipt {
+        CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            new_state: Some(State::repeat(state)),
+            returndata: Some(vec![state]),
+            gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: vec![ReceiptLog::new(log.to_vec())],
+            metrics: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn encode_decode_batch_receipt() {
+        let receipts = vec![call_receipt(0xA0, b"first"), call_receipt(0xB0, b"second")];
+        let receipt = BatchReceipt::new(receipts, Gas::with(100));
+
+        assert_eq!(receipt.new_state(), &State::repeat(0xB0));
+        assert_eq!(receipt.logs.len(), 2);
+
+        let bytes = encode_batch(&receipt);
+        let decoded = decode_receipt(&bytes);
+
+        assert_eq!(decoded.into_batch(), receipt);
+    }
+
+    #[test]
+    fn encode_decode_batch_receipt_error() {
+        let err = RuntimeError::FuncFailed {
+            target: Address::of("@Account"),
+            template: TemplateAddr::of("@Template"),
+            func: "withdraw".to_string(),
+            msg: "insufficient funds".to_string(),
+        };
+        let logs = vec![ReceiptLog::new(b"first".to_vec())];
+        let receipt = BatchReceipt::from_err(err, logs);
+
+        let bytes = encode_batch(&receipt);
+
+        assert_eq!(decode_batch(&bytes), receipt);
+    }
+}
@@ -0,0 +1,54 @@
+use std::convert::TryInto;
+use std::io::Cursor;
+
+use svm_types::LogsBloom;
+
+use crate::{Field, ParseError, ReadExt, WriteExt};
+
+/// Encodes the [`LogsBloom`] of the logs (recorded by the receipts format version `3` onwards,
+/// right after the `gas_used` breakdown).
+///
+/// ```text
+///  +-------------------------------------+
+///  |             |                       |
+///  |  has bloom  |      logs bloom       |
+///  |  (1 byte)   |      (256 bytes)      |
+///  |             |      (optional)       |
+///  |             |                       |
+///  +-------------------------------------+
+/// ```
+pub fn encode_logs_bloom(version: u16, bloom: Option<&LogsBloom>, w: &mut Vec<u8>) {
+    if version < 3 {
+        debug_assert!(bloom.is_none());
+        return;
+    }
+
+    w.write_bool(bloom.is_some());
+
+    if let Some(bloom) = bloom {
+        w.write_bytes(bloom.as_bytes());
+    }
+}
+
+pub fn decode_logs_bloom(
+    version: u16,
+    cursor: &mut Cursor<&[u8]>,
+) -> Result<Option<LogsBloom>, ParseError> {
+    if version < 3 {
+        return Ok(None);
+    }
+
+    let has_bloom = cursor
+        .read_bool()
+        .map_err(|_| ParseError::NotEnoughBytes(Field::LogsBloom))?;
+
+    if !has_bloom {
+        return Ok(None);
+    }
+
+    let bytes = cursor
+        .read_bytes(LogsBloom::BYTES)
+        .map_err(|_| ParseError::NotEnoughBytes(Field::LogsBloom))?;
+
+    Ok(Some(LogsBloom::from_bytes(bytes.try_into().unwrap())))
+}
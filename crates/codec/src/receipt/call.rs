@@ -1,4 +1,4 @@
-//!  ## `Call Account` Receipt Binary Format Version 0
+//!  ## `Call Account` Receipt Binary Format Versions 0 to 6
 //!
 //!  On success (`is_success = 1`)
 //!
@@ -22,17 +22,37 @@
 //!  +---------------------------------------------------+
 //!  ```
 //!
+//!  Version `1` appends the `gas_used` breakdown by phase
+//!  (following the logs, see [gas.rs](./gas.rs)).
+//!
+//!  Version `2` records the gas schedule version (2 bytes) right after `is_success`
+//!  (for failed receipts too), and makes the `gas_used` breakdown optional.
+//!
+//!  Version `3` appends the optional bloom filter of the logs
+//!  (following the `gas_used` breakdown, see [bloom.rs](./bloom.rs)).
+//!
+//!  Version `4` encodes `#logs` and the logs `data length` as variable-length integers
+//!  (see [logs.rs](./logs.rs)).
+//!
+//!  Version `5` ends the receipt (on success and on error alike) with a `Tagged Fields` block
+//!  (see [fields.rs](./fields.rs)), carrying the reported metrics (if any).
+//!
+//!  Version `6` prefixes each log with its event code, followed by the indexed topics
+//!  of event logs (see [logs.rs](./logs.rs)).
+//!
 //!
 //!  On Error (`is_success = 0`)
 //!  See [error.rs](./error.rs)
 
 use std::io::Cursor;
 
-use svm_types::CallReceipt;
+use svm_types::{CallReceipt, Receipt};
 
-use super::{decode_error, encode_error, gas, logs, returndata};
+use super::{
+    bloom, decode_error, decode_field, encode_error, fields, gas, logs, read_field, returndata,
+};
 use crate::version;
-use crate::{ReadExt, WriteExt};
+use crate::{Field, ParseError, ReadExt, WriteExt};
 
 /// Encodes an [`CallReceipt`] into its binary format.
 pub fn encode_call(receipt: &CallReceipt) -> Vec<u8> {
@@ -41,55 +61,71 @@ pub fn encode_call(receipt: &CallReceipt) -> Vec<u8> {
     w.write_byte(super::types::CALL);
     version::encode_version(receipt.version, &mut w);
     w.write_bool(receipt.success);
+    gas::encode_gas_schedule_version(receipt.version, receipt.gas_schedule_version, &mut w);
 
     if receipt.success {
         encode_new_state(receipt, &mut w);
         encode_returndata(receipt, &mut w);
         gas::encode_gas_used(&receipt.gas_used, &mut w);
-        logs::encode_logs(&receipt.logs, &mut w);
+        logs::encode_logs(receipt.version, &receipt.logs, &mut w);
+        gas::encode_gas_breakdown(receipt.version, receipt.gas_breakdown.as_ref(), &mut w);
+        bloom::encode_logs_bloom(receipt.version, receipt.logs_bloom.as_ref(), &mut w);
     } else {
         let logs = receipt.logs();
 
-        encode_error(receipt.error(), logs, &mut w);
+        encode_error(receipt.version, receipt.error(), logs, &mut w);
     };
 
+    fields::encode_tagged_fields(receipt.version, &receipt.metrics, &mut w);
+
     w
 }
 
 /// Decodes a binary [`CallReceipt`].
+///
+/// # Panics
+///
+/// Panics if `bytes` isn't a valid binary [`CallReceipt`] (see [`super::try_decode_receipt`]).
 pub fn decode_call(bytes: &[u8]) -> CallReceipt {
-    let mut cursor = Cursor::new(bytes);
-
-    let ty = cursor.read_byte().unwrap();
-    debug_assert_eq!(ty, crate::receipt::types::CALL);
-
-    let version = version::decode_version(&mut cursor).unwrap();
-    debug_assert_eq!(0, version);
-
-    let is_success = cursor.read_bool().unwrap();
-
-    match is_success {
-        false => {
-            let (err, logs) = decode_error(&mut cursor);
-            CallReceipt::from_err(err, logs)
-        }
-        true => {
-            let new_state = cursor.read_state().unwrap();
-            let returndata = returndata::decode(&mut cursor).unwrap();
-            let gas_used = gas::decode_gas_used(&mut cursor).unwrap();
-            let logs = logs::decode_logs(&mut cursor).unwrap();
-
-            CallReceipt {
-                version,
-                success: true,
-                error: None,
-                new_state: Some(new_state),
-                returndata: Some(returndata),
-                gas_used,
-                logs,
-            }
-        }
+    let receipt = super::decode_receipt(bytes);
+    debug_assert!(matches!(receipt, Receipt::Call(..)));
+
+    receipt.into_call()
+}
+
+/// Decodes into `receipt` the fields following the header of a binary [`CallReceipt`].
+///
+/// On failure, `receipt` holds the fields decoded so far.
+pub(crate) fn decode_call_body(
+    cursor: &mut Cursor<&[u8]>,
+    receipt: &mut CallReceipt,
+) -> Result<(), ParseError> {
+    if !receipt.success {
+        return decode_error(
+            receipt.version,
+            cursor,
+            &mut receipt.error,
+            &mut receipt.logs,
+        );
     }
+
+    let new_state = read_field(cursor, Field::State, |cursor| cursor.read_state())?;
+    receipt.new_state = Some(new_state);
+
+    let returndata = read_field(cursor, Field::ReturnData, returndata::decode)?;
+    receipt.returndata = Some(returndata);
+
+    receipt.gas_used = decode_field(cursor, gas::decode_gas_used)?;
+
+    logs::decode_logs_into(receipt.version, cursor, &mut receipt.logs)?;
+
+    let version = receipt.version;
+    receipt.gas_breakdown =
+        decode_field(cursor, |cursor| gas::decode_gas_breakdown(version, cursor))?;
+
+    receipt.logs_bloom = decode_field(cursor, |cursor| bloom::decode_logs_bloom(version, cursor))?;
+
+    Ok(())
 }
 
 fn encode_new_state(receipt: &CallReceipt, w: &mut Vec<u8>) {
@@ -110,7 +146,9 @@ fn encode_returndata(receipt: &CallReceipt, w: &mut Vec<u8>) {
 mod tests {
     use super::*;
 
-    use svm_types::{Address, Gas, ReceiptLog, RuntimeError, State};
+    use svm_types::{
+        Address, Gas, GasBreakdown, LogsBloom, ReceiptLog, RuntimeError, State, TemplateAddr,
+    };
 
     #[test]
     fn encode_decode_call_receipt_error() {
@@ -126,7 +164,11 @@ mod tests {
             new_state: None,
             returndata: None,
             gas_used: Gas::new(),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
             logs,
+            metrics: Vec::new(),
         };
 
         let bytes = encode_call(&receipt);
@@ -147,7 +189,11 @@ mod tests {
             new_state: Some(new_state),
             returndata: Some(Vec::new()),
             gas_used: Gas::with(100),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
             logs: logs.clone(),
+            metrics: Vec::new(),
         };
 
         let bytes = encode_call(&receipt);
@@ -170,7 +216,11 @@ mod tests {
             new_state: Some(new_state),
             returndata: Some(returndata),
             gas_used: Gas::with(100),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
             logs: logs.clone(),
+            metrics: Vec::new(),
         };
 
         let bytes = encode_call(&receipt);
@@ -178,4 +228,150 @@ mod tests {
 
         assert_eq!(decoded.into_call(), receipt);
     }
+
+    #[test]
+    fn encode_decode_call_receipt_success_with_gas_breakdown() {
+        let mut receipt = CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            new_state: Some(State::of("some-state")),
+            returndata: Some(vec![0x10, 0x20]),
+            gas_used: Gas::with(100),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: vec![ReceiptLog::new(b"something happened".to_vec())],
+            metrics: Vec::new(),
+        };
+        receipt.set_gas_breakdown(GasBreakdown {
+            intrinsic: 50,
+            execution: 30,
+            storage: 15,
+            logs: 10,
+            refund: 5,
+        });
+
+        let bytes = encode_call(&receipt);
+        let decoded = crate::receipt::decode_receipt(&bytes[..]);
+
+        assert_eq!(decoded.into_call(), receipt);
+    }
+
+    #[test]
+    fn encode_decode_call_receipt_with_gas_schedule_version() {
+        let mut receipt = CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            new_state: Some(State::of("some-state")),
+            returndata: Some(vec![0x10, 0x20]),
+            gas_used: Gas::with(100),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: Vec::new(),
+            metrics: Vec::new(),
+        };
+        receipt.set_gas_schedule_version(3);
+
+        let bytes = encode_call(&receipt);
+        let decoded = crate::receipt::decode_receipt(&bytes[..]);
+
+        assert_eq!(decoded.gas_schedule_version(), 3);
+        assert_eq!(decoded.into_call(), receipt);
+    }
+
+    #[test]
+    fn encode_decode_call_receipt_error_with_gas_schedule_version() {
+        let account = Address::of("@Account");
+
+        let mut receipt = CallReceipt {
+            version: 0,
+            success: false,
+            error: Some(RuntimeError::AccountNotFound(account.into())),
+            new_state: None,
+            returndata: None,
+            gas_used: Gas::new(),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: Vec::new(),
+            metrics: Vec::new(),
+        };
+        receipt.set_gas_schedule_version(1);
+
+        let bytes = encode_call(&receipt);
+        let decoded = crate::receipt::decode_receipt(&bytes[..]);
+
+        assert_eq!(decoded.into_call(), receipt);
+    }
+
+    #[test]
+    fn encode_decode_call_receipt_with_logs_bloom() {
+        let template = TemplateAddr::of("@Template");
+        let logs = vec![ReceiptLog::new(b"something happened".to_vec())];
+
+        let mut receipt = CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            new_state: Some(State::of("some-state")),
+            returndata: Some(vec![0x10, 0x20]),
+            gas_used: Gas::with(100),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: logs.clone(),
+            metrics: Vec::new(),
+        };
+        receipt.set_logs_bloom(LogsBloom::for_logs(&template, &logs));
+        receipt.set_gas_schedule_version(1);
+
+        let bytes = encode_call(&receipt);
+        let decoded = crate::receipt::decode_receipt(&bytes[..]).into_call();
+
+        assert_eq!(decoded.version, 3);
+        assert!(decoded
+            .logs_bloom
+            .as_ref()
+            .unwrap()
+            .maybe_contains(b"something happened"));
+        assert_eq!(decoded, receipt);
+    }
+
+    #[test]
+    fn encode_decode_call_receipt_with_uvarint_logs_lengths() {
+        let logs = vec![
+            ReceiptLog::new(b"short".to_vec()),
+            ReceiptLog::new(vec![0xFF; 200]),
+        ];
+
+        let mut receipt = CallReceipt {
+            version: 4,
+            success: true,
+            error: None,
+            new_state: Some(State::of("some-state")),
+            returndata: Some(vec![0x10, 0x20]),
+            gas_used: Gas::with(100),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs,
+            metrics: Vec::new(),
+        };
+        receipt.set_gas_schedule_version(1);
+
+        let v4_bytes = encode_call(&receipt);
+        let decoded = crate::receipt::decode_receipt(&v4_bytes[..]).into_call();
+
+        assert_eq!(decoded.version, 4);
+        assert_eq!(decoded, receipt);
+
+        // The length of the first log takes a single byte (the second one takes 2 bytes either way).
+        receipt.version = 3;
+        let v3_bytes = encode_call(&receipt);
+
+        assert_eq!(v3_bytes.len(), v4_bytes.len() + 1);
+    }
 }
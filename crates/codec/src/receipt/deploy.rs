@@ -1,4 +1,4 @@
-//!  ## `Deploy Template` Receipt Binary Format Version 0
+//!  ## `Deploy Template` Receipt Binary Format Versions 0 to 6
 //!
 //!  On success (`is_success = 1`)
 //!
@@ -11,17 +11,34 @@
 //!  +-----------------------------------------------------------------------+
 //!  ```
 //!
+//!  Version `1` appends the `gas_used` breakdown by phase
+//!  (following the logs, see [gas.rs](./gas.rs)).
+//!
+//!  Version `2` records the gas schedule version (2 bytes) right after `is_success`
+//!  (for failed receipts too), and makes the `gas_used` breakdown optional.
+//!
+//!  Version `3` is laid out as version `2`.
+//!
+//!  Version `4` encodes `#logs` and the logs `data length` as variable-length integers
+//!  (see [logs.rs](./logs.rs)).
+//!
+//!  Version `5` ends the receipt (on success and on error alike) with a `Tagged Fields` block
+//!  (see [fields.rs](./fields.rs)).
+//!
+//!  Version `6` prefixes each log with its event code, followed by the indexed topics
+//!  of event logs (see [logs.rs](./logs.rs)).
+//!
 //!  On Error (`is_success = 0`)
 //!  See [error.rs][./error.rs]
 
 use std::io::Cursor;
 
-use svm_types::DeployReceipt;
+use svm_types::{DeployReceipt, Receipt};
 
-use super::{decode_error, encode_error, gas, logs, types};
+use super::{decode_error, decode_field, encode_error, fields, gas, logs, read_field, types};
 
 use crate::version;
-use crate::{ReadExt, WriteExt};
+use crate::{Field, ParseError, ReadExt, WriteExt};
 
 /// Encodes a [`DeployReceipt`] into its binary format.
 pub fn encode_deploy(receipt: &DeployReceipt) -> Vec<u8> {
@@ -30,55 +47,66 @@ pub fn encode_deploy(receipt: &DeployReceipt) -> Vec<u8> {
     w.write_byte(types::DEPLOY);
     encode_version(receipt, &mut w);
     w.write_bool(receipt.success);
+    gas::encode_gas_schedule_version(receipt.version, receipt.gas_schedule_version, &mut w);
 
     if receipt.success {
         encode_template_addr(receipt, &mut w);
         gas::encode_gas_used(&receipt.gas_used, &mut w);
-        logs::encode_logs(&receipt.logs, &mut w);
+        logs::encode_logs(receipt.version, &receipt.logs, &mut w);
+        gas::encode_gas_breakdown(receipt.version, receipt.gas_breakdown.as_ref(), &mut w);
     } else {
         let logs = Vec::new();
 
-        encode_error(receipt.error(), &logs, &mut w);
+        encode_error(receipt.version, receipt.error(), &logs, &mut w);
     };
 
+    fields::encode_tagged_fields(receipt.version, &[], &mut w);
+
     w
 }
 
 /// Decodes a binary [`DeployReceipt`] transaction.
+///
+/// # Panics
+///
+/// Panics if `bytes` isn't a valid binary [`DeployReceipt`] (see [`super::try_decode_receipt`]).
 pub fn decode_deploy(bytes: &[u8]) -> DeployReceipt {
-    let mut cursor = Cursor::new(bytes);
-
-    let ty = cursor.read_byte().unwrap();
-    debug_assert_eq!(ty, types::DEPLOY);
-
-    let version = version::decode_version(&mut cursor).unwrap();
-    debug_assert_eq!(version, 0);
-
-    let is_success = cursor.read_bool().unwrap();
-
-    match is_success {
-        false => {
-            let (err, logs) = decode_error(&mut cursor);
-
-            DeployReceipt::from_err(err, logs)
-        }
-        true => {
-            let addr = cursor
-                .read_template_addr()
-                .expect("expected a Template Address");
-            let gas_used = gas::decode_gas_used(&mut cursor).unwrap();
-            let logs = logs::decode_logs(&mut cursor).unwrap();
-
-            DeployReceipt {
-                version,
-                success: true,
-                error: None,
-                addr: Some(addr),
-                gas_used,
-                logs,
-            }
-        }
+    let receipt = super::decode_receipt(bytes);
+    debug_assert!(matches!(receipt, Receipt::Deploy(..)));
+
+    receipt.into_deploy()
+}
+
+/// Decodes into `receipt` the fields following the header of a binary [`DeployReceipt`].
+///
+/// On failure, `receipt` holds the fields decoded so far.
+pub(crate) fn decode_deploy_body(
+    cursor: &mut Cursor<&[u8]>,
+    receipt: &mut DeployReceipt,
+) -> Result<(), ParseError> {
+    if !receipt.success {
+        return decode_error(
+            receipt.version,
+            cursor,
+            &mut receipt.error,
+            &mut receipt.logs,
+        );
     }
+
+    let addr = read_field(cursor, Field::TemplateAddr, |cursor| {
+        cursor.read_template_addr()
+    })?;
+    receipt.addr = Some(addr);
+
+    receipt.gas_used = decode_field(cursor, gas::decode_gas_used)?;
+
+    logs::decode_logs_into(receipt.version, cursor, &mut receipt.logs)?;
+
+    let version = receipt.version;
+    receipt.gas_breakdown =
+        decode_field(cursor, |cursor| gas::decode_gas_breakdown(version, cursor))?;
+
+    Ok(())
 }
 
 fn encode_version(receipt: &DeployReceipt, w: &mut Vec<u8>) {
@@ -111,6 +139,8 @@ mod tests {
             error: None,
             addr: Some(addr),
             gas_used: Gas::with(100),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
             logs: Vec::new(),
         };
 
@@ -20,6 +20,13 @@
 //!  +-------------------------------------------------------+
 //!  ```
 //!
+//!  Version `2` records the gas schedule version (2 bytes) between `is_success`
+//!  and the `error code`.
+//!
+//!  Version `4` encodes the logs lengths as variable-length integers (see [logs.rs](./logs.rs)).
+//!
+//!  Version `5` is followed by the `Tagged Fields` block ending the receipt (see [fields.rs](./fields.rs)).
+//!
 //!
 //!  ### Error Blob
 //!
@@ -77,18 +84,42 @@
 //!   |   (20 bytes)      |   (20 bytes)      |  (String)  |
 //!   +-------------------+-------------------+------------+
 //!
+//!  * Upgrade Not Allowed
+//!   +-------------------+-----------------+
+//!   |  Template Address |     Message     |
+//!   |   (20 bytes)      |  (UTF-8 String) |
+//!   +-------------------+-----------------+
+//!
+//!  * Internal Error
+//!   +-----------------+
+//!   |     Message     |
+//!   |  (UTF-8 String) |
+//!   +-----------------+
+//!
+//!  * Insufficient Balance
+//!   +-----------------+-------------+-------------+
+//!   | Account Address |   Balance   |   Amount    |
+//!   |   (20 bytes)    |  (8 bytes)  |  (8 bytes)  |
+//!   +-----------------+-------------+-------------+
+//!
+//!  * Storage Corrupted
+//!   +-----------------+-----------------+
+//!   | Account Address |     Message     |
+//!   |   (20 bytes)    |  (UTF-8 String) |
+//!   +-----------------+-----------------+
+//!
 
 use std::io::Cursor;
 
 use svm_types::{Address, ReceiptLog, RuntimeError, TemplateAddr};
 
-use super::logs;
-use crate::{ReadExt, WriteExt};
+use super::{decode_field, logs, read_field};
+use crate::{Field, ParseError, ReadExt, WriteExt};
 
-pub(crate) fn encode_error(err: &RuntimeError, logs: &[ReceiptLog], w: &mut Vec<u8>) {
+pub(crate) fn encode_error(version: u16, err: &RuntimeError, logs: &[ReceiptLog], w: &mut Vec<u8>) {
     encode_err_type(err, w);
 
-    logs::encode_logs(logs, w);
+    logs::encode_logs(version, logs, w);
 
     match err {
         RuntimeError::OOG => (),
@@ -148,6 +179,24 @@ pub(crate) fn encode_error(err: &RuntimeError, logs: &[ReceiptLog], w: &mut Vec<
             encode_target(target, w);
             encode_func(func, w);
         }
+        RuntimeError::UpgradeNotAllowed { template, msg } => {
+            encode_template(template, w);
+            encode_msg(msg, w);
+        }
+        RuntimeError::InternalError { msg } => encode_msg(msg, w),
+        RuntimeError::InsufficientBalance {
+            target,
+            balance,
+            amount,
+        } => {
+            encode_target(target, w);
+            w.write_u64_be(*balance);
+            w.write_u64_be(*amount);
+        }
+        RuntimeError::StorageCorrupted { target, msg } => {
+            encode_target(target, w);
+            encode_msg(msg, w);
+        }
     };
 }
 
@@ -184,137 +233,207 @@ fn encode_err_type(err: &RuntimeError, w: &mut Vec<u8>) {
         RuntimeError::FuncFailed { .. } => 6,
         RuntimeError::FuncNotAllowed { .. } => 7,
         RuntimeError::FuncInvalidSignature { .. } => 8,
+        RuntimeError::UpgradeNotAllowed { .. } => 9,
+        RuntimeError::InternalError { .. } => 10,
+        RuntimeError::InsufficientBalance { .. } => 11,
+        RuntimeError::StorageCorrupted { .. } => 12,
     };
 
     w.push(ty);
 }
 
-pub(crate) fn decode_error(cursor: &mut Cursor<&[u8]>) -> (RuntimeError, Vec<ReceiptLog>) {
-    let ty = cursor.read_byte().unwrap();
-    let logs = logs::decode_logs(cursor).unwrap();
+pub(crate) fn decode_error(
+    version: u16,
+    cursor: &mut Cursor<&[u8]>,
+    error: &mut Option<RuntimeError>,
+    logs: &mut Vec<ReceiptLog>,
+) -> Result<(), ParseError> {
+    let ty = decode_field(cursor, |cursor| {
+        let ty = cursor
+            .read_byte()
+            .map_err(|_| ParseError::NotEnoughBytes(Field::ErrorCode))?;
 
-    let err = {
         match ty {
-            0 => oog(cursor),
-            1 => template_not_found(cursor),
-            2 => account_not_found(cursor),
-            3 => compilation_error(cursor),
-            4 => instantiation_error(cursor),
-            5 => func_not_found(cursor),
-            6 => func_failed(cursor),
-            7 => func_not_allowed(cursor),
-            8 => func_invalid_sig(cursor),
-            _ => unreachable!(),
+            0..=12 => Ok(ty),
+            _ => Err(ParseError::NotSupported(Field::ErrorCode)),
         }
-    };
-
-    (err, logs)
+    })?;
+
+    logs::decode_logs_into(version, cursor, logs)?;
+
+    let err = match ty {
+        0 => oog(cursor),
+        1 => template_not_found(cursor),
+        2 => account_not_found(cursor),
+        3 => compilation_error(cursor),
+        4 => instantiation_error(cursor),
+        5 => func_not_found(cursor),
+        6 => func_failed(cursor),
+        7 => func_not_allowed(cursor),
+        8 => func_invalid_sig(cursor),
+        9 => upgrade_not_allowed(cursor),
+        10 => internal_error(cursor),
+        11 => insufficient_balance(cursor),
+        12 => storage_corrupted(cursor),
+        _ => unreachable!(),
+    }?;
+
+    *error = Some(err);
+
+    Ok(())
 }
 
-fn oog(_cursor: &mut Cursor<&[u8]>) -> RuntimeError {
-    RuntimeError::OOG
+fn oog(_cursor: &mut Cursor<&[u8]>) -> Result<RuntimeError, ParseError> {
+    Ok(RuntimeError::OOG)
 }
 
-fn template_not_found(cursor: &mut Cursor<&[u8]>) -> RuntimeError {
-    let template_addr = decode_template_addr(cursor);
-    RuntimeError::TemplateNotFound(template_addr)
+fn template_not_found(cursor: &mut Cursor<&[u8]>) -> Result<RuntimeError, ParseError> {
+    let template_addr = decode_template_addr(cursor)?;
+
+    Ok(RuntimeError::TemplateNotFound(template_addr))
 }
 
-fn account_not_found(cursor: &mut Cursor<&[u8]>) -> RuntimeError {
-    let account = decode_account_addr(cursor);
-    RuntimeError::AccountNotFound(account.into())
+fn account_not_found(cursor: &mut Cursor<&[u8]>) -> Result<RuntimeError, ParseError> {
+    let account = decode_account_addr(cursor)?;
+
+    Ok(RuntimeError::AccountNotFound(account.into()))
 }
 
-fn compilation_error(cursor: &mut Cursor<&[u8]>) -> RuntimeError {
-    let template_addr = decode_template_addr(cursor);
-    let account_addr = decode_account_addr(cursor);
-    let msg = decode_msg(cursor);
+fn compilation_error(cursor: &mut Cursor<&[u8]>) -> Result<RuntimeError, ParseError> {
+    let template_addr = decode_template_addr(cursor)?;
+    let account_addr = decode_account_addr(cursor)?;
+    let msg = decode_msg(cursor)?;
 
-    RuntimeError::CompilationFailed {
+    Ok(RuntimeError::CompilationFailed {
         template: template_addr,
         target: account_addr,
         msg,
-    }
+    })
 }
 
-fn instantiation_error(cursor: &mut Cursor<&[u8]>) -> RuntimeError {
-    let template_addr = decode_template_addr(cursor);
-    let account_addr = decode_account_addr(cursor);
-    let msg = decode_msg(cursor);
+fn instantiation_error(cursor: &mut Cursor<&[u8]>) -> Result<RuntimeError, ParseError> {
+    let template_addr = decode_template_addr(cursor)?;
+    let account_addr = decode_account_addr(cursor)?;
+    let msg = decode_msg(cursor)?;
 
-    RuntimeError::InstantiationFailed {
+    Ok(RuntimeError::InstantiationFailed {
         template: template_addr,
         target: account_addr,
         msg,
-    }
+    })
 }
 
-fn func_not_found(cursor: &mut Cursor<&[u8]>) -> RuntimeError {
-    let template_addr = decode_template_addr(cursor);
-    let account_addr = decode_account_addr(cursor);
-    let func = decode_func(cursor);
+fn func_not_found(cursor: &mut Cursor<&[u8]>) -> Result<RuntimeError, ParseError> {
+    let template_addr = decode_template_addr(cursor)?;
+    let account_addr = decode_account_addr(cursor)?;
+    let func = decode_func(cursor)?;
 
-    RuntimeError::FuncNotFound {
+    Ok(RuntimeError::FuncNotFound {
         template: template_addr,
         target: account_addr,
         func,
-    }
+    })
 }
 
-fn func_failed(cursor: &mut Cursor<&[u8]>) -> RuntimeError {
-    let template_addr = decode_template_addr(cursor);
-    let account_addr = decode_account_addr(cursor);
-    let func = decode_func(cursor);
-    let msg = decode_msg(cursor);
+fn func_failed(cursor: &mut Cursor<&[u8]>) -> Result<RuntimeError, ParseError> {
+    let template_addr = decode_template_addr(cursor)?;
+    let account_addr = decode_account_addr(cursor)?;
+    let func = decode_func(cursor)?;
+    let msg = decode_msg(cursor)?;
 
-    RuntimeError::FuncFailed {
+    Ok(RuntimeError::FuncFailed {
         template: template_addr,
         target: account_addr,
         func,
         msg,
-    }
+    })
 }
 
-fn func_not_allowed(cursor: &mut Cursor<&[u8]>) -> RuntimeError {
-    let template_addr = decode_template_addr(cursor);
-    let account_addr = decode_account_addr(cursor);
-    let func = decode_func(cursor);
-    let msg = decode_msg(cursor);
+fn func_not_allowed(cursor: &mut Cursor<&[u8]>) -> Result<RuntimeError, ParseError> {
+    let template_addr = decode_template_addr(cursor)?;
+    let account_addr = decode_account_addr(cursor)?;
+    let func = decode_func(cursor)?;
+    let msg = decode_msg(cursor)?;
 
-    RuntimeError::FuncNotAllowed {
+    Ok(RuntimeError::FuncNotAllowed {
         template: template_addr,
         target: account_addr,
         func,
         msg,
-    }
+    })
 }
 
-fn func_invalid_sig(cursor: &mut Cursor<&[u8]>) -> RuntimeError {
-    let template_addr = decode_template_addr(cursor);
-    let account_addr = decode_account_addr(cursor);
-    let func = decode_func(cursor);
+fn func_invalid_sig(cursor: &mut Cursor<&[u8]>) -> Result<RuntimeError, ParseError> {
+    let template_addr = decode_template_addr(cursor)?;
+    let account_addr = decode_account_addr(cursor)?;
+    let func = decode_func(cursor)?;
 
-    RuntimeError::FuncInvalidSignature {
+    Ok(RuntimeError::FuncInvalidSignature {
         template: template_addr,
         target: account_addr,
         func,
-    }
+    })
 }
 
-fn decode_func(cursor: &mut Cursor<&[u8]>) -> String {
-    cursor.read_string().unwrap().unwrap()
+fn upgrade_not_allowed(cursor: &mut Cursor<&[u8]>) -> Result<RuntimeError, ParseError> {
+    let template_addr = decode_template_addr(cursor)?;
+    let msg = decode_msg(cursor)?;
+
+    Ok(RuntimeError::UpgradeNotAllowed {
+        template: template_addr,
+        msg,
+    })
+}
+
+fn internal_error(cursor: &mut Cursor<&[u8]>) -> Result<RuntimeError, ParseError> {
+    let msg = decode_msg(cursor)?;
+
+    Ok(RuntimeError::InternalError { msg })
+}
+
+fn insufficient_balance(cursor: &mut Cursor<&[u8]>) -> Result<RuntimeError, ParseError> {
+    let target = decode_account_addr(cursor)?;
+    let balance = read_field(cursor, Field::Balance, |cursor| cursor.read_u64_be())?;
+    let amount = read_field(cursor, Field::Amount, |cursor| cursor.read_u64_be())?;
+
+    Ok(RuntimeError::InsufficientBalance {
+        target,
+        balance,
+        amount,
+    })
+}
+
+fn storage_corrupted(cursor: &mut Cursor<&[u8]>) -> Result<RuntimeError, ParseError> {
+    let target = decode_account_addr(cursor)?;
+    let msg = decode_msg(cursor)?;
+
+    Ok(RuntimeError::StorageCorrupted { target, msg })
 }
 
-fn decode_template_addr(cursor: &mut Cursor<&[u8]>) -> TemplateAddr {
-    cursor.read_template_addr().unwrap()
+fn decode_func(cursor: &mut Cursor<&[u8]>) -> Result<String, ParseError> {
+    decode_string(cursor, Field::Function)
 }
 
-fn decode_account_addr(cursor: &mut Cursor<&[u8]>) -> Address {
-    cursor.read_address().unwrap()
+fn decode_template_addr(cursor: &mut Cursor<&[u8]>) -> Result<TemplateAddr, ParseError> {
+    read_field(cursor, Field::TemplateAddr, |cursor| {
+        cursor.read_template_addr()
+    })
 }
 
-fn decode_msg(cursor: &mut Cursor<&[u8]>) -> String {
-    cursor.read_string().unwrap().unwrap()
+fn decode_account_addr(cursor: &mut Cursor<&[u8]>) -> Result<Address, ParseError> {
+    read_field(cursor, Field::Address, |cursor| cursor.read_address())
+}
+
+fn decode_msg(cursor: &mut Cursor<&[u8]>) -> Result<String, ParseError> {
+    decode_string(cursor, Field::ErrorMessage)
+}
+
+fn decode_string(cursor: &mut Cursor<&[u8]>, field: Field) -> Result<String, ParseError> {
+    decode_field(cursor, |cursor| match cursor.read_string() {
+        Ok(Ok(s)) => Ok(s),
+        Ok(Err(..)) => Err(ParseError::InvalidUTF8String(field)),
+        Err(..) => Err(ParseError::NotEnoughBytes(field)),
+    })
 }
 
 #[cfg(test)]
@@ -330,15 +449,26 @@ mod tests {
         ]
     }
 
+    fn decode(cursor: &mut Cursor<&[u8]>) -> (RuntimeError, Vec<ReceiptLog>) {
+        let mut error = None;
+        let mut logs = Vec::new();
+
+        decode_error(0, cursor, &mut error, &mut logs).unwrap();
+
+        (error.unwrap(), logs)
+    }
+
     #[test]
     fn decode_receipt_oog() {
         let err = RuntimeError::OOG;
 
         let mut buf = Vec::new();
-        encode_error(&err, &test_logs(), &mut buf);
+        encode_error(0, &err, &test_logs(), &mut buf);
 
         let mut cursor = Cursor::new(&buf[..]);
-        let _decoded = decode_error(&mut cursor);
+        let (decoded, _logs) = decode(&mut cursor);
+
+        assert_eq!(decoded, err);
     }
 
     #[test]
@@ -347,11 +477,11 @@ mod tests {
         let err = RuntimeError::TemplateNotFound(template_addr);
 
         let mut buf = Vec::new();
-        encode_error(&err, &test_logs(), &mut buf);
+        encode_error(0, &err, &test_logs(), &mut buf);
 
         let mut cursor = Cursor::new(&buf[..]);
 
-        let (decoded, logs) = decode_error(&mut cursor);
+        let (decoded, logs) = decode(&mut cursor);
 
         assert_eq!(decoded, err);
         assert_eq!(logs, test_logs());
@@ -363,11 +493,11 @@ mod tests {
         let err = RuntimeError::AccountNotFound(account_addr);
 
         let mut bytes = Vec::new();
-        encode_error(&err, &test_logs(), &mut bytes);
+        encode_error(0, &err, &test_logs(), &mut bytes);
 
         let mut cursor = Cursor::new(&bytes[..]);
 
-        let (decoded, logs) = decode_error(&mut cursor);
+        let (decoded, logs) = decode(&mut cursor);
 
         assert_eq!(decoded, err);
         assert_eq!(logs, test_logs());
@@ -385,11 +515,11 @@ mod tests {
         };
 
         let mut buf = Vec::new();
-        encode_error(&err, &test_logs(), &mut buf);
+        encode_error(0, &err, &test_logs(), &mut buf);
 
         let mut cursor = Cursor::new(&buf[..]);
 
-        let (decoded, logs) = decode_error(&mut cursor);
+        let (decoded, logs) = decode(&mut cursor);
 
         assert_eq!(decoded, err);
         assert_eq!(logs, test_logs());
@@ -407,11 +537,11 @@ mod tests {
         };
 
         let mut buf = Vec::new();
-        encode_error(&err, &test_logs(), &mut buf);
+        encode_error(0, &err, &test_logs(), &mut buf);
 
         let mut cursor = Cursor::new(&buf[..]);
 
-        let (decoded, logs) = decode_error(&mut cursor);
+        let (decoded, logs) = decode(&mut cursor);
 
         assert_eq!(decoded, err);
         assert_eq!(logs, test_logs());
@@ -430,11 +560,11 @@ mod tests {
         };
 
         let mut buf = Vec::new();
-        encode_error(&err, &test_logs(), &mut buf);
+        encode_error(0, &err, &test_logs(), &mut buf);
 
         let mut cursor = Cursor::new(&buf[..]);
 
-        let (decoded, logs) = decode_error(&mut cursor);
+        let (decoded, logs) = decode(&mut cursor);
 
         assert_eq!(decoded, err);
         assert_eq!(logs, test_logs());
@@ -455,11 +585,11 @@ mod tests {
         };
 
         let mut buf = Vec::new();
-        encode_error(&err, &test_logs(), &mut buf);
+        encode_error(0, &err, &test_logs(), &mut buf);
 
         let mut cursor = Cursor::new(&buf[..]);
 
-        let (decoded, logs) = decode_error(&mut cursor);
+        let (decoded, logs) = decode(&mut cursor);
 
         assert_eq!(decoded, err);
         assert_eq!(logs, test_logs());
@@ -480,11 +610,86 @@ mod tests {
         };
 
         let mut buf = Vec::new();
-        encode_error(&err, &test_logs(), &mut buf);
+        encode_error(0, &err, &test_logs(), &mut buf);
+
+        let mut cursor = Cursor::new(&buf[..]);
+
+        let (decoded, logs) = decode(&mut cursor);
+
+        assert_eq!(decoded, err);
+        assert_eq!(logs, test_logs());
+    }
+
+    #[test]
+    fn decode_receipt_upgrade_not_allowed() {
+        let template_addr = TemplateAddr::of("@Template");
+        let msg = "only the deployer may upgrade".to_string();
+
+        let err = RuntimeError::UpgradeNotAllowed {
+            template: template_addr,
+            msg,
+        };
+
+        let mut buf = Vec::new();
+        encode_error(0, &err, &test_logs(), &mut buf);
+
+        let mut cursor = Cursor::new(&buf[..]);
+
+        let (decoded, logs) = decode(&mut cursor);
+
+        assert_eq!(decoded, err);
+        assert_eq!(logs, test_logs());
+    }
+
+    #[test]
+    fn decode_receipt_internal_error() {
+        let err = RuntimeError::InternalError {
+            msg: "called `Option::unwrap()` on a `None` value".to_string(),
+        };
+
+        let mut buf = Vec::new();
+        encode_error(0, &err, &test_logs(), &mut buf);
+
+        let mut cursor = Cursor::new(&buf[..]);
+
+        let (decoded, logs) = decode(&mut cursor);
+
+        assert_eq!(decoded, err);
+        assert_eq!(logs, test_logs());
+    }
+
+    #[test]
+    fn decode_receipt_insufficient_balance() {
+        let err = RuntimeError::InsufficientBalance {
+            target: Address::of("@Account"),
+            balance: 10,
+            amount: 1000,
+        };
+
+        let mut buf = Vec::new();
+        encode_error(0, &err, &test_logs(), &mut buf);
+
+        let mut cursor = Cursor::new(&buf[..]);
+
+        let (decoded, logs) = decode(&mut cursor);
+
+        assert_eq!(decoded, err);
+        assert_eq!(logs, test_logs());
+    }
+
+    #[test]
+    fn decode_receipt_storage_corrupted() {
+        let err = RuntimeError::StorageCorrupted {
+            target: Address::of("@Account"),
+            msg: "checksum mismatch".to_string(),
+        };
+
+        let mut buf = Vec::new();
+        encode_error(0, &err, &test_logs(), &mut buf);
 
         let mut cursor = Cursor::new(&buf[..]);
 
-        let (decoded, logs) = decode_error(&mut cursor);
+        let (decoded, logs) = decode(&mut cursor);
 
         assert_eq!(decoded, err);
         assert_eq!(logs, test_logs());
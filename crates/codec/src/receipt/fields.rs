@@ -0,0 +1,327 @@
+use std::io::Cursor;
+
+use svm_types::{Receipt, ReceiptMetric};
+
+use super::read_field;
+use crate::tagged::{self, TaggedFields};
+use crate::{Field, ParseError, ReadExt, WriteExt};
+
+/// The receipts format version ending the receipts with a `Tagged Fields` block.
+pub const TAGGED_FIELDS_VERSION: u16 = 5;
+
+/// The tags of the fields ending the receipts.
+///
+/// New optional receipt fields are to be added here under (odd) tags,
+/// instead of forking a new receipts format version.
+pub mod tags {
+    /// The metrics reported by the executed code (see [`encode_metrics`](super::encode_metrics)).
+    ///
+    /// Carried by the `Spawn Account` and `Call Account` receipts, and omitted when there are none.
+    pub const METRICS: u64 = 1;
+
+    /// The tags of the required fields understood by this version of the codec.
+    pub const KNOWN: &[u64] = &[];
+}
+
+/// Encodes the `Tagged Fields` block ending a receipt (recorded by the receipts format version `5` onwards).
+///
+/// See [`crate::tagged`].
+pub fn encode_tagged_fields(version: u16, metrics: &[ReceiptMetric], w: &mut Vec<u8>) {
+    if version < TAGGED_FIELDS_VERSION {
+        debug_assert!(metrics.is_empty());
+        return;
+    }
+
+    let mut fields = TaggedFields::new();
+
+    if !metrics.is_empty() {
+        let mut value = Vec::new();
+        encode_metrics(metrics, &mut value);
+
+        fields.insert(tags::METRICS, value);
+    }
+
+    tagged::encode_tagged(&fields, w);
+}
+
+/// Decodes the `Tagged Fields` block ending a receipt.
+///
+/// The optional fields unknown to this version of the codec are skipped.
+pub fn decode_tagged_fields(
+    version: u16,
+    cursor: &mut Cursor<&[u8]>,
+) -> Result<TaggedFields, ParseError> {
+    if version < TAGGED_FIELDS_VERSION {
+        return Ok(TaggedFields::new());
+    }
+
+    tagged::decode_tagged(cursor, tags::KNOWN)
+}
+
+/// Sets the fields of `receipt` carried by its `Tagged Fields` block.
+pub fn decode_known_fields(fields: &TaggedFields, receipt: &mut Receipt) -> Result<(), ParseError> {
+    let metrics = match fields.get(tags::METRICS) {
+        Some(value) => decode_metrics(value)?,
+        None => return Ok(()),
+    };
+
+    match receipt {
+        Receipt::Spawn(receipt) => receipt.metrics = metrics,
+        Receipt::Call(receipt) => receipt.metrics = metrics,
+        // Other receipts don't carry metrics, so the field is ignored
+        Receipt::Deploy(..) | Receipt::Upgrade(..) | Receipt::Batch(..) => (),
+    }
+
+    Ok(())
+}
+
+/// Encodes the reported metrics as a compact list (the value of the [`tags::METRICS`] field).
+///
+/// ```text
+/// +-------------+-------------+----------------+---------+
+/// |             |             |                |         |
+/// |  #metrics   |  metric id  |  metric value  |  . . .  |
+/// |  (uvarint)  |  (uvarint)  |   (uvarint)    |         |
+/// |             |             |                |         |
+/// +-------------+-------------+----------------+---------+
+/// ```
+pub fn encode_metrics(metrics: &[ReceiptMetric], w: &mut Vec<u8>) {
+    w.write_uvarint(metrics.len() as u64);
+
+    for metric in metrics.iter() {
+        w.write_uvarint(metric.id as u64);
+        w.write_uvarint(metric.value);
+    }
+}
+
+/// Decodes the reported metrics (see [`encode_metrics`]).
+pub fn decode_metrics(bytes: &[u8]) -> Result<Vec<ReceiptMetric>, ParseError> {
+    let mut cursor = Cursor::new(bytes);
+
+    let count = read_field(&mut cursor, Field::MetricsCount, |cursor| {
+        cursor.read_uvarint()
+    })?;
+
+    // Each metric takes at least two bytes, so a bogus `#metrics` can't make us over-allocate.
+    let mut metrics = Vec::with_capacity(count.min(bytes.len() as u64 / 2) as usize);
+
+    for _ in 0..count {
+        let id = read_field(&mut cursor, Field::MetricId, |cursor| cursor.read_uvarint())?;
+
+        if id > std::u32::MAX as u64 {
+            return Err(ParseError::NotSupported(Field::MetricId));
+        }
+
+        let value = read_field(&mut cursor, Field::MetricValue, |cursor| {
+            cursor.read_uvarint()
+        })?;
+
+        metrics.push(ReceiptMetric::new(id as u32, value));
+    }
+
+    if cursor.position() != bytes.len() as u64 {
+        return Err(ParseError::TooManyBytes(Field::MetricValue));
+    }
+
+    Ok(metrics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_types::{
+        Address, CallReceipt, Gas, Receipt, ReceiptLog, ReceiptMetric, RuntimeError, SpawnReceipt,
+        State,
+    };
+
+    use crate::receipt::{decode_receipt_partial, encode_call, encode_spawn, try_decode_receipt};
+    use crate::Field;
+
+    fn call_receipt(version: u16) -> CallReceipt {
+        CallReceipt {
+            version,
+            success: true,
+            error: None,
+            new_state: Some(State::of("some-state")),
+            returndata: Some(vec![0x10, 0x20]),
+            gas_used: Gas::with(100),
+            gas_breakdown: None,
+            gas_schedule_version: 1,
+            logs_bloom: None,
+            logs: vec![ReceiptLog::new(b"Log entry".to_vec())],
+            metrics: Vec::new(),
+        }
+    }
+
+    /// Replaces the (empty) `Tagged Fields` block ending `bytes` with `fields`.
+    fn with_fields(mut bytes: Vec<u8>, fields: &TaggedFields) -> Vec<u8> {
+        assert_eq!(bytes.pop(), Some(0));
+
+        tagged::encode_tagged(fields, &mut bytes);
+        bytes
+    }
+
+    #[test]
+    fn receipt_v5_ends_with_tagged_fields() {
+        let v4_bytes = encode_call(&call_receipt(4));
+        let v5_bytes = encode_call(&call_receipt(5));
+
+        assert_eq!(v5_bytes.len(), v4_bytes.len() + 1);
+        assert_eq!(v5_bytes.last(), Some(&0));
+
+        assert_eq!(
+            try_decode_receipt(&v4_bytes),
+            Ok(Receipt::Call(call_receipt(4)))
+        );
+        assert_eq!(
+            try_decode_receipt(&v5_bytes),
+            Ok(Receipt::Call(call_receipt(5)))
+        );
+    }
+
+    #[test]
+    fn receipt_v5_skips_unknown_optional_fields() {
+        let mut fields = TaggedFields::new();
+        fields.insert(3, vec![0xAB; 3]);
+        fields.insert(1001, Vec::new());
+
+        let bytes = with_fields(encode_call(&call_receipt(5)), &fields);
+
+        let partial = decode_receipt_partial(&bytes);
+
+        assert_eq!(partial.offset, bytes.len());
+        assert_eq!(partial.into_result(), Ok(Receipt::Call(call_receipt(5))));
+    }
+
+    #[test]
+    fn receipt_v5_unknown_required_field() {
+        let mut fields = TaggedFields::new();
+        fields.insert(2, vec![0xAB]);
+
+        let plain = encode_call(&call_receipt(5));
+        let bytes = with_fields(plain.clone(), &fields);
+
+        let partial = decode_receipt_partial(&bytes);
+
+        // The receipt itself is recovered, and decoding has stopped at its `Tagged Fields`
+        assert_eq!(partial.receipt, Some(Receipt::Call(call_receipt(5))));
+        assert_eq!(partial.offset, plain.len() - 1);
+        assert_eq!(
+            partial.error,
+            Some(ParseError::NotSupported(Field::TaggedFieldTag))
+        );
+    }
+
+    #[test]
+    fn receipt_v5_error_with_tagged_fields() {
+        let error = RuntimeError::AccountNotFound(Address::of("@Account"));
+        let receipt = CallReceipt {
+            version: 5,
+            ..CallReceipt::from_err(error, Vec::new())
+        };
+
+        let mut fields = TaggedFields::new();
+        fields.insert(7, vec![0xCD]);
+
+        let bytes = with_fields(encode_call(&receipt), &fields);
+
+        assert_eq!(try_decode_receipt(&bytes), Ok(Receipt::Call(receipt)));
+    }
+
+    #[test]
+    fn receipt_v5_nested_call_receipt() {
+        let receipt = SpawnReceipt {
+            version: 7,
+            success: true,
+            error: None,
+            account_addr: Some(Address::of("@Account")),
+            init_state: Some(State::of("some-state")),
+            returndata: Some(Vec::new()),
+            gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 1,
+            logs_bloom: None,
+            logs: Vec::new(),
+            metrics: Vec::new(),
+            call: Some(call_receipt(5)),
+        };
+
+        let bytes = encode_spawn(&receipt);
+
+        assert_eq!(try_decode_receipt(&bytes), Ok(Receipt::Spawn(receipt)));
+    }
+
+    #[test]
+    fn receipt_metrics_encode_decode() {
+        let mut receipt = call_receipt(4);
+        receipt.set_metrics(vec![
+            ReceiptMetric::new(1, 80),
+            ReceiptMetric::new(300, std::u64::MAX),
+        ]);
+
+        assert_eq!(receipt.version, 5);
+
+        let bytes = encode_call(&receipt);
+        let plain = encode_call(&call_receipt(5));
+
+        // #Fields = 1, Tag = 1, Length = 15, #metrics = 2, ...
+        assert_eq!(&bytes[plain.len() - 1..plain.len() + 4], &[1, 1, 15, 2, 1]);
+        assert_eq!(try_decode_receipt(&bytes), Ok(Receipt::Call(receipt)));
+    }
+
+    #[test]
+    fn receipt_metrics_of_spawn_and_nested_call() {
+        let mut call = call_receipt(5);
+        call.set_metrics(vec![ReceiptMetric::new(2, 20)]);
+
+        let mut receipt = SpawnReceipt {
+            version: 7,
+            success: true,
+            error: None,
+            account_addr: Some(Address::of("@Account")),
+            init_state: Some(State::of("some-state")),
+            returndata: Some(Vec::new()),
+            gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 1,
+            logs_bloom: None,
+            logs: Vec::new(),
+            metrics: Vec::new(),
+            call: Some(call),
+        };
+        receipt.set_metrics(vec![ReceiptMetric::new(1, 10)]);
+
+        let bytes = encode_spawn(&receipt);
+
+        assert_eq!(try_decode_receipt(&bytes), Ok(Receipt::Spawn(receipt)));
+    }
+
+    #[test]
+    fn receipt_metrics_malformed() {
+        let plain = encode_call(&call_receipt(5));
+
+        // #metrics = 2, but a single metric follows
+        let mut fields = TaggedFields::new();
+        fields.insert(tags::METRICS, vec![2, 1, 10]);
+
+        let partial = decode_receipt_partial(&with_fields(plain.clone(), &fields));
+
+        assert_eq!(partial.offset, plain.len() - 1);
+        assert_eq!(
+            partial.error,
+            Some(ParseError::NotEnoughBytes(Field::MetricId))
+        );
+
+        // Trailing bytes following the metrics
+        let mut fields = TaggedFields::new();
+        fields.insert(tags::METRICS, vec![1, 1, 10, 0xFF]);
+
+        let partial = decode_receipt_partial(&with_fields(plain, &fields));
+
+        assert_eq!(
+            partial.error,
+            Some(ParseError::TooManyBytes(Field::MetricValue))
+        );
+    }
+}
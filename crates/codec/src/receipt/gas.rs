@@ -1,6 +1,6 @@
 use std::io::Cursor;
 
-use svm_types::Gas;
+use svm_types::{Gas, GasBreakdown};
 
 use crate::{Field, ParseError, ReadExt, WriteExt};
 
@@ -16,3 +16,92 @@ pub fn decode_gas_used(cursor: &mut Cursor<&[u8]>) -> Result<Gas, ParseError> {
         Err(..) => Err(ParseError::NotEnoughBytes(Field::GasUsed)),
     }
 }
+
+/// Encodes the version of the gas schedule (recorded by the receipts format version `2` onwards,
+/// right after the `is_success` flag).
+pub fn encode_gas_schedule_version(version: u16, gas_schedule_version: u16, w: &mut Vec<u8>) {
+    if version >= 2 {
+        w.write_u16_be(gas_schedule_version);
+    } else {
+        debug_assert_eq!(gas_schedule_version, 0);
+    }
+}
+
+/// Decodes the version of the gas schedule (receipts of format versions `0` and `1`
+/// have been priced by the initial gas schedule, i.e version `0`).
+pub fn decode_gas_schedule_version(
+    version: u16,
+    cursor: &mut Cursor<&[u8]>,
+) -> Result<u16, ParseError> {
+    if version < 2 {
+        return Ok(0);
+    }
+
+    cursor
+        .read_u16_be()
+        .map_err(|_| ParseError::NotEnoughBytes(Field::GasScheduleVersion))
+}
+
+/// Encodes the `gas_used` breakdown (expected by the receipts format version `1`).
+///
+/// ```text
+///  +-------------------------------------------------------------+
+///  |           |           |           |           |             |
+///  | intrinsic | execution |  storage  |   logs    |   refund    |
+///  | (8 bytes) | (8 bytes) | (8 bytes) | (8 bytes) |  (8 bytes)  |
+///  |           |           |           |           |             |
+///  +-------------------------------------------------------------+
+/// ```
+///
+/// From the receipts format version `2` onwards the breakdown is optional,
+/// and it's prefixed with a `has breakdown` flag (1 byte).
+pub fn encode_gas_breakdown(version: u16, breakdown: Option<&GasBreakdown>, w: &mut Vec<u8>) {
+    match version {
+        0 => debug_assert!(breakdown.is_none()),
+        1 => debug_assert!(breakdown.is_some()),
+        _ => w.write_bool(breakdown.is_some()),
+    }
+
+    if let Some(breakdown) = breakdown {
+        w.write_u64_be(breakdown.intrinsic);
+        w.write_u64_be(breakdown.execution);
+        w.write_u64_be(breakdown.storage);
+        w.write_u64_be(breakdown.logs);
+        w.write_u64_be(breakdown.refund);
+    }
+}
+
+pub fn decode_gas_breakdown(
+    version: u16,
+    cursor: &mut Cursor<&[u8]>,
+) -> Result<Option<GasBreakdown>, ParseError> {
+    if version == 0 {
+        return Ok(None);
+    }
+
+    if version >= 2 {
+        let has_breakdown = cursor
+            .read_bool()
+            .map_err(|_| ParseError::NotEnoughBytes(Field::GasBreakdown))?;
+
+        if !has_breakdown {
+            return Ok(None);
+        }
+    }
+
+    let mut read = || {
+        cursor
+            .read_u64_be()
+            .map_err(|_| ParseError::NotEnoughBytes(Field::GasBreakdown))
+    };
+
+    let breakdown = GasBreakdown {
+        intrinsic: read()?,
+        execution: read()?,
+        storage: read()?,
+        logs: read()?,
+        refund: read()?,
+    };
+
+    Ok(Some(breakdown))
+}
@@ -1,10 +1,17 @@
 use std::io::Cursor;
 
-use svm_types::ReceiptLog;
+use svm_types::{LogTopic, ReceiptLog, EVENT_LOGS_VERSION, MAX_LOG_TOPICS};
 
+use super::{decode_field, read_field};
 use crate::{Field, ParseError, ReadExt, WriteExt};
 
-/// ```text                   
+/// The first receipts format version encoding the logs lengths as variable-length integers.
+pub const UVARINT_LENGTH_VERSION: u16 = 4;
+
+/// Since receipts format version [`UVARINT_LENGTH_VERSION`], `#logs` and the `data length`
+/// fields are variable-length integers (LEB128) instead of fixed-width ones.
+///
+/// ```text
 /// +----------------+
 /// | #logs (1 byte) |
 /// +------------------------+----------------------+
@@ -17,68 +24,186 @@ use crate::{Field, ParseError, ReadExt, WriteExt};
 /// |  data length (2 bytes) | data (blob of bytes) |  ---> log #N
 /// +-----------------------------------------------+
 /// ```
-pub fn encode_logs(logs: &[ReceiptLog], w: &mut Vec<u8>) {
+///
+/// Since receipts format version [`EVENT_LOGS_VERSION`], each log starts with its event code
+/// (a variable-length integer holding the code plus one, or zero for a raw log).
+/// An event log continues with its indexed topics, preceding the `data length`:
+///
+/// ```text
+/// +--------------------+------------------+---------------------+----------+-------------+
+/// | event code + 1     | #topics (1 byte) | topic #1 (32 bytes) |  . . .   | topic #N    |
+/// | (variable-length)  |                  |                     |          | (32 bytes)  |
+/// +--------------------+------------------+---------------------+----------+-------------+
+/// ```
+///
+/// # Panics
+///
+/// Panics if an event log is encoded using a version preceding [`EVENT_LOGS_VERSION`].
+pub fn encode_logs(version: u16, logs: &[ReceiptLog], w: &mut Vec<u8>) {
+    let uvarint = version >= UVARINT_LENGTH_VERSION;
+    let events = version >= EVENT_LOGS_VERSION;
     let nlogs = logs.len();
-    assert!(nlogs <= std::u8::MAX as usize);
 
-    w.write_byte(nlogs as u8);
+    if uvarint {
+        w.write_uvarint(nlogs as u64);
+    } else {
+        assert!(nlogs <= std::u8::MAX as usize);
+
+        w.write_byte(nlogs as u8);
+    }
 
     for log in logs.iter() {
-        let len = log.as_bytes().len();
+        if events {
+            encode_event(log, w);
+        } else {
+            assert!(!log.is_event());
+        }
 
-        assert!(len <= std::u16::MAX as usize);
+        let len = log.as_bytes().len();
 
         // `data` length
-        w.write_u16_be(len as u16);
+        if uvarint {
+            w.write_uvarint(len as u64);
+        } else {
+            assert!(len <= std::u16::MAX as usize);
+
+            w.write_u16_be(len as u16);
+        }
 
         // `data` blob
         w.write_bytes(log.as_bytes());
     }
 }
 
-pub fn decode_logs(cursor: &mut Cursor<&[u8]>) -> Result<Vec<ReceiptLog>, ParseError> {
-    match cursor.read_byte() {
-        Ok(nlogs) => {
-            let mut logs = Vec::with_capacity(nlogs as usize);
+fn encode_event(log: &ReceiptLog, w: &mut Vec<u8>) {
+    match log.code() {
+        Some(code) => {
+            w.write_uvarint(code as u64 + 1);
 
-            for _ in 0..nlogs {
-                let log = decode_log(cursor)?;
-                logs.push(log);
+            let topics = log.topics();
+            w.write_byte(topics.len() as u8);
+
+            for topic in topics {
+                w.write_bytes(topic);
             }
+        }
+        None => w.write_uvarint(0),
+    }
+}
 
-            Ok(logs)
+/// Decodes the logs into `logs`, one by one.
+///
+/// On failure, `logs` holds the logs preceding the one that couldn't be decoded
+/// (and `cursor` is positioned at its start).
+pub fn decode_logs_into(
+    version: u16,
+    cursor: &mut Cursor<&[u8]>,
+    logs: &mut Vec<ReceiptLog>,
+) -> Result<(), ParseError> {
+    let uvarint = version >= UVARINT_LENGTH_VERSION;
+
+    let nlogs = read_field(cursor, Field::LogsCount, |cursor| {
+        if uvarint {
+            cursor.read_uvarint()
+        } else {
+            cursor.read_byte().map(u64::from)
         }
-        Err(..) => Err(ParseError::NotEnoughBytes(Field::LogsCount)),
+    })?;
+
+    // Each log takes at least one byte, so a bogus `#logs` can't make us over-allocate.
+    let remaining = (cursor.get_ref().len() as u64).saturating_sub(cursor.position());
+    logs.reserve(nlogs.min(remaining) as usize);
+
+    for _ in 0..nlogs {
+        let log = decode_field(cursor, |cursor| decode_log(version, cursor))?;
+        logs.push(log);
     }
+
+    Ok(())
 }
 
-fn decode_log(cursor: &mut Cursor<&[u8]>) -> Result<ReceiptLog, ParseError> {
-    match cursor.read_u16_be() {
+fn decode_log(version: u16, cursor: &mut Cursor<&[u8]>) -> Result<ReceiptLog, ParseError> {
+    let event = if version >= EVENT_LOGS_VERSION {
+        decode_event(cursor)?
+    } else {
+        None
+    };
+
+    let length = if version >= UVARINT_LENGTH_VERSION {
+        cursor.read_uvarint()
+    } else {
+        cursor.read_u16_be().map(u64::from)
+    };
+
+    match length {
         Ok(length) => {
             let data = cursor.read_bytes(length as usize);
             if data.is_err() {
                 return Err(ParseError::NotEnoughBytes(Field::LogData));
             };
 
-            let log = ReceiptLog::new(data.unwrap());
+            let log = match event {
+                Some((code, topics)) => ReceiptLog::event(code, topics, data.unwrap()),
+                None => ReceiptLog::new(data.unwrap()),
+            };
             Ok(log)
         }
         Err(..) => Err(ParseError::NotEnoughBytes(Field::LogDataLength)),
     }
 }
 
+/// Decodes the event code and topics of a log (`None` for a raw log).
+fn decode_event(cursor: &mut Cursor<&[u8]>) -> Result<Option<(u32, Vec<LogTopic>)>, ParseError> {
+    let code = read_field(cursor, Field::LogCode, |cursor| cursor.read_uvarint())?;
+
+    if code == 0 {
+        return Ok(None);
+    }
+    if code > std::u32::MAX as u64 + 1 {
+        return Err(ParseError::TooManyBytes(Field::LogCode));
+    }
+
+    let ntopics = read_field(cursor, Field::LogTopicsCount, |cursor| cursor.read_byte())?;
+    if ntopics as usize > MAX_LOG_TOPICS {
+        return Err(ParseError::TooManyBytes(Field::LogTopicsCount));
+    }
+
+    let mut topics = Vec::with_capacity(ntopics as usize);
+
+    for _ in 0..ntopics {
+        let bytes = read_field(cursor, Field::LogTopic, |cursor| cursor.read_bytes(32))?;
+
+        let mut topic = [0; 32];
+        topic.copy_from_slice(&bytes);
+        topics.push(topic);
+    }
+
+    Ok(Some(((code - 1) as u32, topics)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn decode_logs(
+        version: u16,
+        cursor: &mut Cursor<&[u8]>,
+    ) -> Result<Vec<ReceiptLog>, ParseError> {
+        let mut logs = Vec::new();
+
+        decode_logs_into(version, cursor, &mut logs)?;
+
+        Ok(logs)
+    }
+
     #[test]
     fn encode_logs_empty() {
         let mut buf = Vec::new();
 
-        encode_logs(&[], &mut buf);
+        encode_logs(0, &[], &mut buf);
 
         let mut cursor = Cursor::new(&buf[..]);
-        let logs = decode_logs(&mut cursor).unwrap();
+        let logs = decode_logs(0, &mut cursor).unwrap();
 
         assert!(logs.is_empty());
     }
@@ -88,10 +213,10 @@ mod tests {
         let mut buf = Vec::new();
 
         let log = ReceiptLog::new(b"been here".to_vec());
-        encode_logs(&[log.clone()], &mut buf);
+        encode_logs(0, &[log.clone()], &mut buf);
 
         let mut cursor = Cursor::new(&buf[..]);
-        let logs = decode_logs(&mut cursor).unwrap();
+        let logs = decode_logs(0, &mut cursor).unwrap();
 
         assert_eq!(logs, vec![log]);
     }
@@ -103,11 +228,69 @@ mod tests {
         let log1 = ReceiptLog::new(b"been here".to_vec());
         let log2 = ReceiptLog::new(b"been there".to_vec());
 
-        encode_logs(&[log1.clone(), log2.clone()], &mut buf);
+        encode_logs(0, &[log1.clone(), log2.clone()], &mut buf);
 
         let mut cursor = Cursor::new(&buf[..]);
-        let logs = decode_logs(&mut cursor).unwrap();
+        let logs = decode_logs(0, &mut cursor).unwrap();
 
         assert_eq!(logs, vec![log1, log2]);
     }
+
+    #[test]
+    fn encode_logs_uvarint_lengths() {
+        let log1 = ReceiptLog::new(b"been here".to_vec());
+        let log2 = ReceiptLog::new(vec![0xAB; 300]);
+        let logs = vec![log1, log2];
+
+        let mut buf = Vec::new();
+        encode_logs(UVARINT_LENGTH_VERSION, &logs, &mut buf);
+
+        // 1-byte `#logs`, then 1 and 2 bytes long `data length` fields.
+        assert_eq!(buf.len(), 1 + (1 + 9) + (2 + 300));
+
+        let mut cursor = Cursor::new(&buf[..]);
+        let decoded = decode_logs(UVARINT_LENGTH_VERSION, &mut cursor).unwrap();
+
+        assert_eq!(decoded, logs);
+    }
+
+    #[test]
+    fn encode_logs_events() {
+        let raw = ReceiptLog::new(b"been here".to_vec());
+        let event = ReceiptLog::event(7, vec![[0xAA; 32], [0xBB; 32]], b"payload".to_vec());
+        let logs = vec![raw, event];
+
+        let mut buf = Vec::new();
+        encode_logs(EVENT_LOGS_VERSION, &logs, &mut buf);
+
+        // `#logs`, then the raw log (a zero code) and the event log (its code and 2 topics)
+        assert_eq!(buf.len(), 1 + (1 + 1 + 9) + (1 + 1 + 2 * 32 + 1 + 7));
+
+        let mut cursor = Cursor::new(&buf[..]);
+        let decoded = decode_logs(EVENT_LOGS_VERSION, &mut cursor).unwrap();
+
+        assert_eq!(decoded, logs);
+    }
+
+    #[test]
+    fn decode_logs_too_many_topics() {
+        let mut buf = Vec::new();
+
+        buf.write_uvarint(1);
+        buf.write_uvarint(1);
+        buf.write_byte(MAX_LOG_TOPICS as u8 + 1);
+
+        let mut cursor = Cursor::new(&buf[..]);
+        let err = decode_logs(EVENT_LOGS_VERSION, &mut cursor).unwrap_err();
+
+        assert_eq!(err, ParseError::TooManyBytes(Field::LogTopicsCount));
+    }
+
+    #[test]
+    #[should_panic]
+    fn encode_logs_event_requires_version() {
+        let event = ReceiptLog::event(7, Vec::new(), Vec::new());
+
+        encode_logs(UVARINT_LENGTH_VERSION, &[event], &mut Vec::new());
+    }
 }
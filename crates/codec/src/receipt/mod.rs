@@ -1,45 +1,94 @@
+mod batch;
+mod bloom;
 mod call;
 mod deploy;
 mod error;
+mod fields;
 mod gas;
+mod partial;
 mod returndata;
 mod spawn;
+mod upgrade;
 
 pub(crate) mod logs;
 
 pub(crate) use error::{decode_error, encode_error};
 
+pub use batch::{decode_batch, encode_batch};
 pub use call::{decode_call, encode_call};
 pub use deploy::{decode_deploy, encode_deploy};
-pub use spawn::{decode_spawn, encode_spawn};
+pub use partial::{decode_receipt_partial, PartialReceipt};
+pub use spawn::{decode_spawn, encode_spawn, CALL_RECEIPT_VERSION};
+pub use upgrade::{decode_upgrade, encode_upgrade};
+
+use std::io::Cursor;
 
 use svm_types::Receipt;
 
+use crate::{Field, ParseError};
+
 mod types {
     pub const DEPLOY: u8 = 0;
     pub const SPAWN: u8 = 1;
     pub const CALL: u8 = 2;
+    pub const UPGRADE: u8 = 3;
+    pub const BATCH: u8 = 4;
 }
 
+/// The latest supported receipts format version.
+const MAX_VERSION: u16 = 7;
+
 /// Decodes a binary Receipt into its Rust struct wrapped as `ReceiptOwned`
+///
+/// # Panics
+///
+/// Panics if `bytes` isn't a valid binary Receipt (see [`try_decode_receipt`]).
 pub fn decode_receipt(bytes: &[u8]) -> Receipt {
-    assert!(bytes.len() > 0);
-
-    let ty = bytes[0];
-
-    match ty {
-        types::DEPLOY => {
-            let receipt = decode_deploy(bytes);
-            Receipt::Deploy(receipt)
-        }
-        types::SPAWN => {
-            let receipt = decode_spawn(bytes);
-            Receipt::Spawn(receipt)
-        }
-        types::CALL => {
-            let receipt = decode_call(bytes);
-            Receipt::Call(receipt)
-        }
-        _ => unreachable!(),
+    try_decode_receipt(bytes).unwrap()
+}
+
+/// Decodes a binary Receipt, failing on the first field that can't be decoded.
+///
+/// In order to recover the fields preceding that field, see [`decode_receipt_partial`].
+pub fn try_decode_receipt(bytes: &[u8]) -> Result<Receipt, ParseError> {
+    decode_receipt_partial(bytes).into_result()
+}
+
+/// The name of the structure of `receipt` (e.g `CallReceipt`), as used within the paths of fields.
+pub(crate) fn structure(receipt: &Receipt) -> &'static str {
+    match receipt {
+        Receipt::Deploy(..) => "DeployReceipt",
+        Receipt::Spawn(..) => "SpawnReceipt",
+        Receipt::Call(..) => "CallReceipt",
+        Receipt::Upgrade(..) => "UpgradeReceipt",
+        Receipt::Batch(..) => "BatchReceipt",
     }
 }
+
+/// Decodes a single field, rewinding `cursor` to the field start on failure
+/// (so that the position of `cursor` tells where decoding has stopped).
+pub(crate) fn decode_field<T, F>(cursor: &mut Cursor<&[u8]>, decode: F) -> Result<T, ParseError>
+where
+    F: FnOnce(&mut Cursor<&[u8]>) -> Result<T, ParseError>,
+{
+    let start = cursor.position();
+
+    decode(cursor).map_err(|err| {
+        cursor.set_position(start);
+        err
+    })
+}
+
+/// Same as [`decode_field`], for readers failing on a lack of bytes only.
+pub(crate) fn read_field<T, F>(
+    cursor: &mut Cursor<&[u8]>,
+    field: Field,
+    read: F,
+) -> Result<T, ParseError>
+where
+    F: FnOnce(&mut Cursor<&[u8]>) -> std::io::Result<T>,
+{
+    decode_field(cursor, |cursor| {
+        read(cursor).map_err(|_| ParseError::NotEnoughBytes(field))
+    })
+}
@@ -0,0 +1,388 @@
+//! Tolerant decoding of binary Receipts.
+//!
+//! Truncated or corrupted binary Receipts can't be decoded as a whole, but the fields
+//! preceding the corruption are still meaningful (e.g. to a blocks explorer).
+//! [`decode_receipt_partial`] recovers these, along with the reason decoding has stopped.
+//!
+//! The header of a Receipt (its `tx type`, `version`, `is_success` and the gas schedule version
+//! of the format version `2`) must be decoded
+//! for any other field to be recovered.
+
+use std::io::Cursor;
+
+use svm_types::{
+    BatchReceipt, CallReceipt, DeployReceipt, Gas, Receipt, SpawnReceipt, UpgradeReceipt,
+};
+
+use super::batch::decode_batch_body;
+use super::call::decode_call_body;
+use super::deploy::decode_deploy_body;
+use super::spawn::decode_spawn_body;
+use super::upgrade::decode_upgrade_body;
+use super::{decode_field, fields, gas, read_field, types, MAX_VERSION};
+use crate::{version, Field, ParseError, ReadExt};
+
+/// A binary Receipt decoded as far as possible (see [`decode_receipt_partial`]).
+#[derive(Debug, PartialEq)]
+pub struct PartialReceipt {
+    /// The decoded [`Receipt`], `None` if its header couldn't be decoded.
+    ///
+    /// The fields following the one decoding has stopped at are left unset
+    /// (i.e `None`, empty or [`Gas::new`]).
+    pub receipt: Option<Receipt>,
+
+    /// The reason decoding has stopped, `None` if the whole Receipt has been decoded.
+    pub error: Option<ParseError>,
+
+    /// The offset of the field decoding has stopped at
+    /// (the number of decoded bytes, if the whole Receipt has been decoded).
+    pub offset: usize,
+}
+
+impl PartialReceipt {
+    /// Returns whether the whole Receipt has been decoded.
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Returns the decoded [`Receipt`], or the reason decoding has stopped.
+    pub fn into_result(self) -> Result<Receipt, ParseError> {
+        match self.error {
+            None => Ok(self.receipt.unwrap()),
+            Some(err) => Err(err),
+        }
+    }
+}
+
+/// Decodes a binary Receipt, recovering the fields preceding the first one
+/// that can't be decoded.
+pub fn decode_receipt_partial(bytes: &[u8]) -> PartialReceipt {
+    let mut cursor = Cursor::new(bytes);
+
+    let (receipt, result) = match decode_header(&mut cursor) {
+        Ok(header) => {
+            let version = header.version;
+            let (mut receipt, result) = decode_body(&mut cursor, header);
+
+            let result = result.and_then(|()| {
+                decode_field(&mut cursor, |cursor| {
+                    let fields = fields::decode_tagged_fields(version, cursor)?;
+
+                    fields::decode_known_fields(&fields, &mut receipt)
+                })
+            });
+
+            (Some(receipt), result)
+        }
+        Err(err) => (None, Err(err)),
+    };
+
+    PartialReceipt {
+        receipt,
+        error: result.err(),
+        offset: cursor.position() as usize,
+    }
+}
+
+struct Header {
+    ty: u8,
+    version: u16,
+    success: bool,
+    gas_schedule_version: u16,
+}
+
+fn decode_header(cursor: &mut Cursor<&[u8]>) -> Result<Header, ParseError> {
+    let ty = decode_field(cursor, |cursor| {
+        let ty = cursor
+            .read_byte()
+            .map_err(|_| ParseError::NotEnoughBytes(Field::ReceiptType))?;
+
+        match ty {
+            types::DEPLOY | types::SPAWN | types::CALL | types::UPGRADE | types::BATCH => Ok(ty),
+            _ => Err(ParseError::NotSupported(Field::ReceiptType)),
+        }
+    })?;
+
+    let version = decode_field(cursor, |cursor| {
+        let version = version::decode_version(cursor)?;
+
+        if version > MAX_VERSION {
+            return Err(ParseError::NotSupported(Field::Version));
+        }
+
+        Ok(version)
+    })?;
+
+    let success = read_field(cursor, Field::ReceiptStatus, |cursor| cursor.read_bool())?;
+
+    let gas_schedule_version = decode_field(cursor, |cursor| {
+        gas::decode_gas_schedule_version(version, cursor)
+    })?;
+
+    Ok(Header {
+        ty,
+        version,
+        success,
+        gas_schedule_version,
+    })
+}
+
+fn decode_body(cursor: &mut Cursor<&[u8]>, header: Header) -> (Receipt, Result<(), ParseError>) {
+    let Header {
+        ty,
+        version,
+        success,
+        gas_schedule_version,
+    } = header;
+
+    match ty {
+        types::DEPLOY => {
+            let mut receipt = DeployReceipt {
+                version,
+                success,
+                error: None,
+                addr: None,
+                gas_used: Gas::new(),
+                gas_breakdown: None,
+                gas_schedule_version,
+                logs: Vec::new(),
+            };
+            let result = decode_deploy_body(cursor, &mut receipt);
+
+            (Receipt::Deploy(receipt), result)
+        }
+        types::SPAWN => {
+            let mut receipt = SpawnReceipt {
+                version,
+                success,
+                error: None,
+                account_addr: None,
+                init_state: None,
+                returndata: None,
+                gas_used: Gas::new(),
+                gas_breakdown: None,
+                gas_schedule_version,
+                logs_bloom: None,
+                logs: Vec::new(),
+                metrics: Vec::new(),
+                call: None,
+            };
+            let result = decode_spawn_body(cursor, &mut receipt);
+
+            (Receipt::Spawn(receipt), result)
+        }
+        types::CALL => {
+            let mut receipt = CallReceipt {
+                version,
+                success,
+                error: None,
+                new_state: None,
+                returndata: None,
+                gas_used: Gas::new(),
+                gas_breakdown: None,
+                gas_schedule_version,
+                logs_bloom: None,
+                logs: Vec::new(),
+                metrics: Vec::new(),
+            };
+            let result = decode_call_body(cursor, &mut receipt);
+
+            (Receipt::Call(receipt), result)
+        }
+        types::UPGRADE => {
+            let mut receipt = UpgradeReceipt {
+                version,
+                success,
+                error: None,
+                addr: None,
+                template_version: 0,
+                gas_used: Gas::new(),
+                gas_breakdown: None,
+                gas_schedule_version,
+                logs: Vec::new(),
+            };
+            let result = decode_upgrade_body(cursor, &mut receipt);
+
+            (Receipt::Upgrade(receipt), result)
+        }
+        types::BATCH => {
+            let mut receipt = BatchReceipt {
+                version,
+                success,
+                error: None,
+                new_state: None,
+                receipts: Vec::new(),
+                gas_used: Gas::new(),
+                gas_breakdown: None,
+                gas_schedule_version,
+                logs: Vec::new(),
+            };
+            let result = decode_batch_body(cursor, &mut receipt);
+
+            (Receipt::Batch(receipt), result)
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_types::{ReceiptLog, RuntimeError, State, TemplateAddr};
+
+    use crate::receipt::{encode_call, encode_spawn, try_decode_receipt};
+
+    fn call_receipt() -> CallReceipt {
+        CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            new_state: Some(State::of("some-state")),
+            returndata: Some(vec![0x10, 0x20]),
+            gas_used: Gas::with(100),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: vec![
+                ReceiptLog::new(b"Log entry #1".to_vec()),
+                ReceiptLog::new(b"Log entry #2".to_vec()),
+            ],
+            metrics: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn decode_receipt_partial_complete() {
+        let receipt = call_receipt();
+        let bytes = encode_call(&receipt);
+
+        let partial = decode_receipt_partial(&bytes);
+
+        assert!(partial.is_complete());
+        assert_eq!(partial.offset, bytes.len());
+        assert_eq!(partial.into_result(), Ok(Receipt::Call(receipt)));
+    }
+
+    #[test]
+    fn decode_receipt_partial_truncated_header() {
+        let bytes = encode_call(&call_receipt());
+
+        let partial = decode_receipt_partial(&bytes[..2]);
+
+        assert_eq!(partial.receipt, None);
+        assert_eq!(
+            partial.error,
+            Some(ParseError::NotEnoughBytes(Field::Version))
+        );
+        assert_eq!(partial.offset, 1);
+    }
+
+    #[test]
+    fn decode_receipt_partial_unknown_type() {
+        let partial = decode_receipt_partial(&[0xFF, 0x00, 0x00, 0x01]);
+
+        assert_eq!(partial.receipt, None);
+        assert_eq!(
+            partial.error,
+            Some(ParseError::NotSupported(Field::ReceiptType))
+        );
+        assert_eq!(partial.offset, 0);
+    }
+
+    #[test]
+    fn decode_receipt_partial_truncated_log() {
+        let receipt = call_receipt();
+        let bytes = encode_call(&receipt);
+
+        // chopping off the last byte of the second log
+        let partial = decode_receipt_partial(&bytes[..bytes.len() - 1]);
+
+        let log_len = 2 + b"Log entry #2".len();
+        assert_eq!(partial.offset, bytes.len() - log_len);
+        assert_eq!(
+            partial.error,
+            Some(ParseError::NotEnoughBytes(Field::LogData))
+        );
+
+        let decoded = partial.receipt.unwrap().into_call();
+        assert_eq!(decoded.new_state, receipt.new_state);
+        assert_eq!(decoded.returndata, receipt.returndata);
+        assert_eq!(decoded.gas_used, receipt.gas_used);
+        assert_eq!(decoded.logs, receipt.logs[..1].to_vec());
+
+        let err = try_decode_receipt(&bytes[..bytes.len() - 1]).unwrap_err();
+        assert_eq!(err, ParseError::NotEnoughBytes(Field::LogData));
+    }
+
+    #[test]
+    fn decode_receipt_partial_truncated_error() {
+        let receipt = CallReceipt::from_err(
+            RuntimeError::TemplateNotFound(TemplateAddr::of("@Template")),
+            vec![ReceiptLog::new(b"something happened".to_vec())],
+        );
+        let bytes = encode_call(&receipt);
+
+        let partial = decode_receipt_partial(&bytes[..bytes.len() - 5]);
+
+        assert_eq!(partial.offset, bytes.len() - TemplateAddr::len());
+        assert_eq!(
+            partial.error,
+            Some(ParseError::NotEnoughBytes(Field::TemplateAddr))
+        );
+
+        let decoded = partial.receipt.unwrap().into_call();
+        assert!(!decoded.success);
+        assert_eq!(decoded.error, None);
+        assert_eq!(decoded.logs, receipt.logs);
+    }
+
+    #[test]
+    fn decode_receipt_partial_truncated_spawn_call() {
+        let call = call_receipt();
+        let receipt = SpawnReceipt {
+            version: 7,
+            success: true,
+            error: None,
+            account_addr: Some(svm_types::Address::repeat(0x10)),
+            init_state: Some(State::repeat(0xA0)),
+            returndata: Some(Vec::new()),
+            gas_used: Gas::with(10),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: Vec::new(),
+            metrics: Vec::new(),
+            call: Some(call.clone()),
+        };
+        let mut bytes = encode_spawn(&receipt);
+
+        // shrinking the `call receipt` byte-size so that its last log is truncated
+        let call_bytes = encode_call(&call);
+        let call_len = call_bytes.len();
+        let call_start = bytes
+            .windows(call_len)
+            .position(|window| window == &call_bytes[..])
+            .unwrap();
+        let len_offset = call_start - 4;
+        let truncated = (call_len as u32 - 1).to_be_bytes();
+        bytes[len_offset..len_offset + 4].copy_from_slice(&truncated);
+        bytes.remove(call_start + call_len - 1);
+
+        let partial = decode_receipt_partial(&bytes);
+
+        let log_len = 2 + b"Log entry #2".len();
+        assert_eq!(partial.offset, call_start + call_len - log_len);
+        assert_eq!(
+            partial.error,
+            Some(ParseError::NotEnoughBytes(Field::LogData))
+        );
+
+        let decoded = partial.receipt.unwrap().into_spawn();
+        assert_eq!(decoded.account_addr, receipt.account_addr);
+
+        let decoded_call = decoded.call.unwrap();
+        assert_eq!(decoded_call.new_state, call.new_state);
+        assert_eq!(decoded_call.logs, call.logs[..1].to_vec());
+    }
+}
@@ -1,4 +1,4 @@
-//!  ## `Spawn Account` Receipt Binary Format Version 0
+//!  ## `Spawn Account` Receipt Binary Format Versions 0 to 7
 //!
 //!  On success (`is_success = 1`)
 //!
@@ -20,79 +20,141 @@
 //!  | (1 byte)  |  (Blob)  |         |       (Blob)           |
 //!  |           |          |         |                        |
 //!  +---------------------------------------------------------+
+//!  |           |                |                            |
+//!  | has call  |  call receipt  |       call receipt         |
+//!  | (1 byte)  |   byte-size    |          (Blob)            |
+//!  |           |   (4 bytes)    |        (optional)          |
+//!  |           |   (optional)   |                            |
+//!  +---------------------------------------------------------+
 //!  ```
 //!
+//!  The `has call` (`0` or `1`) and the trailing `call receipt` exist only from version `7` onwards
+//!  (see [`CALL_RECEIPT_VERSION`]). The `call receipt` is the binary `Call Receipt` of the function
+//!  called right after the `ctor` (see [`svm_types::SpawnCall`]).
+//!
+//!  Version `1` appends the `gas_used` breakdown by phase
+//!  (following the `call receipt`, see [gas.rs](./gas.rs)).
+//!
+//!  Version `2` records the gas schedule version (2 bytes) right after `is_success`
+//!  (for failed receipts too), and makes the `gas_used` breakdown optional.
+//!
+//!  Version `3` appends the optional bloom filter of the logs
+//!  (following the `gas_used` breakdown, see [bloom.rs](./bloom.rs)).
+//!
+//!  Version `4` encodes `#logs` and the logs `data length` as variable-length integers
+//!  (see [logs.rs](./logs.rs)).
+//!
+//!  Version `5` ends the receipt (on success and on error alike) with a `Tagged Fields` block
+//!  (see [fields.rs](./fields.rs)), carrying the reported metrics (if any).
+//!
+//!  Version `6` prefixes each log with its event code, followed by the indexed topics
+//!  of event logs (see [logs.rs](./logs.rs)).
+//!
+//!  Version `7` inserts the `has call` (followed by the optional `call receipt`) right after the logs.
+//!
 //!
 //!  On Error (`is_success = 0`)
 //!  See [error.rs][./error.rs]
 
-use svm_types::SpawnReceipt;
+use svm_types::{CallReceipt, Receipt, SpawnReceipt};
 
 use std::io::Cursor;
 
-use super::{decode_error, encode_error, gas, logs, returndata, types};
+use super::encode_call;
+use super::{
+    bloom, decode_error, decode_field, encode_error, fields, gas, logs, read_field, returndata,
+    types,
+};
 use crate::version;
-use crate::{ReadExt, WriteExt};
+use crate::{Field, ParseError, ReadExt, WriteExt};
+
+/// The receipts format version from which a [`SpawnReceipt`] may carry the [`CallReceipt`] of its post-`ctor` call.
+pub const CALL_RECEIPT_VERSION: u16 = 7;
 
 /// Encodes a [`SpawnReceipt`] into its binary format.
+///
+/// # Panics
+///
+/// Panics if `receipt` carries a [`CallReceipt`] while its version precedes [`CALL_RECEIPT_VERSION`]
+/// (see [`SpawnReceipt::set_call`]).
 pub fn encode_spawn(receipt: &SpawnReceipt) -> Vec<u8> {
     let mut w = Vec::new();
 
     w.write_byte(types::SPAWN);
     encode_version(receipt, &mut w);
     w.write_bool(receipt.success);
+    gas::encode_gas_schedule_version(receipt.version, receipt.gas_schedule_version, &mut w);
 
     if receipt.success {
         encode_account_addr(receipt, &mut w);
         encode_init_state(receipt, &mut w);
         encode_returndata(&receipt, &mut w);
         gas::encode_gas_used(&receipt.gas_used, &mut w);
-        logs::encode_logs(&receipt.logs, &mut w);
+        logs::encode_logs(receipt.version, &receipt.logs, &mut w);
+        encode_call_receipt(receipt, &mut w);
+        gas::encode_gas_breakdown(receipt.version, receipt.gas_breakdown.as_ref(), &mut w);
+        bloom::encode_logs_bloom(receipt.version, receipt.logs_bloom.as_ref(), &mut w);
     } else {
         let logs = receipt.logs();
 
-        encode_error(receipt.error(), logs, &mut w);
+        encode_error(receipt.version, receipt.error(), logs, &mut w);
     };
 
+    fields::encode_tagged_fields(receipt.version, &receipt.metrics, &mut w);
+
     w
 }
 
 /// Decodes a binary [`SpawnReceipt`].
+///
+/// # Panics
+///
+/// Panics if `bytes` isn't a valid binary [`SpawnReceipt`] (see [`super::try_decode_receipt`]).
 pub fn decode_spawn(bytes: &[u8]) -> SpawnReceipt {
-    let mut cursor = Cursor::new(bytes);
+    let receipt = super::decode_receipt(bytes);
+    debug_assert!(matches!(receipt, Receipt::Spawn(..)));
+
+    receipt.into_spawn()
+}
 
-    let ty = cursor.read_byte().unwrap();
-    debug_assert_eq!(ty, types::SPAWN);
+/// Decodes into `receipt` the fields following the header of a binary [`SpawnReceipt`].
+///
+/// On failure, `receipt` holds the fields decoded so far.
+pub(crate) fn decode_spawn_body(
+    cursor: &mut Cursor<&[u8]>,
+    receipt: &mut SpawnReceipt,
+) -> Result<(), ParseError> {
+    if !receipt.success {
+        return decode_error(
+            receipt.version,
+            cursor,
+            &mut receipt.error,
+            &mut receipt.logs,
+        );
+    }
 
-    let version = version::decode_version(&mut cursor).unwrap();
-    debug_assert_eq!(0, version);
+    let addr = read_field(cursor, Field::Address, |cursor| cursor.read_address())?;
+    receipt.account_addr = Some(addr);
 
-    let is_success = cursor.read_bool().unwrap();
+    let init_state = read_field(cursor, Field::State, |cursor| cursor.read_state())?;
+    receipt.init_state = Some(init_state);
 
-    match is_success {
-        false => {
-            let (err, logs) = decode_error(&mut cursor);
-            SpawnReceipt::from_err(err, logs)
-        }
-        true => {
-            let addr = cursor.read_address().unwrap();
-            let init_state = cursor.read_state().unwrap();
-            let returndata = returndata::decode(&mut cursor).unwrap();
-            let gas_used = gas::decode_gas_used(&mut cursor).unwrap();
-            let logs = logs::decode_logs(&mut cursor).unwrap();
-
-            SpawnReceipt {
-                version,
-                success: true,
-                error: None,
-                account_addr: Some(addr.into()),
-                init_state: Some(init_state),
-                returndata: Some(returndata),
-                gas_used,
-                logs,
-            }
-        }
-    }
+    let returndata = read_field(cursor, Field::ReturnData, returndata::decode)?;
+    receipt.returndata = Some(returndata);
+
+    receipt.gas_used = decode_field(cursor, gas::decode_gas_used)?;
+
+    logs::decode_logs_into(receipt.version, cursor, &mut receipt.logs)?;
+
+    decode_call_receipt(receipt.version, cursor, &mut receipt.call)?;
+
+    let version = receipt.version;
+    receipt.gas_breakdown =
+        decode_field(cursor, |cursor| gas::decode_gas_breakdown(version, cursor))?;
+
+    receipt.logs_bloom = decode_field(cursor, |cursor| bloom::decode_logs_bloom(version, cursor))?;
+
+    Ok(())
 }
 
 fn encode_version(receipt: &SpawnReceipt, w: &mut Vec<u8>) {
@@ -121,11 +183,100 @@ fn encode_returndata(receipt: &SpawnReceipt, w: &mut Vec<u8>) {
     returndata::encode(&data, w);
 }
 
+fn encode_call_receipt(receipt: &SpawnReceipt, w: &mut Vec<u8>) {
+    debug_assert!(receipt.success);
+
+    if receipt.version < CALL_RECEIPT_VERSION {
+        assert!(
+            receipt.call().is_none(),
+            "A post-`ctor` call receipt requires the receipts format version {} (at least)",
+            CALL_RECEIPT_VERSION
+        );
+
+        return;
+    }
+
+    match receipt.call() {
+        Some(call) => {
+            w.write_bool(true);
+            encode_sized_call_receipt(call, w);
+        }
+        None => w.write_bool(false),
+    }
+}
+
+/// Encodes `call` prefixed by its byte-size (4 bytes).
+pub(super) fn encode_sized_call_receipt(call: &CallReceipt, w: &mut Vec<u8>) {
+    let bytes = encode_call(call);
+
+    w.write_u32_be(bytes.len() as u32);
+    w.write_bytes(&bytes);
+}
+
+/// Decodes the trailing `call receipt` (of a receipt of format version `version`) into `call`.
+fn decode_call_receipt(
+    version: u16,
+    cursor: &mut Cursor<&[u8]>,
+    call: &mut Option<CallReceipt>,
+) -> Result<(), ParseError> {
+    if version < CALL_RECEIPT_VERSION {
+        return Ok(());
+    }
+
+    let has_call = read_field(cursor, Field::CallReceipt, |cursor| cursor.read_byte())?;
+
+    match has_call {
+        0 => return Ok(()),
+        1 => (),
+        _ => {
+            cursor.set_position(cursor.position() - 1);
+            return Err(ParseError::NonCanonical(Field::CallReceipt));
+        }
+    }
+
+    decode_sized_call_receipt(cursor, call)
+}
+
+/// Decodes into `call` a binary `call receipt` prefixed by its byte-size (4 bytes).
+///
+/// A partially decoded `call receipt` is kept, and the failure is reported
+/// at its offset within the enclosing Receipt.
+pub(super) fn decode_sized_call_receipt(
+    cursor: &mut Cursor<&[u8]>,
+    call: &mut Option<CallReceipt>,
+) -> Result<(), ParseError> {
+    let bytes = read_field(cursor, Field::CallReceipt, |cursor| {
+        let length = cursor.read_u32_be()?;
+
+        cursor.read_bytes(length as usize)
+    })?;
+
+    let start = cursor.position() as usize - bytes.len();
+    let partial = super::decode_receipt_partial(&bytes);
+
+    match partial.receipt {
+        Some(Receipt::Call(receipt)) => *call = Some(receipt),
+        Some(..) => {
+            cursor.set_position(start as u64);
+            return Err(ParseError::UnexpectedLayout(Field::CallReceipt));
+        }
+        None => (),
+    }
+
+    match partial.error {
+        Some(err) => {
+            cursor.set_position((start + partial.offset) as u64);
+            Err(err)
+        }
+        None => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use svm_types::{Address, Gas, ReceiptLog, RuntimeError, State, TemplateAddr};
+    use svm_types::{Address, Gas, GasBreakdown, ReceiptLog, RuntimeError, State, TemplateAddr};
 
     use crate::receipt::decode_receipt;
 
@@ -142,7 +293,12 @@ mod tests {
             init_state: None,
             returndata: None,
             gas_used: Gas::new(),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
             logs: Vec::new(),
+            metrics: Vec::new(),
+            call: None,
         };
 
         let bytes = encode_spawn(&receipt);
@@ -165,7 +321,12 @@ mod tests {
             init_state: Some(init_state),
             returndata: Some(Vec::new()),
             gas_used: Gas::with(100),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
             logs: logs.clone(),
+            metrics: Vec::new(),
+            call: None,
         };
 
         let bytes = encode_spawn(&receipt);
@@ -189,7 +350,54 @@ mod tests {
             init_state: Some(init_state),
             returndata: Some(returndata),
             gas_used: Gas::with(100),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
             logs: logs.clone(),
+            metrics: Vec::new(),
+            call: None,
+        };
+
+        let bytes = encode_spawn(&receipt);
+        let decoded = decode_receipt(&bytes);
+
+        assert_eq!(decoded.into_spawn(), receipt);
+    }
+
+    #[test]
+    fn encode_decode_spawn_receipt_success_with_call() {
+        let addr = Address::of("@Account");
+        let init_state = State::of("some-state");
+        let logs = vec![ReceiptLog::new(b"something happened".to_vec())];
+
+        let call = CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            new_state: Some(State::of("new-state")),
+            returndata: Some(vec![0x30]),
+            gas_used: Gas::with(50),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: vec![ReceiptLog::new(b"setup done".to_vec())],
+            metrics: Vec::new(),
+        };
+
+        let receipt = SpawnReceipt {
+            version: 7,
+            success: true,
+            error: None,
+            account_addr: Some(addr),
+            init_state: Some(init_state),
+            returndata: Some(vec![0x10, 0x20]),
+            gas_used: Gas::with(150),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs,
+            metrics: Vec::new(),
+            call: Some(call),
         };
 
         let bytes = encode_spawn(&receipt);
@@ -197,4 +405,59 @@ mod tests {
 
         assert_eq!(decoded.into_spawn(), receipt);
     }
+
+    #[test]
+    fn encode_decode_spawn_receipt_success_with_gas_breakdown() {
+        let mut call = CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            new_state: Some(State::of("new-state")),
+            returndata: Some(Vec::new()),
+            gas_used: Gas::with(50),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: Vec::new(),
+            metrics: Vec::new(),
+        };
+        let call_breakdown = GasBreakdown {
+            intrinsic: 0,
+            execution: 40,
+            storage: 10,
+            logs: 0,
+            refund: 0,
+        };
+        call.set_gas_breakdown(call_breakdown);
+
+        let mut receipt = SpawnReceipt {
+            version: 7,
+            success: true,
+            error: None,
+            account_addr: Some(Address::of("@Account")),
+            init_state: Some(State::of("some-state")),
+            returndata: Some(Vec::new()),
+            gas_used: Gas::with(150),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: Vec::new(),
+            metrics: Vec::new(),
+            call: Some(call),
+        };
+        let mut breakdown = GasBreakdown {
+            intrinsic: 20,
+            execution: 80,
+            ..Default::default()
+        };
+        breakdown += call_breakdown;
+        receipt.set_gas_breakdown(breakdown);
+
+        let bytes = encode_spawn(&receipt);
+        let decoded = decode_receipt(&bytes).into_spawn();
+
+        assert_eq!(decoded.version, CALL_RECEIPT_VERSION);
+        assert_eq!(decoded.gas_breakdown.unwrap().total(), 150);
+        assert_eq!(decoded, receipt);
+    }
 }
@@ -0,0 +1,148 @@
+//!  ## `Upgrade Template` Receipt Binary Format Versions 0 to 6
+//!
+//!  On success (`is_success = 1`)
+//!
+//!  ```text
+//!  +------------------------------------------------------------------------------------------+
+//!  |          |             |             |                    |                  |           |
+//!  | tx type  |   version   |  is_success | template `Address` | template version | gas_used  |
+//!  | (1 byte) |  (2 bytes)  |  (1 byte)   |     (20 bytes)     |    (2 bytes)     | (8 bytes) |
+//!  |          |             |             |                    |                  |           |
+//!  +------------------------------------------------------------------------------------------+
+//!  ```
+//!
+//!  Version `1` appends the `gas_used` breakdown by phase
+//!  (following the logs, see [gas.rs](./gas.rs)).
+//!
+//!  Version `2` records the gas schedule version (2 bytes) right after `is_success`
+//!  (for failed receipts too), and makes the `gas_used` breakdown optional.
+//!
+//!  Version `3` is laid out as version `2`.
+//!
+//!  Version `4` encodes `#logs` and the logs `data length` as variable-length integers
+//!  (see [logs.rs](./logs.rs)).
+//!
+//!  Version `5` ends the receipt (on success and on error alike) with a `Tagged Fields` block
+//!  (see [fields.rs](./fields.rs)).
+//!
+//!  Version `6` prefixes each log with its event code, followed by the indexed topics
+//!  of event logs (see [logs.rs](./logs.rs)).
+//!
+//!  On Error (`is_success = 0`)
+//!  See [error.rs][./error.rs]
+
+use std::io::Cursor;
+
+use svm_types::{Receipt, UpgradeReceipt};
+
+use super::{decode_error, decode_field, encode_error, fields, gas, logs, read_field, types};
+
+use crate::version;
+use crate::{Field, ParseError, ReadExt, WriteExt};
+
+/// Encodes an [`UpgradeReceipt`] into its binary format.
+pub fn encode_upgrade(receipt: &UpgradeReceipt) -> Vec<u8> {
+    let mut w = Vec::new();
+
+    w.write_byte(types::UPGRADE);
+    version::encode_version(receipt.version, &mut w);
+    w.write_bool(receipt.success);
+    gas::encode_gas_schedule_version(receipt.version, receipt.gas_schedule_version, &mut w);
+
+    if receipt.success {
+        w.write_template_addr(receipt.template_addr());
+        w.write_u16_be(receipt.template_version);
+        gas::encode_gas_used(&receipt.gas_used, &mut w);
+        logs::encode_logs(receipt.version, &receipt.logs, &mut w);
+        gas::encode_gas_breakdown(receipt.version, receipt.gas_breakdown.as_ref(), &mut w);
+    } else {
+        let logs = Vec::new();
+
+        encode_error(receipt.version, receipt.error(), &logs, &mut w);
+    };
+
+    fields::encode_tagged_fields(receipt.version, &[], &mut w);
+
+    w
+}
+
+/// Decodes a binary [`UpgradeReceipt`] transaction.
+///
+/// # Panics
+///
+/// Panics if `bytes` isn't a valid binary [`UpgradeReceipt`] (see [`super::try_decode_receipt`]).
+pub fn decode_upgrade(bytes: &[u8]) -> UpgradeReceipt {
+    let receipt = super::decode_receipt(bytes);
+    debug_assert!(matches!(receipt, Receipt::Upgrade(..)));
+
+    receipt.into_upgrade()
+}
+
+/// Decodes into `receipt` the fields following the header of a binary [`UpgradeReceipt`].
+///
+/// On failure, `receipt` holds the fields decoded so far.
+pub(crate) fn decode_upgrade_body(
+    cursor: &mut Cursor<&[u8]>,
+    receipt: &mut UpgradeReceipt,
+) -> Result<(), ParseError> {
+    if !receipt.success {
+        return decode_error(
+            receipt.version,
+            cursor,
+            &mut receipt.error,
+            &mut receipt.logs,
+        );
+    }
+
+    let addr = read_field(cursor, Field::TemplateAddr, |cursor| {
+        cursor.read_template_addr()
+    })?;
+    receipt.addr = Some(addr);
+
+    receipt.template_version = read_field(cursor, Field::TemplateVersion, |cursor| {
+        cursor.read_u16_be()
+    })?;
+
+    receipt.gas_used = decode_field(cursor, gas::decode_gas_used)?;
+
+    logs::decode_logs_into(receipt.version, cursor, &mut receipt.logs)?;
+
+    let version = receipt.version;
+    receipt.gas_breakdown =
+        decode_field(cursor, |cursor| gas::decode_gas_breakdown(version, cursor))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_types::{Gas, RuntimeError, TemplateAddr};
+
+    use crate::receipt::decode_receipt;
+
+    #[test]
+    fn encode_decode_upgrade_template_receipt() {
+        let addr = TemplateAddr::repeat(0xAB);
+        let receipt = UpgradeReceipt::new(addr, 3, Gas::with(100));
+
+        let bytes = encode_upgrade(&receipt);
+        let decoded = decode_receipt(&bytes);
+
+        assert_eq!(decoded.into_upgrade(), receipt);
+    }
+
+    #[test]
+    fn encode_decode_upgrade_template_receipt_error() {
+        let err = RuntimeError::UpgradeNotAllowed {
+            template: TemplateAddr::repeat(0xAB),
+            msg: "only the deployer may upgrade".to_string(),
+        };
+        let receipt = UpgradeReceipt::from_err(err, Vec::new());
+
+        let bytes = encode_upgrade(&receipt);
+
+        assert_eq!(decode_upgrade(&bytes), receipt);
+    }
+}
@@ -1,11 +1,13 @@
 use std::collections::HashSet;
 use std::io::Cursor;
+use std::ops::Range;
 
 use svm_types::{
-    ApiSection, CodeSection, CtorsSection, DataSection, DeploySection, HeaderSection,
-    SchemaSection, Section, SectionKind, Sections,
+    ApiSection, CodeSection, CtorsSection, DataSection, DefaultsSection, DeploySection,
+    HeaderSection, SchemaSection, Section, SectionKind, Sections,
 };
 
+use super::sections::custom;
 use super::{preview, SectionPreview};
 
 use crate::{Field, ParseError, ReadExt};
@@ -20,6 +22,9 @@ pub trait SectionDecoder: Sized {
 /// to be decoded into its matching Rust form or skipped until the next binary [`Section`].
 /// This mechanism works thanks to having each binary [`Section`] prefixed with a [`SectionPreview`].
 /// It contains information about the kind of [`Section`] and its byte-count.
+///
+/// Skipping a binary [`Section`] seeks past it without reading its content, so the decoder can
+/// stream over the [`SectionPreview`]s only (see [`SectionsDecoder::section_range`]).
 pub struct SectionsDecoder<'a> {
     last_preview: Option<SectionPreview>,
     read_previews: usize,
@@ -47,6 +52,11 @@ impl<'a> SectionsDecoder<'a> {
         self.section_count
     }
 
+    /// Returns the cursor over the input (positioned where decoding has stopped).
+    pub fn cursor(&self) -> &Cursor<&'a [u8]> {
+        &self.cursor
+    }
+
     /// Returns whether decoder has reached it's end of input.
     pub fn is_eof(&mut self) -> bool {
         self.read_previews >= self.section_count
@@ -80,31 +90,45 @@ impl<'a> SectionsDecoder<'a> {
 
         let last_preview = self.last_preview.take().unwrap();
 
-        let cursor = &mut self.cursor;
-
-        let section = match last_preview.kind() {
-            SectionKind::Header => HeaderSection::decode(cursor)?.into(),
-            SectionKind::Code => CodeSection::decode(cursor)?.into(),
-            SectionKind::Data => DataSection::decode(cursor)?.into(),
-            SectionKind::Ctors => CtorsSection::decode(cursor)?.into(),
-            SectionKind::Schema => SchemaSection::decode(cursor)?.into(),
-            SectionKind::Api => ApiSection::decode(cursor)?.into(),
-            SectionKind::Deploy => DeploySection::decode(cursor)?.into(),
-        };
-
-        Ok(section)
+        decode_section(&last_preview, &mut self.cursor)
     }
 
     /// Skips the current pointed to binary [`Section`].
     pub fn skip_section(&mut self) -> Result<(), ParseError> {
+        self.section_range().map(|_| ())
+    }
+
+    /// Skips the current pointed to binary [`Section`] and returns the range of its bytes within the input.
+    ///
+    /// The [`Section`]'s content isn't read, so skipping costs the same regardless of its size.
+    pub fn section_range(&mut self) -> Result<Range<usize>, ParseError> {
         assert!(
             self.last_preview.is_some(),
-            "Please call `next_preview` prior to calling `skip_section`"
+            "Please call `next_preview` prior to calling `section_range`"
         );
 
-        let _bytes = self.section_bytes()?;
+        let last_preview = self.last_preview.take().unwrap();
+
+        let start = self.cursor.position() as usize;
+        let end = start + last_preview.byte_size() as usize;
+
+        if end > self.cursor.get_ref().len() {
+            return Err(ParseError::NotEnoughBytes(Field::Section));
+        }
+
+        self.cursor.set_position(end as u64);
 
-        Ok(())
+        Ok(start..end)
+    }
+
+    /// Skips the current pointed to binary [`Section`] and returns its (raw) bytes.
+    ///
+    /// The returned bytes can be decoded later on (using the matching [`SectionDecoder`]).
+    pub fn raw_section(&mut self) -> Result<&'a [u8], ParseError> {
+        let range = self.section_range()?;
+        let input: &'a [u8] = self.cursor.get_ref();
+
+        Ok(&input[range])
     }
 
     fn read_section_count(&mut self) -> Result<usize, ParseError> {
@@ -113,15 +137,62 @@ impl<'a> SectionsDecoder<'a> {
             Err(..) => Err(ParseError::NotEnoughBytes(Field::SectionCount)),
         }
     }
+}
 
-    fn section_bytes(&mut self) -> Result<Vec<u8>, ParseError> {
-        let last_preview = self.last_preview.take().unwrap();
+/// Decodes the raw bytes of a single binary [`Section`] of the given `kind`
+/// (i.e the bytes following its [`SectionPreview`], as returned by [`SectionsDecoder::raw_section`]).
+///
+/// Fails with [`ParseError::ExpectedEOF`] when the `Section` doesn't span all the `bytes`.
+pub fn decode_raw_section(kind: SectionKind, bytes: &[u8]) -> Result<Section, ParseError> {
+    let preview = SectionPreview::new(kind, bytes.len() as u32);
+    let mut cursor = Cursor::new(bytes);
 
-        let to_skip = last_preview.byte_size();
-        let bytes = self.cursor.read_bytes(to_skip as usize);
+    let section = decode_section(&preview, &mut cursor)?;
 
-        bytes.map_err(|_| ParseError::NotEnoughBytes(Field::Section))
+    if (cursor.position() as usize) < bytes.len() {
+        return Err(ParseError::ExpectedEOF);
     }
+
+    Ok(section)
+}
+
+/// Decodes the [`Section`] following `preview`.
+///
+/// The decoding is bounded by the [`SectionPreview`]'s byte size, so a [`SectionDecoder`] can tell
+/// the absence of trailing (optional) fields, added to its format later on, by reaching the end of its input.
+fn decode_section(
+    preview: &SectionPreview,
+    cursor: &mut Cursor<&[u8]>,
+) -> Result<Section, ParseError> {
+    let input: &[u8] = cursor.get_ref();
+    let start = cursor.position() as usize;
+    let end = input.len().min(start + preview.byte_size() as usize);
+
+    let mut bounded = Cursor::new(&input[start..end]);
+    let section = decode_bounded_section(preview, &mut bounded)?;
+
+    cursor.set_position(start as u64 + bounded.position());
+
+    Ok(section)
+}
+
+fn decode_bounded_section(
+    preview: &SectionPreview,
+    cursor: &mut Cursor<&[u8]>,
+) -> Result<Section, ParseError> {
+    let section = match preview.kind() {
+        SectionKind::Header => HeaderSection::decode(cursor)?.into(),
+        SectionKind::Code => CodeSection::decode(cursor)?.into(),
+        SectionKind::Data => DataSection::decode(cursor)?.into(),
+        SectionKind::Ctors => CtorsSection::decode(cursor)?.into(),
+        SectionKind::Schema => SchemaSection::decode(cursor)?.into(),
+        SectionKind::Api => ApiSection::decode(cursor)?.into(),
+        SectionKind::Deploy => DeploySection::decode(cursor)?.into(),
+        SectionKind::Defaults => DefaultsSection::decode(cursor)?.into(),
+        SectionKind::Custom(kind) => custom::decode(kind, preview.byte_size(), cursor)?.into(),
+    };
+
+    Ok(section)
 }
 
 pub fn decode_sections(
@@ -44,18 +44,29 @@ impl SectionsEncoder {
     }
 
     /// Returns the binary encoding of the [`Section`]s provided so far.
-    pub fn finish(mut self) -> Vec<u8> {
-        let section_count = self.section_buf.len();
+    pub fn finish(self) -> Vec<u8> {
+        let mut w = Vec::with_capacity(self.byte_size());
+        self.finish_into(&mut w);
 
-        assert!(section_count < std::u16::MAX as usize);
+        w
+    }
+
+    /// Returns the byte size of the binary encoding of the [`Section`]s provided so far.
+    pub fn byte_size(&self) -> usize {
+        let section_count = self.section_buf.len();
 
         let section_count_size = 2;
         let previews_size = section_count * SectionPreview::len();
         let sections_size: usize = self.section_buf.values().map(|buf| buf.len()).sum();
 
-        let capacity = section_count_size + previews_size + sections_size;
+        section_count_size + previews_size + sections_size
+    }
+
+    /// Writes the binary encoding of the [`Section`]s provided so far into `w`.
+    pub fn finish_into(mut self, w: &mut impl WriteExt) {
+        let section_count = self.section_buf.len();
 
-        let mut w = Vec::with_capacity(capacity);
+        assert!(section_count < std::u16::MAX as usize);
 
         // Section Count
         w.write_u16_be(section_count as u16);
@@ -67,13 +78,11 @@ impl SectionsEncoder {
             assert!(byte_size < std::u32::MAX as usize);
 
             let preview = SectionPreview::new(kind, byte_size as u32);
-            preview::encode(&preview, &mut w);
+            preview::encode(&preview, w);
 
             // `Section`
             w.write_bytes(&bytes);
         }
-
-        w
     }
 
     fn encode_section(&mut self, section: &Section) {
@@ -88,6 +97,8 @@ impl SectionsEncoder {
             SectionKind::Ctors => section.as_ctors(),
             SectionKind::Schema => section.as_schema(),
             SectionKind::Deploy => section.as_deploy(),
+            SectionKind::Defaults => section.as_defaults(),
+            SectionKind::Custom(..) => section.as_custom(),
         };
 
         encoder.encode(buf);
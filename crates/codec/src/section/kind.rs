@@ -1,4 +1,4 @@
-use svm_types::SectionKind;
+use svm_types::{CustomSection, SectionKind};
 
 use crate::{Field, ParseError, ReadExt, WriteExt};
 
@@ -9,8 +9,9 @@ pub const SCHEMA_SECTION: u16 = 0x00_04;
 pub const API_SECTION: u16 = 0x00_05;
 pub const HEADER_SECTION: u16 = 0x00_06;
 pub const DEPLOY_SECTION: u16 = 0x00_07;
+pub const DEFAULTS_SECTION: u16 = 0x00_08;
 
-pub fn encode(kind: SectionKind, w: &mut Vec<u8>) {
+pub fn encode(kind: SectionKind, w: &mut impl WriteExt) {
     let raw = match kind {
         SectionKind::Code => CODE_SECTION,
         SectionKind::Data => DATA_SECTION,
@@ -19,6 +20,12 @@ pub fn encode(kind: SectionKind, w: &mut Vec<u8>) {
         SectionKind::Api => API_SECTION,
         SectionKind::Header => HEADER_SECTION,
         SectionKind::Deploy => DEPLOY_SECTION,
+        SectionKind::Defaults => DEFAULTS_SECTION,
+        SectionKind::Custom(raw) => {
+            assert!(raw >= CustomSection::MIN_KIND);
+
+            raw
+        }
     };
 
     w.write_u16_be(raw);
@@ -39,6 +46,8 @@ pub fn decode(cursor: &mut std::io::Cursor<&[u8]>) -> Result<SectionKind, ParseE
         API_SECTION => Ok(SectionKind::Api),
         HEADER_SECTION => Ok(SectionKind::Header),
         DEPLOY_SECTION => Ok(SectionKind::Deploy),
+        DEFAULTS_SECTION => Ok(SectionKind::Defaults),
+        raw if raw >= CustomSection::MIN_KIND => Ok(SectionKind::Custom(raw)),
         _ => Err(ParseError::InvalidSection),
     }
 }
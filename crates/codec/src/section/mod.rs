@@ -5,6 +5,6 @@ pub mod kind;
 pub mod preview;
 pub mod sections;
 
-pub use decode::{SectionDecoder, SectionsDecoder};
+pub use decode::{decode_raw_section, SectionDecoder, SectionsDecoder};
 pub use encode::{SectionEncoder, SectionsEncoder};
 pub use preview::SectionPreview;
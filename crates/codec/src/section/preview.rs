@@ -34,7 +34,7 @@ impl SectionPreview {
     }
 }
 
-pub fn encode(preview: &SectionPreview, w: &mut Vec<u8>) {
+pub fn encode(preview: &SectionPreview, w: &mut impl WriteExt) {
     // `Section Kind`
     kind::encode(preview.kind(), w);
 
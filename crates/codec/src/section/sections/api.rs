@@ -1,19 +1,363 @@
-use std::io::Cursor;
+//!
+//! # `API Section`
+//!
+//! +-------------+--------------+-------------+------------+---------+-------------+
+//! |             |              |             |            |         |             |
+//! |   Flags     |   Fallback   |   #Funcs    |  Func #1   |   ...   |    Docs     |
+//! |  (1 byte)   |   (String)   |  (2 bytes)  |            |         |             |
+//! |             |  (optional)  |             |            |         | (optional)  |
+//! |             |              |             |            |         |             |
+//! +-------------+--------------+-------------+------------+---------+-------------+
+//!
+//! * `Flags` - bit `0` is set when there is a `Fallback` and bit `1` is set when there are `Docs`
+//!   (the documentation of the `Template` and of its storage variables).
+//!
+//!
+//! ## `Func`
+//!
+//! +------------+-------------+-----------+-----------+-----------+-----------+-----------+
+//! |            |             |           |           |           |           |           |
+//! |    Name    |  Wasm Name  |   Flags   |  #Params  |  Params   | #Returns  |  Returns  |
+//! |  (String)  |  (String)   | (1 byte)  | (1 byte)  |           | (1 byte)  | (`Type`s) |
+//! |            |             |           |           |           |           |           |
+//! +------------+-------------+-----------+-----------+-----------+-----------+-----------+
+//!
+//! * `Flags` - bit `0` is set for a `ctor`, bit `1` is set for a fundable function
+//!   and bit `2` is set for a documented function (its `Doc` then follows the `Returns`).
+//! * Each `Param` is its `Name` (String) followed by its `Type`.
+//!
+//!
+//! ## `Docs`
+//!
+//! +----------------+-------------+---------------+---------------+---------+
+//! |                |             |               |               |         |
+//! |  Template Doc  |  #VarDocs   |  Var #1 Name  |  Var #1 Doc   |   ...   |
+//! |    (`Doc`)     |  (2 bytes)  |   (String)    |    (`Doc`)    |         |
+//! |                |             |               |               |         |
+//! +----------------+-------------+---------------+---------------+---------+
+//!
+//! * Each `Doc` is a UTF-8 string prefixed by its byte length (2 bytes), since docs may be
+//!   longer than a `String` allows.
+//!
+//!
+//! ## `Type`
+//!
+//! +-------------+------------------------------------------------+
+//! |             |                                                |
+//! |  Type Kind  |  Primitive (1 byte)                            |
+//! |  (1 byte)   |  Primitive (1 byte) + Array Length (4 bytes)   |
+//! |             |  Struct Name (String)                          |
+//! |             |                                                |
+//! +-------------+------------------------------------------------+
+//!
+//! * `Type Kind` - `0` for a `Primitive`, `1` for an `Array` (of `Primitive`s) and `2` for a `Struct`.
+//! * `Primitive` - encoded as in the `Schema Section` (see [`super::schema`]).
+//!
+//!
 
-use svm_types::ApiSection;
+use std::io::Cursor;
 
-use crate::ParseError;
+use svm_types::{ApiFunc, ApiParam, ApiSection, ApiType, ApiVarDoc};
 
+use super::schema::{decode_primitive, encode_primitive, ARRAY, PRIMITIVE};
 use crate::section::{SectionDecoder, SectionEncoder};
+use crate::{Field, ParseError, ReadExt, WriteExt};
+
+const STRUCT: u8 = 2;
+
+const FALLBACK_FLAG: u8 = 1;
+const DOCS_FLAG: u8 = 1 << 1;
+
+const CTOR_FLAG: u8 = 1;
+const FUNDABLE_FLAG: u8 = 1 << 1;
+const DOC_FLAG: u8 = 1 << 2;
 
 impl SectionEncoder for ApiSection {
-    fn encode(&self, _w: &mut Vec<u8>) {
-        todo!("will be implemented in a future PR...");
+    fn encode(&self, w: &mut Vec<u8>) {
+        // `Flags`
+        let mut flags = 0;
+
+        if self.fallback().is_some() {
+            flags |= FALLBACK_FLAG;
+        }
+
+        let has_docs = !self.doc().is_empty() || !self.var_docs().is_empty();
+
+        if has_docs {
+            flags |= DOCS_FLAG;
+        }
+
+        w.write_byte(flags);
+
+        // `Fallback`
+        if let Some(fallback) = self.fallback() {
+            w.write_string(fallback);
+        }
+
+        // `#Funcs`
+        let count = self.funcs().len();
+
+        assert!(count < std::u16::MAX as usize);
+
+        w.write_u16_be(count as u16);
+
+        // Encoding each `Func`
+        for func in self.funcs() {
+            encode_func(func, w);
+        }
+
+        // `Docs`
+        if has_docs {
+            encode_doc(self.doc(), w);
+
+            let count = self.var_docs().len();
+
+            assert!(count < std::u16::MAX as usize);
+
+            w.write_u16_be(count as u16);
+
+            for var_doc in self.var_docs() {
+                w.write_string(&var_doc.name);
+                encode_doc(&var_doc.doc, w);
+            }
+        }
     }
 }
 
 impl SectionDecoder for ApiSection {
-    fn decode(_cursor: &mut Cursor<&[u8]>) -> Result<Self, ParseError> {
-        todo!("will be implemented in a future PR...");
+    fn decode(cursor: &mut Cursor<&[u8]>) -> Result<Self, ParseError> {
+        let flags = cursor
+            .read_byte()
+            .map_err(|_| ParseError::NotEnoughBytes(Field::ApiFlags))?;
+
+        if flags & !(FALLBACK_FLAG | DOCS_FLAG) != 0 {
+            return Err(ParseError::NotSupported(Field::ApiFlags));
+        }
+
+        let mut section = ApiSection::new();
+
+        if flags & FALLBACK_FLAG != 0 {
+            let fallback = decode_string(cursor, Field::Fallback)?;
+
+            section = section.with_fallback(fallback);
+        }
+
+        // `#Funcs`
+        let count = cursor
+            .read_u16_be()
+            .map_err(|_| ParseError::NotEnoughBytes(Field::ApiFuncCount))?;
+
+        // Decoding each `Func`
+        for _ in 0..count {
+            let func = decode_func(cursor)?;
+
+            section = section.with_func(func);
+        }
+
+        // `Docs`
+        if flags & DOCS_FLAG != 0 {
+            let doc = decode_doc(cursor)?;
+
+            section = section.with_doc(doc);
+
+            let count = cursor
+                .read_u16_be()
+                .map_err(|_| ParseError::NotEnoughBytes(Field::ApiVarDocCount))?;
+
+            for _ in 0..count {
+                let name = decode_string(cursor, Field::ApiVarDocName)?;
+                let doc = decode_doc(cursor)?;
+
+                section = section.with_var_doc(ApiVarDoc { name, doc });
+            }
+        }
+
+        Ok(section)
     }
 }
+
+fn encode_func(func: &ApiFunc, w: &mut Vec<u8>) {
+    // `Name`
+    w.write_string(&func.name);
+
+    // `Wasm Name`
+    w.write_string(&func.wasm_name);
+
+    // `Flags`
+    let mut flags = 0;
+
+    if func.is_ctor {
+        flags |= CTOR_FLAG;
+    }
+
+    if func.is_fundable {
+        flags |= FUNDABLE_FLAG;
+    }
+
+    if !func.doc.is_empty() {
+        flags |= DOC_FLAG;
+    }
+
+    w.write_byte(flags);
+
+    // `Params`
+    assert!(func.params.len() <= std::u8::MAX as usize);
+
+    w.write_byte(func.params.len() as u8);
+
+    for param in func.params.iter() {
+        w.write_string(&param.name);
+        encode_type(&param.ty, w);
+    }
+
+    // `Returns`
+    assert!(func.returns.len() <= std::u8::MAX as usize);
+
+    w.write_byte(func.returns.len() as u8);
+
+    for ty in func.returns.iter() {
+        encode_type(ty, w);
+    }
+
+    // `Doc`
+    if !func.doc.is_empty() {
+        encode_doc(&func.doc, w);
+    }
+}
+
+fn decode_func(cursor: &mut Cursor<&[u8]>) -> Result<ApiFunc, ParseError> {
+    let name = decode_string(cursor, Field::ApiFuncName)?;
+    let wasm_name = decode_string(cursor, Field::ApiFuncWasmName)?;
+
+    let flags = cursor
+        .read_byte()
+        .map_err(|_| ParseError::NotEnoughBytes(Field::ApiFuncFlags))?;
+
+    if flags & !(CTOR_FLAG | FUNDABLE_FLAG | DOC_FLAG) != 0 {
+        return Err(ParseError::NotSupported(Field::ApiFuncFlags));
+    }
+
+    // `Params`
+    let count = cursor
+        .read_byte()
+        .map_err(|_| ParseError::NotEnoughBytes(Field::ApiParamCount))?;
+
+    let mut params = Vec::with_capacity(count as usize);
+
+    for _ in 0..count {
+        let name = decode_string(cursor, Field::ApiParamName)?;
+        let ty = decode_type(cursor)?;
+
+        params.push(ApiParam { name, ty });
+    }
+
+    // `Returns`
+    let count = cursor
+        .read_byte()
+        .map_err(|_| ParseError::NotEnoughBytes(Field::ApiReturnCount))?;
+
+    let mut returns = Vec::with_capacity(count as usize);
+
+    for _ in 0..count {
+        returns.push(decode_type(cursor)?);
+    }
+
+    // `Doc`
+    let doc = if flags & DOC_FLAG != 0 {
+        decode_doc(cursor)?
+    } else {
+        String::new()
+    };
+
+    let func = ApiFunc {
+        name,
+        wasm_name,
+        is_ctor: flags & CTOR_FLAG != 0,
+        is_fundable: flags & FUNDABLE_FLAG != 0,
+        params,
+        returns,
+        doc,
+    };
+
+    Ok(func)
+}
+
+fn encode_type(ty: &ApiType, w: &mut Vec<u8>) {
+    match ty {
+        ApiType::Primitive(primitive) => {
+            w.write_byte(PRIMITIVE);
+            w.write_byte(encode_primitive(*primitive));
+        }
+        ApiType::Array { primitive, length } => {
+            assert!(*length <= std::u32::MAX as usize);
+
+            w.write_byte(ARRAY);
+            w.write_byte(encode_primitive(*primitive));
+            w.write_u32_be(*length as u32);
+        }
+        ApiType::Struct(name) => {
+            w.write_byte(STRUCT);
+            w.write_string(name);
+        }
+    }
+}
+
+fn decode_type(cursor: &mut Cursor<&[u8]>) -> Result<ApiType, ParseError> {
+    let mut read_byte = || {
+        cursor
+            .read_byte()
+            .map_err(|_| ParseError::NotEnoughBytes(Field::ApiType))
+    };
+
+    match read_byte()? {
+        PRIMITIVE => {
+            let primitive = decode_primitive(read_byte()?, Field::ApiType)?;
+
+            Ok(ApiType::Primitive(primitive))
+        }
+        ARRAY => {
+            let primitive = decode_primitive(read_byte()?, Field::ApiType)?;
+            let length = cursor
+                .read_u32_be()
+                .map_err(|_| ParseError::NotEnoughBytes(Field::ApiType))?;
+
+            Ok(ApiType::Array {
+                primitive,
+                length: length as usize,
+            })
+        }
+        STRUCT => {
+            let name = decode_string(cursor, Field::ApiType)?;
+
+            Ok(ApiType::Struct(name))
+        }
+        _ => Err(ParseError::NotSupported(Field::ApiType)),
+    }
+}
+
+fn decode_string(cursor: &mut Cursor<&[u8]>, field: Field) -> Result<String, ParseError> {
+    match cursor.read_string() {
+        Ok(Ok(s)) => Ok(s),
+        Ok(Err(..)) => Err(ParseError::InvalidUTF8String(field)),
+        Err(..) => Err(ParseError::NotEnoughBytes(field)),
+    }
+}
+
+fn encode_doc(doc: &str, w: &mut Vec<u8>) {
+    assert!(doc.len() <= std::u16::MAX as usize);
+
+    w.write_u16_be(doc.len() as u16);
+    w.write_bytes(doc.as_bytes());
+}
+
+fn decode_doc(cursor: &mut Cursor<&[u8]>) -> Result<String, ParseError> {
+    let length = cursor
+        .read_u16_be()
+        .map_err(|_| ParseError::NotEnoughBytes(Field::ApiDoc))?;
+
+    let bytes = cursor
+        .read_bytes(length as usize)
+        .map_err(|_| ParseError::NotEnoughBytes(Field::ApiDoc))?;
+
+    String::from_utf8(bytes).map_err(|_| ParseError::InvalidUTF8String(Field::ApiDoc))
+}
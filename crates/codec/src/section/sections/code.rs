@@ -8,6 +8,10 @@
 //! |                |                |             |              |              |          |
 //! +----------------+----------------+-------------+--------------+--------------+----------+
 //!
+//! The `Code Kind` is either `Wasm` (`0x0001`) or `Precompile` (`0x0002`).
+//! The `Gas Mode` is either `Fixed` (`0x0001`) or `Metering` (`0x0002`).
+//! The `Code` of a `Precompile` holds the id (4 bytes) of the native implementation
+//! registered within the `Runtime`.
 //!
 
 use std::io::Cursor;
@@ -18,7 +22,9 @@ use crate::section::{SectionDecoder, SectionEncoder};
 use crate::{Field, ParseError, ReadExt, WriteExt};
 
 pub const WASM: u16 = 0x00_01;
+pub const PRECOMPILE: u16 = 0x00_02;
 pub const GAS_MODE_FIXED: u64 = 0x00_01;
+pub const GAS_MODE_METERING: u64 = 0x00_02;
 
 impl SectionEncoder for CodeSection {
     fn encode(&self, w: &mut Vec<u8>) {
@@ -82,6 +88,7 @@ impl SectionDecoder for CodeSection {
 fn encode_code_kind(kind: CodeKind, w: &mut Vec<u8>) {
     let raw = match kind {
         CodeKind::Wasm => WASM,
+        CodeKind::Precompile => PRECOMPILE,
     };
 
     w.write_u16_be(raw);
@@ -96,7 +103,8 @@ fn decode_code_kind(cursor: &mut Cursor<&[u8]>) -> Result<CodeKind, ParseError>
 
     match value.unwrap() {
         WASM => Ok(CodeKind::Wasm),
-        _ => unreachable!(),
+        PRECOMPILE => Ok(CodeKind::Precompile),
+        _ => Err(ParseError::NotSupported(Field::CodeKind)),
     }
 }
 
@@ -113,7 +121,7 @@ fn decode_code_flags(cursor: &mut Cursor<&[u8]>) -> Result<u64, ParseError> {
 fn encode_gas_mode(gas_mode: GasMode, w: &mut Vec<u8>) {
     match gas_mode {
         GasMode::Fixed => w.write_u64_be(GAS_MODE_FIXED),
-        GasMode::Metering => unreachable!(),
+        GasMode::Metering => w.write_u64_be(GAS_MODE_METERING),
     }
 }
 
@@ -130,7 +138,8 @@ fn decode_gas_mode(cursor: &mut Cursor<&[u8]>) -> Result<GasMode, ParseError> {
 
     match value.unwrap() {
         GAS_MODE_FIXED => Ok(GasMode::Fixed),
-        _ => unreachable!(),
+        GAS_MODE_METERING => Ok(GasMode::Metering),
+        _ => Err(ParseError::NotSupported(Field::GasMode)),
     }
 }
 
@@ -0,0 +1,38 @@
+//!
+//! # `Custom Section`
+//!
+//! +-------------------+
+//! |                   |
+//! |      Payload      |
+//! |      (Blob)       |
+//! |                   |
+//! +-------------------+
+//!
+//! The payload is opaque. Its length is derived from the `Section Preview` (its byte-size).
+//!
+
+use std::io::Cursor;
+
+use svm_types::CustomSection;
+
+use crate::section::SectionEncoder;
+use crate::{Field, ParseError, ReadExt, WriteExt};
+
+impl SectionEncoder for CustomSection {
+    fn encode(&self, w: &mut Vec<u8>) {
+        w.write_bytes(self.payload());
+    }
+}
+
+/// Decodes a [`CustomSection`] of kind `kind` whose payload is `byte_size` bytes long.
+pub fn decode(
+    kind: u16,
+    byte_size: u32,
+    cursor: &mut Cursor<&[u8]>,
+) -> Result<CustomSection, ParseError> {
+    let payload = cursor
+        .read_bytes(byte_size as usize)
+        .map_err(|_| ParseError::NotEnoughBytes(Field::Section))?;
+
+    Ok(CustomSection::new(kind, payload))
+}
@@ -1,28 +1,29 @@
 //!
 //! # `Data Section`
 //!
-//! +------------+----------------+-----------+
-//! |            |                |           |
-//! |  #Layouts  |  Layout #1     |    ...    |
-//! | (2 bytes)  | (see `Layout`) |           |
-//! |            |                |           |
-//! +------------+----------------+-----------+
+//! +------------+----------------+-----------+---------------------+-------------------+
+//! |            |                |           |                     |                   |
+//! |  #Layouts  |  Layout #1     |    ...    | Has Storage Budget  |  Storage Budget   |
+//! | (2 bytes)  | (see `Layout`) |           |     (1 byte)        |  (4 bytes, bytes) |
+//! |            |                |           |                     |                   |
+//! +------------+----------------+-----------+---------------------+-------------------+
+//!
+//! The `Storage Budget` is present only when `Has Storage Budget` is `true`.
+//! Both are absent from the sections encoded prior to their introduction (denoting no budget).
 //!
 //!
 //! ## `Layout`
 //!
-//! +--------------+-----------------------------------------+
-//! |              |                                         |
-//! | Layout Kind  |        Layout Specific Encoding         |
-//! |  (2 bytes)   |          (see `Fixed Layout`)           |
-//! |              |                                         |
-//! +--------------+-----------------------------------------+
+//! +--------------+-------------------------------------------+
+//! |              |                                           |
+//! | Layout Kind  |         Layout Specific Encoding          |
+//! |  (2 bytes)   | (see `Fixed Layout` and `Dynamic Layout`) |
+//! |              |                                           |
+//! +--------------+-------------------------------------------+
 //!
 //!
 //! ### `Fixed Layout`
 //!
-//! Right now, there is only the `Fixed Layout`
-//!
 //! When `#Vars > 0`
 //! +--------------+----------------------------+-------------------+-------------------+
 //! |              |           |                |                   |                   |
@@ -32,6 +33,10 @@
 //! +--------------+-----------+----------------+-------------------+-------------------+
 //!
 //!
+//! A `Var Byte-Size` of zero denotes a packed boolean variable.
+//! Consecutive packed booleans share a single byte (up to 8 of them, bit `0` first).
+//!
+//!
 //! When `#Vars = 0`
 //! +--------------+-----------+
 //! |              |           |
@@ -41,17 +46,30 @@
 //! +--------------+-----------+
 //!
 //!
+//! ### `Dynamic Layout`
+//!
+//! Lists the ids of the dynamic variables (ordered ascending).
+//! Each of them is allocated a variable of the `Fixed Layout` as well.
+//!
+//! +--------------+-----------+-------------------+-----------+
+//! |              |           |                   |           |
+//! |   0x00_02    |   #Ids    |  Dynamic Var Id   |    ...    |
+//! |  (2 bytes)   | (2 bytes) |    (4 bytes)      |           |
+//! |              |           |                   |           |
+//! +--------------+-----------+-------------------+-----------+
+//!
 //!
 
 use std::io::Cursor;
 
-use svm_layout::{FixedLayoutBuilder, Id, Layout, LayoutKind, RawVar};
+use svm_layout::{DynamicLayout, FixedLayoutBuilder, Id, Layout, LayoutKind, RawVar};
 use svm_types::DataSection;
 
 use crate::section::{SectionDecoder, SectionEncoder};
 use crate::{Field, ParseError, ReadExt, WriteExt};
 
 pub const FIXED: u16 = 0x00_01;
+pub const DYNAMIC: u16 = 0x00_02;
 
 impl SectionEncoder for DataSection {
     fn encode(&self, w: &mut Vec<u8>) {
@@ -62,6 +80,9 @@ impl SectionEncoder for DataSection {
         for layout in self.layouts() {
             encode_layout(layout, w);
         }
+
+        // `Storage Budget`
+        encode_max_storage_bytes(self.max_storage_bytes(), w);
     }
 }
 
@@ -79,7 +100,10 @@ impl SectionDecoder for DataSection {
             section.add_layout(layout);
         }
 
-        Ok(section)
+        // `Storage Budget`
+        let max_storage_bytes = decode_max_storage_bytes(cursor)?;
+
+        Ok(section.with_max_storage_bytes(max_storage_bytes))
     }
 }
 
@@ -109,6 +133,19 @@ fn encode_layout(layout: &Layout, w: &mut Vec<u8>) {
                 }
             }
         }
+        LayoutKind::Dynamic => {
+            let layout = layout.as_dynamic();
+
+            // `#Ids`
+            let id_count = layout.len();
+            assert!(id_count < std::u16::MAX as usize);
+            w.write_u16_be(id_count as u16);
+
+            // Encoding each `Dynamic Var Id`
+            for id in layout.iter() {
+                w.write_u32_be(id.0);
+            }
+        }
     }
 }
 
@@ -133,9 +170,10 @@ fn decode_layout(cursor: &mut Cursor<&[u8]>) -> Result<Layout, ParseError> {
 
                         // Decoding each `var`
                         for _ in 0..var_count {
-                            let byte_size = decode_var_byte_size(cursor)?;
-
-                            builder.push(byte_size);
+                            match decode_var_byte_size(cursor)? {
+                                0 => builder.push_bit(),
+                                byte_size => builder.push(byte_size),
+                            }
                         }
                     }
 
@@ -146,12 +184,32 @@ fn decode_layout(cursor: &mut Cursor<&[u8]>) -> Result<Layout, ParseError> {
                 }
             }
         }
+        LayoutKind::Dynamic => {
+            // `#Ids`
+            let id_count = cursor
+                .read_u16_be()
+                .map_err(|_| ParseError::NotEnoughBytes(Field::DynamicVarCount))?;
+
+            // Decoding each `Dynamic Var Id`
+            let mut ids = Vec::with_capacity(id_count as usize);
+
+            for _ in 0..id_count {
+                let id = cursor
+                    .read_u32_be()
+                    .map_err(|_| ParseError::NotEnoughBytes(Field::DynamicVarId))?;
+
+                ids.push(Id(id));
+            }
+
+            Ok(Layout::Dynamic(DynamicLayout::new(ids)))
+        }
     }
 }
 
 fn encode_layout_kind(kind: LayoutKind, w: &mut Vec<u8>) {
     let raw = match kind {
         LayoutKind::Fixed => FIXED,
+        LayoutKind::Dynamic => DYNAMIC,
     };
 
     w.write_u16_be(raw);
@@ -166,6 +224,7 @@ fn decode_layout_kind(cursor: &mut Cursor<&[u8]>) -> Result<LayoutKind, ParseErr
 
     match value.unwrap() {
         FIXED => Ok(LayoutKind::Fixed),
+        DYNAMIC => Ok(LayoutKind::Dynamic),
         _ => unreachable!(),
     }
 }
@@ -194,7 +253,12 @@ fn decode_var_id(cursor: &mut Cursor<&[u8]>) -> Result<Id, ParseError> {
 }
 
 fn encode_var_byte_size(var: &RawVar, w: &mut Vec<u8>) {
-    w.write_u16_be(var.byte_size() as u16);
+    let byte_size = match var.bit() {
+        Some(..) => 0,
+        None => var.byte_size(),
+    };
+
+    w.write_u16_be(byte_size as u16);
 }
 
 fn decode_var_byte_size(cursor: &mut Cursor<&[u8]>) -> Result<u32, ParseError> {
@@ -203,3 +267,32 @@ fn decode_var_byte_size(cursor: &mut Cursor<&[u8]>) -> Result<u32, ParseError> {
         Err(..) => Err(ParseError::NotEnoughBytes(Field::RawVarSize)),
     }
 }
+
+fn encode_max_storage_bytes(max_storage_bytes: Option<u32>, w: &mut Vec<u8>) {
+    w.write_bool(max_storage_bytes.is_some());
+
+    if let Some(max_storage_bytes) = max_storage_bytes {
+        w.write_u32_be(max_storage_bytes);
+    }
+}
+
+fn decode_max_storage_bytes(cursor: &mut Cursor<&[u8]>) -> Result<Option<u32>, ParseError> {
+    // A section encoded prior to the `Storage Budget` ends right after its `Layout`s
+    if cursor.position() as usize == cursor.get_ref().len() {
+        return Ok(None);
+    }
+
+    let has_budget = cursor
+        .read_bool()
+        .map_err(|_| ParseError::NotEnoughBytes(Field::MaxStorageBytes))?;
+
+    if has_budget {
+        let max_storage_bytes = cursor
+            .read_u32_be()
+            .map_err(|_| ParseError::NotEnoughBytes(Field::MaxStorageBytes))?;
+
+        Ok(Some(max_storage_bytes))
+    } else {
+        Ok(None)
+    }
+}
@@ -0,0 +1,79 @@
+//!
+//! # `Defaults Section`
+//!
+//! +-------------+-------------+------------------+
+//! |             |             |                  |
+//! |  #Defaults  |  Default #1 |       ...        |
+//! |  (2 bytes)  |             |                  |
+//! |             |             |                  |
+//! +-------------+-------------+------------------+
+//!
+//!
+//! ## `Default`
+//!
+//! +-------------+----------------+-----------------+
+//! |             |                |                 |
+//! |   Var Id    |  Value Length  |      Value      |
+//! |  (4 bytes)  |    (1 byte)    |     (Blob)      |
+//! |             |                |                 |
+//! +-------------+----------------+-----------------+
+//!
+//!
+
+use std::io::Cursor;
+
+use svm_layout::Id;
+use svm_types::DefaultsSection;
+
+use crate::section::{SectionDecoder, SectionEncoder};
+use crate::{Field, ParseError, ReadExt, WriteExt};
+
+impl SectionEncoder for DefaultsSection {
+    fn encode(&self, w: &mut Vec<u8>) {
+        // `#Defaults`
+        let count = self.len();
+
+        assert!(count < std::u16::MAX as usize);
+
+        w.write_u16_be(count as u16);
+
+        // Encoding each `Default`
+        for (var_id, value) in self.defaults().iter() {
+            assert!(value.len() <= std::u8::MAX as usize);
+
+            w.write_u32_be(var_id.0);
+            w.write_byte(value.len() as u8);
+            w.write_bytes(value);
+        }
+    }
+}
+
+impl SectionDecoder for DefaultsSection {
+    fn decode(cursor: &mut Cursor<&[u8]>) -> Result<Self, ParseError> {
+        // `#Defaults`
+        let count = cursor
+            .read_u16_be()
+            .map_err(|_| ParseError::NotEnoughBytes(Field::DefaultsCount))?;
+
+        let mut section = DefaultsSection::with_capacity(count as usize);
+
+        // Decoding each `Default`
+        for _ in 0..count {
+            let var_id = cursor
+                .read_u32_be()
+                .map_err(|_| ParseError::NotEnoughBytes(Field::DefaultVarId))?;
+
+            let length = cursor
+                .read_byte()
+                .map_err(|_| ParseError::NotEnoughBytes(Field::DefaultValue))?;
+
+            let value = cursor
+                .read_bytes(length as usize)
+                .map_err(|_| ParseError::NotEnoughBytes(Field::DefaultValue))?;
+
+            section.push(Id(var_id), value);
+        }
+
+        Ok(section)
+    }
+}
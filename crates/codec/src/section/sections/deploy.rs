@@ -1,13 +1,15 @@
 //!
 //! # `Deploy Section`
 //!
-//! +------------------+----------------+---------------+-------------+
-//! |                  |                |               |             |
-//! |  Transaction Id  |     Layer      |   Deployer    |  Template   |
-//! |   (32 bytes)     |   (8 bytes)    |   (Address)   |  (Address)  |
-//! |                  |                |               |             |
-//! +------------------+----------------+---------------+-------------+
+//! +------------------+----------------+---------------+-------------+---------------+
+//! |                  |                |               |             |               |
+//! |  Transaction Id  |     Layer      |   Deployer    |  Template   |  Addr Scheme  |
+//! |   (32 bytes)     |   (8 bytes)    |   (Address)   |  (Address)  |     (u16)     |
+//! |                  |                |               |             |               |
+//! +------------------+----------------+---------------+-------------+---------------+
 //!
+//! The `Addr Scheme` is the id of the address derivation scheme used for computing the `Template` Address.
+//! It's absent from the sections encoded prior to its introduction (denoting scheme `0`).
 //!
 
 use std::io::Cursor;
@@ -23,6 +25,7 @@ impl SectionEncoder for DeploySection {
         encode_layer(self.layer(), w);
         encode_deployer(self.deployer(), w);
         encode_template(self.template(), w);
+        encode_addr_scheme(self.addr_scheme(), w);
     }
 }
 
@@ -31,7 +34,7 @@ fn encode_tx_id(tx_id: &TransactionId, w: &mut Vec<u8>) {
 }
 
 fn encode_layer(layer: Layer, w: &mut Vec<u8>) {
-    w.write_u64_be(layer.0);
+    w.write_u64_be(layer.as_u64());
 }
 
 fn encode_deployer(deployer: &Address, w: &mut Vec<u8>) {
@@ -42,14 +45,20 @@ fn encode_template(template: &TemplateAddr, w: &mut Vec<u8>) {
     w.write_template_addr(template);
 }
 
+fn encode_addr_scheme(addr_scheme: u16, w: &mut Vec<u8>) {
+    w.write_u16_be(addr_scheme);
+}
+
 impl SectionDecoder for DeploySection {
     fn decode(cursor: &mut Cursor<&[u8]>) -> Result<Self, ParseError> {
         let tx_id = decode_tx_id(cursor)?;
         let layer = decode_layer(cursor)?;
         let deployer = decode_deployer(cursor)?;
         let template = decode_template(cursor)?;
+        let addr_scheme = decode_addr_scheme(cursor)?;
 
-        let section = DeploySection::new(tx_id, layer, deployer, template);
+        let section =
+            DeploySection::new(tx_id, layer, deployer, template).with_addr_scheme(addr_scheme);
 
         Ok(section)
     }
@@ -65,7 +74,7 @@ fn decode_layer(cursor: &mut Cursor<&[u8]>) -> Result<Layer, ParseError> {
     let layer = cursor.read_u64_be();
 
     match layer {
-        Ok(layer) => Ok(Layer(layer)),
+        Ok(layer) => Ok(Layer::new(layer)),
         Err(..) => Err(ParseError::NotEnoughBytes(Field::Layer)),
     }
 }
@@ -81,3 +90,14 @@ fn decode_template(cursor: &mut Cursor<&[u8]>) -> Result<TemplateAddr, ParseErro
         .read_template_addr()
         .map_err(|_| ParseError::NotEnoughBytes(Field::TemplateAddr))
 }
+
+fn decode_addr_scheme(cursor: &mut Cursor<&[u8]>) -> Result<u16, ParseError> {
+    // A section encoded prior to the `Addr Scheme` ends right after the `Template` Address
+    if cursor.position() as usize == cursor.get_ref().len() {
+        return Ok(0);
+    }
+
+    cursor
+        .read_u16_be()
+        .map_err(|_| ParseError::NotEnoughBytes(Field::AddrScheme))
+}
@@ -1,7 +1,9 @@
 mod api;
 mod code;
 mod ctors;
+pub mod custom;
 mod data;
+mod defaults;
 mod deploy;
 mod header;
 mod schema;
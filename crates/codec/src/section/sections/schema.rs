@@ -1,77 +1,188 @@
-// use svm_layout::{Primitive, SymbolicVar, Type};
+//!
+//! # `Schema Section`
+//!
+//! +-----------+------------+---------+
+//! |           |            |         |
+//! |   #Vars   |   Var #1   |   ...   |
+//! | (2 bytes) |            |         |
+//! |           |            |         |
+//! +-----------+------------+---------+
+//!
+//!
+//! ## `Var`
+//!
+//! +-------------+------------+------------------+
+//! |             |            |                  |
+//! |   Var Id    |    Name    |       Type       |
+//! |  (4 bytes)  |  (String)  |  (see `Type`)    |
+//! |             |            |                  |
+//! +-------------+------------+------------------+
+//!
+//! The `Var Id` is the id of the variable within the `Layout`s of the `Data Section`.
+//!
+//!
+//! ## `Type`
+//!
+//! +-------------+-------------+---------------------------+
+//! |             |             |                           |
+//! |  Type Kind  |  Primitive  |      Array Length         |
+//! |  (1 byte)   |  (1 byte)   |  (4 bytes, arrays only)   |
+//! |             |             |                           |
+//! +-------------+-------------+---------------------------+
+//!
+//! * `Type Kind` - `0` for a `Primitive` and `1` for an `Array` (of `Primitive`s).
+//! * `Primitive` - `bool = 0`, `i8 = 1`, `u8 = 2`, `i16 = 3`, `u16 = 4`, `i32 = 5`,
+//!   `u32 = 6`, `i64 = 7`, `u64 = 8`, `amount = 9` and `address = 10`.
+//!
+//!
+
+use std::io::Cursor;
+
+use svm_layout::{Id, Primitive, SymbolicVar, Type};
 use svm_types::SchemaSection;
 
-// use crate::r#type;
 use crate::section::{SectionDecoder, SectionEncoder};
-use crate::ParseError;
-// use crate::{Field, ParseError, ReadExt, WriteExt};
+use crate::{Field, ParseError, ReadExt, WriteExt};
 
-impl SectionEncoder for SchemaSection {
-    fn encode(&self, _w: &mut Vec<u8>) {
-        todo!("will be implemented in a future PR...");
-        // let mut raw_section = Vec::new();
-
-        // encode_var_count(self, &mut raw_section);
+pub(super) const PRIMITIVE: u8 = 0;
+pub(super) const ARRAY: u8 = 1;
 
-        // for var in self.vars() {
-        //     encode_var(var, &mut raw_section);
-        // }
+impl SectionEncoder for SchemaSection {
+    fn encode(&self, w: &mut Vec<u8>) {
+        // `#Vars`
+        let count = self.var_count();
 
-        // let section = SectionHeader {
-        //     kind: SectionKind::Schema,
-        //     byte_size: raw_section.len() as u32,
-        // };
+        assert!(count < std::u16::MAX as usize);
 
-        // section::encode(&section, w);
+        w.write_u16_be(count as u16);
 
-        // w.write_bytes(&raw_section);
+        // Encoding each `Var`
+        for var in self.vars() {
+            encode_var(var, w);
+        }
     }
 }
 
 impl SectionDecoder for SchemaSection {
-    fn decode(_cursor: &mut std::io::Cursor<&[u8]>) -> Result<Self, ParseError> {
-        todo!("will be implemented in a future PR...");
+    fn decode(cursor: &mut Cursor<&[u8]>) -> Result<Self, ParseError> {
+        // `#Vars`
+        let count = cursor
+            .read_u16_be()
+            .map_err(|_| ParseError::NotEnoughBytes(Field::SymbolicVarCount))?;
+
+        let mut section = SchemaSection::with_capacity(count as usize);
+
+        // Decoding each `Var`
+        for _ in 0..count {
+            let var = decode_var(cursor)?;
+
+            section.push_var(var);
+        }
+
+        Ok(section)
     }
 }
 
-// fn encode_var(var: &SymbolicVar, w: &mut Vec<u8>) {
-//     encode_var_name(var, w);
+fn encode_var(var: &SymbolicVar, w: &mut Vec<u8>) {
+    // `Var Id`
+    w.write_u32_be(var.id().0);
 
-//     r#type::encode_type(var.ty(), w);
-// }
+    // `Name`
+    w.write_string(var.name());
 
-// fn encode_var_count(schema: &SchemaSection, w: &mut Vec<u8>) {
-//     w.write_u16_be(schema.vars().len() as u16);
-// }
+    // `Type`
+    encode_type(var.ty(), w);
+}
 
-// fn decode_var_count(cursor: &mut Cursor<&[u8]>) -> Result<u16, ParseError> {
-//     cursor
-//         .read_u16_be()
-//         .map_err(|_| ParseError::NotEnoughBytes(Field::SymbolicVarCount))
-// }
+fn decode_var(cursor: &mut Cursor<&[u8]>) -> Result<SymbolicVar, ParseError> {
+    let id = cursor
+        .read_u32_be()
+        .map_err(|_| ParseError::NotEnoughBytes(Field::SymbolicVarId))?;
 
-// fn decode_var(cursor: &mut Cursor<&[u8]>) -> Result<SymbolicVar, ParseError> {
-//     todo!("encode var id...");
+    let name = match cursor.read_string() {
+        Ok(Ok(name)) => name,
+        Ok(Err(..)) => return Err(ParseError::InvalidUTF8String(Field::SymbolicVarName)),
+        Err(..) => return Err(ParseError::NotEnoughBytes(Field::SymbolicVarName)),
+    };
 
-//     let id = Id(0);
-//     let name = decode_var_name(cursor)?;
-//     let ty = r#type::decode_type(cursor)?;
+    let ty = decode_type(cursor)?;
 
-//     let var = SymbolicVar::new(id, name, ty);
+    Ok(SymbolicVar::new(Id(id), name, ty))
+}
 
-//     Ok(var)
-// }
+fn encode_type(ty: &Type, w: &mut Vec<u8>) {
+    match ty {
+        Type::Primitive(primitive) => {
+            w.write_byte(PRIMITIVE);
+            w.write_byte(encode_primitive(*primitive));
+        }
+        Type::Array { primitive, length } => {
+            assert!(*length <= std::u32::MAX as usize);
+
+            w.write_byte(ARRAY);
+            w.write_byte(encode_primitive(*primitive));
+            w.write_u32_be(*length as u32);
+        }
+    }
+}
 
-// fn encode_var_name(var: &SymbolicVar, w: &mut Vec<u8>) {
-//     let name = var.name();
+fn decode_type(cursor: &mut Cursor<&[u8]>) -> Result<Type, ParseError> {
+    let mut read_byte = || {
+        cursor
+            .read_byte()
+            .map_err(|_| ParseError::NotEnoughBytes(Field::SymbolicVarType))
+    };
+
+    let kind = read_byte()?;
+    let primitive = decode_primitive(read_byte()?, Field::SymbolicVarType)?;
+
+    match kind {
+        PRIMITIVE => Ok(Type::Primitive(primitive)),
+        ARRAY => {
+            let length = cursor
+                .read_u32_be()
+                .map_err(|_| ParseError::NotEnoughBytes(Field::SymbolicVarType))?;
+
+            Ok(Type::Array {
+                primitive,
+                length: length as usize,
+            })
+        }
+        _ => Err(ParseError::NotSupported(Field::SymbolicVarType)),
+    }
+}
 
-//     w.write_string(name);
-// }
+pub(super) fn encode_primitive(primitive: Primitive) -> u8 {
+    match primitive {
+        Primitive::Bool => 0,
+        Primitive::I8 => 1,
+        Primitive::U8 => 2,
+        Primitive::I16 => 3,
+        Primitive::U16 => 4,
+        Primitive::I32 => 5,
+        Primitive::U32 => 6,
+        Primitive::I64 => 7,
+        Primitive::U64 => 8,
+        Primitive::Amount => 9,
+        Primitive::Address => 10,
+    }
+}
 
-// fn decode_var_name(cursor: &mut Cursor<&[u8]>) -> Result<String, ParseError> {
-//     match cursor.read_string() {
-//         Ok(Ok(name)) => Ok(name),
-//         Ok(Err(..)) => Err(ParseError::InvalidUTF8String(Field::SymbolicVarName)),
-//         Err(..) => Err(ParseError::NotEnoughBytes(Field::SymbolicVarName)),
-//     }
-// }
+pub(super) fn decode_primitive(raw: u8, field: Field) -> Result<Primitive, ParseError> {
+    let primitive = match raw {
+        0 => Primitive::Bool,
+        1 => Primitive::I8,
+        2 => Primitive::U8,
+        3 => Primitive::I16,
+        4 => Primitive::U16,
+        5 => Primitive::I32,
+        6 => Primitive::U32,
+        7 => Primitive::I64,
+        8 => Primitive::U64,
+        9 => Primitive::Amount,
+        10 => Primitive::Address,
+        _ => return Err(ParseError::NotSupported(field)),
+    };
+
+    Ok(primitive)
+}
@@ -0,0 +1,248 @@
+//! Matching a binary `Calldata` against the function signatures listed by the `API Section`.
+//!
+//! A `Template` may export several overloads of a `ctor`: `ctor`s sharing the same `name`
+//! (but of distinct Wasm names), told apart by the types of their params.
+//! Since a binary `Calldata` is self-describing (each value is prefixed by its type),
+//! the overload to run is resolved by the `Calldata` given to it (see [`resolve_ctor`]).
+
+use svm_abi_decoder::{Cursor, Decoder};
+use svm_layout::Primitive;
+use svm_sdk_std::Result as SdkResult;
+use svm_sdk_types::value::{Composite, Primitive as SdkPrimitive, Value};
+use svm_types::{ApiFunc, ApiParam, ApiSection, ApiType};
+
+use thiserror::Error;
+
+/// The reason for failing to resolve a `ctor` (see [`resolve_ctor`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum OverloadError {
+    /// There is no `ctor` of the given name.
+    #[error("The given function is not a `ctor`.")]
+    NotFound,
+
+    /// The `Calldata` doesn't match the signature of the `ctor` (given by its Wasm name).
+    #[error("The `Calldata` doesn't match the signature of the `ctor`.")]
+    Mismatch,
+
+    /// The `Calldata` matches none of the overloads of the `ctor`.
+    #[error("The `Calldata` matches none of the overloads of the `ctor`.")]
+    NoMatch,
+
+    /// The `Calldata` matches more than a single overload of the `ctor`.
+    #[error("The `Calldata` matches more than a single overload of the `ctor`.")]
+    Ambiguous,
+}
+
+/// Returns whether the values of the binary `calldata` are of the types of `params` (in order).
+///
+/// * A primitive value matches its own `Primitive` type (a `String` matches none).
+/// * An array matches an array type of the same `Primitive`, as long as it has no more items than its length
+///   (an empty array matches any array type).
+/// * A struct type matches any composite value (the `Calldata` doesn't tell the fields of a struct).
+pub fn calldata_matches(params: &[ApiParam], calldata: &[u8]) -> bool {
+    let decoder = Decoder::new();
+    let mut cursor = Cursor::new(calldata);
+
+    for param in params {
+        if cursor.is_eof() {
+            return false;
+        }
+
+        match decoder.decode_value(&mut cursor) {
+            SdkResult::Ok(value) if value_matches(&param.ty, &value) => (),
+            _ => return false,
+        }
+    }
+
+    cursor.is_eof()
+}
+
+/// Resolves the `ctor` listed by `api` to run for `name` given `calldata`.
+///
+/// The `name` is either:
+///
+/// * The Wasm name of a `ctor` - which is returned provided the `calldata` matches its signature.
+/// * The name shared by the overloads of a `ctor` - the single overload matching the `calldata` is returned.
+pub fn resolve_ctor<'a>(
+    api: &'a ApiSection,
+    name: &str,
+    calldata: &[u8],
+) -> Result<&'a ApiFunc, OverloadError> {
+    let ctors = || api.funcs().iter().filter(|func| func.is_ctor);
+
+    if let Some(ctor) = ctors().find(|ctor| ctor.wasm_name == name) {
+        return if calldata_matches(&ctor.params, calldata) {
+            Ok(ctor)
+        } else {
+            Err(OverloadError::Mismatch)
+        };
+    }
+
+    let mut overloads = ctors().filter(|ctor| ctor.name == name).peekable();
+
+    if overloads.peek().is_none() {
+        return Err(OverloadError::NotFound);
+    }
+
+    let mut matching = overloads.filter(|ctor| calldata_matches(&ctor.params, calldata));
+
+    match (matching.next(), matching.next()) {
+        (Some(ctor), None) => Ok(ctor),
+        (None, _) => Err(OverloadError::NoMatch),
+        (Some(..), Some(..)) => Err(OverloadError::Ambiguous),
+    }
+}
+
+fn value_matches(ty: &ApiType, value: &Value) -> bool {
+    match (ty, value) {
+        (ApiType::Primitive(primitive), Value::Primitive(value)) => {
+            primitive_matches(*primitive, value)
+        }
+        (ApiType::Array { primitive, length }, Value::Composite(Composite::Vec(items))) => {
+            items.len() <= *length
+                && items.iter().all(|item| match item {
+                    Value::Primitive(item) => primitive_matches(*primitive, item),
+                    Value::Composite(..) => false,
+                })
+        }
+        (ApiType::Struct(..), Value::Composite(..)) => true,
+        _ => false,
+    }
+}
+
+fn primitive_matches(primitive: Primitive, value: &SdkPrimitive) -> bool {
+    matches!(
+        (primitive, value),
+        (Primitive::Bool, SdkPrimitive::Bool(..))
+            | (Primitive::I8, SdkPrimitive::I8(..))
+            | (Primitive::U8, SdkPrimitive::U8(..))
+            | (Primitive::I16, SdkPrimitive::I16(..))
+            | (Primitive::U16, SdkPrimitive::U16(..))
+            | (Primitive::I32, SdkPrimitive::I32(..))
+            | (Primitive::U32, SdkPrimitive::U32(..))
+            | (Primitive::I64, SdkPrimitive::I64(..))
+            | (Primitive::U64, SdkPrimitive::U64(..))
+            | (Primitive::Amount, SdkPrimitive::Amount(..))
+            | (Primitive::Address, SdkPrimitive::Address(..))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::api::json;
+
+    fn calldata(abi: serde_json::Value, data: serde_json::Value) -> Vec<u8> {
+        let json = json!({ "abi": abi, "data": data }).to_string();
+        let encoded = json::encode_inputdata(&json).unwrap();
+
+        hex::decode(encoded["data"].as_str().unwrap()).unwrap()
+    }
+
+    fn param(ty: ApiType) -> ApiParam {
+        ApiParam {
+            name: "param".to_string(),
+            ty,
+        }
+    }
+
+    fn ctor(wasm_name: &str, params: Vec<ApiType>) -> ApiFunc {
+        ApiFunc {
+            name: "init".to_string(),
+            wasm_name: wasm_name.to_string(),
+            is_ctor: true,
+            is_fundable: false,
+            params: params.into_iter().map(param).collect(),
+            returns: Vec::new(),
+            doc: String::new(),
+        }
+    }
+
+    #[test]
+    fn calldata_matches_params() {
+        let params = vec![
+            param(ApiType::Primitive(Primitive::U32)),
+            param(ApiType::Array {
+                primitive: Primitive::Bool,
+                length: 2,
+            }),
+        ];
+
+        assert!(calldata_matches(
+            &params,
+            &calldata(json!(["u32", ["bool"]]), json!([10, [true, false]]))
+        ));
+        assert!(calldata_matches(
+            &params,
+            &calldata(json!(["u32", ["bool"]]), json!([10, []]))
+        ));
+
+        // Too many items
+        assert!(!calldata_matches(
+            &params,
+            &calldata(json!(["u32", ["bool"]]), json!([10, [true, false, true]]))
+        ));
+
+        // Mismatching types, missing values and extra values
+        assert!(!calldata_matches(
+            &params,
+            &calldata(json!(["u64", ["bool"]]), json!([10, [true]]))
+        ));
+        assert!(!calldata_matches(
+            &params,
+            &calldata(json!(["u32"]), json!([10]))
+        ));
+        assert!(!calldata_matches(
+            &params,
+            &calldata(json!(["u32", ["bool"], "u8"]), json!([10, [true], 1]))
+        ));
+
+        // Invalid `Calldata`
+        assert!(!calldata_matches(&params, &[0xFF]));
+        assert!(calldata_matches(&[], &[]));
+    }
+
+    #[test]
+    fn resolve_ctor_overloads() {
+        let api = ApiSection::new()
+            .with_func(ctor("init_u32", vec![ApiType::Primitive(Primitive::U32)]))
+            .with_func(ctor("init_bool", vec![ApiType::Primitive(Primitive::Bool)]))
+            .with_func(ctor(
+                "init_addr",
+                vec![ApiType::Primitive(Primitive::Address)],
+            ))
+            .with_func(ctor(
+                "init_addr_too",
+                vec![ApiType::Primitive(Primitive::Address)],
+            ));
+
+        let u32_calldata = calldata(json!(["u32"]), json!([10]));
+        let bool_calldata = calldata(json!(["bool"]), json!([true]));
+        let addr_calldata = calldata(json!(["address"]), json!(["10".repeat(20)]));
+
+        let resolve = |name, calldata| resolve_ctor(&api, name, calldata).map(|f| &f.wasm_name);
+
+        assert_eq!(resolve("init", &u32_calldata).unwrap(), "init_u32");
+        assert_eq!(resolve("init", &bool_calldata).unwrap(), "init_bool");
+        assert_eq!(resolve("init", &[]), Err(OverloadError::NoMatch));
+        assert_eq!(
+            resolve("init", &addr_calldata),
+            Err(OverloadError::Ambiguous)
+        );
+        assert_eq!(
+            resolve("other", &u32_calldata),
+            Err(OverloadError::NotFound)
+        );
+
+        // By the Wasm name
+        assert_eq!(resolve("init_bool", &bool_calldata).unwrap(), "init_bool");
+        assert_eq!(resolve("init_addr", &addr_calldata).unwrap(), "init_addr");
+        assert_eq!(
+            resolve("init_bool", &u32_calldata),
+            Err(OverloadError::Mismatch)
+        );
+    }
+}
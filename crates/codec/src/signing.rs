@@ -0,0 +1,150 @@
+//! The canonical payload signed by the `principal` of a transaction.
+//!
+//! ```text
+//!
+//!  +-----------+-------------+----------------+------------------+
+//!  |           |             |                |                  |
+//!  |  Version  |  Tx Type    |    Envelope    |     Message      |
+//!  |   (u8)    |   (u8)      |   (44 bytes)   |  (binary `Blob`) |
+//!  |           |             |                |                  |
+//!  +-----------+-------------+----------------+------------------+
+//!
+//! ```
+//!
+//! * `Version` - the version of the payload layout (see [`VERSION`]).
+//! * `Tx Type` - `0` for `Deploy`, `1` for `Spawn` and `2` for `Call` (see [`TxType`]).
+//! * `Envelope` - the binary [`Envelope`] (see [`crate::envelope`]).
+//! * `Message` - the binary message, as given to the `Runtime`.
+//!
+//! The signature covers the [`hash`] of the payload (Blake3, 32 bytes).
+//!
+//! Both wallets (through `api::json::signing_payload`) and signature verifiers should derive
+//! the payload using this module only, so that they can never disagree on the signed bytes.
+//!
+//! With the `sensitive` feature enabled, [`zeroizing_payload`] derives a payload that is zeroed once dropped,
+//! and [`verify_hash`] compares hashes in constant time.
+
+use svm_hash::{Blake3Hasher, Hasher};
+#[cfg(feature = "sensitive")]
+use svm_types::sensitive::{self, Zeroizing};
+use svm_types::Envelope;
+
+/// The version of the signing payload layout.
+pub const VERSION: u8 = 0;
+
+/// The type of a signed transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TxType {
+    /// A `Deploy Template` transaction.
+    Deploy,
+
+    /// A `Spawn Account` transaction.
+    Spawn,
+
+    /// A `Call Account` transaction.
+    Call,
+}
+
+impl TxType {
+    /// The byte identifying the [`TxType`] within the signing payload.
+    pub fn as_u8(self) -> u8 {
+        match self {
+            TxType::Deploy => 0,
+            TxType::Spawn => 1,
+            TxType::Call => 2,
+        }
+    }
+}
+
+/// Returns the number of bytes of the signing payload of a `message_len` bytes message.
+pub const fn byte_size(message_len: usize) -> usize {
+    1 + 1 + crate::envelope::byte_size() + message_len
+}
+
+/// Returns the signing payload of the binary `message` of type `tx_type` sent under `envelope`.
+pub fn payload(tx_type: TxType, envelope: &Envelope, message: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(byte_size(message.len()));
+
+    buf.push(VERSION);
+    buf.push(tx_type.as_u8());
+    crate::envelope::encode(envelope, &mut buf);
+    buf.extend_from_slice(message);
+
+    buf
+}
+
+/// Returns the hash of a signing `payload` (i.e the signed bytes).
+pub fn hash(payload: &[u8]) -> [u8; 32] {
+    Blake3Hasher::hash(payload)
+}
+
+/// Same as [`payload`], but the returned payload is zeroed once dropped.
+///
+/// The payload is allocated upfront with its exact size, so no stale copies are left behind while building it.
+#[cfg(feature = "sensitive")]
+pub fn zeroizing_payload(
+    tx_type: TxType,
+    envelope: &Envelope,
+    message: &[u8],
+) -> Zeroizing<Vec<u8>> {
+    Zeroizing::new(payload(tx_type, envelope, message))
+}
+
+/// Returns whether the [`hash`] of `payload` equals `expected` (compared in constant time).
+#[cfg(feature = "sensitive")]
+pub fn verify_hash(payload: &[u8], expected: &[u8; 32]) -> bool {
+    let mut actual = hash(payload);
+    let equal = sensitive::ct_eq(&actual, expected);
+
+    sensitive::zeroize(&mut actual);
+    equal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_types::{Address, Gas};
+
+    fn envelope() -> Envelope {
+        Envelope::new(Address::repeat(0x10), 10, Gas::with(1000), 1)
+    }
+
+    #[test]
+    fn signing_payload_layout() {
+        let message = vec![0xC0, 0xDE];
+        let payload = payload(TxType::Spawn, &envelope(), &message);
+
+        let mut envelope_bytes = Vec::new();
+        crate::envelope::encode(&envelope(), &mut envelope_bytes);
+
+        assert_eq!(payload.len(), byte_size(message.len()));
+        assert_eq!(payload[0], VERSION);
+        assert_eq!(payload[1], 1);
+        assert_eq!(&payload[2..46], &envelope_bytes[..]);
+        assert_eq!(&payload[46..], &message[..]);
+    }
+
+    #[test]
+    fn signing_hash_binds_tx_type() {
+        let message = vec![0xC0, 0xDE];
+
+        let spawn = hash(&payload(TxType::Spawn, &envelope(), &message));
+        let call = hash(&payload(TxType::Call, &envelope(), &message));
+
+        assert_ne!(spawn, call);
+    }
+
+    #[cfg(feature = "sensitive")]
+    #[test]
+    fn signing_verify_hash() {
+        let message = vec![0xC0, 0xDE];
+        let payload = zeroizing_payload(TxType::Call, &envelope(), &message);
+
+        let expected = hash(&payload);
+        assert!(verify_hash(&payload, &expected));
+
+        let other = hash(&self::payload(TxType::Spawn, &envelope(), &message));
+        assert!(!verify_hash(&payload, &other));
+    }
+}
@@ -12,24 +12,69 @@
 //!  |   Ctor    |          CallData            |
 //!  |  (String) |           (Blob)             |
 //!  |           |                              |
-//!  +-----------+------------------------------+
+//!  +-----------+-------------+----------------+
+//!  |           |             |                |
+//!  | Has Call  |  Function   |    CallData    |
+//!  |  (bool)   |  (String)   |     (Blob)     |
+//!  |           | (optional)  |   (optional)   |
+//!  |           |             |                |
+//!  +-----------+-------------+----------------+
 //!
 //! ```
+//!
+//! From version `2` onwards, the length prefixes of both `CallData`s are
+//! variable-length integers (a single byte length for earlier versions).
+//!
+//! From version `3` onwards (see [`SPAWN_CALL_VERSION`]), the message ends with `Has Call` (`0` or `1`).
+//! The trailing `Function` and `CallData` exist only when `Has Call` is set (see [`SpawnCall`]).
+//! Messages of earlier versions end right after the `ctor`'s `CallData` (having no [`SpawnCall`]).
 
 use std::io::Cursor;
 
-use svm_types::{Account, SpawnAccount, TemplateAddr};
+use svm_types::{Account, SpawnAccount, SpawnCall, TemplateAddr};
 
+use crate::error::validate_string;
+use crate::limits::{self, MessageFamily};
 use crate::{inputdata, version};
-use crate::{Field, ParseError, ReadExt, WriteExt};
+use crate::{EncodeError, Field, ParseError, ReadExt, WriteExt};
+
+/// The messages format version from which a [`SpawnAccount`] may carry a [`SpawnCall`].
+pub const SPAWN_CALL_VERSION: u16 = 3;
 
 /// Encodes a binary [`SpawnAccount`] transaction.
-pub fn encode(spawn: &SpawnAccount, w: &mut Vec<u8>) {
+///
+/// Fails (writing nothing) when a field of `spawn` can't be encoded, i.e a name exceeding `255` bytes,
+/// an `InputData` exceeding the size its version can hold, or a [`SpawnCall`] carried by
+/// a `spawn` whose version precedes [`SPAWN_CALL_VERSION`].
+pub fn encode(spawn: &SpawnAccount, w: &mut impl WriteExt) -> Result<(), EncodeError> {
+    validate(spawn)?;
+
     encode_version(spawn, w);
     encode_template(spawn, w);
     encode_name(spawn, w);
     encode_ctor(spawn, w);
     encode_ctor_calldata(spawn, w);
+    encode_call(spawn, w);
+
+    Ok(())
+}
+
+/// Validates that all the fields of `spawn` can be encoded (see [`encode`]).
+fn validate(spawn: &SpawnAccount) -> Result<(), EncodeError> {
+    validate_string(spawn.account_name(), Field::Name)?;
+    validate_string(spawn.ctor_name(), Field::Ctor)?;
+    inputdata::validate_inputdata(spawn.version, &spawn.calldata, Field::InputData)?;
+
+    if let Some(call) = spawn.call() {
+        if spawn.version < SPAWN_CALL_VERSION {
+            return Err(EncodeError::NotSupported(Field::Function));
+        }
+
+        validate_string(&call.func_name, Field::Function)?;
+        inputdata::validate_inputdata(spawn.version, &call.calldata, Field::InputData)?;
+    }
+
+    Ok(())
 }
 
 /// Parsing a binary [`SpawnAccount`] transaction.
@@ -37,11 +82,14 @@ pub fn encode(spawn: &SpawnAccount, w: &mut Vec<u8>) {
 /// Returns the parsed [`SpawnAccount`],
 /// On failure, returns [`ParseError`].
 pub fn decode(cursor: &mut Cursor<&[u8]>) -> Result<SpawnAccount, ParseError> {
+    limits::check_buffer(MessageFamily::Spawn, cursor)?;
+
     let version = decode_version(cursor)?;
     let template_addr = decode_template(cursor)?;
     let name = decode_name(cursor)?;
     let ctor_name = decode_ctor(cursor)?;
-    let calldata = decode_ctor_calldata(cursor)?;
+    let calldata = decode_ctor_calldata(version, cursor)?;
+    let call = decode_call(version, cursor)?;
 
     let account = Account {
         name,
@@ -53,6 +101,7 @@ pub fn decode(cursor: &mut Cursor<&[u8]>) -> Result<SpawnAccount, ParseError> {
         account,
         ctor_name,
         calldata,
+        call,
     };
 
     Ok(spawn)
@@ -60,45 +109,60 @@ pub fn decode(cursor: &mut Cursor<&[u8]>) -> Result<SpawnAccount, ParseError> {
 
 /// Encoders
 
-fn encode_version(spawn: &SpawnAccount, w: &mut Vec<u8>) {
+fn encode_version(spawn: &SpawnAccount, w: &mut impl WriteExt) {
     let v = &spawn.version;
     version::encode_version(*v, w);
 }
 
-fn encode_name(spawn: &SpawnAccount, w: &mut Vec<u8>) {
+fn encode_name(spawn: &SpawnAccount, w: &mut impl WriteExt) {
     let name = spawn.account_name();
     w.write_string(name);
 }
 
-fn encode_template(spawn: &SpawnAccount, w: &mut Vec<u8>) {
+fn encode_template(spawn: &SpawnAccount, w: &mut impl WriteExt) {
     let template = spawn.template_addr();
     w.write_template_addr(template);
 }
 
-fn encode_ctor(spawn: &SpawnAccount, w: &mut Vec<u8>) {
+fn encode_ctor(spawn: &SpawnAccount, w: &mut impl WriteExt) {
     let ctor = spawn.ctor_name();
     w.write_string(ctor);
 }
 
-fn encode_ctor_calldata(spawn: &SpawnAccount, w: &mut Vec<u8>) {
+fn encode_ctor_calldata(spawn: &SpawnAccount, w: &mut impl WriteExt) {
     let calldata = &*spawn.calldata;
-    inputdata::encode_inputdata(calldata, w);
+    inputdata::encode_inputdata(spawn.version, calldata, w);
+}
+
+fn encode_call(spawn: &SpawnAccount, w: &mut impl WriteExt) {
+    if spawn.version < SPAWN_CALL_VERSION {
+        return;
+    }
+
+    match spawn.call() {
+        Some(call) => {
+            w.write_bool(true);
+            w.write_string(&call.func_name);
+            inputdata::encode_inputdata(spawn.version, &call.calldata, w);
+        }
+        None => w.write_bool(false),
+    }
 }
 
 /// Decoders
 
 #[inline]
-fn decode_version(cursor: &mut Cursor<&[u8]>) -> Result<u16, ParseError> {
+pub(crate) fn decode_version(cursor: &mut Cursor<&[u8]>) -> Result<u16, ParseError> {
     version::decode_version(cursor)
 }
 
-fn decode_template(cursor: &mut Cursor<&[u8]>) -> Result<TemplateAddr, ParseError> {
+pub(crate) fn decode_template(cursor: &mut Cursor<&[u8]>) -> Result<TemplateAddr, ParseError> {
     cursor
         .read_template_addr()
         .map_err(|_| ParseError::NotEnoughBytes(Field::Address))
 }
 
-fn decode_name(cursor: &mut Cursor<&[u8]>) -> Result<String, ParseError> {
+pub(crate) fn decode_name(cursor: &mut Cursor<&[u8]>) -> Result<String, ParseError> {
     match cursor.read_string() {
         Ok(Ok(name)) => Ok(name),
         Ok(Err(..)) => Err(ParseError::InvalidUTF8String(Field::Name)),
@@ -106,7 +170,7 @@ fn decode_name(cursor: &mut Cursor<&[u8]>) -> Result<String, ParseError> {
     }
 }
 
-fn decode_ctor(cursor: &mut Cursor<&[u8]>) -> Result<String, ParseError> {
+pub(crate) fn decode_ctor(cursor: &mut Cursor<&[u8]>) -> Result<String, ParseError> {
     match cursor.read_string() {
         Ok(Ok(ctor)) => Ok(ctor),
         Ok(Err(..)) => Err(ParseError::InvalidUTF8String(Field::Ctor)),
@@ -114,8 +178,42 @@ fn decode_ctor(cursor: &mut Cursor<&[u8]>) -> Result<String, ParseError> {
     }
 }
 
-fn decode_ctor_calldata(cursor: &mut Cursor<&[u8]>) -> Result<Vec<u8>, ParseError> {
-    inputdata::decode_inputdata(cursor)
+pub(crate) fn decode_ctor_calldata(
+    version: u16,
+    cursor: &mut Cursor<&[u8]>,
+) -> Result<Vec<u8>, ParseError> {
+    inputdata::decode_inputdata(version, cursor)
+}
+
+pub(crate) fn decode_call(
+    version: u16,
+    cursor: &mut Cursor<&[u8]>,
+) -> Result<Option<SpawnCall>, ParseError> {
+    if version < SPAWN_CALL_VERSION {
+        return Ok(None);
+    }
+
+    let has_call = cursor
+        .read_byte()
+        .map_err(|_| ParseError::NotEnoughBytes(Field::Function))?;
+
+    match has_call {
+        0 => return Ok(None),
+        1 => (),
+        _ => return Err(ParseError::NonCanonical(Field::Function)),
+    }
+
+    let func_name = match cursor.read_string() {
+        Ok(Ok(func)) => func,
+        Ok(Err(..)) => return Err(ParseError::InvalidUTF8String(Field::Function)),
+        Err(..) => return Err(ParseError::NotEnoughBytes(Field::Function)),
+    };
+    let calldata = inputdata::decode_inputdata(version, cursor)?;
+
+    Ok(Some(SpawnCall {
+        func_name,
+        calldata,
+    }))
 }
 
 #[cfg(test)]
@@ -134,10 +232,129 @@ mod tests {
             },
             ctor_name: "initialize".to_string(),
             calldata: vec![0x10, 0x20, 0x30],
+            call: None,
+        };
+
+        let mut bytes = Vec::new();
+        encode(&spawn, &mut bytes).unwrap();
+
+        let mut cursor = Cursor::new(&bytes[..]);
+        let decoded = decode(&mut cursor).unwrap();
+
+        assert_eq!(spawn, decoded);
+    }
+
+    #[test]
+    fn decode_baseline_spawn() {
+        // A version `0` message ends right after the `ctor`'s `CallData`
+        let mut bytes = Vec::new();
+        bytes.write_u16_be(0);
+        bytes.write_template_addr(&TemplateAddr::of("@template"));
+        bytes.write_string("@account");
+        bytes.write_string("initialize");
+        bytes.write_byte(3);
+        bytes.write_bytes(&[0x10, 0x20, 0x30]);
+
+        let decoded = decode(&mut Cursor::new(&bytes[..])).unwrap();
+
+        assert_eq!(decoded.ctor_name, "initialize");
+        assert_eq!(decoded.calldata, vec![0x10, 0x20, 0x30]);
+        assert_eq!(decoded.call, None);
+
+        let mut encoded = Vec::new();
+        encode(&decoded, &mut encoded).unwrap();
+
+        assert_eq!(encoded, bytes);
+    }
+
+    #[test]
+    fn decode_spawn_non_canonical_has_call() {
+        let spawn = SpawnAccount {
+            version: SPAWN_CALL_VERSION,
+            account: Account {
+                name: "@account".to_string(),
+                template_addr: TemplateAddr::of("@template"),
+            },
+            ctor_name: "initialize".to_string(),
+            calldata: vec![],
+            call: None,
+        };
+
+        let mut bytes = Vec::new();
+        encode(&spawn, &mut bytes).unwrap();
+        assert_eq!(bytes.last(), Some(&0));
+
+        *bytes.last_mut().unwrap() = 2;
+
+        let err = decode(&mut Cursor::new(&bytes[..])).unwrap_err();
+        assert_eq!(err, ParseError::NonCanonical(Field::Function));
+    }
+
+    #[test]
+    fn encode_spawn_call_requires_version() {
+        let spawn = SpawnAccount {
+            version: 0,
+            account: Account {
+                name: "@account".to_string(),
+                template_addr: TemplateAddr::of("@template"),
+            },
+            ctor_name: "initialize".to_string(),
+            calldata: vec![],
+            call: Some(SpawnCall {
+                func_name: "setup".to_string(),
+                calldata: vec![],
+            }),
+        };
+
+        let mut bytes = Vec::new();
+        let err = encode(&spawn, &mut bytes).unwrap_err();
+
+        assert_eq!(err, EncodeError::NotSupported(Field::Function));
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    fn encode_spawn_name_too_long() {
+        let spawn = SpawnAccount {
+            version: 0,
+            account: Account {
+                name: "a".repeat(256),
+                template_addr: TemplateAddr::of("@template"),
+            },
+            ctor_name: "initialize".to_string(),
+            calldata: vec![],
+            call: None,
+        };
+
+        let err = encode(&spawn, &mut Vec::new()).unwrap_err();
+
+        assert_eq!(
+            err,
+            EncodeError::TooManyBytes {
+                field: Field::Name,
+                max: 255
+            }
+        );
+    }
+
+    #[test]
+    fn encode_decode_spawn_with_call() {
+        let spawn = SpawnAccount {
+            version: SPAWN_CALL_VERSION,
+            account: Account {
+                name: "@account".to_string(),
+                template_addr: TemplateAddr::of("@template"),
+            },
+            ctor_name: "initialize".to_string(),
+            calldata: vec![0x10, 0x20, 0x30],
+            call: Some(SpawnCall {
+                func_name: "setup".to_string(),
+                calldata: vec![0x40, 0x50],
+            }),
         };
 
         let mut bytes = Vec::new();
-        encode(&spawn, &mut bytes);
+        encode(&spawn, &mut bytes).unwrap();
 
         let mut cursor = Cursor::new(&bytes[..]);
         let decoded = decode(&mut cursor).unwrap();
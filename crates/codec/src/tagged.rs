@@ -0,0 +1,250 @@
+//! Tagged fields: additive evolution of the binary formats.
+//!
+//! A `Tagged Fields` block ends a binary format that is expected to gain new fields over time
+//! (see the [`Envelope`](crate::envelope) and the [`Receipts`](crate::receipt)).
+//! Since each field is prefixed by its tag and length, a decoder can skip the fields it doesn't know.
+//!
+//! ```text
+//!
+//!  +-----------+----------------------------------------------+---------+
+//!  |           |             |              |                 |         |
+//!  |  #Fields  |  Field Tag  | Value Length |      Value      |  . . .  |
+//!  | (uvarint) |  (uvarint)  |  (uvarint)   |     (Blob)      |         |
+//!  |           |             |              |                 |         |
+//!  +-----------+----------------------------------------------+---------+
+//!
+//! ```
+//!
+//! The rules:
+//!
+//! * The tags are strictly increasing (so that each field appears at most once,
+//!   and a block has a single valid encoding).
+//!
+//! * An odd tag denotes an optional field. A decoder that doesn't know it skips its value
+//!   (while keeping it as is, see [`decode_tagged`]).
+//!
+//! * An even tag denotes a required field, which the other fields can't be understood without.
+//!   A decoder that doesn't know it fails.
+//!
+//! As such, introducing a new optional field amounts to picking the next free odd tag,
+//! and doesn't require a new format version (older decoders keep working).
+
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+use crate::{Field, ParseError, ReadExt, WriteExt};
+
+/// Returns whether `tag` denotes an optional field (i.e, it's odd).
+pub fn is_optional(tag: u64) -> bool {
+    tag % 2 == 1
+}
+
+/// The fields of a `Tagged Fields` block (ordered by their tags).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaggedFields {
+    fields: BTreeMap<u64, Vec<u8>>,
+}
+
+impl TaggedFields {
+    /// New empty instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of fields.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Whether there are no fields.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Sets the `value` of field `tag`, returning its previous value (if any).
+    pub fn insert(&mut self, tag: u64, value: Vec<u8>) -> Option<Vec<u8>> {
+        self.fields.insert(tag, value)
+    }
+
+    /// Returns the value of field `tag`.
+    pub fn get(&self, tag: u64) -> Option<&[u8]> {
+        self.fields.get(&tag).map(|value| &value[..])
+    }
+
+    /// Removes field `tag`, returning its value (if any).
+    pub fn remove(&mut self, tag: u64) -> Option<Vec<u8>> {
+        self.fields.remove(&tag)
+    }
+
+    /// Returns an iterator over the fields `(tag, value)` (ordered by their tags).
+    pub fn iter(&self) -> impl Iterator<Item = (u64, &[u8])> {
+        self.fields.iter().map(|(tag, value)| (*tag, &value[..]))
+    }
+}
+
+/// Encodes a `Tagged Fields` block.
+pub fn encode_tagged(fields: &TaggedFields, w: &mut impl WriteExt) {
+    w.write_uvarint(fields.len() as u64);
+
+    for (tag, value) in fields.iter() {
+        w.write_uvarint(tag);
+        w.write_uvarint(value.len() as u64);
+        w.write_bytes(value);
+    }
+}
+
+/// Decodes a `Tagged Fields` block, given the `known` tags of the decoded format.
+///
+/// The unknown optional fields are kept as is (so that a re-encoding preserves them),
+/// while an unknown required field fails the decoding.
+pub fn decode_tagged(
+    cursor: &mut Cursor<&[u8]>,
+    known: &[u64],
+) -> Result<TaggedFields, ParseError> {
+    let count = cursor
+        .read_uvarint()
+        .map_err(|_| ParseError::NotEnoughBytes(Field::TaggedFieldsCount))?;
+
+    let mut fields = TaggedFields::new();
+    let mut prev: Option<u64> = None;
+
+    for _ in 0..count {
+        let tag = cursor
+            .read_uvarint()
+            .map_err(|_| ParseError::NotEnoughBytes(Field::TaggedFieldTag))?;
+
+        if prev.map_or(false, |prev| tag <= prev) {
+            return Err(ParseError::NonCanonical(Field::TaggedFieldTag));
+        }
+
+        if !is_optional(tag) && !known.contains(&tag) {
+            return Err(ParseError::NotSupported(Field::TaggedFieldTag));
+        }
+
+        let length = cursor
+            .read_uvarint()
+            .map_err(|_| ParseError::NotEnoughBytes(Field::TaggedFieldLength))?;
+
+        let remaining = (cursor.get_ref().len() as u64).saturating_sub(cursor.position());
+
+        if length > remaining {
+            return Err(ParseError::NotEnoughBytes(Field::TaggedFieldValue));
+        }
+
+        let value = cursor
+            .read_bytes(length as usize)
+            .map_err(|_| ParseError::NotEnoughBytes(Field::TaggedFieldValue))?;
+
+        fields.insert(tag, value);
+        prev = Some(tag);
+    }
+
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(entries: &[(u64, &[u8])]) -> TaggedFields {
+        let mut fields = TaggedFields::new();
+
+        for (tag, value) in entries {
+            fields.insert(*tag, value.to_vec());
+        }
+
+        fields
+    }
+
+    fn decode(bytes: &[u8], known: &[u64]) -> Result<TaggedFields, ParseError> {
+        let mut cursor = Cursor::new(bytes);
+
+        decode_tagged(&mut cursor, known)
+    }
+
+    #[test]
+    fn tagged_fields_encode_decode() {
+        let fields = fields(&[(1, b"optional"), (2, b"required"), (300, &[])]);
+
+        let mut bytes = Vec::new();
+        encode_tagged(&fields, &mut bytes);
+
+        assert_eq!(&bytes[..3], &[3, 1, 8]);
+        assert_eq!(decode(&bytes, &[2, 300]), Ok(fields));
+    }
+
+    #[test]
+    fn tagged_fields_empty() {
+        let mut bytes = Vec::new();
+        encode_tagged(&TaggedFields::new(), &mut bytes);
+
+        assert_eq!(bytes, vec![0]);
+        assert_eq!(decode(&bytes, &[]), Ok(TaggedFields::new()));
+    }
+
+    #[test]
+    fn tagged_fields_unknown_optional_are_kept() {
+        let fields = fields(&[(2, &[0xAB]), (7, &[0x10, 0x20])]);
+
+        let mut bytes = Vec::new();
+        encode_tagged(&fields, &mut bytes);
+
+        let decoded = decode(&bytes, &[2]).unwrap();
+
+        assert_eq!(decoded.get(2), Some(&[0xAB][..]));
+        assert_eq!(decoded.get(7), Some(&[0x10, 0x20][..]));
+    }
+
+    #[test]
+    fn tagged_fields_unknown_required_fails() {
+        let mut bytes = Vec::new();
+        encode_tagged(&fields(&[(1, &[0xAB]), (4, &[0xCD])]), &mut bytes);
+
+        assert_eq!(
+            decode(&bytes, &[2]),
+            Err(ParseError::NotSupported(Field::TaggedFieldTag))
+        );
+    }
+
+    #[test]
+    fn tagged_fields_unordered_fails() {
+        // #Fields = 2, tag `3` followed by tag `1`
+        let bytes = [2, 3, 1, 0xAB, 1, 1, 0xCD];
+
+        assert_eq!(
+            decode(&bytes, &[]),
+            Err(ParseError::NonCanonical(Field::TaggedFieldTag))
+        );
+
+        // The same tag twice
+        let bytes = [2, 3, 1, 0xAB, 3, 1, 0xCD];
+
+        assert_eq!(
+            decode(&bytes, &[]),
+            Err(ParseError::NonCanonical(Field::TaggedFieldTag))
+        );
+    }
+
+    #[test]
+    fn tagged_fields_truncated() {
+        let mut bytes = Vec::new();
+        encode_tagged(&fields(&[(1, b"value")]), &mut bytes);
+
+        assert_eq!(
+            decode(&bytes[..bytes.len() - 1], &[]),
+            Err(ParseError::NotEnoughBytes(Field::TaggedFieldValue))
+        );
+        assert_eq!(
+            decode(&bytes[..2], &[]),
+            Err(ParseError::NotEnoughBytes(Field::TaggedFieldLength))
+        );
+        assert_eq!(
+            decode(&bytes[..1], &[]),
+            Err(ParseError::NotEnoughBytes(Field::TaggedFieldTag))
+        );
+        assert_eq!(
+            decode(&[], &[]),
+            Err(ParseError::NotEnoughBytes(Field::TaggedFieldsCount))
+        );
+    }
+}
@@ -40,17 +40,24 @@
 //!  | Deploy Section | (Optional, will be derived from the `Transaction Envelope` and `Transaction Context`)
 //!  |                |
 //!  +----------------+
+//!  |                |
+//!  | Custom Section | (Optional, any number of them - each of a distinct kind)
+//!  |                |
+//!  +----------------+
 //!
 //! ```
 
-use svm_types::{SectionKind, Template};
+use svm_types::{Section, SectionKind, Sections, Template};
 
 use std::collections::HashSet;
 use std::io::Cursor;
 
+pub mod refs;
+
+use crate::limits::{self, MessageFamily};
 use crate::section::decode::decode_sections;
-use crate::section::SectionsEncoder;
-use crate::ParseError;
+use crate::section::{kind, SectionsDecoder, SectionsEncoder};
+use crate::{ParseError, ParseErrorAt, WriteExt};
 
 /// Encodes a `Template` into binary
 ///
@@ -66,19 +73,108 @@ pub fn encode(template: &Template) -> Vec<u8> {
     bytes
 }
 
+/// Encodes a `Template` into binary, writing it into `w`
+pub fn encode_into(template: &Template, w: &mut impl WriteExt) {
+    let sections = template.sections();
+
+    let mut encoder = SectionsEncoder::with_capacity(sections.len());
+    encoder.encode(sections);
+    encoder.finish_into(w);
+}
+
+/// Encodes a single `Section` of a `Template` into binary
+///
+/// The output is the binary of a `Template` holding only `section`, so it can be decoded using [`decode`]
+pub fn encode_section(section: &Section) -> Vec<u8> {
+    let mut sections = Sections::with_capacity(1);
+    sections.insert(section.clone());
+
+    let mut encoder = SectionsEncoder::with_capacity(1);
+    encoder.encode(&sections);
+
+    encoder.finish()
+}
+
+/// Decodes the raw binary representation of a `SectionKind` (as used within the `Section`s previews)
+pub fn decode_section_kind(raw: u16) -> Result<SectionKind, ParseError> {
+    let bytes = raw.to_be_bytes();
+    let mut cursor = Cursor::new(&bytes[..]);
+
+    kind::decode(&mut cursor)
+}
+
 /// Decodes a list of `Section`s that we're interested at (see `interest` parameter) and returns them wrapped within a `Template`
 ///
 /// If the input `interests` is `None` - decodes any kind `Section` belonging to the `Template` pointed by the input `cursor`
+///
+/// A `Template` exceeding [`MAX_DEPLOY_SIZE`](limits::MAX_DEPLOY_SIZE) bytes is rejected up-front.
 pub fn decode(
     cursor: Cursor<&[u8]>,
     interests: Option<HashSet<SectionKind>>,
 ) -> Result<Template, ParseError> {
+    limits::check_buffer(MessageFamily::Deploy, &cursor)?;
+
     let sections = decode_sections(cursor, interests)?;
 
     let template = Template::new(sections);
     Ok(template)
 }
 
+/// Decodes all the `Section`s of a binary `Template` (just like [`decode`]),
+/// locating the failure within `bytes` (see [`ParseError::at`]).
+pub fn decode_at(bytes: &[u8]) -> Result<Template, ParseErrorAt> {
+    const STRUCTURE: &str = "DeployTemplate";
+
+    let cursor = Cursor::new(bytes);
+    limits::check_buffer(MessageFamily::Deploy, &cursor)
+        .map_err(|err| err.at(STRUCTURE, &cursor))?;
+
+    let mut decoder =
+        SectionsDecoder::new(cursor.clone()).map_err(|err| err.at(STRUCTURE, &cursor))?;
+
+    let mut sections = Sections::with_capacity(decoder.section_count());
+
+    while !decoder.is_eof() {
+        let preview = decoder
+            .next_preview()
+            .map_err(|err| err.at(STRUCTURE, decoder.cursor()))?;
+
+        let section = decoder.decode_section().map_err(|err| {
+            let structure = format!("{}.{:?}", STRUCTURE, preview.kind());
+            err.at(&structure, decoder.cursor())
+        })?;
+
+        sections.insert(section);
+    }
+
+    Ok(Template::new(sections))
+}
+
+/// Returns the total byte size of the `Section`s we're interested at (see `interest` parameter),
+/// as would be loaded by [`decode`] given the same input.
+///
+/// Only the `Section`s previews are read (the `Section`s themselves are skipped), so the cost
+/// of computing the byte size doesn't depend on the `Section`s sizes.
+pub fn sections_byte_size(
+    cursor: Cursor<&[u8]>,
+    interests: Option<&HashSet<SectionKind>>,
+) -> Result<u64, ParseError> {
+    let mut decoder = SectionsDecoder::new(cursor)?;
+    let mut byte_size = 0;
+
+    while !decoder.is_eof() {
+        let preview = decoder.next_preview()?;
+
+        decoder.skip_section()?;
+
+        if interests.map_or(true, |interests| interests.contains(&preview.kind())) {
+            byte_size += preview.byte_size() as u64;
+        }
+    }
+
+    Ok(byte_size)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::api::builder::TemplateBuilder;
@@ -87,10 +183,13 @@ mod tests {
 
     use maplit::hashset;
 
-    use svm_layout::{FixedLayout, Id, Layout, RawVar};
+    use svm_layout::{
+        DynamicLayout, FixedLayout, Id, Layout, Primitive, RawVar, SymbolicVar, Type,
+    };
     use svm_types::{
-        Address, CodeKind, CodeSection, CtorsSection, DataSection, DeploySection, GasMode,
-        HeaderSection, Layer, TemplateAddr, TransactionId,
+        Address, ApiSection, CodeKind, CodeSection, CtorsSection, CustomSection, DataSection,
+        DefaultsSection, DeploySection, GasMode, HeaderSection, Layer, SchemaSection, TemplateAddr,
+        TransactionId,
     };
 
     fn make_code_section() -> CodeSection {
@@ -102,8 +201,10 @@ mod tests {
 
         let var1 = RawVar::new(Id(0), 0, 10);
         let var2 = RawVar::new(Id(1), 10, 5);
+        let var3 = RawVar::new_bit(Id(2), 15, 0);
+        let var4 = RawVar::new_bit(Id(3), 15, 1);
 
-        let fixed = FixedLayout::new(vec![var1, var2]);
+        let fixed = FixedLayout::new(vec![var1, var2, var3, var4]);
         section.add_layout(Layout::Fixed(fixed));
 
         section
@@ -163,4 +264,595 @@ mod tests {
 
         assert_eq!(template.sections(), &sections);
     }
+
+    #[test]
+    fn template_sections_byte_size() {
+        let template = TemplateBuilder::default()
+            .with_code(make_code_section())
+            .with_data(make_data_section())
+            .with_ctors(make_ctors_section())
+            .with_header(make_header_section())
+            .build();
+
+        let bytes = encode(&template);
+        let byte_size = |interests: Option<&HashSet<SectionKind>>| {
+            sections_byte_size(Cursor::new(&bytes[..]), interests).unwrap()
+        };
+
+        let code = encode_section(&make_code_section().into());
+        let ctors = encode_section(&make_ctors_section().into());
+
+        // Each single `Section` `Template` is prefixed with the `#Sections` (2 bytes)
+        // and the `Section`'s preview (its kind (2 bytes) and byte size (4 bytes))
+        let overhead = 2 + 6;
+        let expected = (code.len() - overhead) + (ctors.len() - overhead);
+
+        let interests = hashset! { SectionKind::Code, SectionKind::Ctors };
+        assert_eq!(byte_size(Some(&interests)), expected as u64);
+
+        let previews = 2 + 4 * 6;
+        assert_eq!(byte_size(None), (bytes.len() - previews) as u64);
+
+        let truncated = &bytes[..bytes.len() - 1];
+        assert!(sections_byte_size(Cursor::new(truncated), None).is_err());
+    }
+
+    #[test]
+    fn decode_template_streaming_previews() {
+        use crate::section::{SectionDecoder, SectionsDecoder};
+
+        let code = make_code_section();
+        let ctors = make_ctors_section();
+
+        let template = TemplateBuilder::default()
+            .with_code(code.clone())
+            .with_data(make_data_section())
+            .with_ctors(ctors.clone())
+            .with_header(make_header_section())
+            .build();
+
+        let bytes = encode(&template);
+        let mut decoder = SectionsDecoder::new(Cursor::new(&bytes[..])).unwrap();
+
+        let mut skipped = Vec::new();
+
+        while !decoder.is_eof() {
+            let preview = decoder.next_preview().unwrap();
+
+            match preview.kind() {
+                SectionKind::Code => {
+                    let raw = decoder.raw_section().unwrap();
+                    let decoded = CodeSection::decode(&mut Cursor::new(raw)).unwrap();
+
+                    assert_eq!(decoded, code);
+                }
+                SectionKind::Ctors => {
+                    let section = decoder.decode_section().unwrap();
+
+                    assert_eq!(section.as_ctors(), &ctors);
+                }
+                kind => {
+                    let range = decoder.section_range().unwrap();
+
+                    assert_eq!(range.len(), preview.byte_size() as usize);
+                    skipped.push(kind);
+                }
+            }
+        }
+
+        assert_eq!(skipped.len(), 2);
+        assert!(skipped.contains(&SectionKind::Header));
+        assert!(skipped.contains(&SectionKind::Data));
+    }
+
+    #[test]
+    fn decode_template_truncated_section() {
+        use crate::section::SectionsDecoder;
+        use crate::Field;
+
+        let template = TemplateBuilder::default()
+            .with_code(make_code_section())
+            .with_data(make_data_section())
+            .with_ctors(make_ctors_section())
+            .with_header(make_header_section())
+            .build();
+
+        let bytes = encode(&template);
+        let bytes = &bytes[..bytes.len() - 1];
+
+        let mut decoder = SectionsDecoder::new(Cursor::new(bytes)).unwrap();
+        let mut result = Ok(());
+
+        while !decoder.is_eof() && result.is_ok() {
+            decoder.next_preview().unwrap();
+            result = decoder.skip_section();
+        }
+
+        assert_eq!(result, Err(ParseError::NotEnoughBytes(Field::Section)));
+    }
+
+    #[test]
+    fn encode_template_with_defaults() {
+        let mut defaults = DefaultsSection::with_capacity(2);
+        defaults.push(Id(0), vec![0x10, 0x20, 0x30, 0x40]);
+        defaults.push(Id(1), vec![0x01]);
+
+        let template = TemplateBuilder::default()
+            .with_code(make_code_section())
+            .with_data(make_data_section())
+            .with_ctors(make_ctors_section())
+            .with_defaults(defaults.clone())
+            .build();
+
+        let bytes = encode(&template);
+        let cursor = Cursor::new(&bytes[..]);
+
+        let decoded = decode(cursor, None).unwrap();
+
+        assert_eq!(decoded.defaults_section(), Some(&defaults));
+        assert_eq!(decoded, template);
+    }
+
+    #[test]
+    fn encode_template_with_api() {
+        let api = ApiSection::new().with_fallback("forward".to_string());
+
+        let template = TemplateBuilder::default()
+            .with_code(make_code_section())
+            .with_data(make_data_section())
+            .with_ctors(make_ctors_section())
+            .with_api(api.clone())
+            .build();
+
+        let bytes = encode(&template);
+        let cursor = Cursor::new(&bytes[..]);
+
+        let decoded = decode(cursor, None).unwrap();
+
+        assert_eq!(decoded.api_section(), Some(&api));
+        assert_eq!(decoded.api_section().unwrap().fallback(), Some("forward"));
+        assert_eq!(decoded, template);
+    }
+
+    #[test]
+    fn encode_template_with_api_funcs() {
+        use svm_types::{ApiFunc, ApiParam, ApiType};
+
+        let transfer = ApiFunc {
+            name: "transfer".to_string(),
+            wasm_name: "transfer_0".to_string(),
+            is_ctor: false,
+            is_fundable: true,
+            params: vec![
+                ApiParam {
+                    name: "to".to_string(),
+                    ty: ApiType::Primitive(Primitive::Address),
+                },
+                ApiParam {
+                    name: "amounts".to_string(),
+                    ty: ApiType::Array {
+                        primitive: Primitive::Amount,
+                        length: 3,
+                    },
+                },
+            ],
+            returns: vec![
+                ApiType::Primitive(Primitive::Bool),
+                ApiType::Struct("Transfer".to_string()),
+            ],
+            doc: String::new(),
+        };
+        let init = ApiFunc {
+            name: "init".to_string(),
+            wasm_name: "init".to_string(),
+            is_ctor: true,
+            is_fundable: false,
+            params: Vec::new(),
+            returns: Vec::new(),
+            doc: String::new(),
+        };
+        let api = ApiSection::new()
+            .with_fallback("forward".to_string())
+            .with_func(init)
+            .with_func(transfer.clone());
+
+        let template = TemplateBuilder::default()
+            .with_code(make_code_section())
+            .with_data(make_data_section())
+            .with_ctors(make_ctors_section())
+            .with_api(api.clone())
+            .build();
+
+        let bytes = encode(&template);
+        let decoded = decode(Cursor::new(&bytes[..]), None).unwrap();
+
+        assert_eq!(decoded.api_section(), Some(&api));
+        assert_eq!(
+            decoded.api_section().unwrap().func("transfer"),
+            Some(&transfer)
+        );
+        assert_eq!(decoded, template);
+
+        // A truncated `Struct` name
+        let mut bytes = encode_section(&api.into());
+        let err = decode(Cursor::new(&bytes[..bytes.len() - 1]), None).unwrap_err();
+        assert_eq!(err, ParseError::NotEnoughBytes(crate::Field::ApiType));
+
+        // An unknown `Type Kind` (the last returned value of `transfer`)
+        let kind = bytes.len() - (1 + 1 + "Transfer".len());
+        bytes[kind] = 0xFF;
+
+        let err = decode(Cursor::new(&bytes[..]), None).unwrap_err();
+        assert_eq!(err, ParseError::NotSupported(crate::Field::ApiType));
+    }
+
+    #[test]
+    fn encode_template_with_api_docs() {
+        use svm_types::{ApiFunc, ApiVarDoc};
+
+        let init = ApiFunc {
+            name: "init".to_string(),
+            wasm_name: "init".to_string(),
+            is_ctor: true,
+            is_fundable: false,
+            params: Vec::new(),
+            returns: Vec::new(),
+            doc: "Initializes the `owner`".to_string(),
+        };
+        let owner = ApiVarDoc {
+            name: "owner".to_string(),
+            doc: "The owner of the wallet".to_string(),
+        };
+        let api = ApiSection::new()
+            .with_func(init)
+            .with_doc("A multi-sig wallet\n\nLong docs".repeat(20))
+            .with_var_doc(owner.clone());
+
+        let template = TemplateBuilder::default()
+            .with_code(make_code_section())
+            .with_data(make_data_section())
+            .with_ctors(make_ctors_section())
+            .with_api(api.clone())
+            .build();
+
+        let bytes = encode(&template);
+        let decoded = decode(Cursor::new(&bytes[..]), None).unwrap();
+
+        let decoded_api = decoded.api_section().unwrap();
+        assert_eq!(decoded_api, &api);
+        assert!(decoded_api.has_docs());
+        assert_eq!(decoded_api.var_docs(), &[owner]);
+        assert_eq!(
+            decoded_api.func("init").unwrap().doc,
+            "Initializes the `owner`"
+        );
+
+        // An unknown flag of the section (its first byte, following the `Section Preview`)
+        let raw = {
+            let mut w = Vec::new();
+            crate::section::SectionEncoder::encode(&api, &mut w);
+            w
+        };
+        let mut bytes = encode_section(&api.into());
+        let flags = bytes.len() - raw.len();
+        bytes[flags] |= 1 << 7;
+
+        let err = decode(Cursor::new(&bytes[..]), None).unwrap_err();
+        assert_eq!(err, ParseError::NotSupported(crate::Field::ApiFlags));
+    }
+
+    #[test]
+    fn encode_template_with_storage_budget() {
+        let data = make_data_section().with_max_storage_bytes(Some(4096));
+
+        let template = TemplateBuilder::default()
+            .with_code(make_code_section())
+            .with_data(data)
+            .with_ctors(make_ctors_section())
+            .build();
+
+        let bytes = encode(&template);
+        let cursor = Cursor::new(&bytes[..]);
+
+        let decoded = decode(cursor, None).unwrap();
+
+        assert_eq!(decoded.data_section().max_storage_bytes(), Some(4096));
+        // The two packed booleans share a single byte
+        assert_eq!(decoded.data_section().storage_bytes(), 16);
+        assert_eq!(decoded, template);
+    }
+
+    #[test]
+    fn encode_template_with_dynamic_layout() {
+        let mut data = make_data_section();
+        data.add_layout(Layout::Dynamic(DynamicLayout::from([1, 0].as_slice())));
+
+        let template = TemplateBuilder::default()
+            .with_code(make_code_section())
+            .with_data(data)
+            .with_ctors(make_ctors_section())
+            .build();
+
+        let bytes = encode(&template);
+        let cursor = Cursor::new(&bytes[..]);
+
+        let decoded = decode(cursor, None).unwrap();
+
+        let dynamic = decoded.dynamic_layout().unwrap();
+        assert!(dynamic.contains(Id(0)));
+        assert!(dynamic.contains(Id(1)));
+        assert!(!dynamic.contains(Id(2)));
+
+        // The `Dynamic Layout` occupies no fixed storage
+        assert_eq!(decoded.data_section().storage_bytes(), 16);
+        assert_eq!(decoded, template);
+    }
+
+    #[test]
+    fn encode_template_with_custom_sections() {
+        let first = CustomSection::new(0x80_01, vec![0x10, 0x20, 0x30]);
+        let second = CustomSection::new(0xFF_FF, Vec::new());
+
+        let template = TemplateBuilder::default()
+            .with_code(make_code_section())
+            .with_data(make_data_section())
+            .with_ctors(make_ctors_section())
+            .with_custom(first.clone())
+            .with_custom(second.clone())
+            .build();
+
+        let bytes = encode(&template);
+
+        // Decoding all `Section`s
+        let cursor = Cursor::new(&bytes[..]);
+        let decoded = decode(cursor, None).unwrap();
+
+        assert_eq!(decoded, template);
+        assert_eq!(
+            decoded.get(SectionKind::Custom(0x80_01)).as_custom(),
+            &first
+        );
+
+        // Skipping the `Custom Section`s
+        let cursor = Cursor::new(&bytes[..]);
+        let interests = hashset! { SectionKind::Code, SectionKind::Custom(0xFF_FF) };
+        let sections = decode_sections(cursor, Some(interests)).unwrap();
+
+        assert_eq!(sections.len(), 2);
+        assert!(!sections.contains(SectionKind::Custom(0x80_01)));
+        assert_eq!(
+            sections.get(SectionKind::Custom(0xFF_FF)).as_custom(),
+            &second
+        );
+    }
+
+    #[test]
+    fn encode_template_with_schema() {
+        let mut schema = SchemaSection::with_capacity(3);
+        schema.push_var(SymbolicVar::new(
+            Id(0),
+            "owner".to_string(),
+            Type::Primitive(Primitive::Address),
+        ));
+        schema.push_var(SymbolicVar::new(
+            Id(1),
+            "prices".to_string(),
+            Type::Array {
+                primitive: Primitive::U64,
+                length: 4,
+            },
+        ));
+        schema.push_var(SymbolicVar::new(
+            Id(2),
+            "paused".to_string(),
+            Type::Primitive(Primitive::Bool),
+        ));
+
+        let template = TemplateBuilder::default()
+            .with_code(make_code_section())
+            .with_data(make_data_section())
+            .with_ctors(make_ctors_section())
+            .with_schema(schema.clone())
+            .build();
+
+        let bytes = encode(&template);
+        let decoded = decode(Cursor::new(&bytes[..]), None).unwrap();
+
+        assert_eq!(decoded.schema_section(), &schema);
+        assert_eq!(decoded, template);
+
+        // `#Vars`, then `Var Id` + `Name` + `Type` for each
+        let section = encode_section(&schema.into());
+        assert_eq!(
+            section.len(),
+            2 + 6 + 2 + (4 + 6 + 2) + (4 + 7 + 6) + (4 + 7 + 2)
+        );
+    }
+
+    #[test]
+    fn decode_schema_unknown_type() {
+        use crate::Field;
+
+        let mut schema = SchemaSection::with_capacity(1);
+        schema.push_var(SymbolicVar::new(
+            Id(0),
+            "owner".to_string(),
+            Type::Primitive(Primitive::Address),
+        ));
+
+        let mut bytes = encode_section(&schema.into());
+        let last = bytes.len() - 1;
+
+        // An unknown `Primitive`
+        bytes[last] = 0xFF;
+
+        let err = decode(Cursor::new(&bytes[..]), None).unwrap_err();
+        assert_eq!(err, ParseError::NotSupported(Field::SymbolicVarType));
+
+        // A truncated `Type`
+        let err = decode(Cursor::new(&bytes[..last]), None).unwrap_err();
+        assert_eq!(err, ParseError::NotEnoughBytes(Field::SymbolicVarType));
+    }
+
+    #[test]
+    fn encode_single_section() {
+        let header = Section::Header(make_header_section());
+
+        let bytes = encode_section(&header);
+        let cursor = Cursor::new(&bytes[..]);
+
+        let sections = decode_sections(cursor, None).unwrap();
+
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections.get(SectionKind::Header), &header);
+    }
+
+    #[test]
+    fn decode_raw_section_kind() {
+        assert_eq!(decode_section_kind(0x00_01), Ok(SectionKind::Code));
+        assert_eq!(decode_section_kind(0x00_06), Ok(SectionKind::Header));
+        assert_eq!(
+            decode_section_kind(0x7F_FF),
+            Err(ParseError::InvalidSection)
+        );
+        assert_eq!(
+            decode_section_kind(0x80_00),
+            Ok(SectionKind::Custom(0x80_00))
+        );
+        assert_eq!(
+            decode_section_kind(0xFF_FF),
+            Ok(SectionKind::Custom(0xFF_FF))
+        );
+    }
+
+    #[test]
+    fn encode_precompile_template() {
+        let code = CodeSection::new_precompile(7, 1);
+        let ctors = make_ctors_section();
+
+        let template = TemplateBuilder::default()
+            .with_code(code)
+            .with_data(make_data_section())
+            .with_ctors(ctors)
+            .with_header(make_header_section())
+            .build();
+
+        let bytes = encode(&template);
+        let decoded = decode(Cursor::new(&bytes[..]), None).unwrap();
+
+        assert_eq!(decoded.code_section().kind(), CodeKind::Precompile);
+        assert_eq!(decoded.code_section().precompile_id(), Some(7));
+        assert_eq!(template.sections(), decoded.sections());
+    }
+
+    #[test]
+    fn encode_metering_template() {
+        let code = CodeSection::new_metering(vec![0xC0, 0xDE], 1);
+
+        let template = TemplateBuilder::default()
+            .with_code(code)
+            .with_data(make_data_section())
+            .with_ctors(make_ctors_section())
+            .with_header(make_header_section())
+            .build();
+
+        let bytes = encode(&template);
+        let decoded = decode(Cursor::new(&bytes[..]), None).unwrap();
+
+        assert_eq!(decoded.code_section().gas_mode(), GasMode::Metering);
+        assert_eq!(template.sections(), decoded.sections());
+    }
+
+    #[test]
+    fn decode_baseline_data_section() {
+        use crate::section::{decode_raw_section, preview, SectionEncoder, SectionPreview};
+
+        // A `Data Section` encoded prior to the `Storage Budget` (`#Layouts`, followed by a `Fixed Layout`
+        // of 4 vars starting at var `0`: 10 bytes, 5 bytes and two packed booleans)
+        let data: Vec<u8> = vec![
+            0x00, 0x01, 0x00, 0x01, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x05,
+            0x00, 0x00, 0x00, 0x00,
+        ];
+
+        let section = decode_raw_section(SectionKind::Data, &data).unwrap();
+        assert_eq!(section, make_data_section().into());
+        assert_eq!(section.as_data().max_storage_bytes(), None);
+
+        // The section is followed by another one (its decoding stops at the section's end)
+        let mut ctors = Vec::new();
+        make_ctors_section().encode(&mut ctors);
+
+        let mut bytes = Vec::new();
+        bytes.write_u16_be(2);
+        preview::encode(
+            &SectionPreview::new(SectionKind::Data, data.len() as u32),
+            &mut bytes,
+        );
+        bytes.extend_from_slice(&data);
+        preview::encode(
+            &SectionPreview::new(SectionKind::Ctors, ctors.len() as u32),
+            &mut bytes,
+        );
+        bytes.extend_from_slice(&ctors);
+
+        let sections = decode_sections(Cursor::new(&bytes[..]), None).unwrap();
+
+        assert_eq!(sections.get(SectionKind::Data), &make_data_section().into());
+        assert_eq!(
+            sections.get(SectionKind::Ctors),
+            &make_ctors_section().into()
+        );
+    }
+
+    #[test]
+    fn decode_baseline_deploy_section() {
+        use crate::section::decode_raw_section;
+
+        // A `Deploy Section` encoded prior to the `Addr Scheme`
+        let mut bytes = Vec::new();
+        bytes.write_tx_id(&TransactionId::repeat(0xFF));
+        bytes.write_u64_be(10);
+        bytes.write_address(&Address::repeat(0xAB));
+        bytes.write_template_addr(&TemplateAddr::repeat(0xCD));
+
+        let section = decode_raw_section(SectionKind::Deploy, &bytes).unwrap();
+        assert_eq!(section, make_deploy_section().into());
+        assert_eq!(section.as_deploy().addr_scheme(), 0);
+
+        // A truncated `Addr Scheme` is rejected
+        bytes.push(0x01);
+
+        let err = decode_raw_section(SectionKind::Deploy, &bytes).unwrap_err();
+        assert_eq!(err, ParseError::NotEnoughBytes(crate::Field::AddrScheme));
+    }
+
+    #[test]
+    fn decode_unsupported_gas_mode() {
+        use crate::section::{SectionDecoder, SectionEncoder};
+        use crate::Field;
+
+        let mut bytes = Vec::new();
+        make_code_section().encode(&mut bytes);
+
+        // Patching the `Gas Mode` (following the `Code Kind` and the `Flags`)
+        bytes[10..18].copy_from_slice(&[0xFF; 8]);
+
+        let err = CodeSection::decode(&mut Cursor::new(&bytes[..])).unwrap_err();
+        assert_eq!(err, ParseError::NotSupported(Field::GasMode));
+    }
+
+    #[test]
+    fn decode_unsupported_code_kind() {
+        use crate::section::{SectionDecoder, SectionEncoder};
+        use crate::Field;
+
+        let mut bytes = Vec::new();
+        make_code_section().encode(&mut bytes);
+
+        // Patching the `Code Kind`
+        bytes[0..2].copy_from_slice(&[0x00, 0xFF]);
+
+        let err = CodeSection::decode(&mut Cursor::new(&bytes[..])).unwrap_err();
+        assert_eq!(err, ParseError::NotSupported(Field::CodeKind));
+    }
 }
@@ -0,0 +1,278 @@
+//! Encoding a [`Template`] as a list of `Section` references followed by new `Section`s.
+//!
+//! Each `Section` is content-addressed by its [`SectionHash`] (the hash of its single-section encoding,
+//! see [`encode_section`](super::encode_section)). A receiver already holding some of the `Section`s
+//! of a `Template` (i.e when a `Template` upgrade changes only one of its `Section`s) can be sent
+//! references to these `Section`s instead of their whole content.
+//!
+//! ```text
+//!
+//!  +------------+-------------------+-----+-------------------+------------------+
+//!  |            |                   |     |                   |                  |
+//!  |   #Refs    |  Section Ref #1   |     |  Section Ref #n   |   New Sections   |
+//!  |  (u16 BE)  |  kind (u16 BE)    | ... |  kind (u16 BE)    |  (see `encode`)  |
+//!  |            |  hash (32 bytes)  |     |  hash (32 bytes)  |                  |
+//!  |            |                   |     |                   |                  |
+//!  +------------+-------------------+-----+-------------------+------------------+
+//!
+//! ```
+//!
+//! A `Section` kind must not appear more than once (either as a reference or as a new `Section`).
+
+use std::collections::HashSet;
+use std::io::Cursor;
+
+use svm_hash::{Blake3Hasher, Hasher};
+use svm_types::{Section, SectionKind, Sections, Template};
+
+use super::encode_section;
+use crate::limits::{self, MessageFamily};
+use crate::section::decode::decode_sections;
+use crate::section::{kind, SectionsEncoder};
+use crate::{Field, ParseError, ReadExt, WriteExt};
+
+/// The content-address of a `Section`.
+pub type SectionHash = [u8; 32];
+
+/// A reference to a content-addressed `Section`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SectionRef {
+    kind: SectionKind,
+    hash: SectionHash,
+}
+
+impl SectionRef {
+    /// Creates a reference to `section`.
+    pub fn new(section: &Section) -> Self {
+        Self {
+            kind: section.kind(),
+            hash: section_hash(section),
+        }
+    }
+
+    /// The kind of the referred `Section`.
+    pub fn kind(&self) -> SectionKind {
+        self.kind
+    }
+
+    /// The [`SectionHash`] of the referred `Section`.
+    pub fn hash(&self) -> &SectionHash {
+        &self.hash
+    }
+}
+
+/// Computes the [`SectionHash`] of `section`.
+pub fn section_hash(section: &Section) -> SectionHash {
+    let bytes = encode_section(section);
+
+    Blake3Hasher::hash(&bytes)
+}
+
+/// A decoded `Template` whose referred `Section`s haven't been resolved yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateRefs {
+    /// References to `Section`s already known by the receiver.
+    pub refs: Vec<SectionRef>,
+
+    /// The `Section`s sent in full.
+    pub sections: Sections,
+}
+
+impl TemplateRefs {
+    /// Resolves the referred `Section`s using `resolve` and returns the complete [`Template`].
+    ///
+    /// Returns the first [`SectionRef`] that `resolve` couldn't resolve
+    /// (or that has been resolved into a `Section` having a different hash).
+    pub fn resolve<F>(self, mut resolve: F) -> Result<Template, SectionRef>
+    where
+        F: FnMut(&SectionRef) -> Option<Section>,
+    {
+        let mut sections = self.sections;
+
+        for r in self.refs {
+            match resolve(&r) {
+                Some(section) if SectionRef::new(&section) == r => sections.insert(section),
+                _ => return Err(r),
+            }
+        }
+
+        Ok(Template::new(sections))
+    }
+}
+
+/// Encodes `template`, replacing each `Section` whose [`SectionHash`] is `known` with a reference.
+pub fn encode_with_refs(template: &Template, known: &HashSet<SectionHash>) -> Vec<u8> {
+    let (refs, sections): (Vec<_>, Vec<_>) = template
+        .sections()
+        .iter()
+        .map(|section| (SectionRef::new(section), section))
+        .partition(|(r, _)| known.contains(r.hash()));
+
+    let mut w = Vec::new();
+
+    assert!(refs.len() < std::u16::MAX as usize);
+    w.write_u16_be(refs.len() as u16);
+
+    for (r, _) in refs.iter() {
+        kind::encode(r.kind(), &mut w);
+        w.write_bytes(r.hash());
+    }
+
+    let mut new_sections = Sections::with_capacity(sections.len());
+    for (_, section) in sections {
+        new_sections.insert(section.clone());
+    }
+
+    let mut encoder = SectionsEncoder::with_capacity(new_sections.len());
+    encoder.encode(&new_sections);
+    w.extend_from_slice(&encoder.finish());
+
+    w
+}
+
+/// Decodes a `Template` encoded using [`encode_with_refs`].
+pub fn decode_with_refs(mut cursor: Cursor<&[u8]>) -> Result<TemplateRefs, ParseError> {
+    limits::check_buffer(MessageFamily::Deploy, &cursor)?;
+
+    let ref_count = cursor
+        .read_u16_be()
+        .map_err(|_| ParseError::NotEnoughBytes(Field::SectionRefCount))?;
+
+    let mut kinds = HashSet::new();
+    let mut refs = Vec::with_capacity(ref_count as usize);
+
+    for _ in 0..ref_count {
+        let kind = kind::decode(&mut cursor)?;
+        let bytes = cursor
+            .read_bytes(32)
+            .map_err(|_| ParseError::NotEnoughBytes(Field::SectionHash))?;
+
+        let mut hash = SectionHash::default();
+        hash.copy_from_slice(&bytes);
+
+        if !kinds.insert(kind) {
+            return Err(ParseError::InvalidSection);
+        }
+
+        refs.push(SectionRef { kind, hash });
+    }
+
+    let sections = decode_sections(cursor, None)?;
+
+    if sections
+        .iter()
+        .any(|section| kinds.contains(&section.kind()))
+    {
+        return Err(ParseError::InvalidSection);
+    }
+
+    Ok(TemplateRefs { refs, sections })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    use crate::api::builder::TemplateBuilder;
+
+    use svm_types::{CodeSection, CtorsSection, DataSection, HeaderSection};
+
+    fn make_template(code: Vec<u8>, code_version: u32) -> Template {
+        TemplateBuilder::default()
+            .with_code(CodeSection::new_fixed(code, 0))
+            .with_data(DataSection::default())
+            .with_ctors(CtorsSection::new(vec!["init".to_string()]))
+            .with_header(HeaderSection::new(
+                code_version,
+                "My Template".to_string(),
+                "".to_string(),
+            ))
+            .build()
+    }
+
+    fn index(template: &Template) -> HashMap<SectionHash, Section> {
+        template
+            .sections()
+            .iter()
+            .map(|section| (section_hash(section), section.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn encode_with_refs_only_new_sections() {
+        let old = make_template(vec![0xC0; 1024], 1);
+        let new = make_template(vec![0xC0; 1024], 2);
+
+        let store = index(&old);
+        let known = store.keys().cloned().collect();
+
+        let bytes = encode_with_refs(&new, &known);
+        let decoded = decode_with_refs(Cursor::new(&bytes[..])).unwrap();
+
+        assert_eq!(decoded.refs.len(), 3);
+        assert_eq!(decoded.sections.len(), 1);
+        assert!(decoded.sections.contains(SectionKind::Header));
+
+        assert!(bytes.len() < super::super::encode(&new).len());
+
+        let template = decoded.resolve(|r| store.get(r.hash()).cloned()).unwrap();
+        assert_eq!(template, new);
+    }
+
+    #[test]
+    fn encode_with_refs_nothing_known() {
+        let template = make_template(vec![0xC0, 0xDE], 1);
+
+        let bytes = encode_with_refs(&template, &HashSet::new());
+        let decoded = decode_with_refs(Cursor::new(&bytes[..])).unwrap();
+
+        assert!(decoded.refs.is_empty());
+        assert_eq!(decoded.resolve(|_| None).unwrap(), template);
+    }
+
+    #[test]
+    fn resolve_unknown_ref_fails() {
+        let template = make_template(vec![0xC0, 0xDE], 1);
+        let known = index(&template).keys().cloned().collect();
+
+        let bytes = encode_with_refs(&template, &known);
+        let decoded = decode_with_refs(Cursor::new(&bytes[..])).unwrap();
+
+        let unresolved = decoded.refs[0].clone();
+        assert_eq!(decoded.resolve(|_| None), Err(unresolved));
+    }
+
+    #[test]
+    fn resolve_mismatching_section_fails() {
+        let template = make_template(vec![0xC0, 0xDE], 1);
+        let other = make_template(vec![0xFF], 1);
+
+        let code = template.sections().get(SectionKind::Code);
+        let known = vec![section_hash(code)].into_iter().collect();
+
+        let bytes = encode_with_refs(&template, &known);
+        let decoded = decode_with_refs(Cursor::new(&bytes[..])).unwrap();
+
+        let other_code = other.sections().get(SectionKind::Code).clone();
+        let result = decoded.resolve(|_| Some(other_code.clone()));
+
+        assert_eq!(result, Err(SectionRef::new(code)));
+    }
+
+    #[test]
+    fn decode_duplicate_kind_fails() {
+        let template = make_template(vec![0xC0, 0xDE], 1);
+        let code = template.sections().get(SectionKind::Code);
+
+        let mut bytes = Vec::new();
+        bytes.write_u16_be(1);
+        kind::encode(SectionKind::Code, &mut bytes);
+        bytes.write_bytes(&section_hash(code));
+        bytes.extend_from_slice(&encode_section(code));
+
+        let result = decode_with_refs(Cursor::new(&bytes[..]));
+        assert_eq!(result, Err(ParseError::InvalidSection));
+    }
+}
@@ -0,0 +1,121 @@
+//! Encoding of a binary [`UpgradeTemplate`].
+//!
+//! ```text
+//!
+//!  +-----------+-------------+-------------------------+
+//!  |           |             |                         |
+//!  |  Version  |  Template   |   Upgraded `Template`   |
+//!  |   (u16)   |  (Address)  |   (binary `Template`)   |
+//!  |           |             |                         |
+//!  +-----------+-------------+-------------------------+
+//!
+//! ```
+//!
+//! The upgraded `Template` is encoded just like the `Template` of a `Deploy Template`
+//! transaction (see [`crate::template`]). The whole message can't exceed
+//! [`MAX_UPGRADE_SIZE`](crate::limits::MAX_UPGRADE_SIZE) bytes.
+
+use std::io::Cursor;
+
+use svm_types::{TemplateAddr, UpgradeTemplate};
+
+use crate::limits::{self, MessageFamily};
+use crate::{template, version};
+use crate::{Field, ParseError, ReadExt, WriteExt};
+
+/// Encodes a binary [`UpgradeTemplate`] transaction.
+pub fn encode(upgrade: &UpgradeTemplate, w: &mut Vec<u8>) {
+    version::encode_version(upgrade.version, w);
+    w.write_template_addr(upgrade.template_addr());
+    w.extend_from_slice(&template::encode(upgrade.template()));
+}
+
+/// Parsing a binary [`UpgradeTemplate`] transaction.
+///
+/// Returns the parsed [`UpgradeTemplate`],
+/// On failure, returns [`ParseError`].
+pub fn decode(cursor: &mut Cursor<&[u8]>) -> Result<UpgradeTemplate, ParseError> {
+    limits::check_buffer(MessageFamily::Upgrade, cursor)?;
+
+    let version = version::decode_version(cursor)?;
+    let template_addr = decode_template_addr(cursor)?;
+
+    let offset = cursor.position() as usize;
+    let bytes = &cursor.get_ref()[offset..];
+    let template = template::decode(Cursor::new(bytes), None)?;
+
+    cursor.set_position(cursor.get_ref().len() as u64);
+
+    let upgrade = UpgradeTemplate {
+        version,
+        template_addr,
+        template,
+    };
+
+    Ok(upgrade)
+}
+
+fn decode_template_addr(cursor: &mut Cursor<&[u8]>) -> Result<TemplateAddr, ParseError> {
+    cursor
+        .read_template_addr()
+        .map_err(|_| ParseError::NotEnoughBytes(Field::TemplateAddr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_layout::{FixedLayout, Layout};
+    use svm_types::{CodeKind, CodeSection, CtorsSection, DataSection, GasMode, Template};
+
+    use crate::api::builder::TemplateBuilder;
+
+    fn template() -> Template {
+        let mut ctors = CtorsSection::default();
+        ctors.push("init".to_string());
+
+        let mut data = DataSection::default();
+        data.add_layout(Layout::Fixed(FixedLayout::from(vec![4, 8])));
+
+        TemplateBuilder::default()
+            .with_code(CodeSection::new(
+                CodeKind::Wasm,
+                vec![0xC0, 0xDE],
+                0,
+                GasMode::Fixed,
+                1,
+            ))
+            .with_data(data)
+            .with_ctors(ctors)
+            .build()
+    }
+
+    #[test]
+    fn encode_decode_upgrade_template() {
+        let upgrade = UpgradeTemplate {
+            version: 0,
+            template_addr: TemplateAddr::repeat(0xAB),
+            template: template(),
+        };
+
+        let mut bytes = Vec::new();
+        encode(&upgrade, &mut bytes);
+
+        let mut cursor = Cursor::new(&bytes[..]);
+        let decoded = decode(&mut cursor).unwrap();
+
+        assert_eq!(decoded, upgrade);
+        assert_eq!(cursor.position() as usize, bytes.len());
+    }
+
+    #[test]
+    fn decode_upgrade_template_missing_template_addr() {
+        let bytes = vec![0, 0, 0xAB, 0xAB];
+        let mut cursor = Cursor::new(&bytes[..]);
+
+        assert_eq!(
+            decode(&mut cursor),
+            Err(ParseError::NotEnoughBytes(Field::TemplateAddr))
+        );
+    }
+}
@@ -0,0 +1,68 @@
+//! The node-submittable binary transaction (its message along with its `Envelope` and `Context`).
+//!
+//! ```text
+//!
+//!  +-----------+-------------+----------------+----------------+------------------+
+//!  |           |             |                |                |                  |
+//!  |  Version  |  Tx Type    |    Envelope    |    Context     |     Message      |
+//!  |   (u8)    |   (u8)      |   (44 bytes)   |   (72 bytes)   |  (binary `Blob`) |
+//!  |           |             |                |                |                  |
+//!  +-----------+-------------+----------------+----------------+------------------+
+//!
+//! ```
+//!
+//! * `Version` - the version of the wire layout (see [`VERSION`]).
+//! * `Tx Type` - the type of the message, as laid out by the signing payload (see [`TxType`]).
+//! * `Envelope` - the binary [`Envelope`] (see [`crate::envelope`]).
+//! * `Context` - the binary [`Context`] (see [`crate::context`]).
+//! * `Message` - the binary message, as given to the `Runtime`.
+
+use svm_types::{Context, Envelope};
+
+use crate::signing::TxType;
+
+/// The version of the wire layout.
+pub const VERSION: u8 = 0;
+
+/// Returns the number of bytes of the wire transaction of a `message_len` bytes message.
+pub const fn byte_size(message_len: usize) -> usize {
+    1 + 1 + crate::envelope::byte_size() + crate::context::byte_size() + message_len
+}
+
+/// Returns the wire transaction of the binary `message` of type `tx_type`,
+/// sent under `envelope` and executed under `context`.
+pub fn encode(tx_type: TxType, envelope: &Envelope, context: &Context, message: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(byte_size(message.len()));
+
+    buf.push(VERSION);
+    buf.push(tx_type.as_u8());
+    crate::envelope::encode(envelope, &mut buf);
+    crate::context::encode(context, &mut buf);
+    buf.extend_from_slice(message);
+
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_types::{Address, Gas, Layer, State, TransactionId};
+
+    #[test]
+    fn wire_layout() {
+        let envelope = Envelope::new(Address::repeat(0x10), 10, Gas::with(1000), 1);
+        let context = Context::new(TransactionId::repeat(0x20), Layer(5), State::repeat(0x30));
+        let message = vec![0xAB, 0xCD];
+
+        let bytes = encode(TxType::Call, &envelope, &context, &message);
+        assert_eq!(bytes.len(), byte_size(message.len()));
+
+        let mut expected = vec![VERSION, 2];
+        crate::envelope::encode(&envelope, &mut expected);
+        crate::context::encode(&context, &mut expected);
+        expected.extend_from_slice(&message);
+
+        assert_eq!(bytes, expected);
+    }
+}
@@ -22,6 +22,14 @@ where
         /// The `call` instruction offset relative to the beginning of the function
         offset: usize,
     },
+    /// A `memory.copy` or a `memory.fill` must have a statically bounded `length`
+    /// (i.e pushed by an `i32.const` right before it)
+    UnboundedMemoryOp {
+        /// Function containing the instruction
+        func: T,
+        /// The instruction offset relative to the beginning of the function
+        offset: usize,
+    },
     /// Calls cycles (e.g `A -> B -> C -> A`) aren't allowed
     CallCycle(GraphCycles<T>),
 }
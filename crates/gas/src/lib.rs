@@ -11,6 +11,7 @@ mod detect_loops;
 mod errors;
 mod gas;
 mod graph;
+mod memory;
 mod pricing;
 mod validation;
 
@@ -43,4 +44,29 @@ pub mod transaction {
         // TODO: <https://github.com/spacemeshos/svm/issues/241>.
         1000 * (bytes.len() as u64)
     }
+
+    /// Calculates the cost of upgrading a `Template` with `bytes` its binary `upgrade` transaction.
+    pub fn upgrade(bytes: &[u8]) -> u64 {
+        // TODO: <https://github.com/spacemeshos/svm/issues/241>.
+        1000 * (bytes.len() as u64)
+    }
+
+    /// Calculates the cost of calling an `Account` with `bytes` as its binary `call` transaction.
+    pub fn call(bytes: &[u8]) -> u64 {
+        // TODO: <https://github.com/spacemeshos/svm/issues/241>.
+        1000 * (bytes.len() as u64)
+    }
+
+    /// Calculates the cost of the data segments of a `Template`'s code, having `byte_size` bytes in total.
+    ///
+    /// It's charged on top of [`deploy`] and [`upgrade`], since the data segments are copied into
+    /// the memory each time the code is instantiated.
+    pub fn data_segments(byte_size: u64) -> u64 {
+        100 * byte_size
+    }
+
+    /// Calculates the cost of loading a `Template` (for executing its code) having `byte_size` bytes of `Section`s loaded.
+    pub fn template_load(byte_size: u64) -> u64 {
+        10 * byte_size
+    }
 }
@@ -0,0 +1,24 @@
+use parity_wasm::elements::{BulkInstruction, Instruction};
+
+/// Returns whether `op` is a `memory.copy` or a `memory.fill`.
+///
+/// Their price depends on the number of bytes they touch, so they're priced per byte
+/// (see [`PriceResolver::bulk_memory_price`](crate::PriceResolver::bulk_memory_price)).
+pub fn is_bulk_memory(op: &Instruction) -> bool {
+    matches!(
+        op,
+        Instruction::Bulk(BulkInstruction::MemoryCopy)
+            | Instruction::Bulk(BulkInstruction::MemoryFill)
+    )
+}
+
+/// Returns the number of bytes touched by a `memory.copy` or a `memory.fill` preceded by `prev`.
+///
+/// The number of bytes is statically bounded only when the `length` operand is pushed
+/// by an `i32.const` right before the instruction. Otherwise, `None` is returned.
+pub fn static_byte_count(prev: Option<&Instruction>) -> Option<usize> {
+    match prev {
+        Some(Instruction::I32Const(length)) => Some(*length as u32 as usize),
+        _ => None,
+    }
+}
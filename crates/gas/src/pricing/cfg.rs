@@ -3,6 +3,7 @@ use parity_wasm::elements::Instruction;
 
 use svm_program::{FuncIndex, Imports, Op};
 
+use crate::memory;
 use crate::{
     Block, BlockNum, Edge, FuncPrice, Graph, GraphBuilder, NodeLabel, NodeWeight, PriceResolver,
     WeightedGraph, WeightedPath, CFG,
@@ -22,6 +23,9 @@ use crate::{
 /// under the original `Block` in the `CFG`. So if some `Block`, let's named it `B0` contained 3 `ops` namely `op_1`, `op_2` and `op_3` -
 /// the corresponding `Node` under the `WeightedGraph` will have `weight = price(op_1) + price(op_2) + price(op_3)`
 ///
+/// The price of a `memory.copy` or a `memory.fill` also covers the bytes it touches
+/// (see [`PriceResolver::bulk_memory_price`]).
+///
 /// There is no weight associated with the `Edge`(s) of the `WeightedGraph`.
 /// This is an implementation detail. We preferred to use a `Graph` which has no weight in its `Edge`(s)
 /// and pick a `Data` type for the `Node`(s) that will contain `weight`.
@@ -63,19 +67,29 @@ fn compute_block_price(
     imports: &Imports,
     func_price: &FuncPrice,
 ) -> usize {
-    block.ops().iter().fold(0, |acc, op| {
-        acc + resolve_op(op, resolver, imports, func_price)
+    let ops = block.ops();
+
+    ops.iter().enumerate().fold(0, |acc, (i, op)| {
+        let prev = i.checked_sub(1).map(|i| ops[i].raw());
+
+        acc + resolve_op(op, prev, resolver, imports, func_price)
     })
 }
 
 fn resolve_op(
     op: &Op,
+    prev: Option<&Instruction>,
     resolver: &dyn PriceResolver,
     imports: &Imports,
     func_price: &FuncPrice,
 ) -> usize {
     if let Instruction::Call(target) = op.raw() {
         resolve_call_price(op, resolver, imports, func_price)
+    } else if memory::is_bulk_memory(op.raw()) {
+        // A validated `Program` has its `memory.copy` and `memory.fill` statically bounded
+        let byte_count = memory::static_byte_count(prev).unwrap();
+
+        resolver.op_price(op) + resolver.bulk_memory_price(op, byte_count)
     } else {
         resolver.op_price(op)
     }
@@ -32,6 +32,13 @@ impl FuncPrice {
     pub fn get(&self, fn_index: FuncIndex) -> usize {
         *self.inner.get(&fn_index).unwrap()
     }
+
+    /// Returns an iterator over the functions and their prices (in insertion order)
+    pub fn iter(&self) -> impl Iterator<Item = (FuncIndex, usize)> + '_ {
+        self.inner
+            .iter()
+            .map(|(fn_index, price)| (*fn_index, *price))
+    }
 }
 
 impl Display for FuncPrice {
@@ -8,6 +8,24 @@ pub trait PriceResolver {
 
     /// Returns the price for import function `import`
     fn import_price(&self, import: (&str, &str)) -> usize;
+
+    /// Returns the price for the variable part of a batched storage import function `import`
+    /// (i.e `svm_storage_read_many` and `svm_storage_write_many`), touching `var_count`
+    /// variables of `byte_count` bytes in total.
+    ///
+    /// It's charged on top of the `import_price` of `import`.
+    fn storage_batch_price(
+        &self,
+        import: (&str, &str),
+        var_count: usize,
+        byte_count: usize,
+    ) -> usize;
+
+    /// Returns the price for the variable part of a `memory.copy` or a `memory.fill` `op`,
+    /// touching `byte_count` bytes.
+    ///
+    /// It's charged on top of the `op_price` of `op`.
+    fn bulk_memory_price(&self, op: &Op, byte_count: usize) -> usize;
 }
 
 impl<R> PriceResolver for &R
@@ -21,4 +39,17 @@ where
     fn import_price(&self, import: (&str, &str)) -> usize {
         R::import_price(&self, import)
     }
+
+    fn storage_batch_price(
+        &self,
+        import: (&str, &str),
+        var_count: usize,
+        byte_count: usize,
+    ) -> usize {
+        R::storage_batch_price(self, import, var_count, byte_count)
+    }
+
+    fn bulk_memory_price(&self, op: &Op, byte_count: usize) -> usize {
+        R::bulk_memory_price(self, op, byte_count)
+    }
 }
@@ -30,10 +30,46 @@ impl PriceResolver for V0PriceResolver {
             ("svm", "svm_set32") => 1_000_000,
             ("svm", "svm_get64") => 200,
             ("svm", "svm_set64") => 2_000_000,
+            ("svm", "svm_get_bit") => 100,
+            ("svm", "svm_set_bit") => 250_000,
             ("svm", "svm_load160") => 500,
             ("svm", "svm_store160") => 5_000_000,
+            ("svm", "svm_load_dyn") => 1_000,
+            ("svm", "svm_store_dyn") => 5_000_000,
             ("svm", "svm_log") => 3_000,
+            ("svm", "svm_log_event") => 5_000,
+            ("svm", "svm_metric") => 500,
+            ("svm", "svm_call") => 10_000,
+            ("svm", "svm_call_returndata") => 20,
+            ("svm", "svm_transfer") => 4_000_000,
+            ("svm", "svm_layer") => 10,
+            ("svm", "svm_principal") => 20,
+            ("svm", "svm_tx_id") => 20,
+            ("svm", "svm_storage_read_many") => 100,
+            ("svm", "svm_storage_write_many") => 1_000,
+            ("svm", "svm_layout_len") => 10,
+            ("svm", "svm_layout_count") => 10,
             _ => 1000,
         }
     }
+
+    fn storage_batch_price(
+        &self,
+        import: (&str, &str),
+        var_count: usize,
+        byte_count: usize,
+    ) -> usize {
+        let (per_var, per_byte) = match import {
+            ("svm", "svm_storage_read_many") => (10, 25),
+            ("svm", "svm_storage_write_many") => (10_000, 250_000),
+            _ => unreachable!(),
+        };
+
+        var_count * per_var + byte_count * per_byte
+    }
+
+    fn bulk_memory_price(&self, _op: &Op, byte_count: usize) -> usize {
+        // A byte is priced as a (single byte) `load` and `store`
+        2 * byte_count
+    }
 }
@@ -2,6 +2,7 @@ use parity_wasm::elements::{CustomSection, Instruction};
 
 use svm_program::{FuncIndex, Op, Program, ProgramError, ProgramVisitor};
 
+use crate::memory::{is_bulk_memory, static_byte_count};
 use crate::{CallGraphBuilder, FixedGasError, GraphCycles};
 
 /// Further validates a smWasm [`Program`] according to the fixed-gas rules.
@@ -10,6 +11,8 @@ use crate::{CallGraphBuilder, FixedGasError, GraphCycles};
 ///
 /// * It contains the `loop` opcode.
 /// * It contains the `call_indirect` opcode.
+/// * It contains a `memory.copy` or a `memory.fill` whose `length` isn't statically bounded
+///   (i.e isn't pushed by an `i32.const` right before it).
 /// * It contains a call-cycles (at least one).
 ///   For example: function `F` calls function `G` which calls function `H` which calls again function `F`.
 ///   The chain of calls is: `F -> G -> H -> F`.
@@ -23,6 +26,9 @@ pub fn validate_wasm(program: &Program, return_cycles: bool) -> Result<(), Fixed
 pub struct ProgramValidator {
     current_func: Option<FuncIndex>,
 
+    /// The previously visited instruction of the current function.
+    prev_op: Option<Instruction>,
+
     builder: CallGraphBuilder<FuncIndex>,
 
     return_cycles: bool,
@@ -32,6 +38,7 @@ impl ProgramValidator {
     pub fn new(return_cycles: bool) -> Self {
         Self {
             current_func: None,
+            prev_op: None,
             builder: CallGraphBuilder::new(),
             return_cycles,
         }
@@ -94,6 +101,7 @@ impl ProgramVisitor for ProgramValidator {
         _program: &Program,
     ) -> Result<(), Self::Error> {
         self.current_func = Some(fn_index);
+        self.prev_op = None;
 
         self.builder.add_target(fn_index);
 
@@ -111,6 +119,15 @@ impl ProgramVisitor for ProgramValidator {
     }
 
     fn on_op(&mut self, op: &Op, program: &Program) -> Result<(), Self::Error> {
+        let prev_op = self.prev_op.replace(op.raw().clone());
+
+        if is_bulk_memory(op.raw()) && static_byte_count(prev_op.as_ref()).is_none() {
+            return Err(FixedGasError::UnboundedMemoryOp {
+                func: self.current_func(),
+                offset: op.offset(),
+            });
+        }
+
         match *op.raw() {
             Instruction::Loop(..) => Err(FixedGasError::LoopNotAllowed),
             Instruction::CallIndirect(..) => Err(FixedGasError::CallIndirectNotAllowed),
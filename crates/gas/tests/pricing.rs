@@ -35,6 +35,19 @@ impl PriceResolver for TestResolver {
             _ => unreachable!(),
         }
     }
+
+    fn storage_batch_price(
+        &self,
+        _import: (&str, &str),
+        _var_count: usize,
+        _byte_count: usize,
+    ) -> usize {
+        unreachable!()
+    }
+
+    fn bulk_memory_price(&self, _op: &Op, byte_count: usize) -> usize {
+        byte_count
+    }
 }
 
 macro_rules! func_price {
@@ -161,3 +174,44 @@ fn program_pricing_1() {
 
     assert_eq!(expected, actual);
 }
+
+#[test]
+fn program_pricing_bulk_memory() {
+    let wasm = r#"
+          (module
+            (memory 1)
+
+            ;; function #0
+            (func $fill
+                (i32.const 0)       ;; price = 1
+                (i32.const 0xFF)    ;; price = 1
+                (i32.const 65536)   ;; price = 1
+                (memory.fill))      ;; price = 1 + 65536
+
+            ;; function #1
+            (func $copy
+                (i32.const 0)       ;; price = 1
+                (i32.const 1024)    ;; price = 1
+                (i32.const 1024)    ;; price = 1
+                (memory.copy))      ;; price = 1 + 1024
+
+            ;; function #2 (amplifies the memory writes of a short function)
+            (func $amplify
+                (call $fill)        ;; price = 65540
+                (call $fill)        ;; price = 65540
+                (call $fill)        ;; price = 65540
+                (call $copy))       ;; price = 1028
+        )
+        "#;
+
+    let resolver = TestResolver::new(1);
+    let actual = helpers::price_program(wasm, Rc::new(resolver));
+
+    let expected = func_price! {
+        (func: 0, price: 3 + 1 + 65536),
+        (func: 1, price: 3 + 1 + 1024),
+        (func: 2, price: 3 * (3 + 1 + 65536) + (3 + 1 + 1024))
+    };
+
+    assert_eq!(expected, actual);
+}
@@ -85,3 +85,45 @@ fn validate_call_indirect_not_allowed() {
 
     assert_eq!(result, Err(FixedGasError::CallIndirectNotAllowed));
 }
+
+#[test]
+fn validate_unbounded_memory_op_not_allowed() {
+    let wasm = r#"
+          (module
+            (memory 1)
+
+            (func $func0 (param $len i32)
+                (i32.const 0)
+                (i32.const 0)
+                (local.get $len)
+                (memory.fill)))
+        "#;
+
+    let result = validate_wasm(wasm);
+
+    assert_eq!(
+        result,
+        Err(FixedGasError::UnboundedMemoryOp {
+            func: FuncIndex(0),
+            offset: 3,
+        }),
+    );
+}
+
+#[test]
+fn validate_bounded_memory_op_allowed() {
+    let wasm = r#"
+          (module
+            (memory 1)
+
+            (func $func0
+                (i32.const 0)
+                (i32.const 1024)
+                (i32.const 1024)
+                (memory.copy)))
+        "#;
+
+    let result = validate_wasm(wasm);
+
+    assert_eq!(result, Ok(()));
+}
@@ -18,6 +18,39 @@ impl Rocksdb {
             db: rocksdb::DB::open_default(path).unwrap(),
         }
     }
+
+    /// Returns the keys starting with `prefix` (in ascending order).
+    pub fn keys_with_prefix(&self, prefix: &[u8]) -> Vec<Vec<u8>> {
+        self.db
+            .prefix_iterator(prefix)
+            .map(|(key, _value)| key)
+            .take_while(|key| key.starts_with(prefix))
+            .map(|key| key.to_vec())
+            .collect()
+    }
+
+    /// Returns up to `limit` keys starting with `prefix` and following `prefix` + `after`
+    /// (in ascending order). A `None` starts from the first key starting with `prefix`.
+    ///
+    /// The iteration seeks right to `after`, so the keys preceding it aren't visited.
+    pub fn keys_with_prefix_after(
+        &self,
+        prefix: &[u8],
+        after: Option<&[u8]>,
+        limit: usize,
+    ) -> Vec<Vec<u8>> {
+        let start = [prefix, after.unwrap_or_default()].concat();
+        let mode = rocksdb::IteratorMode::From(&start, rocksdb::Direction::Forward);
+
+        self.db
+            .iterator(mode)
+            .map(|(key, _value)| key)
+            .skip_while(|key| after.is_some() && key[..] == start[..])
+            .take_while(|key| key.starts_with(prefix))
+            .take(limit)
+            .map(|key| key.to_vec())
+            .collect()
+    }
 }
 
 impl RawKV for Rocksdb {
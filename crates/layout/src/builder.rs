@@ -3,7 +3,7 @@ use crate::{FixedLayout, Id, RawVar};
 /// Specifies the fixed-sized variables
 pub struct FixedLayoutBuilder {
     first: Option<Id>,
-    vars: Vec<(u32, u32)>,
+    vars: Vec<(u32, u32, Option<u8>)>,
     next_offset: u32,
 }
 
@@ -43,13 +43,18 @@ impl FixedLayoutBuilder {
 
     /// Append new variables to layout.
     /// Each item of `slice` contains the corresponding variable's `byte size`.
+    /// A `byte size` of zero stands for a packed boolean (see [`FixedLayoutBuilder::push_bit`]).
     ///
     /// If prior to calling, `vars` had 4 items,
     /// then the first item of `slice` will map to variable `first + 4`.
     /// `slice[1]` will map to the next variable (`first + 5`) and so on.
     pub fn extend_from_slice(&mut self, slice: &[u32]) {
-        for len in slice.iter() {
-            self.push(*len);
+        for &len in slice.iter() {
+            if len == 0 {
+                self.push_bit();
+            } else {
+                self.push(len);
+            }
         }
     }
 
@@ -59,10 +64,27 @@ impl FixedLayoutBuilder {
     /// The starting offset is right after where the previous variable ended.
     pub fn push(&mut self, len: u32) {
         let offset = self.next_offset;
-        self.vars.push((offset, len));
+        self.vars.push((offset, len, None));
         self.next_offset += len;
     }
 
+    /// Adds the next variable as a packed boolean.
+    ///
+    /// Consecutive packed booleans share the same byte (up to 8 of them),
+    /// otherwise a new byte is allocated right after where the previous variable ended.
+    pub fn push_bit(&mut self) {
+        match self.vars.last() {
+            Some(&(offset, _, Some(bit))) if bit < 7 => {
+                self.vars.push((offset, 1, Some(bit + 1)));
+            }
+            _ => {
+                let offset = self.next_offset;
+                self.vars.push((offset, 1, Some(0)));
+                self.next_offset += 1;
+            }
+        }
+    }
+
     /// Finishes the layout building process and outputs the result `Layout`.
     pub fn build(self) -> FixedLayout {
         if self.vars.is_empty() {
@@ -77,10 +99,13 @@ impl FixedLayoutBuilder {
             .vars
             .iter()
             .enumerate()
-            .map(|(i, &(offset, byte_size))| {
+            .map(|(i, &(offset, byte_size, bit))| {
                 let id = Id(first + i as u32);
 
-                RawVar::new(id, offset, byte_size)
+                match bit {
+                    Some(bit) => RawVar::new_bit(id, offset, bit),
+                    None => RawVar::new(id, offset, byte_size),
+                }
             })
             .collect();
 
@@ -0,0 +1,68 @@
+use crate::Id;
+
+/// In-memory representation of a program's dynamic storage variables.
+///
+/// A dynamic variable (a keyed `Mapping` or a `DynVec`) holds an unbounded number of entries,
+/// each addressed by the hash of the variable's `Id` along with the entry's key.
+///
+/// Its `Id` is allocated by the `FixedLayout` as well (holding a fixed-sized summary
+/// of the variable, such as its number of entries).
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct DynamicLayout {
+    ids: Vec<Id>,
+}
+
+impl DynamicLayout {
+    pub fn new(mut ids: Vec<Id>) -> Self {
+        ids.sort_unstable();
+        ids.dedup();
+
+        Self { ids }
+    }
+
+    /// Whether variable `id` is a dynamic one.
+    #[inline]
+    pub fn contains(&self, id: Id) -> bool {
+        self.ids.binary_search(&id).is_ok()
+    }
+
+    /// Returns an iterator over the dynamic variables `Id`s (in ascending order).
+    pub fn iter(&self) -> impl Iterator<Item = Id> + '_ {
+        self.ids.iter().copied()
+    }
+
+    /// The number of dynamic variables.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Whether the layout has dynamic variables.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+impl From<&[u32]> for DynamicLayout {
+    fn from(ids: &[u32]) -> Self {
+        Self::new(ids.iter().map(|id| Id(*id)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dynamic_layout_contains() {
+        let layout: DynamicLayout = [5, 2, 5].as_slice().into();
+
+        assert_eq!(layout.len(), 2);
+        assert_eq!(layout.iter().collect::<Vec<_>>(), vec![Id(2), Id(5)]);
+
+        assert!(layout.contains(Id(2)));
+        assert!(layout.contains(Id(5)));
+        assert!(!layout.contains(Id(3)));
+    }
+}
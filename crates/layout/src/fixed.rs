@@ -224,4 +224,43 @@ mod tests {
         assert_eq!(third, None);
         assert_eq!(fourth, None);
     }
+
+    #[test]
+    fn layout_push_bit() {
+        let mut builder = FixedLayoutBuilder::with_capacity(12);
+
+        builder.set_first(Id(0));
+        builder.push(4);
+
+        for _ in 0..9 {
+            builder.push_bit();
+        }
+
+        builder.push(8);
+        builder.push_bit();
+
+        let layout = builder.build();
+
+        assert_eq!(layout.get(Id(0)), &RawVar::new(Id(0), 0, 4));
+
+        for i in 0..8 {
+            let var = layout.get(Id(1 + i));
+
+            assert_eq!(var, &RawVar::new_bit(Id(1 + i), 4, i as u8));
+            assert_eq!(var.byte_owner(), Id(1));
+        }
+
+        assert_eq!(layout.get(Id(9)), &RawVar::new_bit(Id(9), 5, 0));
+        assert_eq!(layout.get(Id(10)), &RawVar::new(Id(10), 6, 8));
+        assert_eq!(layout.get(Id(11)), &RawVar::new_bit(Id(11), 14, 0));
+    }
+
+    #[test]
+    fn layout_extend_from_slice_with_bits() {
+        let layout: FixedLayout = vec![4, 0, 0, 2].into();
+
+        assert_eq!(layout.get(Id(1)), &RawVar::new_bit(Id(1), 4, 0));
+        assert_eq!(layout.get(Id(2)), &RawVar::new_bit(Id(2), 4, 1));
+        assert_eq!(layout.get(Id(3)), &RawVar::new(Id(3), 5, 2));
+    }
 }
@@ -6,41 +6,54 @@
 //! This crate is responsible of representing an `Account`'s storage variables `Layout`.
 
 mod builder;
+mod dynamic;
 mod fixed;
 mod var;
 
 pub use builder::FixedLayoutBuilder;
+pub use dynamic::DynamicLayout;
 pub use fixed::FixedLayout;
 pub use var::{Id, Primitive, RawVar, SymbolicVar, Type};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum LayoutKind {
     Fixed,
-    //
-    // TODO: In the future:
-    // Dynamic
-    // <https://github.com/spacemeshos/svm/issues/281>
+
+    Dynamic,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Layout {
     Fixed(FixedLayout),
-    //
-    // TODO: In the future:
-    // Dynamic
-    // <https://github.com/spacemeshos/svm/issues/281>
+
+    Dynamic(DynamicLayout),
 }
 
 impl Layout {
     pub fn kind(&self) -> LayoutKind {
         match self {
             Self::Fixed(..) => LayoutKind::Fixed,
+            Self::Dynamic(..) => LayoutKind::Dynamic,
         }
     }
 
+    /// # Panics
+    ///
+    /// Panics if the `Layout` isn't a `Fixed` one.
     pub fn as_fixed(&self) -> &FixedLayout {
         match self {
             Self::Fixed(layout) => layout,
+            Self::Dynamic(..) => panic!("expected a `Fixed` layout"),
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the `Layout` isn't a `Dynamic` one.
+    pub fn as_dynamic(&self) -> &DynamicLayout {
+        match self {
+            Self::Dynamic(layout) => layout,
+            Self::Fixed(..) => panic!("expected a `Dynamic` layout"),
         }
     }
 }
@@ -28,6 +28,8 @@ pub struct RawVar {
     offset: u32,
 
     byte_size: u32,
+
+    bit: Option<u8>,
 }
 
 impl RawVar {
@@ -36,6 +38,21 @@ impl RawVar {
             id,
             offset,
             byte_size,
+            bit: None,
+        }
+    }
+
+    /// New packed boolean variable, stored as bit `bit` of the byte at `offset`.
+    ///
+    /// Up to 8 consecutive packed booleans share the same byte (see [`crate::FixedLayoutBuilder::push_bit`]).
+    pub fn new_bit(id: Id, offset: u32, bit: u8) -> Self {
+        assert!(bit < 8);
+
+        Self {
+            id,
+            offset,
+            byte_size: 1,
+            bit: Some(bit),
         }
     }
 
@@ -50,6 +67,20 @@ impl RawVar {
     pub fn byte_size(&self) -> u32 {
         self.byte_size
     }
+
+    /// The bit (within the byte at `offset`) of a packed boolean variable (`None` for other variables).
+    pub fn bit(&self) -> Option<u8> {
+        self.bit
+    }
+
+    /// The variable owning the byte of a packed boolean variable (the one at bit `0`).
+    ///
+    /// For any other variable, returns its own `Id`.
+    pub fn byte_owner(&self) -> Id {
+        let bit = self.bit.unwrap_or(0);
+
+        Id(self.id.0 - bit as u32)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
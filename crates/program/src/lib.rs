@@ -13,6 +13,7 @@ mod error;
 mod exports;
 mod function;
 mod import;
+mod link;
 mod op;
 mod program;
 mod validators;
@@ -22,6 +23,7 @@ pub use error::ProgramError;
 pub use exports::Exports;
 pub use function::{FuncIndex, FuncIterator, Function};
 pub use import::Imports;
+pub use link::{LinkError, Linker};
 pub use op::Op;
 pub use program::Program;
 pub use validators::OpcodeValidator;
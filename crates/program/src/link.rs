@@ -0,0 +1,796 @@
+//! Static linking of several Wasm modules into a single module (see [`Linker`]).
+
+use indexmap::IndexMap;
+use thiserror::Error;
+
+use parity_wasm::elements as pwasm;
+use pwasm::{External, Instruction, Internal};
+
+use std::collections::HashMap;
+
+use crate::{Program, ProgramError};
+
+/// The maximum length of a chain of imports re-exported by the linked modules.
+const MAX_IMPORT_CHAIN: usize = 64;
+
+/// Represents error that may occur while linking Wasm modules
+#[derive(Debug, PartialEq, Clone, Error)]
+pub enum LinkError {
+    /// There are no modules to link
+    #[error("There are no modules to link")]
+    NoModules,
+
+    /// Module isn't valid Wasm
+    #[error("Module `{0}` isn't valid Wasm")]
+    InvalidWasm(String),
+
+    /// Module has been added more than once
+    #[error("Module `{0}` has been added more than once")]
+    DuplicateModule(String),
+
+    /// An import refers to a linked module, which doesn't export it
+    #[error("Import `{import}` of module `{module}` can't be resolved")]
+    UnresolvedImport {
+        /// The importing module
+        module: String,
+
+        /// The import (formatted as `module.name`)
+        import: String,
+    },
+
+    /// An import doesn't match the kind (or the signature) of its definition
+    #[error("Import `{import}` of module `{module}` doesn't match its definition")]
+    ImportMismatch {
+        /// The importing module
+        module: String,
+
+        /// The import (formatted as `module.name`)
+        import: String,
+    },
+
+    /// An import refers back to itself (through other modules)
+    #[error("Import `{import}` of module `{module}` is cyclic")]
+    CyclicImport {
+        /// The importing module
+        module: String,
+
+        /// The import (formatted as `module.name`)
+        import: String,
+    },
+
+    /// A requested export doesn't exist
+    #[error("Module `{module}` has no export `{name}`")]
+    ExportNotFound {
+        /// The exporting module
+        module: String,
+
+        /// The export name
+        name: String,
+    },
+
+    /// Two exports of the linked module have the same name
+    #[error("Export `{0}` is defined more than once")]
+    DuplicateExport(String),
+
+    /// The modules use a Wasm feature the linker doesn't support
+    #[error("Linking isn't supported: {0}")]
+    NotSupported(String),
+
+    /// The linked module isn't a valid smWasm program
+    #[error("The linked module is invalid: {0}")]
+    Program(ProgramError),
+}
+
+#[derive(Debug, Clone)]
+struct LinkExport {
+    module: String,
+    name: String,
+    export_as: String,
+}
+
+/// Links several Wasm modules into a single module.
+///
+/// Each module is added under a name. An import whose module name is the name of one of the
+/// linked modules is resolved against the export of that module (of the same name and kind).
+/// Any other import is kept as an import of the linked module (identical imports are merged).
+///
+/// The imports can be renamed using [`Linker::alias`]. By default the linked module has the exports
+/// of the first module added, use [`Linker::export`] to choose them explicitly.
+///
+/// The linked modules should share a single memory (either imported by each of them, or defined by
+/// one module and imported from it by the others). The data segments are kept at their offsets, so the
+/// modules should be built not to overlap. At most one module may have a table.
+#[derive(Debug, Default)]
+pub struct Linker {
+    modules: IndexMap<String, pwasm::Module>,
+    aliases: HashMap<(String, String), (String, String)>,
+    exports: Vec<LinkExport>,
+}
+
+impl Linker {
+    /// Creates a new `Linker` with no modules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the Wasm module `wasm` named `name`
+    pub fn add_module(&mut self, name: &str, wasm: &[u8]) -> Result<(), LinkError> {
+        if self.modules.contains_key(name) {
+            return Err(LinkError::DuplicateModule(name.to_string()));
+        }
+
+        let module = parity_wasm::deserialize_buffer(wasm)
+            .map_err(|_| LinkError::InvalidWasm(name.to_string()))?;
+
+        self.modules.insert(name.to_string(), module);
+
+        Ok(())
+    }
+
+    /// Calls [`Linker::add_module`] after transforming `wat` from
+    /// WebAssembly Text Format to WebAssembly Binary Format.
+    pub fn add_wat(&mut self, name: &str, wat: &str) -> Result<(), LinkError> {
+        let wasm = wat::parse_str(wat).map_err(|_| LinkError::InvalidWasm(name.to_string()))?;
+
+        self.add_module(name, &wasm)
+    }
+
+    /// Renames the imports `import_module.import_name` (of any module) to `target_module.target_name`.
+    ///
+    /// When `target_module` is one of the linked modules the imports are resolved against its export,
+    /// otherwise they're kept as (renamed) imports of the linked module.
+    pub fn alias(
+        &mut self,
+        import_module: &str,
+        import_name: &str,
+        target_module: &str,
+        target_name: &str,
+    ) {
+        self.aliases.insert(
+            (import_module.to_string(), import_name.to_string()),
+            (target_module.to_string(), target_name.to_string()),
+        );
+    }
+
+    /// Exports `module.name` from the linked module as `export_as`.
+    ///
+    /// Once called, only the explicitly requested exports are exported.
+    pub fn export(&mut self, module: &str, name: &str, export_as: &str) {
+        self.exports.push(LinkExport {
+            module: module.to_string(),
+            name: name.to_string(),
+            export_as: export_as.to_string(),
+        });
+    }
+
+    /// Links the modules and returns the linked module (after validating it's a valid smWasm program).
+    pub fn link(&self) -> Result<Vec<u8>, LinkError> {
+        if self.modules.is_empty() {
+            return Err(LinkError::NoModules);
+        }
+
+        let module = Linking::new(self)?.run()?;
+
+        let wasm = parity_wasm::serialize(module)
+            .map_err(|_| LinkError::Program(ProgramError::InvalidWasm))?;
+
+        Program::new(&wasm, false).map_err(LinkError::Program)?;
+
+        Ok(wasm)
+    }
+
+    fn target_of<'a>(&'a self, entry: &'a pwasm::ImportEntry) -> (&'a str, &'a str) {
+        let key = (entry.module().to_string(), entry.field().to_string());
+
+        match self.aliases.get(&key) {
+            Some((module, name)) => (module, name),
+            None => (entry.module(), entry.field()),
+        }
+    }
+}
+
+/// The index spaces relocated by the linker.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Space {
+    Func,
+    Global,
+    Memory,
+}
+
+impl Space {
+    fn of_external(external: &External) -> Option<Space> {
+        match external {
+            External::Function(..) => Some(Space::Func),
+            External::Global(..) => Some(Space::Global),
+            External::Memory(..) => Some(Space::Memory),
+            External::Table(..) => None,
+        }
+    }
+
+    fn of_internal(internal: &Internal) -> Option<(Space, u32)> {
+        match *internal {
+            Internal::Function(index) => Some((Space::Func, index)),
+            Internal::Global(index) => Some((Space::Global, index)),
+            Internal::Memory(index) => Some((Space::Memory, index)),
+            Internal::Table(..) => None,
+        }
+    }
+}
+
+/// A module along with its relocations (the new index of each of its items).
+struct Linked<'a> {
+    name: &'a str,
+    module: &'a pwasm::Module,
+    types: Vec<u32>,
+    relocations: HashMap<Space, Vec<u32>>,
+}
+
+impl<'a> Linked<'a> {
+    fn imports(&self) -> impl Iterator<Item = &'a pwasm::ImportEntry> {
+        self.module
+            .import_section()
+            .map_or(&[][..], |section| section.entries())
+            .iter()
+    }
+
+    fn imports_of(&self, space: Space) -> Vec<&'a pwasm::ImportEntry> {
+        self.imports()
+            .filter(|entry| Space::of_external(entry.external()) == Some(space))
+            .collect()
+    }
+
+    fn defined_count(&self, space: Space) -> usize {
+        match space {
+            Space::Func => self.funcs().len(),
+            Space::Global => self.globals().len(),
+            Space::Memory => self.memories().len(),
+        }
+    }
+
+    fn funcs(&self) -> &'a [pwasm::Func] {
+        self.module
+            .function_section()
+            .map_or(&[][..], |section| section.entries())
+    }
+
+    fn globals(&self) -> &'a [pwasm::GlobalEntry] {
+        self.module
+            .global_section()
+            .map_or(&[][..], |section| section.entries())
+    }
+
+    fn memories(&self) -> &'a [pwasm::MemoryType] {
+        self.module
+            .memory_section()
+            .map_or(&[][..], |section| section.entries())
+    }
+
+    fn tables(&self) -> &'a [pwasm::TableType] {
+        self.module
+            .table_section()
+            .map_or(&[][..], |section| section.entries())
+    }
+
+    fn exports(&self) -> &'a [pwasm::ExportEntry] {
+        self.module
+            .export_section()
+            .map_or(&[][..], |section| section.entries())
+    }
+
+    fn export(&self, name: &str) -> Option<&'a pwasm::ExportEntry> {
+        self.exports().iter().find(|export| export.field() == name)
+    }
+
+    /// The type (within the linked module) of function `index`
+    fn func_type(&self, index: u32) -> Option<u32> {
+        let imports = self.imports_of(Space::Func);
+        let index = index as usize;
+
+        let type_ref = if index < imports.len() {
+            match imports[index].external() {
+                External::Function(type_ref) => *type_ref,
+                _ => unreachable!(),
+            }
+        } else {
+            self.funcs().get(index - imports.len())?.type_ref()
+        };
+
+        self.types.get(type_ref as usize).copied()
+    }
+
+    /// The type of global `index`
+    fn global_type(&self, index: u32) -> Option<pwasm::GlobalType> {
+        let imports = self.imports_of(Space::Global);
+        let index = index as usize;
+
+        if index < imports.len() {
+            match imports[index].external() {
+                External::Global(ty) => Some(*ty),
+                _ => unreachable!(),
+            }
+        } else {
+            self.globals()
+                .get(index - imports.len())
+                .map(|global| *global.global_type())
+        }
+    }
+
+    fn relocate(&self, space: Space, index: u32) -> Result<u32, LinkError> {
+        self.relocations[&space]
+            .get(index as usize)
+            .copied()
+            .ok_or_else(|| LinkError::InvalidWasm(self.name.to_string()))
+    }
+
+    fn relocate_type(&self, index: u32) -> Result<u32, LinkError> {
+        self.types
+            .get(index as usize)
+            .copied()
+            .ok_or_else(|| LinkError::InvalidWasm(self.name.to_string()))
+    }
+
+    fn relocate_code(&self, code: &[Instruction]) -> Result<Vec<Instruction>, LinkError> {
+        code.iter()
+            .map(|op| {
+                let op = match *op {
+                    Instruction::Call(f) => Instruction::Call(self.relocate(Space::Func, f)?),
+                    Instruction::CallIndirect(t, table) => {
+                        Instruction::CallIndirect(self.relocate_type(t)?, table)
+                    }
+                    Instruction::GetGlobal(g) => {
+                        Instruction::GetGlobal(self.relocate(Space::Global, g)?)
+                    }
+                    Instruction::SetGlobal(g) => {
+                        Instruction::SetGlobal(self.relocate(Space::Global, g)?)
+                    }
+                    ref op => op.clone(),
+                };
+
+                Ok(op)
+            })
+            .collect()
+    }
+
+    fn relocate_init(
+        &self,
+        init: &Option<pwasm::InitExpr>,
+    ) -> Result<Option<pwasm::InitExpr>, LinkError> {
+        init.as_ref()
+            .map(|init| self.relocate_code(init.code()).map(pwasm::InitExpr::new))
+            .transpose()
+    }
+}
+
+/// The state of a single linking.
+struct Linking<'a> {
+    linker: &'a Linker,
+    linked: Vec<Linked<'a>>,
+    types: Vec<pwasm::FunctionType>,
+    host_imports: IndexMap<(Space, String, String), External>,
+}
+
+impl<'a> Linking<'a> {
+    fn new(linker: &'a Linker) -> Result<Self, LinkError> {
+        let mut me = Self {
+            linker,
+            linked: Vec::with_capacity(linker.modules.len()),
+            types: Vec::new(),
+            host_imports: IndexMap::new(),
+        };
+
+        for (name, module) in linker.modules.iter() {
+            let types = me.merge_types(module);
+
+            me.linked.push(Linked {
+                name,
+                module,
+                types,
+                relocations: HashMap::new(),
+            });
+        }
+
+        me.check_supported()?;
+        me.collect_host_imports()?;
+
+        Ok(me)
+    }
+
+    fn run(mut self) -> Result<pwasm::Module, LinkError> {
+        for space in [Space::Func, Space::Global, Space::Memory].iter().copied() {
+            self.relocate_space(space)?;
+        }
+
+        let memories = self.count_host(Space::Memory)
+            + self
+                .linked
+                .iter()
+                .map(|linked| linked.memories().len())
+                .sum::<usize>();
+
+        if memories > 1 {
+            return Err(LinkError::NotSupported(
+                "the modules must share a single memory".to_string(),
+            ));
+        }
+
+        self.emit()
+    }
+
+    fn merge_types(&mut self, module: &pwasm::Module) -> Vec<u32> {
+        let types = module
+            .type_section()
+            .map_or(&[][..], |section| section.types());
+
+        types
+            .iter()
+            .map(|pwasm::Type::Function(ty)| {
+                let index = match self.types.iter().position(|t| t == ty) {
+                    Some(index) => index,
+                    None => {
+                        self.types.push(ty.clone());
+                        self.types.len() - 1
+                    }
+                };
+
+                index as u32
+            })
+            .collect()
+    }
+
+    fn check_supported(&self) -> Result<(), LinkError> {
+        let not_supported = |reason: &str| Err(LinkError::NotSupported(reason.to_string()));
+
+        let imports_table = self.linked.iter().any(|linked| {
+            linked
+                .imports()
+                .any(|entry| Space::of_external(entry.external()).is_none())
+        });
+
+        if imports_table {
+            return not_supported("table imports");
+        }
+
+        let tables = self
+            .linked
+            .iter()
+            .filter(|linked| !linked.tables().is_empty())
+            .count();
+
+        if tables > 1 {
+            return not_supported("more than one module having a table");
+        }
+
+        let starts = self
+            .linked
+            .iter()
+            .filter(|linked| linked.module.start_section().is_some())
+            .count();
+
+        if starts > 1 {
+            return not_supported("more than one module having a start function");
+        }
+
+        Ok(())
+    }
+
+    fn collect_host_imports(&mut self) -> Result<(), LinkError> {
+        for linked in self.linked.iter() {
+            for entry in linked.imports() {
+                let (target_module, target_name) = self.linker.target_of(entry);
+
+                if self.linker.modules.contains_key(target_module) {
+                    continue;
+                }
+
+                let external = match *entry.external() {
+                    External::Function(type_ref) => {
+                        External::Function(linked.relocate_type(type_ref)?)
+                    }
+                    other => other,
+                };
+
+                let space = Space::of_external(&external).unwrap();
+                let key = (space, target_module.to_string(), target_name.to_string());
+
+                match self.host_imports.get(&key) {
+                    Some(existing) if existing != &external => {
+                        return Err(LinkError::ImportMismatch {
+                            module: linked.name.to_string(),
+                            import: format!("{}.{}", entry.module(), entry.field()),
+                        });
+                    }
+                    Some(..) => (),
+                    None => {
+                        self.host_imports.insert(key, external);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn count_host(&self, space: Space) -> usize {
+        self.host_imports
+            .keys()
+            .filter(|(s, _, _)| *s == space)
+            .count()
+    }
+
+    fn host_index(&self, space: Space, module: &str, name: &str) -> u32 {
+        let index = self
+            .host_imports
+            .keys()
+            .filter(|(s, _, _)| *s == space)
+            .position(|(_, m, n)| m == module && n == name)
+            .unwrap();
+
+        index as u32
+    }
+
+    fn relocate_space(&mut self, space: Space) -> Result<(), LinkError> {
+        let mut base = self.count_host(space) as u32;
+        let mut bases = Vec::with_capacity(self.linked.len());
+
+        for linked in self.linked.iter() {
+            bases.push(base);
+            base += linked.defined_count(space) as u32;
+        }
+
+        let mut relocations = Vec::with_capacity(self.linked.len());
+
+        for (m, linked) in self.linked.iter().enumerate() {
+            let imports = linked.imports_of(space);
+            let mut relocation = Vec::with_capacity(imports.len() + linked.defined_count(space));
+
+            for (index, _entry) in imports.iter().enumerate() {
+                let index = self.resolve(space, m, index as u32, &bases, 0)?;
+                relocation.push(index);
+            }
+
+            for i in 0..linked.defined_count(space) {
+                relocation.push(bases[m] + i as u32);
+            }
+
+            relocations.push(relocation);
+        }
+
+        for (linked, relocation) in self.linked.iter_mut().zip(relocations) {
+            linked.relocations.insert(space, relocation);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the index (within the linked module) of item `index` of module `m`.
+    fn resolve(
+        &self,
+        space: Space,
+        m: usize,
+        index: u32,
+        bases: &[u32],
+        depth: usize,
+    ) -> Result<u32, LinkError> {
+        let linked = &self.linked[m];
+        let imports = linked.imports_of(space);
+
+        let entry = match imports.get(index as usize) {
+            Some(entry) => entry,
+            None => return Ok(bases[m] + index - imports.len() as u32),
+        };
+
+        let import = || format!("{}.{}", entry.module(), entry.field());
+        let (target_module, target_name) = self.linker.target_of(entry);
+
+        let target = match self.linker.modules.get_index_of(target_module) {
+            Some(target) => target,
+            None => return Ok(self.host_index(space, target_module, target_name)),
+        };
+
+        if depth >= MAX_IMPORT_CHAIN {
+            return Err(LinkError::CyclicImport {
+                module: linked.name.to_string(),
+                import: import(),
+            });
+        }
+
+        let export = self.linked[target]
+            .export(target_name)
+            .and_then(|export| Space::of_internal(export.internal()));
+
+        let target_index = match export {
+            Some((s, target_index)) if s == space => target_index,
+            Some(..) => {
+                return Err(LinkError::ImportMismatch {
+                    module: linked.name.to_string(),
+                    import: import(),
+                })
+            }
+            None => {
+                return Err(LinkError::UnresolvedImport {
+                    module: linked.name.to_string(),
+                    import: import(),
+                })
+            }
+        };
+
+        let matches = match space {
+            Space::Func => linked.func_type(index) == self.linked[target].func_type(target_index),
+            Space::Global => {
+                linked.global_type(index) == self.linked[target].global_type(target_index)
+            }
+            Space::Memory => true,
+        };
+
+        if !matches {
+            return Err(LinkError::ImportMismatch {
+                module: linked.name.to_string(),
+                import: import(),
+            });
+        }
+
+        self.resolve(space, target, target_index, bases, depth + 1)
+    }
+
+    fn emit(self) -> Result<pwasm::Module, LinkError> {
+        let types = self.types.iter().cloned().map(pwasm::Type::Function);
+
+        let imports = self
+            .host_imports
+            .iter()
+            .map(|((_, module, name), external)| {
+                pwasm::ImportEntry::new(module.clone(), name.clone(), *external)
+            })
+            .collect();
+
+        let mut funcs = Vec::new();
+        let mut bodies = Vec::new();
+        let mut tables = Vec::new();
+        let mut memories = Vec::new();
+        let mut globals = Vec::new();
+        let mut elements = Vec::new();
+        let mut data = Vec::new();
+        let mut start = None;
+
+        for linked in self.linked.iter() {
+            for func in linked.funcs() {
+                funcs.push(pwasm::Func::new(linked.relocate_type(func.type_ref())?));
+            }
+
+            let code = linked.module.code_section();
+            for body in code.map_or(&[][..], |code| code.bodies()) {
+                let ops = linked.relocate_code(body.code().elements())?;
+
+                bodies.push(pwasm::FuncBody::new(
+                    body.locals().to_vec(),
+                    pwasm::Instructions::new(ops),
+                ));
+            }
+
+            tables.extend_from_slice(linked.tables());
+            memories.extend_from_slice(linked.memories());
+
+            for global in linked.globals() {
+                let init = linked.relocate_code(global.init_expr().code())?;
+
+                globals.push(pwasm::GlobalEntry::new(
+                    *global.global_type(),
+                    pwasm::InitExpr::new(init),
+                ));
+            }
+
+            let segments = linked.module.elements_section();
+            for segment in segments.map_or(&[][..], |segment| segment.entries()) {
+                let members = segment
+                    .members()
+                    .iter()
+                    .map(|f| linked.relocate(Space::Func, *f))
+                    .collect::<Result<_, _>>()?;
+
+                elements.push(pwasm::ElementSegment::new(
+                    segment.index(),
+                    linked.relocate_init(segment.offset())?,
+                    members,
+                ));
+            }
+
+            let segments = linked.module.data_section();
+            for segment in segments.map_or(&[][..], |segment| segment.entries()) {
+                data.push(pwasm::DataSegment::new(
+                    segment.index(),
+                    linked.relocate_init(segment.offset())?,
+                    segment.value().to_vec(),
+                ));
+            }
+
+            if let Some(f) = linked.module.start_section() {
+                start = Some(linked.relocate(Space::Func, f)?);
+            }
+        }
+
+        let exports = self.emit_exports()?;
+
+        let mut sections = vec![
+            pwasm::Section::Type(pwasm::TypeSection::with_types(types.collect())),
+            pwasm::Section::Import(pwasm::ImportSection::with_entries(imports)),
+            pwasm::Section::Function(pwasm::FunctionSection::with_entries(funcs)),
+            pwasm::Section::Table(pwasm::TableSection::with_entries(tables)),
+            pwasm::Section::Memory(pwasm::MemorySection::with_entries(memories)),
+            pwasm::Section::Global(pwasm::GlobalSection::with_entries(globals)),
+            pwasm::Section::Export(pwasm::ExportSection::with_entries(exports)),
+        ];
+
+        if let Some(start) = start {
+            sections.push(pwasm::Section::Start(start));
+        }
+
+        sections.push(pwasm::Section::Element(
+            pwasm::ElementSection::with_entries(elements),
+        ));
+        sections.push(pwasm::Section::Code(pwasm::CodeSection::with_bodies(
+            bodies,
+        )));
+        sections.push(pwasm::Section::Data(pwasm::DataSection::with_entries(data)));
+
+        Ok(pwasm::Module::new(sections))
+    }
+
+    fn emit_exports(&self) -> Result<Vec<pwasm::ExportEntry>, LinkError> {
+        let requested: Vec<(usize, &pwasm::ExportEntry, &str)> = if self.linker.exports.is_empty() {
+            let first = &self.linked[0];
+
+            first
+                .exports()
+                .iter()
+                .map(|export| (0, export, export.field()))
+                .collect()
+        } else {
+            self.linker
+                .exports
+                .iter()
+                .map(|e| {
+                    let not_found = || LinkError::ExportNotFound {
+                        module: e.module.clone(),
+                        name: e.name.clone(),
+                    };
+
+                    let m = self
+                        .linker
+                        .modules
+                        .get_index_of(&e.module)
+                        .ok_or_else(not_found)?;
+                    let export = self.linked[m].export(&e.name).ok_or_else(not_found)?;
+
+                    Ok((m, export, e.export_as.as_str()))
+                })
+                .collect::<Result<_, LinkError>>()?
+        };
+
+        let mut exports: IndexMap<&str, pwasm::ExportEntry> = IndexMap::new();
+
+        for (m, export, export_as) in requested {
+            let linked = &self.linked[m];
+
+            let internal = match Space::of_internal(export.internal()) {
+                Some((Space::Func, index)) => {
+                    Internal::Function(linked.relocate(Space::Func, index)?)
+                }
+                Some((Space::Global, index)) => {
+                    Internal::Global(linked.relocate(Space::Global, index)?)
+                }
+                Some((Space::Memory, index)) => {
+                    Internal::Memory(linked.relocate(Space::Memory, index)?)
+                }
+                None => *export.internal(),
+            };
+
+            let entry = pwasm::ExportEntry::new(export_as.to_string(), internal);
+
+            if exports.insert(export_as, entry).is_some() {
+                return Err(LinkError::DuplicateExport(export_as.to_string()));
+            }
+        }
+
+        Ok(exports.into_iter().map(|(_, entry)| entry).collect())
+    }
+}
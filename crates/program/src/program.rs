@@ -31,6 +31,7 @@ pub struct Program {
     imports: Imports,
     exports: Exports,
     functions: IndexMap<FuncIndex, Vec<Instruction>>,
+    data_size: u64,
 }
 
 impl Program {
@@ -55,6 +56,7 @@ impl Program {
 
         program.set_imports(imports);
         program.set_exports(exports);
+        program.data_size = read_data_size(&module);
 
         validate_no_floats(&program)?;
         if validate_exports {
@@ -119,6 +121,11 @@ impl Program {
         Function::new(fn_index, code)
     }
 
+    /// The total byte size of the data segments (i.e the bytes copied into the memory on instantiation).
+    pub fn data_size(&self) -> u64 {
+        self.data_size
+    }
+
     /// Returns the indexes of the non-import functions
     pub fn func_indexes(&self) -> Vec<FuncIndex> {
         self.functions.keys().copied().collect()
@@ -138,6 +145,15 @@ fn read_code(module: &pwasm::Module) -> Result<pwasm::CodeSection, ProgramError>
     }
 }
 
+fn read_data_size(module: &pwasm::Module) -> u64 {
+    module.data_section().map_or(0, |data| {
+        data.entries()
+            .iter()
+            .map(|segment| segment.value().len() as u64)
+            .sum()
+    })
+}
+
 fn count_functions_in_program(program: &Program) -> u64 {
     #[derive(Debug, Default, Copy, Clone)]
     struct Counter(u64);
@@ -0,0 +1,126 @@
+use parity_wasm::elements::{Internal, Module};
+
+use svm_program::{LinkError, Linker, Program};
+
+fn main_wat() -> &'static str {
+    r#"
+      (module
+        (func $log (import "svm" "svm_log") (param i32 i32))
+        (func $add (import "math" "add") (param i32 i32) (result i32))
+        (import "svm" "memory" (memory 1))
+
+        (func (export "run") (result i32)
+          (call $log (i32.const 0) (i32.const 4))
+          (call $add (i32.const 10) (i32.const 20))))
+    "#
+}
+
+fn math_wat() -> &'static str {
+    r#"
+      (module
+        (func $log (import "svm" "svm_log") (param i32 i32))
+        (import "svm" "memory" (memory 1))
+
+        (global $calls (mut i32) (i32.const 0))
+
+        (func $add (export "add") (param i32 i32) (result i32)
+          (global.set $calls (i32.add (global.get $calls) (i32.const 1)))
+          (call $log (i32.const 8) (i32.const 4))
+          (i32.add (local.get 0) (local.get 1))))
+    "#
+}
+
+fn export_index(module: &Module, name: &str) -> Internal {
+    let exports = module.export_section().unwrap().entries();
+    let export = exports.iter().find(|e| e.field() == name).unwrap();
+
+    *export.internal()
+}
+
+#[test]
+fn link_resolves_imports_across_modules() {
+    let mut linker = Linker::new();
+    linker.add_wat("main", main_wat()).unwrap();
+    linker.add_wat("math", math_wat()).unwrap();
+
+    let wasm = linker.link().unwrap();
+    assert!(Program::new(&wasm, false).is_ok());
+
+    let module: Module = parity_wasm::deserialize_buffer(&wasm).unwrap();
+
+    // The host imports are merged (the `math` import has been resolved)
+    let imports = module.import_section().unwrap().entries();
+    let imports: Vec<_> = imports.iter().map(|i| (i.module(), i.field())).collect();
+    assert_eq!(imports, vec![("svm", "svm_log"), ("svm", "memory")]);
+
+    // Only the exports of the first module are exported by default
+    assert_eq!(module.export_section().unwrap().entries().len(), 1);
+    assert_eq!(export_index(&module, "run"), Internal::Function(1));
+}
+
+#[test]
+fn link_aliases_and_explicit_exports() {
+    let main = main_wat().replace(r#""math" "add""#, r#""env" "sum""#);
+
+    let mut linker = Linker::new();
+    linker.add_wat("main", &main).unwrap();
+    linker.add_wat("math", math_wat()).unwrap();
+    linker.alias("env", "sum", "math", "add");
+    linker.export("main", "run", "run");
+    linker.export("math", "add", "math_add");
+
+    let wasm = linker.link().unwrap();
+    let module: Module = parity_wasm::deserialize_buffer(&wasm).unwrap();
+
+    assert_eq!(module.import_section().unwrap().entries().len(), 2);
+    assert_eq!(export_index(&module, "run"), Internal::Function(1));
+    assert_eq!(export_index(&module, "math_add"), Internal::Function(2));
+}
+
+#[test]
+fn link_unresolved_import() {
+    let main = main_wat().replace(r#""math" "add""#, r#""math" "sub""#);
+
+    let mut linker = Linker::new();
+    linker.add_wat("main", &main).unwrap();
+    linker.add_wat("math", math_wat()).unwrap();
+
+    assert_eq!(
+        linker.link().unwrap_err(),
+        LinkError::UnresolvedImport {
+            module: "main".to_string(),
+            import: "math.sub".to_string()
+        }
+    );
+}
+
+#[test]
+fn link_import_signature_mismatch() {
+    let main = main_wat().replace("(param i32 i32) (result i32))", "(param i32) (result i32))");
+
+    let mut linker = Linker::new();
+    linker.add_wat("main", &main).unwrap();
+    linker.add_wat("math", math_wat()).unwrap();
+
+    assert_eq!(
+        linker.link().unwrap_err(),
+        LinkError::ImportMismatch {
+            module: "main".to_string(),
+            import: "math.add".to_string()
+        }
+    );
+}
+
+#[test]
+fn link_multiple_memories_not_supported() {
+    let math = math_wat().replace(r#"(import "svm" "memory" (memory 1))"#, "(memory 1)");
+
+    let mut linker = Linker::new();
+    linker.add_wat("main", main_wat()).unwrap();
+    linker.add_wat("math", &math).unwrap();
+
+    assert!(matches!(
+        linker.link().unwrap_err(),
+        LinkError::NotSupported(..)
+    ));
+}
@@ -1,6 +1,8 @@
-use svm_types::Address;
+use svm_types::{Address, TemplateAddr};
 
 use crate::{impl_from_svm_byte_array, impl_into_svm_byte_array};
 
 impl_from_svm_byte_array!(Address);
 impl_into_svm_byte_array!(Address);
+
+impl_from_svm_byte_array!(TemplateAddr);
@@ -1,14 +1,15 @@
 use log::{debug, error};
 
+use std::convert::TryFrom;
 use std::ffi::c_void;
 use std::panic::UnwindSafe;
 
 #[cfg(feature = "default-rocksdb")]
 use std::path::Path;
 
-use svm_codec::receipt;
-use svm_runtime::Runtime;
-use svm_types::{Context, Envelope, Type};
+use svm_codec::{gas_estimate, receipt, template, WriteExt};
+use svm_runtime::{Message, Runtime};
+use svm_types::{Address, Context, Envelope, Layer, TemplateAddr, Type};
 
 use crate::r#ref::RuntimeRef;
 
@@ -25,6 +26,9 @@ static DEPLOY_RECEIPT_TYPE: Type = Type::Str("Deploy Receipt");
 static SPAWN_RECEIPT_TYPE: Type = Type::Str("Spawn Receipt");
 static VERIFY_RECEIPT_TYPE: Type = Type::Str("Verify Receipt");
 static CALL_RECEIPT_TYPE: Type = Type::Str("Call Receipt");
+static TEMPLATE_SECTION_TYPE: Type = Type::Str("Template Section");
+static ACCOUNTS_PAGE_TYPE: Type = Type::Str("Accounts Page");
+static GAS_ESTIMATE_TYPE: Type = Type::Str("Gas Estimate");
 
 static SVM_RESOURCE_TYPE: Type = Type::of::<svm_resource_t>();
 static SVM_RESOURCES_ITER_TYPE: Type = Type::of::<svm_resource_iter_t>();
@@ -223,9 +227,11 @@ pub unsafe extern "C" fn svm_validate_deploy(
                 svm_result_t::SVM_SUCCESS
             }
             Err(e) => {
-                error!("`svm_validate_deploy` returns `SVM_FAILURE`");
+                let res = svm_result_t::from(&e);
+
+                error!("`svm_validate_deploy` returns `{:?}`", res);
                 raw_validate_error(&e, &mut *error);
-                svm_result_t::SVM_FAILURE
+                res
             }
         }
     })
@@ -269,9 +275,11 @@ pub unsafe extern "C" fn svm_validate_spawn(
                 svm_result_t::SVM_SUCCESS
             }
             Err(e) => {
-                error!("`svm_validate_spawn` returns `SVM_FAILURE`");
+                let res = svm_result_t::from(&e);
+
+                error!("`svm_validate_spawn` returns `{:?}`", res);
                 raw_validate_error(&e, &mut *error);
-                svm_result_t::SVM_FAILURE
+                res
             }
         }
     })
@@ -313,9 +321,11 @@ pub unsafe extern "C" fn svm_validate_call(
                 svm_result_t::SVM_SUCCESS
             }
             Err(e) => {
-                error!("`svm_validate_call` returns `SVM_FAILURE`");
+                let res = svm_result_t::from(&e);
+
+                error!("`svm_validate_call` returns `{:?}`", res);
                 raw_validate_error(&e, &mut *error);
-                svm_result_t::SVM_FAILURE
+                res
             }
         }
     })
@@ -370,13 +380,13 @@ pub unsafe extern "C" fn svm_deploy(
         let envelope = decode_envelope(envelope);
         if let Err(e) = envelope {
             raw_io_error(e, &mut *error);
-            return svm_result_t::SVM_FAILURE;
+            return svm_result_t::SVM_INVALID_ARGUMENT;
         }
 
         let context = decode_context(context);
         if let Err(e) = context {
             raw_io_error(e, &mut *error);
-            return svm_result_t::SVM_FAILURE;
+            return svm_result_t::SVM_INVALID_ARGUMENT;
         }
 
         let envelope = envelope.unwrap();
@@ -446,13 +456,13 @@ pub unsafe extern "C" fn svm_spawn(
         let envelope = decode_envelope(envelope);
         if let Err(e) = envelope {
             raw_io_error(e, &mut *error);
-            return svm_result_t::SVM_FAILURE;
+            return svm_result_t::SVM_INVALID_ARGUMENT;
         }
 
         let context = decode_context(context);
         if let Err(e) = context {
             raw_io_error(e, &mut *error);
-            return svm_result_t::SVM_FAILURE;
+            return svm_result_t::SVM_INVALID_ARGUMENT;
         }
 
         let envelope = envelope.unwrap();
@@ -527,13 +537,13 @@ pub unsafe extern "C" fn svm_verify(
         let envelope = decode_envelope(envelope);
         if let Err(e) = envelope {
             raw_io_error(e, &mut *error);
-            return svm_result_t::SVM_FAILURE;
+            return svm_result_t::SVM_INVALID_ARGUMENT;
         }
 
         let context = decode_context(context);
         if let Err(e) = context {
             raw_io_error(e, &mut *error);
-            return svm_result_t::SVM_FAILURE;
+            return svm_result_t::SVM_INVALID_ARGUMENT;
         }
 
         let envelope = envelope.unwrap();
@@ -604,13 +614,13 @@ pub unsafe extern "C" fn svm_call(
         let envelope = decode_envelope(envelope);
         if let Err(e) = envelope {
             raw_io_error(e, &mut *error);
-            return svm_result_t::SVM_FAILURE;
+            return svm_result_t::SVM_INVALID_ARGUMENT;
         }
 
         let context = decode_context(context);
         if let Err(e) = context {
             raw_io_error(e, &mut *error);
-            return svm_result_t::SVM_FAILURE;
+            return svm_result_t::SVM_INVALID_ARGUMENT;
         }
 
         let envelope = envelope.unwrap();
@@ -630,6 +640,445 @@ pub unsafe extern "C" fn svm_call(
     })
 }
 
+/// Estimates the gas of executing a binary message, without executing anything
+/// (see `Runtime::estimate_gas`).
+///
+/// The `kind` parameter tells the kind of the `message`:
+///
+/// * `0` - `Deploy Template`
+/// * `1` - `Upgrade Template`
+/// * `2` - `Spawn Account`
+/// * `3` - `Call Account`
+/// * `4` - `Call Batch`
+///
+/// Returns the estimate via the `estimate` parameter. It's encoded as a binary gas estimate
+/// (see `svm_codec::gas_estimate`), made of the payload price, the price of loading the `Template`s,
+/// the (optional) price of running the executed functions and the recommended `gas_limit`.
+///
+/// # Examples
+///
+/// ```rust, no_run
+/// use svm_runtime_ffi::*;
+///
+/// let mut runtime = std::ptr::null_mut();
+/// let mut error = svm_byte_array::default();
+///
+/// let res = unsafe { svm_memory_runtime_create(&mut runtime, &mut error) };
+/// assert!(res.is_ok());
+///
+/// let mut estimate = svm_byte_array::default();
+/// let envelope = svm_byte_array::default();
+/// let message = svm_byte_array::default();
+/// let kind = 3;
+///
+/// let _res = unsafe {
+///   svm_estimate_gas(
+///     &mut estimate,
+///     runtime,
+///     envelope,
+///     kind,
+///     message,
+///     &mut error)
+/// };
+/// ```
+///
+#[must_use]
+#[no_mangle]
+pub unsafe extern "C" fn svm_estimate_gas(
+    estimate: *mut svm_byte_array,
+    runtime: *mut c_void,
+    envelope: svm_byte_array,
+    kind: u8,
+    message: svm_byte_array,
+    error: *mut svm_byte_array,
+) -> svm_result_t {
+    catch_unwind_with_err(&mut *error, svm_result_t::SVM_FAILURE, || {
+        debug!("`svm_estimate_gas` start");
+
+        let runtime = RuntimeRef::as_native(runtime);
+        let bytes = message.as_slice().to_vec();
+
+        let message = match kind {
+            0 => Message::Deploy(bytes),
+            1 => Message::Upgrade(bytes),
+            2 => Message::Spawn(bytes),
+            3 => Message::Call(bytes),
+            4 => Message::Batch(bytes),
+            _ => {
+                raw_error(format!("Invalid message kind: {}", kind), &mut *error);
+                return svm_result_t::SVM_INVALID_ARGUMENT;
+            }
+        };
+
+        let envelope = match decode_envelope(envelope) {
+            Ok(envelope) => envelope,
+            Err(e) => {
+                raw_io_error(e, &mut *error);
+                return svm_result_t::SVM_INVALID_ARGUMENT;
+            }
+        };
+
+        match runtime.estimate_gas(&envelope, &message) {
+            Ok(rust_estimate) => {
+                let mut bytes = Vec::with_capacity(gas_estimate::byte_size());
+                gas_estimate::encode(&rust_estimate, &mut bytes);
+
+                // # Notes:
+                //
+                // Should call later `svm_byte_array_destroy`
+                data_to_svm_byte_array(GAS_ESTIMATE_TYPE, &mut *estimate, bytes);
+
+                debug!("`svm_estimate_gas` returns `SVM_SUCCESS`");
+                svm_result_t::SVM_SUCCESS
+            }
+            Err(e) => {
+                let res = svm_result_t::from(&e);
+
+                error!("`svm_estimate_gas` returns `{:?}`", res);
+                raw_validate_error(&e, &mut *error);
+                res
+            }
+        }
+    })
+}
+
+/// Fetches a single `Section` of a deployed `Template`.
+///
+/// The `kind` parameter is the raw `Section` kind (for example, `1` for the `Code Section`
+/// and `6` for the `Header Section`). Only the requested `Section` is decoded, so fetching
+/// the metadata of a `Template` doesn't pay for loading its other `Section`s.
+///
+/// Returns the encoded `Section` via the `section` parameter. It's encoded as a binary
+/// `Template` holding only that `Section`.
+///
+/// # Examples
+///
+/// ```rust, no_run
+/// use svm_runtime_ffi::*;
+///
+/// let mut runtime = std::ptr::null_mut();
+/// let mut error = svm_byte_array::default();
+///
+/// let res = unsafe { svm_memory_runtime_create(&mut runtime, &mut error) };
+/// assert!(res.is_ok());
+///
+/// let mut section = svm_byte_array::default();
+/// let template_addr = svm_byte_array::default();
+/// let header_kind = 6;
+///
+/// let _res = unsafe {
+///   svm_template_section(
+///     &mut section,
+///     runtime,
+///     template_addr,
+///     header_kind,
+///     &mut error)
+/// };
+/// ```
+///
+#[must_use]
+#[no_mangle]
+pub unsafe extern "C" fn svm_template_section(
+    section: *mut svm_byte_array,
+    runtime: *mut c_void,
+    template_addr: svm_byte_array,
+    kind: u16,
+    error: *mut svm_byte_array,
+) -> svm_result_t {
+    catch_unwind_with_err(&mut *error, svm_result_t::SVM_FAILURE, || {
+        debug!("`svm_template_section` start");
+
+        let runtime = RuntimeRef::as_native(runtime);
+
+        let template_addr = match TemplateAddr::try_from(template_addr) {
+            Ok(addr) => addr,
+            Err(s) => {
+                raw_error(s, &mut *error);
+                return svm_result_t::SVM_INVALID_ARGUMENT;
+            }
+        };
+
+        let kind = match template::decode_section_kind(kind) {
+            Ok(kind) => kind,
+            Err(e) => {
+                raw_error(e.to_string(), &mut *error);
+                return svm_result_t::from(&e);
+            }
+        };
+
+        match runtime.template_section(&template_addr, kind) {
+            Some(rust_section) => {
+                let section_bytes = template::encode_section(&rust_section);
+
+                // Returns the encoded `Section` as `svm_byte_array`.
+                //
+                // # Notes:
+                //
+                // Should call later `svm_byte_array_destroy`
+                data_to_svm_byte_array(TEMPLATE_SECTION_TYPE, &mut *section, section_bytes);
+
+                debug!("`svm_template_section` returns `SVM_SUCCESS`");
+                svm_result_t::SVM_SUCCESS
+            }
+            None => {
+                error!("`svm_template_section` returns `SVM_NOT_FOUND`");
+
+                let msg = format!(
+                    "`Template` `{}` has no `{:?}` section",
+                    template_addr.as_str(),
+                    kind
+                );
+                raw_error(msg, &mut *error);
+                svm_result_t::SVM_NOT_FOUND
+            }
+        }
+    })
+}
+
+/// Fetches a page of the spawned `Account`s, ordered by their `Address`.
+///
+/// The page holds up to `limit` `Account`s following the `Account` whose `Address` is `after`.
+/// An empty `after` starts from the first `Account`, so paging through all the `Account`s means
+/// passing the `Address` of the last `Account` of each page when asking for the next one
+/// (an empty page means there are no more `Account`s).
+///
+/// Returns the page via the `page` parameter. It's encoded as:
+///
+/// ```text
+/// +-------------+------------+------------+---------------+-------------+-----------+------------+-----+
+/// |  #Accounts  |  Address   |  Template  |    Spawner    |    Name     |  Balance  | State Root |     |
+/// |  (4 bytes)  | (20 bytes) | (20 bytes) |  (20 bytes)   |  (String)   | (8 bytes) | (32 bytes) | ... |
+/// +-------------+------------+------------+---------------+-------------+-----------+------------+-----+
+/// ```
+///
+/// The balance and the `State Root` are the ones as of the most recently committed `State`.
+///
+/// # Examples
+///
+/// ```rust
+/// use svm_runtime_ffi::*;
+///
+/// let mut runtime = std::ptr::null_mut();
+/// let mut error = svm_byte_array::default();
+///
+/// let res = unsafe { svm_memory_runtime_create(&mut runtime, &mut error) };
+/// assert!(res.is_ok());
+///
+/// let mut page = svm_byte_array::default();
+/// let after = svm_byte_array::default();
+///
+/// let res = unsafe { svm_accounts_page(&mut page, runtime, after, 10, &mut error) };
+/// assert!(res.is_ok());
+///
+/// // No `Account`s have been spawned
+/// assert_eq!(page.as_slice(), &[0, 0, 0, 0]);
+/// ```
+///
+#[must_use]
+#[no_mangle]
+pub unsafe extern "C" fn svm_accounts_page(
+    page: *mut svm_byte_array,
+    runtime: *mut c_void,
+    after: svm_byte_array,
+    limit: u32,
+    error: *mut svm_byte_array,
+) -> svm_result_t {
+    catch_unwind_with_err(&mut *error, svm_result_t::SVM_FAILURE, || {
+        debug!("`svm_accounts_page` start");
+
+        let runtime = RuntimeRef::as_native(runtime);
+
+        let after = if after.len() == 0 {
+            None
+        } else {
+            match Address::try_from(after) {
+                Ok(addr) => Some(addr),
+                Err(s) => {
+                    raw_error(s, &mut *error);
+                    return svm_result_t::SVM_INVALID_ARGUMENT;
+                }
+            }
+        };
+
+        let accounts = runtime.accounts_page(after.as_ref(), limit as usize);
+
+        let mut bytes = Vec::new();
+        bytes.write_u32_be(accounts.len() as u32);
+
+        for account in accounts.iter() {
+            bytes.write_address(&account.addr);
+            bytes.write_template_addr(&account.template_addr);
+            bytes.write_address(&account.spawner);
+            bytes.write_string(&account.name);
+            bytes.write_u64_be(account.balance);
+            bytes.write_state(&account.state_root);
+        }
+
+        // # Notes:
+        //
+        // Should call later `svm_byte_array_destroy`
+        data_to_svm_byte_array(ACCOUNTS_PAGE_TYPE, &mut *page, bytes);
+
+        debug!("`svm_accounts_page` returns `SVM_SUCCESS`");
+        svm_result_t::SVM_SUCCESS
+    })
+}
+
+/// Blocks until the writes of the transactions of `layer` (and of the preceding layers) have been persisted.
+///
+/// A `Runtime` configured to persist its writes in the background (see `Config::write_mode`)
+/// expects it to be called before finalizing `layer`. Other `Runtime`s return right away.
+/// On failure, returns `SVM_FAILURE` and the reason via the `error` parameter.
+///
+/// # Examples
+///
+/// ```rust
+/// use svm_runtime_ffi::*;
+///
+/// let mut runtime = std::ptr::null_mut();
+/// let mut error = svm_byte_array::default();
+///
+/// let res = unsafe { svm_memory_runtime_create(&mut runtime, &mut error) };
+/// assert!(res.is_ok());
+///
+/// let res = unsafe { svm_flush_until(runtime, 1, &mut error) };
+/// assert!(res.is_ok());
+/// ```
+///
+#[must_use]
+#[no_mangle]
+pub unsafe extern "C" fn svm_flush_until(
+    runtime: *mut c_void,
+    layer: u64,
+    error: *mut svm_byte_array,
+) -> svm_result_t {
+    catch_unwind_with_err(&mut *error, svm_result_t::SVM_FAILURE, || {
+        debug!("`svm_flush_until` start");
+
+        let runtime = RuntimeRef::as_native(runtime);
+
+        match runtime.flush_until(Layer(layer)) {
+            Ok(()) => {
+                debug!("`svm_flush_until` returns `SVM_SUCCESS`");
+                svm_result_t::SVM_SUCCESS
+            }
+            Err(err) => {
+                error!("`svm_flush_until` returns `SVM_FAILURE`");
+                raw_error(err.to_string(), &mut *error);
+                svm_result_t::SVM_FAILURE
+            }
+        }
+    })
+}
+
+/// Returns the result of an executed transaction given its binary Receipt.
+///
+/// That is `SVM_SUCCESS` for a succeeded transaction, and otherwise the error code
+/// of the Receipt's error (one of the `400-499` codes). The error message is
+/// returned via the `error` parameter.
+///
+/// A `receipt` which isn't a valid binary Receipt results in `SVM_INVALID_ARGUMENT`.
+///
+/// # Examples
+///
+/// ```rust
+/// use svm_codec::receipt;
+/// use svm_runtime_ffi::*;
+/// use svm_types::{CallReceipt, Type};
+///
+/// let bytes = receipt::encode_call(&CallReceipt::new_oog(Vec::new()));
+/// let receipt: svm_byte_array = (Type::Str("Call Receipt"), bytes).into();
+///
+/// let mut error = svm_byte_array::default();
+/// let res = unsafe { svm_receipt_result(receipt, &mut error) };
+/// assert_eq!(res, svm_result_t::SVM_RUNTIME_OOG);
+/// ```
+///
+#[must_use]
+#[no_mangle]
+pub unsafe extern "C" fn svm_receipt_result(
+    receipt: svm_byte_array,
+    error: *mut svm_byte_array,
+) -> svm_result_t {
+    let res = std::panic::catch_unwind(|| {
+        let bytes = receipt.as_slice();
+
+        if bytes.is_empty() {
+            None
+        } else {
+            Some(receipt::decode_receipt(bytes))
+        }
+    });
+
+    match res {
+        Ok(Some(receipt)) if receipt.success() => svm_result_t::SVM_SUCCESS,
+        Ok(Some(receipt)) => {
+            let err = receipt.error();
+
+            raw_error(format!("{:?}", err), &mut *error);
+            svm_result_t::from(err)
+        }
+        _ => {
+            raw_error("Invalid binary Receipt".to_string(), &mut *error);
+            svm_result_t::SVM_INVALID_ARGUMENT
+        }
+    }
+}
+
+/// Returns the version of the gas schedule an executed transaction has been priced by,
+/// given its binary Receipt. The version is returned via the `version` parameter.
+///
+/// A `receipt` which isn't a valid binary Receipt results in `SVM_INVALID_ARGUMENT`.
+///
+/// # Examples
+///
+/// ```rust
+/// use svm_codec::receipt;
+/// use svm_runtime_ffi::*;
+/// use svm_types::{CallReceipt, Type};
+///
+/// let mut oog = CallReceipt::new_oog(Vec::new());
+/// oog.set_gas_schedule_version(3);
+///
+/// let bytes = receipt::encode_call(&oog);
+/// let receipt: svm_byte_array = (Type::Str("Call Receipt"), bytes).into();
+///
+/// let mut version = 0;
+/// let mut error = svm_byte_array::default();
+/// let res = unsafe { svm_receipt_gas_schedule_version(receipt, &mut version, &mut error) };
+/// assert!(res.is_ok());
+/// assert_eq!(version, 3);
+/// ```
+///
+#[must_use]
+#[no_mangle]
+pub unsafe extern "C" fn svm_receipt_gas_schedule_version(
+    receipt: svm_byte_array,
+    version: *mut u16,
+    error: *mut svm_byte_array,
+) -> svm_result_t {
+    let res = std::panic::catch_unwind(|| {
+        let bytes = receipt.as_slice();
+
+        if bytes.is_empty() {
+            None
+        } else {
+            Some(receipt::decode_receipt(bytes))
+        }
+    });
+
+    match res {
+        Ok(Some(receipt)) => {
+            *version = receipt.gas_schedule_version();
+            svm_result_t::SVM_SUCCESS
+        }
+        _ => {
+            raw_error("Invalid binary Receipt".to_string(), &mut *error);
+            svm_result_t::SVM_INVALID_ARGUMENT
+        }
+    }
+}
+
 /// Returns the total live manually-managed resources.
 #[must_use]
 #[no_mangle]
@@ -52,6 +52,22 @@ pub use api::{
     svm_verify,
     svm_call,
 
+    // Gas Estimation
+    svm_estimate_gas,
+
+    // Templates
+    svm_template_section,
+
+    // Accounts
+    svm_accounts_page,
+
+    // Persistence
+    svm_flush_until,
+
+    // Receipts
+    svm_receipt_gas_schedule_version,
+    svm_receipt_result,
+
     // Destroy
     svm_runtime_destroy,
     svm_byte_array_destroy,
@@ -1,6 +1,21 @@
+use svm_codec::ParseError;
+use svm_runtime::ValidateError;
+use svm_types::RuntimeError;
+
 /// FFI representation for function result type
+///
+/// On failure, the result holds an error code telling the kind of the failure
+/// (the human-readable message is returned separately via the `error` parameter).
+///
+/// The codes are grouped by their origin:
+///
+/// * `1` - An internal failure (a bug).
+/// * `100-199` - The input couldn't be parsed (see [`ParseError`]).
+/// * `200-299` - The input has been parsed but it isn't valid (see [`ValidateError`]).
+/// * `300-399` - The FFI arguments are invalid.
+/// * `400-499` - The transaction execution has failed (see [`RuntimeError`]).
 #[allow(non_camel_case_types)]
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 #[repr(C)]
 pub enum svm_result_t {
     #[doc(hidden)]
@@ -8,6 +23,96 @@ pub enum svm_result_t {
 
     #[doc(hidden)]
     SVM_FAILURE = 1,
+
+    #[doc(hidden)]
+    SVM_PARSE_REACHED_EOF = 100,
+
+    #[doc(hidden)]
+    SVM_PARSE_EXPECTED_EOF = 101,
+
+    #[doc(hidden)]
+    SVM_PARSE_EMPTY_FIELD = 102,
+
+    #[doc(hidden)]
+    SVM_PARSE_NOT_ENOUGH_BYTES = 103,
+
+    #[doc(hidden)]
+    SVM_PARSE_TOO_MANY_BYTES = 104,
+
+    #[doc(hidden)]
+    SVM_PARSE_NOT_SUPPORTED = 105,
+
+    #[doc(hidden)]
+    SVM_PARSE_INVALID_UTF8_STRING = 106,
+
+    #[doc(hidden)]
+    SVM_PARSE_UNEXPECTED_LAYOUT = 107,
+
+    #[doc(hidden)]
+    SVM_PARSE_INVALID_SECTION = 108,
+
+    #[doc(hidden)]
+    SVM_PARSE_NON_CANONICAL = 109,
+
+    #[doc(hidden)]
+    SVM_INVALID_PROGRAM = 200,
+
+    #[doc(hidden)]
+    SVM_INVALID_FIXED_GAS = 201,
+
+    #[doc(hidden)]
+    SVM_INVALID_DEFAULT = 202,
+
+    #[doc(hidden)]
+    SVM_INVALID_STORAGE_BUDGET = 203,
+
+    #[doc(hidden)]
+    SVM_INVALID_PRECOMPILE = 204,
+
+    #[doc(hidden)]
+    SVM_INVALID_ARGUMENT = 300,
+
+    #[doc(hidden)]
+    SVM_NOT_FOUND = 301,
+
+    #[doc(hidden)]
+    SVM_RUNTIME_OOG = 400,
+
+    #[doc(hidden)]
+    SVM_RUNTIME_TEMPLATE_NOT_FOUND = 401,
+
+    #[doc(hidden)]
+    SVM_RUNTIME_ACCOUNT_NOT_FOUND = 402,
+
+    #[doc(hidden)]
+    SVM_RUNTIME_COMPILATION_FAILED = 403,
+
+    #[doc(hidden)]
+    SVM_RUNTIME_INSTANTIATION_FAILED = 404,
+
+    #[doc(hidden)]
+    SVM_RUNTIME_FUNC_NOT_FOUND = 405,
+
+    #[doc(hidden)]
+    SVM_RUNTIME_FUNC_FAILED = 406,
+
+    #[doc(hidden)]
+    SVM_RUNTIME_FUNC_NOT_ALLOWED = 407,
+
+    #[doc(hidden)]
+    SVM_RUNTIME_FUNC_INVALID_SIGNATURE = 408,
+
+    #[doc(hidden)]
+    SVM_RUNTIME_UPGRADE_NOT_ALLOWED = 409,
+
+    #[doc(hidden)]
+    SVM_RUNTIME_INTERNAL_ERROR = 410,
+
+    #[doc(hidden)]
+    SVM_RUNTIME_INSUFFICIENT_BALANCE = 411,
+
+    #[doc(hidden)]
+    SVM_RUNTIME_STORAGE_CORRUPTED = 412,
 }
 
 ///
@@ -26,9 +131,79 @@ pub enum svm_result_t {
 impl From<svm_result_t> for bool {
     #[inline]
     fn from(value: svm_result_t) -> bool {
-        match value {
-            svm_result_t::SVM_SUCCESS => true,
-            svm_result_t::SVM_FAILURE => false,
+        matches!(value, svm_result_t::SVM_SUCCESS)
+    }
+}
+
+/// Maps each [`ParseError`] variant onto its error code.
+///
+/// # Examples
+///
+/// ```rust
+/// use svm_codec::{Field, ParseError};
+/// use svm_runtime_ffi::svm_result_t;
+///
+/// let err = ParseError::NotEnoughBytes(Field::Name);
+///
+/// assert_eq!(svm_result_t::from(&err), svm_result_t::SVM_PARSE_NOT_ENOUGH_BYTES);
+/// ```
+///
+impl From<&ParseError> for svm_result_t {
+    fn from(err: &ParseError) -> Self {
+        match err {
+            ParseError::ReachedEOF => svm_result_t::SVM_PARSE_REACHED_EOF,
+            ParseError::ExpectedEOF => svm_result_t::SVM_PARSE_EXPECTED_EOF,
+            ParseError::EmptyField(..) => svm_result_t::SVM_PARSE_EMPTY_FIELD,
+            ParseError::NotEnoughBytes(..) => svm_result_t::SVM_PARSE_NOT_ENOUGH_BYTES,
+            ParseError::TooManyBytes(..) => svm_result_t::SVM_PARSE_TOO_MANY_BYTES,
+            ParseError::NotSupported(..) => svm_result_t::SVM_PARSE_NOT_SUPPORTED,
+            ParseError::InvalidUTF8String(..) => svm_result_t::SVM_PARSE_INVALID_UTF8_STRING,
+            ParseError::UnexpectedLayout(..) => svm_result_t::SVM_PARSE_UNEXPECTED_LAYOUT,
+            ParseError::NonCanonical(..) => svm_result_t::SVM_PARSE_NON_CANONICAL,
+            ParseError::InvalidSection => svm_result_t::SVM_PARSE_INVALID_SECTION,
+        }
+    }
+}
+
+/// Maps each [`ValidateError`] variant onto its error code.
+impl From<&ValidateError> for svm_result_t {
+    fn from(err: &ValidateError) -> Self {
+        match err {
+            ValidateError::Parse(err) => err.into(),
+            ValidateError::Program(..) => svm_result_t::SVM_INVALID_PROGRAM,
+            ValidateError::FixedGas(..) => svm_result_t::SVM_INVALID_FIXED_GAS,
+            ValidateError::InvalidDefault(..) => svm_result_t::SVM_INVALID_DEFAULT,
+            ValidateError::StorageBudget { .. } => svm_result_t::SVM_INVALID_STORAGE_BUDGET,
+            ValidateError::InvalidPrecompileId | ValidateError::UnknownPrecompile(..) => {
+                svm_result_t::SVM_INVALID_PRECOMPILE
+            }
+        }
+    }
+}
+
+/// Maps each [`RuntimeError`] variant onto its error code.
+impl From<&RuntimeError> for svm_result_t {
+    fn from(err: &RuntimeError) -> Self {
+        match err {
+            RuntimeError::OOG => svm_result_t::SVM_RUNTIME_OOG,
+            RuntimeError::TemplateNotFound(..) => svm_result_t::SVM_RUNTIME_TEMPLATE_NOT_FOUND,
+            RuntimeError::AccountNotFound(..) => svm_result_t::SVM_RUNTIME_ACCOUNT_NOT_FOUND,
+            RuntimeError::CompilationFailed { .. } => svm_result_t::SVM_RUNTIME_COMPILATION_FAILED,
+            RuntimeError::InstantiationFailed { .. } => {
+                svm_result_t::SVM_RUNTIME_INSTANTIATION_FAILED
+            }
+            RuntimeError::FuncNotFound { .. } => svm_result_t::SVM_RUNTIME_FUNC_NOT_FOUND,
+            RuntimeError::FuncFailed { .. } => svm_result_t::SVM_RUNTIME_FUNC_FAILED,
+            RuntimeError::FuncNotAllowed { .. } => svm_result_t::SVM_RUNTIME_FUNC_NOT_ALLOWED,
+            RuntimeError::FuncInvalidSignature { .. } => {
+                svm_result_t::SVM_RUNTIME_FUNC_INVALID_SIGNATURE
+            }
+            RuntimeError::UpgradeNotAllowed { .. } => svm_result_t::SVM_RUNTIME_UPGRADE_NOT_ALLOWED,
+            RuntimeError::InternalError { .. } => svm_result_t::SVM_RUNTIME_INTERNAL_ERROR,
+            RuntimeError::InsufficientBalance { .. } => {
+                svm_result_t::SVM_RUNTIME_INSUFFICIENT_BALANCE
+            }
+            RuntimeError::StorageCorrupted { .. } => svm_result_t::SVM_RUNTIME_STORAGE_CORRUPTED,
         }
     }
 }
@@ -53,7 +228,7 @@ impl svm_result_t {
         self.as_bool() == true
     }
 
-    /// Returns whether equals to `svm_result::SVM_FAILURE`
+    /// Returns whether differs from `svm_result::SVM_SUCCESS` (i.e holds an error code)
     ///
     /// # Examples
     ///
@@ -65,12 +240,28 @@ impl svm_result_t {
     ///
     /// assert!(!truthy.is_err());
     /// assert!(falsey.is_err());
+    /// assert!(svm_result_t::SVM_RUNTIME_OOG.is_err());
     /// ```
     #[inline]
     pub fn is_err(self) -> bool {
         self.as_bool() == false
     }
 
+    /// Returns the numeric error code (`0` on success)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use svm_runtime_ffi::svm_result_t;
+    ///
+    /// assert_eq!(svm_result_t::SVM_SUCCESS.code(), 0);
+    /// assert_eq!(svm_result_t::SVM_PARSE_REACHED_EOF.code(), 100);
+    /// ```
+    #[inline]
+    pub fn code(self) -> u32 {
+        self as u32
+    }
+
     /// Convert to a boolean
     #[inline]
     fn as_bool(self) -> bool {
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use std::convert::TryFrom;
 
 use svm_runtime_ffi as api;
-use svm_runtime_ffi::{svm_byte_array, tracking};
+use svm_runtime_ffi::{svm_byte_array, svm_result_t, tracking};
 
 use svm_codec::receipt;
 use svm_runtime::testing;
@@ -347,6 +347,15 @@ fn svm_runtime_failure() {
         let receipt = receipt::decode_receipt(call_receipt.as_slice()).into_call();
         assert_eq!(receipt.success, false);
 
+        let res = api::svm_receipt_result(call_receipt.clone(), &mut error);
+        assert_eq!(res, svm_result_t::SVM_RUNTIME_FUNC_FAILED);
+
+        let mut version = u16::MAX;
+        let res =
+            api::svm_receipt_gas_schedule_version(call_receipt.clone(), &mut version, &mut error);
+        assert!(res.is_ok());
+        assert_eq!(version, 0);
+
         // Asserts there are resources to be destroyed.
         assert_ne!(tracking::total_live(), 0);
 
@@ -355,6 +364,198 @@ fn svm_runtime_failure() {
         destroy(&[deploy_msg, spawn_msg, call_msg]);
         destroy(&[deploy_ctx, spawn_ctx, call_ctx]);
         destroy(&[deploy_receipt, spawn_receipt, call_receipt]);
+        destroy(&[error]);
+
+        // Destroy `Runtime`
+        let _ = api::svm_runtime_destroy(runtime);
+
+        // Asserts there are NO leaked resources
+        assert_eq!(tracking::total_live(), 0);
+
+        tracking::set_tracking_off();
+    }
+}
+
+#[test]
+fn svm_template_section_fetch() {
+    use std::io::Cursor;
+
+    use svm_codec::template;
+    use svm_types::SectionKind;
+
+    unsafe {
+        tracking::set_tracking_on();
+
+        assert_eq!(tracking::total_live(), 0);
+
+        // 1) `Init Runtime`
+        let mut runtime = std::ptr::null_mut();
+        let mut error = svm_byte_array::default();
+
+        let res = api::svm_memory_runtime_create(&mut runtime, &mut error);
+        assert!(res.is_ok());
+
+        // 2) `Deploy Template`
+        let deploy_msg = deploy_message(
+            0,
+            "My Template",
+            &["initialize".to_string()],
+            include_bytes!("wasm/counter.wasm"),
+        );
+        let principal = Address::repeat(0xAB);
+        let deploy_env = encode_envelope(&Envelope::with_principal(principal));
+        let deploy_ctx = encode_context(&Context::default());
+
+        let mut deploy_receipt = svm_byte_array::default();
+        let res = api::svm_deploy(
+            &mut deploy_receipt,
+            runtime,
+            deploy_env.clone(),
+            deploy_msg.clone(),
+            deploy_ctx.clone(),
+            &mut error,
+        );
+        assert!(res.is_ok());
+
+        let receipt = receipt::decode_receipt(deploy_receipt.as_slice()).into_deploy();
+        let template_addr = receipt.template_addr().as_slice().to_vec();
+        let template_addr: svm_byte_array = (Type::Str("Template Address"), template_addr).into();
+
+        // 3) Fetching only the `Ctors Section` (raw kind = 3)
+        let mut section = svm_byte_array::default();
+        let res =
+            api::svm_template_section(&mut section, runtime, template_addr.clone(), 3, &mut error);
+        assert!(res.is_ok());
+
+        let cursor = Cursor::new(section.as_slice());
+        let template = template::decode(cursor, None).unwrap();
+        assert_eq!(template.sections().len(), 1);
+        assert_eq!(template.ctors(), &["initialize".to_string()]);
+
+        // 4) Fetching a missing `Section` (`Schema Section`, raw kind = 4) fails
+        let mut missing = svm_byte_array::default();
+        let res =
+            api::svm_template_section(&mut missing, runtime, template_addr.clone(), 4, &mut error);
+        assert_eq!(res, svm_result_t::SVM_NOT_FOUND);
+        assert!(template.try_get(SectionKind::Schema).is_none());
+
+        // Destroy `svm_byte_array`s
+        destroy(&[deploy_env, deploy_msg, deploy_ctx, deploy_receipt]);
+        destroy(&[template_addr, section, error]);
+
+        // Destroy `Runtime`
+        let _ = api::svm_runtime_destroy(runtime);
+
+        // Asserts there are NO leaked resources
+        assert_eq!(tracking::total_live(), 0);
+
+        tracking::set_tracking_off();
+    }
+}
+
+#[test]
+fn svm_accounts_page_fetch() {
+    use std::io::Cursor;
+
+    use svm_codec::ReadExt;
+    use svm_types::State;
+
+    unsafe {
+        tracking::set_tracking_on();
+
+        assert_eq!(tracking::total_live(), 0);
+
+        // 1) `Init Runtime`
+        let mut runtime = std::ptr::null_mut();
+        let mut error = svm_byte_array::default();
+
+        let res = api::svm_memory_runtime_create(&mut runtime, &mut error);
+        assert!(res.is_ok());
+
+        // 2) `Deploy Template`
+        let deploy_msg = deploy_message(
+            0,
+            "My Template",
+            &["initialize".to_string()],
+            include_bytes!("wasm/counter.wasm"),
+        );
+        let deploy_env = encode_envelope(&Envelope::with_principal(Address::repeat(0xAB)));
+        let deploy_ctx = encode_context(&Context::default());
+
+        let mut deploy_receipt = svm_byte_array::default();
+        let res = api::svm_deploy(
+            &mut deploy_receipt,
+            runtime,
+            deploy_env.clone(),
+            deploy_msg.clone(),
+            deploy_ctx.clone(),
+            &mut error,
+        );
+        assert!(res.is_ok());
+
+        let receipt = receipt::decode_receipt(deploy_receipt.as_slice()).into_deploy();
+        let template_addr = receipt.template_addr();
+
+        // 3) `Spawn Account`
+        let mut calldata = svm_sdk::Vec::with_capacity(1000);
+        10u32.encode(&mut calldata);
+
+        let spawn_msg = spawn_message(&template_addr, "My Account", "initialize", &calldata);
+        let spawner = Address::repeat(0xCD);
+        let spawn_env = encode_envelope(&Envelope::with_principal(spawner.clone()));
+        let spawn_ctx = encode_context(&Context::default());
+
+        let mut spawn_receipt = svm_byte_array::default();
+        let res = api::svm_spawn(
+            &mut spawn_receipt,
+            runtime,
+            spawn_env.clone(),
+            spawn_msg.clone(),
+            spawn_ctx.clone(),
+            &mut error,
+        );
+        assert!(res.is_ok());
+
+        let receipt = receipt::decode_receipt(spawn_receipt.as_slice()).into_spawn();
+        let account_addr = receipt.account_addr().clone();
+
+        // 4) Fetching the first page
+        let mut page = svm_byte_array::default();
+        let res = api::svm_accounts_page(
+            &mut page,
+            runtime,
+            svm_byte_array::default(),
+            10,
+            &mut error,
+        );
+        assert!(res.is_ok());
+
+        let mut cursor = Cursor::new(page.as_slice());
+        assert_eq!(cursor.read_u32_be().unwrap(), 1);
+        assert_eq!(cursor.read_address().unwrap(), account_addr);
+        assert_eq!(&cursor.read_template_addr().unwrap(), template_addr);
+        assert_eq!(cursor.read_address().unwrap(), spawner);
+        assert_eq!(cursor.read_string().unwrap().unwrap(), "My Account");
+        assert_eq!(cursor.read_u64_be().unwrap(), 0);
+        assert_ne!(cursor.read_state().unwrap(), State::zeros());
+        assert_eq!(cursor.position() as usize, page.as_slice().len());
+
+        // 5) Fetching the next (empty) page
+        let after: svm_byte_array = (
+            Type::Str("Account Address"),
+            account_addr.as_slice().to_vec(),
+        )
+            .into();
+
+        let mut next_page = svm_byte_array::default();
+        let res = api::svm_accounts_page(&mut next_page, runtime, after.clone(), 10, &mut error);
+        assert!(res.is_ok());
+        assert_eq!(next_page.as_slice(), &[0, 0, 0, 0]);
+
+        // Destroy `svm_byte_array`s
+        destroy(&[deploy_env, deploy_msg, deploy_ctx, deploy_receipt]);
+        destroy(&[spawn_env, spawn_msg, spawn_ctx, spawn_receipt]);
+        destroy(&[page, after, next_page]);
 
         // Destroy `Runtime`
         let _ = api::svm_runtime_destroy(runtime);
@@ -365,3 +566,138 @@ fn svm_runtime_failure() {
         tracking::set_tracking_off();
     }
 }
+
+#[test]
+fn svm_estimate_gas_spawn() {
+    use std::io::Cursor;
+
+    use svm_codec::gas_estimate;
+
+    unsafe {
+        tracking::set_tracking_on();
+
+        assert_eq!(tracking::total_live(), 0);
+
+        // 1) `Init Runtime`
+        let mut runtime = std::ptr::null_mut();
+        let mut error = svm_byte_array::default();
+
+        let res = api::svm_memory_runtime_create(&mut runtime, &mut error);
+        assert!(res.is_ok());
+
+        // 2) `Deploy Template`
+        let deploy_msg = deploy_message(
+            0,
+            "My Template",
+            &["initialize".to_string()],
+            include_bytes!("wasm/counter.wasm"),
+        );
+        let deploy_env = encode_envelope(&Envelope::with_principal(Address::repeat(0xAB)));
+        let deploy_ctx = encode_context(&Context::default());
+
+        let mut deploy_receipt = svm_byte_array::default();
+        let res = api::svm_deploy(
+            &mut deploy_receipt,
+            runtime,
+            deploy_env.clone(),
+            deploy_msg.clone(),
+            deploy_ctx.clone(),
+            &mut error,
+        );
+        assert!(res.is_ok());
+
+        let receipt = receipt::decode_receipt(deploy_receipt.as_slice()).into_deploy();
+        let template_addr = receipt.template_addr();
+
+        // 3) Estimating the gas of a `Spawn Account`
+        let mut calldata = svm_sdk::Vec::with_capacity(1000);
+        10u32.encode(&mut calldata);
+
+        let spawn_msg = spawn_message(&template_addr, "My Account", "initialize", &calldata);
+        let spawn_env = encode_envelope(&Envelope::with_principal(Address::repeat(0xCD)));
+
+        let mut estimate = svm_byte_array::default();
+        let res = api::svm_estimate_gas(
+            &mut estimate,
+            runtime,
+            spawn_env.clone(),
+            2,
+            spawn_msg.clone(),
+            &mut error,
+        );
+        assert!(res.is_ok());
+
+        let mut cursor = Cursor::new(estimate.as_slice());
+        let decoded = gas_estimate::decode(&mut cursor).unwrap();
+        assert_eq!(
+            decoded.payload_price,
+            svm_gas::transaction::spawn(spawn_msg.as_slice())
+        );
+        assert!(decoded.func_price.is_some());
+        assert_eq!(
+            decoded.gas_limit,
+            decoded.payload_price + decoded.template_load_price + decoded.func_price.unwrap()
+        );
+
+        // 4) An unknown message kind
+        let mut invalid = svm_byte_array::default();
+        let res = api::svm_estimate_gas(
+            &mut invalid,
+            runtime,
+            spawn_env.clone(),
+            5,
+            spawn_msg.clone(),
+            &mut error,
+        );
+        assert_eq!(res, svm_result_t::SVM_INVALID_ARGUMENT);
+
+        // Destroy `svm_byte_array`s
+        destroy(&[deploy_env, deploy_msg, deploy_ctx, deploy_receipt]);
+        destroy(&[spawn_env, spawn_msg, estimate, error]);
+
+        // Destroy `Runtime`
+        let _ = api::svm_runtime_destroy(runtime);
+
+        // Asserts there are NO leaked resources
+        assert_eq!(tracking::total_live(), 0);
+
+        tracking::set_tracking_off();
+    }
+}
+
+#[test]
+fn svm_validate_error_codes() {
+    unsafe {
+        tracking::set_tracking_on();
+
+        assert_eq!(tracking::total_live(), 0);
+
+        let mut runtime = std::ptr::null_mut();
+        let mut error = svm_byte_array::default();
+
+        let res = api::svm_memory_runtime_create(&mut runtime, &mut error);
+        assert!(res.is_ok());
+
+        // A truncated `Deploy Template` message
+        let message = api::svm_message_alloc(1);
+        let res = api::svm_validate_deploy(runtime, message.clone(), &mut error);
+        assert!(res.is_err());
+        assert!((100..200).contains(&res.code()));
+
+        destroy(&[message, error]);
+
+        // A `Deploy Template` message with an invalid smWasm
+        let mut error = svm_byte_array::default();
+        let message = deploy_message(0, "My Template", &[], b"not a Wasm");
+        let res = api::svm_validate_deploy(runtime, message.clone(), &mut error);
+        assert_eq!(res, svm_result_t::SVM_INVALID_PROGRAM);
+
+        destroy(&[message, error]);
+
+        let _ = api::svm_runtime_destroy(runtime);
+
+        assert_eq!(tracking::total_live(), 0);
+
+        tracking::set_tracking_off();
+    }
+}
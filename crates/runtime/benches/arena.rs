@@ -0,0 +1,173 @@
+//! Benchmarks the allocator pressure of the `vmcalls` under a sustained call load.
+//!
+//! The `storage_read_var*` benches compare reading a variable into a newly allocated vector
+//! (as the `vmcalls` used to) against reading it in-place.
+//!
+//! Run with: `cargo +nightly bench -p svm-runtime --bench arena`
+
+#![feature(test)]
+
+extern crate test;
+
+use test::Bencher;
+use wasmer::{imports, Function, Instance, Memory, MemoryType, Module, Pages};
+
+use svm_layout::{FixedLayout, Id};
+use svm_runtime::{testing, vmcalls, FuncEnv, ProtectedMode};
+use svm_storage::account::AccountStorage;
+use svm_types::{Address, Context, Envelope, TemplateAddr};
+
+/// The number of logs emitted by each call.
+const LOGS_PER_CALL: u32 = 64;
+
+/// The length (in bytes) of each log.
+const LOG_LEN: usize = 32;
+
+/// The number of storage loads made by each call.
+const LOADS_PER_CALL: u32 = 64;
+
+fn logging_wat() -> String {
+    format!(
+        r#"
+        (module
+          (func $log (import "svm" "svm_log") (param i32 i32))
+          (import "svm" "memory" (memory 1))
+
+          (func (export "run")
+            (local $i i32)
+            (loop $next
+              (call $log (i32.const 0) (i32.const {len}))
+              (local.set $i (i32.add (local.get $i) (i32.const 1)))
+              (br_if $next (i32.lt_u (local.get $i) (i32.const {count})))))
+        )"#,
+        len = LOG_LEN,
+        count = LOGS_PER_CALL
+    )
+}
+
+fn loading_wat() -> String {
+    format!(
+        r#"
+        (module
+          (func $get64 (import "svm" "svm_get64") (param i32) (result i64))
+          (func $set64 (import "svm" "svm_set64") (param i32 i64))
+          (import "svm" "memory" (memory 1))
+
+          (func (export "run")
+            (local $i i32)
+            (call $set64 (i32.const 0) (i64.const 10))
+            (loop $next
+              (drop (call $get64 (i32.const 0)))
+              (local.set $i (i32.add (local.get $i) (i32.const 1)))
+              (br_if $next (i32.lt_u (local.get $i) (i32.const {count})))))
+        )"#,
+        count = LOADS_PER_CALL
+    )
+}
+
+fn blank_storage(layout: FixedLayout) -> AccountStorage {
+    testing::blank_storage(&Address::repeat(0xCD), &layout)
+}
+
+/// A storage holding a single (uncommitted) 64-bit variable.
+fn written_storage() -> AccountStorage {
+    let mut storage = blank_storage(vec![8].into());
+    storage.write_var(Id(0), vec![10; 8]);
+
+    storage
+}
+
+fn func_env(memory: &Memory, layout: FixedLayout, mode: ProtectedMode) -> FuncEnv {
+    FuncEnv::new_with_memory(
+        memory.clone(),
+        blank_storage(layout),
+        &Envelope::default(),
+        &Context::default(),
+        TemplateAddr::repeat(0xAB),
+        Address::repeat(0xCD),
+        mode,
+    )
+}
+
+#[bench]
+fn call_sustained_logs(b: &mut Bencher) {
+    let store = svm_runtime::new_store();
+    let memory = Memory::new(&store, MemoryType::new(Pages(1), None, false)).unwrap();
+    let env = func_env(&memory, FixedLayout::default(), ProtectedMode::AccessDenied);
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory.clone(),
+            "svm_log" => Function::new_native_with_env(&store, env.clone(), vmcalls::log),
+        },
+    };
+    let module = Module::new(&store, wat::parse_str(logging_wat()).unwrap()).unwrap();
+    let instance = Instance::new(&module, &import_object).unwrap();
+    let run = instance.exports.get_function("run").unwrap();
+
+    let mut call = || {
+        run.call(&[]).unwrap();
+
+        let logs = env.borrow_mut().take_logs();
+        assert_eq!(logs.len(), LOGS_PER_CALL as usize);
+    };
+
+    call();
+
+    b.iter(call);
+}
+
+#[bench]
+fn call_sustained_loads(b: &mut Bencher) {
+    let store = svm_runtime::new_store();
+    let memory = Memory::new(&store, MemoryType::new(Pages(1), None, false)).unwrap();
+    let env = func_env(&memory, vec![8].into(), ProtectedMode::FullAccess);
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory.clone(),
+            "svm_get64" => Function::new_native_with_env(&store, env.clone(), vmcalls::get64),
+            "svm_set64" => Function::new_native_with_env(&store, env.clone(), vmcalls::set64),
+        },
+    };
+    let module = Module::new(&store, wat::parse_str(loading_wat()).unwrap()).unwrap();
+    let instance = Instance::new(&module, &import_object).unwrap();
+    let run = instance.exports.get_function("run").unwrap();
+
+    let mut call = || {
+        run.call(&[]).unwrap();
+    };
+
+    call();
+
+    b.iter(call);
+}
+
+/// The baseline of [`call_sustained_loads`]: reading a variable into a newly allocated vector.
+#[bench]
+fn storage_read_var(b: &mut Bencher) {
+    let storage = written_storage();
+
+    let mut call = || {
+        for _ in 0..LOADS_PER_CALL {
+            test::black_box(storage.read_var(Id(0)).unwrap());
+        }
+    };
+
+    b.iter(call);
+}
+
+#[bench]
+fn storage_read_var_into(b: &mut Bencher) {
+    let storage = written_storage();
+    let mut buf = [0; 8];
+
+    let mut call = || {
+        for _ in 0..LOADS_PER_CALL {
+            storage.read_var_into(Id(0), &mut buf).unwrap();
+            test::black_box(&buf);
+        }
+    };
+
+    b.iter(call);
+}
@@ -0,0 +1,109 @@
+//! A per-transaction bump allocator (see [`Arena`]).
+//!
+//! A running transaction produces many short-lived byte buffers (the `calldata` and `returndata`
+//! of its cross-account calls, for example). Instead of allocating each of them separately, they are
+//! bump-allocated into a single growable buffer owned by the transaction's [`FuncEnv`](crate::FuncEnv),
+//! and all of them are released at once when the transaction completes (or when the [`Arena`] is reset).
+//!
+//! Buffers outliving the transaction (e.g its logs, which are moved into its receipt) aren't allocated
+//! within the [`Arena`], since they would have to be copied out of it.
+//!
+//! The allocated buffers are addressed by [`ArenaSlice`]s (i.e `(offset, length)` pairs)
+//! rather than by references, so that an [`Arena`] can live next to its allocations.
+
+/// The initial capacity (in bytes) of an [`Arena`] created using [`Arena::new`].
+pub const DEFAULT_ARENA_CAPACITY: usize = 4 * 1024;
+
+/// A bump allocator of byte buffers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arena {
+    buf: Vec<u8>,
+}
+
+/// A handle to a buffer allocated by an [`Arena`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ArenaSlice {
+    offset: usize,
+    len: usize,
+}
+
+impl ArenaSlice {
+    /// The length of the allocated buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the allocated buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Default for Arena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Arena {
+    /// Creates a new [`Arena`] with [`DEFAULT_ARENA_CAPACITY`] bytes.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_ARENA_CAPACITY)
+    }
+
+    /// Creates a new [`Arena`] able to hold `capacity` bytes before growing.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Allocates a zeroed buffer of `len` bytes.
+    ///
+    /// Returns the buffer's [`ArenaSlice`] along with the buffer itself (for filling it in-place).
+    pub fn alloc(&mut self, len: usize) -> (ArenaSlice, &mut [u8]) {
+        let offset = self.buf.len();
+        self.buf.resize(offset + len, 0);
+
+        let slice = ArenaSlice { offset, len };
+
+        (slice, &mut self.buf[offset..])
+    }
+
+    /// Allocates a copy of `bytes`.
+    pub fn alloc_copy(&mut self, bytes: &[u8]) -> ArenaSlice {
+        let offset = self.buf.len();
+        self.buf.extend_from_slice(bytes);
+
+        ArenaSlice {
+            offset,
+            len: bytes.len(),
+        }
+    }
+
+    /// Returns the buffer addressed by `slice`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slice` hasn't been allocated by this [`Arena`] (since its last reset).
+    pub fn get(&self, slice: ArenaSlice) -> &[u8] {
+        &self.buf[slice.offset..slice.offset + slice.len]
+    }
+
+    /// The number of allocated bytes.
+    pub fn allocated(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// The number of bytes the [`Arena`] can hold before growing.
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Releases all the allocated buffers (while keeping the [`Arena`] capacity for reuse).
+    ///
+    /// The previously returned [`ArenaSlice`]s must not be used afterwards.
+    pub fn reset(&mut self) {
+        self.buf.clear();
+    }
+}
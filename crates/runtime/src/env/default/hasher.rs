@@ -1,15 +1,41 @@
 use svm_hash::{Blake3Hasher, Hasher};
-use svm_types::Template;
+use svm_types::{Section, Template};
 
 use super::super::traits::TemplateHasher;
 use crate::env::TemplateHash;
 
 /// Default implementation for `TemplateCodeHasher`
+///
+/// Hashes the `Template`'s code along with its `Custom Section`s (ordered by kind).
+/// The runtime ignores the `Custom Section`s, but two `Template`s differing only
+/// by them are still told apart.
 pub struct DefaultTemplateHasher;
 
 impl TemplateHasher for DefaultTemplateHasher {
-    #[inline]
     fn hash(template: &Template) -> TemplateHash {
-        Blake3Hasher::hash(template.code())
+        let mut custom: Vec<_> = template
+            .sections()
+            .iter()
+            .filter_map(|section| match section {
+                Section::Custom(custom) => Some(custom),
+                _ => None,
+            })
+            .collect();
+
+        if custom.is_empty() {
+            return Blake3Hasher::hash(template.code());
+        }
+
+        custom.sort_by_key(|section| section.kind());
+
+        let mut bytes = template.code().to_vec();
+
+        for section in custom {
+            bytes.extend_from_slice(&svm_codec::template::encode_section(
+                &section.clone().into(),
+            ));
+        }
+
+        Blake3Hasher::hash(&bytes)
     }
 }
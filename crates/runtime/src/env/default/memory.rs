@@ -1,4 +1,4 @@
-use crate::env::{default, memory};
+use crate::env::{default, memory, CachedTemplateStore};
 
 use memory::{MemAccountStore, MemTemplateStore};
 
@@ -25,3 +25,18 @@ impl EnvTypes for DefaultMemEnvTypes {
 
     type TemplateHasher = default::DefaultTemplateHasher;
 }
+
+/// Same as [`DefaultMemEnvTypes`], but caching the loaded `Template`s (see [`CachedTemplateStore`]).
+pub struct DefaultCachedMemEnvTypes;
+
+impl EnvTypes for DefaultCachedMemEnvTypes {
+    type TemplateStore = CachedTemplateStore<DefaultMemTemplateStore>;
+
+    type AccountStore = DefaultMemAccountStore;
+
+    type TemplateAddressCompute = default::DefaultTemplateAddressCompute;
+
+    type AccountAddressCompute = default::DefaultAccountAddressCompute;
+
+    type TemplateHasher = default::DefaultTemplateHasher;
+}
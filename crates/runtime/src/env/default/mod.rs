@@ -11,7 +11,9 @@ pub use serialize::{
 mod memory;
 
 #[cfg(feature = "default-memory")]
-pub use memory::{DefaultMemAccountStore, DefaultMemEnvTypes, DefaultMemTemplateStore};
+pub use memory::{
+    DefaultCachedMemEnvTypes, DefaultMemAccountStore, DefaultMemEnvTypes, DefaultMemTemplateStore,
+};
 
 #[cfg(feature = "default-rocksdb")]
 mod rocksdb;
@@ -1,6 +1,6 @@
 use rocksdb::{RocksAccountStore, RocksTemplateStore};
 
-use crate::env::{default, rocksdb};
+use crate::env::{default, rocksdb, CachedTemplateStore};
 use crate::EnvTypes;
 
 /// `RocksTemplateStore` with a default serialization.
@@ -14,7 +14,7 @@ pub type DefaultRocksAccountStore =
 pub struct DefaultRocksEnvTypes;
 
 impl EnvTypes for DefaultRocksEnvTypes {
-    type TemplateStore = DefaultRocksTemplateStore;
+    type TemplateStore = CachedTemplateStore<DefaultRocksTemplateStore>;
 
     type AccountStore = DefaultRocksAccountStore;
 
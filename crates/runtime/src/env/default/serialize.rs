@@ -26,6 +26,7 @@ impl AccountSerializer for DefaultAccountSerializer {
         encode_template(account, &mut w);
         encode_name(account, &mut w);
         encode_spawner(account, &mut w);
+        encode_addr_scheme(account, &mut w);
 
         w
     }
@@ -45,6 +46,10 @@ fn encode_spawner(account: &ExtAccount, w: &mut Vec<u8>) {
     w.write_address(spawner);
 }
 
+fn encode_addr_scheme(account: &ExtAccount, w: &mut Vec<u8>) {
+    w.write_u16_be(account.addr_scheme());
+}
+
 impl AccountDeserializer for DefaultAccountDeserializer {
     fn deserialize(bytes: &[u8]) -> Option<ExtAccount> {
         let mut cursor = Cursor::new(bytes);
@@ -64,8 +69,18 @@ impl AccountDeserializer for DefaultAccountDeserializer {
             _ => return None,
         };
 
+        // An `Account` serialized prior to the address schemes has none (i.e it's of scheme `0`)
+        let addr_scheme = if cursor.position() as usize == bytes.len() {
+            0
+        } else {
+            match cursor.read_u16_be() {
+                Ok(addr_scheme) => addr_scheme,
+                _ => return None,
+            }
+        };
+
         let base = Account::new(template, name);
-        let account = ExtAccount::new(&base, &spawner);
+        let account = ExtAccount::new(&base, &spawner).with_addr_scheme(addr_scheme);
 
         Some(account)
     }
@@ -96,4 +111,51 @@ impl TemplateDeserializer for DefaultTemplateDeserializer {
 
         template.ok()
     }
+
+    fn deserialize_byte_size(
+        bytes: &[u8],
+        interests: Option<&HashSet<SectionKind>>,
+    ) -> Option<u64> {
+        let cursor = Cursor::new(bytes);
+
+        template::sections_byte_size(cursor, interests).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use svm_types::{Address, TemplateAddr};
+
+    use super::*;
+
+    #[test]
+    fn deserialize_baseline_account() {
+        // An `Account` serialized prior to the address schemes (`Template`, `Name` and `Spawner`)
+        let mut bytes = Vec::new();
+        bytes.write_template_addr(&TemplateAddr::repeat(0x10));
+        bytes.write_string("My Account");
+        bytes.write_address(&Address::repeat(0x20));
+
+        let account = DefaultAccountDeserializer::deserialize(&bytes).unwrap();
+
+        assert_eq!(account.template_addr(), &TemplateAddr::repeat(0x10));
+        assert_eq!(account.name(), "My Account");
+        assert_eq!(account.spawner(), &Address::repeat(0x20));
+        assert_eq!(account.addr_scheme(), 0);
+
+        // A truncated `Addr Scheme` is rejected
+        bytes.push(0x01);
+        assert!(DefaultAccountDeserializer::deserialize(&bytes).is_none());
+    }
+
+    #[test]
+    fn serialize_account_addr_scheme() {
+        let base = Account::new(TemplateAddr::repeat(0x10), "My Account".to_string());
+        let account = ExtAccount::new(&base, &Address::repeat(0x20)).with_addr_scheme(7);
+
+        let bytes = DefaultAccountSerializer::serialize(&account);
+        let account = DefaultAccountDeserializer::deserialize(&bytes).unwrap();
+
+        assert_eq!(account.addr_scheme(), 7);
+    }
 }
@@ -1,9 +1,10 @@
-use svm_types::{Account, Address, SpawnAccount, TemplateAddr};
+use svm_types::{Account, Address, SpawnAccount, SpawnCall, TemplateAddr};
 
 #[doc(hidden)]
 pub struct ExtAccount {
     base: Account,
     spawner: Address,
+    addr_scheme: u16,
 }
 
 #[doc(hidden)]
@@ -12,9 +13,15 @@ impl ExtAccount {
         Self {
             base: base.clone(),
             spawner: spawner.clone(),
+            addr_scheme: 0,
         }
     }
 
+    pub fn with_addr_scheme(mut self, addr_scheme: u16) -> Self {
+        self.addr_scheme = addr_scheme;
+        self
+    }
+
     pub fn base(&self) -> &Account {
         &self.base
     }
@@ -30,13 +37,19 @@ impl ExtAccount {
     pub fn spawner(&self) -> &Address {
         &self.spawner
     }
+
+    pub fn addr_scheme(&self) -> u16 {
+        self.addr_scheme
+    }
 }
 
+#[doc(hidden)]
 pub struct ExtSpawn {
     base: SpawnAccount,
     spawner: Address,
 }
 
+#[doc(hidden)]
 impl ExtSpawn {
     pub fn new(base: SpawnAccount, spawner: &Address) -> Self {
         Self {
@@ -69,6 +82,10 @@ impl ExtSpawn {
         self.base().ctor_data()
     }
 
+    pub fn call(&self) -> Option<&SpawnCall> {
+        self.base().call()
+    }
+
     pub fn spawner(&self) -> &Address {
         &self.spawner
     }
@@ -1,5 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::marker::PhantomData;
+use std::ops::Bound;
 
 use svm_types::{Address, TemplateAddr};
 
@@ -13,6 +14,9 @@ use traits::{AccountDeserializer, AccountSerializer, AccountStore};
 /// Should be used for mainly testing purposes only.
 pub struct MemAccountStore<S, D> {
     acc_bytes: HashMap<Address, Vec<u8>>,
+
+    /// The raw bytes of the stored `Account`s `Address`es, kept ordered for paging.
+    addrs: BTreeSet<Vec<u8>>,
     phantom: PhantomData<(S, D)>,
 }
 
@@ -25,6 +29,17 @@ where
     pub fn new() -> Self {
         Self {
             acc_bytes: HashMap::new(),
+            addrs: BTreeSet::new(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S, D> Clone for MemAccountStore<S, D> {
+    fn clone(&self) -> Self {
+        Self {
+            acc_bytes: self.acc_bytes.clone(),
+            addrs: self.addrs.clone(),
             phantom: PhantomData,
         }
     }
@@ -38,6 +53,7 @@ where
     fn store(&mut self, account: &ExtAccount, addr: &Address) {
         let bytes = S::serialize(account);
         self.acc_bytes.insert(addr.clone(), bytes);
+        self.addrs.insert(addr.as_slice().to_vec());
     }
 
     fn load(&self, addr: &Address) -> Option<ExtAccount> {
@@ -49,4 +65,24 @@ where
         let account = self.load(addr);
         account.map(|x| x.template_addr().clone())
     }
+
+    fn addrs(&self) -> Vec<Address> {
+        self.addrs
+            .iter()
+            .map(|addr| Address::from(&addr[..]))
+            .collect()
+    }
+
+    fn addrs_after(&self, after: Option<&Address>, limit: usize) -> Vec<Address> {
+        let start = match after {
+            Some(after) => Bound::Excluded(after.as_slice().to_vec()),
+            None => Bound::Unbounded,
+        };
+
+        self.addrs
+            .range((start, Bound::Unbounded))
+            .take(limit)
+            .map(|addr| Address::from(&addr[..]))
+            .collect()
+    }
 }
@@ -1,18 +1,30 @@
 use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
 
-use svm_types::{SectionKind, Template, TemplateAddr};
+use svm_codec::template::refs::{SectionHash, SectionRef};
+use svm_types::{Section, SectionKind, Sections, Template, TemplateAddr};
 
 use crate::env::{traits, TemplateHash};
 use traits::{TemplateDeserializer, TemplateSerializer, TemplateStore};
 
 /// An in-memory implementation of `TemplateStore`
+///
+/// The `Section`s are content-addressed (i.e stored once under their [`SectionHash`]),
+/// and each `Template` version is stored as a list of [`SectionRef`]s (along with its [`TemplateHash`]).
+/// This way `Template`s sharing `Section`s (e.g a `Template` and its upgrade) don't store the shared `Section`s twice.
 pub struct MemTemplateStore<S, D> {
-    bytes: HashMap<TemplateHash, Vec<u8>>,
-    hash: HashMap<TemplateAddr, TemplateHash>,
+    sections: HashMap<SectionHash, Vec<u8>>,
+    versions: HashMap<TemplateAddr, Vec<StoredTemplate>>,
     phantom: PhantomData<(S, D)>,
 }
 
+/// A stored version of a `Template`.
+#[derive(Clone)]
+struct StoredTemplate {
+    hash: TemplateHash,
+    refs: Vec<SectionRef>,
+}
+
 impl<S, D> MemTemplateStore<S, D>
 where
     S: TemplateSerializer,
@@ -22,8 +34,68 @@ where
     /// Create a new store
     pub fn new() -> Self {
         Self {
-            bytes: HashMap::new(),
-            hash: HashMap::new(),
+            sections: HashMap::new(),
+            versions: HashMap::new(),
+            phantom: PhantomData,
+        }
+    }
+
+    fn store_section(&mut self, section: &Section) -> SectionRef {
+        let r = SectionRef::new(section);
+
+        self.sections.entry(*r.hash()).or_insert_with(|| {
+            let mut sections = Sections::with_capacity(1);
+            sections.insert(section.clone());
+
+            S::serialize(&Template::new(sections))
+        });
+
+        r
+    }
+
+    fn store_template(&mut self, template: &Template, hash: &TemplateHash) -> StoredTemplate {
+        let refs = template
+            .sections()
+            .iter()
+            .map(|section| self.store_section(section))
+            .collect();
+
+        StoredTemplate { hash: *hash, refs }
+    }
+
+    fn latest_refs(&self, addr: &TemplateAddr) -> Option<&Vec<SectionRef>> {
+        self.versions
+            .get(addr)
+            .and_then(|versions| versions.last())
+            .map(|stored| &stored.refs)
+    }
+
+    fn load_refs(
+        &self,
+        refs: &[SectionRef],
+        interests: Option<HashSet<SectionKind>>,
+    ) -> Option<Template> {
+        let mut sections = Sections::with_capacity(refs.len());
+
+        let interested = |kind: SectionKind| match &interests {
+            Some(interests) => interests.contains(&kind),
+            None => true,
+        };
+
+        for r in refs.iter().filter(|r| interested(r.kind())) {
+            let section = self.load_section(r.hash())?;
+            sections.insert(section);
+        }
+
+        Some(Template::new(sections))
+    }
+}
+
+impl<S, D> Clone for MemTemplateStore<S, D> {
+    fn clone(&self) -> Self {
+        Self {
+            sections: self.sections.clone(),
+            versions: self.versions.clone(),
             phantom: PhantomData,
         }
     }
@@ -35,10 +107,33 @@ where
     D: TemplateDeserializer,
 {
     fn store(&mut self, template: &Template, addr: &TemplateAddr, hash: &TemplateHash) {
-        self.hash.insert(addr.clone(), hash.clone());
+        let stored = self.store_template(template, hash);
+
+        self.versions.insert(addr.clone(), vec![stored]);
+    }
+
+    fn upgrade(&mut self, template: &Template, addr: &TemplateAddr, hash: &TemplateHash) -> u16 {
+        let stored = self.store_template(template, hash);
+        let versions = self
+            .versions
+            .get_mut(addr)
+            .expect("Should have validated the upgraded `Template` exists first.");
 
-        let bytes = S::serialize(template);
-        self.bytes.insert(hash.clone(), bytes);
+        versions.push(stored);
+
+        (versions.len() - 1) as u16
+    }
+
+    fn version(&self, addr: &TemplateAddr) -> Option<u16> {
+        self.versions
+            .get(addr)
+            .map(|versions| (versions.len() - 1) as u16)
+    }
+
+    fn template_hash(&self, addr: &TemplateAddr, version: u16) -> Option<TemplateHash> {
+        let stored = self.versions.get(addr)?.get(version as usize)?;
+
+        Some(stored.hash)
     }
 
     fn load(
@@ -46,12 +141,96 @@ where
         addr: &TemplateAddr,
         interests: Option<HashSet<SectionKind>>,
     ) -> Option<Template> {
-        let hash = self.hash.get(addr);
+        let refs = self.latest_refs(addr)?;
+
+        self.load_refs(refs, interests)
+    }
+
+    fn load_version(
+        &self,
+        addr: &TemplateAddr,
+        version: u16,
+        interests: Option<HashSet<SectionKind>>,
+    ) -> Option<Template> {
+        let stored = self.versions.get(addr)?.get(version as usize)?;
+
+        self.load_refs(&stored.refs, interests)
+    }
+
+    fn load_byte_size(
+        &self,
+        addr: &TemplateAddr,
+        interests: Option<&HashSet<SectionKind>>,
+    ) -> Option<u64> {
+        let refs = self.latest_refs(addr)?;
+
+        refs.iter()
+            .filter(|r| interests.map_or(true, |interests| interests.contains(&r.kind())))
+            .try_fold(0, |byte_size, r| {
+                let bytes = self.sections.get(r.hash())?;
+                let size = D::deserialize_byte_size(bytes, None)?;
+
+                Some(byte_size + size)
+            })
+    }
+
+    fn load_section(&self, hash: &SectionHash) -> Option<Section> {
+        let bytes = self.sections.get(hash)?;
+        let template = D::deserialize(bytes, None)?;
+
+        template.sections().iter().next().cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use svm_codec::api::builder::TemplateBuilder;
+    use svm_codec::template::refs::section_hash;
+    use svm_types::{CodeSection, CtorsSection, DataSection, HeaderSection};
+
+    use crate::env::DefaultMemTemplateStore;
+
+    use super::*;
+
+    fn make_template(code: Vec<u8>, code_version: u32) -> Template {
+        TemplateBuilder::default()
+            .with_code(CodeSection::new_fixed(code, 0))
+            .with_data(DataSection::default())
+            .with_ctors(CtorsSection::new(vec!["init".to_string()]))
+            .with_header(HeaderSection::new(
+                code_version,
+                "My Template".to_string(),
+                "".to_string(),
+            ))
+            .build()
+    }
+
+    #[test]
+    fn mem_template_store_shares_sections() {
+        let mut store = DefaultMemTemplateStore::new();
+        let addr = TemplateAddr::repeat(0x10);
+
+        let template = make_template(vec![0x00, 0x61, 0x73, 0x6D], 0);
+        store.store(&template, &addr, &[0x01; 32]);
+        assert_eq!(store.sections.len(), 4);
+
+        // The upgrade changes only the `Code` and `Header` sections
+        let upgraded = make_template(vec![0x00, 0x61, 0x73, 0x6D, 0x01], 1);
+        assert_eq!(store.upgrade(&upgraded, &addr, &[0x02; 32]), 1);
+        assert_eq!(store.sections.len(), 6);
+
+        assert_eq!(store.load(&addr, None), Some(upgraded.clone()));
+        assert_eq!(store.load_version(&addr, 0, None), Some(template.clone()));
+        assert_eq!(store.template_hash(&addr, 0), Some([0x01; 32]));
+        assert_eq!(store.template_hash(&addr, 1), Some([0x02; 32]));
+        assert_eq!(store.template_hash(&addr, 2), None);
+
+        for section in template.sections().iter() {
+            let loaded = store.load_section(&section_hash(section));
+
+            assert_eq!(loaded.as_ref(), Some(section));
+        }
 
-        hash.and_then(|h| {
-            self.bytes
-                .get(h)
-                .and_then(|bytes| D::deserialize(&bytes, interests))
-        })
+        assert_eq!(store.load_section(&[0xFF; 32]), None);
     }
 }
@@ -3,10 +3,15 @@ use std::collections::{HashMap, HashSet};
 use std::io::Cursor;
 use std::rc::Rc;
 
+use svm_codec::template::refs::{SectionRef, TemplateRefs};
 use svm_codec::ParseError;
-use svm_codec::{call, spawn, template};
+use svm_codec::{batch, call, spawn, template, upgrade};
 use svm_gas::{resolvers, PriceResolver};
-use svm_types::{Address, SectionKind, SpawnAccount, Template, TemplateAddr, Transaction};
+use svm_storage::kv::FlushError;
+use svm_types::{
+    Address, BatchTransaction, Layer, SectionKind, SpawnAccount, Template, TemplateAddr,
+    Transaction, UpgradeTemplate,
+};
 
 /// Default implementations
 mod default;
@@ -25,7 +30,9 @@ mod memory;
 pub use memory::{MemAccountStore, MemTemplateStore};
 
 #[cfg(feature = "default-memory")]
-pub use default::{DefaultMemAccountStore, DefaultMemEnvTypes, DefaultMemTemplateStore};
+pub use default::{
+    DefaultCachedMemEnvTypes, DefaultMemAccountStore, DefaultMemEnvTypes, DefaultMemTemplateStore,
+};
 
 /// Rocksdb related types
 #[cfg(feature = "default-rocksdb")]
@@ -37,9 +44,14 @@ pub use rocksdb::{RocksAccountStore, RocksTemplateStore};
 #[cfg(feature = "default-rocksdb")]
 pub use default::{DefaultRocksEnvTypes, DefaultRocksTemplateStore};
 
+/// Caching of loaded `Template`s
+mod template_cache;
+
+pub use template_cache::{CachedTemplateStore, TemplateCacheStats};
+
 mod traits;
 
-pub use traits::{AccountStore, ComputeAddress, TemplateHasher, TemplateStore};
+pub use traits::{AccountStore, AddressScheme, ComputeAddress, TemplateHasher, TemplateStore};
 
 /// Represents an `Template` Hash.
 pub type TemplateHash = [u8; 32];
@@ -53,10 +65,10 @@ pub trait EnvTypes {
     /// [`AccountStore`] type.
     type AccountStore: AccountStore;
 
-    /// Compute a [`Template`] `Address`
+    /// Compute a [`Template`] `Address` (under the address derivation scheme `0`)
     type TemplateAddressCompute: ComputeAddress<Template, Address = TemplateAddr>;
 
-    /// Compute an `Account`'s `Address`
+    /// Compute an `Account`'s `Address` (under the address derivation scheme `0`)
     type AccountAddressCompute: ComputeAddress<ExtSpawn, Address = Address>;
 
     /// [`Template`] content [`TemplateHasher`] type.
@@ -71,6 +83,8 @@ where
     accounts: T::AccountStore,
     templates: T::TemplateStore,
     price_resolver_registry: PriceResolverRegistry,
+    addr_scheme_registry: AddressSchemeRegistry,
+    addr_scheme: u16,
 }
 
 impl<T> Env<T>
@@ -85,6 +99,8 @@ where
             accounts: account_store,
             templates: template_store,
             price_resolver_registry: PriceResolverRegistry::default(),
+            addr_scheme_registry: AddressSchemeRegistry::empty(),
+            addr_scheme: 0,
         }
     }
 
@@ -108,19 +124,76 @@ where
         &mut self.accounts
     }
 
+    /// Resolves a [`Template`] encoded with `Section` references (see [`svm_codec::template::refs`])
+    /// using the `Section`s held by the [`TemplateStore`].
+    ///
+    /// Returns the first [`SectionRef`] that couldn't be resolved.
+    pub fn resolve_template_refs(&self, refs: TemplateRefs) -> Result<Template, SectionRef> {
+        refs.resolve(|r| self.templates.load_section(r.hash()))
+    }
+
     /// Computes the [`TemplateHash`] of a [`Template`].
     pub fn compute_template_hash(&self, template: &Template) -> TemplateHash {
         T::TemplateHasher::hash(template)
     }
 
-    /// Computes the [`Template`]'s `Address` of `template`
+    /// Computes the [`Template`]'s `Address` of `template` (using the current address derivation scheme)
     pub fn compute_template_addr(&self, template: &Template) -> TemplateAddr {
-        T::TemplateAddressCompute::compute(template)
+        match self.addr_scheme_registry.get(self.addr_scheme) {
+            Some(scheme) => scheme.template_addr(template),
+            None => T::TemplateAddressCompute::compute(template),
+        }
     }
 
-    /// Computes an `Account`'s `Address`
+    /// Computes an `Account`'s `Address` (using the current address derivation scheme)
     pub fn compute_account_addr(&self, spawn: &ExtSpawn) -> Address {
-        T::AccountAddressCompute::compute(spawn)
+        match self.addr_scheme_registry.get(self.addr_scheme) {
+            Some(scheme) => scheme.account_addr(spawn),
+            None => T::AccountAddressCompute::compute(spawn),
+        }
+    }
+
+    /// The id of the address derivation scheme used for computing the `Address`es
+    /// of new [`Template`]s and `Account`s.
+    ///
+    /// Scheme `0` stands for the derivation dictated by the [`EnvTypes`]
+    /// (see [`EnvTypes::TemplateAddressCompute`] and [`EnvTypes::AccountAddressCompute`]).
+    pub fn addr_scheme(&self) -> u16 {
+        self.addr_scheme
+    }
+
+    /// Registers an additional address derivation scheme under id `addr_scheme`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `addr_scheme` is `0` (it's reserved for the [`EnvTypes`] derivation),
+    /// or if a scheme has already been registered under `addr_scheme`.
+    pub fn register_addr_scheme(&mut self, addr_scheme: u16, scheme: Rc<dyn AddressScheme>) {
+        assert!(addr_scheme != 0, "Address scheme `0` is reserved");
+        assert!(
+            self.addr_scheme_registry.get(addr_scheme).is_none(),
+            "Address scheme `{}` is already registered",
+            addr_scheme
+        );
+
+        self.addr_scheme_registry.add(addr_scheme, scheme);
+    }
+
+    /// Switches the address derivation scheme of new [`Template`]s and `Account`s to `addr_scheme`.
+    ///
+    /// Existing `Template`s and `Account`s keep their `Address`es.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no scheme has been registered under `addr_scheme`.
+    pub fn set_addr_scheme(&mut self, addr_scheme: u16) {
+        assert!(
+            addr_scheme == 0 || self.addr_scheme_registry.get(addr_scheme).is_some(),
+            "Unknown address scheme `{}`",
+            addr_scheme
+        );
+
+        self.addr_scheme = addr_scheme;
     }
 
     /// Parses a binary `Deploy Template` transaction
@@ -160,6 +233,28 @@ where
         Ok(call)
     }
 
+    /// Parses a binary `Call Batch` (a.k.a a [`BatchTransaction`]).
+    ///
+    /// On success returns [`BatchTransaction`],
+    /// On failure returns [`ParseError`].
+    pub fn parse_batch(&self, bytes: &[u8]) -> Result<BatchTransaction, ParseError> {
+        let mut cursor = Cursor::new(bytes);
+        let batch = batch::decode_batch(&mut cursor)?;
+
+        Ok(batch)
+    }
+
+    /// Parses a binary [`UpgradeTemplate`] transaction.
+    ///
+    /// On success returns [`UpgradeTemplate`],
+    /// On failure returns [`ParseError`].
+    pub fn parse_upgrade(&self, bytes: &[u8]) -> Result<UpgradeTemplate, ParseError> {
+        let mut cursor = Cursor::new(bytes);
+        let upgrade = upgrade::decode(&mut cursor)?;
+
+        Ok(upgrade)
+    }
+
     /// Saves a [`Template`] at the given [`TemplateAddr`].
     pub fn store_template(&mut self, template: &Template, addr: &TemplateAddr) {
         let hash = self.compute_template_hash(template);
@@ -168,6 +263,16 @@ where
         store.store(template, &addr, &hash);
     }
 
+    /// Saves a [`Template`] as the next version of the [`Template`] at the given [`TemplateAddr`].
+    ///
+    /// Returns the version of the saved [`Template`].
+    pub fn upgrade_template(&mut self, template: &Template, addr: &TemplateAddr) -> u16 {
+        let hash = self.compute_template_hash(template);
+        let store = self.template_store_mut();
+
+        store.upgrade(template, addr, &hash)
+    }
+
     /// Stores an `Account Address` -> `Account`'s `Template Address`.
     pub fn store_account(&mut self, account: &ExtAccount, addr: &Address) {
         let template = account.template_addr();
@@ -180,6 +285,19 @@ where
         }
     }
 
+    /// Tags the next writes of both stores with `layer` (see [`TemplateStore::set_layer`]).
+    pub fn set_layer(&mut self, layer: Layer) {
+        self.templates.set_layer(layer);
+        self.accounts.set_layer(layer);
+    }
+
+    /// Blocks until both stores have persisted the writes of `layer`
+    /// (see [`TemplateStore::flush_until`]).
+    pub fn flush_until(&self, layer: Layer) -> Result<(), FlushError> {
+        self.templates.flush_until(layer)?;
+        self.accounts.flush_until(layer)
+    }
+
     /// Given an `Address` `addr`, locates the `TemplateAddr` of its
     /// [`Template`]. Returns [`None`] if and only if no [`Template`] was found.
     pub fn resolve_template_addr(&self, addr: &Address) -> Option<TemplateAddr> {
@@ -200,6 +318,28 @@ where
         })
     }
 
+    /// Given an `Account` Address, returns the byte size of the `Section`s of the associated `Template`
+    /// loaded by [`Self::account_template`] given the same `interests`.
+    pub fn account_template_byte_size(
+        &self,
+        addr: &Address,
+        interests: Option<&HashSet<SectionKind>>,
+    ) -> Option<u64> {
+        self.account(addr)
+            .and_then(|account| self.template_byte_size(account.template_addr(), interests))
+    }
+
+    /// Given a `Template` Address, returns the byte size of its `Section`s
+    /// loaded by [`Self::template`] given the same `interests`.
+    pub fn template_byte_size(
+        &self,
+        addr: &TemplateAddr,
+        interests: Option<&HashSet<SectionKind>>,
+    ) -> Option<u64> {
+        let store = self.template_store();
+        store.load_byte_size(addr, interests)
+    }
+
     /// Loads a [`Template`] given its `Address`
     #[must_use]
     pub fn template(
@@ -211,6 +351,25 @@ where
         store.load(&addr, interests)
     }
 
+    /// Loads the given `version` of a [`Template`] given its `Address`
+    #[must_use]
+    pub fn template_at_version(
+        &self,
+        addr: &TemplateAddr,
+        version: u16,
+        interests: Option<HashSet<SectionKind>>,
+    ) -> Option<Template> {
+        let store = self.template_store();
+        store.load_version(&addr, version, interests)
+    }
+
+    /// Returns the latest version of a [`Template`] given its `Address`
+    #[must_use]
+    pub fn template_version(&self, addr: &TemplateAddr) -> Option<u16> {
+        let store = self.template_store();
+        store.version(&addr)
+    }
+
     /// Loads an `ExtAccount` given its `Address`
     #[must_use]
     pub fn account(&self, addr: &Address) -> Option<ExtAccount> {
@@ -234,9 +393,15 @@ where
     /// to price transactions.
     pub fn price_resolver(&self) -> Rc<dyn PriceResolver> {
         self.price_resolver_registry
-            .get(0)
+            .get(self.pricing_version())
             .expect("Missing pricing utility.")
     }
+
+    /// Returns the version of the [`PriceResolver`] returned by [`Self::price_resolver`].
+    #[inline]
+    pub fn pricing_version(&self) -> u16 {
+        0
+    }
 }
 
 #[derive(Clone)]
@@ -260,6 +425,28 @@ impl PriceResolverRegistry {
     }
 }
 
+/// Holds the registered address derivation schemes (see [`AddressScheme`]), keyed by their ids.
+#[derive(Clone)]
+pub struct AddressSchemeRegistry {
+    schemes: HashMap<u16, Rc<dyn AddressScheme>>,
+}
+
+impl AddressSchemeRegistry {
+    pub fn empty() -> Self {
+        Self {
+            schemes: HashMap::default(),
+        }
+    }
+
+    pub fn add(&mut self, addr_scheme: u16, scheme: Rc<dyn AddressScheme>) {
+        self.schemes.insert(addr_scheme, scheme);
+    }
+
+    pub fn get(&self, addr_scheme: u16) -> Option<Rc<dyn AddressScheme>> {
+        self.schemes.get(&addr_scheme).cloned()
+    }
+}
+
 impl Default for PriceResolverRegistry {
     fn default() -> Self {
         let mut registry = Self::empty();
@@ -5,15 +5,20 @@ use std::path::Path;
 
 use crate::env::{traits, ExtAccount};
 
-use svm_types::{AccountAddr, Address, TemplateAddr};
+use svm_kv::rocksdb::Rocksdb;
+use svm_kv::traits::RawKV;
+use svm_storage::kv::{FlushError, WriteBehindKV, WriteMode};
+use svm_types::{AccountAddr, Address, Layer, TemplateAddr};
 use traits::{AccountDeserializer, AccountSerializer, AccountStore};
 
 const ACCOUNT_KEY_PREFIX: &'static [u8] = b"acc:";
 const ACCOUNT_TEMPLATE_KEY_PREFIX: &'static [u8] = b"acc-temp:";
 
 /// [`AccountStore`] implementation backed-by `rocksdb`
+///
+/// The writes go through a [`WriteBehindKV`] (journaled next to the `rocksdb` directory).
 pub struct RocksAccountStore<S, D> {
-    db: Rocksdb,
+    db: WriteBehindKV<Rocksdb>,
     phantom: PhantomData<(S, D)>,
 }
 
@@ -61,6 +66,43 @@ where
                 .and_then(|bytes| D::deserialize_template_addr(&bytes[..]))
         })
     }
+
+    fn addrs(&self) -> Vec<Address> {
+        // The keys are ordered, so are the `Address`es following the prefix
+        let keys = self
+            .db
+            .read_persisted(|db| db.keys_with_prefix(ACCOUNT_TEMPLATE_KEY_PREFIX))
+            .unwrap_or_else(|err| panic!("{}", err));
+
+        keys.iter()
+            .map(|key| Address::from(&key[ACCOUNT_TEMPLATE_KEY_PREFIX.len()..]))
+            .collect()
+    }
+
+    fn addrs_after(&self, after: Option<&Address>, limit: usize) -> Vec<Address> {
+        let after = after.map(|addr| addr.as_slice());
+
+        let keys = self
+            .db
+            .read_persisted(|db| {
+                db.keys_with_prefix_after(ACCOUNT_TEMPLATE_KEY_PREFIX, after, limit)
+            })
+            .unwrap_or_else(|err| panic!("{}", err));
+
+        keys.iter()
+            .map(|key| Address::from(&key[ACCOUNT_TEMPLATE_KEY_PREFIX.len()..]))
+            .collect()
+    }
+
+    fn set_layer(&mut self, layer: Layer) {
+        if layer > self.db.layer() {
+            self.db.set_layer(layer);
+        }
+    }
+
+    fn flush_until(&self, layer: Layer) -> Result<(), FlushError> {
+        self.db.flush_until(layer)
+    }
 }
 
 impl<S, D> RocksAccountStore<S, D>
@@ -68,13 +110,20 @@ where
     S: AccountSerializer,
     D: AccountDeserializer,
 {
-    /// New instance
-    pub fn new<P>(path: P) -> Self
+    /// New instance, persisting its writes according to `mode`.
+    ///
+    /// The journal of the staged writes is at `path` with a `journal` extension.
+    pub fn new<P>(path: P, mode: WriteMode) -> Self
     where
         P: AsRef<Path>,
     {
+        let path = path.as_ref();
+        let db =
+            WriteBehindKV::with_journal(Rocksdb::new(path), mode, path.with_extension("journal"))
+                .expect("failed opening the `Account`s store journal");
+
         Self {
-            db: Rocksdb::new(path),
+            db,
             phantom: PhantomData,
         }
     }
@@ -1,20 +1,38 @@
 use log::info;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryInto;
+use std::io::Cursor;
 use std::marker::PhantomData;
 use std::path::Path;
 
-use svm_types::{Address, SectionKind, Template, TemplateAddr};
+use svm_codec::template::refs::{self, SectionHash, SectionRef};
+use svm_kv::rocksdb::Rocksdb;
+use svm_kv::traits::RawKV;
+use svm_storage::kv::{FlushError, WriteBehindKV, WriteMode};
+use svm_types::{Address, Layer, Section, SectionKind, Sections, Template, TemplateAddr};
 
 use crate::env::{traits, TemplateHash};
 use traits::{TemplateDeserializer, TemplateSerializer, TemplateStore};
 
 const TEMPLATE_KEY_PREFIX: &'static [u8] = b"template:";
 const TEMPLATE_HASH_KEY_PREFIX: &'static [u8] = b"template-hash:";
+const TEMPLATE_VERSION_KEY_PREFIX: &'static [u8] = b"template-version:";
+const TEMPLATE_REFS_KEY_PREFIX: &'static [u8] = b"template-refs:";
+const SECTION_KEY_PREFIX: &'static [u8] = b"section:";
 
 /// `Template` store backed by `rocksdb`
+///
+/// The `Section`s are content-addressed (i.e stored once under their [`SectionHash`]),
+/// and each `Template` version is stored as a list of [`SectionRef`]s (see [`refs::encode_with_refs`]).
+/// This way `Template`s sharing `Section`s (e.g a `Template` and its upgrade) don't store the shared `Section`s twice.
+///
+/// The `Template`s stored prior to the `Section`s content-addressing are stored in whole under their [`TemplateHash`],
+/// and are still loaded as such.
+///
+/// The writes go through a [`WriteBehindKV`] (journaled next to the `rocksdb` directory).
 pub struct RocksTemplateStore<S, D> {
-    db: Rocksdb,
+    db: WriteBehindKV<Rocksdb>,
     phantom: PhantomData<(S, D)>,
 }
 
@@ -24,22 +42,53 @@ where
     D: TemplateDeserializer,
 {
     fn store(&mut self, template: &Template, addr: &TemplateAddr, hash: &TemplateHash) {
-        let addr = addr.inner();
+        info!("Storing `Template`: \n{:?}", addr.inner());
+        info!("     Hash: {:?}", hash);
+
+        self.store_version(template, addr, 0, hash);
+    }
 
-        info!("Storing `Template`: \n{:?}", addr);
-        info!("     Account Address: {:?}", addr);
+    fn upgrade(&mut self, template: &Template, addr: &TemplateAddr, hash: &TemplateHash) -> u16 {
+        let version = self
+            .version(addr)
+            .expect("Should have validated the upgraded `Template` exists first.")
+            + 1;
+
+        info!(
+            "Upgrading `Template` {:?} to version {}",
+            addr.inner(),
+            version
+        );
         info!("     Hash: {:?}", hash);
 
-        // 1) Template `Address` -> `TemplateHash`
-        let key = self.template_key(addr);
-        let entry1 = (&key[..], hash.as_slice());
+        self.store_version(template, addr, version, hash);
+
+        version
+    }
+
+    fn version(&self, addr: &TemplateAddr) -> Option<u16> {
+        let addr = addr.inner();
+
+        if self.db.get(&self.template_key(addr)).is_none() {
+            return None;
+        }
+
+        let mut version = 0;
+        while self
+            .db
+            .get(&self.template_version_key(addr, version + 1))
+            .is_some()
+        {
+            version += 1;
+        }
+
+        Some(version)
+    }
 
-        // 2) `TemplateHash` -> serialized `Template`
-        let key = self.template_hash_key(hash);
-        let bytes = S::serialize(template);
-        let entry2 = (&key[..], bytes.as_slice());
+    fn template_hash(&self, addr: &TemplateAddr, version: u16) -> Option<TemplateHash> {
+        let key = self.template_version_key(addr.inner(), version);
 
-        self.db.set(&[entry1, entry2]);
+        self.db.get(&key)?.as_slice().try_into().ok()
     }
 
     fn load(
@@ -47,15 +96,78 @@ where
         addr: &TemplateAddr,
         interests: Option<HashSet<SectionKind>>,
     ) -> Option<Template> {
-        let addr = addr.inner().as_slice();
+        info!("Loading `Template` {:?}", addr.inner());
+
+        let version = self.version(addr)?;
+
+        self.load_version(addr, version, interests)
+    }
+
+    fn load_version(
+        &self,
+        addr: &TemplateAddr,
+        version: u16,
+        interests: Option<HashSet<SectionKind>>,
+    ) -> Option<Template> {
+        match self.load_refs(addr, version) {
+            Some(refs) => {
+                let mut sections = Sections::with_capacity(refs.len());
+
+                for r in refs.iter().filter(|r| interested(&interests, r.kind())) {
+                    let section = self.load_section(r.hash())?;
+                    sections.insert(section);
+                }
+
+                Some(Template::new(sections))
+            }
+            None => {
+                let bytes = self.load_legacy(addr, version)?;
+
+                D::deserialize(&bytes[..], interests)
+            }
+        }
+    }
+
+    fn load_byte_size(
+        &self,
+        addr: &TemplateAddr,
+        interests: Option<&HashSet<SectionKind>>,
+    ) -> Option<u64> {
+        let version = self.version(addr)?;
+
+        match self.load_refs(addr, version) {
+            Some(refs) => refs
+                .iter()
+                .filter(|r| interests.map_or(true, |interests| interests.contains(&r.kind())))
+                .try_fold(0, |byte_size, r| {
+                    let bytes = self.db.get(&self.section_key(r.hash()))?;
+                    let size = D::deserialize_byte_size(&bytes[..], None)?;
+
+                    Some(byte_size + size)
+                }),
+            None => {
+                let bytes = self.load_legacy(addr, version)?;
 
-        info!("Loading `Template` {:?}", addr);
+                D::deserialize_byte_size(&bytes[..], interests)
+            }
+        }
+    }
 
-        self.db.get(addr).and_then(|hash| {
-            self.db
-                .get(&hash)
-                .and_then(|bytes| D::deserialize(&bytes[..], interests))
-        })
+    fn set_layer(&mut self, layer: Layer) {
+        if layer > self.db.layer() {
+            self.db.set_layer(layer);
+        }
+    }
+
+    fn flush_until(&self, layer: Layer) -> Result<(), FlushError> {
+        self.db.flush_until(layer)
+    }
+
+    fn load_section(&self, hash: &SectionHash) -> Option<Section> {
+        let bytes = self.db.get(&self.section_key(hash))?;
+        let template = D::deserialize(&bytes[..], None)?;
+
+        template.sections().iter().next().cloned()
     }
 }
 
@@ -64,17 +176,97 @@ where
     S: TemplateSerializer,
     D: TemplateDeserializer,
 {
-    /// Creates a new template store at the given path
-    pub fn new<P>(path: P) -> Self
+    /// Creates a new template store at the given path, persisting its writes according to `mode`.
+    ///
+    /// The journal of the staged writes is at `path` with a `journal` extension.
+    pub fn new<P>(path: P, mode: WriteMode) -> Self
     where
         P: AsRef<Path>,
     {
+        let path = path.as_ref();
+        let db =
+            WriteBehindKV::with_journal(Rocksdb::new(path), mode, path.with_extension("journal"))
+                .expect("failed opening the `Template`s store journal");
+
         Self {
-            db: Rocksdb::new(path),
+            db,
             phantom: PhantomData,
         }
     }
 
+    /// Stores `template` as version `version` of the `Template` located at `addr` (in a single batch).
+    fn store_version(
+        &mut self,
+        template: &Template,
+        addr: &TemplateAddr,
+        version: u16,
+        hash: &TemplateHash,
+    ) {
+        let addr = addr.inner();
+
+        // 1) `SectionHash` -> serialized (single-section) `Template` (for the `Section`s not stored yet)
+        let mut sections = HashMap::new();
+
+        for section in template.sections().iter() {
+            let r = SectionRef::new(section);
+            let key = self.section_key(r.hash());
+
+            if self.db.get(&key).is_none() {
+                let mut single = Sections::with_capacity(1);
+                single.insert(section.clone());
+
+                sections.insert(key, S::serialize(&Template::new(single)));
+            }
+        }
+
+        // 2) Template (`Address`, version) -> `Section`s references (all of them are known by now)
+        let known: HashSet<SectionHash> = template
+            .sections()
+            .iter()
+            .map(|section| *SectionRef::new(section).hash())
+            .collect();
+        let refs = refs::encode_with_refs(template, &known);
+        let refs_key = self.template_refs_key(addr, version);
+
+        // 3) Template `Address` -> latest `TemplateHash`
+        let key = self.template_key(addr);
+
+        // 4) Template (`Address`, version) -> `TemplateHash`
+        let version_key = self.template_version_key(addr, version);
+
+        let mut entries: Vec<(&[u8], &[u8])> = sections
+            .iter()
+            .map(|(key, bytes)| (&key[..], &bytes[..]))
+            .collect();
+        entries.push((&refs_key[..], &refs[..]));
+        entries.push((&key[..], hash.as_slice()));
+        entries.push((&version_key[..], hash.as_slice()));
+
+        self.db.set(&entries);
+    }
+
+    /// The `Section`s references of version `version` of the `Template` located at `addr`.
+    ///
+    /// Returns `None` for a `Template` stored in whole (see [`Self::load_legacy`]).
+    fn load_refs(&self, addr: &TemplateAddr, version: u16) -> Option<Vec<SectionRef>> {
+        let bytes = self
+            .db
+            .get(&self.template_refs_key(addr.inner(), version))?;
+        let decoded = refs::decode_with_refs(Cursor::new(&bytes[..])).ok()?;
+
+        debug_assert_eq!(decoded.sections.len(), 0);
+
+        Some(decoded.refs)
+    }
+
+    /// The serialized version `version` of a `Template` located at `addr`, stored in whole
+    /// (i.e prior to the `Section`s content-addressing).
+    fn load_legacy(&self, addr: &TemplateAddr, version: u16) -> Option<Vec<u8>> {
+        let hash = self.template_hash(addr, version)?;
+
+        self.db.get(&self.template_hash_key(&hash))
+    }
+
     #[inline]
     fn template_key(&self, addr: &Address) -> Vec<u8> {
         // Keys mapping from an `Template Address` to `Template Hash`
@@ -88,16 +280,164 @@ where
         key
     }
 
+    #[inline]
+    fn template_version_key(&self, addr: &Address, version: u16) -> Vec<u8> {
+        // Keys mapping from a (`Template Address`, version) to `Template Hash`
+        // are of the pattern "template-version:TEMPLATE_ADDRESS:VERSION"
+
+        self.versioned_key(TEMPLATE_VERSION_KEY_PREFIX, addr, version)
+    }
+
+    #[inline]
+    fn template_refs_key(&self, addr: &Address, version: u16) -> Vec<u8> {
+        // Keys mapping from a (`Template Address`, version) to the `Section`s references
+        // are of the pattern "template-refs:TEMPLATE_ADDRESS:VERSION"
+
+        self.versioned_key(TEMPLATE_REFS_KEY_PREFIX, addr, version)
+    }
+
+    #[inline]
+    fn versioned_key(&self, prefix: &[u8], addr: &Address, version: u16) -> Vec<u8> {
+        let mut key = Vec::with_capacity(prefix.len() + Address::len() + 3);
+
+        key.extend_from_slice(prefix);
+        key.extend_from_slice(addr.as_slice());
+        key.push(b':');
+        key.extend_from_slice(&version.to_be_bytes());
+
+        key
+    }
+
     #[inline]
     fn template_hash_key(&self, hash: &TemplateHash) -> Vec<u8> {
-        // Keys mapping from an `Template Hash` to `Template`
-        // are of the pattern "template-hash:TEMPLATE_ADDRESS"
+        // Keys mapping from an `Template Hash` to `Template` (stored in whole)
+        // are of the pattern "template-hash:TEMPLATE_HASH"
 
-        let mut key = Vec::with_capacity(TemplateHash::len() + TEMPLATE_HASH_KEY_PREFIX.len());
+        let mut key = Vec::with_capacity(hash.len() + TEMPLATE_HASH_KEY_PREFIX.len());
 
         key.extend_from_slice(TEMPLATE_HASH_KEY_PREFIX);
         key.extend_from_slice(hash.as_slice());
 
         key
     }
+
+    #[inline]
+    fn section_key(&self, hash: &SectionHash) -> Vec<u8> {
+        // Keys mapping from a `Section Hash` to a `Section`
+        // are of the pattern "section:SECTION_HASH"
+
+        let mut key = Vec::with_capacity(hash.len() + SECTION_KEY_PREFIX.len());
+
+        key.extend_from_slice(SECTION_KEY_PREFIX);
+        key.extend_from_slice(hash.as_slice());
+
+        key
+    }
+}
+
+fn interested(interests: &Option<HashSet<SectionKind>>, kind: SectionKind) -> bool {
+    match interests {
+        Some(interests) => interests.contains(&kind),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use svm_codec::api::builder::TemplateBuilder;
+    use svm_codec::template::refs::section_hash;
+    use svm_types::{CodeSection, CtorsSection, DataSection, HeaderSection};
+
+    use crate::env::default::DefaultTemplateSerializer;
+    use crate::env::DefaultRocksTemplateStore;
+
+    use super::*;
+
+    fn make_template(code: Vec<u8>, code_version: u32) -> Template {
+        TemplateBuilder::default()
+            .with_code(CodeSection::new_fixed(code, 0))
+            .with_data(DataSection::default())
+            .with_ctors(CtorsSection::new(vec!["init".to_string()]))
+            .with_header(HeaderSection::new(
+                code_version,
+                "My Template".to_string(),
+                "".to_string(),
+            ))
+            .build()
+    }
+
+    fn open_store(name: &str) -> DefaultRocksTemplateStore {
+        let path = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&path);
+
+        let _ = std::fs::remove_file(path.with_extension("journal"));
+
+        DefaultRocksTemplateStore::new(&path, WriteMode::Synchronous)
+    }
+
+    fn section_keys(store: &DefaultRocksTemplateStore) -> usize {
+        store
+            .db
+            .read_persisted(|db| db.keys_with_prefix(SECTION_KEY_PREFIX).len())
+            .unwrap()
+    }
+
+    #[test]
+    fn rocksdb_template_store_shares_sections() {
+        let mut store = open_store("rocksdb-template-store-sections");
+        let addr = TemplateAddr::repeat(0x10);
+
+        let template = make_template(vec![0x00, 0x61, 0x73, 0x6D], 0);
+        store.store(&template, &addr, &[0x01; 32]);
+        assert_eq!(section_keys(&store), 4);
+
+        // The upgrade changes only the `Code` and `Header` sections
+        let upgraded = make_template(vec![0x00, 0x61, 0x73, 0x6D, 0x01], 1);
+        assert_eq!(store.upgrade(&upgraded, &addr, &[0x02; 32]), 1);
+        assert_eq!(section_keys(&store), 6);
+
+        assert_eq!(store.version(&addr), Some(1));
+        assert_eq!(store.load(&addr, None), Some(upgraded.clone()));
+        assert_eq!(store.load_version(&addr, 0, None), Some(template.clone()));
+        assert_eq!(store.template_hash(&addr, 0), Some([0x01; 32]));
+        assert_eq!(store.template_hash(&addr, 1), Some([0x02; 32]));
+
+        let interests = maplit::hashset! { SectionKind::Code };
+        let loaded = store.load(&addr, Some(interests.clone())).unwrap();
+        assert_eq!(loaded.sections().len(), 1);
+        assert!(store.load_byte_size(&addr, Some(&interests)).unwrap() > 0);
+
+        for section in template.sections().iter() {
+            let loaded = store.load_section(&section_hash(section));
+
+            assert_eq!(loaded.as_ref(), Some(section));
+        }
+
+        assert_eq!(store.load_section(&[0xFF; 32]), None);
+    }
+
+    #[test]
+    fn rocksdb_template_store_loads_legacy_templates() {
+        let mut store = open_store("rocksdb-template-store-legacy");
+        let addr = TemplateAddr::repeat(0x20);
+        let hash = [0x03; 32];
+
+        // A `Template` stored in whole (prior to the `Section`s content-addressing)
+        let template = make_template(vec![0x00, 0x61, 0x73, 0x6D], 0);
+        let bytes = DefaultTemplateSerializer::serialize(&template);
+
+        let template_key = store.template_key(addr.inner());
+        let version_key = store.template_version_key(addr.inner(), 0);
+        let hash_key = store.template_hash_key(&hash);
+
+        store.db.set(&[
+            (&template_key[..], &hash[..]),
+            (&version_key[..], &hash[..]),
+            (&hash_key[..], &bytes[..]),
+        ]);
+
+        assert_eq!(store.version(&addr), Some(0));
+        assert_eq!(store.load(&addr, None), Some(template));
+        assert!(store.load_byte_size(&addr, None).unwrap() > 0);
+    }
 }
@@ -0,0 +1,233 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use svm_codec::template::refs::SectionHash;
+use svm_storage::kv::FlushError;
+use svm_types::{Layer, Section, SectionKind, Template, TemplateAddr};
+
+use crate::env::{TemplateHash, TemplateStore};
+
+/// The statistics of a [`CachedTemplateStore`] (see [`TemplateStore::cache_stats`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TemplateCacheStats {
+    /// The number of loads answered from the cache.
+    pub hits: u64,
+
+    /// The number of loads forwarded to the underlying store.
+    pub misses: u64,
+
+    /// The number of cached `Template`s evicted for making room for newer ones.
+    pub evictions: u64,
+
+    /// The number of currently cached `Template`s.
+    pub entries: usize,
+}
+
+/// Identifies a loaded `Template`.
+///
+/// A `version` of `None` stands for the latest version (see [`TemplateStore::load`]).
+/// The `interests` are kept sorted, so that equal sets of interests result in equal keys.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    addr: TemplateAddr,
+    version: Option<u16>,
+    interests: Option<Vec<SectionKind>>,
+}
+
+impl CacheKey {
+    fn new(
+        addr: &TemplateAddr,
+        version: Option<u16>,
+        interests: &Option<HashSet<SectionKind>>,
+    ) -> Self {
+        let interests = interests.as_ref().map(|interests| {
+            let mut kinds: Vec<SectionKind> = interests.iter().copied().collect();
+            kinds.sort_unstable();
+            kinds
+        });
+
+        Self {
+            addr: addr.clone(),
+            version,
+            interests,
+        }
+    }
+}
+
+struct Cache {
+    entries: HashMap<CacheKey, Template>,
+    order: VecDeque<CacheKey>,
+    stats: TemplateCacheStats,
+}
+
+impl Cache {
+    fn get(&mut self, key: &CacheKey) -> Option<Template> {
+        let template = self.entries.get(key).cloned();
+
+        if template.is_some() {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+        }
+
+        template
+    }
+
+    fn insert(&mut self, key: CacheKey, template: Template, max_entries: usize) {
+        if max_entries == 0 {
+            return;
+        }
+
+        if self.entries.insert(key.clone(), template).is_some() {
+            self.order.retain(|k| k != &key);
+        }
+        self.order.push_back(key);
+
+        while self.order.len() > max_entries {
+            let oldest = self.order.pop_front().unwrap();
+
+            self.entries.remove(&oldest);
+            self.stats.evictions += 1;
+        }
+    }
+}
+
+/// A [`TemplateStore`] caching the `Template`s loaded from an underlying [`TemplateStore`].
+///
+/// At most `max_entries` `Template`s are cached (the oldest is evicted first).
+/// Storing or upgrading a `Template` drops its cached versions.
+pub struct CachedTemplateStore<S> {
+    inner: S,
+    max_entries: usize,
+    cache: RefCell<Cache>,
+}
+
+impl<S> CachedTemplateStore<S>
+where
+    S: TemplateStore,
+{
+    /// Wraps `inner` with a cache holding up to `max_entries` `Template`s.
+    pub fn new(inner: S, max_entries: usize) -> Self {
+        let cache = Cache {
+            entries: HashMap::with_capacity(max_entries),
+            order: VecDeque::with_capacity(max_entries),
+            stats: TemplateCacheStats::default(),
+        };
+
+        Self {
+            inner,
+            max_entries,
+            cache: RefCell::new(cache),
+        }
+    }
+
+    /// Borrows the underlying [`TemplateStore`].
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Drops all the cached `Template`s.
+    pub fn clear(&mut self) {
+        let cache = self.cache.get_mut();
+
+        cache.entries.clear();
+        cache.order.clear();
+    }
+
+    fn load_cached<F>(&self, key: CacheKey, load: F) -> Option<Template>
+    where
+        F: FnOnce(&S) -> Option<Template>,
+    {
+        if let Some(template) = self.cache.borrow_mut().get(&key) {
+            return Some(template);
+        }
+
+        let template = load(&self.inner)?;
+
+        self.cache
+            .borrow_mut()
+            .insert(key, template.clone(), self.max_entries);
+
+        Some(template)
+    }
+
+    fn invalidate(&mut self, addr: &TemplateAddr) {
+        let cache = self.cache.get_mut();
+
+        cache.entries.retain(|key, _| &key.addr != addr);
+        cache.order.retain(|key| &key.addr != addr);
+    }
+}
+
+impl<S> TemplateStore for CachedTemplateStore<S>
+where
+    S: TemplateStore,
+{
+    fn store(&mut self, template: &Template, addr: &TemplateAddr, hash: &TemplateHash) {
+        self.invalidate(addr);
+        self.inner.store(template, addr, hash);
+    }
+
+    fn upgrade(&mut self, template: &Template, addr: &TemplateAddr, hash: &TemplateHash) -> u16 {
+        self.invalidate(addr);
+        self.inner.upgrade(template, addr, hash)
+    }
+
+    fn version(&self, addr: &TemplateAddr) -> Option<u16> {
+        self.inner.version(addr)
+    }
+
+    fn template_hash(&self, addr: &TemplateAddr, version: u16) -> Option<TemplateHash> {
+        self.inner.template_hash(addr, version)
+    }
+
+    fn load(
+        &self,
+        addr: &TemplateAddr,
+        interests: Option<HashSet<SectionKind>>,
+    ) -> Option<Template> {
+        let key = CacheKey::new(addr, None, &interests);
+
+        self.load_cached(key, |inner| inner.load(addr, interests))
+    }
+
+    fn load_version(
+        &self,
+        addr: &TemplateAddr,
+        version: u16,
+        interests: Option<HashSet<SectionKind>>,
+    ) -> Option<Template> {
+        let key = CacheKey::new(addr, Some(version), &interests);
+
+        self.load_cached(key, |inner| inner.load_version(addr, version, interests))
+    }
+
+    fn load_byte_size(
+        &self,
+        addr: &TemplateAddr,
+        interests: Option<&HashSet<SectionKind>>,
+    ) -> Option<u64> {
+        self.inner.load_byte_size(addr, interests)
+    }
+
+    fn load_section(&self, hash: &SectionHash) -> Option<Section> {
+        self.inner.load_section(hash)
+    }
+
+    fn cache_stats(&self) -> Option<TemplateCacheStats> {
+        let cache = self.cache.borrow();
+
+        Some(TemplateCacheStats {
+            entries: cache.entries.len(),
+            ..cache.stats
+        })
+    }
+
+    fn set_layer(&mut self, layer: Layer) {
+        self.inner.set_layer(layer)
+    }
+
+    fn flush_until(&self, layer: Layer) -> Result<(), FlushError> {
+        self.inner.flush_until(layer)
+    }
+}
@@ -1,3 +1,7 @@
+use svm_types::{Address, Template, TemplateAddr};
+
+use crate::env::ExtSpawn;
+
 /// Computes the `Address` of an `Account / Template`.
 ///
 /// The algorithm must be deterministic.
@@ -6,3 +10,16 @@ pub trait ComputeAddress<T> {
 
     fn compute(item: &T) -> Self::Address;
 }
+
+/// A scheme for deriving the `Address`es of new `Template`s and `Account`s.
+///
+/// Schemes are registered under an id (see [`AddressSchemeRegistry`](crate::env::AddressSchemeRegistry)),
+/// which is recorded along with each `Template` and `Account` whose `Address` the scheme has computed.
+/// Once a scheme has been in use, its derivation must never change.
+pub trait AddressScheme {
+    /// Computes the `Address` of a new `Template`.
+    fn template_addr(&self, template: &Template) -> TemplateAddr;
+
+    /// Computes the `Address` of a new `Account`.
+    fn account_addr(&self, spawn: &ExtSpawn) -> Address;
+}
@@ -5,7 +5,7 @@ mod hasher;
 mod serialize;
 mod store;
 
-pub use compute_address::ComputeAddress;
+pub use compute_address::{AddressScheme, ComputeAddress};
 pub use hasher::TemplateHasher;
 pub use serialize::{AccountDeserializer, AccountSerializer};
 pub use serialize::{TemplateDeserializer, TemplateSerializer};
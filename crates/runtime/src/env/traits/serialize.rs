@@ -15,6 +15,11 @@ pub trait TemplateSerializer {
 pub trait TemplateDeserializer {
     #[allow(missing_docs)]
     fn deserialize(bytes: &[u8], interests: Option<HashSet<SectionKind>>) -> Option<Template>;
+
+    /// Returns the byte size of the `Section`s [`TemplateDeserializer::deserialize`] would deserialize
+    /// given the same `interests` (without deserializing them).
+    fn deserialize_byte_size(bytes: &[u8], interests: Option<&HashSet<SectionKind>>)
+        -> Option<u64>;
 }
 
 /// Serializing an `Account` into its binary representation.
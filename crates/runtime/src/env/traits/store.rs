@@ -1,8 +1,10 @@
 use std::collections::HashSet;
 
-use svm_types::{Address, SectionKind, Template, TemplateAddr};
+use svm_codec::template::refs::SectionHash;
+use svm_storage::kv::FlushError;
+use svm_types::{Address, Layer, Section, SectionKind, Template, TemplateAddr};
 
-use crate::env::{ExtAccount, TemplateHash};
+use crate::env::{ExtAccount, TemplateCacheStats, TemplateHash};
 
 /// A persistent store for [`Template`](svm_types::Template)s.
 pub trait TemplateStore {
@@ -15,6 +17,28 @@ pub trait TemplateStore {
     /// `hash`     - [`Template`]'s [`TemplateHash`].
     fn store(&mut self, template: &Template, addr: &TemplateAddr, hash: &TemplateHash);
 
+    /// Stores a [`Template`] as the next version of the [`Template`] located at `addr`.
+    ///
+    /// The former versions are kept (see [`TemplateStore::load_version`]), while [`TemplateStore::load`]
+    /// loads the latest version from now on.
+    ///
+    /// Returns the version of the stored [`Template`] (the originally stored [`Template`] is of version `0`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no [`Template`] located at `addr`.
+    fn upgrade(&mut self, template: &Template, addr: &TemplateAddr, hash: &TemplateHash) -> u16;
+
+    /// Returns the latest version of the [`Template`] located at `addr`.
+    /// Returns `None` if [`Template`] doesn't exist.
+    #[must_use]
+    fn version(&self, addr: &TemplateAddr) -> Option<u16>;
+
+    /// Returns the [`TemplateHash`] the given `version` of the [`Template`] located at `addr` has been stored with.
+    /// Returns `None` if [`Template`] (or the requested version of it) doesn't exist.
+    #[must_use]
+    fn template_hash(&self, addr: &TemplateAddr, version: u16) -> Option<TemplateHash>;
+
     /// Given a [`Template`]'s `Address`, fetches its raw data and deserializes it into `Template`.
     /// Returns `None` if [`Template`] doesn't exist.
     #[must_use]
@@ -23,6 +47,58 @@ pub trait TemplateStore {
         addr: &TemplateAddr,
         interests: Option<HashSet<SectionKind>>,
     ) -> Option<Template>;
+
+    /// Same as [`TemplateStore::load`], but fetches the given `version` of the [`Template`].
+    /// Returns `None` if [`Template`] (or the requested version of it) doesn't exist.
+    #[must_use]
+    fn load_version(
+        &self,
+        addr: &TemplateAddr,
+        version: u16,
+        interests: Option<HashSet<SectionKind>>,
+    ) -> Option<Template>;
+
+    /// Given a [`Template`]'s `Address`, returns the byte size of the `Section`s
+    /// [`TemplateStore::load`] would load given the same `interests`.
+    ///
+    /// The byte size is derived from the `Section`s previews, without decoding the `Section`s.
+    /// Returns `None` if [`Template`] doesn't exist.
+    #[must_use]
+    fn load_byte_size(
+        &self,
+        addr: &TemplateAddr,
+        interests: Option<&HashSet<SectionKind>>,
+    ) -> Option<u64>;
+
+    /// Given a [`SectionHash`], fetches the stored `Section` having that hash.
+    ///
+    /// Used for resolving `Template`s encoded with `Section` references
+    /// (see [`svm_codec::template::refs`]).
+    /// Returns `None` if no such `Section` exists.
+    #[must_use]
+    fn load_section(&self, hash: &SectionHash) -> Option<Section>;
+
+    /// Returns the statistics of the store's cache of loaded [`Template`]s
+    /// (see [`CachedTemplateStore`](crate::env::CachedTemplateStore)).
+    /// Returns `None` if the store doesn't cache.
+    #[must_use]
+    fn cache_stats(&self) -> Option<TemplateCacheStats> {
+        None
+    }
+
+    /// Tags the next writes with `layer` (see [`TemplateStore::flush_until`]).
+    ///
+    /// A `layer` preceding the current one is ignored (i.e these writes are tagged with the current one).
+    /// Does nothing for a store persisting its writes synchronously.
+    fn set_layer(&mut self, _layer: Layer) {}
+
+    /// Blocks until the writes of `layer` (and of the preceding `Layer`s) have been persisted
+    /// (see [`WriteBehindKV::flush_until`](svm_storage::kv::WriteBehindKV::flush_until)).
+    ///
+    /// Returns immediately for a store persisting its writes synchronously.
+    fn flush_until(&self, _layer: Layer) -> Result<(), FlushError> {
+        Ok(())
+    }
 }
 
 /// A persistent store for `Account`(s)
@@ -42,4 +118,22 @@ pub trait AccountStore {
     /// Returns `None` if there is no associated [`TemplateAddr`].
     #[must_use]
     fn resolve_template_addr(&self, addr: &Address) -> Option<TemplateAddr>;
+
+    /// Returns the `Address`es of all the stored `Account`s, in ascending order.
+    #[must_use]
+    fn addrs(&self) -> Vec<Address>;
+
+    /// Returns up to `limit` `Address`es of the stored `Account`s following `after`, in ascending order.
+    ///
+    /// A `None` starts from the first `Account`. The `Address`es preceding `after` aren't visited.
+    #[must_use]
+    fn addrs_after(&self, after: Option<&Address>, limit: usize) -> Vec<Address>;
+
+    /// Same as [`TemplateStore::set_layer`].
+    fn set_layer(&mut self, _layer: Layer) {}
+
+    /// Same as [`TemplateStore::flush_until`].
+    fn flush_until(&self, _layer: Layer) -> Result<(), FlushError> {
+        Ok(())
+    }
 }
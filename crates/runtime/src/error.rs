@@ -3,6 +3,7 @@ use thiserror::Error;
 use svm_codec::ParseError;
 use svm_gas::FixedGasError;
 use svm_program::ProgramError;
+use svm_types::State;
 
 /// The error type that can arise when validating SVM-dialect WASM files.
 #[derive(Debug, PartialEq, Clone, Error)]
@@ -20,4 +21,42 @@ pub enum ValidateError {
     /// run in fixed-gas mode.
     #[error("{0}")]
     FixedGas(#[from] FixedGasError),
+
+    /// A default value (see [`DefaultsSection`](svm_types::DefaultsSection)) doesn't match
+    /// the storage variable it's given for.
+    #[error("Invalid default value for storage variable #{0}")]
+    InvalidDefault(u32),
+
+    /// The storage variables don't fit within the storage budget the `Template` has declared
+    /// (see [`DataSection::max_storage_bytes`](svm_types::DataSection::max_storage_bytes)).
+    #[error("The storage takes {size} bytes, exceeding its budget of {budget} bytes")]
+    StorageBudget {
+        /// The total byte-size of the storage variables.
+        size: u64,
+
+        /// The storage budget (in bytes).
+        budget: u32,
+    },
+
+    /// The code of a precompile `Template` isn't a precompile id
+    /// (see [`CodeKind::Precompile`](svm_types::CodeKind::Precompile)).
+    #[error("The code of a precompile `Template` must be a 4-byte precompile id")]
+    InvalidPrecompileId,
+
+    /// No [`Precompile`](crate::Precompile) is registered under the id of a precompile `Template`.
+    #[error("No precompile is registered under id #{0}")]
+    UnknownPrecompile(u32),
+}
+
+/// The error type that can arise when executing a transaction against a historical `State`
+/// (see [`Runtime::call_at`](crate::Runtime::call_at)).
+#[derive(Debug, PartialEq, Clone, Error)]
+pub enum CallAtError {
+    /// The given `State` isn't the global `State` of any `Layer`.
+    #[error("Unknown global `State` {0:?}")]
+    UnknownState(State),
+
+    /// The message isn't a valid binary `Call Account` message.
+    #[error("{0}")]
+    Parse(#[from] ParseError),
 }
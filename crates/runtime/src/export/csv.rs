@@ -0,0 +1,30 @@
+use std::io::{self, Write};
+
+use super::{to_hex, ExportRow, COLUMNS};
+
+/// Writes a header line followed by a line per row.
+///
+/// None of the exported values may hold a comma or a quote, so no field is quoted.
+pub(super) fn write(rows: &[ExportRow], w: &mut dyn Write) -> io::Result<()> {
+    writeln!(w, "{}", COLUMNS.join(","))?;
+
+    for row in rows {
+        let gas_used = row.gas_used.map(|gas| gas.to_string());
+        let target = row.target.as_ref().map(|addr| to_hex(addr.as_slice()));
+        let template = row.template.as_ref().map(|addr| to_hex(addr.as_slice()));
+
+        writeln!(
+            w,
+            "{},{},{},{},{},{},{}",
+            row.tx_id,
+            row.layer.0,
+            row.kind,
+            row.success,
+            gas_used.unwrap_or_default(),
+            target.unwrap_or_default(),
+            template.unwrap_or_default()
+        )?;
+    }
+
+    Ok(())
+}
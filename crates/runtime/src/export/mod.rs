@@ -0,0 +1,232 @@
+//! Exporting stored receipts for analytics (see [`ReceiptStore::export`](crate::replay::ReceiptStore::export)).
+//!
+//! Each [`TxRecord`] is flattened into a single [`ExportRow`], having the [`COLUMNS`]:
+//!
+//! +---------+---------+--------+-----------+------------+----------+------------+
+//! |  tx_id  |  layer  |  type  |  success  |  gas_used  |  target  |  template  |
+//! +---------+---------+--------+-----------+------------+----------+------------+
+//!
+//! The `Address`es (and the [`TransactionId`]) are written as `0x`-prefixed lower-case hex.
+//! Fields a receipt doesn't determine (e.g the `target` of a `Deploy Template`) are left empty.
+
+use std::fmt;
+use std::io::{self, Cursor, Write};
+use std::str::FromStr;
+
+use svm_types::{Address, Layer, Receipt, TemplateAddr, TransactionId};
+
+use crate::replay::TxRecord;
+
+mod csv;
+mod parquet;
+
+/// The names of the exported columns (in order).
+pub const COLUMNS: [&str; 7] = [
+    "tx_id", "layer", "type", "success", "gas_used", "target", "template",
+];
+
+/// The format of exported receipts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Comma-separated values (with a header line).
+    Csv,
+
+    /// Apache Parquet (a single uncompressed row group).
+    Parquet,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "parquet" => Ok(Self::Parquet),
+            _ => Err(format!("Unknown export format `{}`", s)),
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Csv => f.write_str("csv"),
+            Self::Parquet => f.write_str("parquet"),
+        }
+    }
+}
+
+/// A [`TxRecord`] flattened into the exported columns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportRow {
+    /// The [`TransactionId`] of the transaction.
+    pub tx_id: TransactionId,
+
+    /// The `Layer` the transaction has been executed at.
+    pub layer: Layer,
+
+    /// The kind of the transaction (`deploy`, `spawn`, `call`, `upgrade` or `batch`).
+    pub kind: &'static str,
+
+    /// Whether the transaction has succeeded.
+    pub success: bool,
+
+    /// The gas used by the transaction (`None` when it's unknown).
+    pub gas_used: Option<u64>,
+
+    /// The `Account` targeted by the transaction (the spawned one for a `Spawn Account`).
+    pub target: Option<Address>,
+
+    /// The `Template` of the transaction (the deployed or upgraded one, or the spawned `Account`'s one).
+    pub template: Option<TemplateAddr>,
+}
+
+impl ExportRow {
+    /// Flattens `record`.
+    ///
+    /// The `target` of a `Call Account` and the `Template` of a `Spawn Account` are decoded out of
+    /// the recorded message (and left empty when it can't be decoded).
+    pub fn from_record(record: &TxRecord) -> Self {
+        let receipt = &record.receipt;
+        let message = &record.message[..];
+
+        let (kind, target, template) = match receipt {
+            Receipt::Deploy(receipt) => ("deploy", None, receipt.addr.clone()),
+            Receipt::Upgrade(receipt) => ("upgrade", None, receipt.addr.clone()),
+            Receipt::Batch(..) => ("batch", None, None),
+            Receipt::Spawn(receipt) => {
+                let target = receipt.account_addr.clone();
+                let template = svm_codec::spawn::decode(&mut Cursor::new(message))
+                    .ok()
+                    .map(|spawn| spawn.template_addr().clone());
+
+                ("spawn", target, template)
+            }
+            Receipt::Call(..) => {
+                let target = svm_codec::call::decode_call(&mut Cursor::new(message))
+                    .ok()
+                    .map(|tx| tx.target().clone());
+
+                ("call", target, None)
+            }
+        };
+
+        let gas_used = receipt.gas_used();
+
+        Self {
+            tx_id: record.tx_id().clone(),
+            layer: record.context.layer(),
+            kind,
+            success: receipt.success(),
+            gas_used: gas_used.is_some().then(|| gas_used.unwrap()),
+            target,
+            template,
+        }
+    }
+}
+
+/// Writes `records` in `format` into `writer`, and returns the number of exported rows.
+pub fn export<'a, I>(records: I, format: ExportFormat, writer: &mut dyn Write) -> io::Result<usize>
+where
+    I: IntoIterator<Item = &'a TxRecord>,
+{
+    let rows: Vec<ExportRow> = records.into_iter().map(ExportRow::from_record).collect();
+
+    match format {
+        ExportFormat::Csv => csv::write(&rows, writer)?,
+        ExportFormat::Parquet => parquet::write(&rows, writer)?,
+    }
+
+    Ok(rows.len())
+}
+
+/// Formats `bytes` as `0x`-prefixed lower-case hex.
+fn to_hex(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(2 + bytes.len() * 2);
+    s.push_str("0x");
+
+    for byte in bytes {
+        s.push_str(&format!("{:02x}", byte));
+    }
+
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_types::{CallReceipt, Context, DeployReceipt, Envelope, Gas, State};
+
+    fn records() -> Vec<TxRecord> {
+        let deploy = TxRecord {
+            envelope: Envelope::default(),
+            context: Context::new(TransactionId::repeat(1), Layer(10), State::zeros()),
+            message: Vec::new(),
+            receipt: Receipt::Deploy(DeployReceipt::new(
+                TemplateAddr::repeat(0xAB),
+                Gas::with(100),
+            )),
+        };
+
+        let call = TxRecord {
+            envelope: Envelope::default(),
+            context: Context::new(TransactionId::repeat(2), Layer(11), State::zeros()),
+            message: Vec::new(),
+            receipt: Receipt::Call(CallReceipt::from_err(
+                svm_types::RuntimeError::OOG,
+                Vec::new(),
+            )),
+        };
+
+        vec![deploy, call]
+    }
+
+    #[test]
+    fn export_csv() {
+        let records = records();
+        let mut out = Vec::new();
+
+        let count = export(&records, ExportFormat::Csv, &mut out).unwrap();
+        assert_eq!(count, 2);
+
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "tx_id,layer,type,success,gas_used,target,template"
+        );
+        assert_eq!(
+            lines[1],
+            format!(
+                "{},10,deploy,true,100,,{}",
+                TransactionId::repeat(1),
+                to_hex(TemplateAddr::repeat(0xAB).as_slice())
+            )
+        );
+        assert_eq!(
+            lines[2],
+            format!("{},11,call,false,,,", TransactionId::repeat(2))
+        );
+    }
+
+    #[test]
+    fn export_parquet_framing() {
+        let records = records();
+        let mut out = Vec::new();
+
+        let count = export(&records, ExportFormat::Parquet, &mut out).unwrap();
+        assert_eq!(count, 2);
+
+        // The file starts and ends with the magic, preceded by the length of the footer
+        assert_eq!(&out[..4], b"PAR1");
+        assert_eq!(&out[out.len() - 4..], b"PAR1");
+
+        let footer_len = {
+            let bytes = &out[out.len() - 8..out.len() - 4];
+            u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize
+        };
+        assert!(footer_len > 0 && footer_len < out.len() - 12);
+    }
+}
@@ -0,0 +1,417 @@
+//! A minimal Apache Parquet writer.
+//!
+//! The rows are written as a single row group, holding a single uncompressed `PLAIN` encoded
+//! data page per column. The nullable columns are `OPTIONAL`, having their definition levels
+//! `RLE` encoded. The metadata is serialized using the Thrift Compact Protocol.
+//!
+//! See [the Parquet format](https://github.com/apache/parquet-format).
+
+use std::io::{self, Write};
+
+use super::{to_hex, ExportRow};
+
+const MAGIC: &[u8] = b"PAR1";
+
+/// The application writing the files (recorded in their metadata).
+const CREATED_BY: &str = "svm-runtime";
+
+// Thrift Compact Protocol types
+const T_I32: u8 = 5;
+const T_I64: u8 = 6;
+const T_BINARY: u8 = 8;
+const T_LIST: u8 = 9;
+const T_STRUCT: u8 = 12;
+
+// Parquet physical types
+const TYPE_BOOLEAN: i32 = 0;
+const TYPE_INT64: i32 = 2;
+const TYPE_BYTE_ARRAY: i32 = 6;
+
+// Parquet repetition types
+const REQUIRED: i32 = 0;
+const OPTIONAL: i32 = 1;
+
+// Parquet converted types
+const CONVERTED_UTF8: i32 = 0;
+
+// Parquet encodings
+const ENCODING_PLAIN: i32 = 0;
+const ENCODING_RLE: i32 = 3;
+
+const CODEC_UNCOMPRESSED: i32 = 0;
+const PAGE_DATA: i32 = 0;
+
+/// A column, along with its encoded values.
+struct Column {
+    name: &'static str,
+    physical_type: i32,
+    optional: bool,
+    utf8: bool,
+
+    /// Whether each value is defined (i.e non-null).
+    defined: Vec<bool>,
+
+    /// The `PLAIN` encoded defined values.
+    values: Vec<u8>,
+}
+
+impl Column {
+    fn text<I>(name: &'static str, optional: bool, values: I) -> Self
+    where
+        I: Iterator<Item = Option<String>>,
+    {
+        let mut column = Self::new(name, TYPE_BYTE_ARRAY, optional, true);
+
+        for value in values {
+            column.defined.push(value.is_some());
+
+            if let Some(value) = value {
+                column
+                    .values
+                    .extend_from_slice(&(value.len() as u32).to_le_bytes());
+                column.values.extend_from_slice(value.as_bytes());
+            }
+        }
+
+        column
+    }
+
+    fn int64<I>(name: &'static str, optional: bool, values: I) -> Self
+    where
+        I: Iterator<Item = Option<u64>>,
+    {
+        let mut column = Self::new(name, TYPE_INT64, optional, false);
+
+        for value in values {
+            column.defined.push(value.is_some());
+
+            if let Some(value) = value {
+                column.values.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+
+        column
+    }
+
+    fn boolean<I>(name: &'static str, values: I) -> Self
+    where
+        I: Iterator<Item = bool>,
+    {
+        let mut column = Self::new(name, TYPE_BOOLEAN, false, false);
+
+        // Booleans are bit-packed (the first value is the least significant bit)
+        for (i, value) in values.enumerate() {
+            column.defined.push(true);
+
+            if i % 8 == 0 {
+                column.values.push(0);
+            }
+            if value {
+                *column.values.last_mut().unwrap() |= 1 << (i % 8);
+            }
+        }
+
+        column
+    }
+
+    fn new(name: &'static str, physical_type: i32, optional: bool, utf8: bool) -> Self {
+        Self {
+            name,
+            physical_type,
+            optional,
+            utf8,
+            defined: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// The page data: the definition levels (for an `OPTIONAL` column) followed by the values.
+    fn page_data(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        if self.optional {
+            let levels = encode_levels(&self.defined);
+
+            data.extend_from_slice(&(levels.len() as u32).to_le_bytes());
+            data.extend_from_slice(&levels);
+        }
+
+        data.extend_from_slice(&self.values);
+        data
+    }
+}
+
+/// The location of a written column chunk.
+struct ChunkMeta {
+    offset: u64,
+    size: u64,
+}
+
+/// Writes `rows` as a Parquet file.
+pub(super) fn write(rows: &[ExportRow], w: &mut dyn Write) -> io::Result<()> {
+    let columns = columns(rows);
+
+    w.write_all(MAGIC)?;
+    let mut offset = MAGIC.len() as u64;
+
+    let mut chunks = Vec::with_capacity(columns.len());
+
+    for column in columns.iter() {
+        let data = column.page_data();
+        let header = page_header(column.defined.len(), data.len());
+
+        w.write_all(&header)?;
+        w.write_all(&data)?;
+
+        let size = (header.len() + data.len()) as u64;
+        chunks.push(ChunkMeta { offset, size });
+
+        offset += size;
+    }
+
+    let metadata = file_metadata(&columns, &chunks, rows.len());
+
+    w.write_all(&metadata)?;
+    w.write_all(&(metadata.len() as u32).to_le_bytes())?;
+    w.write_all(MAGIC)?;
+
+    Ok(())
+}
+
+fn columns(rows: &[ExportRow]) -> Vec<Column> {
+    vec![
+        Column::text(
+            "tx_id",
+            false,
+            rows.iter().map(|r| Some(r.tx_id.to_string())),
+        ),
+        Column::int64("layer", false, rows.iter().map(|r| Some(r.layer.0))),
+        Column::text("type", false, rows.iter().map(|r| Some(r.kind.to_string()))),
+        Column::boolean("success", rows.iter().map(|r| r.success)),
+        Column::int64("gas_used", true, rows.iter().map(|r| r.gas_used)),
+        Column::text(
+            "target",
+            true,
+            rows.iter()
+                .map(|r| r.target.as_ref().map(|addr| to_hex(addr.as_slice()))),
+        ),
+        Column::text(
+            "template",
+            true,
+            rows.iter()
+                .map(|r| r.template.as_ref().map(|addr| to_hex(addr.as_slice()))),
+        ),
+    ]
+}
+
+/// Encodes the definition levels (of a single bit each) using the `RLE / Bit-Packing Hybrid` encoding.
+///
+/// Each run of equal levels is encoded as an `RLE` run: its length (shifted left by one bit),
+/// followed by the level (padded to a byte).
+fn encode_levels(defined: &[bool]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    let mut i = 0;
+
+    while i < defined.len() {
+        let level = defined[i];
+        let len = defined[i..].iter().take_while(|&&d| d == level).count();
+
+        write_varint(&mut buf, (len as u64) << 1);
+        buf.push(level as u8);
+
+        i += len;
+    }
+
+    buf
+}
+
+fn page_header(num_values: usize, page_size: usize) -> Vec<u8> {
+    let mut p = CompactWriter::new();
+
+    p.i32_field(1, PAGE_DATA);
+    p.i32_field(2, page_size as i32);
+    p.i32_field(3, page_size as i32);
+
+    p.struct_field(5);
+    p.i32_field(1, num_values as i32);
+    p.i32_field(2, ENCODING_PLAIN);
+    p.i32_field(3, ENCODING_RLE);
+    p.i32_field(4, ENCODING_RLE);
+    p.struct_end();
+
+    p.finish()
+}
+
+fn file_metadata(columns: &[Column], chunks: &[ChunkMeta], num_rows: usize) -> Vec<u8> {
+    let mut p = CompactWriter::new();
+
+    p.i32_field(1, 1);
+
+    // The schema (flattened in depth-first order, starting with the root)
+    p.list_field(2, T_STRUCT, columns.len() + 1);
+
+    p.struct_begin();
+    p.binary_field(4, b"schema");
+    p.i32_field(5, columns.len() as i32);
+    p.struct_end();
+
+    for column in columns {
+        let repetition = if column.optional { OPTIONAL } else { REQUIRED };
+
+        p.struct_begin();
+        p.i32_field(1, column.physical_type);
+        p.i32_field(3, repetition);
+        p.binary_field(4, column.name.as_bytes());
+        if column.utf8 {
+            p.i32_field(6, CONVERTED_UTF8);
+        }
+        p.struct_end();
+    }
+
+    p.i64_field(3, num_rows as i64);
+
+    // A single row group
+    p.list_field(4, T_STRUCT, 1);
+    p.struct_begin();
+
+    p.list_field(1, T_STRUCT, columns.len());
+    for (column, chunk) in columns.iter().zip(chunks.iter()) {
+        p.struct_begin();
+        p.i64_field(2, chunk.offset as i64);
+
+        p.struct_field(3);
+        p.i32_field(1, column.physical_type);
+        p.list_field(2, T_I32, 2);
+        p.write_i32(ENCODING_PLAIN);
+        p.write_i32(ENCODING_RLE);
+        p.list_field(3, T_BINARY, 1);
+        p.write_binary(column.name.as_bytes());
+        p.i32_field(4, CODEC_UNCOMPRESSED);
+        p.i64_field(5, column.defined.len() as i64);
+        p.i64_field(6, chunk.size as i64);
+        p.i64_field(7, chunk.size as i64);
+        p.i64_field(9, chunk.offset as i64);
+        p.struct_end();
+
+        p.struct_end();
+    }
+
+    let total_size: u64 = chunks.iter().map(|chunk| chunk.size).sum();
+    p.i64_field(2, total_size as i64);
+    p.i64_field(3, num_rows as i64);
+    p.struct_end();
+
+    p.binary_field(6, CREATED_BY.as_bytes());
+
+    p.finish()
+}
+
+/// Writes Thrift structs using the Compact Protocol.
+///
+/// The fields of each struct must be written in ascending order of their ids.
+struct CompactWriter {
+    buf: Vec<u8>,
+
+    /// The id of the last written field of the current struct.
+    last_field: i16,
+
+    /// The `last_field` of the enclosing structs.
+    stack: Vec<i16>,
+}
+
+impl CompactWriter {
+    fn new() -> Self {
+        Self {
+            buf: Vec::new(),
+            last_field: 0,
+            stack: Vec::new(),
+        }
+    }
+
+    fn field_header(&mut self, id: i16, ty: u8) {
+        let delta = id - self.last_field;
+
+        if delta > 0 && delta <= 15 {
+            self.buf.push(((delta as u8) << 4) | ty);
+        } else {
+            self.buf.push(ty);
+            write_varint(&mut self.buf, zigzag(id as i64));
+        }
+
+        self.last_field = id;
+    }
+
+    fn i32_field(&mut self, id: i16, value: i32) {
+        self.field_header(id, T_I32);
+        self.write_i32(value);
+    }
+
+    fn i64_field(&mut self, id: i16, value: i64) {
+        self.field_header(id, T_I64);
+        write_varint(&mut self.buf, zigzag(value));
+    }
+
+    fn binary_field(&mut self, id: i16, value: &[u8]) {
+        self.field_header(id, T_BINARY);
+        self.write_binary(value);
+    }
+
+    /// Starts a struct field (to be ended by [`Self::struct_end`]).
+    fn struct_field(&mut self, id: i16) {
+        self.field_header(id, T_STRUCT);
+        self.struct_begin();
+    }
+
+    /// Writes the header of a list field (its elements are to be written right after).
+    fn list_field(&mut self, id: i16, elem_type: u8, size: usize) {
+        self.field_header(id, T_LIST);
+
+        if size < 15 {
+            self.buf.push(((size as u8) << 4) | elem_type);
+        } else {
+            self.buf.push(0xF0 | elem_type);
+            write_varint(&mut self.buf, size as u64);
+        }
+    }
+
+    /// Starts a struct (a struct field or a struct element of a list).
+    fn struct_begin(&mut self) {
+        self.stack.push(self.last_field);
+        self.last_field = 0;
+    }
+
+    fn struct_end(&mut self) {
+        self.buf.push(0);
+        self.last_field = self.stack.pop().unwrap_or(0);
+    }
+
+    fn write_i32(&mut self, value: i32) {
+        write_varint(&mut self.buf, zigzag(value as i64));
+    }
+
+    fn write_binary(&mut self, value: &[u8]) {
+        write_varint(&mut self.buf, value.len() as u64);
+        self.buf.extend_from_slice(value);
+    }
+
+    /// Ends the top-level struct and returns its bytes.
+    fn finish(mut self) -> Vec<u8> {
+        debug_assert!(self.stack.is_empty());
+
+        self.buf.push(0);
+        self.buf
+    }
+}
+
+fn zigzag(n: i64) -> u64 {
+    ((n << 1) ^ (n >> 63)) as u64
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        buf.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+
+    buf.push(n as u8);
+}
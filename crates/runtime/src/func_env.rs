@@ -2,18 +2,115 @@
 
 use wasmer::Memory;
 
+use std::collections::HashMap;
 use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use svm_storage::account::AccountStorage;
-use svm_types::{Address, Context, Envelope, ReceiptLog, TemplateAddr};
+use svm_types::MAX_RECEIPT_METRICS;
+use svm_types::{
+    Address, CallReceipt, Context, Envelope, Gas, LogTopic, ReceiptLog, ReceiptMetric, State,
+    TemplateAddr,
+};
+
+use crate::arena::{Arena, ArenaSlice};
+use crate::trace::{Trace, VmCall};
+
+/// The maximum number of `Account`s taking part in a chain of cross-account calls (see [`CallStack`]).
+pub const MAX_CALL_DEPTH: usize = 8;
+
+/// The chain of `Account`s calling each other (see the `svm_call` vmcall).
+///
+/// The first frame is the `Account` targeted by the transaction, and the last one is the running `Account`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CallStack {
+    frames: Vec<Address>,
+}
+
+impl CallStack {
+    /// New [`CallStack`] of the running `target` called by the `callers` (outermost first).
+    pub fn new(callers: &[Address], target: &Address) -> Self {
+        let mut frames = callers.to_vec();
+        frames.push(target.clone());
+
+        Self { frames }
+    }
+
+    /// The `Account`s of the stack (outermost first).
+    pub fn frames(&self) -> &[Address] {
+        &self.frames
+    }
+
+    /// The number of `Account`s of the stack.
+    pub fn depth(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns whether `addr` is on the stack (i.e calling it again would be a reentrant call).
+    pub fn contains(&self, addr: &Address) -> bool {
+        self.frames.contains(addr)
+    }
+}
+
+/// The uncommitted storages of the `Account`s called (successfully) by the running transaction,
+/// in the order they're to be committed.
+///
+/// The storage changes of a cross-account call aren't committed by the call itself. Instead, they're staged
+/// by the caller and committed along with its own changes, once the outermost call commits.
+pub(crate) type Staged = Vec<AccountStorage>;
+
+/// A cross-account call issued by the running code (see [`vmcalls::call`](crate::vmcalls::call)).
+#[derive(Clone)]
+pub(crate) struct NestedCall<'a> {
+    pub target: Address,
+    pub func_name: String,
+    /// Borrowed from the caller's [`Arena`] (see [`Inner::take_arena`]).
+    pub calldata: &'a [u8],
+    pub gas_limit: Gas,
+    pub protected_mode: ProtectedMode,
+    pub state: State,
+    pub callers: Vec<Address>,
+    /// Whether the caller's changes are to be committed (see [`FuncEnv::set_commit`]).
+    pub commit: bool,
+    /// The storages staged by the caller (the call runs against them, see [`Staged`]).
+    pub staged: Staged,
+}
+
+/// Executes the [`NestedCall`]s of the running code on behalf of the `Runtime` running it
+/// (and tells which `Account`s exist, see [`FuncEnv::account_exists`]).
+///
+/// It holds a type-erased pointer to the `Runtime`, so it must be set only while the `Runtime`
+/// runs the code (see [`FuncEnv::set_nested_caller`]).
+#[derive(Clone, Copy)]
+pub(crate) struct NestedCaller {
+    runtime: *const (),
+    call: unsafe fn(*const (), NestedCall, &Envelope, &Context) -> (CallReceipt, Staged),
+    account_exists: unsafe fn(*const (), &Address) -> bool,
+}
+
+impl NestedCaller {
+    /// New [`NestedCaller`] calling `call` (and `account_exists`) with `runtime` as its first argument.
+    pub fn new(
+        runtime: *const (),
+        call: unsafe fn(*const (), NestedCall, &Envelope, &Context) -> (CallReceipt, Staged),
+        account_exists: unsafe fn(*const (), &Address) -> bool,
+    ) -> Self {
+        Self {
+            runtime,
+            call,
+            account_exists,
+        }
+    }
+}
+
+// The running code (and hence the `vmcalls`) runs synchronously on the thread of the `Runtime`.
+unsafe impl Send for NestedCaller {}
+unsafe impl Sync for NestedCaller {}
 
 /// [`FuncEnv`] is a container for the accessible data by running [`Wasmer instance`](wasmer::Instance).
 #[derive(wasmer::WasmerEnv, Clone)]
 pub struct FuncEnv {
     inner: Arc<RwLock<Inner>>,
     template_addr: TemplateAddr,
-    target_addr: Address,
-    envelope: Envelope,
     context: Context,
 }
 
@@ -27,13 +124,11 @@ impl FuncEnv {
         target_addr: Address,
         mode: ProtectedMode,
     ) -> Self {
-        let inner = Inner::new(storage);
+        let inner = Inner::new(storage, envelope, target_addr);
 
         let env = Self {
             inner: Arc::new(RwLock::new(inner)),
             template_addr,
-            target_addr,
-            envelope: envelope.clone(),
             context: context.clone(),
         };
         env.set_protected_mode(mode);
@@ -63,8 +158,111 @@ impl FuncEnv {
     }
 
     /// Returns the `Address` of the currently executed `Account` (a.k.a the `target`).
-    pub fn target_addr(&self) -> &Address {
-        &self.target_addr
+    pub fn target_addr(&self) -> Address {
+        self.borrow().target_addr.clone()
+    }
+
+    /// Returns the [`Envelope`] of the running transaction.
+    pub fn envelope(&self) -> Envelope {
+        self.borrow().envelope.clone()
+    }
+
+    /// Re-targets the `FuncEnv` to the `Account` of `target_addr` (of the same `Template`),
+    /// executing a new transaction of `envelope` over `storage`.
+    ///
+    /// Anything left by the previous execution is discarded (so the [`ProtectedMode`], tracing and counting
+    /// have to be set again). This way, the `FuncEnv` and the imports registered using it can be reused
+    /// for executing the same code against many `Account`s.
+    pub(crate) fn retarget(
+        &self,
+        storage: AccountStorage,
+        envelope: &Envelope,
+        target_addr: Address,
+        mode: ProtectedMode,
+    ) {
+        *self.borrow_mut() = Inner::new(storage, envelope, target_addr);
+
+        self.set_protected_mode(mode);
+    }
+
+    /// Returns the [`Context`] of the running transaction.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// Returns the [`CallStack`] of the running `Account`.
+    pub fn call_stack(&self) -> CallStack {
+        self.borrow().call_stack.clone()
+    }
+
+    /// Sets the [`CallStack`] of the running `Account`.
+    pub fn set_call_stack(&self, call_stack: CallStack) {
+        self.borrow_mut().call_stack = call_stack;
+    }
+
+    /// Sets (or unsets) the [`NestedCaller`] executing the cross-account calls of the running code.
+    ///
+    /// # Safety
+    ///
+    /// The `Runtime` pointed by `caller` must outlive the running code,
+    /// and the [`NestedCaller`] must be unset once the code completes.
+    pub(crate) unsafe fn set_nested_caller(&self, caller: Option<NestedCaller>) {
+        self.borrow_mut().nested_caller = caller;
+    }
+
+    /// Executes `call` by the [`NestedCaller`] (returns `None` when there is none).
+    ///
+    /// Along with the receipt, returns the storages staged once the call completes (see [`Staged`]),
+    /// which should replace the staged storages of the caller only if the call has succeeded.
+    ///
+    /// Must not be called while the `FuncEnv` is borrowed (the called code may borrow it too).
+    pub(crate) fn nested_call(&self, call: NestedCall) -> Option<(CallReceipt, Staged)> {
+        let (caller, envelope) = {
+            let borrow = self.borrow();
+
+            (borrow.nested_caller?, borrow.envelope.clone())
+        };
+
+        // Safety: the `NestedCaller` is set only while its `Runtime` runs the code (see `set_nested_caller`)
+        let result = unsafe { (caller.call)(caller.runtime, call, &envelope, &self.context) };
+
+        Some(result)
+    }
+
+    /// Returns whether an `Account` resides at `addr`, as told by the [`NestedCaller`]
+    /// (returns `None` when there is none).
+    pub(crate) fn account_exists(&self, addr: &Address) -> Option<bool> {
+        let caller = self.borrow().nested_caller?;
+
+        // Safety: the `NestedCaller` is set only while its `Runtime` runs the code (see `set_nested_caller`)
+        let exists = unsafe { (caller.account_exists)(caller.runtime, addr) };
+
+        Some(exists)
+    }
+
+    /// Sets whether the changes of the running code are to be committed (they are by default).
+    ///
+    /// The cross-account calls issued by the running code inherit it (see [`NestedCall::commit`]).
+    pub(crate) fn set_commit(&self, commit: bool) {
+        self.borrow_mut().commit = commit;
+    }
+
+    /// Sets the storages staged by the caller of the running code (see [`Staged`]).
+    pub(crate) fn set_staged(&self, staged: Staged) {
+        self.borrow_mut().set_staged(staged);
+    }
+
+    /// Stages the storage of the running `Account` next to the storages it has staged, returning them all.
+    ///
+    /// Called once a cross-account call has succeeded, handing its changes back to its caller.
+    pub(crate) fn stage_storage(&self) -> Staged {
+        let mut borrow = self.borrow_mut();
+
+        let storage = borrow.storage.clone();
+        let mut staged = std::mem::take(&mut borrow.staged);
+        staged.push(storage);
+
+        staged
     }
 
     /// Borrows the `FuncEnv`
@@ -94,15 +292,82 @@ impl FuncEnv {
         let borrow = self.borrow();
         borrow.mode
     }
+
+    /// Traps the running code when it's under [`ProtectedMode::ReadOnly`] (i.e it can't modify the [`AccountStorage`]).
+    ///
+    /// Should be called prior to borrowing the [`FuncEnv`], since trapping doesn't release the held borrows.
+    pub fn ensure_writable(&self) {
+        if self.protected_mode() == ProtectedMode::ReadOnly {
+            let msg = "Modifying the `Account` storage isn't allowed while querying";
+
+            wasmer::RuntimeError::raise(msg.into());
+        }
+    }
+
+    /// Starts recording the executed `vmcalls` into a [`Trace`] of at most `limit` bytes.
+    pub fn enable_trace(&self, limit: usize) {
+        let mut borrow = self.borrow_mut();
+        borrow.trace = Some(Trace::new(limit));
+    }
+
+    /// Starts counting the executed `vmcalls` (see [`Inner::vmcall_count`]).
+    pub fn enable_vmcall_counts(&self) {
+        let mut borrow = self.borrow_mut();
+        borrow.vmcall_counts = Some(HashMap::new());
+        borrow.storage_batches = Some(HashMap::new());
+    }
+
+    /// Records a `vmcall` into the [`Trace`] (when tracing is enabled),
+    /// and counts it (when counting is enabled).
+    ///
+    /// Must not be called while the `FuncEnv` is borrowed.
+    pub fn record_vmcall(&self, vmcall: VmCall, args: &[u64], result: Option<u64>) {
+        let mut borrow = self.borrow_mut();
+
+        if let Some(trace) = borrow.trace.as_mut() {
+            trace.record(vmcall, args, result);
+        }
+
+        if let Some(counts) = borrow.vmcall_counts.as_mut() {
+            *counts.entry(vmcall).or_default() += 1;
+        }
+    }
+
+    /// Accumulates the number of variables and bytes touched by a batched storage `vmcall`
+    /// (when counting is enabled, see [`Inner::storage_batch_size`]).
+    ///
+    /// Must not be called while the `FuncEnv` is borrowed.
+    pub fn record_storage_batch(&self, vmcall: VmCall, var_count: usize, byte_count: usize) {
+        let mut borrow = self.borrow_mut();
+
+        if let Some(batches) = borrow.storage_batches.as_mut() {
+            let (vars, bytes) = batches.entry(vmcall).or_default();
+
+            *vars += var_count as u64;
+            *bytes += byte_count as u64;
+        }
+    }
 }
 
 pub struct Inner {
     /// An accessor to the `Account`'s storage.
     storage: AccountStorage,
 
+    /// The `Address` of the currently executed `Account`.
+    target_addr: Address,
+
+    /// The [`Envelope`] of the running transaction.
+    envelope: Envelope,
+
     /// Collected logs during execution.
     logs: Vec<ReceiptLog>,
 
+    /// Reported metrics during execution (at most [`MAX_RECEIPT_METRICS`], see [`Inner::push_metric`]).
+    metrics: Vec<ReceiptMetric>,
+
+    /// Holds the short-lived buffers of the running transaction (i.e the cross-account calls' `calldata` and `returndata`).
+    arena: Arena,
+
     /// Pointer to `returndata`. Tuple stores `(offset, len)`.
     returndata: Option<(usize, usize)>,
 
@@ -116,6 +381,36 @@ pub struct Inner {
     calldata: Option<(usize, usize)>,
 
     mode: ProtectedMode,
+
+    /// The recorded `vmcalls` (when tracing is enabled).
+    trace: Option<Trace>,
+
+    /// The number of times each `vmcall` has been executed (when counting is enabled).
+    vmcall_counts: Option<HashMap<VmCall, u64>>,
+
+    /// The total number of variables and bytes touched by each batched storage `vmcall` (when counting is enabled).
+    storage_batches: Option<HashMap<VmCall, (u64, u64)>>,
+
+    /// The `Account`s calling the running `Account` (see [`CallStack`]).
+    call_stack: CallStack,
+
+    /// Executes the cross-account calls (unset when the code isn't run by a `Runtime`).
+    nested_caller: Option<NestedCaller>,
+
+    /// The gas limit of the running call.
+    gas_limit: Gas,
+
+    /// The gas used by the cross-account calls (on top of the metered gas of the running code).
+    nested_gas_used: u64,
+
+    /// The `returndata` of the last successful cross-account call (allocated within the `arena`).
+    nested_returndata: Option<ArenaSlice>,
+
+    /// Whether the changes of the running code are to be committed.
+    commit: bool,
+
+    /// The storages staged by the cross-account calls, committed along with `storage`.
+    staged: Staged,
 }
 
 /// Denotes the capabilities allowed to the executing Account at a given point in time.
@@ -124,22 +419,46 @@ pub enum ProtectedMode {
     /// Access to [`AccountStorage`] is not allowed.
     AccessDenied,
 
+    /// Reading the [`AccountStorage`] is allowed, but modifying it traps (see [`FuncEnv::ensure_writable`]).
+    ReadOnly,
+
     /// Full-Access to [`AccountStorage`] is allowed.
     FullAccess,
+
+    /// Full-Access to the [`AccountStorage`] of the running `Account` is allowed, but the `Account`s calling it
+    /// (see [`CallStack`]) can't be modified, i.e transferring coins to them traps.
+    ///
+    /// The `Account`s called by a fully-accessing `Account` run under it (see [`vmcalls::call`](crate::vmcalls::call)),
+    /// since their callers commit their own uncommitted changes last (which would override such modifications).
+    CalleeAccess,
 }
 
 impl Inner {
-    fn new(storage: AccountStorage) -> Self {
+    fn new(storage: AccountStorage, envelope: &Envelope, target_addr: Address) -> Self {
         let logs = Vec::new();
 
         Self {
             storage,
+            target_addr,
+            envelope: envelope.clone(),
             logs,
+            metrics: Vec::new(),
+            arena: Arena::new(),
             memory: None,
             calldata: None,
             returndata: None,
             used_memory: 0,
             mode: ProtectedMode::AccessDenied,
+            trace: None,
+            vmcall_counts: None,
+            storage_batches: None,
+            call_stack: CallStack::default(),
+            nested_caller: None,
+            gas_limit: Gas::new(),
+            nested_gas_used: 0,
+            nested_returndata: None,
+            commit: true,
+            staged: Staged::new(),
         }
     }
 
@@ -159,12 +478,111 @@ impl Inner {
         &mut self.storage
     }
 
-    pub fn logs(&self) -> &[ReceiptLog] {
-        &self.logs
+    /// Returns the current `State` of the storage.
+    ///
+    /// Unlike reading the storage, it's allowed under any [`ProtectedMode`].
+    pub fn storage_head(&self) -> State {
+        self.storage.head()
+    }
+
+    /// The number of collected logs.
+    pub fn logs_count(&self) -> usize {
+        self.logs.len()
+    }
+
+    /// Appends a log holding the memory cells `[offset, offset + 1, ..., offset + len - 1]`.
+    ///
+    /// The cells are copied directly into the log (no intermediate buffer is allocated).
+    pub fn push_log_from_memory(&mut self, offset: usize, len: usize) {
+        let data = self.read_memory(offset, len);
+
+        self.logs.push(ReceiptLog::new(data));
+    }
+
+    /// Appends an event log of `code` and `topics`, having its data held by the memory cells
+    /// `[offset, offset + 1, ..., offset + len - 1]` (see [`ReceiptLog::event`]).
+    pub fn push_event_from_memory(
+        &mut self,
+        code: u32,
+        topics: Vec<LogTopic>,
+        offset: usize,
+        len: usize,
+    ) {
+        let data = self.read_memory(offset, len);
+
+        self.logs.push(ReceiptLog::event(code, topics, data));
+    }
+
+    /// Appends a log holding `data` (e.g the log of a transfer).
+    pub fn push_log(&mut self, data: Vec<u8>) {
+        self.logs.push(ReceiptLog::new(data));
+    }
+
+    /// Appends a log emitted by a cross-account call (keeping the event code and topics of an event log).
+    pub fn push_receipt_log(&mut self, log: ReceiptLog) {
+        self.logs.push(log);
+    }
+
+    /// Reads the memory cells `[offset, offset + 1, ..., offset + len - 1]`.
+    fn read_memory(&self, offset: usize, len: usize) -> Vec<u8> {
+        let view = &self.memory().view::<u8>()[offset..offset + len];
+
+        view.iter().map(|cell| cell.get()).collect()
+    }
+
+    /// Copies the memory cells `[offset, offset + 1, ..., offset + len - 1]` directly into the `arena`
+    /// (no intermediate buffer is allocated).
+    pub fn alloc_from_memory(&mut self, offset: usize, len: usize) -> ArenaSlice {
+        let memory = self.memory.as_ref().expect("Memory should be set");
+        let view = &memory.view::<u8>()[offset..offset + len];
+
+        let (slice, buf) = self.arena.alloc(len);
+        for (byte, cell) in buf.iter_mut().zip(view.iter()) {
+            *byte = cell.get();
+        }
+
+        slice
+    }
+
+    /// Records the reported `value` of metric `id`.
+    ///
+    /// Reporting a metric again overrides its previously reported value (keeping its position).
+    /// Once [`MAX_RECEIPT_METRICS`] distinct metrics have been reported, new ones are dropped.
+    pub fn push_metric(&mut self, id: u32, value: u64) {
+        if let Some(metric) = self.metrics.iter_mut().find(|metric| metric.id == id) {
+            metric.value = value;
+        } else if self.metrics.len() < MAX_RECEIPT_METRICS {
+            self.metrics.push(ReceiptMetric::new(id, value));
+        }
+    }
+
+    /// Takes the reported metrics.
+    pub fn take_metrics(&mut self) -> Vec<ReceiptMetric> {
+        std::mem::take(&mut self.metrics)
     }
 
-    pub fn logs_mut(&mut self) -> &mut Vec<ReceiptLog> {
-        &mut self.logs
+    /// The [`Arena`] of the running transaction.
+    pub fn arena(&self) -> &Arena {
+        &self.arena
+    }
+
+    /// Takes the [`Arena`] of the running transaction, e.g for borrowing its buffers
+    /// while the `FuncEnv` is borrowed elsewhere (it must be put back using [`Inner::set_arena`]).
+    pub fn take_arena(&mut self) -> Arena {
+        std::mem::replace(&mut self.arena, Arena::with_capacity(0))
+    }
+
+    /// Puts back the [`Arena`] taken using [`Inner::take_arena`].
+    pub fn set_arena(&mut self, arena: Arena) {
+        self.arena = arena;
+    }
+
+    /// Releases the buffers allocated within the [`Arena`] (while keeping its capacity for reuse).
+    ///
+    /// The `returndata` of the last cross-account call is released as well.
+    pub fn reset_arena(&mut self) {
+        self.arena.reset();
+        self.nested_returndata = None;
     }
 
     pub fn set_calldata(&mut self, offset: usize, len: usize) {
@@ -177,6 +595,10 @@ impl Inner {
         self.calldata.unwrap()
     }
 
+    /// Stages the `returndata` of the running function.
+    ///
+    /// The `returndata` is staged (and not read) until the function completes,
+    /// so a later call overrides the previously staged `returndata`.
     pub fn set_returndata(&mut self, offset: usize, len: usize) {
         assert!(
             len > 0,
@@ -184,8 +606,6 @@ impl Inner {
             offset
         );
 
-        debug_assert!(self.returndata.is_none());
-
         self.returndata = Some((offset, len));
     }
 
@@ -193,6 +613,64 @@ impl Inner {
         self.returndata
     }
 
+    /// Sets the gas limit of the running call (bounding the gas of its cross-account calls).
+    pub fn set_gas_limit(&mut self, gas_limit: Gas) {
+        self.gas_limit = gas_limit;
+    }
+
+    /// The gas left for cross-account calls (`Gas::new()` when the running call is unlimited).
+    ///
+    /// Only the gas used by former cross-account calls is deducted (the metered gas isn't visible here),
+    /// so the total gas used is checked again once the running code completes.
+    pub fn nested_gas_left(&self) -> Gas {
+        let used = self.nested_gas_used;
+
+        self.gas_limit.map(|limit| limit.saturating_sub(used))
+    }
+
+    /// Charges `gas` used by a cross-account call.
+    pub fn charge_nested_gas(&mut self, gas: u64) {
+        self.nested_gas_used += gas;
+    }
+
+    /// The gas used by the cross-account calls.
+    pub fn nested_gas_used(&self) -> u64 {
+        self.nested_gas_used
+    }
+
+    /// Sets the `returndata` of the last cross-account call (copied into the `arena`).
+    pub fn set_nested_returndata(&mut self, returndata: &[u8]) {
+        self.nested_returndata = Some(self.arena.alloc_copy(returndata));
+    }
+
+    /// The `returndata` of the last successful cross-account call (borrowed from the `arena`).
+    pub fn nested_returndata(&self) -> &[u8] {
+        match self.nested_returndata {
+            Some(slice) => self.arena.get(slice),
+            None => &[],
+        }
+    }
+
+    /// Whether the changes of the running code are to be committed (see [`FuncEnv::set_commit`]).
+    pub fn commits(&self) -> bool {
+        self.commit
+    }
+
+    /// The storages staged by the cross-account calls (see [`Staged`]).
+    pub(crate) fn staged(&self) -> &Staged {
+        &self.staged
+    }
+
+    /// Replaces the staged storages by the ones returned by a successful cross-account call.
+    pub(crate) fn set_staged(&mut self, staged: Staged) {
+        self.staged = staged;
+    }
+
+    /// Takes the staged storages (e.g for committing them).
+    pub(crate) fn take_staged(&mut self) -> Staged {
+        std::mem::take(&mut self.staged)
+    }
+
     pub fn set_memory(&mut self, memory: Memory) {
         self.memory = Some(memory);
     }
@@ -215,10 +693,32 @@ impl Inner {
         self.memory().data_size()
     }
 
+    /// Takes the collected logs.
+    ///
+    /// Since the logs outlive the transaction (they're moved into its receipt), they aren't held by the `arena`.
     pub fn take_logs(&mut self) -> Vec<ReceiptLog> {
         std::mem::take(&mut self.logs)
     }
 
+    pub fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
+
+    /// The number of times `vmcall` has been executed (`None` when counting is disabled).
+    pub fn vmcall_count(&self, vmcall: VmCall) -> Option<u64> {
+        self.vmcall_counts
+            .as_ref()
+            .map(|counts| counts.get(&vmcall).copied().unwrap_or(0))
+    }
+
+    /// Returns the total number of variables and bytes touched by the batched storage `vmcall`,
+    /// or `None` when counting isn't enabled.
+    pub fn storage_batch_size(&self, vmcall: VmCall) -> Option<(u64, u64)> {
+        self.storage_batches
+            .as_ref()
+            .map(|batches| batches.get(&vmcall).copied().unwrap_or_default())
+    }
+
     #[inline]
     fn can_read(&self) -> bool {
         self.mode != ProtectedMode::AccessDenied
@@ -226,6 +726,9 @@ impl Inner {
 
     #[inline]
     fn can_write(&self) -> bool {
-        matches!(self.mode, ProtectedMode::FullAccess)
+        matches!(
+            self.mode,
+            ProtectedMode::FullAccess | ProtectedMode::CalleeAccess
+        )
     }
 }
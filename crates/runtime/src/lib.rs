@@ -16,14 +16,37 @@ mod runtime;
 mod storage;
 mod wasm_store;
 
+pub mod arena;
+pub mod export;
+pub mod metering;
+pub mod replay;
 pub mod testing;
+pub mod trace;
 pub mod vmcalls;
 
-pub use env::{Env, EnvTypes};
-pub use error::ValidateError;
-pub use func_env::{FuncEnv, ProtectedMode};
-pub use runtime::{Config, DefaultRuntime, Runtime};
-pub use wasm_store::new_store;
+pub use env::{
+    AddressScheme, CachedTemplateStore, Env, EnvTypes, ExtSpawn, TemplateCacheStats, TemplateStore,
+};
+pub use error::{CallAtError, ValidateError};
+pub use func_env::{CallStack, FuncEnv, ProtectedMode, MAX_CALL_DEPTH};
+pub use runtime::{
+    AccountInfo, BulkSpawnProgress, Config, DefaultRuntime, DuplicatePrecompile, HostCapabilities,
+    HostExportsFn, HostModule, HostModuleError, HostModules, HostPhase, LayerHeader, LayerOutcome,
+    MemPriceCache, Message, MetricsObserver, Precompile, PrecompileCall, PrecompileError,
+    PrecompileOutput, Precompiles, PriceCache, PriceCacheStats, PriceKey, QueryCache,
+    QueryCacheConfig, QueryKey, QueryResult, Runtime, RuntimeMetrics, StateRoots,
+    DEFAULT_PRICE_CACHE_ENTRIES, DEFAULT_STATE_ROOTS_LAYERS, RESERVED_MODULE,
+};
+pub use storage::StorageMode;
+pub use wasm_store::{
+    new_metered_store, new_store, ModuleKey, StoreLease, StorePool, StorePoolConfig, StorePoolStats,
+};
+
+#[cfg(feature = "default-memory")]
+pub use runtime::RuntimeSnapshot;
+
+#[cfg(feature = "default-rocksdb")]
+pub use env::DefaultRocksTemplateStore;
 
 #[cfg(feature = "default-rocksdb")]
-pub use runtime::create_rocksdb_runtime;
+pub use runtime::{create_rocksdb_runtime, open_rocksdb_template_store, RocksPriceCache};
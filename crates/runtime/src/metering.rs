@@ -0,0 +1,378 @@
+//! Gas metering of Wasm code.
+//!
+//! The [`Metering`] middleware instruments the code of a `Template` while it's being compiled,
+//! so that the code charges the gas of its ops as it runs. The ops are priced by the same
+//! [`PriceResolver`] used for pricing the functions of a `Template` upfront (see [`FuncPrice`]):
+//!
+//! * A `call` to an import function costs the `import_price` of the import.
+//! * A `call` to a local function costs nothing by itself (the callee charges its own ops).
+//! * The `end` closing a function costs nothing (it's left out of the function's [`FuncPrice`] too).
+//! * Any other op costs its `op_price`.
+//!
+//! Hence, for a `Template` of [`GasMode::Fixed`](svm_types::GasMode::Fixed), an execution of a function
+//! never costs more than its [`FuncPrice`] (which is the price of the function's most expensive execution path).
+//! The code of a [`GasMode::Metering`](svm_types::GasMode::Metering) `Template` can't be priced upfront
+//! (it may loop), so its executions are bounded only by their `gas_limit`.
+//!
+//! The gas of consecutive ops is charged at once, right before the next op that may branch or call.
+//! When the gas left can't cover the charged gas, the execution traps (see [`Metering::gas_used`]).
+//!
+//! The gas limit isn't part of the instrumented code: it's read from a global of the instance,
+//! set right after instantiating it (see [`Metering::set_gas_limit`]). Hence, a module compiled once
+//! can be run with any gas limit (see [`StorePool::module`](crate::StorePool::module)).
+//!
+//! [`FuncPrice`]: svm_gas::FuncPrice
+
+use std::fmt;
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+use loupe::{MemoryUsage, MemoryUsageTracker};
+use parity_wasm::elements::Instruction;
+use wasmer::wasmparser::{Operator, Type as WpType, TypeOrFuncType};
+use wasmer::{
+    ExportIndex, FunctionMiddleware, GlobalInit, GlobalType, Instance, LocalFunctionIndex,
+    MiddlewareError, MiddlewareReaderState, ModuleMiddleware, Mutability, Type,
+};
+use wasmer_types::entity::EntityRef;
+use wasmer_types::GlobalIndex;
+use wasmer_vm::ModuleInfo;
+
+use svm_gas::PriceResolver;
+use svm_program::{FuncIndex, Program};
+use svm_types::{Gas, OOGError};
+
+/// The name of the exported global holding the gas used so far.
+const GAS_USED_GLOBAL: &str = "svm_metering_gas_used";
+
+/// The name of the exported global set when the execution has run out of gas.
+const EXHAUSTED_GLOBAL: &str = "svm_metering_exhausted";
+
+/// The name of the exported global holding the gas limit of the execution.
+const GAS_LIMIT_GLOBAL: &str = "svm_metering_gas_limit";
+
+/// A Wasmer [`ModuleMiddleware`] charging the gas of the executed ops.
+///
+/// A [`Metering`] instruments a single module.
+pub struct Metering {
+    /// The prices of the ops of each local function (in code order).
+    prices: Vec<Arc<Vec<u64>>>,
+
+    globals: Mutex<Option<MeteringGlobals>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct MeteringGlobals {
+    gas_used: GlobalIndex,
+    exhausted: GlobalIndex,
+    gas_limit: GlobalIndex,
+}
+
+impl Metering {
+    /// Creates a new [`Metering`] for running the code of `program`, pricing its ops using `resolver`.
+    pub fn new(program: &Program, resolver: &dyn PriceResolver) -> Self {
+        let imports = program.imports();
+
+        let prices = program
+            .func_indexes()
+            .into_iter()
+            .map(|fn_index| {
+                let func = program.get_func(fn_index);
+                let mut prices: Vec<u64> = func
+                    .iter()
+                    .map(|op| match *op.raw() {
+                        Instruction::Call(target) => imports
+                            .try_resolve(FuncIndex(target))
+                            .map_or(0, |import| resolver.import_price(import) as u64),
+                        _ => resolver.op_price(&op) as u64,
+                    })
+                    .collect();
+
+                if let Some(closing_end) = prices.last_mut() {
+                    *closing_end = 0;
+                }
+
+                Arc::new(prices)
+            })
+            .collect();
+
+        Self {
+            prices,
+            globals: Mutex::new(None),
+        }
+    }
+
+    /// Returns the gas used so far by `instance` (instantiated from a module instrumented by a [`Metering`]).
+    ///
+    /// Fails when `instance` has run out of gas.
+    pub fn gas_used(instance: &Instance) -> Result<Gas, OOGError> {
+        if Self::exhausted(instance) {
+            return Err(OOGError);
+        }
+
+        let gas_used = Self::global(instance, GAS_USED_GLOBAL).unwrap_i64();
+
+        Ok(Gas::with(gas_used as u64))
+    }
+
+    /// Limits the gas `instance` may use to `gas_limit` (`Gas::new()` stands for no limit).
+    ///
+    /// Must be called prior to running any code of `instance`, since its gas limit is initially zero
+    /// (so a start function, which runs while instantiating, can't use any gas).
+    pub fn set_gas_limit(instance: &Instance, gas_limit: Gas) {
+        let gas_limit = gas_limit.unwrap_or(u64::MAX);
+
+        instance
+            .exports
+            .get_global(GAS_LIMIT_GLOBAL)
+            .expect("The instance hasn't been instrumented by a `Metering`")
+            .set(wasmer::Val::I64(gas_limit as i64))
+            .expect("The gas limit global should be mutable");
+    }
+
+    /// Returns whether `instance` has run out of gas.
+    pub fn exhausted(instance: &Instance) -> bool {
+        Self::global(instance, EXHAUSTED_GLOBAL).unwrap_i32() != 0
+    }
+
+    fn global(instance: &Instance, name: &str) -> wasmer::Val {
+        instance
+            .exports
+            .get_global(name)
+            .expect("The instance hasn't been instrumented by a `Metering`")
+            .get()
+    }
+}
+
+impl fmt::Debug for Metering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Metering")
+            .field("functions", &self.prices.len())
+            .finish()
+    }
+}
+
+impl MemoryUsage for Metering {
+    fn size_of_val(&self, _tracker: &mut dyn MemoryUsageTracker) -> usize {
+        let prices: usize = self
+            .prices
+            .iter()
+            .map(|prices| prices.len() * mem::size_of::<u64>())
+            .sum();
+
+        mem::size_of_val(self) + prices
+    }
+}
+
+impl ModuleMiddleware for Metering {
+    fn generate_function_middleware(
+        &self,
+        local_function_index: LocalFunctionIndex,
+    ) -> Box<dyn FunctionMiddleware> {
+        let globals = self
+            .globals
+            .lock()
+            .unwrap()
+            .expect("The module info should have been transformed first");
+
+        let prices = self
+            .prices
+            .get(local_function_index.index())
+            .cloned()
+            .unwrap_or_default();
+
+        Box::new(FunctionMetering {
+            prices,
+            next_op: 0,
+            pending: 0,
+            globals,
+        })
+    }
+
+    fn transform_module_info(&self, module_info: &mut ModuleInfo) {
+        let mut globals = self.globals.lock().unwrap();
+
+        assert!(
+            globals.is_none(),
+            "A `Metering` can't instrument more than a single module"
+        );
+
+        let gas_used = module_info
+            .globals
+            .push(GlobalType::new(Type::I64, Mutability::Var));
+        module_info
+            .global_initializers
+            .push(GlobalInit::I64Const(0));
+        module_info
+            .exports
+            .insert(GAS_USED_GLOBAL.to_string(), ExportIndex::Global(gas_used));
+
+        let exhausted = module_info
+            .globals
+            .push(GlobalType::new(Type::I32, Mutability::Var));
+        module_info
+            .global_initializers
+            .push(GlobalInit::I32Const(0));
+        module_info
+            .exports
+            .insert(EXHAUSTED_GLOBAL.to_string(), ExportIndex::Global(exhausted));
+
+        let gas_limit = module_info
+            .globals
+            .push(GlobalType::new(Type::I64, Mutability::Var));
+        module_info
+            .global_initializers
+            .push(GlobalInit::I64Const(0));
+        module_info
+            .exports
+            .insert(GAS_LIMIT_GLOBAL.to_string(), ExportIndex::Global(gas_limit));
+
+        *globals = Some(MeteringGlobals {
+            gas_used,
+            exhausted,
+            gas_limit,
+        });
+    }
+}
+
+/// A Wasmer [`ModuleMiddleware`] delegating to the [`Metering`] of the module currently being compiled.
+///
+/// Since a [`Metering`] instruments a single module, a `Store` compiling many modules
+/// (see [`StorePool`](crate::StorePool)) is given a [`MeteringSlot`] instead,
+/// which is set with the [`Metering`] of each module prior to its compilation.
+#[derive(Debug, Default)]
+pub(crate) struct MeteringSlot {
+    metering: Mutex<Option<Arc<Metering>>>,
+}
+
+impl MeteringSlot {
+    /// Sets the [`Metering`] instrumenting the next compiled module (`None` clears it).
+    pub(crate) fn set(&self, metering: Option<Arc<Metering>>) {
+        *self.metering.lock().unwrap() = metering;
+    }
+
+    fn current(&self) -> Arc<Metering> {
+        self.metering
+            .lock()
+            .unwrap()
+            .clone()
+            .expect("No `Metering` has been set for the compiled module")
+    }
+}
+
+impl MemoryUsage for MeteringSlot {
+    fn size_of_val(&self, tracker: &mut dyn MemoryUsageTracker) -> usize {
+        let metering = self.metering.lock().unwrap();
+
+        mem::size_of_val(self)
+            + metering
+                .as_ref()
+                .map_or(0, |metering| metering.size_of_val(tracker))
+    }
+}
+
+impl ModuleMiddleware for MeteringSlot {
+    fn generate_function_middleware(
+        &self,
+        local_function_index: LocalFunctionIndex,
+    ) -> Box<dyn FunctionMiddleware> {
+        self.current()
+            .generate_function_middleware(local_function_index)
+    }
+
+    fn transform_module_info(&self, module_info: &mut ModuleInfo) {
+        self.current().transform_module_info(module_info)
+    }
+}
+
+#[derive(Debug)]
+struct FunctionMetering {
+    prices: Arc<Vec<u64>>,
+
+    /// The index of the next fed op.
+    next_op: usize,
+
+    /// The gas of the fed ops not charged yet.
+    pending: u64,
+
+    globals: MeteringGlobals,
+}
+
+impl FunctionMetering {
+    fn charge<'a>(&mut self, state: &mut MiddlewareReaderState<'a>) {
+        if self.pending == 0 {
+            return;
+        }
+
+        let gas_used = self.globals.gas_used.as_u32();
+        let exhausted = self.globals.exhausted.as_u32();
+        let gas_limit = self.globals.gas_limit.as_u32();
+
+        // if gas_limit - gas_used < pending { exhausted = 1; unreachable }
+        // gas_used += pending
+        state.extend(&[
+            Operator::GlobalGet {
+                global_index: gas_limit,
+            },
+            Operator::GlobalGet {
+                global_index: gas_used,
+            },
+            Operator::I64Sub,
+            Operator::I64Const {
+                value: self.pending as i64,
+            },
+            Operator::I64LtU,
+            Operator::If {
+                ty: TypeOrFuncType::Type(WpType::EmptyBlockType),
+            },
+            Operator::I32Const { value: 1 },
+            Operator::GlobalSet {
+                global_index: exhausted,
+            },
+            Operator::Unreachable,
+            Operator::End,
+            Operator::GlobalGet {
+                global_index: gas_used,
+            },
+            Operator::I64Const {
+                value: self.pending as i64,
+            },
+            Operator::I64Add,
+            Operator::GlobalSet {
+                global_index: gas_used,
+            },
+        ]);
+
+        self.pending = 0;
+    }
+}
+
+impl FunctionMiddleware for FunctionMetering {
+    fn feed<'a>(
+        &mut self,
+        operator: Operator<'a>,
+        state: &mut MiddlewareReaderState<'a>,
+    ) -> Result<(), MiddlewareError> {
+        let price = self.prices.get(self.next_op).copied().unwrap_or(0);
+
+        self.next_op += 1;
+        self.pending = self.pending.saturating_add(price);
+
+        match operator {
+            Operator::Loop { .. }
+            | Operator::If { .. }
+            | Operator::End
+            | Operator::Else
+            | Operator::Br { .. }
+            | Operator::BrIf { .. }
+            | Operator::BrTable { .. }
+            | Operator::Call { .. }
+            | Operator::CallIndirect { .. }
+            | Operator::Return => self.charge(state),
+            _ => {}
+        }
+
+        state.push_operator(operator);
+        Ok(())
+    }
+}
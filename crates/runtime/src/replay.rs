@@ -0,0 +1,461 @@
+//! Re-executing already executed transactions (see [`replay`]).
+//!
+//! Replaying a transaction and comparing the produced [`Receipt`] against the stored one
+//! is the main tool for investigating consensus divergences between nodes.
+
+use log::{debug, info};
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, Write};
+use std::ops::Range;
+
+use svm_types::{
+    BatchReceipt, CallReceipt, Context, DeployReceipt, Envelope, Gas, Layer, LogsBloom, Receipt,
+    SpawnReceipt, TransactionId, UpgradeReceipt,
+};
+
+use crate::export::{self, ExportFormat};
+use crate::trace::Trace;
+use crate::{Runtime, ValidateError};
+
+/// An executed transaction along with the [`Receipt`] it originally produced.
+#[derive(Debug, PartialEq)]
+pub struct TxRecord {
+    /// The transaction's [`Envelope`].
+    pub envelope: Envelope,
+
+    /// The transaction's [`Context`] (holding the pre-execution `State`).
+    pub context: Context,
+
+    /// The binary transaction message.
+    pub message: Vec<u8>,
+
+    /// The stored [`Receipt`]. Its kind determines the kind of `message`.
+    pub receipt: Receipt,
+}
+
+impl TxRecord {
+    /// The [`TransactionId`] of the recorded transaction.
+    pub fn tx_id(&self) -> &TransactionId {
+        self.context.tx_id()
+    }
+
+    /// Returns `false` when the recorded transaction has certainly not emitted `topic`
+    /// (see [`Receipt::logs_bloom`]).
+    pub fn maybe_contains(&self, topic: &[u8]) -> bool {
+        self.receipt
+            .logs_bloom()
+            .map_or(true, |bloom| bloom.maybe_contains(topic))
+    }
+}
+
+/// A persistent store for executed transactions' [`TxRecord`]s.
+pub trait ReceiptStore {
+    /// Stores a [`TxRecord`] under its [`TransactionId`].
+    fn store(&mut self, record: TxRecord);
+
+    /// Given a [`TransactionId`], loads its [`TxRecord`].
+    ///
+    /// Returns `None` if no such transaction has been stored.
+    #[must_use]
+    fn load(&self, tx_id: &TransactionId) -> Option<&TxRecord>;
+
+    /// Returns `false` when none of the transactions stored for `layer` has emitted `topic`
+    /// for certain, judging by the [`LogsBloom`] aggregated over their receipts.
+    #[must_use]
+    fn layer_maybe_contains(&self, layer: Layer, topic: &[u8]) -> bool;
+
+    /// Returns the [`TxRecord`]s stored for the `Layer`s within `layers`, in execution order.
+    #[must_use]
+    fn records(&self, layers: Range<Layer>) -> Vec<&TxRecord>;
+
+    /// Writes the [`TxRecord`]s stored for the `Layer`s within `layers` into `writer`
+    /// (flattened into rows, see [`export`](crate::export)).
+    ///
+    /// Returns the number of exported rows.
+    fn export(
+        &self,
+        layers: Range<Layer>,
+        format: ExportFormat,
+        writer: &mut dyn Write,
+    ) -> io::Result<usize> {
+        export::export(self.records(layers), format, writer)
+    }
+}
+
+/// In-memory [`ReceiptStore`] implementation.
+///
+/// Keeps the records in their insertion (i.e execution) order.
+#[derive(Debug, Default)]
+pub struct MemReceiptStore {
+    records: Vec<TxRecord>,
+    index: HashMap<TransactionId, usize>,
+
+    /// The [`LogsBloom`] aggregated per `Layer`.
+    /// It's `None` when any receipt of the `Layer` has no filter (see [`Receipt::logs_bloom`]).
+    layer_blooms: HashMap<Layer, Option<LogsBloom>>,
+}
+
+impl MemReceiptStore {
+    /// Initializes a new empty [`MemReceiptStore`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns an iterator over the stored records, in execution order.
+    pub fn iter(&self) -> std::slice::Iter<TxRecord> {
+        self.records.iter()
+    }
+
+    /// Returns an iterator over the stored records that may have emitted `topic`, in execution order.
+    ///
+    /// Whole layers are skipped by their aggregated [`LogsBloom`], and so are single records
+    /// by their own. The logs of the returned records still have to be scanned for `topic`.
+    pub fn iter_maybe_containing<'a>(
+        &'a self,
+        topic: &'a [u8],
+    ) -> impl Iterator<Item = &'a TxRecord> + 'a {
+        self.records.iter().filter(move |record| {
+            self.layer_maybe_contains(record.context.layer(), topic) && record.maybe_contains(topic)
+        })
+    }
+
+    /// Returns the [`LogsBloom`] aggregated over the receipts stored for `layer`.
+    ///
+    /// Returns `None` when no receipts are stored for `layer`,
+    /// or when any of them has no filter.
+    pub fn layer_bloom(&self, layer: Layer) -> Option<&LogsBloom> {
+        self.layer_blooms.get(&layer).and_then(Option::as_ref)
+    }
+
+    fn accrue_layer_bloom(&mut self, layer: Layer, bloom: Option<LogsBloom>) {
+        let layer_bloom = self
+            .layer_blooms
+            .entry(layer)
+            .or_insert_with(|| Some(LogsBloom::new()));
+
+        match (layer_bloom.as_mut(), bloom) {
+            (Some(layer_bloom), Some(bloom)) => *layer_bloom |= &bloom,
+            _ => *layer_bloom = None,
+        }
+    }
+
+    /// Recomputes the [`LogsBloom`] of `layer` (a replaced receipt may not be unset out of it).
+    fn rebuild_layer_bloom(&mut self, layer: Layer) {
+        self.layer_blooms.remove(&layer);
+
+        let blooms: Vec<_> = self
+            .records
+            .iter()
+            .filter(|record| record.context.layer() == layer)
+            .map(|record| record.receipt.logs_bloom())
+            .collect();
+
+        for bloom in blooms {
+            self.accrue_layer_bloom(layer, bloom);
+        }
+    }
+}
+
+impl ReceiptStore for MemReceiptStore {
+    fn store(&mut self, record: TxRecord) {
+        let tx_id = record.tx_id().clone();
+        let layer = record.context.layer();
+
+        match self.index.get(&tx_id) {
+            Some(&i) => {
+                let replaced = std::mem::replace(&mut self.records[i], record);
+
+                self.rebuild_layer_bloom(replaced.context.layer());
+                self.rebuild_layer_bloom(layer);
+            }
+            None => {
+                self.accrue_layer_bloom(layer, record.receipt.logs_bloom());
+
+                self.index.insert(tx_id, self.records.len());
+                self.records.push(record);
+            }
+        }
+    }
+
+    fn load(&self, tx_id: &TransactionId) -> Option<&TxRecord> {
+        self.index.get(tx_id).map(|&i| &self.records[i])
+    }
+
+    fn layer_maybe_contains(&self, layer: Layer, topic: &[u8]) -> bool {
+        match self.layer_blooms.get(&layer) {
+            None => false,
+            Some(None) => true,
+            Some(Some(bloom)) => bloom.maybe_contains(topic),
+        }
+    }
+
+    fn records(&self, layers: Range<Layer>) -> Vec<&TxRecord> {
+        self.records
+            .iter()
+            .filter(|record| layers.contains(&record.context.layer()))
+            .collect()
+    }
+}
+
+/// The gas consumed by a single execution phase of a replayed transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Phase {
+    /// The phase name (`deploy`, `spawn`, `verify` or `call`).
+    pub name: &'static str,
+
+    /// Whether the phase has succeeded.
+    pub success: bool,
+
+    /// The amount of gas used by the phase.
+    pub gas_used: Gas,
+
+    /// The `vmcalls` made during the phase (when the `Runtime` has tracing enabled).
+    pub trace: Option<Trace>,
+}
+
+/// A single mismatch between a stored [`Receipt`] and the replayed one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceiptDiff {
+    /// The name of the mismatching receipt field.
+    pub field: &'static str,
+
+    /// The stored value.
+    pub expected: String,
+
+    /// The replayed value.
+    pub actual: String,
+}
+
+impl fmt::Display for ReceiptDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}`: expected {}, got {}",
+            self.field, self.expected, self.actual
+        )
+    }
+}
+
+/// The outcome of replaying a [`TxRecord`].
+#[derive(Debug, PartialEq)]
+pub struct Replay {
+    /// The executed phases, in execution order.
+    pub phases: Vec<Phase>,
+
+    /// The [`Receipt`] produced by the replay.
+    pub receipt: Receipt,
+
+    /// The differences between the stored [`Receipt`] and the replayed one.
+    pub diffs: Vec<ReceiptDiff>,
+}
+
+impl Replay {
+    /// Returns whether the replayed [`Receipt`] matches the stored one.
+    pub fn is_match(&self) -> bool {
+        self.diffs.is_empty()
+    }
+}
+
+/// Re-executes a recorded transaction against `runtime` and diffs the produced [`Receipt`]
+/// against the stored one.
+///
+/// The `runtime` is expected to hold the transaction's pre-state (i.e the `Template`s and `Account`s
+/// existing at the time of the original execution). Running with the `trace` log level enabled
+/// emits the storage accesses and host calls made during execution, and each [`Phase`] holds
+/// its recorded [`Trace`] when the `runtime` has tracing enabled.
+///
+/// Returns [`ValidateError`] when the recorded message doesn't pass validation.
+pub fn replay<R: Runtime>(runtime: &mut R, record: &TxRecord) -> Result<Replay, ValidateError> {
+    let TxRecord {
+        envelope,
+        context,
+        message,
+        receipt,
+    } = record;
+
+    info!("Replaying transaction `{}`", record.tx_id());
+
+    let mut phases = Vec::new();
+
+    let replayed = match receipt {
+        Receipt::Deploy(..) => {
+            runtime.validate_deploy(message)?;
+
+            let receipt = runtime.deploy(envelope, message, context);
+            phases.push(phase("deploy", receipt.success, receipt.gas_used, None));
+
+            Receipt::Deploy(receipt)
+        }
+        Receipt::Upgrade(..) => {
+            runtime.validate_upgrade(message)?;
+
+            let receipt = runtime.upgrade(envelope, message, context);
+            phases.push(phase("upgrade", receipt.success, receipt.gas_used, None));
+
+            Receipt::Upgrade(receipt)
+        }
+        Receipt::Batch(..) => {
+            runtime.validate_batch(message)?;
+
+            let receipt = runtime.call_batch(envelope, message, context);
+            phases.push(phase("batch", receipt.success, receipt.gas_used, None));
+
+            Receipt::Batch(receipt)
+        }
+        Receipt::Spawn(..) => {
+            runtime.validate_spawn(message)?;
+
+            let receipt = runtime.spawn(envelope, message, context);
+            phases.push(phase(
+                "spawn",
+                receipt.success,
+                receipt.gas_used,
+                runtime.take_trace(),
+            ));
+
+            Receipt::Spawn(receipt)
+        }
+        Receipt::Call(..) => {
+            runtime.validate_call(message)?;
+
+            // A stored `CallReceipt` implies the `verify` stage has passed when executed originally.
+            // So `call` is executed regardless, and a failing `verify` is only reported as a `Phase`.
+            let verify = runtime.verify(envelope, message, context);
+            phases.push(phase(
+                "verify",
+                verify.success,
+                verify.gas_used,
+                runtime.take_trace(),
+            ));
+
+            let receipt = runtime.call(envelope, message, context);
+            phases.push(phase(
+                "call",
+                receipt.success,
+                receipt.gas_used,
+                runtime.take_trace(),
+            ));
+
+            Receipt::Call(receipt)
+        }
+    };
+
+    let diffs = diff(receipt, &replayed);
+
+    Ok(Replay {
+        phases,
+        receipt: replayed,
+        diffs,
+    })
+}
+
+fn phase(name: &'static str, success: bool, gas_used: Gas, trace: Option<Trace>) -> Phase {
+    debug!(
+        "Phase `{}` (success = {}, gas_used = {:?})",
+        name, success, gas_used
+    );
+
+    Phase {
+        name,
+        success,
+        gas_used,
+        trace,
+    }
+}
+
+/// Compares the `expected` (stored) [`Receipt`] against the `actual` (replayed) one, field by field.
+pub fn diff(expected: &Receipt, actual: &Receipt) -> Vec<ReceiptDiff> {
+    let mut diffs = Vec::new();
+
+    match (expected, actual) {
+        (Receipt::Deploy(a), Receipt::Deploy(b)) => diff_deploy(a, b, &mut diffs),
+        (Receipt::Spawn(a), Receipt::Spawn(b)) => diff_spawn(a, b, &mut diffs),
+        (Receipt::Call(a), Receipt::Call(b)) => diff_call(a, b, &mut diffs),
+        (Receipt::Upgrade(a), Receipt::Upgrade(b)) => diff_upgrade(a, b, &mut diffs),
+        (Receipt::Batch(a), Receipt::Batch(b)) => diff_batch(a, b, &mut diffs),
+        _ => push_diff(&mut diffs, "kind", kind(expected), kind(actual)),
+    }
+
+    diffs
+}
+
+fn diff_deploy(a: &DeployReceipt, b: &DeployReceipt, diffs: &mut Vec<ReceiptDiff>) {
+    push_diff(diffs, "version", &a.version, &b.version);
+    push_diff(diffs, "success", &a.success, &b.success);
+    push_diff(diffs, "error", &a.error, &b.error);
+    push_diff(diffs, "addr", &a.addr, &b.addr);
+    push_diff(diffs, "gas_used", &a.gas_used, &b.gas_used);
+    push_diff(diffs, "logs", &a.logs, &b.logs);
+}
+
+fn diff_spawn(a: &SpawnReceipt, b: &SpawnReceipt, diffs: &mut Vec<ReceiptDiff>) {
+    push_diff(diffs, "version", &a.version, &b.version);
+    push_diff(diffs, "success", &a.success, &b.success);
+    push_diff(diffs, "error", &a.error, &b.error);
+    push_diff(diffs, "account_addr", &a.account_addr, &b.account_addr);
+    push_diff(diffs, "init_state", &a.init_state, &b.init_state);
+    push_diff(diffs, "returndata", &a.returndata, &b.returndata);
+    push_diff(diffs, "gas_used", &a.gas_used, &b.gas_used);
+    push_diff(diffs, "logs", &a.logs, &b.logs);
+    push_diff(diffs, "call", &a.call, &b.call);
+}
+
+fn diff_call(a: &CallReceipt, b: &CallReceipt, diffs: &mut Vec<ReceiptDiff>) {
+    push_diff(diffs, "version", &a.version, &b.version);
+    push_diff(diffs, "success", &a.success, &b.success);
+    push_diff(diffs, "error", &a.error, &b.error);
+    push_diff(diffs, "new_state", &a.new_state, &b.new_state);
+    push_diff(diffs, "returndata", &a.returndata, &b.returndata);
+    push_diff(diffs, "gas_used", &a.gas_used, &b.gas_used);
+    push_diff(diffs, "logs", &a.logs, &b.logs);
+}
+
+fn diff_upgrade(a: &UpgradeReceipt, b: &UpgradeReceipt, diffs: &mut Vec<ReceiptDiff>) {
+    push_diff(diffs, "version", &a.version, &b.version);
+    push_diff(diffs, "success", &a.success, &b.success);
+    push_diff(diffs, "error", &a.error, &b.error);
+    push_diff(diffs, "addr", &a.addr, &b.addr);
+    push_diff(
+        diffs,
+        "template_version",
+        &a.template_version,
+        &b.template_version,
+    );
+    push_diff(diffs, "gas_used", &a.gas_used, &b.gas_used);
+    push_diff(diffs, "logs", &a.logs, &b.logs);
+}
+
+fn diff_batch(a: &BatchReceipt, b: &BatchReceipt, diffs: &mut Vec<ReceiptDiff>) {
+    push_diff(diffs, "version", &a.version, &b.version);
+    push_diff(diffs, "success", &a.success, &b.success);
+    push_diff(diffs, "error", &a.error, &b.error);
+    push_diff(diffs, "new_state", &a.new_state, &b.new_state);
+    push_diff(diffs, "receipts", &a.receipts, &b.receipts);
+    push_diff(diffs, "gas_used", &a.gas_used, &b.gas_used);
+    push_diff(diffs, "logs", &a.logs, &b.logs);
+}
+
+fn push_diff<T>(diffs: &mut Vec<ReceiptDiff>, field: &'static str, expected: T, actual: T)
+where
+    T: fmt::Debug + PartialEq,
+{
+    if expected != actual {
+        diffs.push(ReceiptDiff {
+            field,
+            expected: format!("{:?}", expected),
+            actual: format!("{:?}", actual),
+        });
+    }
+}
+
+fn kind(receipt: &Receipt) -> &'static str {
+    match receipt {
+        Receipt::Deploy(..) => "deploy",
+        Receipt::Spawn(..) => "spawn",
+        Receipt::Call(..) => "call",
+        Receipt::Upgrade(..) => "upgrade",
+        Receipt::Batch(..) => "batch",
+    }
+}
@@ -0,0 +1,29 @@
+use svm_types::{Address, State, TemplateAddr};
+
+/// A summary of a spawned `Account` (see [`Runtime::iter_accounts`](crate::Runtime::iter_accounts)).
+///
+/// The balance and the storage root are the ones as of the most recently committed global `State`
+/// (see [`Runtime::current_state_root`](crate::Runtime::current_state_root)).
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountInfo {
+    /// The `Account`'s `Address`.
+    pub addr: Address,
+
+    /// The `Address` of the `Template` the `Account` has been spawned from.
+    pub template_addr: TemplateAddr,
+
+    /// The `Account`'s name.
+    pub name: String,
+
+    /// The `Address` of the `Account`'s spawner.
+    pub spawner: Address,
+
+    /// The id of the address derivation scheme the `Account`'s `Address` has been computed with.
+    pub addr_scheme: u16,
+
+    /// The `Account`'s balance.
+    pub balance: u64,
+
+    /// The root of the `Account`'s storage.
+    pub state_root: State,
+}
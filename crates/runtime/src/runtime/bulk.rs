@@ -0,0 +1,18 @@
+/// The progress of a bulk spawn (see [`Runtime::spawn_bulk`](crate::Runtime::spawn_bulk)).
+///
+/// Reported each time one more `Account` has been spawned (successfully or not).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BulkSpawnProgress {
+    /// The number of spawns completed so far.
+    pub done: usize,
+
+    /// The total number of spawns of the batch.
+    pub total: usize,
+}
+
+impl BulkSpawnProgress {
+    /// Returns whether all the spawns of the batch have completed.
+    pub fn is_complete(&self) -> bool {
+        self.done == self.total
+    }
+}
@@ -16,4 +16,6 @@ pub struct Call<'a> {
     pub context: &'a Context,
     pub envelope: &'a Envelope,
     pub protected_mode: ProtectedMode,
+    /// The `Account`s calling `target` (outermost first, see [`CallStack`](crate::CallStack)).
+    pub callers: &'a [Address],
 }
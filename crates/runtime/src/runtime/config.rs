@@ -1,8 +1,51 @@
 use std::path::PathBuf;
 
+use svm_storage::kv::WriteMode;
+
+use super::QueryCacheConfig;
+use crate::StorePoolConfig;
+
 /// Runtime configuration
 #[derive(Debug, Clone, Default)]
 pub struct Config {
     /// The path for the key-value store.
     pub kv_path: PathBuf,
+
+    /// How the `Template`s and `Account`s stores persist their writes into the key-value store
+    /// (see [`WriteBehindKV`](svm_storage::kv::WriteBehindKV)).
+    ///
+    /// Under [`WriteMode::WriteBehind`] the embedder is expected to call
+    /// [`Runtime::flush_until`](crate::Runtime::flush_until) before finalizing a `Layer`.
+    pub write_mode: WriteMode,
+
+    /// When set, the `vmcalls` of executed transactions are recorded into
+    /// a [`Trace`](crate::trace::Trace) of at most that many bytes.
+    pub trace_limit: Option<usize>,
+
+    /// When set, the results of [`Runtime::query`](crate::Runtime::query)
+    /// (and [`DefaultRuntime::query_receipt`](crate::DefaultRuntime::query_receipt)) are cached (within these limits).
+    pub query_cache: Option<QueryCacheConfig>,
+
+    /// When set, the receipts of executed transactions carry the breakdown of their
+    /// `gas_used` by phase (see [`GasBreakdown`](svm_types::GasBreakdown)).
+    pub detailed_gas_accounting: bool,
+
+    /// When set, the receipts of executed transactions carry a bloom filter of their logs
+    /// (see [`LogsBloom`](svm_types::LogsBloom)), so that scanning for events can skip them.
+    pub logs_bloom: bool,
+
+    /// The version of the gas schedule transactions are priced by.
+    ///
+    /// It's recorded in every receipt, so that the pricing of past transactions
+    /// can be audited (and reproduced) later on.
+    pub gas_schedule_version: u16,
+
+    /// The number of the most recent `Layer`s whose committed global `State`s are tracked
+    /// (see [`StateRoots`](crate::StateRoots)), defaults to [`DEFAULT_STATE_ROOTS_LAYERS`](crate::DEFAULT_STATE_ROOTS_LAYERS).
+    ///
+    /// The global `State`s of older `Layer`s are forgotten (so they can't be executed against anymore).
+    pub state_roots_layers: Option<usize>,
+
+    /// The limits of the [`StorePool`](crate::StorePool) of the `Store`s compiling the executed code.
+    pub store_pool: StorePoolConfig,
 }
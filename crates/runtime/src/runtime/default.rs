@@ -1,23 +1,49 @@
-use log::info;
+use log::{error, info};
 use wasmer::{Instance, Module, WasmPtr, WasmTypeList};
 
-use std::cell::RefCell;
+use std::borrow::Cow;
+use std::cell::{Cell, Ref, RefCell};
 use std::collections::{HashMap, HashSet};
+use std::panic::{self, AssertUnwindSafe};
 use std::rc::Rc;
+use std::sync::Arc;
+#[cfg(feature = "default-memory")]
+use std::sync::Mutex;
 
-use svm_gas::FuncPrice;
+use svm_hash::{Blake3Hasher, Hasher};
 use svm_layout::FixedLayout;
 use svm_program::Program;
-use svm_storage::account::AccountStorage;
+use svm_storage::account::{AccountLocks, AccountStorage, InsufficientBalance, PageCorrupted};
+#[cfg(feature = "default-memory")]
+use svm_storage::kv::FakeKV;
+use svm_storage::kv::FlushError;
 use svm_types::{
-    Address, CallReceipt, Context, DeployReceipt, Envelope, Gas, GasMode, OOGError, ReceiptLog,
-    RuntimeError, SectionKind, SpawnReceipt, State, Template, TemplateAddr, Transaction,
+    Address, BatchReceipt, CallReceipt, CodeKind, Context, DefaultsSection, DeployReceipt,
+    DeploySection, Envelope, Gas, GasBreakdown, GasEstimate, GasMode, Layer, LogsBloom, OOGError,
+    Receipt, ReceiptLog, RuntimeError, Section, SectionKind, SpawnReceipt, State, Template,
+    TemplateAddr, Transaction, TransactionId, UpgradeReceipt,
 };
 
-use super::{Call, Failure, Function, Outcome};
-use crate::env::{EnvTypes, ExtAccount, ExtSpawn};
-use crate::error::ValidateError;
-use crate::storage::StorageBuilderFn;
+#[cfg(feature = "default-memory")]
+use super::RuntimeSnapshot;
+use super::{
+    committed_state, AccountInfo, BulkSpawnProgress, Call, DuplicatePrecompile, Failure, Function,
+    HostModule, HostModuleError, HostModules, HostPhase, LayerHeader, LayerOutcome, MemPriceCache,
+    Message, MetricsObserver, Outcome, PendingMetrics, Precompile, PrecompileCall, PrecompileError,
+    Precompiles, PriceCache, PriceCacheStats, PriceKey, QueryCache, QueryCacheConfig, QueryKey,
+    QueryResult, RuntimeMetrics, StateRoots, DEFAULT_PRICE_CACHE_ENTRIES,
+};
+#[cfg(feature = "default-memory")]
+use crate::env::DefaultMemEnvTypes;
+use crate::env::{AccountStore, AddressScheme, EnvTypes, ExtAccount, ExtSpawn, TemplateStore};
+use crate::error::{CallAtError, ValidateError};
+use crate::func_env::{CallStack, NestedCall, NestedCaller, Staged};
+use crate::metering::Metering;
+use crate::replay::{ReceiptStore, TxRecord};
+use crate::storage::{StorageBuilderFn, StorageMode};
+use crate::trace::{Trace, VmCall};
+use crate::vmcalls::UnknownAccount;
+use crate::wasm_store::{ModuleKey, StorePool, StorePoolConfig};
 use crate::Env;
 use crate::{vmcalls, ProtectedMode};
 use crate::{Config, FuncEnv, Runtime};
@@ -34,8 +60,11 @@ where
     /// Used mainly for managing an Account's persistence.
     env: Env<T>,
 
-    /// Provided host functions to be consumed by running transactions.
-    imports: (String, wasmer::Exports),
+    /// Provided host modules to be consumed by running transactions.
+    host_modules: HostModules,
+
+    /// The native implementations of precompile [`Template`]s (see [`CodeKind::Precompile`]).
+    precompiles: Precompiles,
 
     /// Runtime configuration.
     config: Config,
@@ -43,11 +72,44 @@ where
     /// Builds an `AccountStorage` instance.
     storage_builder: Box<StorageBuilderFn>,
 
-    /// A naive cache for [`Template`]s' [`FuncPrice`]s. The cache key will, in
-    /// the future, also include an identifier for which
-    /// [`PriceResolver`](svm_gas::PriceResolver) should be used (possibly an
-    /// `u16`?).
-    template_prices: Rc<RefCell<HashMap<TemplateAddr, FuncPrice>>>,
+    /// The way the storages are opened (a read-only historical view while executing [`Runtime::call_at`]).
+    storage_mode: Cell<StorageMode>,
+
+    /// Caches the [`Template`]s' [`FuncPrice`](svm_gas::FuncPrice)s (see [`PriceCache`]).
+    price_cache: RefCell<Box<dyn PriceCache>>,
+
+    /// The [`Trace`] recorded by the most recent execution (when tracing is enabled).
+    trace: RefCell<Option<Trace>>,
+
+    /// The results of recent queries (when enabled by [`Config::query_cache`]).
+    query_cache: RefCell<Option<QueryCache>>,
+
+    /// The `Store`s reused for compiling the executed code (see [`Config::store_pool`]).
+    stores: StorePool,
+
+    /// The committed global `State`s and the cached `Account`s roots
+    /// (see [`Runtime::state_root`] and [`Runtime::current_state_root`]).
+    state_roots: RefCell<StateRoots>,
+
+    /// Counters of notable events (see [`Self::metrics`]).
+    metrics: RuntimeMetrics,
+
+    /// Observes the metrics reported by the executed code (see [`Self::set_metrics_observer`]).
+    metrics_observer: Option<Box<dyn MetricsObserver>>,
+
+    /// The metrics reported by the running transaction (when observed), pending its outcome.
+    pending_metrics: Vec<PendingMetrics>,
+
+    /// Stores the receipts of the executed `Layer`s (see [`Self::set_receipt_store`]).
+    receipt_store: Option<Box<dyn ReceiptStore>>,
+
+    /// Coordinates the read-only executions with the commits into the `Account`s storage
+    /// (see [`Self::set_account_locks`]).
+    account_locks: AccountLocks,
+
+    /// The in-memory key-value store backing the `Account`s storage (see [`Self::with_memory_kv`]).
+    #[cfg(feature = "default-memory")]
+    memory_kv: Option<Arc<Mutex<FakeKV>>>,
 }
 
 impl<T> DefaultRuntime<T>
@@ -56,28 +118,401 @@ where
 {
     /// Initializes a new [`DefaultRuntime`].
     ///
-    /// `template_prices` offers an easy way to inject an append-only, naive caching mechanism to
-    /// the [`Template`] pricing logic; using a `None` will result in a new
-    /// empty cache and on-the-fly calculation for all [`Template`]s.
+    /// `price_cache` caches the prices of the [`Template`]s (see [`PriceCache`]); using a `None`
+    /// will result in a new empty [`MemPriceCache`] holding up to [`DEFAULT_PRICE_CACHE_ENTRIES`] prices.
     pub fn new(
         env: Env<T>,
-        imports: (String, wasmer::Exports),
+        host_modules: HostModules,
         storage_builder: Box<StorageBuilderFn>,
         config: Config,
-        template_prices: Option<Rc<RefCell<HashMap<TemplateAddr, FuncPrice>>>>,
+        price_cache: Option<Box<dyn PriceCache>>,
     ) -> Self {
-        let template_prices = if let Some(tp) = template_prices {
-            tp
-        } else {
-            Rc::new(RefCell::new(HashMap::default()))
-        };
+        let price_cache = price_cache
+            .unwrap_or_else(|| Box::new(MemPriceCache::new(DEFAULT_PRICE_CACHE_ENTRIES)));
+        let query_cache = RefCell::new(config.query_cache.clone().map(QueryCache::new));
+        let stores = StorePool::new(config.store_pool.clone());
+        let state_roots = config
+            .state_roots_layers
+            .map_or_else(StateRoots::new, StateRoots::with_limit);
+
         Self {
             env,
-            imports,
+            host_modules,
+            precompiles: Precompiles::new(),
             storage_builder,
+            storage_mode: Cell::new(StorageMode::Current),
             config,
-            template_prices,
+            price_cache: RefCell::new(price_cache),
+            trace: RefCell::new(None),
+            query_cache,
+            stores,
+            state_roots: RefCell::new(state_roots),
+            metrics: RuntimeMetrics::new(),
+            metrics_observer: None,
+            receipt_store: None,
+            pending_metrics: Vec::new(),
+            account_locks: AccountLocks::new(),
+            #[cfg(feature = "default-memory")]
+            memory_kv: None,
+        }
+    }
+
+    /// Registers an additional [`HostModule`] (see [`HostModules::register`]).
+    pub fn register_host_module(
+        &mut self,
+        module: HostModule,
+    ) -> std::result::Result<(), HostModuleError> {
+        self.host_modules.register(module)
+    }
+
+    /// The [`HostModule`]s provided to running transactions.
+    pub fn host_modules(&self) -> &HostModules {
+        &self.host_modules
+    }
+
+    /// Registers the native implementation of the precompile [`Template`]s of id `id`
+    /// (see [`Precompiles::register`]).
+    pub fn register_precompile<P>(
+        &mut self,
+        id: u32,
+        precompile: P,
+    ) -> std::result::Result<(), DuplicatePrecompile>
+    where
+        P: Precompile + 'static,
+    {
+        self.precompiles.register(id, precompile)
+    }
+
+    /// The [`Precompile`]s provided to running transactions.
+    pub fn precompiles(&self) -> &Precompiles {
+        &self.precompiles
+    }
+
+    /// Registers an additional address derivation scheme (see [`Env::register_addr_scheme`]).
+    pub fn register_addr_scheme(&mut self, addr_scheme: u16, scheme: Rc<dyn AddressScheme>) {
+        self.env.register_addr_scheme(addr_scheme, scheme);
+    }
+
+    /// Switches the address derivation scheme of new [`Template`]s and `Account`s
+    /// (see [`Env::set_addr_scheme`]).
+    pub fn set_addr_scheme(&mut self, addr_scheme: u16) {
+        self.env.set_addr_scheme(addr_scheme);
+    }
+
+    /// Sets the size limit of the recorded [`Trace`]s. Using `None` disables tracing.
+    pub fn set_trace_limit(&mut self, limit: Option<usize>) {
+        self.config.trace_limit = limit;
+    }
+
+    /// Sets whether the receipts carry the breakdown of their `gas_used` by phase
+    /// (see [`Config::detailed_gas_accounting`]).
+    pub fn set_detailed_gas_accounting(&mut self, enabled: bool) {
+        self.config.detailed_gas_accounting = enabled;
+    }
+
+    /// Sets whether the receipts carry a bloom filter of their logs
+    /// (see [`Config::logs_bloom`]).
+    ///
+    /// Drops all the currently cached [`Self::query_receipt`] results.
+    pub fn set_logs_bloom(&mut self, enabled: bool) {
+        self.config.logs_bloom = enabled;
+        self.query_cache = RefCell::new(self.config.query_cache.clone().map(QueryCache::new));
+    }
+
+    /// Sets the version of the gas schedule recorded in the receipts
+    /// (see [`Config::gas_schedule_version`]).
+    ///
+    /// Drops all the currently cached [`Self::query_receipt`] results (they carry the former version).
+    pub fn set_gas_schedule_version(&mut self, version: u16) {
+        self.config.gas_schedule_version = version;
+        self.query_cache = RefCell::new(self.config.query_cache.clone().map(QueryCache::new));
+    }
+
+    /// Sets the limits of the [`QueryCache`] used by [`Self::query_receipt`]. Using `None` disables caching.
+    ///
+    /// Drops all the currently cached results.
+    pub fn set_query_cache(&mut self, config: Option<QueryCacheConfig>) {
+        self.query_cache = RefCell::new(config.clone().map(QueryCache::new));
+        self.config.query_cache = config;
+    }
+
+    /// Shares `locks` with other [`Runtime`]s backed by the same key-value store.
+    ///
+    /// A read-only execution (such as [`Runtime::query`]) keeps the `Account`s it reads locked until it ends,
+    /// while committing a transaction waits for them (and vice versa). This way, the queries always observe
+    /// the `Account`s storage either before or after a transaction, never in the middle of its commit.
+    pub fn set_account_locks(&mut self, locks: AccountLocks) {
+        self.account_locks = locks;
+    }
+
+    /// The [`AccountLocks`] coordinating the access to the `Account`s storage.
+    pub fn account_locks(&self) -> &AccountLocks {
+        &self.account_locks
+    }
+
+    /// Executes a [`Transaction`] just like [`Runtime::call`], while recording its `vmcalls`
+    /// into a [`Trace`] of at most `trace_limit` bytes.
+    pub fn simulate_call(
+        &mut self,
+        envelope: &Envelope,
+        message: &[u8],
+        context: &Context,
+        trace_limit: usize,
+    ) -> (CallReceipt, Trace) {
+        let origin_limit = self.config.trace_limit.replace(trace_limit);
+
+        let receipt = self.call(envelope, message, context);
+        let trace = self.take_trace().unwrap_or_else(|| Trace::new(trace_limit));
+
+        self.config.trace_limit = origin_limit;
+
+        (receipt, trace)
+    }
+
+    /// Executes a read-only query (i.e a call to a `view` function) of a binary `Call Account` message,
+    /// returning its full [`CallReceipt`] (see [`Runtime::query`], which reports the same execution).
+    ///
+    /// The query is executed against `context`'s `State`, and modifying the storage fails it
+    /// (see [`ProtectedMode::ReadOnly`]). Nothing is committed, so the returned [`CallReceipt`] `new_state`
+    /// is the queried `State`.
+    ///
+    /// When [`Config::query_cache`] is set, successful results are cached by all the inputs the queried
+    /// function can observe (see [`QueryKey`]). Since the `State` is part of the key,
+    /// a result is never served once the queried `Account` has changed.
+    pub fn query_receipt(
+        &self,
+        envelope: &Envelope,
+        message: &[u8],
+        context: &Context,
+    ) -> CallReceipt {
+        let tx = self
+            .env
+            .parse_call(message)
+            .expect("Should have called `validate_call` first");
+
+        let mut call = self.build_call(
+            &tx,
+            envelope,
+            context,
+            ProtectedMode::ReadOnly,
+            tx.func_name(),
+            tx.calldata(),
+        );
+
+        let payload_price = svm_gas::transaction::call(message);
+        match call.gas_limit - payload_price {
+            Ok(gas_left) => call.gas_limit = gas_left,
+            Err(..) => {
+                let mut receipt = CallReceipt::new_oog(Vec::new());
+                receipt.set_gas_schedule_version(self.config.gas_schedule_version);
+
+                return receipt;
+            }
+        }
+
+        let key = QueryKey {
+            account: call.target.clone(),
+            template_version: self.env.template_version(&call.template).unwrap_or(0),
+            state: call.state.clone(),
+            func_name: call.func_name.to_string(),
+            calldata: call.func_input.to_vec(),
+            principal: envelope.principal().clone(),
+            amount: envelope.amount(),
+            gas_fee: envelope.gas_fee(),
+            tx_id: context.tx_id().clone(),
+            layer: context.layer(),
+        };
+
+        if let Some(cache) = self.query_cache.borrow_mut().as_mut() {
+            if let Some(receipt) = cache.get(&key, call.gas_limit) {
+                return receipt;
+            }
+        }
+
+        let mut receipt = self.isolate_ref(
+            |rt| rt.exec_read_only(&call),
+            |err| CallReceipt::from_err(err, Vec::new()),
+        );
+
+        if let Some(breakdown) = receipt.gas_breakdown.as_mut() {
+            breakdown.intrinsic += payload_price;
+        }
+        receipt.set_gas_schedule_version(self.config.gas_schedule_version);
+
+        if let Some(cache) = self.query_cache.borrow_mut().as_mut() {
+            cache.insert(key, receipt.clone());
+        }
+
+        receipt
+    }
+
+    /// The [`QueryCache`] used by [`Self::query_receipt`] (when enabled).
+    pub fn query_cache(&self) -> Option<Ref<QueryCache>> {
+        Ref::filter_map(self.query_cache.borrow(), Option::as_ref).ok()
+    }
+
+    /// The statistics of the [`PriceCache`] caching the [`Template`]s' prices.
+    pub fn price_cache_stats(&self) -> PriceCacheStats {
+        self.price_cache.borrow().stats()
+    }
+
+    /// The [`StorePool`] of the `Store`s compiling the executed code.
+    pub fn store_pool(&self) -> &StorePool {
+        &self.stores
+    }
+
+    /// Sets the limits of the [`StorePool`] (see [`Config::store_pool`]).
+    ///
+    /// Drops all the currently idle `Store`s.
+    pub fn set_store_pool(&mut self, config: StorePoolConfig) {
+        self.stores = StorePool::new(config.clone());
+        self.config.store_pool = config;
+    }
+
+    /// The counters of notable events observed so far.
+    pub fn metrics(&self) -> &RuntimeMetrics {
+        &self.metrics
+    }
+
+    /// Sets the [`MetricsObserver`] of the metrics reported by the executed code.
+    /// Using `None` stops observing.
+    pub fn set_metrics_observer(&mut self, observer: Option<Box<dyn MetricsObserver>>) {
+        self.metrics_observer = observer;
+    }
+
+    /// Sets the [`ReceiptStore`] storing the transactions of the executed `Layer`s
+    /// (see [`Runtime::execute_layer`]). Using `None` stops storing them.
+    pub fn set_receipt_store(&mut self, store: Option<Box<dyn ReceiptStore>>) {
+        self.receipt_store = store;
+    }
+
+    /// Borrows the [`ReceiptStore`] (if any).
+    pub fn receipt_store(&self) -> Option<&dyn ReceiptStore> {
+        self.receipt_store.as_deref()
+    }
+
+    /// Validates and executes a single transaction `message` of a `Layer` (see [`Runtime::execute_layer`]).
+    fn execute_message(
+        &mut self,
+        envelope: &Envelope,
+        message: &Message,
+        context: &Context,
+    ) -> std::result::Result<Receipt, ValidateError> {
+        let receipt = match message {
+            Message::Deploy(bytes) => {
+                self.validate_deploy(bytes)?;
+                Receipt::Deploy(self.deploy(envelope, bytes, context))
+            }
+            Message::Upgrade(bytes) => {
+                self.validate_upgrade(bytes)?;
+                Receipt::Upgrade(self.upgrade(envelope, bytes, context))
+            }
+            Message::Spawn(bytes) => {
+                self.validate_spawn(bytes)?;
+                Receipt::Spawn(self.spawn(envelope, bytes, context))
+            }
+            Message::Call(bytes) => {
+                Runtime::validate_call(self, bytes)?;
+                Receipt::Call(self.call(envelope, bytes, context))
+            }
+            Message::Batch(bytes) => {
+                self.validate_batch(bytes)?;
+                Receipt::Batch(self.call_batch(envelope, bytes, context))
+            }
+        };
+
+        Ok(receipt)
+    }
+
+    /// Reports the pending metrics of the transaction that has just completed
+    /// to the [`MetricsObserver`] (only when it has succeeded),
+    /// followed by the statistics of the `Template`s cache (if any).
+    fn report_metrics(&mut self, success: bool) {
+        let pending = std::mem::take(&mut self.pending_metrics);
+
+        if let Some(observer) = self.metrics_observer.as_mut() {
+            if success {
+                for metrics in pending.iter() {
+                    observer.observe(&metrics.account, &metrics.template, &metrics.metrics);
+                }
+            }
+
+            if let Some(stats) = self.env.template_store().cache_stats() {
+                observer.observe_template_cache(&stats);
+            }
+        }
+    }
+
+    /// Executes a transaction by `exec`, isolating the embedder from its panics.
+    ///
+    /// A panic is recorded (see [`RuntimeMetrics::internal_errors`]) and turned into
+    /// the receipt built by `on_panic` out of a [`RuntimeError::InternalError`].
+    fn isolate<R, F, P>(&mut self, exec: F, on_panic: P) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+        P: FnOnce(RuntimeError) -> R,
+    {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| exec(self)));
+
+        self.recover(result, on_panic)
+    }
+
+    /// Same as [`Self::isolate`], for executions which don't modify the [`DefaultRuntime`].
+    fn isolate_ref<R, F, P>(&self, exec: F, on_panic: P) -> R
+    where
+        F: FnOnce(&Self) -> R,
+        P: FnOnce(RuntimeError) -> R,
+    {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| exec(self)));
+
+        self.recover(result, on_panic)
+    }
+
+    fn recover<R, P>(&self, result: std::thread::Result<R>, on_panic: P) -> R
+    where
+        P: FnOnce(RuntimeError) -> R,
+    {
+        match result {
+            Ok(receipt) => receipt,
+            Err(payload) => {
+                let msg = panic_message(payload.as_ref());
+                error!("Transaction execution has panicked: {}", msg);
+
+                self.metrics.record_internal_error();
+
+                on_panic(RuntimeError::InternalError { msg })
+            }
+        }
+    }
+
+    fn outcome_to_query_receipt(
+        &self,
+        env: &FuncEnv,
+        mut out: Outcome<Box<[wasmer::Val]>>,
+        state: &State,
+    ) -> CallReceipt {
+        let mut receipt = CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            returndata: Some(self.take_returndata(env)),
+            new_state: Some(state.clone()),
+            gas_used: out.gas_used(),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: out.take_logs(),
+            metrics: Vec::new(),
+        };
+
+        receipt.set_metrics(env.borrow_mut().take_metrics());
+        receipt.set_logs_version();
+
+        if let Some(breakdown) = self.gas_breakdown(env, &out) {
+            receipt.set_gas_breakdown(breakdown);
         }
+
+        receipt
     }
 
     fn outcome_to_receipt(
@@ -85,15 +520,89 @@ where
         env: &FuncEnv,
         mut out: Outcome<Box<[wasmer::Val]>>,
     ) -> CallReceipt {
-        CallReceipt {
+        let new_state = match self.commit_changes(env) {
+            Ok(state) => state,
+            Err(err) => {
+                let fail = self.storage_corrupted(err, out.take_logs());
+
+                return self.failure_to_receipt(fail);
+            }
+        };
+
+        let mut receipt = CallReceipt {
             version: 0,
             success: true,
             error: None,
             returndata: Some(self.take_returndata(env)),
-            new_state: Some(self.commit_changes(&env)),
+            new_state: Some(new_state),
             gas_used: out.gas_used(),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
             logs: out.take_logs(),
+            metrics: Vec::new(),
+        };
+
+        receipt.set_metrics(env.borrow_mut().take_metrics());
+        receipt.set_logs_version();
+
+        if let Some(breakdown) = self.gas_breakdown(env, &out) {
+            receipt.set_gas_breakdown(breakdown);
         }
+
+        receipt
+    }
+
+    /// Breaks down the gas used by the executed code (when [`Config::detailed_gas_accounting`] is set).
+    ///
+    /// The storage writes and the logs are priced by their `vmcalls` import price
+    /// (batched storage writes are charged per variable and per byte on top of it).
+    /// Since the metered `gas_used` already covers the import prices, they're moved out of the `execution` phase.
+    /// The `intrinsic` costs (of the transaction payload and of loading the `Template`) are left for the callers to add.
+    fn gas_breakdown(
+        &self,
+        env: &FuncEnv,
+        out: &Outcome<Box<[wasmer::Val]>>,
+    ) -> Option<GasBreakdown> {
+        let borrow = env.borrow();
+        let resolver = self.env.price_resolver();
+
+        let price = |vmcalls: &[VmCall]| -> Option<u64> {
+            vmcalls.iter().try_fold(0, |total, vmcall| {
+                let count = borrow.vmcall_count(*vmcall)?;
+                let price = resolver.import_price(("svm", vmcall.name())) as u64;
+
+                Some(total + count * price)
+            })
+        };
+
+        let batched_storage = {
+            let vmcall = VmCall::StorageWriteMany;
+            let (vars, bytes) = borrow.storage_batch_size(vmcall)?;
+
+            resolver.storage_batch_price(("svm", vmcall.name()), vars as usize, bytes as usize)
+                as u64
+        };
+
+        let storage = price(&[
+            VmCall::Set32,
+            VmCall::Set64,
+            VmCall::SetBit,
+            VmCall::Store160,
+            VmCall::StoreDyn,
+            VmCall::StorageWriteMany,
+        ])?;
+        let logs = price(&[VmCall::Log, VmCall::LogEvent])?;
+
+        let execution = out.gas_used().unwrap_or(0).saturating_sub(storage + logs);
+
+        Some(GasBreakdown {
+            intrinsic: 0,
+            execution,
+            storage: storage + batched_storage,
+            logs,
+            refund: 0,
+        })
     }
 
     fn failure_to_receipt(&self, mut fail: Failure) -> CallReceipt {
@@ -110,12 +619,52 @@ where
         state: &State,
         layout: &FixedLayout,
     ) -> AccountStorage {
-        (self.storage_builder)(target, state, layout, &self.config)
+        let mode = self.storage_mode.get();
+
+        (self.storage_builder)(target, state, mode, layout, &self.config)
+    }
+
+    /// Opens the [`AccountStorage`] of `target` laid out by `template` (including its dynamic variables).
+    fn open_template_storage(
+        &self,
+        target: &Address,
+        state: &State,
+        template: &Template,
+    ) -> AccountStorage {
+        let storage = self
+            .open_storage(target, state, template.fixed_layout())
+            .with_locks(self.account_locks.clone());
+
+        match template.dynamic_layout() {
+            Some(dynamic) => storage.with_dynamic_layout(dynamic.clone()),
+            None => storage,
+        }
+    }
+
+    /// Writes the default values of `template`'s storage variables into a fresh `Account`'s storage.
+    ///
+    /// Called before running the `ctor`, so the values are committed along with the `ctor` changes.
+    fn apply_defaults(&self, storage: &mut AccountStorage, template: &Template) {
+        if let Some(defaults) = template.defaults_section() {
+            for (var_id, value) in defaults.defaults() {
+                storage.write_var(*var_id, value.clone());
+            }
+        }
+    }
+
+    /// Credits a fresh `Account` with the `amount` of the spawning transaction [`Envelope`].
+    ///
+    /// Called along with [`Self::apply_defaults`], so the balance is committed along with the `ctor` changes.
+    fn endow(&self, storage: &mut AccountStorage, envelope: &Envelope) {
+        if envelope.amount() > 0 {
+            storage.set_balance(envelope.amount());
+        }
     }
 
     fn call_ctor(
         &mut self,
         spawn: &ExtSpawn,
+        ctor_name: &str,
         target: Address,
         gas_left: Gas,
         envelope: &Envelope,
@@ -124,7 +673,7 @@ where
         let template = spawn.template_addr().clone();
 
         let call = Call {
-            func_name: spawn.ctor_name(),
+            func_name: ctor_name,
             func_input: spawn.ctor_data(),
             state: &State::zeros(),
             template,
@@ -132,6 +681,7 @@ where
             within_spawn: true,
             gas_limit: gas_left,
             protected_mode: ProtectedMode::FullAccess,
+            callers: &[],
             envelope,
             context,
         };
@@ -142,71 +692,595 @@ where
         svm_types::into_spawn_receipt(receipt, &target)
     }
 
-    fn exec_call<Args, Rets>(&mut self, call: &Call) -> CallReceipt {
-        let result = self.exec::<(), (), _, _>(&call, |env, out| self.outcome_to_receipt(env, out));
+    /// Executes the optional [`SpawnCall`](svm_types::SpawnCall) of `spawn`
+    /// over the `ctor`-initialized `State`.
+    ///
+    /// The call gets the gas left by the `ctor`, and in case it fails the whole spawning fails.
+    fn call_after_ctor(
+        &mut self,
+        spawn: &ExtSpawn,
+        mut receipt: SpawnReceipt,
+        gas_left: Gas,
+        envelope: &Envelope,
+        context: &Context,
+    ) -> SpawnReceipt {
+        let spawn_call = match spawn.call() {
+            Some(spawn_call) if receipt.success => spawn_call,
+            _ => return receipt,
+        };
 
-        result.unwrap_or_else(|fail| self.failure_to_receipt(fail))
-    }
+        let gas_left = match gas_left - receipt.gas_used.unwrap_or(0) {
+            Ok(gas_left) => gas_left,
+            Err(..) => return SpawnReceipt::new_oog(receipt.take_logs()),
+        };
 
-    fn exec<Args, Rets, F, R>(&self, call: &Call, f: F) -> std::result::Result<R, Failure>
-    where
-        Args: WasmTypeList,
-        Rets: WasmTypeList,
-        F: Fn(&FuncEnv, Outcome<Box<[wasmer::Val]>>) -> R,
-    {
-        match self.account_template(&call.target) {
-            Ok(template) => {
-                let storage = self.open_storage(&call.target, call.state, template.fixed_layout());
-
-                let mut env = FuncEnv::new(
-                    storage,
-                    call.envelope,
-                    call.context,
-                    call.template.clone(),
-                    call.target.clone(),
-                    call.protected_mode,
-                );
+        let state = receipt.init_state().clone();
+
+        let call = Call {
+            func_name: &spawn_call.func_name,
+            func_input: &spawn_call.calldata,
+            state: &state,
+            template: spawn.template_addr().clone(),
+            target: receipt.account_addr().clone(),
+            within_spawn: false,
+            gas_limit: gas_left,
+            protected_mode: ProtectedMode::FullAccess,
+            callers: &[],
+            envelope,
+            context,
+        };
 
-                let store = crate::wasm_store::new_store();
-                let import_object = self.create_import_object(&store, &mut env);
+        let mut call_receipt = self.exec_call::<(), ()>(&call);
 
-                let res = self.run::<Args, Rets>(&call, &store, &env, &template, &import_object);
-                res.map(|rets| f(&env, rets))
+        if call_receipt.success {
+            receipt.gas_used += call_receipt.gas_used.unwrap_or(0);
+
+            if let (Some(breakdown), Some(call_breakdown)) =
+                (receipt.gas_breakdown.as_mut(), call_receipt.gas_breakdown)
+            {
+                *breakdown += call_breakdown;
+            }
+            if let (Some(bloom), Some(call_bloom)) = (
+                receipt.logs_bloom.as_mut(),
+                call_receipt.logs_bloom.as_ref(),
+            ) {
+                *bloom |= call_bloom;
             }
-            Err(err) => Err(err.into()),
+            receipt.set_call(call_receipt);
+
+            receipt
+        } else {
+            let mut logs = receipt.take_logs();
+            logs.extend(call_receipt.take_logs());
+
+            SpawnReceipt::from_err(call_receipt.error.unwrap(), logs)
         }
     }
 
-    fn run<Args, Rets>(
-        &self,
-        call: &Call,
-        store: &wasmer::Store,
-        func_env: &FuncEnv,
-        template: &Template,
-        import_object: &wasmer::ImportObject,
-    ) -> Result<Box<[wasmer::Val]>>
-    where
-        Args: WasmTypeList,
-        Rets: WasmTypeList,
-    {
-        self.validate_call(call, template, func_env)?;
+    fn exec_call<Args, Rets>(&mut self, call: &Call) -> CallReceipt {
+        let receipt = self.dispatch(call, true);
 
-        let module = self.compile_template(store, func_env, &template, call.gas_limit)?;
-        let instance = self.instantiate(func_env, &module, import_object)?;
+        if call.protected_mode == ProtectedMode::FullAccess {
+            if let Some(state) = receipt.new_state.as_ref() {
+                self.state_roots
+                    .get_mut()
+                    .record(call.context.layer(), state.clone());
+            }
+        }
 
-        self.set_memory(func_env, &instance);
+        if self.metrics_observer.is_some() && !receipt.metrics.is_empty() {
+            self.pending_metrics.push(PendingMetrics {
+                account: call.target.clone(),
+                template: call.template.clone(),
+                metrics: receipt.metrics.clone(),
+            });
+        }
 
-        let func = self.func::<Args, Rets>(&instance, func_env, call.func_name)?;
+        receipt
+    }
 
-        let mut out = if call.func_input.len() > 0 {
-            self.call_with_alloc(&instance, func_env, call.func_input, &func, &[])?
-        } else {
+    /// Executes `call` without committing its storage changes.
+    fn exec_read_only(&self, call: &Call) -> CallReceipt {
+        self.dispatch(call, false)
+    }
+
+    /// Executes `call`, committing its storage changes only when `commit` is set.
+    ///
+    /// A `call` to an `Account` of a precompile [`Template`] is dispatched to the registered
+    /// [`Precompile`], and any other `call` runs the Wasm code of the [`Template`].
+    ///
+    /// Loading the [`Template`] is charged by the byte size of its loaded `Section`s
+    /// (see [`svm_gas::transaction::template_load`]), prior to executing anything.
+    ///
+    /// A `call` not committing its changes keeps the target `Account` locked for reading
+    /// until it ends (see [`Self::set_account_locks`]).
+    fn dispatch(&self, call: &Call, commit: bool) -> CallReceipt {
+        let (receipt, _staged) = self.dispatch_staged(call, commit, None);
+
+        receipt
+    }
+
+    /// Same as [`Self::dispatch`], while running a cross-account call against the storages `staged` by its caller
+    /// (when given, see [`Staged`]).
+    ///
+    /// Such a `call` never commits its changes. Once it succeeds, its storage is staged next to the `staged`
+    /// storages, and they're all returned (to be committed along with the caller's changes).
+    /// Still, `commit` tells whether the caller is going to commit.
+    fn dispatch_staged(
+        &self,
+        call: &Call,
+        commit: bool,
+        staged: Option<Staged>,
+    ) -> (CallReceipt, Staged) {
+        let _guard = if commit {
+            None
+        } else {
+            Some(self.account_locks.read(&call.target))
+        };
+
+        self.trace.borrow_mut().take();
+
+        let template = match self.account_template(&call.target) {
+            Ok(template) => template,
+            Err(err) => return (self.failure_to_receipt(err.into()), Staged::new()),
+        };
+
+        let load_price = self.template_load_price(&call.target);
+        let call = &match call.gas_limit - load_price {
+            Ok(gas_left) => Call {
+                gas_limit: gas_left,
+                ..call.clone()
+            },
+            Err(..) => return (CallReceipt::new_oog(Vec::new()), Staged::new()),
+        };
+
+        let nested = staged.is_some();
+        let mut staged = staged.unwrap_or_default();
+
+        // A staged storage of the target holds its changes made by the previous calls of the transaction
+        let storage = match staged
+            .iter()
+            .position(|storage| storage.account_addr() == &call.target)
+        {
+            Some(i) => staged.remove(i),
+            None => self.open_call_storage(call, &template),
+        };
+
+        let result = match template.code_section().kind() {
+            CodeKind::Precompile => {
+                let mut storage = storage;
+
+                self.exec_precompile(call, &template, &mut storage, commit && !nested)
+                    .map(|receipt| {
+                        if nested {
+                            staged.push(storage);
+                        }
+
+                        (receipt, staged)
+                    })
+            }
+            CodeKind::Wasm => {
+                let env = self.call_env(call, storage);
+                env.set_commit(commit);
+                env.set_staged(staged);
+
+                self.exec::<(), (), _, _>(call, &template, env, |env, out| {
+                    if nested {
+                        let receipt = self.outcome_to_query_receipt(env, out, call.state);
+
+                        (receipt, env.stage_storage())
+                    } else if commit {
+                        (self.outcome_to_receipt(env, out), Staged::new())
+                    } else {
+                        let receipt = self.outcome_to_query_receipt(env, out, call.state);
+
+                        (receipt, Staged::new())
+                    }
+                })
+            }
+        };
+
+        let (receipt, staged) =
+            result.unwrap_or_else(|fail| (self.failure_to_receipt(fail), Staged::new()));
+
+        (self.complete_receipt(call, receipt, load_price), staged)
+    }
+
+    /// Charges the `load_price` of the `Template` to a successful `receipt` of `call`,
+    /// and sets its `LogsBloom` (when [`Config::logs_bloom`] is set).
+    fn complete_receipt(
+        &self,
+        call: &Call,
+        mut receipt: CallReceipt,
+        load_price: u64,
+    ) -> CallReceipt {
+        if receipt.success {
+            receipt.gas_used += load_price;
+
+            if let Some(breakdown) = receipt.gas_breakdown.as_mut() {
+                breakdown.intrinsic += load_price;
+            }
+
+            if self.config.logs_bloom {
+                let bloom = LogsBloom::for_logs(&call.template, receipt.logs());
+                receipt.set_logs_bloom(bloom);
+            }
+        }
+
+        receipt
+    }
+
+    /// Executes `call` by the [`Precompile`] implementing `template` (see [`CodeKind::Precompile`]).
+    ///
+    /// The outcome of the [`Precompile`] is turned into a [`CallReceipt`] just like the outcome
+    /// of Wasm code. Once it uses more gas than `call.gas_limit`, the `call` fails with `OOG`.
+    fn exec_precompile(
+        &self,
+        call: &Call,
+        template: &Template,
+        storage: &mut AccountStorage,
+        commit: bool,
+    ) -> std::result::Result<CallReceipt, Failure> {
+        let precompile = template
+            .code_section()
+            .precompile_id()
+            .and_then(|id| self.precompiles.get(id));
+
+        let precompile = match precompile {
+            Some(precompile) => precompile,
+            None => {
+                return Err(RuntimeError::CompilationFailed {
+                    target: call.target.clone(),
+                    template: call.template.clone(),
+                    msg: "no precompile is registered for the `Template`".to_string(),
+                }
+                .into())
+            }
+        };
+
+        self.validate_call(call, template)?;
+
+        let phase = self.host_phase(call);
+        let precompile_call = PrecompileCall {
+            func_name: call.func_name,
+            calldata: call.func_input,
+            target: &call.target,
+            template: &call.template,
+            phase,
+            gas_limit: call.gas_limit,
+            envelope: call.envelope,
+            context: call.context,
+        };
+
+        let storage_access = match phase {
+            HostPhase::Verify => None,
+            HostPhase::Spawn | HostPhase::Call => Some(&mut *storage),
+        };
+
+        let out = match precompile.call(&precompile_call, storage_access) {
+            Ok(out) => out,
+            Err(PrecompileError::FuncNotFound) => {
+                return Err(RuntimeError::FuncNotFound {
+                    target: call.target.clone(),
+                    template: call.template.clone(),
+                    func: call.func_name.to_string(),
+                }
+                .into())
+            }
+            Err(PrecompileError::OOG) => return Err(RuntimeError::OOG.into()),
+            Err(PrecompileError::Failed(msg)) => {
+                return Err(RuntimeError::FuncFailed {
+                    target: call.target.clone(),
+                    template: call.template.clone(),
+                    func: call.func_name.to_string(),
+                    msg,
+                }
+                .into())
+            }
+        };
+
+        if call.gas_limit.is_some() && out.gas_used > call.gas_limit.unwrap() {
+            return Err(Failure::new(RuntimeError::OOG, out.logs));
+        }
+
+        let new_state = if commit {
+            match storage.commit() {
+                Ok(state) => state,
+                Err(err) => return Err(self.storage_corrupted(err, out.logs)),
+            }
+        } else {
+            call.state.clone()
+        };
+
+        let mut receipt = CallReceipt {
+            version: 0,
+            success: true,
+            error: None,
+            returndata: Some(out.returndata),
+            new_state: Some(new_state),
+            gas_used: Gas::with(out.gas_used),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
+            logs: out.logs,
+            metrics: Vec::new(),
+        };
+
+        if self.config.detailed_gas_accounting {
+            receipt.set_gas_breakdown(GasBreakdown {
+                execution: out.gas_used,
+                ..GasBreakdown::default()
+            });
+        }
+
+        Ok(receipt)
+    }
+
+    /// Computes the storage root of `account` against the global `state`.
+    ///
+    /// The root is the hash of the `Account`'s `Address` followed by the values of
+    /// its fixed storage variables (ordered by their `Id`), its balance and the digest
+    /// of its dynamic variables entries (see [`AccountStorage::dynamic_digest`]).
+    fn compute_account_root(&self, account: &Address, state: &State) -> Option<State> {
+        let template = self.account_template(account).ok()?;
+        let layout = template.fixed_layout();
+        let _guard = self.account_locks.read(account);
+        let storage = self.open_storage(account, state, layout);
+
+        let mut hasher = Blake3Hasher::default();
+        hasher.update(account.as_slice());
+
+        for var in layout.iter() {
+            match storage.read_var(var.id()) {
+                Ok(value) => hasher.update(&value),
+                Err(err) => {
+                    self.storage_corrupted(err, Vec::new());
+
+                    return None;
+                }
+            };
+        }
+
+        hasher.update(&storage.balance().to_be_bytes());
+        hasher.update(&storage.dynamic_digest());
+
+        Some(State::from(&hasher.finalize()[..]))
+    }
+
+    /// The most recently committed global `State` (or `State::zeros()` if there is none yet).
+    fn head_state(&self) -> State {
+        self.state_roots
+            .borrow()
+            .head()
+            .cloned()
+            .unwrap_or_else(State::zeros)
+    }
+
+    /// Summarizes the `Account` residing at `addr`, as of the most recently committed global `State`.
+    ///
+    /// Returns `None` if there is no such `Account`.
+    fn account_info(&self, addr: Address) -> Option<AccountInfo> {
+        let account = self.env.account_store().load(&addr)?;
+        let template = self.account_template(&addr).ok()?;
+
+        let balance = {
+            let head = self.head_state();
+            let _guard = self.account_locks.read(&addr);
+
+            self.open_storage(&addr, &head, template.fixed_layout())
+                .balance()
+        };
+        let state_root = self.current_state_root(&addr)?;
+
+        let info = AccountInfo {
+            template_addr: account.template_addr().clone(),
+            name: account.name().to_string(),
+            spawner: account.spawner().clone(),
+            addr_scheme: account.addr_scheme(),
+            addr,
+            balance,
+            state_root,
+        };
+
+        Some(info)
+    }
+
+    /// Executes a cross-account call issued by running code (see [`vmcalls::call`]).
+    ///
+    /// The call stages its storage changes (see [`Staged`]), returning them along with its receipt.
+    fn nested_call(
+        &self,
+        nested: NestedCall,
+        envelope: &Envelope,
+        context: &Context,
+    ) -> (CallReceipt, Staged) {
+        let template = match self.env.resolve_template_addr(&nested.target) {
+            Some(template) => template,
+            None => {
+                let err = RuntimeError::AccountNotFound(nested.target.clone());
+                return (CallReceipt::from_err(err, Vec::new()), Staged::new());
+            }
+        };
+
+        let call = Call {
+            func_name: &nested.func_name,
+            func_input: nested.calldata,
+            target: nested.target.clone(),
+            template,
+            state: &nested.state,
+            gas_limit: nested.gas_limit,
+            within_spawn: false,
+            context,
+            envelope,
+            protected_mode: nested.protected_mode,
+            callers: &nested.callers,
+        };
+
+        self.dispatch_staged(&call, nested.commit, Some(nested.staged))
+    }
+
+    /// Returns a [`NestedCaller`] dispatching the cross-account calls to [`Self::nested_call`].
+    fn nested_caller(&self) -> NestedCaller {
+        unsafe fn call<T>(
+            runtime: *const (),
+            nested: NestedCall,
+            envelope: &Envelope,
+            context: &Context,
+        ) -> (CallReceipt, Staged)
+        where
+            T: EnvTypes,
+        {
+            let runtime = &*(runtime as *const DefaultRuntime<T>);
+
+            runtime.nested_call(nested, envelope, context)
+        }
+
+        unsafe fn account_exists<T>(runtime: *const (), addr: &Address) -> bool
+        where
+            T: EnvTypes,
+        {
+            let runtime = &*(runtime as *const DefaultRuntime<T>);
+
+            runtime.env.contains_account(addr)
+        }
+
+        NestedCaller::new(
+            self as *const Self as *const (),
+            call::<T>,
+            account_exists::<T>,
+        )
+    }
+
+    fn exec<Args, Rets, F, R>(
+        &self,
+        call: &Call,
+        template: &Template,
+        mut env: FuncEnv,
+        f: F,
+    ) -> std::result::Result<R, Failure>
+    where
+        Args: WasmTypeList,
+        Rets: WasmTypeList,
+        F: Fn(&FuncEnv, Outcome<Box<[wasmer::Val]>>) -> R,
+    {
+        self.prepare_env(&env, call);
+
+        let res = self.template_module(&env, template).and_then(|module| {
+            let import_object = self.create_import_object(module.store(), &mut env, call);
+
+            // Safety: `self` outlives the running code, and the `NestedCaller` is unset right after it completes
+            unsafe { env.set_nested_caller(Some(self.nested_caller())) };
+            let res = self.run::<Args, Rets>(&call, &env, template, &module, &import_object);
+            unsafe { env.set_nested_caller(None) };
+
+            res
+        });
+
+        *self.trace.borrow_mut() = env.borrow_mut().take_trace();
+
+        res.map(|rets| f(&env, rets))
+    }
+
+    /// Creates the [`FuncEnv`] for running `call` against `storage`.
+    fn call_env(&self, call: &Call, storage: AccountStorage) -> FuncEnv {
+        FuncEnv::new(
+            storage,
+            call.envelope,
+            call.context,
+            call.template.clone(),
+            call.target.clone(),
+            call.protected_mode,
+        )
+    }
+
+    /// Opens the [`AccountStorage`] `call` runs against.
+    ///
+    /// A `call` running a `ctor` gets the storage of a fresh `Account`, initialized
+    /// with its default values and its endowment.
+    fn open_call_storage(&self, call: &Call, template: &Template) -> AccountStorage {
+        let mut storage = self.open_template_storage(&call.target, call.state, template);
+
+        if call.within_spawn {
+            self.apply_defaults(&mut storage, template);
+            self.endow(&mut storage, call.envelope);
+        }
+
+        storage
+    }
+
+    /// Sets up `env` (holding the storage of `call.target`) for running `call`.
+    fn prepare_env(&self, env: &FuncEnv, call: &Call) {
+        if let Some(limit) = self.config.trace_limit {
+            env.enable_trace(limit);
+        }
+
+        if self.config.detailed_gas_accounting {
+            env.enable_vmcall_counts();
+        }
+
+        env.set_call_stack(CallStack::new(call.callers, &call.target));
+        env.borrow_mut().set_gas_limit(call.gas_limit);
+    }
+
+    fn run<Args, Rets>(
+        &self,
+        call: &Call,
+        func_env: &FuncEnv,
+        template: &Template,
+        module: &Module,
+        import_object: &wasmer::ImportObject,
+    ) -> Result<Box<[wasmer::Val]>>
+    where
+        Args: WasmTypeList,
+        Rets: WasmTypeList,
+    {
+        self.validate_call(call, template)?;
+
+        self.run_module::<Args, Rets>(call, func_env, template, module, import_object)
+    }
+
+    /// Runs `call` by a fresh instance of `module` (the compiled code of `template`).
+    fn run_module<Args, Rets>(
+        &self,
+        call: &Call,
+        func_env: &FuncEnv,
+        template: &Template,
+        module: &Module,
+        import_object: &wasmer::ImportObject,
+    ) -> Result<Box<[wasmer::Val]>>
+    where
+        Args: WasmTypeList,
+        Rets: WasmTypeList,
+    {
+        let instance = self.instantiate(func_env, module, import_object)?;
+
+        Metering::set_gas_limit(&instance, call.gas_limit);
+        self.set_memory(func_env, &instance);
+
+        let (func_name, func_input) = self.resolve_func(call, template, &instance, func_env)?;
+        let func = self.func::<Args, Rets>(&instance, func_env, func_name)?;
+
+        let mut out = if func_input.len() > 0 {
+            self.call_with_alloc(&instance, func_env, &func_input, &func, &[])?
+        } else {
             self.wasmer_call(&instance, func_env, &func, &[])?
         };
 
         let logs = out.take_logs();
+        let nested_gas_used = func_env.borrow().nested_gas_used();
 
-        match self.instance_gas_used(&instance) {
+        // The gas used by cross-account calls isn't metered, so it's added (and checked) here
+        let gas_used = self.instance_gas_used(&instance).and_then(|gas_used| {
+            let gas_used = gas_used + nested_gas_used;
+
+            if call.gas_limit.is_some() && gas_used.unwrap_or(0) > call.gas_limit.unwrap() {
+                Err(OOGError)
+            } else {
+                Ok(gas_used)
+            }
+        });
+
+        match gas_used {
             Ok(gas_used) => {
                 let returns = out.take_returns();
                 let out = Outcome::new(returns, gas_used, logs);
@@ -220,6 +1294,53 @@ where
         }
     }
 
+    /// Resolves the function (and its `calldata`) a [`Call`] should be dispatched to.
+    ///
+    /// When the requested function isn't exported and the `Template` has a `fallback` function
+    /// (see [`ApiSection`](svm_types::ApiSection)), the `Call` is dispatched to the `fallback`.
+    /// The `fallback` is given the requested function name along with the original `calldata`:
+    ///
+    /// +---------------+-----------------+--------------------+
+    /// |  Name Length  |  Function Name  |  Original Calldata |
+    /// |   (1 byte)    |     (UTF-8)     |                    |
+    /// +---------------+-----------------+--------------------+
+    fn resolve_func<'a>(
+        &self,
+        call: &Call<'a>,
+        template: &'a Template,
+        instance: &Instance,
+        env: &FuncEnv,
+    ) -> std::result::Result<(&'a str, Cow<'a, [u8]>), Failure> {
+        let requested = (call.func_name, Cow::Borrowed(call.func_input));
+
+        if call.within_spawn || instance.exports.get_function(call.func_name).is_ok() {
+            return Ok(requested);
+        }
+
+        let fallback = template.api_section().and_then(|api| api.fallback());
+
+        match fallback {
+            None => Ok(requested),
+            Some(fallback) if template.is_ctor(fallback) => {
+                let msg = "a `fallback` function can't be a constructor";
+                let err = self.func_not_allowed(env, fallback, msg);
+
+                Err(err)
+            }
+            Some(fallback) => {
+                let name = call.func_name.as_bytes();
+                debug_assert!(name.len() <= std::u8::MAX as usize);
+
+                let mut calldata = Vec::with_capacity(1 + name.len() + call.func_input.len());
+                calldata.push(name.len() as u8);
+                calldata.extend_from_slice(name);
+                calldata.extend_from_slice(call.func_input);
+
+                Ok((fallback, Cow::Owned(calldata)))
+            }
+        }
+    }
+
     fn call_with_alloc<Args, Rets>(
         &self,
         instance: &Instance,
@@ -298,7 +1419,31 @@ where
         let logs = env.borrow_mut().take_logs();
 
         if returns.is_err() {
-            let err = self.func_failed(env, func.name(), returns.unwrap_err(), logs);
+            if Metering::exhausted(instance) {
+                return Err(Failure::new(RuntimeError::OOG, logs));
+            }
+
+            // A failed `svm_transfer` (or a corrupted storage read) fails the transaction with its own error
+            let err = match returns.unwrap_err().downcast::<InsufficientBalance>() {
+                Ok(err) => {
+                    let err = RuntimeError::InsufficientBalance {
+                        target: err.account,
+                        balance: err.balance,
+                        amount: err.amount,
+                    };
+
+                    Failure::new(err, logs)
+                }
+                Err(err) => match err.downcast::<UnknownAccount>() {
+                    Ok(UnknownAccount(addr)) => {
+                        Failure::new(RuntimeError::AccountNotFound(addr), logs)
+                    }
+                    Err(err) => match err.downcast::<PageCorrupted>() {
+                        Ok(err) => self.storage_corrupted(err, logs),
+                        Err(err) => self.func_failed(env, func.name(), err, logs),
+                    },
+                },
+            };
             return Err(err);
         }
 
@@ -314,9 +1459,15 @@ where
         }
     }
 
+    /// Commits the storage changes of `env`, along with the ones staged by its cross-account calls (see [`Staged`]).
     #[inline]
-    fn commit_changes(&self, env: &FuncEnv) -> State {
+    fn commit_changes(&self, env: &FuncEnv) -> std::result::Result<State, PageCorrupted> {
         let mut borrow = env.borrow_mut();
+
+        for mut storage in borrow.take_staged() {
+            let _state = storage.commit()?;
+        }
+
         let storage = borrow.storage_mut();
         storage.commit()
     }
@@ -390,11 +1541,10 @@ where
         env.borrow_mut().set_calldata(offset, len);
     }
 
-    /// Calculates the amount of gas used by `instance`.
+    /// Calculates the amount of gas used by `instance` (see [`Metering`]).
     #[inline]
-    fn instance_gas_used(&self, _instance: &Instance) -> std::result::Result<Gas, OOGError> {
-        // TODO: read `gas_used` out of `instance`
-        Ok(Gas::new())
+    fn instance_gas_used(&self, instance: &Instance) -> std::result::Result<Gas, OOGError> {
+        Metering::gas_used(instance)
     }
 
     fn instantiate(
@@ -441,6 +1591,7 @@ where
         &self,
         store: &wasmer::Store,
         env: &mut FuncEnv,
+        call: &Call,
     ) -> wasmer::ImportObject {
         let mut import_object = wasmer::ImportObject::new();
 
@@ -449,26 +1600,70 @@ where
         vmcalls::wasmer_register(store, env, &mut internals);
         import_object.register("svm", internals);
 
-        // Registering the externals provided to the Runtime
-        let (name, exports) = &self.imports;
-        debug_assert_ne!(name, "svm");
+        // Registering the host modules provided to the Runtime
+        let phase = self.host_phase(call);
 
-        import_object.register(name, exports.clone());
+        self.host_modules
+            .register_into(&mut import_object, store, phase);
 
         import_object
     }
 
+    /// The kind of execution `call` is (see [`HostPhase`]).
+    fn host_phase(&self, call: &Call) -> HostPhase {
+        if call.within_spawn {
+            HostPhase::Spawn
+        } else if call.protected_mode == ProtectedMode::AccessDenied {
+            HostPhase::Verify
+        } else {
+            HostPhase::Call
+        }
+    }
+
     fn account_template(
         &self,
         account_addr: &Address,
     ) -> std::result::Result<Template, RuntimeError> {
+        let interests = Self::template_interests();
+
+        let template = self.env.account_template(account_addr, Some(interests));
+        template.ok_or_else(|| RuntimeError::AccountNotFound(account_addr.clone()))
+    }
+
+    /// The price of loading the `Template` of `account_addr` (as done by [`Self::account_template`]).
+    ///
+    /// Only the previews of the `Template`'s `Section`s are read for computing the loaded byte size.
+    fn template_load_price(&self, account_addr: &Address) -> u64 {
+        let interests = Self::template_interests();
+        let byte_size = self
+            .env
+            .account_template_byte_size(account_addr, Some(&interests))
+            .unwrap_or(0);
+
+        svm_gas::transaction::template_load(byte_size)
+    }
+
+    /// Same as [`Self::template_load_price`], but given the `Template`'s own `Address`.
+    fn template_addr_load_price(&self, template_addr: &TemplateAddr) -> u64 {
+        let interests = Self::template_interests();
+        let byte_size = self
+            .env
+            .template_byte_size(template_addr, Some(&interests))
+            .unwrap_or(0);
+
+        svm_gas::transaction::template_load(byte_size)
+    }
+
+    /// The kinds of the `Template`'s `Section`s required for executing its code.
+    fn template_interests() -> HashSet<SectionKind> {
         let mut interests = HashSet::new();
         interests.insert(SectionKind::Code);
         interests.insert(SectionKind::Data);
         interests.insert(SectionKind::Ctors);
+        interests.insert(SectionKind::Defaults);
+        interests.insert(SectionKind::Api);
 
-        let template = self.env.account_template(account_addr, Some(interests));
-        template.ok_or_else(|| RuntimeError::AccountNotFound(account_addr.clone()))
+        interests
     }
 
     fn compile_template(
@@ -476,20 +1671,60 @@ where
         store: &wasmer::Store,
         env: &FuncEnv,
         template: &Template,
-        gas_left: Gas,
     ) -> std::result::Result<Module, Failure> {
         let module_res = Module::from_binary(store, template.code());
-        let _gas_left = gas_left.unwrap_or(0);
 
         module_res.map_err(|err| self.compilation_failed(env, err))
     }
 
-    fn validate_call(
+    /// Returns the compiled code of `template`, instrumented by its [`Metering`].
+    ///
+    /// The code is compiled by a `Store` leased from the [`StorePool`], which caches the module
+    /// (keyed by the code and the gas schedule). Since the gas limit is set per instance,
+    /// the executions of a `Template` share its module whatever their gas limits.
+    fn template_module(
         &self,
-        call: &Call,
+        env: &FuncEnv,
         template: &Template,
+    ) -> std::result::Result<Module, Failure> {
+        let key = ModuleKey::new(template.code(), self.env.pricing_version());
+
+        if let Some(module) = self.stores.module(&key) {
+            return Ok(module);
+        }
+
+        let metering = self.metering(env, template)?;
+        let lease = self.stores.acquire(metering);
+        let module = self.compile_template(lease.store(), env, template)?;
+
+        self.stores.cache_module(key, module.clone());
+
+        Ok(module)
+    }
+
+    /// Creates the [`Metering`] charging the gas of running the code of `template`.
+    ///
+    /// The code is instrumented while compiled by a `Store` leased from the [`StorePool`].
+    fn metering(
+        &self,
         env: &FuncEnv,
-    ) -> std::result::Result<(), Failure> {
+        template: &Template,
+    ) -> std::result::Result<Arc<Metering>, Failure> {
+        let program = Program::new(template.code(), false).map_err(|err| {
+            Failure::from(RuntimeError::CompilationFailed {
+                target: env.target_addr(),
+                template: env.template_addr().clone(),
+                msg: err.to_string(),
+            })
+        })?;
+
+        let resolver = self.env.price_resolver();
+        let metering = Metering::new(&program, &*resolver);
+
+        Ok(Arc::new(metering))
+    }
+
+    fn validate_call(&self, call: &Call, template: &Template) -> std::result::Result<(), Failure> {
         // TODO: validate there is enough gas for running the `Transaction`.
         // * verify
         // * call
@@ -498,18 +1733,24 @@ where
         let spawning = call.within_spawn;
         let ctor = template.is_ctor(call.func_name);
 
+        let func_not_allowed = |msg: &str| -> Failure {
+            RuntimeError::FuncNotAllowed {
+                target: call.target.clone(),
+                template: call.template.clone(),
+                func: call.func_name.to_string(),
+                msg: msg.to_string(),
+            }
+            .into()
+        };
+
         if spawning && !ctor {
             let msg = "expected function to be a constructor";
-            let err = self.func_not_allowed(env, call.func_name, msg);
-
-            return Err(err);
+            return Err(func_not_allowed(msg));
         }
 
         if !spawning && ctor {
             let msg = "expected function to be a non-constructor";
-            let err = self.func_not_allowed(env, call.func_name, msg);
-
-            return Err(err);
+            return Err(func_not_allowed(msg));
         }
 
         Ok(())
@@ -537,6 +1778,7 @@ where
                 gas_limit: envelope.gas_limit(),
                 protected_mode,
                 within_spawn: false,
+                callers: &[],
                 envelope,
                 context,
             }
@@ -550,7 +1792,7 @@ where
     #[inline]
     fn func_not_found(&self, env: &FuncEnv, func_name: &str) -> Failure {
         RuntimeError::FuncNotFound {
-            target: env.target_addr().clone(),
+            target: env.target_addr(),
             template: env.template_addr().clone(),
             func: func_name.to_string(),
         }
@@ -560,7 +1802,7 @@ where
     #[inline]
     fn instantiation_failed(&self, env: &FuncEnv, err: wasmer::InstantiationError) -> Failure {
         RuntimeError::InstantiationFailed {
-            target: env.target_addr().clone(),
+            target: env.target_addr(),
             template: env.template_addr().clone(),
             msg: err.to_string(),
         }
@@ -570,7 +1812,7 @@ where
     #[inline]
     fn func_not_allowed(&self, env: &FuncEnv, func_name: &str, msg: &str) -> Failure {
         RuntimeError::FuncNotAllowed {
-            target: env.target_addr().clone(),
+            target: env.target_addr(),
             template: env.template_addr().clone(),
             func: func_name.to_string(),
             msg: msg.to_string(),
@@ -578,10 +1820,18 @@ where
         .into()
     }
 
+    #[inline]
+    fn upgrade_not_allowed(&self, template: &TemplateAddr, msg: &str) -> RuntimeError {
+        RuntimeError::UpgradeNotAllowed {
+            template: template.clone(),
+            msg: msg.to_string(),
+        }
+    }
+
     #[inline]
     fn func_invalid_sig(&self, env: &FuncEnv, func_name: &str) -> Failure {
         RuntimeError::FuncInvalidSignature {
-            target: env.target_addr().clone(),
+            target: env.target_addr(),
             template: env.template_addr().clone(),
             func: func_name.to_string(),
         }
@@ -597,7 +1847,7 @@ where
         logs: Vec<ReceiptLog>,
     ) -> Failure {
         let err = RuntimeError::FuncFailed {
-            target: env.target_addr().clone(),
+            target: env.target_addr(),
             template: env.template_addr().clone(),
             func: func_name.to_string(),
             msg: err.to_string(),
@@ -606,75 +1856,205 @@ where
         Failure::new(err, logs)
     }
 
+    /// Records a read of a corrupted storage page (see [`RuntimeMetrics::storage_corruptions`]),
+    /// failing the transaction with a [`RuntimeError::StorageCorrupted`].
+    fn storage_corrupted(&self, err: PageCorrupted, logs: Vec<ReceiptLog>) -> Failure {
+        error!(
+            "Transaction execution has read a corrupted storage: {}",
+            err
+        );
+
+        self.metrics.record_storage_corruption();
+
+        let err = RuntimeError::StorageCorrupted {
+            target: err.account.clone(),
+            msg: err.to_string(),
+        };
+
+        Failure::new(err, logs)
+    }
+
     #[inline]
     fn compilation_failed(&self, env: &FuncEnv, err: wasmer::CompileError) -> Failure {
         RuntimeError::CompilationFailed {
-            target: env.target_addr().clone(),
+            target: env.target_addr(),
             template: env.template_addr().clone(),
             msg: err.to_string(),
         }
         .into()
     }
-}
 
-impl<T> Runtime for DefaultRuntime<T>
-where
-    T: EnvTypes,
-{
-    fn validate_deploy(&self, message: &[u8]) -> std::result::Result<(), ValidateError> {
-        let template = self.env.parse_deploy(message, None)?;
-        let code = template.code();
+    /// Validates a `Template` prior to deploying (or upgrading into) it.
+    fn validate_template(&self, template: &Template) -> std::result::Result<(), ValidateError> {
+        let code_section = template.code_section();
 
-        // Opcode and `svm_alloc` checks should only ever be run when deploying [`Template`]s.
-        // There's no reason to also do it when spawning new `Account`
-        // over already-validated [`Template`]s
-        let program = Program::new(code, true).map_err(ValidateError::from)?;
-        svm_gas::validate_wasm(&program, false).map_err(ValidateError::from)?;
+        match code_section.kind() {
+            CodeKind::Wasm => {
+                // Opcode and `svm_alloc` checks should only ever be run when deploying [`Template`]s.
+                // There's no reason to also do it when spawning new `Account`
+                // over already-validated [`Template`]s
+                let program =
+                    Program::new(code_section.code(), true).map_err(ValidateError::from)?;
+
+                // The code of a [`GasMode::Metering`] `Template` is charged while running,
+                // so it's free to loop (or recurse).
+                if code_section.is_fixed_gas() {
+                    svm_gas::validate_wasm(&program, false).map_err(ValidateError::from)?;
+                }
+            }
+            CodeKind::Precompile => {
+                let id = code_section
+                    .precompile_id()
+                    .ok_or(ValidateError::InvalidPrecompileId)?;
 
-        Ok(())
-    }
+                if self.precompiles.get(id).is_none() {
+                    return Err(ValidateError::UnknownPrecompile(id));
+                }
+            }
+        }
 
-    fn validate_spawn(&self, message: &[u8]) -> std::result::Result<(), ValidateError> {
-        self.env
-            .parse_spawn(message)
-            .map(|_| ())
-            .map_err(Into::into)
-    }
+        if let Some(defaults) = template.defaults_section() {
+            validate_defaults(defaults, template.fixed_layout())?;
+        }
 
-    fn validate_call(&self, message: &[u8]) -> std::result::Result<(), ValidateError> {
-        self.env
-            .parse_call(message)
-            .map(|_| ())
-            .map_err(|e| e.into())
+        let data = template.data_section();
+        if let Some(budget) = data.max_storage_bytes() {
+            let size = data.storage_bytes();
+
+            if size > budget as u64 {
+                return Err(ValidateError::StorageBudget { size, budget });
+            }
+        }
+
+        Ok(())
     }
 
-    fn deploy(&mut self, envelope: &Envelope, message: &[u8], _context: &Context) -> DeployReceipt {
+    fn deploy_template(
+        &mut self,
+        envelope: &Envelope,
+        message: &[u8],
+        context: &Context,
+    ) -> DeployReceipt {
         info!("Runtime `deploy`");
 
-        let template = self
+        let mut template = self
             .env
             .parse_deploy(message, None)
             .expect("Should have called `validate_deploy` first");
 
         let gas_limit = envelope.gas_limit();
-        let install_price = svm_gas::transaction::deploy(message);
+        let install_price = svm_gas::transaction::deploy(message) + data_segments_price(&template);
 
         if gas_limit >= install_price {
             let gas_used = Gas::with(install_price);
             let addr = self.env.compute_template_addr(&template);
+
+            let deploy = DeploySection::new(
+                context.tx_id().clone(),
+                context.layer(),
+                envelope.principal().clone(),
+                addr.clone(),
+            )
+            .with_addr_scheme(self.env.addr_scheme());
+            template.set_deploy_section(deploy);
+
             self.env.store_template(&template, &addr);
 
-            DeployReceipt::new(addr, gas_used)
+            let mut receipt = DeployReceipt::new(addr, gas_used);
+
+            if self.config.detailed_gas_accounting {
+                receipt.set_gas_breakdown(GasBreakdown {
+                    intrinsic: install_price,
+                    ..GasBreakdown::default()
+                });
+            }
+
+            receipt
         } else {
             DeployReceipt::new_oog()
         }
     }
 
-    fn spawn(&mut self, envelope: &Envelope, message: &[u8], context: &Context) -> SpawnReceipt {
-        // TODO: refactor this function (it has got a bit lengthy...)
+    fn upgrade_template(
+        &mut self,
+        envelope: &Envelope,
+        message: &[u8],
+        context: &Context,
+    ) -> UpgradeReceipt {
+        info!("Runtime `upgrade`");
 
-        use svm_gas::ProgramPricing;
-        use svm_program::ProgramVisitor;
+        let upgrade = self
+            .env
+            .parse_upgrade(message)
+            .expect("Should have called `validate_upgrade` first");
+
+        let gas_limit = envelope.gas_limit();
+        let upgrade_price =
+            svm_gas::transaction::upgrade(message) + data_segments_price(upgrade.template());
+
+        if gas_limit < upgrade_price {
+            return UpgradeReceipt::new_oog();
+        }
+
+        let addr = upgrade.template_addr().clone();
+
+        let mut interests = HashSet::new();
+        interests.insert(SectionKind::Deploy);
+        interests.insert(SectionKind::Data);
+
+        let current = match self.env.template(&addr, Some(interests)) {
+            Some(template) => template,
+            None => {
+                return UpgradeReceipt::from_err(RuntimeError::TemplateNotFound(addr), Vec::new())
+            }
+        };
+
+        let deploy = current.deploy_section();
+        if deploy.deployer() != envelope.principal() {
+            let msg = "Only the original deployer of a `Template` may upgrade it";
+            return UpgradeReceipt::from_err(self.upgrade_not_allowed(&addr, msg), Vec::new());
+        }
+
+        if !preserves_layout(current.fixed_layout(), upgrade.template().fixed_layout()) {
+            let msg = "The upgraded `Template` must keep the existing storage variables";
+            return UpgradeReceipt::from_err(self.upgrade_not_allowed(&addr, msg), Vec::new());
+        }
+
+        let mut template = upgrade.template;
+        let deploy = DeploySection::new(
+            context.tx_id().clone(),
+            context.layer(),
+            envelope.principal().clone(),
+            addr.clone(),
+        )
+        .with_addr_scheme(deploy.addr_scheme());
+        template.set_deploy_section(deploy);
+
+        let template_version = self.env.upgrade_template(&template, &addr);
+
+        // The `Account`s of the `Template` run the upgraded code from now on.
+        self.price_cache.borrow_mut().remove(&addr);
+        self.query_cache = RefCell::new(self.config.query_cache.clone().map(QueryCache::new));
+
+        let mut receipt = UpgradeReceipt::new(addr, template_version, Gas::with(upgrade_price));
+
+        if self.config.detailed_gas_accounting {
+            receipt.set_gas_breakdown(GasBreakdown {
+                intrinsic: upgrade_price,
+                ..GasBreakdown::default()
+            });
+        }
+
+        receipt
+    }
+
+    fn spawn_account(
+        &mut self,
+        envelope: &Envelope,
+        message: &[u8],
+        context: &Context,
+    ) -> SpawnReceipt {
+        // TODO: refactor this function (it has got a bit lengthy...)
 
         info!("Runtime `spawn`");
 
@@ -686,83 +2066,484 @@ where
 
         let template_addr = base.account.template_addr();
 
-        // TODO: load only the `Sections` relevant for spawning
+        // Only the `Code`, `Ctors` and `API` are needed for spawning (the other `Section`s are skipped).
+        let mut interests = HashSet::new();
+        interests.insert(SectionKind::Code);
+        interests.insert(SectionKind::Ctors);
+        interests.insert(SectionKind::Api);
+
         let template = self
             .env
-            .template(template_addr, None)
+            .template(template_addr, Some(interests))
             .expect("Should have failed earlier when doing `validate_spawn`");
 
-        let code_section = template.code_section();
-        let code = code_section.code();
-        let gas_mode = code_section.gas_mode();
-        let program = Program::new(code, false).unwrap();
-
-        // We're using a naive memoization mechanism: we only ever add, never
-        // remove. This means there's no cache invalidation at all. We can
-        // easily afford to do this because the number of templates that exist
-        // at genesis is fixed and won't grow.
-        let mut template_prices = self.template_prices.borrow_mut();
-        let func_price = {
-            if let Some(prices) = template_prices.get(&template_addr) {
-                prices
-            } else {
-                let pricer = self.env.price_resolver();
-                let program_pricing = ProgramPricing::new(pricer);
-                let prices = program_pricing.visit(&program).unwrap();
+        let spawner = envelope.principal();
+        let spawn = ExtSpawn::new(base, &spawner);
 
-                template_prices.insert(template_addr.clone(), prices);
-                template_prices.get(template_addr).unwrap()
+        let ctor_name = match Self::resolve_ctor(&template, &spawn) {
+            Ok(ctor_name) => ctor_name,
+            Err(msg) => {
+                // The [`Template`] is faulty (or the `Calldata` doesn't fit the `ctor`).
+                let account = ExtAccount::new(spawn.account(), &spawner);
+                let account_addr = self.env.compute_account_addr(&spawn);
+                return SpawnReceipt::from_err(
+                    RuntimeError::FuncNotAllowed {
+                        target: account_addr,
+                        template: account.template_addr().clone(),
+                        func: spawn.ctor_name().to_string(),
+                        msg,
+                    },
+                    vec![],
+                );
             }
         };
 
-        let spawner = envelope.principal();
-        let spawn = ExtSpawn::new(base, &spawner);
-
-        if !template.is_ctor(spawn.ctor_name()) {
-            // The [`Template`] is faulty.
-            let account = ExtAccount::new(spawn.account(), &spawner);
-            let account_addr = self.env.compute_account_addr(&spawn);
-            return SpawnReceipt::from_err(
-                RuntimeError::FuncNotAllowed {
-                    target: account_addr,
-                    template: account.template_addr().clone(),
-                    func: spawn.ctor_name().to_string(),
-                    msg: "The given function is not a `ctor`.".to_string(),
-                },
-                vec![],
-            );
-        }
+        let price = self.func_price(spawn.template_addr(), &template, ctor_name);
 
-        match gas_mode {
-            GasMode::Fixed => {
-                let ctor_func_index = program.exports().get(spawn.ctor_name()).unwrap();
-                let price = func_price.get(ctor_func_index) as u64;
-                if gas_limit <= price {
-                    return SpawnReceipt::new_oog(vec![]);
-                }
-            }
-            GasMode::Metering => unreachable!("Not supported yet... (TODO)"),
+        if matches!(price, Some(price) if gas_limit <= price) {
+            return SpawnReceipt::new_oog(vec![]);
         }
 
-        // We don't need this anymore!
-        drop(template_prices);
-
         let payload_price = svm_gas::transaction::spawn(message);
         let gas_left = gas_limit - payload_price;
 
         match gas_left {
             Ok(gas_left) => {
-                let account = ExtAccount::new(spawn.account(), &spawner);
+                let account = ExtAccount::new(spawn.account(), &spawner)
+                    .with_addr_scheme(self.env.addr_scheme());
                 let target = self.env.compute_account_addr(&spawn);
 
                 self.env.store_account(&account, &target);
-                self.call_ctor(&spawn, target, gas_left, envelope, context)
+
+                let receipt =
+                    self.call_ctor(&spawn, ctor_name, target, gas_left, envelope, context);
+                let mut receipt =
+                    self.call_after_ctor(&spawn, receipt, gas_left, envelope, context);
+
+                if let Some(breakdown) = receipt.gas_breakdown.as_mut() {
+                    breakdown.intrinsic += payload_price;
+                }
+
+                receipt
             }
             Err(..) => SpawnReceipt::new_oog(Vec::new()),
         }
     }
 
-    fn verify(&mut self, envelope: &Envelope, message: &[u8], context: &Context) -> CallReceipt {
+    /// Resolves the Wasm name of the `ctor` of `template` to run for `spawn`.
+    ///
+    /// When the `Template` has an `API Section`, the `ctor` may be asked for by the name shared by its overloads
+    /// (resolved by the types of the `ctor`'s `Calldata`), and the `Calldata` has to match the signature of the `ctor`
+    /// (see [`svm_codec::signature::resolve_ctor`]). A `ctor` missing from the `API Section` is run unchecked.
+    ///
+    /// On failure returns the reason for rejecting the `spawn`.
+    fn resolve_ctor<'t>(
+        template: &'t Template,
+        spawn: &'t ExtSpawn,
+    ) -> std::result::Result<&'t str, String> {
+        use svm_codec::signature::{self, OverloadError};
+
+        let resolved = match template.api_section() {
+            Some(api) => signature::resolve_ctor(api, spawn.ctor_name(), spawn.ctor_data())
+                .map(|ctor| ctor.wasm_name.as_str()),
+            None => Err(OverloadError::NotFound),
+        };
+
+        match resolved {
+            Ok(ctor_name) if template.is_ctor(ctor_name) => Ok(ctor_name),
+            Err(OverloadError::NotFound) if template.is_ctor(spawn.ctor_name()) => {
+                Ok(spawn.ctor_name())
+            }
+            Ok(..) | Err(OverloadError::NotFound) => Err(OverloadError::NotFound.to_string()),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    /// Spawns `spawns` in the deferred mode (see [`Runtime::spawn_bulk`]).
+    ///
+    /// All the `Account`s are created first, and only then their `ctor`s are run, grouped by
+    /// their `Template`, `ctor` and gas limit (see [`Self::exec_deferred`]). A spawn which can't be
+    /// deferred (see [`Self::defer_spawn`]) is executed right away by [`Runtime::spawn`].
+    ///
+    /// Each `ctor` commits its own changes (so each receipt has its own `init_state`), while the global
+    /// `State` left by the batch is recorded once (see [`StateRoots`]), along with the metrics of its `ctor`s.
+    fn spawn_deferred(
+        &mut self,
+        spawns: &[(Envelope, Vec<u8>)],
+        context: &Context,
+        progress: &mut dyn FnMut(BulkSpawnProgress),
+    ) -> Vec<SpawnReceipt> {
+        let total = spawns.len();
+        let mut done = 0;
+        let mut receipts: Vec<Option<SpawnReceipt>> = vec![None; total];
+
+        let mut templates = HashMap::new();
+        let mut groups: Vec<DeferredGroup> = Vec::new();
+
+        for (index, (envelope, message)) in spawns.iter().enumerate() {
+            match self.defer_spawn(index, envelope, message, &mut templates) {
+                Some(deferred) => {
+                    let account = ExtAccount::new(deferred.spawn.account(), envelope.principal())
+                        .with_addr_scheme(self.env.addr_scheme());
+
+                    self.env.store_account(&account, &deferred.target);
+
+                    match groups.iter_mut().find(|group| group.accepts(&deferred)) {
+                        Some(group) => group.spawns.push(deferred),
+                        None => groups.push(DeferredGroup::new(deferred)),
+                    }
+                }
+                None => {
+                    receipts[index] = Some(self.spawn(envelope, message, context));
+
+                    done += 1;
+                    progress(BulkSpawnProgress { done, total });
+                }
+            }
+        }
+
+        let gas_schedule_version = self.config.gas_schedule_version;
+        let observed = self.metrics_observer.is_some();
+        let mut pending = Vec::new();
+        let mut last_state = None;
+
+        for group in groups.iter() {
+            let template = &templates[&group.template_addr];
+
+            self.exec_deferred(group, template, context, &mut |deferred, receipt| {
+                if let Some(state) = receipt.new_state.as_ref() {
+                    last_state = Some(state.clone());
+                }
+
+                if observed && receipt.success && !receipt.metrics.is_empty() {
+                    pending.push(PendingMetrics {
+                        account: deferred.target.clone(),
+                        template: group.template_addr.clone(),
+                        metrics: receipt.metrics.clone(),
+                    });
+                }
+
+                let mut receipt = svm_types::into_spawn_receipt(receipt, &deferred.target);
+
+                if let Some(breakdown) = receipt.gas_breakdown.as_mut() {
+                    breakdown.intrinsic += deferred.payload_price;
+                }
+                receipt.set_gas_schedule_version(gas_schedule_version);
+
+                receipts[deferred.index] = Some(receipt);
+
+                done += 1;
+                progress(BulkSpawnProgress { done, total });
+            });
+        }
+
+        if let Some(state) = last_state {
+            self.state_roots.get_mut().record(context.layer(), state);
+        }
+
+        self.pending_metrics.extend(pending);
+        self.report_metrics(true);
+
+        receipts
+            .into_iter()
+            .map(|receipt| receipt.expect("Each spawn should have completed"))
+            .collect()
+    }
+
+    /// Prepares the spawn at `index` of a bulk spawn for running its `ctor` deferred
+    /// (the loaded `Template`s are kept in `templates`, so each is loaded once per batch).
+    ///
+    /// Returns `None` for a spawn which has to be executed on its own: a spawn of a precompile `Template`,
+    /// or having a [`SpawnCall`](svm_types::SpawnCall), or bound to fail prior to running its `ctor`.
+    fn defer_spawn<'a>(
+        &self,
+        index: usize,
+        envelope: &'a Envelope,
+        message: &[u8],
+        templates: &mut HashMap<TemplateAddr, Template>,
+    ) -> Option<DeferredSpawn<'a>> {
+        let base = self.env.parse_spawn(message).ok()?;
+        let template_addr = base.account.template_addr().clone();
+
+        if !templates.contains_key(&template_addr) {
+            let interests = Self::template_interests();
+            let template = self.env.template(&template_addr, Some(interests))?;
+
+            templates.insert(template_addr.clone(), template);
+        }
+
+        let template = &templates[&template_addr];
+        let spawn = ExtSpawn::new(base, envelope.principal());
+
+        if spawn.call().is_some() || template.code_section().kind() == CodeKind::Precompile {
+            return None;
+        }
+
+        let ctor_name = Self::resolve_ctor(template, &spawn).ok()?.to_string();
+        let price = self.func_price(&template_addr, template, &ctor_name);
+
+        if matches!(price, Some(price) if envelope.gas_limit() <= price) {
+            return None;
+        }
+
+        let payload_price = svm_gas::transaction::spawn(message);
+        let gas_left = (envelope.gas_limit() - payload_price).ok()?;
+        let target = self.env.compute_account_addr(&spawn);
+
+        Some(DeferredSpawn {
+            index,
+            envelope,
+            spawn,
+            template_addr,
+            ctor_name,
+            gas_left,
+            target,
+            payload_price,
+        })
+    }
+
+    /// Runs the `ctor`s of `group` (whose `Account`s have been stored already) and hands each
+    /// of their receipts to `on_receipt`.
+    ///
+    /// The `Template`'s code is compiled once, and each `ctor` runs by a fresh instance of the same module.
+    /// The instances share a single [`FuncEnv`] along with its imports, retargeted at each `Account` in turn.
+    /// When the code can't be compiled, each `ctor` is executed on its own (failing just the same).
+    fn exec_deferred(
+        &self,
+        group: &DeferredGroup,
+        template: &Template,
+        context: &Context,
+        on_receipt: &mut dyn FnMut(&DeferredSpawn, CallReceipt),
+    ) {
+        let state = State::zeros();
+        let calls: Vec<Call> = group
+            .spawns
+            .iter()
+            .map(|deferred| Call {
+                func_name: &group.ctor_name,
+                func_input: deferred.spawn.ctor_data(),
+                state: &state,
+                template: group.template_addr.clone(),
+                target: deferred.target.clone(),
+                within_spawn: true,
+                gas_limit: deferred.gas_left,
+                protected_mode: ProtectedMode::FullAccess,
+                callers: &[],
+                envelope: deferred.envelope,
+                context,
+            })
+            .collect();
+
+        let dispatch = |call: &Call| {
+            self.isolate_ref(
+                |rt| rt.dispatch(call, true),
+                |err| CallReceipt::from_err(err, Vec::new()),
+            )
+        };
+
+        let dispatch_each = |on_receipt: &mut dyn FnMut(&DeferredSpawn, CallReceipt)| {
+            for (deferred, call) in group.spawns.iter().zip(calls.iter()) {
+                on_receipt(deferred, dispatch(call));
+            }
+        };
+
+        let load_price = self.template_addr_load_price(&group.template_addr);
+
+        let first = &calls[0];
+        let mut env = FuncEnv::new(
+            self.open_call_storage(first, template),
+            first.envelope,
+            context,
+            first.template.clone(),
+            first.target.clone(),
+            first.protected_mode,
+        );
+
+        let module = match self.template_module(&env, template) {
+            Ok(module) => module,
+            Err(..) => return dispatch_each(on_receipt),
+        };
+        let import_object = self.create_import_object(module.store(), &mut env, first);
+
+        for (i, (deferred, call)) in group.spawns.iter().zip(calls.iter()).enumerate() {
+            // A `ctor` which can't pay for loading the `Template` is executed on its own (failing just the same)
+            let call = &match call.gas_limit - load_price {
+                Ok(gas_limit) => Call {
+                    gas_limit,
+                    ..call.clone()
+                },
+                Err(..) => {
+                    on_receipt(deferred, dispatch(call));
+                    continue;
+                }
+            };
+
+            let receipt = self.isolate_ref(
+                |rt| {
+                    if i > 0 {
+                        let storage = rt.open_call_storage(call, template);
+
+                        env.retarget(
+                            storage,
+                            call.envelope,
+                            call.target.clone(),
+                            call.protected_mode,
+                        );
+                    }
+
+                    rt.exec_module(call, &env, template, &module, &import_object, load_price)
+                },
+                |err| CallReceipt::from_err(err, Vec::new()),
+            );
+
+            on_receipt(deferred, receipt);
+        }
+    }
+
+    /// Executes `call` by an already compiled `module`, given `env` targeting `call.target`
+    /// (the counterpart of [`Self::dispatch`] for the `ctor`s of a deferred spawn).
+    fn exec_module(
+        &self,
+        call: &Call,
+        env: &FuncEnv,
+        template: &Template,
+        module: &Module,
+        import_object: &wasmer::ImportObject,
+        load_price: u64,
+    ) -> CallReceipt {
+        self.trace.borrow_mut().take();
+        self.prepare_env(env, call);
+
+        // Safety: `self` outlives the running code, and the `NestedCaller` is unset right after it completes
+        unsafe { env.set_nested_caller(Some(self.nested_caller())) };
+        let res = self
+            .validate_call(call, template)
+            .and_then(|()| self.run_module::<(), ()>(call, env, template, module, import_object));
+        unsafe { env.set_nested_caller(None) };
+
+        *self.trace.borrow_mut() = env.borrow_mut().take_trace();
+
+        let receipt = match res {
+            Ok(out) => self.outcome_to_receipt(env, out),
+            Err(fail) => self.failure_to_receipt(fail),
+        };
+
+        self.complete_receipt(call, receipt, load_price)
+    }
+
+    /// Prices the function named `func_name` of the Wasm [`Template`] residing at `template_addr`
+    /// (the price is an upper bound of the gas of running the function).
+    ///
+    /// Returns `None` when the function can't be priced upfront:
+    ///
+    /// * For a [`GasMode::Metering`] `Template` (its code is charged only while running, see [`Metering`]).
+    /// * For a precompile `Template` (it prices its functions by itself).
+    /// * When the code doesn't export `func_name`.
+    fn func_price(
+        &self,
+        template_addr: &TemplateAddr,
+        template: &Template,
+        func_name: &str,
+    ) -> Option<u64> {
+        use svm_gas::ProgramPricing;
+        use svm_program::ProgramVisitor;
+
+        let code_section = template.code_section();
+        if code_section.kind() == CodeKind::Precompile
+            || code_section.gas_mode() == GasMode::Metering
+        {
+            return None;
+        }
+
+        let program = Program::new(code_section.code(), false).unwrap();
+
+        let key = PriceKey::new(template_addr.clone(), self.env.pricing_version());
+        let mut price_cache = self.price_cache.borrow_mut();
+        let func_price = match price_cache.get(&key) {
+            Some(prices) => prices,
+            None => {
+                let pricer = self.env.price_resolver();
+                let program_pricing = ProgramPricing::new(pricer);
+                let prices = Rc::new(program_pricing.visit(&program).unwrap());
+
+                price_cache.insert(key, Rc::clone(&prices));
+                prices
+            }
+        };
+
+        let func_index = program.exports().get(func_name)?;
+        Some(func_price.get(func_index) as u64)
+    }
+
+    /// Prices executing `spawn`: returns the price of loading its `Template` (once for running the `ctor`,
+    /// and once more for its [`SpawnCall`](svm_types::SpawnCall), if any), along with the price of running these.
+    ///
+    /// The price of running the functions is `None` when they can't be priced upfront (see [`Self::func_price`]),
+    /// or when the `spawn` is bound to fail (i.e its `Template` or `ctor` can't be found).
+    fn spawn_price(&self, spawn: &ExtSpawn) -> (u64, Option<u64>) {
+        let mut interests = HashSet::new();
+        interests.insert(SectionKind::Code);
+        interests.insert(SectionKind::Ctors);
+        interests.insert(SectionKind::Api);
+
+        let template_addr = spawn.template_addr();
+        let template = match self.env.template(template_addr, Some(interests)) {
+            Some(template) => template,
+            None => return (0, None),
+        };
+
+        let load_price = self.template_addr_load_price(template_addr);
+        let ctor_price = Self::resolve_ctor(&template, spawn)
+            .ok()
+            .and_then(|ctor_name| self.func_price(template_addr, &template, ctor_name));
+
+        match spawn.call() {
+            Some(call) => {
+                let call_price = self.func_price(template_addr, &template, &call.func_name);
+                let func_price = ctor_price
+                    .zip(call_price)
+                    .map(|(ctor_price, call_price)| ctor_price.saturating_add(call_price));
+
+                (load_price.saturating_mul(2), func_price)
+            }
+            None => (load_price, ctor_price),
+        }
+    }
+
+    /// Prices executing `tx`: returns the price of loading the `Template` of its target `Account`
+    /// (see [`Self::template_load_price`]), along with the price of running the called function
+    /// (or the `Template`'s `fallback` function, when the called one isn't exported).
+    ///
+    /// The price of running the function is `None` when it can't be priced upfront (see [`Self::func_price`]),
+    /// or when the target `Account` doesn't exist.
+    fn tx_price(&self, tx: &Transaction) -> (u64, Option<u64>) {
+        let target = tx.target();
+
+        let (template_addr, template) = match (
+            self.env.resolve_template_addr(target),
+            self.account_template(target),
+        ) {
+            (Some(template_addr), Ok(template)) => (template_addr, template),
+            _ => return (0, None),
+        };
+
+        let load_price = self.template_load_price(target);
+        let func_price = self
+            .func_price(&template_addr, &template, tx.func_name())
+            .or_else(|| {
+                let fallback = template.api_section().and_then(|api| api.fallback())?;
+                self.func_price(&template_addr, &template, fallback)
+            });
+
+        (load_price, func_price)
+    }
+
+    fn verify_account(
+        &mut self,
+        envelope: &Envelope,
+        message: &[u8],
+        context: &Context,
+    ) -> CallReceipt {
         let tx = self
             .env
             .parse_call(message)
@@ -788,13 +2569,18 @@ where
         self.exec_call::<(), ()>(&call)
     }
 
-    fn call(&mut self, envelope: &Envelope, message: &[u8], context: &Context) -> CallReceipt {
+    fn call_account(
+        &mut self,
+        envelope: &Envelope,
+        message: &[u8],
+        context: &Context,
+    ) -> CallReceipt {
         let tx = self
             .env
             .parse_call(message)
             .expect("Should have called `validate_call` first");
 
-        let call = self.build_call(
+        let mut call = self.build_call(
             &tx,
             envelope,
             context,
@@ -803,6 +2589,663 @@ where
             tx.calldata(),
         );
 
-        self.exec_call::<(), ()>(&call)
+        let payload_price = svm_gas::transaction::call(message);
+        let gas_left = call.gas_limit - payload_price;
+
+        match gas_left {
+            Ok(gas_left) => {
+                call.gas_limit = gas_left;
+
+                let mut receipt = self.exec_call::<(), ()>(&call);
+
+                if let Some(breakdown) = receipt.gas_breakdown.as_mut() {
+                    breakdown.intrinsic += payload_price;
+                }
+
+                receipt
+            }
+            Err(..) => CallReceipt::new_oog(Vec::new()),
+        }
+    }
+
+    fn call_batch_accounts(
+        &mut self,
+        envelope: &Envelope,
+        message: &[u8],
+        context: &Context,
+    ) -> BatchReceipt {
+        info!("Runtime `call_batch`");
+
+        let batch = self
+            .env
+            .parse_batch(message)
+            .expect("Should have called `validate_batch` first");
+
+        let payload_price = svm_gas::transaction::call(message);
+        let mut gas_left = match envelope.gas_limit() - payload_price {
+            Ok(gas_left) => gas_left,
+            Err(..) => return BatchReceipt::new_oog(Vec::new()),
+        };
+
+        let mut receipts = Vec::with_capacity(batch.calls().len());
+        let mut logs = Vec::new();
+        let mut staged = Staged::new();
+
+        for tx in batch.calls() {
+            if !self.env.contains_account(tx.target()) {
+                let err = RuntimeError::AccountNotFound(tx.target().clone());
+                return BatchReceipt::from_err(err, logs);
+            }
+
+            let mut call = self.build_call(
+                tx,
+                envelope,
+                context,
+                ProtectedMode::FullAccess,
+                tx.func_name(),
+                tx.calldata(),
+            );
+            call.gas_limit = gas_left;
+
+            // Each call stages its changes (running against the ones staged by its predecessors),
+            // and these are committed only once all the batched calls have succeeded
+            // (i.e failing discards them all).
+            let (mut receipt, next) = self.dispatch_staged(&call, true, Some(staged));
+            logs.extend_from_slice(receipt.logs());
+
+            if !receipt.success {
+                return BatchReceipt::from_err(receipt.error.take().unwrap(), logs);
+            }
+
+            gas_left = match gas_left - receipt.gas_used.unwrap_or(0) {
+                Ok(gas_left) => gas_left,
+                Err(..) => return BatchReceipt::new_oog(logs),
+            };
+
+            staged = next;
+            receipts.push(receipt);
+        }
+
+        let mut state = context.state().clone();
+
+        for mut storage in staged {
+            state = match storage.commit() {
+                Ok(state) => state,
+                Err(err) => {
+                    let mut fail = self.storage_corrupted(err, logs);
+                    let logs = fail.take_logs();
+
+                    return BatchReceipt::from_err(fail.take_error(), logs);
+                }
+            };
+        }
+
+        // The batched calls are committed at once, so they all end up at the same `State`
+        for receipt in receipts.iter_mut() {
+            receipt.new_state = Some(state.clone());
+        }
+
+        self.state_roots
+            .get_mut()
+            .record(context.layer(), state.clone());
+
+        let gas_used = receipts.iter().fold(payload_price, |gas_used, r| {
+            gas_used + r.gas_used.unwrap_or(0)
+        });
+
+        let breakdown = if self.config.detailed_gas_accounting {
+            let mut breakdown = GasBreakdown {
+                intrinsic: payload_price,
+                ..GasBreakdown::default()
+            };
+
+            for r in receipts.iter() {
+                breakdown += r.gas_breakdown.unwrap_or_default();
+            }
+
+            Some(breakdown)
+        } else {
+            None
+        };
+
+        let mut receipt = BatchReceipt::new(receipts, Gas::with(gas_used));
+
+        if let Some(breakdown) = breakdown {
+            receipt.set_gas_breakdown(breakdown);
+        }
+
+        receipt
+    }
+}
+
+#[cfg(feature = "default-memory")]
+impl DefaultRuntime<DefaultMemEnvTypes> {
+    /// Attaches the in-memory key-value store used by the storage builder of the [`DefaultRuntime`].
+    ///
+    /// Required for taking [`RuntimeSnapshot`]s (otherwise the `Account`s storage can't be captured).
+    pub fn with_memory_kv(mut self, kv: Arc<Mutex<FakeKV>>) -> Self {
+        self.memory_kv = Some(kv);
+        self
+    }
+
+    /// Captures the current state of the [`DefaultRuntime`] into a [`RuntimeSnapshot`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if no in-memory key-value store has been attached (see [`Self::with_memory_kv`]).
+    pub fn snapshot(&self) -> RuntimeSnapshot {
+        let kv = self
+            .memory_kv
+            .as_ref()
+            .expect("Taking a snapshot requires an attached in-memory key-value store");
+
+        RuntimeSnapshot {
+            templates: self.env.template_store().clone(),
+            accounts: self.env.account_store().clone(),
+            kv: kv.lock().unwrap().clone(),
+            state_roots: self.state_roots.borrow().clone(),
+        }
+    }
+
+    /// Resets the [`DefaultRuntime`] to the state captured by `snapshot`.
+    ///
+    /// The same [`RuntimeSnapshot`] can be restored any number of times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no in-memory key-value store has been attached (see [`Self::with_memory_kv`]).
+    pub fn restore(&mut self, snapshot: &RuntimeSnapshot) {
+        let kv = self
+            .memory_kv
+            .as_ref()
+            .expect("Restoring a snapshot requires an attached in-memory key-value store");
+
+        *kv.lock().unwrap() = snapshot.kv.clone();
+        *self.env.template_store_mut() = snapshot.templates.clone();
+        *self.env.account_store_mut() = snapshot.accounts.clone();
+        self.price_cache.get_mut().clear();
+        *self.state_roots.get_mut() = snapshot.state_roots.clone();
+        *self.trace.get_mut() = None;
+    }
+}
+
+impl<T> Runtime for DefaultRuntime<T>
+where
+    T: EnvTypes,
+{
+    fn validate_deploy(&self, message: &[u8]) -> std::result::Result<(), ValidateError> {
+        let template = self.env.parse_deploy(message, None)?;
+
+        self.validate_template(&template)
+    }
+
+    fn validate_upgrade(&self, message: &[u8]) -> std::result::Result<(), ValidateError> {
+        let upgrade = self.env.parse_upgrade(message)?;
+
+        self.validate_template(upgrade.template())
+    }
+
+    fn validate_spawn(&self, message: &[u8]) -> std::result::Result<(), ValidateError> {
+        self.env
+            .parse_spawn(message)
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+
+    fn validate_call(&self, message: &[u8]) -> std::result::Result<(), ValidateError> {
+        self.env
+            .parse_call(message)
+            .map(|_| ())
+            .map_err(|e| e.into())
+    }
+
+    fn validate_batch(&self, message: &[u8]) -> std::result::Result<(), ValidateError> {
+        self.env
+            .parse_batch(message)
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+
+    fn deploy(&mut self, envelope: &Envelope, message: &[u8], context: &Context) -> DeployReceipt {
+        self.env.set_layer(context.layer());
+
+        let mut receipt = self.isolate(
+            |rt| rt.deploy_template(envelope, message, context),
+            |err| DeployReceipt::from_err(err, Vec::new()),
+        );
+        receipt.set_gas_schedule_version(self.config.gas_schedule_version);
+
+        receipt
+    }
+
+    fn upgrade(
+        &mut self,
+        envelope: &Envelope,
+        message: &[u8],
+        context: &Context,
+    ) -> UpgradeReceipt {
+        self.env.set_layer(context.layer());
+
+        let mut receipt = self.isolate(
+            |rt| rt.upgrade_template(envelope, message, context),
+            |err| UpgradeReceipt::from_err(err, Vec::new()),
+        );
+        receipt.set_gas_schedule_version(self.config.gas_schedule_version);
+
+        receipt
+    }
+
+    fn spawn(&mut self, envelope: &Envelope, message: &[u8], context: &Context) -> SpawnReceipt {
+        self.env.set_layer(context.layer());
+
+        let mut receipt = self.isolate(
+            |rt| rt.spawn_account(envelope, message, context),
+            |err| SpawnReceipt::from_err(err, Vec::new()),
+        );
+        receipt.set_gas_schedule_version(self.config.gas_schedule_version);
+        self.report_metrics(receipt.success);
+
+        receipt
+    }
+
+    fn spawn_bulk(
+        &mut self,
+        spawns: &[(Envelope, Vec<u8>)],
+        context: &Context,
+        progress: &mut dyn FnMut(BulkSpawnProgress),
+    ) -> Vec<SpawnReceipt> {
+        self.env.set_layer(context.layer());
+
+        self.spawn_deferred(spawns, context, progress)
+    }
+
+    fn verify(&mut self, envelope: &Envelope, message: &[u8], context: &Context) -> CallReceipt {
+        let mut receipt = self.isolate(
+            |rt| rt.verify_account(envelope, message, context),
+            |err| CallReceipt::from_err(err, Vec::new()),
+        );
+        receipt.set_gas_schedule_version(self.config.gas_schedule_version);
+        self.report_metrics(receipt.success);
+
+        receipt
+    }
+
+    fn call(&mut self, envelope: &Envelope, message: &[u8], context: &Context) -> CallReceipt {
+        self.env.set_layer(context.layer());
+
+        let mut receipt = self.isolate(
+            |rt| rt.call_account(envelope, message, context),
+            |err| CallReceipt::from_err(err, Vec::new()),
+        );
+        receipt.set_gas_schedule_version(self.config.gas_schedule_version);
+        self.report_metrics(receipt.success);
+
+        receipt
+    }
+
+    fn call_batch(
+        &mut self,
+        envelope: &Envelope,
+        message: &[u8],
+        context: &Context,
+    ) -> BatchReceipt {
+        let version = self.config.gas_schedule_version;
+        self.env.set_layer(context.layer());
+
+        let mut receipt = self.isolate(
+            |rt| rt.call_batch_accounts(envelope, message, context),
+            |err| BatchReceipt::from_err(err, Vec::new()),
+        );
+        receipt.set_gas_schedule_version(version);
+        self.report_metrics(receipt.success);
+
+        for call in receipt.receipts.iter_mut() {
+            call.set_gas_schedule_version(version);
+        }
+
+        receipt
+    }
+
+    fn call_at(
+        &mut self,
+        envelope: &Envelope,
+        message: &[u8],
+        state_root: &State,
+    ) -> std::result::Result<CallReceipt, CallAtError> {
+        let layer = self
+            .state_roots
+            .get_mut()
+            .layer_of(state_root)
+            .ok_or_else(|| CallAtError::UnknownState(state_root.clone()))?;
+        let context = Context::new(TransactionId::zeros(), layer, state_root.clone());
+
+        let tx = self.env.parse_call(message)?;
+
+        let mut call = self.build_call(
+            &tx,
+            envelope,
+            &context,
+            ProtectedMode::FullAccess,
+            tx.func_name(),
+            tx.calldata(),
+        );
+
+        let payload_price = svm_gas::transaction::call(message);
+        match call.gas_limit - payload_price {
+            Ok(gas_left) => call.gas_limit = gas_left,
+            Err(..) => {
+                let mut receipt = CallReceipt::new_oog(Vec::new());
+                receipt.set_gas_schedule_version(self.config.gas_schedule_version);
+
+                return Ok(receipt);
+            }
+        }
+
+        // The storages (including the ones of the called `Account`s) are read as of `state_root`
+        self.storage_mode.set(StorageMode::Historical);
+
+        let mut receipt = self.isolate(
+            |rt| rt.exec_read_only(&call),
+            |err| CallReceipt::from_err(err, Vec::new()),
+        );
+
+        self.storage_mode.set(StorageMode::Current);
+
+        if let Some(breakdown) = receipt.gas_breakdown.as_mut() {
+            breakdown.intrinsic += payload_price;
+        }
+        receipt.set_gas_schedule_version(self.config.gas_schedule_version);
+
+        Ok(receipt)
+    }
+
+    fn query(&self, envelope: &Envelope, message: &[u8], context: &Context) -> QueryResult {
+        let receipt = self.query_receipt(envelope, message, context);
+        let payload_price = svm_gas::transaction::call(message);
+
+        QueryResult::from_receipt(receipt, payload_price)
+    }
+
+    fn estimate_gas(
+        &self,
+        envelope: &Envelope,
+        message: &Message,
+    ) -> std::result::Result<GasEstimate, ValidateError> {
+        use svm_codec::intrinsic_gas::{CALL_EXEC_GAS, DEPLOY_EXEC_GAS, SPAWN_EXEC_GAS};
+        use svm_gas::transaction;
+
+        let estimate = match message {
+            Message::Deploy(bytes) => {
+                self.validate_deploy(bytes)?;
+
+                let template = self.env.parse_deploy(bytes, None)?;
+                let price = transaction::deploy(bytes) + data_segments_price(&template);
+
+                GasEstimate::new(price, 0, Some(0), DEPLOY_EXEC_GAS)
+            }
+            Message::Upgrade(bytes) => {
+                self.validate_upgrade(bytes)?;
+
+                let upgrade = self.env.parse_upgrade(bytes)?;
+                let price = transaction::upgrade(bytes) + data_segments_price(upgrade.template());
+
+                GasEstimate::new(price, 0, Some(0), DEPLOY_EXEC_GAS)
+            }
+            Message::Spawn(bytes) => {
+                let base = self.env.parse_spawn(bytes)?;
+                let spawn = ExtSpawn::new(base, envelope.principal());
+
+                let (load_price, func_price) = self.spawn_price(&spawn);
+                GasEstimate::new(
+                    transaction::spawn(bytes),
+                    load_price,
+                    func_price,
+                    SPAWN_EXEC_GAS,
+                )
+            }
+            Message::Call(bytes) => {
+                let tx = self.env.parse_call(bytes)?;
+
+                let (load_price, func_price) = self.tx_price(&tx);
+                GasEstimate::new(
+                    transaction::call(bytes),
+                    load_price,
+                    func_price,
+                    CALL_EXEC_GAS,
+                )
+            }
+            Message::Batch(bytes) => {
+                let batch = self.env.parse_batch(bytes)?;
+
+                // The batched calls whose functions can't be priced upfront are given the allowance of a `call`.
+                let mut load_price = 0u64;
+                let mut func_price = Some(0u64);
+                let mut exec_allowance = 0u64;
+
+                for tx in batch.calls() {
+                    let (tx_load_price, tx_func_price) = self.tx_price(tx);
+
+                    load_price = load_price.saturating_add(tx_load_price);
+                    func_price = func_price
+                        .zip(tx_func_price)
+                        .map(|(price, tx_price)| price.saturating_add(tx_price));
+                    exec_allowance =
+                        exec_allowance.saturating_add(tx_func_price.unwrap_or(CALL_EXEC_GAS));
+                }
+
+                GasEstimate::new(
+                    transaction::call(bytes),
+                    load_price,
+                    func_price,
+                    exec_allowance,
+                )
+            }
+        };
+
+        Ok(estimate)
+    }
+
+    fn execute_layer(
+        &mut self,
+        header: LayerHeader,
+        txs: Vec<(Envelope, Message)>,
+    ) -> LayerOutcome {
+        let LayerHeader { layer, mut state } = header;
+
+        info!("Runtime `execute_layer` (layer = {:?})", layer);
+
+        let mut results = Vec::with_capacity(txs.len());
+        let mut gas_used = 0;
+
+        for (envelope, message) in txs {
+            let tx_id = message.tx_id(&envelope);
+            let context = Context::new(tx_id, layer, state.clone());
+
+            let receipt = match self.execute_message(&envelope, &message, &context) {
+                Ok(receipt) => receipt,
+                Err(err) => {
+                    results.push(Err(err));
+                    continue;
+                }
+            };
+
+            gas_used += receipt.gas_used().unwrap_or(0);
+
+            if let Some(committed) = committed_state(&receipt) {
+                state = committed.clone();
+            }
+
+            if let Some(store) = self.receipt_store.as_mut() {
+                store.store(TxRecord {
+                    envelope,
+                    context,
+                    message: message.into_bytes(),
+                    receipt: receipt.clone(),
+                });
+            }
+
+            results.push(Ok(receipt));
+        }
+
+        let outcome = LayerOutcome {
+            layer,
+            results,
+            state,
+            gas_used,
+        };
+
+        if let Some(observer) = self.metrics_observer.as_mut() {
+            observer.observe_layer(&outcome);
+        }
+
+        outcome
+    }
+
+    fn flush_until(&self, layer: Layer) -> std::result::Result<(), FlushError> {
+        self.env.flush_until(layer)
+    }
+
+    fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.get_mut().take()
+    }
+
+    fn current_state_root(&self, account: &Address) -> Option<State> {
+        let head = self.head_state();
+
+        if let Some(root) = self.state_roots.borrow().account_root(account, &head) {
+            return Some(root.clone());
+        }
+
+        let root = self.compute_account_root(account, &head)?;
+
+        self.state_roots
+            .borrow_mut()
+            .insert_account_root(account.clone(), head, root.clone());
+
+        Some(root)
+    }
+
+    fn state_root(&self, layer: Layer) -> Option<State> {
+        self.state_roots.borrow().layer_root(layer).cloned()
+    }
+
+    fn template_section(&self, template_addr: &TemplateAddr, kind: SectionKind) -> Option<Section> {
+        let mut interests = HashSet::new();
+        interests.insert(kind);
+
+        let template = self.env.template(template_addr, Some(interests))?;
+
+        template.try_get(kind).cloned()
+    }
+
+    fn iter_accounts(&self) -> Box<dyn Iterator<Item = AccountInfo> + '_> {
+        let addrs = self.env.account_store().addrs();
+        let accounts = addrs
+            .into_iter()
+            .filter_map(move |addr| self.account_info(addr));
+
+        Box::new(accounts)
+    }
+
+    fn accounts_page(&self, after: Option<&Address>, limit: usize) -> Vec<AccountInfo> {
+        let addrs = self.env.account_store().addrs_after(after, limit);
+
+        addrs
+            .into_iter()
+            .filter_map(|addr| self.account_info(addr))
+            .collect()
+    }
+}
+
+/// A spawn of a bulk spawn whose `ctor` runs deferred (see [`DefaultRuntime::defer_spawn`]).
+struct DeferredSpawn<'a> {
+    /// The index of the spawn within the batch.
+    index: usize,
+
+    envelope: &'a Envelope,
+
+    spawn: ExtSpawn,
+
+    template_addr: TemplateAddr,
+
+    /// The Wasm name of the `ctor` to run.
+    ctor_name: String,
+
+    /// The gas left for running the `ctor` (after paying for the payload).
+    gas_left: Gas,
+
+    /// The `Address` of the spawned `Account`.
+    target: Address,
+
+    payload_price: u64,
+}
+
+/// The deferred spawns whose `ctor`s run by the same compiled module (see [`DefaultRuntime::exec_deferred`]).
+///
+/// They share the `Template` and the `ctor` (the gas limit is set per instance, see [`Metering::set_gas_limit`]).
+struct DeferredGroup<'a> {
+    template_addr: TemplateAddr,
+
+    ctor_name: String,
+
+    spawns: Vec<DeferredSpawn<'a>>,
+}
+
+impl<'a> DeferredGroup<'a> {
+    fn new(deferred: DeferredSpawn<'a>) -> Self {
+        Self {
+            template_addr: deferred.template_addr.clone(),
+            ctor_name: deferred.ctor_name.clone(),
+            spawns: vec![deferred],
+        }
+    }
+
+    fn accepts(&self, deferred: &DeferredSpawn) -> bool {
+        self.template_addr == deferred.template_addr && self.ctor_name == deferred.ctor_name
+    }
+}
+
+/// Checks that each default value refers to an existing storage variable and matches its size.
+fn validate_defaults(
+    defaults: &DefaultsSection,
+    layout: &FixedLayout,
+) -> std::result::Result<(), ValidateError> {
+    for (var_id, value) in defaults.defaults() {
+        match layout.try_get(*var_id) {
+            Some(var) if var.byte_size() as usize == value.len() => (),
+            _ => return Err(ValidateError::InvalidDefault(var_id.0)),
+        }
+    }
+
+    Ok(())
+}
+
+/// Prices the data segments of the code of `template` (see [`svm_gas::transaction::data_segments`]).
+fn data_segments_price(template: &Template) -> u64 {
+    let code_section = template.code_section();
+
+    if code_section.kind() != CodeKind::Wasm {
+        return 0;
+    }
+
+    Program::new(code_section.code(), false).map_or(0, |program| {
+        svm_gas::transaction::data_segments(program.data_size())
+    })
+}
+
+/// Checks that an upgraded `Template` keeps the storage variables of the `Template` it replaces
+/// (so that the storage of the existing `Account`s remains meaningful), possibly appending new ones.
+fn preserves_layout(current: &FixedLayout, upgraded: &FixedLayout) -> bool {
+    current.len() <= upgraded.len() && current.iter().zip(upgraded.iter()).all(|(a, b)| a == b)
+}
+
+/// Extracts the message of a caught panic (as passed to `panic!`).
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "unknown panic".to_string()
     }
 }
@@ -0,0 +1,207 @@
+use std::fmt;
+use std::rc::Rc;
+
+use thiserror::Error;
+
+/// Builds the [`wasmer::Exports`] of a [`HostModule`].
+///
+/// Each execution builds its own exports (using the `Store` leased from the [`StorePool`](crate::StorePool)),
+/// so no host state is shared between executions.
+pub type HostExportsFn = dyn Fn(&wasmer::Store) -> wasmer::Exports;
+
+/// The import module name reserved for the SVM internals (the `vmcalls`).
+pub const RESERVED_MODULE: &str = "svm";
+
+/// The kind of execution a [`HostModule`] is registered for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HostPhase {
+    /// Running the `ctor` of a spawned `Account`.
+    Spawn,
+
+    /// Running `svm_verify` (no access to the `Account`'s storage).
+    Verify,
+
+    /// Running a function of an `Account` (calls and queries).
+    Call,
+}
+
+/// The kinds of execution a [`HostModule`] is available to.
+///
+/// A `Template` importing from a [`HostModule`] unavailable to the current execution
+/// fails to instantiate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HostCapabilities {
+    /// Whether available while running the `ctor` of a spawned `Account`.
+    pub spawn: bool,
+
+    /// Whether available while running `svm_verify`.
+    pub verify: bool,
+
+    /// Whether available while running a function of an `Account`.
+    pub call: bool,
+}
+
+impl HostCapabilities {
+    /// Available to any kind of execution.
+    pub const fn all() -> Self {
+        Self {
+            spawn: true,
+            verify: true,
+            call: true,
+        }
+    }
+
+    /// Returns whether `phase` is allowed.
+    pub fn allows(&self, phase: HostPhase) -> bool {
+        match phase {
+            HostPhase::Spawn => self.spawn,
+            HostPhase::Verify => self.verify,
+            HostPhase::Call => self.call,
+        }
+    }
+}
+
+impl Default for HostCapabilities {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+/// A named import module of host functions, provided by the embedder of the `Runtime`.
+#[derive(Clone)]
+pub struct HostModule {
+    name: String,
+    exports: Rc<HostExportsFn>,
+    capabilities: HostCapabilities,
+}
+
+impl HostModule {
+    /// Creates a new [`HostModule`] named `name`, whose exports are built by `exports`.
+    ///
+    /// The new [`HostModule`] is available to any kind of execution (see [`Self::with_capabilities`]).
+    pub fn new<F>(name: impl Into<String>, exports: F) -> Self
+    where
+        F: Fn(&wasmer::Store) -> wasmer::Exports + 'static,
+    {
+        Self {
+            name: name.into(),
+            exports: Rc::new(exports),
+            capabilities: HostCapabilities::all(),
+        }
+    }
+
+    /// Restricts the kinds of execution the [`HostModule`] is available to.
+    pub fn with_capabilities(mut self, capabilities: HostCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// The import module name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The kinds of execution the [`HostModule`] is available to.
+    pub fn capabilities(&self) -> HostCapabilities {
+        self.capabilities
+    }
+
+    /// Builds the exports of the [`HostModule`] within `store`.
+    pub fn exports(&self, store: &wasmer::Store) -> wasmer::Exports {
+        (self.exports)(store)
+    }
+}
+
+impl fmt::Debug for HostModule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HostModule")
+            .field("name", &self.name)
+            .field("capabilities", &self.capabilities)
+            .finish()
+    }
+}
+
+/// The error type that can arise when registering a [`HostModule`].
+#[derive(Debug, PartialEq, Clone, Error)]
+pub enum HostModuleError {
+    /// The [`HostModule`] has an empty name.
+    #[error("A host module name must not be empty")]
+    EmptyName,
+
+    /// The [`HostModule`] name is reserved (see [`RESERVED_MODULE`]).
+    #[error("The host module name `{0}` is reserved")]
+    Reserved(String),
+
+    /// A [`HostModule`] of the same name has already been registered.
+    #[error("A host module named `{0}` is already registered")]
+    Duplicate(String),
+}
+
+/// The [`HostModule`]s provided to the `Runtime`, in registration order.
+#[derive(Debug, Clone, Default)]
+pub struct HostModules {
+    modules: Vec<HostModule>,
+}
+
+impl HostModules {
+    /// Creates an empty [`HostModules`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `module`.
+    ///
+    /// Fails if `module` has an empty or reserved name, or if its name is already taken.
+    pub fn register(&mut self, module: HostModule) -> Result<(), HostModuleError> {
+        let name = module.name();
+
+        if name.is_empty() {
+            return Err(HostModuleError::EmptyName);
+        }
+
+        if name == RESERVED_MODULE {
+            return Err(HostModuleError::Reserved(name.to_string()));
+        }
+
+        if self.get(name).is_some() {
+            return Err(HostModuleError::Duplicate(name.to_string()));
+        }
+
+        self.modules.push(module);
+        Ok(())
+    }
+
+    /// Returns the [`HostModule`] named `name`.
+    pub fn get(&self, name: &str) -> Option<&HostModule> {
+        self.modules.iter().find(|module| module.name() == name)
+    }
+
+    /// Iterates over the [`HostModule`]s, in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &HostModule> {
+        self.modules.iter()
+    }
+
+    /// The number of registered [`HostModule`]s.
+    pub fn len(&self) -> usize {
+        self.modules.len()
+    }
+
+    /// Returns whether there are no registered [`HostModule`]s.
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+
+    /// Registers into `import_object` the [`HostModule`]s available to `phase`.
+    pub(crate) fn register_into(
+        &self,
+        import_object: &mut wasmer::ImportObject,
+        store: &wasmer::Store,
+        phase: HostPhase,
+    ) {
+        for module in self.iter() {
+            if module.capabilities().allows(phase) {
+                import_object.register(module.name(), module.exports(store));
+            }
+        }
+    }
+}
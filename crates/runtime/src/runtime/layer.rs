@@ -0,0 +1,122 @@
+use svm_hash::{Blake3Hasher, Hasher};
+use svm_types::{Envelope, Layer, Receipt, State, TransactionId};
+
+use crate::error::ValidateError;
+
+/// A binary transaction message of a [`Layer`], tagged by its kind.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    /// A binary `Deploy Template` message.
+    Deploy(Vec<u8>),
+
+    /// A binary `Upgrade Template` message.
+    Upgrade(Vec<u8>),
+
+    /// A binary `Spawn Account` message.
+    Spawn(Vec<u8>),
+
+    /// A binary `Call Account` message.
+    Call(Vec<u8>),
+
+    /// A binary `Call Batch` message.
+    Batch(Vec<u8>),
+}
+
+impl Message {
+    /// Borrows the binary message.
+    pub fn bytes(&self) -> &[u8] {
+        match self {
+            Message::Deploy(bytes)
+            | Message::Upgrade(bytes)
+            | Message::Spawn(bytes)
+            | Message::Call(bytes)
+            | Message::Batch(bytes) => bytes,
+        }
+    }
+
+    /// The [`TransactionId`] of the message sent within `envelope` (see [`Runtime::execute_layer`](crate::Runtime::execute_layer)).
+    ///
+    /// It's the `Blake3` hash of the binary [`Envelope`] followed by the binary message,
+    /// so that identical messages sent by different principals (or paying different fees) are told apart.
+    pub fn tx_id(&self, envelope: &Envelope) -> TransactionId {
+        let mut bytes = Vec::with_capacity(svm_codec::envelope::byte_size() + self.bytes().len());
+
+        svm_codec::envelope::encode(envelope, &mut bytes);
+        bytes.extend_from_slice(self.bytes());
+
+        TransactionId::from(&Blake3Hasher::hash(&bytes)[..])
+    }
+
+    /// Returns the binary message.
+    pub fn into_bytes(self) -> Vec<u8> {
+        match self {
+            Message::Deploy(bytes)
+            | Message::Upgrade(bytes)
+            | Message::Spawn(bytes)
+            | Message::Call(bytes)
+            | Message::Batch(bytes) => bytes,
+        }
+    }
+}
+
+/// The header of a [`Layer`] to execute (see [`Runtime::execute_layer`](crate::Runtime::execute_layer)).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerHeader {
+    /// The executed [`Layer`].
+    pub layer: Layer,
+
+    /// The global `State` the [`Layer`] starts from (i.e left by the preceding `Layer`s).
+    pub state: State,
+}
+
+impl LayerHeader {
+    /// New [`LayerHeader`] of `layer`, starting from the global `state`.
+    pub fn new(layer: Layer, state: State) -> Self {
+        Self { layer, state }
+    }
+}
+
+/// The outcome of executing the transactions of a [`Layer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerOutcome {
+    /// The executed [`Layer`].
+    pub layer: Layer,
+
+    /// The result of each transaction (in execution order): either its [`Receipt`],
+    /// or the reason it has been rejected by validation (and hence hasn't been executed).
+    pub results: Vec<Result<Receipt, ValidateError>>,
+
+    /// The global `State` at the end of the [`Layer`],
+    /// committing to the changes of all its transactions.
+    pub state: State,
+
+    /// The total gas used by the transactions of the [`Layer`].
+    pub gas_used: u64,
+}
+
+impl LayerOutcome {
+    /// The receipts of the executed transactions (in execution order).
+    pub fn receipts(&self) -> impl Iterator<Item = &Receipt> {
+        self.results
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+    }
+
+    /// The number of transactions rejected by validation.
+    pub fn rejected(&self) -> usize {
+        self.results.iter().filter(|result| result.is_err()).count()
+    }
+}
+
+/// The global `State` committed by the transaction of `receipt` (if any).
+///
+/// For a `Spawn Account` that's the `State` left by its post-`ctor` call, if any (see [`SpawnReceipt::final_state`](svm_types::SpawnReceipt::final_state)).
+pub(crate) fn committed_state(receipt: &Receipt) -> Option<&State> {
+    match receipt {
+        Receipt::Deploy(..) | Receipt::Upgrade(..) => None,
+        Receipt::Spawn(receipt) if receipt.success => Some(receipt.final_state()),
+        Receipt::Spawn(..) => None,
+        Receipt::Call(receipt) => receipt.new_state.as_ref(),
+        Receipt::Batch(receipt) => receipt.new_state.as_ref(),
+    }
+}
@@ -0,0 +1,86 @@
+use std::cell::Cell;
+
+use svm_types::{Address, ReceiptMetric, TemplateAddr};
+
+use crate::env::TemplateCacheStats;
+use crate::LayerOutcome;
+
+/// Counters of notable events observed by a [`DefaultRuntime`](crate::DefaultRuntime).
+///
+/// Meant to be exported by the embedding node into its own metrics system.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuntimeMetrics {
+    internal_errors: Cell<u64>,
+
+    storage_corruptions: Cell<u64>,
+}
+
+impl RuntimeMetrics {
+    /// Creates a new [`RuntimeMetrics`] with all counters zeroed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of transactions whose execution has panicked
+    /// (and resulted in a [`RuntimeError::InternalError`](svm_types::RuntimeError::InternalError)).
+    pub fn internal_errors(&self) -> u64 {
+        self.internal_errors.get()
+    }
+
+    pub(crate) fn record_internal_error(&self) {
+        self.internal_errors.set(self.internal_errors.get() + 1);
+    }
+
+    /// The number of transactions which have read a corrupted storage page
+    /// (and resulted in a [`RuntimeError::StorageCorrupted`](svm_types::RuntimeError::StorageCorrupted)).
+    pub fn storage_corruptions(&self) -> u64 {
+        self.storage_corruptions.get()
+    }
+
+    pub(crate) fn record_storage_corruption(&self) {
+        self.storage_corruptions
+            .set(self.storage_corruptions.get() + 1);
+    }
+}
+
+/// Observes the metrics reported by the `Template`s' code (see [`ReceiptMetric`]),
+/// so that the embedding node may aggregate them without decoding the receipts.
+///
+/// The metrics are reported once a transaction (a `spawn`, a `verify`, a `call` or a `call batch`) succeeds,
+/// one `observe` per execution that has reported any.
+/// The metrics of failed transactions are never reported.
+pub trait MetricsObserver {
+    /// Observes the `metrics` reported by the code of `template` while executing against `account`.
+    fn observe(&mut self, account: &Address, template: &TemplateAddr, metrics: &[ReceiptMetric]);
+
+    /// Observes the statistics of the `Template`s cache (see [`TemplateCacheStats`]).
+    ///
+    /// Called once each transaction completes (whether it has succeeded or not),
+    /// provided the `Runtime`'s `TemplateStore` caches the loaded `Template`s.
+    fn observe_template_cache(&mut self, _stats: &TemplateCacheStats) {}
+
+    /// Observes the outcome of executing a whole `Layer` (see [`Runtime::execute_layer`](crate::Runtime::execute_layer)).
+    ///
+    /// Called once all the transactions of the `Layer` have been executed
+    /// (following the observations of the transactions themselves).
+    fn observe_layer(&mut self, _outcome: &LayerOutcome) {}
+}
+
+impl<F> MetricsObserver for F
+where
+    F: FnMut(&Address, &TemplateAddr, &[ReceiptMetric]),
+{
+    fn observe(&mut self, account: &Address, template: &TemplateAddr, metrics: &[ReceiptMetric]) {
+        self(account, template, metrics)
+    }
+}
+
+/// The metrics of a single execution, pending their transaction's outcome.
+#[derive(Debug, Clone)]
+pub(crate) struct PendingMetrics {
+    pub account: Address,
+
+    pub template: TemplateAddr,
+
+    pub metrics: Vec<ReceiptMetric>,
+}
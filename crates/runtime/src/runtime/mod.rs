@@ -1,58 +1,276 @@
 //! Implements the most high-level API of `SVM`.
 
+mod account_info;
+mod bulk;
 mod call;
 mod config;
 mod default;
 mod failure;
 mod function;
+mod host_modules;
+mod layer;
+mod metrics;
 mod outcome;
+mod precompile;
+mod price_cache;
+mod query_cache;
+mod query_result;
+mod state_roots;
 
+pub use account_info::AccountInfo;
+pub use bulk::BulkSpawnProgress;
 pub use call::Call;
 pub use failure::Failure;
 pub use function::Function;
+pub use host_modules::{
+    HostCapabilities, HostExportsFn, HostModule, HostModuleError, HostModules, HostPhase,
+    RESERVED_MODULE,
+};
+pub(crate) use layer::committed_state;
+pub use layer::{LayerHeader, LayerOutcome, Message};
+pub(crate) use metrics::PendingMetrics;
+pub use metrics::{MetricsObserver, RuntimeMetrics};
 pub use outcome::Outcome;
+pub use precompile::{
+    DuplicatePrecompile, Precompile, PrecompileCall, PrecompileError, PrecompileOutput, Precompiles,
+};
+#[cfg(feature = "default-rocksdb")]
+pub use price_cache::RocksPriceCache;
+pub use price_cache::{
+    MemPriceCache, PriceCache, PriceCacheStats, PriceKey, DEFAULT_PRICE_CACHE_ENTRIES,
+};
+pub use query_cache::{QueryCache, QueryCacheConfig, QueryKey};
+pub use query_result::QueryResult;
+pub use state_roots::{StateRoots, DEFAULT_STATE_ROOTS_LAYERS};
+
+#[cfg(feature = "default-memory")]
+mod snapshot;
+
+#[cfg(feature = "default-memory")]
+pub use snapshot::RuntimeSnapshot;
 
 #[cfg(feature = "default-rocksdb")]
 mod rocksdb;
 
 #[cfg(feature = "default-rocksdb")]
-pub use rocksdb::create_rocksdb_runtime;
+pub use rocksdb::{create_rocksdb_runtime, open_rocksdb_template_store};
 
 pub use config::Config;
 pub use default::DefaultRuntime;
 
-use svm_types::{CallReceipt, Context, DeployReceipt, Envelope, SpawnReceipt};
+use svm_types::{
+    Address, BatchReceipt, CallReceipt, Context, DeployReceipt, Envelope, GasEstimate, Layer,
+    Section, SectionKind, SpawnReceipt, State, TemplateAddr, UpgradeReceipt,
+};
 
-use crate::error::ValidateError;
+use svm_storage::kv::FlushError;
+
+use crate::error::{CallAtError, ValidateError};
+use crate::trace::Trace;
 
 /// Specifies the interface of a SVM [`Runtime`].
 ///
 /// Any [`Runtime`] implementation will implement:
 ///
 /// * `Deploy Template`s
+/// * `Upgrade Template`s
 /// * `Spawn Account`s
 /// * `Call Account`s
+/// * `Call Batch`es
+///
+/// A transaction must never bring down the embedder: if its execution panics (due to a bug),
+/// the panic is caught and reported as a [`RuntimeError::InternalError`](svm_types::RuntimeError::InternalError)
+/// receipt. Reading a corrupted storage page (see [`PageCorrupted`](svm_storage::account::PageCorrupted))
+/// fails the transaction with a [`RuntimeError::StorageCorrupted`](svm_types::RuntimeError::StorageCorrupted) receipt.
 pub trait Runtime {
     /// Validates syntactically a binary `Deploy Template` message prior to executing it.
     fn validate_deploy(&self, message: &[u8]) -> Result<(), ValidateError>;
 
+    /// Validates syntactically a binary `Upgrade Template` message prior to executing it.
+    fn validate_upgrade(&self, message: &[u8]) -> Result<(), ValidateError>;
+
     /// Validates syntactically a binary `Spawn Account` message prior to executing it.
     fn validate_spawn(&self, message: &[u8]) -> Result<(), ValidateError>;
 
     /// Validates syntactically a binary `Call Account` message prior to executing it.
     fn validate_call(&self, message: &[u8]) -> Result<(), ValidateError>;
 
+    /// Validates syntactically a binary `Call Batch` message prior to executing it.
+    fn validate_batch(&self, message: &[u8]) -> Result<(), ValidateError>;
+
     /// Deploys a `Template`
     fn deploy(&mut self, envelope: &Envelope, message: &[u8], context: &Context) -> DeployReceipt;
 
+    /// Upgrades a deployed `Template` into a new version of its code.
+    ///
+    /// Only the original deployer of the `Template` may upgrade it, and the upgraded `Template`
+    /// must keep the storage variables of the current one. From then on, the `Account`s
+    /// of the `Template` execute its latest version.
+    fn upgrade(&mut self, envelope: &Envelope, message: &[u8], context: &Context)
+        -> UpgradeReceipt;
+
     /// Spawns a new `Account`
     fn spawn(&mut self, envelope: &Envelope, message: &[u8], context: &Context) -> SpawnReceipt;
 
+    /// Spawns a batch of `Account`s (each given by its `envelope` and binary `Spawn Account` message),
+    /// all under the same `context`, and returns their receipts (ordered as the `spawns`).
+    ///
+    /// Meant for spawning many `Account`s at once (i.e at genesis). The `progress` callback
+    /// is invoked each time one more spawn completes.
+    ///
+    /// The `Runtime` may defer running the `ctor`s until all the batch `Account`s have been created
+    /// (so the `ctor`s must not depend on the order of spawning). By default, the `spawns` are
+    /// executed one by one, just like by [`Runtime::spawn`].
+    ///
+    /// The messages are expected to have passed `validate_spawn` already.
+    fn spawn_bulk(
+        &mut self,
+        spawns: &[(Envelope, Vec<u8>)],
+        context: &Context,
+        progress: &mut dyn FnMut(BulkSpawnProgress),
+    ) -> Vec<SpawnReceipt> {
+        let total = spawns.len();
+
+        spawns
+            .iter()
+            .enumerate()
+            .map(|(i, (envelope, message))| {
+                let receipt = self.spawn(envelope, message, context);
+
+                progress(BulkSpawnProgress { done: i + 1, total });
+
+                receipt
+            })
+            .collect()
+    }
+
     /// Verifies a [`Transaction`](svm_types::Transaction) before execution.
+    ///
+    /// Runs the `svm_verify` function of the target `Account`, given the transaction's `verifydata`
+    /// as its input (and with no access to the `Account`'s storage).
     fn verify(&mut self, envelope: &Envelope, message: &[u8], context: &Context) -> CallReceipt;
 
     /// Executes a [`Transaction`](svm_types::Transaction) and returns its output [`CallReceipt`].
     ///
     /// This function should be called only if the `verify` stage has passed.
     fn call(&mut self, envelope: &Envelope, message: &[u8], context: &Context) -> CallReceipt;
+
+    /// Executes a [`BatchTransaction`](svm_types::BatchTransaction) atomically and returns
+    /// its output [`BatchReceipt`].
+    ///
+    /// The batched calls are executed in order, under the same `envelope` and `context`
+    /// (each call seeing the changes of its predecessors, and sharing the `gas_limit`).
+    /// The changes are committed at once, only after all the calls have succeeded:
+    /// when any of the calls fails, the whole batch fails and none of the changes are kept.
+    fn call_batch(
+        &mut self,
+        envelope: &Envelope,
+        message: &[u8],
+        context: &Context,
+    ) -> BatchReceipt;
+
+    /// Executes a [`Transaction`](svm_types::Transaction) against the historical global `state_root`,
+    /// as committed by the transactions of some past `Layer` (see [`Runtime::state_root`]).
+    ///
+    /// The execution is read-only: the storages are opened as of `state_root`
+    /// (see [`StorageMode::Historical`](crate::StorageMode::Historical)), leaving the current `State` intact,
+    /// and their changes are never committed (so the returned [`CallReceipt`] `new_state` is `state_root`).
+    /// It's meant for reproducing the outcome of a transaction at an exact point in history.
+    ///
+    /// Fails if `state_root` isn't the global `State` of any `Layer` (of the ones still tracked,
+    /// see [`Config::state_roots_layers`]), or when `message` can't be parsed.
+    fn call_at(
+        &mut self,
+        envelope: &Envelope,
+        message: &[u8],
+        state_root: &State,
+    ) -> std::result::Result<CallReceipt, CallAtError>;
+
+    /// Executes a read-only query (i.e a call to a `view` function) of a binary `Call Account` message,
+    /// against the `State` of `context`.
+    ///
+    /// The queried function may read the `Account`'s storage, but modifying it fails the query
+    /// (see [`ProtectedMode::ReadOnly`](crate::ProtectedMode::ReadOnly)). Nothing is ever committed,
+    /// and no receipt is produced: the [`QueryResult`] holds the returned data along with the gas estimate
+    /// of executing the message as a transaction.
+    fn query(&self, envelope: &Envelope, message: &[u8], context: &Context) -> QueryResult;
+
+    /// Estimates the gas of executing `message` (sent by the `envelope`'s principal), by pricing it
+    /// without executing anything.
+    ///
+    /// The returned [`GasEstimate`] is made of the price of the message's payload (see [`svm_gas::transaction`]),
+    /// the price of loading the `Template`s of the called `Account`s, and an upper bound of the price
+    /// of running the executed functions (for a `Template` running in fixed-gas mode).
+    /// When the functions can't be priced upfront, the recommended `gas_limit` falls back to the execution gas
+    /// allowance of the message's kind (see [`svm_codec::intrinsic_gas`]).
+    ///
+    /// The `message` is validated first (just like prior to executing it).
+    fn estimate_gas(
+        &self,
+        envelope: &Envelope,
+        message: &Message,
+    ) -> Result<GasEstimate, ValidateError>;
+
+    /// Executes the transactions of a whole `Layer`, in order, and returns their [`LayerOutcome`].
+    ///
+    /// Each message is validated first (a rejected message isn't executed), and then executed
+    /// against the global `State` left by its predecessors (starting from the `header`'s `State`).
+    /// The transactions are expected to have passed their `verify` stage already
+    /// (i.e before being included in the `Layer`), so it isn't executed again.
+    ///
+    /// Each transaction's `Context` is made of the `Layer` and of the transaction's [`TransactionId`](svm_types::TransactionId)
+    /// (the `Blake3` hash of its `Envelope` and message, see [`Message::tx_id`]). The produced receipts are stored in the [`ReceiptStore`](crate::replay::ReceiptStore)
+    /// of the `Runtime` (if any), and the outcome is reported to its [`MetricsObserver`] (if any).
+    fn execute_layer(&mut self, header: LayerHeader, txs: Vec<(Envelope, Message)>)
+        -> LayerOutcome;
+
+    /// Blocks until the writes of the transactions of `layer` (and of the preceding `Layer`s) have been persisted.
+    ///
+    /// Under [`WriteMode::WriteBehind`](svm_storage::kv::WriteMode::WriteBehind) (see [`Config::write_mode`])
+    /// the writes are persisted in the background, so it's expected to be called before finalizing `layer`.
+    /// Fails once persisting the writes has failed (see [`FlushError`]).
+    fn flush_until(&self, _layer: Layer) -> Result<(), FlushError> {
+        Ok(())
+    }
+
+    /// Takes the [`Trace`] recorded while executing the most recent `spawn`, `verify` or `call`.
+    ///
+    /// Returns `None` when tracing is disabled (see [`Config::trace_limit`]),
+    /// or when the `Runtime` doesn't support tracing at all.
+    fn take_trace(&mut self) -> Option<Trace> {
+        None
+    }
+
+    /// Returns the storage root of `account`, as of the most recently committed `State`.
+    ///
+    /// The root commits to the current values of all the `Account`'s storage variables
+    /// (both the fixed and the dynamic ones) and to its balance,
+    /// so it can be queried without executing any transaction.
+    ///
+    /// Returns `None` if there is no such `Account`.
+    fn current_state_root(&self, account: &Address) -> Option<State>;
+
+    /// Returns the global `State` at the end of `layer` (i.e committed by its last transaction).
+    ///
+    /// A `Layer` with no transactions keeps the global `State` of the preceding `Layer`s.
+    /// Returns `None` if no transaction has been committed up to `layer`, or when `layer` precedes
+    /// the tracked ones (see [`Config::state_roots_layers`]).
+    fn state_root(&self, layer: Layer) -> Option<State>;
+
+    /// Loads a single [`Section`] of a deployed `Template` (without decoding its other `Section`s).
+    ///
+    /// Returns `None` if there is no such `Template`, or it has no `Section` of the requested kind.
+    fn template_section(&self, template_addr: &TemplateAddr, kind: SectionKind) -> Option<Section>;
+
+    /// Iterates over the spawned `Account`s, ordered by their `Address`.
+    ///
+    /// The `Account`s are loaded lazily, as the iteration advances.
+    fn iter_accounts(&self) -> Box<dyn Iterator<Item = AccountInfo> + '_>;
+
+    /// Returns up to `limit` spawned `Account`s following `after`, ordered by their `Address`.
+    ///
+    /// Meant for paging through the `Account`s: each page should start after the last `Account`
+    /// of the previous page (a `None` starts from the first `Account`). The page is seeked right
+    /// to `after`, so the `Account`s of the preceding pages aren't visited again.
+    fn accounts_page(&self, after: Option<&Address>, limit: usize) -> Vec<AccountInfo>;
 }
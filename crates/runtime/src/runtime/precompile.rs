@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::rc::Rc;
+
+use thiserror::Error;
+
+use svm_storage::account::AccountStorage;
+use svm_types::{Address, Context, Envelope, Gas, ReceiptLog, TemplateAddr};
+
+use super::HostPhase;
+
+/// An execution of a [`Precompile`].
+#[derive(Debug, Clone, Copy)]
+pub struct PrecompileCall<'a> {
+    /// The name of the called function.
+    pub func_name: &'a str,
+
+    /// The input of the called function.
+    pub calldata: &'a [u8],
+
+    /// The `Address` of the called `Account`.
+    pub target: &'a Address,
+
+    /// The `Address` of the `Account`'s `Template`.
+    pub template: &'a TemplateAddr,
+
+    /// The kind of execution (running a `ctor`, `svm_verify` or a function of the `Account`).
+    pub phase: HostPhase,
+
+    /// The gas available for the execution.
+    pub gas_limit: Gas,
+
+    /// The `Envelope` of the executed transaction.
+    pub envelope: &'a Envelope,
+
+    /// The `Context` of the executed transaction.
+    pub context: &'a Context,
+}
+
+/// The output of a succeeding [`Precompile`] execution.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PrecompileOutput {
+    /// The data returned by the called function.
+    pub returndata: Vec<u8>,
+
+    /// The gas used by the execution.
+    pub gas_used: u64,
+
+    /// The logs emitted by the execution.
+    pub logs: Vec<ReceiptLog>,
+}
+
+/// The error type that can arise when executing a [`Precompile`].
+///
+/// Each error ends up within the receipt, just like the matching failure of Wasm code.
+#[derive(Debug, PartialEq, Clone, Error)]
+pub enum PrecompileError {
+    /// The called function isn't implemented (reported as `RuntimeError::FuncNotFound`).
+    #[error("The function isn't implemented by the precompile")]
+    FuncNotFound,
+
+    /// The execution has run out of gas (reported as `RuntimeError::OOG`).
+    #[error("The precompile has run out of gas")]
+    OOG,
+
+    /// The execution has failed (reported as `RuntimeError::FuncFailed`).
+    #[error("{0}")]
+    Failed(String),
+}
+
+/// A native implementation of `Template`s, built into the `Runtime`
+/// (see [`CodeKind::Precompile`](svm_types::CodeKind::Precompile)).
+///
+/// It runs instead of Wasm code for performance-critical system `Template`s (e.g the transfer `Template`).
+/// Its outcome is turned into a receipt just like the outcome of Wasm code
+/// (so the storage changes are committed only when it succeeds).
+pub trait Precompile {
+    /// Executes `call`.
+    ///
+    /// The `Account`'s `storage` is `None` while running `svm_verify`
+    /// (just like Wasm code, `svm_verify` has no storage access).
+    fn call(
+        &self,
+        call: &PrecompileCall,
+        storage: Option<&mut AccountStorage>,
+    ) -> Result<PrecompileOutput, PrecompileError>;
+}
+
+impl<F> Precompile for F
+where
+    F: Fn(
+        &PrecompileCall,
+        Option<&mut AccountStorage>,
+    ) -> Result<PrecompileOutput, PrecompileError>,
+{
+    fn call(
+        &self,
+        call: &PrecompileCall,
+        storage: Option<&mut AccountStorage>,
+    ) -> Result<PrecompileOutput, PrecompileError> {
+        self(call, storage)
+    }
+}
+
+/// The error returned when registering a [`Precompile`] under an id that's already taken.
+#[derive(Debug, PartialEq, Clone, Copy, Error)]
+#[error("A precompile of id #{0} is already registered")]
+pub struct DuplicatePrecompile(pub u32);
+
+/// The [`Precompile`]s provided to the `Runtime`, addressed by their id.
+#[derive(Clone, Default)]
+pub struct Precompiles {
+    precompiles: BTreeMap<u32, Rc<dyn Precompile>>,
+}
+
+impl Precompiles {
+    /// Creates an empty [`Precompiles`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `precompile` under `id`.
+    ///
+    /// Fails if `id` is already taken.
+    pub fn register<P>(&mut self, id: u32, precompile: P) -> Result<(), DuplicatePrecompile>
+    where
+        P: Precompile + 'static,
+    {
+        if self.precompiles.contains_key(&id) {
+            return Err(DuplicatePrecompile(id));
+        }
+
+        self.precompiles.insert(id, Rc::new(precompile));
+        Ok(())
+    }
+
+    /// Returns the [`Precompile`] registered under `id`.
+    pub fn get(&self, id: u32) -> Option<&dyn Precompile> {
+        self.precompiles
+            .get(&id)
+            .map(|precompile| precompile.as_ref())
+    }
+
+    /// Iterates over the ids of the registered [`Precompile`]s (in ascending order).
+    pub fn ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.precompiles.keys().copied()
+    }
+
+    /// The number of registered [`Precompile`]s.
+    pub fn len(&self) -> usize {
+        self.precompiles.len()
+    }
+
+    /// Returns whether there are no registered [`Precompile`]s.
+    pub fn is_empty(&self) -> bool {
+        self.precompiles.is_empty()
+    }
+}
+
+impl fmt::Debug for Precompiles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.ids()).finish()
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+use svm_gas::FuncPrice;
+use svm_types::TemplateAddr;
+
+use super::{PriceCache, PriceCacheStats, PriceKey};
+
+struct Entry {
+    prices: Rc<FuncPrice>,
+    last_used: u64,
+}
+
+/// An in-memory [`PriceCache`] holding up to `max_entries` prices.
+///
+/// Once full, the least recently used prices are evicted first.
+pub struct MemPriceCache {
+    max_entries: usize,
+    entries: HashMap<PriceKey, Entry>,
+    usage: BTreeMap<u64, PriceKey>,
+    clock: u64,
+    stats: PriceCacheStats,
+}
+
+impl MemPriceCache {
+    /// Creates a new empty [`MemPriceCache`] holding up to `max_entries` prices.
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            entries: HashMap::with_capacity(max_entries),
+            usage: BTreeMap::new(),
+            clock: 0,
+            stats: PriceCacheStats::default(),
+        }
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+}
+
+impl PriceCache for MemPriceCache {
+    fn get(&mut self, key: &PriceKey) -> Option<Rc<FuncPrice>> {
+        let now = self.tick();
+
+        match self.entries.get_mut(key) {
+            Some(entry) => {
+                self.usage.remove(&entry.last_used);
+                self.usage.insert(now, key.clone());
+                entry.last_used = now;
+
+                self.stats.hits += 1;
+                Some(Rc::clone(&entry.prices))
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    fn insert(&mut self, key: PriceKey, prices: Rc<FuncPrice>) {
+        if self.max_entries == 0 {
+            return;
+        }
+
+        let now = self.tick();
+        let entry = Entry {
+            prices,
+            last_used: now,
+        };
+
+        if let Some(replaced) = self.entries.insert(key.clone(), entry) {
+            self.usage.remove(&replaced.last_used);
+        }
+        self.usage.insert(now, key);
+
+        while self.entries.len() > self.max_entries {
+            let (&oldest, _) = self.usage.iter().next().unwrap();
+            let key = self.usage.remove(&oldest).unwrap();
+
+            self.entries.remove(&key);
+            self.stats.evictions += 1;
+        }
+    }
+
+    fn remove(&mut self, template_addr: &TemplateAddr) {
+        let usage = &mut self.usage;
+
+        self.entries.retain(|key, entry| {
+            let keep = &key.template_addr != template_addr;
+            if !keep {
+                usage.remove(&entry.last_used);
+            }
+            keep
+        });
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.usage.clear();
+    }
+
+    fn stats(&self) -> PriceCacheStats {
+        PriceCacheStats {
+            entries: self.entries.len(),
+            ..self.stats
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8, pricing_version: u16) -> PriceKey {
+        PriceKey::new(TemplateAddr::repeat(byte), pricing_version)
+    }
+
+    fn prices(price: usize) -> Rc<FuncPrice> {
+        let mut prices = FuncPrice::new();
+        prices.set(svm_program::FuncIndex(0), price);
+
+        Rc::new(prices)
+    }
+
+    #[test]
+    fn mem_price_cache_evicts_least_recently_used() {
+        let mut cache = MemPriceCache::new(2);
+
+        cache.insert(key(1, 0), prices(10));
+        cache.insert(key(2, 0), prices(20));
+
+        // Using the prices of the first `Template` makes the second one the least recently used
+        assert_eq!(cache.get(&key(1, 0)), Some(prices(10)));
+
+        cache.insert(key(3, 0), prices(30));
+
+        assert_eq!(cache.get(&key(2, 0)), None);
+        assert_eq!(cache.get(&key(1, 0)), Some(prices(10)));
+        assert_eq!(cache.get(&key(3, 0)), Some(prices(30)));
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 3);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.entries, 2);
+    }
+
+    #[test]
+    fn mem_price_cache_remove_drops_all_pricing_versions() {
+        let mut cache = MemPriceCache::new(4);
+
+        cache.insert(key(1, 0), prices(10));
+        cache.insert(key(1, 1), prices(11));
+        cache.insert(key(2, 0), prices(20));
+
+        cache.remove(&TemplateAddr::repeat(1));
+
+        assert_eq!(cache.get(&key(1, 0)), None);
+        assert_eq!(cache.get(&key(1, 1)), None);
+        assert_eq!(cache.get(&key(2, 0)), Some(prices(20)));
+        assert_eq!(cache.stats().entries, 1);
+
+        // Inserting after a removal still evicts in order
+        cache.insert(key(3, 0), prices(30));
+        cache.insert(key(4, 0), prices(40));
+        cache.insert(key(5, 0), prices(50));
+        cache.insert(key(6, 0), prices(60));
+
+        assert_eq!(cache.get(&key(2, 0)), None);
+        assert_eq!(cache.stats().entries, 4);
+    }
+
+    #[test]
+    fn mem_price_cache_zero_entries() {
+        let mut cache = MemPriceCache::new(0);
+
+        cache.insert(key(1, 0), prices(10));
+
+        assert_eq!(cache.get(&key(1, 0)), None);
+        assert_eq!(cache.stats().entries, 0);
+    }
+}
@@ -0,0 +1,83 @@
+//! Caching the [`FuncPrice`]s of the priced `Template`s (see [`PriceCache`]).
+
+use std::rc::Rc;
+
+use svm_gas::FuncPrice;
+use svm_types::TemplateAddr;
+
+mod memory;
+
+pub use memory::MemPriceCache;
+
+#[cfg(feature = "default-rocksdb")]
+mod rocksdb;
+
+#[cfg(feature = "default-rocksdb")]
+pub use self::rocksdb::RocksPriceCache;
+
+/// The number of `Template`s whose prices are kept by the [`PriceCache`] a
+/// [`DefaultRuntime`](crate::DefaultRuntime) creates when none is injected.
+pub const DEFAULT_PRICE_CACHE_ENTRIES: usize = 1024;
+
+/// Identifies the prices of a `Template`.
+///
+/// The same `Template` is priced differently by each version of the [`PriceResolver`](svm_gas::PriceResolver),
+/// hence the `pricing_version` is part of the key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PriceKey {
+    /// The priced `Template`.
+    pub template_addr: TemplateAddr,
+
+    /// The version of the [`PriceResolver`](svm_gas::PriceResolver) used for pricing.
+    pub pricing_version: u16,
+}
+
+impl PriceKey {
+    /// New [`PriceKey`].
+    pub fn new(template_addr: TemplateAddr, pricing_version: u16) -> Self {
+        Self {
+            template_addr,
+            pricing_version,
+        }
+    }
+}
+
+/// The statistics of a [`PriceCache`] (see [`DefaultRuntime::price_cache_stats`](crate::DefaultRuntime::price_cache_stats)).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PriceCacheStats {
+    /// The number of lookups answered from the cache.
+    pub hits: u64,
+
+    /// The number of lookups that required pricing the `Template`.
+    pub misses: u64,
+
+    /// The number of cached prices evicted for making room for newer ones.
+    pub evictions: u64,
+
+    /// The number of currently cached prices.
+    pub entries: usize,
+}
+
+/// Caches the [`FuncPrice`]s of the `Template`s priced by a [`DefaultRuntime`](crate::DefaultRuntime).
+///
+/// Pricing a `Template` requires parsing and walking its whole code, so it's done once per `Template`
+/// (and pricing version). Since `Template`s may be deployed at any time, an implementation is expected
+/// to bound the number of prices it holds.
+pub trait PriceCache {
+    /// Returns the cached prices of `key` (when there are any).
+    fn get(&mut self, key: &PriceKey) -> Option<Rc<FuncPrice>>;
+
+    /// Caches the `prices` of `key`.
+    fn insert(&mut self, key: PriceKey, prices: Rc<FuncPrice>);
+
+    /// Drops the cached prices of the `Template` residing at `template_addr` (under any pricing version).
+    ///
+    /// Called once the `Template` is upgraded (its code changes).
+    fn remove(&mut self, template_addr: &TemplateAddr);
+
+    /// Drops all the cached prices.
+    fn clear(&mut self);
+
+    /// Returns the cache's statistics.
+    fn stats(&self) -> PriceCacheStats;
+}
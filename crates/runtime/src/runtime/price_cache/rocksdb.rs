@@ -0,0 +1,146 @@
+use std::io::Cursor;
+use std::path::Path;
+use std::rc::Rc;
+
+use svm_codec::{ReadExt, WriteExt};
+use svm_gas::FuncPrice;
+use svm_kv::rocksdb::Rocksdb;
+use svm_kv::traits::RawKV;
+use svm_program::FuncIndex;
+use svm_types::{Address, TemplateAddr};
+
+use super::{PriceCache, PriceCacheStats, PriceKey};
+
+const PRICES_KEY_PREFIX: &'static [u8] = b"template-prices:";
+
+/// A [`PriceCache`] backed by `rocksdb`, so that the prices outlive the `Runtime`.
+///
+/// There is a single entry per `Template` and pricing version, so the number of entries is bounded
+/// by the number of deployed `Template`s. Removed prices are overwritten by an empty value
+/// (which is read as a missing entry).
+pub struct RocksPriceCache {
+    db: Rocksdb,
+    stats: PriceCacheStats,
+}
+
+impl RocksPriceCache {
+    /// Creates a new prices cache at the given path
+    pub fn new<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Self {
+            db: Rocksdb::new(path),
+            stats: PriceCacheStats::default(),
+        }
+    }
+
+    #[inline]
+    fn template_prefix(template_addr: &TemplateAddr) -> Vec<u8> {
+        let mut key = Vec::with_capacity(PRICES_KEY_PREFIX.len() + Address::len() + 1);
+
+        key.extend_from_slice(PRICES_KEY_PREFIX);
+        key.extend_from_slice(template_addr.inner().as_slice());
+        key.push(b':');
+
+        key
+    }
+
+    #[inline]
+    fn prices_key(key: &PriceKey) -> Vec<u8> {
+        // Keys mapping from a (`Template Address`, pricing version) to the `Template`'s prices
+        // are of the pattern "template-prices:TEMPLATE_ADDRESS:VERSION"
+
+        let mut raw_key = Self::template_prefix(&key.template_addr);
+        raw_key.extend_from_slice(&key.pricing_version.to_be_bytes());
+
+        raw_key
+    }
+}
+
+impl PriceCache for RocksPriceCache {
+    fn get(&mut self, key: &PriceKey) -> Option<Rc<FuncPrice>> {
+        let prices = self
+            .db
+            .get(&Self::prices_key(key))
+            .filter(|bytes| !bytes.is_empty())
+            .and_then(|bytes| decode_prices(&bytes));
+
+        if prices.is_some() {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+        }
+
+        prices.map(Rc::new)
+    }
+
+    fn insert(&mut self, key: PriceKey, prices: Rc<FuncPrice>) {
+        let raw_key = Self::prices_key(&key);
+        let bytes = encode_prices(&prices);
+
+        self.db.set(&[(&raw_key[..], &bytes[..])]);
+    }
+
+    fn remove(&mut self, template_addr: &TemplateAddr) {
+        let keys = self
+            .db
+            .keys_with_prefix(&Self::template_prefix(template_addr));
+        let changes: Vec<(&[u8], &[u8])> = keys.iter().map(|key| (&key[..], &[][..])).collect();
+
+        self.db.set(&changes);
+    }
+
+    fn clear(&mut self) {
+        let keys = self.db.keys_with_prefix(PRICES_KEY_PREFIX);
+        let changes: Vec<(&[u8], &[u8])> = keys.iter().map(|key| (&key[..], &[][..])).collect();
+
+        self.db.set(&changes);
+    }
+
+    fn stats(&self) -> PriceCacheStats {
+        let entries = self
+            .db
+            .keys_with_prefix(PRICES_KEY_PREFIX)
+            .iter()
+            .filter(|key| matches!(self.db.get(key), Some(bytes) if !bytes.is_empty()))
+            .count();
+
+        PriceCacheStats {
+            entries,
+            ..self.stats
+        }
+    }
+}
+
+/// Encodes `prices` as a `#Functions (u32)` followed by
+/// a (`Function Index (u32)`, `Price (u64)`) pair per function (all Big-Endian).
+fn encode_prices(prices: &FuncPrice) -> Vec<u8> {
+    let funcs: Vec<(FuncIndex, usize)> = prices.iter().collect();
+    let mut w = Vec::with_capacity(4 + funcs.len() * 12);
+
+    w.write_u32_be(funcs.len() as u32);
+
+    for (fn_index, price) in funcs {
+        w.write_u32_be(fn_index.0);
+        w.write_u64_be(price as u64);
+    }
+
+    w
+}
+
+fn decode_prices(bytes: &[u8]) -> Option<FuncPrice> {
+    let mut cursor = Cursor::new(bytes);
+
+    let count = cursor.read_u32_be().ok()? as usize;
+    let mut prices = FuncPrice::with_capacity(count);
+
+    for _ in 0..count {
+        let fn_index = cursor.read_u32_be().ok()?;
+        let price = cursor.read_u64_be().ok()?;
+
+        prices.set(FuncIndex(fn_index), price as usize);
+    }
+
+    Some(prices)
+}
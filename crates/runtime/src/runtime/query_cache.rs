@@ -0,0 +1,153 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use svm_types::{Address, CallReceipt, Gas, Layer, State, TransactionId};
+
+/// Limits of the cache used by [`Runtime::query`](crate::Runtime::query)
+/// (see [`DefaultRuntime::query_receipt`](crate::DefaultRuntime::query_receipt)).
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryCacheConfig {
+    /// The maximum number of cached results. The oldest result is evicted first.
+    pub max_entries: usize,
+
+    /// How long a cached result remains valid. `None` means until evicted.
+    pub ttl: Option<Duration>,
+}
+
+/// Identifies a query's result.
+///
+/// It's made of every input the queried function can observe: the queried code, the storage,
+/// the function's input, and the `Envelope` and `Context` fields exposed to running code
+/// (only the `gas_limit` is left out, see [`QueryCache::get`]).
+///
+/// Since the `State` is part of the key, a cached result is never returned once the
+/// queried state changes (there is no need for explicit invalidation).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QueryKey {
+    /// The queried `Account`.
+    pub account: Address,
+
+    /// The version of the queried `Account`'s `Template` (the code being run).
+    pub template_version: u16,
+
+    /// The `State` the query has been executed against.
+    pub state: State,
+
+    /// The name of the queried function.
+    pub func_name: String,
+
+    /// The query's input.
+    pub calldata: Vec<u8>,
+
+    /// The `Envelope`'s principal.
+    pub principal: Address,
+
+    /// The `Envelope`'s amount.
+    pub amount: u64,
+
+    /// The `Envelope`'s gas fee.
+    pub gas_fee: u64,
+
+    /// The `Context`'s transaction id.
+    pub tx_id: TransactionId,
+
+    /// The `Context`'s `Layer`.
+    pub layer: Layer,
+}
+
+struct Entry {
+    receipt: CallReceipt,
+    inserted_at: Instant,
+}
+
+/// A bounded cache of successful query results.
+pub struct QueryCache {
+    config: QueryCacheConfig,
+    entries: HashMap<QueryKey, Entry>,
+    order: VecDeque<QueryKey>,
+    hits: u64,
+    misses: u64,
+}
+
+impl QueryCache {
+    /// Creates a new empty [`QueryCache`] limited by `config`.
+    pub fn new(config: QueryCacheConfig) -> Self {
+        Self {
+            entries: HashMap::with_capacity(config.max_entries),
+            order: VecDeque::with_capacity(config.max_entries),
+            config,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the cached result of `key`, provided it hasn't expired and it fits within `gas_limit`.
+    pub fn get(&mut self, key: &QueryKey, gas_limit: Gas) -> Option<CallReceipt> {
+        let ttl = self.config.ttl;
+
+        let receipt = self
+            .entries
+            .get(key)
+            .filter(|entry| ttl.map_or(true, |ttl| entry.inserted_at.elapsed() < ttl))
+            .filter(|entry| {
+                gas_limit.is_none() || entry.receipt.gas_used.unwrap_or(0) <= gas_limit.unwrap()
+            })
+            .map(|entry| entry.receipt.clone());
+
+        if receipt.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+
+        receipt
+    }
+
+    /// Caches `receipt` as the result of `key`. Failed results aren't cached.
+    pub fn insert(&mut self, key: QueryKey, receipt: CallReceipt) {
+        if !receipt.success || self.config.max_entries == 0 {
+            return;
+        }
+
+        let entry = Entry {
+            receipt,
+            inserted_at: Instant::now(),
+        };
+
+        if self.entries.insert(key.clone(), entry).is_some() {
+            self.order.retain(|k| k != &key);
+        }
+        self.order.push_back(key);
+
+        while self.order.len() > self.config.max_entries {
+            let oldest = self.order.pop_front().unwrap();
+            self.entries.remove(&oldest);
+        }
+    }
+
+    /// The number of cached results.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether there are no cached results.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The number of lookups answered from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// The number of lookups not answered from the cache.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Drops all the cached results.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
@@ -0,0 +1,43 @@
+use svm_types::{CallReceipt, Gas, ReceiptLog, RuntimeError};
+
+/// The outcome of a read-only query (see [`Runtime::query`](crate::Runtime::query)).
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryResult {
+    /// Whether the queried function has run successfully.
+    pub success: bool,
+
+    /// The error of a failed query.
+    pub error: Option<RuntimeError>,
+
+    /// The data returned by the queried function.
+    pub returndata: Vec<u8>,
+
+    /// The gas of executing the query's message as a `Call Account` transaction (including its intrinsic gas).
+    ///
+    /// Meant to be used as the `gas_limit` of that transaction (as long as the queried state doesn't change).
+    /// It's missing when the query has failed.
+    pub gas_estimate: Gas,
+
+    /// The logs emitted by the queried function.
+    pub logs: Vec<ReceiptLog>,
+}
+
+impl QueryResult {
+    /// Creates a [`QueryResult`] out of the [`CallReceipt`] of the query's execution,
+    /// where `intrinsic_gas` is the gas charged prior to that execution.
+    pub fn from_receipt(receipt: CallReceipt, intrinsic_gas: u64) -> Self {
+        let gas_estimate = if receipt.success {
+            receipt.gas_used + intrinsic_gas
+        } else {
+            Gas::new()
+        };
+
+        Self {
+            success: receipt.success,
+            error: receipt.error,
+            returndata: receipt.returndata.unwrap_or_default(),
+            gas_estimate,
+            logs: receipt.logs,
+        }
+    }
+}
@@ -1,53 +1,88 @@
-use std::cell::RefCell;
 use std::path::Path;
-use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 
-use storage::StorageBuilderFn;
-use svm_layout::Layout;
+use storage::{StorageBuilderFn, StorageMode};
+use svm_layout::FixedLayout;
 use svm_storage::account::{AccountKVStore, AccountStorage};
-use svm_storage::kv::StatefulKV;
-use svm_types::{AccountAddr, State};
+use svm_storage::kv::{StatefulKV, WriteMode};
+use svm_types::{Address, State};
 
 use crate::{env, storage};
-use crate::{Config, DefaultRuntime, Env};
+use crate::{Config, DefaultRuntime, Env, HostModules, RocksPriceCache};
 
-use env::{DefaultRocksAccountStore, DefaultRocksEnvTypes, DefaultRocksTemplateStore};
+use env::{
+    CachedTemplateStore, DefaultRocksAccountStore, DefaultRocksEnvTypes, DefaultRocksTemplateStore,
+};
 
-/// Creates a new `Runtime` backed by `rocksdb` for persistence.
-pub fn create_rocksdb_runtime<P>(
-    state_kv: &Rc<RefCell<dyn StatefulKV>>,
-    kv_path: &P,
-) -> DefaultRuntime<DefaultRocksEnvTypes>
+/// The maximum number of `Template`s cached in-memory (see [`CachedTemplateStore`]).
+const TEMPLATE_CACHE_ENTRIES: usize = 1024;
+
+/// Creates a new `Runtime` backed by `rocksdb` for persistence (under [`Config::kv_path`]).
+///
+/// The `Template`s and `Account`s stores persist their writes according to [`Config::write_mode`].
+pub fn create_rocksdb_runtime(
+    state_kv: &Arc<Mutex<dyn StatefulKV + Send>>,
+    config: Config,
+) -> DefaultRuntime<DefaultRocksEnvTypes> {
+    let env = build_env(&config.kv_path, config.write_mode);
+    let price_cache = RocksPriceCache::new(config.kv_path.join("prices"));
+
+    DefaultRuntime::new(
+        env,
+        HostModules::new(),
+        storage_builder(state_kv),
+        config,
+        Some(Box::new(price_cache)),
+    )
+}
+
+/// Opens the `Account`s and `Template`s stores under `kv_path` (each in its own `rocksdb` directory),
+/// persisting their writes according to `write_mode` (see [`Config::write_mode`]).
+fn build_env<P>(kv_path: &P, write_mode: WriteMode) -> Env<DefaultRocksEnvTypes>
 where
     P: AsRef<Path>,
 {
-    todo!()
-    // let env = build_env(&kv_path);
+    let kv_path = kv_path.as_ref();
 
-    // DefaultRuntime::new(env, kv_path, storage_builder(state_kv))
+    let account_store = DefaultRocksAccountStore::new(kv_path.join("accounts"), write_mode);
+    let template_store = CachedTemplateStore::new(
+        open_rocksdb_template_store(&kv_path, write_mode),
+        TEMPLATE_CACHE_ENTRIES,
+    );
+
+    Env::new(account_store, template_store)
 }
 
-fn build_env<P>(kv_path: &P) -> Env<DefaultRocksEnvTypes>
+/// Opens the `Template`s store of a `Runtime` backed by `rocksdb` under `kv_path` (see [`create_rocksdb_runtime`]),
+/// persisting its writes according to `write_mode`.
+pub fn open_rocksdb_template_store<P>(
+    kv_path: &P,
+    write_mode: WriteMode,
+) -> DefaultRocksTemplateStore
 where
     P: AsRef<Path>,
 {
-    let account_store = DefaultRocksAccountStore::new(kv_path);
-    let template_store = DefaultRocksTemplateStore::new(kv_path);
-
-    Env::new(account_store, template_store)
+    DefaultRocksTemplateStore::new(kv_path.as_ref().join("templates"), write_mode)
 }
 
-pub fn storage_builder(state_kv: &Rc<RefCell<dyn StatefulKV>>) -> Box<StorageBuilderFn> {
-    let state_kv = Rc::clone(state_kv);
+pub fn storage_builder(state_kv: &Arc<Mutex<dyn StatefulKV + Send>>) -> Box<StorageBuilderFn> {
+    let state_kv = Arc::clone(state_kv);
 
-    let func = move |addr: &AccountAddr, _state: &State, layout: &Layout, _config: &Config| {
-        // The current pointed-to `State` is managed externally, so we ignore here the `state` parameter.
+    let func = move |addr: &Address,
+                     state: &State,
+                     mode: StorageMode,
+                     layout: &FixedLayout,
+                     _config: &Config| {
+        // The current pointed-to `State` is managed externally, so a storage opened for executing transactions
+        // ignores here the `state` parameter. A historical storage reads the external key-value store as of `state`.
         //
         // Similarly, we ignore the `config` parameter since it only contains the `Path` of the key-value store
         // used for managing the Account's storage. We talk with the external key-value store via FFI interface.
 
-        let addr = addr.inner();
-        let account_kv = AccountKVStore::new(addr.clone(), &state_kv);
+        let account_kv = match mode {
+            StorageMode::Current => AccountKVStore::new(addr.clone(), &state_kv),
+            StorageMode::Historical => AccountKVStore::at(addr.clone(), &state_kv, state),
+        };
 
         AccountStorage::new(layout.clone(), account_kv)
     };
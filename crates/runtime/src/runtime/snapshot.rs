@@ -0,0 +1,25 @@
+use svm_storage::kv::FakeKV;
+
+use super::StateRoots;
+use crate::env::{DefaultMemAccountStore, DefaultMemTemplateStore};
+
+/// A point-in-time copy of an in-memory [`DefaultRuntime`](crate::DefaultRuntime).
+///
+/// Holds the deployed `Template`s, the spawned `Account`s, their storage and the
+/// committed state roots. The `Template`s prices aren't captured (restoring a snapshot
+/// drops the cached prices, see [`PriceCache::clear`](crate::PriceCache::clear)). Restoring a [`RuntimeSnapshot`]
+/// is much cheaper than re-executing the transactions that led to it, which makes it handy
+/// for resetting a `Runtime` to a known state between test cases.
+///
+/// See [`DefaultRuntime::snapshot`](crate::DefaultRuntime::snapshot) and
+/// [`DefaultRuntime::restore`](crate::DefaultRuntime::restore).
+#[derive(Clone)]
+pub struct RuntimeSnapshot {
+    pub(crate) templates: DefaultMemTemplateStore,
+
+    pub(crate) accounts: DefaultMemAccountStore,
+
+    pub(crate) kv: FakeKV,
+
+    pub(crate) state_roots: StateRoots,
+}
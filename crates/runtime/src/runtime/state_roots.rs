@@ -0,0 +1,137 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use svm_types::{Address, Layer, State};
+
+/// The default number of the most recent [`Layer`]s tracked by a [`StateRoots`].
+pub const DEFAULT_STATE_ROOTS_LAYERS: usize = 4096;
+
+/// Tracks the state commitments observed by a [`DefaultRuntime`](crate::DefaultRuntime).
+///
+/// Records the global `State` committed by the transactions of each [`Layer`], and caches
+/// the computed storage roots of `Account`s. An `Account` root is cached along with the
+/// global `State` it has been computed against, so it's recomputed only once the global
+/// `State` moves on (the roots computed against older `State`s are dropped then).
+///
+/// The tracking is kept in memory only, and bounded: just the `limit` most recent [`Layer`]s
+/// are tracked (the oldest ones are forgotten as newer ones get recorded). A `Runtime`
+/// starts over with no tracked [`Layer`]s, so the historical `State`s it may execute against
+/// (see [`Runtime::call_at`](crate::Runtime::call_at)) are the ones committed since.
+#[derive(Debug, Clone)]
+pub struct StateRoots {
+    head: Option<State>,
+    layers: BTreeMap<Layer, State>,
+    by_state: HashMap<State, BTreeSet<Layer>>,
+    accounts: HashMap<Address, (State, State)>,
+    limit: usize,
+}
+
+impl Default for StateRoots {
+    fn default() -> Self {
+        Self::with_limit(DEFAULT_STATE_ROOTS_LAYERS)
+    }
+}
+
+impl StateRoots {
+    /// Creates a new empty [`StateRoots`] tracking up to [`DEFAULT_STATE_ROOTS_LAYERS`] `Layer`s.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new empty [`StateRoots`] tracking up to `limit` `Layer`s.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is zero.
+    pub fn with_limit(limit: usize) -> Self {
+        assert!(limit > 0, "`StateRoots` must track at least one `Layer`");
+
+        Self {
+            head: None,
+            layers: BTreeMap::new(),
+            by_state: HashMap::new(),
+            accounts: HashMap::new(),
+            limit,
+        }
+    }
+
+    /// Records `state` as committed by a transaction of `layer`.
+    pub fn record(&mut self, layer: Layer, state: State) {
+        if let Some(prev) = self.layers.insert(layer, state.clone()) {
+            self.unindex(&prev, layer);
+        }
+
+        self.by_state
+            .entry(state.clone())
+            .or_default()
+            .insert(layer);
+
+        while self.layers.len() > self.limit {
+            let (&oldest, _) = self.layers.iter().next().unwrap();
+            let root = self.layers.remove(&oldest).unwrap();
+
+            self.unindex(&root, oldest);
+        }
+
+        if self.head.as_ref() != Some(&state) {
+            self.accounts.clear();
+        }
+
+        self.head = Some(state);
+    }
+
+    /// The most recently committed global `State` (if any).
+    pub fn head(&self) -> Option<&State> {
+        self.head.as_ref()
+    }
+
+    /// The global `State` at the end of `layer`.
+    ///
+    /// A [`Layer`] without any committing transactions keeps the `State` of the preceding ones.
+    /// Returns `None` for a [`Layer`] preceding the tracked ones.
+    pub fn layer_root(&self, layer: Layer) -> Option<&State> {
+        let (&first, _) = self.layers.iter().next()?;
+
+        if layer < first {
+            return None;
+        }
+
+        self.layers
+            .range(..=layer)
+            .next_back()
+            .map(|(_, state)| state)
+    }
+
+    /// The (earliest tracked) [`Layer`] whose transactions have committed `state` as its global `State`.
+    ///
+    /// Returns `None` if `state` isn't a known global `State` of any tracked [`Layer`].
+    pub fn layer_of(&self, state: &State) -> Option<Layer> {
+        self.by_state
+            .get(state)
+            .and_then(|layers| layers.iter().next())
+            .copied()
+    }
+
+    /// The cached root of `account`, in case it has been computed against `head`.
+    pub fn account_root(&self, account: &Address, head: &State) -> Option<&State> {
+        self.accounts
+            .get(account)
+            .filter(|(state, _)| state == head)
+            .map(|(_, root)| root)
+    }
+
+    /// Caches the `root` of `account` computed against `head`.
+    pub fn insert_account_root(&mut self, account: Address, head: State, root: State) {
+        self.accounts.insert(account, (head, root));
+    }
+
+    /// Drops `layer` from the [`Layer`]s known to have committed `state`.
+    fn unindex(&mut self, state: &State, layer: Layer) {
+        if let Some(layers) = self.by_state.get_mut(state) {
+            layers.remove(&layer);
+
+            if layers.is_empty() {
+                self.by_state.remove(state);
+            }
+        }
+    }
+}
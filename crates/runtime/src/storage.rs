@@ -5,4 +5,16 @@ use svm_types::{Address, State};
 use crate::Config;
 
 /// [`AccountStorage`] building function signature.
-pub type StorageBuilderFn = dyn Fn(&Address, &State, &FixedLayout, &Config) -> AccountStorage;
+pub type StorageBuilderFn =
+    dyn Fn(&Address, &State, StorageMode, &FixedLayout, &Config) -> AccountStorage;
+
+/// The way a [`StorageBuilderFn`] opens an [`AccountStorage`] at the given `State`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageMode {
+    /// The storage is opened for executing transactions, and its changes may be committed.
+    Current,
+
+    /// The storage is a read-only view of a historical `State`, which leaves the current one intact
+    /// (see [`AccountKVStore::at`](svm_storage::account::AccountKVStore::at)).
+    Historical,
+}
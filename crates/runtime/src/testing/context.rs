@@ -0,0 +1,210 @@
+//! A deterministic clock for tests (see [`MockContextProvider`]).
+
+use std::collections::VecDeque;
+
+use svm_types::{Context, Layer, State, TransactionId};
+
+/// A point in the (mocked) time: a [`Layer`] along with its timestamp (in seconds).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MockTick {
+    /// The [`Layer`].
+    pub layer: Layer,
+
+    /// The timestamp of the [`Layer`] (in seconds).
+    pub timestamp: u64,
+}
+
+/// Provides the [`Context`]s of the transactions executed by a test, following a scripted
+/// progression of [`Layer`]s and timestamps.
+///
+/// Each call to [`MockContextProvider::next`] returns the [`Context`] of the next transaction.
+/// It's given a fresh [`TransactionId`], the current [`State`] (see [`MockContextProvider::set_state`])
+/// and the next scripted [`MockTick`]. Once the script is exhausted the time stands still,
+/// until moved explicitly (see [`MockContextProvider::advance`]).
+///
+/// The time never goes backwards.
+#[derive(Debug, Clone)]
+pub struct MockContextProvider {
+    genesis: MockTick,
+    layer_duration: u64,
+    now: MockTick,
+    script: VecDeque<MockTick>,
+    state: State,
+    tx_count: u64,
+    history: Vec<(TransactionId, MockTick)>,
+}
+
+impl MockContextProvider {
+    /// Creates a new [`MockContextProvider`] starting at `genesis`,
+    /// where each [`Layer`] lasts `layer_duration` seconds.
+    pub fn new(genesis: MockTick, layer_duration: u64) -> Self {
+        Self {
+            genesis,
+            layer_duration,
+            now: genesis,
+            script: VecDeque::new(),
+            state: State::zeros(),
+            tx_count: 0,
+            history: Vec::new(),
+        }
+    }
+
+    /// Appends `ticks` to the script. Each scripted [`MockTick`] is used by a single transaction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `ticks` go backwards in time.
+    pub fn script<I>(&mut self, ticks: I)
+    where
+        I: IntoIterator<Item = MockTick>,
+    {
+        for tick in ticks {
+            let last = self.script.back().unwrap_or(&self.now);
+
+            assert!(
+                tick.layer >= last.layer && tick.timestamp >= last.timestamp,
+                "The scripted ticks must not go backwards in time (got {:?} after {:?})",
+                tick,
+                last
+            );
+
+            self.script.push_back(tick);
+        }
+    }
+
+    /// Appends `layers` to the script (see [`Self::script`]). Their timestamps are derived
+    /// from the `genesis` and the layers duration.
+    pub fn script_layers<I>(&mut self, layers: I)
+    where
+        I: IntoIterator<Item = u64>,
+    {
+        let ticks = layers
+            .into_iter()
+            .map(|layer| self.tick_of(Layer(layer)))
+            .collect::<Vec<_>>();
+
+        self.script(ticks);
+    }
+
+    /// Returns the [`MockTick`] of `layer` (derived from the `genesis` and the layers duration).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `layer` precedes the `genesis` layer.
+    pub fn tick_of(&self, layer: Layer) -> MockTick {
+        let elapsed = layer
+            .since(self.genesis.layer)
+            .expect("`layer` must not precede the genesis layer");
+
+        MockTick {
+            layer,
+            timestamp: self.genesis.timestamp + elapsed * self.layer_duration,
+        }
+    }
+
+    /// The current [`MockTick`].
+    pub fn now(&self) -> MockTick {
+        self.now
+    }
+
+    /// Moves the time `layers` layers forward.
+    pub fn advance(&mut self, layers: u64) {
+        let layer = self
+            .now
+            .layer
+            .checked_add(layers)
+            .expect("`Layer` overflow");
+
+        self.advance_to(layer);
+    }
+
+    /// Moves the time forward to `layer`, dropping the scripted [`MockTick`]s preceding it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `layer` is in the past.
+    pub fn advance_to(&mut self, layer: Layer) {
+        assert!(
+            layer >= self.now.layer,
+            "Can't move back in time (from layer {} to layer {})",
+            self.now.layer,
+            layer
+        );
+
+        self.now = self.tick_of(layer);
+
+        while matches!(self.script.front(), Some(tick) if tick.layer < layer) {
+            self.script.pop_front();
+        }
+    }
+
+    /// Sets the [`State`] of the next transactions (usually the `new_state` of the last receipt).
+    pub fn set_state(&mut self, state: State) {
+        self.state = state;
+    }
+
+    /// Returns the [`Context`] of the next transaction.
+    pub fn next(&mut self) -> Context {
+        if let Some(tick) = self.script.pop_front() {
+            self.now = tick;
+        }
+
+        self.tx_count += 1;
+
+        let mut bytes = [0; TransactionId::len()];
+        bytes[TransactionId::len() - 8..].copy_from_slice(&self.tx_count.to_be_bytes());
+        let tx_id = TransactionId::from(bytes);
+
+        self.history.push((tx_id.clone(), self.now));
+
+        Context::new(tx_id, self.now.layer, self.state.clone())
+    }
+
+    /// The [`MockTick`] each of the transactions has been given, in execution order.
+    pub fn history(&self) -> &[(TransactionId, MockTick)] {
+        &self.history
+    }
+
+    /// Returns the [`MockTick`] transaction `tx_id` has been given.
+    pub fn tick_of_tx(&self, tx_id: &TransactionId) -> Option<MockTick> {
+        self.history
+            .iter()
+            .find(|(id, _)| id == tx_id)
+            .map(|(_, tick)| *tick)
+    }
+}
+
+/// Asserts that a time-gated action is denied before layer `unlock`, and allowed at `unlock`.
+///
+/// `exec` executes the action given its [`Context`], and returns whether it has succeeded.
+/// It's executed at the layer preceding `unlock` (unless that's in the past), and then at `unlock`.
+///
+/// # Panics
+///
+/// Panics if the assertion fails, or if `unlock` is in the past.
+pub fn assert_unlocks_at<F>(provider: &mut MockContextProvider, unlock: Layer, mut exec: F)
+where
+    F: FnMut(&Context) -> bool,
+{
+    if let Some(locked) = unlock.checked_sub(1) {
+        if locked >= provider.now().layer {
+            provider.advance_to(locked);
+
+            let context = provider.next();
+            assert!(
+                !exec(&context),
+                "Expected the action to be denied at layer {}",
+                locked
+            );
+        }
+    }
+
+    provider.advance_to(unlock);
+
+    let context = provider.next();
+    assert!(
+        exec(&context),
+        "Expected the action to be allowed at layer {}",
+        unlock
+    );
+}
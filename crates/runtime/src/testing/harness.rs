@@ -0,0 +1,243 @@
+use serde_json::{json, Value as Json};
+
+use std::path::Path;
+
+use svm_codec::api::json;
+use svm_types::{
+    Address, CallReceipt, Context, Envelope, Layer, SpawnReceipt, State, TemplateAddr,
+    TransactionId,
+};
+
+use crate::env::DefaultMemEnvTypes;
+use crate::{DefaultRuntime, Runtime};
+
+/// An in-memory `Runtime` (see [`create_memory_runtime`](super::create_memory_runtime)) wrapped
+/// for integration tests, so that deploying a `Template`, spawning an `Account` and calling it
+/// take a single line each.
+///
+/// The transactions are given in the JSON formats understood by [`svm_codec::api::json`].
+/// Each transaction runs at the current `Layer` (see [`TestRuntime::next_layer`]) against the
+/// latest `State` (i.e the one resulting from the last successful transaction),
+/// and is sent using the same [`Envelope`] (see [`TestRuntime::set_envelope`]).
+///
+/// Since it's intended for tests, malformed JSONs (and failed deploys) panic.
+/// It's available under the `testing` feature.
+///
+/// ```no_run
+/// use serde_json::json;
+/// use svm_runtime::testing::TestRuntime;
+///
+/// let mut runtime = TestRuntime::new();
+///
+/// let template = runtime.deploy_file("tests/wasm/runtime_calldata.wasm");
+/// let receipt = runtime.spawn(&template, "initialize", json!({"abi": [], "data": []}));
+/// let account = receipt.account_addr().clone();
+///
+/// let receipt = runtime.call(&account, "load_addr", json!({"abi": [], "data": []}));
+/// assert!(receipt.success);
+/// ```
+pub struct TestRuntime {
+    runtime: DefaultRuntime<DefaultMemEnvTypes>,
+
+    envelope: Envelope,
+
+    layer: Layer,
+
+    state: State,
+
+    tx_count: u64,
+
+    spawn_count: u64,
+}
+
+impl TestRuntime {
+    /// Creates a new blank `TestRuntime` (starting at `Layer` `0`).
+    pub fn new() -> Self {
+        Self {
+            runtime: super::create_memory_runtime(),
+            envelope: Envelope::default(),
+            layer: Layer(0),
+            state: State::zeros(),
+            tx_count: 0,
+            spawn_count: 0,
+        }
+    }
+
+    /// The underlying `Runtime` (for anything not covered by the helpers).
+    pub fn runtime(&mut self) -> &mut DefaultRuntime<DefaultMemEnvTypes> {
+        &mut self.runtime
+    }
+
+    /// Sets the [`Envelope`] of the following transactions.
+    pub fn set_envelope(&mut self, envelope: Envelope) {
+        self.envelope = envelope;
+    }
+
+    /// Moves to the next `Layer`, returning it.
+    pub fn next_layer(&mut self) -> Layer {
+        self.layer = Layer(self.layer.0 + 1);
+
+        self.layer
+    }
+
+    /// The current `Layer`.
+    pub fn layer(&self) -> Layer {
+        self.layer
+    }
+
+    /// The `State` resulting from the last successful transaction.
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Deploys the `Template` of the Wasm file at `path` (a binary `.wasm`, or a textual one otherwise).
+    ///
+    /// The rest of the `Template` is given by an optional manifest, residing next to the Wasm file
+    /// under the same name with a `.json` extension (e.g `counter.json` for `counter.wasm`).
+    /// The manifest is of the JSON format of [`json::deploy_template`] (without the `code`), and any
+    /// missing field is defaulted: the `name` to the file's stem, the versions to `0`, the `desc`
+    /// to an empty string, and the `data` and `ctors` to none (i.e a `Template` with no storage and
+    /// no `ctor`s).
+    ///
+    /// ```json
+    /// {
+    ///   "data": "00000014",
+    ///   "ctors": ["initialize"]
+    /// }
+    /// ```
+    pub fn deploy_file<P: AsRef<Path>>(&mut self, path: P) -> TemplateAddr {
+        let path = path.as_ref();
+
+        let bytes = std::fs::read(path)
+            .unwrap_or_else(|e| panic!("Failed reading `{}`: {}", path.display(), e));
+        let code = match path.extension() {
+            Some(ext) if ext == "wasm" => bytes,
+            _ => wat::parse_bytes(&bytes)
+                .unwrap_or_else(|e| panic!("Invalid Wasm `{}`: {}", path.display(), e))
+                .into_owned(),
+        };
+
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut deploy = json!({
+            "svm_version": 0,
+            "code_version": 0,
+            "name": name,
+            "desc": "",
+            "data": "",
+            "ctors": [],
+        });
+
+        let manifest_path = path.with_extension("json");
+        if manifest_path.exists() {
+            let manifest = std::fs::read_to_string(&manifest_path).unwrap();
+            let manifest: Json = serde_json::from_str(&manifest).unwrap_or_else(|e| {
+                panic!("Invalid manifest `{}`: {}", manifest_path.display(), e)
+            });
+
+            for (key, value) in manifest
+                .as_object()
+                .expect("manifest must be a JSON object")
+            {
+                deploy[key] = value.clone();
+            }
+        }
+
+        deploy["code"] = json!(hex::encode_upper(code));
+
+        self.deploy_json(&deploy.to_string())
+    }
+
+    /// Deploys a `Template` given in the JSON format of [`json::deploy_template`].
+    pub fn deploy_json(&mut self, json: &str) -> TemplateAddr {
+        let message = json::deploy_template_raw(json).expect("Invalid `Deploy Template` JSON");
+        let context = self.next_context();
+
+        let receipt = self.runtime.deploy(&self.envelope, &message, &context);
+
+        match receipt.addr {
+            Some(addr) if receipt.success => addr,
+            _ => panic!("`Deploy Template` has failed: {:?}", receipt.error),
+        }
+    }
+
+    /// Spawns an `Account` of `template` by running its `ctor` given `calldata`
+    /// (of the `{"abi": [...], "data": [...]}` form).
+    ///
+    /// Each spawned `Account` is given a distinct name (i.e `Account #1`, `Account #2` and so on).
+    pub fn spawn(&mut self, template: &TemplateAddr, ctor: &str, calldata: Json) -> SpawnReceipt {
+        self.spawn_count += 1;
+
+        let spawn = json!({
+            "version": 0,
+            "template": hex::encode_upper(template.as_slice()),
+            "name": format!("Account #{}", self.spawn_count),
+            "ctor_name": ctor,
+            "calldata": calldata,
+        });
+
+        self.spawn_json(&spawn.to_string())
+    }
+
+    /// Spawns an `Account` given in the JSON format of [`json::encode_spawn`].
+    ///
+    /// The following transactions run against the `State` left by the whole `Spawn`
+    /// (i.e including the post-`ctor` call, see [`SpawnReceipt::final_state`]).
+    pub fn spawn_json(&mut self, json: &str) -> SpawnReceipt {
+        let message = json::encode_spawn_raw(json).expect("Invalid `Spawn Account` JSON");
+        let context = self.next_context();
+
+        let receipt = self.runtime.spawn(&self.envelope, &message, &context);
+
+        if receipt.success {
+            self.state = receipt.final_state().clone();
+        }
+
+        receipt
+    }
+
+    /// Calls function `func` of `Account` `target` given `calldata`
+    /// (of the `{"abi": [...], "data": [...]}` form).
+    pub fn call(&mut self, target: &Address, func: &str, calldata: Json) -> CallReceipt {
+        let call = json!({
+            "version": 0,
+            "target": hex::encode_upper(target.as_slice()),
+            "func_name": func,
+            "verifydata": {"abi": [], "data": []},
+            "calldata": calldata,
+        });
+
+        self.call_json(&call.to_string())
+    }
+
+    /// Calls an `Account` given in the JSON format of [`json::encode_call`].
+    pub fn call_json(&mut self, json: &str) -> CallReceipt {
+        let message = json::encode_call_raw(json).expect("Invalid `Call Account` JSON");
+        let context = self.next_context();
+
+        let receipt = self.runtime.call(&self.envelope, &message, &context);
+
+        if receipt.success {
+            self.state = receipt.new_state().clone();
+        }
+
+        receipt
+    }
+
+    fn next_context(&mut self) -> Context {
+        self.tx_count += 1;
+
+        let mut tx_id = [0; 32];
+        tx_id[24..].copy_from_slice(&self.tx_count.to_be_bytes());
+
+        Context::new(TransactionId::from(tx_id), self.layer, self.state.clone())
+    }
+}
+
+impl Default for TestRuntime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,347 @@
+//! Implements common functionality to be consumed by tests.
+
+mod context;
+pub use context::{assert_unlocks_at, MockContextProvider, MockTick};
+
+#[cfg(feature = "testing")]
+mod harness;
+
+#[cfg(feature = "testing")]
+pub use harness::TestRuntime;
+
+use std::sync::{Arc, Mutex};
+
+use svm_codec::api::builder::{CallBuilder, SpawnBuilder, TemplateBuilder};
+use svm_codec::{batch, spawn, template, upgrade};
+use svm_layout::{FixedLayout, Layout};
+use svm_storage::{
+    account::{AccountKVStore, AccountStorage},
+    kv::{FakeKV, StatefulKV},
+};
+use svm_types::{
+    Address, BatchTransaction, CodeSection, CtorsSection, DataSection, DefaultsSection,
+    HeaderSection, State, Template, TemplateAddr, Transaction, UpgradeTemplate,
+};
+
+use crate::env::{
+    CachedTemplateStore, DefaultCachedMemEnvTypes, DefaultMemAccountStore, DefaultMemEnvTypes,
+    DefaultMemTemplateStore,
+};
+use crate::storage::{StorageBuilderFn, StorageMode};
+use crate::{Config, DefaultRuntime, Env, HostModules};
+
+/// Hold a Wasm file in textual or binary form
+pub enum WasmFile<'a> {
+    /// Textual Wasm
+    Text(&'a str),
+
+    /// Binary Wasm
+    Binary(&'a [u8]),
+}
+
+impl<'a> WasmFile<'a> {
+    /// Returns the underlying bytes of the Wasm file binary.  
+    pub fn into_bytes(self) -> Vec<u8> {
+        match self {
+            Self::Text(text) => wat::parse_str(text).unwrap(),
+            Self::Binary(wasm) => wasm.to_vec(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for WasmFile<'a> {
+    fn from(text: &'a str) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl<'a> From<&'a [u8]> for WasmFile<'a> {
+    fn from(wasm: &'a [u8]) -> Self {
+        Self::Binary(wasm)
+    }
+}
+
+/// Given an `Account` `Address` and its `layout`, it initializes a new blank [`AccountStorage`].
+pub fn blank_storage(account_addr: &Address, layout: &FixedLayout) -> AccountStorage {
+    let kv = memory_kv_init();
+    let account_kv = AccountKVStore::new(account_addr.clone(), &kv);
+
+    AccountStorage::new(layout.clone(), account_kv)
+}
+
+/// Returns a new in-memory [`StatefulKV`].
+pub fn memory_kv_init() -> Arc<Mutex<dyn StatefulKV + Send>> {
+    Arc::new(Mutex::new(FakeKV::new()))
+}
+
+/// Creates an in-memory `Runtime` backed by a `state_kv`.
+///
+/// The returned `Runtime` supports taking snapshots (see [`DefaultRuntime::snapshot`]).
+pub fn create_memory_runtime() -> DefaultRuntime<DefaultMemEnvTypes> {
+    let memory_kv = Arc::new(Mutex::new(FakeKV::new()));
+    let kv: Arc<Mutex<dyn StatefulKV + Send>> = memory_kv.clone();
+    let storage_builder = runtime_memory_storage_builder(&kv);
+
+    let template_store = DefaultMemTemplateStore::new();
+    let account_store = DefaultMemAccountStore::new();
+    let env = Env::<DefaultMemEnvTypes>::new(account_store, template_store);
+
+    let config = Config::default();
+    DefaultRuntime::new(
+        env,
+        HostModules::new(),
+        Box::new(storage_builder),
+        config,
+        None,
+    )
+    .with_memory_kv(memory_kv)
+}
+
+/// Creates an in-memory `Runtime` caching up to `max_entries` loaded `Template`s
+/// (see [`CachedTemplateStore`]).
+pub fn create_cached_memory_runtime(
+    max_entries: usize,
+) -> DefaultRuntime<DefaultCachedMemEnvTypes> {
+    let kv = memory_kv_init();
+    let storage_builder = runtime_memory_storage_builder(&kv);
+
+    let template_store = CachedTemplateStore::new(DefaultMemTemplateStore::new(), max_entries);
+    let account_store = DefaultMemAccountStore::new();
+    let env = Env::<DefaultCachedMemEnvTypes>::new(account_store, template_store);
+
+    DefaultRuntime::new(
+        env,
+        HostModules::new(),
+        Box::new(storage_builder),
+        Config::default(),
+        None,
+    )
+}
+
+/// Returns a function (wrapped inside [`Box`]) that initializes an `Account`'s storage client.
+fn runtime_memory_storage_builder(kv: &Arc<Mutex<dyn StatefulKV + Send>>) -> Box<StorageBuilderFn> {
+    let kv = kv.clone();
+
+    let func = move |account_addr: &Address,
+                     state: &State,
+                     mode: StorageMode,
+                     layout: &FixedLayout,
+                     _config: &Config| match mode {
+        StorageMode::Current => {
+            let account_kv = AccountKVStore::new(account_addr.clone(), &kv);
+            let mut storage = AccountStorage::new(layout.clone(), account_kv);
+            storage.rewind(state);
+
+            storage
+        }
+        StorageMode::Historical => {
+            // The shared key-value store isn't rewound (its `State` is the current one of all the `Account`s)
+            let account_kv = AccountKVStore::at(account_addr.clone(), &kv, state);
+
+            AccountStorage::new(layout.clone(), account_kv)
+        }
+    };
+
+    Box::new(func)
+}
+
+/// Builds a binary `Deploy Template` transaction.
+pub fn build_deploy(
+    code_version: u32,
+    name: &str,
+    layout: FixedLayout,
+    ctors: &[String],
+    wasm: WasmFile,
+) -> Vec<u8> {
+    build_deploy_with_defaults(
+        code_version,
+        name,
+        layout,
+        ctors,
+        wasm,
+        DefaultsSection::default(),
+    )
+}
+
+/// Builds a binary `Deploy Template` transaction whose storage variables have `defaults`.
+///
+/// The `Defaults Section` is omitted when `defaults` is empty.
+pub fn build_deploy_with_defaults(
+    code_version: u32,
+    name: &str,
+    layout: FixedLayout,
+    ctors: &[String],
+    wasm: WasmFile,
+    defaults: DefaultsSection,
+) -> Vec<u8> {
+    let code = CodeSection::new_fixed(wasm.into_bytes(), 0);
+
+    encode_deploy(code, code_version, name, layout, ctors, defaults)
+}
+
+/// Builds a binary `Deploy Template` transaction of a [`GasMode::Metering`](svm_types::GasMode::Metering) `Template`.
+pub fn build_deploy_metering(
+    code_version: u32,
+    name: &str,
+    layout: FixedLayout,
+    ctors: &[String],
+    wasm: WasmFile,
+) -> Vec<u8> {
+    let code = CodeSection::new_metering(wasm.into_bytes(), 0);
+
+    encode_deploy(
+        code,
+        code_version,
+        name,
+        layout,
+        ctors,
+        DefaultsSection::default(),
+    )
+}
+
+/// Builds a binary `Deploy Template` transaction of a precompile `Template`,
+/// implemented by the [`Precompile`](crate::Precompile) registered under `id`.
+pub fn build_deploy_precompile(
+    code_version: u32,
+    name: &str,
+    layout: FixedLayout,
+    ctors: &[String],
+    id: u32,
+) -> Vec<u8> {
+    let code = CodeSection::new_precompile(id, 0);
+
+    encode_deploy(
+        code,
+        code_version,
+        name,
+        layout,
+        ctors,
+        DefaultsSection::default(),
+    )
+}
+
+/// Builds a binary `Upgrade Template` transaction, upgrading the `Template` located at `template`.
+pub fn build_upgrade(
+    template: &TemplateAddr,
+    code_version: u32,
+    name: &str,
+    layout: FixedLayout,
+    ctors: &[String],
+    wasm: WasmFile,
+) -> Vec<u8> {
+    let code = CodeSection::new_fixed(wasm.into_bytes(), 0);
+    let upgrade = UpgradeTemplate {
+        version: 0,
+        template_addr: template.clone(),
+        template: build_template(
+            code,
+            code_version,
+            name,
+            layout,
+            ctors,
+            DefaultsSection::default(),
+        ),
+    };
+
+    let mut bytes = Vec::new();
+    upgrade::encode(&upgrade, &mut bytes);
+
+    bytes
+}
+
+fn encode_deploy(
+    code: CodeSection,
+    code_version: u32,
+    name: &str,
+    layout: FixedLayout,
+    ctors: &[String],
+    defaults: DefaultsSection,
+) -> Vec<u8> {
+    let template = build_template(code, code_version, name, layout, ctors, defaults);
+
+    template::encode(&template)
+}
+
+fn build_template(
+    code: CodeSection,
+    code_version: u32,
+    name: &str,
+    layout: FixedLayout,
+    ctors: &[String],
+    defaults: DefaultsSection,
+) -> Template {
+    let ctors = CtorsSection::new(ctors.to_vec());
+    let data = DataSection::with_layout(Layout::Fixed(layout));
+    let header = HeaderSection::new(code_version, name.to_string(), "".to_string());
+
+    let mut builder = TemplateBuilder::default()
+        .with_code(code)
+        .with_data(data)
+        .with_ctors(ctors)
+        .with_header(header);
+
+    if !defaults.is_empty() {
+        builder = builder.with_defaults(defaults);
+    }
+
+    builder.build()
+}
+
+/// Builds a binary `Spawn Account` transaction.
+pub fn build_spawn(template: &TemplateAddr, name: &str, ctor: &str, calldata: &[u8]) -> Vec<u8> {
+    SpawnBuilder::new()
+        .with_version(0)
+        .with_template(template)
+        .with_name(name)
+        .with_ctor(ctor)
+        .with_calldata(calldata)
+        .build()
+}
+
+/// Builds a binary `Spawn Account` transaction, calling `func` right after the `ctor`.
+pub fn build_spawn_with_call(
+    template: &TemplateAddr,
+    name: &str,
+    ctor: &str,
+    calldata: &[u8],
+    func: &str,
+    func_calldata: &[u8],
+) -> Vec<u8> {
+    SpawnBuilder::new()
+        .with_version(spawn::SPAWN_CALL_VERSION)
+        .with_template(template)
+        .with_name(name)
+        .with_ctor(ctor)
+        .with_calldata(calldata)
+        .with_call(func, func_calldata)
+        .build()
+}
+
+/// Builds a binary `Call Account` transaction. (a.k.a a `Transaction`).
+pub fn build_call(target: &Address, func: &str, calldata: &[u8]) -> Vec<u8> {
+    CallBuilder::new()
+        .with_version(0)
+        .with_target(target)
+        .with_func(func)
+        .with_calldata(calldata)
+        .build()
+}
+
+/// Builds a binary `Call Batch` transaction out of `(target, func, calldata)` calls.
+pub fn build_batch(calls: &[(&Address, &str, &[u8])]) -> Vec<u8> {
+    let calls = calls
+        .iter()
+        .map(|(target, func, calldata)| Transaction {
+            version: 0,
+            target: (*target).clone(),
+            func_name: func.to_string(),
+            verifydata: Vec::new(),
+            calldata: calldata.to_vec(),
+        })
+        .collect();
+
+    let mut bytes = Vec::new();
+    batch::encode_batch(&BatchTransaction { version: 0, calls }, &mut bytes).unwrap();
+
+    bytes
+}
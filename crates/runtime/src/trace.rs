@@ -0,0 +1,268 @@
+//! Recording the `vmcalls` made by a running transaction (see [`Trace`]).
+//!
+//! Each recorded `vmcall` is appended to a compact binary buffer:
+//!
+//! ```text
+//!
+//!  +----------+------------+--------------------+-------------+--------------------+
+//!  |          |            |                    |             |                    |
+//!  |  vmcall  |  #args (n) |       args         |  has result |   result           |
+//!  |  (u8)    |   (u8)     |  n x (u64)         |    (u8)     |   (u64, optional)  |
+//!  |          |            |  (Big-Endian)      |             |   (Big-Endian)     |
+//!  |          |            |                    |             |                    |
+//!  +----------+------------+--------------------+-------------+--------------------+
+//!
+//! ```
+
+use byteorder::{BigEndian, ByteOrder};
+
+use std::fmt;
+
+/// The `vmcalls` being recorded into a [`Trace`].
+#[allow(missing_docs)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum VmCall {
+    StaticAlloc = 0,
+    CalldataOffset = 1,
+    CalldataLen = 2,
+    SetReturndata = 3,
+    Get32 = 4,
+    Set32 = 5,
+    Get64 = 6,
+    Set64 = 7,
+    Load160 = 8,
+    Store160 = 9,
+    Log = 10,
+    StorageReadMany = 11,
+    StorageWriteMany = 12,
+    LayoutLen = 13,
+    LayoutCount = 14,
+    GetBit = 15,
+    SetBit = 16,
+    Metric = 17,
+    Call = 18,
+    CallReturndata = 19,
+    Transfer = 20,
+    LogEvent = 21,
+    Layer = 22,
+    Principal = 23,
+    TxId = 24,
+    StoreDyn = 25,
+    LoadDyn = 26,
+}
+
+impl VmCall {
+    /// The name under which the `vmcall` is imported by the running code.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::StaticAlloc => "svm_static_alloc",
+            Self::CalldataOffset => "svm_calldata_offset",
+            Self::CalldataLen => "svm_calldata_len",
+            Self::SetReturndata => "svm_set_returndata",
+            Self::Get32 => "svm_get32",
+            Self::Set32 => "svm_set32",
+            Self::Get64 => "svm_get64",
+            Self::Set64 => "svm_set64",
+            Self::Load160 => "svm_load160",
+            Self::Store160 => "svm_store160",
+            Self::Log => "svm_log",
+            Self::StorageReadMany => "svm_storage_read_many",
+            Self::StorageWriteMany => "svm_storage_write_many",
+            Self::LayoutLen => "svm_layout_len",
+            Self::LayoutCount => "svm_layout_count",
+            Self::GetBit => "svm_get_bit",
+            Self::SetBit => "svm_set_bit",
+            Self::Metric => "svm_metric",
+            Self::Call => "svm_call",
+            Self::CallReturndata => "svm_call_returndata",
+            Self::Transfer => "svm_transfer",
+            Self::LogEvent => "svm_log_event",
+            Self::Layer => "svm_layer",
+            Self::Principal => "svm_principal",
+            Self::TxId => "svm_tx_id",
+            Self::StoreDyn => "svm_store_dyn",
+            Self::LoadDyn => "svm_load_dyn",
+        }
+    }
+
+    fn from_u8(byte: u8) -> Option<Self> {
+        let vmcall = match byte {
+            0 => Self::StaticAlloc,
+            1 => Self::CalldataOffset,
+            2 => Self::CalldataLen,
+            3 => Self::SetReturndata,
+            4 => Self::Get32,
+            5 => Self::Set32,
+            6 => Self::Get64,
+            7 => Self::Set64,
+            8 => Self::Load160,
+            9 => Self::Store160,
+            10 => Self::Log,
+            11 => Self::StorageReadMany,
+            12 => Self::StorageWriteMany,
+            13 => Self::LayoutLen,
+            14 => Self::LayoutCount,
+            15 => Self::GetBit,
+            16 => Self::SetBit,
+            17 => Self::Metric,
+            18 => Self::Call,
+            19 => Self::CallReturndata,
+            20 => Self::Transfer,
+            21 => Self::LogEvent,
+            22 => Self::Layer,
+            23 => Self::Principal,
+            24 => Self::TxId,
+            25 => Self::StoreDyn,
+            26 => Self::LoadDyn,
+            _ => return None,
+        };
+
+        Some(vmcall)
+    }
+}
+
+/// A single recorded `vmcall`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEntry {
+    /// The invoked `vmcall`.
+    pub vmcall: VmCall,
+
+    /// The `vmcall` arguments.
+    pub args: Vec<u64>,
+
+    /// The `vmcall` result (`None` for `vmcalls` returning nothing).
+    pub result: Option<u64>,
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let args: Vec<String> = self.args.iter().map(|arg| arg.to_string()).collect();
+
+        write!(f, "{}({})", self.vmcall.name(), args.join(", "))?;
+
+        match self.result {
+            Some(result) => write!(f, " -> {}", result),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A bounded-size binary recording of the `vmcalls` made by a running transaction.
+///
+/// Once the next entry doesn't fit in the size limit, recording stops and the
+/// [`Trace`] is marked as truncated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace {
+    bytes: Vec<u8>,
+    limit: usize,
+    truncated: bool,
+}
+
+impl Trace {
+    /// Creates a new empty [`Trace`] holding at most `limit` bytes.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            bytes: Vec::new(),
+            limit,
+            truncated: false,
+        }
+    }
+
+    /// Records a single `vmcall`.
+    pub fn record(&mut self, vmcall: VmCall, args: &[u64], result: Option<u64>) {
+        debug_assert!(args.len() <= u8::MAX as usize);
+
+        let size = 1 + 1 + args.len() * 8 + 1 + result.map_or(0, |_| 8);
+
+        if self.truncated || self.bytes.len() + size > self.limit {
+            self.truncated = true;
+            return;
+        }
+
+        self.bytes.push(vmcall as u8);
+        self.bytes.push(args.len() as u8);
+
+        for &arg in args {
+            self.push_u64(arg);
+        }
+
+        match result {
+            Some(result) => {
+                self.bytes.push(1);
+                self.push_u64(result);
+            }
+            None => self.bytes.push(0),
+        }
+    }
+
+    /// The binary trace.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns whether some `vmcalls` haven't been recorded due to the size limit.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Decodes the recorded [`TraceEntry`]s.
+    pub fn entries(&self) -> Vec<TraceEntry> {
+        decode(&self.bytes).expect("A recorded `Trace` should be decodable")
+    }
+
+    fn push_u64(&mut self, n: u64) {
+        let mut buf = [0; 8];
+        BigEndian::write_u64(&mut buf, n);
+
+        self.bytes.extend_from_slice(&buf);
+    }
+}
+
+/// Decodes a binary trace (see [`Trace::as_bytes`]).
+///
+/// Returns `None` if `bytes` isn't a valid binary trace.
+pub fn decode(mut bytes: &[u8]) -> Option<Vec<TraceEntry>> {
+    let mut entries = Vec::new();
+
+    while !bytes.is_empty() {
+        let (&vmcall, rest) = bytes.split_first()?;
+        let (&nargs, mut rest) = rest.split_first()?;
+        let vmcall = VmCall::from_u8(vmcall)?;
+
+        let mut args = Vec::with_capacity(nargs as usize);
+        for _ in 0..nargs {
+            let (arg, tail) = read_u64(rest)?;
+            args.push(arg);
+            rest = tail;
+        }
+
+        let (&has_result, rest) = rest.split_first()?;
+        let (result, rest) = match has_result {
+            0 => (None, rest),
+            1 => {
+                let (result, rest) = read_u64(rest)?;
+                (Some(result), rest)
+            }
+            _ => return None,
+        };
+
+        entries.push(TraceEntry {
+            vmcall,
+            args,
+            result,
+        });
+        bytes = rest;
+    }
+
+    Some(entries)
+}
+
+fn read_u64(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    if bytes.len() < 8 {
+        return None;
+    }
+
+    let (n, rest) = bytes.split_at(8);
+    Some((BigEndian::read_u64(n), rest))
+}
@@ -2,6 +2,7 @@ use wasmer::{Bytes, Pages, WASM_PAGE_SIZE};
 
 use std::convert::TryFrom;
 
+use crate::trace::VmCall;
 use crate::FuncEnv;
 
 /// Statistically allocate `size` bytes within a [`FuncEnv`] `env`.
@@ -22,6 +23,8 @@ pub fn static_alloc(env: &FuncEnv, size: u32) -> u32 {
 
     set_used_memory(env, new_used);
 
+    env.record_vmcall(VmCall::StaticAlloc, &[size as u64], Some(new_used));
+
     new_used as u32
 }
 
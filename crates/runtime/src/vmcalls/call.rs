@@ -0,0 +1,212 @@
+use log::trace;
+
+use svm_types::{Address, Gas};
+
+use crate::func_env::NestedCall;
+use crate::trace::VmCall;
+use crate::{FuncEnv, ProtectedMode, MAX_CALL_DEPTH};
+
+/// Returned by [`call`] when the called function has failed.
+pub const CALL_FAILED: i32 = -1;
+
+/// Returned by [`call`] when the called `Account` is already on the [`CallStack`](crate::CallStack).
+pub const CALL_REENTRANT: i32 = -2;
+
+/// Returned by [`call`] when the [`CallStack`](crate::CallStack) is already [`MAX_CALL_DEPTH`] deep.
+pub const CALL_TOO_DEEP: i32 = -3;
+
+/// Calls function `func_name` of the `Account` residing at `target`, passing it `calldata`:
+///
+/// * `target` - the `Address` residing at memory offset `target_ptr`.
+/// * `func_name` - the UTF-8 name residing at memory cells `[func_ptr, func_ptr + func_len)`.
+/// * `calldata` - the memory cells `[calldata_ptr, calldata_ptr + calldata_len)`.
+///
+/// The called function gets up to `gas_limit` gas (`0` stands for all the gas left),
+/// and runs under the [`ProtectedMode`] of the caller (e.g a querying caller can't modify any storage),
+/// narrowed to [`ProtectedMode::CalleeAccess`] for a fully-accessing caller (i.e it can't modify its callers).
+///
+/// The storage changes of the called function are staged by the caller: they're committed along with
+/// the caller's changes (so they're discarded when the caller fails, or when it doesn't commit at all).
+///
+/// Note that the called function sees the storages as committed (along with the changes staged by
+/// the preceding calls), but not the uncommitted changes of its callers. In particular, coins transferred
+/// by the caller to the called `Account` (see [`transfer`](super::transfer)) aren't reflected
+/// in the called `Account` balance until the caller commits.
+///
+/// On success, returns the length of the `returndata` (see [`call_returndata`]),
+/// the used gas is charged and the logs of the called function are appended to the caller's logs.
+/// Otherwise, one of the following is returned:
+///
+/// * [`CALL_FAILED`] - the called function has failed (its changes are discarded and the whole sub-limit is charged).
+/// * [`CALL_REENTRANT`] - `target` is already executing (reentrant calls aren't allowed).
+/// * [`CALL_TOO_DEEP`] - the chain of calls has reached [`MAX_CALL_DEPTH`] `Account`s.
+///
+/// Traps when the caller can't access any storage (e.g while running `svm_alloc`).
+#[allow(clippy::too_many_arguments)]
+pub fn call(
+    env: &FuncEnv,
+    target_ptr: u32,
+    func_ptr: u32,
+    func_len: u32,
+    calldata_ptr: u32,
+    calldata_len: u32,
+    gas_limit: u64,
+) -> i32 {
+    trace!(
+        "svm_call (target_ptr = {}, func_ptr = {}, func_len = {}, calldata_ptr = {}, calldata_len = {}, gas_limit = {})",
+        target_ptr,
+        func_ptr,
+        func_len,
+        calldata_ptr,
+        calldata_len,
+        gas_limit
+    );
+
+    let protected_mode = env.protected_mode();
+    if protected_mode == ProtectedMode::AccessDenied {
+        let msg = "Calling other `Account`s isn't allowed while the storage is inaccessible";
+
+        wasmer::RuntimeError::raise(msg.into());
+    }
+
+    let (target, func_name, calldata, state, gas_left, commit, staged) = {
+        let mut borrow = env.borrow_mut();
+
+        // The buffers of the former call (i.e its `calldata` and `returndata`) are released
+        borrow.reset_arena();
+
+        let calldata = borrow.alloc_from_memory(calldata_ptr as usize, calldata_len as usize);
+
+        let view = borrow.memory().view::<u8>();
+        let read = |ptr: u32, len: usize| -> Vec<u8> {
+            let start = ptr as usize;
+
+            view[start..start + len]
+                .iter()
+                .map(|cell| cell.get())
+                .collect()
+        };
+
+        let target = Address::from(&read(target_ptr, Address::len())[..]);
+        let func_name = String::from_utf8(read(func_ptr, func_len as usize));
+
+        (
+            target,
+            func_name,
+            calldata,
+            borrow.storage_head(),
+            borrow.nested_gas_left(),
+            borrow.commits(),
+            borrow.staged().clone(),
+        )
+    };
+
+    let func_name = match func_name {
+        Ok(func_name) => func_name,
+        Err(..) => {
+            let msg = "The called function name isn't a valid UTF-8 string";
+
+            wasmer::RuntimeError::raise(msg.into());
+        }
+    };
+
+    let call_stack = env.call_stack();
+
+    let status = if call_stack.contains(&target) {
+        CALL_REENTRANT
+    } else if call_stack.depth() >= MAX_CALL_DEPTH {
+        CALL_TOO_DEEP
+    } else {
+        let gas_limit = match (gas_left.is_some(), gas_limit) {
+            (true, 0) => gas_left,
+            (true, requested) => Gas::with(requested.min(gas_left.unwrap())),
+            (false, 0) => Gas::new(),
+            (false, requested) => Gas::with(requested),
+        };
+
+        let protected_mode = match protected_mode {
+            ProtectedMode::FullAccess => ProtectedMode::CalleeAccess,
+            mode => mode,
+        };
+
+        // The `calldata` is borrowed from the `Arena` (taken meanwhile, since the `FuncEnv` can't stay borrowed)
+        let arena = env.borrow_mut().take_arena();
+
+        let nested = NestedCall {
+            target,
+            func_name,
+            calldata: arena.get(calldata),
+            gas_limit,
+            protected_mode,
+            state,
+            callers: call_stack.frames().to_vec(),
+            commit,
+            staged,
+        };
+
+        let result = env.nested_call(nested);
+
+        let mut borrow = env.borrow_mut();
+        borrow.set_arena(arena);
+
+        match result {
+            Some((mut receipt, staged)) if receipt.success => {
+                let returndata = receipt.returndata.take().unwrap_or_default();
+                let status = returndata.len() as i32;
+
+                borrow.charge_nested_gas(receipt.gas_used.unwrap_or(0));
+
+                for log in receipt.take_logs() {
+                    borrow.push_receipt_log(log);
+                }
+                borrow.set_nested_returndata(&returndata);
+                borrow.set_staged(staged);
+
+                status
+            }
+            _ => {
+                borrow.charge_nested_gas(gas_limit.unwrap_or(0));
+                borrow.set_nested_returndata(&[]);
+
+                CALL_FAILED
+            }
+        }
+    };
+
+    env.record_vmcall(
+        VmCall::Call,
+        &[
+            target_ptr as u64,
+            func_ptr as u64,
+            func_len as u64,
+            calldata_ptr as u64,
+            calldata_len as u64,
+            gas_limit,
+        ],
+        Some(status as i64 as u64),
+    );
+
+    status
+}
+
+/// Copies the `returndata` of the last successful [`call`] into memory offset `ptr`.
+///
+/// The memory should have room for the length returned by [`call`].
+pub fn call_returndata(env: &FuncEnv, ptr: u32) {
+    trace!("svm_call_returndata (ptr = {})", ptr);
+
+    {
+        let borrow = env.borrow();
+        let returndata = borrow.nested_returndata();
+
+        let start = ptr as usize;
+        let view = borrow.memory().view::<u8>();
+        let cells = &view[start..start + returndata.len()];
+
+        for (cell, &byte) in cells.iter().zip(returndata.iter()) {
+            cell.set(byte);
+        }
+    }
+
+    env.record_vmcall(VmCall::CallReturndata, &[ptr as u64], None);
+}
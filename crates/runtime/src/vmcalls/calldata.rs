@@ -1,13 +1,20 @@
+use crate::trace::VmCall;
 use crate::FuncEnv;
 
 /// Returns the memory offset of where the input `Calldata` starts.
 pub fn calldata_offset(env: &FuncEnv) -> i32 {
-    calldata(env).0 as i32
+    let offset = calldata(env).0;
+    env.record_vmcall(VmCall::CalldataOffset, &[], Some(offset as u64));
+
+    offset as i32
 }
 
 /// Returns the length of the input `Calldata`
 pub fn calldata_len(env: &FuncEnv) -> i32 {
-    calldata(env).1 as i32
+    let len = calldata(env).1;
+    env.record_vmcall(VmCall::CalldataLen, &[], Some(len as u64));
+
+    len as i32
 }
 
 #[inline]
@@ -0,0 +1,43 @@
+use log::trace;
+
+use crate::trace::VmCall;
+use crate::FuncEnv;
+
+/// Returns the `Layer` the running transaction is executed at.
+pub fn layer(env: &FuncEnv) -> u64 {
+    trace!("svm_layer");
+
+    let layer = env.context().layer().0;
+
+    env.record_vmcall(VmCall::Layer, &[], Some(layer));
+
+    layer
+}
+
+/// Copies the `Address` of the transaction's `Principal` into memory offset `ptr` (20 bytes).
+pub fn principal(env: &FuncEnv, ptr: u32) {
+    trace!("svm_principal (ptr = {})", ptr);
+
+    write_to_memory(env, ptr, env.envelope().principal().as_slice());
+
+    env.record_vmcall(VmCall::Principal, &[ptr as u64], None);
+}
+
+/// Copies the `TransactionId` of the running transaction into memory offset `ptr` (32 bytes).
+pub fn tx_id(env: &FuncEnv, ptr: u32) {
+    trace!("svm_tx_id (ptr = {})", ptr);
+
+    write_to_memory(env, ptr, env.context().tx_id().as_slice());
+
+    env.record_vmcall(VmCall::TxId, &[ptr as u64], None);
+}
+
+fn write_to_memory(env: &FuncEnv, ptr: u32, bytes: &[u8]) {
+    let borrow = env.borrow();
+    let view = borrow.memory().view::<u8>();
+
+    let start = ptr as usize;
+    for (cell, byte) in view[start..start + bytes.len()].iter().zip(bytes) {
+        cell.set(*byte);
+    }
+}
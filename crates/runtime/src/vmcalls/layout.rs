@@ -0,0 +1,46 @@
+use log::trace;
+
+use svm_layout::Id;
+
+use crate::trace::VmCall;
+use crate::FuncEnv;
+
+/// Returns the byte size of variable `var_id` (as laid out by the `Account`'s fixed layout).
+///
+/// # Panics
+///
+/// Panics when variable `var_id` doesn't exist.
+pub fn layout_len(env: &FuncEnv, var_id: u32) -> u32 {
+    let len = {
+        let borrow = env.borrow();
+        let storage = borrow.storage();
+        let (_off, len) = storage.var_layout(Id(var_id));
+
+        len
+    };
+
+    trace!("svm_layout_len (var_id = {}, len = {})", var_id, len);
+
+    env.record_vmcall(VmCall::LayoutLen, &[var_id as u64], Some(len as u64));
+
+    len
+}
+
+/// Returns the number of variables of the `Account`'s fixed layout.
+///
+/// The variables ids are consecutive, so for a layout starting at variable `0`
+/// the variables are `0, 1, ..., count - 1`.
+pub fn layout_count(env: &FuncEnv) -> u32 {
+    let count = {
+        let borrow = env.borrow();
+        let storage = borrow.storage();
+
+        storage.layout().len() as u32
+    };
+
+    trace!("svm_layout_count (count = {})", count);
+
+    env.record_vmcall(VmCall::LayoutCount, &[], Some(count as u64));
+
+    count
+}
@@ -1,24 +1,83 @@
-use svm_types::ReceiptLog;
+use log::trace;
 
+use svm_types::{LogTopic, MAX_LOG_TOPICS};
+
+use crate::trace::VmCall;
 use crate::FuncEnv;
 
 /// Logs the log entry given in a form of blob (offset and length).
 pub fn log(env: &FuncEnv, offset: u32, length: u32) {
-    let start = offset as usize;
-    let end = start + length as usize;
+    trace!("svm_log (offset = {}, length = {})", offset, length);
+
+    env.borrow_mut()
+        .push_log_from_memory(offset as usize, length as usize);
+
+    env.record_vmcall(VmCall::Log, &[offset as u64, length as u64], None);
+}
+
+/// Logs an event of the template-defined `code`, along with its indexed topics
+/// and its data given in a form of blob (offset and length).
+///
+/// The topics reside at memory offset `topics_ptr`, laid out one after the other (32 bytes each).
+/// Traps when there are more than [`MAX_LOG_TOPICS`] topics.
+pub fn log_event(
+    env: &FuncEnv,
+    code: u32,
+    topics_ptr: u32,
+    topics_count: u32,
+    offset: u32,
+    length: u32,
+) {
+    trace!(
+        "svm_log_event (code = {}, topics_ptr = {}, topics_count = {}, offset = {}, length = {})",
+        code,
+        topics_ptr,
+        topics_count,
+        offset,
+        length
+    );
+
+    if topics_count as usize > MAX_LOG_TOPICS {
+        let msg = format!(
+            "An event log can't have more than {} topics (got {})",
+            MAX_LOG_TOPICS, topics_count
+        );
+
+        wasmer::RuntimeError::raise(msg.into());
+    }
+
+    {
+        let mut borrow = env.borrow_mut();
+
+        let topics = {
+            let view = borrow.memory().view::<u8>();
+
+            (0..topics_count as usize)
+                .map(|i| {
+                    let start = topics_ptr as usize + i * 32;
 
-    // We introduce a new scope to avoid runtime ownership error by the `env`'s internal [`std::cell::RefCell`].
-    let bytes = {
-        let borrow = env.borrow();
-        let memory = borrow.memory();
+                    let mut topic: LogTopic = [0; 32];
+                    for (byte, cell) in topic.iter_mut().zip(view[start..start + 32].iter()) {
+                        *byte = cell.get();
+                    }
 
-        memory.view()[start..end]
-            .iter()
-            .map(|cell| cell.get())
-            .collect()
-    };
+                    topic
+                })
+                .collect()
+        };
 
-    let log = ReceiptLog::new(bytes);
+        borrow.push_event_from_memory(code, topics, offset as usize, length as usize);
+    }
 
-    env.borrow_mut().logs_mut().push(log);
+    env.record_vmcall(
+        VmCall::LogEvent,
+        &[
+            code as u64,
+            topics_ptr as u64,
+            topics_count as u64,
+            offset as u64,
+            length as u64,
+        ],
+        None,
+    );
 }
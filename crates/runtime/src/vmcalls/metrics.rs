@@ -0,0 +1,13 @@
+use log::trace;
+
+use crate::trace::VmCall;
+use crate::FuncEnv;
+
+/// Reports the `value` of metric `id` (see [`ReceiptMetric`](svm_types::ReceiptMetric)).
+pub fn metric(env: &FuncEnv, id: u32, value: u64) {
+    trace!("svm_metric (id = {}, value = {})", id, value);
+
+    env.borrow_mut().push_metric(id, value);
+
+    env.record_vmcall(VmCall::Metric, &[id as u64, value], None);
+}
@@ -5,16 +5,30 @@ use wasmer::{Exports, Function, Store};
 use crate::FuncEnv;
 
 mod alloc;
+mod call;
 mod calldata;
+mod context;
+mod layout;
 mod logs;
+mod metrics;
 mod returndata;
 mod storage;
+mod transfer;
 
 pub use alloc::static_alloc;
+pub use call::{call, call_returndata, CALL_FAILED, CALL_REENTRANT, CALL_TOO_DEEP};
 pub use calldata::{calldata_len, calldata_offset};
-pub use logs::log;
+pub use context::{layer, principal, tx_id};
+pub use layout::{layout_count, layout_len};
+pub use logs::{log, log_event};
+pub use metrics::metric;
 pub use returndata::set_returndata;
-pub use storage::{get32, get64, load160, set32, set64, store160};
+pub use storage::{
+    get32, get64, get_bit, load160, load_dyn, set32, set64, set_bit, storage_read_many,
+    storage_write_many, store160, store_dyn,
+};
+pub use transfer::transfer;
+pub(crate) use transfer::UnknownAccount;
 
 macro_rules! func {
     ($store:ident, $env:ident, $f:expr) => {{
@@ -40,8 +54,37 @@ pub fn wasmer_register(store: &Store, env: &FuncEnv, ns: &mut Exports) {
     ns.insert("svm_get64", func!(store, env, get64));
     ns.insert("svm_set64", func!(store, env, set64));
 
+    ns.insert("svm_get_bit", func!(store, env, get_bit));
+    ns.insert("svm_set_bit", func!(store, env, set_bit));
+
     ns.insert("svm_load160", func!(store, env, load160));
     ns.insert("svm_store160", func!(store, env, store160));
 
+    ns.insert("svm_load_dyn", func!(store, env, load_dyn));
+    ns.insert("svm_store_dyn", func!(store, env, store_dyn));
+
+    ns.insert(
+        "svm_storage_read_many",
+        func!(store, env, storage_read_many),
+    );
+    ns.insert(
+        "svm_storage_write_many",
+        func!(store, env, storage_write_many),
+    );
+
+    ns.insert("svm_layout_len", func!(store, env, layout_len));
+    ns.insert("svm_layout_count", func!(store, env, layout_count));
+
     ns.insert("svm_log", func!(store, env, log));
+    ns.insert("svm_log_event", func!(store, env, log_event));
+    ns.insert("svm_metric", func!(store, env, metric));
+
+    ns.insert("svm_call", func!(store, env, call));
+    ns.insert("svm_call_returndata", func!(store, env, call_returndata));
+
+    ns.insert("svm_transfer", func!(store, env, transfer));
+
+    ns.insert("svm_layer", func!(store, env, layer));
+    ns.insert("svm_principal", func!(store, env, principal));
+    ns.insert("svm_tx_id", func!(store, env, tx_id));
 }
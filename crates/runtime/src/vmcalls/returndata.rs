@@ -1,10 +1,19 @@
+use log::trace;
+
+use crate::trace::VmCall;
 use crate::FuncEnv;
 
 /// Signals the host that the data that resides at offset `offset` of length `length`
 /// holds the `Returndata` of the executed function.
 pub fn set_returndata(env: &FuncEnv, offset: u32, length: u32) {
-    dbg!("set_returndata (offset = {}, length = {})", offset, length);
+    trace!(
+        "svm_set_returndata (offset = {}, length = {})",
+        offset,
+        length
+    );
 
     env.borrow_mut()
-        .set_returndata(offset as usize, length as usize)
+        .set_returndata(offset as usize, length as usize);
+
+    env.record_vmcall(VmCall::SetReturndata, &[offset as u64, length as u64], None);
 }
@@ -1,13 +1,18 @@
 use byteorder::{ByteOrder, LittleEndian};
+use log::trace;
 
 use svm_layout::Id;
+use svm_storage::account::PageCorrupted;
 
+use crate::trace::VmCall;
 use crate::FuncEnv;
 
 macro_rules! store_n_impl {
-    ($nbytes:expr, $env:ident, $mem_ptr:expr, $var_id:expr) => {{
+    ($nbytes:expr, $vmcall:expr, $env:ident, $mem_ptr:expr, $var_id:expr) => {{
         use svm_layout::Id;
 
+        $env.ensure_writable();
+
         let bytes: Vec<u8> = {
             let borrow = $env.borrow();
             let memory = borrow.memory();
@@ -19,32 +24,58 @@ macro_rules! store_n_impl {
         };
         assert_eq!(bytes.len(), $nbytes);
 
-        let mut borrow = $env.borrow_mut();
-        let storage = borrow.storage_mut();
-        storage.write_var(Id($var_id), bytes);
+        trace!(
+            "svm_store{} (var_id = {}, bytes = {:?})",
+            $nbytes * 8,
+            $var_id,
+            bytes
+        );
+
+        {
+            let mut borrow = $env.borrow_mut();
+            let storage = borrow.storage_mut();
+            storage.write_var(Id($var_id), bytes);
+        }
+
+        $env.record_vmcall($vmcall, &[$mem_ptr as u64, $var_id as u64], None);
     }};
 }
 
 macro_rules! load_n_impl {
-    ($nbytes:expr, $env:ident, $var_id:expr, $mem_ptr:expr) => {{
+    ($nbytes:expr, $vmcall:expr, $env:ident, $var_id:expr, $mem_ptr:expr) => {{
         use svm_layout::Id;
 
-        let borrow = $env.borrow();
-        let storage = borrow.storage();
+        let result = {
+            let borrow = $env.borrow();
+            let storage = borrow.storage();
 
-        let bytes = storage.read_var(Id($var_id));
-        let nbytes = bytes.len();
-        assert_eq!(nbytes, $nbytes);
+            let mut bytes = [0; $nbytes];
+            storage
+                .read_var_into(Id($var_id), &mut bytes)
+                .map(|()| bytes)
+        };
+        let bytes = result.unwrap_or_else(|err| corrupted(err));
 
-        let borrow = $env.borrow();
-        let memory = borrow.memory();
-        let start = $mem_ptr as usize;
-        let end = start + $nbytes;
-        let view = &memory.view::<u8>()[start..end];
+        trace!(
+            "svm_load{} (var_id = {}, bytes = {:?})",
+            $nbytes * 8,
+            $var_id,
+            bytes
+        );
 
-        for (cell, &byte) in view.iter().zip(bytes.iter()) {
-            cell.set(byte);
+        {
+            let borrow = $env.borrow();
+            let memory = borrow.memory();
+            let start = $mem_ptr as usize;
+            let end = start + $nbytes;
+            let view = &memory.view::<u8>()[start..end];
+
+            for (cell, &byte) in view.iter().zip(bytes.iter()) {
+                cell.set(byte);
+            }
         }
+
+        $env.record_vmcall($vmcall, &[$var_id as u64, $mem_ptr as u64], None);
     }};
 }
 
@@ -54,7 +85,7 @@ macro_rules! load_n_impl {
 ///
 /// Panics if variable `var_id`'s length isn't 20 bytes.
 pub fn store160(env: &FuncEnv, mem_ptr: u32, var_id: u32) {
-    store_n_impl!(20, env, mem_ptr, var_id);
+    store_n_impl!(20, VmCall::Store160, env, mem_ptr, var_id);
 }
 
 /// Loads variable `var_id` data into memory cells `[mem_ptr, mem_ptr + 1, ..., mem_ptr + 19]`
@@ -65,7 +96,7 @@ pub fn store160(env: &FuncEnv, mem_ptr: u32, var_id: u32) {
 ///
 /// Panics if variable `var_id`'s length isn't 20 bytes.
 pub fn load160(env: &FuncEnv, var_id: u32, mem_ptr: u32) {
-    load_n_impl!(20, env, var_id, mem_ptr);
+    load_n_impl!(20, VmCall::Load160, env, var_id, mem_ptr);
 }
 
 /// Returns the data stored by variable `var_id` as 32-bit integer.
@@ -74,14 +105,26 @@ pub fn load160(env: &FuncEnv, var_id: u32, mem_ptr: u32) {
 ///
 /// Panics when variable `var_id` doesn't exist or when it consumes more than 32-bit.
 pub fn get32(env: &FuncEnv, var_id: u32) -> u32 {
-    let borrow = env.borrow();
-    let storage = borrow.storage();
-    let bytes = storage.read_var(Id(var_id));
-    let nbytes = bytes.len();
+    let mut buf = [0; 4];
+
+    let result = {
+        let borrow = env.borrow();
+        let storage = borrow.storage();
+        let (_off, nbytes) = storage.var_layout(Id(var_id));
+
+        assert!(nbytes <= 4);
 
-    assert!(nbytes <= 4);
+        let nbytes = nbytes as usize;
+        storage
+            .read_var_into(Id(var_id), &mut buf[..nbytes])
+            .map(|()| nbytes)
+    };
+    let nbytes = result.unwrap_or_else(|err| corrupted(err));
 
-    let num = LittleEndian::read_uint(&bytes, nbytes);
+    let num = LittleEndian::read_uint(&buf, nbytes);
+    trace!("svm_get32 (var_id = {}, value = {})", var_id, num);
+
+    env.record_vmcall(VmCall::Get32, &[var_id as u64], Some(num));
 
     debug_assert!(num <= std::u32::MAX as u64);
 
@@ -95,16 +138,24 @@ pub fn get32(env: &FuncEnv, var_id: u32) -> u32 {
 /// Panics when variable `var_id` doesn't exist or when it consumes more than 32-bit,
 /// or when it has not enough bytes to hold `value`.
 pub fn set32(env: &FuncEnv, var_id: u32, value: u32) {
-    let mut borrow = env.borrow_mut();
-    let storage = borrow.storage_mut();
-    let (_off, nbytes) = storage.var_layout(Id(var_id));
+    env.ensure_writable();
+
+    trace!("svm_set32 (var_id = {}, value = {})", var_id, value);
+
+    {
+        let mut borrow = env.borrow_mut();
+        let storage = borrow.storage_mut();
+        let (_off, nbytes) = storage.var_layout(Id(var_id));
 
-    assert!(nbytes <= 4);
+        assert!(nbytes <= 4);
 
-    let mut buf = vec![0; nbytes as usize];
-    LittleEndian::write_uint(&mut buf, value as u64, nbytes as usize);
+        let mut buf = vec![0; nbytes as usize];
+        LittleEndian::write_uint(&mut buf, value as u64, nbytes as usize);
 
-    storage.write_var(Id(var_id), buf);
+        storage.write_var(Id(var_id), buf);
+    }
+
+    env.record_vmcall(VmCall::Set32, &[var_id as u64, value as u64], None);
 }
 
 /// Returns the data stored by variable `var_id` as 64-bit integer.
@@ -113,14 +164,28 @@ pub fn set32(env: &FuncEnv, var_id: u32, value: u32) {
 ///
 /// Panics when variable `var_id` doesn't exist or when it consumes more than 64-bit.
 pub fn get64(env: &FuncEnv, var_id: u32) -> u64 {
-    let borrow = env.borrow();
-    let storage = borrow.storage();
-    let bytes = storage.read_var(Id(var_id));
-    let nbytes = bytes.len();
+    let mut buf = [0; 8];
+
+    let result = {
+        let borrow = env.borrow();
+        let storage = borrow.storage();
+        let (_off, nbytes) = storage.var_layout(Id(var_id));
+
+        assert!(nbytes <= 8);
 
-    assert!(nbytes <= 8);
+        let nbytes = nbytes as usize;
+        storage
+            .read_var_into(Id(var_id), &mut buf[..nbytes])
+            .map(|()| nbytes)
+    };
+    let nbytes = result.unwrap_or_else(|err| corrupted(err));
 
-    LittleEndian::read_uint(&bytes, nbytes)
+    let num = LittleEndian::read_uint(&buf, nbytes);
+    trace!("svm_get64 (var_id = {}, value = {})", var_id, num);
+
+    env.record_vmcall(VmCall::Get64, &[var_id as u64], Some(num));
+
+    num
 }
 
 /// Sets the data of variable `var_id` to Little-Endian representation of `value`.
@@ -130,14 +195,328 @@ pub fn get64(env: &FuncEnv, var_id: u32) -> u64 {
 /// Panics when variable `var_id` consumes more than 64-bit,
 /// or when it has not enough bytes to hold `value`.
 pub fn set64(env: &FuncEnv, var_id: u32, value: u64) {
-    let mut borrow = env.borrow_mut();
-    let storage = borrow.storage_mut();
-    let (_off, nbytes) = storage.var_layout(Id(var_id));
+    env.ensure_writable();
+
+    trace!("svm_set64 (var_id = {}, value = {})", var_id, value);
+
+    {
+        let mut borrow = env.borrow_mut();
+        let storage = borrow.storage_mut();
+        let (_off, nbytes) = storage.var_layout(Id(var_id));
+
+        assert!(nbytes <= 8);
+
+        let mut buf = vec![0; nbytes as usize];
+        LittleEndian::write_uint(&mut buf, value, nbytes as usize);
+
+        storage.write_var(Id(var_id), buf);
+    }
+
+    env.record_vmcall(VmCall::Set64, &[var_id as u64, value as u64], None);
+}
+
+/// Returns the value of the packed boolean variable `var_id` (`0` or `1`).
+///
+/// # Panics
+///
+/// Panics when variable `var_id` doesn't exist or when it isn't a packed boolean.
+pub fn get_bit(env: &FuncEnv, var_id: u32) -> u32 {
+    let result = {
+        let borrow = env.borrow();
+        let storage = borrow.storage();
+
+        storage.read_bit(Id(var_id))
+    };
+    let value = result.unwrap_or_else(|err| corrupted(err));
+
+    trace!("svm_get_bit (var_id = {}, value = {})", var_id, value);
+
+    env.record_vmcall(VmCall::GetBit, &[var_id as u64], Some(value as u64));
+
+    value as u32
+}
+
+/// Sets the packed boolean variable `var_id` (any non-zero `value` stands for `true`).
+///
+/// Only the variable's bit is changed, the other packed booleans sharing its byte are left untouched.
+///
+/// # Panics
+///
+/// Panics when variable `var_id` doesn't exist or when it isn't a packed boolean.
+pub fn set_bit(env: &FuncEnv, var_id: u32, value: u32) {
+    env.ensure_writable();
+
+    trace!("svm_set_bit (var_id = {}, value = {})", var_id, value);
+
+    {
+        let mut borrow = env.borrow_mut();
+        let storage = borrow.storage_mut();
+
+        storage.write_bit(Id(var_id), value != 0);
+    }
+
+    env.record_vmcall(VmCall::SetBit, &[var_id as u64, value as u64], None);
+}
+
+/// Reads `count` variables at once.
+///
+/// The variables ids are given as `count` consecutive 32-bit Little-Endian integers,
+/// starting at memory cell `ids_ptr`. The variables data is written back-to-back
+/// (each variable occupying its byte-size) starting at memory cell `out_ptr`.
+///
+/// Returns the number of bytes written.
+///
+/// # Panics
+///
+/// Panics when one of the variables doesn't exist, or when touching memory out of bounds.
+pub fn storage_read_many(env: &FuncEnv, ids_ptr: u32, count: u32, out_ptr: u32) -> u32 {
+    let ids = read_var_ids(env, ids_ptr, count);
+
+    let result = {
+        let borrow = env.borrow();
+        let storage = borrow.storage();
+
+        ids.iter().try_fold(Vec::new(), |mut bytes, &var_id| {
+            bytes.extend_from_slice(&storage.read_var(Id(var_id))?);
 
-    assert!(nbytes <= 8);
+            Ok(bytes)
+        })
+    };
+    let bytes: Vec<u8> = result.unwrap_or_else(|err| corrupted(err));
+
+    trace!(
+        "svm_storage_read_many (var_ids = {:?}, bytes = {:?})",
+        ids,
+        bytes
+    );
+
+    let nbytes = {
+        let borrow = env.borrow();
+        let memory = borrow.memory();
+        let start = out_ptr as usize;
+        let end = start + bytes.len();
+        let view = &memory.view::<u8>()[start..end];
+
+        for (cell, &byte) in view.iter().zip(bytes.iter()) {
+            cell.set(byte);
+        }
+
+        bytes.len() as u32
+    };
+
+    env.record_storage_batch(VmCall::StorageReadMany, ids.len(), nbytes as usize);
+    env.record_vmcall(
+        VmCall::StorageReadMany,
+        &[ids_ptr as u64, count as u64, out_ptr as u64],
+        Some(nbytes as u64),
+    );
+
+    nbytes
+}
+
+/// Writes `count` variables at once.
+///
+/// The variables ids are given as `count` consecutive 32-bit Little-Endian integers,
+/// starting at memory cell `ids_ptr`. The variables new data is read back-to-back
+/// (each variable occupying its byte-size) starting at memory cell `in_ptr`.
+///
+/// # Panics
+///
+/// Panics when one of the variables doesn't exist, or when touching memory out of bounds.
+pub fn storage_write_many(env: &FuncEnv, ids_ptr: u32, count: u32, in_ptr: u32) {
+    env.ensure_writable();
+
+    let ids = read_var_ids(env, ids_ptr, count);
+
+    let nbytes = {
+        let mut borrow = env.borrow_mut();
+
+        let sizes: Vec<usize> = ids
+            .iter()
+            .map(|&var_id| borrow.storage().var_layout(Id(var_id)).1 as usize)
+            .collect();
+
+        let bytes: Vec<u8> = {
+            let memory = borrow.memory();
+            let start = in_ptr as usize;
+            let end = start + sizes.iter().sum::<usize>();
+            let view = &memory.view::<u8>()[start..end];
+
+            view.iter().map(|cell| cell.get()).collect()
+        };
+
+        trace!(
+            "svm_storage_write_many (var_ids = {:?}, bytes = {:?})",
+            ids,
+            bytes
+        );
+
+        let storage = borrow.storage_mut();
+        let mut rest = &bytes[..];
+
+        for (&var_id, &size) in ids.iter().zip(sizes.iter()) {
+            let (value, tail) = rest.split_at(size);
+            storage.write_var(Id(var_id), value.to_vec());
+
+            rest = tail;
+        }
+
+        bytes.len()
+    };
+
+    env.record_storage_batch(VmCall::StorageWriteMany, ids.len(), nbytes);
+    env.record_vmcall(
+        VmCall::StorageWriteMany,
+        &[ids_ptr as u64, count as u64, in_ptr as u64],
+        None,
+    );
+}
+
+/// Stores entry `key` of the dynamic variable `var_id` (see [`svm_layout::DynamicLayout`]).
+///
+/// The `key` resides at memory cells `[key_ptr, key_ptr + key_len)`,
+/// and its new value at memory cells `[value_ptr, value_ptr + value_len)`.
+/// An empty value (i.e `value_len = 0`) removes the entry.
+///
+/// Traps when variable `var_id` isn't a dynamic one.
+pub fn store_dyn(
+    env: &FuncEnv,
+    var_id: u32,
+    key_ptr: u32,
+    key_len: u32,
+    value_ptr: u32,
+    value_len: u32,
+) {
+    env.ensure_writable();
+    ensure_dynamic(env, var_id);
+
+    {
+        let mut borrow = env.borrow_mut();
+
+        let key = read_memory(borrow.memory(), key_ptr, key_len);
+        let value = read_memory(borrow.memory(), value_ptr, value_len);
+
+        trace!(
+            "svm_store_dyn (var_id = {}, key = {:?}, value = {:?})",
+            var_id,
+            key,
+            value
+        );
+
+        borrow.storage_mut().write_dyn(Id(var_id), &key, value);
+    }
+
+    env.record_vmcall(
+        VmCall::StoreDyn,
+        &[
+            var_id as u64,
+            key_ptr as u64,
+            key_len as u64,
+            value_ptr as u64,
+            value_len as u64,
+        ],
+        None,
+    );
+}
+
+/// Loads entry `key` of the dynamic variable `var_id` (see [`svm_layout::DynamicLayout`]).
+///
+/// The `key` resides at memory cells `[key_ptr, key_ptr + key_len)`.
+/// Up to `value_len` bytes of the entry's value are copied into memory starting at cell `value_ptr`.
+///
+/// Returns the byte size of the entry's value (`0` when there is no such entry).
+///
+/// Traps when variable `var_id` isn't a dynamic one.
+pub fn load_dyn(
+    env: &FuncEnv,
+    var_id: u32,
+    key_ptr: u32,
+    key_len: u32,
+    value_ptr: u32,
+    value_len: u32,
+) -> u32 {
+    ensure_dynamic(env, var_id);
+
+    let nbytes = {
+        let borrow = env.borrow();
+
+        let key = read_memory(borrow.memory(), key_ptr, key_len);
+        let value = borrow
+            .storage()
+            .read_dyn(Id(var_id), &key)
+            .unwrap_or_default();
+
+        trace!(
+            "svm_load_dyn (var_id = {}, key = {:?}, value = {:?})",
+            var_id,
+            key,
+            value
+        );
+
+        let start = value_ptr as usize;
+        let end = start + value.len().min(value_len as usize);
+        let view = &borrow.memory().view::<u8>()[start..end];
+
+        for (cell, &byte) in view.iter().zip(value.iter()) {
+            cell.set(byte);
+        }
+
+        value.len() as u32
+    };
+
+    env.record_vmcall(
+        VmCall::LoadDyn,
+        &[
+            var_id as u64,
+            key_ptr as u64,
+            key_len as u64,
+            value_ptr as u64,
+            value_len as u64,
+        ],
+        Some(nbytes as u64),
+    );
+
+    nbytes
+}
+
+/// Fails the running transaction with the [`PageCorrupted`] error of a storage read
+/// (turned into a [`RuntimeError::StorageCorrupted`](svm_types::RuntimeError::StorageCorrupted) by the `Runtime`).
+///
+/// Raising a trap skips the destructors, so `env` must not be borrowed anymore.
+fn corrupted(err: PageCorrupted) -> ! {
+    wasmer::RuntimeError::raise(Box::new(err))
+}
+
+fn ensure_dynamic(env: &FuncEnv, var_id: u32) {
+    let is_dynamic = env.borrow().storage().dynamic_layout().contains(Id(var_id));
+
+    if !is_dynamic {
+        let msg = format!("Variable #{} isn't a dynamic one", var_id);
+
+        wasmer::RuntimeError::raise(msg.into());
+    }
+}
+
+fn read_memory(memory: &wasmer::Memory, ptr: u32, len: u32) -> Vec<u8> {
+    let start = ptr as usize;
+    let end = start + len as usize;
+
+    memory.view::<u8>()[start..end]
+        .iter()
+        .map(|cell| cell.get())
+        .collect()
+}
+
+fn read_var_ids(env: &FuncEnv, ids_ptr: u32, count: u32) -> Vec<u32> {
+    let borrow = env.borrow();
+    let memory = borrow.memory();
 
-    let mut buf = vec![0; nbytes as usize];
-    LittleEndian::write_uint(&mut buf, value, nbytes as usize);
+    let start = ids_ptr as usize;
+    let end = start + 4 * count as usize;
+    let bytes: Vec<u8> = memory.view::<u8>()[start..end]
+        .iter()
+        .map(|cell| cell.get())
+        .collect();
 
-    storage.write_var(Id(var_id), buf);
+    bytes.chunks(4).map(LittleEndian::read_u32).collect()
 }
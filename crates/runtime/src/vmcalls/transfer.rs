@@ -0,0 +1,109 @@
+use log::trace;
+
+use std::error::Error;
+use std::fmt;
+
+use svm_storage::account::TransferError;
+use svm_types::Address;
+
+use crate::trace::VmCall;
+use crate::{FuncEnv, ProtectedMode};
+
+/// Transfers `amount` coins from the running `Account` to the `Account` residing at memory offset `dst_ptr`.
+///
+/// The balances are updated along with the storage variables (i.e only when the transaction succeeds),
+/// and the transfer is recorded as a log entry:
+///
+/// +-----------------+-------------------+------------------------+
+/// | Source Address  |    Destination    |         Amount         |
+/// |  (20 bytes)     | Address (20 bytes)| (8 bytes, Big-Endian)  |
+/// +-----------------+-------------------+------------------------+
+///
+/// Traps when the storage can't be modified (e.g while querying), or when the destination is calling
+/// the running `Account` (see [`ProtectedMode::CalleeAccess`]), and fails the transaction with [`RuntimeError::InsufficientBalance`](svm_types::RuntimeError::InsufficientBalance)
+/// when the running `Account` holds less than `amount` coins.
+///
+/// Fails the transaction with [`RuntimeError::AccountNotFound`](svm_types::RuntimeError::AccountNotFound)
+/// when no `Account` resides at the destination `Address`, and traps (failing the called function)
+/// when the destination balance can't hold `amount` more coins (see [`TransferError::BalanceOverflow`]).
+pub fn transfer(env: &FuncEnv, dst_ptr: u32, amount: u64) {
+    trace!("svm_transfer (dst_ptr = {}, amount = {})", dst_ptr, amount);
+
+    if env.protected_mode() == ProtectedMode::AccessDenied {
+        let msg = "Transferring coins isn't allowed while the storage is inaccessible";
+
+        wasmer::RuntimeError::raise(msg.into());
+    }
+
+    env.ensure_writable();
+
+    let dst = {
+        let borrow = env.borrow();
+
+        let start = dst_ptr as usize;
+        let view = borrow.memory().view::<u8>();
+        let bytes: Vec<u8> = view[start..start + Address::len()]
+            .iter()
+            .map(|cell| cell.get())
+            .collect();
+
+        Address::from(&bytes[..])
+    };
+
+    if env.protected_mode() == ProtectedMode::CalleeAccess
+        && dst != env.target_addr()
+        && env.call_stack().contains(&dst)
+    {
+        let msg = "Transferring coins to a calling `Account` isn't allowed";
+
+        wasmer::RuntimeError::raise(msg.into());
+    }
+
+    // There's no one to tell when the code doesn't run by a `Runtime` (e.g while testing the `vmcalls`)
+    let known = env.account_exists(&dst).unwrap_or(true);
+
+    let result = {
+        let mut borrow = env.borrow_mut();
+
+        let storage = borrow.storage_mut();
+        let src = storage.account_addr().clone();
+
+        let result: Result<(), Box<dyn Error + Send + Sync>> = if known || dst == src {
+            storage.transfer(&dst, amount).map_err(|err| match err {
+                TransferError::InsufficientBalance(err) => err.into(),
+                TransferError::BalanceOverflow(err) => err.into(),
+            })
+        } else {
+            Err(UnknownAccount(dst.clone()).into())
+        };
+
+        if result.is_ok() {
+            let mut log = Vec::with_capacity(Address::len() * 2 + 8);
+            log.extend_from_slice(src.as_slice());
+            log.extend_from_slice(dst.as_slice());
+            log.extend_from_slice(&amount.to_be_bytes());
+
+            borrow.push_log(log);
+        }
+
+        result
+    };
+
+    env.record_vmcall(VmCall::Transfer, &[dst_ptr as u64, amount], None);
+
+    if let Err(err) = result {
+        wasmer::RuntimeError::raise(err);
+    }
+}
+
+/// The error failing a transfer to an `Address` no `Account` resides at (see [`transfer`]).
+#[derive(Debug)]
+pub(crate) struct UnknownAccount(pub Address);
+
+impl fmt::Display for UnknownAccount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "No `Account` resides at {:?}", self.0)
+    }
+}
+
+impl Error for UnknownAccount {}
@@ -1,4 +1,12 @@
-use wasmer::Store;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use wasmer::{Module, ModuleMiddleware, Store};
+
+use svm_hash::{Blake3Hasher, Hasher};
+
+use crate::metering::{Metering, MeteringSlot};
 
 /// New fresh `Store`.
 #[cfg(feature = "default-cranelift")]
@@ -19,3 +27,269 @@ pub fn new_store() -> Store {
     let engine = Universal::new(Singlepass::default()).engine();
     Store::new(&engine)
 }
+
+/// New fresh `Store` compiling modules instrumented by `metering`.
+#[must_use]
+pub fn new_metered_store(metering: Arc<Metering>) -> Store {
+    new_store_with_middleware(metering)
+}
+
+#[cfg(feature = "default-cranelift")]
+fn new_store_with_middleware(middleware: Arc<dyn ModuleMiddleware>) -> Store {
+    use wasmer::{CompilerConfig, Cranelift, Universal};
+
+    let mut compiler = Cranelift::default();
+    compiler.push_middleware(middleware);
+
+    let engine = Universal::new(compiler).engine();
+    Store::new(&engine)
+}
+
+#[cfg(feature = "default-singlepass")]
+fn new_store_with_middleware(middleware: Arc<dyn ModuleMiddleware>) -> Store {
+    use wasmer::{CompilerConfig, Singlepass, Universal};
+
+    let mut compiler = Singlepass::default();
+    compiler.push_middleware(middleware);
+
+    let engine = Universal::new(compiler).engine();
+    Store::new(&engine)
+}
+
+/// Limits of the [`StorePool`] used for executing transactions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorePoolConfig {
+    /// The maximum number of idle `Store`s kept for reuse.
+    pub max_idle: usize,
+
+    /// The number of modules a `Store` compiles before being dropped.
+    ///
+    /// The engine of a `Store` never releases the code of the modules it has compiled,
+    /// so a `Store` is retired (releasing that code once its cached modules are evicted too)
+    /// after it has compiled that many modules.
+    pub max_compilations: usize,
+
+    /// The maximum number of compiled modules kept for reuse. The oldest module is evicted first.
+    pub max_modules: usize,
+}
+
+impl Default for StorePoolConfig {
+    fn default() -> Self {
+        Self {
+            max_idle: 4,
+            max_compilations: 1024,
+            max_modules: 256,
+        }
+    }
+}
+
+/// Identifies a compiled module (see [`StorePool::module`]).
+///
+/// The code is instrumented according to the gas schedule it's compiled under (see [`Metering`]),
+/// hence the `pricing_version` is part of the key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModuleKey {
+    /// The hash of the compiled Wasm code.
+    pub code_hash: [u8; 32],
+
+    /// The version of the gas schedule pricing the code.
+    pub pricing_version: u16,
+}
+
+impl ModuleKey {
+    /// The key of the module compiled out of `code`, priced under `pricing_version`.
+    pub fn new(code: &[u8], pricing_version: u16) -> Self {
+        Self {
+            code_hash: Blake3Hasher::hash(code),
+            pricing_version,
+        }
+    }
+}
+
+/// The statistics of a [`StorePool`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StorePoolStats {
+    /// The number of `Store`s created.
+    pub created: u64,
+
+    /// The number of executions given an idle `Store`.
+    pub reused: u64,
+
+    /// The number of `Store`s dropped (having reached [`StorePoolConfig::max_compilations`],
+    /// or for exceeding [`StorePoolConfig::max_idle`]).
+    pub retired: u64,
+
+    /// The number of currently idle `Store`s.
+    pub idle: usize,
+
+    /// The number of executions given a cached module (compiling nothing).
+    pub cached: u64,
+
+    /// The number of currently cached modules.
+    pub modules: usize,
+}
+
+struct PooledStore {
+    store: Store,
+    slot: Arc<MeteringSlot>,
+    compilations: usize,
+}
+
+/// A pool of long-lived (metered) `Store`s, reused across executions, along with the modules they've compiled.
+///
+/// An execution whose module isn't cached leases a `Store` of its own for compiling it (see [`StorePool::acquire`]),
+/// so nested compilations never share one. Neither a `Store` nor a module holds execution state:
+/// the memories, globals and tables of a module (including its gas limit, see [`Metering`]) live in its `Instance`,
+/// and the host functions are created per execution. Hence, an execution can't observe any state
+/// left behind by former executions which were given the same `Store` or module.
+pub struct StorePool {
+    config: StorePoolConfig,
+    idle: RefCell<Vec<PooledStore>>,
+    modules: RefCell<ModuleCache>,
+    stats: RefCell<StorePoolStats>,
+}
+
+#[derive(Default)]
+struct ModuleCache {
+    modules: HashMap<ModuleKey, Module>,
+    order: VecDeque<ModuleKey>,
+}
+
+impl StorePool {
+    /// Creates a new empty [`StorePool`] limited by `config`.
+    pub fn new(config: StorePoolConfig) -> Self {
+        Self {
+            idle: RefCell::new(Vec::with_capacity(config.max_idle)),
+            modules: RefCell::new(ModuleCache::default()),
+            stats: RefCell::new(StorePoolStats::default()),
+            config,
+        }
+    }
+
+    /// The limits of the pool.
+    pub fn config(&self) -> &StorePoolConfig {
+        &self.config
+    }
+
+    /// Returns the statistics of the pool.
+    pub fn stats(&self) -> StorePoolStats {
+        StorePoolStats {
+            idle: self.idle.borrow().len(),
+            modules: self.modules.borrow().modules.len(),
+            ..*self.stats.borrow()
+        }
+    }
+
+    /// Drops all the idle `Store`s and the cached modules.
+    pub fn clear(&self) {
+        let mut idle = self.idle.borrow_mut();
+
+        self.stats.borrow_mut().retired += idle.len() as u64;
+        idle.clear();
+
+        let mut modules = self.modules.borrow_mut();
+        modules.modules.clear();
+        modules.order.clear();
+    }
+
+    /// Returns the cached module of `key` (see [`StorePool::cache_module`]).
+    ///
+    /// The returned module should be instantiated using its own `Store` (i.e `Module::store`).
+    pub fn module(&self, key: &ModuleKey) -> Option<Module> {
+        let module = self.modules.borrow().modules.get(key).cloned();
+
+        if module.is_some() {
+            self.stats.borrow_mut().cached += 1;
+        }
+
+        module
+    }
+
+    /// Caches `module`, compiled by a leased `Store`, as the module of `key`.
+    pub fn cache_module(&self, key: ModuleKey, module: Module) {
+        if self.config.max_modules == 0 {
+            return;
+        }
+
+        let mut cache = self.modules.borrow_mut();
+
+        if cache.modules.insert(key, module).is_some() {
+            cache.order.retain(|k| k != &key);
+        }
+        cache.order.push_back(key);
+
+        while cache.order.len() > self.config.max_modules {
+            let oldest = cache.order.pop_front().unwrap();
+            cache.modules.remove(&oldest);
+        }
+    }
+
+    /// Leases a `Store` for compiling a single module, instrumented by `metering`.
+    ///
+    /// An idle `Store` is reused when there's any, otherwise a new one is created.
+    /// The `Store` returns to the pool once the returned [`StoreLease`] is dropped,
+    /// which may happen right after compiling (the compiled module keeps its own handle to the `Store`).
+    pub fn acquire(&self, metering: Arc<Metering>) -> StoreLease<'_> {
+        let pooled = self.idle.borrow_mut().pop();
+
+        let mut pooled = match pooled {
+            Some(pooled) => {
+                self.stats.borrow_mut().reused += 1;
+                pooled
+            }
+            None => {
+                self.stats.borrow_mut().created += 1;
+
+                let slot = Arc::new(MeteringSlot::default());
+                let store = new_store_with_middleware(slot.clone());
+
+                PooledStore {
+                    store,
+                    slot,
+                    compilations: 0,
+                }
+            }
+        };
+
+        pooled.slot.set(Some(metering));
+        pooled.compilations += 1;
+
+        StoreLease {
+            pool: self,
+            pooled: Some(pooled),
+        }
+    }
+
+    fn release(&self, pooled: PooledStore) {
+        pooled.slot.set(None);
+
+        let mut idle = self.idle.borrow_mut();
+
+        if pooled.compilations < self.config.max_compilations && idle.len() < self.config.max_idle {
+            idle.push(pooled);
+        } else {
+            self.stats.borrow_mut().retired += 1;
+        }
+    }
+}
+
+/// A `Store` leased from a [`StorePool`] (see [`StorePool::acquire`]).
+pub struct StoreLease<'a> {
+    pool: &'a StorePool,
+    pooled: Option<PooledStore>,
+}
+
+impl<'a> StoreLease<'a> {
+    /// The leased `Store`.
+    pub fn store(&self) -> &Store {
+        &self.pooled.as_ref().unwrap().store
+    }
+}
+
+impl<'a> Drop for StoreLease<'a> {
+    fn drop(&mut self) {
+        if let Some(pooled) = self.pooled.take() {
+            self.pool.release(pooled);
+        }
+    }
+}
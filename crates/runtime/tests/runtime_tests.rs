@@ -3,13 +3,32 @@ use svm_sdk as sdk;
 use svm_sdk::traits::Encoder;
 use svm_sdk::ReturnData;
 
-use svm_codec::{Field, ParseError};
-use svm_layout::FixedLayout;
+use svm_codec::{spawn, Field, ParseError};
+use svm_layout::{FixedLayout, Id};
 use svm_program::ProgramError;
-use svm_runtime::{testing, Runtime, ValidateError};
-
-use svm_types::{Address, Context, Envelope, Gas, RuntimeError};
-use svm_types::{DeployReceipt, SpawnReceipt};
+use svm_runtime::testing::{self, MockContextProvider, MockTick};
+use svm_runtime::{CallAtError, PriceCacheStats, QueryCacheConfig, Runtime, ValidateError};
+
+use svm_types::{
+    Address, Context, Envelope, Gas, Layer, ReceiptLog, RuntimeError, SectionKind, State,
+};
+use svm_types::{CallReceipt, DefaultsSection, DeployReceipt, SpawnReceipt};
+
+/// The price of loading (for executing its code) the `Template` of the binary `Deploy Template` `message`.
+fn template_load_price(message: &[u8]) -> u64 {
+    let interests = maplit::hashset! {
+        SectionKind::Code,
+        SectionKind::Data,
+        SectionKind::Ctors,
+        SectionKind::Defaults,
+        SectionKind::Api
+    };
+
+    let cursor = std::io::Cursor::new(message);
+    let byte_size = svm_codec::template::sections_byte_size(cursor, Some(&interests)).unwrap();
+
+    svm_gas::transaction::template_load(byte_size)
+}
 
 #[test]
 fn memory_runtime_validate_deploy_not_enough_bytes() {
@@ -135,6 +154,22 @@ fn memory_runtime_validate_deploy_ok() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn memory_runtime_validate_deploy_metering_allows_loops() {
+    let runtime = testing::create_memory_runtime();
+    let wasm = include_str!("wasm/runtime_metering.wast");
+
+    // The code loops, so it can't be of `Fixed Gas`
+    let message = testing::build_deploy(0, "My Template", FixedLayout::default(), &[], wasm.into());
+    let actual = runtime.validate_deploy(&message);
+    assert!(matches!(actual, Err(ValidateError::FixedGas(..))));
+
+    let message =
+        testing::build_deploy_metering(0, "My Template", FixedLayout::default(), &[], wasm.into());
+    let actual = runtime.validate_deploy(&message);
+    assert!(actual.is_ok());
+}
+
 #[test]
 fn memory_runtime_validate_spawn_missing_template_addr() {
     let runtime = testing::create_memory_runtime();
@@ -363,3 +398,3490 @@ fn memory_runtime_call_success() {
     let addr: sdk::Address = returndata.next_1();
     assert_eq!(addr.as_slice(), &[0x10; 20]);
 }
+
+#[test]
+fn memory_runtime_execute_layer() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use svm_runtime::replay::MemReceiptStore;
+    use svm_runtime::{LayerHeader, LayerOutcome, Message, MetricsObserver};
+    use svm_types::{ReceiptMetric, TemplateAddr};
+
+    struct LayerObserver(Rc<RefCell<Vec<(Layer, u64)>>>);
+
+    impl MetricsObserver for LayerObserver {
+        fn observe(&mut self, _: &Address, _: &TemplateAddr, _: &[ReceiptMetric]) {}
+
+        fn observe_layer(&mut self, outcome: &LayerOutcome) {
+            self.0.borrow_mut().push((outcome.layer, outcome.gas_used));
+        }
+    }
+
+    let mut runtime = testing::create_memory_runtime();
+    let observed = Rc::new(RefCell::new(Vec::new()));
+
+    runtime.set_metrics_observer(Some(Box::new(LayerObserver(observed.clone()))));
+    runtime.set_receipt_store(Some(Box::new(MemReceiptStore::new())));
+
+    let envelope = Envelope::default();
+
+    // 1) `Layer #1`: a `Deploy Template` (following an invalid message)
+    let deploy = testing::build_deploy(
+        0,
+        "My Template",
+        vec![8].into(),
+        &["ctor".to_string()],
+        include_str!("wasm/runtime_batch.wast").into(),
+    );
+    let header = LayerHeader::new(Layer(1), State::zeros());
+    let outcome = runtime.execute_layer(
+        header,
+        vec![
+            (envelope.clone(), Message::Deploy(vec![0xFF, 0xFF])),
+            (envelope.clone(), Message::Deploy(deploy.clone())),
+        ],
+    );
+
+    assert_eq!(outcome.rejected(), 1);
+    assert!(outcome.results[0].is_err());
+
+    let receipt = outcome.receipts().next().unwrap().clone().into_deploy();
+    assert!(receipt.success);
+    assert_eq!(outcome.gas_used, receipt.gas_used.unwrap());
+    assert_eq!(outcome.state, State::zeros());
+
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Layer #2`: a `Spawn Account`
+    let spawn = testing::build_spawn(&template_addr, "My Account", "ctor", &[]);
+    let header = LayerHeader::new(Layer(2), outcome.state);
+    let outcome = runtime.execute_layer(header, vec![(envelope.clone(), Message::Spawn(spawn))]);
+
+    let receipt = outcome.receipts().next().unwrap().clone().into_spawn();
+    assert!(receipt.success);
+    assert_eq!(&outcome.state, receipt.init_state());
+
+    let spawned_addr = receipt.account_addr().clone();
+
+    // 3) `Layer #3`: a `Call Account` incrementing a counter, followed by one reading it
+    let inc = testing::build_call(&spawned_addr, "inc", &[]);
+    let get = testing::build_call(&spawned_addr, "get", &[]);
+
+    let header = LayerHeader::new(Layer(3), outcome.state);
+    let outcome = runtime.execute_layer(
+        header,
+        vec![
+            (envelope.clone(), Message::Call(inc.clone())),
+            (envelope, Message::Call(get)),
+        ],
+    );
+
+    let receipts: Vec<CallReceipt> = outcome
+        .receipts()
+        .map(|receipt| receipt.clone().into_call())
+        .collect();
+    assert!(receipts.iter().all(|receipt| receipt.success));
+    assert_eq!(receipts[1].returndata(), &1u64.to_le_bytes());
+
+    // The `Layer`'s commitment is the global `State` left by its last transaction
+    assert_eq!(&outcome.state, receipts[1].new_state());
+    assert_eq!(runtime.state_root(Layer(3)), Some(outcome.state.clone()));
+    assert_eq!(
+        outcome.gas_used,
+        receipts.iter().map(|r| r.gas_used.unwrap()).sum::<u64>()
+    );
+
+    // 4) The receipts have been stored (by their `TransactionId`), and the `Layer`s observed
+    let tx_id = Message::Call(inc).tx_id(&Envelope::default());
+    let record = runtime.receipt_store().unwrap().load(&tx_id).unwrap();
+    assert_eq!(record.context.layer(), Layer(3));
+    assert_eq!(record.receipt.clone().into_call(), receipts[0]);
+
+    let tx_id = Message::Deploy(deploy).tx_id(&Envelope::default());
+    assert!(runtime.receipt_store().unwrap().load(&tx_id).is_some());
+
+    let observed = observed.borrow();
+    let layers: Vec<Layer> = observed.iter().map(|(layer, _)| *layer).collect();
+    assert_eq!(layers, vec![Layer(1), Layer(2), Layer(3)]);
+    assert_eq!(observed[2].1, outcome.gas_used);
+}
+
+#[test]
+fn memory_runtime_execute_layer_spawn_with_call() {
+    use svm_runtime::replay::MemReceiptStore;
+    use svm_runtime::{LayerHeader, Message};
+
+    let mut runtime = testing::create_memory_runtime();
+    runtime.set_receipt_store(Some(Box::new(MemReceiptStore::new())));
+
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let layout: FixedLayout = vec![Address::len() as u32].into();
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        layout,
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+    );
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    let template_addr = receipt.addr.unwrap();
+
+    // 1) `Layer #1`: a `Spawn Account` storing an `Address` right after its `ctor`
+    let param: sdk::Address = sdk::Address::repeat(0x10);
+    let mut calldata = svm_sdk::Vec::with_capacity(Address::len() + 1);
+    param.encode(&mut calldata);
+
+    let spawn = testing::build_spawn_with_call(
+        &template_addr,
+        "My Account",
+        "initialize",
+        &[],
+        "store_addr",
+        &calldata,
+    );
+    let header = LayerHeader::new(Layer(1), State::zeros());
+    let outcome = runtime.execute_layer(header, vec![(envelope.clone(), Message::Spawn(spawn))]);
+
+    let receipt = outcome.receipts().next().unwrap().clone().into_spawn();
+    assert!(receipt.success);
+    assert_eq!(&outcome.state, receipt.final_state());
+    assert_ne!(&outcome.state, receipt.init_state());
+
+    let spawned_addr = receipt.account_addr().clone();
+
+    // 2) `Layer #2`: a `Call Account` loading the stored `Address`
+    let load = testing::build_call(&spawned_addr, "load_addr", &[]);
+    let header = LayerHeader::new(Layer(2), outcome.state);
+    let outcome = runtime.execute_layer(header, vec![(envelope.clone(), Message::Call(load))]);
+
+    let receipt = outcome.receipts().next().unwrap().clone().into_call();
+    assert!(receipt.success);
+
+    let bytes = receipt.returndata.unwrap();
+    let mut returndata = ReturnData::new(&bytes);
+
+    let addr: sdk::Address = returndata.next_1();
+    assert_eq!(addr.as_slice(), &[0x10; 20]);
+
+    // 3) `Layer #3`: identical messages sent within different `Envelope`s are stored apart
+    let get = testing::build_call(&spawned_addr, "load_addr", &[]);
+    let other = Envelope::new(Address::repeat(0x20), 0, Gas::new(), 0);
+    let header = LayerHeader::new(Layer(3), outcome.state);
+    let _outcome = runtime.execute_layer(
+        header,
+        vec![
+            (envelope.clone(), Message::Call(get.clone())),
+            (other.clone(), Message::Call(get.clone())),
+        ],
+    );
+
+    let store = runtime.receipt_store().unwrap();
+    let tx_id = Message::Call(get.clone()).tx_id(&envelope);
+    let other_tx_id = Message::Call(get).tx_id(&other);
+    assert_ne!(tx_id, other_tx_id);
+    assert_eq!(store.load(&tx_id).unwrap().envelope, envelope);
+    assert_eq!(store.load(&other_tx_id).unwrap().envelope, other);
+}
+
+#[test]
+fn memory_runtime_estimate_gas() {
+    use svm_codec::intrinsic_gas::CALL_EXEC_GAS;
+    use svm_runtime::Message;
+
+    let mut runtime = testing::create_memory_runtime();
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    // 1) `Deploy Template`: there's no code to run
+    let deploy = testing::build_deploy(
+        0,
+        "My Template",
+        vec![8].into(),
+        &["ctor".to_string()],
+        include_str!("wasm/runtime_batch.wast").into(),
+    );
+    let estimate = runtime
+        .estimate_gas(&envelope, &Message::Deploy(deploy.clone()))
+        .unwrap();
+    assert_eq!(
+        estimate.payload_price,
+        svm_gas::transaction::deploy(&deploy)
+    );
+    assert_eq!(estimate.func_price, Some(0));
+    assert_eq!(estimate.gas_limit, estimate.payload_price);
+
+    let receipt = runtime.deploy(&envelope, &deploy, &context);
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account`: the `ctor` is priced upfront
+    let spawn = testing::build_spawn(&template_addr, "My Account", "ctor", &[]);
+    let estimate = runtime
+        .estimate_gas(&envelope, &Message::Spawn(spawn.clone()))
+        .unwrap();
+    assert_eq!(estimate.payload_price, svm_gas::transaction::spawn(&spawn));
+    assert_eq!(estimate.template_load_price, template_load_price(&deploy));
+    assert!(estimate.func_price.is_some());
+    assert_eq!(
+        estimate.gas_limit,
+        estimate.payload_price + estimate.template_load_price + estimate.func_price.unwrap()
+    );
+
+    // Nothing has been executed, and spawning given the recommended `gas_limit` succeeds
+    assert_eq!(runtime.iter_accounts().count(), 0);
+
+    let spawn_envelope = Envelope::new(Address::zeros(), 0, Gas::with(estimate.gas_limit), 0);
+    let receipt = runtime.spawn(&spawn_envelope, &spawn, &context);
+    assert!(receipt.success);
+    assert!(receipt.gas_used.unwrap() <= estimate.gas_limit);
+
+    let spawned_addr = receipt.account_addr().clone();
+
+    // 3) `Call Account`: loading the `Template` is priced too
+    let call = testing::build_call(&spawned_addr, "inc", &[]);
+    let estimate = runtime
+        .estimate_gas(&envelope, &Message::Call(call.clone()))
+        .unwrap();
+    assert_eq!(estimate.payload_price, svm_gas::transaction::call(&call));
+    assert_eq!(estimate.template_load_price, template_load_price(&deploy));
+
+    let call_envelope = Envelope::new(Address::zeros(), 0, Gas::with(estimate.gas_limit), 0);
+    let receipt = runtime.call(&call_envelope, &call, &context);
+    assert!(receipt.success);
+    assert!(receipt.gas_used.unwrap() <= estimate.gas_limit);
+
+    // 4) A `Call Account` of a missing `Account` can't be priced (so the allowance of a `call` is used)
+    let call = testing::build_call(&Address::repeat(0xFF), "inc", &[]);
+    let estimate = runtime
+        .estimate_gas(&envelope, &Message::Call(call.clone()))
+        .unwrap();
+    assert_eq!(estimate.func_price, None);
+    assert_eq!(
+        estimate.gas_limit,
+        svm_gas::transaction::call(&call) + CALL_EXEC_GAS
+    );
+
+    // 5) An invalid message is rejected
+    assert!(runtime
+        .estimate_gas(&envelope, &Message::Spawn(vec![0xFF]))
+        .is_err());
+}
+
+#[test]
+fn memory_runtime_detailed_gas_accounting() {
+    use svm_gas::{resolvers::V0PriceResolver, PriceResolver};
+    use svm_types::GasBreakdown;
+
+    let mut runtime = testing::create_memory_runtime();
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    // 1) Without detailed gas accounting the receipts carry no breakdown
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        vec![Address::len() as u32].into(),
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+    );
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert!(receipt.success);
+    assert_eq!(receipt.gas_breakdown, None);
+
+    runtime.set_detailed_gas_accounting(true);
+
+    // 2) `Deploy Template`
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert_eq!(receipt.version, 1);
+    assert_eq!(
+        receipt.gas_breakdown,
+        Some(GasBreakdown {
+            intrinsic: svm_gas::transaction::deploy(&message),
+            ..GasBreakdown::default()
+        })
+    );
+
+    let template_addr = receipt.addr.unwrap();
+    let load_price = template_load_price(&message);
+
+    // 3) `Spawn Account` (the `intrinsic` cost covers loading the `Template` as well)
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let breakdown = receipt.gas_breakdown.unwrap();
+    assert_eq!(
+        breakdown.intrinsic,
+        svm_gas::transaction::spawn(&message) + load_price
+    );
+
+    let spawned_addr = receipt.account_addr().clone();
+    let init_state = receipt.init_state().clone();
+
+    // 4) `Call Account` (storing an `Address`)
+    let param: sdk::Address = sdk::Address::repeat(0x10);
+    let mut calldata = svm_sdk::Vec::with_capacity(Address::len() + 1);
+    param.encode(&mut calldata);
+
+    let message = testing::build_call(&spawned_addr, "store_addr", &calldata);
+    let context = Context::with_state(init_state);
+
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let breakdown = receipt.gas_breakdown.unwrap();
+    let store_price = V0PriceResolver::default().import_price(("svm", "svm_store160")) as u64;
+
+    assert_eq!(
+        breakdown.intrinsic,
+        svm_gas::transaction::call(&message) + load_price
+    );
+    assert_eq!(breakdown.storage, store_price);
+    assert_eq!(breakdown.logs, 0);
+    assert_eq!(
+        load_price + breakdown.execution + breakdown.storage,
+        receipt.gas_used.unwrap()
+    );
+
+    // 5) The breakdown survives the binary encoding
+    let bytes = svm_codec::receipt::encode_call(&receipt);
+    let decoded = svm_codec::receipt::decode_receipt(&bytes).into_call();
+    assert_eq!(decoded.gas_breakdown, receipt.gas_breakdown);
+}
+
+#[test]
+fn memory_runtime_gas_schedule_version() {
+    let mut runtime = testing::create_memory_runtime();
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    // 1) By default the receipts are priced by gas schedule `0`
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        vec![Address::len() as u32].into(),
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+    );
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert_eq!(receipt.gas_schedule_version, 0);
+    assert_eq!(receipt.version, 0);
+
+    runtime.set_gas_schedule_version(7);
+
+    // 2) `Deploy Template`
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert_eq!(receipt.gas_schedule_version, 7);
+
+    let template_addr = receipt.addr.unwrap();
+
+    // 3) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+    assert_eq!(receipt.gas_schedule_version, 7);
+
+    let spawned_addr = receipt.account_addr().clone();
+    let init_state = receipt.init_state().clone();
+
+    // 4) `Call Account` (a failing one as well)
+    let param: sdk::Address = sdk::Address::repeat(0x10);
+    let mut calldata = svm_sdk::Vec::with_capacity(Address::len() + 1);
+    param.encode(&mut calldata);
+
+    let message = testing::build_call(&spawned_addr, "store_addr", &calldata);
+    let context = Context::with_state(init_state);
+
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+    assert_eq!(receipt.gas_schedule_version, 7);
+
+    let message = testing::build_call(&spawned_addr, "no_such_func", &[]);
+    let failed = runtime.call(&envelope, &message, &context);
+    assert!(!failed.success);
+    assert_eq!(failed.gas_schedule_version, 7);
+
+    // 5) The version survives the binary encoding
+    for receipt in [receipt, failed] {
+        let bytes = svm_codec::receipt::encode_call(&receipt);
+        let decoded = svm_codec::receipt::decode_receipt(&bytes);
+        assert_eq!(decoded.gas_schedule_version(), 7);
+    }
+}
+
+#[test]
+fn memory_runtime_validate_deploy_invalid_default() {
+    let runtime = testing::create_memory_runtime();
+
+    let mut defaults = DefaultsSection::default();
+    defaults.push(Id(0), vec![0x10; 4]);
+
+    let message = testing::build_deploy_with_defaults(
+        0,
+        "My Template",
+        vec![Address::len() as u32].into(),
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+        defaults,
+    );
+
+    let error = runtime.validate_deploy(&message).unwrap_err();
+    assert_eq!(error, ValidateError::InvalidDefault(0));
+}
+
+#[test]
+fn memory_runtime_validate_deploy_storage_budget() {
+    use svm_codec::api::builder::TemplateBuilder;
+    use svm_codec::template;
+    use svm_layout::Layout;
+    use svm_types::{CodeSection, CtorsSection, DataSection};
+
+    let runtime = testing::create_memory_runtime();
+
+    let build_deploy = |budget: u32| {
+        let wasm = &include_bytes!("wasm/runtime_calldata.wasm")[..];
+        let layout: FixedLayout = vec![20, 4].into();
+        let data =
+            DataSection::with_layout(Layout::Fixed(layout)).with_max_storage_bytes(Some(budget));
+
+        let template = TemplateBuilder::default()
+            .with_code(CodeSection::new_fixed(wasm.to_vec(), 0))
+            .with_data(data)
+            .with_ctors(CtorsSection::new(vec!["initialize".to_string()]))
+            .build();
+
+        template::encode(&template)
+    };
+
+    let error = runtime.validate_deploy(&build_deploy(23)).unwrap_err();
+    assert_eq!(
+        error,
+        ValidateError::StorageBudget {
+            size: 24,
+            budget: 23
+        }
+    );
+
+    assert!(runtime.validate_deploy(&build_deploy(24)).is_ok());
+}
+
+#[test]
+fn memory_runtime_spawn_applies_defaults() {
+    let mut runtime = testing::create_memory_runtime();
+
+    // 1) `Deploy Template` (with a default value for its single variable)
+    let mut defaults = DefaultsSection::default();
+    defaults.push(Id(0), vec![0x10; Address::len()]);
+
+    let message = testing::build_deploy_with_defaults(
+        0,
+        "My Template",
+        vec![Address::len() as u32].into(),
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+        defaults,
+    );
+    assert!(runtime.validate_deploy(&message).is_ok());
+
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr();
+    let init_state = receipt.init_state();
+
+    // 3) `Call Account` (the `ctor` hasn't touched the variable)
+    let message = testing::build_call(&spawned_addr, "load_addr", &[]);
+    let context = Context::with_state(init_state.clone());
+
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let bytes = receipt.returndata.unwrap();
+    let mut returndata = ReturnData::new(&bytes);
+
+    let addr: sdk::Address = returndata.next_1();
+    assert_eq!(addr.as_slice(), &[0x10; 20]);
+}
+
+#[test]
+fn memory_runtime_deploy_preserves_custom_sections() {
+    use svm_codec::api::builder::TemplateBuilder;
+    use svm_codec::template;
+    use svm_layout::Layout;
+    use svm_types::{CodeSection, CtorsSection, CustomSection, DataSection};
+
+    let mut runtime = testing::create_memory_runtime();
+
+    // 1) `Deploy Template` (having a `Custom Section`)
+    let wasm = include_bytes!("wasm/runtime_calldata.wasm").to_vec();
+    let layout: FixedLayout = vec![Address::len() as u32].into();
+    let custom = CustomSection::new(0x80_01, vec![0xC0, 0xFF, 0xEE]);
+
+    let template = TemplateBuilder::default()
+        .with_code(CodeSection::new_fixed(wasm, 0))
+        .with_data(DataSection::with_layout(Layout::Fixed(layout)))
+        .with_ctors(CtorsSection::new(vec!["initialize".to_string()]))
+        .with_custom(custom.clone())
+        .build();
+
+    let message = template::encode(&template);
+    assert!(runtime.validate_deploy(&message).is_ok());
+
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) The `Custom Section` is stored as is
+    let section = runtime
+        .template_section(&template_addr, SectionKind::Custom(0x80_01))
+        .unwrap();
+    assert_eq!(section.as_custom(), &custom);
+
+    // 3) ...and ignored when spawning an `Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+}
+
+#[test]
+fn memory_runtime_iter_accounts() {
+    let mut runtime = testing::create_memory_runtime();
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    assert_eq!(runtime.iter_accounts().count(), 0);
+
+    let mut spawned = Vec::new();
+
+    for name in &["Alice", "Bob", "Carol"] {
+        // 1) `Deploy Template` (each with a distinct code, appending a Wasm custom section)
+        let mut wasm = include_bytes!("wasm/runtime_calldata.wasm").to_vec();
+        wasm.extend_from_slice(&[0x00, 0x02, 0x01, name.as_bytes()[0]]);
+
+        let message = testing::build_deploy(
+            0,
+            "My Template",
+            vec![Address::len() as u32].into(),
+            &["initialize".to_string()],
+            (&wasm[..]).into(),
+        );
+
+        let receipt = runtime.deploy(&envelope, &message, &context);
+        let template_addr = receipt.addr.unwrap();
+
+        // 2) `Spawn Account`
+        let message = testing::build_spawn(&template_addr, name, "initialize", &[]);
+        let receipt = runtime.spawn(&envelope, &message, &context);
+        assert!(receipt.success);
+
+        spawned.push((receipt.account_addr().clone(), template_addr));
+    }
+
+    // 3) The `Account`s are ordered by their `Address`
+    let accounts: Vec<_> = runtime.iter_accounts().collect();
+    let addrs: Vec<_> = accounts
+        .iter()
+        .map(|info| (info.addr.clone(), info.template_addr.clone()))
+        .collect();
+
+    spawned.sort_by(|(a, _), (b, _)| a.as_slice().cmp(b.as_slice()));
+    assert_eq!(addrs, spawned);
+
+    let mut names: Vec<_> = accounts.iter().map(|info| info.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, vec!["Alice", "Bob", "Carol"]);
+
+    for info in accounts.iter() {
+        assert_eq!(info.balance, 0);
+        assert_eq!(
+            Some(&info.state_root),
+            runtime.current_state_root(&info.addr).as_ref()
+        );
+    }
+
+    // 4) Paging through the `Account`s
+    let page = runtime.accounts_page(None, 2);
+    assert_eq!(page, accounts[..2].to_vec());
+
+    let page = runtime.accounts_page(Some(&page[1].addr), 2);
+    assert_eq!(page, accounts[2..].to_vec());
+
+    let page = runtime.accounts_page(Some(&page[0].addr), 2);
+    assert!(page.is_empty());
+}
+
+#[test]
+fn memory_runtime_custom_addr_scheme() {
+    use std::rc::Rc;
+
+    use svm_runtime::{AddressScheme, ExtSpawn};
+    use svm_types::{Template, TemplateAddr};
+
+    struct FixedScheme;
+
+    impl AddressScheme for FixedScheme {
+        fn template_addr(&self, _template: &Template) -> TemplateAddr {
+            TemplateAddr::repeat(0x77)
+        }
+
+        fn account_addr(&self, _spawn: &ExtSpawn) -> Address {
+            Address::repeat(0x88)
+        }
+    }
+
+    let mut runtime = testing::create_memory_runtime();
+    runtime.register_addr_scheme(1, Rc::new(FixedScheme));
+    runtime.set_addr_scheme(1);
+
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    // 1) `Deploy Template`
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        vec![Address::len() as u32].into(),
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+    );
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+    assert_eq!(template_addr, TemplateAddr::repeat(0x77));
+
+    // 2) The scheme is recorded in the `Deploy Section`
+    let section = runtime
+        .template_section(&template_addr, SectionKind::Deploy)
+        .unwrap();
+    assert_eq!(section.as_deploy().addr_scheme(), 1);
+
+    // 3) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+    assert_eq!(receipt.account_addr(), &Address::repeat(0x88));
+
+    // 4) ...and it's recorded along the `Account`
+    let accounts: Vec<_> = runtime.iter_accounts().collect();
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].addr_scheme, 1);
+}
+
+#[test]
+fn memory_runtime_query_cached_by_state() {
+    use svm_types::TransactionId;
+
+    let mut runtime = testing::create_memory_runtime();
+    runtime.set_query_cache(Some(QueryCacheConfig {
+        max_entries: 16,
+        ttl: None,
+    }));
+
+    // 1) `Deploy Template`
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        vec![Address::len() as u32].into(),
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+    );
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr().clone();
+    let mut state = receipt.init_state().clone();
+
+    let query = testing::build_call(&spawned_addr, "load_addr", &[]);
+
+    for byte in [0x10, 0x20] {
+        // 3) `Call Account` (storing a new `Address`)
+        let param = sdk::Address::repeat(byte);
+        let mut calldata = svm_sdk::Vec::with_capacity(Address::len() + 1);
+        param.encode(&mut calldata);
+
+        let message = testing::build_call(&spawned_addr, "store_addr", &calldata);
+        let receipt = runtime.call(&envelope, &message, &Context::with_state(state));
+        assert!(receipt.success);
+
+        state = receipt.new_state().clone();
+
+        // 4) Querying twice (the second query is answered from the cache)
+        let context = Context::with_state(state.clone());
+
+        let first = runtime.query_receipt(&envelope, &query, &context);
+        let second = runtime.query_receipt(&envelope, &query, &context);
+        assert!(first.success);
+        assert_eq!(first, second);
+        assert_eq!(first.new_state(), &state);
+
+        let bytes = second.returndata.unwrap();
+        let mut returndata = ReturnData::new(&bytes);
+
+        let addr: sdk::Address = returndata.next_1();
+        assert_eq!(addr.as_slice(), &[byte; 20]);
+    }
+
+    // 5) `Runtime::query` goes through the same cache
+    let context = Context::with_state(state.clone());
+    let result = Runtime::query(&runtime, &envelope, &query, &context);
+    assert!(result.success);
+
+    // 6) A query observing another principal (or `Layer`) isn't answered from the cache
+    let other = Envelope::with_principal(Address::repeat(0xAB));
+    assert!(runtime.query_receipt(&other, &query, &context).success);
+
+    let later = Context::new(TransactionId::zeros(), Layer(1), state);
+    assert!(runtime.query_receipt(&envelope, &query, &later).success);
+
+    let cache = runtime.query_cache().unwrap();
+    assert_eq!(cache.len(), 4);
+    assert_eq!(cache.hits(), 3);
+    assert_eq!(cache.misses(), 4);
+}
+
+#[test]
+fn memory_runtime_state_roots() {
+    use svm_types::TransactionId;
+
+    let mut runtime = testing::create_memory_runtime();
+
+    // 1) `Deploy Template`
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        vec![Address::len() as u32].into(),
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+    );
+    let envelope = Envelope::default();
+    let context = Context::new(TransactionId::repeat(1), Layer(1), State::zeros());
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    let template_addr = receipt.addr.unwrap();
+
+    assert_eq!(runtime.state_root(Layer(1)), None);
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let context = Context::new(TransactionId::repeat(2), Layer(2), State::zeros());
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr().clone();
+    let init_state = receipt.init_state().clone();
+
+    let spawned_root = runtime.current_state_root(&spawned_addr).unwrap();
+    assert_eq!(
+        runtime.current_state_root(&spawned_addr),
+        Some(spawned_root.clone())
+    );
+    assert_eq!(runtime.current_state_root(&Address::repeat(0xFF)), None);
+
+    // 3) `Call Account` (storing a new `Address`)
+    let param = sdk::Address::repeat(0x10);
+    let mut calldata = svm_sdk::Vec::with_capacity(Address::len() + 1);
+    param.encode(&mut calldata);
+
+    let message = testing::build_call(&spawned_addr, "store_addr", &calldata);
+    let context = Context::new(TransactionId::repeat(3), Layer(4), init_state.clone());
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let new_state = receipt.new_state().clone();
+
+    // The global `State` of a `Layer` is kept by the following empty `Layer`s
+    assert_eq!(runtime.state_root(Layer(1)), None);
+    assert_eq!(runtime.state_root(Layer(2)), Some(init_state.clone()));
+    assert_eq!(runtime.state_root(Layer(3)), Some(init_state));
+    assert_eq!(runtime.state_root(Layer(4)), Some(new_state.clone()));
+    assert_eq!(runtime.state_root(Layer(10)), Some(new_state));
+
+    // The `Account` root follows its storage
+    let called_root = runtime.current_state_root(&spawned_addr).unwrap();
+    assert_ne!(called_root, spawned_root);
+
+    // 4) Querying changes no root
+    let query = testing::build_call(&spawned_addr, "load_addr", &[]);
+    let context = Context::new(TransactionId::repeat(4), Layer(5), context.state().clone());
+    let receipt = runtime.query_receipt(&envelope, &query, &context);
+    assert!(receipt.success);
+
+    assert_eq!(runtime.current_state_root(&spawned_addr), Some(called_root));
+    assert_eq!(runtime.state_root(Layer(5)), runtime.state_root(Layer(4)));
+}
+
+#[test]
+fn state_roots_track_the_most_recent_layers() {
+    use svm_runtime::StateRoots;
+
+    let mut roots = StateRoots::with_limit(2);
+
+    roots.record(Layer(1), State::repeat(1));
+    roots.record(Layer(2), State::repeat(2));
+    roots.record(Layer(3), State::repeat(1));
+
+    assert_eq!(roots.head(), Some(&State::repeat(1)));
+    assert_eq!(roots.layer_of(&State::repeat(1)), Some(Layer(3)));
+    assert_eq!(roots.layer_of(&State::repeat(2)), Some(Layer(2)));
+
+    // `Layer #2` is forgotten (as are the `Layer`s preceding the tracked ones)
+    roots.record(Layer(4), State::repeat(4));
+
+    assert_eq!(roots.layer_root(Layer(1)), None);
+    assert_eq!(roots.layer_root(Layer(2)), None);
+    assert_eq!(roots.layer_root(Layer(3)), Some(&State::repeat(1)));
+    assert_eq!(roots.layer_root(Layer(5)), Some(&State::repeat(4)));
+    assert_eq!(roots.layer_of(&State::repeat(2)), None);
+
+    // Re-recording a `Layer` replaces its `State`
+    roots.record(Layer(4), State::repeat(5));
+
+    assert_eq!(roots.layer_of(&State::repeat(4)), None);
+    assert_eq!(roots.layer_of(&State::repeat(5)), Some(Layer(4)));
+
+    // The cached `Account` roots are dropped once the head moves on
+    let addr = Address::repeat(0xAA);
+    roots.insert_account_root(addr.clone(), State::repeat(5), State::repeat(0xBB));
+    assert_eq!(
+        roots.account_root(&addr, &State::repeat(5)),
+        Some(&State::repeat(0xBB))
+    );
+
+    roots.record(Layer(5), State::repeat(6));
+    assert_eq!(roots.account_root(&addr, &State::repeat(5)), None);
+}
+
+#[test]
+fn memory_runtime_call_at_historical_state() {
+    use svm_types::TransactionId;
+
+    let mut runtime = testing::create_memory_runtime();
+
+    // 1) `Deploy Template`
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        vec![Address::len() as u32].into(),
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+    );
+    let envelope = Envelope::default();
+    let context = Context::new(TransactionId::repeat(1), Layer(1), State::zeros());
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let context = Context::new(TransactionId::repeat(2), Layer(2), State::zeros());
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr().clone();
+    let init_state = receipt.init_state().clone();
+
+    // 3) `Call Account` (storing a new `Address`)
+    let param = sdk::Address::repeat(0x10);
+    let mut calldata = svm_sdk::Vec::with_capacity(Address::len() + 1);
+    param.encode(&mut calldata);
+
+    let message = testing::build_call(&spawned_addr, "store_addr", &calldata);
+    let context = Context::new(TransactionId::repeat(3), Layer(3), init_state.clone());
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let new_state = receipt.new_state().clone();
+    let account_root = runtime.current_state_root(&spawned_addr);
+
+    // 4) Re-executing the `Call Account` at the `State` of `Layer #2` commits nothing
+    let state_root = runtime.state_root(Layer(2)).unwrap();
+    assert_eq!(state_root, init_state);
+
+    let receipt = runtime.call_at(&envelope, &message, &state_root).unwrap();
+    assert!(receipt.success);
+    assert_eq!(receipt.new_state(), &init_state);
+
+    assert_eq!(runtime.state_root(Layer(3)), Some(new_state.clone()));
+    assert_eq!(runtime.state_root(Layer(10)), Some(new_state.clone()));
+    assert_eq!(runtime.current_state_root(&spawned_addr), account_root);
+
+    // 5) The storage is read as of the historical `State` (the stored `Address` has been overwritten since)
+    let param = sdk::Address::repeat(0x20);
+    let mut calldata = svm_sdk::Vec::with_capacity(Address::len() + 1);
+    param.encode(&mut calldata);
+
+    let message = testing::build_call(&spawned_addr, "store_addr", &calldata);
+    let context = Context::new(TransactionId::repeat(4), Layer(4), new_state.clone());
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let latest_state = receipt.new_state().clone();
+    let load = testing::build_call(&spawned_addr, "load_addr", &[]);
+
+    let load_addr = |receipt: CallReceipt| {
+        assert!(receipt.success);
+
+        let bytes = receipt.returndata.unwrap();
+        let mut returndata = ReturnData::new(&bytes);
+        let addr: sdk::Address = returndata.next_1();
+
+        addr.as_slice().to_vec()
+    };
+
+    let receipt = runtime.call_at(&envelope, &load, &new_state).unwrap();
+    assert_eq!(load_addr(receipt), [0x10; 20]);
+
+    // While the current `State` is left intact
+    let context = Context::new(TransactionId::repeat(5), Layer(5), latest_state.clone());
+    let receipt = runtime.call(&envelope, &load, &context);
+    assert_eq!(load_addr(receipt), [0x20; 20]);
+    assert_eq!(runtime.state_root(Layer(4)), Some(latest_state));
+
+    // 6) An unknown `State` can't be executed against
+    let err = runtime
+        .call_at(&envelope, &load, &State::repeat(0xFF))
+        .unwrap_err();
+    assert_eq!(err, CallAtError::UnknownState(State::repeat(0xFF)));
+
+    // 7) Nor can an unparsable message
+    let err = runtime.call_at(&envelope, &[0xFF], &new_state).unwrap_err();
+    assert!(matches!(err, CallAtError::Parse(..)));
+}
+
+#[test]
+fn memory_runtime_mock_context_provider() {
+    let mut runtime = testing::create_memory_runtime();
+
+    let genesis = MockTick {
+        layer: Layer(100),
+        timestamp: 1_000_000,
+    };
+    let mut provider = MockContextProvider::new(genesis, 30);
+    provider.script_layers(vec![100, 101, 105]);
+
+    let envelope = Envelope::default();
+
+    // 1) `Deploy Template` (at layer #100)
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        vec![Address::len() as u32].into(),
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+    );
+    let receipt = runtime.deploy(&envelope, &message, &provider.next());
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account` (at layer #101)
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &provider.next());
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr().clone();
+    provider.set_state(receipt.init_state().clone());
+
+    // 3) `Call Account` (at layer #105)
+    let message = testing::build_call(&spawned_addr, "load_addr", &[]);
+    let context = provider.next();
+    assert_eq!(context.layer(), Layer(105));
+
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    // Once the script is exhausted, the time stands still
+    assert_eq!(provider.next().layer(), Layer(105));
+
+    let ticks = provider
+        .history()
+        .iter()
+        .map(|(_, tick)| (tick.layer.0, tick.timestamp))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ticks,
+        vec![
+            (100, 1_000_000),
+            (101, 1_000_030),
+            (105, 1_000_150),
+            (105, 1_000_150)
+        ]
+    );
+    assert_eq!(provider.tick_of_tx(context.tx_id()), Some(provider.now()));
+
+    // 4) A call gated by the `Layer` is executed only from layer #110 onwards
+    let unlock = Layer(110);
+
+    testing::assert_unlocks_at(&mut provider, unlock, |context| {
+        context.layer() >= unlock && runtime.call(&envelope, &message, context).success
+    });
+    assert_eq!(provider.now().layer, unlock);
+}
+
+#[test]
+fn memory_runtime_call_reaches_oog() {
+    let mut runtime = testing::create_memory_runtime();
+
+    // 1) `Deploy Template`
+    let layout: FixedLayout = vec![Address::len() as u32].into();
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        layout,
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+    );
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr();
+    let init_state = receipt.init_state();
+
+    // 3) `Call Account` (the `gas_limit` doesn't cover the intrinsic gas)
+    let message = testing::build_call(&spawned_addr, "load_addr", &[]);
+    let envelope = Envelope::with_gas_limit(Gas::with(0));
+    let context = Context::with_state(init_state.clone());
+
+    let expected = CallReceipt::new_oog(Vec::new());
+    let actual = runtime.call(&envelope, &message, &context);
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn memory_runtime_call_metered_gas() {
+    use std::rc::Rc;
+
+    use svm_gas::{resolvers::V0PriceResolver, PriceResolver, ProgramPricing};
+    use svm_program::Program;
+
+    let mut runtime = testing::create_memory_runtime();
+    let code = &include_bytes!("wasm/runtime_calldata.wasm")[..];
+
+    // 1) `Deploy Template`
+    let layout: FixedLayout = vec![Address::len() as u32].into();
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        layout,
+        &["initialize".to_string()],
+        code.into(),
+    );
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+    let load_price = template_load_price(&message);
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr();
+    let init_state = receipt.init_state();
+
+    // 3) `Call Account` (without a `gas_limit`)
+    let param: sdk::Address = sdk::Address::repeat(0x10);
+    let mut calldata = svm_sdk::Vec::with_capacity(Address::len() + 1);
+    param.encode(&mut calldata);
+
+    let message = testing::build_call(&spawned_addr, "store_addr", &calldata);
+    let context = Context::with_state(init_state.clone());
+
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    // The gas used covers loading the `Template` and the executed ops (`svm_alloc` and `store_addr`),
+    // and the latter never exceeds the price of the executed functions.
+    let gas_used = receipt.gas_used.unwrap();
+
+    let resolver = V0PriceResolver::default();
+    let store_price = resolver.import_price(("svm", "svm_store160")) as u64;
+
+    let program = Program::new(code, false).unwrap();
+    let prices = ProgramPricing::new(Rc::new(resolver))
+        .run(&program)
+        .unwrap();
+    let func_price = |name: &str| prices.get(program.exports().get(name).unwrap()) as u64;
+
+    assert!(gas_used > load_price + store_price);
+    assert!(gas_used - load_price <= func_price("svm_alloc") + func_price("store_addr"));
+
+    // 4) `Call Account` (the `gas_limit` covers the execution exactly)
+    let intrinsic = svm_gas::transaction::call(&message);
+    let envelope = Envelope::with_gas_limit(Gas::with(intrinsic + gas_used));
+
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+    assert_eq!(receipt.gas_used, Gas::with(gas_used));
+
+    // 5) `Call Account` (running out of gas in the middle of the execution)
+    let envelope = Envelope::with_gas_limit(Gas::with(intrinsic + gas_used - 1));
+
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(!receipt.success);
+    assert_eq!(receipt.error, Some(RuntimeError::OOG));
+}
+
+#[test]
+fn memory_runtime_call_charges_template_load() {
+    let mut runtime = testing::create_memory_runtime();
+    let code = &include_bytes!("wasm/runtime_calldata.wasm")[..];
+
+    // 1) `Deploy Template`
+    let layout: FixedLayout = vec![Address::len() as u32].into();
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        layout,
+        &["initialize".to_string()],
+        code.into(),
+    );
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+    let load_price = template_load_price(&message);
+
+    // The loaded `Section`s are priced by their byte size (the `Code Section` included)
+    assert!(load_price >= svm_gas::transaction::template_load(code.len() as u64));
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr();
+    let context = Context::with_state(receipt.init_state().clone());
+
+    // 3) `Call Account` (the `gas_limit` can't cover loading the `Template`)
+    let message = testing::build_call(&spawned_addr, "load_addr", &[]);
+    let intrinsic = svm_gas::transaction::call(&message);
+    let envelope = Envelope::with_gas_limit(Gas::with(intrinsic + load_price - 1));
+
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert_eq!(receipt, CallReceipt::new_oog(Vec::new()));
+
+    // 4) `Call Account` (the `gas_limit` covers loading the `Template` but not running its code)
+    let envelope = Envelope::with_gas_limit(Gas::with(intrinsic + load_price));
+
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(!receipt.success);
+    assert_eq!(receipt.error.unwrap(), RuntimeError::OOG);
+}
+
+#[test]
+fn memory_runtime_metering_spawn_and_call() {
+    let mut runtime = testing::create_memory_runtime();
+
+    // 1) `Deploy Template` (of `Metered Gas`)
+    let message = testing::build_deploy_metering(
+        0,
+        "My Template",
+        vec![8].into(),
+        &["initialize".to_string(), "initialize_heavy".to_string()],
+        include_str!("wasm/runtime_metering.wast").into(),
+    );
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account` (the `ctor` loops `100` times)
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let ctor_gas = receipt.gas_used.unwrap();
+    assert!(ctor_gas > 100);
+
+    let spawned_addr = receipt.account_addr().clone();
+    let init_state = receipt.init_state().clone();
+
+    // 3) `Call Account` (looping `1 + 2 + ... + 100 = 5050` times)
+    let message = testing::build_call(&spawned_addr, "sum_again", &[]);
+    let context = Context::with_state(init_state.clone());
+
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let call_gas = receipt.gas_used;
+    assert!(call_gas.unwrap() > ctor_gas);
+
+    // 4) `Call Account` (the gas used by the `ctor` doesn't cover the longer loop)
+    let intrinsic = svm_gas::transaction::call(&message);
+    let limited = Envelope::with_gas_limit(Gas::with(intrinsic + ctor_gas));
+
+    let receipt = runtime.call(&limited, &message, &context);
+    assert!(!receipt.success);
+    assert_eq!(receipt.error, Some(RuntimeError::OOG));
+
+    // 5) `Call Account` (with enough gas again)
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+    assert_eq!(receipt.gas_used, call_gas);
+
+    // The code has been compiled once (by the `ctor`), whatever the gas limit of each execution
+    let stats = runtime.store_pool().stats();
+    assert_eq!(stats.created, 1);
+    assert_eq!(stats.cached, 3);
+    assert_eq!(stats.modules, 1);
+}
+
+#[test]
+fn memory_runtime_metering_ctor_reaches_oog() {
+    let mut runtime = testing::create_memory_runtime();
+
+    // 1) `Deploy Template` (of `Metered Gas`)
+    let message = testing::build_deploy_metering(
+        0,
+        "My Template",
+        vec![8].into(),
+        &["initialize".to_string(), "initialize_heavy".to_string()],
+        include_str!("wasm/runtime_metering.wast").into(),
+    );
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account` (the `ctor` loops `1,000,000` times)
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize_heavy", &[]);
+    let intrinsic = svm_gas::transaction::spawn(&message);
+    let envelope = Envelope::with_gas_limit(Gas::with(intrinsic + 100_000));
+
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(!receipt.success);
+    assert_eq!(receipt.error, Some(RuntimeError::OOG));
+
+    // 3) `Spawn Account` (with enough gas)
+    let envelope = Envelope::with_gas_limit(Gas::with(intrinsic + 100_000_000));
+
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+    assert!(receipt.gas_used.unwrap() > 1_000_000);
+}
+
+#[test]
+fn memory_runtime_spawn_with_call_success() {
+    let mut runtime = testing::create_memory_runtime();
+
+    // 1) `Deploy Template`
+    let layout: FixedLayout = vec![Address::len() as u32].into();
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        layout.clone(),
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+    );
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account` (calling `store_addr` right after the `ctor`)
+    let param: sdk::Address = sdk::Address::repeat(0x10);
+    let mut calldata = svm_sdk::Vec::with_capacity(Address::len() + 1);
+    param.encode(&mut calldata);
+
+    let message = testing::build_spawn_with_call(
+        &template_addr,
+        "My Account",
+        "initialize",
+        &[],
+        "store_addr",
+        &calldata,
+    );
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr();
+    let call_receipt = receipt.call().unwrap();
+    assert!(call_receipt.success);
+    assert_ne!(call_receipt.new_state(), receipt.init_state());
+    assert_eq!(receipt.final_state(), call_receipt.new_state());
+
+    // 3) `Call Account` (over the `State` left by the post-`ctor` call)
+    let message = testing::build_call(&spawned_addr, "load_addr", &[]);
+    let context = Context::with_state(receipt.final_state().clone());
+
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let bytes = receipt.returndata.unwrap();
+    let mut returndata = ReturnData::new(&bytes);
+
+    let addr: sdk::Address = returndata.next_1();
+    assert_eq!(addr.as_slice(), &[0x10; 20]);
+}
+
+#[test]
+fn memory_runtime_spawn_with_failing_call_fails() {
+    let mut runtime = testing::create_memory_runtime();
+
+    // 1) `Deploy Template`
+    let layout: FixedLayout = vec![Address::len() as u32].into();
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        layout.clone(),
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+    );
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account` (calling a non-existing function right after the `ctor`)
+    let message = testing::build_spawn_with_call(
+        &template_addr,
+        "My Account",
+        "initialize",
+        &[],
+        "no_such_func",
+        &[],
+    );
+    let receipt = runtime.spawn(&envelope, &message, &context);
+
+    assert!(!receipt.success);
+    assert!(receipt.account_addr.is_none());
+    assert!(receipt.call.is_none());
+    assert!(matches!(
+        receipt.error.unwrap(),
+        RuntimeError::FuncNotFound { .. }
+    ));
+}
+
+#[test]
+fn memory_runtime_snapshot_restore() {
+    let mut runtime = testing::create_memory_runtime();
+    let empty = runtime.snapshot();
+
+    // 1) `Deploy Template`
+    let layout: FixedLayout = vec![Address::len() as u32].into();
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        layout,
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+    );
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+    let deployed = runtime.snapshot();
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let spawned = runtime.spawn(&envelope, &message, &context);
+    assert!(spawned.success);
+
+    // 3) Restoring to the post-deploy snapshot and spawning again yields the same `Receipt`
+    runtime.restore(&deployed);
+
+    let respawned = runtime.spawn(&envelope, &message, &context);
+    assert_eq!(spawned, respawned);
+
+    // 4) Restoring to the empty snapshot drops the deployed `Template`
+    runtime.restore(&empty);
+    assert!(runtime
+        .template_section(&template_addr, SectionKind::Code)
+        .is_none());
+
+    runtime.restore(&deployed);
+    assert!(runtime
+        .template_section(&template_addr, SectionKind::Code)
+        .is_some());
+}
+
+#[test]
+fn memory_runtime_replay_matches_stored_receipts() {
+    use svm_runtime::replay::{self, MemReceiptStore, ReceiptStore, TxRecord};
+    use svm_types::{Layer, Receipt, TransactionId};
+
+    let mut runtime = testing::create_memory_runtime();
+    let mut store = MemReceiptStore::new();
+    let envelope = Envelope::default();
+
+    // 1) `Deploy Template`
+    let layout: FixedLayout = vec![Address::len() as u32].into();
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        layout.clone(),
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+    );
+    let context = Context::new(TransactionId::repeat(1), Layer(1), State::zeros());
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    let template_addr = receipt.addr.clone().unwrap();
+
+    store.store(TxRecord {
+        envelope: envelope.clone(),
+        context,
+        message,
+        receipt: Receipt::Deploy(receipt),
+    });
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let context = Context::new(TransactionId::repeat(2), Layer(2), State::zeros());
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    let spawned_addr = receipt.account_addr().clone();
+    let init_state = receipt.init_state().clone();
+
+    store.store(TxRecord {
+        envelope: envelope.clone(),
+        context,
+        message,
+        receipt: Receipt::Spawn(receipt),
+    });
+
+    // 3) `Call Account`
+    let param: sdk::Address = sdk::Address::repeat(0x10);
+    let mut calldata = svm_sdk::Vec::with_capacity(Address::len() + 1);
+    param.encode(&mut calldata);
+
+    let message = testing::build_call(&spawned_addr, "store_addr", &calldata);
+    let context = Context::new(TransactionId::repeat(3), Layer(3), init_state);
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    store.store(TxRecord {
+        envelope: envelope.clone(),
+        context,
+        message,
+        receipt: Receipt::Call(receipt),
+    });
+
+    // Replaying all transactions over a fresh `Runtime`
+    let mut runtime = testing::create_memory_runtime();
+
+    for tx_id in 1..=3 {
+        let record = store.load(&TransactionId::repeat(tx_id)).unwrap();
+        let replay = replay::replay(&mut runtime, record).unwrap();
+
+        assert!(replay.is_match(), "{:?}", replay.diffs);
+    }
+
+    let record = store.load(&TransactionId::repeat(3)).unwrap();
+    let replay = replay::replay(&mut runtime, record).unwrap();
+    let phases: Vec<_> = replay.phases.iter().map(|p| p.name).collect();
+    assert_eq!(phases, vec!["verify", "call"]);
+
+    // A diverging stored receipt
+    let mut receipt = replay.receipt;
+    if let Receipt::Call(ref mut receipt) = receipt {
+        receipt.returndata = Some(vec![0xFF]);
+    }
+
+    let diffs = replay::diff(&receipt, &record.receipt);
+    assert_eq!(diffs.len(), 1);
+    assert_eq!(diffs[0].field, "returndata");
+}
+
+#[test]
+fn memory_runtime_simulate_call_records_trace() {
+    use svm_runtime::trace::VmCall;
+
+    let mut runtime = testing::create_memory_runtime();
+
+    // 1) `Deploy Template`
+    let layout: FixedLayout = vec![Address::len() as u32].into();
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        layout.clone(),
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+    );
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+    assert!(runtime.take_trace().is_none());
+
+    let spawned_addr = receipt.account_addr();
+    let init_state = receipt.init_state();
+
+    // 3) Simulating `Call Account`
+    let param: sdk::Address = sdk::Address::repeat(0x10);
+    let mut calldata = svm_sdk::Vec::with_capacity(Address::len() + 1);
+    param.encode(&mut calldata);
+
+    let message = testing::build_call(&spawned_addr, "store_addr", &calldata);
+    let context = Context::with_state(init_state.clone());
+
+    let (receipt, trace) = runtime.simulate_call(&envelope, &message, &context, 1024);
+    assert!(receipt.success);
+    assert!(!trace.is_truncated());
+
+    let vmcalls: Vec<VmCall> = trace.entries().iter().map(|e| e.vmcall).collect();
+    assert!(vmcalls.contains(&VmCall::CalldataOffset));
+    assert!(vmcalls.contains(&VmCall::Store160));
+
+    // Tracing is disabled again after the simulation
+    runtime.call(&envelope, &message, &context);
+    assert!(runtime.take_trace().is_none());
+}
+
+#[test]
+fn memory_runtime_call_dispatched_to_fallback() {
+    use svm_codec::api::builder::TemplateBuilder;
+    use svm_codec::template;
+    use svm_layout::Layout;
+    use svm_types::{ApiSection, CodeSection, CtorsSection, DataSection, HeaderSection};
+
+    let mut runtime = testing::create_memory_runtime();
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let build_deploy = |fallback: &str| {
+        let wasm: testing::WasmFile = include_str!("wasm/runtime_fallback.wast").into();
+        let api = ApiSection::new().with_fallback(fallback.to_string());
+
+        let template = TemplateBuilder::default()
+            .with_code(CodeSection::new_fixed(wasm.into_bytes(), 0))
+            .with_data(DataSection::with_layout(Layout::Fixed(
+                FixedLayout::default(),
+            )))
+            .with_ctors(CtorsSection::new(vec!["ctor".to_string()]))
+            .with_header(HeaderSection::new(
+                0,
+                "My Template".to_string(),
+                "".to_string(),
+            ))
+            .with_api(api)
+            .build();
+
+        template::encode(&template)
+    };
+
+    // 1) `Deploy Template`
+    let message = build_deploy("fallback");
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "ctor", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr().clone();
+    let init_state = receipt.init_state().clone();
+
+    // 3) `Call Account` (a missing function is dispatched to the `fallback`)
+    let message = testing::build_call(&spawned_addr, "transfer", &[0x10, 0x20]);
+    let context = Context::with_state(init_state);
+
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let mut expected = vec!["transfer".len() as u8];
+    expected.extend_from_slice(b"transfer");
+    expected.extend_from_slice(&[0x10, 0x20]);
+    assert_eq!(receipt.returndata(), &expected);
+
+    // 4) A `ctor` can't serve as the `fallback`
+    let message = build_deploy("ctor");
+    let receipt = runtime.deploy(&envelope, &message, &Context::default());
+    let template_addr = receipt.addr.unwrap();
+
+    let message = testing::build_spawn(&template_addr, "My Account", "ctor", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &Context::default());
+    assert!(receipt.success);
+
+    let message = testing::build_call(receipt.account_addr(), "transfer", &[]);
+    let context = Context::with_state(receipt.init_state().clone());
+
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(matches!(
+        receipt.error.unwrap(),
+        RuntimeError::FuncNotAllowed { .. }
+    ));
+}
+
+#[test]
+fn memory_runtime_spawn_resolves_ctor_overload() {
+    use svm_codec::api::builder::TemplateBuilder;
+    use svm_codec::template;
+    use svm_layout::{Layout, Primitive};
+    use svm_types::{
+        ApiFunc, ApiParam, ApiSection, ApiType, CodeSection, CtorsSection, DataSection,
+        HeaderSection,
+    };
+
+    let mut runtime = testing::create_memory_runtime();
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let ctor = |wasm_name: &str, primitive: Primitive| ApiFunc {
+        name: "init".to_string(),
+        wasm_name: wasm_name.to_string(),
+        is_ctor: true,
+        is_fundable: false,
+        params: vec![ApiParam {
+            name: "value".to_string(),
+            ty: ApiType::Primitive(primitive),
+        }],
+        returns: vec![],
+        doc: String::new(),
+    };
+
+    // 1) `Deploy Template` (exporting two overloads of the `init` ctor)
+    let wasm: testing::WasmFile = include_str!("wasm/runtime_ctor_overloads.wast").into();
+    let api = ApiSection::new()
+        .with_func(ctor("init_u32", Primitive::U32))
+        .with_func(ctor("init_bool", Primitive::Bool));
+
+    let template = TemplateBuilder::default()
+        .with_code(CodeSection::new_fixed(wasm.into_bytes(), 0))
+        .with_data(DataSection::with_layout(Layout::Fixed(
+            FixedLayout::default(),
+        )))
+        .with_ctors(CtorsSection::new(vec![
+            "init_u32".to_string(),
+            "init_bool".to_string(),
+        ]))
+        .with_header(HeaderSection::new(
+            0,
+            "My Template".to_string(),
+            "".to_string(),
+        ))
+        .with_api(api)
+        .build();
+
+    let message = template::encode(&template);
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+
+    let mut spawn = |ctor: &str, calldata: svm_sdk::Vec<u8>| {
+        let message = testing::build_spawn(&template_addr, "My Account", ctor, &calldata);
+        runtime.spawn(&envelope, &message, &context)
+    };
+
+    fn encode<T: Encoder<svm_sdk::Vec<u8>>>(value: T) -> svm_sdk::Vec<u8> {
+        let mut calldata = svm_sdk::Vec::with_capacity(16);
+        value.encode(&mut calldata);
+        calldata
+    }
+
+    // 2) The overload is resolved by the types of the `Calldata`
+    let receipt = spawn("init", encode(10u32));
+    assert!(receipt.success);
+    assert_eq!(receipt.returndata(), b"u32");
+
+    let receipt = spawn("init", encode(true));
+    assert!(receipt.success);
+    assert_eq!(receipt.returndata(), b"bool");
+
+    // 3) An overload asked for by its Wasm name is verified against its signature
+    let receipt = spawn("init_bool", encode(false));
+    assert!(receipt.success);
+
+    let receipt = spawn("init_bool", encode(10u32));
+    assert!(matches!(
+        receipt.error.unwrap(),
+        RuntimeError::FuncNotAllowed { .. }
+    ));
+
+    // 4) No overload matches the `Calldata`
+    let receipt = spawn("init", encode(10u64));
+    assert!(matches!(
+        receipt.error.unwrap(),
+        RuntimeError::FuncNotAllowed { .. }
+    ));
+}
+
+#[test]
+fn memory_runtime_host_modules() {
+    use svm_runtime::{HostCapabilities, HostModule, HostModuleError};
+
+    let oracle = || {
+        HostModule::new("oracle", |store| {
+            let mut exports = wasmer::Exports::new();
+            exports.insert("price", wasmer::Function::new_native(store, || 42i64));
+            exports
+        })
+    };
+
+    let mut runtime = testing::create_memory_runtime();
+
+    // 1) Registering the host modules
+    let no_verify = HostCapabilities {
+        verify: false,
+        ..HostCapabilities::all()
+    };
+
+    assert_eq!(
+        runtime.register_host_module(oracle().with_capabilities(no_verify)),
+        Ok(())
+    );
+    assert_eq!(
+        runtime.register_host_module(oracle()),
+        Err(HostModuleError::Duplicate("oracle".to_string()))
+    );
+    assert_eq!(
+        runtime.register_host_module(HostModule::new("svm", |_| wasmer::Exports::new())),
+        Err(HostModuleError::Reserved("svm".to_string()))
+    );
+    assert_eq!(runtime.host_modules().len(), 1);
+
+    // 2) `Deploy Template`
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        FixedLayout::default(),
+        &["ctor".to_string()],
+        include_str!("wasm/runtime_host_modules.wast").into(),
+    );
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+
+    // 3) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "ctor", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr();
+    let context = Context::with_state(receipt.init_state().clone());
+
+    // 4) `Call Account` (the `oracle` is available)
+    let message = testing::build_call(&spawned_addr, "get_price", &[]);
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+    assert_eq!(receipt.returndata.unwrap(), 42u64.to_le_bytes());
+
+    // 5) `Verify` (the `oracle` isn't available)
+    let receipt = runtime.verify(&envelope, &message, &context);
+    assert!(!receipt.success);
+    assert!(matches!(
+        receipt.error.unwrap(),
+        RuntimeError::InstantiationFailed { .. }
+    ));
+}
+
+#[test]
+fn memory_runtime_precompile() {
+    use std::convert::TryInto;
+
+    use svm_runtime::{DuplicatePrecompile, HostPhase};
+    use svm_runtime::{PrecompileCall, PrecompileError, PrecompileOutput};
+    use svm_storage::account::AccountStorage;
+
+    // A native counter (its storage holds a single `u64` variable)
+    fn counter(
+        call: &PrecompileCall,
+        storage: Option<&mut AccountStorage>,
+    ) -> Result<PrecompileOutput, PrecompileError> {
+        let read_u64 = |bytes: &[u8]| u64::from_le_bytes(bytes.try_into().unwrap());
+
+        match (call.func_name, storage) {
+            ("initialize", Some(storage)) => {
+                assert_eq!(call.phase, HostPhase::Spawn);
+                storage.write_var(Id(0), call.calldata.to_vec());
+
+                Ok(PrecompileOutput {
+                    gas_used: 10,
+                    ..PrecompileOutput::default()
+                })
+            }
+            ("add", Some(storage)) => {
+                let value = read_u64(&storage.read_var(Id(0)).unwrap()) + read_u64(call.calldata);
+                storage.write_var(Id(0), value.to_le_bytes().to_vec());
+
+                Ok(PrecompileOutput {
+                    returndata: value.to_le_bytes().to_vec(),
+                    gas_used: 5,
+                    logs: vec![ReceiptLog::new(b"added".to_vec())],
+                })
+            }
+            ("svm_verify", None) => Ok(PrecompileOutput::default()),
+            ("expensive", Some(..)) => Ok(PrecompileOutput {
+                gas_used: 1_000_000,
+                ..PrecompileOutput::default()
+            }),
+            ("fail", Some(..)) => Err(PrecompileError::Failed("Not today".to_string())),
+            _ => Err(PrecompileError::FuncNotFound),
+        }
+    }
+
+    let mut runtime = testing::create_memory_runtime();
+
+    // 1) Registering the precompile
+    assert_eq!(runtime.register_precompile(7, counter), Ok(()));
+    assert_eq!(
+        runtime.register_precompile(7, counter),
+        Err(DuplicatePrecompile(7))
+    );
+
+    // 2) `Deploy Template` (only of a registered precompile)
+    let layout: FixedLayout = vec![8].into();
+    let ctors = ["initialize".to_string()];
+
+    let message = testing::build_deploy_precompile(0, "Counter", layout.clone(), &ctors, 8);
+    assert_eq!(
+        runtime.validate_deploy(&message),
+        Err(ValidateError::UnknownPrecompile(8))
+    );
+
+    let message = testing::build_deploy_precompile(0, "Counter", layout, &ctors, 7);
+    assert_eq!(runtime.validate_deploy(&message), Ok(()));
+
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+    let load_price = template_load_price(&message);
+
+    // 3) `Spawn Account` (loading the precompile `Template` is charged as well)
+    let message = testing::build_spawn(
+        &template_addr,
+        "My Counter",
+        "initialize",
+        &10u64.to_le_bytes(),
+    );
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+    assert_eq!(receipt.gas_used, Gas::with(10 + load_price));
+
+    let spawned_addr = receipt.account_addr().clone();
+    let init_state = receipt.init_state().clone();
+    let context = Context::with_state(init_state.clone());
+
+    // 4) `Call Account`
+    let message = testing::build_call(&spawned_addr, "add", &5u64.to_le_bytes());
+    let receipt = runtime.call(&envelope, &message, &context);
+
+    assert!(receipt.success);
+    assert_eq!(receipt.returndata.unwrap(), 15u64.to_le_bytes());
+    assert_eq!(receipt.gas_used, Gas::with(5 + load_price));
+    assert_eq!(receipt.logs, vec![ReceiptLog::new(b"added".to_vec())]);
+    assert_ne!(receipt.new_state.unwrap(), init_state);
+
+    // 5) `Verify` (without storage access)
+    let receipt = runtime.verify(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    // 6) Failures are reported just like failures of Wasm code
+    let message = testing::build_call(&spawned_addr, "fail", &[]);
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(matches!(
+        receipt.error.unwrap(),
+        RuntimeError::FuncFailed { msg, .. } if msg == "Not today"
+    ));
+
+    let message = testing::build_call(&spawned_addr, "no_such_func", &[]);
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(matches!(
+        receipt.error.unwrap(),
+        RuntimeError::FuncNotFound { .. }
+    ));
+
+    let message = testing::build_call(&spawned_addr, "initialize", &[]);
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(matches!(
+        receipt.error.unwrap(),
+        RuntimeError::FuncNotAllowed { .. }
+    ));
+
+    let message = testing::build_call(&spawned_addr, "expensive", &[]);
+    let gas_limit = svm_gas::transaction::call(&message) + 1000;
+    let envelope = Envelope::with_gas_limit(Gas::with(gas_limit));
+
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert_eq!(receipt.error.unwrap(), RuntimeError::OOG);
+}
+
+#[test]
+fn memory_runtime_panic_isolation() {
+    use svm_runtime::{PrecompileCall, PrecompileError, PrecompileOutput};
+    use svm_storage::account::AccountStorage;
+
+    fn buggy(
+        call: &PrecompileCall,
+        _storage: Option<&mut AccountStorage>,
+    ) -> Result<PrecompileOutput, PrecompileError> {
+        match call.func_name {
+            "initialize" | "ok" => Ok(PrecompileOutput::default()),
+            "bug" => panic!("storage invariant violated"),
+            _ => Err(PrecompileError::FuncNotFound),
+        }
+    }
+
+    let mut runtime = testing::create_memory_runtime();
+    runtime.register_precompile(3, buggy).unwrap();
+
+    let layout: FixedLayout = vec![8].into();
+    let ctors = ["initialize".to_string()];
+    let message = testing::build_deploy_precompile(0, "Buggy", layout, &ctors, 3);
+
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    let template_addr = receipt.addr.unwrap();
+
+    let message = testing::build_spawn(&template_addr, "My Buggy", "initialize", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr().clone();
+    let context = Context::with_state(receipt.init_state().clone());
+
+    // 1) A panic results in an `InternalError` receipt (and doesn't reach us)
+    let message = testing::build_call(&spawned_addr, "bug", &[]);
+    let receipt = runtime.call(&envelope, &message, &context);
+
+    assert!(!receipt.success);
+    assert_eq!(
+        receipt.error.unwrap(),
+        RuntimeError::InternalError {
+            msg: "storage invariant violated".to_string()
+        }
+    );
+    assert_eq!(runtime.metrics().internal_errors(), 1);
+
+    // 2) Queries are isolated as well
+    let receipt = runtime.query_receipt(&envelope, &message, &context);
+    assert!(matches!(
+        receipt.error.unwrap(),
+        RuntimeError::InternalError { .. }
+    ));
+    assert_eq!(runtime.metrics().internal_errors(), 2);
+
+    // 3) The `Runtime` remains usable
+    let message = testing::build_call(&spawned_addr, "ok", &[]);
+    let receipt = runtime.call(&envelope, &message, &context);
+
+    assert!(receipt.success);
+    assert_eq!(runtime.metrics().internal_errors(), 2);
+}
+
+#[test]
+fn memory_runtime_upgrade_template() {
+    let mut runtime = testing::create_memory_runtime();
+
+    let wasm_v1 = include_str!("wasm/runtime_upgrade.wast");
+    let wasm_v2 = wasm_v1.replace("\\01", "\\02");
+    let layout: FixedLayout = vec![8].into();
+    let ctors = ["ctor".to_string()];
+
+    let deployer = Address::of("@Deployer");
+    let envelope = Envelope::with_principal(deployer.clone());
+    let context = Context::default();
+
+    // 1) `Deploy Template`
+    let message = testing::build_deploy(0, "My Template", layout.clone(), &ctors, wasm_v1.into());
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "ctor", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr().clone();
+    let context = Context::with_state(receipt.init_state().clone());
+
+    let call = testing::build_call(&spawned_addr, "version", &[]);
+    let receipt = runtime.call(&envelope, &call, &context);
+    assert_eq!(receipt.returndata(), &[1]);
+
+    // 3) `Upgrade Template` (appending a storage variable)
+    let layout: FixedLayout = vec![8, 4].into();
+    let message = testing::build_upgrade(
+        &template_addr,
+        1,
+        "My Template",
+        layout,
+        &ctors,
+        wasm_v2.as_str().into(),
+    );
+    runtime.validate_upgrade(&message).unwrap();
+
+    let receipt = runtime.upgrade(&envelope, &message, &context);
+    assert!(receipt.success);
+    assert_eq!(receipt.template_addr(), &template_addr);
+    assert_eq!(receipt.template_version, 1);
+
+    // The upgraded code has a single byte data segment
+    assert_eq!(
+        receipt.gas_used,
+        Gas::with(svm_gas::transaction::upgrade(&message) + svm_gas::transaction::data_segments(1))
+    );
+
+    // 4) The existing `Account` runs the upgraded code
+    let receipt = runtime.call(&envelope, &call, &context);
+    assert!(receipt.success);
+    assert_eq!(receipt.returndata(), &[2]);
+}
+
+#[test]
+fn memory_runtime_price_cache() {
+    let mut runtime = testing::create_memory_runtime();
+
+    let wasm_v1 = include_str!("wasm/runtime_upgrade.wast");
+    let wasm_v2 = wasm_v1.replace("\\01", "\\02");
+    let layout: FixedLayout = vec![8].into();
+    let ctors = ["ctor".to_string()];
+
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    // 1) `Deploy Template`
+    let message = testing::build_deploy(0, "My Template", layout.clone(), &ctors, wasm_v1.into());
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    let template_addr = receipt.addr.unwrap();
+
+    assert_eq!(runtime.price_cache_stats(), PriceCacheStats::default());
+
+    // 2) Spawning twice prices the `Template` once
+    let message = testing::build_spawn(&template_addr, "Account #1", "ctor", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let stats = runtime.price_cache_stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (0, 1, 1));
+
+    let message = testing::build_spawn(&template_addr, "Account #2", "ctor", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let stats = runtime.price_cache_stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+
+    // 3) Upgrading the `Template` drops its prices
+    let message = testing::build_upgrade(
+        &template_addr,
+        1,
+        "My Template",
+        layout,
+        &ctors,
+        wasm_v2.as_str().into(),
+    );
+    let receipt = runtime.upgrade(&envelope, &message, &context);
+    assert!(receipt.success);
+    assert_eq!(runtime.price_cache_stats().entries, 0);
+
+    let message = testing::build_spawn(&template_addr, "Account #3", "ctor", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let stats = runtime.price_cache_stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 1));
+}
+
+#[test]
+fn memory_runtime_upgrade_template_not_allowed() {
+    let mut runtime = testing::create_memory_runtime();
+
+    let wasm = include_str!("wasm/runtime_upgrade.wast");
+    let layout: FixedLayout = vec![8, 4].into();
+    let ctors = ["ctor".to_string()];
+
+    let envelope = Envelope::with_principal(Address::of("@Deployer"));
+    let context = Context::default();
+
+    let message = testing::build_deploy(0, "My Template", layout.clone(), &ctors, wasm.into());
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    let template_addr = receipt.addr.unwrap();
+
+    // Only the original deployer may upgrade the `Template`
+    let message = testing::build_upgrade(
+        &template_addr,
+        1,
+        "My Template",
+        layout,
+        &ctors,
+        wasm.into(),
+    );
+    let other = Envelope::with_principal(Address::of("@Other"));
+
+    let receipt = runtime.upgrade(&other, &message, &context);
+    assert!(matches!(
+        receipt.error.unwrap(),
+        RuntimeError::UpgradeNotAllowed { template, .. } if template == template_addr
+    ));
+
+    // The existing storage variables must be kept
+    let layout: FixedLayout = vec![4].into();
+    let message = testing::build_upgrade(
+        &template_addr,
+        1,
+        "My Template",
+        layout,
+        &ctors,
+        wasm.into(),
+    );
+
+    let receipt = runtime.upgrade(&envelope, &message, &context);
+    assert!(matches!(
+        receipt.error.unwrap(),
+        RuntimeError::UpgradeNotAllowed { .. }
+    ));
+
+    // A missing `Template` can't be upgraded
+    let missing = svm_types::TemplateAddr::of("@Missing");
+    let layout: FixedLayout = vec![8, 4].into();
+    let message = testing::build_upgrade(&missing, 1, "My Template", layout, &ctors, wasm.into());
+
+    let receipt = runtime.upgrade(&envelope, &message, &context);
+    assert_eq!(
+        receipt.error.unwrap(),
+        RuntimeError::TemplateNotFound(missing)
+    );
+}
+
+#[test]
+fn memory_runtime_call_batch() {
+    let mut runtime = testing::create_memory_runtime();
+
+    // 1) `Deploy Template`
+    let layout: FixedLayout = vec![8].into();
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        layout,
+        &["ctor".to_string()],
+        include_str!("wasm/runtime_batch.wast").into(),
+    );
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "ctor", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr().clone();
+    let init_state = receipt.init_state().clone();
+
+    // 3) `Call Batch` (each call sees the changes of its predecessors)
+    let message = testing::build_batch(&[
+        (&spawned_addr, "inc", &[]),
+        (&spawned_addr, "inc", &[]),
+        (&spawned_addr, "get", &[]),
+    ]);
+    runtime.validate_batch(&message).unwrap();
+
+    let context = Context::with_state(init_state.clone());
+    let receipt = runtime.call_batch(&envelope, &message, &context);
+    assert!(receipt.success);
+    assert_eq!(receipt.receipts.len(), 3);
+    assert_eq!(receipt.receipts[2].returndata(), &2u64.to_le_bytes());
+    assert_eq!(receipt.new_state(), receipt.receipts[2].new_state());
+    assert_eq!(runtime.state_root(Layer(0)), receipt.new_state.clone());
+
+    let calls_gas: u64 = receipt.receipts.iter().map(|r| r.gas_used.unwrap()).sum();
+    assert_eq!(
+        receipt.gas_used,
+        Gas::with(svm_gas::transaction::call(&message) + calls_gas)
+    );
+
+    let new_state = receipt.new_state().clone();
+
+    // The batched calls are committed at once
+    assert!(receipt.receipts.iter().all(|r| r.new_state() == &new_state));
+
+    // 4) `Call Batch` (a failing call rolls back the whole batch)
+    let message =
+        testing::build_batch(&[(&spawned_addr, "inc", &[]), (&spawned_addr, "fail", &[])]);
+
+    // Any commit moves the head of the (shared) key-value store backing the storages
+    let probe = runtime.open_storage(&spawned_addr, &new_state, &vec![8].into());
+
+    let context = Context::with_state(new_state.clone());
+    let receipt = runtime.call_batch(&envelope, &message, &context);
+    assert!(matches!(
+        receipt.error.unwrap(),
+        RuntimeError::FuncFailed { func, .. } if func == "fail"
+    ));
+    assert!(receipt.receipts.is_empty());
+    assert_eq!(runtime.state_root(Layer(0)), Some(new_state.clone()));
+    assert_eq!(probe.head(), new_state);
+
+    let message = testing::build_call(&spawned_addr, "get", &[]);
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert_eq!(receipt.returndata(), &2u64.to_le_bytes());
+
+    // 5) `Call Batch` (a missing `Account` fails the batch)
+    let missing = Address::of("@Missing");
+    let message = testing::build_batch(&[(&spawned_addr, "inc", &[]), (&missing, "inc", &[])]);
+
+    let receipt = runtime.call_batch(&envelope, &message, &context);
+    assert_eq!(
+        receipt.error.unwrap(),
+        RuntimeError::AccountNotFound(missing)
+    );
+}
+
+#[test]
+fn memory_runtime_metrics() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use svm_types::{ReceiptMetric, TemplateAddr, TransactionId};
+
+    let mut runtime = testing::create_memory_runtime();
+
+    let observed = Rc::new(RefCell::new(Vec::new()));
+    let observer = {
+        let observed = Rc::clone(&observed);
+
+        move |account: &Address, template: &TemplateAddr, metrics: &[ReceiptMetric]| {
+            observed
+                .borrow_mut()
+                .push((account.clone(), template.clone(), metrics.to_vec()))
+        }
+    };
+    runtime.set_metrics_observer(Some(Box::new(observer)));
+
+    let envelope = Envelope::default();
+
+    // 1) `Deploy Template`
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        FixedLayout::default(),
+        &["ctor".to_string()],
+        include_str!("wasm/runtime_metrics.wast").into(),
+    );
+    let context = Context::default();
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "ctor", &[]);
+    let context = Context::new(TransactionId::repeat(2), Layer(2), State::zeros());
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+    assert_eq!(receipt.version, 5);
+    assert_eq!(receipt.metrics, vec![ReceiptMetric::new(1, 10)]);
+
+    let spawned_addr = receipt.account_addr().clone();
+    let init_state = receipt.init_state().clone();
+
+    let bytes = svm_codec::receipt::encode_spawn(&receipt);
+    assert_eq!(svm_codec::receipt::decode_spawn(&bytes), receipt);
+
+    // 3) `Call Account`
+    let message = testing::build_call(&spawned_addr, "report", &[]);
+    let context = Context::new(TransactionId::repeat(3), Layer(3), init_state.clone());
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+    assert!(receipt.logs.is_empty());
+    assert_eq!(
+        receipt.metrics,
+        vec![ReceiptMetric::new(2, 20), ReceiptMetric::new(1, 11)]
+    );
+
+    let bytes = svm_codec::receipt::encode_call(&receipt);
+    assert_eq!(svm_codec::receipt::decode_call(&bytes), receipt);
+
+    // 4) A failed `Call Account` carries (and reports) no metrics
+    let message = testing::build_call(&spawned_addr, "report_and_fail", &[]);
+    let context = Context::new(TransactionId::repeat(4), Layer(4), init_state);
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(!receipt.success);
+    assert!(receipt.metrics.is_empty());
+
+    assert_eq!(
+        *observed.borrow(),
+        vec![
+            (
+                spawned_addr.clone(),
+                template_addr.clone(),
+                vec![ReceiptMetric::new(1, 10)]
+            ),
+            (
+                spawned_addr,
+                template_addr,
+                vec![ReceiptMetric::new(2, 20), ReceiptMetric::new(1, 11)]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn memory_runtime_template_cache() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use svm_runtime::{MetricsObserver, TemplateCacheStats};
+    use svm_types::{ReceiptMetric, TemplateAddr, TransactionId};
+
+    struct CacheObserver(Rc<RefCell<Vec<TemplateCacheStats>>>);
+
+    impl MetricsObserver for CacheObserver {
+        fn observe(&mut self, _: &Address, _: &TemplateAddr, _: &[ReceiptMetric]) {}
+
+        fn observe_template_cache(&mut self, stats: &TemplateCacheStats) {
+            self.0.borrow_mut().push(*stats);
+        }
+    }
+
+    let mut runtime = testing::create_cached_memory_runtime(8);
+
+    let observed = Rc::new(RefCell::new(Vec::new()));
+    let observer = CacheObserver(Rc::clone(&observed));
+    runtime.set_metrics_observer(Some(Box::new(observer)));
+
+    let envelope = Envelope::default();
+
+    // 1) `Deploy Template`
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        FixedLayout::default(),
+        &["ctor".to_string()],
+        include_str!("wasm/runtime_metrics.wast").into(),
+    );
+    let receipt = runtime.deploy(&envelope, &message, &Context::default());
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "ctor", &[]);
+    let context = Context::new(TransactionId::repeat(2), Layer(2), State::zeros());
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr().clone();
+    let state = receipt.init_state().clone();
+
+    // 3) Calling the `Account` twice, the second `Call` loads the `Template` from the cache only
+    let message = testing::build_call(&spawned_addr, "report", &[]);
+    let context = Context::new(TransactionId::repeat(3), Layer(3), state);
+
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+    let first = *observed.borrow().last().unwrap();
+    assert!(first.misses > 0);
+    assert!(first.entries > 0);
+
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+    let second = *observed.borrow().last().unwrap();
+    assert_eq!(second.misses, first.misses);
+    assert!(second.hits > first.hits);
+    assert_eq!(second.entries, first.entries);
+    assert_eq!(second.evictions, 0);
+
+    // Statistics are reported once per transaction
+    assert_eq!(observed.borrow().len(), 3);
+}
+
+#[test]
+fn memory_runtime_logs_bloom() {
+    use svm_runtime::export::ExportFormat;
+    use svm_runtime::replay::{MemReceiptStore, ReceiptStore, TxRecord};
+    use svm_types::{Receipt, TemplateAddr, TransactionId};
+
+    let mut runtime = testing::create_memory_runtime();
+    runtime.set_logs_bloom(true);
+
+    let mut store = MemReceiptStore::new();
+    let envelope = Envelope::default();
+
+    // 1) `Deploy Template`
+    let layout: FixedLayout = vec![8].into();
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        layout,
+        &["ctor".to_string()],
+        include_str!("wasm/runtime_logs.wast").into(),
+    );
+    let context = Context::default();
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "ctor", &[]);
+    let context = Context::new(TransactionId::repeat(2), Layer(2), State::zeros());
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert_eq!(receipt.version, 3);
+
+    let bloom = receipt.logs_bloom.clone().unwrap();
+    assert!(bloom.maybe_contains_template(&template_addr));
+    assert!(!bloom.maybe_contains(b"Hello World"));
+
+    let spawned_addr = receipt.account_addr().clone();
+    let init_state = receipt.init_state().clone();
+
+    store.store(TxRecord {
+        envelope: envelope.clone(),
+        context,
+        message,
+        receipt: Receipt::Spawn(receipt),
+    });
+
+    // 3) `Call Account`
+    let message = testing::build_call(&spawned_addr, "say_hello", &[]);
+    let context = Context::new(TransactionId::repeat(3), Layer(3), init_state);
+    let receipt = runtime.call(&envelope, &message, &context);
+    assert_eq!(receipt.logs, vec![ReceiptLog::new(b"Hello World".to_vec())]);
+    assert_eq!(receipt.version, 3);
+
+    let bloom = receipt.logs_bloom.clone().unwrap();
+    assert!(bloom.maybe_contains_template(&template_addr));
+    assert!(bloom.maybe_contains(b"Hello World"));
+    assert!(!bloom.maybe_contains_template(&TemplateAddr::of("@Other")));
+
+    let bytes = svm_codec::receipt::encode_call(&receipt);
+    assert_eq!(svm_codec::receipt::decode_call(&bytes), receipt);
+
+    store.store(TxRecord {
+        envelope: envelope.clone(),
+        context,
+        message,
+        receipt: Receipt::Call(receipt),
+    });
+
+    // 4) Scanning the stored receipts
+    assert!(store.layer_maybe_contains(Layer(3), b"Hello World"));
+    assert!(!store.layer_maybe_contains(Layer(2), b"Hello World"));
+    assert!(!store.layer_maybe_contains(Layer(4), b"Hello World"));
+    assert!(store.layer_bloom(Layer(2)).is_some());
+
+    let found: Vec<_> = store
+        .iter_maybe_containing(b"Hello World")
+        .map(|record| record.tx_id().clone())
+        .collect();
+    assert_eq!(found, vec![TransactionId::repeat(3)]);
+
+    // 5) Exporting the receipts of a range of layers
+    let mut csv = Vec::new();
+    let count = store
+        .export(Layer(3)..Layer(4), ExportFormat::Csv, &mut csv)
+        .unwrap();
+    assert_eq!(count, 1);
+
+    let csv = String::from_utf8(csv).unwrap();
+    let row = csv.lines().nth(1).unwrap();
+    assert!(row.starts_with(&format!("{},3,call,true,", TransactionId::repeat(3))));
+
+    // The `target` is decoded out of the message (and the `template` is left empty)
+    let target: String = spawned_addr
+        .as_slice()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    assert!(row.ends_with(&format!(",0x{},", target)));
+}
+
+#[test]
+fn memory_runtime_store_pool_reuses_stores() {
+    use svm_runtime::{StorePoolConfig, StorePoolStats};
+
+    let mut runtime = testing::create_memory_runtime();
+    let envelope = Envelope::default();
+
+    // 1) `Deploy Template`
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        FixedLayout::default(),
+        &["initialize".to_string()],
+        include_str!("wasm/runtime_store_isolation.wast").into(),
+    );
+    let receipt = runtime.deploy(&envelope, &message, &Context::default());
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &Context::default());
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr().clone();
+    let context = Context::with_state(receipt.init_state().clone());
+
+    // 3) Calling the `Account` three times, all the executions share the module compiled by the `ctor`
+    let message = testing::build_call(&spawned_addr, "poison", &[]);
+
+    for _ in 0..3 {
+        let receipt = runtime.call(&envelope, &message, &context);
+        assert!(receipt.success);
+    }
+
+    assert_eq!(
+        runtime.store_pool().stats(),
+        StorePoolStats {
+            created: 1,
+            reused: 0,
+            retired: 0,
+            idle: 1,
+            cached: 3,
+            modules: 1,
+        }
+    );
+
+    // 4) Without caching modules, all the executions share a single `Store`
+    runtime.set_store_pool(StorePoolConfig {
+        max_modules: 0,
+        ..StorePoolConfig::default()
+    });
+
+    for _ in 0..3 {
+        let receipt = runtime.call(&envelope, &message, &context);
+        assert!(receipt.success);
+    }
+
+    assert_eq!(
+        runtime.store_pool().stats(),
+        StorePoolStats {
+            created: 1,
+            reused: 2,
+            retired: 0,
+            idle: 1,
+            cached: 0,
+            modules: 0,
+        }
+    );
+
+    // 5) A `Store` compiling a single module is retired right after its execution
+    runtime.set_store_pool(StorePoolConfig {
+        max_idle: 4,
+        max_compilations: 1,
+        max_modules: 0,
+    });
+
+    for _ in 0..2 {
+        let receipt = runtime.call(&envelope, &message, &context);
+        assert!(receipt.success);
+    }
+
+    assert_eq!(
+        runtime.store_pool().stats(),
+        StorePoolStats {
+            created: 2,
+            reused: 0,
+            retired: 2,
+            idle: 0,
+            cached: 0,
+            modules: 0,
+        }
+    );
+}
+
+#[test]
+fn memory_runtime_store_pool_isolates_executions() {
+    let mut runtime = testing::create_memory_runtime();
+    let envelope = Envelope::default();
+
+    // 1) `Deploy Template` (its `poison` function traps when it observes the state left behind by a former execution)
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        FixedLayout::default(),
+        &["initialize".to_string()],
+        include_str!("wasm/runtime_store_isolation.wast").into(),
+    );
+    let receipt = runtime.deploy(&envelope, &message, &Context::default());
+    assert!(receipt.success);
+
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) Spawning two `Account`s of the `Template`
+    let mut accounts = Vec::new();
+
+    for name in ["Account #1", "Account #2"].iter() {
+        let message = testing::build_spawn(&template_addr, name, "initialize", &[]);
+        let receipt = runtime.spawn(&envelope, &message, &Context::default());
+        assert!(receipt.success);
+
+        accounts.push(receipt.account_addr().clone());
+    }
+
+    // 3) Poisoning each `Account` repeatedly (both sequentially and within a `Batch`),
+    // no execution observes the memory, the memory size or the globals of a former one (running the same module)
+    for addr in accounts.iter().chain(accounts.iter()) {
+        let message = testing::build_call(addr, "poison", &[]);
+        let receipt = runtime.call(&envelope, &message, &Context::default());
+        assert!(receipt.success);
+        assert_eq!(receipt.error, None);
+    }
+
+    let calls: Vec<(&Address, &str, &[u8])> = accounts
+        .iter()
+        .chain(accounts.iter())
+        .map(|addr| (addr, "poison", &[][..]))
+        .collect();
+    let message = testing::build_batch(&calls);
+    let receipt = runtime.call_batch(&envelope, &message, &Context::default());
+    assert!(receipt.success);
+
+    assert!(runtime.store_pool().stats().cached > 0);
+}
+
+#[test]
+fn memory_runtime_query_read_only() {
+    let mut runtime = testing::create_memory_runtime();
+
+    // 1) `Deploy Template`
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        vec![Address::len() as u32].into(),
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+    );
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr().clone();
+    let context = Context::with_state(receipt.init_state().clone());
+
+    // 3) `Call Account` (storing an `Address`)
+    let param = sdk::Address::repeat(0x10);
+    let mut calldata = svm_sdk::Vec::with_capacity(Address::len() + 1);
+    param.encode(&mut calldata);
+
+    let store = testing::build_call(&spawned_addr, "store_addr", &calldata);
+    let receipt = runtime.call(&envelope, &store, &context);
+    assert!(receipt.success);
+
+    let context = Context::with_state(receipt.new_state().clone());
+    let account_root = runtime.current_state_root(&spawned_addr).unwrap();
+
+    // 4) Querying a function reading the storage
+    let load = testing::build_call(&spawned_addr, "load_addr", &[]);
+    let result = Runtime::query(&runtime, &envelope, &load, &context);
+    assert!(result.success);
+    assert_eq!(result.error, None);
+
+    let mut returndata = ReturnData::new(&result.returndata);
+    let addr: sdk::Address = returndata.next_1();
+    assert_eq!(addr.as_slice(), &[0x10; 20]);
+
+    // The gas estimate covers executing the message as a transaction exactly
+    let gas_estimate = result.gas_estimate.unwrap();
+
+    let receipt = runtime.call(
+        &Envelope::with_gas_limit(Gas::with(gas_estimate)),
+        &load,
+        &context,
+    );
+    assert!(receipt.success);
+
+    let envelope_short = Envelope::with_gas_limit(Gas::with(gas_estimate - 1));
+    let receipt = runtime.call(&envelope_short, &load, &context);
+    assert_eq!(receipt.error, Some(RuntimeError::OOG));
+
+    let result = Runtime::query(&runtime, &envelope_short, &load, &context);
+    assert_eq!(result.error, Some(RuntimeError::OOG));
+    assert!(result.gas_estimate.is_none());
+
+    // 5) Querying a function modifying the storage fails (without counting as an internal error)
+    let result = Runtime::query(&runtime, &envelope, &store, &context);
+    assert!(!result.success);
+    assert!(matches!(
+        result.error.unwrap(),
+        RuntimeError::FuncFailed { func, .. } if func == "store_addr"
+    ));
+    assert_eq!(runtime.metrics().internal_errors(), 0);
+
+    // Nothing has been committed
+    assert_eq!(
+        runtime.current_state_root(&spawned_addr),
+        Some(account_root)
+    );
+}
+
+/// The `calldata` of the `forward` function of `wasm/runtime_cross_call.wast`.
+/// Derives the `Account`s `Address` from their names (so a `Template` can be spawned many times).
+struct NamedScheme;
+
+impl svm_runtime::AddressScheme for NamedScheme {
+    fn template_addr(&self, _template: &svm_types::Template) -> svm_types::TemplateAddr {
+        svm_types::TemplateAddr::repeat(0x77)
+    }
+
+    fn account_addr(&self, spawn: &svm_runtime::ExtSpawn) -> Address {
+        Address::of(spawn.name())
+    }
+}
+
+fn forward_calldata(target: &Address, func_name: &str, calldata: &[u8]) -> Vec<u8> {
+    let mut forward = target.as_slice().to_vec();
+    forward.push(func_name.len() as u8);
+    forward.extend_from_slice(func_name.as_bytes());
+    forward.extend_from_slice(calldata);
+
+    forward
+}
+
+#[test]
+fn memory_runtime_cross_account_call() {
+    use std::rc::Rc;
+
+    let mut runtime = testing::create_memory_runtime();
+    runtime.register_addr_scheme(1, Rc::new(NamedScheme));
+    runtime.set_addr_scheme(1);
+
+    let envelope = Envelope::default();
+
+    // 1) `Deploy Template`
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        FixedLayout::default(),
+        &["ctor".to_string()],
+        include_str!("wasm/runtime_cross_call.wast").into(),
+    );
+    let receipt = runtime.deploy(&envelope, &message, &Context::default());
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn` an `Account` more than the maximum depth of a calls chain
+    let mut accounts = Vec::new();
+    let mut state = State::zeros();
+
+    for i in 0..=svm_runtime::MAX_CALL_DEPTH {
+        let name = format!("Account #{}", i);
+        let message = testing::build_spawn(&template_addr, &name, "ctor", &[]);
+        let receipt = runtime.spawn(&envelope, &message, &Context::with_state(state));
+        assert!(receipt.success);
+
+        accounts.push(receipt.account_addr().clone());
+        state = receipt.init_state().clone();
+    }
+
+    let context = Context::with_state(state);
+    let call = |runtime: &mut _, calldata: &[u8]| -> CallReceipt {
+        let message = testing::build_call(&accounts[0], "forward", calldata);
+        Runtime::call(runtime, &envelope, &message, &context)
+    };
+
+    // 3) Calling another `Account` (its logs are propagated)
+    let calldata = forward_calldata(&accounts[1], "echo", b"Hi");
+    let receipt = call(&mut runtime, &calldata);
+    assert!(receipt.success);
+    assert_eq!(
+        receipt.returndata.unwrap(),
+        [&2i32.to_le_bytes()[..], b"Hi"].concat()
+    );
+    assert_eq!(receipt.logs, vec![ReceiptLog::new(b"Hi".to_vec())]);
+
+    // 4) Calling a missing `Account` fails (without failing the caller)
+    let calldata = forward_calldata(&Address::repeat(0xFF), "echo", b"Hi");
+    let receipt = call(&mut runtime, &calldata);
+    assert!(receipt.success);
+    assert_eq!(
+        receipt.returndata.unwrap(),
+        svm_runtime::vmcalls::CALL_FAILED.to_le_bytes()
+    );
+
+    // 5) Calling back an `Account` of the calls chain isn't allowed
+    let reenter = forward_calldata(&accounts[0], "echo", b"Hi");
+    let calldata = forward_calldata(&accounts[1], "forward", &reenter);
+    let receipt = call(&mut runtime, &calldata);
+    assert!(receipt.success);
+    assert_eq!(
+        receipt.returndata.unwrap(),
+        [
+            &4i32.to_le_bytes()[..],
+            &svm_runtime::vmcalls::CALL_REENTRANT.to_le_bytes()
+        ]
+        .concat()
+    );
+
+    // 6) A calls chain of up to `MAX_CALL_DEPTH` `Account`s is allowed
+    let chain = |len: usize| -> Vec<u8> {
+        let last = forward_calldata(&accounts[len - 1], "echo", b"Hi");
+
+        accounts[1..len - 1]
+            .iter()
+            .rev()
+            .fold(last, |calldata, account| {
+                forward_calldata(account, "forward", &calldata)
+            })
+    };
+
+    let receipt = call(&mut runtime, &chain(svm_runtime::MAX_CALL_DEPTH));
+    assert!(receipt.success);
+    assert!(receipt.returndata.unwrap().ends_with(b"Hi"));
+    assert_eq!(receipt.logs, vec![ReceiptLog::new(b"Hi".to_vec())]);
+
+    let receipt = call(&mut runtime, &chain(svm_runtime::MAX_CALL_DEPTH + 1));
+    assert!(receipt.success);
+    assert!(receipt
+        .returndata
+        .unwrap()
+        .ends_with(&svm_runtime::vmcalls::CALL_TOO_DEEP.to_le_bytes()));
+    assert!(receipt.logs.is_empty());
+}
+
+#[test]
+fn memory_runtime_cross_account_call_cant_credit_callers() {
+    use std::rc::Rc;
+
+    let mut runtime = testing::create_memory_runtime();
+    runtime.register_addr_scheme(1, Rc::new(NamedScheme));
+    runtime.set_addr_scheme(1);
+
+    // 1) `Deploy Template`
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        FixedLayout::default(),
+        &["ctor".to_string()],
+        include_str!("wasm/runtime_cross_call.wast").into(),
+    );
+    let receipt = runtime.deploy(&Envelope::default(), &message, &Context::default());
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn` the calling `Account`, another one, and the called one (endowed with coins)
+    let mut accounts = Vec::new();
+
+    for (name, amount) in [("Caller", 0), ("Other", 0), ("Callee", 100)].iter() {
+        let envelope = Envelope::new(Address::repeat(0x10), *amount, Gas::new(), 0);
+        let message = testing::build_spawn(&template_addr, name, "ctor", &[]);
+        let receipt = runtime.spawn(&envelope, &message, &Context::default());
+        assert!(receipt.success);
+
+        accounts.push((receipt.account_addr().clone(), receipt.init_state().clone()));
+    }
+
+    let (caller, other, (callee, state)) = (&accounts[0].0, &accounts[1].0, &accounts[2]);
+
+    let transfer = |dst: &Address, amount: u64| -> Vec<u8> {
+        let mut calldata = dst.as_slice().to_vec();
+        calldata.extend_from_slice(&amount.to_le_bytes());
+
+        let calldata = forward_calldata(callee, "transfer", &calldata);
+        testing::build_call(caller, "forward", &calldata)
+    };
+
+    // 3) The called `Account` can't transfer coins to its caller (the called function fails)
+    let envelope = Envelope::default();
+    let context = Context::with_state(state.clone());
+    let receipt = runtime.call(&envelope, &transfer(caller, 10), &context);
+    assert!(receipt.success);
+    assert_eq!(
+        receipt.returndata.unwrap(),
+        svm_runtime::vmcalls::CALL_FAILED.to_le_bytes()
+    );
+
+    // 4) ...but it can transfer coins to any other `Account`
+    let receipt = runtime.call(&envelope, &transfer(other, 10), &context);
+    assert!(receipt.success);
+    assert_eq!(receipt.returndata.unwrap(), 0i32.to_le_bytes());
+
+    let state = receipt.new_state.unwrap();
+    let storage = runtime.open_storage(callee, &state, &FixedLayout::default());
+    assert_eq!(storage.balance(), 90);
+    assert_eq!(storage.balance_of(other), 10);
+    assert_eq!(storage.balance_of(caller), 0);
+}
+
+#[test]
+fn memory_runtime_cross_account_call_stages_changes() {
+    use std::convert::TryInto;
+    use std::rc::Rc;
+
+    use svm_types::TransactionId;
+
+    let mut runtime = testing::create_memory_runtime();
+    runtime.register_addr_scheme(1, Rc::new(NamedScheme));
+    runtime.set_addr_scheme(1);
+
+    let envelope = Envelope::default();
+    let layout: FixedLayout = vec![4].into();
+
+    // 1) `Deploy Template` (having a single 4 bytes variable)
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        layout.clone(),
+        &["ctor".to_string()],
+        include_str!("wasm/runtime_cross_call.wast").into(),
+    );
+    let context = Context::new(TransactionId::repeat(1), Layer(1), State::zeros());
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn` the calling `Account` and two called ones
+    let mut accounts = Vec::new();
+    let mut state = State::zeros();
+
+    for name in ["Caller", "Callee #1", "Callee #2"].iter() {
+        let message = testing::build_spawn(&template_addr, name, "ctor", &[]);
+        let context = Context::new(TransactionId::repeat(2), Layer(2), state);
+        let receipt = runtime.spawn(&envelope, &message, &context);
+        assert!(receipt.success);
+
+        accounts.push(receipt.account_addr().clone());
+        state = receipt.init_state().clone();
+    }
+
+    let (caller, callee, callee2) = (&accounts[0], &accounts[1], &accounts[2]);
+
+    let counter = |storage: svm_storage::account::AccountStorage| {
+        let bytes = storage.read_var(Id(0)).unwrap();
+
+        u32::from_le_bytes(bytes[..].try_into().unwrap())
+    };
+
+    // Any commit moves the head of the (shared) key-value store backing the storages
+    let probe = runtime.open_storage(callee, &state, &layout);
+
+    // 3) A call not committing its changes doesn't commit the changes of the `Account`s it calls
+    let increment = forward_calldata(callee, "increment", &[]);
+    let message = testing::build_call(caller, "forward", &increment);
+    let receipt = runtime.call_at(&envelope, &message, &state).unwrap();
+    assert!(receipt.success);
+    assert_eq!(receipt.new_state(), &state);
+    assert_eq!(receipt.returndata.unwrap(), 0i32.to_le_bytes());
+    assert_eq!(probe.head(), state);
+
+    // 4) Neither does a failing call (failing after the called function has succeeded)
+    let message = testing::build_call(caller, "forward_and_fail", &increment);
+    let context = Context::new(TransactionId::repeat(3), Layer(3), state.clone());
+    let receipt = Runtime::call(&mut runtime, &envelope, &message, &context);
+    assert!(!receipt.success);
+    assert_eq!(probe.head(), state);
+
+    // 5) The changes of a failing called function are discarded, along with the changes of the functions it has called
+    let calldata = forward_calldata(
+        callee,
+        "forward_and_fail",
+        &forward_calldata(callee2, "increment", &[]),
+    );
+    let message = testing::build_call(caller, "forward", &calldata);
+    let receipt = Runtime::call(&mut runtime, &envelope, &message, &context);
+    assert!(receipt.success);
+    assert_eq!(
+        receipt.returndata.as_ref().unwrap(),
+        &svm_runtime::vmcalls::CALL_FAILED.to_le_bytes()
+    );
+
+    let state = receipt.new_state().clone();
+    assert_eq!(counter(runtime.open_storage(callee, &state, &layout)), 0);
+    assert_eq!(counter(runtime.open_storage(callee2, &state, &layout)), 0);
+
+    // 6) Once the outermost call commits, the changes of the whole calls chain are committed along with it
+    let calldata = forward_calldata(
+        callee,
+        "forward",
+        &forward_calldata(callee2, "increment", &[]),
+    );
+    let message = testing::build_call(caller, "forward", &calldata);
+    let context = Context::new(TransactionId::repeat(4), Layer(4), state.clone());
+    let receipt = Runtime::call(&mut runtime, &envelope, &message, &context);
+    assert!(receipt.success);
+
+    let state = receipt.new_state().clone();
+    assert_eq!(counter(runtime.open_storage(callee, &state, &layout)), 0);
+    assert_eq!(counter(runtime.open_storage(callee2, &state, &layout)), 1);
+
+    let message = testing::build_call(caller, "forward", &increment);
+    let context = Context::new(TransactionId::repeat(5), Layer(5), state.clone());
+    let receipt = Runtime::call(&mut runtime, &envelope, &message, &context);
+    assert!(receipt.success);
+
+    let state = receipt.new_state().clone();
+    assert_eq!(counter(runtime.open_storage(callee, &state, &layout)), 1);
+    assert_eq!(counter(runtime.open_storage(callee2, &state, &layout)), 1);
+}
+
+#[test]
+fn memory_runtime_transfer() {
+    use std::rc::Rc;
+
+    // The `Account`s spawned off the same `Template` are addressed by their names
+    let mut runtime = testing::create_memory_runtime();
+    runtime.register_addr_scheme(1, Rc::new(NamedScheme));
+    runtime.set_addr_scheme(1);
+
+    // 1) `Deploy Template`
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        FixedLayout::default(),
+        &["ctor".to_string()],
+        include_str!("wasm/runtime_transfer.wast").into(),
+    );
+    let receipt = runtime.deploy(&Envelope::default(), &message, &Context::default());
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account` (the transfers destination)
+    let message = testing::build_spawn(&template_addr, "Destination", "ctor", &[]);
+    let receipt = runtime.spawn(&Envelope::default(), &message, &Context::default());
+    assert!(receipt.success);
+
+    let dst = receipt.account_addr().clone();
+
+    // 3) `Spawn Account` (endowed with the `amount` of the `Envelope`)
+    let envelope = Envelope::new(Address::repeat(0x10), 100, Gas::new(), 0);
+    let message = testing::build_spawn(&template_addr, "My Account", "ctor", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &Context::default());
+    assert!(receipt.success);
+
+    let account = receipt.account_addr().clone();
+    let state = receipt.init_state().clone();
+
+    let layout = FixedLayout::default();
+    let storage = runtime.open_storage(&account, &state, &layout);
+    assert_eq!(storage.balance(), 100);
+
+    // 4) Transferring coins (the transfer is logged)
+    let transfer_to = |dst: &Address, amount: u64| -> Vec<u8> {
+        let mut calldata = dst.as_slice().to_vec();
+        calldata.extend_from_slice(&amount.to_le_bytes());
+
+        testing::build_call(&account, "transfer", &calldata)
+    };
+    let transfer = |amount: u64| transfer_to(&dst, amount);
+
+    let dst_root = runtime.current_state_root(&dst).unwrap();
+
+    let envelope = Envelope::default();
+    let receipt = runtime.call(&envelope, &transfer(30), &Context::with_state(state));
+    assert!(receipt.success);
+
+    // The storage root of the destination commits to its balance
+    assert_ne!(runtime.current_state_root(&dst).unwrap(), dst_root);
+
+    let log = [account.as_slice(), dst.as_slice(), &30u64.to_be_bytes()].concat();
+    assert_eq!(receipt.logs, vec![ReceiptLog::new(log)]);
+
+    let state = receipt.new_state.unwrap();
+    let storage = runtime.open_storage(&account, &state, &layout);
+    assert_eq!(storage.balance(), 70);
+    assert_eq!(storage.balance_of(&dst), 30);
+
+    // 5) Transferring more than the balance fails the transaction
+    let receipt = runtime.call(
+        &envelope,
+        &transfer(71),
+        &Context::with_state(state.clone()),
+    );
+    assert!(!receipt.success);
+    assert_eq!(
+        receipt.error.unwrap(),
+        RuntimeError::InsufficientBalance {
+            target: account.clone(),
+            balance: 70,
+            amount: 71,
+        }
+    );
+    assert!(receipt.logs.is_empty());
+
+    let storage = runtime.open_storage(&account, &state, &layout);
+    assert_eq!(storage.balance(), 70);
+    assert_eq!(storage.balance_of(&dst), 30);
+
+    // 6) Transferring to an `Address` no `Account` resides at fails the transaction
+    let unknown = Address::repeat(0xEE);
+    let receipt = runtime.call(
+        &envelope,
+        &transfer_to(&unknown, 10),
+        &Context::with_state(state.clone()),
+    );
+    assert_eq!(
+        receipt.error.unwrap(),
+        RuntimeError::AccountNotFound(unknown.clone())
+    );
+
+    let storage = runtime.open_storage(&account, &state, &layout);
+    assert_eq!(storage.balance(), 70);
+    assert_eq!(storage.balance_of(&unknown), 0);
+
+    // 7) The balances are reported along the `Account`s
+    let balances: Vec<_> = runtime
+        .iter_accounts()
+        .map(|info| (info.addr, info.balance))
+        .collect();
+    assert_eq!(balances.len(), 2);
+    assert!(balances.contains(&(account, 70)));
+    assert!(balances.contains(&(dst, 30)));
+}
+
+#[test]
+fn memory_runtime_commit_waits_for_account_readers() {
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    let mut runtime = testing::create_memory_runtime();
+
+    // 1) `Deploy Template`
+    let message = testing::build_deploy(
+        0,
+        "My Template",
+        vec![Address::len() as u32].into(),
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+    );
+    let envelope = Envelope::default();
+    let context = Context::default();
+
+    let receipt = runtime.deploy(&envelope, &message, &context);
+    let template_addr = receipt.addr.unwrap();
+
+    // 2) `Spawn Account`
+    let message = testing::build_spawn(&template_addr, "My Account", "initialize", &[]);
+    let receipt = runtime.spawn(&envelope, &message, &context);
+    assert!(receipt.success);
+
+    let spawned_addr = receipt.account_addr().clone();
+    let state = receipt.init_state().clone();
+
+    // 3) Querying releases the read lock of the `Account` once done
+    let query = testing::build_call(&spawned_addr, "load_addr", &[]);
+    let receipt = runtime.query_receipt(&envelope, &query, &Context::with_state(state.clone()));
+    assert!(receipt.success);
+    assert!(!runtime.account_locks().is_locked(&spawned_addr));
+
+    // 4) A concurrent reader (sharing the locks) holds the `Account` for a while
+    let (locked_tx, locked_rx) = mpsc::channel();
+    let reader = {
+        let locks = runtime.account_locks().clone();
+        let addr = spawned_addr.clone();
+
+        thread::spawn(move || {
+            let guard = locks.read(&addr);
+            locked_tx.send(()).unwrap();
+
+            thread::sleep(Duration::from_millis(200));
+            let released_at = Instant::now();
+            drop(guard);
+
+            released_at
+        })
+    };
+
+    locked_rx.recv().unwrap();
+
+    // 5) `Call Account` (its commit waits for the reader to release the `Account`)
+    let param = sdk::Address::repeat(0x10);
+    let mut calldata = svm_sdk::Vec::with_capacity(Address::len() + 1);
+    param.encode(&mut calldata);
+
+    let message = testing::build_call(&spawned_addr, "store_addr", &calldata);
+    let receipt = runtime.call(&envelope, &message, &Context::with_state(state));
+    let committed_at = Instant::now();
+    assert!(receipt.success);
+
+    let released_at = reader.join().unwrap();
+    assert!(committed_at >= released_at);
+    assert!(!runtime.account_locks().is_locked(&spawned_addr));
+}
+
+#[test]
+fn memory_runtime_spawn_bulk() {
+    use svm_runtime::BulkSpawnProgress;
+    use svm_types::TransactionId;
+
+    let deploy = testing::build_deploy(
+        0,
+        "My Template",
+        vec![Address::len() as u32].into(),
+        &["initialize".to_string()],
+        (&include_bytes!("wasm/runtime_calldata.wasm")[..]).into(),
+    );
+    let envelope = Envelope::default();
+    let context = Context::new(TransactionId::repeat(1), Layer(1), State::zeros());
+
+    let mut bulk_runtime = testing::create_memory_runtime();
+    let mut runtime = testing::create_memory_runtime();
+
+    let template_addr = bulk_runtime
+        .deploy(&envelope, &deploy, &context)
+        .addr
+        .unwrap();
+    assert_eq!(
+        runtime.deploy(&envelope, &deploy, &context).addr,
+        Some(template_addr.clone())
+    );
+
+    // 1) Spawning a batch (the last spawn asks for a non-`ctor` function)
+    let mut spawns: Vec<(Envelope, Vec<u8>)> = (0..5)
+        .map(|i| {
+            let name = format!("Account #{}", i);
+            let message = testing::build_spawn(&template_addr, &name, "initialize", &[]);
+
+            (envelope.clone(), message)
+        })
+        .collect();
+    spawns.push((
+        envelope.clone(),
+        testing::build_spawn(&template_addr, "Faulty", "store_addr", &[]),
+    ));
+
+    let context = Context::new(TransactionId::repeat(2), Layer(2), State::zeros());
+    let mut progress = Vec::new();
+    let receipts = bulk_runtime.spawn_bulk(&spawns, &context, &mut |p| progress.push(p));
+
+    assert_eq!(receipts.len(), 6);
+    assert!(receipts[..5].iter().all(|receipt| receipt.success));
+    assert!(!receipts[5].success);
+
+    // 2) The progress is reported once per spawn
+    assert_eq!(progress.len(), 6);
+    assert!(progress.last().unwrap().is_complete());
+    assert_eq!(
+        progress.iter().map(|p| p.done).collect::<Vec<_>>(),
+        vec![1, 2, 3, 4, 5, 6]
+    );
+    assert!(progress.iter().all(|p| p.total == 6));
+    assert_eq!(progress[0], BulkSpawnProgress { done: 1, total: 6 });
+
+    // 3) ...and the outcome is the same as spawning the `Account`s one by one
+    for ((envelope, message), bulk_receipt) in spawns.iter().zip(receipts.iter()) {
+        let receipt = runtime.spawn(envelope, message, &context);
+
+        assert_eq!(&receipt, bulk_receipt);
+    }
+
+    assert_eq!(
+        bulk_runtime.state_root(Layer(2)),
+        runtime.state_root(Layer(2))
+    );
+    assert_eq!(
+        bulk_runtime.state_root(Layer(2)),
+        Some(receipts[4].init_state().clone())
+    );
+    assert_eq!(
+        bulk_runtime.iter_accounts().collect::<Vec<_>>(),
+        runtime.iter_accounts().collect::<Vec<_>>()
+    );
+
+    // 4) A bulk-spawned `Account` can be called
+    let account_addr = receipts[0].account_addr();
+    let param = sdk::Address::repeat(0x10);
+    let mut calldata = svm_sdk::Vec::with_capacity(Address::len() + 1);
+    param.encode(&mut calldata);
+
+    let message = testing::build_call(account_addr, "store_addr", &calldata);
+    let context = Context::new(
+        TransactionId::repeat(3),
+        Layer(3),
+        receipts[4].init_state().clone(),
+    );
+    let receipt = bulk_runtime.call(&envelope, &message, &context);
+    assert!(receipt.success);
+}
+
+#[test]
+fn test_runtime_deploy_spawn_call() {
+    use serde_json::json;
+    use svm_runtime::testing::TestRuntime;
+
+    let mut runtime = TestRuntime::new();
+
+    // 1) The layout and `ctors` are given by `wasm/runtime_calldata.json`
+    let template = runtime.deploy_file("tests/wasm/runtime_calldata.wasm");
+
+    let receipt = runtime.spawn(&template, "initialize", json!({"abi": [], "data": []}));
+    assert!(receipt.success);
+
+    let account = receipt.account_addr().clone();
+    assert_eq!(runtime.state(), receipt.init_state());
+
+    // 2) Calling `store_addr` and then `load_addr` (running against the latest `State`)
+    runtime.next_layer();
+
+    let addr = "1010101010101010101010101010101010101010";
+    let receipt = runtime.call(
+        &account,
+        "store_addr",
+        json!({"abi": ["address"], "data": [addr]}),
+    );
+    assert!(receipt.success);
+
+    let receipt = runtime.call(&account, "load_addr", json!({"abi": [], "data": []}));
+    assert!(receipt.success);
+
+    let bytes = receipt.returndata.unwrap();
+    let mut returndata = ReturnData::new(&bytes);
+
+    let loaded: sdk::Address = returndata.next_1();
+    assert_eq!(loaded.as_slice(), &[0x10; 20]);
+
+    // 3) Spawning a non-`ctor` fails
+    let receipt = runtime.spawn(&template, "load_addr", json!({"abi": [], "data": []}));
+    assert!(!receipt.success);
+
+    // 4) The following transactions see the changes of a post-`ctor` call
+    let spawn = json!({
+        "version": spawn::SPAWN_CALL_VERSION,
+        "template": hex::encode_upper(template.as_slice()),
+        "name": "With Call",
+        "ctor_name": "initialize",
+        "calldata": {"abi": [], "data": []},
+        "call": {
+            "func_name": "store_addr",
+            "calldata": {"abi": ["address"], "data": ["2020202020202020202020202020202020202020"]},
+        },
+    });
+    let receipt = runtime.spawn_json(&spawn.to_string());
+    assert!(receipt.success);
+    assert_eq!(runtime.state(), receipt.final_state());
+
+    let account = receipt.account_addr().clone();
+    let receipt = runtime.call(&account, "load_addr", json!({"abi": [], "data": []}));
+
+    let bytes = receipt.returndata.unwrap();
+    let mut returndata = ReturnData::new(&bytes);
+
+    let loaded: sdk::Address = returndata.next_1();
+    assert_eq!(loaded.as_slice(), &[0x20; 20]);
+}
+
+#[test]
+fn runtime_storage_corrupted() {
+    use serde_json::json;
+    use svm_runtime::testing::TestRuntime;
+    use svm_storage::kv::StatefulKV;
+
+    let mut runtime = TestRuntime::new();
+
+    let template = runtime.deploy_file("tests/wasm/runtime_calldata.wasm");
+    let receipt = runtime.spawn(&template, "initialize", json!({"abi": [], "data": []}));
+    let account = receipt.account_addr().clone();
+
+    let addr = "1010101010101010101010101010101010101010";
+    let receipt = runtime.call(
+        &account,
+        "store_addr",
+        json!({"abi": ["address"], "data": [addr]}),
+    );
+    assert!(receipt.success);
+
+    // 1) Flipping a bit of the (single) variable, persisted under the page of key `19`
+    let state = runtime.state().clone();
+    let layout: FixedLayout = vec![20].into();
+    let storage = runtime.runtime().open_storage(&account, &state, &layout);
+    let mut kv = storage.account_kv().clone();
+
+    let key = 19u32.to_be_bytes();
+    let mut page = kv.get(&key).unwrap();
+    let last = page.len() - 1;
+    page[last] ^= 0x01;
+    kv.set(&key, &page);
+    let state = kv.checkpoint();
+    kv.flush();
+
+    // 2) Reading the corrupted page fails the transaction
+    let envelope = Envelope::default();
+    let context = Context::with_state(state);
+    let message = testing::build_call(&account, "load_addr", &[]);
+    let receipt = runtime.runtime().call(&envelope, &message, &context);
+
+    assert!(!receipt.success);
+    assert!(matches!(
+        receipt.error.unwrap(),
+        RuntimeError::StorageCorrupted { target, .. } if target == account
+    ));
+    assert_eq!(runtime.runtime().metrics().storage_corruptions(), 1);
+    assert_eq!(runtime.runtime().metrics().internal_errors(), 0);
+
+    // 3) So does committing a variable into the corrupted page
+    let param: sdk::Address = sdk::Address::repeat(0x10);
+    let mut calldata = svm_sdk::Vec::with_capacity(Address::len() + 1);
+    param.encode(&mut calldata);
+
+    let message = testing::build_call(&account, "store_addr", &calldata);
+    let receipt = runtime.runtime().call(&envelope, &message, &context);
+
+    assert!(!receipt.success);
+    assert!(matches!(
+        receipt.error.unwrap(),
+        RuntimeError::StorageCorrupted { .. }
+    ));
+    assert_eq!(runtime.runtime().metrics().storage_corruptions(), 2);
+    assert_eq!(runtime.runtime().metrics().internal_errors(), 0);
+}
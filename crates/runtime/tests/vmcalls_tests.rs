@@ -1,9 +1,12 @@
 use wasmer::{imports, NativeFunc};
 
-use svm_layout::{FixedLayout, Id};
+use svm_layout::{DynamicLayout, FixedLayout, Id};
 use svm_runtime::testing::{self, WasmFile};
 use svm_runtime::{vmcalls, FuncEnv, ProtectedMode};
-use svm_types::{Address, Context, Envelope, ReceiptLog, TemplateAddr};
+use svm_types::{
+    Address, Context, Envelope, Gas, Layer, ReceiptLog, ReceiptMetric, State, TemplateAddr,
+    TransactionId, MAX_LOG_TOPICS, MAX_RECEIPT_METRICS,
+};
 
 /// Creates a new `Wasmer Store`
 pub fn wasmer_store() -> wasmer::Store {
@@ -65,7 +68,7 @@ macro_rules! assert_storage {
         let storage = borrow.storage();
 
         $(
-            let actual = storage.read_var(Id($var_id));
+            let actual = storage.read_var(Id($var_id)).unwrap();
             assert_eq!(actual, $expected);
          )*
     }};
@@ -155,6 +158,54 @@ fn vmcalls_get32_set32() {
     assert_storage!(func_env, 0 => [5, 0, 0, 0], 1 => [10, 0]);
 }
 
+#[test]
+fn vmcalls_get_bit_set_bit() {
+    let template_addr = TemplateAddr::repeat(0xAB);
+    let target_addr = Address::repeat(0xCD);
+
+    // `var #0` consumes 4 bytes, `var #1, #2, #3` are packed into a single byte
+    let layout: FixedLayout = vec![4, 0, 0, 0].into();
+
+    let store = wasmer_store();
+    let storage = testing::blank_storage(&target_addr, &layout);
+    let envelope = Envelope::default();
+    let context = Context::default();
+    let func_env = FuncEnv::new(
+        storage,
+        &envelope,
+        &context,
+        template_addr,
+        target_addr,
+        ProtectedMode::FullAccess,
+    );
+
+    let import_object = imports! {
+        "svm" => {
+            "svm_get_bit" => func!(store, func_env, vmcalls::get_bit),
+            "svm_set_bit" => func!(store, func_env, vmcalls::set_bit),
+        }
+    };
+
+    let instance = wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/get_bit_set_bit.wast").into(),
+    );
+
+    assert_vars32!(instance, 1 => 0, 2 => 0, 3 => 0);
+
+    let set: NativeFunc<(u32, u32)> = instance.exports.get_native_function("set").unwrap();
+    set.call(1, 1).unwrap();
+    set.call(3, 7).unwrap();
+
+    assert_vars32!(instance, 1 => 1, 2 => 0, 3 => 1);
+
+    set.call(1, 0).unwrap();
+
+    assert_vars32!(instance, 1 => 0, 2 => 0, 3 => 1);
+    assert_storage!(func_env, 1 => [0], 2 => [0], 3 => [1]);
+}
+
 #[test]
 fn vmcalls_get64_set64() {
     let template_addr = TemplateAddr::repeat(0xAB);
@@ -343,3 +394,557 @@ fn vmcalls_log() {
     let logs = func_env.borrow_mut().take_logs();
     assert_eq!(logs, vec![ReceiptLog::new(b"Hello World".to_vec(),)]);
 }
+
+#[test]
+fn vmcalls_context() {
+    let template_addr = TemplateAddr::repeat(0xAB);
+    let target_addr = Address::repeat(0xCD);
+    let layout = FixedLayout::default();
+
+    let store = wasmer_store();
+    let memory = wasmer_memory(&store);
+    let storage = testing::blank_storage(&target_addr, &layout);
+    let envelope = Envelope::new(Address::repeat(0x10), 0, Gas::new(), 0);
+    let context = Context::new(TransactionId::repeat(0x20), Layer(7), State::zeros());
+    let func_env = FuncEnv::new_with_memory(
+        memory.clone(),
+        storage,
+        &envelope,
+        &context,
+        template_addr,
+        target_addr,
+        ProtectedMode::AccessDenied,
+    );
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory.clone(),
+            "svm_layer" => func!(store, func_env, vmcalls::layer),
+            "svm_principal" => func!(store, func_env, vmcalls::principal),
+            "svm_tx_id" => func!(store, func_env, vmcalls::tx_id),
+        },
+    };
+
+    let instance = wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/context.wast").into(),
+    );
+
+    let layer: NativeFunc<(), u64> = instance.exports.get_native_function("layer").unwrap();
+    assert_eq!(layer.call().unwrap(), 7);
+
+    let principal: NativeFunc<u32> = instance.exports.get_native_function("principal").unwrap();
+    principal.call(0).unwrap();
+
+    let tx_id: NativeFunc<u32> = instance.exports.get_native_function("tx_id").unwrap();
+    tx_id.call(20).unwrap();
+
+    let view = memory.view::<u8>();
+    let bytes: Vec<u8> = view[0..52].iter().map(|cell| cell.get()).collect();
+
+    assert_eq!(&bytes[..20], Address::repeat(0x10).as_slice());
+    assert_eq!(&bytes[20..], TransactionId::repeat(0x20).as_slice());
+}
+
+#[test]
+fn vmcalls_log_event() {
+    let template_addr = TemplateAddr::repeat(0xAB);
+    let target_addr = Address::repeat(0xCD);
+    let layout = FixedLayout::default();
+
+    let store = wasmer_store();
+    let memory = wasmer_memory(&store);
+    let storage = testing::blank_storage(&target_addr, &layout);
+    let envelope = Envelope::default();
+    let context = Context::default();
+    let func_env = FuncEnv::new_with_memory(
+        memory.clone(),
+        storage,
+        &envelope,
+        &context,
+        template_addr,
+        target_addr,
+        ProtectedMode::AccessDenied,
+    );
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory.clone(),
+            "svm_log_event" => func!(store, func_env, vmcalls::log_event),
+        },
+    };
+
+    let instance = wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/log_event.wast").into(),
+    );
+
+    // The `data` at offset `0`, followed by the topics at offset `16`
+    let mut bytes = b"Transfer".to_vec();
+    bytes.resize(16, 0);
+    bytes.extend_from_slice(&[0xAA; 32]);
+    bytes.extend_from_slice(&[0xBB; 32]);
+
+    for (cell, byte) in memory.view::<u8>().iter().zip(bytes.iter()) {
+        cell.set(*byte);
+    }
+
+    let func: NativeFunc<u32> = instance.exports.get_native_function("emit").unwrap();
+    func.call(2).unwrap();
+
+    let logs = func_env.borrow_mut().take_logs();
+    assert_eq!(
+        logs,
+        vec![ReceiptLog::event(
+            7,
+            vec![[0xAA; 32], [0xBB; 32]],
+            b"Transfer".to_vec()
+        )]
+    );
+
+    // Too many topics traps
+    let res = func.call(MAX_LOG_TOPICS as u32 + 1);
+    assert!(res.is_err());
+
+    let logs = func_env.borrow_mut().take_logs();
+    assert!(logs.is_empty());
+}
+
+#[test]
+fn vmcalls_metric() {
+    let template_addr = TemplateAddr::repeat(0xAB);
+    let target_addr = Address::repeat(0xCD);
+    let layout = FixedLayout::default();
+
+    let store = wasmer_store();
+    let storage = testing::blank_storage(&target_addr, &layout);
+    let envelope = Envelope::default();
+    let context = Context::default();
+    let func_env = FuncEnv::new(
+        storage,
+        &envelope,
+        &context,
+        template_addr,
+        target_addr,
+        ProtectedMode::AccessDenied,
+    );
+
+    let import_object = imports! {
+        "svm" => {
+            "svm_metric" => func!(store, func_env, vmcalls::metric),
+        },
+    };
+
+    let instance = wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/metric.wast").into(),
+    );
+    let report: NativeFunc<(u32, u64)> = instance.exports.get_native_function("report").unwrap();
+
+    report.call(7, 70).unwrap();
+    report.call(3, 30).unwrap();
+    report.call(7, 71).unwrap();
+
+    let metrics = func_env.borrow_mut().take_metrics();
+    assert_eq!(
+        metrics,
+        vec![ReceiptMetric::new(7, 71), ReceiptMetric::new(3, 30)]
+    );
+
+    // Metrics beyond the cap are dropped (while the reported ones can still be overridden)
+    for id in 0..(MAX_RECEIPT_METRICS as u32 + 5) {
+        report.call(id, id as u64).unwrap();
+    }
+    report.call(0, 100).unwrap();
+
+    let metrics = func_env.borrow_mut().take_metrics();
+    assert_eq!(metrics.len(), MAX_RECEIPT_METRICS);
+    assert_eq!(metrics[0], ReceiptMetric::new(0, 100));
+    assert_eq!(
+        metrics.last(),
+        Some(&ReceiptMetric::new(
+            MAX_RECEIPT_METRICS as u32 - 1,
+            MAX_RECEIPT_METRICS as u64 - 1
+        ))
+    );
+}
+
+#[test]
+fn vmcalls_log_multiple() {
+    let template_addr = TemplateAddr::repeat(0xAB);
+    let target_addr = Address::repeat(0xCD);
+    let layout = FixedLayout::default();
+
+    let store = wasmer_store();
+    let memory = wasmer_memory(&store);
+    let storage = testing::blank_storage(&target_addr, &layout);
+    let envelope = Envelope::default();
+    let context = Context::default();
+    let func_env = FuncEnv::new_with_memory(
+        memory.clone(),
+        storage,
+        &envelope,
+        &context,
+        template_addr,
+        target_addr,
+        ProtectedMode::AccessDenied,
+    );
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory.clone(),
+            "svm_log" => func!(store, func_env, vmcalls::log),
+        },
+    };
+
+    let instance = wasmer_instantiate(&store, &import_object, include_str!("wasm/log.wast").into());
+    let func = instance.exports.get_function("sayHello").unwrap();
+
+    for (cell, byte) in memory.view::<u8>().iter().zip(b"Hello World") {
+        cell.set(*byte);
+    }
+    let _ = func.call(&[]).unwrap();
+
+    for (cell, byte) in memory.view::<u8>().iter().zip(b"Hello Again") {
+        cell.set(*byte);
+    }
+    let _ = func.call(&[]).unwrap();
+
+    assert_eq!(func_env.borrow().logs_count(), 2);
+
+    let logs = func_env.borrow_mut().take_logs();
+    assert_eq!(
+        logs,
+        vec![
+            ReceiptLog::new(b"Hello World".to_vec()),
+            ReceiptLog::new(b"Hello Again".to_vec())
+        ]
+    );
+
+    // The logs aren't held by the `Arena`
+    assert_eq!(func_env.borrow().arena().allocated(), 0);
+
+    let _ = func.call(&[]).unwrap();
+
+    let logs = func_env.borrow_mut().take_logs();
+    assert_eq!(logs, vec![ReceiptLog::new(b"Hello Again".to_vec())]);
+}
+
+#[test]
+fn func_env_nested_returndata_in_arena() {
+    let template_addr = TemplateAddr::repeat(0xAB);
+    let target_addr = Address::repeat(0xCD);
+    let layout = FixedLayout::default();
+
+    let storage = testing::blank_storage(&target_addr, &layout);
+    let func_env = FuncEnv::new(
+        storage,
+        &Envelope::default(),
+        &Context::default(),
+        template_addr,
+        target_addr,
+        ProtectedMode::AccessDenied,
+    );
+
+    let mut borrow = func_env.borrow_mut();
+    assert_eq!(borrow.nested_returndata(), &[] as &[u8]);
+
+    borrow.set_nested_returndata(&[10, 20, 30]);
+    assert_eq!(borrow.nested_returndata(), &[10, 20, 30]);
+    assert_eq!(borrow.arena().allocated(), 3);
+
+    // Releasing the `Arena` buffers releases the `returndata` as well
+    borrow.reset_arena();
+    assert_eq!(borrow.nested_returndata(), &[] as &[u8]);
+    assert_eq!(borrow.arena().allocated(), 0);
+}
+
+#[test]
+fn vmcalls_trace() {
+    use svm_runtime::trace::{self, TraceEntry, VmCall};
+
+    let template_addr = TemplateAddr::repeat(0xAB);
+    let target_addr = Address::repeat(0xCD);
+    let layout: FixedLayout = vec![4, 2].into();
+
+    let store = wasmer_store();
+    let storage = testing::blank_storage(&target_addr, &layout);
+    let envelope = Envelope::default();
+    let context = Context::default();
+    let func_env = FuncEnv::new(
+        storage,
+        &envelope,
+        &context,
+        template_addr,
+        target_addr,
+        ProtectedMode::FullAccess,
+    );
+    func_env.enable_trace(1024);
+
+    let import_object = imports! {
+        "svm" => {
+            "svm_get64" => func!(store, func_env, vmcalls::get64),
+            "svm_set64" => func!(store, func_env, vmcalls::set64),
+        },
+    };
+
+    let instance = wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/get64_set64.wast").into(),
+    );
+
+    var_add64!(instance, 1, 10); // adding 10 to var #1
+
+    let trace = func_env.borrow_mut().take_trace().unwrap();
+    assert!(!trace.is_truncated());
+
+    let expected = vec![
+        TraceEntry {
+            vmcall: VmCall::Get64,
+            args: vec![1],
+            result: Some(0),
+        },
+        TraceEntry {
+            vmcall: VmCall::Set64,
+            args: vec![1, 10],
+            result: None,
+        },
+    ];
+    assert_eq!(trace.entries(), expected);
+    assert_eq!(trace::decode(trace.as_bytes()), Some(expected));
+    assert_eq!(trace.entries()[1].to_string(), "svm_set64(1, 10)");
+
+    // A `Trace` with room for a single `svm_get64` only
+    func_env.enable_trace(19);
+    var_add64!(instance, 1, 10);
+
+    let trace = func_env.borrow_mut().take_trace().unwrap();
+    assert!(trace.is_truncated());
+    assert_eq!(trace.entries().len(), 1);
+}
+
+#[test]
+fn vmcalls_storage_read_many_write_many() {
+    let template_addr = TemplateAddr::repeat(0xAB);
+    let target_addr = Address::repeat(0xCD);
+    let layout: FixedLayout = vec![4, 8, 20].into();
+
+    let store = wasmer_store();
+    let memory = wasmer_memory(&store);
+    let storage = testing::blank_storage(&target_addr, &layout);
+    let envelope = Envelope::default();
+    let context = Context::default();
+    let func_env = FuncEnv::new_with_memory(
+        memory.clone(),
+        storage,
+        &envelope,
+        &context,
+        template_addr,
+        target_addr.clone(),
+        ProtectedMode::FullAccess,
+    );
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory.clone(),
+            "svm_storage_read_many" => func!(store, func_env, vmcalls::storage_read_many),
+            "svm_storage_write_many" => func!(store, func_env, vmcalls::storage_write_many),
+        },
+    };
+
+    let instance = wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/storage_read_write_many.wast").into(),
+    );
+
+    let write_memory = |ptr: usize, bytes: &[u8]| {
+        for (cell, byte) in memory.view::<u8>()[ptr..].iter().zip(bytes) {
+            cell.set(*byte);
+        }
+    };
+
+    // The variables ids (32-bit Little-Endian integers) followed by their new data
+    let ids_ptr = 0;
+    let in_ptr = 100;
+    let out_ptr = 200;
+
+    write_memory(ids_ptr, &[2, 0, 0, 0, 0, 0, 0, 0]);
+
+    let mut data = target_addr.as_slice().to_vec();
+    data.extend_from_slice(&[0x10, 0x20, 0x30, 0x40]);
+    write_memory(in_ptr, &data);
+
+    let func: NativeFunc<(u32, u32, u32)> =
+        instance.exports.get_native_function("write_many").unwrap();
+    func.call(ids_ptr as u32, 2, in_ptr as u32)
+        .expect("function has failed");
+
+    assert_storage!(func_env, 0 => vec![0x10, 0x20, 0x30, 0x40], 1 => vec![0; 8], 2 => target_addr.as_slice());
+
+    // Reading the variables back (in a different order)
+    write_memory(ids_ptr, &[0, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0]);
+
+    let func: NativeFunc<(u32, u32, u32), u32> =
+        instance.exports.get_native_function("read_many").unwrap();
+    let nbytes = func
+        .call(ids_ptr as u32, 3, out_ptr as u32)
+        .expect("function has failed");
+
+    assert_eq!(nbytes, 4 + 8 + 20);
+
+    let view = &memory.view::<u8>()[out_ptr..out_ptr + nbytes as usize];
+    let bytes: Vec<u8> = view.iter().map(|cell| cell.get()).collect();
+
+    let mut expected = vec![0x10, 0x20, 0x30, 0x40];
+    expected.extend_from_slice(&[0; 8]);
+    expected.extend_from_slice(target_addr.as_slice());
+
+    assert_eq!(bytes, expected);
+}
+
+#[test]
+fn vmcalls_store_dyn_load_dyn() {
+    let template_addr = TemplateAddr::repeat(0xAB);
+    let target_addr = Address::repeat(0xCD);
+    let layout: FixedLayout = vec![4, 4].into();
+    let dynamic = DynamicLayout::from([1].as_slice());
+
+    let store = wasmer_store();
+    let memory = wasmer_memory(&store);
+    let storage = testing::blank_storage(&target_addr, &layout).with_dynamic_layout(dynamic);
+    let envelope = Envelope::default();
+    let context = Context::default();
+    let func_env = FuncEnv::new_with_memory(
+        memory.clone(),
+        storage,
+        &envelope,
+        &context,
+        template_addr,
+        target_addr,
+        ProtectedMode::FullAccess,
+    );
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory.clone(),
+            "svm_store_dyn" => func!(store, func_env, vmcalls::store_dyn),
+            "svm_load_dyn" => func!(store, func_env, vmcalls::load_dyn),
+        },
+    };
+
+    let instance = wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/store_dyn_load_dyn.wast").into(),
+    );
+
+    let write_memory = |ptr: usize, bytes: &[u8]| {
+        for (cell, byte) in memory.view::<u8>()[ptr..].iter().zip(bytes) {
+            cell.set(*byte);
+        }
+    };
+
+    let read_memory = |ptr: usize, len: usize| -> Vec<u8> {
+        let view = &memory.view::<u8>()[ptr..ptr + len];
+
+        view.iter().map(|cell| cell.get()).collect()
+    };
+
+    let key_ptr = 0;
+    let value_ptr = 100;
+    let out_ptr = 200;
+
+    write_memory(key_ptr as usize, b"alice");
+    write_memory(value_ptr as usize, &[0x10, 0x20, 0x30]);
+
+    let store_fn: NativeFunc<(u32, u32, u32, u32, u32)> =
+        instance.exports.get_native_function("store").unwrap();
+    let load_fn: NativeFunc<(u32, u32, u32, u32, u32), u32> =
+        instance.exports.get_native_function("load").unwrap();
+
+    // A missing entry
+    let nbytes = load_fn.call(1, key_ptr, 5, out_ptr, 3).unwrap();
+    assert_eq!(nbytes, 0);
+
+    store_fn.call(1, key_ptr, 5, value_ptr, 3).unwrap();
+
+    let nbytes = load_fn.call(1, key_ptr, 5, out_ptr, 3).unwrap();
+    assert_eq!(nbytes, 3);
+    assert_eq!(read_memory(out_ptr as usize, 3), vec![0x10, 0x20, 0x30]);
+
+    // Copying no more than the given length
+    write_memory(out_ptr as usize, &[0; 3]);
+
+    let nbytes = load_fn.call(1, key_ptr, 5, out_ptr, 2).unwrap();
+    assert_eq!(nbytes, 3);
+    assert_eq!(read_memory(out_ptr as usize, 3), vec![0x10, 0x20, 0]);
+
+    {
+        let borrow = func_env.borrow();
+        let entry = borrow.storage().read_dyn(Id(1), b"alice");
+
+        assert_eq!(entry, Some(vec![0x10, 0x20, 0x30]));
+    }
+
+    // An empty value removes the entry
+    store_fn.call(1, key_ptr, 5, value_ptr, 0).unwrap();
+
+    let nbytes = load_fn.call(1, key_ptr, 5, out_ptr, 3).unwrap();
+    assert_eq!(nbytes, 0);
+
+    // Variable `#0` isn't a dynamic one
+    let err = store_fn.call(0, key_ptr, 5, value_ptr, 3).unwrap_err();
+    assert_eq!(err.message(), "Variable #0 isn't a dynamic one");
+
+    let err = load_fn.call(0, key_ptr, 5, out_ptr, 3).unwrap_err();
+    assert_eq!(err.message(), "Variable #0 isn't a dynamic one");
+}
+
+#[test]
+fn vmcalls_layout_len_layout_count() {
+    let template_addr = TemplateAddr::repeat(0xAB);
+    let target_addr = Address::repeat(0xCD);
+    let layout: FixedLayout = vec![4, 8, 20].into();
+
+    let store = wasmer_store();
+    let memory = wasmer_memory(&store);
+    let storage = testing::blank_storage(&target_addr, &layout);
+    let envelope = Envelope::default();
+    let context = Context::default();
+    let func_env = FuncEnv::new_with_memory(
+        memory.clone(),
+        storage,
+        &envelope,
+        &context,
+        template_addr,
+        target_addr,
+        ProtectedMode::FullAccess,
+    );
+
+    let import_object = imports! {
+        "svm" => {
+            "memory" => memory,
+            "svm_layout_len" => func!(store, func_env, vmcalls::layout_len),
+            "svm_layout_count" => func!(store, func_env, vmcalls::layout_count),
+        },
+    };
+
+    let instance = wasmer_instantiate(
+        &store,
+        &import_object,
+        include_str!("wasm/layout.wast").into(),
+    );
+
+    let count: NativeFunc<(), u32> = instance.exports.get_native_function("count").unwrap();
+    assert_eq!(count.call().unwrap(), 3);
+
+    let len: NativeFunc<u32, u32> = instance.exports.get_native_function("len").unwrap();
+    assert_eq!(len.call(0).unwrap(), 4);
+    assert_eq!(len.call(1).unwrap(), 8);
+    assert_eq!(len.call(2).unwrap(), 20);
+}
@@ -0,0 +1,17 @@
+(module
+  (func $layer (import "svm" "svm_layer") (result i64))
+  (func $principal (import "svm" "svm_principal") (param $ptr i32))
+  (func $tx_id (import "svm" "svm_tx_id") (param $ptr i32))
+
+  (import "svm" "memory" (memory $mem 0))
+
+  (func (export "layer") (result i64)
+    call $layer)
+
+  (func (export "principal") (param $ptr i32)
+    local.get $ptr
+    call $principal)
+
+  (func (export "tx_id") (param $ptr i32)
+    local.get $ptr
+    call $tx_id))
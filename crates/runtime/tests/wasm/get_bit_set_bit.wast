@@ -0,0 +1,13 @@
+(module
+  ;; import `SVM` vmcalls
+  (func $get_bit (import "svm" "svm_get_bit") (param i32) (result i32))
+  (func $set_bit (import "svm" "svm_set_bit") (param i32 i32))
+
+  (func (export "get") (param $var_id i32) (result i32)
+      get_local $var_id
+      call $get_bit)
+
+  (func (export "set") (param $var_id i32) (param $value i32)
+      get_local $var_id
+      get_local $value
+      call $set_bit))
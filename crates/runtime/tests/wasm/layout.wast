@@ -0,0 +1,13 @@
+(module
+  (func $layout_len (import "svm" "svm_layout_len") (param $var_id i32) (result i32))
+  (func $layout_count (import "svm" "svm_layout_count") (result i32))
+
+  (func (export "ctor")
+  	nop)
+
+  (func (export "len") (param $var_id i32) (result i32)
+  	get_local $var_id
+	call $layout_len)
+
+  (func (export "count") (result i32)
+	call $layout_count))
@@ -0,0 +1,13 @@
+(module
+  (func $log_event (import "svm" "svm_log_event")
+    (param $code i32) (param $topics_ptr i32) (param $topics_count i32) (param $offset i32) (param $length i32))
+
+  (import "svm" "memory" (memory $mem 0))
+
+  (func (export "emit") (param $topics_count i32)
+    i32.const 7             ;; event `code`
+    i32.const 16            ;; `topics` offset
+    local.get $topics_count ;; #topics
+    i32.const 0             ;; `data` offset
+    i32.const 8             ;; `data` length = len('Transfer')
+    call $log_event))
@@ -0,0 +1,7 @@
+(module
+  (func $metric (import "svm" "svm_metric") (param $id i32) (param $value i64))
+
+  (func (export "report") (param $id i32) (param $value i64)
+    local.get $id
+    local.get $value
+    call $metric))
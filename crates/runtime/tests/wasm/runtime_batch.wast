@@ -0,0 +1,37 @@
+(module
+  (func $get64 (import "svm" "svm_get64") (param i32) (result i64))
+  (func $set64 (import "svm" "svm_set64") (param i32 i64))
+  (func $set_returndata (import "svm" "svm_set_returndata") (param i32 i32))
+
+  (memory (;0;) 1)
+  (export "memory" (memory 0))
+
+  (func (export "svm_alloc") (param i32) (result i32)
+    i32.const 1024)
+
+  (func (export "svm_verify") (result i32)
+    i32.const 0)
+
+  (func (export "ctor"))
+
+  ;; Increments var #0
+  (func (export "inc")
+    i32.const 0
+    i32.const 0
+    call $get64
+    i64.const 1
+    i64.add
+    call $set64)
+
+  ;; Returns var #0 (8 bytes, little-endian)
+  (func (export "get")
+    i32.const 0
+    i32.const 0
+    call $get64
+    i64.store
+    i32.const 0
+    i32.const 8
+    call $set_returndata)
+
+  (func (export "fail")
+    unreachable))
@@ -0,0 +1,127 @@
+(module
+  (func $calldata_offset (import "svm" "svm_calldata_offset") (result i32))
+  (func $calldata_len (import "svm" "svm_calldata_len") (result i32))
+  (func $set_returndata (import "svm" "svm_set_returndata") (param i32 i32))
+  (func $log (import "svm" "svm_log") (param i32 i32))
+  (func $call (import "svm" "svm_call") (param i32 i32 i32 i32 i32 i64) (result i32))
+  (func $call_returndata (import "svm" "svm_call_returndata") (param i32))
+  (func $get32 (import "svm" "svm_get32") (param i32) (result i32))
+  (func $set32 (import "svm" "svm_set32") (param i32 i32))
+  (func $transfer (import "svm" "svm_transfer") (param i32 i64))
+
+  (memory (;0;) 1)
+  (export "memory" (memory 0))
+
+  (func (export "svm_alloc") (param i32) (result i32)
+    i32.const 1024)
+
+  (func (export "svm_verify") (result i32)
+    i32.const 0)
+
+  (func (export "ctor"))
+
+  ;; Logs the `calldata` and returns it as the `returndata`
+  (func (export "echo")
+    call $calldata_offset
+    call $calldata_len
+    call $log
+
+    call $calldata_offset
+    call $calldata_len
+    call $set_returndata)
+
+  ;; Calls another `Account`, given the `calldata`:
+  ;;
+  ;; +-----------+---------------+-----------------+-------------------+
+  ;; |  Target   |  Name Length  |  Function Name  |  Called Calldata  |
+  ;; | (20 bytes)|   (1 byte)    |     (UTF-8)     |                   |
+  ;; +-----------+---------------+-----------------+-------------------+
+  ;;
+  ;; Returns the `svm_call` status (4 bytes, Little-Endian) followed by the called function `returndata`
+  (func $forward (export "forward")
+    (local $offset i32)
+    (local $name_len i32)
+    (local $status i32)
+
+    call $calldata_offset
+    local.set $offset
+
+    local.get $offset
+    i32.load8_u offset=20
+    local.set $name_len
+
+    ;; target
+    local.get $offset
+
+    ;; function name
+    local.get $offset
+    i32.const 21
+    i32.add
+    local.get $name_len
+
+    ;; called calldata
+    local.get $offset
+    i32.const 21
+    i32.add
+    local.get $name_len
+    i32.add
+    call $calldata_len
+    i32.const 21
+    i32.sub
+    local.get $name_len
+    i32.sub
+
+    ;; all the gas left
+    i64.const 0
+
+    call $call
+    local.set $status
+
+    i32.const 4096
+    local.get $status
+    i32.store
+
+    (if (i32.gt_s (local.get $status) (i32.const 0))
+      (then (call $call_returndata (i32.const 4100))))
+
+    i32.const 4096
+    i32.const 4
+    local.get $status
+    i32.const 0
+    local.get $status
+    i32.const 0
+    i32.gt_s
+    select
+    i32.add
+    call $set_returndata)
+
+  ;; Same as `forward`, but fails right after the call (once the called function has succeeded)
+  (func (export "forward_and_fail")
+    call $forward
+    unreachable)
+
+  ;; Transfers coins, given the `calldata`:
+  ;;
+  ;; +-------------+-------------------------+
+  ;; | Destination |         Amount          |
+  ;; | (20 bytes)  | (8 bytes, Little-Endian)|
+  ;; +-------------+-------------------------+
+  (func (export "transfer")
+    (local $offset i32)
+
+    call $calldata_offset
+    local.set $offset
+
+    local.get $offset
+    local.get $offset
+    i64.load offset=20
+    call $transfer)
+
+  ;; Increments variable #0 (when the `Template` has a 4 bytes variable #0)
+  (func (export "increment")
+    i32.const 0
+    i32.const 0
+    call $get32
+    i32.const 1
+    i32.add
+    call $set32))
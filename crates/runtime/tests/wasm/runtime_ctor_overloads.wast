@@ -0,0 +1,24 @@
+(module
+  (func $set_returndata (import "svm" "svm_set_returndata") (param i32 i32))
+
+  (memory (;0;) 1)
+  (export "memory" (memory 0))
+
+  (data (i32.const 0) "u32")
+  (data (i32.const 16) "bool")
+
+  (func (export "svm_alloc") (param i32) (result i32)
+    i32.const 1024)
+
+  (func (export "svm_verify"))
+
+  ;; Each overload of the `ctor` returns the type of its param
+  (func (export "init_u32")
+    i32.const 0
+    i32.const 3
+    call $set_returndata)
+
+  (func (export "init_bool")
+    i32.const 16
+    i32.const 4
+    call $set_returndata))
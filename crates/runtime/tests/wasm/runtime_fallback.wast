@@ -0,0 +1,20 @@
+(module
+  (func $calldata_offset (import "svm" "svm_calldata_offset") (result i32))
+  (func $calldata_len (import "svm" "svm_calldata_len") (result i32))
+  (func $set_returndata (import "svm" "svm_set_returndata") (param i32 i32))
+
+  (memory (;0;) 1)
+  (export "memory" (memory 0))
+
+  (func (export "svm_alloc") (param i32) (result i32)
+    i32.const 1024)
+
+  (func (export "svm_verify"))
+
+  (func (export "ctor"))
+
+  ;; Returns the `calldata` as is
+  (func (export "fallback")
+    call $calldata_offset
+    call $calldata_len
+    call $set_returndata))
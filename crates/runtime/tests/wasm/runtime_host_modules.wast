@@ -0,0 +1,23 @@
+(module
+  (func $price (import "oracle" "price") (result i64))
+  (func $set_returndata (import "svm" "svm_set_returndata") (param i32 i32))
+
+  (memory (;0;) 1)
+  (export "memory" (memory 0))
+
+  (func (export "svm_alloc") (param i32) (result i32)
+    i32.const 1024)
+
+  (func (export "svm_verify"))
+
+  (func (export "ctor"))
+
+  ;; Returns the price given by the `oracle` host module (8 bytes, Little-Endian)
+  (func (export "get_price")
+    i32.const 0
+    call $price
+    i64.store
+
+    i32.const 0
+    i32.const 8
+    call $set_returndata))
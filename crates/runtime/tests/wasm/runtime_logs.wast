@@ -0,0 +1,20 @@
+(module
+  (func $log (import "svm" "svm_log") (param i32 i32))
+
+  (memory (;0;) 1)
+  (export "memory" (memory 0))
+
+  (data (i32.const 0) "Hello World")
+
+  (func (export "svm_alloc") (param i32) (result i32)
+    i32.const 1024)
+
+  (func (export "svm_verify") (result i32)
+    i32.const 0)
+
+  (func (export "ctor"))
+
+  (func (export "say_hello")
+    i32.const 0   ;; `data` offset
+    i32.const 11  ;; `data` length = len('Hello World')
+    call $log))
@@ -0,0 +1,51 @@
+(module
+  (func $set64 (import "svm" "svm_set64") (param i32 i64))
+  (func $get64 (import "svm" "svm_get64") (param i32) (result i64))
+
+  (memory (;0;) 1)
+  (export "memory" (memory 0))
+
+  (func (export "svm_alloc") (param i32) (result i32)
+    i32.const 0)
+
+  (func (export "svm_verify") (result i32)
+    i32.const 0)
+
+  ;; Sums `1 + 2 + ... + n` into var #0 (looping `n` times).
+  (func $sum (param $n i64)
+    (local $acc i64)
+
+    (block $done
+      (loop $next
+        get_local $n
+        i64.eqz
+        br_if $done
+
+        get_local $acc
+        get_local $n
+        i64.add
+        set_local $acc
+
+        get_local $n
+        i64.const 1
+        i64.sub
+        set_local $n
+
+        br $next))
+
+    i32.const 0  ;; var_id = 0
+    get_local $acc
+    call $set64)
+
+  (func (export "initialize")
+    i64.const 100
+    call $sum)
+
+  (func (export "initialize_heavy")
+    i64.const 1000000
+    call $sum)
+
+  (func (export "sum_again")
+    i32.const 0  ;; var_id = 0
+    call $get64
+    call $sum))
@@ -0,0 +1,33 @@
+(module
+  (func $metric (import "svm" "svm_metric") (param i32 i64))
+
+  (memory (;0;) 1)
+  (export "memory" (memory 0))
+
+  (func (export "svm_alloc") (param i32) (result i32)
+    i32.const 1024)
+
+  (func (export "svm_verify") (result i32)
+    i32.const 0)
+
+  (func (export "ctor")
+    i32.const 1   ;; metric `id`
+    i64.const 10  ;; metric `value`
+    call $metric)
+
+  (func (export "report")
+    i32.const 2
+    i64.const 20
+    call $metric
+
+    ;; Overrides the value of metric #1
+    i32.const 1
+    i64.const 11
+    call $metric)
+
+  (func (export "report_and_fail")
+    i32.const 3
+    i64.const 30
+    call $metric
+
+    unreachable))
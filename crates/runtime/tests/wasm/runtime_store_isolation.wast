@@ -0,0 +1,49 @@
+(module
+  (memory (;0;) 1)
+  (export "memory" (memory 0))
+
+  (global $poisoned (mut i32) (i32.const 0))
+
+  (func (export "svm_alloc") (param i32) (result i32)
+    i32.const 0)
+
+  (func (export "svm_verify") (result i32)
+    i32.const 0)
+
+  (func (export "initialize")
+    nop)
+
+  ;; Traps when any state left behind by a former execution is observed,
+  ;; and then leaves state behind (memory contents, memory size and a global).
+  (func (export "poison")
+    ;; The global is still at its initial value
+    global.get $poisoned
+    if
+      unreachable
+    end
+
+    ;; The memory hasn't grown
+    memory.size
+    i32.const 1
+    i32.ne
+    if
+      unreachable
+    end
+
+    ;; The memory is still zeroed
+    i32.const 1024
+    i32.load
+    if
+      unreachable
+    end
+
+    i32.const 1
+    global.set $poisoned
+
+    i32.const 1024
+    i32.const 0xDEAD
+    i32.store
+
+    i32.const 1
+    memory.grow
+    drop))
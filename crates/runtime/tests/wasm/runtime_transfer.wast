@@ -0,0 +1,31 @@
+(module
+  (func $calldata_offset (import "svm" "svm_calldata_offset") (result i32))
+  (func $transfer (import "svm" "svm_transfer") (param i32 i64))
+
+  (memory (;0;) 1)
+  (export "memory" (memory 0))
+
+  (func (export "svm_alloc") (param i32) (result i32)
+    i32.const 1024)
+
+  (func (export "svm_verify") (result i32)
+    i32.const 0)
+
+  (func (export "ctor"))
+
+  ;; Transfers coins, given the `calldata`:
+  ;;
+  ;; +-------------+------------------------+
+  ;; | Destination |         Amount         |
+  ;; | (20 bytes)  | (8 bytes, Little-Endian)|
+  ;; +-------------+------------------------+
+  (func (export "transfer")
+    (local $offset i32)
+
+    call $calldata_offset
+    local.set $offset
+
+    local.get $offset
+    local.get $offset
+    i64.load offset=20
+    call $transfer))
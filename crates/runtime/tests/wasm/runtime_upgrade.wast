@@ -0,0 +1,22 @@
+(module
+  (func $set_returndata (import "svm" "svm_set_returndata") (param i32 i32))
+
+  (memory (;0;) 1)
+  (export "memory" (memory 0))
+
+  ;; The version of the code (replaced by the tests upgrading the `Template`)
+  (data (i32.const 0) "\01")
+
+  (func (export "svm_alloc") (param i32) (result i32)
+    i32.const 1024)
+
+  (func (export "svm_verify") (result i32)
+    i32.const 0)
+
+  (func (export "ctor"))
+
+  ;; Returns the version of the code
+  (func (export "version")
+    i32.const 0
+    i32.const 1
+    call $set_returndata))
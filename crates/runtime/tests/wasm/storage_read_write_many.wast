@@ -0,0 +1,18 @@
+(module
+  (func $read_many (import "svm" "svm_storage_read_many") (param $ids_ptr i32) (param $count i32) (param $out_ptr i32) (result i32))
+  (func $write_many (import "svm" "svm_storage_write_many") (param $ids_ptr i32) (param $count i32) (param $in_ptr i32))
+
+  (func (export "ctor")
+  	nop)
+
+  (func (export "write_many") (param $ids_ptr i32) (param $count i32) (param $in_ptr i32)
+  	get_local $ids_ptr
+  	get_local $count
+  	get_local $in_ptr
+	call $write_many)
+
+  (func (export "read_many") (param $ids_ptr i32) (param $count i32) (param $out_ptr i32) (result i32)
+  	get_local $ids_ptr
+  	get_local $count
+  	get_local $out_ptr
+	call $read_many))
@@ -0,0 +1,22 @@
+(module
+  (func $store_dyn (import "svm" "svm_store_dyn") (param $var_id i32) (param $key_ptr i32) (param $key_len i32) (param $value_ptr i32) (param $value_len i32))
+  (func $load_dyn (import "svm" "svm_load_dyn") (param $var_id i32) (param $key_ptr i32) (param $key_len i32) (param $value_ptr i32) (param $value_len i32) (result i32))
+
+  (func (export "ctor")
+  	nop)
+
+  (func (export "store") (param $var_id i32) (param $key_ptr i32) (param $key_len i32) (param $value_ptr i32) (param $value_len i32)
+  	get_local $var_id
+  	get_local $key_ptr
+  	get_local $key_len
+  	get_local $value_ptr
+  	get_local $value_len
+	call $store_dyn)
+
+  (func (export "load") (param $var_id i32) (param $key_ptr i32) (param $key_len i32) (param $value_ptr i32) (param $value_len i32) (result i32)
+  	get_local $var_id
+  	get_local $key_ptr
+  	get_local $key_len
+  	get_local $value_ptr
+  	get_local $value_len
+	call $load_dyn))
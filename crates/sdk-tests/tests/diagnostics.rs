@@ -0,0 +1,28 @@
+use svm_sdk::host::MockHost;
+use svm_sdk::storage::MockStorage;
+
+use trybuild::TestCases;
+
+fn compile_fail(t: &TestCases, test: &'static str) {
+    MockHost::reset();
+    MockStorage::clear();
+
+    t.compile_fail(test);
+}
+
+/// Each test asserts an error reported at the offending item (and not at `#[template]`).
+#[test]
+fn diagnostics_tests() {
+    let t = TestCases::new();
+
+    compile_fail(&t, "tests/diagnostics/endpoint_async.rs");
+    compile_fail(&t, "tests/diagnostics/endpoint_reference_param.rs");
+    compile_fail(&t, "tests/diagnostics/endpoint_unknown_param_type.rs");
+    compile_fail(&t, "tests/diagnostics/fallback_with_self.rs");
+    compile_fail(&t, "tests/diagnostics/fundable_without_endpoint.rs");
+
+    compile_fail(&t, "tests/diagnostics/storage_default_for_array.rs");
+    compile_fail(&t, "tests/diagnostics/storage_default_out_of_range.rs");
+    compile_fail(&t, "tests/diagnostics/storage_field_with_attrs.rs");
+    compile_fail(&t, "tests/diagnostics/storage_invalid_array_length.rs");
+}
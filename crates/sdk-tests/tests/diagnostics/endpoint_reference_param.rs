@@ -0,0 +1,35 @@
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    use svm_sdk::{Address, Amount};
+
+    #[storage]
+    struct Storage {
+        owner: Address,
+        balance: Amount,
+        counter: u32,
+    }
+
+    #[ctor]
+    fn initialize(owner: Address) {
+        Storage::set_owner(&owner);
+    }
+
+    #[endpoint]
+    fn balance() -> Amount {
+        Storage::get_balance()
+    }
+
+    #[endpoint]
+    fn transfer(to: &Address, amount: Amount) {
+        Storage::set_counter(Storage::get_counter() + 1);
+    }
+
+    #[endpoint]
+    fn counter() -> u32 {
+        Storage::get_counter()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    use svm_sdk::{AbiDecode, AbiEncode, Amount};
+
+    #[derive(AbiEncode, AbiDecode)]
+    struct Transfer {
+        amount: Amount,
+    }
+
+    #[endpoint]
+    fn transfer(transfer: Transfr) -> Amount {
+        transfer.amount
+    }
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    use svm_sdk::{Address, Amount};
+
+    #[storage]
+    struct Storage {
+        owner: Address,
+        balance: Amount,
+        counter: u32,
+    }
+
+    #[ctor]
+    fn initialize() {}
+
+    #[endpoint]
+    fn counter() -> u32 {
+        Storage::get_counter()
+    }
+
+    #[fallback]
+    fn forward(&self, calldata: &[u8]) {}
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    use svm_sdk::{Address, Amount};
+
+    #[storage]
+    struct Storage {
+        owner: Address,
+        balance: Amount,
+        counter: u32,
+    }
+
+    #[ctor]
+    fn initialize() {}
+
+    #[endpoint]
+    fn counter() -> u32 {
+        Storage::get_counter()
+    }
+
+    #[fundable]
+    fn deposit() {
+        Storage::set_counter(Storage::get_counter() + 1);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    use svm_sdk::{Address, Amount};
+
+    #[storage]
+    struct Storage {
+        owner: Address,
+        balance: Amount,
+        counter: u32,
+
+        #[storage(default = 0)]
+        history: [u32; 4],
+    }
+
+    #[ctor]
+    fn initialize() {}
+
+    #[endpoint]
+    fn counter() -> u32 {
+        Storage::get_counter()
+    }
+}
+
+fn main() {}
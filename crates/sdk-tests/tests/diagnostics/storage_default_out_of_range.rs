@@ -0,0 +1,28 @@
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    use svm_sdk::{Address, Amount};
+
+    #[storage]
+    struct Storage {
+        owner: Address,
+        balance: Amount,
+        counter: u32,
+
+        #[storage(default = 300)]
+        level: u8,
+
+        active: bool,
+    }
+
+    #[ctor]
+    fn initialize() {}
+
+    #[endpoint]
+    fn level() -> u8 {
+        Storage::get_level()
+    }
+}
+
+fn main() {}
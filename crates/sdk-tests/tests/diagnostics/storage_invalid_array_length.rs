@@ -0,0 +1,24 @@
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    use svm_sdk::{Address, Amount};
+
+    #[storage]
+    struct Storage {
+        owner: Address,
+        balance: Amount,
+        counter: u32,
+        history: [u32; LENGTH],
+    }
+
+    #[ctor]
+    fn initialize() {}
+
+    #[endpoint]
+    fn counter() -> u32 {
+        Storage::get_counter()
+    }
+}
+
+fn main() {}
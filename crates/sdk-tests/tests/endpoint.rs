@@ -24,7 +24,13 @@ fn endpoint_tests() {
     pass(&t, "tests/endpoint/bool_params.rs");
     pass(&t, "tests/endpoint/amount_params.rs");
     pass(&t, "tests/endpoint/address_params.rs");
+    pass(&t, "tests/endpoint/string_params.rs");
     pass(&t, "tests/endpoint/integers_params.rs");
+    pass(&t, "tests/endpoint/struct_params.rs");
+    pass(&t, "tests/endpoint/nested_struct_params.rs");
+    pass(&t, "tests/endpoint/enum_params.rs");
+    pass(&t, "tests/endpoint/return_value.rs");
+    pass(&t, "tests/endpoint/debug_macros.rs");
 
     compile_fail(&t, "tests/endpoint/endpoint_used_twice_fails.rs");
     compile_fail(&t, "tests/endpoint/endpoint_and_ctor_fails.rs");
@@ -0,0 +1,37 @@
+use svm_sdk::{sdk_assert, sdk_debug, template};
+
+use svm_sdk_tests::call_1;
+
+#[template]
+mod Template {
+    #[endpoint]
+    fn small_double(a: u32) -> u32 {
+        sdk_assert!(a < 10, "`a` is too large");
+        sdk_assert!(a < 10);
+        sdk_debug!("doubling");
+
+        a * 2
+    }
+}
+
+fn runtime_check(a: u32) -> bool {
+    a < 10
+}
+
+// Since the `debug-template` feature is turned-off, the macros must expand to nothing
+// (otherwise, calling a non-`const fn` within a `const` wouldn't compile).
+const _: () = {
+    sdk_assert!(runtime_check(100), "`a` is too large");
+    sdk_assert!(runtime_check(100));
+    sdk_debug!("never emitted");
+};
+
+fn test_checks_are_stripped() {
+    let res: u32 = call_1(small_double, vec![100u32]);
+
+    assert_eq!(res, 200);
+}
+
+fn main() {
+    test_checks_are_stripped();
+}
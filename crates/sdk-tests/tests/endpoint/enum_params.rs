@@ -0,0 +1,83 @@
+use svm_sdk::{template, AbiDecode, AbiEncode, Amount};
+
+use svm_sdk_tests::call_1;
+
+#[template]
+mod Template {
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    enum Side {
+        Buy,
+        Sell,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    enum Code {
+        Ok = 1,
+        Moved = 301,
+        NotFound = 404,
+    }
+
+    #[derive(Debug, PartialEq, Clone, AbiEncode, AbiDecode)]
+    struct Order {
+        side: Side,
+        amount: Amount,
+    }
+
+    #[endpoint]
+    fn flip(side: Side) -> Side {
+        match side {
+            Side::Buy => Side::Sell,
+            Side::Sell => Side::Buy,
+        }
+    }
+
+    #[endpoint]
+    fn code(found: bool) -> Code {
+        if found {
+            Code::Ok
+        } else {
+            Code::NotFound
+        }
+    }
+
+    #[endpoint]
+    fn order_side(order: Order) -> Side {
+        order.side
+    }
+}
+
+fn test_flip() {
+    let res: Side = call_1(flip, vec![Side::Buy]);
+    assert_eq!(res, Side::Sell);
+
+    let res: Side = call_1(flip, vec![Side::Sell]);
+    assert_eq!(res, Side::Buy);
+}
+
+fn test_code() {
+    let res: Code = call_1(code, vec![true]);
+    assert_eq!(res, Code::Ok);
+
+    let res: Code = call_1(code, vec![false]);
+    assert_eq!(res, Code::NotFound);
+
+    // A `Code` is represented as a `u16` (since its values don't fit into a `u8`)
+    let res: u16 = call_1(code, vec![false]);
+    assert_eq!(res, 404);
+}
+
+fn test_order_side() {
+    let order = Order {
+        side: Side::Sell,
+        amount: Amount(10),
+    };
+
+    let res: Side = call_1(order_side, vec![order]);
+    assert_eq!(res, Side::Sell);
+}
+
+fn main() {
+    test_flip();
+    test_code();
+    test_order_side();
+}
@@ -0,0 +1,58 @@
+use svm_sdk::{template, AbiDecode, AbiEncode, Address, Amount};
+
+use svm_sdk_tests::call_1;
+
+#[template]
+mod Template {
+    #[derive(Debug, PartialEq, Clone, AbiEncode, AbiDecode)]
+    struct Transfer {
+        to: Address,
+        amount: Amount,
+    }
+
+    #[derive(Debug, PartialEq, Clone, AbiEncode, AbiDecode)]
+    struct Payment {
+        transfer: Transfer,
+        tags: [u8; 3],
+        memo: svm_sdk::Option<u32>,
+    }
+
+    #[endpoint]
+    fn identity(payment: Payment) -> Payment {
+        payment
+    }
+
+    #[endpoint]
+    fn amount(payment: Payment) -> Amount {
+        payment.transfer.amount
+    }
+}
+
+fn payment(memo: svm_sdk::Option<u32>) -> Payment {
+    Payment {
+        transfer: Transfer {
+            to: Address::repeat(0x10),
+            amount: Amount(100),
+        },
+        tags: [1, 2, 3],
+        memo,
+    }
+}
+
+fn test_identity() {
+    let res: Payment = call_1(identity, vec![payment(svm_sdk::Option::Some(7))]);
+    assert_eq!(res, payment(svm_sdk::Option::Some(7)));
+
+    let res: Payment = call_1(identity, vec![payment(svm_sdk::Option::None)]);
+    assert_eq!(res, payment(svm_sdk::Option::None));
+}
+
+fn test_amount() {
+    let res: Amount = call_1(amount, vec![payment(svm_sdk::Option::None)]);
+    assert_eq!(res, Amount(100));
+}
+
+fn main() {
+    test_identity();
+    test_amount();
+}
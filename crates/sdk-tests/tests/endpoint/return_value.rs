@@ -0,0 +1,68 @@
+use svm_sdk::host::MockHost;
+use svm_sdk::{return_value, template};
+
+use svm_sdk_tests::{call, call_1, call_2};
+
+#[template]
+mod Template {
+    #[endpoint]
+    fn checked_div(a: u32, b: u32) {
+        if b == 0 {
+            return_value!((false, 0u32));
+        }
+
+        return_value!((true, a / b));
+    }
+
+    #[endpoint]
+    fn maybe_double(a: u64) {
+        if a > 100 {
+            return;
+        }
+
+        return_value!(a * 2);
+    }
+
+    #[endpoint]
+    fn double(a: u64) -> u64 {
+        a * 2
+    }
+}
+
+fn test_early_return() {
+    let (ok, res): (bool, u32) = call_2(checked_div, vec![10u32, 0u32]);
+
+    assert_eq!(ok, false);
+    assert_eq!(res, 0);
+}
+
+fn test_return_value() {
+    let (ok, res): (bool, u32) = call_2(checked_div, vec![10u32, 2u32]);
+
+    assert_eq!(ok, true);
+    assert_eq!(res, 5);
+
+    let res: u64 = call_1(maybe_double, vec![10u64]);
+    assert_eq!(res, 20);
+}
+
+fn test_no_return_value() {
+    MockHost::reset();
+
+    let _ = call(maybe_double, vec![1000u64]);
+
+    assert!(MockHost::returndata().is_none());
+}
+
+fn test_declared_returns() {
+    let res: u64 = call_1(double, vec![10u64]);
+
+    assert_eq!(res, 20);
+}
+
+fn main() {
+    test_early_return();
+    test_return_value();
+    test_no_return_value();
+    test_declared_returns();
+}
@@ -0,0 +1,33 @@
+use svm_sdk::{template, String};
+
+use svm_sdk_tests::call_1;
+
+#[template]
+mod Template {
+    #[endpoint]
+    fn identity(s: String) -> String {
+        s
+    }
+
+    #[endpoint]
+    fn len(s: String) -> u32 {
+        s.len() as u32
+    }
+}
+
+fn test_identity() {
+    let s = String::new_short("Hello".as_bytes());
+
+    let res: String = call_1(identity, vec![String::new_short("Hello".as_bytes())]);
+    assert_eq!(res, s);
+}
+
+fn test_len() {
+    let res: u32 = call_1(len, vec![String::new_short("Hello".as_bytes())]);
+    assert_eq!(res, 5);
+}
+
+fn main() {
+    test_identity();
+    test_len();
+}
@@ -0,0 +1,63 @@
+use svm_sdk::{template, AbiDecode, AbiEncode, Address, Amount};
+
+use svm_sdk_tests::call_1;
+
+#[template]
+mod Template {
+    #[derive(Debug, PartialEq, Clone, AbiEncode, AbiDecode)]
+    struct Transfer {
+        to: Address,
+        amount: Amount,
+        memo: u32,
+        urgent: bool,
+    }
+
+    #[endpoint]
+    fn identity(transfer: Transfer) -> Transfer {
+        transfer
+    }
+
+    #[endpoint]
+    fn double(transfer: Transfer) -> Transfer {
+        Transfer {
+            amount: transfer.amount * Amount(2),
+            ..transfer
+        }
+    }
+
+    #[endpoint]
+    fn amount(transfer: Transfer) -> Amount {
+        transfer.amount
+    }
+}
+
+fn transfer() -> Transfer {
+    Transfer {
+        to: Address::repeat(0x10),
+        amount: Amount(100),
+        memo: 7,
+        urgent: true,
+    }
+}
+
+fn test_identity() {
+    let res: Transfer = call_1(identity, vec![transfer()]);
+    assert_eq!(res, transfer());
+}
+
+fn test_double() {
+    let res: Transfer = call_1(double, vec![transfer()]);
+    assert_eq!(res.amount, Amount(200));
+    assert_eq!(res.to, Address::repeat(0x10));
+}
+
+fn test_amount() {
+    let res: Amount = call_1(amount, vec![transfer()]);
+    assert_eq!(res, Amount(100));
+}
+
+fn main() {
+    test_identity();
+    test_double();
+    test_amount();
+}
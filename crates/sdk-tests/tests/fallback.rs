@@ -0,0 +1,46 @@
+use svm_sdk::host::{self, MockHost};
+use svm_sdk::storage::MockStorage;
+
+use trybuild::TestCases;
+
+fn pass(t: &TestCases, test: &'static str) {
+    MockHost::reset();
+    MockStorage::clear();
+
+    t.pass(test);
+}
+
+fn compile_fail(t: &TestCases, test: &'static str) {
+    MockHost::reset();
+    MockStorage::clear();
+
+    t.compile_fail(test);
+}
+
+#[test]
+fn fallback_tests() {
+    let t = TestCases::new();
+
+    pass(&t, "tests/fallback/fallback.rs");
+
+    compile_fail(&t, "tests/fallback/fallback_used_twice_fails.rs");
+    compile_fail(&t, "tests/fallback/fallback_and_endpoint_fails.rs");
+    compile_fail(&t, "tests/fallback/fallback_invalid_sig_fails.rs");
+}
+
+#[test]
+fn fallback_args_decoding() {
+    MockHost::reset();
+
+    // The `Runtime` prefixes the original `calldata` with the requested function name
+    let mut bytes = vec!["transfer".len() as u8];
+    bytes.extend_from_slice(b"transfer");
+    bytes.extend_from_slice(&[0x10, 0x20, 0x30]);
+
+    MockHost::set_raw_calldata(&bytes);
+
+    let (func, calldata) = host::fallback_args();
+
+    assert_eq!(func, "transfer");
+    assert_eq!(calldata, &[0x10, 0x20, 0x30]);
+}
@@ -0,0 +1,40 @@
+use svm_sdk::host::MockHost;
+use svm_sdk::{template, ReturnData};
+
+#[template]
+mod Template {
+    #[endpoint]
+    fn get() -> u32 {
+        10
+    }
+
+    #[fallback]
+    fn forward(func: &str, calldata: &[u8]) -> (bool, u32) {
+        (func == "transfer", calldata.len() as u32)
+    }
+}
+
+fn fallback_call(func: &str, calldata: &[u8]) -> (bool, u32) {
+    let mut bytes = vec![func.len() as u8];
+    bytes.extend_from_slice(func.as_bytes());
+    bytes.extend_from_slice(calldata);
+
+    MockHost::set_raw_calldata(&bytes);
+
+    forward();
+
+    let bytes = MockHost::returndata().unwrap();
+    let mut returns = ReturnData::new(&bytes);
+
+    returns.next_2()
+}
+
+fn main() {
+    let (is_transfer, len) = fallback_call("transfer", &[0x10, 0x20]);
+    assert_eq!(is_transfer, true);
+    assert_eq!(len, 2);
+
+    let (is_transfer, len) = fallback_call("withdraw", &[]);
+    assert_eq!(is_transfer, false);
+    assert_eq!(len, 0);
+}
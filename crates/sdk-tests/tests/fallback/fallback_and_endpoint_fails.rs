@@ -0,0 +1,10 @@
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    #[endpoint]
+    #[fallback]
+    fn forward(func: &str, calldata: &[u8]) {}
+}
+
+fn main() {}
@@ -2,7 +2,8 @@ use svm_sdk::template;
 
 #[template]
 mod Template {
-    enum MyEum {}
+    #[fallback]
+    fn forward(calldata: &[u8]) {}
 }
 
 fn main() {}
@@ -0,0 +1,12 @@
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    #[fallback]
+    fn first(func: &str, calldata: &[u8]) {}
+
+    #[fallback]
+    fn second(func: &str, calldata: &[u8]) {}
+}
+
+fn main() {}
@@ -15,17 +15,29 @@ fn meta_tests() {
     let t = TestCases::new();
 
     pass(&t, "tests/meta/storage_meta.rs");
+    pass(&t, "tests/meta/storage_defaults_meta.rs");
+    pass(&t, "tests/meta/storage_packed_meta.rs");
+    pass(&t, "tests/meta/storage_budget_meta.rs");
+    pass(&t, "tests/meta/storage_dynamic_meta.rs");
+    pass(&t, "tests/meta/storage_consts_meta.rs");
+    pass(&t, "tests/meta/storage_nested_meta.rs");
 
     pass(&t, "tests/meta/ctor_meta.rs");
     pass(&t, "tests/meta/ctor_fundable_meta.rs");
 
     pass(&t, "tests/meta/ctor_with_doc.rs");
     pass(&t, "tests/meta/endpoint_with_doc.rs");
+    pass(&t, "tests/meta/template_docs_meta.rs");
 
     pass(&t, "tests/meta/endpoint_fundable_meta.rs");
     pass(&t, "tests/meta/endpoint_with_params_meta.rs");
     pass(&t, "tests/meta/endpoint_with_params_array_meta.rs");
+    pass(&t, "tests/meta/endpoint_with_struct_params_meta.rs");
+    pass(&t, "tests/meta/enum_meta.rs");
 
     pass(&t, "tests/meta/endpoint_with_returns_tuple_meta.rs");
     pass(&t, "tests/meta/endpoint_with_returns_path_meta.rs");
+    pass(&t, "tests/meta/endpoint_with_returns_array_meta.rs");
+
+    pass(&t, "tests/meta/fallback_meta.rs");
 }
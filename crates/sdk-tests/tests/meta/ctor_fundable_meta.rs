@@ -28,6 +28,7 @@ fn main() {
                 "doc": "",
                 "is_ctor": true,
                 "is_fundable": true,
+                "is_fallback": false,
                 "signature": json!({"params": [], "returns": {}}),
             })],
         })
@@ -21,6 +21,7 @@ fn main() {
                 "wasm_name": "initialize",
                 "is_ctor": true,
                 "is_fundable": false,
+                "is_fallback": false,
                 "doc": "Initializing a new Account",
                 "signature": json!({"params": [], "returns": {}}),
             })],
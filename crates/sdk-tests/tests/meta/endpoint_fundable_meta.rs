@@ -27,6 +27,7 @@ fn main() {
                 "wasm_name": "call",
                 "is_ctor": false,
                 "is_fundable": true,
+                "is_fallback": false,
                 "doc": "",
                 "signature": json!({"params": [], "returns": {}}),
             })],
@@ -23,6 +23,7 @@ fn main() {
                 "wasm_name": "and",
                 "is_ctor": false,
                 "is_fundable": false,
+                "is_fallback": false,
                 "doc": "ANDing `a` and `b`",
                 "signature": json!({"params": [
                     json!({"name": "a", "type": "bool"}),
@@ -23,6 +23,7 @@ fn main() {
                 "wasm_name": "call",
                 "is_ctor": false,
                 "is_fundable": false,
+                "is_fallback": false,
                 "doc": "",
                 "signature": json!({
                     "params": [],
@@ -0,0 +1,41 @@
+#![allow(unused)]
+use serde_json::{json, Value};
+
+use svm_sdk::{template, AbiDecode, AbiEncode, Address, Amount};
+
+#[template]
+mod Template {
+    #[derive(AbiEncode, AbiDecode)]
+    struct Transfer {
+        to: Address,
+        amount: Amount,
+    }
+
+    #[endpoint]
+    fn call(transfer: Transfer) -> Transfer {
+        transfer
+    }
+}
+
+fn main() {
+    let raw = raw_meta();
+    let json: Value = serde_json::from_str(&raw).unwrap();
+
+    assert_eq!(
+        json,
+        json!({
+            "schema": [],
+            "api": [json!({
+                "name": "call",
+                "wasm_name": "call",
+                "is_ctor": false,
+                "is_fundable": false,
+                "is_fallback": false,
+                "doc": "",
+                "signature": json!({"params": [
+                    json!({"name": "transfer", "type": "Transfer"}),
+                ], "returns": json!({"type": "Transfer"})}),
+            })],
+        })
+    );
+}
@@ -0,0 +1,74 @@
+#![allow(unused)]
+use serde_json::{json, Value};
+
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    enum Side {
+        Buy,
+        Sell,
+    }
+
+    enum Code {
+        Ok = 1,
+        Moved,
+        NotFound = 404,
+    }
+
+    #[storage]
+    struct Storage {
+        side: Side,
+        code: Code,
+    }
+
+    #[endpoint]
+    fn call(side: Side) -> Code {
+        Code::Ok
+    }
+}
+
+fn main() {
+    let raw = raw_meta();
+    let json: Value = serde_json::from_str(&raw).unwrap();
+
+    assert_eq!(
+        json,
+        json!({
+            "schema": [
+                {"id": 0, "name": "side", "type": "Side", "offset": 0, "byte_count": 1},
+                {"id": 1, "name": "code", "type": "Code", "offset": 1, "byte_count": 2},
+            ],
+            "api": [json!({
+                "name": "call",
+                "wasm_name": "call",
+                "is_ctor": false,
+                "is_fundable": false,
+                "is_fallback": false,
+                "doc": "",
+                "signature": json!({"params": [
+                    json!({"name": "side", "type": "Side"}),
+                ], "returns": json!({"type": "Code"})}),
+            })],
+            "enums": [
+                {
+                    "name": "Side",
+                    "repr": "u8",
+                    "variants": [
+                        {"name": "Buy", "value": 0},
+                        {"name": "Sell", "value": 1},
+                    ]
+                },
+                {
+                    "name": "Code",
+                    "repr": "u16",
+                    "variants": [
+                        {"name": "Ok", "value": 1},
+                        {"name": "Moved", "value": 2},
+                        {"name": "NotFound", "value": 404},
+                    ]
+                },
+            ],
+        })
+    );
+}
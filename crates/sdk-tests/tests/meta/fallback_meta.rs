@@ -0,0 +1,32 @@
+use serde_json::{json, Value};
+
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    #[fallback(doc = "Forwards any call")]
+    fn forward(func: &str, calldata: &[u8]) -> u32 {
+        calldata.len() as u32
+    }
+}
+
+fn main() {
+    let raw = raw_meta();
+    let json: Value = serde_json::from_str(&raw).unwrap();
+
+    assert_eq!(
+        json,
+        json!({
+            "schema": [],
+            "api": [json!({
+                "name": "forward",
+                "wasm_name": "forward",
+                "is_ctor": false,
+                "is_fundable": false,
+                "is_fallback": true,
+                "doc": "Forwards any call",
+                "signature": json!({"params": [], "returns": {"type": "u32"}}),
+            })],
+        })
+    );
+}
@@ -0,0 +1,29 @@
+use serde_json::{json, Value};
+
+use svm_sdk::template;
+
+#[template(max_storage_bytes = 4096)]
+mod Template {
+    #[storage]
+    struct Storage {
+        a: bool,
+        b: [u16; 4],
+    }
+}
+
+fn main() {
+    let raw = raw_meta();
+    let json: Value = serde_json::from_str(&raw).unwrap();
+
+    assert_eq!(
+        json,
+        json!({
+            "schema": [
+                {"id": 0, "name": "a", "type": "bool",  "offset": 0, "byte_count": 1, "bit": 0},
+                {"id": 1, "name": "b", "type": "[u16]", "offset": 1, "byte_count": 2, "length": 4},
+            ],
+            "api": [],
+            "max_storage_bytes": 4096,
+        })
+    );
+}
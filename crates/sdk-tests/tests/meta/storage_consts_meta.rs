@@ -0,0 +1,44 @@
+#![allow(unused)]
+use serde_json::{json, Value};
+
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    const SLOTS: usize = 2;
+
+    #[storage]
+    struct Storage {
+        slots: [u64; SLOTS],
+        count: u8,
+    }
+
+    #[endpoint]
+    fn call(values: [u8; SLOTS]) {}
+}
+
+fn main() {
+    let raw = raw_meta();
+    let json: Value = serde_json::from_str(&raw).unwrap();
+
+    assert_eq!(
+        json,
+        json!({
+            "schema": [
+                {"id": 0, "name": "slots", "type": "[u64]", "offset": 0, "length": 2, "byte_count": 8},
+                {"id": 2, "name": "count", "type": "u8", "offset": 16, "byte_count": 1},
+            ],
+            "api": [json!({
+                "name": "call",
+                "wasm_name": "call",
+                "is_ctor": false,
+                "is_fundable": false,
+                "is_fallback": false,
+                "doc": "",
+                "signature": json!({"params": [
+                    json!({"name": "values", "type": "[u8]", "length": 2}),
+                ], "returns": json!({})}),
+            })],
+        })
+    );
+}
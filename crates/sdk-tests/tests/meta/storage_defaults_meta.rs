@@ -0,0 +1,38 @@
+use serde_json::{json, Value};
+
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    #[storage]
+    struct Storage {
+        #[storage(default = true)]
+        a: bool,
+        #[storage(default = -2)]
+        b: i16,
+        #[storage(default = 1000)]
+        c: u32,
+        #[storage(default = 10)]
+        d: Amount,
+        e: u64,
+    }
+}
+
+fn main() {
+    let raw = raw_meta();
+    let json: Value = serde_json::from_str(&raw).unwrap();
+
+    assert_eq!(
+        json,
+        json!({
+            "schema": [
+                {"id": 0, "name": "a", "type": "bool",   "offset": 0, "byte_count": 1, "default": "01", "bit": 0},
+                {"id": 1, "name": "b", "type": "i16",    "offset": 1, "byte_count": 2, "default": "feff"},
+                {"id": 2, "name": "c", "type": "u32",    "offset": 3, "byte_count": 4, "default": "e8030000"},
+                {"id": 3, "name": "d", "type": "Amount", "offset": 7, "byte_count": 8, "default": "0a00000000000000"},
+                {"id": 4, "name": "e", "type": "u64",    "offset": 15, "byte_count": 8},
+            ],
+            "api": [],
+        })
+    );
+}
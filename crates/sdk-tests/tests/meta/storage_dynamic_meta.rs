@@ -0,0 +1,30 @@
+use serde_json::{json, Value};
+
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    #[storage]
+    struct Storage {
+        total: u64,
+        balances: Mapping<Address, svm_sdk::Amount>,
+        history: DynVec<u32>,
+    }
+}
+
+fn main() {
+    let raw = raw_meta();
+    let json: Value = serde_json::from_str(&raw).unwrap();
+
+    assert_eq!(
+        json,
+        json!({
+            "schema": [
+                {"id": 0, "name": "total", "type": "u64", "offset": 0, "byte_count": 8},
+                {"id": 1, "name": "balances", "type": "Mapping<Address, Amount>", "offset": 8, "byte_count": 4, "dynamic": true},
+                {"id": 2, "name": "history", "type": "DynVec<u32>", "offset": 12, "byte_count": 4, "dynamic": true},
+            ],
+            "api": [],
+        })
+    );
+}
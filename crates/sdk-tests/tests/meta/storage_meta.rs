@@ -31,7 +31,7 @@ fn main() {
         json,
         json!({
             "schema": [
-                {"id": 0,  "name": "a", "type": "bool",     "offset": 0,  "byte_count": 1},
+                {"id": 0,  "name": "a", "type": "bool",     "offset": 0,  "byte_count": 1, "bit": 0},
                 {"id": 1,  "name": "b", "type": "u8",       "offset": 1,  "byte_count": 1},
                 {"id": 2,  "name": "c", "type": "i8",       "offset": 2,  "byte_count": 1},
                 {"id": 3,  "name": "d", "type": "u16",      "offset": 3,  "byte_count": 2},
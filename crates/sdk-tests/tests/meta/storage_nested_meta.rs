@@ -0,0 +1,43 @@
+use serde_json::{json, Value};
+
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    struct Wallet {
+        /// The owner's balance
+        balance: svm_sdk::Amount,
+        frozen: bool,
+    }
+
+    struct Owner {
+        addr: svm_sdk::Address,
+        wallet: Wallet,
+    }
+
+    #[storage]
+    struct Storage {
+        active: bool,
+        owner: Owner,
+        count: u16,
+    }
+}
+
+fn main() {
+    let raw = raw_meta();
+    let json: Value = serde_json::from_str(&raw).unwrap();
+
+    assert_eq!(
+        json,
+        json!({
+            "schema": [
+                {"id": 0, "name": "active",               "type": "bool",    "offset": 0,  "byte_count": 1, "bit": 0},
+                {"id": 1, "name": "owner.addr",           "type": "Address", "offset": 1,  "byte_count": 20},
+                {"id": 2, "name": "owner.wallet.balance", "type": "Amount",  "offset": 21, "byte_count": 8, "doc": "The owner's balance"},
+                {"id": 3, "name": "owner.wallet.frozen",  "type": "bool",    "offset": 29, "byte_count": 1, "bit": 0},
+                {"id": 4, "name": "count",                "type": "u16",     "offset": 30, "byte_count": 2},
+            ],
+            "api": [],
+        })
+    );
+}
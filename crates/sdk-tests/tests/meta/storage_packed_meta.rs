@@ -0,0 +1,48 @@
+use serde_json::{json, Value};
+
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    #[storage]
+    struct Storage {
+        a: bool,
+        b: bool,
+        c: u8,
+        d: bool,
+        e: bool,
+        f: bool,
+        g: bool,
+        h: bool,
+        i: bool,
+        j: bool,
+        k: bool,
+        l: bool,
+    }
+}
+
+fn main() {
+    let raw = raw_meta();
+    let json: Value = serde_json::from_str(&raw).unwrap();
+
+    assert_eq!(
+        json,
+        json!({
+            "schema": [
+                {"id": 0,  "name": "a", "type": "bool", "offset": 0, "byte_count": 1, "bit": 0},
+                {"id": 1,  "name": "b", "type": "bool", "offset": 0, "byte_count": 1, "bit": 1},
+                {"id": 2,  "name": "c", "type": "u8",   "offset": 1, "byte_count": 1},
+                {"id": 3,  "name": "d", "type": "bool", "offset": 2, "byte_count": 1, "bit": 0},
+                {"id": 4,  "name": "e", "type": "bool", "offset": 2, "byte_count": 1, "bit": 1},
+                {"id": 5,  "name": "f", "type": "bool", "offset": 2, "byte_count": 1, "bit": 2},
+                {"id": 6,  "name": "g", "type": "bool", "offset": 2, "byte_count": 1, "bit": 3},
+                {"id": 7,  "name": "h", "type": "bool", "offset": 2, "byte_count": 1, "bit": 4},
+                {"id": 8,  "name": "i", "type": "bool", "offset": 2, "byte_count": 1, "bit": 5},
+                {"id": 9,  "name": "j", "type": "bool", "offset": 2, "byte_count": 1, "bit": 6},
+                {"id": 10, "name": "k", "type": "bool", "offset": 2, "byte_count": 1, "bit": 7},
+                {"id": 11, "name": "l", "type": "bool", "offset": 3, "byte_count": 1, "bit": 0},
+            ],
+            "api": [],
+        })
+    );
+}
@@ -0,0 +1,60 @@
+use serde_json::{json, Value};
+
+use svm_sdk::template;
+
+/// A simple wallet.
+///
+/// Holds the coins of its `owner`.
+#[template]
+mod Template {
+    #[storage]
+    struct Storage {
+        /// The owner of the wallet
+        owner: Address,
+
+        counter: u32,
+    }
+
+    /// Initializes the wallet
+    #[ctor]
+    fn initialize() {}
+
+    /// Returns the `counter`
+    #[endpoint]
+    fn counter() -> u32 {
+        0
+    }
+
+    /// Ignored since the `doc` is given explicitly
+    #[endpoint(doc = "Resets the `counter`")]
+    fn reset() {}
+}
+
+fn main() {
+    let raw = raw_meta();
+    let json: Value = serde_json::from_str(&raw).unwrap();
+
+    assert_eq!(
+        json["doc"],
+        json!("A simple wallet.\n\nHolds the coins of its `owner`.")
+    );
+    assert_eq!(json["schema"][0]["doc"], json!("The owner of the wallet"));
+    assert!(json["schema"][1].get("doc").is_none());
+
+    let raw = raw_docs();
+    let docs: Value = serde_json::from_str(&raw).unwrap();
+
+    assert_eq!(
+        docs,
+        json!({
+            "name": "Template",
+            "doc": "A simple wallet.\n\nHolds the coins of its `owner`.",
+            "storage": [{"name": "owner", "doc": "The owner of the wallet"}],
+            "api": [
+                {"name": "counter", "doc": "Returns the `counter`"},
+                {"name": "initialize", "doc": "Initializes the wallet"},
+                {"name": "reset", "doc": "Resets the `counter`"},
+            ]
+        })
+    );
+}
@@ -23,8 +23,12 @@ fn storage_tests() {
 
     compile_fail(&t, "tests/storage/invalid_field.rs");
     compile_fail(&t, "tests/storage/singleton.rs");
+    compile_fail(&t, "tests/storage/max_storage_bytes_exceeded.rs");
+    compile_fail(&t, "tests/storage/max_storage_bytes_invalid_arg.rs");
+    compile_fail(&t, "tests/storage/nested_struct_recursive.rs");
 
     pass(&t, "tests/storage/bool_field.rs");
+    pass(&t, "tests/storage/packed_bool_fields.rs");
     pass(&t, "tests/storage/amount_field.rs");
     pass(&t, "tests/storage/address_field.rs");
     pass(&t, "tests/storage/amount_fqn_field.rs");
@@ -36,5 +40,10 @@ fn storage_tests() {
     pass(&t, "tests/storage/u32_field.rs");
     pass(&t, "tests/storage/i32_field.rs");
     pass(&t, "tests/storage/u64_field.rs");
+    pass(&t, "tests/storage/scripted_host.rs");
     pass(&t, "tests/storage/i64_field.rs");
+    pass(&t, "tests/storage/max_storage_bytes.rs");
+    pass(&t, "tests/storage/dynamic_fields.rs");
+    pass(&t, "tests/storage/enum_field.rs");
+    pass(&t, "tests/storage/nested_struct.rs");
 }
@@ -0,0 +1,51 @@
+use svm_sdk::{template, Address, Amount};
+
+#[template]
+mod Template {
+    #[storage]
+    struct Storage {
+        total: u64,
+        balances: Mapping<Address, Amount>,
+        history: DynVec<u32>,
+    }
+}
+
+fn main() {
+    let addr = Address::from([0x10; Address::len()]);
+    let other = Address::from([0x20; Address::len()]);
+
+    // `balances`
+    assert_eq!(Storage::balances_len(), 0);
+    assert!(!Storage::contains_balances(&addr));
+    assert_eq!(Storage::get_balances(&addr), Amount(0));
+
+    Storage::set_balances(&addr, Amount(100));
+    Storage::set_balances(&other, Amount(200));
+
+    assert_eq!(Storage::balances_len(), 2);
+    assert!(Storage::contains_balances(&addr));
+    assert_eq!(Storage::get_balances(&addr), Amount(100));
+    assert_eq!(Storage::get_balances(&other), Amount(200));
+
+    Storage::remove_balances(&addr);
+
+    assert_eq!(Storage::balances_len(), 1);
+    assert!(!Storage::contains_balances(&addr));
+
+    // `history`
+    assert_eq!(Storage::history_len(), 0);
+
+    Storage::push_history(10);
+    Storage::push_history(20);
+    Storage::set_history(0, 15);
+
+    assert_eq!(Storage::history_len(), 2);
+    assert_eq!(Storage::get_history(0), 15);
+    assert_eq!(Storage::get_history(1), 20);
+
+    assert_eq!(Storage::pop_history(), 20);
+    assert_eq!(Storage::history_len(), 1);
+
+    // `total` isn't affected
+    assert_eq!(Storage::get_total(), 0);
+}
@@ -0,0 +1,42 @@
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    enum State {
+        Idle,
+        Active,
+        Closed,
+    }
+
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    enum Level {
+        Low = 0,
+        High = 1000,
+    }
+
+    #[storage]
+    struct Storage {
+        state: State,
+        level: Level,
+        count: u32,
+    }
+}
+
+fn main() {
+    // A `State` is read as its first variant (valued `0`) until set
+    assert_eq!(Storage::get_state(), State::Idle);
+    assert_eq!(Storage::get_level(), Level::Low);
+
+    Storage::set_state(State::Closed);
+    Storage::set_level(Level::High);
+    Storage::set_count(7);
+
+    assert_eq!(Storage::get_state(), State::Closed);
+    assert_eq!(Storage::get_level(), Level::High);
+    assert_eq!(Storage::get_count(), 7);
+
+    Storage::set_state(State::Active);
+    assert_eq!(Storage::get_state(), State::Active);
+    assert_eq!(Storage::get_level(), Level::High);
+}
@@ -0,0 +1,22 @@
+use svm_sdk::template;
+
+#[template(max_storage_bytes = 29)]
+mod Template {
+    #[storage]
+    struct Storage {
+        owner: Address,
+        balance: Amount,
+        active: bool,
+    }
+
+    #[endpoint]
+    fn activate() {
+        Storage::set_active(true);
+    }
+}
+
+fn main() {
+    activate();
+
+    assert!(Storage::get_active());
+}
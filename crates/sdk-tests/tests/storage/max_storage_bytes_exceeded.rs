@@ -0,0 +1,13 @@
+use svm_sdk::template;
+
+#[template(max_storage_bytes = 64)]
+mod Template {
+    #[storage]
+    struct Storage {
+        owner: Address,
+        balances: [Amount; 8],
+        active: bool,
+    }
+}
+
+fn main() {}
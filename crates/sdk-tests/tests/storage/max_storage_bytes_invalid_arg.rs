@@ -0,0 +1,11 @@
+use svm_sdk::template;
+
+#[template(max_storage_size = 64)]
+mod Template {
+    #[storage]
+    struct Storage {
+        owner: Address,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,48 @@
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    struct Wallet {
+        balance: svm_sdk::Amount,
+        frozen: bool,
+    }
+
+    struct Owner {
+        addr: svm_sdk::Address,
+        wallet: Wallet,
+        nonces: [u32; 2],
+    }
+
+    #[storage]
+    struct Storage {
+        active: bool,
+        owner: Owner,
+        count: u16,
+    }
+}
+
+fn main() {
+    let addr = svm_sdk::Address::repeat(0x10);
+
+    Storage::set_active(true);
+    Storage::owner().set_addr(&addr);
+    Storage::owner().wallet().set_balance(svm_sdk::Amount(100));
+    Storage::owner().wallet().set_frozen(true);
+    Storage::owner().set_nonces(1, 7);
+    Storage::set_count(3);
+
+    assert!(Storage::get_active());
+    assert_eq!(Storage::owner().get_addr(), addr);
+    assert_eq!(
+        Storage::owner().wallet().get_balance(),
+        svm_sdk::Amount(100)
+    );
+    assert!(Storage::owner().wallet().get_frozen());
+    assert_eq!(Storage::owner().get_nonces(0), 0);
+    assert_eq!(Storage::owner().get_nonces(1), 7);
+    assert_eq!(Storage::get_count(), 3);
+
+    Storage::owner().wallet().set_frozen(false);
+    assert!(Storage::get_active());
+    assert!(!Storage::owner().wallet().get_frozen());
+}
@@ -0,0 +1,16 @@
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    struct Node {
+        value: u32,
+        next: Node,
+    }
+
+    #[storage]
+    struct Storage {
+        head: Node,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+use svm_sdk::template;
+
+#[template(max_storage_bytes = 3)]
+mod Template {
+    #[storage]
+    struct Storage {
+        paused: bool,
+        frozen: bool,
+        version: u8,
+        audited: bool,
+    }
+}
+
+fn main() {
+    assert!(!Storage::get_paused());
+    assert!(!Storage::get_frozen());
+
+    Storage::set_paused(true);
+    Storage::set_version(2);
+
+    assert!(Storage::get_paused());
+    assert!(!Storage::get_frozen());
+    assert_eq!(Storage::get_version(), 2);
+
+    Storage::set_frozen(true);
+    Storage::set_audited(true);
+    Storage::set_paused(false);
+
+    assert!(!Storage::get_paused());
+    assert!(Storage::get_frozen());
+    assert!(Storage::get_audited());
+}
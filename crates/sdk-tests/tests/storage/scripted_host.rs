@@ -0,0 +1,63 @@
+use svm_sdk::host::{HostCall, MockHost};
+use svm_sdk::{template, Address, Amount};
+
+#[template]
+mod Template {
+    #[storage]
+    struct Storage {
+        counter: u64,
+    }
+}
+
+fn test_scripted_get64() {
+    MockHost::script_get64(0, &[41]);
+
+    let counter = Storage::get_counter();
+    assert_eq!(counter, 41);
+
+    // the scripted values are exhausted, so the stored value is read again
+    let counter = Storage::get_counter();
+    assert_eq!(counter, 0);
+}
+
+fn test_history() {
+    MockHost::reset();
+
+    let target: Address = [0x10; 20].into();
+    let dst: Address = [0x20; 20].into();
+
+    MockHost::set_target(target);
+    MockHost::set_balance(&target, Amount(100));
+
+    MockHost::expect_transfer(&dst, Amount(42)).times(1);
+    MockHost::expect_set64(0, 42);
+
+    Storage::set_counter(42);
+    let counter = Storage::get_counter();
+    MockHost::transfer(&dst, Amount(counter));
+
+    assert_eq!(
+        MockHost::history(),
+        vec![
+            HostCall::Set64 {
+                var_id: 0,
+                value: 42
+            },
+            HostCall::Get64 {
+                var_id: 0,
+                value: 42
+            },
+            HostCall::Transfer {
+                dst,
+                amount: Amount(42)
+            },
+        ]
+    );
+
+    MockHost::verify();
+}
+
+fn main() {
+    test_scripted_get64();
+    test_history();
+}
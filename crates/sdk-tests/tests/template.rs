@@ -22,10 +22,11 @@ fn template_tests() {
     let t = TestCases::new();
 
     pass(&t, "tests/template/empty.rs");
+    pass(&t, "tests/template/consts.rs");
 
-    compile_fail(&t, "tests/template/declaring_const_not_allowed.rs");
+    compile_fail(&t, "tests/template/declaring_const_invalid_type.rs");
     compile_fail(&t, "tests/template/declaring_static_not_allowed.rs");
-    compile_fail(&t, "tests/template/declaring_enum_not_allowed.rs");
+    compile_fail(&t, "tests/template/declaring_data_enum_not_allowed.rs");
     compile_fail(&t, "tests/template/declaring_union_not_allowed.rs");
     compile_fail(&t, "tests/template/declaring_traits_not_allowed.rs");
 
@@ -0,0 +1,48 @@
+use svm_sdk::template;
+
+use svm_sdk_tests::call_1;
+
+#[template]
+mod Template {
+    const HISTORY: usize = 3;
+    const OFFSET: i64 = -10;
+    const ENABLED: bool = true;
+    const NAME: &str = "counter";
+
+    #[storage]
+    struct Storage {
+        history: [u32; HISTORY],
+    }
+
+    #[endpoint]
+    fn record(values: [u32; HISTORY]) -> u32 {
+        let mut sum = 0;
+
+        for i in 0..HISTORY {
+            Storage::set_history(i, values[i]);
+            sum += values[i];
+        }
+
+        sum
+    }
+
+    #[endpoint]
+    fn offset(value: i64) -> i64 {
+        if ENABLED && NAME.len() == 7 {
+            value + OFFSET
+        } else {
+            value
+        }
+    }
+}
+
+fn main() {
+    assert_eq!(HISTORY, 3);
+
+    let res: u32 = call_1(record, vec![[1u32, 2, 3]]);
+    assert_eq!(res, 6);
+    assert_eq!(Storage::get_history(2), 3);
+
+    let res: i64 = call_1(offset, vec![100i64]);
+    assert_eq!(res, 90);
+}
@@ -2,7 +2,7 @@ use svm_sdk::template;
 
 #[template]
 mod Template {
-    const N: u32 = 10;
+    const RATE: f32 = 0.5;
 }
 
 fn main() {}
@@ -0,0 +1,11 @@
+use svm_sdk::template;
+
+#[template]
+mod Template {
+    enum MyEnum {
+        Empty,
+        Value(u32),
+    }
+}
+
+fn main() {}
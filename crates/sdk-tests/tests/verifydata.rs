@@ -0,0 +1,25 @@
+use svm_sdk::host::{self, MockHost};
+use svm_sdk::traits::Encoder;
+use svm_sdk::{Address, Vec};
+
+#[test]
+fn verifydata_decoding() {
+    MockHost::reset();
+
+    let pubkey = Address::repeat(0x10);
+    let nonce = 7u32;
+
+    let mut bytes = Vec::with_capacity(100);
+    pubkey.encode(&mut bytes);
+    nonce.encode(&mut bytes);
+
+    // While running `svm_verify`, the `verifydata` is given as the function's input
+    MockHost::set_raw_calldata(bytes.as_slice());
+
+    let mut verifydata = host::verifydata();
+    let (decoded_pubkey, decoded_nonce): (Address, u32) = verifydata.next_2();
+
+    assert_eq!(decoded_pubkey, pubkey);
+    assert_eq!(decoded_nonce, nonce);
+    assert!(matches!(verifydata.next(), svm_sdk::Option::None));
+}
@@ -36,6 +36,17 @@ extern "C" {
     /// at memory offset `offset` (of byte-length `length`)
     /// and it's associated message code (for signaling errors severity such as `trace/info/error` etc.)
     fn svm_log(offset: u32, length: u32, code: u32);
+
+    /// Returns the layer the current executed transaction is running at.
+    fn svm_layer() -> u64;
+
+    /// Receives an offset to allocated `Address` (`Address::len()` of bytes).
+    /// SVM will copy the `Address` of the transaction's `Principal` starting at offset `offset`.
+    fn svm_principal(offset: u32);
+
+    /// Receives an offset to an allocated transaction id (`32` bytes).
+    /// SVM will copy the id of the current executed transaction starting at offset `offset`.
+    fn svm_tx_id(offset: u32);
 }
 
 /// ## Spacemesh Imports
@@ -53,19 +64,11 @@ extern "C" {
     /// Returns the currently executed `Account`'s (a.k.a the `target`) balance.
     fn sm_balance() -> u64;
 
-    /// Receives an offset to allocated `Address` (`Address::len()` of bytes).
-    /// The `Node` will copy the `Address` of the transaction's `Principal`
-    /// starting at offset `offset`.
-    fn sm_principal(offset: u32);
-
     /// Receives an offset to allocated `Address` (`Address::len()` of bytes).
     /// The `Node` will copy the `Address` of the transaction's `Target` (the currently executing `Account`)
     /// starting at offset `offset`.
     fn sm_target(offset: u32);
 
-    /// Returns the Spacemesh layer the current executed transaction is running at.
-    fn sm_layer() -> u64;
-
     /// Transfers `amount` coins from the currently executed `Account` (the `target`)
     /// to the account ("the destination") which is address is starts offset `dst_offset` (`Address::len()` of bytes).
     fn sm_transfer(dst_offset: u32, amount: u64);
@@ -142,6 +145,12 @@ impl Host for ExtHost {
         host.layer_id()
     }
 
+    #[inline]
+    fn tx_id(&self) -> [u8; 32] {
+        let host = Self::instance();
+        host.tx_id()
+    }
+
     #[inline]
     fn balance(&self) -> Amount {
         let host = Self::instance();
@@ -199,7 +208,7 @@ impl Host for InnerHost {
         unsafe {
             let offset = self.alloc_addr();
 
-            sm_principal(offset);
+            svm_principal(offset);
 
             offset.into()
         }
@@ -219,11 +228,22 @@ impl Host for InnerHost {
     #[inline]
     fn layer_id(&self) -> LayerId {
         unsafe {
-            let id = sm_layer();
+            let id = svm_layer();
             LayerId(id)
         }
     }
 
+    #[inline]
+    fn tx_id(&self) -> [u8; 32] {
+        let mut tx_id = [0; 32];
+
+        unsafe {
+            svm_tx_id(tx_id.as_mut_ptr() as u32);
+        }
+
+        tx_id
+    }
+
     #[inline]
     fn balance(&self) -> Amount {
         unsafe {
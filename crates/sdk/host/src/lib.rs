@@ -15,8 +15,12 @@
 pub mod traits;
 
 mod ext;
+#[cfg(feature = "mock")]
 mod mock;
 
+#[cfg(feature = "mock")]
+mod script;
+
 #[cfg(all(feature = "ffi", feature = "mock"))]
 compile_error!("can't have both `ffi` and `mock` features turned-on");
 
@@ -27,4 +31,7 @@ compile_error!("must have at least one feature flag turned-on (`ffi` or `mock`)"
 pub use ext::ExtHost;
 
 #[cfg(feature = "mock")]
-pub use mock::MockHost;
+pub use mock::{ExpectedCall, ExpectedTransfer, MockHost};
+
+#[cfg(feature = "mock")]
+pub use script::{HostCall, ScriptedCall, Verification};
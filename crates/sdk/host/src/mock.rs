@@ -1,3 +1,4 @@
+use crate::script::{HostCall, Script, ScriptedCall, Verification};
 use crate::traits::Host;
 
 use svm_abi_encoder::{ByteSize, Encoder};
@@ -86,6 +87,11 @@ impl MockHost {
         host.set_layer_id(layer_id);
     }
 
+    pub fn set_tx_id(tx_id: [u8; 32]) {
+        let host = Self::instance();
+        host.set_tx_id(tx_id);
+    }
+
     pub fn value() -> Amount {
         let host = Self::instance();
         host.value()
@@ -101,6 +107,11 @@ impl MockHost {
         host.layer_id()
     }
 
+    pub fn tx_id() -> [u8; 32] {
+        let host = Self::instance();
+        host.tx_id()
+    }
+
     pub fn balance() -> Amount {
         let host = Self::instance();
         host.balance()
@@ -126,12 +137,195 @@ impl MockHost {
         host.logs()
     }
 
+    /// Sets how strictly the scripted calls and host data are verified (defaults to `Strict`).
+    pub fn set_verification(verification: Verification) {
+        let host = Self::instance();
+        host.script.set_verification(verification);
+    }
+
+    /// Registers an expected call of function `func` of `Account` `target`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use svm_sdk_host::MockHost;
+    /// use svm_sdk_types::Address;
+    ///
+    /// let oracle: Address = [0x10; 20].into();
+    ///
+    /// MockHost::expect_call(&oracle, "price").returns(&[0, 0, 0, 42]);
+    ///
+    /// assert_eq!(MockHost::call(&oracle, "price", &[]), vec![0, 0, 0, 42]);
+    ///
+    /// MockHost::verify();
+    /// # MockHost::reset();
+    /// ```
+    pub fn expect_call(target: &Address, func: &str) -> ExpectedCall {
+        let host = Self::instance();
+        let index = host.script.expect_call(target, func);
+
+        ExpectedCall { index }
+    }
+
+    /// Calls function `func` of `Account` `target` (as scripted by [`Self::expect_call`]),
+    /// returning its `returndata`.
+    pub fn call(target: &Address, func: &str, calldata: &[u8]) -> alloc::vec::Vec<u8> {
+        let host = Self::instance();
+        host.script.call(target, func, calldata)
+    }
+
+    /// The calls made so far (in order).
+    pub fn calls() -> alloc::vec::Vec<ScriptedCall> {
+        let host = Self::instance();
+        host.script.calls()
+    }
+
+    /// Registers an expected transfer of `amount` to `dst`.
+    ///
+    /// Once any transfer is expected, the transfers made are verified against the expectations
+    /// (see [`Verification`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use svm_sdk_host::MockHost;
+    /// use svm_sdk_types::{Address, Amount};
+    ///
+    /// let target: Address = [0x10; 20].into();
+    /// let dst: Address = [0x20; 20].into();
+    ///
+    /// MockHost::set_target(target);
+    /// MockHost::set_balance(&target, Amount(10));
+    ///
+    /// MockHost::expect_transfer(&dst, Amount(5)).times(1);
+    /// MockHost::transfer(&dst, Amount(5));
+    ///
+    /// assert_eq!(MockHost::balance_of(&dst), Amount(5));
+    ///
+    /// MockHost::verify();
+    /// # MockHost::reset();
+    /// ```
+    pub fn expect_transfer(dst: &Address, amount: Amount) -> ExpectedTransfer {
+        let host = Self::instance();
+        let index = host.script.expect_transfer(dst, amount);
+
+        ExpectedTransfer { index }
+    }
+
+    /// Scripts the values returned by the next `get64` reads of variable `var_id` (one value per read).
+    ///
+    /// Once the scripted values are exhausted, the variable is read from the `MockStorage` again.
+    pub fn script_get64(var_id: u32, values: &[u64]) {
+        let host = Self::instance();
+        host.script.script_get64(var_id, values);
+    }
+
+    /// Expects variable `var_id` to be set (by `set64`) to `value`.
+    pub fn expect_set64(var_id: u32, value: u64) {
+        let host = Self::instance();
+        host.script.expect_set64(var_id, value);
+    }
+
+    /// The host functions invoked so far (in order).
+    pub fn history() -> alloc::vec::Vec<HostCall> {
+        let host = Self::instance();
+        host.script.history()
+    }
+
+    /// Used by the `MockStorage` for reading the values scripted by [`Self::script_get64`].
+    #[doc(hidden)]
+    pub fn scripted_get64(var_id: u32) -> Option<u64> {
+        let host = Self::instance();
+        host.script.scripted_get64(var_id)
+    }
+
+    /// Used by the `MockStorage` for recording its accesses within the [`Self::history`].
+    #[doc(hidden)]
+    pub fn record(call: HostCall) {
+        let host = Self::instance();
+        host.script.record(call);
+    }
+
+    /// Sets the canned host data (e.g oracle data) returned for `key`.
+    pub fn set_host_data(key: &str, data: &[u8]) {
+        let host = Self::instance();
+        host.script.set_host_data(key, data);
+    }
+
+    pub fn host_data(key: &str) -> Option<alloc::vec::Vec<u8>> {
+        let host = Self::instance();
+        host.script.host_data(key)
+    }
+
+    /// Panics unless the expected calls have been made (see [`Verification`]).
+    pub fn verify() {
+        let host = Self::instance();
+        host.script.verify();
+    }
+
     pub fn reset() {
         let host = Self::instance();
         host.reset();
     }
 }
 
+/// An expected call registered by [`MockHost::expect_call`].
+pub struct ExpectedCall {
+    index: usize,
+}
+
+impl ExpectedCall {
+    /// Matches only calls given `calldata` (by default, any `calldata` matches).
+    pub fn with_calldata(self, calldata: &[u8]) -> Self {
+        let host = MockHost::instance();
+        host.script.set_expected_calldata(self.index, calldata);
+
+        self
+    }
+
+    /// Sets the `returndata` of the matching calls (by default, it's empty).
+    pub fn returns(self, returndata: &[u8]) -> Self {
+        let host = MockHost::instance();
+        host.script.set_expected_returndata(self.index, returndata);
+
+        self
+    }
+
+    /// Expects exactly `times` matching calls (by default, at least one).
+    pub fn times(self, times: usize) -> Self {
+        let host = MockHost::instance();
+        host.script.set_expected_times(self.index, times);
+
+        self
+    }
+}
+
+/// An expected transfer registered by [`MockHost::expect_transfer`].
+pub struct ExpectedTransfer {
+    index: usize,
+}
+
+impl ExpectedTransfer {
+    /// Sets the outcome of the matching transfers (by default, `Ok(())`).
+    ///
+    /// A transfer scripted to fail panics with the given message (same as a failing transfer
+    /// traps the running transaction), and doesn't move any coins.
+    pub fn returning(self, result: Result<(), &str>) -> Self {
+        let host = MockHost::instance();
+        host.script.set_transfer_result(self.index, result);
+
+        self
+    }
+
+    /// Expects exactly `times` matching transfers (by default, at least one).
+    pub fn times(self, times: usize) -> Self {
+        let host = MockHost::instance();
+        host.script.set_transfer_times(self.index, times);
+
+        self
+    }
+}
+
 impl Host for MockHost {
     fn calldata(&self) -> &'static [u8] {
         let host = Self::instance();
@@ -163,6 +357,11 @@ impl Host for MockHost {
         host.layer_id()
     }
 
+    fn tx_id(&self) -> [u8; 32] {
+        let host = Self::instance();
+        host.tx_id()
+    }
+
     fn balance(&self) -> Amount {
         let host = Self::instance();
         host.balance()
@@ -194,7 +393,11 @@ pub struct InnerHost {
 
     pub layer_id: Option<LayerId>,
 
+    pub tx_id: Option<[u8; 32]>,
+
     pub logs: alloc::vec::Vec<(String, u8)>,
+
+    pub script: Script,
 }
 
 impl InnerHost {
@@ -207,7 +410,9 @@ impl InnerHost {
             target: None,
             accounts: HashMap::new(),
             layer_id: None,
+            tx_id: None,
             logs: alloc::vec::Vec::new(),
+            script: Script::new(),
         }
     }
 
@@ -256,6 +461,10 @@ impl InnerHost {
         self.layer_id = Some(layer_id);
     }
 
+    pub fn set_tx_id(&mut self, tx_id: [u8; 32]) {
+        self.tx_id = Some(tx_id);
+    }
+
     pub fn logs(&self) -> alloc::vec::Vec<(String, u8)> {
         self.logs.clone()
     }
@@ -271,7 +480,9 @@ impl InnerHost {
         self.principal = None;
         self.target = None;
         self.layer_id = None;
+        self.tx_id = None;
         self.logs.clear();
+        self.script.reset();
     }
 }
 
@@ -300,12 +511,18 @@ impl Host for InnerHost {
         self.layer_id.unwrap()
     }
 
+    fn tx_id(&self) -> [u8; 32] {
+        self.tx_id.unwrap()
+    }
+
     fn balance(&self) -> Amount {
         let target = self.target();
         self.balance_of(&target)
     }
 
     fn transfer(&mut self, dst: &Address, amount: Amount) {
+        self.script.transfer(dst, amount);
+
         let target_balance = self.balance();
 
         assert!(target_balance >= amount);
@@ -324,6 +541,10 @@ impl Host for InnerHost {
     fn log(&mut self, msg: &str, code: u8) {
         let log = (msg.to_string(), code);
 
+        self.script.record(HostCall::Log {
+            msg: msg.to_string(),
+            code,
+        });
         self.logs.push(log);
     }
 }
@@ -345,7 +566,8 @@ mod tests {
 
     fn test(f: fn() -> ()) {
         // we use a `Mutex` to enforce serial execution of `MockHost`'s tests.
-        let guard = TEST_LOCK.lock().unwrap();
+        // (a test expected to panic poisons it, which is fine since `MockHost` is reset anyway)
+        let guard = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
 
         MockHost::instance().reset();
 
@@ -437,6 +659,16 @@ mod tests {
         });
     }
 
+    #[test]
+    fn host_tx_id() {
+        test(|| {
+            MockHost::set_tx_id([0xAB; 32]);
+
+            let tx_id = MockHost::tx_id();
+            assert_eq!(tx_id, [0xAB; 32]);
+        });
+    }
+
     #[test]
     fn host_logs() {
         test(|| {
@@ -454,4 +686,164 @@ mod tests {
             )
         });
     }
+
+    #[test]
+    fn host_scripted_calls() {
+        test(|| {
+            let oracle: Address = [0x10; 20].into();
+
+            MockHost::expect_call(&oracle, "price")
+                .with_calldata(b"BTC")
+                .returns(&[0, 42])
+                .times(2);
+            MockHost::expect_call(&oracle, "price").returns(&[0, 1]);
+
+            assert_eq!(MockHost::call(&oracle, "price", b"BTC"), vec![0, 42]);
+            assert_eq!(MockHost::call(&oracle, "price", b"BTC"), vec![0, 42]);
+
+            // the first expectation is exhausted
+            assert_eq!(MockHost::call(&oracle, "price", b"BTC"), vec![0, 1]);
+            assert_eq!(MockHost::calls().len(), 3);
+
+            MockHost::verify();
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Unexpected call of `price`")]
+    fn host_scripted_calls_strict() {
+        test(|| {
+            let oracle: Address = [0x10; 20].into();
+
+            MockHost::call(&oracle, "price", &[]);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected a call of `price`")]
+    fn host_scripted_calls_strict_verify() {
+        test(|| {
+            let oracle: Address = [0x10; 20].into();
+
+            MockHost::expect_call(&oracle, "price");
+            MockHost::verify();
+        });
+    }
+
+    #[test]
+    fn host_scripted_calls_loose() {
+        test(|| {
+            let oracle: Address = [0x10; 20].into();
+
+            MockHost::set_verification(Verification::Loose);
+            MockHost::expect_call(&oracle, "price");
+
+            assert!(MockHost::call(&oracle, "rate", &[]).is_empty());
+            assert_eq!(MockHost::host_data("BTC/USD"), None);
+
+            MockHost::verify();
+        });
+    }
+
+    #[test]
+    fn host_expected_transfers() {
+        test(|| {
+            let target: Address = [0x10; 20].into();
+            let dst: Address = [0x20; 20].into();
+
+            MockHost::set_target(target);
+            MockHost::set_balance(&target, Amount(10));
+
+            MockHost::expect_transfer(&dst, Amount(5)).times(2);
+
+            MockHost::transfer(&dst, Amount(5));
+            MockHost::log("paid", 1);
+            MockHost::transfer(&dst, Amount(5));
+
+            assert_eq!(MockHost::balance(), Amount(0));
+            assert_eq!(MockHost::balance_of(&dst), Amount(10));
+
+            let transfer = HostCall::Transfer {
+                dst,
+                amount: Amount(5),
+            };
+            let log = HostCall::Log {
+                msg: "paid".to_string(),
+                code: 1,
+            };
+
+            assert_eq!(MockHost::history(), vec![transfer.clone(), log, transfer]);
+
+            MockHost::verify();
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Insufficient funds")]
+    fn host_expected_transfer_failure() {
+        test(|| {
+            let dst: Address = [0x20; 20].into();
+
+            MockHost::expect_transfer(&dst, Amount(5)).returning(Err("Insufficient funds"));
+            MockHost::transfer(&dst, Amount(5));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Unexpected transfer")]
+    fn host_unexpected_transfer() {
+        test(|| {
+            let target: Address = [0x10; 20].into();
+            let dst: Address = [0x20; 20].into();
+
+            MockHost::set_target(target);
+            MockHost::set_balance(&target, Amount(10));
+
+            MockHost::expect_transfer(&dst, Amount(5));
+            MockHost::transfer(&dst, Amount(3));
+        });
+    }
+
+    #[test]
+    fn host_scripted_storage() {
+        test(|| {
+            MockHost::script_get64(0, &[10, 20]);
+            MockHost::expect_set64(1, 30);
+
+            assert_eq!(MockHost::scripted_get64(0), Some(10));
+            assert_eq!(MockHost::scripted_get64(0), Some(20));
+            assert_eq!(MockHost::scripted_get64(0), None);
+            assert_eq!(MockHost::scripted_get64(1), None);
+
+            MockHost::record(HostCall::Set64 {
+                var_id: 1,
+                value: 30,
+            });
+
+            MockHost::verify();
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected variable #1 to be set to 30")]
+    fn host_scripted_storage_verify() {
+        test(|| {
+            MockHost::expect_set64(1, 30);
+            MockHost::record(HostCall::Set64 {
+                var_id: 1,
+                value: 20,
+            });
+
+            MockHost::verify();
+        });
+    }
+
+    #[test]
+    fn host_data() {
+        test(|| {
+            MockHost::set_host_data("BTC/USD", &[1, 2, 3]);
+
+            assert_eq!(MockHost::host_data("BTC/USD"), Some(vec![1, 2, 3]));
+        });
+    }
 }
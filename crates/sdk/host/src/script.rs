@@ -0,0 +1,372 @@
+use svm_sdk_types::{Address, Amount};
+
+extern crate alloc;
+extern crate std;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use std::collections::{HashMap, VecDeque};
+
+/// How strictly a [`Script`] verifies the interactions of the code under test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verification {
+    /// Unscripted calls (and host data requests) panic,
+    /// and every expected call must have been made exactly as many times as expected.
+    Strict,
+
+    /// Unscripted calls return an empty `returndata` (and unscripted host data is missing),
+    /// and expected calls may be left unmade.
+    Loose,
+}
+
+impl Default for Verification {
+    fn default() -> Self {
+        Verification::Strict
+    }
+}
+
+/// A call to another `Account` as made by the code under test.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptedCall {
+    pub target: Address,
+
+    pub func: String,
+
+    pub calldata: Vec<u8>,
+}
+
+/// A host function invocation made by the code under test (see [`Script::history`]).
+#[derive(Debug, Clone, PartialEq)]
+pub enum HostCall {
+    Call(ScriptedCall),
+
+    Transfer { dst: Address, amount: Amount },
+
+    Log { msg: String, code: u8 },
+
+    Get64 { var_id: u32, value: u64 },
+
+    Set64 { var_id: u32, value: u64 },
+}
+
+#[derive(Debug, Clone)]
+struct Expectation {
+    target: Address,
+
+    func: String,
+
+    calldata: Option<Vec<u8>>,
+
+    returndata: Vec<u8>,
+
+    times: Option<usize>,
+
+    made: usize,
+}
+
+impl Expectation {
+    fn matches(&self, target: &Address, func: &str, calldata: &[u8]) -> bool {
+        let calldata_matches = match self.calldata.as_ref() {
+            Some(expected) => expected.as_slice() == calldata,
+            None => true,
+        };
+
+        &self.target == target && self.func == func && calldata_matches
+    }
+
+    fn exhausted(&self) -> bool {
+        self.times.map_or(false, |times| self.made >= times)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TransferExpectation {
+    dst: Address,
+
+    amount: Amount,
+
+    result: Result<(), String>,
+
+    times: Option<usize>,
+
+    made: usize,
+}
+
+impl TransferExpectation {
+    fn exhausted(&self) -> bool {
+        self.times.map_or(false, |times| self.made >= times)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Set64Expectation {
+    var_id: u32,
+
+    value: u64,
+
+    made: usize,
+}
+
+/// The scripted responses of the `MockHost` (to the code under test).
+///
+/// Lets template logic be tested in isolation from the other templates it interacts with.
+#[derive(Debug, Default)]
+pub struct Script {
+    verification: Verification,
+
+    expectations: Vec<Expectation>,
+
+    transfers: Vec<TransferExpectation>,
+
+    get64: HashMap<u32, VecDeque<u64>>,
+
+    set64: Vec<Set64Expectation>,
+
+    host_data: HashMap<String, Vec<u8>>,
+
+    calls: Vec<ScriptedCall>,
+
+    history: Vec<HostCall>,
+}
+
+impl Script {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_verification(&mut self, verification: Verification) {
+        self.verification = verification;
+    }
+
+    pub fn verification(&self) -> Verification {
+        self.verification
+    }
+
+    /// Registers an expected call, returning its index.
+    pub fn expect_call(&mut self, target: &Address, func: &str) -> usize {
+        self.expectations.push(Expectation {
+            target: target.clone(),
+            func: func.to_string(),
+            calldata: None,
+            returndata: Vec::new(),
+            times: None,
+            made: 0,
+        });
+
+        self.expectations.len() - 1
+    }
+
+    pub fn set_expected_calldata(&mut self, index: usize, calldata: &[u8]) {
+        self.expectations[index].calldata = Some(calldata.to_vec());
+    }
+
+    pub fn set_expected_returndata(&mut self, index: usize, returndata: &[u8]) {
+        self.expectations[index].returndata = returndata.to_vec();
+    }
+
+    pub fn set_expected_times(&mut self, index: usize, times: usize) {
+        self.expectations[index].times = Some(times);
+    }
+
+    /// Makes a call against the scripted expectations, returning its `returndata`.
+    ///
+    /// The first matching expectation (which isn't exhausted yet) is the one answering the call.
+    pub fn call(&mut self, target: &Address, func: &str, calldata: &[u8]) -> Vec<u8> {
+        let call = ScriptedCall {
+            target: target.clone(),
+            func: func.to_string(),
+            calldata: calldata.to_vec(),
+        };
+
+        self.calls.push(call.clone());
+        self.history.push(HostCall::Call(call));
+
+        let expectation = self
+            .expectations
+            .iter_mut()
+            .find(|e| !e.exhausted() && e.matches(target, func, calldata));
+
+        match (expectation, self.verification) {
+            (Some(expectation), _) => {
+                expectation.made += 1;
+                expectation.returndata.clone()
+            }
+            (None, Verification::Loose) => Vec::new(),
+            (None, Verification::Strict) => panic!(
+                "Unexpected call of `{}` (Account `{:?}`) with calldata {:?}",
+                func, target, calldata
+            ),
+        }
+    }
+
+    /// The calls made so far (in order).
+    pub fn calls(&self) -> Vec<ScriptedCall> {
+        self.calls.clone()
+    }
+
+    /// Registers an expected transfer of `amount` to `dst`, returning its index.
+    pub fn expect_transfer(&mut self, dst: &Address, amount: Amount) -> usize {
+        self.transfers.push(TransferExpectation {
+            dst: *dst,
+            amount,
+            result: Ok(()),
+            times: None,
+            made: 0,
+        });
+
+        self.transfers.len() - 1
+    }
+
+    pub fn set_transfer_result(&mut self, index: usize, result: Result<(), &str>) {
+        self.transfers[index].result = result.map_err(|err| err.to_string());
+    }
+
+    pub fn set_transfer_times(&mut self, index: usize, times: usize) {
+        self.transfers[index].times = Some(times);
+    }
+
+    /// Makes a transfer against the scripted expectations.
+    ///
+    /// Transfers are verified only once any of them is expected: then, an unexpected transfer panics
+    /// (unless verification is `Loose`). A transfer scripted to fail panics with the scripted message
+    /// (same as a failing transfer traps the running transaction).
+    pub fn transfer(&mut self, dst: &Address, amount: Amount) {
+        self.history.push(HostCall::Transfer { dst: *dst, amount });
+
+        if self.transfers.is_empty() {
+            return;
+        }
+
+        let expectation = self
+            .transfers
+            .iter_mut()
+            .find(|e| !e.exhausted() && &e.dst == dst && e.amount == amount);
+
+        match (expectation, self.verification) {
+            (Some(expectation), _) => {
+                expectation.made += 1;
+
+                if let Err(err) = &expectation.result {
+                    panic!("{}", err);
+                }
+            }
+            (None, Verification::Loose) => (),
+            (None, Verification::Strict) => {
+                panic!("Unexpected transfer of {:?} to Account `{:?}`", amount, dst)
+            }
+        }
+    }
+
+    /// Scripts the values returned by the next reads of variable `var_id` (one value per read).
+    ///
+    /// Once the scripted values are exhausted, the variable is read from the storage again.
+    pub fn script_get64(&mut self, var_id: u32, values: &[u64]) {
+        self.get64
+            .entry(var_id)
+            .or_default()
+            .extend(values.iter().copied());
+    }
+
+    /// Returns the next scripted value of variable `var_id` (if any).
+    pub fn scripted_get64(&mut self, var_id: u32) -> Option<u64> {
+        self.get64
+            .get_mut(&var_id)
+            .and_then(|values| values.pop_front())
+    }
+
+    /// Expects variable `var_id` to be set to `value` (at least once).
+    pub fn expect_set64(&mut self, var_id: u32, value: u64) {
+        self.set64.push(Set64Expectation {
+            var_id,
+            value,
+            made: 0,
+        });
+    }
+
+    /// Records a host function invocation which isn't scripted (e.g a storage access).
+    pub fn record(&mut self, call: HostCall) {
+        if let HostCall::Set64 { var_id, value } = call {
+            for e in self.set64.iter_mut() {
+                if e.var_id == var_id && e.value == value {
+                    e.made += 1;
+                }
+            }
+        }
+
+        self.history.push(call);
+    }
+
+    /// The host functions invoked so far (in order).
+    pub fn history(&self) -> Vec<HostCall> {
+        self.history.clone()
+    }
+
+    pub fn set_host_data(&mut self, key: &str, data: &[u8]) {
+        self.host_data.insert(key.to_string(), data.to_vec());
+    }
+
+    /// Returns the canned host data registered under `key`.
+    pub fn host_data(&self, key: &str) -> Option<Vec<u8>> {
+        let data = self.host_data.get(key).cloned();
+
+        if data.is_none() && self.verification == Verification::Strict {
+            panic!("Unexpected request of host data `{}`", key);
+        }
+
+        data
+    }
+
+    /// Panics unless the expected calls, transfers and storage writes have been made as expected
+    /// (see [`Verification`]).
+    pub fn verify(&self) {
+        if self.verification == Verification::Loose {
+            return;
+        }
+
+        for e in self.expectations.iter() {
+            match e.times {
+                Some(times) => assert_eq!(
+                    e.made, times,
+                    "Expected {} call(s) of `{}` (Account `{:?}`) but got {}",
+                    times, e.func, e.target, e.made
+                ),
+                None => assert!(
+                    e.made > 0,
+                    "Expected a call of `{}` (Account `{:?}`)",
+                    e.func,
+                    e.target
+                ),
+            }
+        }
+
+        for e in self.transfers.iter() {
+            match e.times {
+                Some(times) => assert_eq!(
+                    e.made, times,
+                    "Expected {} transfer(s) of {:?} to Account `{:?}` but got {}",
+                    times, e.amount, e.dst, e.made
+                ),
+                None => assert!(
+                    e.made > 0,
+                    "Expected a transfer of {:?} to Account `{:?}`",
+                    e.amount,
+                    e.dst
+                ),
+            }
+        }
+
+        for e in self.set64.iter() {
+            assert!(
+                e.made > 0,
+                "Expected variable #{} to be set to {}",
+                e.var_id,
+                e.value
+            );
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
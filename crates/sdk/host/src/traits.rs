@@ -1,10 +1,39 @@
+use svm_abi_encoder::{ByteSize, Encoder};
+use svm_sdk_std::Vec;
 use svm_sdk_types::{Address, Amount, LayerId};
 
 pub trait Host {
     fn calldata(&self) -> &'static [u8];
 
+    /// The binary `verifydata` of the running transaction.
+    ///
+    /// While running `svm_verify`, the `verifydata` is given as the function's input,
+    /// so it's exposed the same way as the `calldata`.
+    fn verifydata(&self) -> &'static [u8] {
+        self.calldata()
+    }
+
     fn set_returndata(&mut self, bytes: &[u8]);
 
+    /// Encodes `value` and sets it as the `returndata`.
+    ///
+    /// Values having an empty encoding (i.e `()`) don't set any `returndata`.
+    fn set_return_value<T>(&mut self, value: &T)
+    where
+        T: Encoder<Vec<u8>> + ByteSize,
+        Self: Sized,
+    {
+        let mut bytes = Vec::with_capacity(T::max_byte_size());
+
+        value.encode(&mut bytes);
+
+        if bytes.len() > 0 {
+            let bytes: &'static [u8] = bytes.leak();
+
+            self.set_returndata(bytes);
+        }
+    }
+
     fn principal(&self) -> Address;
 
     fn target(&self) -> Address;
@@ -13,6 +42,9 @@ pub trait Host {
 
     fn layer_id(&self) -> LayerId;
 
+    /// The id of the running transaction.
+    fn tx_id(&self) -> [u8; 32];
+
     fn balance(&self) -> Amount;
 
     fn transfer(&mut self, dst: &Address, amount: Amount);
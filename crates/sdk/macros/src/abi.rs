@@ -0,0 +1,169 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Result};
+
+use crate::r#type::parse_primitive_type;
+
+/// A user-defined struct is encoded as an ABI `Array` of its fields.
+/// The ABI `Small-Array` can hold at most 10 items.
+const MAX_FIELDS: usize = 10;
+
+struct AbiField {
+    name: Ident,
+    ty: syn::Type,
+}
+
+pub fn expand_encode(input: TokenStream) -> Result<TokenStream> {
+    let input: DeriveInput = syn::parse2(input)?;
+    let name = input.ident.clone();
+    let fields = abi_fields(&input, "AbiEncode")?;
+
+    let count = fields.len();
+    let encodes = fields.iter().map(|f| {
+        let name = &f.name;
+
+        quote! { svm_sdk::traits::Encoder::encode(&self.#name, w); }
+    });
+    let sizes = fields.iter().map(|f| {
+        let name = &f.name;
+
+        quote! { svm_sdk::traits::ByteSize::byte_size(&self.#name) }
+    });
+    let max_sizes = fields.iter().map(|f| {
+        let ty = &f.ty;
+
+        quote! { <#ty as svm_sdk::traits::ByteSize>::max_byte_size() }
+    });
+
+    let ast = quote! {
+        impl<W> svm_sdk::traits::Encoder<W> for #name
+        where
+            W: svm_sdk::traits::Push<Item = u8>,
+        {
+            fn encode(&self, w: &mut W) {
+                svm_sdk::abi::encode_array_header(#count, w);
+
+                #(#encodes)*
+            }
+        }
+
+        impl svm_sdk::traits::ByteSize for #name {
+            fn byte_size(&self) -> usize {
+                1 #(+ #sizes)*
+            }
+
+            fn max_byte_size() -> usize {
+                1 #(+ #max_sizes)*
+            }
+        }
+    };
+
+    Ok(ast)
+}
+
+pub fn expand_decode(input: TokenStream) -> Result<TokenStream> {
+    let input: DeriveInput = syn::parse2(input)?;
+    let name = input.ident.clone();
+    let fields = abi_fields(&input, "AbiDecode")?;
+
+    let count = fields.len();
+    let assigns = fields.iter().map(|f| {
+        let name = &f.name;
+
+        quote! {
+            #name: match values.next() {
+                core::option::Option::Some(value) => value.into(),
+                core::option::Option::None => svm_sdk::panic(),
+            },
+        }
+    });
+
+    let ast = quote! {
+        impl From<svm_sdk::value::Value> for #name {
+            fn from(value: svm_sdk::value::Value) -> Self {
+                use svm_sdk::value::{Composite, Value};
+
+                match value {
+                    Value::Composite(Composite::Vec(values)) => {
+                        svm_sdk::ensure!(values.len() == #count);
+
+                        let mut values = values.into_iter();
+
+                        Self {
+                            #(#assigns)*
+                        }
+                    }
+                    _ => svm_sdk::panic(),
+                }
+            }
+        }
+    };
+
+    Ok(ast)
+}
+
+fn abi_fields(input: &DeriveInput, derive: &str) -> Result<Vec<AbiField>> {
+    if !input.generics.params.is_empty() {
+        let msg = format!("`#[derive({})]` doesn't support generic structs.", derive);
+
+        return Err(Error::new_spanned(&input.generics, msg));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                let msg = format!("`#[derive({})]` struct must have named fields.", derive);
+
+                return Err(Error::new(input.ident.span(), msg));
+            }
+        },
+        _ => {
+            let msg = format!("`#[derive({})]` can be used only for structs.", derive);
+
+            return Err(Error::new(input.ident.span(), msg));
+        }
+    };
+
+    if fields.len() > MAX_FIELDS {
+        let msg = format!(
+            "`#[derive({})]` struct can have at most {} fields.",
+            derive, MAX_FIELDS
+        );
+
+        return Err(Error::new(input.ident.span(), msg));
+    }
+
+    let mut abi_fields = Vec::with_capacity(fields.len());
+
+    for field in fields {
+        if !is_abi_field_type(&field.ty) {
+            let msg = format!(
+                "`#[derive({})]` supports only Primitive, struct (deriving `{}` as well), `Option` and Array field types.",
+                derive, derive
+            );
+
+            return Err(Error::new_spanned(&field.ty, msg));
+        }
+
+        abi_fields.push(AbiField {
+            name: field.ident.clone().unwrap(),
+            ty: field.ty.clone(),
+        });
+    }
+
+    Ok(abi_fields)
+}
+
+/// A field may be nested: another user-defined struct (encoded as an ABI `Array` of its own fields),
+/// an `Option` (encoded as the ABI `None` when missing) or an Array of Primitives.
+fn is_abi_field_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(..) => true,
+        syn::Type::Array(array) => match &*array.elem {
+            syn::Type::Path(path) => parse_primitive_type(path).is_ok(),
+            _ => false,
+        },
+        _ => false,
+    }
+}
@@ -0,0 +1,108 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{Error, Expr, ExprLit, ExprUnary, ItemConst, Lit, Result, UnOp};
+
+/// A compile-time constant declared inside `#[template]`.
+///
+/// It's hoisted as-is into the expanded module, so it can be used by the functions' bodies.
+/// A constant of an integer type can also be used as an Array length (of a `#[storage]` field
+/// or of an `endpoint` param).
+pub struct Const {
+    raw_const: ItemConst,
+    value: ConstValue,
+}
+
+pub enum ConstValue {
+    Int(i128),
+    Bool(bool),
+    Str(String),
+}
+
+impl Const {
+    pub fn new(raw_const: ItemConst) -> Result<Self> {
+        let value = const_value(&raw_const)?;
+
+        Ok(Self { raw_const, value })
+    }
+
+    pub fn raw_name(&self) -> Ident {
+        self.raw_const.ident.clone()
+    }
+
+    pub fn value(&self) -> &ConstValue {
+        &self.value
+    }
+}
+
+/// Returns the value of the integer constant named `name` (if any).
+pub fn find_int_const(consts: &[Const], name: &str) -> Option<i128> {
+    consts
+        .iter()
+        .find(|c| c.raw_name() == name)
+        .and_then(|c| match c.value() {
+            ConstValue::Int(value) => Some(*value),
+            _ => None,
+        })
+}
+
+pub fn expand(c: &Const) -> Result<TokenStream> {
+    let raw_const = &c.raw_const;
+
+    Ok(quote! { #raw_const })
+}
+
+/// Parses the value of a constant, which must be a literal matching its type:
+/// an integer (possibly negated, for a signed type), a `bool` or a `&str`.
+fn const_value(raw_const: &ItemConst) -> Result<ConstValue> {
+    let ty = &raw_const.ty;
+    let ty_str = quote! { #ty }.to_string();
+
+    let (neg, lit) = match &*raw_const.expr {
+        Expr::Lit(ExprLit { lit, .. }) => (false, lit),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(..),
+            expr,
+            ..
+        }) => match &**expr {
+            Expr::Lit(ExprLit { lit, .. }) => (true, lit),
+            _ => return Err(invalid_value(raw_const)),
+        },
+        _ => return Err(invalid_value(raw_const)),
+    };
+
+    let value = match (ty_str.as_str(), lit) {
+        ("bool", Lit::Bool(b)) if !neg => ConstValue::Bool(b.value),
+        ("& str" | "& 'static str", Lit::Str(s)) if !neg => ConstValue::Str(s.value()),
+        (
+            "u8" | "u16" | "u32" | "u64" | "usize" | "i8" | "i16" | "i32" | "i64" | "isize",
+            Lit::Int(int),
+        ) => {
+            if neg && ty_str.starts_with('u') {
+                return Err(invalid_value(raw_const));
+            }
+
+            let value = int.base10_parse::<i128>()?;
+
+            ConstValue::Int(if neg { -value } else { value })
+        }
+        ("bool" | "& str" | "& 'static str", _) => return Err(invalid_value(raw_const)),
+        _ => {
+            let msg =
+                "`const` inside `#[template]` must be of an integer type, a `bool` or a `&str`.";
+
+            return Err(Error::new_spanned(ty, msg));
+        }
+    };
+
+    Ok(value)
+}
+
+fn invalid_value(raw_const: &ItemConst) -> Error {
+    let ty = &raw_const.ty;
+    let msg = format!(
+        "`const` inside `#[template]` must be given a literal of type `{}`.",
+        quote! { #ty }
+    );
+
+    Error::new_spanned(&raw_const.expr, msg)
+}
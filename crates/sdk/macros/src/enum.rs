@@ -0,0 +1,235 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{Error, Expr, ExprLit, Fields, ItemEnum, Lit, Result};
+
+/// A C-like `enum` (having only unit variants) declared inside `#[template]`.
+///
+/// It's passed through the ABI (and persisted by `#[storage]`) as the integer value of its variant,
+/// i.e a `u8` or a `u16` (see [`EnumRepr`]).
+pub struct Enum {
+    raw_enum: ItemEnum,
+    repr: EnumRepr,
+    variants: Vec<(Ident, u16)>,
+}
+
+/// The integer type an [`Enum`] is represented by (the smallest one fitting all its variants).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum EnumRepr {
+    U8,
+    U16,
+}
+
+impl EnumRepr {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EnumRepr::U8 => "u8",
+            EnumRepr::U16 => "u16",
+        }
+    }
+
+    pub fn byte_count(&self) -> usize {
+        match self {
+            EnumRepr::U8 => 1,
+            EnumRepr::U16 => 2,
+        }
+    }
+
+    fn ty_ast(&self) -> TokenStream {
+        match self {
+            EnumRepr::U8 => quote! { u8 },
+            EnumRepr::U16 => quote! { u16 },
+        }
+    }
+
+    fn value_ast(&self, value: u16) -> TokenStream {
+        match self {
+            EnumRepr::U8 => {
+                let value = value as u8;
+
+                quote! { #value }
+            }
+            EnumRepr::U16 => quote! { #value },
+        }
+    }
+}
+
+impl Enum {
+    pub fn new(raw_enum: ItemEnum) -> Result<Self> {
+        let variants = enum_variants(&raw_enum)?;
+
+        let max = variants.iter().map(|(_, value)| *value).max().unwrap_or(0);
+        let repr = if max <= u8::MAX as u16 {
+            EnumRepr::U8
+        } else {
+            EnumRepr::U16
+        };
+
+        Ok(Self {
+            raw_enum,
+            repr,
+            variants,
+        })
+    }
+
+    pub fn raw_name(&self) -> Ident {
+        self.raw_enum.ident.clone()
+    }
+
+    pub fn repr(&self) -> EnumRepr {
+        self.repr
+    }
+
+    /// The variants (ordered as declared) along with their values.
+    pub fn variants(&self) -> &[(Ident, u16)] {
+        &self.variants
+    }
+}
+
+/// Returns the `enum` named `name` (if any).
+pub fn find_enum<'a>(enums: &'a [Enum], name: &str) -> Option<&'a Enum> {
+    enums.iter().find(|e| e.raw_name() == name)
+}
+
+/// Emits the `enum` as-is, along with:
+///
+/// * Its conversions into and from its [`EnumRepr`] (an unknown value panics).
+/// * `Encoder` and `ByteSize` (encoding it as its [`EnumRepr`] value).
+/// * `From<Value>` (decoding it from its [`EnumRepr`] value).
+pub fn expand(e: &Enum) -> Result<TokenStream> {
+    let raw_enum = &e.raw_enum;
+    let name = e.raw_name();
+    let repr = e.repr().ty_ast();
+
+    let into_arms = e.variants().iter().map(|(variant, value)| {
+        let value = e.repr().value_ast(*value);
+
+        quote! { #name::#variant => #value, }
+    });
+
+    let from_arms = e.variants().iter().map(|(variant, value)| {
+        let value = e.repr().value_ast(*value);
+
+        quote! { #value => #name::#variant, }
+    });
+
+    let ast = quote! {
+        #raw_enum
+
+        impl From<&#name> for #repr {
+            fn from(value: &#name) -> #repr {
+                match value {
+                    #(#into_arms)*
+                }
+            }
+        }
+
+        impl From<#repr> for #name {
+            fn from(value: #repr) -> #name {
+                match value {
+                    #(#from_arms)*
+                    _ => svm_sdk::panic(),
+                }
+            }
+        }
+
+        impl<W> svm_sdk::traits::Encoder<W> for #name
+        where
+            W: svm_sdk::traits::Push<Item = u8>,
+        {
+            fn encode(&self, w: &mut W) {
+                let value: #repr = self.into();
+
+                svm_sdk::traits::Encoder::encode(&value, w);
+            }
+        }
+
+        impl svm_sdk::traits::ByteSize for #name {
+            fn byte_size(&self) -> usize {
+                let value: #repr = self.into();
+
+                svm_sdk::traits::ByteSize::byte_size(&value)
+            }
+
+            fn max_byte_size() -> usize {
+                <#repr as svm_sdk::traits::ByteSize>::max_byte_size()
+            }
+        }
+
+        impl From<svm_sdk::value::Value> for #name {
+            fn from(value: svm_sdk::value::Value) -> Self {
+                let value: #repr = value.into();
+
+                value.into()
+            }
+        }
+    };
+
+    Ok(ast)
+}
+
+/// Returns the variants of a C-like `enum` along with their values.
+///
+/// A variant without an explicit discriminant is valued one more than the previous one
+/// (the first variant is valued `0`), same as Rust does.
+fn enum_variants(raw_enum: &ItemEnum) -> Result<Vec<(Ident, u16)>> {
+    if !raw_enum.generics.params.is_empty() {
+        let msg = "`enum` inside `#[template]` can't be generic.";
+
+        return Err(Error::new_spanned(&raw_enum.generics, msg));
+    }
+
+    if raw_enum.variants.is_empty() {
+        let msg = "`enum` inside `#[template]` must have at least a single variant.";
+
+        return Err(Error::new(raw_enum.ident.span(), msg));
+    }
+
+    let mut variants = Vec::with_capacity(raw_enum.variants.len());
+    let mut next: u32 = 0;
+
+    for variant in raw_enum.variants.iter() {
+        if !matches!(variant.fields, Fields::Unit) {
+            let msg = "`enum` inside `#[template]` supports only unit variants (for example: `enum Color { Red, Green }`).";
+
+            return Err(Error::new_spanned(variant, msg));
+        }
+
+        let value = match &variant.discriminant {
+            Some((
+                _,
+                Expr::Lit(ExprLit {
+                    lit: Lit::Int(int), ..
+                }),
+            )) => int.base10_parse::<u32>()?,
+            Some((_, expr)) => {
+                let msg = "Invalid `enum` discriminant (expected a non-negative integer literal).";
+
+                return Err(Error::new_spanned(expr, msg));
+            }
+            None => next,
+        };
+
+        if value > u16::MAX as u32 {
+            let msg = format!(
+                "`enum` discriminants must fit into a `u16` (got {}).",
+                value
+            );
+
+            return Err(Error::new_spanned(variant, msg));
+        }
+
+        if let Some((other, _)) = variants.iter().find(|(_, v)| *v == value as u16) {
+            let msg = format!(
+                "`enum` discriminant {} is already used by variant `{}`.",
+                value, other
+            );
+
+            return Err(Error::new_spanned(variant, msg));
+        }
+
+        variants.push((variant.ident.clone(), value as u16));
+        next = value + 1;
+    }
+
+    Ok(variants)
+}
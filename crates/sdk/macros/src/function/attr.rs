@@ -49,6 +49,7 @@ impl Parse for Doc {
 pub enum FuncAttrKind {
     Ctor,
     Endpoint,
+    Fallback,
     Fundable,
     FundableHook,
     Other,
@@ -58,6 +59,7 @@ pub enum FuncAttrKind {
 pub enum FuncAttr {
     Ctor(Doc),
     Endpoint(Doc),
+    Fallback(Doc),
     Fundable(Option<String>),
     FundableHook { default: bool },
     Other(TokenStream),
@@ -68,6 +70,7 @@ impl FuncAttr {
         match *self {
             FuncAttr::Ctor(..) => FuncAttrKind::Ctor,
             FuncAttr::Endpoint(..) => FuncAttrKind::Endpoint,
+            FuncAttr::Fallback(..) => FuncAttrKind::Fallback,
             FuncAttr::FundableHook { .. } => FuncAttrKind::FundableHook,
             FuncAttr::Fundable(..) => FuncAttrKind::Fundable,
             FuncAttr::Other(..) => FuncAttrKind::Other,
@@ -106,6 +109,10 @@ pub fn parse_attr(attr: Attribute) -> Result<FuncAttr> {
             let doc = parse_doc(&attr)?;
             FuncAttr::Endpoint(doc)
         }
+        FuncAttrKind::Fallback => {
+            let doc = parse_doc(&attr)?;
+            FuncAttr::Fallback(doc)
+        }
         FuncAttrKind::FundableHook => {
             if attr.tokens.is_empty() {
                 FuncAttr::FundableHook { default: false }
@@ -129,6 +136,15 @@ pub fn parse_attr(attr: Attribute) -> Result<FuncAttr> {
     Ok(attr)
 }
 
+/// Returns the first raw attribute of `func` of the given `kind` (if any).
+///
+/// Used for reporting errors at the offending attribute.
+pub fn find_raw_attr(func: &Function, kind: FuncAttrKind) -> Option<Attribute> {
+    func.raw_attrs()
+        .into_iter()
+        .find(|attr| matches!(parse_attr_kind(attr), Ok(k) if k == kind))
+}
+
 fn parse_attr_kind(attr: &Attribute) -> Result<FuncAttrKind> {
     let mut tokens = TokenStream::new();
 
@@ -145,6 +161,7 @@ impl Parse for FuncAttrKind {
         let kind = match ident.to_string().as_str() {
             "ctor" => FuncAttrKind::Ctor,
             "endpoint" => FuncAttrKind::Endpoint,
+            "fallback" => FuncAttrKind::Fallback,
             "fundable" => FuncAttrKind::Fundable,
             "fundable_hook" => FuncAttrKind::FundableHook,
             _ => FuncAttrKind::Other,
@@ -166,6 +183,10 @@ pub fn has_endpoint_attr(attrs: &[FuncAttr]) -> bool {
     has_attr(attrs, FuncAttrKind::Endpoint)
 }
 
+pub fn has_fallback_attr(attrs: &[FuncAttr]) -> bool {
+    has_attr(attrs, FuncAttrKind::Fallback)
+}
+
 pub fn has_fundable_hook_attr(attrs: &[FuncAttr]) -> bool {
     has_attr(attrs, FuncAttrKind::FundableHook)
 }
@@ -222,6 +243,21 @@ mod test {
         assert_eq!(func_attr.kind(), FuncAttrKind::Endpoint);
     }
 
+    #[test]
+    fn func_attr_fallback() {
+        let attr: Attribute = parse_quote! {
+            #[fallback(doc = "Forwards any call")]
+        };
+
+        let func_attr = parse_attr(attr).unwrap();
+        assert_eq!(func_attr.kind(), FuncAttrKind::Fallback);
+
+        match func_attr {
+            FuncAttr::Fallback(doc) => assert_eq!(doc.to_string(), "Forwards any call"),
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn func_attr_fundable_hook() {
         let attr: Attribute = parse_quote! {
@@ -1,4 +1,4 @@
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::{Error, FnArg, Pat, PatType, Result, ReturnType, Type};
 
@@ -95,52 +95,17 @@ fn expand_prologue(func: &Function) -> Result<TokenStream> {
     Ok(ast)
 }
 
-fn expand_returns_size(func: &Function) -> Result<TokenStream> {
-    let sig = func.raw_sig();
-    let mut ty_tokens = TokenStream::new();
-
-    match &sig.output {
-        ReturnType::Type(.., ty) => ty.to_tokens(&mut ty_tokens),
-        ReturnType::Default => unreachable!(),
-    };
-
-    // We derive in compile-time the byte-capacity to be fed into the `returndata` buffer
-    // (when doing `Vec::with_capacity(..)`)
-    let ast = quote! {
-        {
-            use svm_sdk::traits::ByteSize;
-
-            < #ty_tokens > :: max_byte_size()
-        }
-    };
-
-    Ok(ast)
-}
-
-fn expand_epilogue(func: &Function) -> Result<TokenStream> {
+pub fn expand_epilogue(func: &Function) -> Result<TokenStream> {
     let ast = if func.has_returns() {
         let includes = function::host_includes();
-        let returns_size = expand_returns_size(func)?;
 
         quote! {
             {
                 #includes
 
-                use svm_sdk::traits::Encoder;
-
                 let returns = __inner__();
 
-                let capacity = #returns_size;
-
-                let mut bytes: svm_sdk::Vec<u8> = svm_sdk::Vec::with_capacity(capacity);
-
-                returns.encode(&mut bytes);
-
-                if bytes.len() > 0 {
-                    let bytes: &'static [u8] = bytes.leak();
-
-                    Node.set_returndata(bytes);
-                }
+                Node.set_return_value(&returns);
             }
         }
     } else {
@@ -156,7 +121,7 @@ fn expand_epilogue(func: &Function) -> Result<TokenStream> {
     Ok(ast)
 }
 
-fn expand_returns(func: &Function) -> Result<TokenStream> {
+pub fn expand_returns(func: &Function) -> Result<TokenStream> {
     let mut tokens = TokenStream::new();
 
     let sig = func.raw_sig();
@@ -167,40 +132,47 @@ fn expand_returns(func: &Function) -> Result<TokenStream> {
 
 fn validate_sig(func: &Function) -> Result<()> {
     let sig = func.raw_sig();
-    let span = Span::call_site();
 
-    if sig.constness.is_some() {
-        return Err(Error::new(span, "`endpoint` function can't be `const`"));
+    if let Some(constness) = &sig.constness {
+        let msg = "`endpoint` function can't be `const`";
+        return Err(Error::new_spanned(constness, msg));
     }
 
-    if sig.asyncness.is_some() {
-        return Err(Error::new(span, "`endpoint` function can't be `async`"));
+    if let Some(asyncness) = &sig.asyncness {
+        let msg = "`endpoint` function can't be `async`";
+        return Err(Error::new_spanned(asyncness, msg));
     }
 
-    if sig.unsafety.is_some() {
-        return Err(Error::new(span, "`endpoint` function can't be `unsafe`"));
+    if let Some(unsafety) = &sig.unsafety {
+        let msg = "`endpoint` function can't be `unsafe`";
+        return Err(Error::new_spanned(unsafety, msg));
     }
 
-    if sig.abi.is_some() {
-        return Err(Error::new(span, "`endpoint` function can't be `extern`"));
+    if let Some(abi) = &sig.abi {
+        let msg = "`endpoint` function can't be `extern`";
+        return Err(Error::new_spanned(abi, msg));
     }
 
     if !sig.generics.params.is_empty() {
-        return Err(Error::new(span, "`endpoint` function can't use generics."));
+        let msg = "`endpoint` function can't use generics.";
+        return Err(Error::new_spanned(&sig.generics, msg));
     }
 
-    if sig.variadic.is_some() {
-        return Err(Error::new(span, "`endpoint` function can't use variadics."));
+    if let Some(variadic) = &sig.variadic {
+        let msg = "`endpoint` function can't use variadics.";
+        return Err(Error::new_spanned(variadic, msg));
     }
 
-    if sig.receiver().is_some() {
-        return Err(Error::new(span, "`endpoint` function can't use `self`"));
+    if let Some(receiver) = sig.receiver() {
+        let msg = "`endpoint` function can't use `self`";
+        return Err(Error::new_spanned(receiver, msg));
     }
 
     for arg in &sig.inputs {
         if let FnArg::Typed(PatType { attrs, pat, ty, .. }) = arg {
-            if !attrs.is_empty() {
-                return Err(Error::new(span, "`endpoint` params can't have attributes."));
+            if let Some(attr) = attrs.first() {
+                let msg = "`endpoint` params can't have attributes.";
+                return Err(Error::new_spanned(attr, msg));
             }
 
             validate_arg_pat(pat)?;
@@ -218,110 +190,100 @@ fn validate_sig(func: &Function) -> Result<()> {
 fn validate_arg_pat(pat: &Box<Pat>) -> Result<()> {
     match **pat {
         Pat::Ident(..) => Ok(()),
-        _ => {
-            let span = Span::call_site();
-
-            Err(Error::new(
-                span,
-                "`endpoint` parameters definitions are expected to be of pattern: `name: type`",
-            ))
-        }
+        _ => Err(Error::new_spanned(
+            pat,
+            "`endpoint` parameters definitions are expected to be of pattern: `name: type`",
+        )),
     }
 }
 
 fn validate_arg_type(ty: &Box<Type>) -> Result<()> {
-    let span = Span::call_site();
-
     match **ty {
-        Type::BareFn(..) => Err(Error::new(
-            span,
+        Type::BareFn(..) => Err(Error::new_spanned(
+            ty,
             "`endpoint` can't have a bare function as a parameter type",
         )),
-        Type::ImplTrait(..) => Err(Error::new(
-            span,
+        Type::ImplTrait(..) => Err(Error::new_spanned(
+            ty,
             "`endpoint` can't use an `impl` for its parameters types",
         )),
-        Type::Macro(..) => Err(Error::new(
-            span,
+        Type::Macro(..) => Err(Error::new_spanned(
+            ty,
             "`endpoint` can't use an macros within it parameters types",
         )),
-        Type::Never(..) => Err(Error::new(
-            span,
+        Type::Never(..) => Err(Error::new_spanned(
+            ty,
             "`endpoint` can't use `!` for its parameters types",
         )),
-        Type::Paren(..) => Err(Error::new(
-            span,
+        Type::Paren(..) => Err(Error::new_spanned(
+            ty,
             "`endpoint` can't use parentheses for its parameters types",
         )),
-        Type::Ptr(..) => Err(Error::new(
-            span,
+        Type::Ptr(..) => Err(Error::new_spanned(
+            ty,
             "`endpoint` can't use raw_func pointers for its parameters types",
         )),
-        Type::Reference(..) => Err(Error::new(
-            span,
+        Type::Reference(..) => Err(Error::new_spanned(
+            ty,
             "`endpoint` can't use references for its parameters types",
         )),
-        Type::Slice(..) => Err(Error::new(
-            span,
+        Type::Slice(..) => Err(Error::new_spanned(
+            ty,
             "`endpoint` can't use dynamically sized slices for its parameters types",
         )),
-        Type::TraitObject(..) => Err(Error::new(
-            span,
+        Type::TraitObject(..) => Err(Error::new_spanned(
+            ty,
             "`endpoint` can't use trait objects for its parameters types",
         )),
-        Type::Tuple(..) => Err(Error::new(
-            span,
+        Type::Tuple(..) => Err(Error::new_spanned(
+            ty,
             "`endpoint` can't use right now tuples for its parameters types",
         )),
         _ => Ok(()),
     }
 }
 
-fn validate_ret_type(ty: &ReturnType) -> Result<()> {
+pub fn validate_ret_type(ty: &ReturnType) -> Result<()> {
     match ty {
         ReturnType::Default => Ok(()),
-        ReturnType::Type(.., ty) => {
-            let span = Span::call_site();
-
-            match **ty {
-                Type::BareFn(..) => Err(Error::new(
-                    span,
-                    "`endpoint` can't have a bare function as a return type",
-                )),
-                Type::ImplTrait(..) => Err(Error::new(
-                    span,
-                    "`endpoint` can't use an `impl` for its return type",
-                )),
-                Type::Macro(..) => Err(Error::new(
-                    span,
-                    "`endpoint` can't use an macros for its return type",
-                )),
-                Type::Never(..) => Err(Error::new(
-                    span,
-                    "`endpoint` can't use `!` for its parameters types",
-                )),
-                Type::Paren(..) => Err(Error::new(
-                    span,
-                    "`endpoint` can't use parentheses for its parameters types",
-                )),
-                Type::Ptr(..) => Err(Error::new(
-                    span,
-                    "`endpoint` can't use raw_func pointers for its parameters types",
-                )),
-                Type::Reference(..) => Err(Error::new(
-                    span,
-                    "`endpoint` can't use reference for its parameters types",
-                )),
-                Type::Slice(..) => Err(Error::new(
-                    span,
-                    "`endpoint` can't use dynamically-sized slices for its parameters types",
-                )),
-                Type::TraitObject(..) => Err(Error::new(
-                    span,
-                    "`endpoint` can't use trait objects for its parameters types",
-                )),
-                _ => Ok(()),
-            }
-        }
+        ReturnType::Type(.., ty) => match **ty {
+            Type::BareFn(..) => Err(Error::new_spanned(
+                ty,
+                "`endpoint` can't have a bare function as a return type",
+            )),
+            Type::ImplTrait(..) => Err(Error::new_spanned(
+                ty,
+                "`endpoint` can't use an `impl` for its return type",
+            )),
+            Type::Macro(..) => Err(Error::new_spanned(
+                ty,
+                "`endpoint` can't use an macros for its return type",
+            )),
+            Type::Never(..) => Err(Error::new_spanned(
+                ty,
+                "`endpoint` can't use `!` for its parameters types",
+            )),
+            Type::Paren(..) => Err(Error::new_spanned(
+                ty,
+                "`endpoint` can't use parentheses for its parameters types",
+            )),
+            Type::Ptr(..) => Err(Error::new_spanned(
+                ty,
+                "`endpoint` can't use raw_func pointers for its parameters types",
+            )),
+            Type::Reference(..) => Err(Error::new_spanned(
+                ty,
+                "`endpoint` can't use reference for its parameters types",
+            )),
+            Type::Slice(..) => Err(Error::new_spanned(
+                ty,
+                "`endpoint` can't use dynamically-sized slices for its parameters types",
+            )),
+            Type::TraitObject(..) => Err(Error::new_spanned(
+                ty,
+                "`endpoint` can't use trait objects for its parameters types",
+            )),
+            _ => Ok(()),
+        },
     }
 }
@@ -0,0 +1,131 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Error, FnArg, Pat, PatType, Result};
+
+use super::{attr, endpoint};
+use attr::{has_fallback_attr, FuncAttr};
+
+use crate::Function;
+
+pub fn expand(func: &Function, attrs: &[FuncAttr]) -> Result<TokenStream> {
+    debug_assert!(has_fallback_attr(attrs));
+
+    validate_sig(func)?;
+
+    let name = func.raw_name();
+    let prologue = expand_prologue(func)?;
+    let epilogue = endpoint::expand_epilogue(func)?;
+    let returns = endpoint::expand_returns(func)?;
+    let body = func.raw_body();
+
+    fn func_attrs(func: &Function) -> TokenStream {
+        if cfg!(target_arch = "wasm32") {
+            let export_name = func.export_name();
+
+            quote! { #[export_name = #export_name] }
+        } else {
+            quote! { #[no_mangle] }
+        }
+    }
+
+    let attrs = func_attrs(func);
+
+    let ast = quote! {
+        #attrs
+        pub extern "C" fn #name() {
+            fn __inner__() #returns {
+                #prologue
+
+                #body
+            }
+
+            #epilogue
+        }
+    };
+
+    Ok(ast)
+}
+
+fn expand_prologue(func: &Function) -> Result<TokenStream> {
+    let sig = func.raw_sig();
+
+    let mut params = sig.inputs.iter().map(|input| {
+        if let FnArg::Typed(PatType { pat, ty, .. }) = input {
+            (pat, ty)
+        } else {
+            unreachable!()
+        }
+    });
+
+    let (func_pat, func_ty) = params.next().unwrap();
+    let (calldata_pat, calldata_ty) = params.next().unwrap();
+
+    let ast = quote! {
+        let (func, calldata) = svm_sdk::host::fallback_args();
+
+        let #func_pat: #func_ty = func;
+        let #calldata_pat: #calldata_ty = calldata;
+    };
+
+    Ok(ast)
+}
+
+fn validate_sig(func: &Function) -> Result<()> {
+    let sig = func.raw_sig();
+
+    if let Some(constness) = &sig.constness {
+        let msg = "`fallback` function can't be `const`";
+        return Err(Error::new_spanned(constness, msg));
+    }
+
+    if let Some(asyncness) = &sig.asyncness {
+        let msg = "`fallback` function can't be `async`";
+        return Err(Error::new_spanned(asyncness, msg));
+    }
+
+    if let Some(unsafety) = &sig.unsafety {
+        let msg = "`fallback` function can't be `unsafe`";
+        return Err(Error::new_spanned(unsafety, msg));
+    }
+
+    if let Some(abi) = &sig.abi {
+        let msg = "`fallback` function can't be `extern`";
+        return Err(Error::new_spanned(abi, msg));
+    }
+
+    if !sig.generics.params.is_empty() {
+        let msg = "`fallback` function can't use generics.";
+        return Err(Error::new_spanned(&sig.generics, msg));
+    }
+
+    if let Some(variadic) = &sig.variadic {
+        let msg = "`fallback` function can't use variadics.";
+        return Err(Error::new_spanned(variadic, msg));
+    }
+
+    if let Some(receiver) = sig.receiver() {
+        let msg = "`fallback` function can't use `self`";
+        return Err(Error::new_spanned(receiver, msg));
+    }
+
+    let msg = "`#[fallback]` annotated function should have signature of `fn(func: &str, calldata: &[u8])`";
+
+    if sig.inputs.len() != 2 {
+        return Err(Error::new_spanned(sig, msg));
+    }
+
+    for arg in &sig.inputs {
+        match arg {
+            FnArg::Typed(PatType { attrs, pat, .. }) if attrs.is_empty() => {
+                if !matches!(**pat, Pat::Ident(..)) {
+                    return Err(Error::new_spanned(pat, msg));
+                }
+            }
+            _ => return Err(Error::new_spanned(arg, msg)),
+        }
+    }
+
+    endpoint::validate_ret_type(&sig.output)?;
+
+    Ok(())
+}
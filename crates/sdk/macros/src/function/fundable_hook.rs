@@ -1,4 +1,4 @@
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Error, Result, ReturnType};
 
@@ -40,11 +40,10 @@ pub fn expand_default() -> Result<TokenStream> {
 
 fn validate_fundable_hook_func_sig(func: &Function) -> Result<()> {
     let sig = func.raw_sig();
-    let span = Span::call_site();
 
     if sig.inputs.len() != 0 || matches!(sig.output, ReturnType::Default) == false {
         let msg = "`#[fundable_hook]` annotated function should have signature of `fn() -> ()`";
-        return Err(Error::new(span, msg));
+        return Err(Error::new_spanned(sig, msg));
     }
 
     Ok(())
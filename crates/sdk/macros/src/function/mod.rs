@@ -1,17 +1,18 @@
-use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro2::{Ident, TokenStream};
 use quote::{quote, ToTokens};
 use syn::{Attribute, Block, Error, ItemFn, Result, ReturnType, Signature};
 
 mod attr;
 mod ctor;
 mod endpoint;
+mod fallback;
 mod fundable;
 pub mod fundable_hook;
 
 use crate::Template;
 pub use attr::{
-    find_attr, func_attrs, has_ctor_attr, has_default_fundable_hook_attr, has_endpoint_attr,
-    has_fundable_attr, has_fundable_hook_attr,
+    find_attr, find_raw_attr, func_attrs, has_ctor_attr, has_default_fundable_hook_attr,
+    has_endpoint_attr, has_fallback_attr, has_fundable_attr, has_fundable_hook_attr,
 };
 pub use attr::{FuncAttr, FuncAttrKind};
 
@@ -67,12 +68,14 @@ impl Function {
 pub fn expand(func: &Function, template: &Template) -> Result<TokenStream> {
     let attrs = func_attrs(func)?;
 
-    validate_attrs(&attrs)?;
+    validate_attrs(func, &attrs)?;
 
     let ast = if has_ctor_attr(&attrs) {
         ctor::expand(func, &attrs, template)?
     } else if has_endpoint_attr(&attrs) {
         endpoint::expand(func, &attrs, template)?
+    } else if has_fallback_attr(&attrs) {
+        fallback::expand(func, &attrs)?
     } else if has_fundable_hook_attr(&attrs) {
         fundable_hook::expand(func, &attrs)?
     } else {
@@ -84,10 +87,15 @@ pub fn expand(func: &Function, template: &Template) -> Result<TokenStream> {
     Ok(ast)
 }
 
-fn validate_attrs(attrs: &[FuncAttr]) -> Result<()> {
-    validate_attrs_no_dups(attrs)?;
-    validate_attrs_usage(attrs)?;
-    validate_attrs_order(attrs)?;
+/// Validates the parsed `attrs` of `func`.
+///
+/// Errors are reported at the offending raw attribute of `func`.
+fn validate_attrs(func: &Function, attrs: &[FuncAttr]) -> Result<()> {
+    let raw_attrs = func.raw_attrs();
+
+    validate_attrs_no_dups(attrs, &raw_attrs)?;
+    validate_attrs_usage(attrs, &raw_attrs)?;
+    validate_attrs_order(attrs, &raw_attrs)?;
 
     Ok(())
 }
@@ -114,20 +122,19 @@ pub fn expand_func(func: &Function, _attrs: &[FuncAttr]) -> Result<TokenStream>
     Ok(ast)
 }
 
-fn validate_attrs_no_dups(attrs: &[FuncAttr]) -> Result<()> {
-    let span = Span::call_site();
-
+fn validate_attrs_no_dups(attrs: &[FuncAttr], raw_attrs: &[Attribute]) -> Result<()> {
     let mut seen_ctor = false;
     let mut seen_endpoint = false;
+    let mut seen_fallback = false;
     let mut seen_fundable = false;
     let mut seen_fundable_hook = false;
 
-    for attr in attrs {
+    for (attr, raw_attr) in attrs.iter().zip(raw_attrs) {
         match attr.kind() {
             FuncAttrKind::Ctor => {
                 if seen_ctor {
-                    return Err(Error::new(
-                        span,
+                    return Err(Error::new_spanned(
+                        raw_attr,
                         "Each function can be annotated with `#[ctor]` exactly once.",
                     ));
                 }
@@ -136,17 +143,26 @@ fn validate_attrs_no_dups(attrs: &[FuncAttr]) -> Result<()> {
 
             FuncAttrKind::Endpoint => {
                 if seen_endpoint {
-                    return Err(Error::new(
-                        span,
+                    return Err(Error::new_spanned(
+                        raw_attr,
                         "Each function can be annotated with `#[endpoint]` exactly once.",
                     ));
                 }
                 seen_endpoint = true;
             }
+            FuncAttrKind::Fallback => {
+                if seen_fallback {
+                    return Err(Error::new_spanned(
+                        raw_attr,
+                        "Each function can be annotated with `#[fallback]` exactly once.",
+                    ));
+                }
+                seen_fallback = true;
+            }
             FuncAttrKind::FundableHook => {
                 if seen_fundable_hook {
-                    return Err(Error::new(
-                        span,
+                    return Err(Error::new_spanned(
+                        raw_attr,
                         "Each function can be annotated with `#[fundable_hook]` exactly once.",
                     ));
                 }
@@ -154,8 +170,8 @@ fn validate_attrs_no_dups(attrs: &[FuncAttr]) -> Result<()> {
             }
             FuncAttrKind::Fundable => {
                 if seen_fundable {
-                    return Err(Error::new(
-                        span,
+                    return Err(Error::new_spanned(
+                        raw_attr,
                         "Each function can be annotated with `#[fundable(..)]` exactly once.",
                     ));
                 }
@@ -168,76 +184,81 @@ fn validate_attrs_no_dups(attrs: &[FuncAttr]) -> Result<()> {
     Ok(())
 }
 
-fn validate_attrs_usage(attrs: &[FuncAttr]) -> Result<()> {
-    let span = Span::call_site();
-    let mut seen_ctor = false;
-    let mut seen_endpoint = false;
-    let mut seen_fundable = false;
-    let mut seen_fundable_hook = false;
+fn validate_attrs_usage(attrs: &[FuncAttr], raw_attrs: &[Attribute]) -> Result<()> {
+    let position = |kind| attrs.iter().position(|attr| attr.kind() == kind);
 
-    for attr in attrs {
-        match attr.kind() {
-            FuncAttrKind::Ctor => seen_ctor = true,
-            FuncAttrKind::Endpoint => seen_endpoint = true,
-            FuncAttrKind::FundableHook => seen_fundable_hook = true,
-            FuncAttrKind::Fundable => seen_fundable = true,
-            FuncAttrKind::Other => continue,
-        }
-    }
+    let ctor = position(FuncAttrKind::Ctor);
+    let endpoint = position(FuncAttrKind::Endpoint);
+    let fallback = position(FuncAttrKind::Fallback);
+    let fundable = position(FuncAttrKind::Fundable);
+    let fundable_hook = position(FuncAttrKind::FundableHook);
+
+    // Conflicting attributes are reported at the one placed last
+    let conflict = |positions: &[Option<usize>], msg: &str| {
+        let last = positions.iter().flatten().max().unwrap();
 
-    if seen_ctor && seen_endpoint {
-        return Err(Error::new(
-            span,
+        Err(Error::new_spanned(&raw_attrs[*last], msg))
+    };
+
+    if ctor.is_some() && endpoint.is_some() {
+        return conflict(
+            &[ctor, endpoint],
             "#[ctor]` and `#[endpoint]` can't co-exist.",
-        ));
+        );
     }
 
-    if seen_endpoint && seen_fundable_hook {
-        return Err(Error::new(
-            span,
+    if endpoint.is_some() && fundable_hook.is_some() {
+        return conflict(
+            &[endpoint, fundable_hook],
             "#[endpoint]` and `#[fundable_hook]` can't co-exist.",
-        ));
+        );
     }
 
-    if seen_fundable && seen_fundable_hook {
-        return Err(Error::new(
-            span,
+    if fundable.is_some() && fundable_hook.is_some() {
+        return conflict(
+            &[fundable, fundable_hook],
             "#[fundable_hook]` and `#[fundable(..)]` can't co-exist.",
-        ));
+        );
     }
 
-    if seen_fundable && !seen_endpoint && !seen_ctor {
-        return Err(Error::new(
-            span,
+    if fallback.is_some() && (ctor.is_some() || endpoint.is_some() || fundable_hook.is_some()) {
+        return conflict(
+            &[fallback, ctor, endpoint, fundable_hook],
+            "`#[fallback]` can't co-exist with `#[ctor]`, `#[endpoint]` or `#[fundable_hook]`.",
+        );
+    }
+
+    if fundable.is_some() && endpoint.is_none() && ctor.is_none() {
+        return conflict(
+            &[fundable],
             "#[fundable(..)] can't be used without `#[endpoint]` or `#[ctor]`",
-        ));
+        );
     }
 
     Ok(())
 }
 
-fn validate_attrs_order(attrs: &[FuncAttr]) -> Result<()> {
-    let span = Span::call_site();
-
+fn validate_attrs_order(attrs: &[FuncAttr], raw_attrs: &[Attribute]) -> Result<()> {
     let mut seen_ctor = false;
     let mut seen_endpoint = false;
 
-    for attr in attrs {
+    for (attr, raw_attr) in attrs.iter().zip(raw_attrs) {
         match attr.kind() {
             FuncAttrKind::Ctor => seen_ctor = true,
             FuncAttrKind::Endpoint => seen_endpoint = true,
+            FuncAttrKind::Fallback => continue,
             FuncAttrKind::FundableHook => continue,
             FuncAttrKind::Fundable => {
                 if seen_ctor {
-                    return Err(Error::new(
-                        span,
+                    return Err(Error::new_spanned(
+                        raw_attr,
                         "`#[fundable(..)]` should be placed above `#[ctor]`",
                     ));
                 }
 
                 if seen_endpoint {
-                    return Err(Error::new(
-                        span,
+                    return Err(Error::new_spanned(
+                        raw_attr,
                         "`#[fundable(..)]` should be placed above `#[endpoint]`",
                     ));
                 }
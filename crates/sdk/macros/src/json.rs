@@ -1,4 +1,5 @@
-use crate::{r#type::Type, Export, PrimType, TemplateMeta, Var};
+use crate::r#struct::flatten_vars;
+use crate::{r#type::Type, DynKind, Export, PrimType, TemplateMeta, Var};
 
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -8,7 +9,49 @@ pub fn meta(meta: &TemplateMeta) -> Value {
     let api = api(meta);
     let schema = schema(meta);
 
-    json!({"api": api, "schema": schema})
+    let mut json = json!({"api": api, "schema": schema});
+
+    if !meta.enums().is_empty() {
+        json["enums"] = enums(meta);
+    }
+
+    if let Some(max_storage_bytes) = meta.max_storage_bytes() {
+        json["max_storage_bytes"] = json!(max_storage_bytes);
+    }
+
+    if !meta.doc().is_empty() {
+        json["doc"] = json!(meta.doc());
+    }
+
+    json
+}
+
+/// The documentation of the template: its own `///` docs, the docs of its documented
+/// storage variables and the docs of its exports (ordered by name, excluding the `fallback`).
+///
+/// It's of the same format as the JSON returned by `svm_codec::api::json::decode_docs`
+/// (for a `Template` deployed along its documentation).
+pub fn docs(meta: &TemplateMeta) -> Value {
+    let storage: Vec<Value> = meta
+        .var_docs()
+        .iter()
+        .map(|(name, doc)| json!({"name": name, "doc": doc}))
+        .collect();
+
+    let mut exports: Vec<&Export> = meta.exports().filter(|e| !e.is_fallback).collect();
+    exports.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let api: Vec<Value> = exports
+        .iter()
+        .map(|e| json!({"name": e.name, "doc": e.doc}))
+        .collect();
+
+    json!({
+        "name": meta.name(),
+        "doc": meta.doc(),
+        "storage": storage,
+        "api": api
+    })
 }
 
 pub fn to_tokens(json: &Value) -> TokenStream {
@@ -32,6 +75,7 @@ fn api(meta: &TemplateMeta) -> Value {
                 "wasm_name": e.wasm_name,
                 "is_ctor": e.is_ctor,
                 "is_fundable": e.is_fundable,
+                "is_fallback": e.is_fallback,
                 "signature": emit_signature(e)
             })
         })
@@ -67,13 +111,14 @@ fn emit_param(param: &(String, Type)) -> Value {
             })
         }
         Type::Tuple { .. } => unreachable!(),
+        Type::Struct(ty) => json!({"name": name, "type": ty.as_str()}),
     }
 }
 
 fn emit_output(ty: Option<&Type>) -> Value {
     if let Some(ty) = ty {
         match ty {
-            Type::Primitive(..) | Type::Array { .. } => emit_output_type(ty),
+            Type::Primitive(..) | Type::Array { .. } | Type::Struct(..) => emit_output_type(ty),
             Type::Tuple { elems, .. } => {
                 let elems = elems.iter().map(|ty| emit_output_type(&*ty)).collect();
 
@@ -93,19 +138,59 @@ fn emit_output_type(ty: &Type) -> Value {
             length,
             ..
         } => {
-            json!({"type": elem.as_str(), "length": length})
+            json!({"type": format!("[{}]", elem.as_str()), "length": length})
         }
         Type::Tuple { .. } => unreachable!("Nested tuples are not allowed"),
+        Type::Struct(ty) => json!({"type": ty.as_str()}),
     }
 }
 
-fn schema(meta: &TemplateMeta) -> Value {
-    let vars = meta
-        .schema()
+/// The `enum`s of the template, each along with the values of its variants
+/// (an `enum` is passed through the ABI as the `repr` value of its variant).
+fn enums(meta: &TemplateMeta) -> Value {
+    let enums = meta
+        .enums()
         .iter()
-        .map(|v| match v {
-            Var::Primitive { .. } => emit_primitive_var(v),
-            Var::Array { .. } => emit_array_var(v),
+        .map(|e| {
+            let variants: Vec<Value> = e
+                .variants
+                .iter()
+                .map(|(name, value)| json!({"name": name, "value": value}))
+                .collect();
+
+            json!({
+                "name": e.name,
+                "repr": e.repr.as_str(),
+                "variants": variants
+            })
+        })
+        .collect();
+
+    Value::Array(enums)
+}
+
+fn schema(meta: &TemplateMeta) -> Value {
+    // A variable of a nested struct is named by its dotted path (for example: `owner.balance`)
+    let vars = flatten_vars(meta.schema())
+        .into_iter()
+        .map(|(path, v)| {
+            let mut json = match v {
+                Var::Primitive { .. } => emit_primitive_var(v),
+                Var::Array { .. } => emit_array_var(v),
+                Var::Enum { .. } => emit_enum_var(v),
+                Var::Dynamic { .. } => emit_dynamic_var(v),
+                Var::Struct { .. } => unreachable!(),
+            };
+
+            json["name"] = json!(path);
+
+            let doc = meta.var_doc(&path);
+
+            if !doc.is_empty() {
+                json["doc"] = json!(doc);
+            }
+
+            json
         })
         .collect();
 
@@ -119,16 +204,28 @@ fn emit_primitive_var(var: &Var) -> Value {
         name,
         byte_count,
         ty,
+        default,
+        bit,
         ..
     } = var
     {
-        json!({
+        let mut json = json!({
             "id": id.0,
             "offset": offset,
             "name": name.to_string(),
             "type": typify(ty),
             "byte_count": byte_count
-        })
+        });
+
+        if let Some(bit) = bit {
+            json["bit"] = json!(bit);
+        }
+
+        if let Some(default) = default {
+            json["default"] = json!(hex_encode(default));
+        }
+
+        json
     } else {
         unreachable!()
     }
@@ -158,6 +255,57 @@ fn emit_array_var(var: &Var) -> Value {
     }
 }
 
+fn emit_enum_var(var: &Var) -> Value {
+    if let Var::Enum {
+        id,
+        offset,
+        name,
+        ty,
+        byte_count,
+        ..
+    } = var
+    {
+        json!({
+            "id": id.0,
+            "offset": offset,
+            "name": name.to_string(),
+            "type": ty.as_str(),
+            "byte_count": byte_count
+        })
+    } else {
+        unreachable!()
+    }
+}
+
+fn emit_dynamic_var(var: &Var) -> Value {
+    if let Var::Dynamic {
+        id,
+        offset,
+        name,
+        kind,
+        byte_count,
+    } = var
+    {
+        let ty = match kind {
+            DynKind::Mapping { key_ty, value_ty } => {
+                format!("Mapping<{}, {}>", typify(key_ty), typify(value_ty))
+            }
+            DynKind::Vec { elem_ty } => format!("DynVec<{}>", typify(elem_ty)),
+        };
+
+        json!({
+            "id": id.0,
+            "offset": offset,
+            "name": name.to_string(),
+            "type": ty,
+            "byte_count": byte_count,
+            "dynamic": true
+        })
+    } else {
+        unreachable!()
+    }
+}
+
 fn typify(ty: &PrimType) -> String {
     match ty.as_str() {
         "svm_sdk :: Amount" => "Amount".to_string(),
@@ -165,3 +313,7 @@ fn typify(ty: &PrimType) -> String {
         _ => ty.as_str().to_string(),
     }
 }
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -3,6 +3,9 @@
 #![allow(dead_code)]
 #![allow(unreachable_code)]
 
+mod abi;
+mod r#const;
+mod r#enum;
 mod function;
 mod json;
 mod meta;
@@ -11,9 +14,11 @@ mod template;
 mod r#type;
 
 use function::{FuncAttr, FuncAttrKind, Function};
-use meta::{Export, TemplateMeta};
+use meta::{EnumMeta, Export, TemplateMeta};
+use r#const::Const;
+use r#enum::{Enum, EnumRepr};
 use r#struct::storage_vars;
-use r#struct::{Struct, Var};
+use r#struct::{DynKind, Struct, Var};
 use r#type::{PrimType, Type};
 use template::Template;
 
@@ -28,12 +33,32 @@ pub fn template(
     }
 }
 
+#[proc_macro_derive(AbiEncode)]
+pub fn abi_encode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    match abi::expand_encode(input.into()) {
+        Ok(ast) => ast.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(AbiDecode)]
+pub fn abi_decode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    match abi::expand_decode(input.into()) {
+        Ok(ast) => ast.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 #[cfg(feature = "meta")]
 fn finalize_ast(ast: proc_macro2::TokenStream, meta: &TemplateMeta) -> proc_macro::TokenStream {
     let path = format!("{}-meta.json", meta.name());
     let meta_json = json::meta(&meta);
     json::json_write(&path, &meta_json);
 
+    let path = format!("{}-docs.json", meta.name());
+    let docs_json = json::docs(meta);
+    json::json_write(&path, &docs_json);
+
     ast.into()
 }
 
@@ -44,14 +69,21 @@ fn finalize_ast(ast: proc_macro2::TokenStream, meta: &TemplateMeta) -> proc_macr
     let meta_json = json::meta(&meta);
     let meta_stream = json::to_tokens(&meta_json);
 
+    let docs_json = json::docs(meta);
+    let docs_stream = json::to_tokens(&docs_json);
+
     let final_ast = quote! {
         #ast
 
-        pub fn raw_meta() -> String {
+        pub fn raw_meta() -> std::string::String {
             // We can't implement [`quote::ToTokens`] for [`serde_json::Value`] since both are defined in other crates.
             // Instead, we return a `String` and we'll use [`serde_json::from_str`] within the tests.
             #meta_stream.to_string()
         }
+
+        pub fn raw_docs() -> std::string::String {
+            #docs_stream.to_string()
+        }
     };
 
     final_ast.into()
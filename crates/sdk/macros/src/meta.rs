@@ -2,22 +2,37 @@ use std::collections::hash_map::Values;
 use std::collections::HashMap;
 
 use quote::quote;
-use syn::{FnArg, PatType, Result, ReturnType};
+use syn::{Attribute, Fields, FnArg, Lit, Meta, MetaNameValue, PatType, Result, ReturnType};
 
-use crate::function::{find_attr, func_attrs, has_ctor_attr, has_endpoint_attr, has_fundable_attr};
-use crate::r#struct::has_storage_attr;
+use crate::function::{
+    find_attr, func_attrs, has_ctor_attr, has_endpoint_attr, has_fallback_attr, has_fundable_attr,
+};
+use crate::r#struct::{has_storage_attr, nested_struct};
 use crate::storage_vars;
-use crate::{FuncAttr, FuncAttrKind, Function, Template, Type, Var};
+use crate::{EnumRepr, FuncAttr, FuncAttrKind, Function, Struct, Template, Type, Var};
 
 pub struct TemplateMeta {
     name: String,
+    doc: String,
     schema: Vec<Var>,
+    var_docs: Vec<(String, String)>,
     exports: HashMap<String, Export>,
+    enums: Vec<EnumMeta>,
+    max_storage_bytes: Option<u32>,
+}
+
+/// A C-like `enum` declared inside `#[template]` (so clients can decode its values).
+pub struct EnumMeta {
+    pub name: String,
+    pub repr: EnumRepr,
+    /// The variants (ordered as declared) along with their values.
+    pub variants: Vec<(String, u16)>,
 }
 
 pub struct Export {
     pub is_ctor: bool,
     pub is_fundable: bool,
+    pub is_fallback: bool,
     pub name: String,
     pub wasm_name: String,
     pub signature: Signature,
@@ -58,8 +73,12 @@ impl TemplateMeta {
     pub fn new(name: String) -> Self {
         Self {
             name,
+            doc: String::new(),
             exports: HashMap::new(),
             schema: Vec::new(),
+            var_docs: Vec::new(),
+            enums: Vec::new(),
+            max_storage_bytes: None,
         }
     }
 
@@ -91,61 +110,191 @@ impl TemplateMeta {
     pub fn schema(&self) -> &[Var] {
         &self.schema
     }
+
+    /// The `///` docs of the template (empty when it's undocumented).
+    pub fn doc(&self) -> &str {
+        &self.doc
+    }
+
+    /// The `///` docs of the documented storage variables (ordered as declared).
+    pub fn var_docs(&self) -> &[(String, String)] {
+        &self.var_docs
+    }
+
+    /// The `///` docs of the storage variable named `name` (empty when it's undocumented).
+    pub fn var_doc(&self, name: &str) -> &str {
+        self.var_docs
+            .iter()
+            .find(|(var, _)| var == name)
+            .map(|(_, doc)| doc.as_str())
+            .unwrap_or_default()
+    }
+
+    /// The `enum`s declared inside the template (ordered as declared).
+    pub fn enums(&self) -> &[EnumMeta] {
+        &self.enums
+    }
+
+    pub fn max_storage_bytes(&self) -> Option<u32> {
+        self.max_storage_bytes
+    }
 }
 
 pub fn template_meta(template: &Template) -> Result<TemplateMeta> {
     let name = template.name().to_string();
-    let schema = template_schema(template);
+    let schema = template_schema(template)?;
+    let var_docs = template_var_docs(template);
+    let enums = template_enums(template);
 
-    let exports = template
-        .functions()
-        .iter()
-        .filter(|func| {
-            let attrs = func_attrs(func).unwrap();
+    let mut exports = HashMap::new();
 
-            let is_endpoint = has_endpoint_attr(&attrs);
-            let is_ctor = has_ctor_attr(&attrs);
+    for func in template.functions() {
+        let attrs = func_attrs(func)?;
 
-            is_endpoint || is_ctor
-        })
-        .map(export_schema)
-        .map(|export| (export.name.clone(), export))
-        .collect();
+        let is_endpoint = has_endpoint_attr(&attrs);
+        let is_ctor = has_ctor_attr(&attrs);
+        let is_fallback = has_fallback_attr(&attrs);
+
+        if is_endpoint || is_ctor || is_fallback {
+            let export = export_schema(func, template)?;
+
+            exports.insert(export.name.clone(), export);
+        }
+    }
 
     let schema = TemplateMeta {
         name,
+        doc: template.doc().to_string(),
         schema,
+        var_docs,
         exports,
+        enums,
+        max_storage_bytes: template.max_storage_bytes(),
     };
 
     Ok(schema)
 }
 
-fn template_schema(template: &Template) -> Vec<Var> {
-    let storage = template.structs().iter().find(|s| {
-        let attrs = s.attrs().as_ref().unwrap();
+fn template_schema(template: &Template) -> Result<Vec<Var>> {
+    for strukt in template.structs() {
+        let attrs = strukt.attrs().as_ref().map_err(Clone::clone)?;
+
+        if has_storage_attr(attrs) {
+            return storage_vars(strukt, template);
+        }
+    }
+
+    Ok(Vec::new())
+}
 
-        has_storage_attr(attrs)
-    });
+fn template_enums(template: &Template) -> Vec<EnumMeta> {
+    template
+        .enums()
+        .iter()
+        .map(|e| EnumMeta {
+            name: e.raw_name().to_string(),
+            repr: e.repr(),
+            variants: e
+                .variants()
+                .iter()
+                .map(|(variant, value)| (variant.to_string(), *value))
+                .collect(),
+        })
+        .collect()
+}
 
-    if let Some(storage) = storage {
-        storage_vars(&storage).unwrap()
-    } else {
-        Vec::new()
+fn template_var_docs(template: &Template) -> Vec<(String, String)> {
+    let strukt = template
+        .structs()
+        .iter()
+        .find(|strukt| matches!(strukt.attrs(), Ok(attrs) if has_storage_attr(attrs)));
+
+    match strukt {
+        Some(strukt) => fields_docs(strukt, "", &mut Vec::new(), template),
+        None => Vec::new(),
     }
 }
 
-fn export_schema(func: &Function) -> Export {
-    let attrs = func_attrs(func).unwrap();
+/// The docs of the documented fields of `strukt`, named by their dotted paths starting at `prefix`
+/// (the fields of a nested struct are documented by the struct's own field docs).
+fn fields_docs(
+    strukt: &Struct,
+    prefix: &str,
+    nesting: &mut Vec<String>,
+    template: &Template,
+) -> Vec<(String, String)> {
+    let fields = match strukt.raw_fields() {
+        Fields::Named(fields) if !nesting.contains(&strukt.raw_name().to_string()) => &fields.named,
+        _ => return Vec::new(),
+    };
+
+    nesting.push(strukt.raw_name().to_string());
+
+    let mut docs = Vec::new();
+
+    for field in fields.iter() {
+        let name = match field.ident.as_ref() {
+            Some(ident) => format!("{}{}", prefix, ident),
+            None => continue,
+        };
+
+        match nested_struct(field, template) {
+            Some(nested) => {
+                let prefix = format!("{}.", name);
+
+                docs.extend(fields_docs(nested, &prefix, nesting, template));
+            }
+            None => {
+                let doc = doc_comments(&field.attrs);
+
+                if !doc.is_empty() {
+                    docs.push((name, doc));
+                }
+            }
+        }
+    }
+
+    nesting.pop();
+
+    docs
+}
+
+/// Returns the `///` docs given by `attrs` (i.e their `#[doc = "..."]` attributes), one line each.
+pub fn doc_comments(attrs: &[Attribute]) -> String {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(lit), ..
+            })) => Some(lit.value()),
+            _ => None,
+        })
+        .map(|line| {
+            line.strip_prefix(' ')
+                .unwrap_or(&line)
+                .trim_end()
+                .to_string()
+        })
+        .collect();
+
+    lines.join("\n").trim().to_string()
+}
+
+fn export_schema(func: &Function, template: &Template) -> Result<Export> {
+    let attrs = func_attrs(func)?;
 
     let is_ctor = has_ctor_attr(&attrs);
     let is_fundable = has_fundable_attr(&attrs);
+    let is_fallback = has_fallback_attr(&attrs);
 
     let api_name = func.raw_name().to_string();
     let export_name = func.export_name();
 
     let attr = if is_ctor {
         find_attr(&attrs, FuncAttrKind::Ctor)
+    } else if is_fallback {
+        find_attr(&attrs, FuncAttrKind::Fallback)
     } else {
         find_attr(&attrs, FuncAttrKind::Endpoint)
     };
@@ -153,29 +302,43 @@ fn export_schema(func: &Function) -> Export {
     let doc = match attr.unwrap() {
         FuncAttr::Ctor(doc) => doc.to_string(),
         FuncAttr::Endpoint(doc) => doc.to_string(),
+        FuncAttr::Fallback(doc) => doc.to_string(),
         _ => unreachable!(),
     };
 
-    let signature = function_sig(func);
+    // An explicit `doc = "..."` takes precedence over the `///` docs
+    let doc = if doc.is_empty() {
+        doc_comments(&func.raw_attrs())
+    } else {
+        doc
+    };
 
-    Export {
+    let signature = function_sig(func, is_fallback, template)?;
+
+    let export = Export {
         is_ctor,
         is_fundable,
+        is_fallback,
         name: api_name,
         wasm_name: export_name,
         signature,
         doc,
-    }
+    };
+
+    Ok(export)
 }
 
-fn function_sig(func: &Function) -> Signature {
+fn function_sig(func: &Function, is_fallback: bool, template: &Template) -> Result<Signature> {
     let raw_sig = func.raw_sig();
 
     let mut sig = Signature::new();
 
-    for input in &raw_sig.inputs {
+    // The params of a `#[fallback]` function are fixed (they aren't ABI-encoded)
+    let inputs = raw_sig.inputs.iter().filter(|_| !is_fallback);
+
+    for input in inputs {
         if let FnArg::Typed(PatType { pat, ty, .. }) = input {
-            let ty = Type::new(ty).unwrap();
+            let ty = Type::new(ty, template)?;
             let name = quote! { #pat };
 
             sig.push_param((name.to_string(), ty));
@@ -185,10 +348,10 @@ fn function_sig(func: &Function) -> Signature {
     }
 
     if let ReturnType::Type(.., ty) = &raw_sig.output {
-        let ty = Type::new(&ty).unwrap();
+        let ty = Type::new(&ty, template)?;
 
         sig.set_output(ty);
     }
 
-    sig
+    Ok(sig)
 }
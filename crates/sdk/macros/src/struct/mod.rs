@@ -1,13 +1,16 @@
 use proc_macro2::{Ident, TokenStream};
+use quote::quote;
 use syn::{Fields, ItemStruct, Result};
 
+use crate::Template;
+
 mod attr;
 mod storage;
 mod var;
 
 pub use attr::{has_storage_attr, StructAttr, StructAttrKind};
-pub use storage::storage_vars;
-pub use var::{Var, VarId};
+pub use storage::{nested_struct, storage_vars};
+pub use var::{flatten_vars, DynKind, Var, VarId};
 
 pub struct Struct {
     raw_struct: ItemStruct,
@@ -34,13 +37,27 @@ impl Struct {
     }
 }
 
-pub fn expand(strukt: &Struct) -> Result<TokenStream> {
+/// Returns the plain (i.e not `#[storage]`) struct named `name` (if any).
+///
+/// A `#[storage]` field of such a struct type embeds its fields (see [`storage_vars`]).
+pub fn find_plain_struct<'a>(structs: &'a [Struct], name: &str) -> Option<&'a Struct> {
+    structs.iter().find(|strukt| {
+        strukt.raw_name() == name && matches!(strukt.attrs(), Ok(attrs) if !has_storage_attr(attrs))
+    })
+}
+
+/// Expands `strukt` (a `#[storage]` may use the `enums` and `consts` of the `template`).
+pub fn expand(strukt: &Struct, template: &Template) -> Result<TokenStream> {
     match strukt.attrs() {
         Ok(attrs) => {
             if has_storage_attr(attrs) {
-                storage::expand(strukt, attrs)
+                storage::expand(strukt, attrs, template)
             } else {
-                todo!()
+                // A plain struct (for example, one deriving `AbiEncode` and `AbiDecode`)
+                // is emitted as-is.
+                let raw_struct = &strukt.raw_struct;
+
+                Ok(quote! { #raw_struct })
             }
         }
         Err(err) => Err(err.clone()),
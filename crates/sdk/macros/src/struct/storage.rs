@@ -1,56 +1,149 @@
 use proc_macro2::{Ident, Span, TokenStream};
 
 use quote::quote;
-use syn::{Error, Field, Fields, Result};
+use syn::parse::ParseStream;
+use syn::{Error, Field, Fields, GenericArgument, Lit, PathArguments, Result, Token};
 
-use super::{attr, Var, VarId};
+use super::{attr, find_plain_struct, DynKind, Var, VarId};
 use attr::{has_storage_attr, StructAttr};
 
-use crate::{PrimType, Struct, Type};
+use crate::r#enum::find_enum;
+use crate::r#type::parse_primitive_type;
+use crate::{PrimType, Struct, Template, Type};
 
-pub fn expand(strukt: &Struct, attrs: &[StructAttr]) -> Result<TokenStream> {
+pub fn expand(strukt: &Struct, attrs: &[StructAttr], template: &Template) -> Result<TokenStream> {
     debug_assert!(has_storage_attr(attrs));
 
-    let vars = storage_vars(strukt)?;
+    let vars = storage_vars(strukt, template)?;
 
     let name = strukt.raw_name();
-    let getters = getters_ast(&vars);
-    let setters = setters_ast(&vars);
+    let accessors = accessors_ast(&name, &vars, quote! {});
 
     let ast = quote! {
         struct #name;
 
-        impl #name {
+        #accessors
+    };
+
+    Ok(ast)
+}
+
+/// Emits the getters and setters of `vars` as methods of `handle`, taking `receiver`
+/// (none for the `#[storage]` struct itself, whose methods are associated functions).
+///
+/// A nested struct field gets a handle of its own, returned by a method named after the field.
+/// For example, field `balance` of a nested `owner` is accessed by `Storage::owner().get_balance()`.
+fn accessors_ast(handle: &Ident, vars: &[Var], receiver: TokenStream) -> TokenStream {
+    let getters = getters_ast(vars, &receiver);
+    let setters = setters_ast(vars, &receiver);
+
+    let nested = vars.iter().filter_map(|var| match var {
+        Var::Struct { name, vars, .. } => {
+            let nested_handle = Ident::new(&format!("{}_{}", handle, name), Span::call_site());
+            let accessors = accessors_ast(&nested_handle, vars, quote! { &self, });
+
+            Some(quote! {
+                #[allow(non_camel_case_types)]
+                struct #nested_handle;
+
+                impl #handle {
+                    fn #name (#receiver) -> #nested_handle {
+                        #nested_handle
+                    }
+                }
+
+                #accessors
+            })
+        }
+        _ => None,
+    });
+
+    quote! {
+        impl #handle {
             #getters
 
             #setters
         }
-    };
 
-    Ok(ast)
+        #(#nested)*
+    }
 }
 
-pub fn storage_vars(strukt: &Struct) -> Result<Vec<Var>> {
-    let mut vars = Vec::new();
+/// Lays out the `#[storage]` fields.
+///
+/// Consecutive `bool` fields are packed into the bits of a shared byte (up to 8 of them),
+/// the same way `svm_layout::FixedLayoutBuilder::push_bit` does.
+///
+/// A dynamic field (a `Mapping<K, V>` or a `DynVec<T>`) is laid out as a `u32` variable
+/// (holding its number of entries).
+///
+/// A field of one of the template's `enums` is laid out as the integer its variants are valued by,
+/// while an Array field's length may name one of the template's `consts`.
+///
+/// A field of one of the template's plain structs embeds the struct's fields, laid out in place
+/// (nested structs are flattened recursively, and `bool`s aren't packed across their boundaries).
+pub fn storage_vars(strukt: &Struct, template: &Template) -> Result<Vec<Var>> {
     let mut id = VarId(0);
     let mut offset = 0;
+    let mut nesting = vec![strukt.raw_name().to_string()];
+
+    fields_vars(strukt, &mut id, &mut offset, &mut nesting, template)
+}
+
+/// Lays out the fields of `strukt` starting at `id` and `offset` (advancing both past them).
+///
+/// The `nesting` holds the names of the structs being laid out (the outermost first).
+fn fields_vars(
+    strukt: &Struct,
+    id: &mut VarId,
+    offset: &mut usize,
+    nesting: &mut Vec<String>,
+    template: &Template,
+) -> Result<Vec<Var>> {
+    let mut vars: Vec<Var> = Vec::new();
+
+    ensure_named_fields(strukt)?;
+
+    for f in strukt.raw_fields() {
+        if let Some(nested) = nested_struct(f, template) {
+            let var = nested_var(f, nested, id, offset, nesting, template)?;
+
+            vars.push(var);
+            continue;
+        }
 
-    let fields = strukt.raw_fields();
+        if nesting.len() > 1 {
+            if let Some(default) = field_default(f)? {
+                let msg = "`#[storage(default = ...)]` isn't supported for the fields of a nested struct.";
 
-    ensure_named_fields(fields)?;
+                return Err(Error::new_spanned(&default.lit, msg));
+            }
+        }
+
+        let packed = match vars.last() {
+            Some(Var::Primitive {
+                offset,
+                bit: Some(bit),
+                ..
+            }) if *bit < 7 => Some((*offset, *bit + 1)),
+            _ => None,
+        };
 
-    for f in fields {
-        let var = field_var(f, id, offset)?;
+        let var = field_var(f, *id, *offset, packed, template)?;
 
         match var {
-            Var::Primitive { .. } => {
-                offset += var.byte_count();
-                id = next_var(id, 1);
+            Var::Primitive { bit: Some(bit), .. } if bit > 0 => {
+                *id = next_var(*id, 1);
+            }
+            Var::Primitive { .. } | Var::Enum { .. } | Var::Dynamic { .. } => {
+                *offset += var.byte_count();
+                *id = next_var(*id, 1);
             }
             Var::Array { length, .. } => {
-                offset += var.byte_count() * (length as usize);
-                id = next_var(id, length);
+                *offset += var.byte_count() * (length as usize);
+                *id = next_var(*id, length);
             }
+            Var::Struct { .. } => unreachable!(),
         }
 
         vars.push(var);
@@ -59,17 +152,91 @@ pub fn storage_vars(strukt: &Struct) -> Result<Vec<Var>> {
     Ok(vars)
 }
 
-fn field_var(field: &Field, id: VarId, offset: usize) -> Result<Var> {
-    let span = Span::call_site();
+/// Returns the plain struct `field` is of (if any).
+pub fn nested_struct<'a>(field: &Field, template: &'a Template) -> Option<&'a Struct> {
+    match &field.ty {
+        syn::Type::Path(path) if path.qself.is_none() => {
+            let ident = path.path.get_ident()?;
+
+            find_plain_struct(template.structs(), &ident.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Returns the variable of `field` (of the plain struct `nested`), laying out the struct's fields.
+fn nested_var(
+    field: &Field,
+    nested: &Struct,
+    id: &mut VarId,
+    offset: &mut usize,
+    nesting: &mut Vec<String>,
+    template: &Template,
+) -> Result<Var> {
+    let ty = nested.raw_name();
+
+    if nesting.contains(&ty.to_string()) {
+        let msg = format!("`#[storage]` can't embed struct `{}` within itself.", ty);
+
+        return Err(Error::new_spanned(&field.ty, msg));
+    }
 
-    if !field.attrs.is_empty() {
-        let msg = "`#[storage]` fields should have no attributes.";
+    if let Some(default) = field_default(field)? {
+        let msg = "`#[storage(default = ...)]` is supported only for Primitive fields.";
 
-        return Err(Error::new(span, msg));
+        return Err(Error::new_spanned(&default.lit, msg));
     }
 
+    nesting.push(ty.to_string());
+    let vars = fields_vars(nested, id, offset, nesting, template)?;
+    nesting.pop();
+
+    Ok(Var::Struct {
+        name: field_ident(field),
+        ty,
+        vars,
+    })
+}
+
+/// Returns the variable of `field`.
+///
+/// A `bool` field is packed, either into the next bit of `packed` (the byte offset and bit
+/// following the previous packed `bool`) or into bit `0` of a new byte at `offset`.
+fn field_var(
+    field: &Field,
+    id: VarId,
+    offset: usize,
+    packed: Option<(usize, u8)>,
+    template: &Template,
+) -> Result<Var> {
     let name = field_ident(field);
-    let ty = Type::new(&field.ty)?;
+    let default = field_default(field)?;
+
+    if let Some(kind) = dyn_kind(&field.ty)? {
+        if let Some(default) = &default {
+            let msg = "`#[storage(default = ...)]` is supported only for Primitive fields.";
+
+            return Err(Error::new_spanned(&default.lit, msg));
+        }
+
+        return Ok(Var::Dynamic {
+            id,
+            name,
+            kind,
+            offset,
+            byte_count: 4,
+        });
+    }
+
+    let ty = Type::new(&field.ty, template)?;
+
+    if let Some(default) = &default {
+        if !matches!(ty, Type::Primitive(..)) {
+            let msg = "`#[storage(default = ...)]` is supported only for Primitive fields.";
+
+            return Err(Error::new_spanned(&default.lit, msg));
+        }
+    }
 
     let var = match ty {
         Type::Array {
@@ -88,6 +255,16 @@ fn field_var(field: &Field, id: VarId, offset: usize) -> Result<Var> {
         }
         Type::Primitive(ty) => {
             let byte_count = field_byte_count(&ty);
+            let default = match default {
+                Some(lit) => Some(default_bytes(&ty, &lit, byte_count)?),
+                None => None,
+            };
+
+            let (offset, bit) = match (ty.as_str(), packed) {
+                ("bool", Some((offset, bit))) => (offset, Some(bit)),
+                ("bool", None) => (offset, Some(0)),
+                _ => (offset, None),
+            };
 
             Var::Primitive {
                 id,
@@ -95,11 +272,30 @@ fn field_var(field: &Field, id: VarId, offset: usize) -> Result<Var> {
                 ty,
                 offset,
                 byte_count,
+                default,
+                bit,
             }
         }
+        Type::Struct(ty) if find_enum(template.enums(), ty.as_str()).is_some() => {
+            let repr = find_enum(template.enums(), ty.as_str()).unwrap().repr();
+
+            Var::Enum {
+                id,
+                name,
+                ty,
+                repr,
+                offset,
+                byte_count: repr.byte_count(),
+            }
+        }
+        Type::Struct(ty) => {
+            let msg = format!("Invalid `#[storage]` field type: {}", ty.as_str());
+
+            return Err(Error::new_spanned(&field.ty, msg));
+        }
         _ => {
-            return Err(Error::new(
-                span,
+            return Err(Error::new_spanned(
+                &field.ty,
                 "`#[storage]` supports only Primitive (for example: `svm_sdk::Amount`) and Array types.",
             ));
         }
@@ -108,11 +304,143 @@ fn field_var(field: &Field, id: VarId, offset: usize) -> Result<Var> {
     Ok(var)
 }
 
-fn ensure_named_fields(fields: &Fields) -> Result<()> {
-    if let Fields::Named(..) = fields {
+/// Returns the kind of a `Mapping<K, V>` or a `DynVec<T>` field type (`None` for any other type).
+fn dyn_kind(ty: &syn::Type) -> Result<Option<DynKind>> {
+    let segment = match ty {
+        syn::Type::Path(path) => path.path.segments.last().unwrap(),
+        _ => return Ok(None),
+    };
+
+    let types: Vec<&syn::Type> = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let kind = match (segment.ident.to_string().as_str(), types.as_slice()) {
+        ("Mapping", [key_ty, value_ty]) => DynKind::Mapping {
+            key_ty: dyn_item_type(key_ty)?,
+            value_ty: dyn_item_type(value_ty)?,
+        },
+        ("DynVec", [elem_ty]) => DynKind::Vec {
+            elem_ty: dyn_item_type(elem_ty)?,
+        },
+        ("Mapping", _) => return Err(Error::new_spanned(ty, "expected `Mapping<K, V>`")),
+        ("DynVec", _) => return Err(Error::new_spanned(ty, "expected `DynVec<T>`")),
+        _ => return Ok(None),
+    };
+
+    Ok(Some(kind))
+}
+
+fn dyn_item_type(ty: &syn::Type) -> Result<PrimType> {
+    match ty {
+        syn::Type::Path(path) => parse_primitive_type(path),
+        _ => Err(Error::new_spanned(
+            ty,
+            "`Mapping` and `DynVec` items must be primitives (for example: `svm_sdk::Amount`).",
+        )),
+    }
+}
+
+/// A (possibly negated) literal given by `#[storage(default = ...)]`.
+struct DefaultLit {
+    neg: bool,
+    lit: Lit,
+}
+
+/// Parses the optional `#[storage(default = <literal>)]` attribute of a field.
+fn field_default(field: &Field) -> Result<Option<DefaultLit>> {
+    let mut default = None;
+
+    // The `///` docs are extracted into the template's docs (see `meta::doc_comments`)
+    let attrs = field.attrs.iter().filter(|attr| !attr.path.is_ident("doc"));
+
+    for attr in attrs {
+        if !attr.path.is_ident("storage") || default.is_some() {
+            let msg = "`#[storage]` fields should have no attributes other than a single `#[storage(default = ...)]`.";
+
+            return Err(Error::new_spanned(attr, msg));
+        }
+
+        let lit = attr.parse_args_with(|input: ParseStream| {
+            let key: Ident = input.parse()?;
+
+            if key != "default" {
+                return Err(Error::new(key.span(), "expected `default = ...`"));
+            }
+
+            input.parse::<Token![=]>()?;
+
+            let neg = input.parse::<Option<Token![-]>>()?.is_some();
+            let lit: Lit = input.parse()?;
+
+            Ok(DefaultLit { neg, lit })
+        })?;
+
+        default = Some(lit);
+    }
+
+    Ok(default)
+}
+
+/// Returns the raw bytes of a default value, as persisted by the `svm_set32 / svm_set64` vmcalls
+/// (i.e Little-Endian, truncated to the variable's `byte_count`).
+fn default_bytes(ty: &PrimType, default: &DefaultLit, byte_count: usize) -> Result<Vec<u8>> {
+    let ty_str = ty.as_str();
+
+    let value: u64 = match (&default.lit, ty_str) {
+        (Lit::Bool(b), "bool") if !default.neg => b.value as u64,
+        (Lit::Int(int), "i8" | "i16" | "i32" | "i64") => {
+            let value = int.base10_parse::<i64>()?;
+            let value = if default.neg { -value } else { value };
+
+            let bits = 8 * byte_count as u32;
+            let min = -(1i128 << (bits - 1));
+            let max = (1i128 << (bits - 1)) - 1;
+
+            if (value as i128) < min || (value as i128) > max {
+                let msg = format!("Default value out of range for type `{}`", ty_str);
+
+                return Err(Error::new(int.span(), msg));
+            }
+
+            value as u64
+        }
+        (Lit::Int(int), "u8" | "u16" | "u32" | "u64" | "Amount" | "svm_sdk :: Amount")
+            if !default.neg =>
+        {
+            let value = int.base10_parse::<u64>()?;
+
+            if byte_count < 8 && value >= (1u64 << (8 * byte_count)) {
+                let msg = format!("Default value out of range for type `{}`", ty_str);
+
+                return Err(Error::new(int.span(), msg));
+            }
+
+            value
+        }
+        _ => {
+            let msg = format!("Invalid default value for a field of type `{}`", ty_str);
+
+            return Err(Error::new_spanned(&default.lit, msg));
+        }
+    };
+
+    Ok(value.to_le_bytes()[..byte_count].to_vec())
+}
+
+fn ensure_named_fields(strukt: &Struct) -> Result<()> {
+    if let Fields::Named(..) = strukt.raw_fields() {
         Ok(())
     } else {
-        let span = Span::call_site();
+        let span = strukt.raw_name().span();
 
         Err(Error::new(
             span,
@@ -121,11 +449,11 @@ fn ensure_named_fields(fields: &Fields) -> Result<()> {
     }
 }
 
-fn getters_ast(vars: &[Var]) -> TokenStream {
+fn getters_ast(vars: &[Var], receiver: &TokenStream) -> TokenStream {
     let mut getters = Vec::new();
 
     for var in vars.iter() {
-        let ast = getter_ast(var);
+        let ast = getter_ast(var, receiver);
 
         getters.push(ast);
     }
@@ -135,11 +463,11 @@ fn getters_ast(vars: &[Var]) -> TokenStream {
     }
 }
 
-fn setters_ast(vars: &[Var]) -> TokenStream {
+fn setters_ast(vars: &[Var], receiver: &TokenStream) -> TokenStream {
     let mut setters = Vec::new();
 
     for var in vars.iter() {
-        let ast = setter_ast(var);
+        let ast = setter_ast(var, receiver);
 
         setters.push(ast);
     }
@@ -149,17 +477,86 @@ fn setters_ast(vars: &[Var]) -> TokenStream {
     }
 }
 
-fn getter_ast(var: &Var) -> TokenStream {
+fn getter_ast(var: &Var, receiver: &TokenStream) -> TokenStream {
     let includes = include_storage_ast();
 
     match var {
+        // Accessed by its own handle (see `accessors_ast`)
+        Var::Struct { .. } => quote! {},
+        Var::Dynamic { id, name, kind, .. } => {
+            let getter_name = getter_ident(name);
+            let len_name = Ident::new(&format!("{}_len", name), Span::call_site());
+
+            match kind {
+                DynKind::Mapping { key_ty, value_ty } => {
+                    let contains_name =
+                        Ident::new(&format!("contains_{}", name), Span::call_site());
+                    let (key_param, key_ref) = dyn_param_ast(key_ty, quote! { key });
+                    let mapping = mapping_ast(key_ty, value_ty);
+                    let value_ty = dyn_item_ast(value_ty);
+
+                    quote! {
+                        fn #getter_name (#receiver key: #key_param) -> #value_ty {
+                            #includes
+
+                            #mapping::new(#id).get::<StorageImpl>(#key_ref)
+                        }
+
+                        fn #contains_name (#receiver key: #key_param) -> bool {
+                            #includes
+
+                            #mapping::new(#id).contains::<StorageImpl>(#key_ref)
+                        }
+
+                        fn #len_name (#receiver) -> u32 {
+                            #includes
+
+                            #mapping::new(#id).len::<StorageImpl>()
+                        }
+                    }
+                }
+                DynKind::Vec { elem_ty } => {
+                    let vec = dyn_vec_ast(elem_ty);
+                    let elem_ty = dyn_item_ast(elem_ty);
+
+                    quote! {
+                        fn #getter_name (#receiver index: u32) -> #elem_ty {
+                            #includes
+
+                            #vec::new(#id).get::<StorageImpl>(index)
+                        }
+
+                        fn #len_name (#receiver) -> u32 {
+                            #includes
+
+                            #vec::new(#id).len::<StorageImpl>()
+                        }
+                    }
+                }
+            }
+        }
+        Var::Enum {
+            id, name, ty, repr, ..
+        } => {
+            let getter_name = getter_ident(name);
+            let repr = Ident::new(repr.as_str(), Span::call_site());
+
+            quote! {
+                fn #getter_name (#receiver) -> #ty {
+                    #includes
+
+                    let value = svm_sdk::storage::ops::get32::<StorageImpl>(#id) as #repr;
+                    value.into()
+                }
+            }
+        }
         Var::Primitive { id, name, ty, .. } => {
             let getter_name = getter_ident(name);
 
             match ty.as_str() {
                 "i8" | "u8" | "i16" | "u16" | "i32" | "u32" => {
                     quote! {
-                        fn #getter_name () -> #ty {
+                        fn #getter_name (#receiver) -> #ty {
                             #includes
 
                             svm_sdk::storage::ops::get32::<StorageImpl>(#id) as #ty
@@ -168,7 +565,7 @@ fn getter_ast(var: &Var) -> TokenStream {
                 }
                 "u64" | "i64" => {
                     quote! {
-                        fn #getter_name () -> #ty {
+                        fn #getter_name (#receiver) -> #ty {
                             #includes
 
                             svm_sdk::storage::ops::get64::<StorageImpl>(#id) as #ty
@@ -177,16 +574,16 @@ fn getter_ast(var: &Var) -> TokenStream {
                 }
                 "bool" => {
                     quote! {
-                        fn #getter_name () -> bool {
+                        fn #getter_name (#receiver) -> bool {
                             #includes
 
-                            svm_sdk::storage::ops::get_bool::<StorageImpl>(#id) as #ty
+                            svm_sdk::storage::ops::get_bit::<StorageImpl>(#id)
                         }
                     }
                 }
                 "svm_sdk :: Amount" | "Amount" => {
                     quote! {
-                        fn #getter_name () -> svm_sdk::Amount {
+                        fn #getter_name (#receiver) -> svm_sdk::Amount {
                             #includes
 
                             svm_sdk::storage::ops::get_amount::<StorageImpl>(#id)
@@ -195,7 +592,7 @@ fn getter_ast(var: &Var) -> TokenStream {
                 }
                 "svm_sdk :: Address" | "Address" => {
                     quote! {
-                        fn #getter_name () -> svm_sdk::Address {
+                        fn #getter_name (#receiver) -> svm_sdk::Address {
                             #includes
 
                             svm_sdk::storage::ops::get_addr::<StorageImpl>(#id)
@@ -217,7 +614,7 @@ fn getter_ast(var: &Var) -> TokenStream {
             match elem_ty.as_str() {
                 "i8" | "u8" | "i16" | "u16" | "i32" | "u32" => {
                     quote! {
-                        fn #getter_name (index: usize) -> #elem_ty {
+                        fn #getter_name (#receiver index: usize) -> #elem_ty {
                             #includes
 
                             let value = svm_sdk::storage::ops::array_get32::<StorageImpl>(#id, index, #length);
@@ -227,7 +624,7 @@ fn getter_ast(var: &Var) -> TokenStream {
                 }
                 "u64" | "i64" => {
                     quote! {
-                        fn #getter_name (index: usize) -> #elem_ty {
+                        fn #getter_name (#receiver index: usize) -> #elem_ty {
                             #includes
 
                             let value = svm_sdk::storage::ops::array_get64::<StorageImpl>(#id, index, #length);
@@ -237,7 +634,7 @@ fn getter_ast(var: &Var) -> TokenStream {
                 }
                 "bool" => {
                     quote! {
-                        fn #getter_name (index: usize) -> bool {
+                        fn #getter_name (#receiver index: usize) -> bool {
                             #includes
 
                             svm_sdk::storage::ops::array_get_bool::<StorageImpl>(#id, index, #length)
@@ -245,14 +642,14 @@ fn getter_ast(var: &Var) -> TokenStream {
                     }
                 }
                 "svm_sdk :: Amount" | "Amount" => quote! {
-                    fn #getter_name (index: usize) -> svm_sdk::Amount {
+                    fn #getter_name (#receiver index: usize) -> svm_sdk::Amount {
                         #includes
 
                         svm_sdk::storage::ops::array_get_amount::<StorageImpl>(#id, index, #length)
                     }
                 },
                 "svm_sdk :: Address" | "Address" => quote! {
-                    fn #getter_name (index: usize) -> svm_sdk::Address {
+                    fn #getter_name (#receiver index: usize) -> svm_sdk::Address {
                         #includes
 
                         svm_sdk::storage::ops::array_get_addr::<StorageImpl>(#id, index, #length)
@@ -264,17 +661,87 @@ fn getter_ast(var: &Var) -> TokenStream {
     }
 }
 
-fn setter_ast(var: &Var) -> TokenStream {
+fn setter_ast(var: &Var, receiver: &TokenStream) -> TokenStream {
     let includes = include_storage_ast();
 
     match var {
+        // Accessed by its own handle (see `accessors_ast`)
+        Var::Struct { .. } => quote! {},
+        Var::Dynamic { id, name, kind, .. } => {
+            let setter_name = setter_ident(name);
+
+            match kind {
+                DynKind::Mapping { key_ty, value_ty } => {
+                    let remove_name = Ident::new(&format!("remove_{}", name), Span::call_site());
+                    let (key_param, key_ref) = dyn_param_ast(key_ty, quote! { key });
+                    let (value_param, value_ref) = dyn_param_ast(value_ty, quote! { value });
+                    let mapping = mapping_ast(key_ty, value_ty);
+
+                    quote! {
+                        fn #setter_name (#receiver key: #key_param, value: #value_param) {
+                            #includes
+
+                            #mapping::new(#id).set::<StorageImpl>(#key_ref, #value_ref);
+                        }
+
+                        fn #remove_name (#receiver key: #key_param) {
+                            #includes
+
+                            #mapping::new(#id).remove::<StorageImpl>(#key_ref);
+                        }
+                    }
+                }
+                DynKind::Vec { elem_ty } => {
+                    let push_name = Ident::new(&format!("push_{}", name), Span::call_site());
+                    let pop_name = Ident::new(&format!("pop_{}", name), Span::call_site());
+                    let (value_param, value_ref) = dyn_param_ast(elem_ty, quote! { value });
+                    let vec = dyn_vec_ast(elem_ty);
+                    let elem_ty = dyn_item_ast(elem_ty);
+
+                    quote! {
+                        fn #setter_name (#receiver index: u32, value: #value_param) {
+                            #includes
+
+                            #vec::new(#id).set::<StorageImpl>(index, #value_ref);
+                        }
+
+                        fn #push_name (#receiver value: #value_param) {
+                            #includes
+
+                            #vec::new(#id).push::<StorageImpl>(#value_ref);
+                        }
+
+                        fn #pop_name (#receiver) -> #elem_ty {
+                            #includes
+
+                            #vec::new(#id).pop::<StorageImpl>()
+                        }
+                    }
+                }
+            }
+        }
+        Var::Enum {
+            id, name, ty, repr, ..
+        } => {
+            let setter_name = setter_ident(name);
+            let repr = Ident::new(repr.as_str(), Span::call_site());
+
+            quote! {
+                fn #setter_name (#receiver value: #ty) {
+                    #includes
+
+                    let value: #repr = (&value).into();
+                    svm_sdk::storage::ops::set32::<StorageImpl>(#id, value as u32);
+                }
+            }
+        }
         Var::Primitive { id, name, ty, .. } => {
             let setter_name = setter_ident(name);
 
             match ty.as_str() {
                 "i8" | "u8" | "i16" | "u16" | "i32" | "u32" => {
                     quote! {
-                        fn #setter_name (value: #ty) {
+                        fn #setter_name (#receiver value: #ty) {
                             #includes
 
                             svm_sdk::storage::ops::set32::<StorageImpl>(#id, value as u32);
@@ -283,7 +750,7 @@ fn setter_ast(var: &Var) -> TokenStream {
                 }
                 "u64" | "i64" => {
                     quote! {
-                        fn #setter_name (value: #ty) {
+                        fn #setter_name (#receiver value: #ty) {
                             #includes
 
                             svm_sdk::storage::ops::set64::<StorageImpl>(#id, value as u64);
@@ -291,21 +758,21 @@ fn setter_ast(var: &Var) -> TokenStream {
                     }
                 }
                 "bool" => quote! {
-                    fn #setter_name (value: bool) {
+                    fn #setter_name (#receiver value: bool) {
                         #includes
 
-                        svm_sdk::storage::ops::set_bool::<StorageImpl>(#id, value);
+                        svm_sdk::storage::ops::set_bit::<StorageImpl>(#id, value);
                     }
                 },
                 "svm_sdk :: Amount" | "Amount" => quote! {
-                    fn #setter_name (value: svm_sdk::Amount) {
+                    fn #setter_name (#receiver value: svm_sdk::Amount) {
                         #includes
 
                         svm_sdk::storage::ops::set_amount::<StorageImpl>(#id, value);
                     }
                 },
                 "svm_sdk :: Address" | "Address" => quote! {
-                    fn #setter_name(value: &svm_sdk::Address) {
+                    fn #setter_name (#receiver value: &svm_sdk::Address) {
                         #includes
 
                         svm_sdk::storage::ops::set_addr::<StorageImpl>(#id, value);
@@ -326,7 +793,7 @@ fn setter_ast(var: &Var) -> TokenStream {
             match elem_ty.as_str() {
                 "i8" | "u8" | "i16" | "u16" | "i32" | "u32" => {
                     quote! {
-                        fn #setter_name (index: usize, value: #elem_ty) {
+                        fn #setter_name (#receiver index: usize, value: #elem_ty) {
                             #includes
 
                             svm_sdk::storage::ops::array_set32::<StorageImpl>(#id, index, #length, value as u32);
@@ -335,7 +802,7 @@ fn setter_ast(var: &Var) -> TokenStream {
                 }
                 "u64" | "i64" => {
                     quote! {
-                        fn #setter_name (index: usize, value: #elem_ty) {
+                        fn #setter_name (#receiver index: usize, value: #elem_ty) {
                             #includes
 
                             svm_sdk::storage::ops::array_set64::<StorageImpl>(#id, index, #length, value as u64);
@@ -344,7 +811,7 @@ fn setter_ast(var: &Var) -> TokenStream {
                 }
                 "bool" => {
                     quote! {
-                        fn #setter_name (index: usize, value: bool) {
+                        fn #setter_name (#receiver index: usize, value: bool) {
                             #includes
 
                             svm_sdk::storage::ops::array_set_bool::<StorageImpl>(#id, index, #length, value);
@@ -353,7 +820,7 @@ fn setter_ast(var: &Var) -> TokenStream {
                 }
                 "svm_sdk :: Amount" | "Amount" => {
                     quote! {
-                        fn #setter_name (index: usize, value: svm_sdk::Amount) {
+                        fn #setter_name (#receiver index: usize, value: svm_sdk::Amount) {
                             #includes
 
                             svm_sdk::storage::ops::array_set_amount::<StorageImpl>(#id, index, #length, value);
@@ -362,7 +829,7 @@ fn setter_ast(var: &Var) -> TokenStream {
                 }
                 "svm_sdk :: Address" | "Address" => {
                     quote! {
-                        fn #setter_name (index: usize, value: &svm_sdk::Address) {
+                        fn #setter_name (#receiver index: usize, value: &svm_sdk::Address) {
                             #includes
 
                             svm_sdk::storage::ops::array_set_addr::<StorageImpl>(#id, index, #length, value);
@@ -383,6 +850,41 @@ fn setter_ident(var_name: &Ident) -> Ident {
     Ident::new(&format!("set_{}", var_name), Span::call_site())
 }
 
+/// The type of a `Mapping` (or a `DynVec`) item, as exposed by the generated methods.
+fn dyn_item_ast(ty: &PrimType) -> TokenStream {
+    match ty.as_str() {
+        "svm_sdk :: Amount" | "Amount" => quote! { svm_sdk::Amount },
+        "svm_sdk :: Address" | "Address" => quote! { svm_sdk::Address },
+        _ => quote! { #ty },
+    }
+}
+
+/// Returns the type of a generated method's param `param` holding a `Mapping` (or a `DynVec`) item,
+/// along with a reference to it.
+///
+/// An `Address` is given by reference (as by the setters of the fixed variables).
+fn dyn_param_ast(ty: &PrimType, param: TokenStream) -> (TokenStream, TokenStream) {
+    let item_ty = dyn_item_ast(ty);
+
+    match ty.as_str() {
+        "svm_sdk :: Address" | "Address" => (quote! { &#item_ty }, param),
+        _ => (item_ty, quote! { &#param }),
+    }
+}
+
+fn mapping_ast(key_ty: &PrimType, value_ty: &PrimType) -> TokenStream {
+    let key_ty = dyn_item_ast(key_ty);
+    let value_ty = dyn_item_ast(value_ty);
+
+    quote! { svm_sdk::storage::Mapping::<#key_ty, #value_ty> }
+}
+
+fn dyn_vec_ast(elem_ty: &PrimType) -> TokenStream {
+    let elem_ty = dyn_item_ast(elem_ty);
+
+    quote! { svm_sdk::storage::DynVec::<#elem_ty> }
+}
+
 fn include_storage_ast() -> TokenStream {
     quote! {
         use svm_sdk::traits::Storage;
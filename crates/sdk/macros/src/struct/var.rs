@@ -3,7 +3,7 @@ use quote::{quote, ToTokens};
 
 use std::fmt;
 
-use crate::PrimType;
+use crate::{EnumRepr, PrimType};
 
 pub enum Var {
     Primitive {
@@ -12,6 +12,9 @@ pub enum Var {
         name: Ident,
         ty: PrimType,
         byte_count: usize,
+        default: Option<Vec<u8>>,
+        /// The bit (within the byte at `offset`) of a packed `bool`.
+        bit: Option<u8>,
     },
     Array {
         id: VarId,
@@ -21,6 +24,45 @@ pub enum Var {
         length: u32,
         byte_count: usize,
     },
+    /// A field of a C-like `enum` declared inside `#[template]`
+    /// (persisted as the value of its variant, see `EnumRepr`).
+    Enum {
+        id: VarId,
+        offset: usize,
+        name: Ident,
+        ty: PrimType,
+        repr: EnumRepr,
+        byte_count: usize,
+    },
+    /// A `Mapping<K, V>` or a `DynVec<T>` field.
+    ///
+    /// Its (fixed) variable holds the number of its entries,
+    /// while the entries themselves are keyed by the variable's id.
+    Dynamic {
+        id: VarId,
+        offset: usize,
+        name: Ident,
+        kind: DynKind,
+        byte_count: usize,
+    },
+    /// A field of a named struct declared inside `#[template]`.
+    ///
+    /// It isn't a variable on its own: the struct's fields are laid out (flattened) right in place of it.
+    Struct {
+        name: Ident,
+        ty: Ident,
+        vars: Vec<Var>,
+    },
+}
+
+pub enum DynKind {
+    Mapping {
+        key_ty: PrimType,
+        value_ty: PrimType,
+    },
+    Vec {
+        elem_ty: PrimType,
+    },
 }
 
 impl Var {
@@ -28,6 +70,47 @@ impl Var {
         match *self {
             Var::Primitive { byte_count, .. } => byte_count,
             Var::Array { byte_count, .. } => byte_count,
+            Var::Enum { byte_count, .. } => byte_count,
+            Var::Dynamic { byte_count, .. } => byte_count,
+            Var::Struct { .. } => self.total_byte_count(),
+        }
+    }
+
+    /// The number of bytes the variable takes (for an `Array`, the sum of its elements).
+    ///
+    /// A byte shared by packed `bool`s is accounted to the first of them.
+    pub fn total_byte_count(&self) -> usize {
+        match *self {
+            Var::Primitive { bit: Some(bit), .. } if bit > 0 => 0,
+            Var::Primitive { byte_count, .. } => byte_count,
+            Var::Array {
+                byte_count, length, ..
+            } => byte_count * (length as usize),
+            Var::Enum { byte_count, .. } => byte_count,
+            Var::Dynamic { byte_count, .. } => byte_count,
+            Var::Struct { ref vars, .. } => vars.iter().map(Var::total_byte_count).sum(),
+        }
+    }
+
+    pub fn name(&self) -> &Ident {
+        match self {
+            Var::Primitive { name, .. } => name,
+            Var::Array { name, .. } => name,
+            Var::Enum { name, .. } => name,
+            Var::Dynamic { name, .. } => name,
+            Var::Struct { name, .. } => name,
+        }
+    }
+
+    pub fn type_name(&self) -> String {
+        match self {
+            Var::Primitive { ty, .. } => ty.as_str().to_string(),
+            Var::Array {
+                elem_ty, length, ..
+            } => format!("[{}; {}]", elem_ty.as_str(), length),
+            Var::Enum { ty, .. } => ty.as_str().to_string(),
+            Var::Dynamic { kind, .. } => kind.type_name(),
+            Var::Struct { ty, .. } => ty.to_string(),
         }
     }
 }
@@ -35,7 +118,7 @@ impl Var {
 impl fmt::Debug for Var {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Var::Primitive { id, name, ty, .. } => {
+            Var::Primitive { id, name, ty, .. } | Var::Enum { id, name, ty, .. } => {
                 writeln!(f, "Var #{} - {}: {}", id.0, name, ty.as_str())
             }
             Var::Array {
@@ -52,6 +135,44 @@ impl fmt::Debug for Var {
                 elem_ty.as_str(),
                 length
             ),
+            Var::Dynamic { id, name, kind, .. } => {
+                writeln!(f, "Var #{} - {}: {}", id.0, name, kind.type_name())
+            }
+            Var::Struct { name, ty, vars } => {
+                writeln!(f, "Struct {}: {}", name, ty)?;
+
+                vars.iter().try_for_each(|var| write!(f, "  {:?}", var))
+            }
+        }
+    }
+}
+
+/// Flattens `vars` into the variables they are made of (i.e the ones of the `FixedLayout`),
+/// each along with its dotted path (for example, `owner.balance` for field `balance` of a `Struct` field `owner`).
+pub fn flatten_vars(vars: &[Var]) -> Vec<(String, &Var)> {
+    let mut flat = Vec::new();
+
+    for var in vars.iter() {
+        match var {
+            Var::Struct { name, vars, .. } => {
+                for (path, var) in flatten_vars(vars) {
+                    flat.push((format!("{}.{}", name, path), var));
+                }
+            }
+            _ => flat.push((var.name().to_string(), var)),
+        }
+    }
+
+    flat
+}
+
+impl DynKind {
+    pub fn type_name(&self) -> String {
+        match self {
+            DynKind::Mapping { key_ty, value_ty } => {
+                format!("Mapping<{}, {}>", key_ty.as_str(), value_ty.as_str())
+            }
+            DynKind::Vec { elem_ty } => format!("DynVec<{}>", elem_ty.as_str()),
         }
     }
 }
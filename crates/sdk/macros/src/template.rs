@@ -1,20 +1,28 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use syn::{Error, Item, ItemMod, ItemType, ItemUse, Result};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{Error, Item, ItemMod, ItemType, ItemUse, Lit, Meta, MetaNameValue, Result, Token};
 
-use super::{function, r#struct};
-use crate::{json, meta, Function, Struct, TemplateMeta};
+use super::{function, r#const, r#enum, r#struct};
+use crate::{json, meta, Const, Enum, Function, Struct, TemplateMeta};
 
-use r#function::{func_attrs, has_default_fundable_hook_attr};
+use r#function::{
+    find_raw_attr, func_attrs, has_default_fundable_hook_attr, has_fallback_attr, FuncAttrKind,
+};
 use r#struct::has_storage_attr;
 
 pub struct Template {
     name: Ident,
+    doc: String,
     functions: Vec<Function>,
     structs: Vec<Struct>,
+    enums: Vec<Enum>,
+    consts: Vec<Const>,
     imports: Vec<ItemUse>,
     aliases: Vec<ItemType>,
     default_fundable_hook: Option<Ident>,
+    max_storage_bytes: Option<u32>,
 }
 
 impl Template {
@@ -22,6 +30,10 @@ impl Template {
         self.name.to_string()
     }
 
+    pub fn doc(&self) -> &str {
+        &self.doc
+    }
+
     pub fn functions(&self) -> &[Function] {
         &self.functions
     }
@@ -30,6 +42,14 @@ impl Template {
         &self.structs
     }
 
+    pub fn enums(&self) -> &[Enum] {
+        &self.enums
+    }
+
+    pub fn consts(&self) -> &[Const] {
+        &self.consts
+    }
+
     pub fn imports(&self) -> &[ItemUse] {
         &self.imports
     }
@@ -45,18 +65,40 @@ impl Template {
     pub fn set_default_fundable_hook(&mut self, hook: Ident) {
         self.default_fundable_hook = Some(hook)
     }
+
+    pub fn max_storage_bytes(&self) -> Option<u32> {
+        self.max_storage_bytes
+    }
+
+    pub fn set_max_storage_bytes(&mut self, max_storage_bytes: Option<u32>) {
+        self.max_storage_bytes = max_storage_bytes
+    }
+}
+
+/// The arguments given to `#[template(...)]`.
+#[derive(Default)]
+pub struct TemplateArgs {
+    max_storage_bytes: Option<u32>,
 }
 
-pub fn expand(_args: TokenStream, input: TokenStream) -> Result<(TokenStream, TemplateMeta)> {
+pub fn expand(args: TokenStream, input: TokenStream) -> Result<(TokenStream, TemplateMeta)> {
+    let args = parse_args(args)?;
     let module = syn::parse2(input)?;
-    let template = parse_template(module)?;
-    let meta = meta::template_meta(&template)?;
+    let mut template = parse_template(module)?;
+    template.set_max_storage_bytes(args.max_storage_bytes);
 
     let _imports = template.imports();
     let _aliases = template.aliases();
 
+    // Expanding first, so each item reports its own errors (before computing the `TemplateMeta`)
+    let consts = expand_consts(&template)?;
+    let enums = expand_enums(&template)?;
     let structs = expand_structs(&template)?;
     let functions = expand_functions(&template)?;
+
+    let meta = meta::template_meta(&template)?;
+    validate_storage_budget(&template, &meta)?;
+
     let verify_export = export_verify_ast();
     let alloc_export = export_alloc_ast();
 
@@ -65,6 +107,10 @@ pub fn expand(_args: TokenStream, input: TokenStream) -> Result<(TokenStream, Te
 
         #alloc_export
 
+        #consts
+
+        #enums
+
         #structs
 
         #functions
@@ -73,21 +119,49 @@ pub fn expand(_args: TokenStream, input: TokenStream) -> Result<(TokenStream, Te
     Ok((ast, meta))
 }
 
+pub fn parse_args(args: TokenStream) -> Result<TemplateArgs> {
+    let mut parsed = TemplateArgs::default();
+
+    let args = Punctuated::<Meta, Token![,]>::parse_terminated.parse2(args)?;
+
+    for arg in args.iter() {
+        match arg {
+            Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Int(lit),
+                ..
+            }) if path.is_ident("max_storage_bytes") => {
+                if parsed.max_storage_bytes.is_some() {
+                    let msg = "`max_storage_bytes` can be given only once";
+                    return Err(Error::new_spanned(arg, msg));
+                }
+
+                parsed.max_storage_bytes = Some(lit.base10_parse()?);
+            }
+            _ => {
+                let msg = "`#[template]` supports only `#[template(max_storage_bytes = <number>)]`";
+                return Err(Error::new_spanned(arg, msg));
+            }
+        }
+    }
+
+    Ok(parsed)
+}
+
 pub fn parse_template(mut raw_template: ItemMod) -> Result<Template> {
     let name = raw_template.ident.clone();
+    let doc = meta::doc_comments(&raw_template.attrs);
 
     let mut functions = Vec::new();
     let mut structs = Vec::new();
+    let mut enums = Vec::new();
+    let mut consts = Vec::new();
     let mut imports = Vec::new();
     let mut aliases = Vec::new();
 
     let (_, content) = raw_template.content.take().unwrap();
 
     for item in content {
-        // TODO: Is is possible to extract the `item` real `Span`?
-        // See <https://github.com/spacemeshos/svm/issues/278>.
-        let span = Span::call_site();
-
         match item {
             Item::Fn(item) => {
                 let func = Function::new(item, functions.len());
@@ -97,74 +171,35 @@ pub fn parse_template(mut raw_template: ItemMod) -> Result<Template> {
                 let strukt = Struct::new(item);
                 structs.push(strukt);
             }
-            Item::Use(item) => imports.push(item),
-            Item::Type(item) => aliases.push(item),
-            Item::Const(..) => {
-                let msg = "declaring `const` inside `#[template]` is not supported.";
-                return Err(Error::new(span, msg));
-            }
-            Item::Enum(..) => {
-                let msg = "declaring `enum` inside `#[template]` is not supported.";
-                return Err(Error::new(span, msg));
-            }
-            Item::ExternCrate(..) => {
-                let msg = "using `extern crate` inside `#[template]` is not supported.";
-                return Err(Error::new(span, msg));
-            }
-            Item::ForeignMod(..) => {
-                let msg =
-                    "using foreign items such as `extern \"C\"` inside `#[template]` is not supported.";
-                return Err(Error::new(span, msg));
+            Item::Enum(item) => {
+                let e = Enum::new(item)?;
+                enums.push(e);
             }
-            Item::Impl(..) => {
-                let msg = "using `impl` inside `#[template]` is not supported.";
-                return Err(Error::new(span, msg));
+            Item::Const(item) => {
+                let c = Const::new(item)?;
+                consts.push(c);
             }
-            Item::Macro(..) => {
-                let msg = "declaring `macro_rules!` inside `#[template]` is not supported.";
-                return Err(Error::new(span, msg));
-            }
-            Item::Macro2(..) => {
-                let msg = "declaring `macro` inside `#[template]` is not supported.";
-                return Err(Error::new(span, msg));
-            }
-            Item::Mod(..) => {
-                let msg = "declaring new modules inside `#[template]` is not supported.";
-                return Err(Error::new(span, msg));
-            }
-            Item::Static(..) => {
-                let msg = "declaring new `static` items inside `#[template]` is not supported.";
-                return Err(Error::new(span, msg));
-            }
-            Item::Trait(..) => {
-                let msg = "declaring new traits inside `#[template]` is not supported.";
-                return Err(Error::new(span, msg));
-            }
-            Item::TraitAlias(..) => {
-                let msg = "using trait aliases inside `#[template]` is not supported.";
-                return Err(Error::new(span, msg));
-            }
-            Item::Union(..) => {
-                let msg = "declaring `union` inside `#[template]` is not supported.";
-                return Err(Error::new(span, msg));
-            }
-            Item::Verbatim(item) => {
-                let msg = format!("invalid Rust code: {}", item);
-                return Err(Error::new(span, msg));
-            }
-            Item::__TestExhaustive(..) => unreachable!(),
+            Item::Use(item) => imports.push(item),
+            Item::Type(item) => aliases.push(item),
+            item => return Err(unsupported_item(&item)),
         }
     }
 
     let mut template = Template {
         name,
+        doc,
         functions,
         structs,
+        enums,
+        consts,
         imports,
         aliases,
         default_fundable_hook: None,
+        max_storage_bytes: None,
     };
 
+    validate_single_fallback(&template)?;
+
     let default = extract_default_fundable_hook(&template)?;
 
     if default.is_some() {
@@ -174,8 +209,39 @@ pub fn parse_template(mut raw_template: ItemMod) -> Result<Template> {
     Ok(template)
 }
 
+/// Reports an `item` which can't be declared inside `#[template]` (at the `item` itself).
+fn unsupported_item(item: &Item) -> Error {
+    let msg = match item {
+        Item::ExternCrate(..) => "using `extern crate` inside `#[template]` is not supported.",
+        Item::ForeignMod(..) => {
+            "using foreign items such as `extern \"C\"` inside `#[template]` is not supported."
+        }
+        Item::Impl(..) => "using `impl` inside `#[template]` is not supported.",
+        Item::Macro(..) => "declaring `macro_rules!` inside `#[template]` is not supported.",
+        Item::Macro2(..) => "declaring `macro` inside `#[template]` is not supported.",
+        Item::Mod(..) => "declaring new modules inside `#[template]` is not supported.",
+        Item::Static(..) => "declaring new `static` items inside `#[template]` is not supported.",
+        Item::Trait(..) => "declaring new traits inside `#[template]` is not supported.",
+        Item::TraitAlias(..) => "using trait aliases inside `#[template]` is not supported.",
+        Item::Union(..) => "declaring `union` inside `#[template]` is not supported.",
+        Item::Verbatim(tokens) => {
+            let msg = format!("invalid Rust code: {}", tokens);
+
+            return Error::new_spanned(item, msg);
+        }
+        Item::Fn(..)
+        | Item::Struct(..)
+        | Item::Enum(..)
+        | Item::Const(..)
+        | Item::Use(..)
+        | Item::Type(..) => unreachable!(),
+        Item::__TestExhaustive(..) => unreachable!(),
+    };
+
+    Error::new_spanned(item, msg)
+}
+
 fn extract_default_fundable_hook(template: &Template) -> Result<Option<Ident>> {
-    let span = Span::call_site();
     let mut seen_default_fundable_hook = false;
     let mut default = None;
 
@@ -184,8 +250,10 @@ fn extract_default_fundable_hook(template: &Template) -> Result<Option<Ident>> {
 
         if has_default_fundable_hook_attr(&attrs) {
             if seen_default_fundable_hook {
-                return Err(Error::new(
-                    span,
+                let attr = find_raw_attr(func, FuncAttrKind::FundableHook).unwrap();
+
+                return Err(Error::new_spanned(
+                    attr,
                     "There can be only a single default `fundable hook`",
                 ));
             }
@@ -199,13 +267,80 @@ fn extract_default_fundable_hook(template: &Template) -> Result<Option<Ident>> {
     Ok(default)
 }
 
+fn validate_single_fallback(template: &Template) -> Result<()> {
+    let second = template
+        .functions()
+        .iter()
+        .filter(|func| has_fallback_attr(&func_attrs(func).unwrap()))
+        .nth(1);
+
+    if let Some(func) = second {
+        let attr = find_raw_attr(func, FuncAttrKind::Fallback).unwrap();
+
+        return Err(Error::new_spanned(
+            attr,
+            "There can be only a single `#[fallback]` function",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Ensures the `#[storage]` fits within the `#[template(max_storage_bytes = ...)]` budget (if any).
+///
+/// The error is reported at the `#[storage]` struct name.
+fn validate_storage_budget(template: &Template, meta: &TemplateMeta) -> Result<()> {
+    let budget = match meta.max_storage_bytes() {
+        Some(budget) => budget as usize,
+        None => return Ok(()),
+    };
+
+    let size: usize = meta.schema().iter().map(|var| var.total_byte_count()).sum();
+
+    if size > budget {
+        let breakdown: Vec<String> = meta
+            .schema()
+            .iter()
+            .map(|var| {
+                let bytes = var.total_byte_count();
+                let unit = if bytes == 1 { "byte" } else { "bytes" };
+
+                format!(
+                    "  `{}`: {} ({} {})",
+                    var.name(),
+                    var.type_name(),
+                    bytes,
+                    unit
+                )
+            })
+            .collect();
+
+        let msg = format!(
+            "The `#[storage]` takes {} bytes, exceeding the `max_storage_bytes` budget of {} bytes:\n{}",
+            size,
+            budget,
+            breakdown.join("\n")
+        );
+
+        let span = template
+            .structs()
+            .iter()
+            .find(|strukt| matches!(strukt.attrs(), Ok(attrs) if has_storage_attr(attrs)))
+            .map_or_else(Span::call_site, |strukt| strukt.raw_name().span());
+
+        return Err(Error::new(span, msg));
+    }
+
+    Ok(())
+}
+
 fn expand_structs(template: &Template) -> Result<TokenStream> {
     let mut structs = Vec::new();
 
     validate_structs(template)?;
 
     for strukt in template.structs() {
-        let strukt = r#struct::expand(strukt)?;
+        let strukt = r#struct::expand(strukt, template)?;
 
         structs.push(strukt);
     }
@@ -217,6 +352,38 @@ fn expand_structs(template: &Template) -> Result<TokenStream> {
     Ok(ast)
 }
 
+fn expand_consts(template: &Template) -> Result<TokenStream> {
+    let mut consts = Vec::new();
+
+    for c in template.consts() {
+        let c = r#const::expand(c)?;
+
+        consts.push(c);
+    }
+
+    let ast = quote! {
+        #(#consts)*
+    };
+
+    Ok(ast)
+}
+
+fn expand_enums(template: &Template) -> Result<TokenStream> {
+    let mut enums = Vec::new();
+
+    for e in template.enums() {
+        let e = r#enum::expand(e)?;
+
+        enums.push(e);
+    }
+
+    let ast = quote! {
+        #(#enums)*
+    };
+
+    Ok(ast)
+}
+
 fn validate_structs(template: &Template) -> Result<()> {
     let mut seen_storage = false;
 
@@ -225,8 +392,8 @@ fn validate_structs(template: &Template) -> Result<()> {
             Ok(attrs) => {
                 if has_storage_attr(attrs) {
                     if seen_storage {
-                        let msg = format!("A Template can have only a single `#[storage]`");
-                        let span = Span::call_site();
+                        let msg = "A Template can have only a single `#[storage]`";
+                        let span = strukt.raw_name().span();
 
                         return Err(Error::new(span, msg));
                     }
@@ -1,6 +1,11 @@
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{Error, Expr, ExprLit, Lit, Result, TypeArray, TypePath};
+use syn::{Error, Expr, ExprLit, ExprPath, Lit, Result, TypeArray, TypePath};
+
+use crate::r#const::find_int_const;
+use crate::r#enum::find_enum;
+use crate::r#struct::find_plain_struct;
+use crate::{Const, Template};
 
 pub struct PrimType {
     ty_raw: TokenStream,
@@ -36,6 +41,9 @@ pub enum Type {
         elems: Vec<Box<Type>>,
         tuple_raw: TokenStream,
     },
+
+    /// A user-defined struct (expected to `#[derive(AbiEncode, AbiDecode)]`).
+    Struct(PrimType),
 }
 
 impl ToTokens for Type {
@@ -44,27 +52,75 @@ impl ToTokens for Type {
             Type::Primitive(prim) => prim.to_tokens(tokens),
             Type::Array { array_raw, .. } => array_raw.to_tokens(tokens),
             Type::Tuple { tuple_raw, .. } => tuple_raw.to_tokens(tokens),
+            Type::Struct(ty) => ty.to_tokens(tokens),
         }
     }
 }
 
 impl Type {
-    pub fn new(ty: &syn::Type) -> Result<Self> {
+    /// Parses `ty` (an Array length may name one of the template's `consts`,
+    /// and a path may name one of the template's structs or `enum`s).
+    pub fn new(ty: &syn::Type, template: &Template) -> Result<Self> {
         match ty {
-            syn::Type::Array(ty) => parse_array_type(ty),
-            syn::Type::Path(ty) => {
-                let prim = parse_primitive_type(ty)?;
-                let ty = Type::Primitive(prim);
+            syn::Type::Array(ty) => parse_array_type(ty, template.consts()),
+            syn::Type::Path(ty) => parse_path_type(ty, template),
+            syn::Type::Tuple(ty) => parse_tuple_type(ty, template),
+            _ => Err(Error::new_spanned(ty, "Unsupported type")),
+        }
+    }
+}
 
-                Ok(ty)
-            }
-            syn::Type::Tuple(ty) => parse_tuple_type(ty),
-            _ => unreachable!(),
+/// A path is either a primitive, one of the `svm_sdk` types (see [`is_sdk_type`]),
+/// or the name of a (plain) struct or an `enum` declared inside `#[template]`.
+fn parse_path_type(path: &TypePath, template: &Template) -> Result<Type> {
+    if let Ok(prim) = parse_primitive_type(path) {
+        return Ok(Type::Primitive(prim));
+    }
+
+    let ty_raw = quote! { #path };
+    let ty_str = type_path_as_str(&path);
+
+    if !is_sdk_type(path) && !is_declared_type(path, template) {
+        let msg = format!(
+            "Unknown type: {} (expected a primitive, or a struct or an `enum` declared inside `#[template]`)",
+            ty_str
+        );
+
+        return Err(Error::new_spanned(path, msg));
+    }
+
+    Ok(Type::Struct(PrimType { ty_raw, ty_str }))
+}
+
+/// Whether `path` is one of the non-primitive types the `svm_sdk` can pass through the ABI
+/// (i.e `String` and `Option<T>`, optionally prefixed by `svm_sdk::`).
+fn is_sdk_type(path: &TypePath) -> bool {
+    let segments = &path.path.segments;
+    let ident = &segments.last().unwrap().ident;
+
+    let in_sdk = match segments.len() {
+        1 => true,
+        2 => segments[0].ident == "svm_sdk",
+        _ => false,
+    };
+
+    path.qself.is_none() && in_sdk && (ident == "String" || ident == "Option")
+}
+
+/// Whether `path` names a (plain) struct or an `enum` declared inside `#[template]`.
+fn is_declared_type(path: &TypePath, template: &Template) -> bool {
+    match path.path.get_ident() {
+        Some(ident) if path.qself.is_none() => {
+            let name = ident.to_string();
+
+            find_plain_struct(template.structs(), &name).is_some()
+                || find_enum(template.enums(), &name).is_some()
         }
+        _ => false,
     }
 }
 
-fn parse_primitive_type(path: &TypePath) -> Result<PrimType> {
+pub fn parse_primitive_type(path: &TypePath) -> Result<PrimType> {
     let ty_str = type_path_as_str(&path);
 
     match ty_str.as_str() {
@@ -88,18 +144,17 @@ fn parse_primitive_type(path: &TypePath) -> Result<PrimType> {
             Ok(prim)
         }
         _ => {
-            let span = Span::call_site();
             let msg = format!("Invalid `#[storage]` field type: {}", ty_str);
 
-            Err(Error::new(span, msg))
+            Err(Error::new_spanned(path, msg))
         }
     }
 }
 
-fn parse_array_type(ty: &TypeArray) -> Result<Type> {
+fn parse_array_type(ty: &TypeArray, consts: &[Const]) -> Result<Type> {
     let array_raw = quote! { #ty };
     let elem_ty = parse_array_element_type(ty)?;
-    let length = parse_array_length(ty)?;
+    let length = parse_array_length(ty, consts)?;
 
     let ty = Type::Array {
         array_raw,
@@ -109,22 +164,21 @@ fn parse_array_type(ty: &TypeArray) -> Result<Type> {
     Ok(ty)
 }
 
-fn parse_tuple_type(ty: &syn::TypeTuple) -> Result<Type> {
+fn parse_tuple_type(ty: &syn::TypeTuple, template: &Template) -> Result<Type> {
     let tuple_raw = quote! { #ty };
     let mut elems = Vec::new();
 
     for elem in ty.elems.iter() {
         match elem {
             syn::Type::Path(path) => {
-                let prim = parse_primitive_type(path)?;
-                let elem = Type::Primitive(prim);
+                let elem = parse_path_type(path, template)?;
                 elems.push(Box::new(elem));
             }
             syn::Type::Array(array) => {
-                let elem = parse_array_type(array)?;
+                let elem = parse_array_type(array, template.consts())?;
                 elems.push(Box::new(elem));
             }
-            _ => unreachable!(),
+            _ => return Err(Error::new_spanned(elem, "Unsupported tuple element type")),
         };
     }
 
@@ -135,34 +189,42 @@ fn parse_tuple_type(ty: &syn::TypeTuple) -> Result<Type> {
 fn parse_array_element_type(ty: &TypeArray) -> Result<PrimType> {
     match *ty.elem {
         syn::Type::Path(ref path) => parse_primitive_type(path),
-        _ => {
-            let span = Span::call_site();
-
-            Err(Error::new(
-                span,
-                "`Array elements must be primitives (for example: `svm_sdk::Amount`).",
-            ))
-        }
+        _ => Err(Error::new_spanned(
+            &ty.elem,
+            "`Array elements must be primitives (for example: `svm_sdk::Amount`).",
+        )),
     }
 }
 
-fn parse_array_length(array: &TypeArray) -> Result<u32> {
-    if let Expr::Lit(ExprLit { attrs, lit }) = &array.len {
-        assert!(attrs.is_empty());
+/// The length is either an integer literal or the name of an integer `const` (declared inside `#[template]`).
+fn parse_array_length(array: &TypeArray, consts: &[Const]) -> Result<u32> {
+    match &array.len {
+        Expr::Lit(ExprLit { attrs, lit }) => {
+            assert!(attrs.is_empty());
 
-        if let Lit::Int(num) = lit {
-            let num = num.base10_parse();
+            if let Lit::Int(num) = lit {
+                let num = num.base10_parse();
+
+                if num.is_ok() {
+                    return num;
+                }
+            }
+        }
+        Expr::Path(ExprPath { path, .. }) => {
+            let length = path
+                .get_ident()
+                .and_then(|ident| find_int_const(consts, &ident.to_string()));
 
-            if num.is_ok() {
-                return num;
+            if let Some(length) = length.filter(|length| (0..=u32::MAX as i128).contains(length)) {
+                return Ok(length as u32);
             }
         }
+        _ => (),
     }
 
-    let span = Span::call_site();
     let msg = "Invalid array length";
 
-    Err(Error::new(span, msg))
+    Err(Error::new_spanned(&array.len, msg))
 }
 
 fn type_path_as_str(path: &TypePath) -> String {
@@ -157,6 +157,46 @@
 /// }
 /// ```
 ///
+/// A Template can cap its storage size using `#[template(max_storage_bytes = ...)]`.
+/// When the `#[storage]` fields take more bytes than the budget, a compile-time error
+/// listing the size of each field will be raised. The budget is also recorded in the
+/// Template's meta (and then in its `Data Section`), so the Runtime rejects deploying
+/// a Template whose storage doesn't fit within it.
+///
+/// ```rust, no_run
+/// use svm_sdk::{template, Amount, Address};
+///
+/// #[template(max_storage_bytes = 64)]
+/// mod MyTemplate {
+///   #[storage]
+///   struct MyStorage {
+///     amount: Amount,
+///     addr: Address,
+///   }
+/// }
+/// ```
+///
+/// Besides fixed-sized fields, a `#[storage]` struct may have dynamic ones: a keyed `Mapping<K, V>`
+/// and a growable `DynVec<T>` (of Primitive keys, values and elements). Their entries are stored
+/// by the `svm_store_dyn / svm_load_dyn` vmcalls, each under the hash of the field's variable id and the entry's key.
+///
+/// ```rust, no_run
+/// use svm_sdk::{template, Amount, Address};
+///
+/// #[template]
+/// mod MyTemplate {
+///   #[storage]
+///   struct MyStorage {
+///     balances: Mapping<Address, Amount>,
+///     history: DynVec<u64>,
+///   }
+/// }
+/// ```
+///
+/// For the above `balances` the `get_balances(key)`, `set_balances(key, value)`, `contains_balances(key)`,
+/// `remove_balances(key)` and `balances_len()` methods will be generated. For the `history` the
+/// `get_history(index)`, `set_history(index, value)`, `push_history(value)`, `pop_history()` and `history_len()` ones.
+///
 /// Besides `#[storage]` each Template should expose a public API for the platform, otherwise
 /// no one can use it - that's the role of the endpoints.
 ///
@@ -232,6 +272,26 @@
 /// }
 /// ```
 ///
+/// ### `#[fallback]` proc-macro:
+///
+/// Calling a function which isn't exported by a Template usually fails.
+/// A Template may declare (at most) a single `#[fallback]` function - the `Runtime` will dispatch such calls to it.
+///
+/// The `#[fallback]` function is given the name of the requested function along with the original `calldata`
+/// (left undecoded), which makes it handy for implementing proxy/forwarder Templates.
+///
+/// ```rust, no_run
+/// use svm_sdk::template;
+///
+/// #[template]
+/// mod MyTemplate {
+///   #[fallback]
+///   fn forward(func: &str, calldata: &[u8]) -> u32 {
+///     calldata.len() as u32
+///   }
+/// }
+/// ```
+///
 /// ### Funding
 ///
 /// Each Account (instance of a Template) is holds a balance.
@@ -330,6 +390,32 @@
 ///     }
 /// }
 /// ```
+///
+/// ### User-defined types
+///
+/// A plain struct can be passed as an endpoint parameter (or be returned) by deriving `AbiEncode` and `AbiDecode`.
+/// Such a struct is encoded as an ABI `Array` of its fields (thus it can have at most 10 fields).
+///
+/// Its fields are Primitives, Arrays of Primitives, `Option`s or nested user-defined structs
+/// (deriving `AbiEncode` and `AbiDecode` as well).
+///
+/// ```rust, no_run
+/// use svm_sdk::{template, AbiDecode, AbiEncode, Address, Amount};
+///
+/// #[template]
+/// mod MyTemplate {
+///   #[derive(AbiEncode, AbiDecode)]
+///   struct Transfer {
+///     to: Address,
+///     amount: Amount,
+///   }
+///
+///   #[endpoint]
+///   fn amount(transfer: Transfer) -> Amount {
+///     transfer.amount
+///   }
+/// }
+/// ```
 
 #[cfg(all(feature = "static-alloc", feature = "dynamic-alloc"))]
 compile_error!("Cannot have both `static-alloc` and `dynamic-alloc` features turned-on");
@@ -338,12 +424,12 @@ compile_error!("Cannot have both `static-alloc` and `dynamic-alloc` features tur
 compile_error!("Must have either `static-alloc` or `dynamic-alloc` features turned-on");
 
 /// Logging API
-pub use svm_abi_decoder::{CallData, DecodeError, ReturnData};
-pub use svm_sdk_macros::template;
+pub use svm_abi_decoder::{CallData, DecodeError, ReturnData, VerifyData};
+pub use svm_sdk_macros::{template, AbiDecode, AbiEncode};
 
-pub use svm_sdk_std::{ensure, log};
+pub use svm_sdk_std::{ensure, log, metric, panic};
 /// std
-pub use svm_sdk_std::{Option, Result, Vec};
+pub use svm_sdk_std::{Option, Result, String, Vec};
 
 // alloc
 //
@@ -359,20 +445,225 @@ compile_error!("must have at least one feature flag turned-on (`ffi` or `mock`)"
 compile_error!("cannot have both feature-flags `ffi` and `mock` turned-on");
 
 pub mod host {
+    use svm_abi_encoder::{ByteSize, Encoder};
+    use svm_sdk_host::traits::Host;
+
     #[cfg(feature = "ffi")]
     pub use svm_sdk_host::ExtHost;
 
     #[cfg(feature = "mock")]
-    pub use svm_sdk_host::MockHost;
+    pub use svm_sdk_host::{
+        ExpectedCall, ExpectedTransfer, HostCall, MockHost, ScriptedCall, Verification,
+    };
+
+    #[cfg(feature = "ffi")]
+    use svm_sdk_host::ExtHost as Node;
+
+    #[cfg(feature = "mock")]
+    use svm_sdk_host::MockHost as Node;
+
+    /// Returns a decoder of the `verifydata` of the running transaction.
+    ///
+    /// The `verifydata` is available only while running `svm_verify`.
+    pub fn verifydata() -> crate::VerifyData {
+        crate::VerifyData::new(Node.verifydata())
+    }
+
+    /// Returns the arguments given to a `#[fallback]` function: the name of the requested
+    /// (non-existing) function along with the original `calldata`.
+    ///
+    /// The `Runtime` prefixes the original `calldata` with the requested function name
+    /// (a single byte holding the name's length, followed by its UTF-8 bytes).
+    pub fn fallback_args() -> (&'static str, &'static [u8]) {
+        let bytes = Node.calldata();
+
+        crate::ensure!(bytes.len() > 0);
+
+        let (len, bytes) = (bytes[0] as usize, &bytes[1..]);
+
+        crate::ensure!(bytes.len() >= len);
+
+        let (func, calldata) = bytes.split_at(len);
+
+        match core::str::from_utf8(func) {
+            Ok(func) => (func, calldata),
+            Err(..) => crate::panic(),
+        }
+    }
+
+    /// Encodes `value` and sets it as the `returndata` of the running transaction.
+    ///
+    /// See also the [`return_value!`](crate::return_value) macro.
+    pub fn set_return_value<T>(value: &T)
+    where
+        T: Encoder<crate::Vec<u8>> + ByteSize,
+    {
+        Node.set_return_value(value);
+    }
+}
+
+/// The `return_value!` macro sets the `returndata` of the running transaction and returns.
+///
+/// Any type implementing the ABI encoding (including `#[derive(AbiEncode)]` structs) can be returned.
+/// Since it returns from the enclosing function, the macro is intended to be used within
+/// `#[endpoint]` (or `#[ctor]`) functions having no declared return type - which makes early returns
+/// possible:
+///
+/// ```rust, no_run
+/// use svm_sdk::{return_value, template};
+///
+/// #[template]
+/// mod MyTemplate {
+///   #[endpoint]
+///   fn div(a: u32, b: u32) {
+///     if b == 0 {
+///       return_value!(false);
+///     }
+///
+///     return_value!((true, a / b));
+///   }
+/// }
+/// ```
+#[macro_export]
+macro_rules! return_value {
+    ($expr:expr) => {{
+        $crate::host::set_return_value(&$expr);
+
+        return;
+    }};
+}
+
+/// The `sdk_assert!` macro panics when the given condition doesn't hold (optionally logging a message first).
+///
+/// It's intended for development-time checks only: unless the `debug-template` feature is turned-on,
+/// the macro expands to nothing (the condition isn't even evaluated), so deployed Templates don't pay any gas for it.
+///
+/// ```rust, no_run
+/// use svm_sdk::{sdk_assert, template};
+///
+/// #[template]
+/// mod MyTemplate {
+///   #[endpoint]
+///   fn div(a: u32, b: u32) -> u32 {
+///     sdk_assert!(b != 0, "division by zero");
+///
+///     a / b
+///   }
+/// }
+/// ```
+#[cfg(feature = "debug-template")]
+#[macro_export]
+macro_rules! sdk_assert {
+    ($expr:expr) => {
+        $crate::sdk_assert!($expr, concat!("assertion failed: ", stringify!($expr)))
+    };
+    ($expr:expr, $msg:expr) => {{
+        if !$expr {
+            $crate::sdk_debug!($msg);
+            $crate::panic();
+        }
+    }};
+}
+
+/// Expands to nothing (the `debug-template` feature is turned-off).
+#[cfg(not(feature = "debug-template"))]
+#[macro_export]
+macro_rules! sdk_assert {
+    ($($tt:tt)*) => {};
+}
+
+/// The `sdk_debug!` macro logs the given `&str` message.
+///
+/// Same as [`sdk_assert!`], it expands to nothing unless the `debug-template` feature is turned-on.
+#[cfg(feature = "debug-template")]
+#[macro_export]
+macro_rules! sdk_debug {
+    ($msg:expr) => {{
+        $crate::debug::log($msg);
+    }};
+}
+
+/// Expands to nothing (the `debug-template` feature is turned-off).
+#[cfg(not(feature = "debug-template"))]
+#[macro_export]
+macro_rules! sdk_debug {
+    ($($tt:tt)*) => {};
+}
+
+#[cfg(feature = "debug-template")]
+#[doc(hidden)]
+pub mod debug {
+    use crate::{String, Vec};
+
+    /// Logs `msg` (when running against the `MockHost`, it's recorded under its `logs`).
+    #[cfg(feature = "ffi")]
+    pub fn log(msg: &str) {
+        crate::log(&to_string(msg));
+    }
+
+    /// Logs `msg` (when running against the `MockHost`, it's recorded under its `logs`).
+    #[cfg(feature = "mock")]
+    pub fn log(msg: &str) {
+        svm_sdk_host::MockHost::log(msg, 0);
+    }
+
+    pub fn to_string(msg: &str) -> String {
+        let mut bytes = Vec::with_capacity(msg.len());
+
+        for b in msg.as_bytes().iter() {
+            bytes.push(*b);
+        }
+
+        unsafe { String::new_unchecked(bytes) }
+    }
+
+    #[cfg(all(test, feature = "mock"))]
+    mod tests {
+        extern crate std;
+
+        use crate::host::MockHost;
+
+        #[test]
+        fn sdk_assert_logs_and_panics() {
+            // the `MockHost` is shared, so a single test exercises both outcomes
+            MockHost::reset();
+
+            crate::sdk_assert!(1 + 1 == 2, "never logged");
+            crate::sdk_debug!("all good");
+
+            let res = std::panic::catch_unwind(|| {
+                let b = 0;
+
+                crate::sdk_assert!(b != 0, "division by zero");
+            });
+
+            assert!(res.is_err());
+            assert_eq!(
+                MockHost::logs(),
+                [("all good".into(), 0), ("division by zero".into(), 0)]
+            );
+        }
+    }
 }
 
 pub mod traits {
-    pub use svm_abi_encoder::{ByteSize, Encoder};
+    pub use svm_abi_encoder::{ByteSize, Encoder, Push};
     pub use svm_sdk_host::traits::Host;
     pub use svm_sdk_storage::Storage;
 }
 
+pub mod value {
+    pub use svm_sdk_types::value::{Composite, Primitive, Value};
+}
+
+#[doc(hidden)]
+pub mod abi {
+    pub use svm_abi_encoder::encode_array_header;
+}
+
 pub mod storage {
+    pub use svm_sdk_storage::{DynItem, DynVec, Mapping};
+
     #[cfg(feature = "ffi")]
     pub use svm_sdk_storage::ExtStorage;
 
@@ -391,6 +682,9 @@ pub mod storage {
             get_bool,
             set_bool,
 
+            get_bit,
+            set_bit,
+
             get_amount,
             set_amount,
 
@@ -413,7 +707,10 @@ pub mod storage {
             array_set_amount,
 
             array_get_addr,
-            array_set_addr
+            array_set_addr,
+
+            layout_len,
+            layout_count
         };
     }
 }
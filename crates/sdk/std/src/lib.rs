@@ -23,7 +23,10 @@
 #![deny(unreachable_code)]
 
 mod log;
-pub use log::log;
+pub use log::{log, log_event};
+
+mod metric;
+pub use metric::metric;
 
 mod string;
 pub use string::{DecDigit, HexDigit, String, StringBuilder, ToString};
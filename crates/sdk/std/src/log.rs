@@ -6,6 +6,8 @@ use crate::String;
 #[link_section = "svm"]
 extern "C" {
     fn svm_log(offset: u32, length: u32);
+
+    fn svm_log_event(code: u32, topics_ptr: u32, topics_count: u32, offset: u32, length: u32);
 }
 
 /// Logs the given [`String`]
@@ -22,3 +24,21 @@ pub fn log(data: &String) {
 pub fn log(_data: &String) {
     //
 }
+
+/// Logs an event of the template-defined `code`, having up to 4 indexed `topics`
+/// (so that it can be filtered by them) and `data`.
+#[cfg(target_arch = "wasm32")]
+pub fn log_event(code: u32, topics: &[[u8; 32]], data: &[u8]) {
+    let topics_ptr = topics.as_ptr() as u32;
+    let topics_count = topics.len() as u32;
+    let offset = data.as_ptr() as u32;
+    let length = data.len() as u32;
+
+    unsafe { svm_log_event(code, topics_ptr, topics_count, offset, length) }
+}
+
+/// Stub method implementation (when code isn't compiled into Wasm)
+#[cfg(not(target_arch = "wasm32"))]
+pub fn log_event(_code: u32, _topics: &[[u8; 32]], _data: &[u8]) {
+    //
+}
@@ -0,0 +1,20 @@
+/// The external function (a.k.a host function) to be called from `metric`.
+#[allow(unused)]
+#[cfg(target_arch = "wasm32")]
+#[link_section = "svm"]
+extern "C" {
+    fn svm_metric(id: u32, value: u64);
+}
+
+/// Reports the metric `id` with the given `value`
+/// (reporting the same `id` twice within a transaction keeps the last `value`).
+#[cfg(target_arch = "wasm32")]
+pub fn metric(id: u32, value: u64) {
+    unsafe { svm_metric(id, value) }
+}
+
+/// Stub method implementation (when code isn't compiled into Wasm)
+#[cfg(not(target_arch = "wasm32"))]
+pub fn metric(_id: u32, _value: u64) {
+    //
+}
@@ -6,7 +6,9 @@ pub use builder::StringBuilder;
 pub use digit::{DecDigit, HexDigit};
 pub use traits::ToString;
 
-use crate::Vec;
+use core::cmp::PartialEq;
+
+use crate::{Option, Vec};
 
 /// Fixed-Gas replacement for [`std::string::String`].
 pub enum String {
@@ -56,6 +58,26 @@ impl String {
         }
     }
 
+    /// Creates a new [`String`] out of UTF-8 encoded `data`.
+    ///
+    /// Returns `Option::None` when `data` isn't valid UTF-8.
+    pub fn from_utf8(data: Vec<u8>) -> Option<Self> {
+        match core::str::from_utf8(data.as_slice()) {
+            Ok(..) => Option::Some(unsafe { Self::new_unchecked(data) }),
+            Err(..) => Option::None,
+        }
+    }
+
+    /// Returns the length of the [`String`] (in bytes).
+    pub fn len(&self) -> usize {
+        self.as_bytes().len()
+    }
+
+    /// Returns whether the [`String`] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Returns a raw pointer to the underlying [`String`] first byte.
     pub fn as_ptr(&self) -> *const u8 {
         self.as_bytes().as_ptr()
@@ -94,18 +116,18 @@ impl String {
     }
 }
 
-#[cfg(any(test, feature = "debug"))]
+impl PartialEq for String {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes().eq(other.as_bytes())
+    }
+}
+
 impl core::fmt::Debug for String {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        extern crate std;
-
-        let ptr = self.as_ptr() as *mut u8;
-        let len = self.as_bytes().len();
-
-        let bytes = unsafe { core::slice::from_raw_parts(ptr, len) };
-        let string = std::string::String::from_utf8_lossy(bytes);
-
-        string.fmt(f)
+        match core::str::from_utf8(self.as_bytes()) {
+            Ok(s) => s.fmt(f),
+            Err(..) => f.write_str("<invalid UTF-8>"),
+        }
     }
 }
 
@@ -128,16 +150,8 @@ fn ensure_ascii(byte: u8) {
 
 #[cfg(test)]
 mod tests {
-    use core::cmp::PartialEq;
-
     use super::*;
 
-    impl PartialEq for String {
-        fn eq(&self, other: &Self) -> bool {
-            self.as_bytes().eq(other.as_bytes())
-        }
-    }
-
     #[test]
     fn string_builder_one_string() {
         let mut sb = StringBuilder::with_capacity(5);
@@ -173,4 +187,21 @@ mod tests {
 
         assert!(res.is_err());
     }
+
+    #[test]
+    fn string_from_utf8() {
+        let s = String::from_utf8(vec_of("Shalom עולם".as_bytes())).unwrap();
+        assert_eq!(s.to_std_string().as_str(), "Shalom עולם");
+
+        let s = String::from_utf8(vec_of(&[0xC3, 0x28]));
+        assert!(matches!(s, Option::None));
+    }
+
+    fn vec_of(bytes: &[u8]) -> Vec<u8> {
+        let mut vec = Vec::with_capacity(bytes.len());
+        for &b in bytes {
+            vec.push(b);
+        }
+        vec
+    }
 }
@@ -74,11 +74,20 @@ impl<T> Vec<T> {
 
     /// Returns a shared view over the underlying items.
     pub fn as_slice(&self) -> &[T] {
+        // An empty `Vec` may hold a null pointer (e.g given to `from_raw_parts`)
+        if self.len == 0 {
+            return &[];
+        }
+
         unsafe { core::slice::from_raw_parts(self.ptr, self.len) }
     }
 
     /// Returns a mutable view over the underlying items.
     pub fn as_mut(&mut self) -> &mut [T] {
+        if self.len == 0 {
+            return &mut [];
+        }
+
         unsafe { core::slice::from_raw_parts_mut(self.ptr, self.len) }
     }
 
@@ -164,6 +173,11 @@ impl<T> Vec<T> {
     fn alloc(size: usize) -> *mut T {
         let nbytes = size_of::<T>() * size;
 
+        // Nothing is allocated, but the pointer is kept non-null (as expected by `core::slice`)
+        if nbytes == 0 {
+            return core::ptr::NonNull::dangling().as_ptr();
+        }
+
         alloc(nbytes).as_mut_ptr() as _
     }
 }
@@ -261,6 +275,10 @@ mod tests {
 
         assert!(vec.is_empty());
         assert_eq!(vec.len(), 0);
+        assert_eq!(vec.as_slice(), &[]);
+
+        let vec: Vec<u8> = unsafe { Vec::from_raw_parts(core::ptr::null(), 0) };
+        assert_eq!(vec.as_slice(), &[]);
     }
 
     #[test]
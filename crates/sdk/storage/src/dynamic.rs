@@ -0,0 +1,347 @@
+use core::marker::PhantomData;
+
+use svm_sdk_types::{Address, Amount};
+
+use crate::traits::Storage;
+
+/// The maximum byte size of a [`DynItem`].
+pub const MAX_ITEM_BYTE_SIZE: usize = 20;
+
+/// A fixed-sized item of a dynamic variable: a key of a [`Mapping`] or a value of its entries,
+/// or an element of a [`DynVec`].
+pub trait DynItem: Sized {
+    /// The number of bytes the item is persisted into (at most [`MAX_ITEM_BYTE_SIZE`]).
+    const BYTE_SIZE: usize;
+
+    /// Writes the item into `buf` (of length `BYTE_SIZE`).
+    fn write(&self, buf: &mut [u8]);
+
+    /// Reads the item from `buf` (of length `BYTE_SIZE`).
+    fn read(buf: &'static [u8]) -> Self;
+}
+
+macro_rules! impl_int_item {
+    ($($ty:ty),*) => {
+        $(
+            impl DynItem for $ty {
+                const BYTE_SIZE: usize = core::mem::size_of::<$ty>();
+
+                fn write(&self, buf: &mut [u8]) {
+                    buf.copy_from_slice(&self.to_le_bytes());
+                }
+
+                fn read(buf: &'static [u8]) -> Self {
+                    let mut bytes = [0; core::mem::size_of::<$ty>()];
+                    bytes.copy_from_slice(buf);
+
+                    <$ty>::from_le_bytes(bytes)
+                }
+            }
+        )*
+    };
+}
+
+impl_int_item!(u8, i8, u16, i16, u32, i32, u64, i64);
+
+impl DynItem for bool {
+    const BYTE_SIZE: usize = 1;
+
+    fn write(&self, buf: &mut [u8]) {
+        buf[0] = *self as u8;
+    }
+
+    fn read(buf: &'static [u8]) -> Self {
+        buf[0] != 0
+    }
+}
+
+impl DynItem for Amount {
+    const BYTE_SIZE: usize = 8;
+
+    fn write(&self, buf: &mut [u8]) {
+        self.0.write(buf);
+    }
+
+    fn read(buf: &'static [u8]) -> Self {
+        Amount(u64::read(buf))
+    }
+}
+
+impl DynItem for Address {
+    const BYTE_SIZE: usize = 20;
+
+    fn write(&self, buf: &mut [u8]) {
+        buf.copy_from_slice(self.as_slice());
+    }
+
+    fn read(buf: &'static [u8]) -> Self {
+        buf.into()
+    }
+}
+
+/// A keyed dynamic variable, mapping keys of type `K` into values of type `V`.
+///
+/// Its fixed variable (of the same id) holds the number of its entries.
+/// A missing entry is read as a zeroed value.
+pub struct Mapping<K, V> {
+    var_id: u32,
+
+    marker: PhantomData<(K, V)>,
+}
+
+impl<K, V> Mapping<K, V>
+where
+    K: DynItem,
+    V: DynItem,
+{
+    pub const fn new(var_id: u32) -> Self {
+        Self {
+            var_id,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of entries.
+    pub fn len<S: Storage>(&self) -> u32 {
+        S::get32(self.var_id)
+    }
+
+    /// Returns whether there are no entries.
+    pub fn is_empty<S: Storage>(&self) -> bool {
+        self.len::<S>() == 0
+    }
+
+    /// Returns whether there is an entry for `key`.
+    pub fn contains<S: Storage>(&self, key: &K) -> bool {
+        let mut key_buf = [0; MAX_ITEM_BYTE_SIZE];
+        let key = item_bytes(key, &mut key_buf);
+
+        S::load_dyn(self.var_id, key, 0, 0) > 0
+    }
+
+    /// Returns the value of entry `key` (a zeroed value when there is no such entry).
+    pub fn get<S: Storage>(&self, key: &K) -> V {
+        let mut key_buf = [0; MAX_ITEM_BYTE_SIZE];
+        let key = item_bytes(key, &mut key_buf);
+
+        let (_found, value) = load_item::<S, V>(self.var_id, key);
+
+        value
+    }
+
+    /// Sets the value of entry `key` (adding the entry when there is none).
+    pub fn set<S: Storage>(&self, key: &K, value: &V) {
+        if !self.contains::<S>(key) {
+            S::set32(self.var_id, self.len::<S>() + 1);
+        }
+
+        let mut key_buf = [0; MAX_ITEM_BYTE_SIZE];
+        let key = item_bytes(key, &mut key_buf);
+
+        let mut value_buf = [0; MAX_ITEM_BYTE_SIZE];
+        let value = item_bytes(value, &mut value_buf);
+
+        S::store_dyn(self.var_id, key, value);
+    }
+
+    /// Removes entry `key` (does nothing when there is no such entry).
+    pub fn remove<S: Storage>(&self, key: &K) {
+        if self.contains::<S>(key) {
+            S::set32(self.var_id, self.len::<S>() - 1);
+
+            let mut key_buf = [0; MAX_ITEM_BYTE_SIZE];
+            let key = item_bytes(key, &mut key_buf);
+
+            S::store_dyn(self.var_id, key, &[]);
+        }
+    }
+}
+
+/// A growable dynamic variable, holding elements of type `T` (keyed by their index).
+///
+/// Its fixed variable (of the same id) holds the number of its elements.
+pub struct DynVec<T> {
+    var_id: u32,
+
+    marker: PhantomData<T>,
+}
+
+impl<T> DynVec<T>
+where
+    T: DynItem,
+{
+    pub const fn new(var_id: u32) -> Self {
+        Self {
+            var_id,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements.
+    pub fn len<S: Storage>(&self) -> u32 {
+        S::get32(self.var_id)
+    }
+
+    /// Returns whether there are no elements.
+    pub fn is_empty<S: Storage>(&self) -> bool {
+        self.len::<S>() == 0
+    }
+
+    /// Returns the element at `index`.
+    ///
+    /// Panics when `index` is out of bounds.
+    pub fn get<S: Storage>(&self, index: u32) -> T {
+        if index >= self.len::<S>() {
+            svm_sdk_std::panic();
+        }
+
+        let (_found, elem) = load_item::<S, T>(self.var_id, &index.to_le_bytes());
+
+        elem
+    }
+
+    /// Sets the element at `index`.
+    ///
+    /// Panics when `index` is out of bounds.
+    pub fn set<S: Storage>(&self, index: u32, elem: &T) {
+        if index >= self.len::<S>() {
+            svm_sdk_std::panic();
+        }
+
+        self.store::<S>(index, elem);
+    }
+
+    /// Appends `elem` to the end.
+    pub fn push<S: Storage>(&self, elem: &T) {
+        let len = self.len::<S>();
+
+        self.store::<S>(len, elem);
+
+        S::set32(self.var_id, len + 1);
+    }
+
+    /// Removes the last element and returns it.
+    ///
+    /// Panics when there are no elements.
+    pub fn pop<S: Storage>(&self) -> T {
+        let len = self.len::<S>();
+
+        if len == 0 {
+            svm_sdk_std::panic();
+        }
+
+        let index = (len - 1).to_le_bytes();
+        let (_found, elem) = load_item::<S, T>(self.var_id, &index);
+
+        S::store_dyn(self.var_id, &index, &[]);
+        S::set32(self.var_id, len - 1);
+
+        elem
+    }
+
+    fn store<S: Storage>(&self, index: u32, elem: &T) {
+        let mut elem_buf = [0; MAX_ITEM_BYTE_SIZE];
+        let elem = item_bytes(elem, &mut elem_buf);
+
+        S::store_dyn(self.var_id, &index.to_le_bytes(), elem);
+    }
+}
+
+#[inline]
+fn item_bytes<'a, T: DynItem>(item: &T, buf: &'a mut [u8; MAX_ITEM_BYTE_SIZE]) -> &'a [u8] {
+    let buf = &mut buf[..T::BYTE_SIZE];
+
+    item.write(buf);
+
+    buf
+}
+
+/// Loads the item of entry `key` (a zeroed one when there is no such entry),
+/// along with whether the entry has been found.
+fn load_item<S: Storage, T: DynItem>(var_id: u32, key: &[u8]) -> (bool, T) {
+    extern crate svm_sdk_alloc;
+
+    let ptr = svm_sdk_alloc::alloc(T::BYTE_SIZE);
+    let found = S::load_dyn(var_id, key, ptr.offset(), T::BYTE_SIZE) > 0;
+
+    if !found {
+        unsafe { core::ptr::write_bytes(ptr.as_mut_ptr(), 0, T::BYTE_SIZE) };
+    }
+
+    let buf = unsafe { core::slice::from_raw_parts(ptr.as_ptr(), T::BYTE_SIZE) };
+
+    (found, T::read(buf))
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+
+    use crate::MockStorage;
+
+    use lazy_static::lazy_static;
+    use std::sync::Mutex;
+
+    extern crate std;
+
+    lazy_static! {
+        static ref TEST_LOCK: Mutex<()> = Mutex::new(());
+    }
+
+    fn test(f: fn() -> ()) {
+        // See the tests of `MockStorage`
+        let guard = TEST_LOCK.lock().unwrap();
+
+        MockStorage::clear();
+
+        f();
+    }
+
+    #[test]
+    fn mapping_get_set_remove() {
+        test(|| {
+            let map: Mapping<u32, Amount> = Mapping::new(2);
+
+            assert_eq!(map.len::<MockStorage>(), 0);
+            assert!(!map.contains::<MockStorage>(&10));
+            assert!(map.get::<MockStorage>(&10) == Amount(0));
+
+            map.set::<MockStorage>(&10, &Amount(100));
+            map.set::<MockStorage>(&20, &Amount(200));
+            map.set::<MockStorage>(&10, &Amount(150));
+
+            assert_eq!(map.len::<MockStorage>(), 2);
+            assert!(map.contains::<MockStorage>(&10));
+            assert!(map.get::<MockStorage>(&10) == Amount(150));
+            assert!(map.get::<MockStorage>(&20) == Amount(200));
+
+            map.remove::<MockStorage>(&10);
+            map.remove::<MockStorage>(&30);
+
+            assert_eq!(map.len::<MockStorage>(), 1);
+            assert!(!map.contains::<MockStorage>(&10));
+            assert!(map.get::<MockStorage>(&10) == Amount(0));
+        });
+    }
+
+    #[test]
+    fn dyn_vec_push_pop() {
+        test(|| {
+            let vec: DynVec<u64> = DynVec::new(1);
+
+            assert!(vec.is_empty::<MockStorage>());
+
+            vec.push::<MockStorage>(&10);
+            vec.push::<MockStorage>(&20);
+            vec.set::<MockStorage>(0, &15);
+
+            assert_eq!(vec.len::<MockStorage>(), 2);
+            assert_eq!(vec.get::<MockStorage>(0), 15);
+            assert_eq!(vec.get::<MockStorage>(1), 20);
+
+            assert_eq!(vec.pop::<MockStorage>(), 20);
+            assert_eq!(vec.len::<MockStorage>(), 1);
+            assert_eq!(vec.get::<MockStorage>(0), 15);
+        });
+    }
+}
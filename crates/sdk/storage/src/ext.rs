@@ -10,9 +10,26 @@ extern "C" {
 
     fn svm_set64(var_id: u32, value: u64);
 
+    fn svm_get_bit(var_id: u32) -> u32;
+
+    fn svm_set_bit(var_id: u32, value: u32);
+
     fn svm_store160(offset: u32, var_id: u32);
 
     fn svm_load160(var_id: u32, offset: u32);
+
+    fn svm_storage_read_many(ids_offset: u32, count: u32, offset: u32) -> u32;
+
+    fn svm_storage_write_many(ids_offset: u32, count: u32, offset: u32);
+
+    fn svm_layout_len(var_id: u32) -> u32;
+
+    fn svm_layout_count() -> u32;
+
+    fn svm_store_dyn(var_id: u32, key_ptr: u32, key_len: u32, value_ptr: u32, value_len: u32);
+
+    fn svm_load_dyn(var_id: u32, key_ptr: u32, key_len: u32, value_ptr: u32, value_len: u32)
+        -> u32;
 }
 
 pub struct ExtStorage;
@@ -34,6 +51,14 @@ impl Storage for ExtStorage {
         unsafe { svm_set64(var_id, value) }
     }
 
+    fn get_bit(var_id: u32) -> bool {
+        unsafe { svm_get_bit(var_id) != 0 }
+    }
+
+    fn set_bit(var_id: u32, value: bool) {
+        unsafe { svm_set_bit(var_id, value as u32) }
+    }
+
     fn store160(var_id: u32, offset: usize) {
         unsafe { svm_store160(offset as u32, var_id) }
     }
@@ -41,4 +66,68 @@ impl Storage for ExtStorage {
     fn load160(var_id: u32, offset: usize) {
         unsafe { svm_load160(var_id, offset as u32) }
     }
+
+    fn read_many(vars: &[(u32, u32)], offset: usize) {
+        let ids_offset = var_ids(vars);
+
+        unsafe { svm_storage_read_many(ids_offset as u32, vars.len() as u32, offset as u32) };
+    }
+
+    fn write_many(vars: &[(u32, u32)], offset: usize) {
+        let ids_offset = var_ids(vars);
+
+        unsafe { svm_storage_write_many(ids_offset as u32, vars.len() as u32, offset as u32) }
+    }
+
+    fn layout_len(var_id: u32) -> u32 {
+        unsafe { svm_layout_len(var_id) }
+    }
+
+    fn layout_count() -> u32 {
+        unsafe { svm_layout_count() }
+    }
+
+    fn store_dyn(var_id: u32, key: &[u8], value: &[u8]) {
+        unsafe {
+            svm_store_dyn(
+                var_id,
+                key.as_ptr() as u32,
+                key.len() as u32,
+                value.as_ptr() as u32,
+                value.len() as u32,
+            )
+        }
+    }
+
+    fn load_dyn(var_id: u32, key: &[u8], offset: usize, len: usize) -> u32 {
+        unsafe {
+            svm_load_dyn(
+                var_id,
+                key.as_ptr() as u32,
+                key.len() as u32,
+                offset as u32,
+                len as u32,
+            )
+        }
+    }
+}
+
+/// Lays out the ids of `vars` as expected by the batched storage `vmcalls`
+/// (consecutive 32-bit Little-Endian integers), and returns their memory offset.
+fn var_ids(vars: &[(u32, u32)]) -> usize {
+    extern crate svm_sdk_alloc;
+
+    let ptr = svm_sdk_alloc::alloc(4 * vars.len());
+
+    for (i, (var_id, _size)) in vars.iter().enumerate() {
+        let bytes = var_id.to_le_bytes();
+
+        unsafe {
+            let dst = ptr.as_mut_ptr().add(4 * i);
+
+            core::ptr::copy_nonoverlapping(bytes.as_ptr(), dst, 4);
+        }
+    }
+
+    ptr.offset()
 }
@@ -11,7 +11,9 @@
 #![allow(dead_code)]
 #![allow(unreachable_code)]
 
+mod dynamic;
 mod ext;
+#[cfg(feature = "mock")]
 mod mock;
 mod traits;
 
@@ -24,6 +26,7 @@ pub use ext::ExtStorage;
 #[cfg(feature = "mock")]
 pub use mock::MockStorage;
 
+pub use dynamic::{DynItem, DynVec, Mapping, MAX_ITEM_BYTE_SIZE};
 pub use traits::Storage;
 
 use svm_sdk_types::{Address, Amount};
@@ -63,6 +66,16 @@ pub fn set_bool<S: Storage>(var_id: u32, value: bool) {
     S::set32(var_id, value)
 }
 
+/// Returns the value of the packed boolean variable `var_id` (see `get_bool` for an unpacked one).
+pub fn get_bit<S: Storage>(var_id: u32) -> bool {
+    S::get_bit(var_id)
+}
+
+/// Sets the packed boolean variable `var_id` (see `set_bool` for an unpacked one).
+pub fn set_bit<S: Storage>(var_id: u32, value: bool) {
+    S::set_bit(var_id, value)
+}
+
 pub fn get_amount<S: Storage>(var_id: u32) -> Amount {
     let value = get64::<S>(var_id);
 
@@ -104,6 +117,60 @@ pub fn set_addr<S: Storage>(var_id: u32, value: &Address) {
     store160::<S>(var_id, slice);
 }
 
+// Batched
+
+/// Reads the variables `vars` (pairs of `(var_id, byte_size)`) using a single host call.
+///
+/// Returns the variables data laid back-to-back (in the order of `vars`).
+pub fn read_many<S: Storage>(vars: &[(u32, u32)]) -> &'static [u8] {
+    extern crate svm_sdk_alloc;
+
+    if vars.is_empty() {
+        return &[];
+    }
+
+    let len = batch_byte_size(vars);
+    let ptr = svm_sdk_alloc::alloc(len);
+
+    S::read_many(vars, ptr.offset());
+
+    unsafe { core::slice::from_raw_parts(ptr.as_ptr(), len) }
+}
+
+/// Writes the variables `vars` (pairs of `(var_id, byte_size)`) using a single host call.
+///
+/// The `data` should hold the variables new data laid back-to-back (in the order of `vars`).
+pub fn write_many<S: Storage>(vars: &[(u32, u32)], data: &[u8]) {
+    if data.len() != batch_byte_size(vars) {
+        svm_sdk_std::panic();
+    }
+
+    if vars.is_empty() {
+        return;
+    }
+
+    let ptr: *const u8 = data.as_ptr();
+
+    S::write_many(vars, ptr as usize);
+}
+
+#[inline]
+fn batch_byte_size(vars: &[(u32, u32)]) -> usize {
+    vars.iter().map(|(_var_id, size)| *size as usize).sum()
+}
+
+// Layout
+
+/// Returns the byte size of variable `var_id` (as laid out by the running `Account`'s fixed layout).
+pub fn layout_len<S: Storage>(var_id: u32) -> u32 {
+    S::layout_len(var_id)
+}
+
+/// Returns the number of variables of the running `Account`'s fixed layout.
+pub fn layout_count<S: Storage>() -> u32 {
+    S::layout_count()
+}
+
 // Array
 
 pub fn array_get_bool<S: Storage>(var_id: u32, index: usize, length: u32) -> bool {
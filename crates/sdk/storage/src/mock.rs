@@ -10,6 +10,8 @@ use std::sync::Once;
 
 use crate::traits::Storage;
 
+use svm_sdk_host::{HostCall, MockHost};
+
 /// Regarding why we don't use any concurrency primitives for initializing `STORAGE`
 /// see the explanation of `MockHost`.
 
@@ -26,15 +28,35 @@ enum Var {
 
 pub struct InnerStorage {
     vars: HashMap<u32, Var>,
+
+    /// The byte size of each variable (indexed by the variable's id).
+    layout: Vec<u32>,
+
+    /// The entries of the dynamic variables (keyed by the variable's id and the entry's key).
+    entries: HashMap<(u32, Vec<u8>), Vec<u8>>,
 }
 
 impl InnerStorage {
     pub fn new() -> Self {
         Self {
             vars: HashMap::new(),
+            layout: Vec::new(),
+            entries: HashMap::new(),
         }
     }
 
+    pub fn set_layout(&mut self, layout: &[u32]) {
+        self.layout = layout.to_vec();
+    }
+
+    pub fn layout_len(&self, var_id: u32) -> u32 {
+        self.layout[var_id as usize]
+    }
+
+    pub fn layout_count(&self) -> u32 {
+        self.layout.len() as u32
+    }
+
     pub fn get32(&self, var_id: u32) -> u32 {
         let var = self.var(var_id, || Var::I32(0));
 
@@ -61,6 +83,14 @@ impl InnerStorage {
         self.set_var(var_id, Var::I64(value));
     }
 
+    pub fn get_bit(&self, var_id: u32) -> bool {
+        self.get32(var_id) != 0
+    }
+
+    pub fn set_bit(&mut self, var_id: u32, value: bool) {
+        self.set32(var_id, value as u32);
+    }
+
     pub fn store160(&mut self, var_id: u32, offset: usize) {
         self.store_vec(var_id, offset, 20);
     }
@@ -69,6 +99,84 @@ impl InnerStorage {
         self.load_vec(var_id, offset, 20)
     }
 
+    pub fn read_many(&self, vars: &[(u32, u32)], offset: usize) {
+        let mut dst = offset as *mut u8;
+
+        for &(var_id, size) in vars {
+            let size = size as usize;
+            let var = self.var(var_id, || Var::Blob(vec![0; size]));
+
+            let bytes = match var {
+                Var::I32(v) => v.to_le_bytes()[..size].to_vec(),
+                Var::I64(v) => v.to_le_bytes()[..size].to_vec(),
+                Var::Blob(vec) => vec,
+            };
+
+            assert_eq!(bytes.len(), size);
+
+            unsafe {
+                core::ptr::copy_nonoverlapping(bytes.as_ptr(), dst, size);
+
+                dst = dst.add(size);
+            }
+        }
+    }
+
+    pub fn write_many(&mut self, vars: &[(u32, u32)], offset: usize) {
+        let mut offset = offset;
+
+        for &(var_id, size) in vars {
+            let size = size as usize;
+            let bytes = self.from_raw_parts(offset, size);
+
+            // Keeping the integers as such (for `get32` and `get64`)
+            let var = if size <= 4 {
+                let mut buf = [0; 4];
+                buf[..size].copy_from_slice(bytes);
+
+                Var::I32(u32::from_le_bytes(buf))
+            } else if size <= 8 {
+                let mut buf = [0; 8];
+                buf[..size].copy_from_slice(bytes);
+
+                Var::I64(u64::from_le_bytes(buf))
+            } else {
+                Var::Blob(bytes.to_vec())
+            };
+
+            self.set_var(var_id, var);
+
+            offset += size;
+        }
+    }
+
+    pub fn store_dyn(&mut self, var_id: u32, key: &[u8], value: &[u8]) {
+        let key = (var_id, key.to_vec());
+
+        if value.is_empty() {
+            self.entries.remove(&key);
+        } else {
+            self.entries.insert(key, value.to_vec());
+        }
+    }
+
+    pub fn load_dyn(&self, var_id: u32, key: &[u8], offset: usize, len: usize) -> u32 {
+        match self.entries.get(&(var_id, key.to_vec())) {
+            Some(value) => {
+                let len = value.len().min(len);
+
+                if len > 0 {
+                    unsafe {
+                        core::ptr::copy_nonoverlapping(value.as_ptr(), offset as *mut u8, len)
+                    };
+                }
+
+                value.len() as u32
+            }
+            None => 0,
+        }
+    }
+
     fn var<F>(&self, var_id: u32, default: F) -> Var
     where
         F: Fn() -> Var,
@@ -108,6 +216,8 @@ impl InnerStorage {
 
     fn clear(&mut self) {
         self.vars.clear();
+        self.layout.clear();
+        self.entries.clear();
     }
 }
 
@@ -130,6 +240,14 @@ impl MockStorage {
         storage.clear();
     }
 
+    /// Sets the fixed layout introspected by [`Storage::layout_len`] and [`Storage::layout_count`]
+    /// (the byte size of each variable, starting at variable `0`).
+    pub fn set_layout(layout: &[u32]) {
+        let mut storage = Self::instance();
+
+        storage.set_layout(layout);
+    }
+
     fn from_raw_parts<'a>(offset: usize, len: usize) -> &'a [u8] {
         unsafe { core::slice::from_raw_parts(offset as *const u8, len) }
     }
@@ -142,10 +260,18 @@ impl Storage for MockStorage {
         storage.get32(var_id)
     }
 
+    /// Returns the value scripted by `MockHost::script_get64` (if any), or the stored one otherwise.
     fn get64(var_id: u32) -> u64 {
         let mut storage = Self::instance();
 
-        storage.get64(var_id)
+        let value = match MockHost::scripted_get64(var_id) {
+            Some(value) => value,
+            None => storage.get64(var_id),
+        };
+
+        MockHost::record(HostCall::Get64 { var_id, value });
+
+        value
     }
 
     fn set32(var_id: u32, value: u32) {
@@ -157,9 +283,23 @@ impl Storage for MockStorage {
     fn set64(var_id: u32, value: u64) {
         let mut storage = Self::instance();
 
+        MockHost::record(HostCall::Set64 { var_id, value });
+
         storage.set64(var_id, value)
     }
 
+    fn get_bit(var_id: u32) -> bool {
+        let storage = Self::instance();
+
+        storage.get_bit(var_id)
+    }
+
+    fn set_bit(var_id: u32, value: bool) {
+        let mut storage = Self::instance();
+
+        storage.set_bit(var_id, value)
+    }
+
     fn store160(var_id: u32, offset: usize) {
         let mut storage = Self::instance();
 
@@ -171,6 +311,42 @@ impl Storage for MockStorage {
 
         storage.load160(var_id, offset)
     }
+
+    fn read_many(vars: &[(u32, u32)], offset: usize) {
+        let mut storage = Self::instance();
+
+        storage.read_many(vars, offset)
+    }
+
+    fn write_many(vars: &[(u32, u32)], offset: usize) {
+        let mut storage = Self::instance();
+
+        storage.write_many(vars, offset)
+    }
+
+    fn layout_len(var_id: u32) -> u32 {
+        let storage = Self::instance();
+
+        storage.layout_len(var_id)
+    }
+
+    fn layout_count() -> u32 {
+        let storage = Self::instance();
+
+        storage.layout_count()
+    }
+
+    fn store_dyn(var_id: u32, key: &[u8], value: &[u8]) {
+        let mut storage = Self::instance();
+
+        storage.store_dyn(var_id, key, value)
+    }
+
+    fn load_dyn(var_id: u32, key: &[u8], offset: usize, len: usize) -> u32 {
+        let storage = Self::instance();
+
+        storage.load_dyn(var_id, key, offset, len)
+    }
 }
 
 #[cfg(test)]
@@ -235,6 +411,22 @@ mod tests {
         });
     }
 
+    #[test]
+    fn storage_mock_get_bit_set_bit() {
+        test(|| {
+            assert!(!MockStorage::get_bit(1));
+
+            MockStorage::set_bit(1, true);
+            MockStorage::set_bit(2, false);
+
+            assert!(MockStorage::get_bit(1));
+            assert!(!MockStorage::get_bit(2));
+
+            MockStorage::set_bit(1, false);
+            assert!(!MockStorage::get_bit(1));
+        });
+    }
+
     macro_rules! check_load_store {
         ($n:expr, $load_fn:ident, $store_fn:ident) => {{
             test(|| {
@@ -267,4 +459,65 @@ mod tests {
     fn storage_mock_load160_store160() {
         check_load_store!(20, load160, store160);
     }
+
+    #[test]
+    fn storage_mock_read_many_write_many() {
+        test(|| {
+            let vars = [(1, 4), (2, 8), (3, 20)];
+
+            let mut data = vec![0x10, 0x00, 0x00, 0x00];
+            data.extend_from_slice(&20u64.to_le_bytes());
+            data.extend_from_slice(&[0xAB; 20]);
+
+            MockStorage::write_many(&vars, data.as_ptr() as usize);
+
+            assert_eq!(MockStorage::get32(1), 0x10);
+            assert_eq!(MockStorage::get64(2), 20);
+
+            MockStorage::set32(1, 0x20);
+
+            let off = alloc(32).offset();
+            MockStorage::read_many(&[(3, 20), (1, 4), (4, 8)], off);
+
+            let mut expected = vec![0xAB; 20];
+            expected.extend_from_slice(&[0x20, 0x00, 0x00, 0x00]);
+            expected.extend_from_slice(&[0; 8]);
+
+            assert_eq!(MockStorage::from_raw_parts(off, 32), expected);
+        });
+    }
+
+    #[test]
+    fn storage_mock_store_dyn_load_dyn() {
+        test(|| {
+            let off = alloc(4).offset();
+
+            assert_eq!(MockStorage::load_dyn(1, b"key", off, 4), 0);
+
+            MockStorage::store_dyn(1, b"key", &[10, 20, 30]);
+
+            assert_eq!(MockStorage::load_dyn(1, b"key", off, 4), 3);
+            assert_eq!(MockStorage::from_raw_parts(off, 3), [10, 20, 30]);
+
+            // the entries of each variable are kept apart
+            assert_eq!(MockStorage::load_dyn(2, b"key", off, 4), 0);
+
+            MockStorage::store_dyn(1, b"key", &[]);
+            assert_eq!(MockStorage::load_dyn(1, b"key", off, 4), 0);
+        });
+    }
+
+    #[test]
+    fn storage_mock_layout() {
+        test(|| {
+            assert_eq!(MockStorage::layout_count(), 0);
+
+            MockStorage::set_layout(&[4, 8, 20]);
+
+            assert_eq!(MockStorage::layout_count(), 3);
+            assert_eq!(MockStorage::layout_len(0), 4);
+            assert_eq!(MockStorage::layout_len(1), 8);
+            assert_eq!(MockStorage::layout_len(2), 20);
+        });
+    }
 }
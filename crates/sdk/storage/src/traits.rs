@@ -7,7 +7,35 @@ pub trait Storage {
 
     fn set64(var_id: u32, value: u64);
 
+    /// Returns the value of the packed boolean variable `var_id`.
+    fn get_bit(var_id: u32) -> bool;
+
+    /// Sets the packed boolean variable `var_id` (leaving the others sharing its byte untouched).
+    fn set_bit(var_id: u32, value: bool);
+
     fn store160(var_id: u32, offset: usize);
 
     fn load160(var_id: u32, offset: usize);
+
+    /// Reads the variables `vars` (pairs of `(var_id, byte_size)`) into memory starting at `offset`,
+    /// laid back-to-back in the order of `vars`.
+    fn read_many(vars: &[(u32, u32)], offset: usize);
+
+    /// Writes the variables `vars` (pairs of `(var_id, byte_size)`) from memory starting at `offset`,
+    /// laid back-to-back in the order of `vars`.
+    fn write_many(vars: &[(u32, u32)], offset: usize);
+
+    /// Returns the byte size of variable `var_id` (as laid out by the running `Account`'s fixed layout).
+    fn layout_len(var_id: u32) -> u32;
+
+    /// Returns the number of variables of the running `Account`'s fixed layout.
+    fn layout_count() -> u32;
+
+    /// Stores entry `key` of the dynamic variable `var_id` (an empty `value` removes the entry).
+    fn store_dyn(var_id: u32, key: &[u8], value: &[u8]);
+
+    /// Loads (up to `len` bytes of) entry `key` of the dynamic variable `var_id` into memory starting at `offset`.
+    ///
+    /// Returns the byte size of the entry (`0` when there is no such entry).
+    fn load_dyn(var_id: u32, key: &[u8], offset: usize, len: usize) -> u32;
 }
@@ -1,6 +1,6 @@
 use crate::{Address, Amount};
 
-use svm_sdk_std::{ensure, panic, Option};
+use svm_sdk_std::{ensure, panic, Option, String};
 
 /// Primitive value
 #[allow(missing_docs)]
@@ -20,6 +20,7 @@ pub enum Primitive {
     U32(u32),
     I64(i64),
     U64(u64),
+    Str(String),
 }
 
 /// Composite value
@@ -120,6 +121,7 @@ impl_from_rust_to_value!(I64, i64);
 impl_from_rust_to_value!(U64, u64);
 
 impl_from_rust_to_value!(Address, Address);
+impl_from_rust_to_value!(Str, String);
 
 macro_rules! impl_from_value_to_rust {
     ($prim_ident:ident, $T:ty) => {
@@ -169,6 +171,7 @@ impl_from_value_to_rust!(I64, i64);
 impl_from_value_to_rust!(U64, u64);
 
 impl_from_value_to_rust!(Address, Address);
+impl_from_value_to_rust!(Str, String);
 
 macro_rules! impl_value_to_rust_array {
     ([] => $($tt:tt)*) => {};
@@ -310,6 +313,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn str_to_value_and_vice_versa() {
+        let s = String::new_short("Hello".as_bytes());
+        let value: Value = s.into();
+
+        let s: String = value.into();
+        assert_eq!(s.as_bytes(), "Hello".as_bytes());
+    }
+
     #[test]
     fn unit_to_value_and_vice_versa() {
         let value: Value = ().into();
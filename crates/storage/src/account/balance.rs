@@ -0,0 +1,102 @@
+use std::fmt;
+
+use svm_types::Address;
+
+use super::AccountKVStore;
+use crate::kv::StatefulKV;
+
+/// The key holding an `Account`'s balance under its [`AccountKVStore`].
+///
+/// The variables are stored under keys smaller than the `kv_value_size`, so this key never clashes with them.
+const BALANCE_KEY: u32 = u32::MAX;
+
+/// The error returned when an `Account` transfers more coins than its balance holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InsufficientBalance {
+    /// The `Account` attempting the transfer.
+    pub account: Address,
+
+    /// The balance of the `Account`.
+    pub balance: u64,
+
+    /// The amount it has attempted to transfer.
+    pub amount: u64,
+}
+
+impl fmt::Display for InsufficientBalance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Insufficient balance: `Account` {:?} holds {} coins (transferring {})",
+            self.account, self.balance, self.amount
+        )
+    }
+}
+
+impl std::error::Error for InsufficientBalance {}
+
+/// The error returned when a transfer would overflow the balance of its destination `Account`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceOverflow {
+    /// The `Account` receiving the coins.
+    pub account: Address,
+
+    /// The balance of the receiving `Account`.
+    pub balance: u64,
+
+    /// The amount transferred.
+    pub amount: u64,
+}
+
+impl fmt::Display for BalanceOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Balance overflow: `Account` {:?} holds {} coins (receiving {})",
+            self.account, self.balance, self.amount
+        )
+    }
+}
+
+impl std::error::Error for BalanceOverflow {}
+
+/// The error returned when transferring coins fails (see [`AccountStorage::transfer`](super::AccountStorage::transfer)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransferError {
+    /// The transferring `Account` holds less than the transferred amount.
+    InsufficientBalance(InsufficientBalance),
+
+    /// The balance of the receiving `Account` can't hold the transferred amount.
+    BalanceOverflow(BalanceOverflow),
+}
+
+impl fmt::Display for TransferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransferError::InsufficientBalance(err) => err.fmt(f),
+            TransferError::BalanceOverflow(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for TransferError {}
+
+/// Reads the persisted balance of the `Account` of `account_kv` (zero when none is stored).
+pub(super) fn read_balance(account_kv: &AccountKVStore) -> u64 {
+    let value = account_kv.get(&BALANCE_KEY.to_be_bytes());
+
+    match value {
+        Some(bytes) if bytes.len() == 8 => {
+            let mut buf = [0; 8];
+            buf.copy_from_slice(&bytes);
+
+            u64::from_be_bytes(buf)
+        }
+        _ => 0,
+    }
+}
+
+/// Writes the balance of the `Account` of `account_kv` (to be persisted by the next `checkpoint`).
+pub(super) fn write_balance(account_kv: &mut AccountKVStore, balance: u64) {
+    account_kv.set(&BALANCE_KEY.to_be_bytes(), &balance.to_be_bytes());
+}
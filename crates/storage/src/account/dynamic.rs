@@ -0,0 +1,112 @@
+use svm_hash::{Blake3Hasher, Hasher};
+use svm_layout::Id;
+
+use super::AccountKVStore;
+use crate::kv::StatefulKV;
+
+/// The key of an entry of a dynamic variable under its [`AccountKVStore`].
+pub(super) type DynKey = [u8; 32];
+
+/// Returns the key holding entry `key` of dynamic variable `var_id`.
+///
+/// It's the hash of the variable's `Id` (as Big-Endian) followed by `key`.
+/// Being of 32 bytes, it never clashes with the (4 bytes) keys of the fixed variables and the balance.
+pub(super) fn dyn_key(var_id: Id, key: &[u8]) -> DynKey {
+    let mut buf = Vec::with_capacity(4 + key.len());
+
+    buf.extend_from_slice(&var_id.0.to_be_bytes());
+    buf.extend_from_slice(key);
+
+    Blake3Hasher::hash(&buf)
+}
+
+/// Reads the persisted entry under `key` (`None` when there is none, or it has been removed).
+pub(super) fn read_entry(account_kv: &AccountKVStore, key: &DynKey) -> Option<Vec<u8>> {
+    account_kv.get(key).filter(|value| !value.is_empty())
+}
+
+/// Writes the entry under `key` (to be persisted by the next `checkpoint`).
+///
+/// An empty `value` removes the entry.
+pub(super) fn write_entry(account_kv: &mut AccountKVStore, key: &DynKey, value: &[u8]) {
+    account_kv.set(key, value);
+}
+
+/// The key holding the digest of an `Account`'s dynamic variables entries under its [`AccountKVStore`].
+///
+/// Like the balance key, it's above the keys of the fixed variables (and shorter than the entries keys).
+const DIGEST_KEY: u32 = u32::MAX - 1;
+
+/// A digest committing to all the persisted entries of the dynamic variables of an `Account`.
+///
+/// It's the sum (modulo `2^256`) of the hashes of the entries (each hashed along with its key),
+/// so it's updated incrementally as entries are written and removed (the entries can't be enumerated).
+pub(super) type DynDigest = [u8; 32];
+
+/// Reads the persisted digest of the `Account` of `account_kv` (all zeros when there are no entries).
+pub(super) fn read_digest(account_kv: &AccountKVStore) -> DynDigest {
+    let mut digest = [0; 32];
+
+    if let Some(bytes) = account_kv.get(&DIGEST_KEY.to_be_bytes()) {
+        if bytes.len() == digest.len() {
+            digest.copy_from_slice(&bytes);
+        }
+    }
+
+    digest
+}
+
+/// Writes the digest of the `Account` of `account_kv` (to be persisted by the next `checkpoint`).
+pub(super) fn write_digest(account_kv: &mut AccountKVStore, digest: &DynDigest) {
+    account_kv.set(&DIGEST_KEY.to_be_bytes(), digest);
+}
+
+/// Updates `digest` with the replacement of the persisted `old` entry under `key` by `new`
+/// (either may be `None`, for an absent or removed entry).
+pub(super) fn update_digest(
+    digest: &mut DynDigest,
+    key: &DynKey,
+    old: Option<&[u8]>,
+    new: Option<&[u8]>,
+) {
+    if let Some(old) = old {
+        sub(digest, &entry_hash(key, old));
+    }
+
+    if let Some(new) = new {
+        add(digest, &entry_hash(key, new));
+    }
+}
+
+fn entry_hash(key: &DynKey, value: &[u8]) -> [u8; 32] {
+    let mut buf = Vec::with_capacity(key.len() + value.len());
+
+    buf.extend_from_slice(key);
+    buf.extend_from_slice(value);
+
+    Blake3Hasher::hash(&buf)
+}
+
+/// `digest += hash` (as Big-Endian numbers, modulo `2^256`).
+fn add(digest: &mut DynDigest, hash: &[u8; 32]) {
+    let mut carry = 0u16;
+
+    for (d, h) in digest.iter_mut().zip(hash.iter()).rev() {
+        let sum = *d as u16 + *h as u16 + carry;
+
+        *d = sum as u8;
+        carry = sum >> 8;
+    }
+}
+
+/// `digest -= hash` (as Big-Endian numbers, modulo `2^256`).
+fn sub(digest: &mut DynDigest, hash: &[u8; 32]) {
+    let mut borrow = 0i16;
+
+    for (d, h) in digest.iter_mut().zip(hash.iter()).rev() {
+        let diff = *d as i16 - *h as i16 - borrow;
+
+        *d = diff.rem_euclid(256) as u8;
+        borrow = (diff < 0) as i16;
+    }
+}
@@ -8,11 +8,17 @@ use svm_types::{Address, State};
 /// An Account-aware (and `State`-aware) key-value store interface responsible of
 /// mapping `u32` input keys (given as a 4 byte-length slice) to global keys under a raw key-value store.
 ///
+/// The entries of dynamic variables are given 32 byte-length keys instead (see `AccountStorage::write_dyn`).
+///
 /// The mapping is dependant on the contextual `Account`'s `Address` (see the `new` method).
+///
+/// A store opened at a historical `State` (see the `at` method) is a read-only view of the underlying one.
 pub struct AccountKVStore {
     pub(crate) account_addr: Address,
 
     pub(crate) kv: Arc<Mutex<dyn StatefulKV + Send>>,
+
+    state: Option<State>,
 }
 
 impl StatefulKV for AccountKVStore {
@@ -20,11 +26,24 @@ impl StatefulKV for AccountKVStore {
     #[must_use]
     fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
         let key = self.build_key(key);
-        self.kv().get(&key)
+
+        match self.state {
+            Some(ref state) => self.kv().get_at(state, &key),
+            None => self.kv().get(&key),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    fn get_at(&self, state: &State, key: &[u8]) -> Option<Vec<u8>> {
+        let key = self.build_key(key);
+        self.kv().get_at(state, &key)
     }
 
     #[inline]
     fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.assert_writable();
+
         let key = self.build_key(key);
 
         self.kv().set(&key, value);
@@ -32,30 +51,41 @@ impl StatefulKV for AccountKVStore {
 
     #[inline]
     fn discard(&mut self) {
-        self.kv().discard();
+        if self.state.is_none() {
+            self.kv().discard();
+        }
     }
 
     #[inline]
     fn flush(&mut self) {
+        self.assert_writable();
+
         self.kv().flush();
     }
 
     #[inline]
     #[must_use]
     fn checkpoint(&mut self) -> State {
+        self.assert_writable();
+
         self.kv().checkpoint()
     }
 
     #[inline]
     #[must_use]
     fn rewind(&mut self, state: &State) {
+        self.assert_writable();
+
         self.kv().rewind(state)
     }
 
     #[inline]
     #[must_use]
     fn head(&self) -> State {
-        self.kv().head()
+        match self.state {
+            Some(ref state) => state.clone(),
+            None => self.kv().head(),
+        }
     }
 }
 
@@ -66,12 +96,56 @@ impl AccountKVStore {
     pub fn new(account_addr: Address, kv: &Arc<Mutex<dyn StatefulKV + Send>>) -> Self {
         let kv = Arc::clone(&kv);
 
-        Self { account_addr, kv }
+        Self {
+            account_addr,
+            kv,
+            state: None,
+        }
+    }
+
+    /// Create a new read-only `AccountStore` instance for `Address` `account_addr`,
+    /// reading the values of `kv` as of the historical `state` (see [`StatefulKV::get_at`]).
+    ///
+    /// The current pointed-to `State` of `kv` is left intact.
+    ///
+    /// # Panics
+    ///
+    /// Modifying the returned store (i.e `set`, `checkpoint`, `flush` or `rewind`) panics.
+    pub fn at(
+        account_addr: Address,
+        kv: &Arc<Mutex<dyn StatefulKV + Send>>,
+        state: &State,
+    ) -> Self {
+        Self {
+            state: Some(state.clone()),
+            ..Self::new(account_addr, kv)
+        }
+    }
+
+    /// The store of `Account` `account_addr` over the same key-value store
+    /// (and at the same historical `State`, if any).
+    pub fn sibling(&self, account_addr: Address) -> Self {
+        Self {
+            account_addr,
+            ..self.clone()
+        }
+    }
+
+    /// Whether the store is a read-only view of a historical `State`.
+    pub fn is_read_only(&self) -> bool {
+        self.state.is_some()
+    }
+
+    fn assert_writable(&self) {
+        assert!(
+            !self.is_read_only(),
+            "An `AccountKVStore` opened at a historical `State` is read-only"
+        );
     }
 
     #[inline]
     fn build_key(&self, key: &[u8]) -> Vec<u8> {
-        debug_assert_eq!(key.len(), 4);
+        debug_assert!(key.len() == 4 || key.len() == 32);
 
         let mut buf = Vec::with_capacity(Address::len() + key.len());
 
@@ -87,7 +161,7 @@ impl AccountKVStore {
     }
 
     fn kv(&self) -> MutexGuard<dyn StatefulKV + Send + 'static> {
-        self.kv.try_lock().unwrap()
+        self.kv.lock().unwrap()
     }
 }
 
@@ -96,6 +170,7 @@ impl Clone for AccountKVStore {
         Self {
             account_addr: self.account_addr.clone(),
             kv: Arc::clone(&self.kv),
+            state: self.state.clone(),
         }
     }
 }
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, ThreadId};
+
+use svm_types::Address;
+
+/// Coordinates the concurrent access to the `Account`s storage (shared by all the parties
+/// reading or committing the `Account`s backed by the same key-value store).
+///
+/// An `Account` can be read by many parties at once, while committing changes into it
+/// waits for them to finish (and vice versa). This way, a reader holding an [`AccountReadGuard`]
+/// always observes a consistent storage: either before a committed transaction or after it.
+///
+/// A commit locks all the `Account`s it touches at once (see [`AccountLocks::write`]),
+/// so it never holds an `Account` while waiting for another (i.e there are no deadlocks).
+/// The locks are re-entrant: a thread may commit into the `Account`s it's reading itself.
+#[derive(Clone, Default)]
+pub struct AccountLocks {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    states: Mutex<HashMap<Address, LockState>>,
+
+    released: Condvar,
+}
+
+#[derive(Default)]
+struct LockState {
+    /// The threads reading the `Account` (a thread appears once per guard it holds).
+    readers: Vec<ThreadId>,
+
+    /// The thread committing into the `Account`.
+    writer: Option<ThreadId>,
+}
+
+impl LockState {
+    fn can_read(&self, thread: ThreadId) -> bool {
+        self.writer.map_or(true, |writer| writer == thread)
+    }
+
+    fn can_write(&self, thread: ThreadId) -> bool {
+        self.writer.is_none() && self.readers.iter().all(|reader| *reader == thread)
+    }
+
+    fn is_free(&self) -> bool {
+        self.writer.is_none() && self.readers.is_empty()
+    }
+}
+
+impl AccountLocks {
+    /// Creates a new registry (with no locked `Account`s).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Locks `addr` for reading, waiting for any commit into it to finish.
+    ///
+    /// The `Account` can't be committed into (by other threads) until the returned guard is dropped.
+    pub fn read(&self, addr: &Address) -> AccountReadGuard {
+        let thread = thread::current().id();
+
+        let mut states = self.wait_until(|states| {
+            states
+                .get(addr)
+                .map_or(true, |state| state.can_read(thread))
+        });
+
+        states.entry(addr.clone()).or_default().readers.push(thread);
+
+        AccountReadGuard {
+            locks: self.clone(),
+            addr: addr.clone(),
+            thread,
+        }
+    }
+
+    /// Locks `addrs` for committing, waiting until none of them is read (or committed into)
+    /// by other threads. All the `Account`s are locked at once.
+    pub fn write(&self, addrs: &[Address]) -> AccountWriteGuard {
+        let thread = thread::current().id();
+
+        let mut addrs = addrs.to_vec();
+        addrs.sort_by(|a, b| a.as_slice().cmp(b.as_slice()));
+        addrs.dedup();
+
+        let mut states = self.wait_until(|states| {
+            addrs.iter().all(|addr| {
+                states
+                    .get(addr)
+                    .map_or(true, |state| state.can_write(thread))
+            })
+        });
+
+        for addr in addrs.iter() {
+            states.entry(addr.clone()).or_default().writer = Some(thread);
+        }
+
+        AccountWriteGuard {
+            locks: self.clone(),
+            addrs,
+        }
+    }
+
+    /// Returns whether `addr` is currently locked (for reading or for committing).
+    pub fn is_locked(&self, addr: &Address) -> bool {
+        self.states().contains_key(addr)
+    }
+
+    fn wait_until<F>(&self, ready: F) -> MutexGuard<HashMap<Address, LockState>>
+    where
+        F: Fn(&HashMap<Address, LockState>) -> bool,
+    {
+        let mut states = self.states();
+
+        while !ready(&states) {
+            states = self.inner.released.wait(states).unwrap();
+        }
+
+        states
+    }
+
+    fn release<F>(&self, addr: &Address, f: F)
+    where
+        F: FnOnce(&mut LockState),
+    {
+        let mut states = self.states();
+
+        if let Some(state) = states.get_mut(addr) {
+            f(state);
+
+            if state.is_free() {
+                states.remove(addr);
+            }
+        }
+    }
+
+    fn states(&self) -> MutexGuard<HashMap<Address, LockState>> {
+        self.inner.states.lock().unwrap()
+    }
+}
+
+/// Holds an `Account` locked for reading (see [`AccountLocks::read`]). Dropping it releases the lock.
+pub struct AccountReadGuard {
+    locks: AccountLocks,
+
+    addr: Address,
+
+    thread: ThreadId,
+}
+
+impl Drop for AccountReadGuard {
+    fn drop(&mut self) {
+        let thread = self.thread;
+
+        self.locks.release(&self.addr, |state| {
+            if let Some(pos) = state.readers.iter().position(|reader| *reader == thread) {
+                state.readers.swap_remove(pos);
+            }
+        });
+
+        self.locks.inner.released.notify_all();
+    }
+}
+
+/// Holds `Account`s locked for committing (see [`AccountLocks::write`]). Dropping it releases the locks.
+pub struct AccountWriteGuard {
+    locks: AccountLocks,
+
+    addrs: Vec<Address>,
+}
+
+impl Drop for AccountWriteGuard {
+    fn drop(&mut self) {
+        for addr in self.addrs.iter() {
+            self.locks.release(addr, |state| state.writer = None);
+        }
+
+        self.locks.inner.released.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn account_locks_commit_waits_for_readers() {
+        let locks = AccountLocks::new();
+        let addr = Address::repeat(0x10);
+
+        let guard = locks.read(&addr);
+        assert!(locks.is_locked(&addr));
+
+        let (tx, rx) = mpsc::channel();
+        let handle = {
+            let locks = locks.clone();
+            let addr = addr.clone();
+
+            thread::spawn(move || {
+                let _guard = locks.write(&[addr]);
+                tx.send(()).unwrap();
+            })
+        };
+
+        assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+
+        drop(guard);
+
+        rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        handle.join().unwrap();
+
+        assert!(!locks.is_locked(&addr));
+    }
+
+    #[test]
+    fn account_locks_are_reentrant() {
+        let locks = AccountLocks::new();
+        let addr = Address::repeat(0x10);
+        let other = Address::repeat(0x20);
+
+        let read = locks.read(&addr);
+        let write = locks.write(&[other.clone(), addr.clone()]);
+        let nested_read = locks.read(&other);
+
+        drop(nested_read);
+        drop(write);
+        drop(read);
+
+        assert!(!locks.is_locked(&addr));
+        assert!(!locks.is_locked(&other));
+    }
+}
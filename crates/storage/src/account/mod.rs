@@ -1,5 +1,7 @@
 //! High-level `Storage`
-use std::collections::HashMap;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 
 mod raw;
 use raw::{RawChange, RawStorage};
@@ -7,8 +9,23 @@ use raw::{RawChange, RawStorage};
 mod kv;
 pub use kv::AccountKVStore;
 
-use svm_layout::{FixedLayout, Id};
-use svm_types::State;
+mod balance;
+pub use balance::{BalanceOverflow, InsufficientBalance, TransferError};
+
+mod dynamic;
+use dynamic::DynKey;
+
+mod page;
+pub use page::PageCorrupted;
+
+mod lock;
+pub use lock::{AccountLocks, AccountReadGuard, AccountWriteGuard};
+
+mod transform;
+pub use transform::{IdentityTransform, VarTransform};
+
+use svm_layout::{DynamicLayout, FixedLayout, Id};
+use svm_types::{Address, State};
 
 ///
 /// The `AccountStorage` manages a running `Account`'s storage.
@@ -22,6 +39,27 @@ use svm_types::State;
 ///
 /// If `Account`'s execution fails - no changes will be persisted (`commit` won't be called).
 ///
+/// A packed boolean variable (see [`svm_layout::RawVar::bit`]) is read and written as a single byte
+/// holding either `0` or `1`. Upon `commit`, the packed booleans sharing a byte are merged into it.
+///
+/// The entries of the dynamic variables (see [`DynamicLayout`]) are read and written by their keys.
+/// They're stored next to the fixed variables as well, each under the hash of its variable's `Id` and key.
+/// Since these can't be enumerated, a digest of all the entries is maintained along them (see `dynamic_digest`).
+///
+/// The `AccountStorage` also tracks the coins balance of its `Account` (stored next to the variables).
+/// Transferring coins to other `Account`s updates their balances upon `commit` as well.
+///
+/// When given [`AccountLocks`], a `commit` locks all the `Account`s it touches while writing into them,
+/// so that concurrent readers never observe a partially committed transaction.
+///
+/// The fixed variables are persisted in checksummed pages, verified whenever read.
+/// A corrupted page fails the read (or the `commit` touching it) with a [`PageCorrupted`] error.
+/// The pages persisted prior to the checksums are migrated once rewritten, or all at once by `migrate_pages`.
+///
+/// Cloning an `AccountStorage` clones its uncommitted changes too (backed by the same key-value store),
+/// so the clone can be committed (or dropped) independently.
+///
+#[derive(Clone)]
 pub struct AccountStorage {
     /// Interface to the underlying raw storage.
     raw_storage: RawStorage,
@@ -29,8 +67,27 @@ pub struct AccountStorage {
     /// Fixed-Sized variables layout.
     layout: FixedLayout,
 
+    /// Dynamic variables layout.
+    dynamic: DynamicLayout,
+
     /// Uncommitted changes.
     uncommitted: HashMap<Id, Vec<u8>>,
+
+    /// Uncommitted changes of the dynamic variables entries, along with their variables `Id`s
+    /// (an empty value marks a removed entry).
+    uncommitted_dyn: HashMap<DynKey, (Id, Vec<u8>)>,
+
+    /// Transforms the variables persisted into (and read from) the raw storage.
+    transform: Arc<dyn VarTransform + Send + Sync>,
+
+    /// Uncommitted balances (of the `Account` and of the `Account`s it has transferred coins to).
+    balances: HashMap<Address, u64>,
+
+    /// Coordinates the `commit` with the concurrent readers (none by default).
+    locks: Option<AccountLocks>,
+
+    /// The legacy pages to be sealed upon `commit` (see `migrate_pages`).
+    legacy_pages: Vec<u32>,
 }
 
 // TODO:
@@ -44,11 +101,35 @@ impl AccountStorage {
     pub fn new(layout: FixedLayout, account_kv: AccountKVStore) -> Self {
         Self {
             layout,
+            dynamic: DynamicLayout::default(),
             raw_storage: RawStorage::new(account_kv, KV_VALUE_SIZE),
             uncommitted: HashMap::new(),
+            uncommitted_dyn: HashMap::new(),
+            transform: Arc::new(IdentityTransform),
+            balances: HashMap::new(),
+            locks: None,
+            legacy_pages: Vec::new(),
         }
     }
 
+    /// Sets the [`VarTransform`] applied to the variables (overrides the [`IdentityTransform`] default).
+    pub fn with_transform(mut self, transform: Arc<dyn VarTransform + Send + Sync>) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Sets the [`DynamicLayout`] of the dynamic variables (none by default).
+    pub fn with_dynamic_layout(mut self, dynamic: DynamicLayout) -> Self {
+        self.dynamic = dynamic;
+        self
+    }
+
+    /// Sets the [`AccountLocks`] taken by `commit` (no locks are taken by default).
+    pub fn with_locks(mut self, locks: AccountLocks) -> Self {
+        self.locks = Some(locks);
+        self
+    }
+
     /// Rewinds the current `Account`'s `State` to point to `state`.
     #[inline]
     pub fn rewind(&mut self, state: &State) {
@@ -62,29 +143,141 @@ impl AccountStorage {
     }
 
     /// Reads variable `var_id`.
-    pub fn read_var(&self, var_id: Id) -> Vec<u8> {
-        let var = self.uncommitted.get(&var_id).cloned();
+    pub fn read_var(&self, var_id: Id) -> Result<Vec<u8>, PageCorrupted> {
+        if let Some(var) = self.uncommitted.get(&var_id) {
+            return Ok(var.clone());
+        }
+
+        if let Some(bit) = self.read_packed(var_id)? {
+            return Ok(vec![bit as u8]);
+        }
+
+        let (off, len) = self.var_layout(var_id);
+
+        let mut bytes = self.raw_storage.read(off, len)?;
+
+        debug_assert_eq!(bytes.len(), len as usize);
 
-        var.unwrap_or_else(|| {
-            let (off, len) = self.var_layout(var_id);
+        self.transform.decode(var_id, &mut bytes);
 
-            let bytes = self.raw_storage.read(off, len);
+        Ok(bytes)
+    }
+
+    /// Reads variable `var_id` into `buf` (without allocating).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `buf` differs from the variable's length.
+    pub fn read_var_into(&self, var_id: Id, buf: &mut [u8]) -> Result<(), PageCorrupted> {
+        let (off, len) = self.var_layout(var_id);
 
-            debug_assert_eq!(bytes.len(), len as usize);
+        assert_eq!(buf.len(), len as usize);
+
+        match self.uncommitted.get(&var_id) {
+            Some(var) => buf.copy_from_slice(var),
+            None => match self.read_packed(var_id)? {
+                Some(bit) => buf[0] = bit as u8,
+                None => {
+                    self.raw_storage.read_into(off, buf)?;
+                    self.transform.decode(var_id, buf);
+                }
+            },
+        }
 
-            bytes
-        })
+        Ok(())
     }
 
     /// Marks variable as `dirty`. Upon `commit` will persist the variable.
-    pub fn write_var(&mut self, var_id: Id, value: Vec<u8>) {
+    pub fn write_var(&mut self, var_id: Id, mut value: Vec<u8>) {
         let (_off, len) = self.var_layout(var_id);
 
         assert_eq!(value.len(), len as usize);
 
+        if self.layout.get(var_id).bit().is_some() {
+            value[0] = (value[0] != 0) as u8;
+        }
+
         self.uncommitted.insert(var_id, value);
     }
 
+    /// Reads the packed boolean variable `var_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if variable `var_id` isn't a packed boolean.
+    pub fn read_bit(&self, var_id: Id) -> Result<bool, PageCorrupted> {
+        assert!(self.layout.get(var_id).bit().is_some());
+
+        Ok(self.read_var(var_id)?[0] != 0)
+    }
+
+    /// Marks the packed boolean variable `var_id` as `dirty`. Upon `commit` will persist the variable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if variable `var_id` isn't a packed boolean.
+    pub fn write_bit(&mut self, var_id: Id, value: bool) {
+        assert!(self.layout.get(var_id).bit().is_some());
+
+        self.uncommitted.insert(var_id, vec![value as u8]);
+    }
+
+    /// Reads entry `key` of the dynamic variable `var_id` (`None` when there is no such entry).
+    ///
+    /// # Panics
+    ///
+    /// Panics if variable `var_id` isn't a dynamic one.
+    pub fn read_dyn(&self, var_id: Id, key: &[u8]) -> Option<Vec<u8>> {
+        assert!(self.dynamic.contains(var_id));
+
+        let key = dynamic::dyn_key(var_id, key);
+
+        match self.uncommitted_dyn.get(&key) {
+            Some((_, value)) if value.is_empty() => None,
+            Some((_, value)) => Some(value.clone()),
+            None => {
+                let mut value = dynamic::read_entry(self.raw_storage.account_kv(), &key)?;
+
+                self.transform.decode(var_id, &mut value);
+
+                Some(value)
+            }
+        }
+    }
+
+    /// Marks entry `key` of the dynamic variable `var_id` as `dirty`. Upon `commit` will persist the entry.
+    ///
+    /// An empty `value` removes the entry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if variable `var_id` isn't a dynamic one.
+    pub fn write_dyn(&mut self, var_id: Id, key: &[u8], value: Vec<u8>) {
+        assert!(self.dynamic.contains(var_id));
+
+        let key = dynamic::dyn_key(var_id, key);
+
+        self.uncommitted_dyn.insert(key, (var_id, value));
+    }
+
+    /// Returns the digest committing to the persisted entries of the dynamic variables
+    /// (all zeros when there are none). It's updated upon `commit`.
+    pub fn dynamic_digest(&self) -> [u8; 32] {
+        dynamic::read_digest(self.account_kv())
+    }
+
+    /// Returns the fixed layout of the variables.
+    #[inline]
+    pub fn layout(&self) -> &FixedLayout {
+        &self.layout
+    }
+
+    /// Returns the layout of the dynamic variables.
+    #[inline]
+    pub fn dynamic_layout(&self) -> &DynamicLayout {
+        &self.dynamic
+    }
+
     /// Returns the layout of variable `var_id`.
     /// The layout is a tuple of `(offset, length)`.
     #[inline]
@@ -94,9 +287,97 @@ impl AccountStorage {
         (var.offset(), var.byte_size())
     }
 
-    /// Commits modified variables into the raw storage.
-    #[must_use]
-    pub fn commit(&mut self) -> State {
+    /// The [`AccountKVStore`] backing the storage.
+    #[inline]
+    pub fn account_kv(&self) -> &AccountKVStore {
+        self.raw_storage.account_kv()
+    }
+
+    /// Returns the `Address` of the `Account`.
+    #[inline]
+    pub fn account_addr(&self) -> &Address {
+        &self.raw_storage.account_kv().account_addr
+    }
+
+    /// Returns the most updated balance of the `Account`.
+    pub fn balance(&self) -> u64 {
+        let addr = self.account_addr().clone();
+
+        self.balance_of(&addr)
+    }
+
+    /// Returns the most updated balance of `Account` `addr` (as seen by this `Account`).
+    pub fn balance_of(&self, addr: &Address) -> u64 {
+        match self.balances.get(addr) {
+            Some(balance) => *balance,
+            None => {
+                let account_kv = self.account_kv().sibling(addr.clone());
+
+                balance::read_balance(&account_kv)
+            }
+        }
+    }
+
+    /// Sets the balance of the `Account`. Upon `commit` will persist the balance.
+    pub fn set_balance(&mut self, balance: u64) {
+        let addr = self.account_addr().clone();
+
+        self.balances.insert(addr, balance);
+    }
+
+    /// Transfers `amount` coins from the `Account` to `Account` `dst`.
+    /// Upon `commit` will persist both balances.
+    ///
+    /// Fails (leaving the balances intact) when the `Account` holds less than `amount` coins,
+    /// or when the balance of `dst` can't hold `amount` more coins.
+    ///
+    /// The storage doesn't know which `Account`s exist, so any `dst` is credited.
+    /// Rejecting transfers to unknown `Account`s is left for the caller (the `Runtime` does so).
+    pub fn transfer(&mut self, dst: &Address, amount: u64) -> Result<(), TransferError> {
+        let src = self.account_addr().clone();
+        let balance = self.balance();
+
+        if balance < amount {
+            let err = InsufficientBalance {
+                account: src,
+                balance,
+                amount,
+            };
+
+            return Err(TransferError::InsufficientBalance(err));
+        }
+
+        if *dst != src {
+            let dst_balance = self.balance_of(dst);
+
+            let credited = dst_balance.checked_add(amount).ok_or_else(|| {
+                TransferError::BalanceOverflow(BalanceOverflow {
+                    account: dst.clone(),
+                    balance: dst_balance,
+                    amount,
+                })
+            })?;
+
+            self.balances.insert(src, balance - amount);
+            self.balances.insert(dst.clone(), credited);
+        }
+
+        Ok(())
+    }
+
+    /// Commits modified variables (and balances) into the raw storage.
+    ///
+    /// The touched pages are all verified before writing anything,
+    /// so a [`PageCorrupted`] error leaves the persisted storage intact.
+    pub fn commit(&mut self) -> Result<State, PageCorrupted> {
+        // All the touched `Account`s stay locked until the changes are checkpointed
+        let _guard = self.locks.as_ref().map(|locks| {
+            let mut addrs: Vec<Address> = self.balances.keys().cloned().collect();
+            addrs.push(self.account_addr().clone());
+
+            locks.write(&addrs)
+        });
+
         let var_offset: HashMap<Id, u32> = self
             .uncommitted
             .keys()
@@ -107,20 +388,147 @@ impl AccountStorage {
             })
             .collect();
 
-        let changes = self
+        // The packed booleans are merged into their (persisted) bytes, one `RawChange` per byte
+        let mut packed: BTreeMap<u32, (Id, u8)> = BTreeMap::new();
+
+        for (var_id, data) in self.uncommitted.iter() {
+            let var = self.layout.get(*var_id);
+
+            if let Some(bit) = var.bit() {
+                let offset = var.offset();
+                let (owner, byte) = match packed.entry(offset) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => {
+                        let byte = self.read_byte(var.byte_owner(), offset)?;
+
+                        entry.insert((var.byte_owner(), byte))
+                    }
+                };
+
+                debug_assert_eq!(*owner, var.byte_owner());
+
+                if data[0] != 0 {
+                    *byte |= 1 << bit;
+                } else {
+                    *byte &= !(1 << bit);
+                }
+            }
+        }
+
+        let layout = &self.layout;
+        let transform = &self.transform;
+
+        let mut changes = self
             .uncommitted
-            .drain()
+            .iter()
+            .filter(|(var_id, _data)| layout.get(**var_id).bit().is_none())
             .map(|(var_id, data)| {
-                let offset = *var_offset.get(&var_id).unwrap();
+                let offset = *var_offset.get(var_id).unwrap();
+
+                let mut data = data.clone();
+                transform.encode(*var_id, &mut data);
 
                 RawChange { offset, data }
             })
             .collect::<Vec<_>>();
 
-        self.raw_storage.write(&changes);
+        changes.extend(packed.into_iter().map(|(offset, (owner, byte))| {
+            let mut data = vec![byte];
 
-        debug_assert!(self.uncommitted.is_empty());
+            transform.encode(owner, &mut data);
 
-        self.raw_storage.head()
+            RawChange { offset, data }
+        }));
+
+        let pages = self.raw_storage.patch(&changes, &self.legacy_pages)?;
+
+        // The balances are set (ordered by `Address`) before writing the fixed variables,
+        // which checkpoints them all at once
+        let mut balances: Vec<_> = self.balances.drain().collect();
+        balances.sort_by(|(a, _), (b, _)| a.as_slice().cmp(b.as_slice()));
+
+        for (addr, balance) in balances {
+            let mut account_kv = self.raw_storage.account_kv().sibling(addr);
+
+            balance::write_balance(&mut account_kv, balance);
+        }
+
+        // So are the dynamic variables entries (ordered by their keys)
+        let mut entries: Vec<_> = self.uncommitted_dyn.drain().collect();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut account_kv = self.raw_storage.account_kv().clone();
+
+        if !entries.is_empty() {
+            let mut digest = dynamic::read_digest(&account_kv);
+
+            for (key, (var_id, mut value)) in entries {
+                // An empty value (i.e a removed entry) is kept as is
+                if !value.is_empty() {
+                    self.transform.encode(var_id, &mut value);
+                }
+
+                let old = dynamic::read_entry(&account_kv, &key);
+                let new = Some(&value[..]).filter(|value| !value.is_empty());
+                dynamic::update_digest(&mut digest, &key, old.as_deref(), new);
+
+                dynamic::write_entry(&mut account_kv, &key, &value);
+            }
+
+            dynamic::write_digest(&mut account_kv, &digest);
+        }
+
+        self.raw_storage.write(&pages);
+
+        self.uncommitted.clear();
+        self.legacy_pages.clear();
+
+        Ok(self.raw_storage.head())
+    }
+
+    /// Marks the fixed variables pages persisted prior to the checksummed page format (see [`PageCorrupted`])
+    /// to be sealed upon `commit` (along with the other changes), returning the number of such pages.
+    ///
+    /// The variables values are left intact (so the pages may be migrated by any transaction).
+    pub fn migrate_pages(&mut self) -> Result<usize, PageCorrupted> {
+        let mut keys: Vec<u32> = self
+            .layout
+            .iter()
+            .map(|var| {
+                self.raw_storage
+                    .offset_length_key(var.offset(), var.byte_size())
+            })
+            .collect();
+
+        keys.sort_unstable();
+        keys.dedup();
+
+        self.legacy_pages = self.raw_storage.legacy_keys(&keys)?;
+
+        Ok(self.legacy_pages.len())
+    }
+
+    /// Reads the persisted value of a packed boolean variable (`None` for any other variable).
+    fn read_packed(&self, var_id: Id) -> Result<Option<bool>, PageCorrupted> {
+        let var = self.layout.get(var_id);
+
+        match var.bit() {
+            Some(bit) => {
+                let byte = self.read_byte(var.byte_owner(), var.offset())?;
+
+                Ok(Some(byte & (1 << bit) != 0))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Reads the persisted byte at `offset` (shared by the packed booleans starting at variable `owner`).
+    fn read_byte(&self, owner: Id, offset: u32) -> Result<u8, PageCorrupted> {
+        let mut byte = [0; 1];
+
+        self.raw_storage.read_into(offset, &mut byte)?;
+        self.transform.decode(owner, &mut byte);
+
+        Ok(byte[0])
     }
 }
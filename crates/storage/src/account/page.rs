@@ -0,0 +1,167 @@
+use std::fmt;
+
+use svm_hash::{Blake3Hasher, Hasher};
+use svm_types::Address;
+
+/// The magic prefixing each sealed page.
+const MAGIC: [u8; 2] = *b"SP";
+
+/// The current version of the page format.
+const VERSION: u8 = 1;
+
+/// The length of the page header (magic, version and checksum).
+pub(super) const HEADER_LEN: usize = MAGIC.len() + 1 + CHECKSUM_LEN;
+
+/// The length of a page checksum (a prefix of its Blake3 hash).
+const CHECKSUM_LEN: usize = 4;
+
+/// The error raised when a persisted page of an `Account`'s storage fails its verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageCorrupted {
+    /// The `Account` owning the page.
+    pub account: Address,
+
+    /// The key of the page (under the `Account`'s [`AccountKVStore`](super::AccountKVStore)).
+    pub key: u32,
+
+    /// Describes the failed verification.
+    pub reason: String,
+}
+
+impl fmt::Display for PageCorrupted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Corrupted storage: page #{} of `Account` {:?} ({})",
+            self.key, self.account, self.reason
+        )
+    }
+}
+
+impl std::error::Error for PageCorrupted {}
+
+/// A persisted page, as told by [`open`].
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Page {
+    /// A verified page (of the current format).
+    Sealed(Vec<u8>),
+
+    /// A page persisted prior to the introduction of the page format (i.e raw data).
+    Legacy(Vec<u8>),
+}
+
+/// Wraps the `data` of page `key` with its header:
+///
+/// +-----------+-----------+-----------------+--------------+
+/// |   Magic   |  Version  |    Checksum     |     Data     |
+/// | (2 bytes) | (1 byte)  |    (4 bytes)    |              |
+/// +-----------+-----------+-----------------+--------------+
+///
+/// The checksum covers the page's `key` too, so that a page persisted under another key is detected as well.
+pub(super) fn seal(key: u32, data: &[u8]) -> Vec<u8> {
+    let mut page = Vec::with_capacity(HEADER_LEN + data.len());
+
+    page.extend_from_slice(&MAGIC);
+    page.push(VERSION);
+    page.extend_from_slice(&checksum(key, data));
+    page.extend_from_slice(data);
+
+    page
+}
+
+/// Verifies the persisted `value` of page `key` (holding `page_size` bytes of data), returning its data.
+///
+/// A `value` of exactly `page_size` bytes is a [`Page::Legacy`] one, accepted as is. Pages are told apart
+/// by their length only, since a legacy page holds arbitrary data (it may start with the header's magic too).
+pub(super) fn open(key: u32, value: Vec<u8>, page_size: usize) -> Result<Page, String> {
+    if value.len() == page_size {
+        return Ok(Page::Legacy(value));
+    }
+
+    if value.len() != HEADER_LEN + page_size {
+        return Err(format!(
+            "expected {} bytes, got {}",
+            HEADER_LEN + page_size,
+            value.len()
+        ));
+    }
+
+    let (header, data) = value.split_at(HEADER_LEN);
+
+    if header[..MAGIC.len()] != MAGIC {
+        return Err("bad magic".to_string());
+    }
+
+    let version = header[MAGIC.len()];
+    if version != VERSION {
+        return Err(format!("unsupported version {}", version));
+    }
+
+    if header[MAGIC.len() + 1..] != checksum(key, data) {
+        return Err("checksum mismatch".to_string());
+    }
+
+    Ok(Page::Sealed(data.to_vec()))
+}
+
+fn checksum(key: u32, data: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let mut hasher = Blake3Hasher::default();
+    hasher.update(&key.to_be_bytes()).update(data);
+
+    let hash = hasher.finalize();
+
+    let mut checksum = [0; CHECKSUM_LEN];
+    checksum.copy_from_slice(&hash[..CHECKSUM_LEN]);
+
+    checksum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_seal_and_open() {
+        let data = vec![0x10; 32];
+        let page = seal(7, &data);
+
+        assert_eq!(page.len(), HEADER_LEN + 32);
+        assert_eq!(open(7, page, 32), Ok(Page::Sealed(data)));
+    }
+
+    #[test]
+    fn page_open_legacy() {
+        let data = vec![0x20; 32];
+
+        assert_eq!(open(7, data.clone(), 32), Ok(Page::Legacy(data)));
+
+        let mut data = vec![0x20; 32];
+        data[..3].copy_from_slice(b"SP\x01");
+
+        assert_eq!(open(7, data.clone(), 32), Ok(Page::Legacy(data)));
+    }
+
+    #[test]
+    fn page_open_corrupted() {
+        let page = seal(7, &[0x10; 32]);
+
+        let mut flipped = page.clone();
+        flipped[HEADER_LEN + 3] ^= 0x01;
+        assert_eq!(open(7, flipped, 32).unwrap_err(), "checksum mismatch");
+
+        assert_eq!(open(8, page.clone(), 32).unwrap_err(), "checksum mismatch");
+
+        let mut version = page.clone();
+        version[2] = 2;
+        assert_eq!(open(7, version, 32).unwrap_err(), "unsupported version 2");
+
+        let mut magic = page.clone();
+        magic[0] = b'X';
+        assert_eq!(open(7, magic, 32).unwrap_err(), "bad magic");
+
+        assert_eq!(
+            open(7, page[..20].to_vec(), 32).unwrap_err(),
+            "expected 39 bytes, got 20"
+        );
+    }
+}
@@ -2,11 +2,19 @@ use std::collections::HashMap;
 
 use svm_types::State;
 
+use super::page::{self, Page, PageCorrupted};
 use super::AccountKVStore;
 use crate::kv::StatefulKV;
 
 /// Interface against the underling key-value store.
 /// Data is manipulated using `offset` and `length`.
+///
+/// Each value (i.e page) is persisted sealed by a checksummed header (see [`page::seal`]),
+/// verified whenever it's read back. The pages persisted prior to the sealing are still readable,
+/// and are sealed once rewritten (see [`RawStorage::patch`]).
+///
+/// Reading a page failing its verification returns a [`PageCorrupted`] error.
+#[derive(Clone)]
 pub struct RawStorage {
     account_kv: AccountKVStore,
     kv_value_size: u32,
@@ -48,37 +56,74 @@ impl RawStorage {
         self.account_kv.head()
     }
 
+    /// The [`AccountKVStore`] backing the storage.
+    #[inline]
+    pub fn account_kv(&self) -> &AccountKVStore {
+        &self.account_kv
+    }
+
     /// Reads the raw data under `offset, offset + 1, ..., offset + length - 1`
     /// In case there is no stored blob, returns a zeros vector of length `length`.
-    pub fn read(&self, offset: u32, length: u32) -> Vec<u8> {
+    pub fn read(&self, offset: u32, length: u32) -> Result<Vec<u8>, PageCorrupted> {
         assert!(length <= self.kv_value_size);
 
         let key = self.offset_length_key(offset, length);
-        let value = self.do_read_key(key);
+        let value = self.do_read_key(key)?;
 
         let slice = self.value_slice(&value[..], offset, length);
-        slice.to_vec()
+        Ok(slice.to_vec())
     }
 
-    /// Writes a batch of `RawChange` into the underlying key-value store.
-    pub fn write(&mut self, changes: &[RawChange]) {
-        let changes = self.group_changes_by_key(changes);
+    /// Same as [`RawStorage::read`], but copies the raw data into `buf` (of length `length`)
+    /// instead of allocating a new vector.
+    pub fn read_into(&self, offset: u32, buf: &mut [u8]) -> Result<(), PageCorrupted> {
+        let length = buf.len() as u32;
+        assert!(length <= self.kv_value_size);
 
-        let mut raw_changes = Vec::with_capacity(changes.len());
+        let key = self.offset_length_key(offset, length);
+        let value = self.do_read_key(key)?;
 
-        for (key, value_changes) in changes.iter() {
-            let raw_key = key.to_be_bytes();
+        let slice = self.value_slice(&value[..], offset, length);
+        buf.copy_from_slice(slice);
+
+        Ok(())
+    }
+
+    /// Applies a batch of `RawChange` to the pages they touch, returning the patched pages (sealed)
+    /// to be written by [`RawStorage::write`].
+    ///
+    /// The pages of keys `reseal` are returned (i.e sealed) as well, even if left unchanged.
+    ///
+    /// Nothing is written yet, so a [`PageCorrupted`] error leaves the key-value store intact.
+    pub fn patch(
+        &self,
+        changes: &[RawChange],
+        reseal: &[u32],
+    ) -> Result<Vec<(u32, Vec<u8>)>, PageCorrupted> {
+        let mut changes = self.group_changes_by_key(changes);
+
+        for key in reseal.iter() {
+            changes.entry(*key).or_insert_with(Vec::new);
+        }
+
+        let mut pages = Vec::with_capacity(changes.len());
 
-            let mut raw_value = self.do_read_key(*key);
+        for (key, value_changes) in changes.iter() {
+            let mut raw_value = self.do_read_key(*key)?;
             debug_assert_eq!(raw_value.len(), self.kv_value_size as usize);
 
             self.patch_value(&mut raw_value, &value_changes[..]);
 
-            raw_changes.push((raw_key, raw_value));
+            pages.push((*key, page::seal(*key, &raw_value)));
         }
 
-        for (k, v) in raw_changes.iter() {
-            self.account_kv.set(k, v);
+        Ok(pages)
+    }
+
+    /// Writes the `pages` returned by [`RawStorage::patch`] into the underlying key-value store.
+    pub fn write(&mut self, pages: &[(u32, Vec<u8>)]) {
+        for (key, page) in pages.iter() {
+            self.account_kv.set(&key.to_be_bytes(), page);
         }
 
         let _state = self.account_kv.checkpoint();
@@ -86,13 +131,42 @@ impl RawStorage {
         self.account_kv.flush();
     }
 
+    /// Returns the legacy (i.e not sealed yet) pages among `keys`.
+    pub fn legacy_keys(&self, keys: &[u32]) -> Result<Vec<u32>, PageCorrupted> {
+        let mut legacy = Vec::new();
+
+        for &key in keys.iter() {
+            if let Some(Page::Legacy(..)) = self.do_read_page(key)? {
+                legacy.push(key);
+            }
+        }
+
+        Ok(legacy)
+    }
+
     #[inline]
-    fn do_read_key(&self, key: u32) -> Vec<u8> {
-        let key = key.to_be_bytes();
+    fn do_read_key(&self, key: u32) -> Result<Vec<u8>, PageCorrupted> {
+        let data = match self.do_read_page(key)? {
+            Some(Page::Sealed(data)) | Some(Page::Legacy(data)) => data,
+            None => vec![0; self.kv_value_size as usize],
+        };
+
+        Ok(data)
+    }
+
+    fn do_read_page(&self, key: u32) -> Result<Option<Page>, PageCorrupted> {
+        let value = match self.account_kv.get(&key.to_be_bytes()[..]) {
+            Some(value) => value,
+            None => return Ok(None),
+        };
 
-        self.account_kv
-            .get(&key[..])
-            .unwrap_or(vec![0; self.kv_value_size as usize])
+        page::open(key, value, self.kv_value_size as usize)
+            .map(Some)
+            .map_err(|reason| PageCorrupted {
+                account: self.account_kv.account_addr.clone(),
+                key,
+                reason,
+            })
     }
 
     #[inline]
@@ -174,7 +248,7 @@ mod tests {
         let len = 20;
 
         let storage = RawStorage::new(kv, KV_VALUE_SIZE);
-        let bytes = storage.read(off, len);
+        let bytes = storage.read(off, len).unwrap();
 
         assert_eq!(bytes, vec![0; len as usize]);
     }
@@ -197,12 +271,13 @@ mod tests {
         let changes = vec![var1.clone(), var2.clone()];
 
         let mut storage = RawStorage::new(kv, KV_VALUE_SIZE);
-        storage.write(&changes);
+        let pages = storage.patch(&changes, &[]).unwrap();
+        storage.write(&pages);
 
-        let data1 = storage.read(var1.offset, var1.len());
+        let data1 = storage.read(var1.offset, var1.len()).unwrap();
         assert_eq!(data1, vec![0x10, 0x20, 0x30]);
 
-        let data2 = storage.read(var2.offset, var2.len());
+        let data2 = storage.read(var2.offset, var2.len()).unwrap();
         assert_eq!(data2, vec![0x40, 0x50]);
     }
 }
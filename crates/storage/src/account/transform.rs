@@ -0,0 +1,35 @@
+use svm_layout::Id;
+
+/// A pluggable transformation of an `Account`'s variables applied on their way
+/// to (and from) the underlying raw storage.
+///
+/// It allows embedders to experiment with confidential state (for example, encrypting
+/// specific variables with a layer-key), while a running `Account` keeps seeing plain data.
+///
+/// # Notes
+///
+/// * A transformation must be length-preserving (it works in-place), since each variable
+///   occupies a fixed region of the raw storage. That also means gas and storage-size
+///   calculations keep relying only on the variables' layout sizes, never on transformed bytes.
+///
+/// * Never-written variables are read as raw zeros. A `decode` that doesn't map zeros
+///   to zeros will expose non-zero initial values to the running `Account`.
+pub trait VarTransform {
+    /// Transforms in-place the plain `bytes` of variable `var_id` before they are persisted.
+    fn encode(&self, var_id: Id, bytes: &mut [u8]);
+
+    /// Transforms in-place the persisted `bytes` of variable `var_id` back into plain data.
+    fn decode(&self, var_id: Id, bytes: &mut [u8]);
+}
+
+/// The default [`VarTransform`]. Leaves the variables untouched.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IdentityTransform;
+
+impl VarTransform for IdentityTransform {
+    #[inline]
+    fn encode(&self, _var_id: Id, _bytes: &mut [u8]) {}
+
+    #[inline]
+    fn decode(&self, _var_id: Id, _bytes: &mut [u8]) {}
+}
@@ -52,6 +52,7 @@ use svm_types::State;
 /// If we find a matching value we halt and return the found value.
 /// If we've reached the end of the journal then we move to the `unflushed` (see detailed explanation above).
 ///
+#[derive(Clone)]
 pub struct FakeKV {
     head: State,
 
@@ -62,9 +63,10 @@ pub struct FakeKV {
     journal: Vec<(Option<State>, Vec<Change>)>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Change(Vec<u8>, Vec<u8>);
 
+#[derive(Clone)]
 struct Node {
     parent: State,
 
@@ -83,6 +85,26 @@ impl StatefulKV for FakeKV {
         self.get_journal(key).or_else(|| self.get_flushed(key))
     }
 
+    #[must_use]
+    fn get_at(&self, state: &State, key: &[u8]) -> Option<Vec<u8>> {
+        // A checkpoint not flushed yet sees its own changes and the ones of the preceding checkpoints
+        let checkpoint = self
+            .journal
+            .iter()
+            .position(|(checkpoint, _)| checkpoint.as_ref() == Some(state));
+
+        match checkpoint {
+            Some(index) => self.journal[..=index]
+                .iter()
+                .rev()
+                .flat_map(|(_state, changes)| changes.iter().rev())
+                .find(|change| change.0 == key)
+                .map(|change| change.1.to_vec())
+                .or_else(|| self.get_flushed_at(&self.flushed_head, key)),
+            None => self.get_flushed_at(state, key),
+        }
+    }
+
     fn set(&mut self, key: &[u8], value: &[u8]) {
         let key = key.to_vec();
         let value = value.to_vec();
@@ -177,14 +199,16 @@ impl FakeKV {
     }
 
     fn get_flushed(&self, key: &[u8]) -> Option<Vec<u8>> {
-        let mut state = &self.head;
+        self.get_flushed_at(&self.head, key)
+    }
 
+    fn get_flushed_at<'a>(&'a self, mut state: &'a State, key: &[u8]) -> Option<Vec<u8>> {
         loop {
             if state.is_zeros() {
                 return None;
             }
 
-            let node = self.flushed.get(&state).unwrap();
+            let node = self.flushed.get(state).unwrap();
 
             match node.get(key) {
                 None => state = &node.parent,
@@ -523,4 +547,37 @@ mod tests {
           (k3 => v3),
         );
     }
+
+    #[test]
+    fn fake_kv_get_at() {
+        let mut kv = FakeKV::new();
+
+        let (k1, v1) = (b"aaa", vec![0x10, 0x20]);
+        let (k2, v2) = (b"bbb", vec![0x30, 0x40, 0x50]);
+        let (k3, v3) = (b"aaa", vec![0x60, 0x70]);
+
+        let s1 = apply_changes!(kv,
+          (k1 => v1),
+        );
+
+        let s2 = apply_changes!(kv,
+          (k2 => v2),
+          (k3 => v3),
+        );
+
+        // An unflushed checkpoint
+        kv.set(k2, &[0x80]);
+        let s3 = kv.checkpoint();
+
+        assert_eq!(kv.get_at(&s1, k1), Some(v1));
+        assert_eq!(kv.get_at(&s1, k2), None);
+        assert_eq!(kv.get_at(&s2, k1), Some(v3.clone()));
+        assert_eq!(kv.get_at(&s2, k2), Some(v2));
+        assert_eq!(kv.get_at(&s3, k1), Some(v3));
+        assert_eq!(kv.get_at(&s3, k2), Some(vec![0x80]));
+        assert_eq!(kv.get_at(&State::zeros(), k1), None);
+
+        // Reading a historical `State` leaves the current one intact
+        assert_eq!(kv.head(), s3);
+    }
 }
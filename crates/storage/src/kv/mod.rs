@@ -2,6 +2,8 @@
 
 mod mock;
 mod traits;
+mod write_behind;
 
 pub use mock::FakeKV;
 pub use traits::StatefulKV;
+pub use write_behind::{FlushError, WriteBehindKV, WriteMode};
@@ -15,6 +15,15 @@ pub trait StatefulKV {
     #[must_use]
     fn get(&self, key: &[u8]) -> Option<Vec<u8>>;
 
+    /// Gets the `value` pointed by `key` as of the checkpoint `state` (i.e a historical read).
+    ///
+    /// The current pointed-to `State` is left intact, and so are the pending changes
+    /// (the ones not associated with any checkpoint are ignored).
+    ///
+    /// In case there is no matching `value` under `state`, `None` should be returned.
+    #[must_use]
+    fn get_at(&self, state: &State, key: &[u8]) -> Option<Vec<u8>>;
+
     /// Sets a new pending change.
     ///
     /// Calling `set` should not persist the change but mark it as a pending change.
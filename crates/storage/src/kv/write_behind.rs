@@ -0,0 +1,492 @@
+use std::collections::VecDeque;
+use std::convert::TryInto;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+
+use svm_kv::traits::RawKV;
+use svm_types::Layer;
+
+/// How a [`WriteBehindKV`] persists the batches of changes it's given.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WriteMode {
+    /// Each batch is persisted before [`RawKV::set`] returns.
+    Synchronous,
+
+    /// Each batch is staged in memory, and [`RawKV::set`] returns right away.
+    /// A background thread persists the staged batches (see [`WriteBehindKV::flush_until`]).
+    WriteBehind,
+}
+
+impl Default for WriteMode {
+    fn default() -> Self {
+        WriteMode::Synchronous
+    }
+}
+
+/// A [`RawKV`] decorator, moving the writes into the underlying key-value store
+/// (e.g `rocksdb`) out of the transactions execution path.
+///
+/// Under [`WriteMode::WriteBehind`], committing changes only stages them in an in-memory queue.
+/// A background thread drains the queue, persisting all the batches staged so far as a
+/// single write. Reads observe the staged batches, so the staging is transparent to the
+/// transactions execution.
+///
+/// Each staged batch is tagged with the [`Layer`] it belongs to (see [`WriteBehindKV::set_layer`]).
+/// Before finalizing a [`Layer`] the embedder is expected to call [`WriteBehindKV::flush_until`],
+/// which blocks until the [`Layer`] batches have been persisted.
+///
+/// Dropping a [`WriteBehindKV`] persists the staged batches, unless the background thread has failed
+/// (and stops the background thread).
+///
+/// A [`WriteBehindKV`] opened with a journal (see [`WriteBehindKV::with_journal`]) makes the queue durable:
+/// each batch is appended to the journal (and synced) before being staged, and the batches left in the
+/// journal by a crash of the process are persisted when the journal is opened again.
+/// The journal is emptied each time the background thread has drained the queue.
+///
+/// Without a journal the staged batches live in memory only, and a crash of the process loses them.
+/// Either way, once the background thread has failed (see [`FlushError`]) no more batches are accepted.
+pub struct WriteBehindKV<KV: RawKV + Send + 'static> {
+    mode: WriteMode,
+
+    layer: Layer,
+
+    shared: Arc<Shared<KV>>,
+
+    flusher: Option<JoinHandle<()>>,
+}
+
+struct Shared<KV> {
+    kv: Mutex<KV>,
+
+    /// Locked before `queue` (when both are locked).
+    journal: Option<Mutex<File>>,
+
+    queue: Mutex<Queue>,
+
+    /// Signaled when a batch is staged, or when shutting down.
+    staged: Condvar,
+
+    /// Signaled when batches have been persisted.
+    flushed: Condvar,
+}
+
+#[derive(Default)]
+struct Queue {
+    batches: VecDeque<Arc<Batch>>,
+
+    shutdown: bool,
+
+    /// Set once the background thread has failed (and stopped persisting the staged batches).
+    failed: Option<FlushError>,
+}
+
+/// The error returned once the background thread of a [`WriteBehindKV`] has failed
+/// persisting the staged batches (i.e the underlying key-value store has panicked),
+/// or when a batch couldn't be appended to the journal.
+///
+/// The batches staged at the time are never persisted (unless replayed from the journal),
+/// and later batches are rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlushError {
+    /// The reason of the failure.
+    pub msg: String,
+}
+
+impl fmt::Display for FlushError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The storage flusher has failed: {}", self.msg)
+    }
+}
+
+impl std::error::Error for FlushError {}
+
+struct Batch {
+    layer: Layer,
+
+    changes: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl Batch {
+    /// Appends the journal record of the batch to `buf`.
+    ///
+    /// A record is made of the `Layer` (8 bytes), the number of changes (4 bytes), and then of
+    /// the length-prefixed (4 bytes) key and value of each change. All numbers are Big-Endian.
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.layer.0.to_be_bytes());
+        buf.extend_from_slice(&(self.changes.len() as u32).to_be_bytes());
+
+        for (k, v) in self.changes.iter() {
+            buf.extend_from_slice(&(k.len() as u32).to_be_bytes());
+            buf.extend_from_slice(k);
+            buf.extend_from_slice(&(v.len() as u32).to_be_bytes());
+            buf.extend_from_slice(v);
+        }
+    }
+
+    /// Decodes the journal record at the start of `bytes`, and returns it along with the rest of `bytes`.
+    ///
+    /// Returns `None` when the record is truncated (i.e a crash occurred while appending it).
+    fn decode(bytes: &[u8]) -> Option<(Self, &[u8])> {
+        fn take<'a>(bytes: &mut &'a [u8], n: usize) -> Option<&'a [u8]> {
+            if bytes.len() < n {
+                return None;
+            }
+
+            let (head, rest) = bytes.split_at(n);
+            *bytes = rest;
+
+            Some(head)
+        }
+
+        fn take_u32(bytes: &mut &[u8]) -> Option<usize> {
+            take(bytes, 4).map(|b| u32::from_be_bytes(b.try_into().unwrap()) as usize)
+        }
+
+        let mut bytes = bytes;
+
+        let layer = take(&mut bytes, 8).map(|b| u64::from_be_bytes(b.try_into().unwrap()))?;
+        let count = take_u32(&mut bytes)?;
+
+        let mut changes = Vec::new();
+
+        for _ in 0..count {
+            let len = take_u32(&mut bytes)?;
+            let k = take(&mut bytes, len)?.to_vec();
+            let len = take_u32(&mut bytes)?;
+            let v = take(&mut bytes, len)?.to_vec();
+
+            changes.push((k, v));
+        }
+
+        let batch = Batch {
+            layer: Layer(layer),
+            changes,
+        };
+
+        Some((batch, bytes))
+    }
+
+    fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        self.changes
+            .iter()
+            .rev()
+            .find(|(k, _v)| k == key)
+            .map(|(_k, v)| v.as_slice())
+    }
+}
+
+impl<KV: RawKV + Send + 'static> WriteBehindKV<KV> {
+    /// Wraps `kv`, persisting the batches of changes according to `mode`.
+    ///
+    /// The batches are tagged with [`Layer`] `#0` until [`Self::set_layer`] is called.
+    pub fn new(kv: KV, mode: WriteMode) -> Self {
+        Self::build(kv, mode, None)
+    }
+
+    /// Same as [`Self::new`], but journals the staged batches into the file at `path` (created if missing),
+    /// so that these survive a crash of the process.
+    ///
+    /// The batches found in the journal (i.e left by a crash) are first persisted into `kv`,
+    /// ignoring a truncated trailing batch.
+    pub fn with_journal<P: AsRef<Path>>(mut kv: KV, mode: WriteMode, path: P) -> io::Result<Self> {
+        let mut journal = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+
+        let mut bytes = Vec::new();
+        journal.read_to_end(&mut bytes)?;
+
+        let mut batches = Vec::new();
+        let mut rest = &bytes[..];
+
+        while let Some((batch, next)) = Batch::decode(rest) {
+            batches.push(batch);
+            rest = next;
+        }
+
+        if !batches.is_empty() {
+            let changes = batches
+                .iter()
+                .flat_map(|batch| batch.changes.iter())
+                .map(|(k, v)| (k.as_slice(), v.as_slice()))
+                .collect::<Vec<_>>();
+
+            kv.set(&changes);
+        }
+
+        journal.set_len(0)?;
+        journal.sync_all()?;
+
+        Ok(Self::build(kv, mode, Some(journal)))
+    }
+
+    fn build(kv: KV, mode: WriteMode, journal: Option<File>) -> Self {
+        let shared = Arc::new(Shared {
+            kv: Mutex::new(kv),
+            journal: journal.map(Mutex::new),
+            queue: Mutex::new(Queue::default()),
+            staged: Condvar::new(),
+            flushed: Condvar::new(),
+        });
+
+        let flusher = match mode {
+            WriteMode::Synchronous => None,
+            WriteMode::WriteBehind => {
+                let shared = Arc::clone(&shared);
+
+                let handle = thread::Builder::new()
+                    .name("svm-storage-flusher".to_string())
+                    .spawn(move || shared.run_flusher())
+                    .expect("failed spawning the storage flusher thread");
+
+                Some(handle)
+            }
+        };
+
+        Self {
+            mode,
+            layer: Layer(0),
+            shared,
+            flusher,
+        }
+    }
+
+    /// The [`WriteMode`].
+    pub fn mode(&self) -> WriteMode {
+        self.mode
+    }
+
+    /// The [`Layer`] the next batches of changes belong to.
+    pub fn layer(&self) -> Layer {
+        self.layer
+    }
+
+    /// Tags the next batches of changes with `layer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `layer` precedes the current [`Layer`].
+    pub fn set_layer(&mut self, layer: Layer) {
+        assert!(
+            layer >= self.layer,
+            "Can't move back to layer {} (the current layer is {})",
+            layer,
+            self.layer
+        );
+
+        self.layer = layer;
+    }
+
+    /// Blocks until all the batches of changes belonging to `layer` (or to a preceding [`Layer`])
+    /// have been persisted.
+    ///
+    /// Returns immediately under [`WriteMode::Synchronous`].
+    /// Fails if the background thread has failed before persisting these batches (see [`FlushError`]).
+    pub fn flush_until(&self, layer: Layer) -> Result<(), FlushError> {
+        let mut queue = self.shared.queue();
+
+        while matches!(queue.batches.front(), Some(batch) if batch.layer <= layer) {
+            if let Some(err) = queue.failed.as_ref() {
+                return Err(err.clone());
+            }
+
+            queue = self
+                .shared
+                .flushed
+                .wait(queue)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+
+        Ok(())
+    }
+
+    /// Blocks until all the staged batches of changes have been persisted (see [`Self::flush_until`]).
+    pub fn flush_all(&self) -> Result<(), FlushError> {
+        self.flush_until(self.layer)
+    }
+
+    /// Persists all the staged batches (see [`Self::flush_all`]), and then runs `f` against the underlying
+    /// key-value store.
+    ///
+    /// Meant for reads [`RawKV`] doesn't offer (e.g scanning by key prefix), which can't observe the staged batches.
+    pub fn read_persisted<R, F>(&self, f: F) -> Result<R, FlushError>
+    where
+        F: FnOnce(&KV) -> R,
+    {
+        self.flush_all()?;
+
+        Ok(f(&self.shared.kv()))
+    }
+
+    /// Same as [`RawKV::set`], but fails (rejecting the batch) with a [`FlushError`] once the background thread
+    /// has failed, or when the batch couldn't be journaled.
+    pub fn try_set(&mut self, changes: &[(&[u8], &[u8])]) -> Result<(), FlushError> {
+        if self.mode == WriteMode::Synchronous {
+            self.shared.kv().set(changes);
+            return Ok(());
+        }
+
+        if let Some(err) = self.shared.queue().failed.as_ref() {
+            return Err(err.clone());
+        }
+
+        let batch = Batch {
+            layer: self.layer,
+            changes: changes
+                .iter()
+                .map(|(k, v)| (k.to_vec(), v.to_vec()))
+                .collect(),
+        };
+
+        let journal = self.shared.journal();
+
+        if let Some(mut file) = journal {
+            let mut record = Vec::new();
+            batch.encode(&mut record);
+
+            file.write_all(&record)
+                .and_then(|_| file.sync_data())
+                .map_err(|e| FlushError {
+                    msg: format!("failed journaling a batch: {}", e),
+                })?;
+
+            // Staging while still holding the journal keeps both in the same order
+            self.shared.queue().batches.push_back(Arc::new(batch));
+        } else {
+            self.shared.queue().batches.push_back(Arc::new(batch));
+        }
+
+        self.shared.staged.notify_one();
+
+        Ok(())
+    }
+
+    /// The number of staged batches of changes, not persisted yet.
+    pub fn pending_batches(&self) -> usize {
+        self.shared.queue().batches.len()
+    }
+
+    fn shutdown(&mut self) {
+        if let Some(flusher) = self.flusher.take() {
+            self.shared.queue().shutdown = true;
+            self.shared.staged.notify_all();
+
+            flusher
+                .join()
+                .expect("the storage flusher thread has panicked");
+        }
+    }
+}
+
+impl<KV: RawKV + Send + 'static> RawKV for WriteBehindKV<KV> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        // A batch is dequeued only after it has been persisted,
+        // so a key missing from the queue can be looked up under `kv`.
+        let staged = self
+            .shared
+            .queue()
+            .batches
+            .iter()
+            .rev()
+            .find_map(|batch| batch.get(key).map(|v| v.to_vec()));
+
+        staged.or_else(|| self.shared.kv().get(key))
+    }
+
+    /// # Panics
+    ///
+    /// Panics with the [`FlushError`] returned by [`WriteBehindKV::try_set`].
+    fn set(&mut self, changes: &[(&[u8], &[u8])]) {
+        if let Err(err) = self.try_set(changes) {
+            panic!("{}", err);
+        }
+    }
+}
+
+impl<KV: RawKV + Send + 'static> Drop for WriteBehindKV<KV> {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl<KV: RawKV> Shared<KV> {
+    fn queue(&self) -> MutexGuard<Queue> {
+        self.queue.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn kv(&self) -> MutexGuard<KV> {
+        self.kv.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn journal(&self) -> Option<MutexGuard<File>> {
+        self.journal
+            .as_ref()
+            .map(|journal| journal.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Persists the staged batches, until shutting down (with an empty queue).
+    ///
+    /// A failure is recorded (see [`FlushError`]) and wakes up the waiting [`WriteBehindKV::flush_until`]s.
+    fn run_flusher(&self) {
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| self.flush_loop())) {
+            let msg = if let Some(msg) = payload.downcast_ref::<&str>() {
+                msg.to_string()
+            } else if let Some(msg) = payload.downcast_ref::<String>() {
+                msg.clone()
+            } else {
+                "unknown panic".to_string()
+            };
+
+            self.queue().failed = Some(FlushError { msg });
+            self.flushed.notify_all();
+        }
+    }
+
+    fn flush_loop(&self) {
+        loop {
+            let batches = {
+                let mut queue = self.queue();
+
+                while queue.batches.is_empty() && !queue.shutdown {
+                    queue = self.staged.wait(queue).unwrap();
+                }
+
+                if queue.batches.is_empty() {
+                    return;
+                }
+
+                queue.batches.iter().cloned().collect::<Vec<_>>()
+            };
+
+            let changes = batches
+                .iter()
+                .flat_map(|batch| batch.changes.iter())
+                .map(|(k, v)| (k.as_slice(), v.as_slice()))
+                .collect::<Vec<_>>();
+
+            self.kv().set(&changes);
+
+            let journal = self.journal();
+            let mut queue = self.queue();
+
+            queue.batches.drain(..batches.len());
+
+            if let (Some(file), true) = (journal, queue.batches.is_empty()) {
+                // All the journaled batches are persisted
+                file.set_len(0)
+                    .and_then(|_| file.sync_all())
+                    .expect("failed emptying the storage journal");
+            }
+
+            drop(queue);
+            self.flushed.notify_all();
+        }
+    }
+}
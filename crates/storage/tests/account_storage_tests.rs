@@ -1,9 +1,16 @@
-use svm_layout::{FixedLayout, Id};
-use svm_storage::{account::AccountStorage, testing};
+use std::sync::Arc;
+
+use svm_layout::{DynamicLayout, FixedLayout, Id};
+use svm_storage::account::{
+    AccountKVStore, AccountStorage, BalanceOverflow, InsufficientBalance, PageCorrupted,
+    TransferError, VarTransform,
+};
+use svm_storage::kv::StatefulKV;
+use svm_storage::testing;
 use svm_types::Address;
 
 fn assert_var<const N: usize>(account: &AccountStorage, var_id: u32, expected: [u8; N]) {
-    let var = account.read_var(Id(var_id));
+    let var = account.read_var(Id(var_id)).unwrap();
     assert_eq!(&var[..], &expected[..]);
 }
 
@@ -41,7 +48,7 @@ fn account_storage_vars_are_persisted_only_on_commit() {
     assert_var(account2, 1, [0, 0]);
 
     // now, we'll commit the dirty changes
-    let _state = account.commit();
+    let _state = account.commit().unwrap();
 
     // we'll spin a new account with no caching
     let account3 = &mut AccountStorage::new(layout.clone(), kv.clone());
@@ -51,6 +58,89 @@ fn account_storage_vars_are_persisted_only_on_commit() {
     assert_var(account3, 1, [50, 60]);
 }
 
+/// XORs the bytes of `var #1` only (i.e the single "confidential" variable).
+struct XorTransform(u8);
+
+impl VarTransform for XorTransform {
+    fn encode(&self, var_id: Id, bytes: &mut [u8]) {
+        if var_id == Id(1) {
+            bytes.iter_mut().for_each(|b| *b ^= self.0);
+        }
+    }
+
+    fn decode(&self, var_id: Id, bytes: &mut [u8]) {
+        self.encode(var_id, bytes)
+    }
+}
+
+#[test]
+fn account_storage_vars_are_transformed_on_persistence() {
+    // `var #0` consumes 4 bytes (offsets: `[0..4)`)
+    // `var #1` consumes 2 bytes (offsets: `[4, 6)`)
+    let layout = FixedLayout::from(vec![4, 2].as_slice());
+
+    let addr = Address::of("@Account");
+    let kv = testing::create_account_kv(addr);
+
+    let transform = Arc::new(XorTransform(0xFF));
+    let account =
+        &mut AccountStorage::new(layout.clone(), kv.clone()).with_transform(transform.clone());
+
+    write_var(account, 0, [10, 20, 30, 40]);
+    write_var(account, 1, [50, 60]);
+
+    let _state = account.commit().unwrap();
+
+    // the running `Account` keeps seeing plain data
+    assert_var(account, 0, [10, 20, 30, 40]);
+    assert_var(account, 1, [50, 60]);
+
+    // only `var #1` is persisted transformed
+    let raw = &mut AccountStorage::new(layout.clone(), kv.clone());
+    assert_var(raw, 0, [10, 20, 30, 40]);
+    assert_var(raw, 1, [50 ^ 0xFF, 60 ^ 0xFF]);
+
+    // spinning a new account using the same transform reads back the plain data
+    let account2 = &mut AccountStorage::new(layout.clone(), kv.clone()).with_transform(transform);
+    assert_var(account2, 0, [10, 20, 30, 40]);
+    assert_var(account2, 1, [50, 60]);
+}
+
+#[test]
+fn account_storage_dynamic_entries_are_transformed_on_persistence() {
+    // `var #1` is a dynamic one (and the single transformed variable)
+    let layout = FixedLayout::from(vec![4, 4].as_slice());
+    let dynamic = DynamicLayout::from(vec![1].as_slice());
+
+    let addr = Address::of("@Account");
+    let kv = testing::create_account_kv(addr);
+
+    let transform = Arc::new(XorTransform(0xFF));
+    let account = &mut AccountStorage::new(layout.clone(), kv.clone())
+        .with_dynamic_layout(dynamic.clone())
+        .with_transform(transform.clone());
+
+    // the uncommitted entries are read back plain
+    account.write_dyn(Id(1), b"alice", vec![10, 20]);
+    assert_eq!(account.read_dyn(Id(1), b"alice"), Some(vec![10, 20]));
+
+    let _state = account.commit().unwrap();
+    assert_eq!(account.read_dyn(Id(1), b"alice"), Some(vec![10, 20]));
+
+    // the entries are persisted transformed
+    let raw =
+        &mut AccountStorage::new(layout.clone(), kv.clone()).with_dynamic_layout(dynamic.clone());
+    assert_eq!(
+        raw.read_dyn(Id(1), b"alice"),
+        Some(vec![10 ^ 0xFF, 20 ^ 0xFF])
+    );
+
+    let account2 = &mut AccountStorage::new(layout, kv)
+        .with_dynamic_layout(dynamic)
+        .with_transform(transform);
+    assert_eq!(account2.read_dyn(Id(1), b"alice"), Some(vec![10, 20]));
+}
+
 #[test]
 #[cfg(debug_assertions)]
 #[should_panic]
@@ -65,3 +155,319 @@ fn account_storage_write_var_value_should_match_layout_length() {
     // calling `write_var` with 2-byte value (expected variable's to value to be 4 bytes)
     account.write_var(Id(0), vec![0, 0]);
 }
+
+#[test]
+fn account_storage_packed_bools_share_a_byte() {
+    // `var #0` consumes 4 bytes (offsets: `[0..4)`)
+    // `var #1`, `var #2` and `var #3` are packed into bits `0, 1, 2` of offset `4`
+    let layout = FixedLayout::from(vec![4, 0, 0, 0].as_slice());
+
+    let addr = Address::of("@Account");
+    let kv = testing::create_account_kv(addr);
+
+    let account = &mut AccountStorage::new(layout.clone(), kv.clone());
+
+    assert!(!account.read_bit(Id(1)).unwrap());
+    assert_eq!(account.var_layout(Id(2)), (4, 1));
+
+    account.write_bit(Id(1), true);
+    write_var(account, 3, [1]);
+
+    assert!(account.read_bit(Id(1)).unwrap());
+    assert!(!account.read_bit(Id(2)).unwrap());
+    assert_var(account, 3, [1]);
+
+    let _state = account.commit().unwrap();
+
+    // the packed booleans are persisted as a single byte
+    let raw = &mut AccountStorage::new(FixedLayout::from(vec![4, 1].as_slice()), kv.clone());
+    assert_var(raw, 1, [0b101]);
+
+    // clearing a bit leaves the others as they are
+    let account2 = &mut AccountStorage::new(layout.clone(), kv.clone());
+    account2.write_bit(Id(1), false);
+    account2.write_bit(Id(2), true);
+
+    let _state = account2.commit().unwrap();
+
+    let raw = &mut AccountStorage::new(FixedLayout::from(vec![4, 1].as_slice()), kv.clone());
+    assert_var(raw, 1, [0b110]);
+
+    let account3 = &mut AccountStorage::new(layout, kv);
+    assert_var(account3, 1, [0]);
+    assert_var(account3, 2, [1]);
+    assert_var(account3, 3, [1]);
+}
+
+#[test]
+fn account_storage_transfer_balances() {
+    let layout = FixedLayout::from(vec![4].as_slice());
+    let kv = testing::create_kv();
+
+    let src = Address::of("@Source");
+    let dst = Address::of("@Destination");
+
+    let account = &mut AccountStorage::new(layout.clone(), AccountKVStore::new(src.clone(), &kv));
+
+    // balances default to zero
+    assert_eq!(account.balance(), 0);
+    assert_eq!(account.balance_of(&dst), 0);
+
+    account.set_balance(100);
+    account.transfer(&dst, 30).unwrap();
+
+    assert_eq!(account.balance(), 70);
+    assert_eq!(account.balance_of(&dst), 30);
+
+    // transferring more than the balance fails, leaving the balances intact
+    let err = account.transfer(&dst, 71).unwrap_err();
+    assert_eq!(
+        err,
+        TransferError::InsufficientBalance(InsufficientBalance {
+            account: src.clone(),
+            balance: 70,
+            amount: 71
+        })
+    );
+    assert_eq!(account.balance(), 70);
+    assert_eq!(account.balance_of(&dst), 30);
+
+    // the balances aren't persisted before `commit`
+    let dst_account =
+        &mut AccountStorage::new(layout.clone(), AccountKVStore::new(dst.clone(), &kv));
+    assert_eq!(dst_account.balance(), 0);
+
+    let _state = account.commit().unwrap();
+
+    let src_account = &mut AccountStorage::new(layout.clone(), AccountKVStore::new(src, &kv));
+    let dst_account = &mut AccountStorage::new(layout, AccountKVStore::new(dst, &kv));
+
+    assert_eq!(src_account.balance(), 70);
+    assert_eq!(dst_account.balance(), 30);
+}
+
+#[test]
+fn account_storage_transfer_overflow() {
+    let layout = FixedLayout::from(vec![4].as_slice());
+    let kv = testing::create_kv();
+
+    let src = Address::of("@Source");
+    let dst = Address::of("@Destination");
+
+    let dst_account =
+        &mut AccountStorage::new(layout.clone(), AccountKVStore::new(dst.clone(), &kv));
+    dst_account.set_balance(u64::MAX - 10);
+    let _state = dst_account.commit().unwrap();
+
+    let account = &mut AccountStorage::new(layout, AccountKVStore::new(src.clone(), &kv));
+    account.set_balance(100);
+
+    // crediting `dst` beyond `u64::MAX` fails, leaving the balances intact
+    let err = account.transfer(&dst, 11).unwrap_err();
+    assert_eq!(
+        err,
+        TransferError::BalanceOverflow(BalanceOverflow {
+            account: dst.clone(),
+            balance: u64::MAX - 10,
+            amount: 11
+        })
+    );
+    assert_eq!(account.balance(), 100);
+    assert_eq!(account.balance_of(&dst), u64::MAX - 10);
+
+    account.transfer(&dst, 10).unwrap();
+    assert_eq!(account.balance(), 90);
+    assert_eq!(account.balance_of(&dst), u64::MAX);
+}
+
+#[test]
+fn account_storage_dynamic_entries() {
+    // `var #0` is a fixed one, `var #1` holds the number of entries of the dynamic variable `#1`
+    let layout = FixedLayout::from(vec![4, 4].as_slice());
+    let dynamic = DynamicLayout::from(vec![1].as_slice());
+
+    let addr = Address::of("@Account");
+    let kv = testing::create_account_kv(addr);
+
+    let account =
+        &mut AccountStorage::new(layout.clone(), kv.clone()).with_dynamic_layout(dynamic.clone());
+
+    // missing entries are read as `None`
+    assert_eq!(account.read_dyn(Id(1), b"alice"), None);
+
+    account.write_dyn(Id(1), b"alice", vec![10, 20]);
+    account.write_dyn(Id(1), b"bob", vec![30]);
+    write_var(account, 1, [2, 0, 0, 0]);
+
+    assert_eq!(account.read_dyn(Id(1), b"alice"), Some(vec![10, 20]));
+    assert_eq!(account.read_dyn(Id(1), b"bob"), Some(vec![30]));
+
+    // the entries aren't persisted before `commit`
+    let account2 =
+        &mut AccountStorage::new(layout.clone(), kv.clone()).with_dynamic_layout(dynamic.clone());
+    assert_eq!(account2.read_dyn(Id(1), b"alice"), None);
+
+    let _state = account.commit().unwrap();
+
+    let account3 =
+        &mut AccountStorage::new(layout.clone(), kv.clone()).with_dynamic_layout(dynamic.clone());
+
+    assert_eq!(account3.read_dyn(Id(1), b"alice"), Some(vec![10, 20]));
+    assert_eq!(account3.read_dyn(Id(1), b"bob"), Some(vec![30]));
+    assert_var(account3, 0, [0, 0, 0, 0]);
+    assert_var(account3, 1, [2, 0, 0, 0]);
+
+    // an empty value removes an entry
+    account3.write_dyn(Id(1), b"alice", Vec::new());
+    assert_eq!(account3.read_dyn(Id(1), b"alice"), None);
+
+    let _state = account3.commit().unwrap();
+
+    let account4 = &mut AccountStorage::new(layout, kv).with_dynamic_layout(dynamic);
+
+    assert_eq!(account4.read_dyn(Id(1), b"alice"), None);
+    assert_eq!(account4.read_dyn(Id(1), b"bob"), Some(vec![30]));
+}
+
+#[test]
+fn account_storage_dynamic_digest() {
+    let layout = FixedLayout::from(vec![4].as_slice());
+    let dynamic = DynamicLayout::from(vec![0].as_slice());
+
+    let open = |name: &str| {
+        let kv = testing::create_account_kv(Address::of(name));
+
+        AccountStorage::new(layout.clone(), kv).with_dynamic_layout(dynamic.clone())
+    };
+
+    // no entries, no digest
+    let account = &mut open("@Account");
+    assert_eq!(account.dynamic_digest(), [0; 32]);
+
+    // the digest is updated upon `commit`
+    account.write_dyn(Id(0), b"alice", vec![10, 20]);
+    account.write_dyn(Id(0), b"bob", vec![30]);
+    assert_eq!(account.dynamic_digest(), [0; 32]);
+
+    let _state = account.commit().unwrap();
+    let digest = account.dynamic_digest();
+    assert_ne!(digest, [0; 32]);
+
+    // it commits to the entries only (regardless of the order they've been written in)
+    let reordered = &mut open("@Reordered");
+    reordered.write_dyn(Id(0), b"bob", vec![30]);
+    let _state = reordered.commit().unwrap();
+    assert_ne!(reordered.dynamic_digest(), digest);
+
+    reordered.write_dyn(Id(0), b"alice", vec![10, 20]);
+    let _state = reordered.commit().unwrap();
+    assert_eq!(reordered.dynamic_digest(), digest);
+
+    let single = &mut open("@Single");
+    single.write_dyn(Id(0), b"bob", vec![30]);
+    let _state = single.commit().unwrap();
+    let single_digest = single.dynamic_digest();
+
+    // modifying an entry changes the digest, and restoring it restores the digest
+    account.write_dyn(Id(0), b"alice", vec![11]);
+    let _state = account.commit().unwrap();
+    assert_ne!(account.dynamic_digest(), digest);
+
+    account.write_dyn(Id(0), b"alice", vec![10, 20]);
+    let _state = account.commit().unwrap();
+    assert_eq!(account.dynamic_digest(), digest);
+
+    // removing an entry takes it out of the digest
+    account.write_dyn(Id(0), b"alice", Vec::new());
+    let _state = account.commit().unwrap();
+    assert_eq!(account.dynamic_digest(), single_digest);
+}
+
+#[test]
+#[should_panic]
+fn account_storage_dynamic_entries_of_fixed_var_panics() {
+    let layout = FixedLayout::from(vec![4].as_slice());
+    let kv = testing::create_account_kv(Address::of("@Account"));
+
+    let account = &mut AccountStorage::new(layout, kv);
+
+    account.write_dyn(Id(0), b"key", vec![1]);
+}
+
+#[test]
+fn account_storage_legacy_pages_migration() {
+    // `var #0` consumes 4 bytes (offsets: `[0..4)`), persisted under the page of key `3`
+    let layout = FixedLayout::from(vec![4, 2].as_slice());
+
+    let addr = Address::of("@Account");
+    let mut kv = testing::create_account_kv(addr);
+
+    // a page persisted prior to the checksummed page format (i.e the raw data only)
+    let mut legacy = vec![0; 32];
+    legacy[..4].copy_from_slice(&[10, 20, 30, 40]);
+    kv.set(&3u32.to_be_bytes(), &legacy);
+    let _state = kv.checkpoint();
+    kv.flush();
+
+    let account = &mut AccountStorage::new(layout.clone(), kv.clone());
+    assert_var(account, 0, [10, 20, 30, 40]);
+
+    // only the persisted (legacy) pages are migrated, once committed
+    assert_eq!(account.migrate_pages(), Ok(1));
+    assert_eq!(kv.get(&3u32.to_be_bytes()).unwrap(), legacy);
+
+    let _state = account.commit().unwrap();
+    assert_eq!(account.migrate_pages(), Ok(0));
+
+    let page = kv.get(&3u32.to_be_bytes()).unwrap();
+    assert_eq!(&page[..3], b"SP\x01");
+    assert_eq!(&page[7..], &legacy[..]);
+
+    let account2 = &mut AccountStorage::new(layout, kv);
+    assert_var(account2, 0, [10, 20, 30, 40]);
+    assert_var(account2, 1, [0, 0]);
+}
+
+#[test]
+fn account_storage_corrupted_page() {
+    let layout = FixedLayout::from(vec![4, 2].as_slice());
+
+    let addr = Address::of("@Account");
+    let mut kv = testing::create_account_kv(addr.clone());
+
+    let account = &mut AccountStorage::new(layout.clone(), kv.clone());
+    write_var(account, 0, [10, 20, 30, 40]);
+    let _state = account.commit().unwrap();
+
+    // flipping a bit of `var #0`
+    let mut page = kv.get(&3u32.to_be_bytes()).unwrap();
+    page[7] ^= 0x01;
+    kv.set(&3u32.to_be_bytes(), &page);
+    let _state = kv.checkpoint();
+    kv.flush();
+
+    let account2 = &mut AccountStorage::new(layout.clone(), kv.clone());
+
+    // the other pages are still readable
+    assert_var(account2, 1, [0, 0]);
+
+    let err = PageCorrupted {
+        account: addr,
+        key: 3,
+        reason: "checksum mismatch".to_string(),
+    };
+
+    assert_eq!(account2.read_var(Id(0)), Err(err.clone()));
+
+    // a `commit` touching the corrupted page fails, leaving the storage intact
+    write_var(account2, 0, [50, 60, 70, 80]);
+    write_var(account2, 1, [50, 60]);
+    account2.set_balance(100);
+
+    assert_eq!(account2.commit(), Err(err));
+    assert_eq!(kv.get(&3u32.to_be_bytes()).unwrap(), page);
+
+    let account3 = &mut AccountStorage::new(layout, kv);
+    assert_var(account3, 1, [0, 0]);
+    assert_eq!(account3.balance(), 0);
+}
@@ -0,0 +1,331 @@
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::path::PathBuf;
+use std::sync::{Arc, Condvar, Mutex};
+
+use svm_kv::traits::RawKV;
+use svm_storage::kv::{FlushError, WriteBehindKV, WriteMode};
+use svm_types::Layer;
+
+/// A [`RawKV`] recording its writes, which can be held from persisting them.
+#[derive(Clone, Default)]
+struct GatedKV {
+    data: Arc<Mutex<HashMap<Vec<u8>, Vec<u8>>>>,
+
+    writes: Arc<Mutex<usize>>,
+
+    gate: Arc<(Mutex<bool>, Condvar)>,
+}
+
+#[allow(clippy::mutex_atomic)]
+impl GatedKV {
+    fn hold(&self) {
+        *self.gate.0.lock().unwrap() = true;
+    }
+
+    fn release(&self) {
+        *self.gate.0.lock().unwrap() = false;
+        self.gate.1.notify_all();
+    }
+
+    fn persisted(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.data.lock().unwrap().get(key).cloned()
+    }
+
+    fn writes(&self) -> usize {
+        *self.writes.lock().unwrap()
+    }
+}
+
+impl RawKV for GatedKV {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.persisted(key)
+    }
+
+    fn set(&mut self, changes: &[(&[u8], &[u8])]) {
+        let (lock, cvar) = &*self.gate;
+        let mut held = lock.lock().unwrap();
+
+        while *held {
+            held = cvar.wait(held).unwrap();
+        }
+
+        let mut data = self.data.lock().unwrap();
+
+        for (k, v) in changes {
+            data.insert(k.to_vec(), v.to_vec());
+        }
+
+        *self.writes.lock().unwrap() += 1;
+    }
+}
+
+fn set(kv: &mut WriteBehindKV<GatedKV>, changes: &[(&str, &str)]) {
+    let changes = changes
+        .iter()
+        .map(|(k, v)| (k.as_bytes(), v.as_bytes()))
+        .collect::<Vec<_>>();
+
+    kv.set(&changes);
+}
+
+#[test]
+fn write_behind_kv_synchronous() {
+    let raw = GatedKV::default();
+    let mut kv = WriteBehindKV::new(raw.clone(), WriteMode::Synchronous);
+
+    set(&mut kv, &[("key", "value")]);
+
+    assert_eq!(raw.persisted(b"key"), Some(b"value".to_vec()));
+    assert_eq!(kv.pending_batches(), 0);
+}
+
+#[test]
+fn write_behind_kv_reads_staged_changes() {
+    let raw = GatedKV::default();
+    let mut kv = WriteBehindKV::new(raw.clone(), WriteMode::WriteBehind);
+
+    raw.hold();
+
+    kv.set_layer(Layer(1));
+    set(&mut kv, &[("key1", "value1"), ("key2", "value2")]);
+
+    kv.set_layer(Layer(2));
+    set(&mut kv, &[("key1", "value1'")]);
+
+    // the changes are staged (and not persisted)
+    assert_eq!(kv.get(b"key1"), Some(b"value1'".to_vec()));
+    assert_eq!(kv.get(b"key2"), Some(b"value2".to_vec()));
+    assert_eq!(kv.get(b"key3"), None);
+    assert_eq!(raw.persisted(b"key1"), None);
+
+    raw.release();
+    kv.flush_until(Layer(2)).unwrap();
+
+    assert_eq!(kv.pending_batches(), 0);
+    assert_eq!(raw.persisted(b"key1"), Some(b"value1'".to_vec()));
+    assert_eq!(raw.persisted(b"key2"), Some(b"value2".to_vec()));
+    assert_eq!(kv.get(b"key1"), Some(b"value1'".to_vec()));
+}
+
+#[test]
+fn write_behind_kv_flush_until_layer() {
+    let raw = GatedKV::default();
+    let mut kv = WriteBehindKV::new(raw.clone(), WriteMode::WriteBehind);
+
+    kv.set_layer(Layer(1));
+    set(&mut kv, &[("key1", "value1")]);
+    kv.flush_until(Layer(1)).unwrap();
+
+    assert_eq!(raw.persisted(b"key1"), Some(b"value1".to_vec()));
+
+    raw.hold();
+
+    kv.set_layer(Layer(2));
+    set(&mut kv, &[("key2", "value2")]);
+
+    // there's nothing left to persist for `Layer #1`
+    kv.flush_until(Layer(1)).unwrap();
+    assert_eq!(raw.persisted(b"key2"), None);
+
+    raw.release();
+    kv.flush_until(Layer(2)).unwrap();
+
+    assert_eq!(raw.persisted(b"key2"), Some(b"value2".to_vec()));
+}
+
+#[test]
+fn write_behind_kv_read_persisted() {
+    let raw = GatedKV::default();
+    let mut kv = WriteBehindKV::new(raw, WriteMode::WriteBehind);
+
+    set(&mut kv, &[("key", "value")]);
+
+    let value = kv.read_persisted(|raw| raw.persisted(b"key")).unwrap();
+    assert_eq!(value, Some(b"value".to_vec()));
+    assert_eq!(kv.pending_batches(), 0);
+}
+
+#[test]
+fn write_behind_kv_persists_on_drop() {
+    let raw = GatedKV::default();
+    let mut kv = WriteBehindKV::new(raw.clone(), WriteMode::WriteBehind);
+
+    raw.hold();
+
+    for i in 0..10u8 {
+        kv.set(&[(&[i][..], &[i][..])]);
+    }
+
+    raw.release();
+    drop(kv);
+
+    for i in 0..10u8 {
+        assert_eq!(raw.persisted(&[i]), Some(vec![i]));
+    }
+
+    // the batches staged while being held are persisted together
+    assert!(raw.writes() < 10);
+}
+
+/// A [`RawKV`] failing all its writes.
+struct FailingKV;
+
+impl RawKV for FailingKV {
+    fn get(&self, _key: &[u8]) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn set(&mut self, _changes: &[(&[u8], &[u8])]) {
+        panic!("disk is full");
+    }
+}
+
+#[test]
+fn write_behind_kv_flush_until_fails_with_the_flusher() {
+    let mut kv = WriteBehindKV::new(FailingKV, WriteMode::WriteBehind);
+
+    kv.set_layer(Layer(1));
+    kv.set(&[(b"key", b"value")]);
+
+    let err = FlushError {
+        msg: "disk is full".to_string(),
+    };
+    assert_eq!(kv.flush_until(Layer(1)), Err(err.clone()));
+
+    // the staged batches are kept (and still readable), but never persisted
+    assert_eq!(kv.pending_batches(), 1);
+    assert_eq!(kv.get(b"key"), Some(b"value".to_vec()));
+    assert_eq!(kv.flush_all(), Err(err));
+}
+
+#[test]
+#[should_panic]
+fn write_behind_kv_layer_must_not_go_backwards() {
+    let mut kv = WriteBehindKV::new(GatedKV::default(), WriteMode::WriteBehind);
+
+    kv.set_layer(Layer(2));
+    kv.set_layer(Layer(1));
+}
+
+#[test]
+fn write_behind_kv_rejects_batches_once_the_flusher_has_failed() {
+    let mut kv = WriteBehindKV::new(FailingKV, WriteMode::WriteBehind);
+
+    kv.set(&[(b"key", b"value")]);
+
+    let err = FlushError {
+        msg: "disk is full".to_string(),
+    };
+    assert_eq!(kv.flush_all(), Err(err.clone()));
+
+    assert_eq!(kv.try_set(&[(b"key2", b"value2")]), Err(err));
+    assert_eq!(kv.pending_batches(), 1);
+    assert_eq!(kv.get(b"key2"), None);
+}
+
+#[test]
+#[should_panic(expected = "The storage flusher has failed: disk is full")]
+fn write_behind_kv_set_panics_once_the_flusher_has_failed() {
+    let mut kv = WriteBehindKV::new(FailingKV, WriteMode::WriteBehind);
+
+    kv.set(&[(b"key", b"value")]);
+    let _ = kv.flush_all();
+
+    kv.set(&[(b"key2", b"value2")]);
+}
+
+fn journal_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "svm-write-behind-{}-{}.journal",
+        name,
+        std::process::id()
+    ));
+
+    let _ = std::fs::remove_file(&path);
+
+    path
+}
+
+#[test]
+fn write_behind_kv_journal_replays_staged_batches() {
+    let path = journal_path("replay");
+
+    let raw = GatedKV::default();
+    let mut kv = WriteBehindKV::with_journal(raw.clone(), WriteMode::WriteBehind, &path).unwrap();
+
+    raw.hold();
+
+    kv.set_layer(Layer(1));
+    set(&mut kv, &[("key1", "value1"), ("key2", "value2")]);
+
+    kv.set_layer(Layer(2));
+    set(&mut kv, &[("key1", "value1'")]);
+
+    // simulating a crash: the staged batches are never persisted into `raw`
+    std::mem::forget(kv);
+    assert_eq!(raw.persisted(b"key1"), None);
+
+    let recovered = GatedKV::default();
+    let kv = WriteBehindKV::with_journal(recovered.clone(), WriteMode::WriteBehind, &path).unwrap();
+
+    assert_eq!(recovered.persisted(b"key1"), Some(b"value1'".to_vec()));
+    assert_eq!(recovered.persisted(b"key2"), Some(b"value2".to_vec()));
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), 0);
+
+    drop(kv);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn write_behind_kv_journal_ignores_a_truncated_batch() {
+    let path = journal_path("truncated");
+
+    {
+        let raw = GatedKV::default();
+        let mut kv =
+            WriteBehindKV::with_journal(raw.clone(), WriteMode::WriteBehind, &path).unwrap();
+
+        raw.hold();
+        set(&mut kv, &[("key1", "value1")]);
+        set(&mut kv, &[("key2", "value2")]);
+
+        std::mem::forget(kv);
+    }
+
+    // simulating a crash while appending the second batch
+    let len = std::fs::metadata(&path).unwrap().len();
+    OpenOptions::new()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_len(len - 1)
+        .unwrap();
+
+    let recovered = GatedKV::default();
+    let _kv =
+        WriteBehindKV::with_journal(recovered.clone(), WriteMode::WriteBehind, &path).unwrap();
+
+    assert_eq!(recovered.persisted(b"key1"), Some(b"value1".to_vec()));
+    assert_eq!(recovered.persisted(b"key2"), None);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn write_behind_kv_journal_is_emptied_once_flushed() {
+    let path = journal_path("flushed");
+
+    let raw = GatedKV::default();
+    let mut kv = WriteBehindKV::with_journal(raw.clone(), WriteMode::WriteBehind, &path).unwrap();
+
+    kv.set_layer(Layer(1));
+    set(&mut kv, &[("key", "value")]);
+    kv.flush_until(Layer(1)).unwrap();
+
+    assert_eq!(raw.persisted(b"key"), Some(b"value".to_vec()));
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), 0);
+
+    drop(kv);
+    std::fs::remove_file(&path).unwrap();
+}
@@ -38,4 +38,20 @@ pub enum RuntimeError {
         template: TemplateAddr,
         func: String,
     },
+    UpgradeNotAllowed {
+        template: TemplateAddr,
+        msg: String,
+    },
+    InternalError {
+        msg: String,
+    },
+    InsufficientBalance {
+        target: Address,
+        balance: u64,
+        amount: u64,
+    },
+    StorageCorrupted {
+        target: Address,
+        msg: String,
+    },
 }
@@ -0,0 +1,41 @@
+/// An upfront estimate of the gas a transaction is going to use, computed by pricing it
+/// without executing anything.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GasEstimate {
+    /// The price of the transaction's payload (its intrinsic gas).
+    pub payload_price: u64,
+
+    /// The price of loading the executed `Template`s (by their byte size).
+    pub template_load_price: u64,
+
+    /// An upper bound of the price of running the executed functions (e.g the `ctor` of a `spawn`).
+    ///
+    /// It's missing when the code can't be priced upfront (e.g a `Template` running in [`GasMode::Metering`](crate::GasMode::Metering)).
+    pub func_price: Option<u64>,
+
+    /// The recommended `gas_limit` of the transaction.
+    pub gas_limit: u64,
+}
+
+impl GasEstimate {
+    /// New [`GasEstimate`], recommending a `gas_limit` of the sum of the given prices.
+    ///
+    /// When `func_price` is missing, the running of the functions is assumed to use `exec_allowance`.
+    pub fn new(
+        payload_price: u64,
+        template_load_price: u64,
+        func_price: Option<u64>,
+        exec_allowance: u64,
+    ) -> Self {
+        let gas_limit = payload_price
+            .saturating_add(template_load_price)
+            .saturating_add(func_price.unwrap_or(exec_allowance));
+
+        Self {
+            payload_price,
+            template_load_price,
+            func_price,
+            gas_limit,
+        }
+    }
+}
@@ -2,8 +2,10 @@ use std::cmp::{Ordering, PartialEq, PartialOrd};
 use std::ops::{Add, AddAssign, Sub};
 
 mod error;
+mod estimate;
 
 pub use error::OOGError;
+pub use estimate::GasEstimate;
 
 /// The method for deriving the `Gas Units` of a transaction
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -11,7 +13,7 @@ pub enum GasMode {
     /// Fixed-Gas - Determined by using static-analysis prior to execution
     Fixed,
 
-    /// Fixed-Gas - Determined as part of transaction execution
+    /// Metered-Gas - Determined as part of transaction execution
     Metering,
 }
 /// `Gas` is essentially an `Option<u64>` with extensions
@@ -19,34 +19,43 @@ mod spawn_account;
 mod state;
 mod template;
 mod transaction;
+mod upgrade_template;
 mod wasm_type;
 mod wasm_value;
 
+/// Constant-time comparisons and zeroization of sensitive bytes
+#[cfg(feature = "sensitive")]
+pub mod sensitive;
+
 /// Type for failed running transactions
 pub use error::RuntimeError;
 
 /// Gas-related types
 mod gas;
-pub use gas::{Gas, GasMode, OOGError};
+pub use gas::{Gas, GasEstimate, GasMode, OOGError};
 
 /// `Receipt`-related types
 mod receipt;
 
 pub use receipt::{
-    into_spawn_receipt, CallReceipt, DeployReceipt, Receipt, ReceiptLog, ReceiptRef, SpawnReceipt,
+    into_spawn_receipt, BatchReceipt, CallReceipt, DeployReceipt, GasBreakdown, LogTopic,
+    LogsBloom, Receipt, ReceiptLog, ReceiptMetric, ReceiptRef, SpawnReceipt, UpgradeReceipt,
+    EVENT_LOGS_VERSION, MAX_LOG_TOPICS, MAX_RECEIPT_METRICS,
 };
 
 /// `Addressable` types
 pub use address::{Address, TemplateAddr};
 
 pub use account::Account;
-pub use spawn_account::SpawnAccount;
+pub use spawn_account::{SpawnAccount, SpawnCall};
 pub use state::State;
 pub use template::{
-    ApiSection, CodeKind, CodeSection, CtorsSection, DataSection, DeploySection, HeaderSection,
-    SchemaSection, Section, SectionKind, SectionLike, Sections, SectionsIter, Template,
+    ApiFunc, ApiParam, ApiSection, ApiType, ApiVarDoc, CodeKind, CodeSection, CtorsSection,
+    CustomSection, DataSection, DefaultsSection, DeploySection, HeaderSection, SchemaSection,
+    Section, SectionKind, SectionLike, Sections, SectionsIter, Template,
 };
-pub use transaction::{Context, Envelope, Layer, Transaction, TransactionId};
+pub use transaction::{BatchTransaction, Context, Envelope, Layer, Transaction, TransactionId};
+pub use upgrade_template::UpgradeTemplate;
 pub use wasm_type::{WasmType, WasmTypeError};
 pub use wasm_value::WasmValue;
 
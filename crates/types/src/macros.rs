@@ -1,12 +1,28 @@
 /// `impl_bytes_primitive` macro implements a struct consisting of one array of bytes.
+///
+/// When the `sensitive` feature is enabled, instances are compared in constant time
+/// (see [`ct_eq`](crate::sensitive::ct_eq)).
 #[macro_export]
 macro_rules! impl_bytes_primitive {
     ($primitive: ident, $byte_count: expr) => {
         /// `$primitive` consists of `$byte_count` bytes.
-        #[derive(Debug, Clone, Hash, PartialEq, Eq)]
+        #[derive(Debug, Clone, Hash)]
+        #[cfg_attr(not(feature = "sensitive"), derive(PartialEq, Eq))]
+        #[cfg_attr(feature = "sensitive", allow(clippy::derive_hash_xor_eq))]
         #[repr(transparent)]
         pub struct $primitive(pub(self) [u8; $byte_count]);
 
+        #[cfg(feature = "sensitive")]
+        impl PartialEq for $primitive {
+            #[inline]
+            fn eq(&self, other: &$primitive) -> bool {
+                $crate::sensitive::ct_eq(&self.0, &other.0)
+            }
+        }
+
+        #[cfg(feature = "sensitive")]
+        impl Eq for $primitive {}
+
         impl From<[u8; $byte_count]> for $primitive {
             fn from(data: [u8; $byte_count]) -> $primitive {
                 $primitive(data)
@@ -0,0 +1,146 @@
+use crate::gas::Gas;
+use crate::receipt::{log, CallReceipt, GasBreakdown, LogsBloom, ReceiptLog, RuntimeError};
+use crate::State;
+
+/// The receipt of a `Call Batch` transaction (see [`BatchTransaction`](crate::BatchTransaction)).
+///
+/// The batched calls are all-or-nothing: when any of them fails, the changes of the preceding
+/// calls are rolled back as well, and only the error (and the logs emitted so far) is kept.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BatchReceipt {
+    /// Transaction format version.
+    pub version: u16,
+
+    /// Whether all the batched calls succeeded.
+    pub success: bool,
+
+    /// The error of the failed call (for a failed batch).
+    pub error: Option<RuntimeError>,
+
+    /// The global `State` following the last call (if the batch succeeded).
+    pub new_state: Option<State>,
+
+    /// The receipts of the batched calls (if the batch succeeded).
+    pub receipts: Vec<CallReceipt>,
+
+    /// The amount of gas used by the whole batch.
+    pub gas_used: Gas,
+
+    /// The `gas_used` broken down by phase (see [`GasBreakdown`]).
+    pub gas_breakdown: Option<GasBreakdown>,
+
+    /// The version of the gas schedule the transaction has been priced by.
+    pub gas_schedule_version: u16,
+
+    /// The logs generated by the executed calls (in execution order).
+    pub logs: Vec<ReceiptLog>,
+}
+
+impl BatchReceipt {
+    /// Creates a successful [`BatchReceipt`] out of the `receipts` of the batched calls.
+    ///
+    /// The `new_state` and `logs` are taken from the `receipts`, and `gas_used`
+    /// covers the batch as a whole.
+    pub fn new(receipts: Vec<CallReceipt>, gas_used: Gas) -> Self {
+        let new_state = receipts.last().and_then(|r| r.new_state.clone());
+        let logs: Vec<ReceiptLog> = receipts.iter().flat_map(|r| r.logs.clone()).collect();
+
+        Self {
+            version: log::logs_version(&logs),
+            success: true,
+            error: None,
+            new_state,
+            receipts,
+            gas_used,
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs,
+        }
+    }
+
+    /// Creates a [`BatchReceipt`] for reaching `Out-of-Gas`.
+    pub fn new_oog(logs: Vec<ReceiptLog>) -> Self {
+        Self::from_err(RuntimeError::OOG, logs)
+    }
+
+    /// Creates a new failure [`BatchReceipt`] out of the `err` parameter.
+    pub fn from_err(err: RuntimeError, logs: Vec<ReceiptLog>) -> Self {
+        Self {
+            version: log::logs_version(&logs),
+            success: false,
+            error: Some(err),
+            new_state: None,
+            receipts: Vec::new(),
+            gas_used: Gas::new(),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs,
+        }
+    }
+
+    /// Returns the global `State` following the last call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the batch has failed.
+    pub fn new_state(&self) -> &State {
+        self.new_state.as_ref().unwrap()
+    }
+
+    /// Returns the error within the Receipt (for failing Receipts)
+    pub fn error(&self) -> &RuntimeError {
+        self.error.as_ref().unwrap()
+    }
+
+    /// Returns the logs generated during the transaction execution
+    pub fn logs(&self) -> &[ReceiptLog] {
+        &self.logs
+    }
+
+    /// Raises the `version` to [`EVENT_LOGS_VERSION`](crate::EVENT_LOGS_VERSION)
+    /// when any of the logs is an event (see [`ReceiptLog::event`]).
+    pub fn set_logs_version(&mut self) {
+        self.version = self.version.max(log::logs_version(&self.logs));
+    }
+
+    /// Attaches the breakdown of the `gas_used` by phase.
+    ///
+    /// Receipts carrying a [`GasBreakdown`] are encoded using the binary format version `1` (at least).
+    pub fn set_gas_breakdown(&mut self, breakdown: GasBreakdown) {
+        self.version = self.version.max(1);
+        self.gas_breakdown = Some(breakdown);
+    }
+
+    /// Returns the [`LogsBloom`] of the batched calls, aggregated out of their receipts.
+    ///
+    /// Returns `None` for a failed batch, or when any of the calls has logs but no recorded filter.
+    pub fn logs_bloom(&self) -> Option<LogsBloom> {
+        if !self.success {
+            return None;
+        }
+
+        let mut bloom = LogsBloom::new();
+
+        for receipt in self.receipts.iter() {
+            match receipt.logs_bloom.as_ref() {
+                Some(call_bloom) => bloom |= call_bloom,
+                None if receipt.logs.is_empty() => (),
+                None => return None,
+            }
+        }
+
+        Some(bloom)
+    }
+
+    /// Records the version of the gas schedule the transaction has been priced by.
+    ///
+    /// Receipts of a gas schedule other than the initial one (version `0`)
+    /// are encoded using the binary format version `2`.
+    pub fn set_gas_schedule_version(&mut self, version: u16) {
+        if version > 0 {
+            self.version = self.version.max(2);
+        }
+
+        self.gas_schedule_version = version;
+    }
+}
@@ -0,0 +1,176 @@
+use std::fmt;
+use std::ops::BitOrAssign;
+
+use svm_hash::{Blake3Hasher, Hasher};
+
+use crate::{ReceiptLog, TemplateAddr};
+
+/// The number of bits set per accrued topic.
+const BITS_PER_TOPIC: usize = 3;
+
+/// A Bloom filter over the topics of a Receipt's logs (and the [`TemplateAddr`] having emitted them).
+///
+/// Each raw log counts as a single topic (its bytes), while each event log
+/// counts as its indexed topics (see [`ReceiptLog::event`]).
+/// A filter never misses an accrued topic, but it may report topics never accrued
+/// (so a positive [`LogsBloom::maybe_contains`] still requires decoding the logs).
+#[derive(Clone, PartialEq, Eq)]
+pub struct LogsBloom(Box<[u8; LogsBloom::BYTES]>);
+
+impl LogsBloom {
+    /// The byte size of a filter (2048 bits).
+    pub const BYTES: usize = 256;
+
+    /// Creates an empty filter.
+    pub fn new() -> Self {
+        Self(Box::new([0; Self::BYTES]))
+    }
+
+    /// Creates a filter of the logs emitted by the code of `template`.
+    pub fn for_logs(template: &TemplateAddr, logs: &[ReceiptLog]) -> Self {
+        let mut bloom = Self::new();
+
+        bloom.accrue(template.as_slice());
+
+        for log in logs {
+            if log.is_event() {
+                for topic in log.topics() {
+                    bloom.accrue(topic);
+                }
+            } else {
+                bloom.accrue(log.as_bytes());
+            }
+        }
+
+        bloom
+    }
+
+    /// Wraps the raw bits of a filter.
+    pub fn from_bytes(bytes: [u8; Self::BYTES]) -> Self {
+        Self(Box::new(bytes))
+    }
+
+    /// Borrows the raw bits of the filter.
+    pub fn as_bytes(&self) -> &[u8; Self::BYTES] {
+        &self.0
+    }
+
+    /// Returns whether no topic has been accrued.
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|&byte| byte == 0)
+    }
+
+    /// Adds `topic` to the filter.
+    pub fn accrue(&mut self, topic: &[u8]) {
+        for (index, mask) in Self::bits(topic) {
+            self.0[index] |= mask;
+        }
+    }
+
+    /// Adds the topics of `other` to the filter.
+    pub fn accrue_bloom(&mut self, other: &LogsBloom) {
+        for (byte, other) in self.0.iter_mut().zip(other.0.iter()) {
+            *byte |= other;
+        }
+    }
+
+    /// Returns `false` when `topic` has certainly not been accrued.
+    pub fn maybe_contains(&self, topic: &[u8]) -> bool {
+        Self::bits(topic).all(|(index, mask)| self.0[index] & mask == mask)
+    }
+
+    /// Returns `false` when `template` has certainly not emitted any of the filtered logs.
+    pub fn maybe_contains_template(&self, template: &TemplateAddr) -> bool {
+        self.maybe_contains(template.as_slice())
+    }
+
+    /// Derives the bits of `topic` out of its hash, as `(byte index, bit mask)` pairs.
+    fn bits(topic: &[u8]) -> impl Iterator<Item = (usize, u8)> {
+        let hash = Blake3Hasher::hash(topic);
+
+        (0..BITS_PER_TOPIC).map(move |i| {
+            let bit = u16::from_be_bytes([hash[2 * i], hash[2 * i + 1]]) as usize;
+            let bit = bit % (Self::BYTES * 8);
+
+            (bit / 8, 1 << (bit % 8))
+        })
+    }
+}
+
+impl Default for LogsBloom {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BitOrAssign<&LogsBloom> for LogsBloom {
+    fn bitor_assign(&mut self, other: &LogsBloom) {
+        self.accrue_bloom(other);
+    }
+}
+
+impl fmt::Debug for LogsBloom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("LogsBloom")
+            .field(&hex::encode_upper(&self.0[..]))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logs_bloom_contains_accrued_topics() {
+        let template = TemplateAddr::of("@Template");
+        let logs = vec![
+            ReceiptLog::new(b"transfer".to_vec()),
+            ReceiptLog::new(b"approve".to_vec()),
+        ];
+
+        let bloom = LogsBloom::for_logs(&template, &logs);
+
+        assert!(bloom.maybe_contains_template(&template));
+        assert!(bloom.maybe_contains(b"transfer"));
+        assert!(bloom.maybe_contains(b"approve"));
+        assert!(!bloom.maybe_contains_template(&TemplateAddr::of("@Other")));
+    }
+
+    #[test]
+    fn logs_bloom_contains_event_topics() {
+        let template = TemplateAddr::of("@Template");
+        let logs = vec![ReceiptLog::event(
+            1,
+            vec![[0xAA; 32], [0xBB; 32]],
+            b"payload".to_vec(),
+        )];
+
+        let bloom = LogsBloom::for_logs(&template, &logs);
+
+        assert!(bloom.maybe_contains(&[0xAA; 32]));
+        assert!(bloom.maybe_contains(&[0xBB; 32]));
+    }
+
+    #[test]
+    fn logs_bloom_empty() {
+        let bloom = LogsBloom::new();
+
+        assert!(bloom.is_empty());
+        assert!(!bloom.maybe_contains(b"transfer"));
+    }
+
+    #[test]
+    fn logs_bloom_accrue_bloom() {
+        let mut a = LogsBloom::new();
+        a.accrue(b"transfer");
+
+        let mut b = LogsBloom::new();
+        b.accrue(b"approve");
+
+        a |= &b;
+
+        assert!(a.maybe_contains(b"transfer"));
+        assert!(a.maybe_contains(b"approve"));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::gas::Gas;
-use crate::receipt::{ReceiptLog, RuntimeError};
+use crate::receipt::{log, GasBreakdown, LogsBloom, ReceiptLog, ReceiptMetric, RuntimeError};
 use crate::State;
 
 /// Runtime transaction execution receipt
@@ -23,8 +23,20 @@ pub struct CallReceipt {
     /// The amount of gas used.
     pub gas_used: Gas,
 
+    /// The `gas_used` broken down by phase (see [`GasBreakdown`]).
+    pub gas_breakdown: Option<GasBreakdown>,
+
+    /// The version of the gas schedule the transaction has been priced by.
+    pub gas_schedule_version: u16,
+
+    /// The [`LogsBloom`] of the logs (see [`Receipt::logs_bloom`](crate::Receipt::logs_bloom)).
+    pub logs_bloom: Option<LogsBloom>,
+
     /// Logs generated during execution of the transaction.
     pub logs: Vec<ReceiptLog>,
+
+    /// Metrics reported during execution of the transaction (see [`ReceiptMetric`]).
+    pub metrics: Vec<ReceiptMetric>,
 }
 
 impl From<RuntimeError> for CallReceipt {
@@ -42,13 +54,17 @@ impl CallReceipt {
     /// Creates a new failure Receipt out of the `err` parameter
     pub fn from_err(err: RuntimeError, logs: Vec<ReceiptLog>) -> Self {
         Self {
-            version: 0,
+            version: log::logs_version(&logs),
             success: false,
             error: Some(err),
             new_state: None,
             returndata: None,
             gas_used: Gas::new(),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
             logs,
+            metrics: Vec::new(),
         }
     }
 
@@ -84,4 +100,54 @@ impl CallReceipt {
     pub fn take_logs(&mut self) -> Vec<ReceiptLog> {
         std::mem::take(&mut self.logs)
     }
+
+    /// Returns the metrics reported during the transaction execution
+    pub fn metrics(&self) -> &[ReceiptMetric] {
+        &self.metrics
+    }
+
+    /// Attaches the reported metrics.
+    ///
+    /// Receipts carrying metrics are encoded using the binary format version `5`.
+    pub fn set_metrics(&mut self, metrics: Vec<ReceiptMetric>) {
+        if !metrics.is_empty() {
+            self.version = self.version.max(5);
+        }
+
+        self.metrics = metrics;
+    }
+
+    /// Attaches the breakdown of the `gas_used` by phase.
+    ///
+    /// Receipts carrying a [`GasBreakdown`] are encoded using the binary format version `1` (at least).
+    pub fn set_gas_breakdown(&mut self, breakdown: GasBreakdown) {
+        self.version = self.version.max(1);
+        self.gas_breakdown = Some(breakdown);
+    }
+
+    /// Raises the `version` to [`EVENT_LOGS_VERSION`](crate::EVENT_LOGS_VERSION)
+    /// when any of the logs is an event (see [`ReceiptLog::event`]).
+    pub fn set_logs_version(&mut self) {
+        self.version = self.version.max(log::logs_version(&self.logs));
+    }
+
+    /// Attaches the [`LogsBloom`] of the logs.
+    ///
+    /// Receipts carrying a [`LogsBloom`] are encoded using the binary format version `3`.
+    pub fn set_logs_bloom(&mut self, bloom: LogsBloom) {
+        self.version = self.version.max(3);
+        self.logs_bloom = Some(bloom);
+    }
+
+    /// Records the version of the gas schedule the transaction has been priced by.
+    ///
+    /// Receipts of a gas schedule other than the initial one (version `0`)
+    /// are encoded using the binary format version `2` (at least).
+    pub fn set_gas_schedule_version(&mut self, version: u16) {
+        if version > 0 {
+            self.version = self.version.max(2);
+        }
+
+        self.gas_schedule_version = version;
+    }
 }
@@ -1,4 +1,4 @@
-use crate::receipt::{ReceiptLog, RuntimeError};
+use crate::receipt::{log, GasBreakdown, ReceiptLog, RuntimeError};
 use crate::{Gas, TemplateAddr};
 
 /// Information about the attempted deployment of a
@@ -23,6 +23,12 @@ pub struct DeployReceipt {
     /// The amount of gas used for template deployment
     pub gas_used: Gas,
 
+    /// The `gas_used` broken down by phase (see [`GasBreakdown`]).
+    pub gas_breakdown: Option<GasBreakdown>,
+
+    /// The version of the gas schedule the transaction has been priced by.
+    pub gas_schedule_version: u16,
+
     /// generated logs during transaction execution.
     pub logs: Vec<ReceiptLog>,
 }
@@ -37,6 +43,8 @@ impl DeployReceipt {
             error: None,
             addr: Some(addr),
             gas_used,
+            gas_breakdown: None,
+            gas_schedule_version: 0,
             logs: Vec::new(),
         }
     }
@@ -54,6 +62,8 @@ impl DeployReceipt {
             error: Some(error),
             addr: None,
             gas_used: Gas::new(),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
             logs,
         }
     }
@@ -83,4 +93,30 @@ impl DeployReceipt {
     pub fn take_logs(&mut self) -> Vec<ReceiptLog> {
         std::mem::take(&mut self.logs)
     }
+
+    /// Raises the `version` to [`EVENT_LOGS_VERSION`](crate::EVENT_LOGS_VERSION)
+    /// when any of the logs is an event (see [`ReceiptLog::event`]).
+    pub fn set_logs_version(&mut self) {
+        self.version = self.version.max(log::logs_version(&self.logs));
+    }
+
+    /// Attaches the breakdown of the `gas_used` by phase.
+    ///
+    /// Receipts carrying a [`GasBreakdown`] are encoded using the binary format version `1` (at least).
+    pub fn set_gas_breakdown(&mut self, breakdown: GasBreakdown) {
+        self.version = self.version.max(1);
+        self.gas_breakdown = Some(breakdown);
+    }
+
+    /// Records the version of the gas schedule the transaction has been priced by.
+    ///
+    /// Receipts of a gas schedule other than the initial one (version `0`)
+    /// are encoded using the binary format version `2`.
+    pub fn set_gas_schedule_version(&mut self, version: u16) {
+        if version > 0 {
+            self.version = self.version.max(2);
+        }
+
+        self.gas_schedule_version = version;
+    }
 }
@@ -0,0 +1,42 @@
+use std::ops::AddAssign;
+
+/// The gas used by a transaction, broken down by the phase it has been spent on.
+///
+/// Populated only when the `Runtime` runs with detailed gas accounting enabled.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct GasBreakdown {
+    /// The intrinsic cost of the transaction payload and of loading the executed `Template`s
+    /// (charged before executing anything).
+    pub intrinsic: u64,
+
+    /// The gas used running the code (excluding the `storage` writes and the `logs`).
+    pub execution: u64,
+
+    /// The gas used by the storage writes.
+    pub storage: u64,
+
+    /// The gas used by the emitted logs.
+    pub logs: u64,
+
+    /// The gas refunded.
+    pub refund: u64,
+}
+
+impl GasBreakdown {
+    /// The net amount of gas (the sum of all the phases minus the `refund`).
+    pub fn total(&self) -> u64 {
+        let spent = self.intrinsic + self.execution + self.storage + self.logs;
+
+        spent.saturating_sub(self.refund)
+    }
+}
+
+impl AddAssign for GasBreakdown {
+    fn add_assign(&mut self, other: Self) {
+        self.intrinsic += other.intrinsic;
+        self.execution += other.execution;
+        self.storage += other.storage;
+        self.logs += other.logs;
+        self.refund += other.refund;
+    }
+}
@@ -1,16 +1,66 @@
 use std::fmt;
 
+/// The maximum number of indexed topics of an event log (see [`ReceiptLog::event`]).
+pub const MAX_LOG_TOPICS: usize = 4;
+
+/// The first receipts binary format version able to encode event logs.
+///
+/// Receipts holding event logs are encoded using this version (at least).
+pub const EVENT_LOGS_VERSION: u16 = 6;
+
+/// An indexed topic of an event log.
+pub type LogTopic = [u8; 32];
+
 /// A log entry. Logs are generated during executing of transactions.
-/// Their main usage is for debugging / testing purposes.
+///
+/// A log is either raw (a blob of bytes, mainly used for debugging / testing purposes),
+/// or an event: a template-defined event `code` plus up to [`MAX_LOG_TOPICS`] indexed `topics`,
+/// along with the event data.
 #[derive(PartialEq, Clone)]
 pub struct ReceiptLog {
+    code: Option<u32>,
+    topics: Vec<LogTopic>,
     bytes: Vec<u8>,
 }
 
 impl ReceiptLog {
-    /// New log entry
+    /// New (raw) log entry
     pub fn new(bytes: Vec<u8>) -> Self {
-        Self { bytes }
+        Self {
+            code: None,
+            topics: Vec::new(),
+            bytes,
+        }
+    }
+
+    /// New event log entry
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than [`MAX_LOG_TOPICS`] `topics`.
+    pub fn event(code: u32, topics: Vec<LogTopic>, bytes: Vec<u8>) -> Self {
+        assert!(topics.len() <= MAX_LOG_TOPICS);
+
+        Self {
+            code: Some(code),
+            topics,
+            bytes,
+        }
+    }
+
+    /// Returns whether the log is an event (rather than a raw log).
+    pub fn is_event(&self) -> bool {
+        self.code.is_some()
+    }
+
+    /// The event code (`None` for a raw log)
+    pub fn code(&self) -> Option<u32> {
+        self.code
+    }
+
+    /// The indexed topics of the event (empty for a raw log)
+    pub fn topics(&self) -> &[LogTopic] {
+        &self.topics
     }
 
     /// Borrows the underlying bytes
@@ -19,11 +69,27 @@ impl ReceiptLog {
     }
 }
 
+/// Returns the binary format version required for encoding `logs`
+/// (that is [`EVENT_LOGS_VERSION`] when any of them is an event, and `0` otherwise).
+pub(crate) fn logs_version(logs: &[ReceiptLog]) -> u16 {
+    if logs.iter().any(ReceiptLog::is_event) {
+        EVENT_LOGS_VERSION
+    } else {
+        0
+    }
+}
+
 impl fmt::Debug for ReceiptLog {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("ReceiptLog")
-            .field("data", &fmt_msg(self))
-            .finish()
+        let mut s = f.debug_struct("ReceiptLog");
+
+        if let Some(code) = self.code {
+            let topics: Vec<String> = self.topics.iter().map(hex::encode_upper).collect();
+
+            s.field("code", &code).field("topics", &topics);
+        }
+
+        s.field("data", &fmt_msg(self)).finish()
     }
 }
 
@@ -0,0 +1,22 @@
+/// The maximum number of distinct metrics a single receipt may carry.
+pub const MAX_RECEIPT_METRICS: usize = 32;
+
+/// A numeric metric (e.g. a pool utilization) reported by the executed code.
+///
+/// Unlike the logs (which are meant for the users), metrics are meant for the nodes,
+/// which may aggregate them cheaply (by their `id`) without decoding any payload.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ReceiptMetric {
+    /// The metric id (its meaning is up to the `Template`).
+    pub id: u32,
+
+    /// The reported value.
+    pub value: u64,
+}
+
+impl ReceiptMetric {
+    /// New metric
+    pub fn new(id: u32, value: u64) -> Self {
+        Self { id, value }
+    }
+}
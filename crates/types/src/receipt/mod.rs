@@ -1,12 +1,22 @@
+mod batch;
+mod bloom;
 mod call;
 mod deploy;
+mod gas_breakdown;
 mod log;
+mod metric;
 mod spawn;
+mod upgrade;
 
+pub use batch::BatchReceipt;
+pub use bloom::LogsBloom;
 pub use call::CallReceipt;
 pub use deploy::DeployReceipt;
-pub use log::ReceiptLog;
+pub use gas_breakdown::GasBreakdown;
+pub use log::{LogTopic, ReceiptLog, EVENT_LOGS_VERSION, MAX_LOG_TOPICS};
+pub use metric::{ReceiptMetric, MAX_RECEIPT_METRICS};
 pub use spawn::{into_spawn_receipt, SpawnReceipt};
+pub use upgrade::UpgradeReceipt;
 
 use crate::gas::Gas;
 use crate::RuntimeError;
@@ -21,6 +31,12 @@ pub enum ReceiptRef<'a> {
 
     /// Borrows a `CallReceipt`.
     Call(&'a CallReceipt),
+
+    /// Borrows an `UpgradeReceipt`.
+    Upgrade(&'a UpgradeReceipt),
+
+    /// Borrows a `BatchReceipt`.
+    Batch(&'a BatchReceipt),
 }
 
 impl<'a> ReceiptRef<'a> {
@@ -30,13 +46,15 @@ impl<'a> ReceiptRef<'a> {
             Self::Deploy(r) => r.success,
             Self::Spawn(r) => r.success,
             Self::Call(r) => r.success,
+            Self::Upgrade(r) => r.success,
+            Self::Batch(r) => r.success,
         }
     }
 
     /// Returns the executed transaction results.
     pub fn returndata(&self) -> &Vec<u8> {
         match self {
-            Self::Deploy(..) => unreachable!(),
+            Self::Deploy(..) | Self::Upgrade(..) | Self::Batch(..) => unreachable!(),
             Self::Spawn(r) => r.returndata(),
             Self::Call(r) => r.returndata(),
         }
@@ -48,6 +66,8 @@ impl<'a> ReceiptRef<'a> {
             Self::Deploy(r) => r.gas_used,
             Self::Spawn(r) => r.gas_used,
             Self::Call(r) => r.gas_used,
+            Self::Upgrade(r) => r.gas_used,
+            Self::Batch(r) => r.gas_used,
         }
     }
 
@@ -57,12 +77,14 @@ impl<'a> ReceiptRef<'a> {
             Self::Deploy(r) => r.error.as_ref().unwrap(),
             Self::Spawn(r) => r.error.as_ref().unwrap(),
             Self::Call(r) => r.error.as_ref().unwrap(),
+            Self::Upgrade(r) => r.error.as_ref().unwrap(),
+            Self::Batch(r) => r.error.as_ref().unwrap(),
         }
     }
 }
 
-/// Holds a Receipt of kind `Deploy/Spawn/Call`
-#[derive(Debug, PartialEq)]
+/// Holds a Receipt of kind `Deploy/Spawn/Call/Upgrade/Batch`
+#[derive(Debug, PartialEq, Clone)]
 pub enum Receipt {
     /// `Deploy Template`
     Deploy(DeployReceipt),
@@ -72,6 +94,12 @@ pub enum Receipt {
 
     /// `Call Account`
     Call(CallReceipt),
+
+    /// `Upgrade Template`
+    Upgrade(UpgradeReceipt),
+
+    /// `Call Batch`
+    Batch(BatchReceipt),
 }
 
 impl Receipt {
@@ -82,6 +110,8 @@ impl Receipt {
             Receipt::Deploy(receipt) => receipt.success,
             Receipt::Spawn(receipt) => receipt.success,
             Receipt::Call(receipt) => receipt.success,
+            Receipt::Upgrade(receipt) => receipt.success,
+            Receipt::Batch(receipt) => receipt.success,
         }
     }
 
@@ -109,12 +139,78 @@ impl Receipt {
         }
     }
 
+    /// Returns the inner [`UpgradeReceipt`]
+    pub fn into_upgrade(self) -> UpgradeReceipt {
+        match self {
+            Receipt::Upgrade(r) => r,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the inner [`BatchReceipt`]
+    pub fn into_batch(self) -> BatchReceipt {
+        match self {
+            Receipt::Batch(r) => r,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the gas used for the transaction.
+    pub fn gas_used(&self) -> Gas {
+        match self {
+            Receipt::Deploy(receipt) => receipt.gas_used,
+            Receipt::Spawn(receipt) => receipt.gas_used,
+            Receipt::Call(receipt) => receipt.gas_used,
+            Receipt::Upgrade(receipt) => receipt.gas_used,
+            Receipt::Batch(receipt) => receipt.gas_used,
+        }
+    }
+
+    /// Returns the version of the gas schedule the transaction has been priced by.
+    pub fn gas_schedule_version(&self) -> u16 {
+        match self {
+            Receipt::Deploy(receipt) => receipt.gas_schedule_version,
+            Receipt::Spawn(receipt) => receipt.gas_schedule_version,
+            Receipt::Call(receipt) => receipt.gas_schedule_version,
+            Receipt::Upgrade(receipt) => receipt.gas_schedule_version,
+            Receipt::Batch(receipt) => receipt.gas_schedule_version,
+        }
+    }
+
     /// Returns the logs generated during the transaction execution
     pub fn logs(&self) -> &[ReceiptLog] {
         match self {
             Receipt::Deploy(receipt) => receipt.logs(),
             Receipt::Spawn(receipt) => receipt.logs(),
             Receipt::Call(receipt) => receipt.logs(),
+            Receipt::Upgrade(receipt) => receipt.logs(),
+            Receipt::Batch(receipt) => receipt.logs(),
+        }
+    }
+
+    /// Returns the [`LogsBloom`] of the receipt's logs.
+    ///
+    /// Returns `None` when the receipt has logs but no recorded filter
+    /// (in that case the logs have to be scanned).
+    pub fn logs_bloom(&self) -> Option<LogsBloom> {
+        let (bloom, has_logs) = match self {
+            Receipt::Deploy(receipt) => (None, !receipt.logs.is_empty()),
+            Receipt::Upgrade(receipt) => (None, !receipt.logs.is_empty()),
+            Receipt::Spawn(receipt) => {
+                let call_logs = receipt.call().map_or(&[][..], |call| call.logs());
+
+                (
+                    receipt.logs_bloom.clone(),
+                    !receipt.logs.is_empty() || !call_logs.is_empty(),
+                )
+            }
+            Receipt::Call(receipt) => (receipt.logs_bloom.clone(), !receipt.logs.is_empty()),
+            Receipt::Batch(receipt) => (receipt.logs_bloom(), !receipt.logs.is_empty()),
+        };
+
+        match bloom {
+            None if !has_logs => Some(LogsBloom::new()),
+            bloom => bloom,
         }
     }
 
@@ -124,6 +220,8 @@ impl Receipt {
             Receipt::Deploy(receipt) => receipt.error(),
             Receipt::Spawn(receipt) => receipt.error(),
             Receipt::Call(receipt) => receipt.error(),
+            Receipt::Upgrade(receipt) => receipt.error(),
+            Receipt::Batch(receipt) => receipt.error(),
         }
     }
 }
@@ -1,5 +1,6 @@
+use super::log;
 use crate::{Address, Gas, State};
-use crate::{CallReceipt, ReceiptLog, RuntimeError};
+use crate::{CallReceipt, GasBreakdown, LogsBloom, ReceiptLog, ReceiptMetric, RuntimeError};
 
 /// Returned Receipt after spawning an [`Account`](crate::Account)
 #[derive(Debug, PartialEq, Clone)]
@@ -17,16 +18,34 @@ pub struct SpawnReceipt {
     pub account_addr: Option<Address>,
 
     /// The spawned [`Account`](crate::Account) initial state (after executing its ctor)
+    ///
+    /// It excludes the changes of the post-`ctor` call (if any), see [`SpawnReceipt::final_state`].
     pub init_state: Option<State>,
 
     /// Returned `ctor` data
     pub returndata: Option<Vec<u8>>,
 
-    /// The amount of gas used.
+    /// The amount of gas used (including the post-`ctor` call, if any).
     pub gas_used: Gas,
 
+    /// The `gas_used` broken down by phase (see [`GasBreakdown`]).
+    pub gas_breakdown: Option<GasBreakdown>,
+
+    /// The version of the gas schedule the transaction has been priced by.
+    pub gas_schedule_version: u16,
+
+    /// The [`LogsBloom`] of the logs (see [`Receipt::logs_bloom`](crate::Receipt::logs_bloom)).
+    pub logs_bloom: Option<LogsBloom>,
+
     /// Logs collected during `Spawning` `ctor` running.
     pub logs: Vec<ReceiptLog>,
+
+    /// Metrics reported during `Spawning` `ctor` running (see [`ReceiptMetric`]).
+    pub metrics: Vec<ReceiptMetric>,
+
+    /// The [`CallReceipt`] of the function called right after the `ctor`
+    /// (see [`SpawnCall`](crate::SpawnCall)). Set only when spawning has succeeded.
+    pub call: Option<CallReceipt>,
 }
 
 impl SpawnReceipt {
@@ -38,14 +57,19 @@ impl SpawnReceipt {
     /// Creates a new failure Receipt out of the `error` parameter
     pub fn from_err(error: RuntimeError, logs: Vec<ReceiptLog>) -> Self {
         Self {
-            version: 0,
+            version: log::logs_version(&logs),
             success: false,
             error: Some(error),
             account_addr: None,
             init_state: None,
             returndata: None,
             gas_used: Gas::new(),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
             logs,
+            metrics: Vec::new(),
+            call: None,
         }
     }
 
@@ -76,6 +100,21 @@ impl SpawnReceipt {
         self.init_state.as_ref().unwrap()
     }
 
+    /// Returns the `State` left by the whole `Spawn` transaction, i.e the `new_state` of the
+    /// post-`ctor` call when there is one (see [`SpawnReceipt::call`]), and the `init_state` otherwise.
+    ///
+    /// That's the `State` the following transactions should run against.
+    ///
+    /// # Panics
+    ///
+    /// Panics if spawning has failed.
+    pub fn final_state(&self) -> &State {
+        match self.call.as_ref().and_then(|call| call.new_state.as_ref()) {
+            Some(state) => state,
+            None => self.init_state(),
+        }
+    }
+
     /// Returns spawned [`Account`](crate::Account) results. Panics if spawning has failed.
     pub fn returndata(&self) -> &Vec<u8> {
         self.returndata.as_ref().unwrap()
@@ -86,6 +125,11 @@ impl SpawnReceipt {
         self.gas_used
     }
 
+    /// Returns the [`CallReceipt`] of the function called right after the `ctor` (if any).
+    pub fn call(&self) -> Option<&CallReceipt> {
+        self.call.as_ref()
+    }
+
     /// Returns the collected Logs during the transaction execution.
     pub fn logs(&self) -> &[ReceiptLog] {
         &self.logs
@@ -95,6 +139,68 @@ impl SpawnReceipt {
     pub fn take_logs(&mut self) -> Vec<ReceiptLog> {
         std::mem::take(&mut self.logs)
     }
+
+    /// Returns the metrics reported by the `ctor`.
+    pub fn metrics(&self) -> &[ReceiptMetric] {
+        &self.metrics
+    }
+
+    /// Attaches the metrics reported by the `ctor`.
+    ///
+    /// Receipts carrying metrics are encoded using the binary format version `5`.
+    pub fn set_metrics(&mut self, metrics: Vec<ReceiptMetric>) {
+        if !metrics.is_empty() {
+            self.version = self.version.max(5);
+        }
+
+        self.metrics = metrics;
+    }
+
+    /// Attaches the [`CallReceipt`] of the function called right after the `ctor`.
+    ///
+    /// Receipts carrying it are encoded using the binary format version `7`.
+    pub fn set_call(&mut self, call: CallReceipt) {
+        self.version = self.version.max(7);
+        self.call = Some(call);
+    }
+
+    /// Raises the `version` to [`EVENT_LOGS_VERSION`](crate::EVENT_LOGS_VERSION)
+    /// when any of the logs is an event (see [`ReceiptLog::event`]).
+    pub fn set_logs_version(&mut self) {
+        self.version = self.version.max(log::logs_version(&self.logs));
+    }
+
+    /// Attaches the breakdown of the `gas_used` by phase.
+    ///
+    /// Receipts carrying a [`GasBreakdown`] are encoded using the binary format version `1` (at least).
+    pub fn set_gas_breakdown(&mut self, breakdown: GasBreakdown) {
+        self.version = self.version.max(1);
+        self.gas_breakdown = Some(breakdown);
+    }
+
+    /// Attaches the [`LogsBloom`] of the logs.
+    ///
+    /// Receipts carrying a [`LogsBloom`] are encoded using the binary format version `3`.
+    pub fn set_logs_bloom(&mut self, bloom: LogsBloom) {
+        self.version = self.version.max(3);
+        self.logs_bloom = Some(bloom);
+    }
+
+    /// Records the version of the gas schedule the transaction has been priced by.
+    ///
+    /// Receipts of a gas schedule other than the initial one (version `0`)
+    /// are encoded using the binary format version `2` (at least).
+    pub fn set_gas_schedule_version(&mut self, version: u16) {
+        if version > 0 {
+            self.version = self.version.max(2);
+        }
+
+        self.gas_schedule_version = version;
+
+        if let Some(call) = self.call.as_mut() {
+            call.set_gas_schedule_version(version);
+        }
+    }
 }
 
 #[allow(missing_docs)]
@@ -103,27 +209,37 @@ pub fn into_spawn_receipt(mut ctor_receipt: CallReceipt, account_addr: &Address)
 
     if ctor_receipt.success {
         SpawnReceipt {
-            version: 0,
+            version: ctor_receipt.version.max(log::logs_version(&logs)),
             success: true,
             error: None,
             account_addr: Some(account_addr.clone()),
             init_state: ctor_receipt.new_state,
             returndata: ctor_receipt.returndata,
             gas_used: ctor_receipt.gas_used,
+            gas_breakdown: ctor_receipt.gas_breakdown,
+            gas_schedule_version: ctor_receipt.gas_schedule_version,
+            logs_bloom: ctor_receipt.logs_bloom,
             logs,
+            metrics: ctor_receipt.metrics,
+            call: None,
         }
     } else {
         let error = ctor_receipt.error.unwrap();
 
         SpawnReceipt {
-            version: 0,
+            version: log::logs_version(&logs),
             success: false,
             error: Some(error),
             account_addr: None,
             init_state: None,
             returndata: None,
             gas_used: Gas::new(),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs_bloom: None,
             logs,
+            metrics: Vec::new(),
+            call: None,
         }
     }
 }
@@ -0,0 +1,127 @@
+use crate::receipt::{log, GasBreakdown, ReceiptLog, RuntimeError};
+use crate::{Gas, TemplateAddr};
+
+/// Information about the attempted upgrade of a [`Template`](crate::Template)
+/// (see [`UpgradeTemplate`](crate::UpgradeTemplate)).
+///
+/// [`UpgradeReceipt`] should *not* be wrapped in a [`Result`] for failure
+/// detection; error detection is built-in the type itself.
+#[derive(Debug, PartialEq, Clone)]
+pub struct UpgradeReceipt {
+    /// Transaction format version
+    pub version: u16,
+
+    /// whether the upgrade succeeded or not
+    pub success: bool,
+
+    /// the error in case the upgrade failed
+    pub error: Option<RuntimeError>,
+
+    /// The upgraded template `Address`
+    pub addr: Option<TemplateAddr>,
+
+    /// The version of the upgraded template (the originally deployed version is `0`)
+    pub template_version: u16,
+
+    /// The amount of gas used for the template upgrade
+    pub gas_used: Gas,
+
+    /// The `gas_used` broken down by phase (see [`GasBreakdown`]).
+    pub gas_breakdown: Option<GasBreakdown>,
+
+    /// The version of the gas schedule the transaction has been priced by.
+    pub gas_schedule_version: u16,
+
+    /// generated logs during transaction execution.
+    pub logs: Vec<ReceiptLog>,
+}
+
+impl UpgradeReceipt {
+    /// Creates a [`UpgradeReceipt`] which indicates a successful upgrade of
+    /// the template located at `addr` into `template_version`, which cost `gas_used`.
+    pub fn new(addr: TemplateAddr, template_version: u16, gas_used: Gas) -> Self {
+        Self {
+            version: 0,
+            success: true,
+            error: None,
+            addr: Some(addr),
+            template_version,
+            gas_used,
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs: Vec::new(),
+        }
+    }
+
+    /// Creates a [`UpgradeReceipt`] for reaching `Out-of-Gas`.
+    pub fn new_oog() -> Self {
+        Self::from_err(RuntimeError::OOG, Vec::new())
+    }
+
+    /// Creates a new failure [`UpgradeReceipt`] out of the `error` parameter.
+    pub fn from_err(error: RuntimeError, logs: Vec<ReceiptLog>) -> Self {
+        Self {
+            version: 0,
+            success: false,
+            error: Some(error),
+            addr: None,
+            template_version: 0,
+            gas_used: Gas::new(),
+            gas_breakdown: None,
+            gas_schedule_version: 0,
+            logs,
+        }
+    }
+
+    /// Returns the address of the upgraded [`Template`](crate::Template) as a
+    /// [`TemplateAddr`].
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the [`Template`](crate::Template) upgrade
+    /// has failed.
+    pub fn template_addr(&self) -> &TemplateAddr {
+        self.addr.as_ref().unwrap()
+    }
+
+    /// Returns the error within the Receipt (for failing Receipts)
+    pub fn error(&self) -> &RuntimeError {
+        self.error.as_ref().unwrap()
+    }
+
+    /// Returns the logs generated during the transaction execution
+    pub fn logs(&self) -> &[ReceiptLog] {
+        &self.logs
+    }
+
+    /// Taking the `logs` out
+    pub fn take_logs(&mut self) -> Vec<ReceiptLog> {
+        std::mem::take(&mut self.logs)
+    }
+
+    /// Raises the `version` to [`EVENT_LOGS_VERSION`](crate::EVENT_LOGS_VERSION)
+    /// when any of the logs is an event (see [`ReceiptLog::event`]).
+    pub fn set_logs_version(&mut self) {
+        self.version = self.version.max(log::logs_version(&self.logs));
+    }
+
+    /// Attaches the breakdown of the `gas_used` by phase.
+    ///
+    /// Receipts carrying a [`GasBreakdown`] are encoded using the binary format version `1` (at least).
+    pub fn set_gas_breakdown(&mut self, breakdown: GasBreakdown) {
+        self.version = self.version.max(1);
+        self.gas_breakdown = Some(breakdown);
+    }
+
+    /// Records the version of the gas schedule the transaction has been priced by.
+    ///
+    /// Receipts of a gas schedule other than the initial one (version `0`)
+    /// are encoded using the binary format version `2`.
+    pub fn set_gas_schedule_version(&mut self, version: u16) {
+        if version > 0 {
+            self.version = self.version.max(2);
+        }
+
+        self.gas_schedule_version = version;
+    }
+}
@@ -0,0 +1,144 @@
+//! Hardening the handling of sensitive bytes (enabled by the `sensitive` feature).
+//!
+//! * [`ct_eq`] compares byte slices in constant time. Once the feature is enabled,
+//!   the fixed-size byte types (e.g [`Address`](crate::Address) and [`State`](crate::State))
+//!   implement their [`PartialEq`] using it.
+//!
+//! * [`Zeroizing`] wraps a buffer and zeroes it once dropped, so its content doesn't linger in memory.
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{self, Ordering};
+
+/// Returns whether `a` and `b` are equal, in time depending only on their lengths.
+///
+/// The lengths themselves aren't considered secret (slices of different lengths are unequal right away).
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let diff = a
+        .iter()
+        .zip(b.iter())
+        .fold(0u8, |diff, (x, y)| diff | std::hint::black_box(x ^ y));
+
+    std::hint::black_box(diff) == 0
+}
+
+/// Overwrites `buf` with zeros, in a way the compiler can't optimize away.
+pub fn zeroize(buf: &mut [u8]) {
+    for byte in buf.iter_mut() {
+        // Safety: `byte` is a valid and aligned reference
+        unsafe { std::ptr::write_volatile(byte, 0) };
+    }
+
+    atomic::compiler_fence(Ordering::SeqCst);
+}
+
+/// Wraps a buffer of bytes, zeroing it once dropped (see [`zeroize`]).
+///
+/// The wrapped buffer is accessible through [`Deref`] and [`DerefMut`].
+/// Its [`Debug`](fmt::Debug) representation never reveals the content.
+pub struct Zeroizing<T>(T)
+where
+    T: AsMut<[u8]>;
+
+impl<T> Zeroizing<T>
+where
+    T: AsMut<[u8]>,
+{
+    /// Wraps `inner`.
+    pub fn new(inner: T) -> Self {
+        Self(inner)
+    }
+}
+
+impl<T> Deref for Zeroizing<T>
+where
+    T: AsMut<[u8]>,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Zeroizing<T>
+where
+    T: AsMut<[u8]>,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Zeroizing<T>
+where
+    T: AsMut<[u8]>,
+{
+    fn from(inner: T) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl<T> fmt::Debug for Zeroizing<T>
+where
+    T: AsMut<[u8]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Zeroizing(..)")
+    }
+}
+
+impl<T> Drop for Zeroizing<T>
+where
+    T: AsMut<[u8]>,
+{
+    fn drop(&mut self) {
+        zeroize(self.0.as_mut());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{Address, State};
+
+    #[test]
+    fn ct_eq_compares_bytes() {
+        assert!(ct_eq(&[], &[]));
+        assert!(ct_eq(&[1, 2, 3], &[1, 2, 3]));
+
+        assert!(!ct_eq(&[1, 2, 3], &[1, 2, 4]));
+        assert!(!ct_eq(&[1, 2, 3], &[1, 2]));
+    }
+
+    #[test]
+    fn bytes_primitives_eq_in_constant_time() {
+        assert_eq!(Address::repeat(0xAB), Address::repeat(0xAB));
+        assert_ne!(Address::repeat(0xAB), Address::repeat(0xCD));
+
+        assert_eq!(State::zeros(), State::zeros());
+        assert_ne!(State::zeros(), State::repeat(1));
+    }
+
+    #[test]
+    fn zeroize_clears_buffer() {
+        let mut buf = [0xFF; 16];
+        zeroize(&mut buf);
+
+        assert_eq!(buf, [0; 16]);
+    }
+
+    #[test]
+    fn zeroizing_wraps_buffer() {
+        let mut secret = Zeroizing::new(vec![1, 2, 3]);
+        secret.push(4);
+
+        assert_eq!(secret.as_slice(), &[1, 2, 3, 4]);
+        assert_eq!(format!("{:?}", secret), "Zeroizing(..)");
+    }
+}
@@ -16,6 +16,21 @@ pub struct SpawnAccount {
 
     /// calldata
     pub calldata: Vec<u8>,
+
+    /// An optional function called right after the `ctor` (within the same transaction).
+    pub call: Option<SpawnCall>,
+}
+
+/// A function call executed over a freshly spawned `Account`, right after its `ctor` has succeeded.
+///
+/// Both run within the same gas envelope, and a failing call fails the whole [`SpawnAccount`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnCall {
+    /// Function name
+    pub func_name: String,
+
+    /// calldata
+    pub calldata: Vec<u8>,
 }
 
 #[doc(hidden)]
@@ -39,6 +54,10 @@ impl SpawnAccount {
     pub fn ctor_data(&self) -> &[u8] {
         &self.calldata
     }
+
+    pub fn call(&self) -> Option<&SpawnCall> {
+        self.call.as_ref()
+    }
 }
 
 impl fmt::Debug for SpawnAccount {
@@ -50,6 +69,12 @@ impl fmt::Debug for SpawnAccount {
             f,
             "calldata: {:?}",
             self.calldata.iter().take(4).collect::<Vec<_>>()
-        )
+        )?;
+
+        if let Some(call) = self.call.as_ref() {
+            writeln!(f, "call: {}", call.func_name)?;
+        }
+
+        Ok(())
     }
 }
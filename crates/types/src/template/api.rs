@@ -1,11 +1,154 @@
+use svm_layout::Primitive;
+
 use crate::{SectionKind, SectionLike};
 
-/// TODO: ...
+/// Holds the `Template`'s API: the signatures of its exported functions and its `fallback` function.
+///
+/// The signatures allow decoding any `Calldata` (and `Returndata`) of the `Template` generically,
+/// without knowing the `Template` in advance (e.g by wallets).
+///
 /// See <https://github.com/spacemeshos/svm/issues/277>.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Along the signatures, the section may hold the `Template`'s documentation (the `///` docs
+/// of the `Template` itself, of its storage variables and of each exported function),
+/// allowing explorers to render documentation pages out of on-chain data.
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct ApiSection {
-    // TODO: in the future...
-// See <https://github.com/spacemeshos/svm/issues/277>.
+    fallback: Option<String>,
+    doc: String,
+    var_docs: Vec<ApiVarDoc>,
+    funcs: Vec<ApiFunc>,
+}
+
+/// The signature of a function exported by a `Template`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiFunc {
+    /// The function's name (as called by transactions).
+    pub name: String,
+
+    /// The name of the function within the Wasm code.
+    pub wasm_name: String,
+
+    /// Whether the function is a `ctor`.
+    pub is_ctor: bool,
+
+    /// Whether the function may be funded.
+    pub is_fundable: bool,
+
+    /// The function's params (in order).
+    pub params: Vec<ApiParam>,
+
+    /// The types of the function's returned values (in order).
+    pub returns: Vec<ApiType>,
+
+    /// The function's documentation (empty when it's undocumented).
+    pub doc: String,
+}
+
+/// The documentation of a `Template`'s storage variable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiVarDoc {
+    /// The variable's name.
+    pub name: String,
+
+    /// The variable's documentation.
+    pub doc: String,
+}
+
+/// A param of an [`ApiFunc`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiParam {
+    /// The param's name.
+    pub name: String,
+
+    /// The param's type.
+    pub ty: ApiType,
+}
+
+/// The (ABI) type of a param or a returned value of an [`ApiFunc`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiType {
+    /// A primitive (e.g `u64` or an `Address`).
+    Primitive(Primitive),
+
+    /// A fixed-length array of primitives.
+    Array {
+        /// The type of the array's elements.
+        primitive: Primitive,
+
+        /// The array's length.
+        length: usize,
+    },
+
+    /// A user-defined struct (given by its name).
+    Struct(String),
+}
+
+impl ApiSection {
+    /// Creates a new empty `ApiSection`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the name of the `fallback` function
+    ///
+    /// When a transaction asks for a function that isn't exported by the `Template`,
+    /// the `Runtime` will dispatch it to the `fallback` function instead.
+    pub fn with_fallback(mut self, fallback: String) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// The name of the `fallback` function (if any)
+    pub fn fallback(&self) -> Option<&str> {
+        self.fallback.as_deref()
+    }
+
+    /// Sets the documentation of the `Template`
+    pub fn with_doc(mut self, doc: String) -> Self {
+        self.doc = doc;
+        self
+    }
+
+    /// The documentation of the `Template` (empty when it's undocumented)
+    pub fn doc(&self) -> &str {
+        &self.doc
+    }
+
+    /// Appends the documentation of a storage variable
+    pub fn with_var_doc(mut self, var_doc: ApiVarDoc) -> Self {
+        self.var_docs.push(var_doc);
+        self
+    }
+
+    /// The documentation of the storage variables (in the order they have been added)
+    pub fn var_docs(&self) -> &[ApiVarDoc] {
+        &self.var_docs
+    }
+
+    /// Returns whether the section holds any documentation
+    /// (of the `Template`, its storage variables or its exported functions)
+    pub fn has_docs(&self) -> bool {
+        !self.doc.is_empty()
+            || !self.var_docs.is_empty()
+            || self.funcs.iter().any(|func| !func.doc.is_empty())
+    }
+
+    /// Appends the signature of an exported function
+    pub fn with_func(mut self, func: ApiFunc) -> Self {
+        self.funcs.push(func);
+        self
+    }
+
+    /// The signatures of the exported functions (in the order they have been added)
+    pub fn funcs(&self) -> &[ApiFunc] {
+        &self.funcs
+    }
+
+    /// The signature of the exported function named `name` (if any)
+    pub fn func(&self, name: &str) -> Option<&ApiFunc> {
+        self.funcs.iter().find(|func| func.name == name)
+    }
 }
 
 impl SectionLike for ApiSection {
@@ -1,3 +1,5 @@
+use std::convert::TryInto;
+
 use crate::{GasMode, SectionKind, SectionLike};
 
 const EXEC_FLAGS: u64 = 0x01;
@@ -45,12 +47,36 @@ impl CodeSection {
         )
     }
 
+    /// Creates a new `Section` holding executable code and of `Metered Gas`
+    /// (i.e its gas is charged while running, so its code isn't restricted by the `Fixed Gas` rules).
+    pub fn new_metering(code: Vec<u8>, svm_version: u32) -> Self {
+        Self::new(
+            CodeKind::Wasm,
+            code,
+            EXEC_FLAGS,
+            GasMode::Metering,
+            svm_version,
+        )
+    }
+
     /// Returns the constant integer denoting that a `Template` is executable
     pub const fn exec_flags() -> u64 {
         EXEC_FLAGS
     }
 
-    /// Returns the bytecode kind being used (only `Wasm` for now)
+    /// Creates a new `Section` of a [`CodeKind::Precompile`] `Template`, implemented natively
+    /// by the precompile registered under `id`.
+    pub fn new_precompile(id: u32, svm_version: u32) -> Self {
+        Self::new(
+            CodeKind::Precompile,
+            id.to_be_bytes().to_vec(),
+            EXEC_FLAGS,
+            GasMode::Fixed,
+            svm_version,
+        )
+    }
+
+    /// Returns the bytecode kind being used
     pub fn kind(&self) -> CodeKind {
         self.kind
     }
@@ -86,6 +112,18 @@ impl CodeSection {
     pub fn code(&self) -> &[u8] {
         &self.code
     }
+
+    /// Returns the id of the precompile implementing a [`CodeKind::Precompile`] `Template`.
+    ///
+    /// Returns `None` for other kinds of code, or when the code isn't a valid id (4 bytes).
+    pub fn precompile_id(&self) -> Option<u32> {
+        if self.kind != CodeKind::Precompile {
+            return None;
+        }
+
+        let bytes: [u8; 4] = self.code.as_slice().try_into().ok()?;
+        Some(u32::from_be_bytes(bytes))
+    }
 }
 
 impl SectionLike for CodeSection {
@@ -97,4 +135,9 @@ impl SectionLike for CodeSection {
 pub enum CodeKind {
     /// WebAssembly Byte Code
     Wasm,
+
+    /// A native implementation built into the `Runtime` (a.k.a a precompile).
+    ///
+    /// The code holds the id (Big-Endian `u32`) the implementation is registered under.
+    Precompile,
 }
@@ -0,0 +1,51 @@
+use crate::SectionKind;
+
+/// A user-defined (or experimental) `Section` holding an opaque payload.
+///
+/// SVM doesn't interpret a `CustomSection` - it's preserved as is when encoding, decoding
+/// and storing a `Template`. This allows tooling to attach extra data to a `Template`
+/// (and new `Section` kinds to be tried out) without changing the format.
+///
+/// The kind of a `CustomSection` must be at least [`CustomSection::MIN_KIND`]
+/// (the values below it are reserved for the built-in `Section`s).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomSection {
+    kind: u16,
+    payload: Vec<u8>,
+}
+
+impl CustomSection {
+    /// The smallest kind value of a `CustomSection`.
+    pub const MIN_KIND: u16 = 0x80_00;
+
+    /// Creates a new `CustomSection` of kind `kind` holding `payload`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `kind` is smaller than [`CustomSection::MIN_KIND`].
+    pub fn new(kind: u16, payload: Vec<u8>) -> Self {
+        assert!(
+            kind >= Self::MIN_KIND,
+            "The kind of a Custom Section must be at least {:#06x} (got {:#06x})",
+            Self::MIN_KIND,
+            kind
+        );
+
+        Self { kind, payload }
+    }
+
+    /// Returns the raw kind of the Section
+    pub fn kind(&self) -> u16 {
+        self.kind
+    }
+
+    /// Returns the [`SectionKind`] of the Section
+    pub fn section_kind(&self) -> SectionKind {
+        SectionKind::Custom(self.kind)
+    }
+
+    /// Borrows the payload of the Section
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+}
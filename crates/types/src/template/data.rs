@@ -1,4 +1,4 @@
-use svm_layout::Layout;
+use svm_layout::{DynamicLayout, Layout, LayoutKind};
 
 use crate::{SectionKind, SectionLike};
 
@@ -8,6 +8,7 @@ use crate::{SectionKind, SectionLike};
 #[derive(Debug, Clone, PartialEq)]
 pub struct DataSection {
     layouts: Vec<Layout>,
+    max_storage_bytes: Option<u32>,
 }
 
 impl Default for DataSection {
@@ -21,6 +22,7 @@ impl DataSection {
     pub fn with_layout(layout: Layout) -> Self {
         Self {
             layouts: vec![layout],
+            max_storage_bytes: None,
         }
     }
 
@@ -30,6 +32,7 @@ impl DataSection {
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             layouts: Vec::with_capacity(capacity),
+            max_storage_bytes: None,
         }
     }
 
@@ -47,6 +50,38 @@ impl DataSection {
     pub fn len(&self) -> usize {
         self.layouts.len()
     }
+
+    /// Sets the storage budget (in bytes) the `Layout`s must fit within
+    pub fn with_max_storage_bytes(mut self, max_storage_bytes: Option<u32>) -> Self {
+        self.max_storage_bytes = max_storage_bytes;
+        self
+    }
+
+    /// Returns the storage budget (in bytes) the `Layout`s must fit within (if any)
+    pub fn max_storage_bytes(&self) -> Option<u32> {
+        self.max_storage_bytes
+    }
+
+    /// Returns the `Dynamic` layout of the Section (if any)
+    pub fn dynamic_layout(&self) -> Option<&DynamicLayout> {
+        self.layouts
+            .iter()
+            .find(|layout| layout.kind() == LayoutKind::Dynamic)
+            .map(Layout::as_dynamic)
+    }
+
+    /// Returns the total byte-size of the variables of the `Fixed` layouts
+    ///
+    /// (a byte shared by packed booleans is counted once).
+    pub fn storage_bytes(&self) -> u64 {
+        self.layouts
+            .iter()
+            .filter(|layout| layout.kind() == LayoutKind::Fixed)
+            .flat_map(|layout| layout.as_fixed().iter())
+            .filter(|var| var.byte_owner() == var.id())
+            .map(|var| var.byte_size() as u64)
+            .sum()
+    }
 }
 
 impl SectionLike for DataSection {
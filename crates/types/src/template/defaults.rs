@@ -0,0 +1,59 @@
+use svm_layout::Id;
+
+use crate::{SectionKind, SectionLike};
+
+/// Contains the initial (non-zero) values of the `Template`'s storage variables.
+///
+/// When spawning a new `Account`, its storage variables start zeroed, and then
+/// the default values are applied (before the `ctor` runs).
+/// Each default value holds the raw bytes of the variable (as laid out in the storage).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefaultsSection {
+    defaults: Vec<(Id, Vec<u8>)>,
+}
+
+impl Default for DefaultsSection {
+    fn default() -> Self {
+        Self::with_capacity(0)
+    }
+}
+
+impl DefaultsSection {
+    /// Creates a new Section having input `defaults`
+    pub fn new(defaults: Vec<(Id, Vec<u8>)>) -> Self {
+        Self { defaults }
+    }
+
+    /// Initializes a new `Section` and reserving room for `capacity` default values.
+    ///
+    /// See: `push`
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            defaults: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Adds the default `value` of variable `var_id`.
+    pub fn push(&mut self, var_id: Id, value: Vec<u8>) {
+        self.defaults.push((var_id, value));
+    }
+
+    /// Borrows the default values of the Section
+    pub fn defaults(&self) -> &[(Id, Vec<u8>)] {
+        &self.defaults
+    }
+
+    /// Returns the number of default values.
+    pub fn len(&self) -> usize {
+        self.defaults.len()
+    }
+
+    /// Returns whether the Section has no default values.
+    pub fn is_empty(&self) -> bool {
+        self.defaults.is_empty()
+    }
+}
+
+impl SectionLike for DefaultsSection {
+    const KIND: SectionKind = SectionKind::Defaults;
+}
@@ -7,6 +7,7 @@ pub struct DeploySection {
     layer: Layer,
     deployer: Address,
     template: TemplateAddr,
+    addr_scheme: u16,
 }
 
 impl DeploySection {
@@ -22,9 +23,17 @@ impl DeploySection {
             layer,
             deployer,
             template,
+            addr_scheme: 0,
         }
     }
 
+    /// Sets the id of the address derivation scheme the [`Template`](crate::Template)'s `Address`
+    /// has been computed with (defaults to `0`).
+    pub fn with_addr_scheme(mut self, addr_scheme: u16) -> Self {
+        self.addr_scheme = addr_scheme;
+        self
+    }
+
     /// The [`TransactionId`] of the `Deploy Template` transaction.
     pub fn tx_id(&self) -> &TransactionId {
         &self.tx_id
@@ -44,6 +53,12 @@ impl DeploySection {
     pub fn template(&self) -> &TemplateAddr {
         &self.template
     }
+
+    /// The id of the address derivation scheme the [`Template`](crate::Template)'s `Address`
+    /// has been computed with.
+    pub fn addr_scheme(&self) -> u16 {
+        self.addr_scheme
+    }
 }
 
 impl SectionLike for DeploySection {
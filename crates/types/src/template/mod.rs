@@ -24,22 +24,26 @@
 mod api;
 mod code;
 mod ctors;
+mod custom;
 mod data;
+mod defaults;
 mod deploy;
 mod header;
 mod schema;
 mod section;
 
-pub use api::ApiSection;
+pub use api::{ApiFunc, ApiParam, ApiSection, ApiType, ApiVarDoc};
 pub use code::{CodeKind, CodeSection};
 pub use ctors::CtorsSection;
+pub use custom::CustomSection;
 pub use data::DataSection;
+pub use defaults::DefaultsSection;
 pub use deploy::DeploySection;
 pub use header::HeaderSection;
 pub use schema::SchemaSection;
 pub use section::{Section, SectionKind, SectionLike, Sections, SectionsIter};
 
-use svm_layout::FixedLayout;
+use svm_layout::{DynamicLayout, FixedLayout};
 
 use crate::TemplateAddr;
 
@@ -118,6 +122,11 @@ impl Template {
         layout.as_fixed()
     }
 
+    /// Returns an immutable borrow of `self`'s [`DynamicLayout`] (if any).
+    pub fn dynamic_layout(&self) -> Option<&DynamicLayout> {
+        self.data_section().dynamic_layout()
+    }
+
     /// Borrows the `Ctors Section`
     ///
     /// # Panics
@@ -165,6 +174,22 @@ impl Template {
         section.as_schema()
     }
 
+    /// Borrows the `Defaults Section`
+    ///
+    /// Returns `None` when there is no `Defaults Section` (i.e all storage variables start zeroed)
+    pub fn defaults_section(&self) -> Option<&DefaultsSection> {
+        self.try_get(SectionKind::Defaults)
+            .map(|section| section.as_defaults())
+    }
+
+    /// Borrows the `API Section`
+    ///
+    /// Returns `None` when there is no `API Section`
+    pub fn api_section(&self) -> Option<&ApiSection> {
+        self.try_get(SectionKind::Api)
+            .map(|section| section.as_api())
+    }
+
     /// Sets the `DeploySection` to a `Template`
     pub fn set_deploy_section(&mut self, section: DeploySection) {
         debug_assert!(self.sections.contains(SectionKind::Deploy) == false);
@@ -5,7 +5,8 @@ use indexmap::map::Values;
 use indexmap::IndexMap;
 
 use super::{
-    ApiSection, CodeSection, CtorsSection, DataSection, DeploySection, HeaderSection, SchemaSection,
+    ApiSection, CodeSection, CtorsSection, CustomSection, DataSection, DefaultsSection,
+    DeploySection, HeaderSection, SchemaSection,
 };
 
 /// A trait to be implemented by each `Section` type.
@@ -37,6 +38,12 @@ pub enum Section {
 
     /// A Section of kind `Deploy`.
     Deploy(DeploySection),
+
+    /// A Section of kind `Defaults`.
+    Defaults(DefaultsSection),
+
+    /// A user-defined Section (see [`CustomSection`]).
+    Custom(CustomSection),
 }
 
 impl Section {
@@ -50,6 +57,8 @@ impl Section {
             Self::Schema(..) => SectionKind::Schema,
             Self::Api(..) => SectionKind::Api,
             Self::Deploy(..) => SectionKind::Deploy,
+            Self::Defaults(..) => SectionKind::Defaults,
+            Self::Custom(ref section) => section.section_kind(),
         }
     }
 
@@ -136,6 +145,30 @@ impl Section {
             _ => unreachable!(),
         }
     }
+
+    /// Returns the wrapped `DefaultsSection`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the wrapped `Section` isn't `DefaultsSection`
+    pub fn as_defaults(&self) -> &DefaultsSection {
+        match self {
+            Self::Defaults(section) => section,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns the wrapped `CustomSection`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the wrapped `Section` isn't `CustomSection`
+    pub fn as_custom(&self) -> &CustomSection {
+        match self {
+            Self::Custom(section) => section,
+            _ => unreachable!(),
+        }
+    }
 }
 
 impl From<HeaderSection> for Section {
@@ -179,6 +212,18 @@ impl From<DeploySection> for Section {
     }
 }
 
+impl From<DefaultsSection> for Section {
+    fn from(section: DefaultsSection) -> Self {
+        Section::Defaults(section)
+    }
+}
+
+impl From<CustomSection> for Section {
+    fn from(section: CustomSection) -> Self {
+        Section::Custom(section)
+    }
+}
+
 impl fmt::Display for Section {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.kind().fmt(f)
@@ -186,7 +231,7 @@ impl fmt::Display for Section {
 }
 
 /// Holds the Kind of `Section`
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum SectionKind {
     /// Represents `HeaderSection`
     Header,
@@ -208,6 +253,12 @@ pub enum SectionKind {
 
     /// Represents `DeploySection`
     Deploy,
+
+    /// Represents `DefaultsSection`
+    Defaults,
+
+    /// Represents a `CustomSection` of the given (raw) kind
+    Custom(u16),
 }
 
 impl fmt::Display for SectionKind {
@@ -220,6 +271,8 @@ impl fmt::Display for SectionKind {
             Self::Schema => write!(f, "Schema Section"),
             Self::Api => write!(f, "API Section"),
             Self::Deploy => write!(f, "Deploy Section"),
+            Self::Defaults => write!(f, "Defaults Section"),
+            Self::Custom(kind) => write!(f, "Custom Section #{:#06x}", kind),
         }
     }
 }
@@ -0,0 +1,21 @@
+use crate::Transaction;
+
+/// An in-memory representation of a `Call Batch` transaction.
+///
+/// Holds a couple of `Call Account` transactions executed atomically, one after the other,
+/// under the same [`Envelope`](crate::Envelope) and [`Context`](crate::Context).
+#[derive(Debug, PartialEq, Clone)]
+pub struct BatchTransaction {
+    /// The `version`.
+    pub version: u16,
+
+    /// The batched `Call Account` transactions (in execution order).
+    pub calls: Vec<Transaction>,
+}
+
+impl BatchTransaction {
+    #[doc(hidden)]
+    pub fn calls(&self) -> &[Transaction] {
+        &self.calls
+    }
+}
@@ -1,3 +1,70 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use std::fmt;
+use std::str::FromStr;
+
 use crate::impl_bytes_primitive;
 
 impl_bytes_primitive!(TransactionId, 32);
+
+/// Formats the [`TransactionId`] as `0x`-prefixed lower-case hex.
+impl fmt::Display for TransactionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+/// Parses a hex-encoded [`TransactionId`] (the `0x` prefix is optional).
+impl FromStr for TransactionId {
+    type Err = hex::FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix("0x").unwrap_or(s);
+
+        let mut bytes = [0; 32];
+        hex::decode_to_slice(s, &mut bytes)?;
+
+        Ok(Self(bytes))
+    }
+}
+
+impl Serialize for TransactionId {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for TransactionId {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let s = String::deserialize(de)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tx_id_display_from_str() {
+        let tx_id = TransactionId::repeat(0xAB);
+        let s = tx_id.to_string();
+
+        assert_eq!(s, format!("0x{}", "ab".repeat(32)));
+        assert_eq!(s.parse::<TransactionId>(), Ok(tx_id.clone()));
+        assert_eq!(s[2..].parse::<TransactionId>(), Ok(tx_id));
+
+        assert_eq!(
+            "0xabcd".parse::<TransactionId>(),
+            Err(hex::FromHexError::InvalidStringLength)
+        );
+    }
+}
@@ -1,10 +1,115 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
 /// Represent a `Layer` of the Spacemesh Protocol
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct Layer(pub u64);
 
+impl Layer {
+    /// Creates a new [`Layer`].
+    pub const fn new(layer: u64) -> Self {
+        Self(layer)
+    }
+
+    /// Returns the [`Layer`] number.
+    pub const fn as_u64(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the [`Layer`] `n` layers ahead, or `None` on overflow.
+    ///
+    /// Should be used for computing horizons (expiration windows, pruning etc.)
+    pub fn checked_add(self, n: u64) -> Option<Self> {
+        self.0.checked_add(n).map(Self)
+    }
+
+    /// Returns the [`Layer`] `n` layers behind, or `None` on underflow.
+    pub fn checked_sub(self, n: u64) -> Option<Self> {
+        self.0.checked_sub(n).map(Self)
+    }
+
+    /// Returns the number of layers from `earlier` to `self`,
+    /// or `None` if `earlier` comes after `self`.
+    pub fn since(self, earlier: Layer) -> Option<u64> {
+        self.0.checked_sub(earlier.0)
+    }
+}
+
 impl Default for Layer {
     fn default() -> Self {
         Self(0)
     }
 }
+
+impl From<u64> for Layer {
+    fn from(layer: u64) -> Self {
+        Self(layer)
+    }
+}
+
+impl From<Layer> for u64 {
+    fn from(layer: Layer) -> Self {
+        layer.0
+    }
+}
+
+impl fmt::Display for Layer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Layer {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
+impl Serialize for Layer {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_u64(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Layer {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        u64::deserialize(de).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layer_arithmetic() {
+        let layer = Layer(10);
+
+        assert_eq!(layer.checked_add(5), Some(Layer(15)));
+        assert_eq!(Layer(u64::MAX).checked_add(1), None);
+        assert_eq!(layer.checked_sub(10), Some(Layer(0)));
+        assert_eq!(layer.checked_sub(11), None);
+        assert_eq!(layer.since(Layer(4)), Some(6));
+        assert_eq!(Layer(4).since(layer), None);
+        assert!(Layer(4) < layer);
+    }
+
+    #[test]
+    fn layer_display_from_str() {
+        assert_eq!(Layer(1234).to_string(), "1234");
+        assert_eq!("1234".parse::<Layer>(), Ok(Layer(1234)));
+        assert!("-1".parse::<Layer>().is_err());
+    }
+}
@@ -1,10 +1,12 @@
 use std::fmt;
 
+mod batch;
 mod context;
 mod envelope;
 mod id;
 mod layer;
 
+pub use batch::BatchTransaction;
 pub use context::Context;
 pub use envelope::Envelope;
 pub use id::TransactionId;
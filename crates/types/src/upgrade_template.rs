@@ -0,0 +1,29 @@
+use crate::{Template, TemplateAddr};
+
+/// Struct representation of the parsed raw `Upgrade Template` transaction.
+///
+/// Deploys `template` as the next version of the already deployed `Template` at `template_addr`.
+/// The `Template`'s `Address` is kept as is, so that the `Account`s of the `Template`
+/// run the upgraded version from then on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpgradeTemplate {
+    /// Transaction format version
+    pub version: u16,
+
+    /// The `Address` of the upgraded `Template`
+    pub template_addr: TemplateAddr,
+
+    /// The upgraded `Template`
+    pub template: Template,
+}
+
+#[doc(hidden)]
+impl UpgradeTemplate {
+    pub fn template_addr(&self) -> &TemplateAddr {
+        &self.template_addr
+    }
+
+    pub fn template(&self) -> &Template {
+        &self.template
+    }
+}